@@ -122,6 +122,13 @@ fn configure_validation(builder: Builder) -> Builder {
             ("CreateCollection.optimizers_config", ""),
             ("CreateCollection.vectors_config", ""),
             ("CreateCollection.quantization_config", ""),
+            ("CreateCollection.strict_mode_config", ""),
+            ("CreateCollection.payload_compression_level", "range(min = 1, max = 22)"),
+            ("CreateCollection.point_history_versions", "range(min = 1)"),
+            ("CreateCollection.soft_delete_retention_sec", "range(min = 1)"),
+            ("CreateCollection.computed_fields", ""),
+            ("CreateCollection.template", "length(min = 1)"),
+            ("ComputedField.key", "length(min = 1)"),
             ("UpdateCollection.collection_name", "length(min = 1, max = 255)"),
             ("UpdateCollection.optimizers_config", ""),
             ("UpdateCollection.params", ""),
@@ -129,6 +136,7 @@ fn configure_validation(builder: Builder) -> Builder {
             ("UpdateCollection.hnsw_config", ""),
             ("UpdateCollection.vectors_config", ""),
             ("UpdateCollection.quantization_config", ""),
+            ("UpdateCollection.strict_mode_config", ""),
             ("DeleteCollection.collection_name", "length(min = 1, max = 255)"),
             ("DeleteCollection.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("CollectionConfig.params", ""),
@@ -145,6 +153,7 @@ fn configure_validation(builder: Builder) -> Builder {
             ("VectorsConfig.config", ""),
             ("VectorsConfigDiff.config", ""),
             ("VectorParams.size", "range(min = 1, max = 65536)"),
+            ("VectorParams.input_size", "range(min = 1, max = 65536)"),
             ("VectorParams.hnsw_config", ""),
             ("VectorParams.quantization_config", ""),
             ("VectorParamsMap.map", ""),
@@ -154,6 +163,15 @@ fn configure_validation(builder: Builder) -> Builder {
             ("QuantizationConfig.quantization", ""),
             ("QuantizationConfigDiff.quantization", ""),
             ("ScalarQuantization.quantile", "custom = \"crate::grpc::validate::validate_f32_range_min_0_5_max_1\""),
+            ("StrictModeConfig.max_query_limit", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("StrictModeConfig.search_max_hnsw_ef", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("StrictModeConfig.max_payload_size_bytes", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("StrictModeConfig.payload_schema", ""),
+            ("StrictModeConfig.max_points_count", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("StrictModeConfig.max_ram_usage_bytes", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("StrictModeConfig.max_disk_usage_bytes", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("PayloadSchemaConfig.fields", ""),
+            ("PayloadFieldConstraints.max_length", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("UpdateCollectionClusterSetupRequest.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("UpdateCollectionClusterSetupRequest.operation", ""),
         ], &[
@@ -182,15 +200,22 @@ fn configure_validation(builder: Builder) -> Builder {
         .validates(&[
             ("UpsertPoints.collection_name", "length(min = 1, max = 255)"),
             ("UpsertPoints.points", ""),
+            ("UpsertPoints.precondition", ""),
+            ("UpsertPointsChunk.collection_name", "length(min = 1, max = 255)"),
+            ("UpsertPointsChunk.points", ""),
             ("DeletePoints.collection_name", "length(min = 1, max = 255)"),
+            ("DeletePoints.precondition", ""),
             ("UpdatePointVectors.collection_name", "length(min = 1, max = 255)"),
             ("UpdatePointVectors.vectors", "custom(function = \"crate::grpc::validate::validate_named_vectors_not_empty\", message = \"must specify vectors to update\")"),
             ("DeletePointVectors.collection_name", "length(min = 1, max = 255)"),
             ("DeletePointVectors.vector_names", "length(min = 1, message = \"must specify vector names to delete\")"),
             ("GetPoints.collection_name", "length(min = 1, max = 255)"),
             ("SetPayloadPoints.collection_name", "length(min = 1, max = 255)"),
+            ("SetPayloadPoints.precondition", ""),
+            ("WritePrecondition.filter", ""),
             ("DeletePayloadPoints.collection_name", "length(min = 1, max = 255)"),
             ("ClearPayloadPoints.collection_name", "length(min = 1, max = 255)"),
+            ("SetAttributesPoints.collection_name", "length(min = 1, max = 255)"),
             ("UpdateBatchPoints.collection_name", "length(min = 1, max = 255)"),
             ("UpdateBatchPoints.operations", "length(min = 1)"),
             ("CreateFieldIndexCollection.collection_name", "length(min = 1, max = 255)"),
@@ -245,6 +270,14 @@ fn configure_validation(builder: Builder) -> Builder {
             ("DiscoverBatchPoints.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("CountPoints.collection_name", "length(min = 1, max = 255)"),
             ("CountPoints.filter", ""),
+            ("CountDistinctPoints.collection_name", "length(min = 1, max = 255)"),
+            ("CountDistinctPoints.key", "length(min = 1)"),
+            ("CountDistinctPoints.filter", ""),
+            ("HistogramPoints.collection_name", "length(min = 1, max = 255)"),
+            ("HistogramPoints.key", "length(min = 1)"),
+            ("FieldStatsPoints.collection_name", "length(min = 1, max = 255)"),
+            ("FieldStatsPoints.key", "length(min = 1)"),
+            ("FieldStatsPoints.filter", ""),
             ("GeoPolygon.exterior", "custom = \"crate::grpc::validate::validate_geo_polygon_exterior\""),
             ("GeoPolygon.interiors", "custom = \"crate::grpc::validate::validate_geo_polygon_interiors\""),
             ("Filter.should", ""),
@@ -264,6 +297,7 @@ fn configure_validation(builder: Builder) -> Builder {
             ("QueryPoints.filter", ""),
             ("QueryPoints.params", ""),
             ("QueryPoints.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("QueryPoints.payload_size_limit", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("QueryBatchPoints.collection_name", "length(min = 1, max = 255)"),
             ("QueryBatchPoints.query_points", ""),
             ("QueryBatchPoints.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
@@ -278,6 +312,7 @@ fn configure_validation(builder: Builder) -> Builder {
             ("SetPayloadPointsInternal.set_payload_points", ""),
             ("DeletePayloadPointsInternal.delete_payload_points", ""),
             ("ClearPayloadPointsInternal.clear_payload_points", ""),
+            ("SetAttributesPointsInternal.set_attributes_points", ""),
             ("CreateFieldIndexCollectionInternal.create_field_index_collection", ""),
             ("DeleteFieldIndexCollectionInternal.delete_field_index_collection", ""),
             ("SearchPointsInternal.search_points", ""),
@@ -300,6 +335,14 @@ fn configure_validation(builder: Builder) -> Builder {
             ("ScrollPointsInternal.scroll_points", ""),
             ("GetPointsInternal.get_points", ""),
             ("CountPointsInternal.count_points", ""),
+            ("CountDistinctPointsInternal.count_distinct_points", ""),
+            ("HistogramPointsInternal.histogram_points", ""),
+            ("FieldStatsPointsInternal.field_stats_points", ""),
+            ("PointDigestsInternal.collection_name", "length(min = 1, max = 255)"),
+            ("PointHistoryInternal.collection_name", "length(min = 1, max = 255)"),
+            ("TrashedPointsInternal.collection_name", "length(min = 1, max = 255)"),
+            ("ScanTrashInternal.collection_name", "length(min = 1, max = 255)"),
+            ("ScanTrashInternal.filter", ""),
             ("SyncPointsInternal.sync_points", ""),
             ("SyncPoints.collection_name", "length(min = 1, max = 255)"),
             ("QueryBatchPointsInternal.collection_name", "length(min = 1, max = 255)"),
@@ -1,14 +1,18 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::str::FromStr as _;
 use std::time::Instant;
 
 use chrono::{NaiveDateTime, Timelike};
 use itertools::Itertools;
+use segment::data_types::geo_index::GeoIndexType;
 use segment::data_types::integer_index::IntegerIndexType;
 use segment::data_types::text_index::TextIndexType;
 use segment::data_types::vectors as segment_vectors;
 use segment::json_path::JsonPath;
-use segment::types::{default_quantization_ignore_value, DateTimePayloadType, FloatPayloadType};
+use segment::types::{
+    default_quantization_ignore_value, BigIntPayloadType, DateTimePayloadType, FloatPayloadType,
+};
+use segment::utils::payload_size_limit::truncate_payloads;
 use segment::vector_storage::query as segment_query;
 use sparse::common::sparse_vector::validate_sparse_vector_impl;
 use tonic::Status;
@@ -16,10 +20,11 @@ use uuid::Uuid;
 
 use super::qdrant::raw_query::RawContextPair;
 use super::qdrant::{
-    raw_query, start_from, BinaryQuantization, CompressionRatio, DatetimeRange, Direction,
-    GeoLineString, GroupId, LookupLocation, MultiVectorComparator, MultiVectorConfig, OrderBy,
-    OrderValue, Range, RawVector, RecommendStrategy, SearchPointGroups, SearchPoints,
-    ShardKeySelector, SparseIndices, StartFrom, WithLookup,
+    raw_query, start_from, BigIntRange, BinaryQuantization, CompressionRatio, DatetimeRange,
+    Direction, GeoLineString, GroupId, HardwareUsage, LookupLocation, MultiVectorAggregation,
+    MultiVectorComparator, MultiVectorConfig, OrderBy, OrderValue, Range, RawVector,
+    RecommendStrategy, SearchPointGroups, SearchPoints, ShardKeySelector, ShardReadStatus,
+    SparseIndices, StartFrom, ThenBy, WithLookup,
 };
 use crate::grpc::models::{CollectionsResponse, VersionInfo};
 use crate::grpc::qdrant::condition::ConditionOneOf;
@@ -30,17 +35,19 @@ use crate::grpc::qdrant::value::Kind;
 use crate::grpc::qdrant::vectors::VectorsOptions;
 use crate::grpc::qdrant::with_payload_selector::SelectorOptions;
 use crate::grpc::qdrant::{
-    shard_key, with_vectors_selector, CollectionDescription, CollectionOperationResponse,
-    Condition, DenseVector, Distance, FieldCondition, Filter, GeoBoundingBox, GeoPoint, GeoPolygon,
-    GeoRadius, HasIdCondition, HealthCheckReply, HnswConfigDiff, IntegerIndexParams,
-    IsEmptyCondition, IsNullCondition, ListCollectionsResponse, ListValue, Match, MinShould,
-    MultiDenseVector, NamedVectors, NestedCondition, PayloadExcludeSelector,
-    PayloadIncludeSelector, PayloadIndexParams, PayloadSchemaInfo, PayloadSchemaType, PointId,
-    PointsOperationResponse, PointsOperationResponseInternal, ProductQuantization,
-    QuantizationConfig, QuantizationSearchParams, QuantizationType, RepeatedIntegers,
-    RepeatedStrings, ScalarQuantization, ScoredPoint, SearchParams, ShardKey, SparseVector, Struct,
-    TextIndexParams, TokenizerType, UpdateResult, UpdateResultInternal, Value, ValuesCount, Vector,
-    Vectors, VectorsSelector, WithPayloadSelector, WithVectorsSelector,
+    attribute_value, shard_key, with_vectors_selector, AttributeCondition, AttributeType,
+    AttributeValue, CollectionDescription, CollectionOperationResponse, Condition, DenseVector,
+    Distance, FieldCondition, Filter, GeoBoundingBox, GeoIndexParams, GeoPoint, GeoPolygon,
+    GeoRadius, HasIdCondition, HasVectorCondition, HealthCheckReply, HnswConfigDiff,
+    IntegerIndexParams, IsEmptyCondition, IsNullCondition, KeywordRange, ListCollectionsResponse,
+    ListValue, Match, MatchSimilar, MinShould, MultiDenseVector, NamedVectors, NestedCondition,
+    PayloadExcludeSelector, PayloadIncludeSelector, PayloadIndexParams, PayloadProjectSelector,
+    PayloadSchemaInfo, PayloadSchemaType, PointId, PointsOperationResponse,
+    PointsOperationResponseInternal, ProductQuantization, QuantizationConfig,
+    QuantizationSearchParams, QuantizationType, RepeatedIntegers, RepeatedStrings,
+    ScalarQuantization, ScoreNormalization, ScoredPoint, SearchParams, ShardKey, SparseVector,
+    Struct, TextIndexParams, TokenizerType, UpdateResult, UpdateResultInternal, Value, ValuesCount,
+    Vector, Vectors, VectorsSelector, WithPayloadSelector, WithVectorsSelector,
 };
 use crate::rest::schema as rest;
 
@@ -88,6 +95,88 @@ pub fn json_path_from_proto(a: &str) -> Result<JsonPath, Status> {
     JsonPath::try_from(a).map_err(|_| Status::invalid_argument("Invalid json path"))
 }
 
+impl From<segment::types::AttributeType> for AttributeType {
+    fn from(value: segment::types::AttributeType) -> Self {
+        match value {
+            segment::types::AttributeType::Float => AttributeType::FloatAttribute,
+            segment::types::AttributeType::Integer => AttributeType::IntegerAttribute,
+        }
+    }
+}
+
+impl From<AttributeType> for segment::types::AttributeType {
+    fn from(value: AttributeType) -> Self {
+        match value {
+            AttributeType::FloatAttribute => segment::types::AttributeType::Float,
+            AttributeType::IntegerAttribute => segment::types::AttributeType::Integer,
+        }
+    }
+}
+
+pub fn attribute_columns_from_proto(
+    columns: HashMap<String, i32>,
+) -> Result<BTreeMap<String, segment::types::AttributeType>, Status> {
+    columns
+        .into_iter()
+        .map(|(name, attribute_type)| {
+            let attribute_type = AttributeType::from_i32(attribute_type).ok_or_else(|| {
+                Status::invalid_argument(format!(
+                    "Unknown type of attribute `{name}`: {attribute_type}"
+                ))
+            })?;
+            Ok((name, attribute_type.into()))
+        })
+        .collect()
+}
+
+pub fn attribute_columns_to_proto(
+    columns: BTreeMap<String, segment::types::AttributeType>,
+) -> HashMap<String, i32> {
+    columns
+        .into_iter()
+        .map(|(name, attribute_type)| (name, AttributeType::from(attribute_type) as i32))
+        .collect()
+}
+
+/// Convert attribute values of the operation, values without a kind remove the attribute
+pub fn attributes_from_proto(
+    attributes: HashMap<String, AttributeValue>,
+) -> HashMap<String, Option<segment::types::AttributeValue>> {
+    attributes
+        .into_iter()
+        .map(|(name, value)| {
+            let value = value.kind.map(|kind| match kind {
+                attribute_value::Kind::FloatValue(value) => {
+                    segment::types::AttributeValue::Float(value)
+                }
+                attribute_value::Kind::IntegerValue(value) => {
+                    segment::types::AttributeValue::Integer(value)
+                }
+            });
+            (name, value)
+        })
+        .collect()
+}
+
+pub fn attributes_to_proto(
+    attributes: HashMap<String, Option<segment::types::AttributeValue>>,
+) -> HashMap<String, AttributeValue> {
+    attributes
+        .into_iter()
+        .map(|(name, value)| {
+            let kind = value.map(|value| match value {
+                segment::types::AttributeValue::Float(value) => {
+                    attribute_value::Kind::FloatValue(value)
+                }
+                segment::types::AttributeValue::Integer(value) => {
+                    attribute_value::Kind::IntegerValue(value)
+                }
+            });
+            (name, AttributeValue { kind })
+        })
+        .collect()
+}
+
 pub fn proto_to_payloads(proto: HashMap<String, Value>) -> Result<segment::types::Payload, Status> {
     let mut map: serde_json::Map<String, serde_json::Value> = serde_json::Map::new();
     for (k, v) in proto.into_iter() {
@@ -245,19 +334,59 @@ impl From<segment::data_types::integer_index::IntegerIndexParams> for PayloadInd
     }
 }
 
+impl From<segment::data_types::geo_index::GeoIndexParams> for PayloadIndexParams {
+    fn from(params: segment::data_types::geo_index::GeoIndexParams) -> Self {
+        PayloadIndexParams {
+            index_params: Some(IndexParams::GeoIndexParams(GeoIndexParams {
+                on_disk: params.on_disk,
+            })),
+        }
+    }
+}
+
+impl From<segment::types::PayloadSchemaType> for PayloadSchemaType {
+    fn from(schema_type: segment::types::PayloadSchemaType) -> Self {
+        match schema_type {
+            segment::types::PayloadSchemaType::Keyword => PayloadSchemaType::Keyword,
+            segment::types::PayloadSchemaType::Integer => PayloadSchemaType::Integer,
+            segment::types::PayloadSchemaType::Float => PayloadSchemaType::Float,
+            segment::types::PayloadSchemaType::Geo => PayloadSchemaType::Geo,
+            segment::types::PayloadSchemaType::Text => PayloadSchemaType::Text,
+            segment::types::PayloadSchemaType::Bool => PayloadSchemaType::Bool,
+            segment::types::PayloadSchemaType::Datetime => PayloadSchemaType::Datetime,
+            segment::types::PayloadSchemaType::BigInt => PayloadSchemaType::BigInt,
+            segment::types::PayloadSchemaType::MinHash => PayloadSchemaType::MinHash,
+        }
+    }
+}
+
+impl TryFrom<PayloadSchemaType> for segment::types::PayloadSchemaType {
+    type Error = Status;
+
+    fn try_from(schema_type: PayloadSchemaType) -> Result<Self, Self::Error> {
+        Ok(match schema_type {
+            PayloadSchemaType::Keyword => segment::types::PayloadSchemaType::Keyword,
+            PayloadSchemaType::Integer => segment::types::PayloadSchemaType::Integer,
+            PayloadSchemaType::Float => segment::types::PayloadSchemaType::Float,
+            PayloadSchemaType::Geo => segment::types::PayloadSchemaType::Geo,
+            PayloadSchemaType::Text => segment::types::PayloadSchemaType::Text,
+            PayloadSchemaType::Bool => segment::types::PayloadSchemaType::Bool,
+            PayloadSchemaType::Datetime => segment::types::PayloadSchemaType::Datetime,
+            PayloadSchemaType::BigInt => segment::types::PayloadSchemaType::BigInt,
+            PayloadSchemaType::MinHash => segment::types::PayloadSchemaType::MinHash,
+            PayloadSchemaType::UnknownType => {
+                return Err(Status::invalid_argument(
+                    "Malformed payload schema".to_string(),
+                ));
+            }
+        })
+    }
+}
+
 impl From<segment::types::PayloadIndexInfo> for PayloadSchemaInfo {
     fn from(schema: segment::types::PayloadIndexInfo) -> Self {
         PayloadSchemaInfo {
-            data_type: match schema.data_type {
-                segment::types::PayloadSchemaType::Keyword => PayloadSchemaType::Keyword,
-                segment::types::PayloadSchemaType::Integer => PayloadSchemaType::Integer,
-                segment::types::PayloadSchemaType::Float => PayloadSchemaType::Float,
-                segment::types::PayloadSchemaType::Geo => PayloadSchemaType::Geo,
-                segment::types::PayloadSchemaType::Text => PayloadSchemaType::Text,
-                segment::types::PayloadSchemaType::Bool => PayloadSchemaType::Bool,
-                segment::types::PayloadSchemaType::Datetime => PayloadSchemaType::Datetime,
-            }
-            .into(),
+            data_type: PayloadSchemaType::from(schema.data_type).into(),
             params: schema.params.map(|params| match params {
                 segment::types::PayloadSchemaParams::Text(text_index_params) => {
                     text_index_params.into()
@@ -265,6 +394,7 @@ impl From<segment::types::PayloadIndexInfo> for PayloadSchemaInfo {
                 segment::types::PayloadSchemaParams::Integer(integer_params) => {
                     integer_params.into()
                 }
+                segment::types::PayloadSchemaParams::Geo(geo_params) => geo_params.into(),
             }),
             points: Some(schema.points as u64),
         }
@@ -314,6 +444,16 @@ impl TryFrom<IntegerIndexParams> for segment::data_types::integer_index::Integer
     }
 }
 
+impl TryFrom<GeoIndexParams> for segment::data_types::geo_index::GeoIndexParams {
+    type Error = Status;
+    fn try_from(params: GeoIndexParams) -> Result<Self, Self::Error> {
+        Ok(segment::data_types::geo_index::GeoIndexParams {
+            r#type: GeoIndexType::Geo,
+            on_disk: params.on_disk,
+        })
+    }
+}
+
 impl TryFrom<IndexParams> for segment::types::PayloadSchemaParams {
     type Error = Status;
 
@@ -325,6 +465,9 @@ impl TryFrom<IndexParams> for segment::types::PayloadSchemaParams {
             IndexParams::IntegerIndexParams(integer_params) => Ok(
                 segment::types::PayloadSchemaParams::Integer(integer_params.try_into()?),
             ),
+            IndexParams::GeoIndexParams(geo_params) => Ok(
+                segment::types::PayloadSchemaParams::Geo(geo_params.try_into()?),
+            ),
         }
     }
 }
@@ -333,27 +476,9 @@ impl TryFrom<PayloadSchemaInfo> for segment::types::PayloadIndexInfo {
     type Error = Status;
 
     fn try_from(schema: PayloadSchemaInfo) -> Result<Self, Self::Error> {
-        let data_type = match PayloadSchemaType::from_i32(schema.data_type) {
-            None => {
-                return Err(Status::invalid_argument(
-                    "Malformed payload schema".to_string(),
-                ));
-            }
-            Some(data_type) => match data_type {
-                PayloadSchemaType::Keyword => segment::types::PayloadSchemaType::Keyword,
-                PayloadSchemaType::Integer => segment::types::PayloadSchemaType::Integer,
-                PayloadSchemaType::Float => segment::types::PayloadSchemaType::Float,
-                PayloadSchemaType::Geo => segment::types::PayloadSchemaType::Geo,
-                PayloadSchemaType::Text => segment::types::PayloadSchemaType::Text,
-                PayloadSchemaType::Bool => segment::types::PayloadSchemaType::Bool,
-                PayloadSchemaType::Datetime => segment::types::PayloadSchemaType::Datetime,
-                PayloadSchemaType::UnknownType => {
-                    return Err(Status::invalid_argument(
-                        "Malformed payload schema".to_string(),
-                    ));
-                }
-            },
-        };
+        let data_type = PayloadSchemaType::from_i32(schema.data_type)
+            .ok_or_else(|| Status::invalid_argument("Malformed payload schema".to_string()))?
+            .try_into()?;
         let params = match schema.params {
             None => None,
             Some(PayloadIndexParams { index_params: None }) => None,
@@ -380,6 +505,28 @@ impl From<(Instant, bool)> for CollectionOperationResponse {
     }
 }
 
+impl From<ScoreNormalization> for segment::common::weighted_sum_fusion::ScoreNormalization {
+    fn from(value: ScoreNormalization) -> Self {
+        use segment::common::weighted_sum_fusion::ScoreNormalization as Normalization;
+        match value {
+            ScoreNormalization::MinMax => Normalization::MinMax,
+            ScoreNormalization::ZScore => Normalization::ZScore,
+            ScoreNormalization::Rank => Normalization::Rank,
+        }
+    }
+}
+
+impl From<segment::common::weighted_sum_fusion::ScoreNormalization> for ScoreNormalization {
+    fn from(value: segment::common::weighted_sum_fusion::ScoreNormalization) -> Self {
+        use segment::common::weighted_sum_fusion::ScoreNormalization as Normalization;
+        match value {
+            Normalization::MinMax => ScoreNormalization::MinMax,
+            Normalization::ZScore => ScoreNormalization::ZScore,
+            Normalization::Rank => ScoreNormalization::Rank,
+        }
+    }
+}
+
 impl From<segment::types::GeoPoint> for GeoPoint {
     fn from(geo: segment::types::GeoPoint) -> Self {
         Self {
@@ -410,6 +557,13 @@ impl TryFrom<WithPayloadSelector> for segment::types::WithPayloadInterface {
                         .collect::<Result<_, _>>()?,
                 )
                 .into(),
+                SelectorOptions::Project(s) => segment::types::PayloadSelectorProject::new(
+                    s.fields
+                        .into_iter()
+                        .map(|(key, path)| Ok((key, json_path_from_proto(&path)?)))
+                        .collect::<Result<_, Status>>()?,
+                )
+                .into(),
             }),
             _ => Err(Status::invalid_argument("No PayloadSelector".to_string())),
         }
@@ -436,6 +590,15 @@ impl From<segment::types::WithPayloadInterface> for WithPayloadSelector {
                         fields: s.exclude.iter().map(|f| f.to_string()).collect(),
                     })
                 }
+                segment::types::PayloadSelector::Project(s) => {
+                    SelectorOptions::Project(PayloadProjectSelector {
+                        fields: s
+                            .project
+                            .into_iter()
+                            .map(|(key, path)| (key, path.to_string()))
+                            .collect(),
+                    })
+                }
             },
         };
         WithPayloadSelector {
@@ -464,14 +627,17 @@ impl From<segment::types::QuantizationSearchParams> for QuantizationSearchParams
     }
 }
 
-impl From<SearchParams> for segment::types::SearchParams {
-    fn from(params: SearchParams) -> Self {
-        Self {
+impl TryFrom<SearchParams> for segment::types::SearchParams {
+    type Error = Status;
+
+    fn try_from(params: SearchParams) -> Result<Self, Self::Error> {
+        Ok(Self {
             hnsw_ef: params.hnsw_ef.map(|x| x as usize),
             exact: params.exact.unwrap_or(false),
             quantization: params.quantization.map(|q| q.into()),
             indexed_only: params.indexed_only.unwrap_or(false),
-        }
+            distance: params.distance.map(from_grpc_dist).transpose()?,
+        })
     }
 }
 
@@ -482,6 +648,9 @@ impl From<segment::types::SearchParams> for SearchParams {
             exact: Some(params.exact),
             quantization: params.quantization.map(|q| q.into()),
             indexed_only: Some(params.indexed_only),
+            distance: params
+                .distance
+                .map(|distance| Distance::from(distance) as i32),
         }
     }
 }
@@ -644,6 +813,63 @@ impl From<segment::types::ScoredPoint> for ScoredPoint {
             vectors: point.vector.map(|v| v.into()),
             shard_key: point.shard_key.map(convert_shard_key_to_grpc),
             order_value: point.order_value.map(From::from),
+            truncated: false,
+        }
+    }
+}
+
+/// Convert scored points, truncating their payloads to `payload_size_limit` bytes in total
+pub fn scored_points_with_payload_limit(
+    mut points: Vec<segment::types::ScoredPoint>,
+    payload_size_limit: Option<u64>,
+) -> Vec<ScoredPoint> {
+    let Some(limit) = payload_size_limit else {
+        return points.into_iter().map(ScoredPoint::from).collect();
+    };
+    let truncated = truncate_payloads(&mut points, limit as usize);
+    points
+        .into_iter()
+        .zip(truncated)
+        .map(|(point, truncated)| ScoredPoint {
+            truncated,
+            ..ScoredPoint::from(point)
+        })
+        .collect()
+}
+
+impl From<common::hardware_counter::HardwareUsage> for HardwareUsage {
+    fn from(usage: common::hardware_counter::HardwareUsage) -> Self {
+        Self {
+            cpu: usage.cpu as u64,
+            payload_io_read: usage.payload_io_read as u64,
+            vector_io_read: usage.vector_io_read as u64,
+        }
+    }
+}
+
+impl From<&HardwareUsage> for common::hardware_counter::HardwareUsage {
+    fn from(usage: &HardwareUsage) -> Self {
+        Self {
+            cpu: usage.cpu as usize,
+            payload_io_read: usage.payload_io_read as usize,
+            vector_io_read: usage.vector_io_read as usize,
+        }
+    }
+}
+
+impl From<rest::ShardReadStatus> for ShardReadStatus {
+    fn from(status: rest::ShardReadStatus) -> Self {
+        let rest::ShardReadStatus {
+            shard_id,
+            shard_key,
+            ok,
+            error,
+        } = status;
+        Self {
+            shard_id,
+            shard_key: shard_key.map(convert_shard_key_to_grpc),
+            ok,
+            error,
         }
     }
 }
@@ -911,6 +1137,9 @@ impl From<segment::types::MultiVectorConfig> for MultiVectorConfig {
     fn from(value: segment::types::MultiVectorConfig) -> Self {
         Self {
             comparator: MultiVectorComparator::from(value.comparator) as i32,
+            hnsw_aggregation: value
+                .hnsw_aggregation
+                .map(|aggregation| MultiVectorAggregation::from(aggregation) as i32),
         }
     }
 }
@@ -929,12 +1158,38 @@ impl TryFrom<MultiVectorConfig> for segment::types::MultiVectorConfig {
     fn try_from(value: MultiVectorConfig) -> Result<Self, Self::Error> {
         let comparator = MultiVectorComparator::from_i32(value.comparator)
             .ok_or_else(|| Status::invalid_argument("Unknown multi vector comparator"))?;
+        let hnsw_aggregation = value
+            .hnsw_aggregation
+            .map(|aggregation| {
+                MultiVectorAggregation::from_i32(aggregation)
+                    .ok_or_else(|| Status::invalid_argument("Unknown multi vector aggregation"))
+            })
+            .transpose()?;
         Ok(segment::types::MultiVectorConfig {
             comparator: segment::types::MultiVectorComparator::from(comparator),
+            hnsw_aggregation: hnsw_aggregation.map(segment::types::MultiVectorAggregation::from),
         })
     }
 }
 
+impl From<segment::types::MultiVectorAggregation> for MultiVectorAggregation {
+    fn from(value: segment::types::MultiVectorAggregation) -> Self {
+        match value {
+            segment::types::MultiVectorAggregation::Mean => MultiVectorAggregation::Mean,
+            segment::types::MultiVectorAggregation::First => MultiVectorAggregation::First,
+        }
+    }
+}
+
+impl From<MultiVectorAggregation> for segment::types::MultiVectorAggregation {
+    fn from(value: MultiVectorAggregation) -> Self {
+        match value {
+            MultiVectorAggregation::Mean => segment::types::MultiVectorAggregation::Mean,
+            MultiVectorAggregation::First => segment::types::MultiVectorAggregation::First,
+        }
+    }
+}
+
 impl From<MultiVectorComparator> for segment::types::MultiVectorComparator {
     fn from(value: MultiVectorComparator) -> Self {
         match value {
@@ -1035,6 +1290,12 @@ impl TryFrom<Condition> for segment::types::Condition {
                 ConditionOneOf::HasId(has_id) => {
                     Ok(segment::types::Condition::HasId(has_id.try_into()?))
                 }
+                ConditionOneOf::HasVector(has_vector) => Ok(segment::types::Condition::HasVector(
+                    segment::types::HasVectorCondition::from(has_vector.has_vector),
+                )),
+                ConditionOneOf::Attribute(attribute) => {
+                    Ok(segment::types::Condition::Attribute(attribute.try_into()?))
+                }
                 ConditionOneOf::Filter(filter) => {
                     Ok(segment::types::Condition::Filter(filter.try_into()?))
                 }
@@ -1062,6 +1323,14 @@ impl From<segment::types::Condition> for Condition {
             }
             segment::types::Condition::IsNull(is_null) => ConditionOneOf::IsNull(is_null.into()),
             segment::types::Condition::HasId(has_id) => ConditionOneOf::HasId(has_id.into()),
+            segment::types::Condition::HasVector(has_vector) => {
+                ConditionOneOf::HasVector(HasVectorCondition {
+                    has_vector: has_vector.has_vector,
+                })
+            }
+            segment::types::Condition::Attribute(attribute) => {
+                ConditionOneOf::Attribute(attribute.into())
+            }
             segment::types::Condition::Filter(filter) => ConditionOneOf::Filter(filter.into()),
             segment::types::Condition::Nested(nested) => {
                 ConditionOneOf::Nested(nested.nested.into())
@@ -1176,6 +1445,8 @@ impl TryFrom<FieldCondition> for segment::types::FieldCondition {
             values_count,
             geo_polygon,
             datetime_range,
+            keyword_range,
+            big_int_range,
         } = value;
 
         let geo_bounding_box =
@@ -1187,11 +1458,15 @@ impl TryFrom<FieldCondition> for segment::types::FieldCondition {
         let datetime_range = datetime_range
             .map(segment::types::RangeInterface::try_from)
             .transpose()?;
+        let keyword_range = keyword_range.map(Into::into);
+        let big_int_range = big_int_range
+            .map(segment::types::RangeInterface::try_from)
+            .transpose()?;
 
         Ok(Self {
             key: json_path_from_proto(&key)?,
             r#match: r#match.map_or_else(|| Ok(None), |m| m.try_into().map(Some))?,
-            range: range.or(datetime_range),
+            range: range.or(datetime_range).or(keyword_range).or(big_int_range),
             geo_bounding_box,
             geo_radius,
             geo_polygon,
@@ -1212,10 +1487,20 @@ impl From<segment::types::FieldCondition> for FieldCondition {
             values_count,
         } = value;
 
-        let (range, datetime_range) = match range {
-            Some(segment::types::RangeInterface::Float(range)) => (Some(range.into()), None),
-            Some(segment::types::RangeInterface::DateTime(range)) => (None, Some(range.into())),
-            None => (None, None),
+        let (range, datetime_range, keyword_range, big_int_range) = match range {
+            Some(segment::types::RangeInterface::Float(range)) => {
+                (Some(range.into()), None, None, None)
+            }
+            Some(segment::types::RangeInterface::DateTime(range)) => {
+                (None, Some(range.into()), None, None)
+            }
+            Some(segment::types::RangeInterface::Keyword(range)) => {
+                (None, None, Some(range.into()), None)
+            }
+            Some(segment::types::RangeInterface::BigInt(range)) => {
+                (None, None, None, Some(range.into()))
+            }
+            None => (None, None, None, None),
         };
 
         Self {
@@ -1227,6 +1512,8 @@ impl From<segment::types::FieldCondition> for FieldCondition {
             geo_polygon: geo_polygon.map(Into::into),
             values_count: values_count.map(Into::into),
             datetime_range,
+            keyword_range,
+            big_int_range,
         }
     }
 }
@@ -1339,6 +1626,30 @@ impl From<segment::types::GeoLineString> for GeoLineString {
     }
 }
 
+impl TryFrom<AttributeCondition> for segment::types::AttributeCondition {
+    type Error = Status;
+
+    fn try_from(value: AttributeCondition) -> Result<Self, Self::Error> {
+        let AttributeCondition { attribute, range } = value;
+        let range = range
+            .ok_or_else(|| Status::invalid_argument("Range is required for attribute condition"))?;
+        Ok(Self {
+            attribute,
+            range: range.into(),
+        })
+    }
+}
+
+impl From<segment::types::AttributeCondition> for AttributeCondition {
+    fn from(value: segment::types::AttributeCondition) -> Self {
+        let segment::types::AttributeCondition { attribute, range } = value;
+        Self {
+            attribute,
+            range: Some(range.into()),
+        }
+    }
+}
+
 impl From<Range> for segment::types::Range<FloatPayloadType> {
     fn from(value: Range) -> Self {
         Self {
@@ -1391,6 +1702,61 @@ impl From<segment::types::Range<DateTimePayloadType>> for DatetimeRange {
     }
 }
 
+impl From<KeywordRange> for segment::types::RangeInterface {
+    fn from(value: KeywordRange) -> Self {
+        Self::Keyword(segment::types::Range {
+            lt: value.lt,
+            gt: value.gt,
+            gte: value.gte,
+            lte: value.lte,
+        })
+    }
+}
+
+impl From<segment::types::Range<String>> for KeywordRange {
+    fn from(value: segment::types::Range<String>) -> Self {
+        Self {
+            lt: value.lt,
+            gt: value.gt,
+            gte: value.gte,
+            lte: value.lte,
+        }
+    }
+}
+
+impl TryFrom<BigIntRange> for segment::types::RangeInterface {
+    type Error = Status;
+
+    fn try_from(value: BigIntRange) -> Result<Self, Self::Error> {
+        let parse = |bound: Option<String>| {
+            bound
+                .map(|bound| {
+                    bound.parse::<BigIntPayloadType>().map_err(|err| {
+                        Status::invalid_argument(format!("Invalid integer bound {bound}: {err}"))
+                    })
+                })
+                .transpose()
+        };
+        Ok(Self::BigInt(segment::types::Range {
+            lt: parse(value.lt)?,
+            gt: parse(value.gt)?,
+            gte: parse(value.gte)?,
+            lte: parse(value.lte)?,
+        }))
+    }
+}
+
+impl From<segment::types::Range<BigIntPayloadType>> for BigIntRange {
+    fn from(value: segment::types::Range<BigIntPayloadType>) -> Self {
+        Self {
+            lt: value.lt.map(|bound| bound.to_string()),
+            gt: value.gt.map(|bound| bound.to_string()),
+            gte: value.gte.map(|bound| bound.to_string()),
+            lte: value.lte.map(|bound| bound.to_string()),
+        }
+    }
+}
+
 impl From<ValuesCount> for segment::types::ValuesCount {
     fn from(value: ValuesCount) -> Self {
         Self {
@@ -1431,6 +1797,21 @@ impl TryFrom<Match> for segment::types::Match {
                 MatchValue::ExceptKeywords(ints) => {
                     segment::types::Match::Except(ints.strings.into())
                 }
+                MatchValue::Similar(similar) => {
+                    let MatchSimilar {
+                        text,
+                        min_similarity,
+                    } = similar;
+                    if min_similarity.is_some_and(|similarity| !(0.0..=1.0).contains(&similarity)) {
+                        return Err(Status::invalid_argument(
+                            "min_similarity must be between 0 and 1",
+                        ));
+                    }
+                    segment::types::Match::Similar(segment::types::MatchSimilar {
+                        similar: text,
+                        min_similarity,
+                    })
+                }
             }),
             _ => Err(Status::invalid_argument("Malformed Match condition")),
         }
@@ -1468,6 +1849,13 @@ impl From<segment::types::Match> for Match {
                     MatchValue::ExceptIntegers(RepeatedIntegers { integers })
                 }
             },
+            segment::types::Match::Similar(segment::types::MatchSimilar {
+                similar,
+                min_similarity,
+            }) => MatchValue::Similar(MatchSimilar {
+                text: similar,
+                min_similarity,
+            }),
         };
         Self {
             match_value: Some(match_value),
@@ -1524,10 +1912,20 @@ impl TryFrom<OrderBy> for segment::data_types::order_by::OrderBy {
             })
             .transpose()?;
 
+        let then_by = value
+            .then_by
+            .into_iter()
+            .map(segment::data_types::order_by::ThenBy::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
         Ok(Self {
             key: json_path_from_proto(&value.key)?,
             direction,
             start_from,
+            geo: value.geo.map(segment::types::GeoPoint::from),
+            then_by: (!then_by.is_empty()).then_some(then_by),
+            start_after_id: None,
+            start_after_then_by: Vec::new(),
         })
     }
 }
@@ -1538,6 +1936,36 @@ impl From<segment::data_types::order_by::OrderBy> for OrderBy {
             key: value.key.to_string(),
             direction: value.direction.map(|d| Direction::from(d) as i32),
             start_from: value.start_from.map(|start_from| start_from.into()),
+            geo: value.geo.map(GeoPoint::from),
+            then_by: value
+                .then_by
+                .into_iter()
+                .flatten()
+                .map(ThenBy::from)
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<ThenBy> for segment::data_types::order_by::ThenBy {
+    type Error = Status;
+
+    fn try_from(value: ThenBy) -> Result<Self, Self::Error> {
+        Ok(Self {
+            key: json_path_from_proto(&value.key)?,
+            direction: value
+                .direction
+                .and_then(Direction::from_i32)
+                .map(segment::data_types::order_by::Direction::from),
+        })
+    }
+}
+
+impl From<segment::data_types::order_by::ThenBy> for ThenBy {
+    fn from(value: segment::data_types::order_by::ThenBy) -> Self {
+        Self {
+            key: value.key.to_string(),
+            direction: value.direction.map(|d| Direction::from(d) as i32),
         }
     }
 }
@@ -1610,6 +2038,17 @@ impl TryFrom<Distance> for segment::types::Distance {
     }
 }
 
+impl From<segment::types::Distance> for Distance {
+    fn from(value: segment::types::Distance) -> Self {
+        match value {
+            segment::types::Distance::Cosine => Distance::Cosine,
+            segment::types::Distance::Euclid => Distance::Euclid,
+            segment::types::Distance::Dot => Distance::Dot,
+            segment::types::Distance::Manhattan => Distance::Manhattan,
+        }
+    }
+}
+
 pub fn from_grpc_dist(dist: i32) -> Result<segment::types::Distance, Status> {
     match Distance::from_i32(dist) {
         None => Err(Status::invalid_argument(format!(
@@ -1671,6 +2110,7 @@ impl From<UpdateResultInternal> for UpdateResult {
         Self {
             operation_id: res.operation_id,
             status: res.status,
+            conflicts: res.conflicts,
         }
     }
 }
@@ -1682,6 +2122,7 @@ impl From<UpdateResult> for UpdateResultInternal {
             operation_id: res.operation_id,
             status: res.status,
             clock_tag: None,
+            conflicts: res.conflicts,
         }
     }
 }
@@ -1932,7 +2373,7 @@ impl TryFrom<SearchPoints> for rest::SearchRequestInternal {
             )?
             .into(),
             filter: value.filter.map(|f| f.try_into()).transpose()?,
-            params: value.params.map(|p| p.into()),
+            params: value.params.map(|p| p.try_into()).transpose()?,
             limit: value.limit as usize,
             offset: value.offset.map(|x| x as usize),
             with_payload: value.with_payload.map(|wp| wp.try_into()).transpose()?,
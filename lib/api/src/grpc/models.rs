@@ -4,6 +4,8 @@ use schemars::JsonSchema;
 use serde;
 use serde::Serialize;
 
+use crate::rest::{HardwareUsage, ShardReadStatus};
+
 pub fn get_git_commit_id() -> Option<String> {
     option_env!("GIT_COMMIT_ID")
         .map(ToString::to_string)
@@ -43,6 +45,14 @@ pub struct ApiResponse<D> {
     pub result: Option<D>,
     pub status: ApiStatus,
     pub time: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usage: Option<HardwareUsage>,
+    /// Whether the request reached its timeout. Results of such request might be incomplete.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timed_out: Option<bool>,
+    /// Status of each requested shard, if read with `partial` consistency
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shards: Option<Vec<ShardReadStatus>>,
 }
 
 #[derive(Debug, Serialize, JsonSchema)]
@@ -27,6 +27,10 @@ pub struct VectorParams {
     /// Configuration for multi-vector search
     #[prost(message, optional, tag = "7")]
     pub multivector_config: ::core::option::Option<MultiVectorConfig>,
+    /// Size of the input vectors, if larger than `size`. Input vectors are truncated to `size` and normalized
+    #[prost(uint64, optional, tag = "8")]
+    #[validate(range(min = 1, max = 65536))]
+    pub input_size: ::core::option::Option<u64>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -118,6 +122,9 @@ pub struct SparseVectorParams {
     /// If set - apply modifier to the vector values
     #[prost(enumeration = "Modifier", optional, tag = "2")]
     pub modifier: ::core::option::Option<i32>,
+    /// If set - share IDF statistics with other collections of the same vocabulary
+    #[prost(string, optional, tag = "3")]
+    pub vocabulary: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -136,6 +143,9 @@ pub struct MultiVectorConfig {
     /// Comparator for multi-vector search
     #[prost(enumeration = "MultiVectorComparator", tag = "1")]
     pub comparator: i32,
+    /// Aggregation of the vectors of each point to build and traverse HNSW graph with. Candidates are rescored with the comparator
+    #[prost(enumeration = "MultiVectorAggregation", optional, tag = "2")]
+    pub hnsw_aggregation: ::core::option::Option<i32>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -352,6 +362,11 @@ pub struct OptimizersConfigDiff {
     /// If 0 - no optimization threads, optimizations will be disabled.
     #[prost(uint64, optional, tag = "8")]
     pub max_optimization_threads: ::core::option::Option<u64>,
+    ///
+    /// Defer building of vector indexes, for example, during a bulk upload.
+    /// Once disabled, all deferred segments are indexed in the background.
+    #[prost(bool, optional, tag = "9")]
+    pub defer_indexing: ::core::option::Option<bool>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -447,6 +462,103 @@ pub mod quantization_config_diff {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StrictModeConfig {
+    /// Whether strict mode is enabled for the collection
+    #[prost(bool, optional, tag = "1")]
+    pub enabled: ::core::option::Option<bool>,
+    /// Max `limit` of search, query, recommend, discover and scroll requests
+    #[prost(uint64, optional, tag = "2")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub max_query_limit: ::core::option::Option<u64>,
+    /// Max `hnsw_ef` search parameter
+    #[prost(uint64, optional, tag = "3")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub search_max_hnsw_ef: ::core::option::Option<u64>,
+    /// Whether exact search, bypassing the vector index, is allowed
+    #[prost(bool, optional, tag = "4")]
+    pub search_allow_exact: ::core::option::Option<bool>,
+    /// Whether read requests may filter by payload fields without an index
+    #[prost(bool, optional, tag = "5")]
+    pub unindexed_filtering_retrieve: ::core::option::Option<bool>,
+    /// Whether update requests may filter by payload fields without an index
+    #[prost(bool, optional, tag = "6")]
+    pub unindexed_filtering_update: ::core::option::Option<bool>,
+    /// Max size of the payload of a single point in bytes, measured as serialized JSON
+    #[prost(uint64, optional, tag = "7")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub max_payload_size_bytes: ::core::option::Option<u64>,
+    /// Schema, which payloads of updated points must match
+    #[prost(message, optional, tag = "8")]
+    #[validate]
+    pub payload_schema: ::core::option::Option<PayloadSchemaConfig>,
+    /// Max number of points in the collection. Only deletes are allowed, once it is reached
+    #[prost(uint64, optional, tag = "9")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub max_points_count: ::core::option::Option<u64>,
+    /// Max size of vector storages, vector indexes and quantized vectors of the collection, which are loaded into RAM, in bytes
+    #[prost(uint64, optional, tag = "10")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub max_ram_usage_bytes: ::core::option::Option<u64>,
+    /// Max size of the segments of the collection on disk, in bytes
+    #[prost(uint64, optional, tag = "11")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub max_disk_usage_bytes: ::core::option::Option<u64>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PayloadSchemaConfig {
+    /// Constraints of top level payload keys
+    #[prost(map = "string, message", tag = "1")]
+    #[validate]
+    pub fields: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        PayloadFieldConstraints,
+    >,
+    /// Whether payloads may have keys, which are not in `fields`. Default: true
+    #[prost(bool, optional, tag = "2")]
+    pub allow_unknown_fields: ::core::option::Option<bool>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PayloadFieldConstraints {
+    /// Type of the values. Arrays are allowed, if all of their elements have this type
+    #[prost(enumeration = "PayloadSchemaType", optional, tag = "1")]
+    pub r#type: ::core::option::Option<i32>,
+    /// Whether payloads must have a non-null value of this key. Default: false
+    #[prost(bool, optional, tag = "2")]
+    pub required: ::core::option::Option<bool>,
+    /// Max length of string values, in characters
+    #[prost(uint64, optional, tag = "3")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub max_length: ::core::option::Option<u64>,
+    /// Max number of elements of array values
+    #[prost(uint64, optional, tag = "4")]
+    pub max_array_length: ::core::option::Option<u64>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ComputedField {
+    /// Top-level payload field to store the computed value in
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1))]
+    pub key: ::prost::alloc::string::String,
+    /// Function, which computes the value
+    #[prost(enumeration = "ComputedFunction", tag = "2")]
+    pub function: i32,
+    /// Payload field, which the value is computed from, or name of the vector for `VectorNorm`
+    #[prost(string, tag = "3")]
+    pub source: ::prost::alloc::string::String,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CreateCollection {
     /// Name of the collection
     #[prost(string, tag = "1")]
@@ -499,6 +611,42 @@ pub struct CreateCollection {
     /// Configuration for sparse vectors
     #[prost(message, optional, tag = "16")]
     pub sparse_vectors_config: ::core::option::Option<SparseVectorConfig>,
+    /// Strict mode limits of the collection
+    #[prost(message, optional, tag = "17")]
+    #[validate]
+    pub strict_mode_config: ::core::option::Option<StrictModeConfig>,
+    /// If true - point's payload is stored in gridstore instead of RocksDB
+    #[prost(bool, optional, tag = "18")]
+    pub gridstore_payload: ::core::option::Option<bool>,
+    /// Numeric payload fields, whose values are also stored per field for fast scans
+    #[prost(string, repeated, tag = "19")]
+    pub payload_columns: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// If set - payload in the on-disk payload storage is compressed with zstd of this level
+    #[prost(int32, optional, tag = "20")]
+    #[validate(range(min = 1, max = 22))]
+    pub payload_compression_level: ::core::option::Option<i32>,
+    /// If set - this number of last versions of each point is retained
+    #[prost(uint32, optional, tag = "21")]
+    #[validate(range(min = 1))]
+    pub point_history_versions: ::core::option::Option<u32>,
+    /// If set - deleted points are kept in the trash for this number of seconds, and can be restored
+    #[prost(uint64, optional, tag = "22")]
+    #[validate(range(min = 1))]
+    pub soft_delete_retention_sec: ::core::option::Option<u64>,
+    /// Payload fields, computed on every upsert and indexed
+    #[prost(message, repeated, tag = "23")]
+    #[validate]
+    pub computed_fields: ::prost::alloc::vec::Vec<ComputedField>,
+    /// Name of the collection template to take parameters from, which are not set in this request
+    #[prost(string, optional, tag = "24")]
+    #[validate(length(min = 1))]
+    pub template: ::core::option::Option<::prost::alloc::string::String>,
+    /// Typed scalar attributes of points, stored in dense columns outside of the payload
+    #[prost(map = "string, enumeration(AttributeType)", tag = "25")]
+    pub attribute_columns: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        i32,
+    >,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -536,6 +684,10 @@ pub struct UpdateCollection {
     /// New sparse vector parameters
     #[prost(message, optional, tag = "8")]
     pub sparse_vectors_config: ::core::option::Option<SparseVectorConfig>,
+    /// New strict mode limits, fields which are not set are left unchanged
+    #[prost(message, optional, tag = "9")]
+    #[validate]
+    pub strict_mode_config: ::core::option::Option<StrictModeConfig>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -592,6 +744,30 @@ pub struct CollectionParams {
     /// Configuration for sparse vectors
     #[prost(message, optional, tag = "10")]
     pub sparse_vectors_config: ::core::option::Option<SparseVectorConfig>,
+    /// If true - point's payload is stored in gridstore instead of RocksDB
+    #[prost(bool, tag = "11")]
+    pub gridstore_payload: bool,
+    /// Numeric payload fields, whose values are also stored per field for fast scans
+    #[prost(string, repeated, tag = "12")]
+    pub payload_columns: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// If set - payload in the on-disk payload storage is compressed with zstd of this level
+    #[prost(int32, optional, tag = "13")]
+    pub payload_compression_level: ::core::option::Option<i32>,
+    /// If set - this number of last versions of each point is retained
+    #[prost(uint32, optional, tag = "14")]
+    pub point_history_versions: ::core::option::Option<u32>,
+    /// If set - deleted points are kept in the trash for this number of seconds, and can be restored
+    #[prost(uint64, optional, tag = "15")]
+    pub soft_delete_retention_sec: ::core::option::Option<u64>,
+    /// Payload fields, computed on every upsert and indexed
+    #[prost(message, repeated, tag = "16")]
+    pub computed_fields: ::prost::alloc::vec::Vec<ComputedField>,
+    /// Typed scalar attributes of points, stored in dense columns outside of the payload
+    #[prost(map = "string, enumeration(AttributeType)", tag = "17")]
+    pub attribute_columns: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        i32,
+    >,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -610,6 +786,9 @@ pub struct CollectionParamsDiff {
     /// Fan-out every read request to these many additional remote nodes (and return first available response)
     #[prost(uint32, optional, tag = "4")]
     pub read_fan_out_factor: ::core::option::Option<u32>,
+    /// If true - point's payload is stored in gridstore instead of RocksDB
+    #[prost(bool, optional, tag = "5")]
+    pub gridstore_payload: ::core::option::Option<bool>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -634,6 +813,9 @@ pub struct CollectionConfig {
     #[prost(message, optional, tag = "5")]
     #[validate]
     pub quantization_config: ::core::option::Option<QuantizationConfig>,
+    /// Strict mode limits of the collection
+    #[prost(message, optional, tag = "6")]
+    pub strict_mode_config: ::core::option::Option<StrictModeConfig>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -666,8 +848,16 @@ pub struct IntegerIndexParams {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GeoIndexParams {
+    /// If true - store index on disk and access it through memory mapped files
+    #[prost(bool, optional, tag = "1")]
+    pub on_disk: ::core::option::Option<bool>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct PayloadIndexParams {
-    #[prost(oneof = "payload_index_params::IndexParams", tags = "1, 2")]
+    #[prost(oneof = "payload_index_params::IndexParams", tags = "1, 2, 3")]
     pub index_params: ::core::option::Option<payload_index_params::IndexParams>,
 }
 /// Nested message and enum types in `PayloadIndexParams`.
@@ -682,6 +872,9 @@ pub mod payload_index_params {
         /// Parameters for integer index
         #[prost(message, tag = "2")]
         IntegerIndexParams(super::IntegerIndexParams),
+        /// Parameters for geo index
+        #[prost(message, tag = "3")]
+        GeoIndexParams(super::GeoIndexParams),
     }
 }
 #[derive(serde::Serialize)]
@@ -876,6 +1069,9 @@ pub struct LocalShardInfo {
     /// User-defined shard key
     #[prost(message, optional, tag = "4")]
     pub shard_key: ::core::option::Option<ShardKey>,
+    /// Segments of the replica, which failed to load and were moved into quarantine
+    #[prost(string, repeated, tag = "5")]
+    pub quarantined_segments: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -1208,6 +1404,35 @@ impl MultiVectorComparator {
 #[derive(serde::Serialize)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
+pub enum MultiVectorAggregation {
+    /// Mean of the vectors
+    Mean = 0,
+    /// First vector, e.g. the vector of the `\[CLS\]` token
+    First = 1,
+}
+impl MultiVectorAggregation {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            MultiVectorAggregation::Mean => "Mean",
+            MultiVectorAggregation::First => "First",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Mean" => Some(Self::Mean),
+            "First" => Some(Self::First),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
 pub enum Distance {
     UnknownDistance = 0,
     Cosine = 1,
@@ -1293,6 +1518,8 @@ pub enum PayloadSchemaType {
     Text = 5,
     Bool = 6,
     Datetime = 7,
+    BigInt = 8,
+    MinHash = 9,
 }
 impl PayloadSchemaType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -1309,6 +1536,8 @@ impl PayloadSchemaType {
             PayloadSchemaType::Text => "Text",
             PayloadSchemaType::Bool => "Bool",
             PayloadSchemaType::Datetime => "Datetime",
+            PayloadSchemaType::BigInt => "BigInt",
+            PayloadSchemaType::MinHash => "MinHash",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -1322,6 +1551,8 @@ impl PayloadSchemaType {
             "Text" => Some(Self::Text),
             "Bool" => Some(Self::Bool),
             "Datetime" => Some(Self::Datetime),
+            "BigInt" => Some(Self::BigInt),
+            "MinHash" => Some(Self::MinHash),
             _ => None,
         }
     }
@@ -1421,6 +1652,68 @@ impl ShardingMethod {
 #[derive(serde::Serialize)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
+pub enum ComputedFunction {
+    /// Lowercase copy of a string payload field
+    Lowercase = 0,
+    /// Number of whitespace separated words in a string payload field
+    TokenCount = 1,
+    /// Euclidean norm of a dense or sparse vector
+    VectorNorm = 2,
+}
+impl ComputedFunction {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ComputedFunction::Lowercase => "Lowercase",
+            ComputedFunction::TokenCount => "TokenCount",
+            ComputedFunction::VectorNorm => "VectorNorm",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Lowercase" => Some(Self::Lowercase),
+            "TokenCount" => Some(Self::TokenCount),
+            "VectorNorm" => Some(Self::VectorNorm),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum AttributeType {
+    /// 32-bit float attribute, integer values are converted
+    FloatAttribute = 0,
+    /// 64-bit integer attribute
+    IntegerAttribute = 1,
+}
+impl AttributeType {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            AttributeType::FloatAttribute => "FloatAttribute",
+            AttributeType::IntegerAttribute => "IntegerAttribute",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "FloatAttribute" => Some(Self::FloatAttribute),
+            "IntegerAttribute" => Some(Self::IntegerAttribute),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
 pub enum TokenizerType {
     Unknown = 0,
     Prefix = 1,
@@ -3834,6 +4127,35 @@ pub struct UpsertPoints {
     /// Option for custom sharding to specify used shard keys
     #[prost(message, optional, tag = "5")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// Only update existing points, which satisfy this condition
+    #[prost(message, optional, tag = "6")]
+    #[validate]
+    pub precondition: ::core::option::Option<WritePrecondition>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpsertPointsChunk {
+    /// name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Wait until the changes of the chunk have been applied before acknowledging it?
+    #[prost(bool, optional, tag = "2")]
+    pub wait: ::core::option::Option<bool>,
+    #[prost(message, repeated, tag = "3")]
+    #[validate]
+    pub points: ::prost::alloc::vec::Vec<PointStruct>,
+    /// Write ordering guarantees
+    #[prost(message, optional, tag = "4")]
+    pub ordering: ::core::option::Option<WriteOrdering>,
+    /// Option for custom sharding to specify used shard keys
+    #[prost(message, optional, tag = "5")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// Disable vector indexing of the collection until the stream is completed. Only considered in the first chunk
+    #[prost(bool, optional, tag = "6")]
+    pub defer_indexing: ::core::option::Option<bool>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -3856,6 +4178,10 @@ pub struct DeletePoints {
     /// Option for custom sharding to specify used shard keys
     #[prost(message, optional, tag = "5")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// Only delete points, which satisfy this condition. Only supported for point ids selector
+    #[prost(message, optional, tag = "6")]
+    #[validate]
+    pub precondition: ::core::option::Option<WritePrecondition>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -3967,6 +4293,10 @@ pub struct SetPayloadPoints {
     /// Option for indicate property of payload
     #[prost(string, optional, tag = "8")]
     pub key: ::core::option::Option<::prost::alloc::string::String>,
+    /// Only update points, which satisfy this condition
+    #[prost(message, optional, tag = "9")]
+    #[validate]
+    pub precondition: ::core::option::Option<WritePrecondition>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -4015,6 +4345,55 @@ pub struct ClearPayloadPoints {
     #[prost(message, optional, tag = "5")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
 }
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AttributeValue {
+    #[prost(oneof = "attribute_value::Kind", tags = "1, 2")]
+    pub kind: ::core::option::Option<attribute_value::Kind>,
+}
+/// Nested message and enum types in `AttributeValue`.
+pub mod attribute_value {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Kind {
+        /// Value of a float attribute
+        #[prost(float, tag = "1")]
+        FloatValue(f32),
+        /// Value of an integer attribute
+        #[prost(int64, tag = "2")]
+        IntegerValue(i64),
+    }
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetAttributesPoints {
+    /// name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Wait until the changes have been applied?
+    #[prost(bool, optional, tag = "2")]
+    pub wait: ::core::option::Option<bool>,
+    /// New attribute values, attributes without a value are removed
+    #[prost(map = "string, message", tag = "3")]
+    pub attributes: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        AttributeValue,
+    >,
+    /// Affected points
+    #[prost(message, optional, tag = "4")]
+    pub points_selector: ::core::option::Option<PointsSelector>,
+    /// Write ordering guarantees
+    #[prost(message, optional, tag = "5")]
+    pub ordering: ::core::option::Option<WriteOrdering>,
+    /// Option for custom sharding to specify used shard keys
+    #[prost(message, optional, tag = "6")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+}
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -4080,8 +4459,19 @@ pub struct PayloadExcludeSelector {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PayloadProjectSelector {
+    /// Keys of the result payload, mapped to the paths of their values in the original payload
+    #[prost(map = "string, string", tag = "1")]
+    pub fields: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        ::prost::alloc::string::String,
+    >,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct WithPayloadSelector {
-    #[prost(oneof = "with_payload_selector::SelectorOptions", tags = "1, 2, 3")]
+    #[prost(oneof = "with_payload_selector::SelectorOptions", tags = "1, 2, 3, 4")]
     pub selector_options: ::core::option::Option<with_payload_selector::SelectorOptions>,
 }
 /// Nested message and enum types in `WithPayloadSelector`.
@@ -4097,6 +4487,8 @@ pub mod with_payload_selector {
         Include(super::PayloadIncludeSelector),
         #[prost(message, tag = "3")]
         Exclude(super::PayloadExcludeSelector),
+        #[prost(message, tag = "4")]
+        Project(super::PayloadProjectSelector),
     }
 }
 #[derive(validator::Validate)]
@@ -4208,6 +4600,12 @@ pub struct SearchParams {
     /// guarantee that all uploaded vectors will be included in search results
     #[prost(bool, optional, tag = "4")]
     pub indexed_only: ::core::option::Option<bool>,
+    ///
+    /// Score vectors with this distance instead of the configured one.
+    /// `Cosine` can only be used for vectors, stored with `Cosine` distance, as only those are normalized.
+    /// Search with a different distance is always exact.
+    #[prost(enumeration = "Distance", optional, tag = "5")]
+    pub distance: ::core::option::Option<i32>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -4391,6 +4789,23 @@ pub struct OrderBy {
     /// Start from this value
     #[prost(message, optional, tag = "3")]
     pub start_from: ::core::option::Option<StartFrom>,
+    /// Order by distance from this point, in meters. Requires a geo index on the key
+    #[prost(message, optional, tag = "4")]
+    pub geo: ::core::option::Option<GeoPoint>,
+    /// Order points with equal values of the key by these keys, remaining ties are ordered by point id
+    #[prost(message, repeated, tag = "5")]
+    pub then_by: ::prost::alloc::vec::Vec<ThenBy>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ThenBy {
+    /// Payload key to order by. Requires a range index
+    #[prost(string, tag = "1")]
+    pub key: ::prost::alloc::string::String,
+    /// Ascending or descending order
+    #[prost(enumeration = "Direction", optional, tag = "2")]
+    pub direction: ::core::option::Option<i32>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -4426,6 +4841,9 @@ pub struct ScrollPoints {
     /// Order the records by a payload field
     #[prost(message, optional, tag = "10")]
     pub order_by: ::core::option::Option<OrderBy>,
+    /// Continue ordered scroll after the position of a `next_page_cursor` from the previous page. Requires `order_by`
+    #[prost(string, optional, tag = "11")]
+    pub cursor: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -4742,38 +5160,125 @@ pub struct CountPoints {
     #[prost(message, optional, tag = "5")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
 }
+#[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct RecommendInput {
-    /// Look for vectors closest to the vectors from these points
-    #[prost(message, repeated, tag = "1")]
-    pub positive: ::prost::alloc::vec::Vec<VectorInput>,
-    /// Try to avoid vectors like the vector from these points
-    #[prost(message, repeated, tag = "2")]
-    pub negative: ::prost::alloc::vec::Vec<VectorInput>,
-    /// How to use the provided vectors to find the results
-    #[prost(enumeration = "RecommendStrategy", optional, tag = "3")]
-    pub strategy: ::core::option::Option<i32>,
-}
-#[derive(serde::Serialize)]
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct ContextInputPair {
-    /// A positive vector
-    #[prost(message, optional, tag = "1")]
-    pub positive: ::core::option::Option<VectorInput>,
-    /// Repel from this vector
-    #[prost(message, optional, tag = "2")]
-    pub negative: ::core::option::Option<VectorInput>,
+pub struct CountDistinctPoints {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Payload field to count the distinct values of
+    #[prost(string, tag = "2")]
+    #[validate(length(min = 1))]
+    pub key: ::prost::alloc::string::String,
+    /// Filter conditions - count values only of the points that satisfy the specified conditions
+    #[prost(message, optional, tag = "3")]
+    #[validate]
+    pub filter: ::core::option::Option<Filter>,
+    /// Options for specifying read consistency guarantees
+    #[prost(message, optional, tag = "4")]
+    pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[prost(message, optional, tag = "5")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
 }
+#[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct DiscoverInput {
-    /// Use this as the primary search objective
-    #[prost(message, optional, tag = "1")]
-    pub target: ::core::option::Option<VectorInput>,
+pub struct HistogramPoints {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Payload field with an integer, float or datetime index
+    #[prost(string, tag = "2")]
+    #[validate(length(min = 1))]
+    pub key: ::prost::alloc::string::String,
+    /// Options for specifying read consistency guarantees
+    #[prost(message, optional, tag = "5")]
+    pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[prost(message, optional, tag = "6")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    #[prost(oneof = "histogram_points::Buckets", tags = "3, 4")]
+    pub buckets: ::core::option::Option<histogram_points::Buckets>,
+}
+/// Nested message and enum types in `HistogramPoints`.
+pub mod histogram_points {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Buckets {
+        /// Buckets of the same width, aligned to multiples of the width
+        #[prost(double, tag = "3")]
+        Interval(f64),
+        /// Given number of buckets with about the same number of values in each
+        #[prost(uint64, tag = "4")]
+        Quantiles(u64),
+    }
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FieldStatsPoints {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Payload field with an integer, float or datetime index
+    #[prost(string, tag = "2")]
+    #[validate(length(min = 1))]
+    pub key: ::prost::alloc::string::String,
+    /// Filter conditions - use values only of the points that satisfy the specified conditions
+    #[prost(message, optional, tag = "3")]
+    #[validate]
+    pub filter: ::core::option::Option<Filter>,
+    /// Percentiles to compute, from 0 to 100. If empty - 50, 95 and 99
+    #[prost(double, repeated, tag = "4")]
+    pub percentiles: ::prost::alloc::vec::Vec<f64>,
+    /// Options for specifying read consistency guarantees
+    #[prost(message, optional, tag = "5")]
+    pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[prost(message, optional, tag = "6")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RecommendInput {
+    /// Look for vectors closest to the vectors from these points
+    #[prost(message, repeated, tag = "1")]
+    pub positive: ::prost::alloc::vec::Vec<VectorInput>,
+    /// Try to avoid vectors like the vector from these points
+    #[prost(message, repeated, tag = "2")]
+    pub negative: ::prost::alloc::vec::Vec<VectorInput>,
+    /// How to use the provided vectors to find the results
+    #[prost(enumeration = "RecommendStrategy", optional, tag = "3")]
+    pub strategy: ::core::option::Option<i32>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ContextInputPair {
+    /// A positive vector
+    #[prost(message, optional, tag = "1")]
+    pub positive: ::core::option::Option<VectorInput>,
+    /// Repel from this vector
+    #[prost(message, optional, tag = "2")]
+    pub negative: ::core::option::Option<VectorInput>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DiscoverInput {
+    /// Use this as the primary search objective
+    #[prost(message, optional, tag = "1")]
+    pub target: ::core::option::Option<VectorInput>,
     /// Search space will be constrained by these pairs of vectors
     #[prost(message, optional, tag = "2")]
     pub context: ::core::option::Option<ContextInput>,
@@ -4789,8 +5294,149 @@ pub struct ContextInput {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WeightedFusion {
+    /// Weight of each prefetch, in the same order as the prefetches. Scores of the prefetches are multiplied by the weights and summed.
+    #[prost(float, repeated, tag = "1")]
+    pub weights: ::prost::alloc::vec::Vec<f32>,
+    /// Normalization of the scores of each prefetch, applied before the weights. If not set, the scores are summed as-is.
+    #[prost(enumeration = "ScoreNormalization", optional, tag = "2")]
+    pub normalization: ::core::option::Option<i32>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Formula {
+    #[prost(message, optional, tag = "1")]
+    pub expression: ::core::option::Option<Expression>,
+    /// Values of the payload variables, used for points which don't have them
+    #[prost(map = "string, message", tag = "2")]
+    pub defaults: ::std::collections::HashMap<::prost::alloc::string::String, Value>,
+    /// Values of the query-time parameters. Parameters can be numbers, geo points or datetimes in RFC 3339 format.
+    #[prost(map = "string, message", tag = "3")]
+    pub params: ::std::collections::HashMap<::prost::alloc::string::String, Value>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Expression {
+    #[prost(oneof = "expression::Variant", tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10")]
+    pub variant: ::core::option::Option<expression::Variant>,
+}
+/// Nested message and enum types in `Expression`.
+pub mod expression {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Variant {
+        #[prost(float, tag = "1")]
+        Constant(f32),
+        /// Payload key, or score of a prefetch: `$score` for the first one, `$score\[<index>\]` for others
+        #[prost(string, tag = "2")]
+        Variable(::prost::alloc::string::String),
+        /// Value of a query-time parameter
+        #[prost(string, tag = "3")]
+        Param(::prost::alloc::string::String),
+        #[prost(message, tag = "4")]
+        Mult(super::MultExpression),
+        #[prost(message, tag = "5")]
+        Sum(super::SumExpression),
+        #[prost(message, tag = "6")]
+        Neg(::prost::alloc::boxed::Box<super::Expression>),
+        #[prost(message, tag = "7")]
+        Div(::prost::alloc::boxed::Box<super::DivExpression>),
+        #[prost(message, tag = "8")]
+        GeoDistance(super::GeoDistance),
+        #[prost(message, tag = "9")]
+        DatetimeDecay(super::DatetimeDecay),
+        /// Value in the attribute column with this name
+        #[prost(string, tag = "10")]
+        Attribute(::prost::alloc::string::String),
+    }
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MultExpression {
+    #[prost(message, repeated, tag = "1")]
+    pub mult: ::prost::alloc::vec::Vec<Expression>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SumExpression {
+    #[prost(message, repeated, tag = "1")]
+    pub sum: ::prost::alloc::vec::Vec<Expression>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DivExpression {
+    #[prost(message, optional, boxed, tag = "1")]
+    pub left: ::core::option::Option<::prost::alloc::boxed::Box<Expression>>,
+    #[prost(message, optional, boxed, tag = "2")]
+    pub right: ::core::option::Option<::prost::alloc::boxed::Box<Expression>>,
+    /// Result of the division by zero. Default is 0.
+    #[prost(float, optional, tag = "3")]
+    pub by_zero_default: ::core::option::Option<f32>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GeoDistance {
+    /// Payload key with the geo point
+    #[prost(string, tag = "3")]
+    pub to: ::prost::alloc::string::String,
+    #[prost(oneof = "geo_distance::Origin", tags = "1, 2")]
+    pub origin: ::core::option::Option<geo_distance::Origin>,
+}
+/// Nested message and enum types in `GeoDistance`.
+pub mod geo_distance {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Origin {
+        #[prost(message, tag = "1")]
+        Point(super::GeoPoint),
+        /// Query-time parameter with the geo point
+        #[prost(string, tag = "2")]
+        OriginParam(::prost::alloc::string::String),
+    }
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DatetimeDecay {
+    /// Payload key with the datetime
+    #[prost(string, tag = "1")]
+    pub key: ::prost::alloc::string::String,
+    /// Distance from the target in seconds, at which the decay reaches `midpoint`
+    #[prost(float, tag = "4")]
+    pub scale: f32,
+    /// Value of the decay at `scale` distance. Default is 0.5.
+    #[prost(float, optional, tag = "5")]
+    pub midpoint: ::core::option::Option<f32>,
+    #[prost(oneof = "datetime_decay::Target", tags = "2, 3")]
+    pub target: ::core::option::Option<datetime_decay::Target>,
+}
+/// Nested message and enum types in `DatetimeDecay`.
+pub mod datetime_decay {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Target {
+        /// Datetime in RFC 3339 format
+        #[prost(string, tag = "2")]
+        Datetime(::prost::alloc::string::String),
+        /// Query-time parameter with the datetime
+        #[prost(string, tag = "3")]
+        TargetParam(::prost::alloc::string::String),
+    }
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Query {
-    #[prost(oneof = "query::Variant", tags = "1, 2, 3, 4, 5, 6")]
+    #[prost(oneof = "query::Variant", tags = "1, 2, 3, 4, 5, 6, 7, 8")]
     pub variant: ::core::option::Option<query::Variant>,
 }
 /// Nested message and enum types in `Query`.
@@ -4817,6 +5463,12 @@ pub mod query {
         /// Fuse the results of multiple prefetches.
         #[prost(enumeration = "super::Fusion", tag = "6")]
         Fusion(i32),
+        /// Fuse the results of multiple prefetches by weighted sum of their scores.
+        #[prost(message, tag = "7")]
+        WeightedFusion(super::WeightedFusion),
+        /// Score the results of the prefetches by a formula.
+        #[prost(message, tag = "8")]
+        Formula(super::Formula),
     }
 }
 #[derive(serde::Serialize)]
@@ -4835,7 +5487,7 @@ pub struct PrefetchQuery {
     /// Filter conditions - return only those points that satisfy the specified conditions.
     #[prost(message, optional, tag = "4")]
     pub filter: ::core::option::Option<Filter>,
-    /// Search params for when there is no prefetch.
+    /// Search params. With prefetches, they are used to re-score the prefetched points by vector query.
     #[prost(message, optional, tag = "5")]
     pub search_params: ::core::option::Option<SearchParams>,
     /// Return points with scores better than this threshold.
@@ -4870,7 +5522,7 @@ pub struct QueryPoints {
     #[prost(message, optional, tag = "5")]
     #[validate]
     pub filter: ::core::option::Option<Filter>,
-    /// Search params for when there is no prefetch.
+    /// Search params. With prefetches, they are used to re-score the prefetched points by vector query.
     #[prost(message, optional, tag = "6")]
     pub search_params: ::core::option::Option<SearchParams>,
     /// Return points with scores better than this threshold.
@@ -4902,6 +5554,10 @@ pub struct QueryPoints {
     #[prost(uint64, optional, tag = "15")]
     #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
     pub timeout: ::core::option::Option<u64>,
+    /// Max total size of payloads in the response, in bytes. Payload fields, which don't fit, are removed, and the points are marked as `truncated`.
+    #[prost(uint64, optional, tag = "16")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub payload_size_limit: ::core::option::Option<u64>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -5101,6 +5757,19 @@ pub struct PointsOperationResponse {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpsertStreamResponse {
+    /// Number of points acknowledged in the stream so far, including this chunk
+    #[prost(uint64, tag = "1")]
+    pub offset: u64,
+    #[prost(message, optional, tag = "2")]
+    pub result: ::core::option::Option<UpdateResult>,
+    /// Time spent to process the chunk
+    #[prost(double, tag = "3")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct UpdateResult {
     /// Number of operation
     #[prost(uint64, optional, tag = "1")]
@@ -5108,6 +5777,33 @@ pub struct UpdateResult {
     /// Operation status
     #[prost(enumeration = "UpdateStatus", tag = "2")]
     pub status: i32,
+    /// Points, which were not updated because their precondition was not satisfied
+    #[prost(message, repeated, tag = "4")]
+    pub conflicts: ::prost::alloc::vec::Vec<WriteConflict>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WritePrecondition {
+    /// Expected current version of the point
+    #[prost(uint64, optional, tag = "1")]
+    pub version: ::core::option::Option<u64>,
+    /// Filter, which the current point must satisfy
+    #[prost(message, optional, tag = "2")]
+    #[validate]
+    pub filter: ::core::option::Option<Filter>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WriteConflict {
+    /// Point, which did not satisfy the precondition
+    #[prost(message, optional, tag = "1")]
+    pub id: ::core::option::Option<PointId>,
+    /// Current version of the point, if it exists
+    #[prost(uint64, optional, tag = "2")]
+    pub current_version: ::core::option::Option<u64>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5153,6 +5849,9 @@ pub struct ScoredPoint {
     /// Order by value
     #[prost(message, optional, tag = "8")]
     pub order_value: ::core::option::Option<OrderValue>,
+    /// If true, some payload fields were removed to fit into the payload size limit of the request
+    #[prost(bool, tag = "9")]
+    pub truncated: bool,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5203,12 +5902,50 @@ pub struct GroupsResult {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HardwareUsage {
+    /// Number of vectors, scored against the query
+    #[prost(uint64, tag = "1")]
+    pub cpu: u64,
+    /// Number of payload bytes read
+    #[prost(uint64, tag = "2")]
+    pub payload_io_read: u64,
+    /// Number of vector bytes read
+    #[prost(uint64, tag = "3")]
+    pub vector_io_read: u64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ShardReadStatus {
+    #[prost(uint32, tag = "1")]
+    pub shard_id: u32,
+    #[prost(message, optional, tag = "2")]
+    pub shard_key: ::core::option::Option<ShardKey>,
+    /// Whether the shard responded. Points of the shards, which didn't, are missing in the result
+    #[prost(bool, tag = "3")]
+    pub ok: bool,
+    /// Reason, why the shard didn't respond
+    #[prost(string, optional, tag = "4")]
+    pub error: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SearchResponse {
     #[prost(message, repeated, tag = "1")]
     pub result: ::prost::alloc::vec::Vec<ScoredPoint>,
     /// Time spent to process
     #[prost(double, tag = "2")]
     pub time: f64,
+    /// Hardware resources spent on the request, if reporting is enabled
+    #[prost(message, optional, tag = "3")]
+    pub usage: ::core::option::Option<HardwareUsage>,
+    /// Whether the request reached its timeout, results might be incomplete then
+    #[prost(bool, optional, tag = "4")]
+    pub timed_out: ::core::option::Option<bool>,
+    /// Status of each requested shard, if read with `Partial` consistency
+    #[prost(message, repeated, tag = "5")]
+    pub shards: ::prost::alloc::vec::Vec<ShardReadStatus>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5219,6 +5956,12 @@ pub struct QueryResponse {
     /// Time spent to process
     #[prost(double, tag = "2")]
     pub time: f64,
+    /// Hardware resources spent on the request, if reporting is enabled
+    #[prost(message, optional, tag = "3")]
+    pub usage: ::core::option::Option<HardwareUsage>,
+    /// Whether the request reached its timeout, results might be incomplete then
+    #[prost(bool, optional, tag = "4")]
+    pub timed_out: ::core::option::Option<bool>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5229,6 +5972,12 @@ pub struct QueryBatchResponse {
     /// Time spent to process
     #[prost(double, tag = "2")]
     pub time: f64,
+    /// Hardware resources spent on the request, if reporting is enabled
+    #[prost(message, optional, tag = "3")]
+    pub usage: ::core::option::Option<HardwareUsage>,
+    /// Whether the request reached its timeout, results might be incomplete then
+    #[prost(bool, optional, tag = "4")]
+    pub timed_out: ::core::option::Option<bool>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5246,6 +5995,15 @@ pub struct SearchBatchResponse {
     /// Time spent to process
     #[prost(double, tag = "2")]
     pub time: f64,
+    /// Hardware resources spent on the request, if reporting is enabled
+    #[prost(message, optional, tag = "3")]
+    pub usage: ::core::option::Option<HardwareUsage>,
+    /// Whether the request reached its timeout, results might be incomplete then
+    #[prost(bool, optional, tag = "4")]
+    pub timed_out: ::core::option::Option<bool>,
+    /// Status of each requested shard, if read with `Partial` consistency
+    #[prost(message, repeated, tag = "5")]
+    pub shards: ::prost::alloc::vec::Vec<ShardReadStatus>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5279,6 +6037,9 @@ pub struct ScrollResponse {
     /// Time spent to process
     #[prost(double, tag = "3")]
     pub time: f64,
+    /// Use this cursor for the next query, if ordering by a payload field
+    #[prost(string, optional, tag = "4")]
+    pub next_page_cursor: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5286,27 +6047,16 @@ pub struct ScrollResponse {
 pub struct CountResult {
     #[prost(uint64, tag = "1")]
     pub count: u64,
+    /// Whether the replicas, read according to the read consistency, returned the same count. Only set if more than one replica was read.
+    #[prost(bool, optional, tag = "2")]
+    pub consistent: ::core::option::Option<bool>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct RetrievedPoint {
+pub struct CountDistinctResponse {
     #[prost(message, optional, tag = "1")]
-    pub id: ::core::option::Option<PointId>,
-    #[prost(map = "string, message", tag = "2")]
-    pub payload: ::std::collections::HashMap<::prost::alloc::string::String, Value>,
-    #[prost(message, optional, tag = "4")]
-    pub vectors: ::core::option::Option<Vectors>,
-    /// Shard key
-    #[prost(message, optional, tag = "5")]
-    pub shard_key: ::core::option::Option<ShardKey>,
-}
-#[derive(serde::Serialize)]
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct GetResponse {
-    #[prost(message, repeated, tag = "1")]
-    pub result: ::prost::alloc::vec::Vec<RetrievedPoint>,
+    pub result: ::core::option::Option<CountDistinctResult>,
     /// Time spent to process
     #[prost(double, tag = "2")]
     pub time: f64,
@@ -5314,19 +6064,24 @@ pub struct GetResponse {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct RecommendResponse {
-    #[prost(message, repeated, tag = "1")]
-    pub result: ::prost::alloc::vec::Vec<ScoredPoint>,
-    /// Time spent to process
-    #[prost(double, tag = "2")]
-    pub time: f64,
+pub struct CountDistinctResult {
+    /// Number of distinct values of the field
+    #[prost(uint64, tag = "1")]
+    pub count: u64,
+    /// If `false` - the count is an estimation
+    #[prost(bool, tag = "2")]
+    pub exact: bool,
+    /// Whether the replicas, read according to the read consistency, returned the same values. Only set if more than one replica was read.
+    #[prost(bool, optional, tag = "3")]
+    pub consistent: ::core::option::Option<bool>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct RecommendBatchResponse {
+pub struct HistogramResponse {
+    /// Buckets in ascending order of values
     #[prost(message, repeated, tag = "1")]
-    pub result: ::prost::alloc::vec::Vec<BatchResult>,
+    pub result: ::prost::alloc::vec::Vec<HistogramBucket>,
     /// Time spent to process
     #[prost(double, tag = "2")]
     pub time: f64,
@@ -5334,19 +6089,23 @@ pub struct RecommendBatchResponse {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct DiscoverResponse {
-    #[prost(message, repeated, tag = "1")]
-    pub result: ::prost::alloc::vec::Vec<ScoredPoint>,
-    /// Time spent to process
+pub struct HistogramBucket {
+    /// Lower bound of the bucket, inclusive
+    #[prost(double, tag = "1")]
+    pub from: f64,
+    /// Upper bound of the bucket, exclusive. The last bucket includes its upper bound
     #[prost(double, tag = "2")]
-    pub time: f64,
+    pub to: f64,
+    /// Approximate number of values in the bucket
+    #[prost(uint64, tag = "3")]
+    pub count: u64,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct DiscoverBatchResponse {
-    #[prost(message, repeated, tag = "1")]
-    pub result: ::prost::alloc::vec::Vec<BatchResult>,
+pub struct FieldStatsResponse {
+    #[prost(message, optional, tag = "1")]
+    pub result: ::core::option::Option<FieldStatsResult>,
     /// Time spent to process
     #[prost(double, tag = "2")]
     pub time: f64,
@@ -5354,19 +6113,134 @@ pub struct DiscoverBatchResponse {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct RecommendGroupsResponse {
-    #[prost(message, optional, tag = "1")]
-    pub result: ::core::option::Option<GroupsResult>,
-    /// Time spent to process
-    #[prost(double, tag = "2")]
-    pub time: f64,
+pub struct FieldStatsResult {
+    /// Number of values of the field
+    #[prost(uint64, tag = "1")]
+    pub count: u64,
+    /// Smallest value, if there are any values
+    #[prost(double, optional, tag = "2")]
+    pub min: ::core::option::Option<f64>,
+    /// Largest value, if there are any values
+    #[prost(double, optional, tag = "3")]
+    pub max: ::core::option::Option<f64>,
+    /// Average value, if there are any values
+    #[prost(double, optional, tag = "4")]
+    pub avg: ::core::option::Option<f64>,
+    /// Requested percentiles. Approximate, if there are many values
+    #[prost(message, repeated, tag = "5")]
+    pub percentiles: ::prost::alloc::vec::Vec<PercentileValue>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct UpdateBatchResponse {
-    #[prost(message, repeated, tag = "1")]
-    pub result: ::prost::alloc::vec::Vec<UpdateResult>,
+pub struct PercentileValue {
+    #[prost(double, tag = "1")]
+    pub percentile: f64,
+    /// Value, below which the given percent of values are
+    #[prost(double, optional, tag = "2")]
+    pub value: ::core::option::Option<f64>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RetrievedPoint {
+    #[prost(message, optional, tag = "1")]
+    pub id: ::core::option::Option<PointId>,
+    #[prost(map = "string, message", tag = "2")]
+    pub payload: ::std::collections::HashMap<::prost::alloc::string::String, Value>,
+    #[prost(message, optional, tag = "4")]
+    pub vectors: ::core::option::Option<Vectors>,
+    /// Shard key
+    #[prost(message, optional, tag = "5")]
+    pub shard_key: ::core::option::Option<ShardKey>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub result: ::prost::alloc::vec::Vec<RetrievedPoint>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RecommendResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub result: ::prost::alloc::vec::Vec<ScoredPoint>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RecommendBatchResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub result: ::prost::alloc::vec::Vec<BatchResult>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DiscoverResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub result: ::prost::alloc::vec::Vec<ScoredPoint>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DiscoverBatchResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub result: ::prost::alloc::vec::Vec<BatchResult>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RecommendGroupsResponse {
+    #[prost(message, optional, tag = "1")]
+    pub result: ::core::option::Option<GroupsResult>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateBatchResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub result: ::prost::alloc::vec::Vec<UpdateResult>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BulkUpdateItemResult {
+    /// Result of the operation, if it was applied successfully
+    #[prost(message, optional, tag = "1")]
+    pub result: ::core::option::Option<UpdateResult>,
+    /// Reason of the operation failure
+    #[prost(string, optional, tag = "2")]
+    pub error: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BulkUpdateResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub result: ::prost::alloc::vec::Vec<BulkUpdateItemResult>,
     /// Time spent to process
     #[prost(double, tag = "2")]
     pub time: f64,
@@ -5406,7 +6280,7 @@ pub struct MinShould {
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Condition {
-    #[prost(oneof = "condition::ConditionOneOf", tags = "1, 2, 3, 4, 5, 6")]
+    #[prost(oneof = "condition::ConditionOneOf", tags = "1, 2, 3, 4, 5, 6, 7, 8")]
     #[validate]
     pub condition_one_of: ::core::option::Option<condition::ConditionOneOf>,
 }
@@ -5428,6 +6302,10 @@ pub mod condition {
         IsNull(super::IsNullCondition),
         #[prost(message, tag = "6")]
         Nested(super::NestedCondition),
+        #[prost(message, tag = "7")]
+        HasVector(super::HasVectorCondition),
+        #[prost(message, tag = "8")]
+        Attribute(super::AttributeCondition),
     }
 }
 #[derive(serde::Serialize)]
@@ -5451,6 +6329,24 @@ pub struct HasIdCondition {
     #[prost(message, repeated, tag = "1")]
     pub has_id: ::prost::alloc::vec::Vec<PointId>,
 }
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HasVectorCondition {
+    #[prost(string, tag = "1")]
+    pub has_vector: ::prost::alloc::string::String,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AttributeCondition {
+    /// Name of the attribute
+    #[prost(string, tag = "1")]
+    pub attribute: ::prost::alloc::string::String,
+    /// Range of the attribute value
+    #[prost(message, optional, tag = "2")]
+    pub range: ::core::option::Option<Range>,
+}
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5491,12 +6387,18 @@ pub struct FieldCondition {
     /// Check if datetime is within a given range
     #[prost(message, optional, tag = "8")]
     pub datetime_range: ::core::option::Option<DatetimeRange>,
+    /// Check if keyword is within a given range, in lexicographic order
+    #[prost(message, optional, tag = "9")]
+    pub keyword_range: ::core::option::Option<KeywordRange>,
+    /// Check if integer is within a given range, without conversion to float
+    #[prost(message, optional, tag = "10")]
+    pub big_int_range: ::core::option::Option<BigIntRange>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Match {
-    #[prost(oneof = "r#match::MatchValue", tags = "1, 2, 3, 4, 5, 6, 7, 8")]
+    #[prost(oneof = "r#match::MatchValue", tags = "1, 2, 3, 4, 5, 6, 7, 8, 9")]
     pub match_value: ::core::option::Option<r#match::MatchValue>,
 }
 /// Nested message and enum types in `Match`.
@@ -5529,11 +6431,25 @@ pub mod r#match {
         /// Match any other value except those keywords
         #[prost(message, tag = "8")]
         ExceptKeywords(super::RepeatedStrings),
+        /// Match near-duplicate texts
+        #[prost(message, tag = "9")]
+        Similar(super::MatchSimilar),
     }
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MatchSimilar {
+    /// Text to find near-duplicates of
+    #[prost(string, tag = "1")]
+    pub text: ::prost::alloc::string::String,
+    /// Minimal estimated Jaccard similarity of word shingles, default 0.8
+    #[prost(float, optional, tag = "2")]
+    pub min_similarity: ::core::option::Option<f32>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct RepeatedStrings {
     #[prost(string, repeated, tag = "1")]
     pub strings: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
@@ -5576,6 +6492,33 @@ pub struct DatetimeRange {
     #[validate(custom = "crate::grpc::validate::validate_timestamp")]
     pub lte: ::core::option::Option<::prost_wkt_types::Timestamp>,
 }
+/// Integers are represented as decimal strings, to fit values outside of int64
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BigIntRange {
+    #[prost(string, optional, tag = "1")]
+    pub lt: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "2")]
+    pub gt: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "3")]
+    pub gte: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "4")]
+    pub lte: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct KeywordRange {
+    #[prost(string, optional, tag = "1")]
+    pub lt: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "2")]
+    pub gt: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "3")]
+    pub gte: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "4")]
+    pub lte: ::core::option::Option<::prost::alloc::string::String>,
+}
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -5728,6 +6671,8 @@ pub enum ReadConsistencyType {
     Majority = 1,
     /// Send requests to half + 1 nodes, return points which are present on all of them
     Quorum = 2,
+    /// Send request to one available replica of each shard, skip shards which are not available. Only supported by search
+    Partial = 3,
 }
 impl ReadConsistencyType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -5739,6 +6684,7 @@ impl ReadConsistencyType {
             ReadConsistencyType::All => "All",
             ReadConsistencyType::Majority => "Majority",
             ReadConsistencyType::Quorum => "Quorum",
+            ReadConsistencyType::Partial => "Partial",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -5747,6 +6693,7 @@ impl ReadConsistencyType {
             "All" => Some(Self::All),
             "Majority" => Some(Self::Majority),
             "Quorum" => Some(Self::Quorum),
+            "Partial" => Some(Self::Partial),
             _ => None,
         }
     }
@@ -5762,6 +6709,8 @@ pub enum FieldType {
     Text = 4,
     Bool = 5,
     Datetime = 6,
+    BigInt = 7,
+    MinHash = 8,
 }
 impl FieldType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -5777,6 +6726,8 @@ impl FieldType {
             FieldType::Text => "FieldTypeText",
             FieldType::Bool => "FieldTypeBool",
             FieldType::Datetime => "FieldTypeDatetime",
+            FieldType::BigInt => "FieldTypeBigInt",
+            FieldType::MinHash => "FieldTypeMinHash",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -5789,6 +6740,8 @@ impl FieldType {
             "FieldTypeText" => Some(Self::Text),
             "FieldTypeBool" => Some(Self::Bool),
             "FieldTypeDatetime" => Some(Self::Datetime),
+            "FieldTypeBigInt" => Some(Self::BigInt),
+            "FieldTypeMinHash" => Some(Self::MinHash),
             _ => None,
         }
     }
@@ -5881,6 +6834,39 @@ impl Fusion {
 #[derive(serde::Serialize)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
+pub enum ScoreNormalization {
+    /// Scale the scores linearly, so that the best score of the prefetch is 1 and the worst is 0
+    MinMax = 0,
+    /// Subtract the mean score of the prefetch and divide by the standard deviation
+    ZScore = 1,
+    /// Use the rank of the point in the prefetch: 1 for the first point, down to 1 / number of points
+    Rank = 2,
+}
+impl ScoreNormalization {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ScoreNormalization::MinMax => "MinMax",
+            ScoreNormalization::ZScore => "ZScore",
+            ScoreNormalization::Rank => "Rank",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "MinMax" => Some(Self::MinMax),
+            "ZScore" => Some(Self::ZScore),
+            "Rank" => Some(Self::Rank),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
 pub enum UpdateStatus {
     UnknownUpdateStatus = 0,
     /// Update is received, but not processed yet
@@ -6024,6 +7010,36 @@ pub mod points_client {
             self.inner.unary(req, path, codec).await
         }
         ///
+        /// Upsert points streamed in chunks. Each chunk is acknowledged with the offset of points processed so far.
+        /// The next chunk is only read once the previous one is acknowledged.
+        pub async fn upsert_stream(
+            &mut self,
+            request: impl tonic::IntoStreamingRequest<
+                Message = super::UpsertPointsChunk,
+            >,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::UpsertStreamResponse>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/UpsertStream",
+            );
+            let mut req = request.into_streaming_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Points", "UpsertStream"));
+            self.inner.streaming(req, path, codec).await
+        }
+        ///
         /// Delete points
         pub async fn delete(
             &mut self,
@@ -6174,6 +7190,33 @@ pub mod points_client {
             self.inner.unary(req, path, codec).await
         }
         ///
+        /// Apply JSON Merge Patch (RFC 7396) to payload of points
+        pub async fn patch_payload(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetPayloadPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/PatchPayload",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Points", "PatchPayload"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
         /// Delete specified key payload for points
         pub async fn delete_payload(
             &mut self,
@@ -6228,6 +7271,33 @@ pub mod points_client {
             self.inner.unary(req, path, codec).await
         }
         ///
+        /// Set or remove values of attribute columns for points
+        pub async fn set_attributes(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetAttributesPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/SetAttributes",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Points", "SetAttributes"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
         /// Create index for field in collection
         pub async fn create_field_index(
             &mut self,
@@ -6377,6 +7447,34 @@ pub mod points_client {
             self.inner.unary(req, path, codec).await
         }
         ///
+        /// Iterate over all or filtered points, streaming them as they are read.
+        /// Points are read in pages of `limit` points, so the whole result is never kept in memory.
+        pub async fn scroll_stream(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ScrollPoints>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::RetrievedPoint>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/ScrollStream",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Points", "ScrollStream"));
+            self.inner.server_streaming(req, path, codec).await
+        }
+        ///
         /// Look for the points which are closer to stored positive examples and at the same time further to negative examples.
         pub async fn recommend(
             &mut self,
@@ -6541,14 +7639,11 @@ pub mod points_client {
             self.inner.unary(req, path, codec).await
         }
         ///
-        /// Perform multiple update operations in one request
-        pub async fn update_batch(
+        /// Count distinct values of the payload field among the points with given filtering conditions
+        pub async fn count_distinct(
             &mut self,
-            request: impl tonic::IntoRequest<super::UpdateBatchPoints>,
-        ) -> std::result::Result<
-            tonic::Response<super::UpdateBatchResponse>,
-            tonic::Status,
-        > {
+            request: impl tonic::IntoRequest<super::CountDistinctPoints>,
+        ) -> std::result::Result<tonic::Response<super::CountDistinctResponse>, tonic::Status> {
             self.inner
                 .ready()
                 .await
@@ -6559,18 +7654,110 @@ pub mod points_client {
                     )
                 })?;
             let codec = tonic::codec::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static(
-                "/qdrant.Points/UpdateBatch",
-            );
+            let path = http::uri::PathAndQuery::from_static("/qdrant.Points/CountDistinct");
             let mut req = request.into_request();
-            req.extensions_mut().insert(GrpcMethod::new("qdrant.Points", "UpdateBatch"));
+            req.extensions_mut().insert(GrpcMethod::new("qdrant.Points", "CountDistinct"));
             self.inner.unary(req, path, codec).await
         }
         ///
-        /// Universally query points. This endpoint covers all capabilities of search, recommend, discover, filters. But also enables hybrid and multi-stage queries.
-        pub async fn query(
+        /// Build the histogram of the values of a numeric payload field from its range index
+        pub async fn histogram(
             &mut self,
-            request: impl tonic::IntoRequest<super::QueryPoints>,
+            request: impl tonic::IntoRequest<super::HistogramPoints>,
+        ) -> std::result::Result<tonic::Response<super::HistogramResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/qdrant.Points/Histogram");
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("qdrant.Points", "Histogram"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Compute min, max, average and percentiles of the values of a numeric payload field
+        pub async fn field_stats(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FieldStatsPoints>,
+        ) -> std::result::Result<tonic::Response<super::FieldStatsResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/qdrant.Points/FieldStats");
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("qdrant.Points", "FieldStats"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Perform multiple update operations in one request
+        pub async fn update_batch(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UpdateBatchPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::UpdateBatchResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/UpdateBatch",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("qdrant.Points", "UpdateBatch"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Perform multiple update operations in one request, reporting status of each operation individually
+        pub async fn bulk_update(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UpdateBatchPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::BulkUpdateResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/BulkUpdate",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("qdrant.Points", "BulkUpdate"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Universally query points. This endpoint covers all capabilities of search, recommend, discover, filters. But also enables hybrid and multi-stage queries.
+        pub async fn query(
+            &mut self,
+            request: impl tonic::IntoRequest<super::QueryPoints>,
         ) -> std::result::Result<tonic::Response<super::QueryResponse>, tonic::Status> {
             self.inner
                 .ready()
@@ -6629,6 +7816,22 @@ pub mod points_server {
             tonic::Response<super::PointsOperationResponse>,
             tonic::Status,
         >;
+        /// Server streaming response type for the UpsertStream method.
+        type UpsertStreamStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::UpsertStreamResponse, tonic::Status>,
+            >
+            + Send
+            + 'static;
+        ///
+        /// Upsert points streamed in chunks. Each chunk is acknowledged with the offset of points processed so far.
+        /// The next chunk is only read once the previous one is acknowledged.
+        async fn upsert_stream(
+            &self,
+            request: tonic::Request<tonic::Streaming<super::UpsertPointsChunk>>,
+        ) -> std::result::Result<
+            tonic::Response<Self::UpsertStreamStream>,
+            tonic::Status,
+        >;
         ///
         /// Delete points
         async fn delete(
@@ -6681,6 +7884,15 @@ pub mod points_server {
             tonic::Status,
         >;
         ///
+        /// Apply JSON Merge Patch (RFC 7396) to payload of points
+        async fn patch_payload(
+            &self,
+            request: tonic::Request<super::SetPayloadPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponse>,
+            tonic::Status,
+        >;
+        ///
         /// Delete specified key payload for points
         async fn delete_payload(
             &self,
@@ -6699,6 +7911,15 @@ pub mod points_server {
             tonic::Status,
         >;
         ///
+        /// Set or remove values of attribute columns for points
+        async fn set_attributes(
+            &self,
+            request: tonic::Request<super::SetAttributesPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponse>,
+            tonic::Status,
+        >;
+        ///
         /// Create index for field in collection
         async fn create_field_index(
             &self,
@@ -6746,6 +7967,22 @@ pub mod points_server {
             &self,
             request: tonic::Request<super::ScrollPoints>,
         ) -> std::result::Result<tonic::Response<super::ScrollResponse>, tonic::Status>;
+        /// Server streaming response type for the ScrollStream method.
+        type ScrollStreamStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::RetrievedPoint, tonic::Status>,
+            >
+            + Send
+            + 'static;
+        ///
+        /// Iterate over all or filtered points, streaming them as they are read.
+        /// Points are read in pages of `limit` points, so the whole result is never kept in memory.
+        async fn scroll_stream(
+            &self,
+            request: tonic::Request<super::ScrollPoints>,
+        ) -> std::result::Result<
+            tonic::Response<Self::ScrollStreamStream>,
+            tonic::Status,
+        >;
         ///
         /// Look for the points which are closer to stored positive examples and at the same time further to negative examples.
         async fn recommend(
@@ -6812,6 +8049,24 @@ pub mod points_server {
             request: tonic::Request<super::CountPoints>,
         ) -> std::result::Result<tonic::Response<super::CountResponse>, tonic::Status>;
         ///
+        /// Count distinct values of the payload field among the points with given filtering conditions
+        async fn count_distinct(
+            &self,
+            request: tonic::Request<super::CountDistinctPoints>,
+        ) -> std::result::Result<tonic::Response<super::CountDistinctResponse>, tonic::Status>;
+        ///
+        /// Build the histogram of the values of a numeric payload field from its range index
+        async fn histogram(
+            &self,
+            request: tonic::Request<super::HistogramPoints>,
+        ) -> std::result::Result<tonic::Response<super::HistogramResponse>, tonic::Status>;
+        ///
+        /// Compute min, max, average and percentiles of the values of a numeric payload field
+        async fn field_stats(
+            &self,
+            request: tonic::Request<super::FieldStatsPoints>,
+        ) -> std::result::Result<tonic::Response<super::FieldStatsResponse>, tonic::Status>;
+        ///
         /// Perform multiple update operations in one request
         async fn update_batch(
             &self,
@@ -6821,6 +8076,15 @@ pub mod points_server {
             tonic::Status,
         >;
         ///
+        /// Perform multiple update operations in one request, reporting status of each operation individually
+        async fn bulk_update(
+            &self,
+            request: tonic::Request<super::UpdateBatchPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::BulkUpdateResponse>,
+            tonic::Status,
+        >;
+        ///
         /// Universally query points. This endpoint covers all capabilities of search, recommend, discover, filters. But also enables hybrid and multi-stage queries.
         async fn query(
             &self,
@@ -6959,6 +8223,55 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.Points/UpsertStream" => {
+                    #[allow(non_camel_case_types)]
+                    struct UpsertStreamSvc<T: Points>(pub Arc<T>);
+                    impl<
+                        T: Points,
+                    > tonic::server::StreamingService<super::UpsertPointsChunk>
+                    for UpsertStreamSvc<T> {
+                        type Response = super::UpsertStreamResponse;
+                        type ResponseStream = T::UpsertStreamStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                tonic::Streaming<super::UpsertPointsChunk>,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::upsert_stream(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = UpsertStreamSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/qdrant.Points/Delete" => {
                     #[allow(non_camel_case_types)]
                     struct DeleteSvc<T: Points>(pub Arc<T>);
@@ -7227,6 +8540,50 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.Points/PatchPayload" => {
+                    #[allow(non_camel_case_types)]
+                    struct PatchPayloadSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::SetPayloadPoints>
+                    for PatchPayloadSvc<T> {
+                        type Response = super::PointsOperationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetPayloadPoints>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::patch_payload(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = PatchPayloadSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/qdrant.Points/DeletePayload" => {
                     #[allow(non_camel_case_types)]
                     struct DeletePayloadSvc<T: Points>(pub Arc<T>);
@@ -7319,6 +8676,52 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.Points/SetAttributes" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetAttributesSvc<T: Points>(pub Arc<T>);
+                    impl<
+                        T: Points,
+                    > tonic::server::UnaryService<super::SetAttributesPoints>
+                    for SetAttributesSvc<T> {
+                        type Response = super::PointsOperationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetAttributesPoints>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::set_attributes(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SetAttributesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/qdrant.Points/CreateFieldIndex" => {
                     #[allow(non_camel_case_types)]
                     struct CreateFieldIndexSvc<T: Points>(pub Arc<T>);
@@ -7587,23 +8990,26 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
-                "/qdrant.Points/Recommend" => {
+                "/qdrant.Points/ScrollStream" => {
                     #[allow(non_camel_case_types)]
-                    struct RecommendSvc<T: Points>(pub Arc<T>);
-                    impl<T: Points> tonic::server::UnaryService<super::RecommendPoints>
-                    for RecommendSvc<T> {
-                        type Response = super::RecommendResponse;
+                    struct ScrollStreamSvc<T: Points>(pub Arc<T>);
+                    impl<
+                        T: Points,
+                    > tonic::server::ServerStreamingService<super::ScrollPoints>
+                    for ScrollStreamSvc<T> {
+                        type Response = super::RetrievedPoint;
+                        type ResponseStream = T::ScrollStreamStream;
                         type Future = BoxFuture<
-                            tonic::Response<Self::Response>,
+                            tonic::Response<Self::ResponseStream>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::RecommendPoints>,
+                            request: tonic::Request<super::ScrollPoints>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Points>::recommend(&inner, request).await
+                                <T as Points>::scroll_stream(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -7615,7 +9021,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = RecommendSvc(inner);
+                        let method = ScrollStreamSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -7626,26 +9032,70 @@ pub mod points_server {
                                 max_decoding_message_size,
                                 max_encoding_message_size,
                             );
-                        let res = grpc.unary(method, req).await;
+                        let res = grpc.server_streaming(method, req).await;
                         Ok(res)
                     };
                     Box::pin(fut)
                 }
-                "/qdrant.Points/RecommendBatch" => {
+                "/qdrant.Points/Recommend" => {
                     #[allow(non_camel_case_types)]
-                    struct RecommendBatchSvc<T: Points>(pub Arc<T>);
-                    impl<
-                        T: Points,
-                    > tonic::server::UnaryService<super::RecommendBatchPoints>
-                    for RecommendBatchSvc<T> {
-                        type Response = super::RecommendBatchResponse;
+                    struct RecommendSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::RecommendPoints>
+                    for RecommendSvc<T> {
+                        type Response = super::RecommendResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::RecommendBatchPoints>,
+                            request: tonic::Request<super::RecommendPoints>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::recommend(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = RecommendSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/RecommendBatch" => {
+                    #[allow(non_camel_case_types)]
+                    struct RecommendBatchSvc<T: Points>(pub Arc<T>);
+                    impl<
+                        T: Points,
+                    > tonic::server::UnaryService<super::RecommendBatchPoints>
+                    for RecommendBatchSvc<T> {
+                        type Response = super::RecommendBatchResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RecommendBatchPoints>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
@@ -7857,6 +9307,138 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.Points/CountDistinct" => {
+                    #[allow(non_camel_case_types)]
+                    struct CountDistinctSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::CountDistinctPoints>
+                    for CountDistinctSvc<T> {
+                        type Response = super::CountDistinctResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CountDistinctPoints>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::count_distinct(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = CountDistinctSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/Histogram" => {
+                    #[allow(non_camel_case_types)]
+                    struct HistogramSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::HistogramPoints>
+                    for HistogramSvc<T> {
+                        type Response = super::HistogramResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::HistogramPoints>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::histogram(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = HistogramSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/FieldStats" => {
+                    #[allow(non_camel_case_types)]
+                    struct FieldStatsSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::FieldStatsPoints>
+                    for FieldStatsSvc<T> {
+                        type Response = super::FieldStatsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FieldStatsPoints>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::field_stats(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = FieldStatsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/qdrant.Points/UpdateBatch" => {
                     #[allow(non_camel_case_types)]
                     struct UpdateBatchSvc<T: Points>(pub Arc<T>);
@@ -7901,6 +9483,50 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.Points/BulkUpdate" => {
+                    #[allow(non_camel_case_types)]
+                    struct BulkUpdateSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::UpdateBatchPoints>
+                    for BulkUpdateSvc<T> {
+                        type Response = super::BulkUpdateResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UpdateBatchPoints>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::bulk_update(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = BulkUpdateSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/qdrant.Points/Query" => {
                     #[allow(non_camel_case_types)]
                     struct QuerySvc<T: Points>(pub Arc<T>);
@@ -8161,6 +9787,19 @@ pub struct ClearPayloadPointsInternal {
 #[derive(validator::Validate)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetAttributesPointsInternal {
+    #[prost(message, optional, tag = "1")]
+    #[validate]
+    pub set_attributes_points: ::core::option::Option<SetAttributesPoints>,
+    #[prost(uint32, optional, tag = "2")]
+    pub shard_id: ::core::option::Option<u32>,
+    #[prost(message, optional, tag = "3")]
+    pub clock_tag: ::core::option::Option<ClockTag>,
+}
+#[derive(serde::Serialize)]
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CreateFieldIndexCollectionInternal {
     #[prost(message, optional, tag = "1")]
     #[validate]
@@ -8211,6 +9850,8 @@ pub struct UpdateResultInternal {
     pub status: i32,
     #[prost(message, optional, tag = "3")]
     pub clock_tag: ::core::option::Option<ClockTag>,
+    #[prost(message, repeated, tag = "4")]
+    pub conflicts: ::prost::alloc::vec::Vec<WriteConflict>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -8419,55 +10060,292 @@ pub struct CountPointsInternal {
     #[prost(uint32, optional, tag = "2")]
     pub shard_id: ::core::option::Option<u32>,
 }
-/// A bare vector. No id reference here.
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CountDistinctPointsInternal {
+    #[prost(message, optional, tag = "1")]
+    #[validate]
+    pub count_distinct_points: ::core::option::Option<CountDistinctPoints>,
+    #[prost(uint32, optional, tag = "2")]
+    pub shard_id: ::core::option::Option<u32>,
+}
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct RawVector {
-    #[prost(oneof = "raw_vector::Variant", tags = "1, 2, 3")]
-    pub variant: ::core::option::Option<raw_vector::Variant>,
+pub struct ExactDistinctValuesInternal {
+    /// Hashes of all distinct values
+    #[prost(uint64, repeated, tag = "1")]
+    pub hashes: ::prost::alloc::vec::Vec<u64>,
 }
-/// Nested message and enum types in `RawVector`.
-pub mod raw_vector {
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DistinctValuesInternal {
+    #[prost(oneof = "distinct_values_internal::Values", tags = "1, 2")]
+    pub values: ::core::option::Option<distinct_values_internal::Values>,
+}
+/// Nested message and enum types in `DistinctValuesInternal`.
+pub mod distinct_values_internal {
     #[derive(serde::Serialize)]
     #[allow(clippy::derive_partial_eq_without_eq)]
     #[derive(Clone, PartialEq, ::prost::Oneof)]
-    pub enum Variant {
+    pub enum Values {
         #[prost(message, tag = "1")]
-        Dense(super::DenseVector),
-        #[prost(message, tag = "2")]
-        Sparse(super::SparseVector),
-        #[prost(message, tag = "3")]
-        MultiDense(super::MultiDenseVector),
+        Exact(super::ExactDistinctValuesInternal),
+        /// Registers of the HyperLogLog sketch
+        #[prost(bytes = "vec", tag = "2")]
+        Estimated(::prost::alloc::vec::Vec<u8>),
     }
 }
-/// Query variants for raw vectors (ids have been substituted with vectors)
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct RawQuery {
-    #[prost(oneof = "raw_query::Variant", tags = "1, 2, 3, 4")]
-    pub variant: ::core::option::Option<raw_query::Variant>,
+pub struct DistinctValuesResponseInternal {
+    #[prost(message, optional, tag = "1")]
+    pub result: ::core::option::Option<DistinctValuesInternal>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
 }
-/// Nested message and enum types in `RawQuery`.
-pub mod raw_query {
-    #[derive(serde::Serialize)]
-    #[allow(clippy::derive_partial_eq_without_eq)]
-    #[derive(Clone, PartialEq, ::prost::Message)]
-    pub struct Recommend {
-        #[prost(message, repeated, tag = "1")]
-        pub positives: ::prost::alloc::vec::Vec<super::RawVector>,
-        #[prost(message, repeated, tag = "2")]
-        pub negatives: ::prost::alloc::vec::Vec<super::RawVector>,
-    }
-    #[derive(serde::Serialize)]
-    #[allow(clippy::derive_partial_eq_without_eq)]
-    #[derive(Clone, PartialEq, ::prost::Message)]
-    pub struct RawContextPair {
-        #[prost(message, optional, tag = "1")]
-        pub positive: ::core::option::Option<super::RawVector>,
-        #[prost(message, optional, tag = "2")]
-        pub negative: ::core::option::Option<super::RawVector>,
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HistogramPointsInternal {
+    #[prost(message, optional, tag = "1")]
+    #[validate]
+    pub histogram_points: ::core::option::Option<HistogramPoints>,
+    #[prost(uint32, optional, tag = "2")]
+    pub shard_id: ::core::option::Option<u32>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ValueRangeInternal {
+    #[prost(double, tag = "1")]
+    pub from: f64,
+    #[prost(double, tag = "2")]
+    pub to: f64,
+    /// Number of values between `from` and `to`, or equal to `from` if `from == to`
+    #[prost(uint64, tag = "3")]
+    pub count: u64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ValueDistributionInternal {
+    #[prost(message, repeated, tag = "1")]
+    pub ranges: ::prost::alloc::vec::Vec<ValueRangeInternal>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ValueDistributionResponseInternal {
+    #[prost(message, optional, tag = "1")]
+    pub result: ::core::option::Option<ValueDistributionInternal>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FieldStatsPointsInternal {
+    #[prost(message, optional, tag = "1")]
+    #[validate]
+    pub field_stats_points: ::core::option::Option<FieldStatsPoints>,
+    #[prost(uint32, optional, tag = "2")]
+    pub shard_id: ::core::option::Option<u32>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct NumericStatsInternal {
+    #[prost(uint64, tag = "1")]
+    pub count: u64,
+    #[prost(double, tag = "2")]
+    pub sum: f64,
+    #[prost(double, tag = "3")]
+    pub min: f64,
+    #[prost(double, tag = "4")]
+    pub max: f64,
+    /// Compressed distribution of values, for percentiles
+    #[prost(message, optional, tag = "5")]
+    pub distribution: ::core::option::Option<ValueDistributionInternal>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct NumericStatsResponseInternal {
+    #[prost(message, optional, tag = "1")]
+    pub result: ::core::option::Option<NumericStatsInternal>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PointDigestsInternal {
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    #[prost(uint32, optional, tag = "2")]
+    pub shard_id: ::core::option::Option<u32>,
+    /// Number of buckets, point ids are hashed into
+    #[prost(uint64, tag = "3")]
+    pub buckets: u64,
+    /// Buckets, versions of whose points are also returned
+    #[prost(uint64, repeated, tag = "4")]
+    pub list_buckets: ::prost::alloc::vec::Vec<u64>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BucketDigestInternal {
+    /// Number of points in the bucket
+    #[prost(uint64, tag = "1")]
+    pub count: u64,
+    /// XOR of the hashes of the ids and versions of the points
+    #[prost(uint64, tag = "2")]
+    pub digest: u64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PointVersionInternal {
+    #[prost(message, optional, tag = "1")]
+    pub id: ::core::option::Option<PointId>,
+    #[prost(uint64, tag = "2")]
+    pub version: u64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PointDigestsResponseInternal {
+    #[prost(message, repeated, tag = "1")]
+    pub buckets: ::prost::alloc::vec::Vec<BucketDigestInternal>,
+    /// Versions of the points in the listed buckets
+    #[prost(message, repeated, tag = "2")]
+    pub points: ::prost::alloc::vec::Vec<PointVersionInternal>,
+    /// Time spent to process
+    #[prost(double, tag = "3")]
+    pub time: f64,
+}
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PointHistoryInternal {
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    #[prost(uint32, optional, tag = "2")]
+    pub shard_id: ::core::option::Option<u32>,
+    #[prost(message, optional, tag = "3")]
+    pub id: ::core::option::Option<PointId>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PointHistoryResponseInternal {
+    /// CBOR encoded versions of the point, oldest first. Absent if the collection doesn't retain point history
+    #[prost(bytes = "vec", optional, tag = "1")]
+    pub versions: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TrashedPointsInternal {
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    #[prost(uint32, optional, tag = "2")]
+    pub shard_id: ::core::option::Option<u32>,
+    #[prost(message, repeated, tag = "3")]
+    pub ids: ::prost::alloc::vec::Vec<PointId>,
+}
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ScanTrashInternal {
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    #[prost(uint32, optional, tag = "2")]
+    pub shard_id: ::core::option::Option<u32>,
+    /// Lowest id to return, inclusive
+    #[prost(message, optional, tag = "3")]
+    pub from: ::core::option::Option<PointId>,
+    /// Highest id to return, exclusive
+    #[prost(message, optional, tag = "4")]
+    pub to: ::core::option::Option<PointId>,
+    #[prost(message, optional, tag = "5")]
+    #[validate]
+    pub filter: ::core::option::Option<Filter>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TrashResponseInternal {
+    /// CBOR encoded deleted points. Absent if the collection doesn't keep deleted points
+    #[prost(bytes = "vec", optional, tag = "1")]
+    pub points: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+/// A bare vector. No id reference here.
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RawVector {
+    #[prost(oneof = "raw_vector::Variant", tags = "1, 2, 3")]
+    pub variant: ::core::option::Option<raw_vector::Variant>,
+}
+/// Nested message and enum types in `RawVector`.
+pub mod raw_vector {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Variant {
+        #[prost(message, tag = "1")]
+        Dense(super::DenseVector),
+        #[prost(message, tag = "2")]
+        Sparse(super::SparseVector),
+        #[prost(message, tag = "3")]
+        MultiDense(super::MultiDenseVector),
+    }
+}
+/// Query variants for raw vectors (ids have been substituted with vectors)
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RawQuery {
+    #[prost(oneof = "raw_query::Variant", tags = "1, 2, 3, 4")]
+    pub variant: ::core::option::Option<raw_query::Variant>,
+}
+/// Nested message and enum types in `RawQuery`.
+pub mod raw_query {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Recommend {
+        #[prost(message, repeated, tag = "1")]
+        pub positives: ::prost::alloc::vec::Vec<super::RawVector>,
+        #[prost(message, repeated, tag = "2")]
+        pub negatives: ::prost::alloc::vec::Vec<super::RawVector>,
+    }
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct RawContextPair {
+        #[prost(message, optional, tag = "1")]
+        pub positive: ::core::option::Option<super::RawVector>,
+        #[prost(message, optional, tag = "2")]
+        pub negative: ::core::option::Option<super::RawVector>,
     }
     #[derive(serde::Serialize)]
     #[allow(clippy::derive_partial_eq_without_eq)]
@@ -8534,7 +10412,7 @@ pub mod query_shard_points {
     #[allow(clippy::derive_partial_eq_without_eq)]
     #[derive(Clone, PartialEq, ::prost::Message)]
     pub struct Query {
-        #[prost(oneof = "query::Score", tags = "1, 2, 3")]
+        #[prost(oneof = "query::Score", tags = "1, 2, 3, 4, 5")]
         pub score: ::core::option::Option<query::Score>,
     }
     /// Nested message and enum types in `Query`.
@@ -8552,6 +10430,12 @@ pub mod query_shard_points {
             /// Order by a field
             #[prost(message, tag = "3")]
             OrderBy(super::super::OrderBy),
+            /// Weighted sum of the scores of the prefetches
+            #[prost(message, tag = "4")]
+            WeightedFusion(super::super::WeightedFusion),
+            /// Formula with substituted parameters
+            #[prost(message, tag = "5")]
+            Formula(super::super::Formula),
         }
     }
     #[derive(serde::Serialize)]
@@ -8613,6 +10497,12 @@ pub struct QueryBatchResponseInternal {
     /// Time spent to process
     #[prost(double, tag = "2")]
     pub time: f64,
+    /// Hardware resources spent on the request, if reporting is enabled
+    #[prost(message, optional, tag = "3")]
+    pub usage: ::core::option::Option<HardwareUsage>,
+    /// Whether the request reached its timeout, results might be incomplete then
+    #[prost(bool, optional, tag = "4")]
+    pub timed_out: ::core::option::Option<bool>,
 }
 /// Generated client implementations.
 pub mod points_internal_client {
@@ -8874,6 +10764,31 @@ pub mod points_internal_client {
                 .insert(GrpcMethod::new("qdrant.PointsInternal", "OverwritePayload"));
             self.inner.unary(req, path, codec).await
         }
+        pub async fn patch_payload(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetPayloadPointsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponseInternal>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/PatchPayload",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "PatchPayload"));
+            self.inner.unary(req, path, codec).await
+        }
         pub async fn delete_payload(
             &mut self,
             request: impl tonic::IntoRequest<super::DeletePayloadPointsInternal>,
@@ -8924,6 +10839,31 @@ pub mod points_internal_client {
                 .insert(GrpcMethod::new("qdrant.PointsInternal", "ClearPayload"));
             self.inner.unary(req, path, codec).await
         }
+        pub async fn set_attributes(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetAttributesPointsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponseInternal>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/SetAttributes",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "SetAttributes"));
+            self.inner.unary(req, path, codec).await
+        }
         pub async fn create_field_index(
             &mut self,
             request: impl tonic::IntoRequest<super::CreateFieldIndexCollectionInternal>,
@@ -9043,13 +10983,10 @@ pub mod points_internal_client {
                 .insert(GrpcMethod::new("qdrant.PointsInternal", "Count"));
             self.inner.unary(req, path, codec).await
         }
-        pub async fn recommend(
+        pub async fn count_distinct(
             &mut self,
-            request: impl tonic::IntoRequest<super::RecommendPointsInternal>,
-        ) -> std::result::Result<
-            tonic::Response<super::RecommendResponse>,
-            tonic::Status,
-        > {
+            request: impl tonic::IntoRequest<super::CountDistinctPointsInternal>,
+        ) -> std::result::Result<tonic::Response<super::DistinctValuesResponseInternal>, tonic::Status> {
             self.inner
                 .ready()
                 .await
@@ -9061,17 +10998,17 @@ pub mod points_internal_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/qdrant.PointsInternal/Recommend",
+                "/qdrant.PointsInternal/CountDistinct",
             );
             let mut req = request.into_request();
             req.extensions_mut()
-                .insert(GrpcMethod::new("qdrant.PointsInternal", "Recommend"));
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "CountDistinct"));
             self.inner.unary(req, path, codec).await
         }
-        pub async fn get(
+        pub async fn histogram(
             &mut self,
-            request: impl tonic::IntoRequest<super::GetPointsInternal>,
-        ) -> std::result::Result<tonic::Response<super::GetResponse>, tonic::Status> {
+            request: impl tonic::IntoRequest<super::HistogramPointsInternal>,
+        ) -> std::result::Result<tonic::Response<super::ValueDistributionResponseInternal>, tonic::Status> {
             self.inner
                 .ready()
                 .await
@@ -9083,19 +11020,17 @@ pub mod points_internal_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/qdrant.PointsInternal/Get",
+                "/qdrant.PointsInternal/Histogram",
             );
             let mut req = request.into_request();
-            req.extensions_mut().insert(GrpcMethod::new("qdrant.PointsInternal", "Get"));
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "Histogram"));
             self.inner.unary(req, path, codec).await
         }
-        pub async fn query_batch(
+        pub async fn field_stats(
             &mut self,
-            request: impl tonic::IntoRequest<super::QueryBatchPointsInternal>,
-        ) -> std::result::Result<
-            tonic::Response<super::QueryBatchResponseInternal>,
-            tonic::Status,
-        > {
+            request: impl tonic::IntoRequest<super::FieldStatsPointsInternal>,
+        ) -> std::result::Result<tonic::Response<super::NumericStatsResponseInternal>, tonic::Status> {
             self.inner
                 .ready()
                 .await
@@ -9107,14 +11042,173 @@ pub mod points_internal_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/qdrant.PointsInternal/QueryBatch",
+                "/qdrant.PointsInternal/FieldStats",
             );
             let mut req = request.into_request();
             req.extensions_mut()
-                .insert(GrpcMethod::new("qdrant.PointsInternal", "QueryBatch"));
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "FieldStats"));
             self.inner.unary(req, path, codec).await
         }
-    }
+        pub async fn point_digests(
+            &mut self,
+            request: impl tonic::IntoRequest<super::PointDigestsInternal>,
+        ) -> std::result::Result<tonic::Response<super::PointDigestsResponseInternal>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/PointDigests",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "PointDigests"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn point_history(
+            &mut self,
+            request: impl tonic::IntoRequest<super::PointHistoryInternal>,
+        ) -> std::result::Result<tonic::Response<super::PointHistoryResponseInternal>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/PointHistory",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "PointHistory"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn trashed_points(
+            &mut self,
+            request: impl tonic::IntoRequest<super::TrashedPointsInternal>,
+        ) -> std::result::Result<tonic::Response<super::TrashResponseInternal>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/TrashedPoints",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "TrashedPoints"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn scan_trash(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ScanTrashInternal>,
+        ) -> std::result::Result<tonic::Response<super::TrashResponseInternal>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/ScanTrash",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "ScanTrash"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn recommend(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RecommendPointsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::RecommendResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/Recommend",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "Recommend"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn get(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetPointsInternal>,
+        ) -> std::result::Result<tonic::Response<super::GetResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/Get",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("qdrant.PointsInternal", "Get"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn query_batch(
+            &mut self,
+            request: impl tonic::IntoRequest<super::QueryBatchPointsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::QueryBatchResponseInternal>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/QueryBatch",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "QueryBatch"));
+            self.inner.unary(req, path, codec).await
+        }
+    }
 }
 /// Generated server implementations.
 pub mod points_internal_server {
@@ -9172,6 +11266,13 @@ pub mod points_internal_server {
             tonic::Response<super::PointsOperationResponseInternal>,
             tonic::Status,
         >;
+        async fn patch_payload(
+            &self,
+            request: tonic::Request<super::SetPayloadPointsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponseInternal>,
+            tonic::Status,
+        >;
         async fn delete_payload(
             &self,
             request: tonic::Request<super::DeletePayloadPointsInternal>,
@@ -9186,6 +11287,13 @@ pub mod points_internal_server {
             tonic::Response<super::PointsOperationResponseInternal>,
             tonic::Status,
         >;
+        async fn set_attributes(
+            &self,
+            request: tonic::Request<super::SetAttributesPointsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponseInternal>,
+            tonic::Status,
+        >;
         async fn create_field_index(
             &self,
             request: tonic::Request<super::CreateFieldIndexCollectionInternal>,
@@ -9215,6 +11323,34 @@ pub mod points_internal_server {
             &self,
             request: tonic::Request<super::CountPointsInternal>,
         ) -> std::result::Result<tonic::Response<super::CountResponse>, tonic::Status>;
+        async fn count_distinct(
+            &self,
+            request: tonic::Request<super::CountDistinctPointsInternal>,
+        ) -> std::result::Result<tonic::Response<super::DistinctValuesResponseInternal>, tonic::Status>;
+        async fn histogram(
+            &self,
+            request: tonic::Request<super::HistogramPointsInternal>,
+        ) -> std::result::Result<tonic::Response<super::ValueDistributionResponseInternal>, tonic::Status>;
+        async fn field_stats(
+            &self,
+            request: tonic::Request<super::FieldStatsPointsInternal>,
+        ) -> std::result::Result<tonic::Response<super::NumericStatsResponseInternal>, tonic::Status>;
+        async fn point_digests(
+            &self,
+            request: tonic::Request<super::PointDigestsInternal>,
+        ) -> std::result::Result<tonic::Response<super::PointDigestsResponseInternal>, tonic::Status>;
+        async fn point_history(
+            &self,
+            request: tonic::Request<super::PointHistoryInternal>,
+        ) -> std::result::Result<tonic::Response<super::PointHistoryResponseInternal>, tonic::Status>;
+        async fn trashed_points(
+            &self,
+            request: tonic::Request<super::TrashedPointsInternal>,
+        ) -> std::result::Result<tonic::Response<super::TrashResponseInternal>, tonic::Status>;
+        async fn scan_trash(
+            &self,
+            request: tonic::Request<super::ScanTrashInternal>,
+        ) -> std::result::Result<tonic::Response<super::TrashResponseInternal>, tonic::Status>;
         async fn recommend(
             &self,
             request: tonic::Request<super::RecommendPointsInternal>,
@@ -9636,6 +11772,53 @@ pub mod points_internal_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.PointsInternal/PatchPayload" => {
+                    #[allow(non_camel_case_types)]
+                    struct PatchPayloadSvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::SetPayloadPointsInternal>
+                    for PatchPayloadSvc<T> {
+                        type Response = super::PointsOperationResponseInternal;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetPayloadPointsInternal>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PointsInternal>::patch_payload(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = PatchPayloadSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/qdrant.PointsInternal/DeletePayload" => {
                     #[allow(non_camel_case_types)]
                     struct DeletePayloadSvc<T: PointsInternal>(pub Arc<T>);
@@ -9728,6 +11911,52 @@ pub mod points_internal_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.PointsInternal/SetAttributes" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetAttributesSvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::SetAttributesPointsInternal>
+                    for SetAttributesSvc<T> {
+                        type Response = super::PointsOperationResponseInternal;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetAttributesPointsInternal>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PointsInternal>::set_attributes(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SetAttributesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/qdrant.PointsInternal/CreateFieldIndex" => {
                     #[allow(non_camel_case_types)]
                     struct CreateFieldIndexSvc<T: PointsInternal>(pub Arc<T>);
@@ -9967,6 +12196,328 @@ pub mod points_internal_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.PointsInternal/CountDistinct" => {
+                    #[allow(non_camel_case_types)]
+                    struct CountDistinctSvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::CountDistinctPointsInternal>
+                    for CountDistinctSvc<T> {
+                        type Response = super::DistinctValuesResponseInternal;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CountDistinctPointsInternal>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PointsInternal>::count_distinct(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = CountDistinctSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.PointsInternal/Histogram" => {
+                    #[allow(non_camel_case_types)]
+                    struct HistogramSvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::HistogramPointsInternal>
+                    for HistogramSvc<T> {
+                        type Response = super::ValueDistributionResponseInternal;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::HistogramPointsInternal>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PointsInternal>::histogram(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = HistogramSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.PointsInternal/FieldStats" => {
+                    #[allow(non_camel_case_types)]
+                    struct FieldStatsSvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::FieldStatsPointsInternal>
+                    for FieldStatsSvc<T> {
+                        type Response = super::NumericStatsResponseInternal;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FieldStatsPointsInternal>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PointsInternal>::field_stats(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = FieldStatsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.PointsInternal/PointDigests" => {
+                    #[allow(non_camel_case_types)]
+                    struct PointDigestsSvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::PointDigestsInternal>
+                    for PointDigestsSvc<T> {
+                        type Response = super::PointDigestsResponseInternal;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::PointDigestsInternal>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PointsInternal>::point_digests(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = PointDigestsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.PointsInternal/PointHistory" => {
+                    #[allow(non_camel_case_types)]
+                    struct PointHistorySvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::PointHistoryInternal>
+                    for PointHistorySvc<T> {
+                        type Response = super::PointHistoryResponseInternal;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::PointHistoryInternal>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PointsInternal>::point_history(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = PointHistorySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.PointsInternal/TrashedPoints" => {
+                    #[allow(non_camel_case_types)]
+                    struct TrashedPointsSvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::TrashedPointsInternal>
+                    for TrashedPointsSvc<T> {
+                        type Response = super::TrashResponseInternal;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::TrashedPointsInternal>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PointsInternal>::trashed_points(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = TrashedPointsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.PointsInternal/ScanTrash" => {
+                    #[allow(non_camel_case_types)]
+                    struct ScanTrashSvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::ScanTrashInternal>
+                    for ScanTrashSvc<T> {
+                        type Response = super::TrashResponseInternal;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ScanTrashInternal>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PointsInternal>::scan_trash(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ScanTrashSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/qdrant.PointsInternal/Recommend" => {
                     #[allow(non_camel_case_types)]
                     struct RecommendSvc<T: PointsInternal>(pub Arc<T>);
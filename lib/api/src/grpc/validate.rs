@@ -187,6 +187,8 @@ impl Validate for grpc::condition::ConditionOneOf {
             ConditionOneOf::Filter(filter) => filter.validate(),
             ConditionOneOf::IsEmpty(_) => Ok(()),
             ConditionOneOf::HasId(_) => Ok(()),
+            ConditionOneOf::HasVector(_) => Ok(()),
+            ConditionOneOf::Attribute(_) => Ok(()),
             ConditionOneOf::IsNull(_) => Ok(()),
         }
     }
@@ -199,6 +201,8 @@ impl Validate for grpc::FieldCondition {
             r#match,
             range,
             datetime_range,
+            keyword_range,
+            big_int_range,
             geo_bounding_box,
             geo_radius,
             geo_polygon,
@@ -208,6 +212,8 @@ impl Validate for grpc::FieldCondition {
         let all_fields_none = r#match.is_none()
             && range.is_none()
             && datetime_range.is_none()
+            && keyword_range.is_none()
+            && big_int_range.is_none()
             && geo_bounding_box.is_none()
             && geo_radius.is_none()
             && geo_polygon.is_none()
@@ -1,10 +1,29 @@
+use segment::common::weighted_sum_fusion;
 use segment::data_types::order_by::OrderBy;
 use segment::data_types::vectors::DEFAULT_VECTOR_NAME;
+use segment::utils::payload_size_limit::truncate_payloads;
 
-use super::schema::{BatchVectorStruct, ScoredPoint, Vector, VectorStruct};
+use super::schema::{
+    BatchVectorStruct, HardwareUsage, ScoreNormalization, ScoredPoint, Vector, VectorStruct,
+};
 use super::{NearestQuery, OrderByInterface, Query, QueryInterface};
 use crate::rest::{DenseVector, NamedVectorStruct};
 
+impl From<common::hardware_counter::HardwareUsage> for HardwareUsage {
+    fn from(usage: common::hardware_counter::HardwareUsage) -> Self {
+        let common::hardware_counter::HardwareUsage {
+            cpu,
+            payload_io_read,
+            vector_io_read,
+        } = usage;
+        Self {
+            cpu,
+            payload_io_read,
+            vector_io_read,
+        }
+    }
+}
+
 impl From<segment::data_types::vectors::Vector> for Vector {
     fn from(value: segment::data_types::vectors::Vector) -> Self {
         match value {
@@ -29,6 +48,9 @@ impl From<Vector> for segment::data_types::vectors::Vector {
                     segment::data_types::vectors::MultiDenseVectorInternal::new_unchecked(vector),
                 )
             }
+            // Inference objects are replaced with vectors by the API before conversion.
+            // Unresolved ones end up as empty vectors, which are rejected by dimension checks.
+            Vector::Inference(_) => segment::data_types::vectors::Vector::Dense(vec![]),
         }
     }
 }
@@ -65,6 +87,9 @@ impl From<VectorStruct> for segment::data_types::vectors::VectorStructInternal {
                     vectors.into_iter().map(|(k, v)| (k, v.into())).collect(),
                 )
             }
+            VectorStruct::Inference(_) => {
+                segment::data_types::vectors::VectorStructInternal::Single(vec![])
+            }
         }
     }
 }
@@ -96,6 +121,12 @@ impl<'a> From<VectorStruct> for segment::data_types::named_vectors::NamedVectors
                 }
                 named_vector
             }
+            VectorStruct::Inference(_) => {
+                segment::data_types::named_vectors::NamedVectors::from_pairs([(
+                    DEFAULT_VECTOR_NAME.to_string(),
+                    vec![],
+                )])
+            }
         }
     }
 }
@@ -164,10 +195,30 @@ impl From<segment::types::ScoredPoint> for ScoredPoint {
             vector: value.vector.map(From::from),
             shard_key: value.shard_key,
             order_value: value.order_value.map(From::from),
+            truncated: false,
         }
     }
 }
 
+/// Convert scored points, truncating their payloads to `payload_size_limit` bytes in total
+pub fn scored_points_with_payload_limit(
+    mut points: Vec<segment::types::ScoredPoint>,
+    payload_size_limit: Option<usize>,
+) -> Vec<ScoredPoint> {
+    let Some(limit) = payload_size_limit else {
+        return points.into_iter().map(ScoredPoint::from).collect();
+    };
+    let truncated = truncate_payloads(&mut points, limit);
+    points
+        .into_iter()
+        .zip(truncated)
+        .map(|(point, truncated)| ScoredPoint {
+            truncated,
+            ..ScoredPoint::from(point)
+        })
+        .collect()
+}
+
 impl From<ScoredPoint> for segment::types::ScoredPoint {
     fn from(value: ScoredPoint) -> Self {
         segment::types::ScoredPoint {
@@ -237,6 +288,10 @@ impl From<OrderByInterface> for OrderBy {
                 key,
                 direction: None,
                 start_from: None,
+                geo: None,
+                then_by: None,
+                start_after_id: None,
+                start_after_then_by: Vec::new(),
             },
             OrderByInterface::Struct(order_by) => order_by,
         }
@@ -251,3 +306,13 @@ impl From<QueryInterface> for Query {
         }
     }
 }
+
+impl From<ScoreNormalization> for weighted_sum_fusion::ScoreNormalization {
+    fn from(value: ScoreNormalization) -> Self {
+        match value {
+            ScoreNormalization::MinMax => weighted_sum_fusion::ScoreNormalization::MinMax,
+            ScoreNormalization::ZScore => weighted_sum_fusion::ScoreNormalization::ZScore,
+            ScoreNormalization::Rank => weighted_sum_fusion::ScoreNormalization::Rank,
+        }
+    }
+}
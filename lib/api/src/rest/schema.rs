@@ -22,6 +22,44 @@ pub enum Vector {
     Dense(DenseVector),
     Sparse(sparse::common::sparse_vector::SparseVector),
     MultiDense(MultiDenseVector),
+    Inference(InferenceObject),
+}
+
+/// Text, which is converted into a vector by the configured inference service
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize, JsonSchema, Validate)]
+pub struct Document {
+    /// Text of the document
+    pub text: String,
+    /// Name of the model to embed the text with
+    #[validate(length(min = 1))]
+    pub model: String,
+}
+
+/// Image, which is converted into a vector by the configured inference service
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize, JsonSchema, Validate)]
+pub struct Image {
+    /// URL or base64 encoded content of the image
+    pub image: String,
+    /// Name of the model to embed the image with
+    #[validate(length(min = 1))]
+    pub model: String,
+}
+
+/// Raw object, which is converted into a vector by the server before indexing or searching
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize, JsonSchema)]
+#[serde(untagged)]
+pub enum InferenceObject {
+    Document(Document),
+    Image(Image),
+}
+
+impl InferenceObject {
+    pub fn model(&self) -> &str {
+        match self {
+            InferenceObject::Document(document) => &document.model,
+            InferenceObject::Image(image) => &image.model,
+        }
+    }
 }
 
 /// Full vector data per point separator with single and multiple vector modes
@@ -31,6 +69,7 @@ pub enum VectorStruct {
     Single(DenseVector),
     MultiDense(MultiDenseVector),
     Named(HashMap<String, Vector>),
+    Inference(InferenceObject),
 }
 
 impl VectorStruct {
@@ -43,7 +82,9 @@ impl VectorStruct {
                 Vector::Dense(vector) => vector.is_empty(),
                 Vector::Sparse(vector) => vector.indices.is_empty(),
                 Vector::MultiDense(vector) => vector.is_empty(),
+                Vector::Inference(_) => false,
             }),
+            VectorStruct::Inference(_) => false,
         }
     }
 }
@@ -83,6 +124,9 @@ pub struct ScoredPoint {
     /// Order-by value
     #[serde(skip_serializing_if = "Option::is_none")]
     pub order_value: Option<segment::data_types::order_by::OrderValue>,
+    /// If true, some payload fields were removed to fit into the payload size limit of the request
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
 }
 
 /// Point data
@@ -146,6 +190,7 @@ pub enum VectorInput {
     SparseVector(SparseVector),
     MultiDenseVector(MultiDenseVector),
     Id(segment::types::PointIdType),
+    Inference(InferenceObject),
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
@@ -167,7 +212,7 @@ pub struct QueryRequestInternal {
     #[validate]
     pub filter: Option<Filter>,
 
-    /// Search params for when there is no prefetch
+    /// Search params. With prefetches, they are used to re-score the prefetched points by vector query
     pub params: Option<SearchParams>,
 
     /// Return points with scores better than this threshold.
@@ -189,6 +234,11 @@ pub struct QueryRequestInternal {
     /// Note: the other collection vectors should have the same vector size as the 'using' vector in the current collection
     #[serde(default)]
     pub lookup_from: Option<LookupLocation>,
+
+    /// Max total size of payloads in the response, in bytes. Payload fields, which don't fit, are
+    /// removed, and the points are marked as `truncated`. Better points keep more of their payload.
+    #[validate(range(min = 1))]
+    pub payload_size_limit: Option<usize>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
@@ -210,6 +260,38 @@ pub struct QueryResponse {
     pub points: Vec<ScoredPoint>,
 }
 
+/// Search result together with the description of how it was executed
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct SearchExplainResponse {
+    pub points: Vec<ScoredPoint>,
+    /// Search plans of each searched shard
+    pub shards: Vec<segment::data_types::search_plan::ShardSearchPlan>,
+}
+
+/// Status of a shard, requested by a read with `partial` consistency
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct ShardReadStatus {
+    pub shard_id: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKey>,
+    /// Whether the shard responded. Points of the shards, which didn't, are missing in the result.
+    pub ok: bool,
+    /// Reason, why the shard didn't respond
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Hardware resources, spent on processing a request
+#[derive(Debug, Clone, Copy, Default, Serialize, JsonSchema)]
+pub struct HardwareUsage {
+    /// Number of vectors, scored against the query
+    pub cpu: usize,
+    /// Number of payload bytes read
+    pub payload_io_read: usize,
+    /// Number of vector bytes read
+    pub vector_io_read: usize,
+}
+
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(untagged)]
 pub enum QueryInterface {
@@ -237,6 +319,12 @@ pub enum Query {
 
     /// Fuse the results of multiple prefetches.
     Fusion(FusionQuery),
+
+    /// Fuse the results of multiple prefetches by weighted sum of their scores.
+    WeightedFusion(WeightedFusionQuery),
+
+    /// Score the results of the prefetches by a formula.
+    Formula(FormulaQuery),
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -275,6 +363,172 @@ pub struct FusionQuery {
     pub fusion: Fusion,
 }
 
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct WeightedFusionQuery {
+    pub weighted_fusion: WeightedFusion,
+}
+
+/// Fuse the results of the prefetches by weighted sum of their scores,
+/// e.g. to search by several named vectors at once
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
+pub struct WeightedFusion {
+    /// Weight of each prefetch, in the same order as the prefetches.
+    /// Scores of the prefetches are multiplied by the weights and summed.
+    #[validate(length(min = 1))]
+    pub weights: Vec<f32>,
+
+    /// Normalization of the scores of each prefetch, applied before the weights.
+    /// Allows to combine prefetches with different score ranges, e.g. dense and sparse vectors.
+    /// If not set, the scores are summed as-is.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub normalization: Option<ScoreNormalization>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ScoreNormalization {
+    /// Scale the scores linearly, so that the best score of the prefetch is 1 and the worst is 0
+    MinMax,
+    /// Subtract the mean score of the prefetch and divide by the standard deviation
+    ZScore,
+    /// Use the rank of the point in the prefetch: 1 for the first point, down to 1 / number of points
+    Rank,
+}
+
+/// Score the results of the prefetches by a formula over their scores and payload values
+///
+/// The same formula can be reused with different values of `params`, e.g. current time or
+/// location of the user.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct FormulaQuery {
+    pub formula: Expression,
+
+    /// Values of the query-time parameters, referenced by `{"param": "<name>"}` in the formula.
+    /// Parameters can be numbers, geo points or datetimes in RFC 3339 format.
+    #[serde(default)]
+    pub params: HashMap<String, serde_json::Value>,
+
+    /// Values of the payload variables, used for points which don't have them
+    #[serde(default)]
+    pub defaults: HashMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum Expression {
+    Constant(f32),
+    /// Payload key, or score of a prefetch: `$score` for the first one, `$score[<index>]` for others
+    Variable(String),
+    Param(ParamExpression),
+    Mult(MultExpression),
+    Sum(SumExpression),
+    Neg(NegExpression),
+    Div(DivExpression),
+    GeoDistance(GeoDistanceExpression),
+    DatetimeDecay(DatetimeDecayExpression),
+    Attribute(AttributeExpression),
+}
+
+/// Value of a query-time parameter
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ParamExpression {
+    pub param: String,
+}
+
+/// Value in the attribute column with this name
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct AttributeExpression {
+    pub attribute: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct MultExpression {
+    pub mult: Vec<Expression>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct SumExpression {
+    pub sum: Vec<Expression>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct NegExpression {
+    pub neg: Box<Expression>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DivExpression {
+    pub div: DivParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DivParams {
+    pub left: Box<Expression>,
+    pub right: Box<Expression>,
+    /// Result of the division by zero. Default is 0.
+    pub by_zero_default: Option<f32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct GeoDistanceExpression {
+    pub geo_distance: GeoDistanceParams,
+}
+
+/// Haversine distance in meters between the origin and a geo point in the payload
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct GeoDistanceParams {
+    /// Geo point, or the parameter with it
+    pub origin: GeoOrigin,
+    /// Payload key with the geo point
+    pub to: JsonPath,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum GeoOrigin {
+    Point(segment::types::GeoPoint),
+    Param(ParamExpression),
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DatetimeDecayExpression {
+    pub datetime_decay: DatetimeDecayParams,
+}
+
+/// Exponential decay by the distance between the target and a datetime in the payload.
+/// Points with the target datetime get 1, points `scale` seconds away from it get `midpoint`.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DatetimeDecayParams {
+    /// Payload key with the datetime
+    pub key: JsonPath,
+    /// Datetime in RFC 3339 format, or the parameter with it
+    pub target: DatetimeTarget,
+    /// Distance from the target in seconds, at which the decay reaches `midpoint`
+    pub scale: f32,
+    /// Value of the decay at `scale` distance. Default is 0.5.
+    pub midpoint: Option<f32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum DatetimeTarget {
+    Datetime(String),
+    Param(ParamExpression),
+}
+
 #[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
 pub struct Prefetch {
     /// Sub-requests to perform first. If present, the query will be performed on the results of the prefetches.
@@ -294,7 +548,7 @@ pub struct Prefetch {
     #[validate]
     pub filter: Option<Filter>,
 
-    /// Search params for when there is no prefetch
+    /// Search params. With prefetches, they are used to re-score the prefetched points by vector query
     pub params: Option<SearchParams>,
 
     /// Return points with scores better than this threshold.
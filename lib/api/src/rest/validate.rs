@@ -1,7 +1,7 @@
 use common::validation::validate_multi_vector;
 use validator::{Validate, ValidationError};
 
-use super::schema::{BatchVectorStruct, Vector, VectorStruct};
+use super::schema::{BatchVectorStruct, InferenceObject, Vector, VectorStruct};
 use super::{
     ContextInput, Fusion, OrderByInterface, Query, QueryInterface, RecommendInput, VectorInput,
 };
@@ -13,6 +13,7 @@ impl Validate for VectorStruct {
             VectorStruct::Single(_) => Ok(()),
             VectorStruct::MultiDense(v) => validate_multi_vector(v),
             VectorStruct::Named(v) => common::validation::validate_iter(v.values()),
+            VectorStruct::Inference(v) => v.validate(),
         }
     }
 }
@@ -40,6 +41,16 @@ impl Validate for Vector {
             Vector::Dense(_) => Ok(()),
             Vector::Sparse(v) => v.validate(),
             Vector::MultiDense(m) => common::validation::validate_multi_vector(m),
+            Vector::Inference(v) => v.validate(),
+        }
+    }
+}
+
+impl Validate for InferenceObject {
+    fn validate(&self) -> Result<(), validator::ValidationErrors> {
+        match self {
+            InferenceObject::Document(document) => document.validate(),
+            InferenceObject::Image(image) => image.validate(),
         }
     }
 }
@@ -71,6 +82,8 @@ impl Validate for Query {
             Query::Discover(discover) => discover.discover.validate(),
             Query::Context(context) => context.context.validate(),
             Query::Fusion(fusion) => fusion.fusion.validate(),
+            Query::WeightedFusion(fusion) => fusion.weighted_fusion.validate(),
+            Query::Formula(_) => Ok(()), // validated during parsing
             Query::OrderBy(order_by) => order_by.order_by.validate(),
         }
     }
@@ -83,6 +96,7 @@ impl Validate for VectorInput {
             VectorInput::DenseVector(_dense) => Ok(()),
             VectorInput::SparseVector(sparse) => sparse.validate(),
             VectorInput::MultiDenseVector(multi) => validate_multi_vector(multi),
+            VectorInput::Inference(v) => v.validate(),
         }
     }
 }
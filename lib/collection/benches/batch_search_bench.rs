@@ -13,6 +13,7 @@ use collection::save_on_disk::SaveOnDisk;
 use collection::shards::local_shard::LocalShard;
 use collection::shards::shard_trait::ShardOperation;
 use common::cpu::CpuBudget;
+use common::hardware_counter::HardwareCounterCell;
 use criterion::{criterion_group, criterion_main, Criterion};
 use rand::thread_rng;
 use segment::data_types::vectors::{only_default_vector, VectorStructInternal};
@@ -77,10 +78,12 @@ fn batch_search_bench(c: &mut Criterion) {
             indexing_threshold: Some(50_000),
             flush_interval_sec: 30,
             max_optimization_threads: Some(2),
+            defer_indexing: false,
         },
         wal_config,
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
+        strict_mode_config: None,
     };
 
     let optimizers_config = collection_config.optimizer_config.clone();
@@ -158,10 +161,11 @@ fn batch_search_bench(c: &mut Criterion) {
                                 }),
                                 search_runtime_handle,
                                 None,
+                                &HardwareCounterCell::disabled(),
                             )
                             .await
                             .unwrap();
-                        assert!(!result.is_empty());
+                        assert!(!result.value.is_empty());
                     }
                 });
             })
@@ -189,10 +193,15 @@ fn batch_search_bench(c: &mut Criterion) {
 
                     let search_query = CoreSearchRequestBatch { searches };
                     let result = shard
-                        .core_search(Arc::new(search_query), search_runtime_handle, None)
+                        .core_search(
+                            Arc::new(search_query),
+                            search_runtime_handle,
+                            None,
+                            &HardwareCounterCell::disabled(),
+                        )
                         .await
                         .unwrap();
-                    assert!(!result.is_empty());
+                    assert!(!result.value.is_empty());
                 });
             })
         });
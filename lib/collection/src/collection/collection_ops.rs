@@ -1,18 +1,27 @@
 use std::cmp;
 use std::sync::Arc;
 
+use chrono::{DateTime, Utc};
 use futures::{future, TryStreamExt as _};
 use lazy_static::lazy_static;
-use segment::types::QuantizationConfig;
+use segment::types::{PointIdType, SeqNumberType};
 use semver::Version;
 
 use super::Collection;
+use crate::collection_manager::point_history::{PointHistory, PointVersion};
+use crate::common::collection_stats::{
+    CollectionStats, OptimizerActivity, StatsCounters, STATS_WINDOW,
+};
+use crate::config::{CollectionConfig, StrictModeConfig};
 use crate::operations::config_diff::*;
+use crate::operations::config_impact::ConfigChangeImpact;
+use crate::operations::segment_details::ShardSegmentsInfo;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::*;
+use crate::operations::verification::{StrictModeChecker, StrictModeVerification};
 use crate::optimizers_builder::OptimizersConfig;
 use crate::shards::replica_set::{Change, ReplicaState};
-use crate::shards::shard::PeerId;
+use crate::shards::shard::{PeerId, ShardId};
 
 lazy_static! {
     /// When dropping a shard, only cancel all related shard transfers to and from it when all nodes
@@ -92,6 +101,45 @@ impl Collection {
         Ok(())
     }
 
+    /// Updates strict mode config:
+    /// Saves new params on disk
+    ///
+    /// Fields not set in `strict_mode_diff` are left unchanged.
+    pub async fn update_strict_mode_config(
+        &self,
+        strict_mode_diff: StrictModeConfig,
+    ) -> CollectionResult<()> {
+        {
+            let mut config = self.collection_config.write().await;
+            config.strict_mode_config = Some(match &config.strict_mode_config {
+                Some(strict_mode_config) => strict_mode_diff.update(strict_mode_config)?,
+                None => strict_mode_diff,
+            });
+        }
+        self.collection_config.read().await.save(&self.path)?;
+        Ok(())
+    }
+
+    /// Reject the request if it exceeds the strict mode limits of the collection.
+    ///
+    /// Does nothing if strict mode is not enabled.
+    pub async fn check_strict_mode(
+        &self,
+        request: &(impl StrictModeVerification + ?Sized),
+    ) -> CollectionResult<()> {
+        let config = self.collection_config.read().await;
+        let Some(strict_mode_config) = config
+            .strict_mode_config
+            .as_ref()
+            .filter(|config| config.is_enabled())
+        else {
+            return Ok(());
+        };
+
+        let payload_schema = self.payload_index_schema.read();
+        request.check_strict_mode(&StrictModeChecker::new(strict_mode_config, &payload_schema))
+    }
+
     /// Updates shard optimization params:
     /// Saves new params on disk
     ///
@@ -137,26 +185,7 @@ impl Collection {
     ) -> CollectionResult<()> {
         {
             let mut config = self.collection_config.write().await;
-            match quantization_config_diff {
-                QuantizationConfigDiff::Scalar(scalar) => {
-                    config
-                        .quantization_config
-                        .replace(QuantizationConfig::Scalar(scalar));
-                }
-                QuantizationConfigDiff::Product(product) => {
-                    config
-                        .quantization_config
-                        .replace(QuantizationConfig::Product(product));
-                }
-                QuantizationConfigDiff::Binary(binary) => {
-                    config
-                        .quantization_config
-                        .replace(QuantizationConfig::Binary(binary));
-                }
-                QuantizationConfigDiff::Disabled(_) => {
-                    config.quantization_config = None;
-                }
-            }
+            config.quantization_config = quantization_config_diff.into_config();
         }
         self.collection_config.read().await.save(&self.path)?;
         Ok(())
@@ -286,6 +315,149 @@ impl Collection {
         Ok(info)
     }
 
+    /// Internals of segments of all shards, which are located on this peer
+    pub async fn segments_info(&self) -> Vec<ShardSegmentsInfo> {
+        let shards_holder = self.shards_holder.read().await;
+        let mut shards = Vec::new();
+        for (_shard_id, replica_set) in shards_holder.get_shards() {
+            if let Some(info) = replica_set.segments_info().await {
+                shards.push(info);
+            }
+        }
+        shards.sort_by_key(|info| info.shard_id);
+        shards
+    }
+
+    /// Estimate the effect of changing the collection config to `new_config`, without applying it
+    ///
+    /// Only shards, which are located on this peer, are estimated.
+    pub async fn estimate_config_change(&self, new_config: CollectionConfig) -> ConfigChangeImpact {
+        let shards_holder = self.shards_holder.read().await;
+        let mut shards = Vec::new();
+        for (_shard_id, replica_set) in shards_holder.get_shards() {
+            if let Some(impact) = replica_set.config_change_impact(&new_config).await {
+                shards.push(impact);
+            }
+        }
+        shards.sort_by_key(|impact| impact.shard_id);
+        ConfigChangeImpact::new(new_config, shards)
+    }
+
+    /// Rolling-window statistics of the requests to the shards, which are located on this peer
+    pub async fn stats(&self) -> CollectionStats {
+        let shards_holder = self.shards_holder.read().await;
+        let mut counters = StatsCounters::default();
+        let mut window = STATS_WINDOW;
+        let mut trackers = Vec::new();
+        for (_shard_id, replica_set) in shards_holder.get_shards() {
+            if let Some((shard_counters, shard_window, shard_trackers)) =
+                replica_set.local_stats().await
+            {
+                counters.merge(&shard_counters);
+                // Shards, loaded later, only have counters for a shorter window
+                window = window.min(shard_window);
+                trackers.extend(shard_trackers);
+            }
+        }
+        CollectionStats::new(counters, window, OptimizerActivity::new(&trackers))
+    }
+
+    /// Disk usage of shards, which are located on this peer, with their projected growth
+    pub async fn disk_usage(&self) -> CollectionResult<CollectionDiskUsage> {
+        let shards_holder = self.shards_holder.read().await;
+        let mut shards = Vec::new();
+        for (_shard_id, replica_set) in shards_holder.get_shards() {
+            if let Some(usage) = replica_set.disk_usage().await? {
+                shards.push(usage);
+            }
+        }
+        shards.sort_by_key(|usage| usage.shard_id);
+
+        let disk_usage_bytes = shards.iter().map(|shard| shard.disk_usage_bytes).sum();
+        let growth_bytes_per_hour = shards
+            .iter()
+            .filter_map(|shard| shard.growth_bytes_per_hour)
+            .reduce(|a, b| a + b);
+        // Shards share the disk, so it is filled by the growth of all of them
+        let hours_until_writes_rejected = shards
+            .iter()
+            .filter_map(|shard| {
+                let hours = shard.hours_until_writes_rejected?;
+                let shard_growth = shard.growth_bytes_per_hour?;
+                let total_growth = growth_bytes_per_hour.filter(|growth| *growth > 0.0)?;
+                Some(hours * shard_growth / total_growth)
+            })
+            .min_by(f64::total_cmp);
+        let writes_rejected = shards.iter().any(|shard| shard.writes_rejected);
+
+        Ok(CollectionDiskUsage {
+            disk_usage_bytes,
+            growth_bytes_per_hour,
+            hours_until_writes_rejected,
+            writes_rejected,
+            shards,
+        })
+    }
+
+    /// Retained versions of the point from active replicas of the shards of the collection
+    ///
+    /// If `version` or `timestamp` is given, only the version, which was current at that moment,
+    /// is returned.
+    pub async fn point_history(
+        &self,
+        point_id: PointIdType,
+        version: Option<SeqNumberType>,
+        timestamp: Option<DateTime<Utc>>,
+    ) -> CollectionResult<Vec<PointVersion>> {
+        if self
+            .collection_config
+            .read()
+            .await
+            .params
+            .point_history_versions
+            .is_none()
+        {
+            return Err(CollectionError::bad_request(format!(
+                "Point history is not enabled for collection {}",
+                self.name(),
+            )));
+        }
+
+        let shards_holder = self.shards_holder.read().await;
+        let mut versions = Vec::new();
+        for (_shard_id, replica_set) in shards_holder.get_shards() {
+            if let Some(shard_versions) = replica_set.point_history(point_id).await? {
+                versions.extend(shard_versions);
+            }
+        }
+        if versions.is_empty() {
+            return Err(CollectionError::PointNotFound {
+                missed_point_id: point_id,
+            });
+        }
+        versions.sort_by_key(|point_version| point_version.version);
+
+        Ok(PointHistory::select(versions, version, timestamp))
+    }
+
+    /// Retained versions of the point in the local replica of the shard, for other peers
+    ///
+    /// Returns `None` if the point history is not retained.
+    pub async fn local_point_history(
+        &self,
+        shard_id: ShardId,
+        point_id: PointIdType,
+    ) -> CollectionResult<Option<Vec<PointVersion>>> {
+        let shards_holder = self.shards_holder.read().await;
+        let Some(replica_set) = shards_holder.get_shard(&shard_id) else {
+            return Err(CollectionError::NotFound {
+                what: format!("Shard {shard_id}"),
+            });
+        };
+
+        replica_set.local_point_history(point_id).await
+    }
+
     pub async fn cluster_info(&self, peer_id: PeerId) -> CollectionResult<CollectionClusterInfo> {
         let shards_holder = self.shards_holder.read().await;
         let shard_count = shards_holder.len();
@@ -317,6 +489,7 @@ impl Collection {
                     points_count,
                     state,
                     shard_key: shard_to_key.get(&shard_id).cloned(),
+                    quarantined_segments: replica_set.quarantined_segments().await,
                 })
             }
             for (peer_id, state) in replica_set.peers().into_iter() {
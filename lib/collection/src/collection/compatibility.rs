@@ -0,0 +1,128 @@
+use std::path::{Path, PathBuf};
+
+use io::storage_version::StorageVersion as _;
+use segment::segment::SegmentVersion;
+use segment::segment_constructor::migrations::{
+    check_segment_version, pending_migrations, SegmentMigration,
+};
+
+use super::{Collection, CollectionVersion};
+use crate::shards::local_shard::LocalShard;
+use crate::shards::shard::ShardId;
+
+/// Result of the check, whether the stored collection can be loaded by this version
+#[derive(Debug, Default)]
+pub struct CollectionCompatibility {
+    /// Number of checked segments
+    pub segments: usize,
+    /// Migrations, which are applied to segments on load
+    pub pending_migrations: Vec<(PathBuf, &'static SegmentMigration)>,
+    /// Problems, which prevent the collection from loading
+    pub errors: Vec<String>,
+}
+
+impl CollectionCompatibility {
+    pub fn is_compatible(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+impl Collection {
+    /// Check, whether the collection stored in `path` can be loaded by this version of the
+    /// application, and which migrations it needs. The stored data is not changed.
+    ///
+    /// This method performs blocking IO.
+    pub fn check_compatibility(path: &Path) -> CollectionCompatibility {
+        let mut report = CollectionCompatibility::default();
+
+        let app_version = CollectionVersion::current();
+        match CollectionVersion::load(path) {
+            Ok(Some(stored_version)) if stored_version > app_version => {
+                report.errors.push(format!(
+                    "Collection version {stored_version} is newer than application version {app_version}",
+                ));
+            }
+            Ok(Some(stored_version))
+                if !Self::can_upgrade_storage(&stored_version, &app_version) =>
+            {
+                report.errors.push(format!(
+                    "Cannot upgrade collection version {stored_version} to {app_version}",
+                ));
+            }
+            Ok(Some(_)) => {}
+            Ok(None) => report
+                .errors
+                .push("Collection version is not found".to_string()),
+            Err(err) => report
+                .errors
+                .push(format!("Can't read collection version: {err}")),
+        }
+
+        let entries = match std::fs::read_dir(path) {
+            Ok(entries) => entries,
+            Err(err) => {
+                report
+                    .errors
+                    .push(format!("Can't read collection directory: {err}"));
+                return report;
+            }
+        };
+        for entry in entries.flatten() {
+            let is_shard = entry
+                .file_name()
+                .to_str()
+                .is_some_and(|name| name.parse::<ShardId>().is_ok());
+            let segments_path = LocalShard::segments_path(&entry.path());
+            if is_shard && segments_path.is_dir() {
+                check_segments(&segments_path, &mut report);
+            }
+        }
+
+        report
+    }
+}
+
+fn check_segments(segments_path: &Path, report: &mut CollectionCompatibility) {
+    let entries = match std::fs::read_dir(segments_path) {
+        Ok(entries) => entries,
+        Err(err) => {
+            report.errors.push(format!(
+                "Can't read segments directory {}: {err}",
+                segments_path.display(),
+            ));
+            return;
+        }
+    };
+    for entry in entries.flatten() {
+        let segment_path = entry.path();
+        // Deleted segments and segments without version are skipped on load
+        if segment_path.extension().is_some_and(|ext| ext == "deleted") {
+            continue;
+        }
+        let stored_version = match SegmentVersion::load(&segment_path) {
+            Ok(Some(version)) => version,
+            Ok(None) => continue,
+            Err(err) => {
+                report.errors.push(format!(
+                    "Segment {}: can't read version: {err}",
+                    segment_path.display(),
+                ));
+                continue;
+            }
+        };
+        report.segments += 1;
+
+        let pending = check_segment_version(&stored_version)
+            .and_then(|()| pending_migrations(&segment_path, &stored_version));
+        match pending {
+            Ok(pending) => report.pending_migrations.extend(
+                pending
+                    .into_iter()
+                    .map(|migration| (segment_path.clone(), migration)),
+            ),
+            Err(err) => report
+                .errors
+                .push(format!("Segment {}: {err}", segment_path.display())),
+        }
+    }
+}
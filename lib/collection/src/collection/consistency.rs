@@ -0,0 +1,50 @@
+use super::Collection;
+use crate::operations::types::{CollectionConsistency, CollectionError, CollectionResult};
+use crate::shards::point_digests::{PointDigests, PointDigestsRequest};
+use crate::shards::shard::ShardId;
+
+impl Collection {
+    /// Digests of the points in the local replica of the shard, to compare it with other replicas
+    pub async fn local_point_digests(
+        &self,
+        shard_id: ShardId,
+        request: PointDigestsRequest,
+    ) -> CollectionResult<PointDigests> {
+        let shard_holder = self.shards_holder.read().await;
+        let Some(replica_set) = shard_holder.get_shard(&shard_id) else {
+            return Err(CollectionError::NotFound {
+                what: format!("Shard {shard_id}"),
+            });
+        };
+
+        replica_set.local_point_digests(request).await
+    }
+
+    /// Compare the replicas of the shards, which have an active replica on this peer, and
+    /// optionally re-sync the divergent points.
+    ///
+    /// With `only_coordinated`, a shard is only checked by its active peer with the lowest id, so
+    /// that periodic checks on all peers don't compare the same replicas several times.
+    pub async fn check_consistency(
+        &self,
+        buckets: usize,
+        repair: bool,
+        only_coordinated: bool,
+    ) -> CollectionResult<CollectionConsistency> {
+        let shard_holder = self.shards_holder.read().await;
+        let mut report = CollectionConsistency::default();
+        for (_shard_id, replica_set) in shard_holder.get_shards() {
+            let active_peers = replica_set.active_shards().await;
+            let is_active = active_peers.contains(&self.this_peer_id);
+            let is_coordinator = active_peers.iter().min() == Some(&self.this_peer_id);
+            if !is_active || (only_coordinated && !is_coordinator) {
+                continue;
+            }
+            report
+                .shards
+                .push(replica_set.check_consistency(buckets, repair).await?);
+        }
+        report.shards.sort_by_key(|shard| shard.shard_id);
+        Ok(report)
+    }
+}
@@ -0,0 +1,61 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use super::Collection;
+use crate::operations::types::CollectionResult;
+use crate::shards::shard::ShardId;
+
+/// Freeze of the local shards of the collection on this peer
+#[derive(Debug, Default, Serialize, JsonSchema)]
+pub struct CollectionFreezeStatus {
+    /// Local shards, whose segments are frozen
+    pub frozen_shards: Vec<ShardId>,
+    /// Time, at which the first of the freezes is lifted automatically
+    pub frozen_until: Option<DateTime<Utc>>,
+}
+
+impl Collection {
+    /// Freeze updates of segments, flushes and optimizations of the local shards, so that an
+    /// external backup of the storage directory captures a consistent state.
+    ///
+    /// Reads are served and updates are accepted into the WAL, but they are only applied once the
+    /// freeze is lifted, by `unfreeze` or after `timeout`.
+    pub async fn freeze(&self, timeout: Duration) -> CollectionResult<CollectionFreezeStatus> {
+        let shard_holder = self.shards_holder.read().await;
+        for (_shard_id, replica_set) in shard_holder.get_shards() {
+            replica_set.freeze_local(timeout).await?;
+        }
+        drop(shard_holder);
+
+        Ok(self.freeze_status().await)
+    }
+
+    pub async fn unfreeze(&self) -> CollectionResult<CollectionFreezeStatus> {
+        let shard_holder = self.shards_holder.read().await;
+        for (_shard_id, replica_set) in shard_holder.get_shards() {
+            replica_set.unfreeze_local().await?;
+        }
+        drop(shard_holder);
+
+        Ok(self.freeze_status().await)
+    }
+
+    pub async fn freeze_status(&self) -> CollectionFreezeStatus {
+        let shard_holder = self.shards_holder.read().await;
+        let mut status = CollectionFreezeStatus::default();
+        for (shard_id, replica_set) in shard_holder.get_shards() {
+            if let Some(frozen_until) = replica_set.local_frozen_until().await {
+                status.frozen_shards.push(*shard_id);
+                status.frozen_until = Some(match status.frozen_until {
+                    Some(until) => until.min(frozen_until),
+                    None => frozen_until,
+                });
+            }
+        }
+        status.frozen_shards.sort_unstable();
+        status
+    }
+}
@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use super::Collection;
+use crate::common::warmup::WarmupStatus;
+use crate::shards::replica_set::ReplicaState;
+use crate::shards::shard::PeerId;
+
+/// Whether the collection can serve requests on this peer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CollectionHealth {
+    /// Local replicas are still loading or receiving data, page cache is warming up,
+    /// or some shard has no active replica
+    Loading,
+    /// All replicas are active
+    Ready,
+    /// All shards can be served, but some replicas are dead
+    Degraded,
+}
+
+impl CollectionHealth {
+    fn from_replicas<'a>(
+        this_peer_id: PeerId,
+        shards: impl IntoIterator<Item = &'a HashMap<PeerId, ReplicaState>>,
+    ) -> Self {
+        let mut health = CollectionHealth::Ready;
+        for replicas in shards {
+            let local_loading = replicas.get(&this_peer_id).is_some_and(|state| {
+                !state.is_active_or_listener() && *state != ReplicaState::Dead
+            });
+            let has_active = replicas
+                .values()
+                .any(|state| *state == ReplicaState::Active);
+            if local_loading || !has_active {
+                return CollectionHealth::Loading;
+            }
+            if replicas.values().any(|state| *state == ReplicaState::Dead) {
+                health = CollectionHealth::Degraded;
+            }
+        }
+        health
+    }
+}
+
+impl Collection {
+    /// Health of the collection on this peer, based on states of the replicas and the warmup
+    pub async fn health(&self) -> CollectionHealth {
+        if self.warmup_progress().status == WarmupStatus::InProgress {
+            return CollectionHealth::Loading;
+        }
+        let shards_holder = self.shards_holder.read().await;
+        let replicas: Vec<_> = shards_holder
+            .get_shards()
+            .map(|(_shard_id, replica_set)| replica_set.peers())
+            .collect();
+        CollectionHealth::from_replicas(self.this_peer_id, &replicas)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_health_from_replicas() {
+        let this_peer_id = 1;
+        let shards = |states: &[(PeerId, ReplicaState)]| -> HashMap<_, _> {
+            states.iter().copied().collect()
+        };
+
+        let active = shards(&[(1, ReplicaState::Active), (2, ReplicaState::Active)]);
+        let remote_dead = shards(&[(1, ReplicaState::Active), (2, ReplicaState::Dead)]);
+        let local_partial = shards(&[(1, ReplicaState::Partial), (2, ReplicaState::Active)]);
+        let all_dead = shards(&[(1, ReplicaState::Dead), (2, ReplicaState::Dead)]);
+
+        assert_eq!(
+            CollectionHealth::from_replicas(this_peer_id, [&active, &active]),
+            CollectionHealth::Ready,
+        );
+        assert_eq!(
+            CollectionHealth::from_replicas(this_peer_id, [&active, &remote_dead]),
+            CollectionHealth::Degraded,
+        );
+        assert_eq!(
+            CollectionHealth::from_replicas(this_peer_id, [&remote_dead, &local_partial]),
+            CollectionHealth::Loading,
+        );
+        assert_eq!(
+            CollectionHealth::from_replicas(this_peer_id, [&all_dead]),
+            CollectionHealth::Loading,
+        );
+    }
+}
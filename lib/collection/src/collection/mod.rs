@@ -1,13 +1,25 @@
 mod collection_ops;
+pub mod compatibility;
+mod consistency;
+pub mod freeze;
+pub mod health;
 pub mod payload_index_schema;
+pub mod payload_schema_inference;
 mod point_ops;
+mod point_sampling;
 pub mod query;
+mod quotas;
+mod recall_evaluation;
 mod resharding;
 mod search;
+mod search_tuning;
 mod shard_transfer;
 mod sharding_keys;
+pub mod snapshot_verification;
 mod snapshots;
+mod sparse_vocabulary;
 mod state_management;
+pub mod vector_diagnostics;
 
 use std::collections::{HashMap, HashSet};
 use std::ops::Deref;
@@ -23,10 +35,15 @@ use semver::Version;
 use tokio::runtime::Handle;
 use tokio::sync::{Mutex, RwLock, RwLockWriteGuard};
 
+use self::quotas::QuotaUsageCache;
 use crate::collection::payload_index_schema::PayloadIndexSchema;
 use crate::collection_state::{ShardInfo, State};
+use crate::common::index_advisor::IndexAdvisor;
 use crate::common::is_ready::IsReady;
+use crate::common::slow_query_log::SlowQueryLog;
+use crate::common::warmup::{Warmup, WarmupProgress};
 use crate::config::CollectionConfig;
+use crate::events::ReplicaDeadEvent;
 use crate::operations::config_diff::{DiffConfig, OptimizersConfigDiff};
 use crate::operations::shared_storage_config::SharedStorageConfig;
 use crate::operations::types::{CollectionError, CollectionResult, NodeType};
@@ -78,6 +95,10 @@ pub struct Collection {
     // Search runtime handle.
     search_runtime: Handle,
     optimizer_cpu_budget: CpuBudget,
+    slow_query_log: SlowQueryLog,
+    index_advisor: IndexAdvisor,
+    warmup: Warmup,
+    quota_usage_cache: QuotaUsageCache,
 }
 
 pub type RequestShardTransfer = Arc<dyn Fn(ShardTransfer) + Send + Sync>;
@@ -150,6 +171,12 @@ impl Collection {
         CollectionVersion::save(path)?;
         collection_config.save(path)?;
 
+        let slow_query_log = SlowQueryLog::new(&shared_storage_config.slow_query_log, &name);
+        let index_advisor = IndexAdvisor::new(&shared_storage_config.index_advisor);
+        // New collection has nothing to warm up
+        let warmup = Warmup::new(&shared_storage_config.warmup, path);
+        warmup.start(Vec::new());
+
         Ok(Self {
             id: name.clone(),
             shards_holder: locked_shard_holder,
@@ -172,6 +199,10 @@ impl Collection {
             update_runtime: update_runtime.unwrap_or_else(Handle::current),
             search_runtime: search_runtime.unwrap_or_else(Handle::current),
             optimizer_cpu_budget,
+            slow_query_log,
+            index_advisor,
+            warmup,
+            quota_usage_cache: Default::default(),
         })
     }
 
@@ -257,8 +288,21 @@ impl Collection {
             )
             .await;
 
+        let warmup = Warmup::new(&shared_storage_config.warmup, path);
+        if warmup.is_enabled() {
+            let mut files = Vec::new();
+            for (_shard_id, replica_set) in shard_holder.get_shards() {
+                files.extend(replica_set.mmap_files().await);
+            }
+            warmup.start(files);
+        }
+
         let locked_shard_holder = Arc::new(LockedShardHolder::new(shard_holder));
 
+        let slow_query_log =
+            SlowQueryLog::new(&shared_storage_config.slow_query_log, &collection_id);
+        let index_advisor = IndexAdvisor::new(&shared_storage_config.index_advisor);
+
         Self {
             id: collection_id.clone(),
             shards_holder: locked_shard_holder,
@@ -281,6 +325,10 @@ impl Collection {
             update_runtime: update_runtime.unwrap_or_else(Handle::current),
             search_runtime: search_runtime.unwrap_or_else(Handle::current),
             optimizer_cpu_budget,
+            slow_query_log,
+            index_advisor,
+            warmup,
+            quota_usage_cache: Default::default(),
         }
     }
 
@@ -326,6 +374,11 @@ impl Collection {
         self.shards_holder.read().await.get_local_shards().await
     }
 
+    /// Progress of reading memory mapped files of local shards after collection load
+    pub fn warmup_progress(&self) -> WarmupProgress {
+        self.warmup.progress()
+    }
+
     pub async fn contains_shard(&self, shard_id: ShardId) -> bool {
         self.shards_holder.read().await.contains_shard(&shard_id)
     }
@@ -424,6 +477,14 @@ impl Collection {
             .await?;
 
         if state == ReplicaState::Dead {
+            if current_state != Some(ReplicaState::Dead) {
+                issues::publish(ReplicaDeadEvent {
+                    collection_id: self.id.clone(),
+                    shard_id,
+                    peer_id,
+                });
+            }
+
             // TODO(resharding): Abort all resharding transfers!?
 
             // Terminate transfer if source or target replicas are now dead
@@ -700,12 +761,30 @@ impl Collection {
             )
         };
 
+        let config = self.collection_config.read().await.clone();
+        let has_quotas = config
+            .strict_mode_config
+            .as_ref()
+            .is_some_and(|config| config.has_quotas());
+        let quota_usage = if has_quotas {
+            self.quota_usage().await.unwrap_or_else(|err| {
+                log::warn!(
+                    "Failed to measure quota usage of collection {}: {err}",
+                    self.id
+                );
+                None
+            })
+        } else {
+            None
+        };
+
         CollectionTelemetry {
             id: self.name(),
             init_time_ms: self.init_time.as_millis() as u64,
-            config: self.collection_config.read().await.clone(),
+            config,
             shards: shards_telemetry,
             transfers,
+            quota_usage,
         }
     }
 
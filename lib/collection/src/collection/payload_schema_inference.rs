@@ -0,0 +1,367 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use segment::data_types::aggregation::{hash_payload_value, DistinctValues};
+use segment::json_path::{JsonPath, JsonPathInterface as _};
+use segment::types::{
+    value_type, DateTimePayloadType, Payload, PayloadFieldSchema, PayloadKeyType,
+    PayloadSchemaType, WithPayloadInterface, WithVector,
+};
+use serde_json::Value;
+
+use super::Collection;
+use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::{
+    CollectionResult, InferredPayloadField, InferredValueType, PayloadSchemaReport,
+};
+
+/// Number of points, sampled for schema inference by default
+pub const DEFAULT_PAYLOAD_SCHEMA_SAMPLE: usize = 1_000;
+
+/// Strings of this average length, which mostly consist of several words, are suggested
+/// for full-text index rather than keyword index
+const TEXT_MIN_AVG_LENGTH: usize = 32;
+
+impl Collection {
+    /// Infer structure of payloads from a uniform random sample of points
+    pub async fn infer_payload_schema(
+        &self,
+        sample: usize,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: &ShardSelectorInternal,
+    ) -> CollectionResult<PayloadSchemaReport> {
+        let points = self
+            .sample_points(
+                sample,
+                None,
+                WithPayloadInterface::Bool(true),
+                WithVector::Bool(false),
+                read_consistency,
+                shard_selection,
+            )
+            .await?;
+
+        let mut inference = PayloadSchemaInference::default();
+        for point in &points {
+            inference.add_payload(point.payload.as_ref());
+        }
+
+        let index_schema = self.payload_index_schema.read();
+        Ok(inference.into_report(&index_schema.schema))
+    }
+}
+
+/// Accumulates fields of payloads, flattened into paths
+#[derive(Default)]
+struct PayloadSchemaInference {
+    sampled_points: usize,
+    fields: BTreeMap<String, FieldInference>,
+}
+
+impl PayloadSchemaInference {
+    fn add_payload(&mut self, payload: Option<&Payload>) {
+        self.sampled_points += 1;
+        for (key, value) in payload.into_iter().flat_map(|payload| payload.0.iter()) {
+            self.add_value(&key_path(key), value);
+        }
+    }
+
+    fn add_value(&mut self, path: &JsonPath, value: &Value) {
+        let point = self.sampled_points;
+        match value {
+            Value::Array(values) => {
+                self.field(path).add_array(point);
+                for value in values {
+                    match value {
+                        Value::Object(_) if !is_geo(value) => {
+                            self.field(path).add_type(point, InferredValueType::Object);
+                            self.add_object_fields(&path.array_key(), value);
+                        }
+                        // Nested arrays are flattened, the same way as in payload indexes
+                        Value::Array(_) => self.add_value(path, value),
+                        _ => self.field(path).add_scalar(point, value),
+                    }
+                }
+            }
+            Value::Object(_) if !is_geo(value) => {
+                self.field(path).add_type(point, InferredValueType::Object);
+                self.add_object_fields(path, value);
+            }
+            _ => self.field(path).add_scalar(point, value),
+        }
+    }
+
+    fn add_object_fields(&mut self, path: &JsonPath, object: &Value) {
+        for (key, value) in object.as_object().into_iter().flatten() {
+            self.add_value(&path.extend(&key_path(key)), value);
+        }
+    }
+
+    fn field(&mut self, path: &JsonPath) -> &mut FieldInference {
+        self.fields
+            .entry(path.to_string())
+            .or_insert_with(|| FieldInference::new(path.clone()))
+    }
+
+    fn into_report(
+        self,
+        index_schema: &HashMap<PayloadKeyType, PayloadFieldSchema>,
+    ) -> PayloadSchemaReport {
+        let fields: BTreeMap<_, _> = self
+            .fields
+            .into_iter()
+            .map(|(name, field)| (name, field.into_report(index_schema)))
+            .collect();
+        PayloadSchemaReport {
+            sampled_points: self.sampled_points,
+            max_depth: fields.values().map(|field| field.depth).max().unwrap_or(0),
+            fields,
+        }
+    }
+}
+
+struct FieldInference {
+    path: JsonPath,
+    /// Number of the last point, which had this field, to count each point once
+    last_point: usize,
+    points_count: usize,
+    values_count: usize,
+    types: BTreeSet<InferredValueType>,
+    is_array: bool,
+    /// Integers, which don't fit into `i64`
+    has_big_integers: bool,
+    distinct: DistinctValues,
+    strings_count: usize,
+    strings_length: usize,
+    multi_word_strings: usize,
+}
+
+impl FieldInference {
+    fn new(path: JsonPath) -> Self {
+        Self {
+            path,
+            last_point: 0,
+            points_count: 0,
+            values_count: 0,
+            types: BTreeSet::new(),
+            is_array: false,
+            has_big_integers: false,
+            distinct: DistinctValues::default(),
+            strings_count: 0,
+            strings_length: 0,
+            multi_word_strings: 0,
+        }
+    }
+
+    fn touch(&mut self, point: usize) {
+        if self.last_point != point {
+            self.last_point = point;
+            self.points_count += 1;
+        }
+    }
+
+    fn add_array(&mut self, point: usize) {
+        self.touch(point);
+        self.is_array = true;
+    }
+
+    fn add_type(&mut self, point: usize, value_type: InferredValueType) {
+        self.touch(point);
+        self.values_count += 1;
+        self.types.insert(value_type);
+    }
+
+    fn add_scalar(&mut self, point: usize, value: &Value) {
+        let value_type = match value {
+            Value::Null => InferredValueType::Null,
+            Value::Bool(_) => InferredValueType::Bool,
+            Value::Number(number) if number.is_i64() => InferredValueType::Integer,
+            Value::Number(number) if number.is_u64() => {
+                self.has_big_integers = true;
+                InferredValueType::Integer
+            }
+            Value::Number(_) => InferredValueType::Float,
+            Value::String(string) => {
+                self.strings_count += 1;
+                self.strings_length += string.chars().count();
+                if string.split_whitespace().nth(1).is_some() {
+                    self.multi_word_strings += 1;
+                }
+                if string.parse::<DateTimePayloadType>().is_ok() {
+                    InferredValueType::Datetime
+                } else {
+                    InferredValueType::String
+                }
+            }
+            // Arrays are flattened and objects are traversed, except for geo points
+            Value::Array(_) | Value::Object(_) => InferredValueType::Geo,
+        };
+        self.add_type(point, value_type);
+        if let Some(hash) = hash_payload_value(value) {
+            self.distinct.insert(hash);
+        }
+    }
+
+    fn suggested_index(&self) -> Option<PayloadSchemaType> {
+        // Field with a single value doesn't help to select points
+        if self.distinct.count() <= 1 {
+            return None;
+        }
+
+        let types: Vec<_> = self
+            .types
+            .iter()
+            .filter(|value_type| **value_type != InferredValueType::Null)
+            .collect();
+        match types.as_slice() {
+            [InferredValueType::Bool] => Some(PayloadSchemaType::Bool),
+            [InferredValueType::Integer] if self.has_big_integers => {
+                Some(PayloadSchemaType::BigInt)
+            }
+            [InferredValueType::Integer] => Some(PayloadSchemaType::Integer),
+            [InferredValueType::Float] | [InferredValueType::Integer, InferredValueType::Float] => {
+                Some(PayloadSchemaType::Float)
+            }
+            [InferredValueType::String] => {
+                let avg_length = self.strings_length / self.strings_count.max(1);
+                if avg_length >= TEXT_MIN_AVG_LENGTH
+                    && self.multi_word_strings * 2 > self.strings_count
+                {
+                    Some(PayloadSchemaType::Text)
+                } else {
+                    Some(PayloadSchemaType::Keyword)
+                }
+            }
+            [InferredValueType::Datetime] => Some(PayloadSchemaType::Datetime),
+            [InferredValueType::Geo] => Some(PayloadSchemaType::Geo),
+            _ => None,
+        }
+    }
+
+    fn into_report(
+        self,
+        index_schema: &HashMap<PayloadKeyType, PayloadFieldSchema>,
+    ) -> InferredPayloadField {
+        let index = index_schema.get(&self.path).cloned();
+        let suggested_index = match index {
+            Some(_) => None,
+            None => self.suggested_index(),
+        };
+        InferredPayloadField {
+            points_count: self.points_count,
+            values_count: self.values_count,
+            types: self.types.iter().copied().collect(),
+            is_array: self.is_array,
+            depth: self.path.rest.len(),
+            distinct_values: self.distinct.count(),
+            index,
+            suggested_index,
+        }
+    }
+}
+
+fn key_path(key: &str) -> JsonPath {
+    JsonPath {
+        first_key: key.to_string(),
+        rest: Vec::new(),
+    }
+}
+
+fn is_geo(value: &Value) -> bool {
+    value_type(value) == Some(PayloadSchemaType::Geo)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn infer(payloads: &[Value]) -> PayloadSchemaReport {
+        let mut inference = PayloadSchemaInference::default();
+        for payload in payloads {
+            let payload = Payload(payload.as_object().unwrap().clone());
+            inference.add_payload(Some(&payload));
+        }
+        let index_schema = HashMap::from([(
+            key_path("city"),
+            PayloadFieldSchema::FieldType(PayloadSchemaType::Keyword),
+        )]);
+        inference.into_report(&index_schema)
+    }
+
+    #[test]
+    fn test_infer_payload_schema() {
+        let report = infer(&[
+            json!({
+                "city": "Berlin",
+                "price": 10,
+                "tags": ["a", "b"],
+                "location": {"lat": 52.5, "lon": 13.4},
+                "items": [{"name": "x", "weight": 1.5}],
+                "created": "2024-01-01T00:00:00Z",
+            }),
+            json!({
+                "city": "London",
+                "price": 12.5,
+                "tags": "c",
+                "location": {"lat": 51.5, "lon": -0.1},
+                "items": [{"name": "y", "weight": 2}, {"name": "z"}],
+                "created": "2024-02-01T00:00:00Z",
+                "flag": true,
+            }),
+            json!({"city": "Berlin", "price": null, "mixed": 1}),
+            json!({"city": "Paris", "mixed": "one"}),
+        ]);
+
+        assert_eq!(report.sampled_points, 4);
+        assert_eq!(report.max_depth, 2);
+
+        let city = &report.fields["city"];
+        assert_eq!(city.points_count, 4);
+        assert_eq!(city.distinct_values, 3);
+        assert!(city.index.is_some());
+        assert_eq!(city.suggested_index, None);
+
+        let price = &report.fields["price"];
+        assert_eq!(price.points_count, 3);
+        assert_eq!(
+            price.types,
+            vec![
+                InferredValueType::Null,
+                InferredValueType::Integer,
+                InferredValueType::Float,
+            ],
+        );
+        assert_eq!(price.suggested_index, Some(PayloadSchemaType::Float));
+
+        let tags = &report.fields["tags"];
+        assert!(tags.is_array);
+        assert_eq!(tags.values_count, 3);
+        assert_eq!(tags.suggested_index, Some(PayloadSchemaType::Keyword));
+
+        let location = &report.fields["location"];
+        assert_eq!(location.types, vec![InferredValueType::Geo]);
+        assert_eq!(location.suggested_index, Some(PayloadSchemaType::Geo));
+        assert!(!report.fields.contains_key("location.lat"));
+
+        let items = &report.fields["items"];
+        assert_eq!(items.types, vec![InferredValueType::Object]);
+        assert_eq!(items.suggested_index, None);
+
+        let weight = &report.fields["items[].weight"];
+        assert_eq!(weight.depth, 2);
+        assert_eq!(weight.points_count, 2);
+        assert_eq!(weight.suggested_index, Some(PayloadSchemaType::Float));
+        assert_eq!(report.fields["items[].name"].values_count, 3);
+
+        let created = &report.fields["created"];
+        assert_eq!(created.types, vec![InferredValueType::Datetime]);
+        assert_eq!(created.suggested_index, Some(PayloadSchemaType::Datetime));
+
+        // Single value is not selective
+        assert_eq!(report.fields["flag"].suggested_index, None);
+        // Inconsistent types
+        assert_eq!(report.fields["mixed"].suggested_index, None);
+    }
+}
@@ -4,16 +4,24 @@ use std::sync::Arc;
 use futures::stream::FuturesUnordered;
 use futures::{future, StreamExt as _, TryFutureExt, TryStreamExt as _};
 use itertools::Itertools;
-use segment::data_types::order_by::{Direction, OrderBy};
-use segment::types::{Filter, ShardKey, WithPayload, WithPayloadInterface};
+use segment::data_types::aggregation::{DistinctValues, NumericStats, ValueDistribution};
+use segment::data_types::order_by::{OrderBy, OrderByCursor, OrderedPoint};
+use segment::types::{
+    Filter, PointIdType, ShardKey, WithPayload, WithPayloadInterface, WithVector,
+};
 use validator::Validate as _;
 
 use super::Collection;
+use crate::collection_manager::point_trash::{DeletedRecord, TrashedPoint};
+use crate::operations::attribute_ops::check_attribute_values;
+use crate::operations::computed_fields::materialize_computed_fields;
 use crate::operations::consistency_params::ReadConsistency;
-use crate::operations::point_ops::WriteOrdering;
+use crate::operations::point_ops::{PointStruct, WriteOrdering};
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::*;
+use crate::operations::vector_truncation::truncate_input_vectors;
 use crate::operations::{CollectionUpdateOperations, OperationWithClockTag};
+use crate::shards::resolve::Reconciled;
 use crate::shards::shard::ShardId;
 
 impl Collection {
@@ -107,7 +115,12 @@ impl Collection {
                     }
 
                     shard
-                        .update_with_consistency(operation.operation, wait, ordering)
+                        .update_with_consistency(
+                            operation.operation,
+                            operation.idempotency_key,
+                            wait,
+                            ordering,
+                        )
                         .await
                         .map(Some)
                 }
@@ -131,11 +144,18 @@ impl Collection {
     /// This method is cancel safe.
     pub async fn update_from_client(
         &self,
-        operation: CollectionUpdateOperations,
+        mut operation: CollectionUpdateOperations,
+        idempotency_key: Option<String>,
         wait: bool,
         ordering: WriteOrdering,
         shard_keys_selection: Option<ShardKey>,
     ) -> CollectionResult<UpdateResult> {
+        {
+            let collection_config = self.collection_config.read().await;
+            truncate_input_vectors(&collection_config.params.vectors, &mut operation)?;
+            materialize_computed_fields(&collection_config.params.computed_fields, &mut operation);
+            check_attribute_values(&collection_config.params.attribute_columns, &mut operation)?;
+        }
         operation.validate()?;
 
         let update_lock = self.updates_lock.clone().read_owned().await;
@@ -148,7 +168,12 @@ impl Collection {
                 .split_by_shard(operation, &shard_keys_selection)?
                 .into_iter()
                 .map(move |(shard, operation)| {
-                    shard.update_with_consistency(operation, wait, ordering)
+                    shard.update_with_consistency(
+                        operation,
+                        idempotency_key.clone(),
+                        wait,
+                        ordering,
+                    )
                 })
                 .collect();
 
@@ -188,7 +213,14 @@ impl Collection {
             }
         } else {
             // At least one result is always present.
-            results.pop().unwrap()
+            let mut result = results.pop().unwrap()?;
+
+            // Each shard reports conflicts of its own points
+            for shard_result in results {
+                result.conflicts.extend(shard_result?.conflicts);
+            }
+
+            Ok(result)
         }
     }
 
@@ -201,7 +233,7 @@ impl Collection {
         wait: bool,
         ordering: WriteOrdering,
     ) -> CollectionResult<UpdateResult> {
-        self.update_from_client(operation, wait, ordering, None)
+        self.update_from_client(operation, None, wait, ordering, None)
             .await
     }
 
@@ -228,13 +260,43 @@ impl Collection {
             .unwrap_or_else(|| default_request.with_payload.clone().unwrap());
         let with_vector = request.with_vector;
 
-        let order_by = request.order_by.map(OrderBy::from);
+        let mut order_by = request.order_by.map(OrderBy::from);
 
         // Validate user did not try to use an id offset with order_by
         if order_by.is_some() && id_offset.is_some() {
-            return Err(CollectionError::bad_input("Cannot use an `offset` when using `order_by`. The alternative for paging is to use `order_by.start_from` and a filter to exclude the IDs that you've already seen for the `order_by.start_from` value".to_string()));
+            return Err(CollectionError::bad_input("Cannot use an `offset` when using `order_by`. The alternative for paging is to use the `next_page_cursor` of the previous page as `cursor`".to_string()));
         };
 
+        if order_by.is_some() && request.include_deleted.unwrap_or(false) {
+            return Err(CollectionError::bad_input(
+                "Cannot use `include_deleted` when using `order_by`".to_string(),
+            ));
+        }
+
+        if let Some(cursor) = &request.cursor {
+            let cursor = OrderByCursor::decode(cursor).map_err(CollectionError::bad_input)?;
+            order_by = match order_by {
+                Some(order_by) => {
+                    if order_by.start_from.is_some() {
+                        return Err(CollectionError::bad_input(
+                            "Cannot use both `order_by.start_from` and `cursor`".to_string(),
+                        ));
+                    }
+                    if order_by.then_by().len() != cursor.then_by.len() {
+                        return Err(CollectionError::bad_input(
+                            "`cursor` was created for a different `order_by.then_by`".to_string(),
+                        ));
+                    }
+                    Some(order_by.with_cursor(&cursor))
+                }
+                None => {
+                    return Err(CollectionError::bad_input(
+                        "`cursor` can only be used together with `order_by`".to_string(),
+                    ))
+                }
+            };
+        }
+
         if limit == 0 {
             return Err(CollectionError::BadRequest {
                 description: "Limit cannot be 0".to_string(),
@@ -281,6 +343,8 @@ impl Collection {
 
         let retrieved_iter = retrieved_points.into_iter();
 
+        let mut last_ordered_point = None;
+
         let mut points = match &order_by {
             None => retrieved_iter
                 .flatten()
@@ -296,33 +360,50 @@ impl Collection {
                     .map(|records| {
                         records.into_iter().map(|mut record| {
                             let value;
+                            let then_by;
                             if local_only {
                                 value =
                                     order_by.get_order_value_from_payload(record.payload.as_ref());
+                                then_by = order_by
+                                    .get_then_by_values_from_payload(record.payload.as_ref());
                             } else {
                                 value = order_by
                                     .remove_order_value_from_payload(record.payload.as_mut());
+                                then_by = order_by
+                                    .remove_then_by_values_from_payload(record.payload.as_mut());
                                 if !with_payload_interface.is_required() {
                                     // Use None instead of empty hashmap
                                     record.payload = None;
                                 }
                             };
-                            (value, record)
+                            let point = OrderedPoint {
+                                value,
+                                then_by,
+                                id: record.id,
+                            };
+                            (point, record)
                         })
                     })
-                    // Get top results
-                    .kmerge_by(|(value_a, _), (value_b, _)| match order_by.direction() {
-                        Direction::Asc => value_a <= value_b,
-                        Direction::Desc => value_a >= value_b,
+                    // Get top results, points with equal values are ordered by `then_by` keys and id
+                    .kmerge_by(|(point_a, _), (point_b, _)| {
+                        order_by.cmp_points(point_a, point_b).is_le()
                     })
                     // Add each point only once, deduplicate point IDs
                     .dedup_by(|(_, record_a), (_, record_b)| record_a.id == record_b.id)
-                    .map(|(_, record)| api::rest::Record::from(record))
                     .take(limit)
+                    .map(|(point, record)| {
+                        last_ordered_point = Some(point);
+                        api::rest::Record::from(record)
+                    })
                     .collect_vec()
             }
         };
 
+        let next_page_cursor = match last_ordered_point {
+            Some(point) if points.len() == limit => Some(OrderByCursor::from(point).encode()),
+            _ => None,
+        };
+
         let next_page_offset = if points.len() < limit || order_by.is_some() {
             // This was the last page
             None
@@ -330,12 +411,156 @@ impl Collection {
             // remove extra point, it would be a first point of the next page
             Some(points.pop().unwrap().id)
         };
+
+        let deleted_points = if request.include_deleted.unwrap_or(false) {
+            let deleted_points = self
+                .scroll_trash(
+                    id_offset,
+                    next_page_offset,
+                    request.filter.as_ref(),
+                    &with_payload_interface,
+                    &with_vector,
+                    shard_selection,
+                )
+                .await?;
+            Some(deleted_points)
+        } else {
+            None
+        };
+
         Ok(ScrollResult {
             points,
             next_page_offset,
+            next_page_cursor,
+            deleted_points,
         })
     }
 
+    /// Deleted points from the trash of active replicas, ready to be inserted again, with the
+    /// shard keys of their shards
+    pub async fn trashed_points(
+        &self,
+        point_ids: &[PointIdType],
+    ) -> CollectionResult<Vec<(PointStruct, Option<ShardKey>)>> {
+        self.check_soft_delete_enabled().await?;
+
+        let shards_holder = self.shards_holder.read().await;
+        let shard_to_key = shards_holder.get_shard_id_to_key_mapping();
+        let mut trashed: Vec<(TrashedPoint, Option<ShardKey>)> = Vec::new();
+        for (shard_id, replica_set) in shards_holder.get_shards() {
+            if let Some(points) = replica_set.trashed_points(point_ids).await? {
+                let shard_key = shard_to_key.get(shard_id);
+                trashed.extend(points.into_iter().map(|point| (point, shard_key.cloned())));
+            }
+        }
+
+        // Point may be deleted from several shards while it is moved, the latest deletion wins
+        trashed.sort_unstable_by(|(a, _), (b, _)| {
+            (a.point.id, b.deleted_at).cmp(&(b.point.id, a.deleted_at))
+        });
+        trashed.dedup_by(|(a, _), (b, _)| a.point.id == b.point.id);
+
+        if let Some(&missed_point_id) = point_ids.iter().find(|id| {
+            trashed
+                .binary_search_by_key(id, |(trashed, _)| &trashed.point.id)
+                .is_err()
+        }) {
+            return Err(CollectionError::PointNotFound { missed_point_id });
+        }
+
+        Ok(trashed
+            .into_iter()
+            .map(|(trashed, shard_key)| (trashed.point, shard_key))
+            .collect())
+    }
+
+    /// Deleted points with the given ids in the local replica of the shard, for other peers
+    ///
+    /// Returns `None` if deleted points are not kept.
+    pub async fn local_trashed_points(
+        &self,
+        shard_id: ShardId,
+        point_ids: &[PointIdType],
+    ) -> CollectionResult<Option<Vec<TrashedPoint>>> {
+        let shards_holder = self.shards_holder.read().await;
+        let Some(replica_set) = shards_holder.get_shard(&shard_id) else {
+            return Err(CollectionError::NotFound {
+                what: format!("Shard {shard_id}"),
+            });
+        };
+
+        replica_set.local_trashed_points(point_ids).await
+    }
+
+    /// Deleted points with ids in `from..to`, which match the filter, in the local replica of
+    /// the shard, for other peers
+    pub async fn local_scan_trash(
+        &self,
+        shard_id: ShardId,
+        from: Option<PointIdType>,
+        to: Option<PointIdType>,
+        filter: Option<&Filter>,
+    ) -> CollectionResult<Vec<TrashedPoint>> {
+        let shards_holder = self.shards_holder.read().await;
+        let Some(replica_set) = shards_holder.get_shard(&shard_id) else {
+            return Err(CollectionError::NotFound {
+                what: format!("Shard {shard_id}"),
+            });
+        };
+
+        replica_set.local_scan_trash(from, to, filter).await
+    }
+
+    async fn check_soft_delete_enabled(&self) -> CollectionResult<()> {
+        let params = &self.collection_config.read().await.params;
+        if params.soft_delete_retention_sec.is_none() {
+            return Err(CollectionError::bad_request(format!(
+                "Soft delete is not enabled for collection {}, set `soft_delete_retention_sec` in collection params",
+                self.name(),
+            )));
+        }
+        Ok(())
+    }
+
+    /// Deleted points from the trash of active replicas, with ids in `from..to`
+    async fn scroll_trash(
+        &self,
+        from: Option<PointIdType>,
+        to: Option<PointIdType>,
+        filter: Option<&Filter>,
+        with_payload_interface: &WithPayloadInterface,
+        with_vector: &WithVector,
+        shard_selection: &ShardSelectorInternal,
+    ) -> CollectionResult<Vec<DeletedRecord>> {
+        self.check_soft_delete_enabled().await?;
+
+        let shards_holder = self.shards_holder.read().await;
+        let mut trashed = Vec::new();
+        for (shard, _shard_key) in shards_holder.select_shards(shard_selection)? {
+            trashed.extend(shard.scan_trash(from, to, filter).await?);
+        }
+
+        let with_payload = WithPayload::from(with_payload_interface);
+        let deleted_points = trashed
+            .into_iter()
+            .sorted_unstable_by_key(|trashed| trashed.point.id)
+            .dedup_by(|a, b| a.point.id == b.point.id)
+            .map(|trashed| {
+                let mut record = DeletedRecord::from(trashed);
+                record.payload = match (&with_payload.payload_selector, record.payload) {
+                    _ if !with_payload.enable => None,
+                    (Some(selector), Some(payload)) => Some(selector.process(payload)),
+                    (_, payload) => payload,
+                };
+                if !with_vector.is_enabled() {
+                    record.vector = None;
+                }
+                record
+            })
+            .collect();
+        Ok(deleted_points)
+    }
+
     pub async fn count(
         &self,
         mut request: CountRequestInternal,
@@ -351,7 +576,7 @@ impl Collection {
         let shards = shards_holder.select_shards(shard_selection)?;
 
         let request = Arc::new(request);
-        let mut requests: futures::stream::FuturesUnordered<_> = shards
+        let mut requests: FuturesUnordered<_> = shards
             .into_iter()
             // `count` requests received through internal gRPC *always* have `shard_selection`
             .map(|(shard, _shard_key)| {
@@ -363,13 +588,123 @@ impl Collection {
             })
             .collect();
 
-        let mut count = 0;
+        let mut count = Reconciled::new(0);
+
+        while let Some(response) = requests.try_next().await? {
+            let response = Reconciled {
+                value: response.value.count,
+                consistent: response.consistent,
+            };
+            count.merge(response, |count, shard_count| *count += shard_count);
+        }
+
+        Ok(CountResult {
+            count: count.value,
+            consistent: count.consistent,
+        })
+    }
+
+    /// Distinct values of the payload field, merged across the selected shards
+    pub async fn count_distinct(
+        &self,
+        mut request: CountDistinctRequestInternal,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: &ShardSelectorInternal,
+    ) -> CollectionResult<Reconciled<DistinctValues>> {
+        merge_filters(
+            &mut request.filter,
+            self.shards_holder.read().await.resharding_filter(),
+        );
+
+        let shards_holder = self.shards_holder.read().await;
+        let shards = shards_holder.select_shards(shard_selection)?;
+
+        let request = Arc::new(request);
+        let mut requests: FuturesUnordered<_> = shards
+            .into_iter()
+            .map(|(shard, _shard_key)| {
+                shard.count_distinct(
+                    request.clone(),
+                    read_consistency,
+                    shard_selection.is_shard_id(),
+                )
+            })
+            .collect();
+
+        let mut distinct = Reconciled::new(DistinctValues::default());
+
+        while let Some(response) = requests.try_next().await? {
+            distinct.merge(response, DistinctValues::merge);
+        }
+
+        Ok(distinct)
+    }
+
+    /// Distribution of the numeric payload field, merged across the selected shards
+    pub async fn histogram(
+        &self,
+        request: HistogramRequestInternal,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: &ShardSelectorInternal,
+    ) -> CollectionResult<ValueDistribution> {
+        let shards_holder = self.shards_holder.read().await;
+        let shards = shards_holder.select_shards(shard_selection)?;
+
+        let request = Arc::new(request);
+        let mut requests: FuturesUnordered<_> = shards
+            .into_iter()
+            .map(|(shard, _shard_key)| {
+                shard.histogram(
+                    request.clone(),
+                    read_consistency,
+                    shard_selection.is_shard_id(),
+                )
+            })
+            .collect();
+
+        let mut distribution = ValueDistribution::default();
 
         while let Some(response) = requests.try_next().await? {
-            count += response.count;
+            distribution.merge(response);
         }
 
-        Ok(CountResult { count })
+        Ok(distribution)
+    }
+
+    /// Statistics of the numeric payload field, merged across the selected shards
+    pub async fn field_stats(
+        &self,
+        mut request: FieldStatsRequestInternal,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: &ShardSelectorInternal,
+    ) -> CollectionResult<NumericStats> {
+        merge_filters(
+            &mut request.filter,
+            self.shards_holder.read().await.resharding_filter(),
+        );
+
+        let shards_holder = self.shards_holder.read().await;
+        let shards = shards_holder.select_shards(shard_selection)?;
+
+        let request = Arc::new(request);
+        let mut requests: FuturesUnordered<_> = shards
+            .into_iter()
+            .map(|(shard, _shard_key)| {
+                shard.field_stats(
+                    request.clone(),
+                    read_consistency,
+                    shard_selection.is_shard_id(),
+                )
+            })
+            .collect();
+
+        let mut stats = NumericStats::default();
+
+        while let Some(response) = requests.try_next().await? {
+            stats.merge(response);
+        }
+
+        Ok(stats)
     }
 
     pub async fn retrieve(
@@ -377,6 +712,19 @@ impl Collection {
         request: PointRequestInternal,
         read_consistency: Option<ReadConsistency>,
         shard_selection: &ShardSelectorInternal,
+    ) -> CollectionResult<Vec<Record>> {
+        self.retrieve_from_shards(request, read_consistency, shard_selection, None)
+            .await
+    }
+
+    /// Same as [`Collection::retrieve`], but if `shard_ids` are given, only these shards of the
+    /// selection are read
+    pub(crate) async fn retrieve_from_shards(
+        &self,
+        request: PointRequestInternal,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: &ShardSelectorInternal,
+        shard_ids: Option<&HashSet<ShardId>>,
     ) -> CollectionResult<Vec<Record>> {
         let with_payload_interface = request
             .with_payload
@@ -395,26 +743,29 @@ impl Collection {
             resharding_filter = shard_holder.resharding_filter_impl();
 
             let target_shards = shard_holder.select_shards(shard_selection)?;
-            let retrieve_futures = target_shards.into_iter().map(|(shard, shard_key)| {
-                let shard_key = shard_key.cloned();
-                shard
-                    .retrieve(
-                        request.clone(),
-                        &with_payload,
-                        &request.with_vector,
-                        read_consistency,
-                        shard_selection.is_shard_id(),
-                    )
-                    .and_then(move |mut records| async move {
-                        if shard_key.is_none() {
-                            return Ok(records);
-                        }
-                        for point in &mut records {
-                            point.shard_key.clone_from(&shard_key);
-                        }
-                        Ok(records)
-                    })
-            });
+            let retrieve_futures = target_shards
+                .into_iter()
+                .filter(|(shard, _)| shard_ids.map_or(true, |ids| ids.contains(&shard.shard_id)))
+                .map(|(shard, shard_key)| {
+                    let shard_key = shard_key.cloned();
+                    shard
+                        .retrieve(
+                            request.clone(),
+                            &with_payload,
+                            &request.with_vector,
+                            read_consistency,
+                            shard_selection.is_shard_id(),
+                        )
+                        .and_then(move |mut records| async move {
+                            if shard_key.is_none() {
+                                return Ok(records);
+                            }
+                            for point in &mut records {
+                                point.shard_key.clone_from(&shard_key);
+                            }
+                            Ok(records)
+                        })
+                });
 
             future::try_join_all(retrieve_futures).await?
         };
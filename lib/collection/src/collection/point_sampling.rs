@@ -0,0 +1,141 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom as _;
+use rand::{Rng, SeedableRng};
+use segment::types::{Filter, PointIdType, WithPayloadInterface, WithVector};
+
+use super::Collection;
+use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::{
+    CollectionResult, PointRequestInternal, Record, ScrollRequestInternal,
+};
+
+/// Number of ids, read per page while sampling
+const SAMPLE_PAGE_SIZE: usize = 10_000;
+
+impl Collection {
+    /// Uniform random sample of points over all selected shards, in random order
+    ///
+    /// Ids of all points, matching the filter, are read to select the sample, which keeps
+    /// the sample unbiased by the order of ids. Payloads and vectors are only read for the
+    /// sampled points.
+    pub(super) async fn sample_points(
+        &self,
+        sample: usize,
+        filter: Option<Filter>,
+        with_payload: WithPayloadInterface,
+        with_vector: WithVector,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: &ShardSelectorInternal,
+    ) -> CollectionResult<Vec<Record>> {
+        if sample == 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut rng = StdRng::from_entropy();
+        let mut sampled_ids = ReservoirSample::new(sample);
+        let mut offset = None;
+        loop {
+            let request = ScrollRequestInternal {
+                offset,
+                limit: Some(SAMPLE_PAGE_SIZE),
+                filter: filter.clone(),
+                with_payload: Some(WithPayloadInterface::Bool(false)),
+                with_vector: WithVector::Bool(false),
+                ..Default::default()
+            };
+            let page = self
+                .scroll_by(request, read_consistency, shard_selection)
+                .await?;
+            for point in page.points {
+                sampled_ids.add(point.id, &mut rng);
+            }
+            match page.next_page_offset {
+                Some(next_page_offset) => offset = Some(next_page_offset),
+                None => break,
+            }
+        }
+
+        let request = PointRequestInternal {
+            ids: sampled_ids.into_vec(),
+            with_payload: Some(with_payload),
+            with_vector,
+        };
+        let mut points = self
+            .retrieve(request, read_consistency, shard_selection)
+            .await?;
+        // Records are grouped by shards, don't let callers depend on it
+        points.shuffle(&mut rng);
+        Ok(points)
+    }
+}
+
+/// Uniform sample of a fixed size from a sequence of unknown length, see Algorithm R
+struct ReservoirSample {
+    size: usize,
+    seen: usize,
+    ids: Vec<PointIdType>,
+}
+
+impl ReservoirSample {
+    fn new(size: usize) -> Self {
+        Self {
+            size,
+            seen: 0,
+            ids: Vec::with_capacity(size.min(SAMPLE_PAGE_SIZE)),
+        }
+    }
+
+    fn add(&mut self, id: PointIdType, rng: &mut impl Rng) {
+        self.seen += 1;
+        if self.ids.len() < self.size {
+            self.ids.push(id);
+        } else {
+            let position = rng.gen_range(0..self.seen);
+            if position < self.size {
+                self.ids[position] = id;
+            }
+        }
+    }
+
+    fn into_vec(self) -> Vec<PointIdType> {
+        self.ids
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reservoir_sample_is_uniform() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut counts = [0usize; 10];
+        for _ in 0..10_000 {
+            let mut sample = ReservoirSample::new(2);
+            for id in 0..10 {
+                sample.add(PointIdType::NumId(id), &mut rng);
+            }
+            let ids = sample.into_vec();
+            assert_eq!(ids.len(), 2);
+            for id in ids {
+                let PointIdType::NumId(id) = id else {
+                    unreachable!()
+                };
+                counts[id as usize] += 1;
+            }
+        }
+        // Each id is expected to be sampled 2000 times
+        assert!(counts.iter().all(|count| (1_800..=2_200).contains(count)));
+    }
+
+    #[test]
+    fn test_reservoir_sample_keeps_short_sequence() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut sample = ReservoirSample::new(5);
+        for id in 0..3 {
+            sample.add(PointIdType::NumId(id), &mut rng);
+        }
+        assert_eq!(sample.into_vec().len(), 3);
+    }
+}
@@ -1,27 +1,33 @@
 use std::sync::Arc;
 use std::time::Duration;
 
+use common::hardware_counter::HardwareCounterCell;
 use futures::{future, TryFutureExt};
 use itertools::{Either, Itertools};
 use segment::common::reciprocal_rank_fusion::rrf_scoring;
-use segment::types::{Order, ScoredPoint};
+use segment::common::weighted_sum_fusion::weighted_sum_scoring;
+use segment::types::{Order, ScoredPoint, SearchParams};
 use segment::utils::scored_point_ties::ScoredPointTies;
 use tokio::time::Instant;
 
 use super::Collection;
 use crate::common::batching::batch_requests;
 use crate::common::fetch_vectors::resolve_referenced_vectors_batch;
+use crate::common::slow_query_log::SlowQueryRequest;
 use crate::common::transpose_iterator::transposed_iter;
 use crate::operations::consistency_params::ReadConsistency;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
-use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::types::{CollectionError, CollectionResult, PartialResponse};
 use crate::operations::universal_query::collection_query::CollectionQueryRequest;
 use crate::operations::universal_query::shard_query::{
     Fusion, ScoringQuery, ShardQueryRequest, ShardQueryResponse,
 };
+use crate::operations::vector_truncation::truncate_shard_query_vectors;
 
 struct IntermediateQueryInfo<'a> {
     scoring_query: Option<&'a ScoringQuery>,
+    /// Search params, which the query is scored with
+    params: Option<&'a SearchParams>,
     /// Limit + offset
     take: usize,
 }
@@ -34,7 +40,8 @@ impl Collection {
         read_consistency: Option<ReadConsistency>,
         shard_selection: &ShardSelectorInternal,
         timeout: Option<Duration>,
-    ) -> CollectionResult<Vec<Vec<ShardQueryResponse>>> {
+        hw_counter: &HardwareCounterCell,
+    ) -> CollectionResult<PartialResponse<Vec<Vec<ShardQueryResponse>>>> {
         // query all shards concurrently
         let shard_holder = self.shards_holder.read().await;
         let target_shards = shard_holder.select_shards(shard_selection)?;
@@ -46,12 +53,14 @@ impl Collection {
                     read_consistency,
                     shard_selection.is_shard_id(),
                     timeout,
+                    hw_counter,
                 )
                 .and_then(move |mut shard_responses| async move {
                     if shard_key.is_none() {
                         return Ok(shard_responses);
                     }
                     shard_responses
+                        .value
                         .iter_mut()
                         .flatten()
                         .flatten()
@@ -60,17 +69,42 @@ impl Collection {
                     Ok(shard_responses)
                 })
         });
-        future::try_join_all(all_searches).await
+        let all_responses = future::try_join_all(all_searches).await?;
+
+        let timed_out = all_responses.iter().any(|responses| responses.timed_out);
+        Ok(PartialResponse {
+            value: all_responses
+                .into_iter()
+                .map(|responses| responses.value)
+                .collect(),
+            timed_out,
+            shards: Vec::new(),
+        })
     }
 
     /// This function is used to query the collection. It will return a list of scored points.
     async fn do_query_batch(
         &self,
-        requests_batch: Vec<ShardQueryRequest>,
+        mut requests_batch: Vec<ShardQueryRequest>,
         read_consistency: Option<ReadConsistency>,
         shard_selection: ShardSelectorInternal,
         timeout: Option<Duration>,
-    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        hw_counter: &HardwareCounterCell,
+    ) -> CollectionResult<PartialResponse<Vec<Vec<ScoredPoint>>>> {
+        {
+            let collection_config = self.collection_config.read().await;
+            for request in &mut requests_batch {
+                truncate_shard_query_vectors(&collection_config.params.vectors, request)?;
+            }
+        }
+
+        // Hardware usage is only collected if it might end up in the slow query log
+        let hw_counter = if self.slow_query_log.is_enabled() && !hw_counter.is_enabled() {
+            HardwareCounterCell::new()
+        } else {
+            hw_counter.clone()
+        };
+
         let instant = Instant::now();
 
         let requests_batch = Arc::new(requests_batch);
@@ -81,10 +115,11 @@ impl Collection {
                 read_consistency,
                 &shard_selection,
                 timeout,
+                &hw_counter,
             )
             .await?;
 
-        let results_f = transposed_iter(all_shards_results)
+        let results_f = transposed_iter(all_shards_results.value)
             .zip(requests_batch.iter())
             .map(|(shards_results, request)| async {
                 // shards_results shape: [num_shards, num_intermediate_results, num_points]
@@ -96,6 +131,10 @@ impl Collection {
                     // If the root query is a Fusion, the returned results correspond to each the prefetches.
                     match fusion {
                         Fusion::Rrf => rrf_scoring(merged_intermediates),
+                        Fusion::WeightedSum {
+                            weights,
+                            normalization,
+                        } => weighted_sum_scoring(merged_intermediates, weights, *normalization),
                     }
                 } else {
                     // Otherwise, it will be a list with a single list of scored points.
@@ -114,13 +153,24 @@ impl Collection {
                     .collect();
 
                 let filter_refs = request.filter_refs();
-                self.post_process_if_slow_request(instant.elapsed(), filter_refs);
+                self.post_process_request(instant.elapsed(), filter_refs);
 
                 Ok::<_, CollectionError>(result)
             });
         let results = future::try_join_all(results_f).await?;
 
-        Ok(results)
+        self.record_if_slow_query(
+            instant.elapsed(),
+            requests_batch.iter().map(SlowQueryRequest::from).collect(),
+            vec![],
+            &hw_counter,
+        );
+
+        Ok(PartialResponse {
+            value: results,
+            timed_out: all_shards_results.timed_out,
+            shards: Vec::new(),
+        })
     }
 
     /// To be called on the user-responding instance. Resolves ids into vectors, and merges the results from local and remote shards.
@@ -131,7 +181,8 @@ impl Collection {
         requests_batch: Vec<(CollectionQueryRequest, ShardSelectorInternal)>,
         read_consistency: Option<ReadConsistency>,
         timeout: Option<Duration>,
-    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        hw_counter: &HardwareCounterCell,
+    ) -> CollectionResult<PartialResponse<Vec<Vec<ScoredPoint>>>> {
         // Turn ids into vectors, if necessary
         let ids_to_vectors = resolve_referenced_vectors_batch(
             &requests_batch,
@@ -165,19 +216,24 @@ impl Collection {
                     read_consistency,
                     shard_selection,
                     timeout,
+                    hw_counter,
                 ));
 
                 Ok(())
             },
         )?;
 
-        let results = future::try_join_all(futures)
-            .await?
-            .into_iter()
-            .flatten()
-            .collect();
-
-        Ok(results)
+        let results = future::try_join_all(futures).await?;
+
+        let timed_out = results.iter().any(|results| results.timed_out);
+        Ok(PartialResponse {
+            value: results
+                .into_iter()
+                .flat_map(|results| results.value)
+                .collect(),
+            timed_out,
+            shards: Vec::new(),
+        })
     }
 
     /// To be called on the remote instance. Only used for the internal service.
@@ -189,7 +245,8 @@ impl Collection {
         requests: Vec<ShardQueryRequest>,
         shard_selection: &ShardSelectorInternal,
         timeout: Option<Duration>,
-    ) -> CollectionResult<Vec<ShardQueryResponse>> {
+        hw_counter: &HardwareCounterCell,
+    ) -> CollectionResult<PartialResponse<Vec<ShardQueryResponse>>> {
         let requests_arc = Arc::new(requests);
 
         // Results from all shards
@@ -200,10 +257,11 @@ impl Collection {
                 None,
                 shard_selection,
                 timeout,
+                hw_counter,
             )
             .await?;
 
-        let merged_f = transposed_iter(all_shards_results)
+        let merged_f = transposed_iter(all_shards_results.value)
             .zip(requests_arc.iter())
             .map(|(shards_results, request)| async {
                 // shards_results shape: [num_shards, num_intermediate_results, num_points]
@@ -211,7 +269,11 @@ impl Collection {
                     .await
             });
         let merged = futures::future::try_join_all(merged_f).await?;
-        Ok(merged)
+        Ok(PartialResponse {
+            value: merged,
+            timed_out: all_shards_results.timed_out,
+            shards: Vec::new(),
+        })
     }
 
     /// Merges the results in each shard for each intermediate query.
@@ -243,7 +305,11 @@ impl Collection {
             query_infos.into_iter().zip(all_shards_result_by_transposed)
         {
             // `shards_results` shape: [num_shards, num_scored_points]
-            let order = ScoringQuery::order(query_info.scoring_query, &collection_params)?;
+            let order = ScoringQuery::order(
+                query_info.scoring_query,
+                query_info.params,
+                &collection_params,
+            )?;
 
             // Equivalent to:
             //
@@ -297,6 +363,7 @@ fn intermediate_query_infos(request: &ShardQueryRequest) -> Vec<IntermediateQuer
             .iter()
             .map(|prefetch| IntermediateQueryInfo {
                 scoring_query: prefetch.query.as_ref(),
+                params: prefetch.params.as_ref(),
                 take: prefetch.limit,
             })
             .collect_vec()
@@ -304,6 +371,7 @@ fn intermediate_query_infos(request: &ShardQueryRequest) -> Vec<IntermediateQuer
         // Otherwise, we expect the root result
         vec![IntermediateQueryInfo {
             scoring_query: request.query.as_ref(),
+            params: request.params.as_ref(),
             take: request.offset + request.limit,
         }]
     }
@@ -0,0 +1,125 @@
+use std::time::Duration;
+
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+use super::Collection;
+use crate::operations::types::{CollectionError, CollectionResult, QuotaUsage};
+use crate::operations::CollectionUpdateOperations;
+
+/// Measured usage is reused for this long, as measuring the segments on every update is costly
+const QUOTA_USAGE_CACHE_TIME: Duration = Duration::from_secs(5);
+
+/// Latest measured usage of the collection, with the time of the measurement
+#[derive(Default)]
+pub(super) struct QuotaUsageCache(Mutex<Option<(Instant, Option<QuotaUsage>)>>);
+
+impl Collection {
+    /// Estimated resource usage of the whole collection
+    ///
+    /// Usage is measured on the shards, located on this peer. Shards without a local replica are
+    /// assumed to have the average usage of the local ones. Returns `None`, if there are no local
+    /// shards.
+    pub async fn quota_usage(&self) -> CollectionResult<Option<QuotaUsage>> {
+        let mut cache = self.quota_usage_cache.0.lock().await;
+        if let Some((measured_at, usage)) = *cache {
+            if measured_at.elapsed() < QUOTA_USAGE_CACHE_TIME {
+                return Ok(usage);
+            }
+        }
+
+        let usage = self.measure_quota_usage().await?;
+        *cache = Some((Instant::now(), usage));
+        Ok(usage)
+    }
+
+    async fn measure_quota_usage(&self) -> CollectionResult<Option<QuotaUsage>> {
+        let shards_holder = self.shards_holder.read().await;
+        let mut total = QuotaUsage::default();
+        let mut shards = 0;
+        let mut local_shards = 0;
+        for (_shard_id, replica_set) in shards_holder.get_shards() {
+            shards += 1;
+            if let Some(usage) = replica_set.quota_usage().await? {
+                local_shards += 1;
+                total.points_count += usage.points_count;
+                total.ram_usage_bytes += usage.ram_usage_bytes;
+                total.disk_usage_bytes += usage.disk_usage_bytes;
+            }
+        }
+
+        if local_shards == 0 {
+            return Ok(None);
+        }
+        let scale = |value: usize| value * shards / local_shards;
+        Ok(Some(QuotaUsage {
+            points_count: scale(total.points_count),
+            ram_usage_bytes: scale(total.ram_usage_bytes),
+            disk_usage_bytes: scale(total.disk_usage_bytes),
+        }))
+    }
+
+    /// Reject the operation, if it adds data to the collection, which reached any of its quotas.
+    ///
+    /// Deletes are always allowed, as they free up resources. Does nothing if strict mode is not
+    /// enabled, or no quotas are set.
+    pub async fn check_quotas(
+        &self,
+        operation: &CollectionUpdateOperations,
+    ) -> CollectionResult<()> {
+        if !operation.is_write_operation() {
+            return Ok(());
+        }
+
+        let Some(strict_mode_config) = self
+            .collection_config
+            .read()
+            .await
+            .strict_mode_config
+            .clone()
+            .filter(|config| config.is_enabled() && config.has_quotas())
+        else {
+            return Ok(());
+        };
+
+        let Some(usage) = self.quota_usage().await? else {
+            return Ok(());
+        };
+
+        let quotas = [
+            (
+                "max_points_count",
+                strict_mode_config.max_points_count,
+                usage.points_count,
+                "points",
+            ),
+            (
+                "max_ram_usage_bytes",
+                strict_mode_config.max_ram_usage_bytes,
+                usage.ram_usage_bytes,
+                "bytes in RAM",
+            ),
+            (
+                "max_disk_usage_bytes",
+                strict_mode_config.max_disk_usage_bytes,
+                usage.disk_usage_bytes,
+                "bytes on disk",
+            ),
+        ];
+        for (setting, max, used, unit) in quotas {
+            match max {
+                Some(max) if used >= max => {
+                    return Err(CollectionError::strict_mode(
+                        setting,
+                        format!(
+                            "collection uses {used} {unit}, which reaches the quota of {max}, only deletes are allowed",
+                        ),
+                    ));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,299 @@
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+use common::hardware_counter::HardwareCounterCell;
+use segment::data_types::vectors::{
+    NamedVectorStruct, Vector, VectorStructInternal, DEFAULT_VECTOR_NAME,
+};
+use segment::types::{
+    Condition, Filter, HasIdCondition, QuantizationSearchParams, ScoredPoint, SearchParams,
+    WithPayloadInterface, WithVector,
+};
+
+use super::Collection;
+use crate::common::recall_evaluation::{
+    DEFAULT_RECALL_EVALUATION_LIMIT, DEFAULT_RECALL_EVALUATION_SAMPLE,
+};
+use crate::operations::query_enum::QueryEnum;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::{
+    CollectionError, CollectionResult, CoreSearchRequest, LatencyDistribution, RecallEvaluation,
+    RecallEvaluationRequest, VectorRecall,
+};
+
+impl Collection {
+    /// Compare the approximate search with exact search, using vectors of a sample of points as
+    /// queries
+    ///
+    /// Points are sampled uniformly at random among the points, which satisfy the filter.
+    /// Queries are run one by one, so that latencies are not affected by batching.
+    pub async fn evaluate_recall(
+        &self,
+        request: RecallEvaluationRequest,
+    ) -> CollectionResult<RecallEvaluation> {
+        let RecallEvaluationRequest {
+            using,
+            sample,
+            limit,
+            filter,
+            params,
+        } = request;
+        let sample = sample.unwrap_or(DEFAULT_RECALL_EVALUATION_SAMPLE);
+        let limit = limit.unwrap_or(DEFAULT_RECALL_EVALUATION_LIMIT);
+
+        let vector_names: Vec<String> = {
+            let config = self.collection_config.read().await;
+            let dense = config.params.vectors.params_iter().map(|(name, _)| name);
+            let sparse = config
+                .params
+                .sparse_vectors
+                .iter()
+                .flat_map(|sparse| sparse.keys().map(String::as_str));
+            dense.chain(sparse).map(str::to_string).collect()
+        };
+        let vector_names = match using {
+            Some(using) if !vector_names.contains(&using) => {
+                return Err(CollectionError::bad_input(format!(
+                    "Vector `{using}` does not exist in the collection"
+                )));
+            }
+            Some(using) => vec![using],
+            None => vector_names,
+        };
+
+        let points = self
+            .sample_points(
+                sample,
+                filter.clone(),
+                WithPayloadInterface::Bool(false),
+                WithVector::Bool(true),
+                None,
+                &ShardSelectorInternal::All,
+            )
+            .await?;
+
+        let exact_params = SearchParams {
+            exact: true,
+            quantization: Some(QuantizationSearchParams {
+                ignore: true,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let mut report = RecallEvaluation::default();
+        for name in vector_names {
+            let mut evaluation = RecallCollector::default();
+            for point in &points {
+                let Some(vector) = point
+                    .vector
+                    .as_ref()
+                    .and_then(|vector| take_vector(vector, &name))
+                else {
+                    continue;
+                };
+                let query = QueryEnum::Nearest(NamedVectorStruct::new_from_vector(vector, &name));
+                // The query point is always its own nearest neighbour
+                let point_filter = Filter::merge_opts(
+                    filter.clone(),
+                    Some(Filter::new_must_not(Condition::HasId(
+                        HasIdCondition::from(HashSet::from([point.id])),
+                    ))),
+                );
+
+                let (exact, exact_latency) = self
+                    .timed_search(&query, point_filter.clone(), Some(exact_params), limit)
+                    .await?;
+                let (approximate, latency) = self
+                    .timed_search(&query, point_filter, params, limit)
+                    .await?;
+                evaluation.add_query(&exact, &approximate, exact_latency, latency);
+            }
+            report
+                .vectors
+                .insert(name, evaluation.into_vector_recall(limit));
+        }
+
+        Ok(report)
+    }
+
+    async fn timed_search(
+        &self,
+        query: &QueryEnum,
+        filter: Option<Filter>,
+        params: Option<SearchParams>,
+        limit: usize,
+    ) -> CollectionResult<(Vec<ScoredPoint>, Duration)> {
+        let request = CoreSearchRequest {
+            query: query.clone(),
+            filter,
+            params,
+            limit,
+            offset: 0,
+            with_payload: None,
+            with_vector: None,
+            score_threshold: None,
+        };
+        let start = Instant::now();
+        let result = self
+            .search(
+                request,
+                None,
+                &ShardSelectorInternal::All,
+                None,
+                &HardwareCounterCell::new(),
+            )
+            .await?;
+        Ok((result, start.elapsed()))
+    }
+
+    /// Whether this peer has the lowest id among the peers with active replicas of the collection
+    ///
+    /// Evaluation searches all shards of the collection, so scheduled evaluations are only run
+    /// by a single peer.
+    pub async fn is_recall_evaluation_coordinator(&self) -> bool {
+        let shard_holder = self.shards_holder.read().await;
+        let mut lowest_peer = None;
+        for (_shard_id, replica_set) in shard_holder.get_shards() {
+            lowest_peer = lowest_peer
+                .into_iter()
+                .chain(replica_set.active_shards().await)
+                .min();
+        }
+        lowest_peer == Some(self.this_peer_id)
+    }
+}
+
+pub(super) fn take_vector(vector: &VectorStructInternal, name: &str) -> Option<Vector> {
+    match vector {
+        VectorStructInternal::Single(vector) => {
+            (name == DEFAULT_VECTOR_NAME).then(|| Vector::Dense(vector.clone()))
+        }
+        VectorStructInternal::MultiDense(vector) => {
+            (name == DEFAULT_VECTOR_NAME).then(|| Vector::MultiDense(vector.clone()))
+        }
+        VectorStructInternal::Named(vectors) => vectors.get(name).cloned(),
+    }
+}
+
+/// Accumulates recalls and latencies of the queries to a single vector
+#[derive(Default)]
+struct RecallCollector {
+    recalls: Vec<f64>,
+    latencies: Vec<Duration>,
+    exact_latencies: Vec<Duration>,
+}
+
+impl RecallCollector {
+    fn add_query(
+        &mut self,
+        exact: &[ScoredPoint],
+        approximate: &[ScoredPoint],
+        exact_latency: Duration,
+        latency: Duration,
+    ) {
+        self.latencies.push(latency);
+        self.exact_latencies.push(exact_latency);
+        // Queries without neighbours have no defined recall
+        if let Some(recall) = recall(exact, approximate) {
+            self.recalls.push(recall);
+        }
+    }
+
+    fn into_vector_recall(self, limit: usize) -> VectorRecall {
+        let queries = self.latencies.len();
+        let (recall, min_recall) = if self.recalls.is_empty() {
+            (1.0, 1.0)
+        } else {
+            (
+                self.recalls.iter().sum::<f64>() / self.recalls.len() as f64,
+                self.recalls.iter().copied().fold(f64::INFINITY, f64::min),
+            )
+        };
+        VectorRecall {
+            queries,
+            limit,
+            recall,
+            min_recall,
+            latency: latency_distribution(self.latencies),
+            exact_latency: latency_distribution(self.exact_latencies),
+        }
+    }
+}
+
+/// Share of the exact results, which are also found by the approximate search
+pub(super) fn recall(exact: &[ScoredPoint], approximate: &[ScoredPoint]) -> Option<f64> {
+    if exact.is_empty() {
+        return None;
+    }
+    let found: HashSet<_> = approximate.iter().map(|point| point.id).collect();
+    let matched = exact
+        .iter()
+        .filter(|point| found.contains(&point.id))
+        .count();
+    Some(matched as f64 / exact.len() as f64)
+}
+
+pub(super) fn latency_distribution(mut latencies: Vec<Duration>) -> LatencyDistribution {
+    if latencies.is_empty() {
+        return LatencyDistribution::default();
+    }
+    latencies.sort_unstable();
+
+    let count = latencies.len();
+    let total: Duration = latencies.iter().sum();
+    // Nearest-rank percentile
+    let percentile = |percentile: f64| {
+        let rank = (count as f64 * percentile).ceil() as usize;
+        latencies[rank.clamp(1, count) - 1].as_secs_f64()
+    };
+
+    LatencyDistribution {
+        mean: total.as_secs_f64() / count as f64,
+        p50: percentile(0.5),
+        p99: percentile(0.99),
+        max: latencies[count - 1].as_secs_f64(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scored(ids: &[u64]) -> Vec<ScoredPoint> {
+        ids.iter()
+            .map(|id| ScoredPoint {
+                id: (*id).into(),
+                version: 0,
+                score: 0.0,
+                payload: None,
+                vector: None,
+                shard_key: None,
+                order_value: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_recall() {
+        assert_eq!(recall(&scored(&[]), &scored(&[1])), None);
+        assert_eq!(
+            recall(&scored(&[1, 2, 3, 4]), &scored(&[4, 2, 5])),
+            Some(0.5)
+        );
+
+        let mut collector = RecallCollector::default();
+        let ms = Duration::from_millis;
+        collector.add_query(&scored(&[1, 2]), &scored(&[1, 2]), ms(10), ms(1));
+        collector.add_query(&scored(&[1, 2]), &scored(&[1, 3]), ms(20), ms(3));
+        collector.add_query(&scored(&[]), &scored(&[]), ms(30), ms(2));
+
+        let report = collector.into_vector_recall(2);
+        assert_eq!(report.queries, 3);
+        assert_eq!(report.recall, 0.75);
+        assert_eq!(report.min_recall, 0.5);
+        assert_eq!(report.latency.p50, 0.002);
+        assert_eq!(report.latency.max, 0.003);
+        assert_eq!(report.exact_latency.p99, 0.03);
+    }
+}
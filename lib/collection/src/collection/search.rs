@@ -3,19 +3,28 @@ use std::mem;
 use std::sync::Arc;
 use std::time::Duration;
 
-use futures::{future, TryFutureExt};
+use api::rest::{HardwareUsage, ShardReadStatus};
+use chrono::Utc;
+use common::hardware_counter::HardwareCounterCell;
+use futures::{future, FutureExt, TryFutureExt};
 use itertools::{Either, Itertools};
+use segment::data_types::search_plan::ShardSearchPlan;
 use segment::data_types::vectors::VectorStructInternal;
 use segment::types::{
-    ExtendedPointId, Filter, Order, ScoredPoint, WithPayloadInterface, WithVector,
+    ExtendedPointId, Filter, Order, PayloadFieldSchema, PayloadKeyType, ScoredPoint, ShardKey,
+    WithPayloadInterface, WithVector,
 };
 use tokio::time::Instant;
 
 use super::Collection;
+use crate::common::index_advisor::IndexRecommendation;
+use crate::common::slow_query_log::{SlowQueryEntry, SlowQueryRequest};
 use crate::events::SlowQueryEvent;
 use crate::operations::consistency_params::ReadConsistency;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::*;
+use crate::operations::vector_truncation::truncate_query_vectors;
+use crate::shards::shard::ShardId;
 
 impl Collection {
     pub async fn search(
@@ -24,6 +33,7 @@ impl Collection {
         read_consistency: Option<ReadConsistency>,
         shard_selection: &ShardSelectorInternal,
         timeout: Option<Duration>,
+        hw_counter: &HardwareCounterCell,
     ) -> CollectionResult<Vec<ScoredPoint>> {
         if request.limit == 0 {
             return Ok(vec![]);
@@ -33,9 +43,15 @@ impl Collection {
             searches: vec![request],
         };
         let results = self
-            .do_core_search_batch(request_batch, read_consistency, shard_selection, timeout)
+            .do_core_search_batch(
+                request_batch,
+                read_consistency,
+                shard_selection,
+                timeout,
+                hw_counter,
+            )
             .await?;
-        Ok(results.into_iter().next().unwrap())
+        Ok(results.value.into_iter().next().unwrap())
     }
 
     pub async fn core_search_batch(
@@ -44,11 +60,44 @@ impl Collection {
         read_consistency: Option<ReadConsistency>,
         shard_selection: ShardSelectorInternal,
         timeout: Option<Duration>,
+        hw_counter: &HardwareCounterCell,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        let results = self
+            .core_search_batch_with_shards(
+                request,
+                read_consistency,
+                shard_selection,
+                timeout,
+                hw_counter,
+            )
+            .await?;
+        Ok(results.value)
+    }
+
+    /// Same as [`Collection::core_search_batch`], but also reports whether any search was
+    /// stopped at the timeout, and the status of each shard, if the read consistency is `partial`
+    pub async fn core_search_batch_with_shards(
+        &self,
+        mut request: CoreSearchRequestBatch,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: ShardSelectorInternal,
+        timeout: Option<Duration>,
+        hw_counter: &HardwareCounterCell,
+    ) -> CollectionResult<PartialResponse<Vec<Vec<ScoredPoint>>>> {
         // shortcuts batch if all requests with limit=0
         if request.searches.iter().all(|s| s.limit == 0) {
-            return Ok(vec![]);
+            return Ok(PartialResponse::new(vec![]));
+        }
+        {
+            let collection_config = self.collection_config.read().await;
+            for search in &mut request.searches {
+                search.query = truncate_query_vectors(
+                    &collection_config.params.vectors,
+                    search.query.clone(),
+                )?;
+            }
         }
+        let partial = read_consistency.is_some_and(|consistency| consistency.is_partial());
         // A factor which determines if we need to use the 2-step search or not
         // Should be adjusted based on usage statistics.
         const PAYLOAD_TRANSFERS_FACTOR_THRESHOLD: usize = 10;
@@ -100,9 +149,20 @@ impl Collection {
                     read_consistency,
                     &shard_selection,
                     timeout,
+                    hw_counter,
                 )
                 .await?;
+            // Payload of the points can only be fetched from the shards, which responded
+            let ok_shard_ids: Option<HashSet<ShardId>> = partial.then(|| {
+                without_payload_results
+                    .shards
+                    .iter()
+                    .filter(|status| status.ok)
+                    .map(|status| status.shard_id)
+                    .collect()
+            });
             let filled_results = without_payload_results
+                .value
                 .into_iter()
                 .zip(request.clone().searches.into_iter())
                 .map(|(without_payload_result, req)| {
@@ -112,14 +172,23 @@ impl Collection {
                         req.with_vector.unwrap_or_default(),
                         read_consistency,
                         &shard_selection,
+                        ok_shard_ids.as_ref(),
                     )
                 });
-            future::try_join_all(filled_results).await
+            Ok(PartialResponse {
+                value: future::try_join_all(filled_results).await?,
+                timed_out: without_payload_results.timed_out,
+                shards: without_payload_results.shards,
+            })
         } else {
-            let result = self
-                .do_core_search_batch(request, read_consistency, &shard_selection, timeout)
-                .await?;
-            Ok(result)
+            self.do_core_search_batch(
+                request,
+                read_consistency,
+                &shard_selection,
+                timeout,
+                hw_counter,
+            )
+            .await
         }
     }
 
@@ -129,7 +198,8 @@ impl Collection {
         read_consistency: Option<ReadConsistency>,
         shard_selection: &ShardSelectorInternal,
         timeout: Option<Duration>,
-    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        hw_counter: &HardwareCounterCell,
+    ) -> CollectionResult<PartialResponse<Vec<Vec<ScoredPoint>>>> {
         if let Some(resharding_filter) = self.shards_holder.read().await.resharding_filter() {
             for search in &mut request.searches {
                 match &mut search.filter {
@@ -146,34 +216,172 @@ impl Collection {
 
         let request = Arc::new(request);
 
+        // Plans and hardware usage are only collected if they might end up in the slow query log
+        let collect_plans = self.slow_query_log.is_enabled();
+        let hw_counter = if collect_plans && !hw_counter.is_enabled() {
+            HardwareCounterCell::new()
+        } else {
+            hw_counter.clone()
+        };
+
         let instant = Instant::now();
 
+        let partial = read_consistency.is_some_and(|consistency| consistency.is_partial());
+
         // query all shards concurrently
-        let all_searches_res = {
+        let (all_searches_res, shard_plans, shard_statuses): (Vec<_>, Vec<_>, _) = {
             let shard_holder = self.shards_holder.read().await;
             let target_shards = shard_holder.select_shards(shard_selection)?;
             let all_searches = target_shards.iter().map(|(shard, shard_key)| {
                 let shard_key = shard_key.cloned();
+                let shard_id = shard.shard_id;
+                let request = Arc::clone(&request);
+                let hw_counter = &hw_counter;
+                async move {
+                    let result = async {
+                        let (mut records, segments) = if collect_plans {
+                            shard
+                                .core_search_explain(
+                                    request,
+                                    read_consistency,
+                                    shard_selection.is_shard_id(),
+                                    timeout,
+                                    hw_counter,
+                                )
+                                .await?
+                        } else {
+                            let records = shard
+                                .core_search(
+                                    request,
+                                    read_consistency,
+                                    shard_selection.is_shard_id(),
+                                    timeout,
+                                    hw_counter,
+                                )
+                                .await?;
+                            (records, None)
+                        };
+                        if shard_key.is_some() {
+                            for batch in &mut records.value {
+                                for point in batch {
+                                    point.shard_key.clone_from(&shard_key);
+                                }
+                            }
+                        }
+                        Ok::<_, CollectionError>((records, ShardSearchPlan { shard_id, segments }))
+                    }
+                    .await;
+                    (shard_id, shard_key, result)
+                }
+            });
+            if partial {
+                let (results, shard_statuses) =
+                    split_partial_results(future::join_all(all_searches).await)?;
+                let (all_searches_res, shard_plans) = results.into_iter().unzip();
+                (all_searches_res, shard_plans, shard_statuses)
+            } else {
+                let all_searches = all_searches.map(|search| search.map(|(_, _, result)| result));
+                let (all_searches_res, shard_plans) = future::try_join_all(all_searches)
+                    .await?
+                    .into_iter()
+                    .unzip();
+                (all_searches_res, shard_plans, Vec::new())
+            }
+        };
+
+        let timed_out = all_searches_res
+            .iter()
+            .any(|records: &PartialResponse<_>| records.timed_out);
+        let all_searches_res = all_searches_res
+            .into_iter()
+            .map(|records| records.value)
+            .collect();
+
+        let result = self
+            .merge_from_shards(
+                all_searches_res,
+                Arc::clone(&request),
+                !shard_selection.is_shard_id(),
+            )
+            .await;
+
+        let duration = instant.elapsed();
+
+        let filters_refs = request.searches.iter().map(|req| req.filter.as_ref());
+
+        self.post_process_request(duration, filters_refs);
+
+        self.record_if_slow_query(
+            duration,
+            request
+                .searches
+                .iter()
+                .map(SlowQueryRequest::from)
+                .collect(),
+            shard_plans,
+            &hw_counter,
+        );
+
+        result.map(|result| PartialResponse {
+            value: result,
+            timed_out,
+            shards: shard_statuses,
+        })
+    }
+
+    /// Execute a single search and report how each shard and segment executed it.
+    ///
+    /// Unlike regular search, payload and vectors are always fetched together with the
+    /// scores, so the reported plans describe exactly the work done for this request.
+    pub async fn explain_search(
+        &self,
+        mut request: CoreSearchRequest,
+        shard_selection: &ShardSelectorInternal,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<(Vec<ScoredPoint>, Vec<ShardSearchPlan>)> {
+        if let Some(resharding_filter) = self.shards_holder.read().await.resharding_filter() {
+            request.filter = Some(match &request.filter {
+                Some(filter) => filter.merge(&resharding_filter),
+                None => resharding_filter,
+            });
+        }
+
+        let request = Arc::new(CoreSearchRequestBatch {
+            searches: vec![request],
+        });
+
+        let instant = Instant::now();
+
+        let (all_searches_res, shard_plans): (Vec<_>, Vec<_>) = {
+            let shard_holder = self.shards_holder.read().await;
+            let target_shards = shard_holder.select_shards(shard_selection)?;
+            let all_searches = target_shards.iter().map(|(shard, shard_key)| {
+                let shard_key = shard_key.cloned();
+                let shard_id = shard.shard_id;
                 shard
-                    .core_search(
+                    .core_search_explain(
                         Arc::clone(&request),
-                        read_consistency,
+                        None,
                         shard_selection.is_shard_id(),
                         timeout,
+                        &HardwareCounterCell::disabled(),
                     )
-                    .and_then(move |mut records| async move {
-                        if shard_key.is_none() {
-                            return Ok(records);
-                        }
-                        for batch in &mut records {
-                            for point in batch {
-                                point.shard_key.clone_from(&shard_key);
+                    .and_then(move |(records, segments)| async move {
+                        let mut records = records.value;
+                        if shard_key.is_some() {
+                            for batch in &mut records {
+                                for point in batch {
+                                    point.shard_key.clone_from(&shard_key);
+                                }
                             }
                         }
-                        Ok(records)
+                        Ok((records, ShardSearchPlan { shard_id, segments }))
                     })
             });
-            future::try_join_all(all_searches).await?
+            future::try_join_all(all_searches)
+                .await?
+                .into_iter()
+                .unzip()
         };
 
         let result = self
@@ -182,13 +390,14 @@ impl Collection {
                 Arc::clone(&request),
                 !shard_selection.is_shard_id(),
             )
-            .await;
+            .await?;
 
         let filters_refs = request.searches.iter().map(|req| req.filter.as_ref());
 
-        self.post_process_if_slow_request(instant.elapsed(), filters_refs);
+        self.post_process_request(instant.elapsed(), filters_refs);
 
-        result
+        let points = result.into_iter().next().unwrap_or_default();
+        Ok((points, shard_plans))
     }
 
     pub(crate) async fn fill_search_result_with_payload(
@@ -198,6 +407,7 @@ impl Collection {
         with_vector: WithVector,
         read_consistency: Option<ReadConsistency>,
         shard_selection: &ShardSelectorInternal,
+        shard_ids: Option<&HashSet<ShardId>>,
     ) -> CollectionResult<Vec<ScoredPoint>> {
         // short-circuit if not needed
         if let (&Some(WithPayloadInterface::Bool(false)), &WithVector::Bool(false)) =
@@ -219,7 +429,12 @@ impl Collection {
             with_vector,
         };
         let retrieved_records = self
-            .retrieve(retrieve_request, read_consistency, shard_selection)
+            .retrieve_from_shards(
+                retrieve_request,
+                read_consistency,
+                shard_selection,
+                shard_ids,
+            )
             .await?;
         let mut records_map: HashMap<ExtendedPointId, Record> = retrieved_records
             .into_iter()
@@ -258,7 +473,7 @@ impl Collection {
         for (batch_index, request) in request.searches.iter().enumerate() {
             let order = if request.query.is_distance_scored() {
                 collection_params
-                    .get_distance(request.query.get_vector_name())?
+                    .get_search_distance(request.query.get_vector_name(), request.params.as_ref())?
                     .distance_order()
             } else {
                 // Score comes from special handling of the distances in a way that it doesn't
@@ -295,13 +510,29 @@ impl Collection {
         Ok(top_results)
     }
 
-    pub fn post_process_if_slow_request<'a>(
+    /// Record filters of handled requests in the index advisor, and report unindexed fields
+    /// if the requests were slow
+    pub fn post_process_request<'a>(
         &self,
         duration: Duration,
         filters: impl IntoIterator<Item = Option<&'a Filter>>,
     ) {
+        let filters = filters.into_iter().flatten().collect_vec();
+        if filters.is_empty() {
+            return;
+        }
+
+        if self.index_advisor.is_enabled() {
+            let payload_index_schema = self.payload_index_schema.read();
+            self.index_advisor.record(
+                duration,
+                filters.iter().copied(),
+                &payload_index_schema.schema,
+            );
+        }
+
         if duration > segment::problems::UnindexedField::slow_query_threshold() {
-            let filters = filters.into_iter().flatten().cloned().collect_vec();
+            let filters = filters.into_iter().cloned().collect_vec();
 
             let schema = self.payload_index_schema.read().schema.clone();
 
@@ -312,4 +543,120 @@ impl Collection {
             });
         }
     }
+
+    /// Indexes, which would speed up the filters of requests, handled by this peer
+    pub fn index_recommendations(&self) -> Vec<IndexRecommendation> {
+        let payload_index_schema = self.payload_index_schema.read();
+        self.index_advisor
+            .recommendations(&payload_index_schema.schema)
+    }
+
+    /// Recommended indexes, which should be created automatically now
+    ///
+    /// Each index is returned once, it's up to the caller to create it.
+    pub fn take_indexes_to_auto_create(&self) -> Vec<(PayloadKeyType, PayloadFieldSchema)> {
+        let payload_index_schema = self.payload_index_schema.read();
+        self.index_advisor
+            .take_auto_create(&payload_index_schema.schema)
+    }
+
+    /// Add the request to the slow query log, if it exceeds the configured threshold
+    pub(crate) fn record_if_slow_query(
+        &self,
+        duration: Duration,
+        requests: Vec<SlowQueryRequest>,
+        shards: Vec<ShardSearchPlan>,
+        hw_counter: &HardwareCounterCell,
+    ) {
+        if !self.slow_query_log.is_slow(duration) {
+            return;
+        }
+
+        self.slow_query_log.record(SlowQueryEntry {
+            timestamp: Utc::now(),
+            duration: duration.as_secs_f64(),
+            requests,
+            shards,
+            usage: hw_counter.usage().map(HardwareUsage::from),
+        });
+    }
+
+    /// Slow requests of this collection, handled by this peer. Oldest first.
+    pub fn slow_queries(&self) -> Vec<SlowQueryEntry> {
+        self.slow_query_log.entries()
+    }
+}
+
+/// Split results of the shards, read with `partial` consistency, into the successful results and
+/// statuses of all shards. Fails only if none of the shards responded.
+fn split_partial_results<T>(
+    results: Vec<(ShardId, Option<ShardKey>, CollectionResult<T>)>,
+) -> CollectionResult<(Vec<T>, Vec<ShardReadStatus>)> {
+    let mut successful = Vec::with_capacity(results.len());
+    let mut statuses = Vec::with_capacity(results.len());
+    let mut last_error = None;
+
+    for (shard_id, shard_key, result) in results {
+        let error = match result {
+            Ok(result) => {
+                successful.push(result);
+                None
+            }
+            Err(err) => {
+                log::warn!("Shard {shard_id} failed to respond to a partial read: {err}");
+                let description = err.to_string();
+                last_error = Some(err);
+                Some(description)
+            }
+        };
+        statuses.push(ShardReadStatus {
+            shard_id,
+            shard_key,
+            ok: error.is_none(),
+            error,
+        });
+    }
+
+    match last_error {
+        Some(err) if successful.is_empty() => Err(err),
+        _ => Ok((successful, statuses)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_partial_results() {
+        let results = vec![
+            (0, None, Ok(1)),
+            (
+                1,
+                None,
+                Err(CollectionError::service_error("shard is down")),
+            ),
+            (2, Some(ShardKey::from("key")), Ok(2)),
+        ];
+        let (successful, statuses) = split_partial_results(results).unwrap();
+        assert_eq!(successful, vec![1, 2]);
+        assert_eq!(
+            statuses.iter().map(|status| status.ok).collect::<Vec<_>>(),
+            vec![true, false, true],
+        );
+        assert!(statuses[1]
+            .error
+            .as_ref()
+            .unwrap()
+            .contains("shard is down"));
+        assert_eq!(statuses[2].shard_key, Some(ShardKey::from("key")));
+
+        // Nothing to respond with, if all shards failed
+        let results: Vec<(_, _, CollectionResult<i32>)> = vec![(
+            0,
+            None,
+            Err(CollectionError::service_error("shard is down")),
+        )];
+        assert!(split_partial_results(results).is_err());
+    }
 }
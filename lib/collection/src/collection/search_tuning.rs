@@ -0,0 +1,415 @@
+use std::iter;
+use std::path::Path;
+use std::sync::atomic::AtomicBool;
+use std::time::{Duration, Instant};
+
+use common::cpu::{get_num_cpus, CpuPermit};
+use segment::data_types::named_vectors::NamedVectors;
+use segment::data_types::vectors::{QueryVector, Vector, VectorRef, DEFAULT_VECTOR_NAME};
+use segment::entry::entry_point::SegmentEntry;
+use segment::segment::Segment;
+use segment::segment_constructor::build_segment;
+use segment::segment_constructor::segment_builder::SegmentBuilder;
+use segment::types::{
+    HnswConfig, Indexes, PointIdType, QuantizationConfig, QuantizationSearchParams, ScoredPoint,
+    SearchParams, SegmentConfig, SeqNumberType, WithPayload, WithPayloadInterface, WithVector,
+};
+
+use super::recall_evaluation::{latency_distribution, recall, take_vector};
+use super::Collection;
+use crate::operations::config_diff::DiffConfig;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::{
+    CollectionError, CollectionResult, SearchTuningReport, SearchTuningRequest, SearchTuningResult,
+};
+
+const DEFAULT_SEARCH_TUNING_SAMPLE: usize = 10_000;
+const DEFAULT_SEARCH_TUNING_QUERIES: usize = 100;
+const DEFAULT_SEARCH_TUNING_LIMIT: usize = 10;
+const DEFAULT_SEARCH_TUNING_M: [usize; 3] = [8, 16, 32];
+const DEFAULT_SEARCH_TUNING_EF: [usize; 5] = [16, 32, 64, 128, 256];
+const DEFAULT_SEARCH_TUNING_OVERSAMPLING: [f64; 3] = [1.0, 2.0, 4.0];
+const DEFAULT_TARGET_RECALL: f64 = 0.95;
+
+/// Settings to sweep, resolved from the request and the config of the vector
+struct SearchTuningPlan {
+    vector_name: String,
+    /// Config of the segment with all sampled points and without index
+    plain_config: SegmentConfig,
+    hnsw_config: HnswConfig,
+    m: Vec<usize>,
+    ef: Vec<usize>,
+    /// `None` stands for the index without quantization
+    quantization: Vec<Option<QuantizationConfig>>,
+    oversampling: Vec<f64>,
+    limit: usize,
+}
+
+impl Collection {
+    /// Sweep `m`, quantization, `hnsw_ef` and oversampling on temporary indexes over a sample of
+    /// points, and compare results of each combination with exact search
+    ///
+    /// Points are sampled uniformly at random, and split into queries and indexed points. The
+    /// temporary index is smaller than the indexes of the collection, so recall on the sample is
+    /// usually higher, and the report is most useful to compare the settings with each other.
+    pub async fn tune_search(
+        &self,
+        request: SearchTuningRequest,
+        temp_dir: &Path,
+    ) -> CollectionResult<SearchTuningReport> {
+        let vector_name = request
+            .using
+            .clone()
+            .unwrap_or_else(|| DEFAULT_VECTOR_NAME.to_string());
+
+        let (plain_config, hnsw_config, current_quantization) = {
+            let config = self.collection_config.read().await;
+            let Some(params) = config.params.vectors.get_params(&vector_name) else {
+                return Err(CollectionError::bad_input(format!(
+                    "Dense vector `{vector_name}` does not exist in the collection"
+                )));
+            };
+            let hnsw_config = params
+                .hnsw_config
+                .and_then(|diff| diff.update(&config.hnsw_config).ok())
+                .unwrap_or_else(|| config.hnsw_config.clone());
+            let quantization = params
+                .quantization_config
+                .clone()
+                .or_else(|| config.quantization_config.clone());
+
+            let mut vector_data = config.params.to_base_vector_data()?;
+            vector_data.retain(|name, _| *name == vector_name);
+            let plain_config = SegmentConfig {
+                vector_data,
+                sparse_vector_data: Default::default(),
+                payload_storage_type: config.params.payload_storage_type(),
+                payload_columns: vec![],
+                payload_compression: None,
+            };
+            (plain_config, hnsw_config, quantization)
+        };
+
+        let plan = SearchTuningPlan {
+            m: candidates(request.m, &DEFAULT_SEARCH_TUNING_M, hnsw_config.m),
+            ef: candidates(
+                request.ef,
+                &DEFAULT_SEARCH_TUNING_EF,
+                hnsw_config.ef_construct,
+            ),
+            quantization: iter::once(None)
+                .chain(
+                    request
+                        .quantization
+                        .unwrap_or_else(|| current_quantization.into_iter().collect())
+                        .into_iter()
+                        .map(Some),
+                )
+                .collect(),
+            oversampling: request
+                .oversampling
+                .unwrap_or_else(|| DEFAULT_SEARCH_TUNING_OVERSAMPLING.to_vec()),
+            limit: request.limit.unwrap_or(DEFAULT_SEARCH_TUNING_LIMIT),
+            vector_name,
+            plain_config,
+            hnsw_config,
+        };
+        if plan.m.contains(&0) || plan.ef.contains(&0) {
+            return Err(CollectionError::bad_input(
+                "Values of `m` and `ef` must be positive",
+            ));
+        }
+        if plan
+            .oversampling
+            .iter()
+            .any(|oversampling| *oversampling < 1.0)
+        {
+            return Err(CollectionError::bad_input(
+                "Values of `oversampling` must be at least 1",
+            ));
+        }
+
+        let queries_count = request.queries.unwrap_or(DEFAULT_SEARCH_TUNING_QUERIES);
+        let sample = request.sample.unwrap_or(DEFAULT_SEARCH_TUNING_SAMPLE);
+        let mut points: Vec<(PointIdType, Vector)> = self
+            .sample_points(
+                queries_count + sample,
+                None,
+                WithPayloadInterface::Bool(false),
+                WithVector::Selector(vec![plan.vector_name.clone()]),
+                None,
+                &ShardSelectorInternal::All,
+            )
+            .await?
+            .into_iter()
+            .filter_map(|point| {
+                let vector = take_vector(point.vector.as_ref()?, &plan.vector_name)?;
+                Some((point.id, vector))
+            })
+            .collect();
+        let indexed = points.split_off(queries_count.min(points.len()));
+        let queries: Vec<_> = points.into_iter().map(|(_, vector)| vector).collect();
+        if indexed.is_empty() {
+            return Err(CollectionError::bad_input(format!(
+                "Collection has no points to index besides {} queries",
+                queries.len(),
+            )));
+        }
+
+        // Indexes are built in a directory, which is removed with all its segments when dropped
+        let tuning_dir = tempfile::Builder::new()
+            .prefix("search_tuning-")
+            .tempdir_in(temp_dir)?;
+        let indexed_points = indexed.len();
+        let queries_count = queries.len();
+        let (plan, results) = tokio::task::spawn_blocking(move || {
+            let results = run_search_tuning(&plan, tuning_dir.path(), &indexed, &queries);
+            (plan, results)
+        })
+        .await?;
+        let mut results = results?;
+
+        mark_pareto_frontier(&mut results);
+        let target_recall = request.target_recall.unwrap_or(DEFAULT_TARGET_RECALL);
+        let chosen = choose_settings(&results, target_recall).cloned();
+
+        Ok(SearchTuningReport {
+            vector: plan.vector_name,
+            indexed_points,
+            queries: queries_count,
+            limit: plan.limit,
+            results,
+            chosen,
+            applied: false,
+        })
+    }
+}
+
+/// Requested values, or the default values with the current one, sorted and deduplicated
+fn candidates(requested: Option<Vec<usize>>, defaults: &[usize], current: usize) -> Vec<usize> {
+    let mut values = requested.unwrap_or_else(|| {
+        defaults
+            .iter()
+            .copied()
+            .chain(iter::once(current))
+            .collect()
+    });
+    values.sort_unstable();
+    values.dedup();
+    values
+}
+
+fn run_search_tuning(
+    plan: &SearchTuningPlan,
+    dir: &Path,
+    indexed: &[(PointIdType, Vector)],
+    queries: &[Vector],
+) -> CollectionResult<Vec<SearchTuningResult>> {
+    let stopped = AtomicBool::new(false);
+    let name = plan.vector_name.as_str();
+
+    let segments_dir = dir.join("segments");
+    let builder_dir = dir.join("builder");
+    std::fs::create_dir_all(&segments_dir)?;
+    std::fs::create_dir_all(&builder_dir)?;
+
+    let mut plain = build_segment(&segments_dir, &plan.plain_config, true)?;
+    for (op_num, (point_id, vector)) in indexed.iter().enumerate() {
+        plain.upsert_point(
+            op_num as SeqNumberType,
+            *point_id,
+            NamedVectors::from_ref(name, VectorRef::from(vector)),
+        )?;
+    }
+
+    let queries: Vec<QueryVector> = queries.iter().cloned().map(QueryVector::from).collect();
+    let exact_params = SearchParams {
+        exact: true,
+        quantization: Some(QuantizationSearchParams {
+            ignore: true,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let exact = queries
+        .iter()
+        .map(|query| timed_search(&plain, name, query, plan.limit, &exact_params))
+        .map(|result| result.map(|(points, _latency)| points))
+        .collect::<CollectionResult<Vec<_>>>()?;
+
+    let mut results = Vec::new();
+    for &m in &plan.m {
+        for quantization in &plan.quantization {
+            let mut config = plan.plain_config.clone();
+            for vector_config in config.vector_data.values_mut() {
+                vector_config.index = Indexes::Hnsw(HnswConfig {
+                    m,
+                    // Always search the graph, even if the sample is small
+                    full_scan_threshold: 0,
+                    ..plan.hnsw_config.clone()
+                });
+                vector_config.quantization_config = quantization.clone();
+            }
+            let mut builder = SegmentBuilder::new(&segments_dir, &builder_dir, &config)?;
+            builder.update_from(&plain, &stopped)?;
+            let permit = CpuPermit::dummy(get_num_cpus() as u32);
+            let segment = builder.build(permit, &stopped)?;
+
+            // Oversampling only applies to quantized indexes
+            let oversampling: Vec<Option<f64>> = match quantization {
+                Some(_) => plan.oversampling.iter().copied().map(Some).collect(),
+                None => vec![None],
+            };
+            for &hnsw_ef in &plan.ef {
+                for &oversampling in &oversampling {
+                    let params = SearchParams {
+                        hnsw_ef: Some(hnsw_ef),
+                        quantization: oversampling.map(|oversampling| QuantizationSearchParams {
+                            rescore: Some(true),
+                            oversampling: Some(oversampling),
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    };
+                    let mut recalls = Vec::with_capacity(queries.len());
+                    let mut latencies = Vec::with_capacity(queries.len());
+                    for (query, exact) in queries.iter().zip(&exact) {
+                        let (points, latency) =
+                            timed_search(&segment, name, query, plan.limit, &params)?;
+                        recalls.extend(recall(exact, &points));
+                        latencies.push(latency);
+                    }
+                    results.push(SearchTuningResult {
+                        m,
+                        quantization: quantization.clone(),
+                        hnsw_ef,
+                        oversampling,
+                        recall: mean_recall(&recalls),
+                        latency: latency_distribution(latencies),
+                        pareto_optimal: false,
+                    });
+                }
+            }
+
+            let segment_path = segment.current_path.clone();
+            drop(segment);
+            std::fs::remove_dir_all(segment_path)?;
+        }
+    }
+
+    Ok(results)
+}
+
+fn timed_search(
+    segment: &Segment,
+    vector_name: &str,
+    query: &QueryVector,
+    limit: usize,
+    params: &SearchParams,
+) -> CollectionResult<(Vec<ScoredPoint>, Duration)> {
+    let start = Instant::now();
+    let mut result = segment.search_batch(
+        vector_name,
+        &[query],
+        &WithPayload::default(),
+        &WithVector::Bool(false),
+        None,
+        limit,
+        Some(params),
+        Default::default(),
+    )?;
+    Ok((result.pop().unwrap_or_default(), start.elapsed()))
+}
+
+fn mean_recall(recalls: &[f64]) -> f64 {
+    if recalls.is_empty() {
+        // No query has neighbours, so nothing is missed
+        return 1.0;
+    }
+    recalls.iter().sum::<f64>() / recalls.len() as f64
+}
+
+/// Sort results by mean latency, and mark the results, for which there are no faster results
+/// with the same or higher recall
+fn mark_pareto_frontier(results: &mut [SearchTuningResult]) {
+    results.sort_by(|a, b| {
+        a.latency
+            .mean
+            .total_cmp(&b.latency.mean)
+            .then(b.recall.total_cmp(&a.recall))
+    });
+    let mut best_recall = f64::NEG_INFINITY;
+    for result in results {
+        result.pareto_optimal = result.recall > best_recall;
+        best_recall = best_recall.max(result.recall);
+    }
+}
+
+/// Fastest settings on the frontier, which reach the target recall, or the most accurate ones
+///
+/// Results must be sorted by [`mark_pareto_frontier`].
+fn choose_settings(
+    results: &[SearchTuningResult],
+    target_recall: f64,
+) -> Option<&SearchTuningResult> {
+    let mut frontier = results.iter().filter(|result| result.pareto_optimal);
+    frontier
+        .clone()
+        .find(|result| result.recall >= target_recall)
+        .or_else(|| frontier.next_back())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operations::types::LatencyDistribution;
+
+    fn result(m: usize, recall: f64, mean_latency: f64) -> SearchTuningResult {
+        SearchTuningResult {
+            m,
+            quantization: None,
+            hnsw_ef: 64,
+            oversampling: None,
+            recall,
+            latency: LatencyDistribution {
+                mean: mean_latency,
+                ..Default::default()
+            },
+            pareto_optimal: false,
+        }
+    }
+
+    #[test]
+    fn test_pareto_frontier() {
+        let mut results = vec![
+            result(1, 0.99, 0.004),
+            result(2, 0.90, 0.001),
+            result(3, 0.85, 0.002),
+            result(4, 0.96, 0.002),
+            result(5, 0.99, 0.005),
+        ];
+        mark_pareto_frontier(&mut results);
+
+        let order: Vec<_> = results.iter().map(|result| result.m).collect();
+        assert_eq!(order, vec![2, 4, 3, 1, 5]);
+        let frontier: Vec<_> = results
+            .iter()
+            .filter(|result| result.pareto_optimal)
+            .map(|result| result.m)
+            .collect();
+        assert_eq!(frontier, vec![2, 4, 1]);
+
+        assert_eq!(choose_settings(&results, 0.95).unwrap().m, 4);
+        assert_eq!(choose_settings(&results, 0.5).unwrap().m, 2);
+        // Unreachable target, the most accurate settings are chosen
+        assert_eq!(choose_settings(&results, 1.0).unwrap().m, 1);
+        assert_eq!(choose_settings(&[], 0.95), None);
+    }
+
+    #[test]
+    fn test_candidates() {
+        assert_eq!(candidates(None, &[8, 16, 32], 16), vec![8, 16, 32]);
+        assert_eq!(candidates(None, &[8, 16, 32], 12), vec![8, 12, 16, 32]);
+        assert_eq!(candidates(Some(vec![64, 4, 64]), &[8], 16), vec![4, 64]);
+    }
+}
@@ -7,6 +7,7 @@ use common::defaults;
 use parking_lot::Mutex;
 
 use super::Collection;
+use crate::events::ShardTransferFinishedEvent;
 use crate::operations::types::{CollectionError, CollectionResult};
 use crate::shards::local_shard::LocalShard;
 use crate::shards::replica_set::ReplicaState;
@@ -267,6 +268,15 @@ impl Collection {
         }
         let finish_was_registered = shard_holder.register_finish_transfer(&transfer.key())?;
         log::debug!("finish_was_registered: {finish_was_registered}");
+
+        issues::publish(ShardTransferFinishedEvent {
+            collection_id: self.id.clone(),
+            shard_id: transfer.shard_id,
+            from: transfer.from,
+            to: transfer.to,
+            method: transfer.method,
+        });
+
         Ok(())
     }
 
@@ -0,0 +1,244 @@
+use std::path::Path;
+use std::sync::atomic::AtomicBool;
+
+use schemars::JsonSchema;
+use segment::entry::entry_point::SegmentEntry as _;
+use segment::segment::Segment;
+use segment::segment_constructor::load_segment;
+use serde::Serialize;
+
+use super::Collection;
+use crate::common::sha_256::{hash_file_blocking, hashes_equal};
+use crate::common::snapshot_manifest::SnapshotManifest;
+use crate::common::validate_snapshot_archive::validate_open_snapshot_archive;
+use crate::config::CollectionConfig;
+use crate::operations::types::CollectionResult;
+use crate::shards::local_shard::LocalShard;
+use crate::shards::shard::ShardId;
+use crate::shards::shard_config::ShardConfig;
+use crate::shards::shard_versioning;
+
+/// Result of the verification of a collection snapshot
+#[derive(Debug, Default, Serialize, JsonSchema, Clone)]
+pub struct SnapshotVerification {
+    /// True if no problems were found
+    pub valid: bool,
+    /// Whether the SHA256 checksum of the archive matches the stored checksum.
+    /// Not set if no checksum is stored for the snapshot.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checksum_matches: Option<bool>,
+    /// Whether the snapshot has a manifest with checksums of its files.
+    /// Snapshots created by older versions don't have it.
+    pub has_manifest: bool,
+    /// Number of files, checked against the manifest
+    pub verified_files: usize,
+    /// Collection config, the snapshot would be restored with
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config: Option<CollectionConfig>,
+    pub shards: Vec<ShardSnapshotVerification>,
+    /// Problems, found in the snapshot
+    pub errors: Vec<String>,
+}
+
+/// Result of the verification of a shard in a collection snapshot
+#[derive(Debug, Serialize, JsonSchema, Clone)]
+pub struct ShardSnapshotVerification {
+    pub shard_id: ShardId,
+    /// Whether the snapshot contains data of the shard, or only its remote replicas
+    pub has_local_data: bool,
+    /// Number of segments, which were opened successfully
+    pub segments: usize,
+    /// Number of points in the opened segments
+    pub points: usize,
+}
+
+impl Collection {
+    /// Verify the snapshot of this collection, stored on this peer.
+    ///
+    /// Snapshots in object storage are downloaded into `temp_dir` first.
+    pub async fn verify_stored_snapshot(
+        &self,
+        snapshot_name: &str,
+        temp_dir: &Path,
+    ) -> CollectionResult<SnapshotVerification> {
+        let snapshot_manager = self.get_snapshots_storage_manager()?;
+        let snapshot_path = snapshot_manager
+            .get_snapshot_path(&self.snapshots_path, snapshot_name)
+            .await?;
+        let expected_checksum = snapshot_manager
+            .list_snapshots(&self.snapshots_path)
+            .await?
+            .into_iter()
+            .find(|snapshot| snapshot.name == snapshot_name)
+            .and_then(|snapshot| snapshot.checksum);
+        let (local_path, _downloaded) = snapshot_manager
+            .get_local_snapshot(&snapshot_path, temp_dir)
+            .await?;
+
+        let temp_dir = temp_dir.to_owned();
+        let report = tokio::task::spawn_blocking(move || {
+            Self::verify_snapshot(&local_path, &temp_dir, expected_checksum.as_deref())
+        })
+        .await?;
+        Ok(report)
+    }
+
+    /// Verify the collection snapshot archive without restoring it.
+    ///
+    /// The archive is unpacked into a temporary directory within `temp_dir`, which is removed
+    /// afterwards. `expected_checksum` is the stored SHA256 checksum of the archive, if any.
+    ///
+    /// This method performs blocking IO.
+    pub fn verify_snapshot(
+        snapshot_path: &Path,
+        temp_dir: &Path,
+        expected_checksum: Option<&str>,
+    ) -> SnapshotVerification {
+        let mut report = SnapshotVerification::default();
+
+        if let Some(expected_checksum) = expected_checksum {
+            match hash_file_blocking(snapshot_path) {
+                Ok(checksum) => {
+                    let matches = hashes_equal(&checksum, expected_checksum);
+                    if !matches {
+                        let error = "Checksum of the archive doesn't match".to_string();
+                        report.errors.push(error);
+                    }
+                    report.checksum_matches = Some(matches);
+                }
+                Err(err) => report.errors.push(format!("Can't read the archive: {err}")),
+            }
+        }
+
+        // Problems, which don't prevent further checks, are collected in the report
+        if let Err(err) = Self::verify_snapshot_content(snapshot_path, temp_dir, &mut report) {
+            report.errors.push(err.to_string());
+        }
+
+        report.valid = report.errors.is_empty();
+        report
+    }
+
+    fn verify_snapshot_content(
+        snapshot_path: &Path,
+        temp_dir: &Path,
+        report: &mut SnapshotVerification,
+    ) -> CollectionResult<()> {
+        let unpack_dir = tempfile::Builder::new()
+            .prefix("snapshot-verify-")
+            .tempdir_in(temp_dir)?;
+        let snapshot_dir = unpack_dir.path();
+
+        let mut archive = validate_open_snapshot_archive(snapshot_path)?;
+        archive.unpack(snapshot_dir)?;
+
+        // Manifest is checked first, segments are unpacked in place later
+        if let Some(manifest) = SnapshotManifest::load(snapshot_dir)? {
+            report.has_manifest = true;
+            report.verified_files = manifest.files.len();
+            report.errors.extend(manifest.verify(snapshot_dir)?);
+        }
+
+        let config = CollectionConfig::load(snapshot_dir)?;
+        let shard_ids = Self::snapshot_shard_ids(&config, snapshot_dir)?;
+        report.config = Some(config);
+
+        for shard_id in shard_ids {
+            let shard_path = shard_versioning::versioned_shard_path(snapshot_dir, shard_id, 0);
+            let mut shard = ShardSnapshotVerification {
+                shard_id,
+                has_local_data: false,
+                segments: 0,
+                points: 0,
+            };
+
+            match ShardConfig::load(&shard_path) {
+                Ok(Some(_)) => {}
+                Ok(None) => report
+                    .errors
+                    .push(format!("Shard {shard_id}: shard config is missing")),
+                Err(err) => report
+                    .errors
+                    .push(format!("Shard {shard_id}: can't read shard config: {err}")),
+            }
+
+            let segments_path = LocalShard::segments_path(&shard_path);
+            if segments_path.is_dir() {
+                shard.has_local_data = true;
+                verify_segments(&segments_path, &mut shard, &mut report.errors)?;
+            }
+
+            report.shards.push(shard);
+        }
+
+        Ok(())
+    }
+}
+
+/// Unpack and open each segment of the shard, one at a time
+fn verify_segments(
+    segments_path: &Path,
+    shard: &mut ShardSnapshotVerification,
+    errors: &mut Vec<String>,
+) -> CollectionResult<()> {
+    let shard_id = shard.shard_id;
+    // Segments are unpacked into the same directory, so list the archives before that
+    let entries = std::fs::read_dir(segments_path)?.collect::<Result<Vec<_>, _>>()?;
+    for entry in entries {
+        let archive_path = entry.path();
+        if !archive_path.extension().is_some_and(|ext| ext == "tar") {
+            continue;
+        }
+        let Some(segment_id) = archive_path.file_stem().and_then(|stem| stem.to_str()) else {
+            errors.push(format!("Shard {shard_id}: invalid segment archive name"));
+            continue;
+        };
+
+        if let Err(err) = Segment::restore_snapshot(&archive_path, segment_id) {
+            errors.push(format!(
+                "Shard {shard_id}: can't unpack segment {segment_id}: {err}",
+            ));
+            continue;
+        }
+        std::fs::remove_file(&archive_path)?;
+
+        let segment_path = segments_path.join(segment_id);
+        match load_segment(&segment_path, &AtomicBool::new(false)) {
+            Ok(Some(segment)) => {
+                shard.segments += 1;
+                shard.points += segment.available_point_count();
+            }
+            Ok(None) => errors.push(format!(
+                "Shard {shard_id}: segment {segment_id} is incomplete",
+            )),
+            Err(err) => errors.push(format!(
+                "Shard {shard_id}: can't open segment {segment_id}: {err}",
+            )),
+        }
+
+        // Only keep one unpacked segment on disk at a time
+        std::fs::remove_dir_all(&segment_path)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+
+    #[test]
+    fn test_verify_broken_snapshot() {
+        let dir = Builder::new().prefix("snapshot_verify").tempdir().unwrap();
+        let snapshot_path = dir.path().join("broken.snapshot");
+        std::fs::write(&snapshot_path, "not an archive").unwrap();
+
+        let report = Collection::verify_snapshot(&snapshot_path, dir.path(), Some("0123abc"));
+        assert!(!report.valid);
+        assert_eq!(report.checksum_matches, Some(false));
+        assert!(report.config.is_none());
+        // Checksum mismatch and unreadable archive
+        assert_eq!(report.errors.len(), 2);
+    }
+}
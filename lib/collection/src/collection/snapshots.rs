@@ -1,13 +1,18 @@
 use std::collections::HashSet;
 use std::path::Path;
 
+use chrono::NaiveDateTime;
+use common::io_throttling::{BackgroundIo, ThrottledWriter};
 use io::file_operations::read_json;
 use io::storage_version::StorageVersion as _;
 use tokio::fs;
 
 use super::Collection;
 use crate::collection::CollectionVersion;
-use crate::common::snapshots_manager::SnapshotStorageManager;
+use crate::common::snapshot_manifest::SnapshotManifest;
+use crate::common::snapshots_manager::{
+    SnapShotsConfig, SnapshotStorageManager, SnapshotsStorageConfig,
+};
 use crate::common::validate_snapshot_archive::validate_open_snapshot_archive;
 use crate::config::{CollectionConfig, ShardingMethod};
 use crate::operations::snapshot_ops::SnapshotDescription;
@@ -20,6 +25,9 @@ use crate::shards::shard_config::{self, ShardConfig};
 use crate::shards::shard_holder::{ShardKeyMapping, SHARD_KEY_MAPPING_FILE};
 use crate::shards::shard_versioning;
 
+/// Format of the creation time in the names of collection snapshots
+const SNAPSHOT_TIME_FORMAT: &str = "%Y-%m-%d-%H-%M-%S";
+
 impl Collection {
     pub fn get_snapshots_storage_manager(&self) -> CollectionResult<SnapshotStorageManager> {
         SnapshotStorageManager::new(self.shared_storage_config.snapshots_config.clone())
@@ -32,11 +40,6 @@ impl Collection {
 
     /// Creates a snapshot of the collection.
     ///
-    /// The snapshot is created in three steps:
-    /// 1. Create a temporary directory and create a snapshot of each shard in it.
-    /// 2. Archive the temporary directory into a single file.
-    /// 3. Move the archive to the final location.
-    ///
     /// # Arguments
     ///
     /// * `global_temp_dir`: directory used to host snapshots while they are being created
@@ -51,11 +54,85 @@ impl Collection {
         let snapshot_name = format!(
             "{}-{this_peer_id}-{}.snapshot",
             self.name(),
-            chrono::Utc::now().format("%Y-%m-%d-%H-%M-%S"),
+            chrono::Utc::now().format(SNAPSHOT_TIME_FORMAT),
+        );
+        let snapshot_manager = self.get_snapshots_storage_manager()?;
+        self.create_named_snapshot(global_temp_dir, &snapshot_name, &snapshot_manager)
+            .await
+    }
+
+    /// Creates a snapshot of the collection for the schedule `schedule_name`, and deletes the
+    /// oldest snapshots of this schedule, so that only `retention` latest ones are kept.
+    ///
+    /// Snapshots are saved to `storage`, with the S3 settings of the snapshots config.
+    pub async fn create_scheduled_snapshot(
+        &self,
+        global_temp_dir: &Path,
+        this_peer_id: PeerId,
+        schedule_name: &str,
+        storage: SnapshotsStorageConfig,
+        retention: usize,
+    ) -> CollectionResult<SnapshotDescription> {
+        let snapshot_manager = SnapshotStorageManager::new(SnapShotsConfig {
+            snapshots_storage: storage,
+            s3_config: self
+                .shared_storage_config
+                .snapshots_config
+                .s3_config
+                .clone(),
+        })?;
+        let prefix = format!("{}-{this_peer_id}-{schedule_name}-", self.name());
+        let snapshot_name = format!(
+            "{prefix}{}.snapshot",
+            chrono::Utc::now().format(SNAPSHOT_TIME_FORMAT),
         );
+        let snapshot = self
+            .create_named_snapshot(global_temp_dir, &snapshot_name, &snapshot_manager)
+            .await?;
+
+        // Only snapshots of this schedule are rotated: names of other snapshots may share the
+        // prefix, but the rest of them is not a creation time
+        let mut scheduled: Vec<_> = snapshot_manager
+            .list_snapshots(&self.snapshots_path)
+            .await?
+            .into_iter()
+            .filter_map(|snapshot| {
+                let time = snapshot
+                    .name
+                    .strip_prefix(&prefix)?
+                    .strip_suffix(".snapshot")?;
+                let time = NaiveDateTime::parse_from_str(time, SNAPSHOT_TIME_FORMAT).ok()?;
+                Some((time, snapshot.name))
+            })
+            .collect();
+        scheduled.sort_unstable();
+        for (_, name) in scheduled.into_iter().rev().skip(retention) {
+            log::info!(
+                "Deleting snapshot {name}, which is beyond retention of schedule {schedule_name}"
+            );
+            let path = snapshot_manager
+                .get_snapshot_path(&self.snapshots_path, &name)
+                .await?;
+            snapshot_manager.delete_snapshot(&path).await?;
+        }
+
+        Ok(snapshot)
+    }
 
+    /// Creates a snapshot of the collection with the given name.
+    ///
+    /// The snapshot is created in three steps:
+    /// 1. Create a temporary directory and create a snapshot of each shard in it.
+    /// 2. Archive the temporary directory into a single file.
+    /// 3. Move the archive to the final location.
+    async fn create_named_snapshot(
+        &self,
+        global_temp_dir: &Path,
+        snapshot_name: &str,
+        snapshot_manager: &SnapshotStorageManager,
+    ) -> CollectionResult<SnapshotDescription> {
         // Final location of snapshot
-        let snapshot_path = self.snapshots_path.join(&snapshot_name);
+        let snapshot_path = self.snapshots_path.join(snapshot_name);
         log::info!(
             "Creating collection snapshot {} into {:?}",
             snapshot_name,
@@ -154,7 +231,14 @@ impl Collection {
         // Archive snapshot folder into a single file
         log::debug!("Archiving snapshot {snapshot_temp_target_dir_path:?}");
         let archiving = tokio::task::spawn_blocking(move || -> CollectionResult<_> {
-            let mut builder = tar::Builder::new(snapshot_temp_arc_file.as_file_mut());
+            // Checksums of all files, so that the snapshot can be verified without restoring it
+            SnapshotManifest::build(&snapshot_temp_target_dir_path)?
+                .save(&snapshot_temp_target_dir_path)?;
+
+            let mut builder = tar::Builder::new(ThrottledWriter::new(
+                snapshot_temp_arc_file.as_file_mut(),
+                BackgroundIo::Snapshot,
+            ));
             // archive recursively collection directory `snapshot_path_with_arc_extension` into `snapshot_path`
             builder.append_dir_all(".", &snapshot_temp_target_dir_path)?;
             builder.finish()?;
@@ -166,7 +250,6 @@ impl Collection {
             CollectionError::service_error(format!("failed to create snapshot archive: {err}"))
         })?;
 
-        let snapshot_manager = self.get_snapshots_storage_manager()?;
         snapshot_manager
             .store_file(snapshot_temp_arc_file.path(), snapshot_path.as_path())
             .await
@@ -193,13 +276,49 @@ impl Collection {
 
         let config = CollectionConfig::load(target_dir)?;
         config.validate_and_warn();
+        let shard_ids_list = Self::snapshot_shard_ids(&config, target_dir)?;
+
+        for shard_id in shard_ids_list {
+            let shard_path = shard_versioning::versioned_shard_path(target_dir, shard_id, 0);
+            let shard_config_opt = ShardConfig::load(&shard_path)?;
+            if let Some(shard_config) = shard_config_opt {
+                match shard_config.r#type {
+                    shard_config::ShardType::Local => LocalShard::restore_snapshot(&shard_path)?,
+                    shard_config::ShardType::Remote { .. } => {
+                        RemoteShard::restore_snapshot(&shard_path)
+                    }
+                    shard_config::ShardType::Temporary => {}
+                    shard_config::ShardType::ReplicaSet { .. } => {
+                        ShardReplicaSet::restore_snapshot(
+                            &shard_path,
+                            this_peer_id,
+                            is_distributed,
+                        )?
+                    }
+                }
+            } else {
+                return Err(CollectionError::service_error(format!(
+                    "Can't read shard config at {}",
+                    shard_path.display()
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Ids of the shards in the unpacked collection snapshot
+    pub(super) fn snapshot_shard_ids(
+        config: &CollectionConfig,
+        snapshot_dir: &Path,
+    ) -> CollectionResult<Vec<ShardId>> {
         let configured_shards = config.params.shard_number.get();
 
         let shard_ids_list: Vec<_> = match config.params.sharding_method.unwrap_or_default() {
             ShardingMethod::Auto => (0..configured_shards).collect(),
             ShardingMethod::Custom => {
                 // Load shard mapping from disk
-                let mapping_path = target_dir.join(SHARD_KEY_MAPPING_FILE);
+                let mapping_path = snapshot_dir.join(SHARD_KEY_MAPPING_FILE);
                 debug_assert!(
                     mapping_path.exists(),
                     "Shard mapping file must exist once custom sharding is used"
@@ -224,33 +343,7 @@ impl Collection {
             "Shard mapping must contain all shards",
         );
 
-        for shard_id in shard_ids_list {
-            let shard_path = shard_versioning::versioned_shard_path(target_dir, shard_id, 0);
-            let shard_config_opt = ShardConfig::load(&shard_path)?;
-            if let Some(shard_config) = shard_config_opt {
-                match shard_config.r#type {
-                    shard_config::ShardType::Local => LocalShard::restore_snapshot(&shard_path)?,
-                    shard_config::ShardType::Remote { .. } => {
-                        RemoteShard::restore_snapshot(&shard_path)
-                    }
-                    shard_config::ShardType::Temporary => {}
-                    shard_config::ShardType::ReplicaSet { .. } => {
-                        ShardReplicaSet::restore_snapshot(
-                            &shard_path,
-                            this_peer_id,
-                            is_distributed,
-                        )?
-                    }
-                }
-            } else {
-                return Err(CollectionError::service_error(format!(
-                    "Can't read shard config at {}",
-                    shard_path.display()
-                )));
-            }
-        }
-
-        Ok(())
+        Ok(shard_ids_list)
     }
 
     /// # Cancel safety
@@ -331,6 +424,44 @@ impl Collection {
             .await
     }
 
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe.
+    pub async fn create_segment_snapshot(
+        &self,
+        shard_id: ShardId,
+        segment_uuid: &str,
+        temp_dir: &Path,
+    ) -> CollectionResult<SnapshotDescription> {
+        self.shards_holder
+            .read()
+            .await
+            .create_segment_snapshot(
+                &self.snapshots_path,
+                &self.name(),
+                shard_id,
+                segment_uuid,
+                temp_dir,
+            )
+            .await
+    }
+
+    /// # Cancel safety
+    ///
+    /// This method is *not* cancel safe.
+    pub async fn restore_segment_snapshot(
+        &self,
+        shard_id: ShardId,
+        segment_uuid: &str,
+        snapshot_path: &Path,
+    ) -> CollectionResult<()> {
+        self.shards_holder
+            .read()
+            .await
+            .restore_segment_snapshot(snapshot_path, shard_id, segment_uuid)
+            .await
+    }
+
     pub async fn assert_shard_exists(&self, shard_id: ShardId) -> CollectionResult<()> {
         self.shards_holder
             .read()
@@ -0,0 +1,49 @@
+use std::collections::HashMap;
+
+use super::Collection;
+use crate::common::sparse_vocabulary::SparseVocabularyStats;
+use crate::operations::types::{CollectionResult, Modifier};
+
+impl Collection {
+    /// Statistics of the shared sparse vocabularies, used by the sparse vectors of the collection
+    ///
+    /// Statistics are collected over the shards, located on this peer. Vectors without the `idf`
+    /// modifier don't contribute to the vocabulary.
+    pub async fn sparse_vocabulary_stats(
+        &self,
+    ) -> CollectionResult<HashMap<String, SparseVocabularyStats>> {
+        let vocabulary_vectors: Vec<_> = {
+            let config = self.collection_config.read().await;
+            config
+                .params
+                .sparse_vectors
+                .iter()
+                .flatten()
+                .filter(|(_, params)| params.modifier == Some(Modifier::Idf))
+                .filter_map(|(vector_name, params)| {
+                    let vocabulary = params.vocabulary.clone()?;
+                    Some((vector_name.clone(), vocabulary))
+                })
+                .collect()
+        };
+
+        let mut vocabularies: HashMap<String, SparseVocabularyStats> = HashMap::new();
+        if vocabulary_vectors.is_empty() {
+            return Ok(vocabularies);
+        }
+
+        let shards_holder = self.shards_holder.read().await;
+        for (vector_name, vocabulary) in vocabulary_vectors {
+            let vocabulary_stats = vocabularies.entry(vocabulary).or_default();
+            for (_shard_id, replica_set) in shards_holder.get_shards() {
+                if let Some(stats) = replica_set
+                    .local_sparse_vocabulary_stats(&vector_name)
+                    .await?
+                {
+                    vocabulary_stats.merge(&stats);
+                }
+            }
+        }
+        Ok(vocabularies)
+    }
+}
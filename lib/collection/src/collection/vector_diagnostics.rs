@@ -0,0 +1,314 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashSet};
+use std::hash::{Hash, Hasher};
+
+use segment::data_types::vectors::{Vector, VectorStructInternal, DEFAULT_VECTOR_NAME};
+use segment::types::{VectorElementType, WithPayloadInterface, WithVector};
+use sparse::common::sparse_vector::SparseVector;
+use sparse::common::types::DimId;
+
+use super::Collection;
+use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::{
+    CollectionResult, NormDistribution, VectorDiagnostics, VectorDiagnosticsReport,
+};
+
+/// Number of points, sampled for vector diagnostics by default
+pub const DEFAULT_VECTOR_DIAGNOSTICS_SAMPLE: usize = 1_000;
+
+/// Max number of dense vectors, the intrinsic dimensionality is estimated over.
+/// The estimation compares all pairs of vectors, so its cost grows quadratically.
+const MAX_DIMENSIONALITY_SAMPLE: usize = 1_000;
+
+impl Collection {
+    /// Inspect vectors of a sample of points for signs of a broken embedding pipeline
+    ///
+    /// Points are sampled uniformly at random over the selected shards.
+    pub async fn vector_diagnostics(
+        &self,
+        sample: usize,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: &ShardSelectorInternal,
+    ) -> CollectionResult<VectorDiagnosticsReport> {
+        let points = self
+            .sample_points(
+                sample,
+                None,
+                WithPayloadInterface::Bool(false),
+                WithVector::Bool(true),
+                read_consistency,
+                shard_selection,
+            )
+            .await?;
+
+        // Comparing pairs of vectors is CPU heavy, don't block the async runtime
+        let report = tokio::task::spawn_blocking(move || {
+            let mut diagnostics = VectorDiagnosticsCollector::default();
+            for point in &points {
+                diagnostics.add_point(point.vector.as_ref());
+            }
+            diagnostics.into_report()
+        })
+        .await?;
+
+        Ok(report)
+    }
+}
+
+/// Accumulates vectors of the sampled points
+#[derive(Default)]
+struct VectorDiagnosticsCollector {
+    sampled_points: usize,
+    vectors: BTreeMap<String, VectorCollector>,
+}
+
+impl VectorDiagnosticsCollector {
+    fn add_point(&mut self, vector: Option<&VectorStructInternal>) {
+        self.sampled_points += 1;
+        match vector {
+            None => {}
+            Some(VectorStructInternal::Single(vector)) => {
+                self.vector(DEFAULT_VECTOR_NAME).add_dense(vector);
+            }
+            Some(VectorStructInternal::MultiDense(vectors)) => {
+                self.vector(DEFAULT_VECTOR_NAME)
+                    .add_multi_dense(vectors.multi_vectors());
+            }
+            Some(VectorStructInternal::Named(vectors)) => {
+                for (name, vector) in vectors {
+                    let collector = self.vector(name);
+                    match vector {
+                        Vector::Dense(vector) => collector.add_dense(vector),
+                        Vector::Sparse(vector) => collector.add_sparse(vector),
+                        Vector::MultiDense(vectors) => {
+                            collector.add_multi_dense(vectors.multi_vectors())
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn vector(&mut self, name: &str) -> &mut VectorCollector {
+        self.vectors.entry(name.to_string()).or_default()
+    }
+
+    fn into_report(self) -> VectorDiagnosticsReport {
+        VectorDiagnosticsReport {
+            sampled_points: self.sampled_points,
+            vectors: self
+                .vectors
+                .into_iter()
+                .map(|(name, vector)| (name, vector.into_diagnostics()))
+                .collect(),
+        }
+    }
+}
+
+/// Accumulates values of a single named vector
+#[derive(Default)]
+struct VectorCollector {
+    points_count: usize,
+    norms: Vec<f64>,
+    non_finite_vectors: usize,
+    /// Hashes of the values of distinct vectors
+    hashes: HashSet<u64>,
+    duplicates: usize,
+    /// Dense vectors for the estimation of intrinsic dimensionality
+    dense: Vec<Vec<VectorElementType>>,
+}
+
+impl VectorCollector {
+    fn add_dense(&mut self, vector: &[VectorElementType]) {
+        self.points_count += 1;
+        self.add_values(None, vector);
+    }
+
+    /// Each vector of a multivector is inspected separately
+    fn add_multi_dense<'a>(&mut self, vectors: impl Iterator<Item = &'a [VectorElementType]>) {
+        self.points_count += 1;
+        for vector in vectors {
+            self.add_values(None, vector);
+        }
+    }
+
+    fn add_sparse(&mut self, vector: &SparseVector) {
+        self.points_count += 1;
+        self.add_values(Some(&vector.indices), &vector.values);
+    }
+
+    /// Add values of one vector, `indices` are only set for sparse vectors
+    fn add_values(&mut self, indices: Option<&[DimId]>, values: &[VectorElementType]) {
+        if values.iter().any(|value| !value.is_finite()) {
+            self.non_finite_vectors += 1;
+        }
+        let norm = values
+            .iter()
+            .map(|value| f64::from(*value).powi(2))
+            .sum::<f64>()
+            .sqrt();
+        self.norms.push(norm);
+
+        let mut hasher = DefaultHasher::new();
+        indices.hash(&mut hasher);
+        values.len().hash(&mut hasher);
+        for value in values {
+            value.to_bits().hash(&mut hasher);
+        }
+        if !self.hashes.insert(hasher.finish()) {
+            self.duplicates += 1;
+        }
+
+        // Sparse vectors are not comparable by euclidean distance between their values
+        if indices.is_none() && self.dense.len() < MAX_DIMENSIONALITY_SAMPLE {
+            self.dense.push(values.to_vec());
+        }
+    }
+
+    fn into_diagnostics(self) -> VectorDiagnostics {
+        let vectors_count = self.norms.len();
+        let zero_vectors = self.norms.iter().filter(|norm| **norm == 0.0).count();
+        let duplicate_rate = if vectors_count > 0 {
+            self.duplicates as f64 / vectors_count as f64
+        } else {
+            0.0
+        };
+        VectorDiagnostics {
+            points_count: self.points_count,
+            vectors_count,
+            norm: norm_distribution(self.norms),
+            zero_vectors,
+            non_finite_vectors: self.non_finite_vectors,
+            duplicate_rate,
+            intrinsic_dimensionality: intrinsic_dimensionality(&self.dense),
+        }
+    }
+}
+
+fn norm_distribution(mut norms: Vec<f64>) -> Option<NormDistribution> {
+    norms.retain(|norm| norm.is_finite());
+    if norms.is_empty() {
+        return None;
+    }
+    norms.sort_unstable_by(f64::total_cmp);
+
+    let count = norms.len() as f64;
+    let mean = norms.iter().sum::<f64>() / count;
+    let variance = norms.iter().map(|norm| (norm - mean).powi(2)).sum::<f64>() / count;
+    // Nearest-rank percentile
+    let percentile = |percentile: f64| {
+        let rank = (count * percentile).ceil() as usize;
+        norms[rank.clamp(1, norms.len()) - 1]
+    };
+
+    Some(NormDistribution {
+        min: norms[0],
+        max: norms[norms.len() - 1],
+        mean,
+        std_dev: variance.sqrt(),
+        p01: percentile(0.01),
+        p50: percentile(0.5),
+        p99: percentile(0.99),
+    })
+}
+
+/// Estimate intrinsic dimensionality by the ratios of distances to the second and the first
+/// nearest neighbour (TwoNN estimator, Facco et al. 2017)
+///
+/// Exact duplicates are ignored, as they have no defined ratio.
+fn intrinsic_dimensionality(vectors: &[Vec<VectorElementType>]) -> Option<f64> {
+    let euclidean = |a: &[VectorElementType], b: &[VectorElementType]| {
+        a.iter()
+            .zip(b)
+            .map(|(a, b)| (f64::from(*a) - f64::from(*b)).powi(2))
+            .sum::<f64>()
+            .sqrt()
+    };
+
+    let mut log_ratios_sum = 0.0;
+    let mut ratios_count = 0;
+    for (i, vector) in vectors.iter().enumerate() {
+        let mut first = f64::INFINITY;
+        let mut second = f64::INFINITY;
+        for (j, other) in vectors.iter().enumerate() {
+            if i == j || vector.len() != other.len() {
+                continue;
+            }
+            let distance = euclidean(vector, other);
+            if !distance.is_finite() || distance == 0.0 {
+                continue;
+            }
+            if distance < first {
+                second = first;
+                first = distance;
+            } else if distance < second {
+                second = distance;
+            }
+        }
+        if second.is_finite() {
+            log_ratios_sum += (second / first).ln();
+            ratios_count += 1;
+        }
+    }
+
+    (ratios_count > 0 && log_ratios_sum > 0.0).then(|| ratios_count as f64 / log_ratios_sum)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn test_vector_diagnostics() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut diagnostics = VectorDiagnosticsCollector::default();
+
+        for i in 0..200 {
+            // Points on a 2-dimensional plane, embedded into 8 dimensions
+            let (x, y): (f32, f32) = (rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0));
+            let mut vector = vec![x, y, x + y, x - y, 0.0, 0.0, 0.0, 0.0];
+            if i % 50 == 0 {
+                vector = vec![0.0; 8];
+            }
+            diagnostics.add_point(Some(&VectorStructInternal::Single(vector)));
+        }
+        diagnostics.add_point(None);
+
+        let report = diagnostics.into_report();
+        assert_eq!(report.sampled_points, 201);
+
+        let vector = &report.vectors[DEFAULT_VECTOR_NAME];
+        assert_eq!(vector.points_count, 200);
+        assert_eq!(vector.vectors_count, 200);
+        assert_eq!(vector.zero_vectors, 4);
+        assert_eq!(vector.non_finite_vectors, 0);
+        // 4 zero vectors, 3 of them are duplicates
+        assert!((vector.duplicate_rate - 3.0 / 200.0).abs() < 1e-9);
+
+        let norm = vector.norm.as_ref().unwrap();
+        assert_eq!(norm.min, 0.0);
+        assert!(norm.min <= norm.p01 && norm.p01 <= norm.p50 && norm.p50 <= norm.p99);
+        assert!(norm.p99 <= norm.max);
+
+        let dimensionality = vector.intrinsic_dimensionality.unwrap();
+        assert!(
+            (1.5..3.0).contains(&dimensionality),
+            "estimated dimensionality {dimensionality}",
+        );
+    }
+
+    #[test]
+    fn test_norm_distribution() {
+        assert_eq!(norm_distribution(vec![]), None);
+
+        let norms = norm_distribution(vec![3.0, 1.0, f64::NAN, 2.0]).unwrap();
+        assert_eq!(norms.min, 1.0);
+        assert_eq!(norms.max, 3.0);
+        assert_eq!(norms.mean, 2.0);
+        assert_eq!(norms.p50, 2.0);
+    }
+}
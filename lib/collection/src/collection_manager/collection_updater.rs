@@ -1,11 +1,42 @@
+use std::sync::Arc;
+
 use parking_lot::RwLock;
-use segment::types::SeqNumberType;
+use segment::types::{PointIdType, SeqNumberType};
 
 use crate::collection_manager::holders::segment_holder::SegmentHolder;
+use crate::collection_manager::point_history::operation_point_ids;
+use crate::collection_manager::point_trash::PointTrash;
 use crate::collection_manager::segments_updater::*;
-use crate::operations::types::CollectionResult;
+use crate::operations::point_ops::{PointOperations, PointStruct};
+use crate::operations::types::{CollectionResult, WriteConflict};
 use crate::operations::CollectionUpdateOperations;
 
+/// Change of the trash of deleted points, made by an operation
+enum TrashChange {
+    /// Points, as they were before the operation, which deleted them
+    Deleted(Vec<PointStruct>),
+    /// Points, which were inserted again
+    Inserted(Vec<PointIdType>),
+}
+
+/// Result of applying an update operation to the segments of a shard
+#[derive(Debug, Default)]
+pub struct UpdateOutcome {
+    /// Number of points, affected by the operation
+    pub affected_points: usize,
+    /// Points, which were left intact because they did not satisfy the write precondition
+    pub conflicts: Vec<WriteConflict>,
+}
+
+impl From<usize> for UpdateOutcome {
+    fn from(affected_points: usize) -> Self {
+        Self {
+            affected_points,
+            conflicts: Vec::new(),
+        }
+    }
+}
+
 /// Implementation of the update operation
 #[derive(Default)]
 pub struct CollectionUpdater {}
@@ -18,7 +49,7 @@ impl CollectionUpdater {
     fn handle_update_result(
         segments: &RwLock<SegmentHolder>,
         op_num: SeqNumberType,
-        operation_result: &CollectionResult<usize>,
+        operation_result: &CollectionResult<UpdateOutcome>,
     ) {
         match operation_result {
             Ok(_) => {
@@ -42,30 +73,92 @@ impl CollectionUpdater {
         }
     }
 
+    /// Read points, which may be deleted by the operation, or ids of points, which may be
+    /// inserted again, if the collection keeps deleted points in the trash
+    fn prepare_trash(
+        segments: &SegmentHolder,
+        operation: &CollectionUpdateOperations,
+    ) -> CollectionResult<Option<(Arc<PointTrash>, TrashChange)>> {
+        let Some(point_trash) = segments.point_trash.clone() else {
+            return Ok(None);
+        };
+        let CollectionUpdateOperations::PointOperation(point_operation) = operation else {
+            return Ok(None);
+        };
+        let change = match point_operation {
+            PointOperations::DeletePoints { .. }
+            | PointOperations::DeletePointsConditional(_)
+            | PointOperations::DeletePointsByFilter(_) => {
+                let point_ids = operation_point_ids(segments, operation)?;
+                TrashChange::Deleted(PointTrash::collect(segments, &point_ids)?)
+            }
+            PointOperations::UpsertPoints(_)
+            | PointOperations::UpsertPointsConditional(_)
+            | PointOperations::SyncPoints(_) => {
+                TrashChange::Inserted(operation_point_ids(segments, operation)?)
+            }
+        };
+        Ok(Some((point_trash, change)))
+    }
+
     pub fn update(
         segments: &RwLock<SegmentHolder>,
         op_num: SeqNumberType,
         operation: CollectionUpdateOperations,
-    ) -> CollectionResult<usize> {
+    ) -> CollectionResult<UpdateOutcome> {
         // Allow only one update at a time, ensure no data races between segments.
         // let _lock = self.update_lock.lock().unwrap();
+        let history = {
+            let segments = segments.read();
+            match &segments.point_history {
+                Some(point_history) => Some((
+                    point_history.clone(),
+                    operation_point_ids(&segments, &operation)?,
+                )),
+                None => None,
+            }
+        };
+        let trash = Self::prepare_trash(&segments.read(), &operation)?;
+
         let operation_result = match operation {
             CollectionUpdateOperations::PointOperation(point_operation) => {
                 process_point_operation(segments, op_num, point_operation)
             }
             CollectionUpdateOperations::VectorOperation(vector_operation) => {
                 process_vector_operation(segments, op_num, vector_operation)
+                    .map(UpdateOutcome::from)
             }
             CollectionUpdateOperations::PayloadOperation(payload_operation) => {
                 process_payload_operation(segments, op_num, payload_operation)
             }
             CollectionUpdateOperations::FieldIndexOperation(index_operation) => {
                 process_field_index_operation(segments, op_num, &index_operation)
+                    .map(UpdateOutcome::from)
+            }
+            CollectionUpdateOperations::AttributeOperation(attribute_operation) => {
+                process_attribute_operation(segments, op_num, attribute_operation)
+                    .map(UpdateOutcome::from)
             }
         };
 
         CollectionUpdater::handle_update_result(segments, op_num, &operation_result);
 
+        if let (Ok(_), Some((point_history, point_ids))) = (&operation_result, history) {
+            if let Err(err) = point_history.record(&segments.read(), op_num, &point_ids) {
+                log::error!("Failed to record history of points: {err}");
+            }
+        }
+
+        if let (Ok(_), Some((point_trash, change))) = (&operation_result, trash) {
+            let result = match change {
+                TrashChange::Deleted(points) => point_trash.put_deleted(&segments.read(), points),
+                TrashChange::Inserted(point_ids) => point_trash.remove(&point_ids),
+            };
+            if let Err(err) = result {
+                log::error!("Failed to update trash of deleted points: {err}");
+            }
+        }
+
         operation_result
     }
 }
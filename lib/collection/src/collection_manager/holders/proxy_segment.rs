@@ -4,11 +4,13 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use bitvec::prelude::BitVec;
+use chrono::{DateTime, Utc};
 use common::types::{PointOffsetType, TelemetryDetail};
 use parking_lot::{RwLock, RwLockUpgradableReadGuard};
 use segment::common::operation_error::{OperationResult, SegmentFailedState};
+use segment::data_types::aggregation::{DistinctValues, NumericStats, ValueDistribution};
 use segment::data_types::named_vectors::NamedVectors;
-use segment::data_types::order_by::OrderValue;
+use segment::data_types::order_by::OrderedPoint;
 use segment::data_types::query_context::{QueryContext, SegmentQueryContext};
 use segment::data_types::vectors::{QueryVector, Vector};
 use segment::entry::entry_point::SegmentEntry;
@@ -16,10 +18,11 @@ use segment::index::field_index::CardinalityEstimation;
 use segment::json_path::JsonPath;
 use segment::telemetry::SegmentTelemetry;
 use segment::types::{
-    Condition, Filter, Payload, PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef, PointIdType,
-    ScoredPoint, SearchParams, SegmentConfig, SegmentInfo, SegmentType, SeqNumberType, WithPayload,
-    WithVector,
+    AttributeValue, Condition, Filter, Payload, PayloadFieldSchema, PayloadKeyType,
+    PayloadKeyTypeRef, PayloadSelector, PointIdType, ScoredPoint, SearchParams, SegmentConfig,
+    SegmentInfo, SegmentType, SeqNumberType, WithPayload, WithVector,
 };
+use sparse::common::types::DimId;
 
 use crate::collection_manager::holders::segment_holder::LockedSegment;
 
@@ -155,9 +158,10 @@ impl ProxySegment {
                 return Ok(false);
             }
 
-            let (all_vectors, payload) = (
+            let (all_vectors, payload, attributes) = (
                 wrapped_segment_guard.all_vectors(point_id)?,
                 wrapped_segment_guard.payload(point_id)?,
+                wrapped_segment_guard.attributes(point_id)?,
             );
 
             {
@@ -168,6 +172,13 @@ impl ProxySegment {
                 if !payload.is_empty() {
                     write_segment.set_full_payload(op_num, point_id, &payload)?;
                 }
+                if !attributes.is_empty() {
+                    let attributes = attributes
+                        .into_iter()
+                        .map(|(name, value)| (name, Some(value)))
+                        .collect();
+                    write_segment.set_attributes(op_num, point_id, &attributes)?;
+                }
             };
 
             point_offset
@@ -490,6 +501,19 @@ impl SegmentEntry for ProxySegment {
             .set_full_payload(op_num, point_id, full_payload)
     }
 
+    fn patch_payload(
+        &mut self,
+        op_num: SeqNumberType,
+        point_id: PointIdType,
+        patch: &Payload,
+    ) -> OperationResult<bool> {
+        self.move_if_exists(op_num, point_id)?;
+        self.write_segment
+            .get()
+            .write()
+            .patch_payload(op_num, point_id, patch)
+    }
+
     fn set_payload(
         &mut self,
         op_num: SeqNumberType,
@@ -529,6 +553,37 @@ impl SegmentEntry for ProxySegment {
             .clear_payload(op_num, point_id)
     }
 
+    fn set_attributes(
+        &mut self,
+        op_num: SeqNumberType,
+        point_id: PointIdType,
+        attributes: &HashMap<String, Option<AttributeValue>>,
+    ) -> OperationResult<bool> {
+        self.move_if_exists(op_num, point_id)?;
+        self.write_segment
+            .get()
+            .write()
+            .set_attributes(op_num, point_id, attributes)
+    }
+
+    fn attributes(
+        &self,
+        point_id: PointIdType,
+    ) -> OperationResult<HashMap<String, AttributeValue>> {
+        return if self.deleted_points.read().contains(&point_id) {
+            self.write_segment.get().read().attributes(point_id)
+        } else {
+            {
+                let write_segment = self.write_segment.get();
+                let segment_guard = write_segment.read();
+                if segment_guard.has_point(point_id) {
+                    return segment_guard.attributes(point_id);
+                }
+            }
+            self.wrapped_segment.get().read().attributes(point_id)
+        };
+    }
+
     fn vector(&self, vector_name: &str, point_id: PointIdType) -> OperationResult<Option<Vector>> {
         return if self.deleted_points.read().contains(&point_id) {
             self.write_segment
@@ -594,6 +649,31 @@ impl SegmentEntry for ProxySegment {
         };
     }
 
+    fn selected_payload(
+        &self,
+        point_id: PointIdType,
+        selector: &PayloadSelector,
+    ) -> OperationResult<Payload> {
+        if self.deleted_points.read().contains(&point_id) {
+            return self
+                .write_segment
+                .get()
+                .read()
+                .selected_payload(point_id, selector);
+        }
+        {
+            let write_segment = self.write_segment.get();
+            let segment_guard = write_segment.read();
+            if segment_guard.has_point(point_id) {
+                return segment_guard.selected_payload(point_id, selector);
+            }
+        }
+        self.wrapped_segment
+            .get()
+            .read()
+            .selected_payload(point_id, selector)
+    }
+
     /// Not implemented for proxy
     fn iter_points(&self) -> Box<dyn Iterator<Item = PointIdType> + '_> {
         // iter_points is not available for Proxy implementation
@@ -636,7 +716,7 @@ impl SegmentEntry for ProxySegment {
         limit: Option<usize>,
         filter: Option<&'a Filter>,
         order_by: &'a segment::data_types::order_by::OrderBy,
-    ) -> OperationResult<Vec<(OrderValue, PointIdType)>> {
+    ) -> OperationResult<Vec<OrderedPoint>> {
         let deleted_points = self.deleted_points.read();
         let mut read_points = if deleted_points.is_empty() {
             self.wrapped_segment
@@ -658,7 +738,7 @@ impl SegmentEntry for ProxySegment {
             .read()
             .read_ordered_filtered(limit, filter, order_by)?;
         read_points.append(&mut write_segment_points);
-        read_points.sort_unstable();
+        order_by.sort_points(&mut read_points);
         Ok(read_points)
     }
 
@@ -722,6 +802,65 @@ impl SegmentEntry for ProxySegment {
         }
     }
 
+    fn distinct_values(
+        &self,
+        key: &JsonPath,
+        filter: Option<&Filter>,
+    ) -> OperationResult<DistinctValues> {
+        let deleted_points = self.deleted_points.read();
+        let mut distinct = if deleted_points.is_empty() {
+            self.wrapped_segment
+                .get()
+                .read()
+                .distinct_values(key, filter)?
+        } else {
+            let wrapped_filter =
+                self.add_deleted_points_condition_to_filter(filter, &deleted_points);
+            self.wrapped_segment
+                .get()
+                .read()
+                .distinct_values(key, Some(&wrapped_filter))?
+        };
+        let write_segment_distinct = self
+            .write_segment
+            .get()
+            .read()
+            .distinct_values(key, filter)?;
+        distinct.merge(write_segment_distinct);
+        Ok(distinct)
+    }
+
+    fn numeric_stats(
+        &self,
+        key: &JsonPath,
+        filter: Option<&Filter>,
+    ) -> OperationResult<NumericStats> {
+        let deleted_points = self.deleted_points.read();
+        let mut stats = if deleted_points.is_empty() {
+            self.wrapped_segment
+                .get()
+                .read()
+                .numeric_stats(key, filter)?
+        } else {
+            let wrapped_filter =
+                self.add_deleted_points_condition_to_filter(filter, &deleted_points);
+            self.wrapped_segment
+                .get()
+                .read()
+                .numeric_stats(key, Some(&wrapped_filter))?
+        };
+        let write_segment_stats = self.write_segment.get().read().numeric_stats(key, filter)?;
+        stats.merge(write_segment_stats);
+        Ok(stats)
+    }
+
+    /// Points, moved from the wrapped segment, might be counted twice until the proxy is released
+    fn value_distribution(&self, key: &JsonPath) -> OperationResult<ValueDistribution> {
+        let mut distribution = self.wrapped_segment.get().read().value_distribution(key)?;
+        distribution.merge(self.write_segment.get().read().value_distribution(key)?);
+        Ok(distribution)
+    }
+
     fn estimate_point_count<'a>(&'a self, filter: Option<&'a Filter>) -> CardinalityEstimation {
         let deleted_point_count = self.deleted_points.read().len();
 
@@ -809,6 +948,17 @@ impl SegmentEntry for ProxySegment {
         }
     }
 
+    fn size_info(&self) -> SegmentInfo {
+        let wrapped_info = self.wrapped_segment.get().read().size_info();
+        let write_info = self.write_segment.get().read().size_info();
+
+        SegmentInfo {
+            ram_usage_bytes: wrapped_info.ram_usage_bytes + write_info.ram_usage_bytes,
+            disk_usage_bytes: wrapped_info.disk_usage_bytes + write_info.disk_usage_bytes,
+            ..self.info()
+        }
+    }
+
     fn config(&self) -> &SegmentConfig {
         &self.wrapped_config
     }
@@ -817,6 +967,11 @@ impl SegmentEntry for ProxySegment {
         true
     }
 
+    fn is_vector_appendable(&self, _vector_name: &str) -> bool {
+        // Updated points are moved into the write segment anyway
+        true
+    }
+
     fn flush(&self, sync: bool) -> OperationResult<SeqNumberType> {
         let deleted_points_guard = self.deleted_points.read();
         let deleted_indexes_guard = self.deleted_indexes.read();
@@ -848,6 +1003,10 @@ impl SegmentEntry for ProxySegment {
         self.wrapped_segment.get().read().data_path()
     }
 
+    fn created_at(&self) -> Option<DateTime<Utc>> {
+        self.wrapped_segment.get().read().created_at()
+    }
+
     fn delete_field_index(&mut self, op_num: u64, key: PayloadKeyTypeRef) -> OperationResult<bool> {
         if self.version() > op_num {
             return Ok(false);
@@ -992,6 +1151,18 @@ impl SegmentEntry for ProxySegment {
             .read()
             .fill_query_context(query_context)
     }
+
+    fn fill_sparse_document_frequencies(
+        &self,
+        vector_name: &str,
+        frequencies: &mut HashMap<DimId, usize>,
+    ) -> usize {
+        // Like for the query context, the temporary segment is not taken into account
+        self.wrapped_segment
+            .get()
+            .read()
+            .fill_sparse_document_frequencies(vector_name, frequencies)
+    }
 }
 
 #[cfg(test)]
@@ -1553,6 +1724,8 @@ mod tests {
             ]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_columns: Default::default(),
+            payload_compression: Default::default(),
         };
         let mut original_segment = build_segment(dir.path(), &config, true).unwrap();
         let write_segment = build_segment(dir.path(), &config, true).unwrap();
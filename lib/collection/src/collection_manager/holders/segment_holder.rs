@@ -18,9 +18,14 @@ use segment::types::{PointIdType, SegmentConfig, SeqNumberType};
 
 use crate::collection::payload_index_schema::PayloadIndexSchema;
 use crate::collection_manager::holders::proxy_segment::ProxySegment;
+use crate::collection_manager::point_history::PointHistory;
+use crate::collection_manager::point_trash::PointTrash;
 use crate::config::CollectionParams;
+use crate::events::OptimizerErrorEvent;
 use crate::operations::types::CollectionError;
+use crate::shards::shard::ShardId;
 use crate::shards::update_tracker::UpdateTracker;
+use crate::shards::CollectionId;
 
 pub type SegmentId = usize;
 
@@ -152,6 +157,15 @@ pub struct SegmentHolder {
 
     /// Holds the first uncorrected error happened with optimizer
     pub optimizer_errors: Option<CollectionError>,
+
+    /// Collection and shard, the segments belong to. Used to publish events.
+    pub(crate) owner: Option<(CollectionId, ShardId)>,
+
+    /// Last versions of updated points, if the collection retains point history
+    pub(crate) point_history: Option<Arc<PointHistory>>,
+
+    /// Deleted points, if the collection keeps them in the trash
+    pub(crate) point_trash: Option<Arc<PointTrash>>,
 }
 
 pub type LockedSegmentHolder = Arc<RwLock<SegmentHolder>>;
@@ -579,10 +593,22 @@ impl<'s> SegmentHolder {
                         |_appendable_idx, appendable_write_segment| {
                             let all_vectors = write_segment.all_vectors(point_id)?;
                             let payload = write_segment.payload(point_id)?;
+                            let attributes = write_segment.attributes(point_id)?;
 
                             appendable_write_segment.upsert_point(op_num, point_id, all_vectors)?;
                             appendable_write_segment
                                 .set_full_payload(op_num, point_id, &payload)?;
+                            if !attributes.is_empty() {
+                                let attributes = attributes
+                                    .into_iter()
+                                    .map(|(name, value)| (name, Some(value)))
+                                    .collect();
+                                appendable_write_segment.set_attributes(
+                                    op_num,
+                                    point_id,
+                                    &attributes,
+                                )?;
+                            }
 
                             write_segment.delete_point(op_num, point_id)?;
 
@@ -690,6 +716,14 @@ impl<'s> SegmentHolder {
             "Must flush appendable segments first",
         );
 
+        // History is flushed before segments, so that versions of persisted operations are not lost
+        if let Some(point_history) = &self.point_history {
+            point_history.flusher()()?;
+        }
+        if let Some(point_trash) = &self.point_trash {
+            point_trash.flusher()()?;
+        }
+
         let mut max_persisted_version: SeqNumberType = SeqNumberType::MIN;
         let mut min_unsaved_version: SeqNumberType = SeqNumberType::MAX;
         let mut has_unsaved = false;
@@ -881,6 +915,8 @@ impl<'s> SegmentHolder {
                     .to_sparse_vector_data()
                     .map_err(|err| OperationError::service_error(format!("Failed to source sparse vector configuration from collection parameters: {err:?}")))?,
                 payload_storage_type: collection_params.payload_storage_type(),
+                payload_columns: collection_params.payload_columns.clone(),
+                payload_compression: collection_params.payload_compression,
             },
             // Fall back: base config on existing appendable segment
             None => {
@@ -1137,7 +1173,15 @@ impl<'s> SegmentHolder {
         // Save only the first error
         // If is more likely to be the real cause of all further problems
         if self.optimizer_errors.is_none() {
-            self.optimizer_errors = Some(error.into());
+            let error = error.into();
+            if let Some((collection_id, shard_id)) = &self.owner {
+                issues::publish(OptimizerErrorEvent {
+                    collection_id: collection_id.clone(),
+                    shard_id: *shard_id,
+                    error: error.to_string(),
+                });
+            }
+            self.optimizer_errors = Some(error);
         }
     }
 
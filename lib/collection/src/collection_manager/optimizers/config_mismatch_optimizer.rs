@@ -5,8 +5,11 @@ use std::sync::Arc;
 
 use parking_lot::Mutex;
 use segment::common::operation_time_statistics::OperationDurationsAggregator;
+use segment::entry::entry_point::SegmentEntry;
 use segment::index::sparse_index::sparse_index_config::SparseIndexType;
-use segment::types::{HnswConfig, Indexes, QuantizationConfig, SegmentType};
+use segment::types::{
+    HnswConfig, Indexes, QuantizationConfig, SegmentType, VectorDataConfig, VectorStorageType,
+};
 
 use crate::collection_manager::holders::segment_holder::{LockedSegmentHolder, SegmentId};
 use crate::collection_manager::optimizers::segment_optimizer::{
@@ -97,6 +100,116 @@ impl ConfigMismatchOptimizer {
         }
     }
 
+    /// Calculates quantization config that should be used for a given vector
+    /// with current configuration.
+    fn get_required_quantization_config(&self, vector_name: &str) -> Option<QuantizationConfig> {
+        self.collection_params
+            .vectors
+            .get_params(vector_name)
+            .and_then(|vector_params| vector_params.quantization_config.clone())
+            .or_else(|| self.quantization_config.clone())
+    }
+
+    /// Check, whether the segment has to be rebuilt to match the current configuration
+    pub(crate) fn requires_rebuild(&self, segment: &dyn SegmentEntry) -> bool {
+        let segment_config = segment.config();
+
+        if segment.segment_type() == SegmentType::Special {
+            return false; // Never optimize already optimized segment
+        }
+
+        // Also migrates payload between RocksDB and gridstore storages
+        if self.collection_params.payload_storage_type() != segment_config.payload_storage_type {
+            return true;
+        }
+
+        // Determine whether dense data in segment has mismatch
+        let dense_has_mismatch =
+            segment_config
+                .vector_data
+                .iter()
+                .any(|(vector_name, vector_data)| {
+                    // Check HNSW mismatch
+                    match &vector_data.index {
+                        Indexes::Plain {} => {}
+                        Indexes::Hnsw(effective_hnsw) => {
+                            // Select segment if we have an HNSW mismatch that requires rebuild
+                            let target_hnsw = self.get_required_hnsw_config(vector_name);
+                            if effective_hnsw.mismatch_requires_rebuild(&target_hnsw) {
+                                return true;
+                            }
+                        }
+                    }
+
+                    if let Some(is_required_on_disk) = self.check_if_vectors_on_disk(vector_name) {
+                        if is_required_on_disk != vector_data.storage_type.is_on_disk() {
+                            return true;
+                        }
+                    }
+
+                    // Check quantization mismatch
+                    let target_quantization = self.get_required_quantization_config(vector_name);
+                    let quantization_mismatch = vector_data
+                        .quantization_config
+                        .as_ref()
+                        .zip(target_quantization.as_ref())
+                        // Rebuild if current parameters differ from target parameters
+                        .map(|(current, target)| current.mismatch_requires_rebuild(target))
+                        // Or rebuild if we now change the enabled state on an indexed segment
+                        .unwrap_or_else(|| {
+                            vector_data.index.is_indexed()
+                                && (vector_data.quantization_config.is_some()
+                                    != target_quantization.is_some())
+                        });
+
+                    quantization_mismatch
+                });
+
+        // Determine whether dense data in segment has mismatch
+        let sparse_has_mismatch =
+            segment_config
+                .sparse_vector_data
+                .iter()
+                .any(|(vector_name, vector_data)| {
+                    let Some(is_required_on_disk) =
+                        self.check_if_sparse_vectors_index_on_disk(vector_name)
+                    else {
+                        return false; // Do nothing if not specified
+                    };
+
+                    match vector_data.index.index_type {
+                        SparseIndexType::MutableRam => false, // Do nothing for mutable RAM
+                        SparseIndexType::ImmutableRam => is_required_on_disk, // Rebuild if we require on disk
+                        SparseIndexType::Mmap => !is_required_on_disk, // Rebuild if we require in RAM
+                    }
+                });
+
+        sparse_has_mismatch || dense_has_mismatch
+    }
+
+    /// Config of the dense vector data, as it would be after the segment is rebuilt
+    pub(crate) fn target_vector_config(
+        &self,
+        vector_name: &str,
+        current: &VectorDataConfig,
+    ) -> VectorDataConfig {
+        let mut target = current.clone();
+        if let Some(is_required_on_disk) = self.check_if_vectors_on_disk(vector_name) {
+            if is_required_on_disk != current.storage_type.is_on_disk() {
+                target.storage_type = if is_required_on_disk {
+                    VectorStorageType::Mmap
+                } else {
+                    VectorStorageType::Memory
+                };
+            }
+        }
+        if current.index.is_indexed() {
+            target.index = Indexes::Hnsw(self.get_required_hnsw_config(vector_name).into_owned());
+            target.quantization_config = self.get_required_quantization_config(vector_name);
+        }
+        target
+    }
+
     fn worst_segment(
         &self,
         segments: LockedSegmentHolder,
@@ -114,91 +227,8 @@ impl ConfigMismatchOptimizer {
                     .max_available_vectors_size_in_bytes()
                     .unwrap_or_default();
 
-                let segment_config = read_segment.config();
-
-                if read_segment.segment_type() == SegmentType::Special {
-                    return None; // Never optimize already optimized segment
-                }
-
-                if self.collection_params.on_disk_payload
-                    != segment_config.payload_storage_type.is_on_disk()
-                {
-                    return Some((*idx, vector_size)); // Skip segments with payload mismatch
-                }
-
-                // Determine whether dense data in segment has mismatch
-                let dense_has_mismatch =
-                    segment_config
-                        .vector_data
-                        .iter()
-                        .any(|(vector_name, vector_data)| {
-                            // Check HNSW mismatch
-                            match &vector_data.index {
-                                Indexes::Plain {} => {}
-                                Indexes::Hnsw(effective_hnsw) => {
-                                    // Select segment if we have an HNSW mismatch that requires rebuild
-                                    let target_hnsw = self.get_required_hnsw_config(vector_name);
-                                    if effective_hnsw.mismatch_requires_rebuild(&target_hnsw) {
-                                        return true;
-                                    }
-                                }
-                            }
-
-                            if let Some(is_required_on_disk) =
-                                self.check_if_vectors_on_disk(vector_name)
-                            {
-                                if is_required_on_disk != vector_data.storage_type.is_on_disk() {
-                                    return true;
-                                }
-                            }
-
-                            // Check quantization mismatch
-                            let target_quantization_collection = self.quantization_config.as_ref();
-                            let target_quantization_vector = self
-                                .collection_params
-                                .vectors
-                                .get_params(vector_name)
-                                .and_then(|vector_params| {
-                                    vector_params.quantization_config.clone()
-                                });
-                            let target_quantization = target_quantization_vector
-                                .as_ref()
-                                .or(target_quantization_collection);
-                            let quantization_mismatch = vector_data
-                                .quantization_config
-                                .as_ref()
-                                .zip(target_quantization)
-                                // Rebuild if current parameters differ from target parameters
-                                .map(|(current, target)| current.mismatch_requires_rebuild(target))
-                                // Or rebuild if we now change the enabled state on an indexed segment
-                                .unwrap_or_else(|| {
-                                    vector_data.index.is_indexed()
-                                        && (vector_data.quantization_config.is_some()
-                                            != target_quantization.is_some())
-                                });
-
-                            quantization_mismatch
-                        });
-
-                // Determine whether dense data in segment has mismatch
-                let sparse_has_mismatch =
-                    segment_config
-                        .sparse_vector_data
-                        .iter()
-                        .any(|(vector_name, vector_data)| {
-                            let Some(is_required_on_disk) =
-                                self.check_if_sparse_vectors_index_on_disk(vector_name)
-                            else {
-                                return false; // Do nothing if not specified
-                            };
-
-                            match vector_data.index.index_type {
-                                SparseIndexType::MutableRam => false, // Do nothing for mutable RAM
-                                SparseIndexType::ImmutableRam => is_required_on_disk, // Rebuild if we require on disk
-                                SparseIndexType::Mmap => !is_required_on_disk, // Rebuild if we require in RAM
-                            }
-                        });
-                (sparse_has_mismatch || dense_has_mismatch).then_some((*idx, vector_size))
+                self.requires_rebuild(&*read_segment)
+                    .then_some((*idx, vector_size))
             })
             .collect();
 
@@ -57,6 +57,31 @@ impl TrackerLog {
         });
     }
 
+    /// Speed of finished optimizations in the log, in points per second
+    pub fn points_per_second(&self) -> Option<f64> {
+        let (points, seconds) = self
+            .descriptions
+            .iter()
+            .filter_map(|tracker| {
+                let state = tracker.state.lock();
+                if state.status != TrackerStatus::Done {
+                    return None;
+                }
+                let duration = state.end_at? - tracker.start_at;
+                Some((
+                    tracker.num_points,
+                    duration.num_milliseconds() as f64 / 1000.0,
+                ))
+            })
+            .fold(
+                (0, 0.0),
+                |(points, seconds), (tracker_points, tracker_seconds)| {
+                    (points + tracker_points, seconds + tracker_seconds)
+                },
+            );
+        (points > 0 && seconds > 0.0).then(|| points as f64 / seconds)
+    }
+
     /// Convert log into list of objects usable in telemetry
     pub fn to_telemetry(&self) -> Vec<TrackerTelemetry> {
         self.descriptions
@@ -75,6 +100,8 @@ pub struct Tracker {
     pub name: String,
     /// Segment IDs being optimized
     pub segment_ids: Vec<SegmentId>,
+    /// Number of points in the optimized segments
+    pub num_points: usize,
     /// Start time of the optimizer
     pub start_at: DateTime<Utc>,
     /// Latest state of the optimizer
@@ -83,10 +110,11 @@ pub struct Tracker {
 
 impl Tracker {
     /// Start a new optimizer tracker
-    pub fn start(name: impl Into<String>, segment_ids: Vec<SegmentId>) -> Self {
+    pub fn start(name: impl Into<String>, segment_ids: Vec<SegmentId>, num_points: usize) -> Self {
         Self {
             name: name.into(),
             segment_ids,
+            num_points,
             state: Default::default(),
             start_at: Utc::now(),
         }
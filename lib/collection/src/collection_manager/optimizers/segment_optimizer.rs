@@ -17,8 +17,8 @@ use segment::segment::{Segment, SegmentVersion};
 use segment::segment_constructor::build_segment;
 use segment::segment_constructor::segment_builder::SegmentBuilder;
 use segment::types::{
-    HnswConfig, Indexes, PayloadFieldSchema, PayloadKeyType, PayloadStorageType, PointIdType,
-    QuantizationConfig, SegmentConfig, VectorStorageType,
+    HnswConfig, Indexes, PayloadFieldSchema, PayloadKeyType, PointIdType, QuantizationConfig,
+    SegmentConfig, VectorStorageType,
 };
 
 use crate::collection_manager::holders::proxy_segment::ProxySegment;
@@ -38,6 +38,26 @@ pub struct OptimizerThresholds {
     pub indexing_threshold_kb: usize,
 }
 
+/// Cancel the optimization, if there is not enough free space to build the new segment
+fn check_free_disk_space(path: &Path, required_bytes: u64) -> CollectionResult<()> {
+    match fs4::available_space(path) {
+        Ok(free_bytes) if free_bytes < required_bytes => Err(CollectionError::Cancelled {
+            description: format!(
+                "not enough disk space to build optimized segment, \
+                 required: {required_bytes} bytes, available: {free_bytes} bytes",
+            ),
+        }),
+        Ok(_) => Ok(()),
+        Err(err) => {
+            log::debug!(
+                "Failed to get free space for path: {} due to: {err}",
+                path.display(),
+            );
+            Ok(())
+        }
+    }
+}
+
 /// SegmentOptimizer - trait implementing common functionality of the optimizers
 ///
 /// It provides functions which allow to re-build specified segments into a new, better one.
@@ -83,11 +103,9 @@ pub trait SegmentOptimizer {
         let config = SegmentConfig {
             vector_data: collection_params.to_base_vector_data()?,
             sparse_vector_data: collection_params.to_sparse_vector_data()?,
-            payload_storage_type: if collection_params.on_disk_payload {
-                PayloadStorageType::OnDisk
-            } else {
-                PayloadStorageType::InMemory
-            },
+            payload_storage_type: collection_params.payload_storage_type(),
+            payload_columns: collection_params.payload_columns.clone(),
+            payload_compression: collection_params.payload_compression,
         };
         Ok(LockedSegment::new(build_segment(
             self.segments_path(),
@@ -235,11 +253,9 @@ pub trait SegmentOptimizer {
         let optimized_config = SegmentConfig {
             vector_data,
             sparse_vector_data,
-            payload_storage_type: if collection_params.on_disk_payload {
-                PayloadStorageType::OnDisk
-            } else {
-                PayloadStorageType::InMemory
-            },
+            payload_storage_type: collection_params.payload_storage_type(),
+            payload_columns: collection_params.payload_columns.clone(),
+            payload_compression: collection_params.payload_compression,
         };
 
         Ok(SegmentBuilder::new(
@@ -459,6 +475,14 @@ pub trait SegmentOptimizer {
             return Ok(false);
         }
 
+        // The new segment takes about as much space as the optimized ones, until they are removed.
+        // Better to postpone the optimization, than to run out of space in the middle of it.
+        let required_space: usize = optimizing_segments
+            .iter()
+            .map(|segment| segment.get().read().info().disk_usage_bytes)
+            .sum();
+        check_free_disk_space(self.temp_path(), required_space as u64)?;
+
         check_process_stopped(stopped)?;
 
         let tmp_segment = self.temp_segment(false)?;
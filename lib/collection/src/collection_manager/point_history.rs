@@ -0,0 +1,277 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use segment::common::rocksdb_wrapper::{open_db, DatabaseColumnWrapper};
+use segment::common::Flusher;
+use segment::data_types::vectors::VectorStructInternal;
+use segment::types::{Filter, Payload, PointIdType, SeqNumberType};
+use serde::{Deserialize, Serialize};
+
+use super::holders::segment_holder::SegmentHolder;
+use super::segments_updater::points_by_filter;
+use crate::operations::attribute_ops::AttributeOps;
+use crate::operations::payload_ops::PayloadOps;
+use crate::operations::point_ops::{PointInsertOperationsInternal, PointOperations};
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::vector_ops::VectorOperations;
+use crate::operations::CollectionUpdateOperations;
+
+/// Directory of the history storage, relative to the shard directory
+pub const POINT_HISTORY_PATH: &str = "history";
+const DB_HISTORY_CF: &str = "history";
+
+/// State of a point after one of its updates
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct PointVersion {
+    /// Version of the point, the number of the operation which produced it
+    pub version: SeqNumberType,
+    /// Time, when the version was recorded
+    pub timestamp: DateTime<Utc>,
+    /// The point was deleted by this version
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub deleted: bool,
+    pub payload: Option<Payload>,
+    pub vector: Option<api::rest::VectorStruct>,
+}
+
+/// Last versions of the points of a shard
+///
+/// Versions of each point are stored under the point id, oldest first. Points keep their history
+/// when they are moved between segments by optimizers, as it is stored outside of the segments.
+pub struct PointHistory {
+    db_wrapper: DatabaseColumnWrapper,
+    max_versions: usize,
+}
+
+impl PointHistory {
+    /// Open the history of the shard, keeping up to `max_versions` versions of each point
+    pub fn open(shard_path: &Path, max_versions: usize) -> CollectionResult<Self> {
+        let db = open_db(&shard_path.join(POINT_HISTORY_PATH), &[DB_HISTORY_CF])
+            .map_err(|err| CollectionError::service_error(format!("{err}")))?;
+        Ok(Self {
+            db_wrapper: DatabaseColumnWrapper::new(db, DB_HISTORY_CF),
+            max_versions: max_versions.max(1),
+        })
+    }
+
+    /// Retained versions of the point, oldest first
+    pub fn get(&self, point_id: PointIdType) -> CollectionResult<Vec<PointVersion>> {
+        let key = Self::key(point_id)?;
+        let Some(value) = self.db_wrapper.get_pinned(&key, |value| value.to_vec())? else {
+            return Ok(Vec::new());
+        };
+        serde_cbor::from_slice(&value).map_err(|err| {
+            CollectionError::service_error(format!("Failed to read history of {point_id}: {err}"))
+        })
+    }
+
+    /// Record the current state of the points, affected by an operation
+    ///
+    /// Points, which are not found in the segments, get a deleted version if they have history.
+    /// Points, whose version did not change, are skipped, so replaying operations from WAL
+    /// doesn't duplicate versions.
+    pub fn record(
+        &self,
+        segments: &SegmentHolder,
+        op_num: SeqNumberType,
+        point_ids: &[PointIdType],
+    ) -> CollectionResult<()> {
+        if point_ids.is_empty() {
+            return Ok(());
+        }
+
+        let mut current: HashMap<PointIdType, PointVersion> = HashMap::new();
+        let timestamp = Utc::now();
+        segments.read_points(point_ids, |id, segment| {
+            let Some(version) = segment.point_version(id) else {
+                return Ok(true);
+            };
+            // Point may be found in several segments while it is moved, the latest one wins
+            if current
+                .get(&id)
+                .map_or(true, |found| found.version < version)
+            {
+                let vector = VectorStructInternal::from(segment.all_vectors(id)?);
+                current.insert(
+                    id,
+                    PointVersion {
+                        version,
+                        timestamp,
+                        deleted: false,
+                        payload: Some(segment.payload(id)?),
+                        vector: Some(vector.into()),
+                    },
+                );
+            }
+            Ok(true)
+        })?;
+
+        for &point_id in point_ids {
+            let mut versions = self.get(point_id)?;
+            let last = versions.last();
+            let new_version = match current.remove(&point_id) {
+                Some(version) => {
+                    if last.is_some_and(|last| last.version >= version.version && !last.deleted) {
+                        continue;
+                    }
+                    version
+                }
+                None => {
+                    if last.map_or(true, |last| last.deleted) {
+                        continue;
+                    }
+                    PointVersion {
+                        version: op_num,
+                        timestamp,
+                        deleted: true,
+                        payload: None,
+                        vector: None,
+                    }
+                }
+            };
+            versions.push(new_version);
+            if versions.len() > self.max_versions {
+                versions.drain(..versions.len() - self.max_versions);
+            }
+            self.put(point_id, &versions)?;
+        }
+        Ok(())
+    }
+
+    /// Select the version of the point, which was current at the given `version` or `timestamp`.
+    /// If neither is given, all retained versions are returned.
+    pub fn select(
+        versions: Vec<PointVersion>,
+        version: Option<SeqNumberType>,
+        timestamp: Option<DateTime<Utc>>,
+    ) -> Vec<PointVersion> {
+        if version.is_none() && timestamp.is_none() {
+            return versions;
+        }
+        versions
+            .into_iter()
+            .filter(|point_version| {
+                version.map_or(true, |version| point_version.version <= version)
+                    && timestamp.map_or(true, |timestamp| point_version.timestamp <= timestamp)
+            })
+            .last()
+            .into_iter()
+            .collect()
+    }
+
+    pub fn flusher(&self) -> Flusher {
+        self.db_wrapper.flusher()
+    }
+
+    fn put(&self, point_id: PointIdType, versions: &[PointVersion]) -> CollectionResult<()> {
+        let value = serde_cbor::to_vec(versions).map_err(|err| {
+            CollectionError::service_error(format!("Failed to write history of {point_id}: {err}"))
+        })?;
+        self.db_wrapper.put(Self::key(point_id)?, value)?;
+        Ok(())
+    }
+
+    fn key(point_id: PointIdType) -> CollectionResult<Vec<u8>> {
+        serde_cbor::to_vec(&point_id).map_err(|err| {
+            CollectionError::service_error(format!("Bad point id {point_id}: {err}"))
+        })
+    }
+}
+
+/// Points, which may be changed by the operation
+///
+/// Points, selected by a filter, are resolved before the operation is applied.
+pub(crate) fn operation_point_ids(
+    segments: &SegmentHolder,
+    operation: &CollectionUpdateOperations,
+) -> CollectionResult<Vec<PointIdType>> {
+    let insert_ids = |insert: &PointInsertOperationsInternal| match insert {
+        PointInsertOperationsInternal::PointsBatch(batch) => batch.ids.clone(),
+        PointInsertOperationsInternal::PointsList(points) => {
+            points.iter().map(|point| point.id).collect()
+        }
+    };
+    let selected_ids = |points: &Option<Vec<PointIdType>>,
+                        filter: Option<&Filter>|
+     -> CollectionResult<Vec<PointIdType>> {
+        let mut ids = points.clone().unwrap_or_default();
+        if let Some(filter) = filter {
+            ids.extend(points_by_filter(segments, filter)?);
+        }
+        Ok(ids)
+    };
+
+    let ids = match operation {
+        CollectionUpdateOperations::PointOperation(operation) => match operation {
+            PointOperations::UpsertPoints(insert) => insert_ids(insert),
+            PointOperations::UpsertPointsConditional(operation) => insert_ids(&operation.points_op),
+            PointOperations::DeletePoints { ids } => ids.clone(),
+            PointOperations::DeletePointsConditional(operation) => operation.ids.clone(),
+            PointOperations::DeletePointsByFilter(filter) => points_by_filter(segments, filter)?,
+            PointOperations::SyncPoints(sync) => sync.points.iter().map(|point| point.id).collect(),
+        },
+        CollectionUpdateOperations::VectorOperation(operation) => match operation {
+            VectorOperations::UpdateVectors(operation) => {
+                operation.points.iter().map(|point| point.id).collect()
+            }
+            VectorOperations::DeleteVectors(points, _) => points.points.clone(),
+            VectorOperations::DeleteVectorsByFilter(filter, _) => {
+                points_by_filter(segments, filter)?
+            }
+        },
+        CollectionUpdateOperations::PayloadOperation(operation) => match operation {
+            PayloadOps::SetPayload(operation)
+            | PayloadOps::OverwritePayload(operation)
+            | PayloadOps::PatchPayload(operation) => {
+                selected_ids(&operation.points, operation.filter.as_ref())?
+            }
+            PayloadOps::SetPayloadConditional(operation) => selected_ids(
+                &operation.payload_op.points,
+                operation.payload_op.filter.as_ref(),
+            )?,
+            PayloadOps::DeletePayload(operation) => {
+                selected_ids(&operation.points, operation.filter.as_ref())?
+            }
+            PayloadOps::ClearPayload { points } => points.clone(),
+            PayloadOps::ClearPayloadByFilter(filter) => points_by_filter(segments, filter)?,
+        },
+        CollectionUpdateOperations::FieldIndexOperation(_) => Vec::new(),
+        CollectionUpdateOperations::AttributeOperation(AttributeOps::SetAttributes(operation)) => {
+            selected_ids(&operation.points, operation.filter.as_ref())?
+        }
+    };
+    Ok(ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point_version(version: SeqNumberType, timestamp: i64) -> PointVersion {
+        PointVersion {
+            version,
+            timestamp: DateTime::from_timestamp(timestamp, 0).unwrap(),
+            deleted: false,
+            payload: None,
+            vector: None,
+        }
+    }
+
+    #[test]
+    fn test_select_point_version() {
+        let versions = vec![point_version(3, 100), point_version(7, 200)];
+
+        assert_eq!(PointHistory::select(versions.clone(), None, None).len(), 2);
+        assert_eq!(
+            PointHistory::select(versions.clone(), Some(5), None),
+            vec![point_version(3, 100)],
+        );
+        assert_eq!(
+            PointHistory::select(versions.clone(), None, DateTime::from_timestamp(250, 0)),
+            vec![point_version(7, 200)],
+        );
+        assert!(PointHistory::select(versions, Some(1), None).is_empty());
+    }
+}
@@ -0,0 +1,274 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use chrono::{DateTime, TimeDelta, Utc};
+use schemars::JsonSchema;
+use segment::common::rocksdb_wrapper::{open_db, DatabaseColumnWrapper};
+use segment::common::Flusher;
+use segment::data_types::vectors::{VectorStructInternal, DEFAULT_VECTOR_NAME};
+use segment::index::field_index::FieldIndex;
+use segment::payload_storage::query_checker::{check_filter, check_payload};
+use segment::types::{
+    Condition, Filter, OwnedPayloadRef, Payload, PayloadKeyType, PointIdType, SeqNumberType,
+};
+use serde::{Deserialize, Serialize};
+
+use super::holders::segment_holder::SegmentHolder;
+use crate::operations::point_ops::PointStruct;
+use crate::operations::types::{CollectionError, CollectionResult};
+
+/// Directory of the trash storage, relative to the shard directory
+pub const POINT_TRASH_PATH: &str = "trash";
+const DB_TRASH_CF: &str = "trash";
+
+/// Point, removed by a delete operation, as it was at the moment of deletion
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashedPoint {
+    pub deleted_at: DateTime<Utc>,
+    pub point: PointStruct,
+}
+
+/// Deleted point, which can still be restored
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct DeletedRecord {
+    /// Id of the point
+    pub id: PointIdType,
+    /// Time, when the point was deleted
+    pub deleted_at: DateTime<Utc>,
+    /// Payload of the point at the moment of deletion
+    pub payload: Option<Payload>,
+    /// Vector of the point at the moment of deletion
+    pub vector: Option<api::rest::VectorStruct>,
+}
+
+/// Deleted points of a shard, retained for a configured period
+///
+/// Points are removed from the segments as usual, so they are excluded from search and the
+/// vacuum optimizer reclaims their space. A copy of each deleted point is stored here until the
+/// retention period expires, so it can be inserted again.
+pub struct PointTrash {
+    db_wrapper: DatabaseColumnWrapper,
+    retention: TimeDelta,
+}
+
+impl PointTrash {
+    /// Open the trash of the shard, keeping deleted points for `retention_sec` seconds
+    pub fn open(shard_path: &Path, retention_sec: u64) -> CollectionResult<Self> {
+        let db = open_db(&shard_path.join(POINT_TRASH_PATH), &[DB_TRASH_CF])
+            .map_err(|err| CollectionError::service_error(format!("{err}")))?;
+        let retention = i64::try_from(retention_sec)
+            .ok()
+            .and_then(TimeDelta::try_seconds)
+            .unwrap_or(TimeDelta::MAX);
+        Ok(Self {
+            db_wrapper: DatabaseColumnWrapper::new(db, DB_TRASH_CF),
+            retention,
+        })
+    }
+
+    /// Read the points, which are about to be deleted, from the segments
+    pub fn collect(
+        segments: &SegmentHolder,
+        point_ids: &[PointIdType],
+    ) -> CollectionResult<Vec<PointStruct>> {
+        let mut points: HashMap<PointIdType, (SeqNumberType, PointStruct)> = HashMap::new();
+        segments.read_points(point_ids, |id, segment| {
+            let version = segment.point_version(id).unwrap_or_default();
+            // Point may be found in several segments while it is moved, the latest one wins
+            if points.get(&id).map_or(true, |(found, _)| *found < version) {
+                let vector = VectorStructInternal::from(segment.all_vectors(id)?);
+                let point = PointStruct {
+                    id,
+                    vector: vector.into(),
+                    payload: Some(segment.payload(id)?),
+                };
+                points.insert(id, (version, point));
+            }
+            Ok(true)
+        })?;
+        Ok(points.into_values().map(|(_, point)| point).collect())
+    }
+
+    /// Move `points` into the trash, if they are not present in the segments anymore
+    pub fn put_deleted(
+        &self,
+        segments: &SegmentHolder,
+        points: Vec<PointStruct>,
+    ) -> CollectionResult<()> {
+        let point_ids: Vec<_> = points.iter().map(|point| point.id).collect();
+        let mut present = HashSet::new();
+        segments.read_points(&point_ids, |id, _| {
+            present.insert(id);
+            Ok(true)
+        })?;
+
+        let deleted_at = Utc::now();
+        for point in points {
+            if present.contains(&point.id) {
+                continue;
+            }
+            let key = Self::key(point.id)?;
+            let value = serde_cbor::to_vec(&TrashedPoint { deleted_at, point })
+                .map_err(|err| CollectionError::service_error(format!("{err}")))?;
+            self.db_wrapper.put(key, value)?;
+        }
+        Ok(())
+    }
+
+    /// Remove the points from the trash, e.g. because they were inserted again
+    pub fn remove(&self, point_ids: &[PointIdType]) -> CollectionResult<()> {
+        for &point_id in point_ids {
+            self.db_wrapper.remove(Self::key(point_id)?)?;
+        }
+        Ok(())
+    }
+
+    /// Deleted point, if its retention period is not expired
+    pub fn get(&self, point_id: PointIdType) -> CollectionResult<Option<TrashedPoint>> {
+        let key = Self::key(point_id)?;
+        let Some(value) = self.db_wrapper.get_pinned(&key, |value| value.to_vec())? else {
+            return Ok(None);
+        };
+        let trashed = Self::decode(&value)?;
+        Ok(Some(trashed).filter(|trashed| !self.is_expired(trashed, Utc::now())))
+    }
+
+    /// Deleted points with ids in `from..to`, matching the filter, ordered by id
+    pub fn scan(
+        &self,
+        from: Option<PointIdType>,
+        to: Option<PointIdType>,
+        filter: Option<&Filter>,
+    ) -> CollectionResult<Vec<TrashedPoint>> {
+        let now = Utc::now();
+        let mut points = Vec::new();
+        for (_, value) in self.db_wrapper.lock_db().iter()? {
+            let trashed = Self::decode(&value)?;
+            let id = trashed.point.id;
+            if self.is_expired(&trashed, now)
+                || from.is_some_and(|from| id < from)
+                || to.is_some_and(|to| id >= to)
+                || filter.is_some_and(|filter| !Self::check_filter(filter, &trashed.point))
+            {
+                continue;
+            }
+            points.push(trashed);
+        }
+        points.sort_unstable_by_key(|trashed| trashed.point.id);
+        Ok(points)
+    }
+
+    /// Remove points, whose retention period is expired. Returns the number of removed points.
+    pub fn purge_expired(&self) -> CollectionResult<usize> {
+        let now = Utc::now();
+        let mut expired = Vec::new();
+        for (key, value) in self.db_wrapper.lock_db().iter()? {
+            if self.is_expired(&Self::decode(&value)?, now) {
+                expired.push(key);
+            }
+        }
+        for key in &expired {
+            self.db_wrapper.remove(key)?;
+        }
+        Ok(expired.len())
+    }
+
+    pub fn flusher(&self) -> Flusher {
+        self.db_wrapper.flusher()
+    }
+
+    fn is_expired(&self, trashed: &TrashedPoint, now: DateTime<Utc>) -> bool {
+        now.signed_duration_since(trashed.deleted_at) > self.retention
+    }
+
+    /// Check the filter against the stored point, as there are no indexes for trashed points
+    fn check_filter(filter: &Filter, point: &PointStruct) -> bool {
+        let no_indexes: HashMap<PayloadKeyType, Vec<FieldIndex>> = HashMap::new();
+        let empty_payload = Payload::default();
+        let payload = point.payload.as_ref().unwrap_or(&empty_payload);
+        let checker = |condition: &Condition| match condition {
+            Condition::HasId(has_id) => has_id.has_id.contains(&point.id),
+            Condition::HasVector(has_vector) => match &point.vector {
+                api::rest::VectorStruct::Named(vectors) => {
+                    vectors.contains_key(&has_vector.has_vector)
+                }
+                _ => has_vector.has_vector == DEFAULT_VECTOR_NAME,
+            },
+            Condition::Resharding(condition) => condition.check(point.id),
+            condition => check_payload(
+                Box::new(|| OwnedPayloadRef::from(payload)),
+                None,
+                &Filter::new_must(condition.clone()),
+                0,
+                &no_indexes,
+            ),
+        };
+        check_filter(&checker, filter)
+    }
+
+    fn decode(value: &[u8]) -> CollectionResult<TrashedPoint> {
+        serde_cbor::from_slice(value).map_err(|err| {
+            CollectionError::service_error(format!("Failed to read deleted point: {err}"))
+        })
+    }
+
+    fn key(point_id: PointIdType) -> CollectionResult<Vec<u8>> {
+        serde_cbor::to_vec(&point_id).map_err(|err| {
+            CollectionError::service_error(format!("Bad point id {point_id}: {err}"))
+        })
+    }
+}
+
+impl From<TrashedPoint> for DeletedRecord {
+    fn from(trashed: TrashedPoint) -> Self {
+        let TrashedPoint { deleted_at, point } = trashed;
+        Self {
+            id: point.id,
+            deleted_at,
+            payload: point.payload,
+            vector: Some(point.vector),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::collection_manager::fixtures::build_segment_1;
+    use crate::collection_manager::holders::segment_holder::SegmentHolder;
+
+    #[test]
+    fn test_point_trash() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let shard_dir = Builder::new().prefix("shard_dir").tempdir().unwrap();
+
+        let mut holder = SegmentHolder::default();
+        holder.add_new(build_segment_1(dir.path()));
+
+        let trash = PointTrash::open(shard_dir.path(), 60).unwrap();
+        let points = PointTrash::collect(&holder, &[1.into(), 2.into(), 100.into()]).unwrap();
+        assert_eq!(points.len(), 2);
+
+        // Points, which are still present in the segments, are not trashed
+        trash.put_deleted(&holder, points.clone()).unwrap();
+        assert!(trash.get(1.into()).unwrap().is_none());
+
+        let empty_holder = SegmentHolder::default();
+        trash.put_deleted(&empty_holder, points).unwrap();
+        assert_eq!(trash.get(1.into()).unwrap().unwrap().point.id, 1.into());
+        assert_eq!(trash.scan(None, None, None).unwrap().len(), 2);
+        assert_eq!(trash.scan(Some(2.into()), None, None).unwrap().len(), 1);
+
+        let filter: Filter = serde_json::from_value(json!({"must": [{"has_id": [2]}]})).unwrap();
+        let found = trash.scan(None, None, Some(&filter)).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].point.id, 2.into());
+
+        trash.remove(&[2.into()]).unwrap();
+        assert!(trash.get(2.into()).unwrap().is_none());
+        assert_eq!(trash.purge_expired().unwrap(), 0);
+    }
+}
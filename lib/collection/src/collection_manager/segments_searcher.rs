@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Instant;
 
+use common::scheduling::Workload;
 use common::types::ScoreType;
 use futures::future::try_join_all;
 use itertools::Itertools;
@@ -9,10 +11,11 @@ use parking_lot::RwLock;
 use segment::common::operation_error::OperationError;
 use segment::data_types::named_vectors::NamedVectors;
 use segment::data_types::query_context::QueryContext;
+use segment::data_types::search_plan::{SearchPlanCollector, SegmentSearchPlan};
 use segment::data_types::vectors::{QueryVector, VectorStructInternal};
 use segment::types::{
-    Filter, Indexes, PointIdType, ScoredPoint, SearchParams, SegmentConfig, SeqNumberType,
-    WithPayload, WithPayloadInterface, WithVector,
+    AttributeValue, Filter, Indexes, PointIdType, ScoredPoint, SearchParams, SegmentConfig,
+    SeqNumberType, WithPayload, WithPayloadInterface, WithVector,
 };
 use tinyvec::TinyVec;
 use tokio::runtime::Handle;
@@ -22,6 +25,7 @@ use super::holders::segment_holder::LockedSegmentHolder;
 use crate::collection_manager::holders::segment_holder::{LockedSegment, SegmentHolder};
 use crate::collection_manager::probabilistic_segment_search_sampling::find_search_sampling_over_point_distribution;
 use crate::collection_manager::search_result_aggregator::BatchResultAggregator;
+use crate::common::sparse_vocabulary::shared_vocabulary;
 use crate::common::stopping_guard::StoppingGuard;
 use crate::config::CollectionConfig;
 use crate::operations::query_enum::QueryEnum;
@@ -166,11 +170,14 @@ impl SegmentsSearcher {
 
         const DEFAULT_CAPACITY: usize = 3;
         let mut idf_vectors: TinyVec<[&str; DEFAULT_CAPACITY]> = Default::default();
+        let mut shared_idf = Vec::new();
 
         // check vector names existing
         for req in &batch_request.searches {
             let vector_name = req.query.get_vector_name();
-            collection_config.params.get_distance(vector_name)?;
+            collection_config
+                .params
+                .get_search_distance(vector_name, req.params.as_ref())?;
             if let Some(sparse_vector_params) = collection_config
                 .params
                 .get_sparse_vector_params_opt(vector_name)
@@ -179,6 +186,14 @@ impl SegmentsSearcher {
                     && !idf_vectors.contains(&vector_name)
                 {
                     idf_vectors.push(vector_name);
+                    // Shared statistics are not available until the first collection of them
+                    if let Some(vocabulary) = sparse_vector_params
+                        .vocabulary
+                        .as_deref()
+                        .and_then(shared_vocabulary)
+                    {
+                        shared_idf.push((vector_name.to_string(), vocabulary));
+                    }
                 }
             }
         }
@@ -214,6 +229,13 @@ impl SegmentsSearcher {
                     let segment_guard = segment.read();
                     segment_guard.fill_query_context(&mut query_context);
                 }
+                for (vector_name, vocabulary) in shared_idf {
+                    query_context.use_shared_idf(
+                        &vector_name,
+                        vocabulary.points_count,
+                        &vocabulary.document_frequencies,
+                    );
+                }
                 Some(query_context)
             })
         };
@@ -228,6 +250,9 @@ impl SegmentsSearcher {
         sampling_enabled: bool,
         query_context: QueryContext,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        // Count the batch as queued, until all segments are searched
+        let _queued = Workload::Search.enter();
+
         let query_context_acr = Arc::new(query_context);
 
         // Using block to ensure `segments` variable is dropped in the end of it
@@ -364,7 +389,7 @@ impl SegmentsSearcher {
                         id,
                         payload: if with_payload.enable {
                             if let Some(selector) = &with_payload.payload_selector {
-                                Some(selector.process(segment.payload(id)?))
+                                Some(segment.selected_payload(id, selector)?)
                             } else {
                                 Some(segment.payload(id)?)
                             }
@@ -404,6 +429,29 @@ impl SegmentsSearcher {
 
         Ok(ordered_records)
     }
+
+    /// Attributes of the points, for the latest version of each point
+    pub fn retrieve_attributes(
+        segments: &RwLock<SegmentHolder>,
+        points: &[PointIdType],
+    ) -> CollectionResult<HashMap<PointIdType, HashMap<String, AttributeValue>>> {
+        let mut point_version: HashMap<PointIdType, SeqNumberType> = Default::default();
+        let mut point_attributes: HashMap<PointIdType, HashMap<String, AttributeValue>> =
+            Default::default();
+
+        segments.read().read_points(points, |id, segment| {
+            let version = segment.point_version(id).ok_or_else(|| {
+                OperationError::service_error(format!("No version for point {id}"))
+            })?;
+            if !point_version.contains_key(&id) || point_version[&id] < version {
+                point_attributes.insert(id, segment.attributes(id)?);
+                point_version.insert(id, version);
+            }
+            Ok(true)
+        })?;
+
+        Ok(point_attributes)
+    }
 }
 
 #[derive(PartialEq, Default, Debug)]
@@ -584,7 +632,14 @@ fn execute_batch_search(
     };
 
     let vectors_batch = &vectors_batch.iter().collect_vec();
-    let segment_query_context = query_context.get_segment_query_context();
+    let search_plan_collector = query_context
+        .search_plans()
+        .map(|_| SearchPlanCollector::default());
+    let mut segment_query_context = query_context.get_segment_query_context();
+    if let Some(collector) = &search_plan_collector {
+        segment_query_context = segment_query_context.with_search_plans(collector);
+    }
+    let search_start = Instant::now();
     let res = read_segment.search_batch(
         search_params.vector_name,
         vectors_batch,
@@ -596,6 +651,26 @@ fn execute_batch_search(
         segment_query_context,
     )?;
 
+    if let (Some(collector), Some(search_plans)) =
+        (search_plan_collector, query_context.search_plans())
+    {
+        let segment_plan = SegmentSearchPlan {
+            segment: read_segment
+                .data_path()
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            segment_type: read_segment.segment_type(),
+            available_points: segment_points,
+            vector_name: search_params.vector_name.to_string(),
+            batch_size: vectors_batch.len(),
+            top,
+            plans: collector.into_plans(),
+            search_time: search_start.elapsed().as_secs_f64(),
+        };
+        search_plans.lock().push(segment_plan);
+    }
+
     let further_results = res
         .iter()
         .map(|batch_result| batch_result.len() == top)
@@ -10,14 +10,19 @@ use segment::data_types::vectors::{BatchVectorStructInternal, VectorStructIntern
 use segment::entry::entry_point::SegmentEntry;
 use segment::json_path::JsonPath;
 use segment::types::{
-    Filter, Payload, PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef, PointIdType,
-    SeqNumberType,
+    AttributeValue, Condition, Filter, HasIdCondition, Payload, PayloadFieldSchema, PayloadKeyType,
+    PayloadKeyTypeRef, PointIdType, SeqNumberType,
 };
 
+use crate::collection_manager::collection_updater::UpdateOutcome;
 use crate::collection_manager::holders::segment_holder::SegmentHolder;
-use crate::operations::payload_ops::PayloadOps;
-use crate::operations::point_ops::{PointInsertOperationsInternal, PointOperations, PointStruct};
-use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::attribute_ops::AttributeOps;
+use crate::operations::payload_ops::{ConditionalSetPayloadOp, PayloadOps, SetPayloadOp};
+use crate::operations::point_ops::{
+    ConditionalDeleteOperationInternal, ConditionalInsertOperationInternal,
+    PointInsertOperationsInternal, PointOperations, PointStruct, WritePrecondition,
+};
+use crate::operations::types::{CollectionError, CollectionResult, WriteConflict};
 use crate::operations::vector_ops::{PointVectors, VectorOperations};
 use crate::operations::FieldIndexOperations;
 
@@ -33,6 +38,85 @@ pub(crate) fn check_unprocessed_points(
     }
 }
 
+/// Split points into the ones, which satisfy the precondition, and conflicting ones
+///
+/// Must be called within the same update operation, which modifies the points,
+/// so no other update can change the points in between.
+fn check_write_precondition(
+    segments: &SegmentHolder,
+    ids: &[PointIdType],
+    precondition: &WritePrecondition,
+) -> (HashSet<PointIdType>, Vec<WriteConflict>) {
+    let condition_filter = precondition.filter.as_ref().map(|filter| {
+        Filter::new_must(Condition::HasId(HasIdCondition {
+            has_id: ids.iter().copied().collect(),
+        }))
+        .merge(filter)
+    });
+
+    // Point might be present in multiple segments during optimization,
+    // the copy with the latest version is the actual one
+    let mut current_state: HashMap<PointIdType, (SeqNumberType, bool)> = HashMap::new();
+
+    for (_, segment) in segments.iter() {
+        let segment_arc = segment.get();
+        let read_segment = segment_arc.read();
+
+        let matching_points: Option<HashSet<PointIdType>> =
+            condition_filter.as_ref().map(|filter| {
+                read_segment
+                    .read_filtered(None, None, Some(filter))
+                    .into_iter()
+                    .collect()
+            });
+
+        for &id in ids {
+            let Some(version) = read_segment.point_version(id) else {
+                continue;
+            };
+
+            let is_matching = matching_points
+                .as_ref()
+                .map_or(true, |matching_points| matching_points.contains(&id));
+
+            match current_state.get(&id) {
+                Some((current_version, _)) if *current_version >= version => {}
+                _ => {
+                    current_state.insert(id, (version, is_matching));
+                }
+            }
+        }
+    }
+
+    let mut satisfied = HashSet::with_capacity(ids.len());
+    let mut conflicts = Vec::new();
+
+    for &id in ids {
+        let state = current_state.get(&id).copied();
+
+        let is_satisfied = match state {
+            None => false,
+            Some((version, is_matching)) => {
+                is_matching
+                    && precondition
+                        .version
+                        .map_or(true, |expected| expected == version)
+            }
+        };
+
+        if is_satisfied {
+            satisfied.insert(id);
+        } else {
+            conflicts.push(WriteConflict {
+                id,
+                current_version: state.map(|(version, _)| version),
+            });
+        }
+    }
+
+    (satisfied, conflicts)
+}
+
 /// Tries to delete points from all segments, returns number of actually deleted points
 pub(crate) fn delete_points(
     segments: &SegmentHolder,
@@ -48,6 +132,28 @@ pub(crate) fn delete_points(
         .map_err(Into::into)
 }
 
+/// Deletes points, which satisfy the precondition, and reports the rest as conflicts
+pub(crate) fn delete_points_conditional(
+    segments: &SegmentHolder,
+    op_num: SeqNumberType,
+    ids: &[PointIdType],
+    precondition: &WritePrecondition,
+) -> CollectionResult<UpdateOutcome> {
+    let (satisfied, conflicts) = check_write_precondition(segments, ids, precondition);
+    let ids_to_delete: Vec<_> = ids
+        .iter()
+        .copied()
+        .filter(|id| satisfied.contains(id))
+        .collect();
+
+    let affected_points = delete_points(segments, op_num, &ids_to_delete)?;
+
+    Ok(UpdateOutcome {
+        affected_points,
+        conflicts,
+    })
+}
+
 /// Update the specified named vectors of a point, keeping unspecified vectors intact.
 pub(crate) fn update_vectors(
     segments: &SegmentHolder,
@@ -66,6 +172,13 @@ pub(crate) fn update_vectors(
 
     let ids: Vec<PointIdType> = points_map.keys().copied().collect();
 
+    // Only names of updated vectors decide whether points must be moved out of a segment,
+    // other vectors and their indexes are left intact
+    let vector_names: HashSet<String> = points_map
+        .values()
+        .flat_map(|vectors| vectors.keys().map(ToString::to_string))
+        .collect();
+
     let updated_points = segments.apply_points_with_conditional_move(
         op_num,
         &ids,
@@ -73,7 +186,11 @@ pub(crate) fn update_vectors(
             let vectors = points_map[&id].clone();
             write_segment.update_vectors(op_num, id, vectors)
         },
-        |_| false,
+        |segment| {
+            vector_names
+                .iter()
+                .all(|vector_name| segment.is_vector_appendable(vector_name))
+        },
     )?;
     check_unprocessed_points(&ids, &updated_points)?;
     Ok(updated_points.len())
@@ -139,6 +256,33 @@ pub(crate) fn overwrite_payload_by_filter(
     overwrite_payload(segments, op_num, payload, &affected_points)
 }
 
+pub(crate) fn patch_payload(
+    segments: &SegmentHolder,
+    op_num: SeqNumberType,
+    patch: &Payload,
+    points: &[PointIdType],
+) -> CollectionResult<usize> {
+    let updated_points = segments.apply_points_with_conditional_move(
+        op_num,
+        points,
+        |id, write_segment| write_segment.patch_payload(op_num, id, patch),
+        |segment| segment.get_indexed_fields().is_empty(),
+    )?;
+
+    check_unprocessed_points(points, &updated_points)?;
+    Ok(updated_points.len())
+}
+
+pub(crate) fn patch_payload_by_filter(
+    segments: &SegmentHolder,
+    op_num: SeqNumberType,
+    patch: &Payload,
+    filter: &Filter,
+) -> CollectionResult<usize> {
+    let affected_points = points_by_filter(segments, filter)?;
+    patch_payload(segments, op_num, patch, &affected_points)
+}
+
 pub(crate) fn set_payload(
     segments: &SegmentHolder,
     op_num: SeqNumberType,
@@ -161,7 +305,48 @@ pub(crate) fn set_payload(
     Ok(updated_points.len())
 }
 
-fn points_by_filter(
+/// Sets payload of the points, which satisfy the precondition, and reports the rest as conflicts
+pub(crate) fn set_payload_conditional(
+    segments: &SegmentHolder,
+    op_num: SeqNumberType,
+    operation: ConditionalSetPayloadOp,
+) -> CollectionResult<UpdateOutcome> {
+    let ConditionalSetPayloadOp {
+        payload_op:
+            SetPayloadOp {
+                payload,
+                points,
+                filter,
+                key,
+            },
+        precondition,
+    } = operation;
+
+    let points = match (points, filter) {
+        (Some(points), _) => points,
+        (None, Some(filter)) => points_by_filter(segments, &filter)?,
+        (None, None) => {
+            return Err(CollectionError::BadRequest {
+                description: "No points or filter specified".to_string(),
+            })
+        }
+    };
+
+    let (satisfied, conflicts) = check_write_precondition(segments, &points, &precondition);
+    let points_to_update: Vec<_> = points
+        .into_iter()
+        .filter(|id| satisfied.contains(id))
+        .collect();
+
+    let affected_points = set_payload(segments, op_num, &payload, &points_to_update, &key)?;
+
+    Ok(UpdateOutcome {
+        affected_points,
+        conflicts,
+    })
+}
+
+pub(crate) fn points_by_filter(
     segments: &SegmentHolder,
     filter: &Filter,
 ) -> CollectionResult<Vec<PointIdType>> {
@@ -442,44 +627,84 @@ where
     Ok(res)
 }
 
+/// Upserts points, existing points are only updated if they satisfy the precondition.
+/// Points, which do not satisfy the precondition, are reported as conflicts.
+pub(crate) fn upsert_points_conditional(
+    segments: &SegmentHolder,
+    op_num: SeqNumberType,
+    points: Vec<PointStruct>,
+    precondition: &WritePrecondition,
+) -> CollectionResult<UpdateOutcome> {
+    let ids: Vec<_> = points.iter().map(|point| point.id).collect();
+    let (satisfied, conflicts) = check_write_precondition(segments, &ids, precondition);
+
+    let affected_points = upsert_points(
+        segments,
+        op_num,
+        points.iter().filter(|point| satisfied.contains(&point.id)),
+    )?;
+
+    Ok(UpdateOutcome {
+        affected_points,
+        conflicts,
+    })
+}
+
+fn points_from_insert_operation(operation: PointInsertOperationsInternal) -> Vec<PointStruct> {
+    match operation {
+        PointInsertOperationsInternal::PointsBatch(batch) => {
+            let batch_vectors: BatchVectorStructInternal = batch.vectors.into();
+            let all_vectors = batch_vectors.into_all_vectors(batch.ids.len());
+            let vectors_iter = batch.ids.into_iter().zip(all_vectors);
+            match batch.payloads {
+                None => vectors_iter
+                    .map(|(id, vectors)| PointStruct {
+                        id,
+                        vector: VectorStructInternal::from(vectors).into(),
+                        payload: None,
+                    })
+                    .collect(),
+                Some(payloads) => vectors_iter
+                    .zip(payloads)
+                    .map(|((id, vectors), payload)| PointStruct {
+                        id,
+                        vector: VectorStructInternal::from(vectors).into(),
+                        payload,
+                    })
+                    .collect(),
+            }
+        }
+        PointInsertOperationsInternal::PointsList(points) => points,
+    }
+}
+
 pub(crate) fn process_point_operation(
     segments: &RwLock<SegmentHolder>,
     op_num: SeqNumberType,
     point_operation: PointOperations,
-) -> CollectionResult<usize> {
+) -> CollectionResult<UpdateOutcome> {
     match point_operation {
-        PointOperations::DeletePoints { ids, .. } => delete_points(&segments.read(), op_num, &ids),
+        PointOperations::DeletePoints { ids, .. } => {
+            delete_points(&segments.read(), op_num, &ids).map(UpdateOutcome::from)
+        }
+        PointOperations::DeletePointsConditional(ConditionalDeleteOperationInternal {
+            ids,
+            precondition,
+        }) => delete_points_conditional(&segments.read(), op_num, &ids, &precondition),
         PointOperations::UpsertPoints(operation) => {
-            let points: Vec<_> = match operation {
-                PointInsertOperationsInternal::PointsBatch(batch) => {
-                    let batch_vectors: BatchVectorStructInternal = batch.vectors.into();
-                    let all_vectors = batch_vectors.into_all_vectors(batch.ids.len());
-                    let vectors_iter = batch.ids.into_iter().zip(all_vectors);
-                    match batch.payloads {
-                        None => vectors_iter
-                            .map(|(id, vectors)| PointStruct {
-                                id,
-                                vector: VectorStructInternal::from(vectors).into(),
-                                payload: None,
-                            })
-                            .collect(),
-                        Some(payloads) => vectors_iter
-                            .zip(payloads)
-                            .map(|((id, vectors), payload)| PointStruct {
-                                id,
-                                vector: VectorStructInternal::from(vectors).into(),
-                                payload,
-                            })
-                            .collect(),
-                    }
-                }
-                PointInsertOperationsInternal::PointsList(points) => points,
-            };
+            let points = points_from_insert_operation(operation);
             let res = upsert_points(&segments.read(), op_num, points.iter())?;
-            Ok(res)
+            Ok(res.into())
+        }
+        PointOperations::UpsertPointsConditional(ConditionalInsertOperationInternal {
+            points_op,
+            precondition,
+        }) => {
+            let points = points_from_insert_operation(points_op);
+            upsert_points_conditional(&segments.read(), op_num, points, &precondition)
         }
         PointOperations::DeletePointsByFilter(filter) => {
-            delete_points_by_filter(&segments.read(), op_num, &filter)
+            delete_points_by_filter(&segments.read(), op_num, &filter).map(UpdateOutcome::from)
         }
         PointOperations::SyncPoints(operation) => {
             let (deleted, new, updated) = sync_points(
@@ -489,7 +714,7 @@ pub(crate) fn process_point_operation(
                 operation.to_id,
                 &operation.points,
             )?;
-            Ok(deleted + new + updated)
+            Ok((deleted + new + updated).into())
         }
     }
 }
@@ -516,8 +741,8 @@ pub(crate) fn process_payload_operation(
     segments: &RwLock<SegmentHolder>,
     op_num: SeqNumberType,
     payload_operation: PayloadOps,
-) -> CollectionResult<usize> {
-    match payload_operation {
+) -> CollectionResult<UpdateOutcome> {
+    let res = match payload_operation {
         PayloadOps::SetPayload(sp) => {
             let payload: Payload = sp.payload;
             if let Some(points) = sp.points {
@@ -530,6 +755,9 @@ pub(crate) fn process_payload_operation(
                 })
             }
         }
+        PayloadOps::SetPayloadConditional(operation) => {
+            return set_payload_conditional(&segments.read(), op_num, operation);
+        }
         PayloadOps::DeletePayload(dp) => {
             if let Some(points) = dp.points {
                 delete_payload(&segments.read(), op_num, &points, &dp.keys)
@@ -559,6 +787,68 @@ pub(crate) fn process_payload_operation(
                 })
             }
         }
+        PayloadOps::PatchPayload(sp) => {
+            let patch: Payload = sp.payload;
+            if let Some(points) = sp.points {
+                patch_payload(&segments.read(), op_num, &patch, &points)
+            } else if let Some(filter) = sp.filter {
+                patch_payload_by_filter(&segments.read(), op_num, &patch, &filter)
+            } else {
+                Err(CollectionError::BadRequest {
+                    description: "No points or filter specified".to_string(),
+                })
+            }
+        }
+    };
+
+    res.map(UpdateOutcome::from)
+}
+
+pub(crate) fn set_attributes(
+    segments: &SegmentHolder,
+    op_num: SeqNumberType,
+    attributes: &HashMap<String, Option<AttributeValue>>,
+    points: &[PointIdType],
+) -> CollectionResult<usize> {
+    let updated_points = segments.apply_points_with_conditional_move(
+        op_num,
+        points,
+        |id, write_segment| write_segment.set_attributes(op_num, id, attributes),
+        // Attribute columns are not part of the immutable indexes, so they are updated in place
+        |_| true,
+    )?;
+
+    check_unprocessed_points(points, &updated_points)?;
+    Ok(updated_points.len())
+}
+
+pub(crate) fn set_attributes_by_filter(
+    segments: &SegmentHolder,
+    op_num: SeqNumberType,
+    attributes: &HashMap<String, Option<AttributeValue>>,
+    filter: &Filter,
+) -> CollectionResult<usize> {
+    let affected_points = points_by_filter(segments, filter)?;
+    set_attributes(segments, op_num, attributes, &affected_points)
+}
+
+pub(crate) fn process_attribute_operation(
+    segments: &RwLock<SegmentHolder>,
+    op_num: SeqNumberType,
+    attribute_operation: AttributeOps,
+) -> CollectionResult<usize> {
+    match attribute_operation {
+        AttributeOps::SetAttributes(operation) => {
+            if let Some(points) = operation.points {
+                set_attributes(&segments.read(), op_num, &operation.attributes, &points)
+            } else if let Some(filter) = operation.filter {
+                set_attributes_by_filter(&segments.read(), op_num, &operation.attributes, &filter)
+            } else {
+                Err(CollectionError::BadRequest {
+                    description: "No points or filter specified".to_string(),
+                })
+            }
+        }
     }
 }
 
@@ -8,7 +8,10 @@ use itertools::Itertools;
 use parking_lot::RwLock;
 use segment::data_types::vectors::{only_default_vector, VectorStructInternal};
 use segment::entry::entry_point::SegmentEntry;
-use segment::types::{PayloadFieldSchema, PayloadKeyType, PointIdType};
+use segment::types::{
+    Condition, FieldCondition, Filter, PayloadFieldSchema, PayloadKeyType, PointIdType,
+    VectorStorageType,
+};
 use tempfile::Builder;
 
 use crate::collection_manager::fixtures::{build_segment_1, build_segment_2, empty_segment};
@@ -16,8 +19,12 @@ use crate::collection_manager::holders::proxy_segment::ProxySegment;
 use crate::collection_manager::holders::segment_holder::{
     LockedSegment, LockedSegmentHolder, SegmentHolder, SegmentId,
 };
-use crate::collection_manager::segments_updater::upsert_points;
-use crate::operations::point_ops::PointStruct;
+use crate::collection_manager::segments_updater::{
+    delete_points_conditional, update_vectors, upsert_points, upsert_points_conditional,
+};
+use crate::operations::point_ops::{PointStruct, WritePrecondition};
+use crate::operations::types::WriteConflict;
+use crate::operations::vector_ops::PointVectors;
 
 fn wrap_proxy(segments: LockedSegmentHolder, sid: SegmentId, path: &Path) -> SegmentId {
     let mut write_segments = segments.write();
@@ -233,3 +240,119 @@ fn test_upsert_points_in_smallest_segment() {
         }
     }
 }
+
+#[test]
+fn test_conditional_updates() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+    let mut holder = SegmentHolder::default();
+    holder.add_new(build_segment_1(dir.path()));
+    let segments = Arc::new(RwLock::new(holder));
+
+    // All points of the segment have version 6
+    let points: Vec<_> = [1, 100]
+        .into_iter()
+        .map(|id| PointStruct {
+            id: id.into(),
+            vector: VectorStructInternal::from(vec![0.0, 0.0, 0.0, 0.0]).into(),
+            payload: None,
+        })
+        .collect();
+    let precondition = WritePrecondition {
+        version: Some(6),
+        filter: None,
+    };
+    let outcome = upsert_points_conditional(&segments.read(), 10, points, &precondition).unwrap();
+    assert_eq!(outcome.affected_points, 1);
+    assert_eq!(
+        outcome.conflicts,
+        vec![WriteConflict {
+            id: 100.into(),
+            current_version: None,
+        }],
+    );
+    assert!(!segments
+        .read()
+        .iter()
+        .any(|(_, segment)| segment.get().read().has_point(100.into())));
+
+    // Point 1 was updated, so the version does not match anymore
+    let outcome =
+        delete_points_conditional(&segments.read(), 11, &[1.into(), 2.into()], &precondition)
+            .unwrap();
+    assert_eq!(outcome.affected_points, 1);
+    assert_eq!(
+        outcome.conflicts,
+        vec![WriteConflict {
+            id: 1.into(),
+            current_version: Some(10),
+        }],
+    );
+
+    // Only point 3 is blue
+    let precondition = WritePrecondition {
+        version: None,
+        filter: Some(Filter::new_must(Condition::Field(
+            FieldCondition::new_match("color".parse().unwrap(), "blue".to_string().into()),
+        ))),
+    };
+    let outcome =
+        delete_points_conditional(&segments.read(), 12, &[1.into(), 3.into()], &precondition)
+            .unwrap();
+    assert_eq!(outcome.affected_points, 1);
+    assert_eq!(
+        outcome.conflicts,
+        vec![WriteConflict {
+            id: 1.into(),
+            current_version: Some(10),
+        }],
+    );
+}
+
+#[test]
+fn test_update_vectors_in_place() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+    let segment1 = build_segment_1(dir.path());
+    let mut segment2 = build_segment_2(dir.path());
+    segment2.appendable_flag = false;
+
+    let mut holder = SegmentHolder::default();
+    let sid1 = holder.add_new(segment1);
+    let sid2 = holder.add_new(segment2);
+    let segments = Arc::new(RwLock::new(holder));
+
+    let point_vectors = |id: u64| PointVectors {
+        id: id.into(),
+        vector: api::rest::VectorStruct::Single(vec![0.0, 1.0, 0.0, 1.0]),
+    };
+
+    // Vector storage and index of segment 2 support updates, point is kept in place
+    update_vectors(&segments.read(), 100, vec![point_vectors(11)]).unwrap();
+    {
+        let holder = segments.read();
+        assert!(holder.get(sid2).unwrap().get().read().has_point(11.into()));
+        assert!(!holder.get(sid1).unwrap().get().read().has_point(11.into()));
+    }
+
+    // Vector storage of segment 2 can't be updated, point is moved into appendable segment
+    {
+        let holder = segments.read();
+        let locked_segment = holder.get(sid2).unwrap();
+        let LockedSegment::Original(segment) = locked_segment else {
+            panic!("segment must not be a proxy");
+        };
+        segment
+            .write()
+            .segment_config
+            .vector_data
+            .values_mut()
+            .for_each(|config| config.storage_type = VectorStorageType::Mmap);
+    }
+    update_vectors(&segments.read(), 101, vec![point_vectors(12)]).unwrap();
+    {
+        let holder = segments.read();
+        assert!(!holder.get(sid2).unwrap().get().read().has_point(12.into()));
+        assert!(holder.get(sid1).unwrap().get().read().has_point(12.into()));
+    }
+}
@@ -0,0 +1,285 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use chrono::{TimeDelta, Utc};
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::collection_manager::optimizers::{TrackerStatus, TrackerTelemetry};
+
+/// Length of the rolling window, statistics are computed over
+pub const STATS_WINDOW: Duration = Duration::from_secs(60);
+
+/// Upper bounds of the read latency buckets, in seconds
+const LATENCY_BUCKETS_SEC: [f64; 14] = [
+    0.001, 0.002, 0.005, 0.01, 0.02, 0.05, 0.1, 0.2, 0.5, 1.0, 2.0, 5.0, 10.0, 30.0,
+];
+
+/// Number of latency buckets, the last one is for reads longer than all the bounds
+const LATENCY_BUCKETS: usize = LATENCY_BUCKETS_SEC.len() + 1;
+
+/// Counters of reads and writes of a shard within the rolling window
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct StatsCounters {
+    pub reads: usize,
+    pub writes: usize,
+    /// Reads, which had a filter
+    pub filtered_reads: usize,
+    /// Filtered reads, which only used indexed fields
+    pub indexed_filtered_reads: usize,
+    latencies: [usize; LATENCY_BUCKETS],
+}
+
+impl StatsCounters {
+    pub fn merge(&mut self, other: &StatsCounters) {
+        self.reads += other.reads;
+        self.writes += other.writes;
+        self.filtered_reads += other.filtered_reads;
+        self.indexed_filtered_reads += other.indexed_filtered_reads;
+        for (latency, other) in self.latencies.iter_mut().zip(other.latencies) {
+            *latency += other;
+        }
+    }
+
+    /// Approximate latency quantile of the reads: upper bound of the bucket, which contains it
+    pub fn latency_quantile(&self, quantile: f64) -> Option<f64> {
+        let total: usize = self.latencies.iter().sum();
+        if total == 0 {
+            return None;
+        }
+        let rank = ((total as f64 * quantile).ceil() as usize).clamp(1, total);
+        let mut seen = 0;
+        for (bucket, count) in self.latencies.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                // Reads above the last bound are reported as the last bound
+                let bound = bucket.min(LATENCY_BUCKETS_SEC.len() - 1);
+                return Some(LATENCY_BUCKETS_SEC[bound]);
+            }
+        }
+        None
+    }
+}
+
+#[derive(Debug)]
+struct StatsBucket {
+    /// Second since the creation of the stats
+    second: u64,
+    counters: StatsCounters,
+}
+
+/// Per-second counters of reads and writes of a shard, handled by this peer.
+///
+/// Counters are not persisted, only the last [`STATS_WINDOW`] is kept.
+#[derive(Debug)]
+pub struct ShardStats {
+    started: Instant,
+    buckets: Mutex<VecDeque<StatsBucket>>,
+}
+
+impl Default for ShardStats {
+    fn default() -> Self {
+        Self {
+            started: Instant::now(),
+            buckets: Mutex::new(VecDeque::new()),
+        }
+    }
+}
+
+impl ShardStats {
+    /// Record a batch of reads, which took `duration` in total.
+    /// `filters` tells, for each filtered read, whether all its fields are indexed.
+    pub fn record_reads(
+        &self,
+        reads: usize,
+        duration: Duration,
+        filters: impl IntoIterator<Item = bool>,
+    ) {
+        let bucket = LATENCY_BUCKETS_SEC
+            .iter()
+            .position(|bound| duration.as_secs_f64() <= *bound)
+            .unwrap_or(LATENCY_BUCKETS_SEC.len());
+        let (filtered, indexed) = filters
+            .into_iter()
+            .fold((0, 0), |(filtered, indexed), is_indexed| {
+                (filtered + 1, indexed + usize::from(is_indexed))
+            });
+        self.update(Instant::now(), |counters| {
+            counters.reads += reads;
+            counters.latencies[bucket] += reads;
+            counters.filtered_reads += filtered;
+            counters.indexed_filtered_reads += indexed;
+        });
+    }
+
+    pub fn record_write(&self) {
+        self.update(Instant::now(), |counters| counters.writes += 1);
+    }
+
+    /// Counters of the last [`STATS_WINDOW`], and the length of the window they were collected
+    /// for, which is shorter right after the shard is loaded
+    pub fn window(&self) -> (StatsCounters, Duration) {
+        self.window_at(Instant::now())
+    }
+
+    fn window_at(&self, now: Instant) -> (StatsCounters, Duration) {
+        let second = self.second(now);
+        let mut counters = StatsCounters::default();
+        for bucket in self.buckets.lock().iter() {
+            if bucket.second + STATS_WINDOW.as_secs() > second {
+                counters.merge(&bucket.counters);
+            }
+        }
+        let window = now.duration_since(self.started).min(STATS_WINDOW);
+        (counters, window)
+    }
+
+    fn second(&self, now: Instant) -> u64 {
+        now.duration_since(self.started).as_secs()
+    }
+
+    fn update(&self, now: Instant, update: impl FnOnce(&mut StatsCounters)) {
+        let second = self.second(now);
+        let mut buckets = self.buckets.lock();
+        while buckets
+            .front()
+            .is_some_and(|bucket| bucket.second + STATS_WINDOW.as_secs() <= second)
+        {
+            buckets.pop_front();
+        }
+        if !buckets.back().is_some_and(|bucket| bucket.second == second) {
+            buckets.push_back(StatsBucket {
+                second,
+                counters: StatsCounters::default(),
+            });
+        }
+        update(&mut buckets.back_mut().unwrap().counters);
+    }
+}
+
+/// Optimizations of the local shards, within the rolling window
+#[derive(Debug, Clone, Default, PartialEq, Serialize, JsonSchema)]
+pub struct OptimizerActivity {
+    /// Number of optimizations, which are running now
+    pub running: usize,
+    /// Number of optimizations, finished within the window
+    pub finished: usize,
+    /// Number of optimizations, failed within the window
+    pub failed: usize,
+}
+
+impl OptimizerActivity {
+    pub fn new<'a>(trackers: impl IntoIterator<Item = &'a TrackerTelemetry>) -> Self {
+        let since =
+            Utc::now() - TimeDelta::from_std(STATS_WINDOW).unwrap_or_else(|_| TimeDelta::zero());
+        let mut activity = OptimizerActivity::default();
+        for tracker in trackers {
+            let in_window = tracker.end_at.is_some_and(|end_at| end_at >= since);
+            match tracker.status {
+                TrackerStatus::Optimizing => activity.running += 1,
+                TrackerStatus::Done if in_window => activity.finished += 1,
+                TrackerStatus::Error(_) if in_window => activity.failed += 1,
+                TrackerStatus::Done | TrackerStatus::Error(_) | TrackerStatus::Cancelled(_) => {}
+            }
+        }
+        activity
+    }
+}
+
+/// Rolling-window statistics of the requests to the local shards of the collection
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct CollectionStats {
+    /// Length of the window, statistics are computed over, in seconds
+    pub window_sec: f64,
+    /// Number of search and query requests per second, served by the local shards.
+    /// A request is counted once by each shard it reaches.
+    pub reads_per_second: f64,
+    /// Approximate median latency of the reads by the local shards, in seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read_latency_p50: Option<f64>,
+    /// Approximate 99th percentile of the latency of the reads by the local shards, in seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read_latency_p99: Option<f64>,
+    /// Number of update operations per second, applied to the local shards
+    pub writes_per_second: f64,
+    /// Share of the filtered reads, which only filtered by indexed payload fields
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter_index_hit_rate: Option<f64>,
+    pub optimizer: OptimizerActivity,
+}
+
+impl CollectionStats {
+    pub fn new(counters: StatsCounters, window: Duration, optimizer: OptimizerActivity) -> Self {
+        let window_sec = window.as_secs_f64();
+        let per_second = |count: usize| {
+            if window_sec > 0.0 {
+                count as f64 / window_sec
+            } else {
+                0.0
+            }
+        };
+        let filter_index_hit_rate = (counters.filtered_reads > 0)
+            .then(|| counters.indexed_filtered_reads as f64 / counters.filtered_reads as f64);
+        Self {
+            window_sec,
+            reads_per_second: per_second(counters.reads),
+            read_latency_p50: counters.latency_quantile(0.5),
+            read_latency_p99: counters.latency_quantile(0.99),
+            writes_per_second: per_second(counters.writes),
+            filter_index_hit_rate,
+            optimizer,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shard_stats_window() {
+        let stats = ShardStats::default();
+        let start = stats.started;
+
+        stats.update(start, |counters| {
+            counters.reads += 10;
+            counters.latencies[0] += 10;
+        });
+        stats.update(start + Duration::from_secs(30), |counters| {
+            counters.writes += 2;
+        });
+
+        let (counters, window) = stats.window_at(start + Duration::from_secs(30));
+        assert_eq!(window, Duration::from_secs(30));
+        assert_eq!(counters.reads, 10);
+        assert_eq!(counters.writes, 2);
+
+        // Reads of the first second leave the window
+        let (counters, window) = stats.window_at(start + STATS_WINDOW + Duration::from_secs(1));
+        assert_eq!(window, STATS_WINDOW);
+        assert_eq!(counters.reads, 0);
+        assert_eq!(counters.writes, 2);
+    }
+
+    #[test]
+    fn test_stats_latency_quantile() {
+        let stats = ShardStats::default();
+        assert_eq!(stats.window().0.latency_quantile(0.5), None);
+
+        stats.record_reads(98, Duration::from_micros(1500), [true, false]);
+        stats.record_reads(2, Duration::from_secs(60), []);
+
+        let (counters, _) = stats.window();
+        assert_eq!(counters.reads, 100);
+        assert_eq!(counters.filtered_reads, 2);
+        assert_eq!(counters.indexed_filtered_reads, 1);
+        assert_eq!(counters.latency_quantile(0.5), Some(0.002));
+        assert_eq!(counters.latency_quantile(0.99), Some(30.0));
+
+        let collection_stats =
+            CollectionStats::new(counters, Duration::from_secs(10), Default::default());
+        assert_eq!(collection_stats.reads_per_second, 10.0);
+        assert_eq!(collection_stats.filter_index_hit_rate, Some(0.5));
+    }
+}
@@ -0,0 +1,34 @@
+use serde::Deserialize;
+
+pub const DEFAULT_CONSISTENCY_CHECK_BUCKETS: usize = 256;
+
+/// Periodic comparison of the replicas of shards
+///
+/// Replicas are compared by digests of point ranges, so only points in divergent ranges are
+/// transferred between peers.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ConsistencyCheckConfig {
+    /// Interval between the checks in seconds. If not set, replicas are only checked on request.
+    #[serde(default)]
+    pub interval_sec: Option<u64>,
+    /// Re-sync divergent points from the most recently updated replica
+    #[serde(default)]
+    pub repair: bool,
+    /// Number of ranges, into which the points of a shard are split for comparison
+    #[serde(default = "default_buckets")]
+    pub buckets: usize,
+}
+
+impl Default for ConsistencyCheckConfig {
+    fn default() -> Self {
+        Self {
+            interval_sec: None,
+            repair: false,
+            buckets: DEFAULT_CONSISTENCY_CHECK_BUCKETS,
+        }
+    }
+}
+
+const fn default_buckets() -> usize {
+    DEFAULT_CONSISTENCY_CHECK_BUCKETS
+}
@@ -8,7 +8,6 @@ use segment::types::{PointIdType, WithPayloadInterface, WithVector};
 use tokio::sync::RwLockReadGuard;
 
 use crate::collection::Collection;
-use crate::common::batching::batch_requests;
 use crate::common::retrieve_request_trait::RetrieveRequest;
 use crate::operations::consistency_params::ReadConsistency;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
@@ -308,44 +307,45 @@ where
     F: Fn(String) -> Fut,
     Fut: Future<Output = Option<RwLockReadGuard<'a, Collection>>>,
 {
-    let fetch_requests = batch_requests::<
-        &(Req, ShardSelectorInternal),
-        Option<ShardKeySelector>,
-        ReferencedPoints,
-        Vec<_>,
-    >(
-        requests,
-        |(request, _)| request.get_lookup_shard_key(),
-        |(request, _), referenced_points| {
-            let collection_name = request.get_lookup_collection();
-            let vector_name = request.get_lookup_vector_name();
-            let point_ids_iter = request.get_referenced_point_ids();
-            referenced_points.add_from_iter(
-                point_ids_iter.into_iter(),
-                vector_name,
-                collection_name,
-            );
-            Ok(())
-        },
-        |shard_selector, referenced_points, requests| {
-            let shard_selector = match shard_selector {
+    // Group referenced points by the lookup shard key, not only of the adjacent requests,
+    // so that points, referenced by many requests of the batch, are retrieved once
+    let mut referenced_points_by_shard_key: Vec<(&Option<ShardKeySelector>, ReferencedPoints)> =
+        Vec::new();
+    for (request, _) in requests {
+        let shard_key = request.get_lookup_shard_key();
+        let position = referenced_points_by_shard_key
+            .iter()
+            .position(|(key, _)| *key == shard_key)
+            .unwrap_or_else(|| {
+                referenced_points_by_shard_key.push((shard_key, ReferencedPoints::new()));
+                referenced_points_by_shard_key.len() - 1
+            });
+        let collection_name = request.get_lookup_collection();
+        let vector_name = request.get_lookup_vector_name();
+        let point_ids_iter = request.get_referenced_point_ids();
+        referenced_points_by_shard_key[position].1.add_from_iter(
+            point_ids_iter.into_iter(),
+            vector_name,
+            collection_name,
+        );
+    }
+
+    let fetch_requests: Vec<_> = referenced_points_by_shard_key
+        .into_iter()
+        .filter(|(_, referenced_points)| !referenced_points.is_empty())
+        .map(|(shard_key, referenced_points)| {
+            let shard_selector = match shard_key {
                 None => ShardSelectorInternal::All,
-                Some(shard_key_selector) => ShardSelectorInternal::from(shard_key_selector),
+                Some(shard_key_selector) => ShardSelectorInternal::from(shard_key_selector.clone()),
             };
-
-            if referenced_points.is_empty() {
-                return Ok(());
-            }
-            let fetch = referenced_points.fetch_vectors(
+            referenced_points.fetch_vectors(
                 collection,
                 read_consistency,
                 &collection_by_name,
                 shard_selector,
-            );
-            requests.push(fetch);
-            Ok(())
-        },
-    )?;
+            )
+        })
+        .collect();
 
     let batch_reference_vectors: Vec<_> = try_join_all(fetch_requests).await?;
 
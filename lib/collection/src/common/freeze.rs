@@ -0,0 +1,123 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use tokio::sync::{Notify, RwLock, RwLockReadGuard};
+use tokio::time::Instant;
+
+/// Longest allowed freeze, after which the shard is unfrozen automatically
+pub const MAX_FREEZE_TIMEOUT: Duration = Duration::from_secs(24 * 60 * 60);
+
+type FrozenUntil = Option<(Instant, DateTime<Utc>)>;
+
+/// Freeze of segment flushes and optimizations of a shard, so that its segment files don't change
+/// during an external backup of the storage directory.
+///
+/// Updates are still written to the WAL, but only applied to the segments once the freeze is
+/// lifted. So reads are served from the state at the moment of the freeze.
+#[derive(Debug, Default)]
+pub struct FreezeState {
+    /// Time, at which the freeze is lifted automatically.
+    /// Workers hold a read lock while changing segments, so taking the write lock waits for them.
+    frozen_until: RwLock<FrozenUntil>,
+    changed: Notify,
+}
+
+impl FreezeState {
+    /// Freeze the shard for `timeout`, once the current update or flush is finished.
+    ///
+    /// Freezing an already frozen shard extends the freeze.
+    pub async fn freeze(&self, timeout: Duration) -> DateTime<Utc> {
+        let timeout = timeout.min(MAX_FREEZE_TIMEOUT);
+        let until = Utc::now() + chrono::Duration::from_std(timeout).unwrap_or_default();
+        *self.frozen_until.write().await = Some((Instant::now() + timeout, until));
+        self.changed.notify_waiters();
+        until
+    }
+
+    /// Lift the freeze, returns whether the shard was frozen
+    pub async fn unfreeze(&self) -> bool {
+        let was_frozen = self.frozen_until().await.is_some();
+        *self.frozen_until.write().await = None;
+        self.changed.notify_waiters();
+        was_frozen
+    }
+
+    /// Time, at which the freeze is lifted automatically, `None` if the shard is not frozen
+    pub async fn frozen_until(&self) -> Option<DateTime<Utc>> {
+        active_freeze(&self.frozen_until.read().await).map(|(_, until)| until)
+    }
+
+    pub async fn is_frozen(&self) -> bool {
+        self.frozen_until().await.is_some()
+    }
+
+    /// Wait until the shard is not frozen. The shard can't be frozen, while the guard is held.
+    pub async fn unfrozen(&self) -> RwLockReadGuard<'_, FrozenUntil> {
+        loop {
+            // Listen for changes before checking the state, not to miss an unfreeze in between
+            let changed = self.changed.notified();
+            let guard = self.frozen_until.read().await;
+            let Some((until, _)) = active_freeze(&guard) else {
+                return guard;
+            };
+            drop(guard);
+
+            tokio::select! {
+                _ = tokio::time::sleep_until(until) => {}
+                _ = changed => {}
+            }
+        }
+    }
+
+    /// Guard, which keeps the shard unfrozen while held, `None` if the shard is frozen
+    pub async fn try_unfrozen(&self) -> Option<RwLockReadGuard<'_, FrozenUntil>> {
+        let guard = self.frozen_until.read().await;
+        active_freeze(&guard).is_none().then_some(guard)
+    }
+}
+
+fn active_freeze(frozen_until: &FrozenUntil) -> FrozenUntil {
+    frozen_until.filter(|(until, _)| Instant::now() < *until)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_freeze_is_lifted_after_timeout() {
+        let freeze = FreezeState::default();
+        assert!(freeze.try_unfrozen().await.is_some());
+
+        freeze.freeze(Duration::from_millis(100)).await;
+        assert!(freeze.is_frozen().await);
+        assert!(freeze.try_unfrozen().await.is_none());
+
+        let started = std::time::Instant::now();
+        drop(freeze.unfrozen().await);
+        assert!(started.elapsed() >= Duration::from_millis(50));
+        assert!(!freeze.is_frozen().await);
+    }
+
+    #[tokio::test]
+    async fn test_unfreeze_wakes_waiters() {
+        let freeze = std::sync::Arc::new(FreezeState::default());
+        freeze.freeze(Duration::from_secs(60)).await;
+
+        let waiter = tokio::spawn({
+            let freeze = freeze.clone();
+            async move {
+                drop(freeze.unfrozen().await);
+            }
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert!(!waiter.is_finished());
+
+        assert!(freeze.unfreeze().await);
+        tokio::time::timeout(Duration::from_secs(5), waiter)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(!freeze.unfreeze().await);
+    }
+}
@@ -0,0 +1,339 @@
+use std::collections::{HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::time::{Duration, Instant};
+
+use seahash::SeaHasher;
+use serde::Deserialize;
+use serde_json::Value;
+use tokio::sync::watch;
+
+use crate::operations::types::{CollectionError, CollectionResult, UpdateResult};
+use crate::operations::CollectionUpdateOperations;
+
+/// Name of the HTTP header and gRPC metadata key, which carries the idempotency key of an update
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Max length of an idempotency key in bytes
+pub const MAX_IDEMPOTENCY_KEY_LENGTH: usize = 256;
+
+const DEFAULT_IDEMPOTENCY_WINDOW_SEC: u64 = 600;
+
+/// Deduplication of retried updates, which carry the same idempotency key
+#[derive(Clone, Debug, Deserialize)]
+pub struct IdempotencyConfig {
+    /// Time in seconds, during which an update with an already seen key is not applied again,
+    /// and the result of the first update is returned instead. Updates, which reuse a key of a
+    /// different update, are rejected. If 0 - keys are ignored.
+    #[serde(default = "default_window_sec")]
+    pub window_sec: u64,
+}
+
+impl Default for IdempotencyConfig {
+    fn default() -> Self {
+        Self {
+            window_sec: DEFAULT_IDEMPOTENCY_WINDOW_SEC,
+        }
+    }
+}
+
+impl IdempotencyConfig {
+    pub fn window(&self) -> Option<Duration> {
+        (self.window_sec > 0).then(|| Duration::from_secs(self.window_sec))
+    }
+}
+
+fn default_window_sec() -> u64 {
+    DEFAULT_IDEMPOTENCY_WINDOW_SEC
+}
+
+/// Validate idempotency key, received from a client
+pub fn parse_idempotency_key(key: &str) -> CollectionResult<String> {
+    if key.is_empty() {
+        return Err(CollectionError::bad_input(
+            "Idempotency key must not be empty",
+        ));
+    }
+    if key.len() > MAX_IDEMPOTENCY_KEY_LENGTH {
+        return Err(CollectionError::bad_input(format!(
+            "Idempotency key must not be longer than {MAX_IDEMPOTENCY_KEY_LENGTH} bytes",
+        )));
+    }
+    Ok(key.to_string())
+}
+
+/// Hash of the operation, to detect reuse of an idempotency key for a different update
+///
+/// Maps are hashed in the order of their keys, so equal operations have equal hashes regardless of
+/// the order of their map entries.
+pub fn operation_hash(operation: &CollectionUpdateOperations) -> CollectionResult<u64> {
+    let value = serde_json::to_value(operation).map_err(|err| {
+        CollectionError::service_error(format!("Failed to hash update operation: {err}"))
+    })?;
+    let mut hasher = SeaHasher::new();
+    hash_value(&value, &mut hasher);
+    Ok(hasher.finish())
+}
+
+fn hash_value(value: &Value, hasher: &mut impl Hasher) {
+    match value {
+        Value::Null => 0u8.hash(hasher),
+        Value::Bool(value) => (1u8, value).hash(hasher),
+        Value::Number(value) => (2u8, value.to_string()).hash(hasher),
+        Value::String(value) => (3u8, value).hash(hasher),
+        Value::Array(values) => {
+            (4u8, values.len()).hash(hasher);
+            values.iter().for_each(|value| hash_value(value, hasher));
+        }
+        Value::Object(map) => {
+            (5u8, map.len()).hash(hasher);
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_unstable_by_key(|(key, _)| *key);
+            for (key, value) in entries {
+                key.hash(hasher);
+                hash_value(value, hasher);
+            }
+        }
+    }
+}
+
+/// Outcome of a lookup of an idempotency key
+#[derive(Debug)]
+pub enum IdempotencyLookup {
+    /// The key is reserved for the update. Its result is reported through the sender, once the
+    /// update is written to WAL. If the sender is dropped without a result, the key is released.
+    Reserved(watch::Sender<Option<UpdateResult>>),
+    /// The update was already written to WAL, with this result
+    Done(UpdateResult),
+    /// The same update is being written to WAL by another request
+    InProgress(watch::Receiver<Option<UpdateResult>>),
+}
+
+#[derive(Debug)]
+struct IdempotentUpdate {
+    operation_hash: u64,
+    result: watch::Receiver<Option<UpdateResult>>,
+    expires_at: Instant,
+}
+
+impl IdempotentUpdate {
+    /// Update was not written, and the request, which reserved the key, is gone
+    fn is_abandoned(&self) -> bool {
+        self.result.borrow().is_none() && self.result.has_changed().is_err()
+    }
+}
+
+/// Results of recent updates of a shard, by their idempotency keys
+///
+/// Keys are restored from the WAL on load, so only keys of the operations, which are still in
+/// the WAL, survive a restart. Time of restored keys is counted from the time of the load.
+///
+/// The cache is only locked to look up and reserve keys, updates are written to WAL outside of
+/// the lock. Retries, which arrive while the update is written, wait for its result.
+#[derive(Debug, Default)]
+pub struct IdempotencyCache {
+    updates: HashMap<String, IdempotentUpdate>,
+    /// Keys in order of insertion, with the time they expire at
+    expirations: VecDeque<(Instant, String)>,
+}
+
+impl IdempotencyCache {
+    /// Look up the update with the given key, and reserve the key if it is not used yet
+    ///
+    /// Returns an error if the key was used for a different update within the window.
+    pub fn lookup(
+        &mut self,
+        key: &str,
+        operation_hash: u64,
+        expires_at: Instant,
+        now: Instant,
+    ) -> CollectionResult<IdempotencyLookup> {
+        self.evict(now);
+
+        if let Some(update) = self
+            .updates
+            .get(key)
+            .filter(|update| !update.is_abandoned())
+        {
+            if update.operation_hash != operation_hash {
+                return Err(CollectionError::bad_request(format!(
+                    "Idempotency key {key} was already used for a different update",
+                )));
+            }
+            let lookup = match update.result.borrow().clone() {
+                Some(result) => IdempotencyLookup::Done(result),
+                None => IdempotencyLookup::InProgress(update.result.clone()),
+            };
+            return Ok(lookup);
+        }
+
+        let (sender, receiver) = watch::channel(None);
+        self.insert(key.to_string(), operation_hash, receiver, expires_at);
+        Ok(IdempotencyLookup::Reserved(sender))
+    }
+
+    /// Remember the update with a known result, which was restored from WAL
+    pub fn insert_done(
+        &mut self,
+        key: String,
+        operation_hash: u64,
+        result: UpdateResult,
+        expires_at: Instant,
+    ) {
+        let (_, receiver) = watch::channel(Some(result));
+        self.insert(key, operation_hash, receiver, expires_at);
+    }
+
+    fn insert(
+        &mut self,
+        key: String,
+        operation_hash: u64,
+        result: watch::Receiver<Option<UpdateResult>>,
+        expires_at: Instant,
+    ) {
+        self.expirations.push_back((expires_at, key.clone()));
+        self.updates.insert(
+            key,
+            IdempotentUpdate {
+                operation_hash,
+                result,
+                expires_at,
+            },
+        );
+    }
+
+    pub fn len(&self) -> usize {
+        self.updates.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.updates.is_empty()
+    }
+
+    fn evict(&mut self, now: Instant) {
+        while let Some((expires_at, _)) = self.expirations.front() {
+            if *expires_at > now {
+                break;
+            }
+            let (expires_at, key) = self.expirations.pop_front().unwrap();
+            // The key might have been inserted again later, with a later expiration
+            if self
+                .updates
+                .get(&key)
+                .is_some_and(|update| update.expires_at <= expires_at)
+            {
+                self.updates.remove(&key);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operations::types::UpdateStatus;
+
+    fn result(operation_id: u64) -> UpdateResult {
+        UpdateResult {
+            operation_id: Some(operation_id),
+            status: UpdateStatus::Acknowledged,
+            clock_tag: None,
+            conflicts: Vec::new(),
+        }
+    }
+
+    fn done(lookup: IdempotencyLookup) -> UpdateResult {
+        match lookup {
+            IdempotencyLookup::Done(result) => result,
+            other => panic!("Expected a done update, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_idempotency_cache() {
+        let window = Duration::from_secs(10);
+        let start = Instant::now();
+        let mut cache = IdempotencyCache::default();
+
+        cache.insert_done("a".to_string(), 1, result(1), start + window);
+        cache.insert_done("b".to_string(), 2, result(2), start + window * 2);
+        let lookup = cache.lookup("a", 1, start + window, start).unwrap();
+        assert_eq!(done(lookup).operation_id, Some(1));
+
+        // Key can't be reused for a different update
+        assert!(cache.lookup("a", 3, start + window, start).is_err());
+
+        // Key "a" expires, while "b" is still within the window
+        let lookup = cache
+            .lookup("b", 2, start + window * 2, start + window)
+            .unwrap();
+        assert_eq!(done(lookup).operation_id, Some(2));
+        assert_eq!(cache.len(), 1);
+
+        // Re-inserted key is kept until its new expiration
+        cache.insert_done("b".to_string(), 2, result(3), start + window * 3);
+        let lookup = cache
+            .lookup("b", 2, start + window * 3, start + window * 2)
+            .unwrap();
+        assert_eq!(done(lookup).operation_id, Some(3));
+        let lookup = cache.lookup("b", 2, start + window * 4, start + window * 3);
+        assert!(matches!(lookup.unwrap(), IdempotencyLookup::Reserved(_)));
+    }
+
+    #[test]
+    fn test_idempotency_cache_reservation() {
+        let window = Duration::from_secs(10);
+        let now = Instant::now();
+        let mut cache = IdempotencyCache::default();
+
+        let IdempotencyLookup::Reserved(sender) = cache.lookup("a", 1, now + window, now).unwrap()
+        else {
+            panic!("Key must be reserved");
+        };
+
+        // Retry waits for the result of the reserved update
+        let IdempotencyLookup::InProgress(receiver) =
+            cache.lookup("a", 1, now + window, now).unwrap()
+        else {
+            panic!("Update must be in progress");
+        };
+        sender.send_replace(Some(result(1)));
+        assert_eq!(receiver.borrow().as_ref().unwrap().operation_id, Some(1));
+
+        let mut completed = result(1);
+        completed.status = UpdateStatus::Completed;
+        sender.send_replace(Some(completed));
+        drop(sender);
+        let lookup = cache.lookup("a", 1, now + window, now).unwrap();
+        assert_eq!(done(lookup).status, UpdateStatus::Completed);
+
+        // Key is released, if the update was not written
+        let IdempotencyLookup::Reserved(sender) = cache.lookup("b", 2, now + window, now).unwrap()
+        else {
+            panic!("Key must be reserved");
+        };
+        drop(sender);
+        let lookup = cache.lookup("b", 3, now + window, now).unwrap();
+        assert!(matches!(lookup, IdempotencyLookup::Reserved(_)));
+    }
+
+    #[test]
+    fn test_operation_hash_ignores_order_of_map_entries() {
+        let a: Value = serde_json::from_str(r#"{"x": 1, "y": [1, 2]}"#).unwrap();
+        let b: Value = serde_json::from_str(r#"{"y": [1, 2], "x": 1}"#).unwrap();
+        let c: Value = serde_json::from_str(r#"{"y": [2, 1], "x": 1}"#).unwrap();
+        let hash = |value: &Value| {
+            let mut hasher = SeaHasher::new();
+            hash_value(value, &mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(hash(&a), hash(&b));
+        assert_ne!(hash(&a), hash(&c));
+    }
+
+    #[test]
+    fn test_parse_idempotency_key() {
+        assert_eq!(parse_idempotency_key("retry-1").unwrap(), "retry-1");
+        assert!(parse_idempotency_key("").is_err());
+        assert!(parse_idempotency_key(&"k".repeat(MAX_IDEMPOTENCY_KEY_LENGTH + 1)).is_err());
+    }
+}
@@ -0,0 +1,300 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use segment::problems::unindexed_field::unindexed_fields;
+use segment::types::{Filter, PayloadFieldSchema, PayloadKeyType};
+use serde::{Deserialize, Serialize};
+
+const DEFAULT_MAX_FIELDS: usize = 1000;
+
+/// Configuration of the index advisor
+#[derive(Clone, Debug, Deserialize)]
+pub struct IndexAdvisorConfig {
+    /// Record usage of fields without a suitable index in filters of search and query requests
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Create the recommended index automatically, once the field was used by this number of
+    /// filtered requests. If not set, indexes are only recommended.
+    #[serde(default)]
+    pub auto_create_threshold: Option<usize>,
+    /// Only create indexes automatically, if the requests took at least this number of seconds
+    /// on average
+    #[serde(default)]
+    pub auto_create_min_avg_duration_sec: f64,
+    /// Max number of fields tracked for each collection, the least used fields are dropped first
+    #[serde(default = "default_max_fields")]
+    pub max_fields: usize,
+}
+
+impl Default for IndexAdvisorConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            auto_create_threshold: None,
+            auto_create_min_avg_duration_sec: 0.0,
+            max_fields: DEFAULT_MAX_FIELDS,
+        }
+    }
+}
+
+const fn default_enabled() -> bool {
+    true
+}
+
+const fn default_max_fields() -> usize {
+    DEFAULT_MAX_FIELDS
+}
+
+/// Index, which would speed up the filters of recent requests
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct IndexRecommendation {
+    /// Payload field, used in filters without a suitable index
+    pub key: PayloadKeyType,
+    /// Index, which suits the most of the conditions on this field
+    pub field_schema: PayloadFieldSchema,
+    /// Number of filtered requests, which used this field
+    pub requests: usize,
+    /// Total time of these requests, in seconds
+    pub total_duration: f64,
+    /// Average time of these requests, in seconds
+    pub avg_duration: f64,
+    pub last_used: DateTime<Utc>,
+    /// Whether the creation of this index was already requested automatically
+    pub auto_create_requested: bool,
+}
+
+#[derive(Debug)]
+struct FieldUsage {
+    requests: usize,
+    total_duration: Duration,
+    last_used: DateTime<Utc>,
+    /// Suitable indexes, with the number of conditions they suit. In the order of inference.
+    candidates: Vec<(PayloadFieldSchema, usize)>,
+    auto_create_requested: bool,
+}
+
+impl FieldUsage {
+    fn best_candidate(&self) -> Option<&PayloadFieldSchema> {
+        let mut best: Option<&(PayloadFieldSchema, usize)> = None;
+        for candidate in &self.candidates {
+            // On ties, prefer the earlier inferred, more specific, index
+            if best.map_or(true, |best| candidate.1 > best.1) {
+                best = Some(candidate);
+            }
+        }
+        best.map(|(schema, _)| schema)
+    }
+}
+
+/// Usage of unindexed fields in filters of a single collection.
+///
+/// Usage is not persisted and only covers requests, handled by this peer.
+#[derive(Debug)]
+pub struct IndexAdvisor {
+    config: IndexAdvisorConfig,
+    fields: Mutex<HashMap<PayloadKeyType, FieldUsage>>,
+}
+
+impl IndexAdvisor {
+    pub fn new(config: &IndexAdvisorConfig) -> Self {
+        Self {
+            config: config.clone(),
+            fields: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled && self.config.max_fields > 0
+    }
+
+    /// Record filters of a batch of requests, which took `duration`
+    pub fn record<'a>(
+        &self,
+        duration: Duration,
+        filters: impl IntoIterator<Item = &'a Filter>,
+        payload_schema: &HashMap<PayloadKeyType, PayloadFieldSchema>,
+    ) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        let unindexed: Vec<_> = filters
+            .into_iter()
+            .map(|filter| unindexed_fields(filter, payload_schema))
+            .collect();
+        if unindexed.iter().all(HashMap::is_empty) {
+            return;
+        }
+
+        // Time of the batch is split between its requests
+        let request_duration = duration / unindexed.len() as u32;
+        let now = Utc::now();
+
+        let mut fields = self.fields.lock();
+        for (key, schemas) in unindexed.into_iter().flatten() {
+            let usage = fields.entry(key).or_insert_with(|| FieldUsage {
+                requests: 0,
+                total_duration: Duration::ZERO,
+                last_used: now,
+                candidates: Vec::new(),
+                auto_create_requested: false,
+            });
+            usage.requests += 1;
+            usage.total_duration += request_duration;
+            usage.last_used = now;
+            for schema in schemas {
+                match usage
+                    .candidates
+                    .iter_mut()
+                    .find(|(known, _)| *known == schema)
+                {
+                    Some((_, count)) => *count += 1,
+                    None => usage.candidates.push((schema, 1)),
+                }
+            }
+        }
+
+        while fields.len() > self.config.max_fields {
+            let least_used = fields
+                .iter()
+                .min_by_key(|(_, usage)| (usage.requests, usage.last_used))
+                .map(|(key, _)| key.clone());
+            match least_used {
+                Some(key) => fields.remove(&key),
+                None => break,
+            };
+        }
+    }
+
+    /// Recommended indexes, the ones which would save the most time first
+    ///
+    /// Fields, which got a suitable index since they were recorded, are not recommended.
+    pub fn recommendations(
+        &self,
+        payload_schema: &HashMap<PayloadKeyType, PayloadFieldSchema>,
+    ) -> Vec<IndexRecommendation> {
+        let mut fields = self.fields.lock();
+        fields.retain(|key, usage| {
+            payload_schema
+                .get(key)
+                .map_or(true, |index| usage.best_candidate() != Some(index))
+        });
+
+        let mut recommendations: Vec<_> = fields
+            .iter()
+            .filter_map(|(key, usage)| {
+                let total_duration = usage.total_duration.as_secs_f64();
+                Some(IndexRecommendation {
+                    key: key.clone(),
+                    field_schema: usage.best_candidate()?.clone(),
+                    requests: usage.requests,
+                    total_duration,
+                    avg_duration: total_duration / usage.requests.max(1) as f64,
+                    last_used: usage.last_used,
+                    auto_create_requested: usage.auto_create_requested,
+                })
+            })
+            .collect();
+        recommendations.sort_by(|a, b| b.total_duration.total_cmp(&a.total_duration));
+        recommendations
+    }
+
+    /// Recommended indexes, which exceed the auto-creation thresholds and were not requested yet
+    ///
+    /// Returned indexes are marked as requested, so they are returned only once.
+    pub fn take_auto_create(
+        &self,
+        payload_schema: &HashMap<PayloadKeyType, PayloadFieldSchema>,
+    ) -> Vec<(PayloadKeyType, PayloadFieldSchema)> {
+        let Some(threshold) = self.config.auto_create_threshold else {
+            return Vec::new();
+        };
+
+        let min_avg_duration = self.config.auto_create_min_avg_duration_sec;
+        let auto_create: Vec<_> = self
+            .recommendations(payload_schema)
+            .into_iter()
+            .filter(|recommendation| {
+                !recommendation.auto_create_requested
+                    && recommendation.requests >= threshold
+                    && recommendation.avg_duration >= min_avg_duration
+            })
+            .map(|recommendation| (recommendation.key, recommendation.field_schema))
+            .collect();
+
+        let mut fields = self.fields.lock();
+        for (key, _) in &auto_create {
+            if let Some(usage) = fields.get_mut(key) {
+                usage.auto_create_requested = true;
+            }
+        }
+        auto_create
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::json_path::path;
+    use segment::types::{Condition, FieldCondition, Match, PayloadSchemaType, ValueVariants};
+
+    use super::*;
+
+    fn filter(key: &str) -> Filter {
+        Filter::new_must(Condition::Field(FieldCondition::new_match(
+            path(key),
+            Match::new_value(ValueVariants::Keyword("x".to_string())),
+        )))
+    }
+
+    #[test]
+    fn test_index_advisor() {
+        let config = IndexAdvisorConfig {
+            auto_create_threshold: Some(2),
+            max_fields: 2,
+            ..Default::default()
+        };
+        let advisor = IndexAdvisor::new(&config);
+        let mut payload_schema = HashMap::new();
+
+        let city = filter("city");
+        let country = filter("country");
+        advisor.record(Duration::from_secs(2), [&city, &country], &payload_schema);
+        advisor.record(Duration::from_secs(1), [&city], &payload_schema);
+
+        let recommendations = advisor.recommendations(&payload_schema);
+        assert_eq!(recommendations.len(), 2);
+        assert_eq!(recommendations[0].key, path("city"));
+        assert_eq!(recommendations[0].requests, 2);
+        assert_eq!(recommendations[0].total_duration, 2.0);
+        assert_eq!(
+            recommendations[0].field_schema,
+            PayloadFieldSchema::FieldType(PayloadSchemaType::Keyword),
+        );
+
+        let auto_create = advisor.take_auto_create(&payload_schema);
+        assert_eq!(auto_create.len(), 1);
+        assert_eq!(auto_create[0].0, path("city"));
+        assert!(advisor.take_auto_create(&payload_schema).is_empty());
+
+        // Indexed fields are not recommended anymore
+        payload_schema.insert(path("city"), PayloadSchemaType::Keyword.into());
+        let recommendations = advisor.recommendations(&payload_schema);
+        assert_eq!(recommendations.len(), 1);
+        assert_eq!(recommendations[0].key, path("country"));
+
+        // Least used fields are dropped
+        let a = filter("a");
+        let b = filter("b");
+        advisor.record(Duration::from_secs(1), [&a, &a], &payload_schema);
+        advisor.record(Duration::from_millis(500), [&b], &payload_schema);
+        let keys: Vec<_> = advisor
+            .recommendations(&payload_schema)
+            .into_iter()
+            .map(|recommendation| recommendation.key)
+            .collect();
+        assert_eq!(keys, vec![path("a"), path("b")]);
+    }
+}
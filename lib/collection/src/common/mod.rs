@@ -1,14 +1,25 @@
 pub mod batching;
+pub mod collection_stats;
+pub mod consistency_check;
 pub mod eta_calculator;
 pub mod fetch_vectors;
 pub mod file_utils;
+pub mod freeze;
+pub mod idempotency;
+pub mod index_advisor;
 pub mod is_ready;
+pub mod recall_evaluation;
 pub mod retrieve_request_trait;
 pub mod sha_256;
+pub mod slow_query_log;
+pub mod snapshot_manifest;
 pub mod snapshot_stream;
 pub mod snapshots_manager;
+pub mod sparse_vocabulary;
 pub mod stoppable_task;
 pub mod stoppable_task_async;
 pub mod stopping_guard;
 pub mod transpose_iterator;
+pub mod update_backpressure;
 pub mod validate_snapshot_archive;
+pub mod warmup;
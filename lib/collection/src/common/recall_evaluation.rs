@@ -0,0 +1,40 @@
+use serde::Deserialize;
+
+pub const DEFAULT_RECALL_EVALUATION_SAMPLE: usize = 100;
+
+pub const DEFAULT_RECALL_EVALUATION_LIMIT: usize = 10;
+
+/// Periodic evaluation of the approximate search against exact search
+///
+/// Each evaluation runs `sample` exact searches over the collection, so the interval should be
+/// large for big collections.
+#[derive(Clone, Debug, Deserialize)]
+pub struct RecallEvaluationConfig {
+    /// Interval between the evaluations in seconds. If not set, search is only evaluated on request.
+    #[serde(default)]
+    pub interval_sec: Option<u64>,
+    /// Number of queries for each vector
+    #[serde(default = "default_sample")]
+    pub sample: usize,
+    /// Number of nearest neighbours to compare, the `k` of recall@k
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+}
+
+impl Default for RecallEvaluationConfig {
+    fn default() -> Self {
+        Self {
+            interval_sec: None,
+            sample: DEFAULT_RECALL_EVALUATION_SAMPLE,
+            limit: DEFAULT_RECALL_EVALUATION_LIMIT,
+        }
+    }
+}
+
+const fn default_sample() -> usize {
+    DEFAULT_RECALL_EVALUATION_SAMPLE
+}
+
+const fn default_limit() -> usize {
+    DEFAULT_RECALL_EVALUATION_LIMIT
+}
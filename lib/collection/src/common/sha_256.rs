@@ -1,4 +1,4 @@
-use std::io;
+use std::io::{self, Read as _};
 use std::path::Path;
 
 use bytes::BytesMut;
@@ -25,6 +25,23 @@ pub async fn hash_file(file_path: &Path) -> io::Result<String> {
     Ok(format!("{hash:x}"))
 }
 
+/// Same as [`hash_file`], for use in blocking code
+pub fn hash_file_blocking(file_path: &Path) -> io::Result<String> {
+    const ONE_MB: usize = 1024 * 1024;
+    let mut file = std::fs::File::open(file_path)?;
+    let mut sha = Sha256::new();
+    let mut buf = vec![0; ONE_MB];
+    loop {
+        let len = file.read(&mut buf)?;
+        if len == 0 {
+            break;
+        }
+        sha.update(&buf[..len]);
+    }
+    let hash = sha.finalize();
+    Ok(format!("{hash:x}"))
+}
+
 /// Compare two hashes, ignoring whitespace and case
 pub fn hashes_equal(a: &str, b: &str) -> bool {
     Iterator::eq(
@@ -0,0 +1,226 @@
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+use api::rest::HardwareUsage;
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use segment::data_types::search_plan::ShardSearchPlan;
+use segment::types::Filter;
+use serde::{Deserialize, Serialize};
+
+use crate::operations::query_enum::QueryEnum;
+use crate::operations::types::CoreSearchRequest;
+use crate::operations::universal_query::shard_query::{
+    ScoringQuery, ShardPrefetch, ShardQueryRequest,
+};
+
+const DEFAULT_MAX_ENTRIES: usize = 100;
+
+/// Configuration of the slow query log
+#[derive(Clone, Debug, Deserialize)]
+pub struct SlowQueryLogConfig {
+    /// Record requests, which took longer than this number of seconds.
+    /// If not set, only collections listed in `collections` are logged.
+    #[serde(default)]
+    pub threshold_sec: Option<f64>,
+    /// Per-collection thresholds in seconds, overriding `threshold_sec`
+    #[serde(default)]
+    pub collections: HashMap<String, f64>,
+    /// Max number of entries kept for each collection, oldest entries are dropped first
+    #[serde(default = "default_max_entries")]
+    pub max_entries: usize,
+}
+
+impl Default for SlowQueryLogConfig {
+    fn default() -> Self {
+        Self {
+            threshold_sec: None,
+            collections: HashMap::new(),
+            max_entries: DEFAULT_MAX_ENTRIES,
+        }
+    }
+}
+
+const fn default_max_entries() -> usize {
+    DEFAULT_MAX_ENTRIES
+}
+
+impl SlowQueryLogConfig {
+    pub fn threshold(&self, collection_name: &str) -> Option<Duration> {
+        self.collections
+            .get(collection_name)
+            .copied()
+            .or(self.threshold_sec)
+            .map(Duration::from_secs_f64)
+    }
+}
+
+/// Shape of a single request of the logged batch
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct SlowQueryRequest {
+    /// Kind of the scoring query: `nearest`, `recommend`, `discover`, `context`, `fusion` or `order_by`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+    /// Name of the vector, used for scoring
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub using: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<Filter>,
+    pub limit: usize,
+    pub offset: usize,
+    /// Total number of prefetches, including nested ones
+    pub prefetches: usize,
+}
+
+fn describe_query_enum(query: &QueryEnum) -> &'static str {
+    match query {
+        QueryEnum::Nearest(_) => "nearest",
+        QueryEnum::RecommendBestScore(_) => "recommend",
+        QueryEnum::Discover(_) => "discover",
+        QueryEnum::Context(_) => "context",
+    }
+}
+
+impl From<&CoreSearchRequest> for SlowQueryRequest {
+    fn from(request: &CoreSearchRequest) -> Self {
+        Self {
+            query: Some(describe_query_enum(&request.query).to_string()),
+            using: Some(request.query.get_vector_name().to_string()),
+            filter: request.filter.clone(),
+            limit: request.limit,
+            offset: request.offset,
+            prefetches: 0,
+        }
+    }
+}
+
+impl From<&ShardQueryRequest> for SlowQueryRequest {
+    fn from(request: &ShardQueryRequest) -> Self {
+        let (query, using) = match &request.query {
+            Some(ScoringQuery::Vector(query)) => (
+                Some(describe_query_enum(query)),
+                Some(query.get_vector_name().to_string()),
+            ),
+            Some(ScoringQuery::Fusion(_)) => (Some("fusion"), None),
+            Some(ScoringQuery::OrderBy(_)) => (Some("order_by"), None),
+            Some(ScoringQuery::Formula(_)) => (Some("formula"), None),
+            None => (None, None),
+        };
+        Self {
+            query: query.map(str::to_string),
+            using,
+            filter: request.filter.clone(),
+            limit: request.limit,
+            offset: request.offset,
+            prefetches: count_prefetches(&request.prefetches),
+        }
+    }
+}
+
+fn count_prefetches(prefetches: &[ShardPrefetch]) -> usize {
+    prefetches
+        .iter()
+        .map(|prefetch| 1 + count_prefetches(&prefetch.prefetches))
+        .sum()
+}
+
+/// Single request, which exceeded the slow query threshold
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct SlowQueryEntry {
+    pub timestamp: DateTime<Utc>,
+    /// Time spent on the request, in seconds
+    pub duration: f64,
+    pub requests: Vec<SlowQueryRequest>,
+    /// How each shard executed the search, with segment timings and filter cardinality estimates.
+    /// Only recorded for search requests.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub shards: Vec<ShardSearchPlan>,
+    /// Hardware resources, spent on the request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usage: Option<HardwareUsage>,
+}
+
+/// Bounded in-memory log of slow requests of a single collection.
+///
+/// Entries are not persisted and only cover requests, handled by this peer.
+#[derive(Debug)]
+pub struct SlowQueryLog {
+    threshold: Option<Duration>,
+    max_entries: usize,
+    entries: Mutex<VecDeque<SlowQueryEntry>>,
+}
+
+impl SlowQueryLog {
+    pub fn new(config: &SlowQueryLogConfig, collection_name: &str) -> Self {
+        Self {
+            threshold: config.threshold(collection_name),
+            max_entries: config.max_entries,
+            entries: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.threshold.is_some() && self.max_entries > 0
+    }
+
+    pub fn is_slow(&self, duration: Duration) -> bool {
+        self.is_enabled() && self.threshold.is_some_and(|threshold| duration > threshold)
+    }
+
+    pub fn record(&self, entry: SlowQueryEntry) {
+        if !self.is_enabled() {
+            return;
+        }
+        let mut entries = self.entries.lock();
+        while entries.len() >= self.max_entries {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+
+    /// Logged entries, oldest first
+    pub fn entries(&self) -> Vec<SlowQueryEntry> {
+        self.entries.lock().iter().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(duration: f64) -> SlowQueryEntry {
+        SlowQueryEntry {
+            timestamp: Utc::now(),
+            duration,
+            requests: vec![],
+            shards: vec![],
+            usage: None,
+        }
+    }
+
+    #[test]
+    fn test_slow_query_log_rotation() {
+        let config = SlowQueryLogConfig {
+            threshold_sec: None,
+            collections: HashMap::from([("test".to_string(), 0.5)]),
+            max_entries: 2,
+        };
+
+        let disabled = SlowQueryLog::new(&config, "other");
+        assert!(!disabled.is_slow(Duration::from_secs(10)));
+        disabled.record(entry(10.0));
+        assert!(disabled.entries().is_empty());
+
+        let log = SlowQueryLog::new(&config, "test");
+        assert!(!log.is_slow(Duration::from_millis(100)));
+        assert!(log.is_slow(Duration::from_secs(1)));
+
+        log.record(entry(1.0));
+        log.record(entry(2.0));
+        log.record(entry(3.0));
+
+        let durations: Vec<_> = log.entries().iter().map(|e| e.duration).collect();
+        assert_eq!(durations, vec![2.0, 3.0]);
+    }
+}
@@ -0,0 +1,115 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use io::file_operations::{atomic_save_json, read_json};
+use serde::{Deserialize, Serialize};
+
+use crate::common::sha_256::hash_file_blocking;
+use crate::operations::types::CollectionResult;
+
+/// Name of the manifest file in the root of a collection snapshot
+pub const SNAPSHOT_MANIFEST_FILE: &str = "snapshot_manifest.json";
+
+/// SHA256 checksums of the files of a snapshot, to verify it without restoring
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct SnapshotManifest {
+    /// Checksum of each file by its path, relative to the snapshot root and separated by `/`
+    pub files: BTreeMap<String, String>,
+}
+
+impl SnapshotManifest {
+    /// Compute checksums of all files in `dir`.
+    ///
+    /// This method performs blocking IO.
+    pub fn build(dir: &Path) -> std::io::Result<Self> {
+        let mut manifest = Self::default();
+        manifest.add_dir(dir, "")?;
+        manifest.files.remove(SNAPSHOT_MANIFEST_FILE);
+        Ok(manifest)
+    }
+
+    fn add_dir(&mut self, dir: &Path, prefix: &str) -> std::io::Result<()> {
+        for entry in std::fs::read_dir(dir)? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let relative = format!("{prefix}{name}");
+            if entry.file_type()?.is_dir() {
+                self.add_dir(&entry.path(), &format!("{relative}/"))?;
+            } else {
+                self.files
+                    .insert(relative, hash_file_blocking(&entry.path())?);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn save(&self, dir: &Path) -> CollectionResult<()> {
+        Ok(atomic_save_json(&dir.join(SNAPSHOT_MANIFEST_FILE), self)?)
+    }
+
+    /// Load the manifest from the unpacked snapshot, if it has one
+    pub fn load(dir: &Path) -> CollectionResult<Option<Self>> {
+        let path = dir.join(SNAPSHOT_MANIFEST_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(read_json(&path)?))
+    }
+
+    /// Compare the files in `dir` with the manifest, returns the found mismatches
+    ///
+    /// This method performs blocking IO.
+    pub fn verify(&self, dir: &Path) -> std::io::Result<Vec<String>> {
+        let actual = Self::build(dir)?;
+        let mut mismatches = Vec::new();
+        for (file, checksum) in &self.files {
+            match actual.files.get(file) {
+                None => mismatches.push(format!("File {file} is missing")),
+                Some(actual_checksum) if actual_checksum != checksum => {
+                    mismatches.push(format!("Checksum of file {file} doesn't match"));
+                }
+                Some(_) => {}
+            }
+        }
+        for file in actual.files.keys() {
+            if !self.files.contains_key(file) {
+                mismatches.push(format!("File {file} is not in the manifest"));
+            }
+        }
+        Ok(mismatches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+
+    #[test]
+    fn test_snapshot_manifest() {
+        let dir = Builder::new()
+            .prefix("snapshot_manifest")
+            .tempdir()
+            .unwrap();
+        std::fs::create_dir_all(dir.path().join("0/segments")).unwrap();
+        std::fs::write(dir.path().join("config.json"), "{}").unwrap();
+        std::fs::write(dir.path().join("0/segments/a.tar"), "segment").unwrap();
+
+        let manifest = SnapshotManifest::build(dir.path()).unwrap();
+        assert_eq!(
+            manifest.files.keys().collect::<Vec<_>>(),
+            ["0/segments/a.tar", "config.json"],
+        );
+        manifest.save(dir.path()).unwrap();
+
+        let loaded = SnapshotManifest::load(dir.path()).unwrap().unwrap();
+        assert_eq!(loaded, manifest);
+        assert!(loaded.verify(dir.path()).unwrap().is_empty());
+
+        std::fs::write(dir.path().join("0/segments/a.tar"), "corrupted").unwrap();
+        std::fs::write(dir.path().join("extra"), "").unwrap();
+        std::fs::remove_file(dir.path().join("config.json")).unwrap();
+        assert_eq!(loaded.verify(dir.path()).unwrap().len(), 3);
+    }
+}
@@ -42,6 +42,8 @@ pub struct S3Config {
 
 pub struct SnapshotStorageCloud {
     client: Box<dyn object_store::ObjectStore>,
+    /// Configured bucket, if not set - taken by the client from the environment
+    bucket: Option<String>,
 }
 
 pub struct SnapshotStorageLocalFS;
@@ -87,7 +89,11 @@ impl SnapshotStorageManager {
                         CollectionError::service_error(format!("Failed to create S3 client: {}", e))
                     })?);
 
-                Ok(SnapshotStorageManager::S3(SnapshotStorageCloud { client }))
+                let bucket = snapshots_config.s3_config.map(|s3_config| s3_config.bucket);
+                Ok(SnapshotStorageManager::S3(SnapshotStorageCloud {
+                    client,
+                    bucket,
+                }))
             }
         }
     }
@@ -145,6 +151,33 @@ impl SnapshotStorageManager {
         }
     }
 
+    /// Make a stored snapshot available as a local file, e.g. to recover from it.
+    ///
+    /// Local snapshots are used in place. Snapshots in object storage are downloaded into
+    /// `download_dir`, the returned `TempPath` deletes the downloaded file once dropped.
+    pub async fn get_local_snapshot(
+        &self,
+        snapshot_path: &Path,
+        download_dir: &Path,
+    ) -> CollectionResult<(PathBuf, Option<TempPath>)> {
+        match self {
+            SnapshotStorageManager::LocalFS(_) => Ok((snapshot_path.to_path_buf(), None)),
+            SnapshotStorageManager::S3(storage_impl) => {
+                storage_impl
+                    .get_local_snapshot(snapshot_path, download_dir)
+                    .await
+            }
+        }
+    }
+
+    /// Bucket of the object storage, if snapshots are stored in S3
+    pub fn s3_bucket(&self) -> Option<&str> {
+        match self {
+            SnapshotStorageManager::LocalFS(_) => None,
+            SnapshotStorageManager::S3(storage_impl) => storage_impl.bucket.as_deref(),
+        }
+    }
+
     pub async fn get_snapshot_path(
         &self,
         snapshots_path: &Path,
@@ -449,6 +482,20 @@ impl SnapshotStorageCloud {
         Ok(())
     }
 
+    async fn get_local_snapshot(
+        &self,
+        snapshot_path: &Path,
+        download_dir: &Path,
+    ) -> CollectionResult<(PathBuf, Option<TempPath>)> {
+        let file_name = snapshot_path.file_name().ok_or_else(|| {
+            CollectionError::bad_input(format!("Invalid snapshot path {}", snapshot_path.display()))
+        })?;
+        let local_path = download_dir.join(file_name);
+        let temp_path = TempPath::from_path(&local_path);
+        snapshot_storage_ops::download_snapshot(&self.client, snapshot_path, &local_path).await?;
+        Ok((local_path, Some(temp_path)))
+    }
+
     async fn get_snapshot_path(
         &self,
         snapshots_path: &Path,
@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use lazy_static::lazy_static;
+use parking_lot::RwLock;
+use sparse::common::types::DimId;
+
+lazy_static! {
+    /// Statistics of the shared sparse vocabularies, by vocabulary name
+    static ref SHARED_VOCABULARIES: RwLock<HashMap<String, Arc<SparseVocabularyStats>>> =
+        RwLock::new(HashMap::new());
+}
+
+/// Statistics of a sparse vocabulary, required for IDF weighting of sparse vectors
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SparseVocabularyStats {
+    /// Number of points, which the statistics are collected over
+    pub points_count: usize,
+    /// Number of vectors, which contain each of the dimensions
+    pub document_frequencies: HashMap<DimId, usize>,
+}
+
+impl SparseVocabularyStats {
+    pub fn merge(&mut self, other: &SparseVocabularyStats) {
+        self.points_count += other.points_count;
+        for (dim_id, frequency) in &other.document_frequencies {
+            *self.document_frequencies.entry(*dim_id).or_default() += frequency;
+        }
+    }
+}
+
+/// Latest statistics of the shared vocabulary, `None` if they were not collected yet
+pub fn shared_vocabulary(name: &str) -> Option<Arc<SparseVocabularyStats>> {
+    SHARED_VOCABULARIES.read().get(name).cloned()
+}
+
+/// Replace the statistics of all shared vocabularies
+///
+/// Statistics are collected periodically over all collections of the peer, as collecting them
+/// on each query would require reading the indexes of all collections with the vocabulary.
+pub fn set_shared_vocabularies(vocabularies: HashMap<String, SparseVocabularyStats>) {
+    *SHARED_VOCABULARIES.write() = vocabularies
+        .into_iter()
+        .map(|(name, stats)| (name, Arc::new(stats)))
+        .collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_vocabulary_stats() {
+        let mut stats = SparseVocabularyStats {
+            points_count: 10,
+            document_frequencies: HashMap::from([(1, 3), (2, 5)]),
+        };
+        stats.merge(&SparseVocabularyStats {
+            points_count: 4,
+            document_frequencies: HashMap::from([(2, 1), (7, 2)]),
+        });
+
+        assert_eq!(stats.points_count, 14);
+        assert_eq!(
+            stats.document_frequencies,
+            HashMap::from([(1, 3), (2, 6), (7, 2)]),
+        );
+    }
+}
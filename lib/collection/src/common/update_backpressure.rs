@@ -0,0 +1,16 @@
+use serde::Deserialize;
+
+/// Handling of updates, which arrive when the update queue of a shard is full
+///
+/// Without backpressure, updates wait for a place in the queue without a limit, so ingestion
+/// spikes show up as growing latency and memory of the waiting requests.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct UpdateBackpressureConfig {
+    /// Reject updates, which can't be queued within `max_wait_ms`, with a rate limit error
+    #[serde(default)]
+    pub enabled: bool,
+    /// Time to wait for a place in a full update queue before rejecting the update.
+    /// If 0 - updates are rejected immediately.
+    #[serde(default)]
+    pub max_wait_ms: u64,
+}
@@ -0,0 +1,238 @@
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Size of a single read
+const CHUNK_SIZE: usize = 1024 * 1024;
+
+/// Configuration of the warmup of memory mapped files after collection load
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct WarmupConfig {
+    /// Read memory mapped files of segments in the background after the collection is loaded.
+    /// Replaces the prefaulting of mmap pages on shard load.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Max read rate of the warmup in megabytes per second. If not set, files are read as fast
+    /// as the disk allows.
+    #[serde(default)]
+    pub max_mb_per_sec: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WarmupStatus {
+    /// Warmup is not enabled in the configuration
+    Disabled,
+    /// Files are being read
+    InProgress,
+    /// All files were read
+    Done,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct WarmupFileProgress {
+    /// Path of the file, relative to the collection directory
+    pub path: String,
+    pub total_bytes: u64,
+    pub populated_bytes: u64,
+    /// Reading of the file failed, for example, because it was removed by an optimization
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Progress of populating the page cache with memory mapped files of the collection
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct WarmupProgress {
+    pub status: WarmupStatus,
+    pub total_bytes: u64,
+    pub populated_bytes: u64,
+    pub files: Vec<WarmupFileProgress>,
+}
+
+/// Background warmup of memory mapped files of a single collection
+///
+/// Files are read sequentially by a single thread, so the page cache is populated before the
+/// first searches need them. Only files of segments, present on collection load, are read.
+#[derive(Debug)]
+pub struct Warmup {
+    config: WarmupConfig,
+    collection_path: PathBuf,
+    files: Arc<Mutex<Vec<WarmupFileProgress>>>,
+    done: Arc<AtomicBool>,
+    stopped: Arc<AtomicBool>,
+}
+
+impl Warmup {
+    pub fn new(config: &WarmupConfig, collection_path: &Path) -> Self {
+        Self {
+            config: config.clone(),
+            collection_path: collection_path.to_path_buf(),
+            files: Default::default(),
+            done: Arc::new(AtomicBool::new(false)),
+            stopped: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Start reading `files` in the background
+    pub fn start(&self, files: Vec<PathBuf>) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        let files: Vec<_> = files
+            .into_iter()
+            .filter_map(|path| {
+                let total_bytes = path.metadata().ok()?.len();
+                Some((path, total_bytes))
+            })
+            .collect();
+        *self.files.lock() = files
+            .iter()
+            .map(|(path, total_bytes)| WarmupFileProgress {
+                path: path
+                    .strip_prefix(&self.collection_path)
+                    .unwrap_or(path)
+                    .display()
+                    .to_string(),
+                total_bytes: *total_bytes,
+                populated_bytes: 0,
+                error: None,
+            })
+            .collect();
+
+        let progress = self.files.clone();
+        let done = self.done.clone();
+        let stopped = self.stopped.clone();
+        let bytes_per_sec = self
+            .config
+            .max_mb_per_sec
+            .filter(|rate| *rate > 0)
+            .map(|rate| rate as u64 * 1024 * 1024);
+        let thread_name = format!(
+            "warmup-{}",
+            self.collection_path
+                .file_name()
+                .map(|name| name.to_string_lossy())
+                .unwrap_or_default(),
+        );
+
+        let result = thread::Builder::new().name(thread_name).spawn(move || {
+            let start = Instant::now();
+            let mut read_bytes = 0;
+            for (index, (path, _)) in files.iter().enumerate() {
+                let result = read_file(path, &stopped, |chunk_bytes| {
+                    progress.lock()[index].populated_bytes += chunk_bytes;
+                    read_bytes += chunk_bytes;
+                    if let Some(bytes_per_sec) = bytes_per_sec {
+                        let expected =
+                            Duration::from_secs_f64(read_bytes as f64 / bytes_per_sec as f64);
+                        if let Some(delay) = expected.checked_sub(start.elapsed()) {
+                            thread::sleep(delay);
+                        }
+                    }
+                });
+                if let Err(err) = result {
+                    log::debug!("Failed to warm up file {}: {err}", path.display());
+                    progress.lock()[index].error = Some(err.to_string());
+                }
+                if stopped.load(Ordering::Relaxed) {
+                    return;
+                }
+            }
+            done.store(true, Ordering::Relaxed);
+            log::debug!("Warmup of {} files took {:?}", files.len(), start.elapsed());
+        });
+        if let Err(err) = result {
+            log::error!("Failed to start warmup thread: {err}");
+        }
+    }
+
+    pub fn progress(&self) -> WarmupProgress {
+        let files = self.files.lock().clone();
+        let status = if !self.is_enabled() {
+            WarmupStatus::Disabled
+        } else if self.done.load(Ordering::Relaxed) {
+            WarmupStatus::Done
+        } else {
+            WarmupStatus::InProgress
+        };
+        WarmupProgress {
+            status,
+            total_bytes: files.iter().map(|file| file.total_bytes).sum(),
+            populated_bytes: files.iter().map(|file| file.populated_bytes).sum(),
+            files,
+        }
+    }
+}
+
+impl Drop for Warmup {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Read the whole file, calling `on_chunk` with the size of each read chunk
+fn read_file(
+    path: &Path,
+    stopped: &AtomicBool,
+    mut on_chunk: impl FnMut(u64),
+) -> std::io::Result<()> {
+    let mut file = File::open(path)?;
+    let mut buffer = vec![0; CHUNK_SIZE];
+    while !stopped.load(Ordering::Relaxed) {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        on_chunk(read as u64);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+
+    #[test]
+    fn test_warmup_progress() {
+        let dir = Builder::new().prefix("collection").tempdir().unwrap();
+        let file = dir.path().join("vectors.bin");
+        std::fs::write(&file, vec![1u8; CHUNK_SIZE + 10]).unwrap();
+
+        let config = WarmupConfig {
+            enabled: true,
+            max_mb_per_sec: None,
+        };
+        let warmup = Warmup::new(&config, dir.path());
+        warmup.start(vec![file, dir.path().join("missing.bin")]);
+
+        let start = Instant::now();
+        while warmup.progress().status != WarmupStatus::Done {
+            assert!(start.elapsed() < Duration::from_secs(10));
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        let progress = warmup.progress();
+        assert_eq!(progress.files.len(), 1);
+        assert_eq!(progress.files[0].path, "vectors.bin");
+        assert_eq!(progress.total_bytes, CHUNK_SIZE as u64 + 10);
+        assert_eq!(progress.populated_bytes, progress.total_bytes);
+
+        let disabled = Warmup::new(&WarmupConfig::default(), dir.path());
+        assert_eq!(disabled.progress().status, WarmupStatus::Disabled);
+    }
+}
@@ -1,27 +1,30 @@
 use std::collections::{BTreeMap, HashMap};
 use std::fs::File;
 use std::io::{Read, Write};
-use std::num::NonZeroU32;
+use std::num::{NonZeroU32, NonZeroU64};
 use std::path::Path;
 
 use atomicwrites::AtomicFile;
 use atomicwrites::OverwriteBehavior::AllowOverwrite;
+use merge::Merge;
 use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
 use segment::data_types::vectors::DEFAULT_VECTOR_NAME;
 use segment::index::sparse_index::sparse_index_config::{
     SparseIndexConfig, SparseIndexType, SparseVectorIndexDatatype,
 };
+use segment::json_path::JsonPath;
 use segment::types::{
     default_replication_factor_const, default_shard_number_const,
-    default_write_consistency_factor_const, Distance, HnswConfig, Indexes, PayloadStorageType,
-    QuantizationConfig, SparseVectorDataConfig, VectorDataConfig, VectorStorageDatatype,
-    VectorStorageType,
+    default_write_consistency_factor_const, AttributeType, Distance, HnswConfig, Indexes,
+    PayloadCompression, PayloadSchemaType, PayloadStorageType, QuantizationConfig, SearchParams,
+    SparseVectorDataConfig, VectorDataConfig, VectorStorageDatatype, VectorStorageType,
 };
 use serde::{Deserialize, Serialize};
-use validator::Validate;
+use validator::{Validate, ValidationError};
 use wal::WalOptions;
 
+use crate::operations::computed_fields::ComputedField;
 use crate::operations::config_diff::{DiffConfig, QuantizationConfigDiff};
 use crate::operations::types::{
     CollectionError, CollectionResult, SparseVectorParams, SparseVectorsConfig, VectorParams,
@@ -104,6 +107,38 @@ pub struct CollectionParams {
     /// Note: those payload values that are involved in filtering and are indexed - remain in RAM.
     #[serde(default = "default_on_disk_payload")]
     pub on_disk_payload: bool,
+    /// If true - point's payload is stored in gridstore: append-only blocks on disk, read through
+    /// mmap, instead of RocksDB. Writes of payload are never stalled by compactions.
+    /// Existing segments are migrated to the new storage in background by optimizers.
+    /// When enabled, `on_disk_payload` has no effect.
+    #[serde(default)]
+    pub gridstore_payload: bool,
+    /// Numeric payload fields, whose values are also stored per field contiguously.
+    /// Ordering by such a field scans its column instead of reading full payloads,
+    /// even if the field has no range index.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub payload_columns: Vec<JsonPath>,
+    /// If set - payload in the on-disk payload storage is compressed with zstd,
+    /// using a dictionary trained on payloads of each segment.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub payload_compression: Option<PayloadCompression>,
+    /// If set - this number of last versions of each point (vectors and payload) is retained,
+    /// and can be retrieved by version or by time.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub point_history_versions: Option<NonZeroU32>,
+    /// If set - deleted points are kept in the trash of the shard for this number of seconds,
+    /// and can be restored by id.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub soft_delete_retention_sec: Option<NonZeroU64>,
+    /// Payload fields, computed from other payload fields or vectors on every upsert, and indexed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[validate]
+    pub computed_fields: Vec<ComputedField>,
+    /// Typed scalar attributes of points, stored in dense columns outside of the payload.
+    /// Filters and formula queries read attributes without reading the payload.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub attribute_columns: BTreeMap<String, AttributeType>,
     /// Configuration of the sparse vector storage
     #[serde(default, skip_serializing_if = "Option::is_none")]
     #[validate]
@@ -112,7 +147,9 @@ pub struct CollectionParams {
 
 impl CollectionParams {
     pub fn payload_storage_type(&self) -> PayloadStorageType {
-        if self.on_disk_payload {
+        if self.gridstore_payload {
+            PayloadStorageType::Gridstore
+        } else if self.on_disk_payload {
             PayloadStorageType::OnDisk
         } else {
             PayloadStorageType::InMemory
@@ -130,6 +167,17 @@ impl Anonymize for CollectionParams {
             write_consistency_factor: self.write_consistency_factor,
             read_fan_out_factor: self.read_fan_out_factor,
             on_disk_payload: self.on_disk_payload,
+            gridstore_payload: self.gridstore_payload,
+            payload_columns: self.payload_columns.anonymize(),
+            payload_compression: self.payload_compression,
+            point_history_versions: self.point_history_versions,
+            soft_delete_retention_sec: self.soft_delete_retention_sec,
+            computed_fields: self.computed_fields.anonymize(),
+            attribute_columns: self
+                .attribute_columns
+                .iter()
+                .map(|(name, attribute_type)| (name.anonymize(), *attribute_type))
+                .collect(),
             sparse_vectors: self.sparse_vectors.anonymize(),
         }
     }
@@ -151,6 +199,123 @@ const fn default_on_disk_payload() -> bool {
     false
 }
 
+/// Limits, enforced on requests to the collection in strict mode.
+///
+/// Requests, exceeding the limits, are rejected instead of being executed slowly.
+/// Limits which are not set are not enforced.
+#[derive(
+    Debug, Default, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq, Hash, Merge,
+)]
+#[serde(rename_all = "snake_case")]
+pub struct StrictModeConfig {
+    /// Whether strict mode is enabled for the collection
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    /// Max `limit` of search, query, recommend, discover and scroll requests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub max_query_limit: Option<usize>,
+    /// Max `hnsw_ef` search parameter
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub search_max_hnsw_ef: Option<usize>,
+    /// Whether exact search, bypassing the vector index, is allowed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search_allow_exact: Option<bool>,
+    /// Whether read requests may filter by payload fields without an index
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unindexed_filtering_retrieve: Option<bool>,
+    /// Whether update requests may filter by payload fields without an index
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unindexed_filtering_update: Option<bool>,
+    /// Max size of the payload of a single point in bytes, measured as serialized JSON
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub max_payload_size_bytes: Option<usize>,
+    /// Schema, which payloads of updated points must match. Replaced as a whole on update.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub payload_schema: Option<PayloadSchemaConfig>,
+    /// Max number of points in the collection. Only deletes are allowed, once it is reached.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub max_points_count: Option<usize>,
+    /// Max size of vector storages, vector indexes and quantized vectors of the collection,
+    /// which are loaded into RAM, in bytes. Only deletes are allowed, once it is reached.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub max_ram_usage_bytes: Option<usize>,
+    /// Max size of the segments of the collection on disk, in bytes.
+    /// Only deletes are allowed, once it is reached.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub max_disk_usage_bytes: Option<usize>,
+}
+
+/// Schema of payloads, enforced on updates in strict mode
+///
+/// Payloads of upserted points are checked completely. Payloads, which are set or patched,
+/// are only checked for the keys which they contain.
+#[derive(
+    Debug, Default, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq, Hash,
+)]
+#[serde(rename_all = "snake_case")]
+pub struct PayloadSchemaConfig {
+    /// Constraints of top level payload keys
+    #[serde(default)]
+    #[validate(custom = "validate_payload_field_constraints")]
+    pub fields: BTreeMap<String, PayloadFieldConstraints>,
+    /// Whether payloads may have keys, which are not in `fields`. Default: true
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allow_unknown_fields: Option<bool>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub struct PayloadFieldConstraints {
+    /// Type of the values. Arrays are allowed, if all of their elements have this type.
+    #[serde(default, rename = "type", skip_serializing_if = "Option::is_none")]
+    pub value_type: Option<PayloadSchemaType>,
+    /// Whether payloads must have a non-null value of this key. Default: false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub required: Option<bool>,
+    /// Max length of string values, in characters
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<usize>,
+    /// Max number of elements of array values
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_array_length: Option<usize>,
+}
+
+fn validate_payload_field_constraints(
+    fields: &BTreeMap<String, PayloadFieldConstraints>,
+) -> Result<(), ValidationError> {
+    if fields.keys().any(|key| key.is_empty()) {
+        let mut error = ValidationError::new("empty_key");
+        error.message = Some("payload schema keys must not be empty".into());
+        return Err(error);
+    }
+    if fields.values().any(|field| field.max_length == Some(0)) {
+        let mut error = ValidationError::new("range");
+        error.message = Some("`max_length` must be at least 1".into());
+        return Err(error);
+    }
+    Ok(())
+}
+
+impl StrictModeConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.unwrap_or(false)
+    }
+
+    /// Whether any quota on the resource usage of the collection is set
+    pub fn has_quotas(&self) -> bool {
+        self.max_points_count.is_some()
+            || self.max_ram_usage_bytes.is_some()
+            || self.max_disk_usage_bytes.is_some()
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
 pub struct CollectionConfig {
     #[validate]
@@ -163,6 +328,9 @@ pub struct CollectionConfig {
     pub wal_config: WalConfig,
     #[serde(default)]
     pub quantization_config: Option<QuantizationConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub strict_mode_config: Option<StrictModeConfig>,
 }
 
 impl CollectionConfig {
@@ -207,6 +375,13 @@ impl CollectionParams {
             write_consistency_factor: default_write_consistency_factor(),
             read_fan_out_factor: None,
             on_disk_payload: default_on_disk_payload(),
+            gridstore_payload: false,
+            payload_columns: Vec::new(),
+            payload_compression: None,
+            point_history_versions: None,
+            soft_delete_retention_sec: None,
+            computed_fields: Vec::new(),
+            attribute_columns: BTreeMap::new(),
             sparse_vectors: None,
         }
     }
@@ -237,6 +412,30 @@ impl CollectionParams {
         }
     }
 
+    /// Distance to score `vector_name` with, taking the distance override of the search into account
+    pub fn get_search_distance(
+        &self,
+        vector_name: &str,
+        params: Option<&SearchParams>,
+    ) -> CollectionResult<Distance> {
+        let distance = self.get_distance(vector_name)?;
+        let Some(search_distance) = params.and_then(|params| params.distance) else {
+            return Ok(distance);
+        };
+        if self.vectors.get_params(vector_name).is_none() {
+            return Err(CollectionError::bad_input(format!(
+                "Distance can't be overridden for sparse vector {vector_name}"
+            )));
+        }
+        if !distance.can_score_with(search_distance) {
+            return Err(CollectionError::bad_input(format!(
+                "Vector {vector_name} is stored for {distance:?} distance and can't be scored with \
+                 {search_distance:?} distance, only Cosine vectors can be scored with other distances"
+            )));
+        }
+        Ok(search_distance)
+    }
+
     fn get_vector_params_mut(&mut self, vector_name: &str) -> CollectionResult<&mut VectorParams> {
         self.vectors
             .get_params_mut(vector_name)
@@ -320,12 +519,20 @@ impl CollectionParams {
     ) -> CollectionResult<()> {
         for (vector_name, update_params) in update_vectors.0.iter() {
             let sparse_vector_params = self.get_sparse_vector_params_mut(vector_name)?;
-            let SparseVectorParams { index, modifier } = update_params.clone();
+            let SparseVectorParams {
+                index,
+                modifier,
+                vocabulary,
+            } = update_params.clone();
 
             if let Some(modifier) = modifier {
                 sparse_vector_params.modifier = Some(modifier);
             }
 
+            if let Some(vocabulary) = vocabulary {
+                sparse_vector_params.vocabulary = Some(vocabulary);
+            }
+
             if let Some(index) = index {
                 if let Some(existing_index) = &mut sparse_vector_params.index {
                     existing_index.update_from_other(&index);
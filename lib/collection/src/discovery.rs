@@ -1,5 +1,6 @@
 use std::time::Duration;
 
+use common::hardware_counter::HardwareCounterCell;
 use futures::Future;
 use itertools::Itertools;
 use segment::data_types::vectors::NamedQuery;
@@ -211,6 +212,7 @@ where
                 read_consistency,
                 shard_selector,
                 timeout,
+                &HardwareCounterCell::disabled(),
             ));
 
             Ok(())
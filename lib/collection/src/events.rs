@@ -3,12 +3,40 @@ use std::collections::HashMap;
 use segment::json_path::JsonPathV2;
 use segment::types::{Filter, PayloadFieldSchema};
 
+use crate::shards::shard::{PeerId, ShardId};
+use crate::shards::transfer::ShardTransferMethod;
 use crate::shards::CollectionId;
 
+pub struct CollectionCreatedEvent {
+    pub collection_id: CollectionId,
+}
+
 pub struct CollectionDeletedEvent {
     pub collection_id: CollectionId,
 }
 
+/// First optimizer error of a local shard, optimizations of the shard are stopped after it
+pub struct OptimizerErrorEvent {
+    pub collection_id: CollectionId,
+    pub shard_id: ShardId,
+    pub error: String,
+}
+
+pub struct ShardTransferFinishedEvent {
+    pub collection_id: CollectionId,
+    pub shard_id: ShardId,
+    pub from: PeerId,
+    pub to: PeerId,
+    pub method: Option<ShardTransferMethod>,
+}
+
+/// Replica of a shard is marked as dead, it doesn't receive updates until it is recovered
+pub struct ReplicaDeadEvent {
+    pub collection_id: CollectionId,
+    pub shard_id: ShardId,
+    pub peer_id: PeerId,
+}
+
 pub struct SlowQueryEvent {
     pub collection_id: CollectionId,
     pub filters: Vec<Filter>,
@@ -3,6 +3,7 @@ use std::future::Future;
 use std::time::Duration;
 
 use api::rest::{BaseGroupRequest, SearchGroupsRequestInternal, SearchRequestInternal};
+use common::hardware_counter::HardwareCounterCell;
 use fnv::FnvBuildHasher;
 use indexmap::IndexSet;
 use segment::json_path::{JsonPath, JsonPathInterface as _};
@@ -133,7 +134,13 @@ impl CoreGroupRequest {
         request.with_vector = None;
 
         collection
-            .search(request, read_consistency, &shard_selection, timeout)
+            .search(
+                request,
+                read_consistency,
+                &shard_selection,
+                timeout,
+                &HardwareCounterCell::disabled(),
+            )
             .await
     }
 }
@@ -235,7 +242,9 @@ pub async fn group_by(
     let score_ordering = if request.source.query.is_distance_scored() {
         let vector_name = request.source.query.get_vector_name();
         let collection_params = collection.collection_config.read().await;
-        let distance = collection_params.params.get_distance(vector_name)?;
+        let distance = collection_params
+            .params
+            .get_search_distance(vector_name, request.source.params.as_ref())?;
         distance.distance_order()
     } else {
         Order::LargeBetter
@@ -387,6 +396,7 @@ pub async fn group_by(
             request.source.with_vector.unwrap_or_default(),
             read_consistency,
             &shard_selection,
+            None,
         )
         .await?
         .into_iter()
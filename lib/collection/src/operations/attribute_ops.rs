@@ -0,0 +1,208 @@
+use std::collections::{BTreeMap, HashMap};
+
+use api::rest::ShardKeySelector;
+use schemars::JsonSchema;
+use segment::types::{AttributeType, AttributeValue, Filter, PointIdType};
+use serde::{Deserialize, Serialize};
+use strum::{EnumDiscriminants, EnumIter};
+use validator::Validate;
+
+use super::payload_ops::PointsSelectorValidationError;
+use super::types::{CollectionError, CollectionResult};
+use super::{split_iter_by_shard, CollectionUpdateOperations, OperationToShard, SplitByShard};
+use crate::hash_ring::HashRing;
+
+/// This data structure is used in API interface and applied across multiple shards
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(try_from = "SetAttributesShadow")]
+pub struct SetAttributes {
+    /// Values of attributes to set, `null` removes the attribute
+    pub attributes: HashMap<String, Option<AttributeValue>>,
+    /// Assigns attributes to each point in this list
+    pub points: Option<Vec<PointIdType>>,
+    /// Assigns attributes to each point that satisfy this filter condition
+    pub filter: Option<Filter>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKeySelector>,
+}
+
+/// This data structure is used inside shard operations queue
+/// and supposed to be written into WAL of individual shard.
+///
+/// Unlike `SetAttributes` it does not contain `shard_key` field
+/// as individual shard does not need to know about shard key
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, Validate)]
+pub struct SetAttributesOp {
+    /// Values of attributes to set, `None` removes the attribute
+    pub attributes: HashMap<String, Option<AttributeValue>>,
+    /// Assigns attributes to each point in this list
+    pub points: Option<Vec<PointIdType>>,
+    /// Assigns attributes to each point that satisfy this filter condition
+    pub filter: Option<Filter>,
+}
+
+#[derive(Deserialize)]
+struct SetAttributesShadow {
+    pub attributes: HashMap<String, Option<AttributeValue>>,
+    pub points: Option<Vec<PointIdType>>,
+    pub filter: Option<Filter>,
+    pub shard_key: Option<ShardKeySelector>,
+}
+
+impl TryFrom<SetAttributesShadow> for SetAttributes {
+    type Error = PointsSelectorValidationError;
+
+    fn try_from(value: SetAttributesShadow) -> Result<Self, Self::Error> {
+        if value.points.is_some() || value.filter.is_some() {
+            Ok(SetAttributes {
+                attributes: value.attributes,
+                points: value.points,
+                filter: value.filter,
+                shard_key: value.shard_key,
+            })
+        } else {
+            Err(PointsSelectorValidationError)
+        }
+    }
+}
+
+/// Define operations description for point attributes manipulation
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, EnumDiscriminants)]
+#[strum_discriminants(derive(EnumIter))]
+#[serde(rename_all = "snake_case")]
+pub enum AttributeOps {
+    /// Set or remove values of attributes, other attributes of the points are kept
+    SetAttributes(SetAttributesOp),
+}
+
+impl AttributeOps {
+    pub fn is_write_operation(&self) -> bool {
+        match self {
+            AttributeOps::SetAttributes(_) => true,
+        }
+    }
+}
+
+impl Validate for AttributeOps {
+    fn validate(&self) -> Result<(), validator::ValidationErrors> {
+        match self {
+            AttributeOps::SetAttributes(operation) => operation.validate(),
+        }
+    }
+}
+
+impl SplitByShard for AttributeOps {
+    fn split_by_shard(self, ring: &HashRing) -> OperationToShard<Self> {
+        match self {
+            AttributeOps::SetAttributes(operation) => operation
+                .split_by_shard(ring)
+                .map(AttributeOps::SetAttributes),
+        }
+    }
+}
+
+impl SplitByShard for SetAttributesOp {
+    fn split_by_shard(self, ring: &HashRing) -> OperationToShard<Self> {
+        match (&self.points, &self.filter) {
+            (Some(_), _) => {
+                split_iter_by_shard(self.points.unwrap(), |id| *id, ring).map(|points| {
+                    SetAttributesOp {
+                        points: Some(points),
+                        attributes: self.attributes.clone(),
+                        filter: self.filter.clone(),
+                    }
+                })
+            }
+            (None, Some(_)) => OperationToShard::to_all(self),
+            (None, None) => OperationToShard::to_none(),
+        }
+    }
+}
+
+/// Check the attributes, set by the operation, against the attribute columns of the collection.
+///
+/// Integer values of float attributes are converted, so that shards store values of the
+/// declared type only.
+pub fn check_attribute_values(
+    columns: &BTreeMap<String, AttributeType>,
+    operation: &mut CollectionUpdateOperations,
+) -> CollectionResult<()> {
+    let CollectionUpdateOperations::AttributeOperation(AttributeOps::SetAttributes(operation)) =
+        operation
+    else {
+        return Ok(());
+    };
+    for (name, value) in operation.attributes.iter_mut() {
+        let Some(attribute_type) = columns.get(name) else {
+            return Err(CollectionError::bad_input(format!(
+                "Attribute `{name}` is not declared in `attribute_columns` of the collection"
+            )));
+        };
+        let Some(attribute_value) = value else {
+            continue;
+        };
+        match (attribute_type, *attribute_value) {
+            (AttributeType::Float, AttributeValue::Integer(integer)) => {
+                *value = Some(AttributeValue::Float(integer as f32));
+            }
+            (AttributeType::Integer, AttributeValue::Float(float)) => {
+                return Err(CollectionError::bad_input(format!(
+                    "Attribute `{name}` is an integer, got {float}"
+                )));
+            }
+            (AttributeType::Float, AttributeValue::Float(_))
+            | (AttributeType::Integer, AttributeValue::Integer(_)) => {}
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set_attributes(
+        attributes: HashMap<String, Option<AttributeValue>>,
+    ) -> CollectionUpdateOperations {
+        CollectionUpdateOperations::AttributeOperation(AttributeOps::SetAttributes(
+            SetAttributesOp {
+                attributes,
+                points: Some(vec![1.into()]),
+                filter: None,
+            },
+        ))
+    }
+
+    #[test]
+    fn test_check_attribute_values() {
+        let columns = BTreeMap::from([
+            ("price".to_string(), AttributeType::Float),
+            ("stock".to_string(), AttributeType::Integer),
+        ]);
+
+        let mut operation = set_attributes(HashMap::from([
+            ("price".to_string(), Some(AttributeValue::Integer(3))),
+            ("stock".to_string(), None),
+        ]));
+        check_attribute_values(&columns, &mut operation).unwrap();
+        assert_eq!(
+            operation,
+            set_attributes(HashMap::from([
+                ("price".to_string(), Some(AttributeValue::Float(3.0))),
+                ("stock".to_string(), None),
+            ])),
+        );
+
+        let mut operation = set_attributes(HashMap::from([(
+            "stock".to_string(),
+            Some(AttributeValue::Float(1.5)),
+        )]));
+        assert!(check_attribute_values(&columns, &mut operation).is_err());
+
+        let mut operation = set_attributes(HashMap::from([(
+            "unknown".to_string(),
+            Some(AttributeValue::Integer(1)),
+        )]));
+        assert!(check_attribute_values(&columns, &mut operation).is_err());
+    }
+}
@@ -0,0 +1,293 @@
+use std::collections::HashSet;
+
+use api::rest::{BatchVectorStruct, Vector, VectorStruct};
+use schemars::JsonSchema;
+use segment::common::anonymize::Anonymize;
+use segment::data_types::vectors::DEFAULT_VECTOR_NAME;
+use segment::json_path::JsonPath;
+use segment::types::{Payload, PayloadContainer, PayloadFieldSchema, PayloadSchemaType};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use validator::Validate;
+
+use super::point_ops::{PointInsertOperationsInternal, PointOperations};
+use super::types::{CollectionError, CollectionResult};
+use super::CollectionUpdateOperations;
+use crate::config::CollectionParams;
+
+/// Function, which computes the value of a computed payload field
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ComputedFunction {
+    /// Lowercase copy of a string payload field
+    Lowercase,
+    /// Number of whitespace separated words in a string payload field
+    TokenCount,
+    /// Euclidean norm of a dense or sparse vector
+    VectorNorm,
+}
+
+impl ComputedFunction {
+    /// Type of the payload index, which is built for the computed field
+    pub fn index_schema(&self) -> PayloadFieldSchema {
+        let schema_type = match self {
+            ComputedFunction::Lowercase => PayloadSchemaType::Keyword,
+            ComputedFunction::TokenCount => PayloadSchemaType::Integer,
+            ComputedFunction::VectorNorm => PayloadSchemaType::Float,
+        };
+        PayloadFieldSchema::FieldType(schema_type)
+    }
+}
+
+/// Payload field, which value is computed from other payload fields or vectors of the point on
+/// every upsert, and indexed
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub struct ComputedField {
+    /// Top-level payload field to store the computed value in
+    #[validate(length(min = 1))]
+    pub key: String,
+    pub function: ComputedFunction,
+    /// Payload field, which the value is computed from, or name of the vector for `vector_norm`.
+    /// Default vector is used for `vector_norm`, if not specified.
+    #[serde(default)]
+    pub source: String,
+}
+
+impl ComputedField {
+    pub fn key_path(&self) -> JsonPath {
+        JsonPath {
+            first_key: self.key.clone(),
+            rest: Vec::new(),
+        }
+    }
+
+    fn compute(&self, payload: &Payload, vector: Option<VectorRef>) -> Option<Value> {
+        match self.function {
+            ComputedFunction::Lowercase => {
+                let value = self.source_value(payload)?;
+                Some(Value::from(value.to_lowercase()))
+            }
+            ComputedFunction::TokenCount => {
+                let value = self.source_value(payload)?;
+                Some(Value::from(value.split_whitespace().count()))
+            }
+            ComputedFunction::VectorNorm => {
+                let squares: f64 = match vector? {
+                    VectorRef::Dense(vector) => vector.iter().map(|&x| f64::from(x).powi(2)).sum(),
+                    VectorRef::Sparse(values) => values.iter().map(|&x| f64::from(x).powi(2)).sum(),
+                };
+                Some(Value::from(squares.sqrt()))
+            }
+        }
+    }
+
+    fn source_value<'a>(&self, payload: &'a Payload) -> Option<&'a str> {
+        let path = JsonPath::try_from(self.source.as_str()).ok()?;
+        payload
+            .get_value(&path)
+            .into_iter()
+            .find_map(|value| value.as_str())
+    }
+
+    fn vector_name(&self) -> &str {
+        if self.source.is_empty() {
+            DEFAULT_VECTOR_NAME
+        } else {
+            &self.source
+        }
+    }
+}
+
+impl Anonymize for ComputedField {
+    fn anonymize(&self) -> Self {
+        Self {
+            key: self.key.anonymize(),
+            function: self.function,
+            source: self.source.anonymize(),
+        }
+    }
+}
+
+/// Check, that computed fields can be materialized for the collection
+pub fn check_computed_fields(
+    fields: &[ComputedField],
+    params: &CollectionParams,
+) -> CollectionResult<()> {
+    let mut keys = HashSet::new();
+    for field in fields {
+        field.validate()?;
+        if !keys.insert(&field.key) {
+            return Err(CollectionError::bad_input(format!(
+                "Computed field `{}` is defined more than once",
+                field.key,
+            )));
+        }
+        match field.function {
+            ComputedFunction::Lowercase | ComputedFunction::TokenCount => {
+                if JsonPath::try_from(field.source.as_str()).is_err() {
+                    return Err(CollectionError::bad_input(format!(
+                        "Computed field `{}` requires a payload field as `source`",
+                        field.key,
+                    )));
+                }
+            }
+            ComputedFunction::VectorNorm => {
+                let name = field.vector_name();
+                let is_dense = params.vectors.get_params(name).is_some();
+                let is_sparse = params
+                    .sparse_vectors
+                    .as_ref()
+                    .is_some_and(|sparse| sparse.contains_key(name));
+                if !is_dense && !is_sparse {
+                    return Err(CollectionError::bad_input(format!(
+                        "Computed field `{}` refers to vector `{name}`, which doesn't exist",
+                        field.key,
+                    )));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Write computed fields into payloads of the points, inserted by the operation
+///
+/// Fields are materialized before the operation is split by shards, so that all replicas store
+/// the same values. Fields, which can't be computed for a point, e.g. because the source field
+/// is missing, are not set.
+pub fn materialize_computed_fields(
+    fields: &[ComputedField],
+    operation: &mut CollectionUpdateOperations,
+) {
+    if fields.is_empty() {
+        return;
+    }
+    let CollectionUpdateOperations::PointOperation(operation) = operation else {
+        return;
+    };
+    let insert = match operation {
+        PointOperations::UpsertPoints(insert) => insert,
+        PointOperations::UpsertPointsConditional(operation) => &mut operation.points_op,
+        _ => return,
+    };
+
+    match insert {
+        PointInsertOperationsInternal::PointsList(points) => {
+            for point in points {
+                let payload = point.payload.get_or_insert_with(Payload::default);
+                for field in fields {
+                    let vector = VectorRef::from_struct(&point.vector, field.vector_name());
+                    set_computed(field, payload, vector);
+                }
+            }
+        }
+        PointInsertOperationsInternal::PointsBatch(batch) => {
+            let payloads = batch
+                .payloads
+                .get_or_insert_with(|| vec![None; batch.ids.len()]);
+            payloads.resize(batch.ids.len(), None);
+            for (index, payload) in payloads.iter_mut().enumerate() {
+                let payload = payload.get_or_insert_with(Payload::default);
+                for field in fields {
+                    let vector = VectorRef::from_batch(&batch.vectors, field.vector_name(), index);
+                    set_computed(field, payload, vector);
+                }
+            }
+        }
+    }
+}
+
+fn set_computed(field: &ComputedField, payload: &mut Payload, vector: Option<VectorRef>) {
+    match field.compute(payload, vector) {
+        Some(value) => {
+            payload.0.insert(field.key.clone(), value);
+        }
+        None => {
+            // Don't keep a value, provided by the user or computed from a previous version
+            payload.0.remove(&field.key);
+        }
+    }
+}
+
+/// Vector, which norm can be computed
+enum VectorRef<'a> {
+    Dense(&'a [f32]),
+    /// Values of a sparse vector
+    Sparse(&'a [f32]),
+}
+
+impl<'a> VectorRef<'a> {
+    fn from_vector(vector: &'a Vector) -> Option<Self> {
+        match vector {
+            Vector::Dense(vector) => Some(Self::Dense(vector)),
+            Vector::Sparse(vector) => Some(Self::Sparse(&vector.values)),
+            Vector::MultiDense(_) | Vector::Inference(_) => None,
+        }
+    }
+
+    fn from_struct(vector: &'a VectorStruct, name: &str) -> Option<Self> {
+        match vector {
+            VectorStruct::Single(vector) if name == DEFAULT_VECTOR_NAME => {
+                Some(Self::Dense(vector))
+            }
+            VectorStruct::Named(vectors) => Self::from_vector(vectors.get(name)?),
+            _ => None,
+        }
+    }
+
+    fn from_batch(vectors: &'a BatchVectorStruct, name: &str, index: usize) -> Option<Self> {
+        match vectors {
+            BatchVectorStruct::Single(vectors) if name == DEFAULT_VECTOR_NAME => {
+                vectors.get(index).map(|vector| Self::Dense(vector))
+            }
+            BatchVectorStruct::Named(vectors) => Self::from_vector(vectors.get(name)?.get(index)?),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::operations::point_ops::PointStruct;
+
+    #[test]
+    fn test_materialize_computed_fields() {
+        let fields: Vec<ComputedField> = serde_json::from_value(json!([
+            {"key": "title_lower", "function": "lowercase", "source": "title"},
+            {"key": "words", "function": "token_count", "source": "description"},
+            {"key": "norm", "function": "vector_norm"},
+        ]))
+        .unwrap();
+
+        let payload: Payload = serde_json::from_value(json!({
+            "title": "Hello World",
+            "words": 100,
+        }))
+        .unwrap();
+        let mut operation =
+            CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                PointInsertOperationsInternal::PointsList(vec![PointStruct {
+                    id: 1.into(),
+                    vector: VectorStruct::Single(vec![3.0, 4.0]),
+                    payload: Some(payload),
+                }]),
+            ));
+        materialize_computed_fields(&fields, &mut operation);
+
+        let CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+            PointInsertOperationsInternal::PointsList(points),
+        )) = operation
+        else {
+            panic!("operation is changed");
+        };
+        let payload = points[0].payload.as_ref().unwrap();
+        assert_eq!(payload.0.get("title_lower"), Some(&json!("hello world")));
+        assert_eq!(payload.0.get("norm"), Some(&json!(5.0)));
+        // Source field is missing, the value is not kept
+        assert_eq!(payload.0.get("words"), None);
+    }
+}
@@ -2,13 +2,15 @@ use std::num::NonZeroU32;
 
 use merge::Merge;
 use schemars::JsonSchema;
-use segment::types::{BinaryQuantization, HnswConfig, ProductQuantization, ScalarQuantization};
+use segment::types::{
+    BinaryQuantization, HnswConfig, ProductQuantization, QuantizationConfig, ScalarQuantization,
+};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use validator::{Validate, ValidationErrors};
 
-use crate::config::{CollectionParams, WalConfig};
+use crate::config::{CollectionParams, StrictModeConfig, WalConfig};
 use crate::operations::types::CollectionResult;
 use crate::optimizers_builder::OptimizersConfig;
 
@@ -110,6 +112,10 @@ pub struct CollectionParamsDiff {
     /// Note: those payload values that are involved in filtering and are indexed - remain in RAM.
     #[serde(default)]
     pub on_disk_payload: Option<bool>,
+    /// If true - point's payload is stored in gridstore instead of RocksDB.
+    /// Existing segments are migrated to the new storage in background by optimizers.
+    #[serde(default)]
+    pub gridstore_payload: Option<bool>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Merge)]
@@ -162,6 +168,9 @@ pub struct OptimizersConfigDiff {
     /// If null - have no limit and choose dynamically to saturate CPU.
     /// If 0 - no optimization threads, optimizations will be disabled.
     pub max_optimization_threads: Option<usize>,
+    /// Defer building of vector indexes, for example, during a bulk upload.
+    /// Once disabled, all deferred segments are indexed in the background.
+    pub defer_indexing: Option<bool>,
 }
 
 impl std::hash::Hash for OptimizersConfigDiff {
@@ -174,6 +183,7 @@ impl std::hash::Hash for OptimizersConfigDiff {
         self.indexing_threshold.hash(state);
         self.flush_interval_sec.hash(state);
         self.max_optimization_threads.hash(state);
+        self.defer_indexing.hash(state);
     }
 }
 
@@ -188,6 +198,7 @@ impl PartialEq for OptimizersConfigDiff {
             && self.indexing_threshold == other.indexing_threshold
             && self.flush_interval_sec == other.flush_interval_sec
             && self.max_optimization_threads == other.max_optimization_threads
+            && self.defer_indexing == other.defer_indexing
     }
 }
 
@@ -203,6 +214,8 @@ impl DiffConfig<WalConfig> for WalConfigDiff {}
 
 impl DiffConfig<CollectionParams> for CollectionParamsDiff {}
 
+impl DiffConfig<StrictModeConfig> for StrictModeConfig {}
+
 impl From<HnswConfig> for HnswConfigDiff {
     fn from(config: HnswConfig) -> Self {
         HnswConfigDiff::from_full(&config).unwrap()
@@ -313,6 +326,26 @@ impl QuantizationConfigDiff {
     pub fn new_disabled() -> Self {
         QuantizationConfigDiff::Disabled(Disabled::Disabled)
     }
+
+    /// Quantization config, which is set by this diff. `None` if quantization is disabled.
+    pub fn into_config(self) -> Option<QuantizationConfig> {
+        match self {
+            QuantizationConfigDiff::Scalar(scalar) => Some(QuantizationConfig::Scalar(scalar)),
+            QuantizationConfigDiff::Product(product) => Some(QuantizationConfig::Product(product)),
+            QuantizationConfigDiff::Binary(binary) => Some(QuantizationConfig::Binary(binary)),
+            QuantizationConfigDiff::Disabled(_) => None,
+        }
+    }
+}
+
+impl From<QuantizationConfig> for QuantizationConfigDiff {
+    fn from(config: QuantizationConfig) -> Self {
+        match config {
+            QuantizationConfig::Scalar(scalar) => QuantizationConfigDiff::Scalar(scalar),
+            QuantizationConfig::Product(product) => QuantizationConfigDiff::Product(product),
+            QuantizationConfig::Binary(binary) => QuantizationConfigDiff::Binary(binary),
+        }
+    }
 }
 
 impl Validate for QuantizationConfigDiff {
@@ -348,6 +381,7 @@ mod tests {
             write_consistency_factor: Some(NonZeroU32::new(2).unwrap()),
             read_fan_out_factor: None,
             on_disk_payload: None,
+            gridstore_payload: None,
         };
 
         let new_params = diff.update(&params).unwrap();
@@ -376,11 +410,24 @@ mod tests {
             indexing_threshold: Some(50_000),
             flush_interval_sec: 30,
             max_optimization_threads: Some(1),
+            defer_indexing: false,
         };
         let update: OptimizersConfigDiff =
             serde_json::from_str(r#"{ "indexing_threshold": 10000 }"#).unwrap();
         let new_config = update.update(&base_config).unwrap();
-        assert_eq!(new_config.indexing_threshold, Some(10000))
+        assert_eq!(new_config.indexing_threshold, Some(10000));
+
+        let update: OptimizersConfigDiff =
+            serde_json::from_str(r#"{ "defer_indexing": true }"#).unwrap();
+        let deferred_config = update.update(&new_config).unwrap();
+        assert!(deferred_config.defer_indexing);
+        assert_eq!(
+            deferred_config
+                .optimizer_thresholds(1)
+                .indexing_threshold_kb,
+            usize::MAX
+        );
+        assert_eq!(deferred_config.indexing_threshold_kb(), 10000);
     }
 
     #[test]
@@ -0,0 +1,187 @@
+use schemars::JsonSchema;
+use segment::types::{
+    CompressionRatio, Indexes, QuantizationConfig, VectorDataConfig, VectorStorageDatatype,
+};
+use serde::Serialize;
+
+use crate::config::CollectionConfig;
+use crate::shards::shard::ShardId;
+
+/// Expected effect of a collection config change, without applying it
+///
+/// Only shards, located on the peer which handles the request, are estimated.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ConfigChangeImpact {
+    /// Collection config, as it would be after the change
+    pub config: CollectionConfig,
+    /// Number of segments, which optimizers would rebuild to match the new config
+    pub segments_to_rebuild: usize,
+    /// Number of points in the segments to rebuild
+    pub points_to_rebuild: usize,
+    /// Expected change of the disk usage in bytes, after all segments are rebuilt
+    pub disk_delta_bytes: i64,
+    /// Expected change of the RAM usage in bytes, after all segments are rebuilt
+    pub ram_delta_bytes: i64,
+    /// Expected time of the rebuild, based on the speed of recent optimizations.
+    /// Not set, if there were no finished optimizations to measure the speed by.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_reindex_time_sec: Option<f64>,
+    /// Estimation of each local shard
+    pub shards: Vec<ShardConfigChangeImpact>,
+}
+
+impl ConfigChangeImpact {
+    pub fn new(config: CollectionConfig, shards: Vec<ShardConfigChangeImpact>) -> Self {
+        let estimated_reindex_time_sec = shards
+            .iter()
+            .filter(|shard| shard.points_to_rebuild > 0)
+            .map(|shard| shard.estimated_reindex_time_sec)
+            .sum();
+        Self {
+            config,
+            segments_to_rebuild: shards.iter().map(|shard| shard.segments_to_rebuild).sum(),
+            points_to_rebuild: shards.iter().map(|shard| shard.points_to_rebuild).sum(),
+            disk_delta_bytes: shards.iter().map(|shard| shard.disk_delta_bytes).sum(),
+            ram_delta_bytes: shards.iter().map(|shard| shard.ram_delta_bytes).sum(),
+            estimated_reindex_time_sec,
+            shards,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, JsonSchema)]
+pub struct ShardConfigChangeImpact {
+    pub shard_id: ShardId,
+    pub total_segments: usize,
+    pub segments_to_rebuild: usize,
+    pub points_to_rebuild: usize,
+    pub disk_delta_bytes: i64,
+    pub ram_delta_bytes: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_reindex_time_sec: Option<f64>,
+}
+
+/// Rough size of the dense vector data of a segment: vectors, HNSW links and quantized vectors
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct VectorFootprint {
+    pub disk_bytes: u64,
+    pub ram_bytes: u64,
+}
+
+impl VectorFootprint {
+    pub fn estimate(config: &VectorDataConfig, num_vectors: usize) -> Self {
+        let num_vectors = num_vectors as u64;
+        let dim = config.size as u64;
+        let mut footprint = Self::default();
+
+        let element_size = match config.datatype.unwrap_or_default() {
+            VectorStorageDatatype::Float32 => 4,
+            VectorStorageDatatype::Float16 => 2,
+            VectorStorageDatatype::Uint8 => 1,
+        };
+        let vectors_bytes = num_vectors * dim * element_size;
+        footprint.disk_bytes += vectors_bytes;
+        if !config.storage_type.is_on_disk() {
+            footprint.ram_bytes += vectors_bytes;
+        }
+
+        if let Indexes::Hnsw(hnsw_config) = &config.index {
+            // Links of the level 0 take the most of the graph
+            let links_bytes = num_vectors * 2 * hnsw_config.m as u64 * 4;
+            footprint.disk_bytes += links_bytes;
+            if !hnsw_config.on_disk.unwrap_or(false) {
+                footprint.ram_bytes += links_bytes;
+            }
+        }
+
+        if let Some(quantization_config) = &config.quantization_config {
+            let (vector_bytes, always_ram) = match quantization_config {
+                QuantizationConfig::Scalar(scalar) => (dim, scalar.scalar.always_ram),
+                QuantizationConfig::Product(product) => {
+                    let ratio = match product.product.compression {
+                        CompressionRatio::X4 => 4,
+                        CompressionRatio::X8 => 8,
+                        CompressionRatio::X16 => 16,
+                        CompressionRatio::X32 => 32,
+                        CompressionRatio::X64 => 64,
+                    };
+                    ((dim * 4).div_ceil(ratio), product.product.always_ram)
+                }
+                QuantizationConfig::Binary(binary) => (dim.div_ceil(8), binary.binary.always_ram),
+            };
+            let quantized_bytes = num_vectors * vector_bytes;
+            footprint.disk_bytes += quantized_bytes;
+            if always_ram == Some(true) {
+                footprint.ram_bytes += quantized_bytes;
+            }
+        }
+
+        footprint
+    }
+
+    /// Change of the footprint from `self` to `other`
+    pub fn delta(&self, other: &Self) -> (i64, i64) {
+        (
+            other.disk_bytes as i64 - self.disk_bytes as i64,
+            other.ram_bytes as i64 - self.ram_bytes as i64,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::types::{
+        BinaryQuantization, BinaryQuantizationConfig, Distance, HnswConfig, VectorStorageType,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_vector_footprint() {
+        let plain = VectorDataConfig {
+            size: 128,
+            distance: Distance::Cosine,
+            storage_type: VectorStorageType::Memory,
+            index: Indexes::Plain {},
+            quantization_config: None,
+            multivec_config: None,
+            datatype: None,
+        };
+        let footprint = VectorFootprint::estimate(&plain, 1000);
+        assert_eq!(footprint.disk_bytes, 1000 * 128 * 4);
+        assert_eq!(footprint.ram_bytes, 1000 * 128 * 4);
+
+        let indexed = VectorDataConfig {
+            storage_type: VectorStorageType::Mmap,
+            index: Indexes::Hnsw(HnswConfig {
+                m: 16,
+                ef_construct: 100,
+                full_scan_threshold: 10_000,
+                max_indexing_threads: 0,
+                on_disk: None,
+                payload_m: None,
+            }),
+            quantization_config: Some(QuantizationConfig::Binary(BinaryQuantization {
+                binary: BinaryQuantizationConfig {
+                    always_ram: Some(true),
+                },
+            })),
+            ..plain
+        };
+        let indexed_footprint = VectorFootprint::estimate(&indexed, 1000);
+        let links_bytes = 1000 * 32 * 4;
+        let quantized_bytes = 1000 * 16;
+        assert_eq!(
+            indexed_footprint.disk_bytes,
+            1000 * 128 * 4 + links_bytes + quantized_bytes,
+        );
+        assert_eq!(indexed_footprint.ram_bytes, links_bytes + quantized_bytes);
+        assert_eq!(
+            footprint.delta(&indexed_footprint),
+            (
+                (links_bytes + quantized_bytes) as i64,
+                (links_bytes + quantized_bytes) as i64 - 1000 * 128 * 4,
+            ),
+        );
+    }
+}
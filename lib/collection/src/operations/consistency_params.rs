@@ -8,6 +8,8 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use validator::{Validate, ValidationError as ValidatorError, ValidationErrors};
 
+use crate::operations::types::{CollectionError, CollectionResult};
+
 /// Read consistency parameter
 ///
 /// Defines how many replicas should be queried to get the result
@@ -20,6 +22,10 @@ use validator::{Validate, ValidationError as ValidatorError, ValidationErrors};
 ///
 /// * `all` - send requests to all nodes and return points which present on all of them
 ///
+/// * `partial` - send request to one available replica of each shard, and return points of the
+///   shards, which responded. Status of each shard is reported with the result. Only supported
+///   by search.
+///
 /// Default value is `Factor(1)`
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
 #[serde(untagged)]
@@ -54,6 +60,22 @@ impl Default for ReadConsistency {
 }
 
 impl ReadConsistency {
+    /// Whether results of the shards, which are available, are returned, even if some shards fail
+    pub fn is_partial(&self) -> bool {
+        matches!(self, ReadConsistency::Type(ReadConsistencyType::Partial))
+    }
+
+    /// Reject `partial` consistency for reads, which can't report the status of each shard.
+    /// Only searches respond with results of the shards, which are available.
+    pub fn check_not_partial(consistency: Option<Self>, operation: &str) -> CollectionResult<()> {
+        if consistency.is_some_and(|consistency| consistency.is_partial()) {
+            return Err(CollectionError::bad_input(format!(
+                "Read consistency `partial` is only supported by search, not by {operation}",
+            )));
+        }
+        Ok(())
+    }
+
     pub fn try_from_optional(
         consistency: Option<ReadConsistencyGrpc>,
     ) -> Result<Option<Self>, tonic::Status> {
@@ -145,6 +167,9 @@ where
 /// * `quorum` - send requests to all nodes and return points which present on majority of nodes
 ///
 /// * `all` - send requests to all nodes and return points which present on all nodes
+///
+/// * `partial` - send request to one available replica of each shard, skip shards, which are not
+///   available, instead of failing the request. Only supported by search.
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Copy, Clone, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum ReadConsistencyType {
@@ -154,6 +179,8 @@ pub enum ReadConsistencyType {
     Quorum,
     // send requests to all nodes and return points which present on all nodes
     All,
+    // send request to one available replica of each shard, skip unavailable shards
+    Partial,
 }
 
 impl TryFrom<i32> for ReadConsistencyType {
@@ -176,6 +203,7 @@ impl From<ReadConsistencyTypeGrpc> for ReadConsistencyType {
             ReadConsistencyTypeGrpc::Majority => Self::Majority,
             ReadConsistencyTypeGrpc::Quorum => Self::Quorum,
             ReadConsistencyTypeGrpc::All => Self::All,
+            ReadConsistencyTypeGrpc::Partial => Self::Partial,
         }
     }
 }
@@ -192,6 +220,7 @@ impl From<ReadConsistencyType> for ReadConsistencyTypeGrpc {
             ReadConsistencyType::Majority => ReadConsistencyTypeGrpc::Majority,
             ReadConsistencyType::Quorum => ReadConsistencyTypeGrpc::Quorum,
             ReadConsistencyType::All => ReadConsistencyTypeGrpc::All,
+            ReadConsistencyType::Partial => ReadConsistencyTypeGrpc::Partial,
         }
     }
 }
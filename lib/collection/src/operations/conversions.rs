@@ -3,8 +3,9 @@ use std::num::{NonZeroU32, NonZeroU64};
 use std::time::Duration;
 
 use api::grpc::conversions::{
-    convert_shard_key_from_grpc, convert_shard_key_from_grpc_opt, convert_shard_key_to_grpc,
-    from_grpc_dist, json_path_from_proto, payload_to_proto, proto_to_payloads,
+    attribute_columns_from_proto, attribute_columns_to_proto, convert_shard_key_from_grpc,
+    convert_shard_key_from_grpc_opt, convert_shard_key_to_grpc, from_grpc_dist,
+    json_path_from_proto, payload_to_proto, proto_to_payloads,
 };
 use api::grpc::qdrant::quantization_config_diff::Quantization;
 use api::grpc::qdrant::update_collection_cluster_setup_request::{
@@ -15,11 +16,17 @@ use api::rest::schema::ShardKeySelector;
 use api::rest::BaseGroupRequest;
 use common::types::ScoreType;
 use itertools::Itertools;
+use segment::common::hyperloglog::HyperLogLog;
+use segment::data_types::aggregation::{
+    DistinctValues, HistogramBucket, NumericStats, ValueDistribution, ValueRange,
+};
 use segment::data_types::vectors::{
     BatchVectorStructInternal, Named, NamedQuery, NamedVectorStruct, Vector, VectorStructInternal,
     DEFAULT_VECTOR_NAME,
 };
-use segment::types::{Distance, MultiVectorConfig, QuantizationConfig, ScoredPoint};
+use segment::types::{
+    Distance, MultiVectorConfig, PayloadCompression, QuantizationConfig, ScoredPoint,
+};
 use segment::vector_storage::query::{ContextPair, ContextQuery, DiscoveryQuery, RecoQuery};
 use sparse::common::sparse_vector::{validate_sparse_vector_impl, SparseVector};
 use tonic::Status;
@@ -32,7 +39,8 @@ use super::types::{
 };
 use crate::config::{
     default_replication_factor, default_write_consistency_factor, CollectionConfig,
-    CollectionParams, ShardingMethod, WalConfig,
+    CollectionParams, PayloadFieldConstraints, PayloadSchemaConfig, ShardingMethod,
+    StrictModeConfig, WalConfig,
 };
 use crate::lookup::types::WithLookupInterface;
 use crate::lookup::WithLookup;
@@ -42,6 +50,7 @@ use crate::operations::cluster_ops::{
     MoveShard, MoveShardOperation, Replica, ReplicateShard, ReplicateShardOperation,
     RestartTransfer, RestartTransferOperation,
 };
+use crate::operations::computed_fields::{ComputedField, ComputedFunction};
 use crate::operations::config_diff::{
     CollectionParamsDiff, HnswConfigDiff, OptimizersConfigDiff, QuantizationConfigDiff,
     WalConfigDiff,
@@ -49,15 +58,18 @@ use crate::operations::config_diff::{
 use crate::operations::point_ops::PointsSelector::PointIdsSelector;
 use crate::operations::point_ops::{
     Batch, FilterSelector, PointIdsList, PointStruct, PointsSelector, WriteOrdering,
+    WritePrecondition,
 };
 use crate::operations::query_enum::QueryEnum;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::{
-    AliasDescription, CollectionClusterInfo, CollectionInfo, CollectionStatus, CountResult,
-    LocalShardInfo, OptimizersStatus, RecommendRequestInternal, Record, RemoteShardInfo,
-    ShardTransferInfo, UpdateResult, UpdateStatus, VectorParams, VectorsConfig,
+    AliasDescription, CollectionClusterInfo, CollectionInfo, CollectionStatus, CountDistinctResult,
+    CountResult, FieldStatsResult, HistogramBuckets, LocalShardInfo, OptimizersStatus,
+    RecommendRequestInternal, Record, RemoteShardInfo, ShardTransferInfo, UpdateResult,
+    UpdateStatus, VectorParams, VectorsConfig, WriteConflict,
 };
 use crate::optimizers_builder::OptimizersConfig;
+use crate::shards::point_digests::{BucketDigest, PointDigests};
 use crate::shards::remote_shard::{CollectionCoreSearchRequest, CollectionSearchRequest};
 use crate::shards::replica_set::ReplicaState;
 use crate::shards::transfer::ShardTransferMethod;
@@ -198,7 +210,7 @@ pub fn try_discover_request_from_grpc(
         target,
         context: Some(context),
         filter: filter.map(|f| f.try_into()).transpose()?,
-        params: params.map(|p| p.into()),
+        params: params.map(|p| p.try_into()).transpose()?,
         limit: limit as usize,
         offset: offset.map(|x| x as usize),
         with_payload: with_payload.map(|wp| wp.try_into()).transpose()?,
@@ -250,6 +262,107 @@ impl From<HnswConfigDiff> for api::grpc::qdrant::HnswConfigDiff {
     }
 }
 
+impl TryFrom<api::grpc::qdrant::StrictModeConfig> for StrictModeConfig {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::StrictModeConfig) -> Result<Self, Self::Error> {
+        Ok(Self {
+            enabled: value.enabled,
+            max_query_limit: value.max_query_limit.map(|v| v as usize),
+            search_max_hnsw_ef: value.search_max_hnsw_ef.map(|v| v as usize),
+            search_allow_exact: value.search_allow_exact,
+            unindexed_filtering_retrieve: value.unindexed_filtering_retrieve,
+            unindexed_filtering_update: value.unindexed_filtering_update,
+            max_payload_size_bytes: value.max_payload_size_bytes.map(|v| v as usize),
+            payload_schema: value.payload_schema.map(TryInto::try_into).transpose()?,
+            max_points_count: value.max_points_count.map(|v| v as usize),
+            max_ram_usage_bytes: value.max_ram_usage_bytes.map(|v| v as usize),
+            max_disk_usage_bytes: value.max_disk_usage_bytes.map(|v| v as usize),
+        })
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::PayloadSchemaConfig> for PayloadSchemaConfig {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::PayloadSchemaConfig) -> Result<Self, Self::Error> {
+        let fields = value
+            .fields
+            .into_iter()
+            .map(|(key, field)| Ok((key, field.try_into()?)))
+            .collect::<Result<_, Status>>()?;
+        Ok(Self {
+            fields,
+            allow_unknown_fields: value.allow_unknown_fields,
+        })
+    }
+}
+
+impl From<PayloadSchemaConfig> for api::grpc::qdrant::PayloadSchemaConfig {
+    fn from(value: PayloadSchemaConfig) -> Self {
+        Self {
+            fields: value
+                .fields
+                .into_iter()
+                .map(|(key, field)| (key, field.into()))
+                .collect(),
+            allow_unknown_fields: value.allow_unknown_fields,
+        }
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::PayloadFieldConstraints> for PayloadFieldConstraints {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::PayloadFieldConstraints) -> Result<Self, Self::Error> {
+        let value_type = value
+            .r#type
+            .map(|value_type| {
+                api::grpc::qdrant::PayloadSchemaType::from_i32(value_type)
+                    .ok_or_else(|| Status::invalid_argument("Malformed payload schema type"))?
+                    .try_into()
+            })
+            .transpose()?;
+        Ok(Self {
+            value_type,
+            required: value.required,
+            max_length: value.max_length.map(|v| v as usize),
+            max_array_length: value.max_array_length.map(|v| v as usize),
+        })
+    }
+}
+
+impl From<PayloadFieldConstraints> for api::grpc::qdrant::PayloadFieldConstraints {
+    fn from(value: PayloadFieldConstraints) -> Self {
+        Self {
+            r#type: value
+                .value_type
+                .map(|value_type| api::grpc::qdrant::PayloadSchemaType::from(value_type) as i32),
+            required: value.required,
+            max_length: value.max_length.map(|v| v as u64),
+            max_array_length: value.max_array_length.map(|v| v as u64),
+        }
+    }
+}
+
+impl From<StrictModeConfig> for api::grpc::qdrant::StrictModeConfig {
+    fn from(value: StrictModeConfig) -> Self {
+        Self {
+            enabled: value.enabled,
+            max_query_limit: value.max_query_limit.map(|v| v as u64),
+            search_max_hnsw_ef: value.search_max_hnsw_ef.map(|v| v as u64),
+            search_allow_exact: value.search_allow_exact,
+            unindexed_filtering_retrieve: value.unindexed_filtering_retrieve,
+            unindexed_filtering_update: value.unindexed_filtering_update,
+            max_payload_size_bytes: value.max_payload_size_bytes.map(|v| v as u64),
+            payload_schema: value.payload_schema.map(Into::into),
+            max_points_count: value.max_points_count.map(|v| v as u64),
+            max_ram_usage_bytes: value.max_ram_usage_bytes.map(|v| v as u64),
+            max_disk_usage_bytes: value.max_disk_usage_bytes.map(|v| v as u64),
+        }
+    }
+}
+
 impl From<api::grpc::qdrant::WalConfigDiff> for WalConfigDiff {
     fn from(value: api::grpc::qdrant::WalConfigDiff) -> Self {
         Self {
@@ -281,6 +394,7 @@ impl TryFrom<api::grpc::qdrant::CollectionParamsDiff> for CollectionParamsDiff {
                 .transpose()?,
             read_fan_out_factor: value.read_fan_out_factor,
             on_disk_payload: value.on_disk_payload,
+            gridstore_payload: value.gridstore_payload,
         })
     }
 }
@@ -296,6 +410,7 @@ impl From<api::grpc::qdrant::OptimizersConfigDiff> for OptimizersConfigDiff {
             indexing_threshold: value.indexing_threshold.map(|v| v as usize),
             flush_interval_sec: value.flush_interval_sec,
             max_optimization_threads: value.max_optimization_threads.map(|v| v as usize),
+            defer_indexing: value.defer_indexing,
         }
     }
 }
@@ -379,6 +494,32 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
                     shard_number: config.params.shard_number.get(),
                     replication_factor: Some(config.params.replication_factor.get()),
                     on_disk_payload: config.params.on_disk_payload,
+                    gridstore_payload: config.params.gridstore_payload,
+                    payload_columns: config
+                        .params
+                        .payload_columns
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect(),
+                    payload_compression_level: config
+                        .params
+                        .payload_compression
+                        .map(|compression| compression.level),
+                    point_history_versions: config
+                        .params
+                        .point_history_versions
+                        .map(NonZeroU32::get),
+                    soft_delete_retention_sec: config
+                        .params
+                        .soft_delete_retention_sec
+                        .map(NonZeroU64::get),
+                    computed_fields: config
+                        .params
+                        .computed_fields
+                        .into_iter()
+                        .map(api::grpc::qdrant::ComputedField::from)
+                        .collect(),
+                    attribute_columns: attribute_columns_to_proto(config.params.attribute_columns),
                     write_consistency_factor: Some(config.params.write_consistency_factor.get()),
                     read_fan_out_factor: config.params.read_fan_out_factor,
                     sharding_method: config.params.sharding_method.map(sharding_method_to_proto),
@@ -420,12 +561,14 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
                         .optimizer_config
                         .max_optimization_threads
                         .map(|n| n as u64),
+                    defer_indexing: Some(config.optimizer_config.defer_indexing),
                 }),
                 wal_config: Some(api::grpc::qdrant::WalConfigDiff {
                     wal_capacity_mb: Some(config.wal_config.wal_capacity_mb as u64),
                     wal_segments_ahead: Some(config.wal_config.wal_segments_ahead as u64),
                 }),
                 quantization_config: config.quantization_config.map(|x| x.into()),
+                strict_mode_config: config.strict_mode_config.map(Into::into),
             }),
             payload_schema: payload_schema
                 .into_iter()
@@ -477,6 +620,7 @@ impl From<api::grpc::qdrant::OptimizersConfigDiff> for OptimizersConfig {
             max_optimization_threads: optimizer_config
                 .max_optimization_threads
                 .map(|n| n as usize),
+            defer_indexing: optimizer_config.defer_indexing.unwrap_or_default(),
         }
     }
 }
@@ -551,6 +695,16 @@ impl TryFrom<api::grpc::qdrant::VectorParams> for VectorParams {
                 .multivector_config
                 .map(MultiVectorConfig::try_from)
                 .transpose()?,
+            input_size: vector_params
+                .input_size
+                .map(|input_size| {
+                    NonZeroU64::new(input_size).ok_or_else(|| {
+                        Status::invalid_argument(
+                            "VectorParams input_size must be greater than zero",
+                        )
+                    })
+                })
+                .transpose()?,
         })
     }
 }
@@ -621,6 +775,7 @@ impl TryFrom<api::grpc::qdrant::SparseVectorParams> for SparseVectorParams {
                 .modifier
                 .and_then(api::grpc::qdrant::Modifier::from_i32)
                 .map(Modifier::from),
+            vocabulary: sparse_vector_params.vocabulary,
         })
     }
 }
@@ -649,10 +804,58 @@ impl From<SparseVectorParams> for api::grpc::qdrant::SparseVectorParams {
             modifier: sparse_vector_params
                 .modifier
                 .map(|modifier| api::grpc::qdrant::Modifier::from(modifier) as i32),
+            vocabulary: sparse_vector_params.vocabulary,
+        }
+    }
+}
+
+impl From<ComputedField> for api::grpc::qdrant::ComputedField {
+    fn from(value: ComputedField) -> Self {
+        let ComputedField {
+            key,
+            function,
+            source,
+        } = value;
+        let function = match function {
+            ComputedFunction::Lowercase => api::grpc::qdrant::ComputedFunction::Lowercase,
+            ComputedFunction::TokenCount => api::grpc::qdrant::ComputedFunction::TokenCount,
+            ComputedFunction::VectorNorm => api::grpc::qdrant::ComputedFunction::VectorNorm,
+        };
+        Self {
+            key,
+            function: function as i32,
+            source,
         }
     }
 }
 
+impl TryFrom<api::grpc::qdrant::ComputedField> for ComputedField {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::ComputedField) -> Result<Self, Self::Error> {
+        let api::grpc::qdrant::ComputedField {
+            key,
+            function,
+            source,
+        } = value;
+        let function = match api::grpc::qdrant::ComputedFunction::from_i32(function) {
+            Some(api::grpc::qdrant::ComputedFunction::Lowercase) => ComputedFunction::Lowercase,
+            Some(api::grpc::qdrant::ComputedFunction::TokenCount) => ComputedFunction::TokenCount,
+            Some(api::grpc::qdrant::ComputedFunction::VectorNorm) => ComputedFunction::VectorNorm,
+            None => {
+                return Err(Status::invalid_argument(format!(
+                    "Unknown computed function: {function}"
+                )))
+            }
+        };
+        Ok(Self {
+            key,
+            function,
+            source,
+        })
+    }
+}
+
 fn grpc_to_segment_quantization_config(
     value: api::grpc::qdrant::QuantizationConfig,
 ) -> Result<QuantizationConfig, Status> {
@@ -713,6 +916,25 @@ impl TryFrom<api::grpc::qdrant::CollectionConfig> for CollectionConfig {
                     shard_number: NonZeroU32::new(params.shard_number)
                         .ok_or_else(|| Status::invalid_argument("`shard_number` cannot be zero"))?,
                     on_disk_payload: params.on_disk_payload,
+                    gridstore_payload: params.gridstore_payload,
+                    payload_columns: params
+                        .payload_columns
+                        .iter()
+                        .map(|key| json_path_from_proto(key))
+                        .collect::<Result<_, _>>()?,
+                    payload_compression: params
+                        .payload_compression_level
+                        .map(|level| PayloadCompression { level }),
+                    point_history_versions: params.point_history_versions.and_then(NonZeroU32::new),
+                    soft_delete_retention_sec: params
+                        .soft_delete_retention_sec
+                        .and_then(NonZeroU64::new),
+                    computed_fields: params
+                        .computed_fields
+                        .into_iter()
+                        .map(ComputedField::try_from)
+                        .collect::<Result<_, _>>()?,
+                    attribute_columns: attribute_columns_from_proto(params.attribute_columns)?,
                     replication_factor: NonZeroU32::new(
                         params
                             .replication_factor
@@ -756,6 +978,10 @@ impl TryFrom<api::grpc::qdrant::CollectionConfig> for CollectionConfig {
                     None
                 }
             },
+            strict_mode_config: config
+                .strict_mode_config
+                .map(TryInto::try_into)
+                .transpose()?,
         })
     }
 }
@@ -904,6 +1130,7 @@ pub fn try_points_selector_from_grpc(
                     .map(|p| p.try_into())
                     .collect::<Result<_, _>>()?,
                 shard_key: shard_key_selector.map(ShardKeySelector::from),
+                precondition: None,
             }))
         }
         Some(api::grpc::qdrant::points_selector::PointsSelectorOneOf::Filter(f)) => {
@@ -922,6 +1149,7 @@ impl From<UpdateResult> for api::grpc::qdrant::UpdateResultInternal {
             operation_id: res.operation_id,
             status: res.status.into(),
             clock_tag: res.clock_tag.map(Into::into),
+            conflicts: res.conflicts.into_iter().map(Into::into).collect(),
         }
     }
 }
@@ -940,6 +1168,11 @@ impl TryFrom<api::grpc::qdrant::UpdateResultInternal> for UpdateResult {
             operation_id: res.operation_id,
             status: res.status.try_into()?,
             clock_tag: res.clock_tag.map(Into::into),
+            conflicts: res
+                .conflicts
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
         };
 
         Ok(res)
@@ -954,6 +1187,49 @@ impl TryFrom<api::grpc::qdrant::UpdateResult> for UpdateResult {
     }
 }
 
+impl From<WriteConflict> for api::grpc::qdrant::WriteConflict {
+    fn from(value: WriteConflict) -> Self {
+        Self {
+            id: Some(value.id.into()),
+            current_version: value.current_version,
+        }
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::WriteConflict> for WriteConflict {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::WriteConflict) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: value
+                .id
+                .ok_or_else(|| Status::invalid_argument("Malformed WriteConflict: missing id"))?
+                .try_into()?,
+            current_version: value.current_version,
+        })
+    }
+}
+
+impl From<WritePrecondition> for api::grpc::qdrant::WritePrecondition {
+    fn from(value: WritePrecondition) -> Self {
+        Self {
+            version: value.version,
+            filter: value.filter.map(Into::into),
+        }
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::WritePrecondition> for WritePrecondition {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::WritePrecondition) -> Result<Self, Self::Error> {
+        Ok(Self {
+            version: value.version,
+            filter: value.filter.map(TryInto::try_into).transpose()?,
+        })
+    }
+}
+
 impl From<UpdateStatus> for i32 {
     fn from(status: UpdateStatus) -> Self {
         match status {
@@ -991,6 +1267,7 @@ impl From<api::grpc::qdrant::CountResult> for CountResult {
     fn from(value: api::grpc::qdrant::CountResult) -> Self {
         Self {
             count: value.count as usize,
+            consistent: value.consistent,
         }
     }
 }
@@ -999,10 +1276,230 @@ impl From<CountResult> for api::grpc::qdrant::CountResult {
     fn from(value: CountResult) -> Self {
         Self {
             count: value.count as u64,
+            consistent: value.consistent,
         }
     }
 }
 
+impl From<CountDistinctResult> for api::grpc::qdrant::CountDistinctResult {
+    fn from(value: CountDistinctResult) -> Self {
+        Self {
+            count: value.count as u64,
+            exact: value.exact,
+            consistent: value.consistent,
+        }
+    }
+}
+
+impl From<DistinctValues> for api::grpc::qdrant::DistinctValuesInternal {
+    fn from(value: DistinctValues) -> Self {
+        let values = match value {
+            DistinctValues::Exact(hashes) => {
+                api::grpc::qdrant::distinct_values_internal::Values::Exact(
+                    api::grpc::qdrant::ExactDistinctValuesInternal {
+                        hashes: hashes.into_iter().collect(),
+                    },
+                )
+            }
+            DistinctValues::Estimated(sketch) => {
+                api::grpc::qdrant::distinct_values_internal::Values::Estimated(
+                    sketch.registers().to_vec(),
+                )
+            }
+        };
+        Self {
+            values: Some(values),
+        }
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::DistinctValuesInternal> for DistinctValues {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::DistinctValuesInternal) -> Result<Self, Self::Error> {
+        use api::grpc::qdrant::distinct_values_internal::Values;
+
+        match value.values {
+            Some(Values::Exact(exact)) => {
+                Ok(DistinctValues::Exact(exact.hashes.into_iter().collect()))
+            }
+            Some(Values::Estimated(registers)) => HyperLogLog::from_registers(registers)
+                .map(DistinctValues::Estimated)
+                .ok_or_else(|| Status::invalid_argument("Malformed sketch of distinct values")),
+            None => Err(Status::invalid_argument("Distinct values are missing")),
+        }
+    }
+}
+
+impl From<HistogramBuckets> for api::grpc::qdrant::histogram_points::Buckets {
+    fn from(value: HistogramBuckets) -> Self {
+        match value {
+            HistogramBuckets::Interval(interval) => Self::Interval(interval),
+            HistogramBuckets::Quantiles(quantiles) => Self::Quantiles(quantiles as u64),
+        }
+    }
+}
+
+impl From<api::grpc::qdrant::histogram_points::Buckets> for HistogramBuckets {
+    fn from(value: api::grpc::qdrant::histogram_points::Buckets) -> Self {
+        use api::grpc::qdrant::histogram_points::Buckets;
+
+        match value {
+            Buckets::Interval(interval) => HistogramBuckets::Interval(interval),
+            Buckets::Quantiles(quantiles) => HistogramBuckets::Quantiles(quantiles as usize),
+        }
+    }
+}
+
+impl From<HistogramBucket> for api::grpc::qdrant::HistogramBucket {
+    fn from(value: HistogramBucket) -> Self {
+        Self {
+            from: value.from,
+            to: value.to,
+            count: value.count as u64,
+        }
+    }
+}
+
+impl From<ValueDistribution> for api::grpc::qdrant::ValueDistributionInternal {
+    fn from(value: ValueDistribution) -> Self {
+        let ranges = value
+            .ranges()
+            .iter()
+            .map(|range| api::grpc::qdrant::ValueRangeInternal {
+                from: range.from,
+                to: range.to,
+                count: range.count as u64,
+            })
+            .collect();
+        Self { ranges }
+    }
+}
+
+impl From<api::grpc::qdrant::ValueDistributionInternal> for ValueDistribution {
+    fn from(value: api::grpc::qdrant::ValueDistributionInternal) -> Self {
+        let ranges = value
+            .ranges
+            .into_iter()
+            .map(|range| ValueRange {
+                from: range.from,
+                to: range.to,
+                count: range.count as usize,
+            })
+            .collect();
+        ValueDistribution::new(ranges)
+    }
+}
+
+impl From<FieldStatsResult> for api::grpc::qdrant::FieldStatsResult {
+    fn from(value: FieldStatsResult) -> Self {
+        let FieldStatsResult {
+            count,
+            min,
+            max,
+            avg,
+            percentiles,
+        } = value;
+        Self {
+            count: count as u64,
+            min,
+            max,
+            avg,
+            percentiles: percentiles
+                .into_iter()
+                .map(|percentile| api::grpc::qdrant::PercentileValue {
+                    percentile: percentile.percentile,
+                    value: percentile.value,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<NumericStats> for api::grpc::qdrant::NumericStatsInternal {
+    fn from(value: NumericStats) -> Self {
+        let (count, sum, min, max, distribution) = value.into_parts();
+        Self {
+            count: count as u64,
+            sum,
+            min,
+            max,
+            distribution: Some(distribution.into()),
+        }
+    }
+}
+
+impl From<api::grpc::qdrant::NumericStatsInternal> for NumericStats {
+    fn from(value: api::grpc::qdrant::NumericStatsInternal) -> Self {
+        let api::grpc::qdrant::NumericStatsInternal {
+            count,
+            sum,
+            min,
+            max,
+            distribution,
+        } = value;
+        let distribution = distribution
+            .map(ValueDistribution::from)
+            .unwrap_or_default();
+        NumericStats::from_parts(count as usize, sum, min, max, distribution)
+    }
+}
+
+impl From<PointDigests> for api::grpc::qdrant::PointDigestsResponseInternal {
+    fn from(value: PointDigests) -> Self {
+        let PointDigests { buckets, points } = value;
+        Self {
+            buckets: buckets
+                .into_iter()
+                .map(|bucket| api::grpc::qdrant::BucketDigestInternal {
+                    count: bucket.count as u64,
+                    digest: bucket.digest,
+                })
+                .collect(),
+            points: points
+                .into_iter()
+                .map(|(id, version)| api::grpc::qdrant::PointVersionInternal {
+                    id: Some(id.into()),
+                    version,
+                })
+                .collect(),
+            time: 0.0,
+        }
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::PointDigestsResponseInternal> for PointDigests {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::PointDigestsResponseInternal) -> Result<Self, Status> {
+        let api::grpc::qdrant::PointDigestsResponseInternal {
+            buckets,
+            points,
+            time: _,
+        } = value;
+        let points = points
+            .into_iter()
+            .map(|point| {
+                let id = point
+                    .id
+                    .ok_or_else(|| Status::invalid_argument("Point id is missing"))?
+                    .try_into()?;
+                Ok((id, point.version))
+            })
+            .collect::<Result<_, Status>>()?;
+        Ok(Self {
+            buckets: buckets
+                .into_iter()
+                .map(|bucket| BucketDigest {
+                    count: bucket.count as usize,
+                    digest: bucket.digest,
+                })
+                .collect(),
+            points,
+        })
+    }
+}
+
 impl TryFrom<api::grpc::qdrant::SearchPoints> for CoreSearchRequest {
     type Error = Status;
     fn try_from(value: api::grpc::qdrant::SearchPoints) -> Result<Self, Self::Error> {
@@ -1035,7 +1532,7 @@ impl TryFrom<api::grpc::qdrant::SearchPoints> for CoreSearchRequest {
         Ok(Self {
             query: QueryEnum::Nearest(vector_struct),
             filter: filter.map(TryInto::try_into).transpose()?,
-            params: params.map(Into::into),
+            params: params.map(TryInto::try_into).transpose()?,
             limit: limit as usize,
             offset: offset.map(|v| v as usize).unwrap_or_default(),
             with_payload: with_payload.map(TryInto::try_into).transpose()?,
@@ -1284,7 +1781,7 @@ impl TryFrom<api::grpc::qdrant::CoreSearchPoints> for CoreSearchRequest {
         Ok(Self {
             query,
             filter: value.filter.map(|f| f.try_into()).transpose()?,
-            params: value.params.map(|p| p.into()),
+            params: value.params.map(|p| p.try_into()).transpose()?,
             limit: value.limit as usize,
             offset: value.offset.unwrap_or_default() as usize,
             with_payload: value.with_payload.map(|wp| wp.try_into()).transpose()?,
@@ -1450,7 +1947,7 @@ impl TryFrom<api::grpc::qdrant::RecommendPoints> for RecommendRequestInternal {
             negative,
             strategy: value.strategy.map(|s| s.try_into()).transpose()?,
             filter: value.filter.map(|f| f.try_into()).transpose()?,
-            params: value.params.map(|p| p.into()),
+            params: value.params.map(|p| p.try_into()).transpose()?,
             limit: value.limit as usize,
             offset: value.offset.map(|x| x as usize),
             with_payload: value.with_payload.map(|wp| wp.try_into()).transpose()?,
@@ -1556,6 +2053,7 @@ impl From<VectorParams> for api::grpc::qdrant::VectorParams {
             multivector_config: value
                 .multivec_config
                 .map(api::grpc::qdrant::MultiVectorConfig::from),
+            input_size: value.input_size.map(NonZeroU64::get),
         }
     }
 }
@@ -1572,9 +2070,14 @@ impl From<Datatype> for api::grpc::qdrant::Datatype {
 
 impl From<AliasDescription> for api::grpc::qdrant::AliasDescription {
     fn from(value: AliasDescription) -> Self {
+        let AliasDescription {
+            alias_name,
+            collection_name,
+            filter: _,
+        } = value;
         api::grpc::qdrant::AliasDescription {
-            alias_name: value.alias_name,
-            collection_name: value.collection_name,
+            alias_name,
+            collection_name,
         }
     }
 }
@@ -1586,6 +2089,7 @@ impl From<LocalShardInfo> for api::grpc::qdrant::LocalShardInfo {
             points_count: value.points_count as u64,
             state: value.state as i32,
             shard_key: value.shard_key.map(convert_shard_key_to_grpc),
+            quarantined_segments: value.quarantined_segments,
         }
     }
 }
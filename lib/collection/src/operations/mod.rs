@@ -1,5 +1,8 @@
+pub mod attribute_ops;
 pub mod cluster_ops;
+pub mod computed_fields;
 pub mod config_diff;
+pub mod config_impact;
 pub mod consistency_params;
 pub mod conversions;
 pub mod conversions_rest;
@@ -7,6 +10,7 @@ pub mod operation_effect;
 pub mod payload_ops;
 pub mod point_ops;
 pub mod query_enum;
+pub mod segment_details;
 pub mod shard_selector_internal;
 pub mod shared_storage_config;
 pub mod snapshot_ops;
@@ -16,6 +20,8 @@ pub mod universal_query;
 pub mod validation;
 pub mod vector_ops;
 pub mod vector_params_builder;
+pub mod vector_truncation;
+pub mod verification;
 
 use std::collections::HashMap;
 
@@ -54,6 +60,10 @@ pub struct OperationWithClockTag {
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub clock_tag: Option<ClockTag>,
+
+    /// Key of the client request, used to detect retries of the same update
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idempotency_key: Option<String>,
 }
 
 impl OperationWithClockTag {
@@ -64,8 +74,14 @@ impl OperationWithClockTag {
         Self {
             operation: operation.into(),
             clock_tag,
+            idempotency_key: None,
         }
     }
+
+    pub fn with_idempotency_key(mut self, idempotency_key: Option<String>) -> Self {
+        self.idempotency_key = idempotency_key;
+        self
+    }
 }
 
 impl From<CollectionUpdateOperations> for OperationWithClockTag {
@@ -143,6 +159,7 @@ pub enum CollectionUpdateOperations {
     VectorOperation(vector_ops::VectorOperations),
     PayloadOperation(payload_ops::PayloadOps),
     FieldIndexOperation(FieldIndexOperations),
+    AttributeOperation(attribute_ops::AttributeOps),
 }
 
 /// A mapping of operation to shard.
@@ -203,6 +220,7 @@ impl Validate for CollectionUpdateOperations {
             CollectionUpdateOperations::VectorOperation(operation) => operation.validate(),
             CollectionUpdateOperations::PayloadOperation(operation) => operation.validate(),
             CollectionUpdateOperations::FieldIndexOperation(operation) => operation.validate(),
+            CollectionUpdateOperations::AttributeOperation(operation) => operation.validate(),
         }
     }
 }
@@ -268,6 +286,9 @@ impl SplitByShard for CollectionUpdateOperations {
             operation @ CollectionUpdateOperations::FieldIndexOperation(_) => {
                 OperationToShard::to_all(operation)
             }
+            CollectionUpdateOperations::AttributeOperation(operation) => operation
+                .split_by_shard(ring)
+                .map(CollectionUpdateOperations::AttributeOperation),
         }
     }
 }
@@ -285,6 +306,9 @@ impl CollectionUpdateOperations {
             CollectionUpdateOperations::FieldIndexOperation(operation) => {
                 operation.is_write_operation()
             }
+            CollectionUpdateOperations::AttributeOperation(operation) => {
+                operation.is_write_operation()
+            }
         }
     }
 }
@@ -368,6 +392,7 @@ mod tests {
                 any::<vector_ops::VectorOperations>().prop_map(Self::VectorOperation),
                 any::<payload_ops::PayloadOps>().prop_map(Self::PayloadOperation),
                 any::<FieldIndexOperations>().prop_map(Self::FieldIndexOperation),
+                any::<attribute_ops::AttributeOps>().prop_map(Self::AttributeOperation),
             ]
             .boxed()
         }
@@ -379,8 +404,26 @@ mod tests {
 
         fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
             let upsert = Self::UpsertPoints(PointInsertOperationsInternal::PointsList(Vec::new()));
+            let upsert_conditional =
+                Self::UpsertPointsConditional(ConditionalInsertOperationInternal {
+                    points_op: PointInsertOperationsInternal::PointsList(Vec::new()),
+                    precondition: WritePrecondition {
+                        version: Some(0),
+                        filter: None,
+                    },
+                });
+
             let delete = Self::DeletePoints { ids: Vec::new() };
 
+            let delete_conditional =
+                Self::DeletePointsConditional(ConditionalDeleteOperationInternal {
+                    ids: Vec::new(),
+                    precondition: WritePrecondition {
+                        version: Some(0),
+                        filter: None,
+                    },
+                });
+
             let delete_by_filter = Self::DeletePointsByFilter(Filter {
                 should: None,
                 min_should: None,
@@ -396,7 +439,9 @@ mod tests {
 
             prop_oneof![
                 Just(upsert),
+                Just(upsert_conditional),
                 Just(delete),
+                Just(delete_conditional),
                 Just(delete_by_filter),
                 Just(sync),
             ]
@@ -415,6 +460,7 @@ mod tests {
                 PointIdsList {
                     points: Vec::new(),
                     shard_key: None,
+                    precondition: None,
                 },
                 Vec::new(),
             );
@@ -445,6 +491,19 @@ mod tests {
                 key: None,
             });
 
+            let set_conditional = Self::SetPayloadConditional(ConditionalSetPayloadOp {
+                payload_op: SetPayloadOp {
+                    payload: Payload(Default::default()),
+                    points: None,
+                    filter: None,
+                    key: None,
+                },
+                precondition: WritePrecondition {
+                    version: Some(0),
+                    filter: None,
+                },
+            });
+
             let overwrite = Self::OverwritePayload(SetPayloadOp {
                 payload: Payload(Default::default()),
                 points: None,
@@ -452,6 +511,13 @@ mod tests {
                 key: None,
             });
 
+            let patch = Self::PatchPayload(SetPayloadOp {
+                payload: Payload(Default::default()),
+                points: None,
+                filter: None,
+                key: None,
+            });
+
             let delete = Self::DeletePayload(DeletePayloadOp {
                 keys: Vec::new(),
                 points: None,
@@ -469,7 +535,9 @@ mod tests {
 
             prop_oneof![
                 Just(set),
+                Just(set_conditional),
                 Just(overwrite),
+                Just(patch),
                 Just(delete),
                 Just(clear),
                 Just(clear_by_filter),
@@ -493,4 +561,19 @@ mod tests {
             prop_oneof![Just(create), Just(delete),].boxed()
         }
     }
+
+    impl Arbitrary for attribute_ops::AttributeOps {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<Self>;
+
+        fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+            let set = Self::SetAttributes(attribute_ops::SetAttributesOp {
+                attributes: Default::default(),
+                points: None,
+                filter: None,
+            });
+
+            Just(set).boxed()
+        }
+    }
 }
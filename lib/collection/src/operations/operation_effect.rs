@@ -1,7 +1,8 @@
 use segment::types::{Filter, PointIdType};
 
 use super::vector_ops;
-use crate::operations::payload_ops::PayloadOps;
+use crate::operations::attribute_ops::AttributeOps;
+use crate::operations::payload_ops::{ConditionalSetPayloadOp, PayloadOps};
 use crate::operations::{point_ops, CollectionUpdateOperations};
 
 /// Structure to define what part of the shard are affected by the operation
@@ -38,6 +39,9 @@ impl EstimateOperationEffectArea for CollectionUpdateOperations {
                 payload_operation.estimate_effect_area()
             }
             CollectionUpdateOperations::FieldIndexOperation(_) => OperationEffectArea::Empty,
+            CollectionUpdateOperations::AttributeOperation(attribute_operation) => {
+                attribute_operation.estimate_effect_area()
+            }
         }
     }
 }
@@ -48,9 +52,15 @@ impl EstimateOperationEffectArea for point_ops::PointOperations {
             point_ops::PointOperations::UpsertPoints(insert_operations) => {
                 insert_operations.estimate_effect_area()
             }
+            point_ops::PointOperations::UpsertPointsConditional(conditional_upsert) => {
+                conditional_upsert.points_op.estimate_effect_area()
+            }
             point_ops::PointOperations::DeletePoints { ids } => {
                 OperationEffectArea::Points(ids.clone())
             }
+            point_ops::PointOperations::DeletePointsConditional(conditional_delete) => {
+                OperationEffectArea::Points(conditional_delete.ids.clone())
+            }
             point_ops::PointOperations::DeletePointsByFilter(filter) => {
                 OperationEffectArea::Filter(filter.clone())
             }
@@ -98,7 +108,11 @@ impl EstimateOperationEffectArea for point_ops::PointInsertOperationsInternal {
 impl EstimateOperationEffectArea for PayloadOps {
     fn estimate_effect_area(&self) -> OperationEffectArea {
         match self {
-            PayloadOps::SetPayload(set_payload) => {
+            PayloadOps::SetPayload(set_payload)
+            | PayloadOps::SetPayloadConditional(ConditionalSetPayloadOp {
+                payload_op: set_payload,
+                ..
+            }) => {
                 if let Some(points) = &set_payload.points {
                     OperationEffectArea::Points(points.clone())
                 } else if let Some(filter) = &set_payload.filter {
@@ -118,7 +132,7 @@ impl EstimateOperationEffectArea for PayloadOps {
             }
             PayloadOps::ClearPayload { points } => OperationEffectArea::Points(points.clone()),
             PayloadOps::ClearPayloadByFilter(filter) => OperationEffectArea::Filter(filter.clone()),
-            PayloadOps::OverwritePayload(set_payload) => {
+            PayloadOps::OverwritePayload(set_payload) | PayloadOps::PatchPayload(set_payload) => {
                 if let Some(points) = &set_payload.points {
                     OperationEffectArea::Points(points.clone())
                 } else if let Some(filter) = &set_payload.filter {
@@ -130,3 +144,19 @@ impl EstimateOperationEffectArea for PayloadOps {
         }
     }
 }
+
+impl EstimateOperationEffectArea for AttributeOps {
+    fn estimate_effect_area(&self) -> OperationEffectArea {
+        match self {
+            AttributeOps::SetAttributes(set_attributes) => {
+                if let Some(points) = &set_attributes.points {
+                    OperationEffectArea::Points(points.clone())
+                } else if let Some(filter) = &set_attributes.filter {
+                    OperationEffectArea::Filter(filter.clone())
+                } else {
+                    OperationEffectArea::Empty
+                }
+            }
+        }
+    }
+}
@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 use strum::{EnumDiscriminants, EnumIter};
 use validator::Validate;
 
+use super::point_ops::WritePrecondition;
 use super::{split_iter_by_shard, OperationToShard, SplitByShard};
 use crate::hash_ring::HashRing;
 
@@ -23,6 +24,10 @@ pub struct SetPayload {
     pub shard_key: Option<ShardKeySelector>,
     /// Assigns payload to each point that satisfy this path of property
     pub key: Option<JsonPath>,
+    /// Only modify points, which satisfy this condition
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub precondition: Option<WritePrecondition>,
 }
 
 /// This data structure is used inside shard operations queue
@@ -48,6 +53,17 @@ struct SetPayloadShadow {
     pub filter: Option<Filter>,
     pub shard_key: Option<ShardKeySelector>,
     pub key: Option<JsonPath>,
+    #[serde(default)]
+    pub precondition: Option<WritePrecondition>,
+}
+
+/// Set payload of the points, which satisfy the precondition
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, Validate)]
+pub struct ConditionalSetPayloadOp {
+    #[validate]
+    pub payload_op: SetPayloadOp,
+    #[validate]
+    pub precondition: WritePrecondition,
 }
 
 pub struct PointsSelectorValidationError;
@@ -72,6 +88,7 @@ impl TryFrom<SetPayloadShadow> for SetPayload {
                 filter: value.filter,
                 shard_key: value.shard_key,
                 key: value.key,
+                precondition: value.precondition,
             })
         } else {
             Err(PointsSelectorValidationError)
@@ -140,6 +157,8 @@ impl TryFrom<DeletePayloadShadow> for DeletePayload {
 pub enum PayloadOps {
     /// Set payload value, overrides if it is already exists
     SetPayload(SetPayloadOp),
+    /// Set payload value of the points, guarded by a precondition
+    SetPayloadConditional(ConditionalSetPayloadOp),
     /// Deletes specified payload values if they are assigned
     DeletePayload(DeletePayloadOp),
     /// Drops all Payload values associated with given points.
@@ -148,16 +167,21 @@ pub enum PayloadOps {
     ClearPayloadByFilter(Filter),
     /// Overwrite full payload with given keys
     OverwritePayload(SetPayloadOp),
+    /// Merge given payload into the existing one, following JSON Merge Patch (RFC 7396):
+    /// nested objects are merged recursively, `null` values remove keys
+    PatchPayload(SetPayloadOp),
 }
 
 impl PayloadOps {
     pub fn is_write_operation(&self) -> bool {
         match self {
             PayloadOps::SetPayload(_) => true,
+            PayloadOps::SetPayloadConditional(_) => true,
             PayloadOps::DeletePayload(_) => false,
             PayloadOps::ClearPayload { .. } => false,
             PayloadOps::ClearPayloadByFilter(_) => false,
             PayloadOps::OverwritePayload(_) => true,
+            PayloadOps::PatchPayload(_) => true,
         }
     }
 }
@@ -166,10 +190,12 @@ impl Validate for PayloadOps {
     fn validate(&self) -> Result<(), validator::ValidationErrors> {
         match self {
             PayloadOps::SetPayload(operation) => operation.validate(),
+            PayloadOps::SetPayloadConditional(operation) => operation.validate(),
             PayloadOps::DeletePayload(operation) => operation.validate(),
             PayloadOps::ClearPayload { .. } => Ok(()),
             PayloadOps::ClearPayloadByFilter(_) => Ok(()),
             PayloadOps::OverwritePayload(operation) => operation.validate(),
+            PayloadOps::PatchPayload(operation) => operation.validate(),
         }
     }
 }
@@ -180,6 +206,15 @@ impl SplitByShard for PayloadOps {
             PayloadOps::SetPayload(operation) => {
                 operation.split_by_shard(ring).map(PayloadOps::SetPayload)
             }
+            PayloadOps::SetPayloadConditional(ConditionalSetPayloadOp {
+                payload_op,
+                precondition,
+            }) => payload_op.split_by_shard(ring).map(|payload_op| {
+                PayloadOps::SetPayloadConditional(ConditionalSetPayloadOp {
+                    payload_op,
+                    precondition: precondition.clone(),
+                })
+            }),
             PayloadOps::DeletePayload(operation) => operation
                 .split_by_shard(ring)
                 .map(PayloadOps::DeletePayload),
@@ -189,6 +224,9 @@ impl SplitByShard for PayloadOps {
             PayloadOps::OverwritePayload(operation) => operation
                 .split_by_shard(ring)
                 .map(PayloadOps::OverwritePayload),
+            PayloadOps::PatchPayload(operation) => {
+                operation.split_by_shard(ring).map(PayloadOps::PatchPayload)
+            }
         }
     }
 }
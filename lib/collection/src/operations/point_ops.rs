@@ -7,7 +7,7 @@ use schemars::JsonSchema;
 use segment::common::utils::transpose_map_into_named_vector;
 use segment::data_types::named_vectors::NamedVectors;
 use segment::data_types::vectors::{MultiDenseVectorInternal, Vector, DEFAULT_VECTOR_NAME};
-use segment::types::{Filter, Payload, PointIdType};
+use segment::types::{Filter, Payload, PointIdType, SeqNumberType};
 use serde::{Deserialize, Serialize};
 use strum::{EnumDiscriminants, EnumIter};
 use validator::Validate;
@@ -97,12 +97,49 @@ impl Batch {
     }
 }
 
+/// Condition, which an existing point must satisfy to be modified by the operation.
+///
+/// Checked for each point right before the point is modified.
+/// Points, which do not satisfy the condition, are left intact and reported as conflicts.
+/// Points, which do not exist, never satisfy the condition.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize, JsonSchema, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct WritePrecondition {
+    /// Only modify points, whose current version is equal to this value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<SeqNumberType>,
+    /// Only modify points, which match this filter
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub filter: Option<Filter>,
+}
+
+impl WritePrecondition {
+    pub fn is_empty(&self) -> bool {
+        self.version.is_none() && self.filter.is_none()
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize, JsonSchema, Validate)]
 #[serde(rename_all = "snake_case")]
 pub struct PointIdsList {
     pub points: Vec<PointIdType>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub shard_key: Option<ShardKeySelector>,
+    /// Only delete points, which satisfy this condition.
+    /// Only supported for point deletion.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub precondition: Option<WritePrecondition>,
+}
+
+/// Restore deleted points from the trash
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, JsonSchema, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct RestorePoints {
+    /// Ids of the deleted points
+    #[validate(length(min = 1))]
+    pub points: Vec<PointIdType>,
 }
 
 impl From<Vec<PointIdType>> for PointIdsList {
@@ -110,6 +147,7 @@ impl From<Vec<PointIdType>> for PointIdsList {
         Self {
             points,
             shard_key: None,
+            precondition: None,
         }
     }
 }
@@ -155,6 +193,10 @@ pub struct PointsBatch {
     pub batch: Batch,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub shard_key: Option<ShardKeySelector>,
+    /// Only update existing points, which satisfy this condition
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub precondition: Option<WritePrecondition>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, JsonSchema, Validate)]
@@ -163,6 +205,10 @@ pub struct PointsList {
     pub points: Vec<PointStruct>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub shard_key: Option<ShardKeySelector>,
+    /// Only update existing points, which satisfy this condition
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub precondition: Option<WritePrecondition>,
 }
 
 impl<'de> serde::Deserialize<'de> for PointInsertOperations {
@@ -170,24 +216,39 @@ impl<'de> serde::Deserialize<'de> for PointInsertOperations {
     where
         D: serde::Deserializer<'de>,
     {
-        let value = serde_json::Value::deserialize(deserializer)?;
-        match value {
-            serde_json::Value::Object(map) => {
-                if map.contains_key("batch") {
-                    PointsBatch::deserialize(serde_json::Value::Object(map))
-                        .map(PointInsertOperations::PointsBatch)
-                        .map_err(serde::de::Error::custom)
-                } else if map.contains_key("points") {
-                    PointsList::deserialize(serde_json::Value::Object(map))
-                        .map(PointInsertOperations::PointsList)
-                        .map_err(serde::de::Error::custom)
-                } else {
-                    Err(serde::de::Error::custom(
-                        "Invalid PointInsertOperations format",
-                    ))
-                }
-            }
-            _ => Err(serde::de::Error::custom(
+        // Fields are deserialized directly, without an intermediate `serde_json::Value`,
+        // so a large request isn't kept in memory twice
+        #[derive(Deserialize)]
+        #[serde(rename = "PointInsertOperations")]
+        struct Fields {
+            #[serde(default)]
+            batch: Option<Batch>,
+            #[serde(default)]
+            points: Option<Vec<PointStruct>>,
+            #[serde(default)]
+            shard_key: Option<ShardKeySelector>,
+            #[serde(default)]
+            precondition: Option<WritePrecondition>,
+        }
+
+        let Fields {
+            batch,
+            points,
+            shard_key,
+            precondition,
+        } = Fields::deserialize(deserializer)?;
+        match (batch, points) {
+            (Some(batch), _) => Ok(PointInsertOperations::PointsBatch(PointsBatch {
+                batch,
+                shard_key,
+                precondition,
+            })),
+            (None, Some(points)) => Ok(PointInsertOperations::PointsList(PointsList {
+                points,
+                shard_key,
+                precondition,
+            })),
+            (None, None) => Err(serde::de::Error::custom(
                 "Invalid PointInsertOperations format",
             )),
         }
@@ -213,10 +274,20 @@ impl Validate for PointInsertOperations {
 }
 
 impl PointInsertOperations {
-    pub fn decompose(self) -> (Option<ShardKeySelector>, PointInsertOperationsInternal) {
+    pub fn decompose(
+        self,
+    ) -> (
+        Option<ShardKeySelector>,
+        PointInsertOperationsInternal,
+        Option<WritePrecondition>,
+    ) {
         match self {
-            PointInsertOperations::PointsBatch(batch) => (batch.shard_key, batch.batch.into()),
-            PointInsertOperations::PointsList(list) => (list.shard_key, list.points.into()),
+            PointInsertOperations::PointsBatch(batch) => {
+                (batch.shard_key, batch.batch.into(), batch.precondition)
+            }
+            PointInsertOperations::PointsList(list) => {
+                (list.shard_key, list.points.into(), list.precondition)
+            }
         }
     }
 }
@@ -319,6 +390,7 @@ impl From<Batch> for PointInsertOperations {
         PointInsertOperations::PointsBatch(PointsBatch {
             batch,
             shard_key: None,
+            precondition: None,
         })
     }
 }
@@ -328,6 +400,7 @@ impl From<Vec<PointStruct>> for PointInsertOperations {
         PointInsertOperations::PointsList(PointsList {
             points,
             shard_key: None,
+            precondition: None,
         })
     }
 }
@@ -344,14 +417,35 @@ impl From<Vec<PointStruct>> for PointInsertOperationsInternal {
     }
 }
 
+/// Insert or update points, existing points are only updated if they satisfy the precondition
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, Validate)]
+pub struct ConditionalInsertOperationInternal {
+    #[validate]
+    pub points_op: PointInsertOperationsInternal,
+    #[validate]
+    pub precondition: WritePrecondition,
+}
+
+/// Delete points, which satisfy the precondition
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, Validate)]
+pub struct ConditionalDeleteOperationInternal {
+    pub ids: Vec<PointIdType>,
+    #[validate]
+    pub precondition: WritePrecondition,
+}
+
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize, EnumDiscriminants)]
 #[strum_discriminants(derive(EnumIter))]
 #[serde(rename_all = "snake_case")]
 pub enum PointOperations {
     /// Insert or update points
     UpsertPoints(PointInsertOperationsInternal),
+    /// Insert or update points, guarded by a precondition
+    UpsertPointsConditional(ConditionalInsertOperationInternal),
     /// Delete point if exists
     DeletePoints { ids: Vec<PointIdType> },
+    /// Delete points, guarded by a precondition
+    DeletePointsConditional(ConditionalDeleteOperationInternal),
     /// Delete points by given filter criteria
     DeletePointsByFilter(Filter),
     /// Points Sync
@@ -362,7 +456,9 @@ impl PointOperations {
     pub fn is_write_operation(&self) -> bool {
         match self {
             PointOperations::UpsertPoints(_) => true,
+            PointOperations::UpsertPointsConditional(_) => true,
             PointOperations::DeletePoints { .. } => false,
+            PointOperations::DeletePointsConditional(_) => false,
             PointOperations::DeletePointsByFilter(_) => false,
             PointOperations::SyncPoints(_) => true,
         }
@@ -373,7 +469,13 @@ impl Validate for PointOperations {
     fn validate(&self) -> Result<(), validator::ValidationErrors> {
         match self {
             PointOperations::UpsertPoints(upsert_points) => upsert_points.validate(),
+            PointOperations::UpsertPointsConditional(conditional_upsert) => {
+                conditional_upsert.validate()
+            }
             PointOperations::DeletePoints { ids: _ } => Ok(()),
+            PointOperations::DeletePointsConditional(conditional_delete) => {
+                conditional_delete.validate()
+            }
             PointOperations::DeletePointsByFilter(_) => Ok(()),
             PointOperations::SyncPoints(_) => Ok(()),
         }
@@ -541,8 +643,26 @@ impl SplitByShard for PointOperations {
             PointOperations::UpsertPoints(upsert_points) => upsert_points
                 .split_by_shard(ring)
                 .map(PointOperations::UpsertPoints),
+            PointOperations::UpsertPointsConditional(ConditionalInsertOperationInternal {
+                points_op,
+                precondition,
+            }) => points_op.split_by_shard(ring).map(|points_op| {
+                PointOperations::UpsertPointsConditional(ConditionalInsertOperationInternal {
+                    points_op,
+                    precondition: precondition.clone(),
+                })
+            }),
             PointOperations::DeletePoints { ids } => split_iter_by_shard(ids, |id| *id, ring)
                 .map(|ids| PointOperations::DeletePoints { ids }),
+            PointOperations::DeletePointsConditional(ConditionalDeleteOperationInternal {
+                ids,
+                precondition,
+            }) => split_iter_by_shard(ids, |id| *id, ring).map(|ids| {
+                PointOperations::DeletePointsConditional(ConditionalDeleteOperationInternal {
+                    ids,
+                    precondition: precondition.clone(),
+                })
+            }),
             by_filter @ PointOperations::DeletePointsByFilter(_) => {
                 OperationToShard::to_all(by_filter)
             }
@@ -585,6 +705,9 @@ impl PointStruct {
                     named_vectors.insert(name.clone(), Vector::from(vector.clone()));
                 }
             }
+            VectorStruct::Inference(_) => {
+                named_vectors.insert(DEFAULT_VECTOR_NAME.to_string(), Vector::from(vec![]))
+            }
         }
         named_vectors
     }
@@ -0,0 +1,214 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use segment::index::sparse_index::sparse_index_config::SparseIndexType;
+use segment::telemetry::SegmentTelemetry;
+use segment::types::{
+    Indexes, PayloadKeyType, PayloadSchemaType, PayloadStorageType, SegmentType, VectorStorageType,
+};
+use serde::Serialize;
+
+use crate::shards::shard::ShardId;
+
+/// Segments of a local shard of the collection
+#[derive(Debug, Serialize, JsonSchema, Clone)]
+pub struct ShardSegmentsInfo {
+    pub shard_id: ShardId,
+    /// When the last optimization of the shard finished, since the start of this peer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_optimization_at: Option<DateTime<Utc>>,
+    pub segments: Vec<SegmentDetails>,
+    /// Segments, which failed to load and were moved into the quarantine directory of the shard
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub quarantined_segments: Vec<String>,
+}
+
+/// Internals of a single segment
+#[derive(Debug, Serialize, JsonSchema, Clone)]
+pub struct SegmentDetails {
+    /// Name of the segment directory
+    pub uuid: String,
+    pub segment_type: SegmentType,
+    pub is_appendable: bool,
+    pub num_points: usize,
+    pub num_deleted_vectors: usize,
+    /// Fraction of deleted vectors among all vectors, which still take space in the segment
+    pub deleted_ratio: f64,
+    pub ram_usage_bytes: usize,
+    pub disk_usage_bytes: usize,
+    /// Where payload is read from
+    pub payload_residency: Residency,
+    pub vectors: HashMap<String, SegmentVectorDetails>,
+    pub payload_indices: HashMap<PayloadKeyType, SegmentPayloadIndexDetails>,
+    /// When the segment was created, either empty or built by an optimizer.
+    /// Unknown for segments, created by older versions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, JsonSchema, Clone)]
+pub struct SegmentVectorDetails {
+    pub num_vectors: usize,
+    pub num_indexed_vectors: usize,
+    pub num_deleted_vectors: usize,
+    pub index: SegmentVectorIndex,
+    /// If all vectors of the segment are in the index
+    pub is_indexed: bool,
+    /// If quantized vectors are used for search
+    pub is_quantized: bool,
+    /// Where vectors are read from
+    pub vectors_residency: Residency,
+    /// Where the index is read from, absent for plain index
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index_residency: Option<Residency>,
+}
+
+#[derive(Debug, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SegmentVectorIndex {
+    Plain,
+    Hnsw,
+    Sparse,
+}
+
+#[derive(Debug, Serialize, JsonSchema, Clone)]
+pub struct SegmentPayloadIndexDetails {
+    pub data_type: PayloadSchemaType,
+    /// Number of points, indexed with this index
+    pub points: usize,
+    /// Number of values, indexed with this index
+    pub values: usize,
+}
+
+/// Where the data of a segment component is read from
+#[derive(Debug, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Residency {
+    /// Fully loaded into RAM
+    Ram,
+    /// Memory mapped files, cached in RAM by the OS
+    Mmap,
+    /// Read from RocksDB on each request
+    Disk,
+}
+
+impl SegmentDetails {
+    pub fn new(
+        uuid: String,
+        telemetry: SegmentTelemetry,
+        created_at: Option<DateTime<Utc>>,
+    ) -> Self {
+        let SegmentTelemetry {
+            info,
+            config,
+            vector_index_searches: _,
+            payload_field_indices,
+        } = telemetry;
+
+        let dense_vectors = config.vector_data.iter().map(|(name, vector_config)| {
+            let (index, index_residency) = match &vector_config.index {
+                Indexes::Plain {} => (SegmentVectorIndex::Plain, None),
+                Indexes::Hnsw(hnsw) => {
+                    let residency = if hnsw.on_disk.unwrap_or(false) {
+                        Residency::Mmap
+                    } else {
+                        Residency::Ram
+                    };
+                    (SegmentVectorIndex::Hnsw, Some(residency))
+                }
+            };
+            let vectors_residency = match vector_config.storage_type {
+                VectorStorageType::Memory => Residency::Ram,
+                VectorStorageType::Mmap | VectorStorageType::ChunkedMmap => Residency::Mmap,
+            };
+            let is_quantized = vector_config.quantization_config.is_some();
+            (
+                name,
+                index,
+                index_residency,
+                vectors_residency,
+                is_quantized,
+            )
+        });
+        let sparse_vectors = config
+            .sparse_vector_data
+            .iter()
+            .map(|(name, vector_config)| {
+                let index_residency = match vector_config.index.index_type {
+                    SparseIndexType::MutableRam | SparseIndexType::ImmutableRam => Residency::Ram,
+                    SparseIndexType::Mmap => Residency::Mmap,
+                };
+                let index = SegmentVectorIndex::Sparse;
+                (name, index, Some(index_residency), Residency::Disk, false)
+            });
+
+        let vectors = dense_vectors
+            .chain(sparse_vectors)
+            .map(
+                |(name, index, index_residency, vectors_residency, is_quantized)| {
+                    let data = info.vector_data.get(name).cloned().unwrap_or_default();
+                    let details = SegmentVectorDetails {
+                        num_vectors: data.num_vectors,
+                        num_indexed_vectors: data.num_indexed_vectors,
+                        num_deleted_vectors: data.num_deleted_vectors,
+                        index,
+                        is_indexed: index != SegmentVectorIndex::Plain
+                            && data.num_indexed_vectors >= data.num_vectors,
+                        is_quantized,
+                        vectors_residency,
+                        index_residency,
+                    };
+                    (name.clone(), details)
+                },
+            )
+            .collect();
+
+        let payload_indices = info
+            .index_schema
+            .iter()
+            .map(|(field, index_info)| {
+                let field_name = field.to_string();
+                let values = payload_field_indices
+                    .iter()
+                    .filter(|index| index.field_name.as_deref() == Some(field_name.as_str()))
+                    .map(|index| index.points_values_count)
+                    .sum();
+                let details = SegmentPayloadIndexDetails {
+                    data_type: index_info.data_type,
+                    points: index_info.points,
+                    values,
+                };
+                (field.clone(), details)
+            })
+            .collect();
+
+        let payload_residency = match config.payload_storage_type {
+            PayloadStorageType::InMemory => Residency::Ram,
+            PayloadStorageType::OnDisk => Residency::Disk,
+            PayloadStorageType::Gridstore => Residency::Mmap,
+        };
+
+        let total_vectors = info.num_vectors + info.num_deleted_vectors;
+        let deleted_ratio = if total_vectors > 0 {
+            info.num_deleted_vectors as f64 / total_vectors as f64
+        } else {
+            0.0
+        };
+
+        Self {
+            uuid,
+            segment_type: info.segment_type,
+            is_appendable: info.is_appendable,
+            num_points: info.num_points,
+            num_deleted_vectors: info.num_deleted_vectors,
+            deleted_ratio,
+            ram_usage_bytes: info.ram_usage_bytes,
+            disk_usage_bytes: info.disk_usage_bytes,
+            payload_residency,
+            vectors,
+            payload_indices,
+            created_at,
+        }
+    }
+}
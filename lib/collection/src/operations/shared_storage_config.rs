@@ -2,7 +2,12 @@ use std::default;
 use std::num::NonZeroUsize;
 use std::time::Duration;
 
+use crate::common::idempotency::IdempotencyConfig;
+use crate::common::index_advisor::IndexAdvisorConfig;
+use crate::common::slow_query_log::SlowQueryLogConfig;
 use crate::common::snapshots_manager::SnapShotsConfig;
+use crate::common::update_backpressure::UpdateBackpressureConfig;
+use crate::common::warmup::WarmupConfig;
 use crate::operations::types::NodeType;
 use crate::shards::transfer::ShardTransferMethod;
 
@@ -22,6 +27,7 @@ pub struct SharedStorageConfig {
     pub update_queue_size: usize,
     pub node_type: NodeType,
     pub handle_collection_load_errors: bool,
+    pub quarantine_corrupted_segments: bool,
     pub recovery_mode: Option<String>,
     pub search_timeout: Duration,
     pub update_concurrency: Option<NonZeroUsize>,
@@ -31,6 +37,12 @@ pub struct SharedStorageConfig {
     pub outgoing_shard_transfers_limit: Option<usize>,
     pub snapshots_path: String,
     pub snapshots_config: SnapShotsConfig,
+    pub slow_query_log: SlowQueryLogConfig,
+    pub index_advisor: IndexAdvisorConfig,
+    pub warmup: WarmupConfig,
+    pub min_free_disk_space_mb: Option<usize>,
+    pub update_backpressure: UpdateBackpressureConfig,
+    pub idempotency: IdempotencyConfig,
 }
 
 impl Default for SharedStorageConfig {
@@ -39,6 +51,7 @@ impl Default for SharedStorageConfig {
             update_queue_size: DEFAULT_UPDATE_QUEUE_SIZE,
             node_type: Default::default(),
             handle_collection_load_errors: false,
+            quarantine_corrupted_segments: false,
             recovery_mode: None,
             search_timeout: DEFAULT_SEARCH_TIMEOUT,
             update_concurrency: None,
@@ -48,6 +61,12 @@ impl Default for SharedStorageConfig {
             outgoing_shard_transfers_limit: DEFAULT_IO_SHARD_TRANSFER_LIMIT,
             snapshots_path: DEFAULT_SNAPSHOTS_PATH.to_string(),
             snapshots_config: default::Default::default(),
+            slow_query_log: Default::default(),
+            index_advisor: Default::default(),
+            warmup: Default::default(),
+            min_free_disk_space_mb: None,
+            update_backpressure: Default::default(),
+            idempotency: Default::default(),
         }
     }
 }
@@ -58,6 +77,7 @@ impl SharedStorageConfig {
         update_queue_size: Option<usize>,
         node_type: NodeType,
         handle_collection_load_errors: bool,
+        quarantine_corrupted_segments: bool,
         recovery_mode: Option<String>,
         search_timeout: Option<Duration>,
         update_concurrency: Option<NonZeroUsize>,
@@ -67,6 +87,12 @@ impl SharedStorageConfig {
         outgoing_shard_transfers_limit: Option<usize>,
         snapshots_path: String,
         snapshots_config: SnapShotsConfig,
+        slow_query_log: SlowQueryLogConfig,
+        index_advisor: IndexAdvisorConfig,
+        warmup: WarmupConfig,
+        min_free_disk_space_mb: Option<usize>,
+        update_backpressure: UpdateBackpressureConfig,
+        idempotency: IdempotencyConfig,
     ) -> Self {
         let update_queue_size = update_queue_size.unwrap_or(match node_type {
             NodeType::Normal => DEFAULT_UPDATE_QUEUE_SIZE,
@@ -76,6 +102,7 @@ impl SharedStorageConfig {
             update_queue_size,
             node_type,
             handle_collection_load_errors,
+            quarantine_corrupted_segments,
             recovery_mode,
             search_timeout: search_timeout.unwrap_or(DEFAULT_SEARCH_TIMEOUT),
             update_concurrency,
@@ -85,6 +112,12 @@ impl SharedStorageConfig {
             outgoing_shard_transfers_limit,
             snapshots_path,
             snapshots_config,
+            slow_query_log,
+            index_advisor,
+            warmup,
+            min_free_disk_space_mb,
+            update_backpressure,
+            idempotency,
         }
     }
 }
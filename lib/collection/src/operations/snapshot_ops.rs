@@ -63,6 +63,7 @@ pub struct SnapshotRecover {
     /// Examples:
     /// - URL `http://localhost:8080/collections/my_collection/snapshots/my_snapshot`
     /// - Local path `file:///qdrant/snapshots/test_collection-2022-08-04-10-49-10.snapshot`
+    /// - S3 object `s3://bucket/snapshots/test_collection/test_collection-2022-08-04-10-49-10.snapshot`, if snapshots are stored in S3
     pub location: Url,
 
     /// Defines which data should be used as a source of truth if there are other replicas in the cluster.
@@ -81,6 +82,34 @@ pub struct SnapshotRecover {
     pub api_key: Option<String>,
 }
 
+/// Recover a collection from a snapshot, pulled directly from another Qdrant instance
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+pub struct RemoteSnapshotRecover {
+    /// Base URL of the source Qdrant instance.
+    /// Example: `http://source-qdrant:6333`
+    pub url: Url,
+
+    /// Name of the collection on the source instance.
+    /// If not provided - the name of the recovered collection is used.
+    #[serde(default)]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: Option<String>,
+
+    /// Name of an existing snapshot on the source instance.
+    /// If not provided - a new snapshot is created on the source instance.
+    #[serde(default)]
+    #[validate(length(min = 1))]
+    pub snapshot_name: Option<String>,
+
+    /// Defines which data should be used as a source of truth if there are other replicas in the cluster.
+    #[serde(default)]
+    pub priority: Option<SnapshotPriority>,
+
+    /// Optional API key of the source instance.
+    #[serde(default)]
+    pub api_key: Option<String>,
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
 pub struct SnapshotDescription {
     pub name: String,
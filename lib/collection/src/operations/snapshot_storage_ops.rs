@@ -170,10 +170,12 @@ pub async fn download_snapshot(
     target_path: &Path,
 ) -> CollectionResult<()> {
     let s3_path = trim_dot_slash(path)?;
-    let download = client
-        .get(&s3_path)
-        .await
-        .map_err(|e| CollectionError::service_error(format!("Failed to get {}: {}", s3_path, e)))?;
+    let download = client.get(&s3_path).await.map_err(|e| match e {
+        object_store::Error::NotFound { path, .. } => {
+            CollectionError::not_found(format!("Snapshot {path}"))
+        }
+        _ => CollectionError::service_error(format!("Failed to get {}: {}", s3_path, e)),
+    })?;
 
     let mut stream = download.into_stream();
 
@@ -9,7 +9,7 @@ use std::time::SystemTimeError;
 use api::grpc::transport_channel_pool::RequestError;
 use api::rest::{
     BaseGroupRequest, LookupLocation, OrderByInterface, RecommendStrategy,
-    SearchGroupsRequestInternal, SearchRequestInternal, ShardKeySelector,
+    SearchGroupsRequestInternal, SearchRequestInternal, ShardKeySelector, ShardReadStatus,
 };
 use common::defaults;
 use common::types::ScoreType;
@@ -20,15 +20,19 @@ use merge::Merge;
 use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
 use segment::common::operation_error::OperationError;
+use segment::data_types::aggregation::{
+    DistinctValues, HistogramBucket, NumericStats, ValueDistribution, MAX_HISTOGRAM_BUCKETS,
+};
 use segment::data_types::groups::GroupId;
 use segment::data_types::vectors::{
     DenseVector, QueryVector, VectorRef, VectorStructInternal, DEFAULT_VECTOR_NAME,
 };
 use segment::index::sparse_index::sparse_index_config::SparseVectorIndexDatatype;
+use segment::json_path::JsonPath;
 use segment::types::{
-    Distance, Filter, MultiVectorConfig, Payload, PayloadIndexInfo, PayloadKeyType, PointIdType,
-    QuantizationConfig, SearchParams, SeqNumberType, ShardKey, VectorStorageDatatype,
-    WithPayloadInterface, WithVector,
+    Distance, Filter, MultiVectorConfig, Payload, PayloadFieldSchema, PayloadIndexInfo,
+    PayloadKeyType, PayloadSchemaType, PointIdType, QuantizationConfig, SearchParams,
+    SeqNumberType, ShardKey, VectorStorageDatatype, WithPayloadInterface, WithVector,
 };
 use semver::Version;
 use serde;
@@ -44,11 +48,13 @@ use validator::{Validate, ValidationError, ValidationErrors};
 
 use super::config_diff::{self};
 use super::ClockTag;
+use crate::collection_manager::point_trash::DeletedRecord;
 use crate::config::{CollectionConfig, CollectionParams};
 use crate::operations::config_diff::{HnswConfigDiff, QuantizationConfigDiff};
 use crate::operations::query_enum::QueryEnum;
 use crate::save_on_disk;
 use crate::shards::replica_set::ReplicaState;
+use crate::shards::resolve::Reconciled;
 use crate::shards::shard::{PeerId, ShardId};
 use crate::shards::transfer::ShardTransferMethod;
 use crate::wal::WalError;
@@ -241,6 +247,10 @@ pub struct LocalShardInfo {
     pub points_count: usize,
     /// Is replica active
     pub state: ReplicaState,
+    /// Segments of the replica, which failed to load and were moved into quarantine.
+    /// Their points are missing, until the replica is recovered from another replica.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub quarantined_segments: Vec<String>,
 }
 
 #[derive(Debug, Serialize, JsonSchema)]
@@ -257,9 +267,355 @@ pub struct RemoteShardInfo {
     pub state: ReplicaState,
 }
 
+/// Resource usage of a collection or a shard, limited by quotas in strict mode
+#[derive(Debug, Default, Clone, Copy, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct QuotaUsage {
+    /// Number of points
+    pub points_count: usize,
+    /// Size of vector storages, vector indexes and quantized vectors, which are loaded into RAM
+    pub ram_usage_bytes: usize,
+    /// Size of the segments on disk
+    pub disk_usage_bytes: usize,
+}
+
+/// Result of the comparison of the replicas of the collection shards
+#[derive(Debug, Default, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct CollectionConsistency {
+    /// Checked shards, which have an active replica on this peer
+    pub shards: Vec<ShardConsistency>,
+}
+
+impl CollectionConsistency {
+    pub fn is_consistent(&self) -> bool {
+        self.shards.iter().all(|shard| shard.divergent_points == 0)
+    }
+}
+
+/// Differences between the active replicas of a shard
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ShardConsistency {
+    pub shard_id: ShardId,
+    /// Peers of the compared replicas
+    pub peers: Vec<PeerId>,
+    /// Number of point ranges, which have different digests on the replicas
+    pub divergent_buckets: usize,
+    /// Number of points, which are missing or have different data on some of the replicas
+    pub divergent_points: usize,
+    /// Some of the divergent points
+    pub divergent_points_sample: Vec<DivergentPoint>,
+    /// Number of divergent points, which were re-synced from the most recently updated replica
+    pub repaired_points: usize,
+}
+
+/// Point, which differs between the replicas of a shard
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DivergentPoint {
+    pub id: PointIdType,
+    /// Version of the point on each of the `peers`, `null` if the point is missing on the peer
+    pub versions: Vec<Option<SeqNumberType>>,
+}
+
+/// Disk usage of the collection on this peer, with its projected growth
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct CollectionDiskUsage {
+    /// Total size of local shards
+    pub disk_usage_bytes: u64,
+    /// Growth of local shards, averaged over recent samples
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub growth_bytes_per_hour: Option<f64>,
+    /// Time until writes are rejected, at the current growth of the collection
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hours_until_writes_rejected: Option<f64>,
+    /// If any local shard rejects writes, because free disk space is below the threshold
+    pub writes_rejected: bool,
+    pub shards: Vec<ShardDiskUsage>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ShardDiskUsage {
+    /// Local shard id
+    pub shard_id: ShardId,
+    /// Size of all files of the shard
+    pub disk_usage_bytes: u64,
+    /// Free space of the disk, the shard is stored on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub free_space_bytes: Option<u64>,
+    /// Growth of the shard, averaged over recent samples.
+    /// Unknown until the shard is sampled at least twice.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub growth_bytes_per_hour: Option<f64>,
+    /// Time until free disk space falls below the threshold, at the current growth of the shard
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hours_until_writes_rejected: Option<f64>,
+    /// Free disk space is below the threshold, so writes are rejected.
+    /// Deletes and snapshots are still allowed.
+    pub writes_rejected: bool,
+}
+
+/// Structure of payloads, inferred from a sample of points
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct PayloadSchemaReport {
+    /// Number of points, which payloads were inspected
+    pub sampled_points: usize,
+    /// Max nesting depth of the fields in the sample
+    pub max_depth: usize,
+    /// Fields of the sampled payloads, by their path.
+    /// Fields of objects in arrays are denoted by `[]`, e.g. `items[].price`.
+    pub fields: BTreeMap<String, InferredPayloadField>,
+}
+
+#[derive(Debug, Serialize, JsonSchema, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct InferredPayloadField {
+    /// Number of sampled points, which have this field
+    pub points_count: usize,
+    /// Number of values of the field, including the elements of arrays
+    pub values_count: usize,
+    /// Types of the values of the field
+    pub types: Vec<InferredValueType>,
+    /// If values of the field are arrays, at least in some points
+    pub is_array: bool,
+    /// Nesting depth of the field, 0 for the top level fields
+    pub depth: usize,
+    /// Number of distinct values in the sample, not counting objects
+    pub distinct_values: usize,
+    /// Existing index of the field
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index: Option<PayloadFieldSchema>,
+    /// Index, which could speed up filtering by this field.
+    /// Only suggested for not indexed fields, which values have a consistent type.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggested_index: Option<PayloadSchemaType>,
+}
+
+#[derive(Debug, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum InferredValueType {
+    Null,
+    Bool,
+    Integer,
+    Float,
+    String,
+    /// String in one of the supported datetime formats
+    Datetime,
+    /// Object with `lat` and `lon` fields
+    Geo,
+    Object,
+}
+
+/// Quality of the stored vectors, inspected over a sample of points
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct VectorDiagnosticsReport {
+    /// Number of points, which vectors were inspected
+    pub sampled_points: usize,
+    /// Diagnostics of each vector by its name, the default vector has an empty name
+    pub vectors: BTreeMap<String, VectorDiagnostics>,
+}
+
+#[derive(Debug, Serialize, JsonSchema, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct VectorDiagnostics {
+    /// Number of sampled points, which have this vector
+    pub points_count: usize,
+    /// Number of inspected vectors, each vector of a multivector is counted separately
+    pub vectors_count: usize,
+    /// Distribution of the norms of the vectors
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub norm: Option<NormDistribution>,
+    /// Number of vectors, which only have zero values
+    pub zero_vectors: usize,
+    /// Number of vectors with NaN or infinite values
+    pub non_finite_vectors: usize,
+    /// Share of the vectors, which are exact copies of another vector in the sample
+    pub duplicate_rate: f64,
+    /// Intrinsic dimensionality of dense vectors, estimated from the distances to the nearest
+    /// neighbours within the sample. Values far below the size of the vectors hint at
+    /// degenerate embeddings.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub intrinsic_dimensionality: Option<f64>,
+}
+
+/// Distribution of the euclidean norms of the vectors
+#[derive(Debug, Serialize, JsonSchema, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct NormDistribution {
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub std_dev: f64,
+    /// 1st percentile
+    pub p01: f64,
+    /// Median
+    pub p50: f64,
+    /// 99th percentile
+    pub p99: f64,
+}
+
+/// Evaluation of the approximate search against exact search
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct RecallEvaluationRequest {
+    /// Name of the vector to evaluate. If not set, all vectors of the collection are evaluated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub using: Option<String>,
+    /// Number of queries for each vector. Vectors of randomly sampled points are used as queries,
+    /// and the query point itself is excluded from results. Default: 100
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1, max = 10_000))]
+    pub sample: Option<usize>,
+    /// Number of nearest neighbours to compare, the `k` of recall@k. Default: 10
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1, max = 1_000))]
+    pub limit: Option<usize>,
+    /// Evaluate search among the points, which satisfy this filter
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub filter: Option<Filter>,
+    /// Params of the approximate search. If not set, current settings of the collection are used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub params: Option<SearchParams>,
+}
+
+/// Recall and latency of the approximate search, compared with exact search
+#[derive(Debug, Default, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct RecallEvaluation {
+    /// Evaluation of each vector by its name, the default vector has an empty name
+    pub vectors: BTreeMap<String, VectorRecall>,
+}
+
+#[derive(Debug, Serialize, JsonSchema, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct VectorRecall {
+    /// Number of evaluated queries
+    pub queries: usize,
+    /// Number of compared nearest neighbours
+    pub limit: usize,
+    /// Mean share of the exact nearest neighbours, which were found by the approximate search
+    pub recall: f64,
+    /// Lowest recall of a single query
+    pub min_recall: f64,
+    /// Latency of the approximate search
+    pub latency: LatencyDistribution,
+    /// Latency of the exact search
+    pub exact_latency: LatencyDistribution,
+}
+
+/// Distribution of request latencies, in seconds
+#[derive(Debug, Default, Clone, Serialize, JsonSchema, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct LatencyDistribution {
+    pub mean: f64,
+    /// Median
+    pub p50: f64,
+    /// 99th percentile
+    pub p99: f64,
+    pub max: f64,
+}
+
+/// Sweep of index and search params, evaluated on temporary indexes over a sample of points
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchTuningRequest {
+    /// Name of the dense vector to tune. Default: the default vector
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub using: Option<String>,
+    /// Number of points, which temporary indexes are built over. Default: 10000
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1, max = 100_000))]
+    pub sample: Option<usize>,
+    /// Number of queries. Vectors of randomly sampled points are used as queries, and are not
+    /// indexed. Default: 100
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1, max = 1_000))]
+    pub queries: Option<usize>,
+    /// Number of nearest neighbours to compare, the `k` of recall@k. Default: 10
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1, max = 1_000))]
+    pub limit: Option<usize>,
+    /// Values of `m` to build the index with. Default: 8, 16, 32 and the current value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(length(min = 1, max = 16))]
+    pub m: Option<Vec<usize>>,
+    /// Values of `hnsw_ef` to search with. Default: 16, 32, 64, 128, 256 and the current
+    /// `ef_construct`, which is used if `hnsw_ef` is not set in the request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(length(min = 1, max = 16))]
+    pub ef: Option<Vec<usize>>,
+    /// Quantization configs to build the index with, in addition to the index without
+    /// quantization. Default: the current quantization of the vector, if configured
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(length(max = 8))]
+    pub quantization: Option<Vec<QuantizationConfig>>,
+    /// Oversampling values to search the quantized indexes with. Default: 1, 2, 4
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(length(min = 1, max = 8))]
+    pub oversampling: Option<Vec<f64>>,
+    /// Minimal recall of the chosen settings. Default: 0.95
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub target_recall: Option<f64>,
+    /// If true, `m` and quantization of the chosen settings are applied to the config of the
+    /// vector. Search params are not part of the collection config, and are only reported.
+    #[serde(default)]
+    pub apply: bool,
+}
+
+/// Recall and latency of the swept settings
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchTuningReport {
+    /// Name of the tuned vector
+    pub vector: String,
+    /// Number of points in the temporary indexes
+    pub indexed_points: usize,
+    /// Number of evaluated queries
+    pub queries: usize,
+    /// Number of compared nearest neighbours
+    pub limit: usize,
+    /// Evaluated settings in the order of their mean latency
+    pub results: Vec<SearchTuningResult>,
+    /// Fastest settings, which reach the target recall. The most accurate settings, if none do.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chosen: Option<SearchTuningResult>,
+    /// Whether the chosen settings were applied to the collection
+    pub applied: bool,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchTuningResult {
+    /// `m` of the index
+    pub m: usize,
+    /// Quantization of the index, none if not quantized
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quantization: Option<QuantizationConfig>,
+    /// `hnsw_ef` of the search
+    pub hnsw_ef: usize,
+    /// Oversampling of the search, none if not quantized
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub oversampling: Option<f64>,
+    /// Mean share of the exact nearest neighbours, which were found
+    pub recall: f64,
+    /// Latency of the search in the temporary index
+    pub latency: LatencyDistribution,
+    /// Whether these settings are on the Pareto frontier of recall and latency, so that no other
+    /// settings are both faster and more accurate
+    pub pareto_optimal: bool,
+}
+
 /// `Acknowledged` - Request is saved to WAL and will be process in a queue.
 /// `Completed` - Request is completed, changes are actual.
-#[derive(Debug, Serialize, JsonSchema, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, JsonSchema, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum UpdateStatus {
     Acknowledged,
@@ -269,7 +625,7 @@ pub enum UpdateStatus {
     ClockRejected,
 }
 
-#[derive(Debug, Serialize, JsonSchema)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct UpdateResult {
     /// Sequential number of the operation
@@ -283,6 +639,22 @@ pub struct UpdateResult {
     /// Provided if incoming update request also specify clock tick
     #[serde(skip)]
     pub clock_tag: Option<ClockTag>,
+
+    /// Points, which were not modified because they did not satisfy the write precondition.
+    /// Only reported if the request waited for the result.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub conflicts: Vec<WriteConflict>,
+}
+
+/// Point, which was left intact because it did not satisfy the write precondition
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct WriteConflict {
+    /// Id of the point
+    pub id: PointIdType,
+    /// Current version of the point, absent if the point does not exist
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current_version: Option<SeqNumberType>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
@@ -320,6 +692,15 @@ pub struct ScrollRequestInternal {
 
     /// Order the records by a payload field.
     pub order_by: Option<OrderByInterface>,
+
+    /// Continue ordered scroll right after the position of a `next_page_cursor` from the previous page.
+    /// Requires `order_by`.
+    pub cursor: Option<String>,
+
+    /// Also return deleted points, which are still in the trash, with ids within the range of
+    /// the page. Requires `soft_delete_retention_sec` in collection params. Default: false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_deleted: Option<bool>,
 }
 
 /// Scroll request, used as a part of query request
@@ -368,6 +749,8 @@ impl Default for ScrollRequestInternal {
             with_payload: Some(Self::default_with_payload()),
             with_vector: Self::default_with_vector(),
             order_by: None,
+            cursor: None,
+            include_deleted: None,
         }
     }
 }
@@ -380,6 +763,12 @@ pub struct ScrollResult {
     pub points: Vec<api::rest::Record>,
     /// Offset which should be used to retrieve a next page result
     pub next_page_offset: Option<PointIdType>,
+    /// Cursor which should be used to retrieve a next page result, if ordering by a payload field
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_page_cursor: Option<String>,
+    /// Deleted points with ids within the range of the page, if `include_deleted` is requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_points: Option<Vec<DeletedRecord>>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
@@ -426,6 +815,38 @@ pub struct CoreSearchRequestBatch {
     pub searches: Vec<CoreSearchRequest>,
 }
 
+/// Response of a search or query, which might be incomplete
+///
+/// Searches, which reach an explicit timeout of the request, are stopped and respond with the
+/// points found so far. Shards, which fail a read with `partial` consistency, are skipped.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PartialResponse<T> {
+    pub value: T,
+    /// Whether any search was stopped at the timeout of the request
+    pub timed_out: bool,
+    /// Status of each requested shard, if read with `partial` consistency. Otherwise, empty.
+    pub shards: Vec<ShardReadStatus>,
+}
+
+impl<T> PartialResponse<T> {
+    /// Complete response
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            timed_out: false,
+            shards: Vec::new(),
+        }
+    }
+
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> PartialResponse<U> {
+        PartialResponse {
+            value: f(self.value),
+            timed_out: self.timed_out,
+            shards: self.shards,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
 pub struct SearchGroupsRequest {
     #[serde(flatten)]
@@ -585,6 +1006,42 @@ pub struct RecommendRequestBatch {
     pub searches: Vec<RecommendRequest>,
 }
 
+/// Batch of recommendation requests, which share the same examples.
+/// Shared examples are added to the own examples of each search, and vectors of the referenced
+/// points are retrieved once for the whole batch.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct RecommendSharedRequestBatch {
+    /// Look for vectors closest to those, in every search of the batch
+    #[serde(default)]
+    #[validate]
+    pub positive: Vec<RecommendExample>,
+
+    /// Try to avoid vectors like this, in every search of the batch
+    #[serde(default)]
+    #[validate]
+    pub negative: Vec<RecommendExample>,
+
+    #[validate]
+    pub searches: Vec<RecommendRequest>,
+}
+
+impl From<RecommendSharedRequestBatch> for RecommendRequestBatch {
+    fn from(batch: RecommendSharedRequestBatch) -> Self {
+        let RecommendSharedRequestBatch {
+            positive,
+            negative,
+            mut searches,
+        } = batch;
+        for search in &mut searches {
+            let request = &mut search.recommend_request;
+            request.positive.extend(positive.iter().cloned());
+            request.negative.extend(negative.iter().cloned());
+        }
+        Self { searches }
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
 #[serde(rename_all = "snake_case")]
 pub struct RecommendGroupsRequest {
@@ -781,11 +1238,220 @@ pub const fn default_exact_count() -> bool {
     true
 }
 
-#[derive(Debug, Serialize, JsonSchema)]
+#[derive(Debug, PartialEq, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct CountResult {
     /// Number of points which satisfy the conditions
     pub count: usize,
+    /// Whether the replicas, read according to the read consistency, returned the same count.
+    /// Only set if more than one replica was read.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub consistent: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct CountDistinctRequest {
+    #[serde(flatten)]
+    #[validate]
+    pub count_distinct_request: CountDistinctRequestInternal,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKeySelector>,
+}
+
+/// Count Distinct Request
+/// Counts the number of distinct values of the payload field among the points, which satisfy the
+/// given filter. The count is exact if the field has a keyword or integer index with lookup,
+/// otherwise it is estimated from payload.
+#[derive(Deserialize, Serialize, JsonSchema, Validate, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct CountDistinctRequestInternal {
+    /// Payload field to count the distinct values of
+    pub key: JsonPath,
+    /// Look only for points which satisfies this conditions
+    #[validate]
+    pub filter: Option<Filter>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct CountDistinctResult {
+    /// Number of distinct values of the field
+    pub count: usize,
+    /// If false, the count is an estimation
+    pub exact: bool,
+    /// Whether the replicas, read according to the read consistency, returned the same values.
+    /// Only set if more than one replica was read.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub consistent: Option<bool>,
+}
+
+impl From<Reconciled<DistinctValues>> for CountDistinctResult {
+    fn from(distinct: Reconciled<DistinctValues>) -> Self {
+        Self {
+            count: distinct.value.count(),
+            exact: distinct.value.is_exact(),
+            consistent: distinct.consistent,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct HistogramRequest {
+    #[serde(flatten)]
+    #[validate]
+    pub histogram_request: HistogramRequestInternal,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKeySelector>,
+}
+
+/// Histogram Request
+/// Builds the histogram of the values of a numeric payload field from its range index.
+/// Counts are approximate and include the values of all points in the collection.
+#[derive(Deserialize, Serialize, JsonSchema, Validate, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct HistogramRequestInternal {
+    /// Payload field with an integer, float or datetime index.
+    /// Datetime values are in microseconds since the Unix epoch
+    pub key: JsonPath,
+    /// How to split values into buckets
+    #[validate(custom = "validate_histogram_buckets")]
+    pub buckets: HistogramBuckets,
+}
+
+#[derive(Deserialize, Serialize, JsonSchema, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum HistogramBuckets {
+    /// Buckets of the same width, aligned to multiples of the width
+    Interval(f64),
+    /// Given number of buckets with about the same number of values in each
+    Quantiles(usize),
+}
+
+fn validate_histogram_buckets(buckets: &HistogramBuckets) -> Result<(), ValidationError> {
+    match *buckets {
+        HistogramBuckets::Interval(interval) if interval.is_finite() && interval > 0.0 => Ok(()),
+        HistogramBuckets::Interval(_) => {
+            let mut error = ValidationError::new("range");
+            error.message = Some("interval must be a positive number".into());
+            Err(error)
+        }
+        HistogramBuckets::Quantiles(quantiles) => {
+            validate_range_generic(quantiles, Some(1), Some(MAX_HISTOGRAM_BUCKETS))
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct HistogramResult {
+    /// Buckets in ascending order of values
+    pub buckets: Vec<HistogramBucket>,
+}
+
+impl HistogramResult {
+    /// Split the merged `distribution` into `buckets`
+    pub fn new(
+        distribution: &ValueDistribution,
+        buckets: HistogramBuckets,
+    ) -> CollectionResult<Self> {
+        let buckets = match buckets {
+            HistogramBuckets::Interval(interval) => distribution
+                .fixed_width_buckets(interval)
+                .ok_or_else(|| {
+                    CollectionError::bad_input(format!(
+                        "Interval {interval} is too small, histogram would have more than {MAX_HISTOGRAM_BUCKETS} buckets"
+                    ))
+                })?,
+            HistogramBuckets::Quantiles(quantiles) => distribution.quantile_buckets(quantiles),
+        };
+        Ok(Self { buckets })
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct FieldStatsRequest {
+    #[serde(flatten)]
+    #[validate]
+    pub field_stats_request: FieldStatsRequestInternal,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKeySelector>,
+}
+
+/// Field Stats Request
+/// Computes min, max, average and percentiles of the values of a numeric payload field among the
+/// points, which satisfy the given filter. Values are read from the range index of the field.
+#[derive(Deserialize, Serialize, JsonSchema, Validate, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct FieldStatsRequestInternal {
+    /// Payload field with an integer, float or datetime index.
+    /// Datetime values are in microseconds since the Unix epoch
+    pub key: JsonPath,
+    /// Look only for points which satisfies this conditions
+    #[validate]
+    pub filter: Option<Filter>,
+    /// Percentiles to compute, from 0 to 100. Default: 50, 95, 99
+    #[serde(default = "default_percentiles")]
+    #[validate(length(max = 100), custom = "validate_percentiles")]
+    pub percentiles: Vec<f64>,
+}
+
+pub fn default_percentiles() -> Vec<f64> {
+    vec![50.0, 95.0, 99.0]
+}
+
+#[allow(clippy::ptr_arg)] // Validator passes the field as is
+fn validate_percentiles(percentiles: &Vec<f64>) -> Result<(), ValidationError> {
+    percentiles
+        .iter()
+        .try_for_each(|&percentile| validate_range_generic(percentile, Some(0.0), Some(100.0)))
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct FieldStatsResult {
+    /// Number of values of the field
+    pub count: usize,
+    /// Smallest value, `null` if there are no values
+    pub min: Option<f64>,
+    /// Largest value, `null` if there are no values
+    pub max: Option<f64>,
+    /// Average value, `null` if there are no values
+    pub avg: Option<f64>,
+    /// Requested percentiles. Approximate, if there are many values
+    pub percentiles: Vec<PercentileValue>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct PercentileValue {
+    pub percentile: f64,
+    /// Value, below which the given percent of values are. `null` if there are no values
+    pub value: Option<f64>,
+}
+
+impl FieldStatsResult {
+    pub fn new(stats: &NumericStats, percentiles: &[f64]) -> Self {
+        let percentiles = percentiles
+            .iter()
+            .map(|&percentile| PercentileValue {
+                percentile,
+                value: stats.percentile(percentile),
+            })
+            .collect();
+        Self {
+            count: stats.count(),
+            min: stats.min(),
+            max: stats.max(),
+            avg: stats.avg(),
+            percentiles,
+        }
+    }
 }
 
 #[derive(Error, Debug, Clone, PartialEq)]
@@ -826,6 +1492,13 @@ pub enum CollectionError {
     PreConditionFailed { description: String },
     #[error("Object Store error: {what}")]
     ObjectStoreError { what: String },
+    #[error("Strict mode violation of `{setting}`: {description}")]
+    StrictMode {
+        setting: String,
+        description: String,
+    },
+    #[error("Rate limit exceeded: {description}")]
+    RateLimitExceeded { description: String },
 }
 
 impl CollectionError {
@@ -869,6 +1542,20 @@ impl CollectionError {
         CollectionError::ObjectStoreError { what: what.into() }
     }
 
+    /// Request is rejected by the strict mode `setting` of the collection
+    pub fn strict_mode(setting: impl Into<String>, description: impl Into<String>) -> Self {
+        CollectionError::StrictMode {
+            setting: setting.into(),
+            description: description.into(),
+        }
+    }
+
+    pub fn rate_limit_exceeded(description: impl Into<String>) -> CollectionError {
+        CollectionError::RateLimitExceeded {
+            description: description.into(),
+        }
+    }
+
     pub fn forward_proxy_error(peer_id: PeerId, error: impl Into<Self>) -> Self {
         Self::ForwardProxyError {
             peer_id,
@@ -919,6 +1606,9 @@ impl CollectionError {
             Self::InconsistentShardFailure { .. } => false,
             Self::ForwardProxyError { .. } => false,
             Self::ObjectStoreError { .. } => false,
+            Self::StrictMode { .. } => false,
+            // Rejected before being applied to any replica
+            Self::RateLimitExceeded { .. } => false,
         }
     }
 }
@@ -988,6 +1678,11 @@ impl From<OperationError> for CollectionError {
             },
             OperationError::WrongPayloadKey { description } => Self::BadInput { description },
             OperationError::MissingRangeIndexForOrderBy { .. } => Self::bad_input(format!("{err}")),
+            OperationError::MissingGeoIndexForOrderBy { .. } => Self::bad_input(format!("{err}")),
+            OperationError::MissingRangeIndexForHistogram { .. } => {
+                Self::bad_input(format!("{err}"))
+            }
+            OperationError::MissingRangeIndexForStats { .. } => Self::bad_input(format!("{err}")),
         }
     }
 }
@@ -1089,6 +1784,9 @@ impl From<tonic::Status> for CollectionError {
             tonic::Code::FailedPrecondition => CollectionError::PreConditionFailed {
                 description: format!("{err}"),
             },
+            tonic::Code::ResourceExhausted => CollectionError::RateLimitExceeded {
+                description: format!("{err}"),
+            },
             _other => CollectionError::ServiceError {
                 error: format!("Tonic status error: {err}"),
                 backtrace: Some(Backtrace::force_capture().to_string()),
@@ -1234,6 +1932,7 @@ impl TryFrom<Datatype> for SparseVectorIndexDatatype {
 
 /// Params of single vector data storage
 #[derive(Debug, Hash, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq)]
+#[validate(schema(function = "validate_vector_input_size"))]
 #[serde(rename_all = "snake_case")]
 pub struct VectorParams {
     /// Size of a vectors used
@@ -1263,6 +1962,32 @@ pub struct VectorParams {
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub multivec_config: Option<MultiVectorConfig>,
+
+    /// Size of the input vectors, if larger than `size`.
+    /// Input vectors, including query vectors, are truncated to the first `size` dimensions and
+    /// normalized to unit length. Useful for embeddings trained with Matryoshka Representation
+    /// Learning. Vectors of `size` dimensions are accepted as well.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(custom = "validate_nonzerou64_range_min_1_max_65536")]
+    pub input_size: Option<NonZeroU64>,
+}
+
+/// Input vectors can only be truncated to a smaller size, and are renormalized as floats
+fn validate_vector_input_size(params: &VectorParams) -> Result<(), ValidationError> {
+    let Some(input_size) = params.input_size else {
+        return Ok(());
+    };
+    if input_size <= params.size {
+        let mut error = ValidationError::new("input_size_not_greater_than_size");
+        error.message = Some("input_size must be greater than size".into());
+        return Err(error);
+    }
+    if params.datatype == Some(Datatype::Uint8) {
+        let mut error = ValidationError::new("input_size_with_uint8");
+        error.message = Some("input_size can't be used with uint8 datatype".into());
+        return Err(error);
+    }
+    Ok(())
 }
 
 /// Validate the value is in `[1, 65536]` or `None`.
@@ -1309,6 +2034,14 @@ pub struct SparseVectorParams {
     /// Default: none
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub modifier: Option<Modifier>,
+
+    /// Name of the shared vocabulary of the sparse vector.
+    /// IDF statistics are collected over all collections with the same vocabulary on this peer,
+    /// so that their scores are comparable. Only used with the `idf` modifier.
+    /// Default: statistics of the collection itself
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(length(min = 1, max = 255))]
+    pub vocabulary: Option<String>,
 }
 
 impl Anonymize for SparseVectorParams {
@@ -1316,6 +2049,7 @@ impl Anonymize for SparseVectorParams {
         Self {
             index: self.index.anonymize(),
             modifier: self.modifier.clone(),
+            vocabulary: self.vocabulary.clone(),
         }
     }
 }
@@ -1408,6 +2142,12 @@ impl VectorsConfig {
         }
     }
 
+    /// Whether any of the vectors is truncated from a larger input size
+    pub fn has_input_size(&self) -> bool {
+        self.params_iter()
+            .any(|(_, params)| params.input_size.is_some())
+    }
+
     pub fn get_params_mut(&mut self, name: &str) -> Option<&mut VectorParams> {
         match self {
             VectorsConfig::Single(params) => (name == DEFAULT_VECTOR_NAME).then_some(params),
@@ -1705,6 +2445,9 @@ impl Validate for SparseVectorsConfig {
 pub struct AliasDescription {
     pub alias_name: String,
     pub collection_name: String,
+    /// Filter, which is merged into every request made through the alias
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<Filter>,
 }
 
 #[derive(Debug, Serialize, JsonSchema)]
@@ -13,6 +13,7 @@ use segment::types::{
 };
 use segment::vector_storage::query::{ContextPair, ContextQuery, DiscoveryQuery, RecoQuery};
 
+use super::formula::FormulaInternal;
 use super::shard_query::{Fusion, ScoringQuery, ShardPrefetch, ShardQueryRequest};
 use crate::common::fetch_vectors::ReferencedVectors;
 use crate::common::retrieve_request_trait::RetrieveRequest;
@@ -29,7 +30,7 @@ pub struct CollectionQueryRequest {
     pub score_threshold: Option<ScoreType>,
     pub limit: usize,
     pub offset: usize,
-    /// Search params for when there is no prefetch
+    /// Search params. With prefetches, they are used to re-score the prefetched points by vector query
     pub params: Option<SearchParams>,
     pub with_vector: WithVector,
     pub with_payload: WithPayloadInterface,
@@ -50,14 +51,26 @@ pub enum Query {
     /// Score points against some vector(s)
     Vector(VectorQuery<VectorInput>),
 
-    /// Reciprocal rank fusion
+    /// Fuse the results of the prefetches
     Fusion(Fusion),
 
     /// Order by a payload field
     OrderBy(OrderBy),
+
+    /// Score the results of the prefetches by a formula
+    Formula(FormulaInternal),
 }
 
 impl Query {
+    /// Check, that the query can be applied to this number of prefetches
+    fn check_prefetches(&self, num_prefetches: usize) -> CollectionResult<()> {
+        match self {
+            Query::Fusion(fusion) => fusion.check_prefetches(num_prefetches),
+            Query::Formula(formula) => formula.check_prefetches(num_prefetches),
+            Query::Vector(_) | Query::OrderBy(_) => Ok(()),
+        }
+    }
+
     pub fn try_into_scoring_query(
         self,
         ids_to_vectors: &ReferencedVectors,
@@ -77,6 +90,7 @@ impl Query {
             }
             Query::Fusion(fusion) => ScoringQuery::Fusion(fusion),
             Query::OrderBy(order_by) => ScoringQuery::OrderBy(order_by),
+            Query::Formula(formula) => ScoringQuery::Formula(formula.parse()?),
         };
 
         Ok(scoring_query)
@@ -263,7 +277,7 @@ pub struct CollectionPrefetch {
     pub filter: Option<Filter>,
     pub score_threshold: Option<ScoreType>,
     pub limit: usize,
-    /// Search params for when there is no prefetch
+    /// Search params. With prefetches, they are used to re-score the prefetched points by vector query
     pub params: Option<SearchParams>,
     pub lookup_from: Option<LookupLocation>,
 }
@@ -294,6 +308,10 @@ impl CollectionPrefetch {
             ));
         }
 
+        if let Some(query) = &self.query {
+            query.check_prefetches(self.prefetch.len())?;
+        }
+
         let query = self
             .query
             .map(|query| {
@@ -342,6 +360,10 @@ impl CollectionQueryRequest {
             ));
         }
 
+        if let Some(query) = &self.query {
+            query.check_prefetches(self.prefetch.len())?;
+        }
+
         // Check we actually fetched all referenced vectors in this request (and nested prefetches)
         for &point_id in &self.get_referenced_point_ids() {
             if ids_to_vectors.get(&None, point_id).is_none() {
@@ -415,6 +437,7 @@ mod from_rest {
                 with_vector,
                 with_payload,
                 lookup_from,
+                payload_size_limit: _,
             } = value;
 
             Self {
@@ -476,6 +499,17 @@ mod from_rest {
                 rest::Query::Context(context) => Query::Vector(From::from(context.context)),
                 rest::Query::OrderBy(order_by) => Query::OrderBy(OrderBy::from(order_by.order_by)),
                 rest::Query::Fusion(fusion) => Query::Fusion(Fusion::from(fusion.fusion)),
+                rest::Query::WeightedFusion(fusion) => {
+                    let rest::WeightedFusion {
+                        weights,
+                        normalization,
+                    } = fusion.weighted_fusion;
+                    Query::Fusion(Fusion::WeightedSum {
+                        weights,
+                        normalization: normalization.map(From::from),
+                    })
+                }
+                rest::Query::Formula(formula) => Query::Formula(FormulaInternal::from(formula)),
             }
         }
     }
@@ -541,6 +575,8 @@ mod from_rest {
                     // TODO(universal-query): Validate at API level
                     Vector::MultiDense(MultiDenseVectorInternal::new_unchecked(multi_dense)),
                 ),
+                // Resolved into vectors by the API, see `From<rest::Vector>`
+                rest::VectorInput::Inference(_) => VectorInput::Vector(Vector::Dense(vec![])),
             }
         }
     }
@@ -590,6 +626,7 @@ pub mod from_grpc {
                 shard_key_selector: _,
                 lookup_from,
                 timeout: _,
+                payload_size_limit: _,
             } = value;
 
             let request = CollectionQueryRequest {
@@ -607,7 +644,7 @@ pub mod from_grpc {
                 offset: offset
                     .map(|o| o as usize)
                     .unwrap_or(CollectionQueryRequest::DEFAULT_OFFSET),
-                params: search_params.map(From::from),
+                params: search_params.map(TryFrom::try_from).transpose()?,
                 with_vector: with_vectors
                     .map(From::from)
                     .unwrap_or(CollectionQueryRequest::DEFAULT_WITH_VECTOR),
@@ -648,7 +685,7 @@ pub mod from_grpc {
                 limit: limit
                     .map(|l| l as usize)
                     .unwrap_or(CollectionQueryRequest::DEFAULT_LIMIT),
-                params: search_params.map(From::from),
+                params: search_params.map(TryFrom::try_from).transpose()?,
                 lookup_from: lookup_from.map(From::from),
             };
 
@@ -675,6 +712,8 @@ pub mod from_grpc {
                 Variant::Context(context) => Query::Vector(TryFrom::try_from(context)?),
                 Variant::OrderBy(order_by) => Query::OrderBy(OrderBy::try_from(order_by)?),
                 Variant::Fusion(fusion) => Query::Fusion(Fusion::try_from(fusion)?),
+                Variant::WeightedFusion(fusion) => Query::Fusion(Fusion::try_from(fusion)?),
+                Variant::Formula(formula) => Query::Formula(FormulaInternal::try_from(formula)?),
             };
 
             Ok(query)
@@ -0,0 +1,890 @@
+//! Formula, which scores the results of the prefetches by their scores, payload and attribute
+//! values
+//!
+//! The formula, as it comes from the API, can reference query-time parameters. They are
+//! substituted at the collection level, so that shards receive a [ParsedFormula] with concrete
+//! values only.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use api::grpc::conversions::{payload_to_proto, proto_to_payloads};
+use api::grpc::qdrant as grpc;
+use api::rest::schema as rest;
+use common::types::ScoreType;
+use segment::json_path::JsonPath;
+use segment::types::{AttributeValue, DateTimePayloadType, GeoPoint, Payload, PayloadContainer};
+use serde_json::Value;
+use tonic::Status;
+
+use crate::operations::types::{CollectionError, CollectionResult};
+
+/// Variable, which references the score of a prefetch
+const SCORE_VARIABLE: &str = "$score";
+
+const DEFAULT_DECAY_MIDPOINT: f32 = 0.5;
+
+/// Formula, as it is defined in the request
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormulaInternal {
+    pub formula: ExpressionInternal,
+    pub params: HashMap<String, Value>,
+    pub defaults: HashMap<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExpressionInternal {
+    Constant(f32),
+    Variable(String),
+    Param(String),
+    Attribute(String),
+    Mult(Vec<ExpressionInternal>),
+    Sum(Vec<ExpressionInternal>),
+    Neg(Box<ExpressionInternal>),
+    Div {
+        left: Box<ExpressionInternal>,
+        right: Box<ExpressionInternal>,
+        by_zero_default: Option<f32>,
+    },
+    GeoDistance {
+        origin: GeoOriginInternal,
+        to: JsonPath,
+    },
+    DatetimeDecay {
+        key: JsonPath,
+        target: DatetimeTargetInternal,
+        scale: f32,
+        midpoint: Option<f32>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GeoOriginInternal {
+    Point(GeoPoint),
+    Param(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DatetimeTargetInternal {
+    Datetime(String),
+    Param(String),
+}
+
+/// Formula with substituted parameters, ready to be evaluated for each point
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParsedFormula {
+    Constant(f32),
+    /// Score in the prefetch with this index. Points, which are not found by the prefetch,
+    /// get the default score.
+    Score {
+        index: usize,
+        default: ScoreType,
+    },
+    Payload {
+        key: JsonPath,
+        default: Option<f32>,
+    },
+    /// Value in the attribute column with this name
+    Attribute {
+        name: String,
+        default: Option<f32>,
+    },
+    Mult(Vec<ParsedFormula>),
+    Sum(Vec<ParsedFormula>),
+    Neg(Box<ParsedFormula>),
+    Div {
+        left: Box<ParsedFormula>,
+        right: Box<ParsedFormula>,
+        by_zero_default: f32,
+    },
+    GeoDistance {
+        origin: GeoPoint,
+        key: JsonPath,
+        default: Option<GeoPoint>,
+    },
+    DatetimeDecay {
+        key: JsonPath,
+        target: DateTimePayloadType,
+        scale: f32,
+        midpoint: f32,
+        default: Option<DateTimePayloadType>,
+    },
+}
+
+/// Parse `$score` or `$score[<index>]` into the index of the prefetch
+fn parse_score_variable(name: &str) -> Option<CollectionResult<usize>> {
+    let rest = name.strip_prefix(SCORE_VARIABLE)?;
+    if rest.is_empty() {
+        return Some(Ok(0));
+    }
+    let index = rest
+        .strip_prefix('[')
+        .and_then(|rest| rest.strip_suffix(']'))
+        .and_then(|index| index.parse().ok())
+        .ok_or_else(|| {
+            CollectionError::bad_request(format!(
+                "Invalid score variable `{name}`, expected `{SCORE_VARIABLE}` or `{SCORE_VARIABLE}[<index>]`"
+            ))
+        });
+    Some(index)
+}
+
+fn parse_geo_point(value: &Value, what: &str) -> CollectionResult<GeoPoint> {
+    serde_json::from_value(value.clone())
+        .map_err(|_| CollectionError::bad_request(format!("{what} is not a geo point")))
+}
+
+fn parse_datetime(value: &Value, what: &str) -> CollectionResult<DateTimePayloadType> {
+    value
+        .as_str()
+        .and_then(|value| DateTimePayloadType::from_str(value).ok())
+        .ok_or_else(|| {
+            CollectionError::bad_request(format!("{what} is not a datetime in RFC 3339 format"))
+        })
+}
+
+impl FormulaInternal {
+    /// Check, that referenced scores belong to the existing prefetches
+    pub fn check_prefetches(&self, num_prefetches: usize) -> CollectionResult<()> {
+        fn check(expression: &ExpressionInternal, num_prefetches: usize) -> CollectionResult<()> {
+            match expression {
+                ExpressionInternal::Variable(name) => {
+                    if let Some(index) = parse_score_variable(name) {
+                        let index = index?;
+                        if index >= num_prefetches {
+                            return Err(CollectionError::bad_request(format!(
+                                "Formula references `{name}`, but there are {num_prefetches} prefetches"
+                            )));
+                        }
+                    }
+                    Ok(())
+                }
+                ExpressionInternal::Mult(expressions) | ExpressionInternal::Sum(expressions) => {
+                    expressions
+                        .iter()
+                        .try_for_each(|expression| check(expression, num_prefetches))
+                }
+                ExpressionInternal::Neg(expression) => check(expression, num_prefetches),
+                ExpressionInternal::Div { left, right, .. } => {
+                    check(left, num_prefetches)?;
+                    check(right, num_prefetches)
+                }
+                ExpressionInternal::Constant(_)
+                | ExpressionInternal::Param(_)
+                | ExpressionInternal::Attribute(_)
+                | ExpressionInternal::GeoDistance { .. }
+                | ExpressionInternal::DatetimeDecay { .. } => Ok(()),
+            }
+        }
+        check(&self.formula, num_prefetches)
+    }
+
+    /// Substitute the parameters and resolve the variables
+    pub fn parse(self) -> CollectionResult<ParsedFormula> {
+        let FormulaInternal {
+            formula,
+            params,
+            defaults,
+        } = self;
+        Parser {
+            params: &params,
+            defaults: &defaults,
+        }
+        .parse(formula)
+    }
+}
+
+struct Parser<'a> {
+    params: &'a HashMap<String, Value>,
+    defaults: &'a HashMap<String, Value>,
+}
+
+impl Parser<'_> {
+    fn param(&self, name: &str) -> CollectionResult<&Value> {
+        self.params.get(name).ok_or_else(|| {
+            CollectionError::bad_request(format!("Formula parameter `{name}` is not provided"))
+        })
+    }
+
+    fn number(value: &Value, what: &str) -> CollectionResult<f32> {
+        value
+            .as_f64()
+            .map(|value| value as f32)
+            .ok_or_else(|| CollectionError::bad_request(format!("{what} is not a number")))
+    }
+
+    fn parse(&self, expression: ExpressionInternal) -> CollectionResult<ParsedFormula> {
+        let parsed = match expression {
+            ExpressionInternal::Constant(value) => ParsedFormula::Constant(value),
+            ExpressionInternal::Variable(name) => {
+                let default = self
+                    .defaults
+                    .get(&name)
+                    .map(|value| Self::number(value, &format!("Default of `{name}`")))
+                    .transpose()?;
+                match parse_score_variable(&name) {
+                    Some(index) => ParsedFormula::Score {
+                        index: index?,
+                        default: default.unwrap_or_default(),
+                    },
+                    None => ParsedFormula::Payload {
+                        key: JsonPath::try_from(name.as_str()).map_err(|_| {
+                            CollectionError::bad_request(format!(
+                                "Formula variable `{name}` is not a valid payload key"
+                            ))
+                        })?,
+                        default,
+                    },
+                }
+            }
+            ExpressionInternal::Param(name) => ParsedFormula::Constant(Self::number(
+                self.param(&name)?,
+                &format!("Parameter `{name}`"),
+            )?),
+            ExpressionInternal::Attribute(name) => {
+                let default = self
+                    .defaults
+                    .get(&name)
+                    .map(|value| Self::number(value, &format!("Default of `{name}`")))
+                    .transpose()?;
+                ParsedFormula::Attribute { name, default }
+            }
+            ExpressionInternal::Mult(expressions) => ParsedFormula::Mult(
+                expressions
+                    .into_iter()
+                    .map(|expression| self.parse(expression))
+                    .collect::<CollectionResult<_>>()?,
+            ),
+            ExpressionInternal::Sum(expressions) => ParsedFormula::Sum(
+                expressions
+                    .into_iter()
+                    .map(|expression| self.parse(expression))
+                    .collect::<CollectionResult<_>>()?,
+            ),
+            ExpressionInternal::Neg(expression) => {
+                ParsedFormula::Neg(Box::new(self.parse(*expression)?))
+            }
+            ExpressionInternal::Div {
+                left,
+                right,
+                by_zero_default,
+            } => ParsedFormula::Div {
+                left: Box::new(self.parse(*left)?),
+                right: Box::new(self.parse(*right)?),
+                by_zero_default: by_zero_default.unwrap_or_default(),
+            },
+            ExpressionInternal::GeoDistance { origin, to } => {
+                let origin = match origin {
+                    GeoOriginInternal::Point(point) => point,
+                    GeoOriginInternal::Param(name) => {
+                        parse_geo_point(self.param(&name)?, &format!("Parameter `{name}`"))?
+                    }
+                };
+                let default = self
+                    .defaults
+                    .get(&to.to_string())
+                    .map(|value| parse_geo_point(value, &format!("Default of `{to}`")))
+                    .transpose()?;
+                ParsedFormula::GeoDistance {
+                    origin,
+                    key: to,
+                    default,
+                }
+            }
+            ExpressionInternal::DatetimeDecay {
+                key,
+                target,
+                scale,
+                midpoint,
+            } => {
+                let target = match target {
+                    DatetimeTargetInternal::Datetime(datetime) => {
+                        parse_datetime(&Value::String(datetime), "Decay target")?
+                    }
+                    DatetimeTargetInternal::Param(name) => {
+                        parse_datetime(self.param(&name)?, &format!("Parameter `{name}`"))?
+                    }
+                };
+                if scale.is_nan() || scale <= 0.0 {
+                    return Err(CollectionError::bad_request(
+                        "Decay scale must be greater than 0",
+                    ));
+                }
+                let midpoint = midpoint.unwrap_or(DEFAULT_DECAY_MIDPOINT);
+                if !(midpoint > 0.0 && midpoint < 1.0) {
+                    return Err(CollectionError::bad_request(
+                        "Decay midpoint must be between 0 and 1",
+                    ));
+                }
+                let default = self
+                    .defaults
+                    .get(&key.to_string())
+                    .map(|value| parse_datetime(value, &format!("Default of `{key}`")))
+                    .transpose()?;
+                ParsedFormula::DatetimeDecay {
+                    key,
+                    target,
+                    scale,
+                    midpoint,
+                    default,
+                }
+            }
+        };
+        Ok(parsed)
+    }
+}
+
+impl ParsedFormula {
+    /// Payload keys, which values are needed to evaluate the formula
+    pub fn payload_keys(&self) -> Vec<JsonPath> {
+        let mut keys = Vec::new();
+        self.collect_payload_keys(&mut keys);
+        keys
+    }
+
+    fn collect_payload_keys(&self, keys: &mut Vec<JsonPath>) {
+        match self {
+            ParsedFormula::Payload { key, .. }
+            | ParsedFormula::GeoDistance { key, .. }
+            | ParsedFormula::DatetimeDecay { key, .. } => {
+                if !keys.contains(key) {
+                    keys.push(key.clone());
+                }
+            }
+            ParsedFormula::Mult(expressions) | ParsedFormula::Sum(expressions) => expressions
+                .iter()
+                .for_each(|expression| expression.collect_payload_keys(keys)),
+            ParsedFormula::Neg(expression) => expression.collect_payload_keys(keys),
+            ParsedFormula::Div { left, right, .. } => {
+                left.collect_payload_keys(keys);
+                right.collect_payload_keys(keys);
+            }
+            ParsedFormula::Constant(_)
+            | ParsedFormula::Score { .. }
+            | ParsedFormula::Attribute { .. } => {}
+        }
+    }
+
+    /// Whether the formula reads attribute columns of the points
+    pub fn has_attributes(&self) -> bool {
+        match self {
+            ParsedFormula::Attribute { .. } => true,
+            ParsedFormula::Mult(expressions) | ParsedFormula::Sum(expressions) => {
+                expressions.iter().any(ParsedFormula::has_attributes)
+            }
+            ParsedFormula::Neg(expression) => expression.has_attributes(),
+            ParsedFormula::Div { left, right, .. } => {
+                left.has_attributes() || right.has_attributes()
+            }
+            ParsedFormula::Constant(_)
+            | ParsedFormula::Score { .. }
+            | ParsedFormula::Payload { .. }
+            | ParsedFormula::GeoDistance { .. }
+            | ParsedFormula::DatetimeDecay { .. } => false,
+        }
+    }
+
+    /// Score of a point with the given scores in each prefetch, `None` if the prefetch didn't
+    /// find the point
+    pub fn evaluate(
+        &self,
+        scores: &[Option<ScoreType>],
+        payload: &Payload,
+        attributes: &HashMap<String, AttributeValue>,
+    ) -> CollectionResult<ScoreType> {
+        let missing = |key: &dyn std::fmt::Display| {
+            CollectionError::bad_request(format!(
+                "No value for the formula variable `{key}`, and no default is set"
+            ))
+        };
+        let score = match self {
+            ParsedFormula::Constant(value) => *value,
+            ParsedFormula::Score { index, default } => {
+                scores.get(*index).copied().flatten().unwrap_or(*default)
+            }
+            ParsedFormula::Payload { key, default } => payload
+                .get_value(key)
+                .iter()
+                .find_map(|value| value.as_f64())
+                .map(|value| value as f32)
+                .or(*default)
+                .ok_or_else(|| missing(key))?,
+            ParsedFormula::Attribute { name, default } => attributes
+                .get(name)
+                .map(|value| value.as_f64() as f32)
+                .or(*default)
+                .ok_or_else(|| missing(name))?,
+            ParsedFormula::Mult(expressions) => {
+                let mut product = 1.0;
+                for expression in expressions {
+                    product *= expression.evaluate(scores, payload, attributes)?;
+                }
+                product
+            }
+            ParsedFormula::Sum(expressions) => {
+                let mut sum = 0.0;
+                for expression in expressions {
+                    sum += expression.evaluate(scores, payload, attributes)?;
+                }
+                sum
+            }
+            ParsedFormula::Neg(expression) => -expression.evaluate(scores, payload, attributes)?,
+            ParsedFormula::Div {
+                left,
+                right,
+                by_zero_default,
+            } => {
+                let right = right.evaluate(scores, payload, attributes)?;
+                if right == 0.0 {
+                    *by_zero_default
+                } else {
+                    left.evaluate(scores, payload, attributes)? / right
+                }
+            }
+            ParsedFormula::GeoDistance {
+                origin,
+                key,
+                default,
+            } => {
+                let point = payload
+                    .get_value(key)
+                    .iter()
+                    .find_map(|value| serde_json::from_value::<GeoPoint>((*value).clone()).ok())
+                    .or_else(|| default.clone())
+                    .ok_or_else(|| missing(key))?;
+                origin.distance(&point) as f32
+            }
+            ParsedFormula::DatetimeDecay {
+                key,
+                target,
+                scale,
+                midpoint,
+                default,
+            } => {
+                let datetime = payload
+                    .get_value(key)
+                    .iter()
+                    .find_map(|value| value.as_str())
+                    .and_then(|value| DateTimePayloadType::from_str(value).ok())
+                    .or(*default)
+                    .ok_or_else(|| missing(key))?;
+                let distance_sec =
+                    (datetime.timestamp() - target.timestamp()).unsigned_abs() as f64 / 1_000_000.0;
+                let lambda = f64::from(*midpoint).ln() / f64::from(*scale);
+                (lambda * distance_sec).exp() as f32
+            }
+        };
+        Ok(score)
+    }
+}
+
+mod from_rest {
+    use super::*;
+
+    impl From<rest::FormulaQuery> for FormulaInternal {
+        fn from(value: rest::FormulaQuery) -> Self {
+            let rest::FormulaQuery {
+                formula,
+                params,
+                defaults,
+            } = value;
+            Self {
+                formula: ExpressionInternal::from(formula),
+                params,
+                defaults,
+            }
+        }
+    }
+
+    impl From<rest::Expression> for ExpressionInternal {
+        fn from(value: rest::Expression) -> Self {
+            let boxed = |expression: Box<rest::Expression>| Box::new(Self::from(*expression));
+            match value {
+                rest::Expression::Constant(value) => ExpressionInternal::Constant(value),
+                rest::Expression::Variable(name) => ExpressionInternal::Variable(name),
+                rest::Expression::Param(param) => ExpressionInternal::Param(param.param),
+                rest::Expression::Attribute(attribute) => {
+                    ExpressionInternal::Attribute(attribute.attribute)
+                }
+                rest::Expression::Mult(mult) => {
+                    ExpressionInternal::Mult(mult.mult.into_iter().map(Self::from).collect())
+                }
+                rest::Expression::Sum(sum) => {
+                    ExpressionInternal::Sum(sum.sum.into_iter().map(Self::from).collect())
+                }
+                rest::Expression::Neg(neg) => ExpressionInternal::Neg(boxed(neg.neg)),
+                rest::Expression::Div(div) => {
+                    let rest::DivParams {
+                        left,
+                        right,
+                        by_zero_default,
+                    } = div.div;
+                    ExpressionInternal::Div {
+                        left: boxed(left),
+                        right: boxed(right),
+                        by_zero_default,
+                    }
+                }
+                rest::Expression::GeoDistance(geo_distance) => {
+                    let rest::GeoDistanceParams { origin, to } = geo_distance.geo_distance;
+                    let origin = match origin {
+                        rest::GeoOrigin::Point(point) => GeoOriginInternal::Point(point),
+                        rest::GeoOrigin::Param(param) => GeoOriginInternal::Param(param.param),
+                    };
+                    ExpressionInternal::GeoDistance { origin, to }
+                }
+                rest::Expression::DatetimeDecay(decay) => {
+                    let rest::DatetimeDecayParams {
+                        key,
+                        target,
+                        scale,
+                        midpoint,
+                    } = decay.datetime_decay;
+                    let target = match target {
+                        rest::DatetimeTarget::Datetime(datetime) => {
+                            DatetimeTargetInternal::Datetime(datetime)
+                        }
+                        rest::DatetimeTarget::Param(param) => {
+                            DatetimeTargetInternal::Param(param.param)
+                        }
+                    };
+                    ExpressionInternal::DatetimeDecay {
+                        key,
+                        target,
+                        scale,
+                        midpoint,
+                    }
+                }
+            }
+        }
+    }
+}
+
+mod from_grpc {
+    use api::grpc::conversions::json_path_from_proto;
+
+    use super::*;
+
+    fn json_map_from_proto(
+        map: HashMap<String, grpc::Value>,
+    ) -> Result<HashMap<String, Value>, Status> {
+        Ok(proto_to_payloads(map)?.0.into_iter().collect())
+    }
+
+    impl TryFrom<grpc::Formula> for FormulaInternal {
+        type Error = Status;
+
+        fn try_from(value: grpc::Formula) -> Result<Self, Self::Error> {
+            let grpc::Formula {
+                expression,
+                defaults,
+                params,
+            } = value;
+            let expression =
+                expression.ok_or_else(|| Status::invalid_argument("missing field: expression"))?;
+            Ok(Self {
+                formula: ExpressionInternal::try_from(expression)?,
+                params: json_map_from_proto(params)?,
+                defaults: json_map_from_proto(defaults)?,
+            })
+        }
+    }
+
+    impl TryFrom<grpc::Expression> for ExpressionInternal {
+        type Error = Status;
+
+        fn try_from(value: grpc::Expression) -> Result<Self, Self::Error> {
+            use grpc::expression::Variant;
+
+            let required = |expression: Option<Box<grpc::Expression>>, field: &str| {
+                expression
+                    .ok_or_else(|| Status::invalid_argument(format!("missing field: {field}")))
+                    .and_then(|expression| Self::try_from(*expression))
+                    .map(Box::new)
+            };
+
+            let variant = value
+                .variant
+                .ok_or_else(|| Status::invalid_argument("Expression variant is missing"))?;
+            let expression = match variant {
+                Variant::Constant(value) => ExpressionInternal::Constant(value),
+                Variant::Variable(name) => ExpressionInternal::Variable(name),
+                Variant::Param(name) => ExpressionInternal::Param(name),
+                Variant::Attribute(name) => ExpressionInternal::Attribute(name),
+                Variant::Mult(mult) => ExpressionInternal::Mult(
+                    mult.mult
+                        .into_iter()
+                        .map(Self::try_from)
+                        .collect::<Result<_, _>>()?,
+                ),
+                Variant::Sum(sum) => ExpressionInternal::Sum(
+                    sum.sum
+                        .into_iter()
+                        .map(Self::try_from)
+                        .collect::<Result<_, _>>()?,
+                ),
+                Variant::Neg(neg) => ExpressionInternal::Neg(Box::new(Self::try_from(*neg)?)),
+                Variant::Div(div) => {
+                    let grpc::DivExpression {
+                        left,
+                        right,
+                        by_zero_default,
+                    } = *div;
+                    ExpressionInternal::Div {
+                        left: required(left, "left")?,
+                        right: required(right, "right")?,
+                        by_zero_default,
+                    }
+                }
+                Variant::GeoDistance(geo_distance) => {
+                    let grpc::GeoDistance { origin, to } = geo_distance;
+                    let origin = match origin
+                        .ok_or_else(|| Status::invalid_argument("missing field: origin"))?
+                    {
+                        grpc::geo_distance::Origin::Point(point) => {
+                            GeoOriginInternal::Point(GeoPoint::from(point))
+                        }
+                        grpc::geo_distance::Origin::OriginParam(name) => {
+                            GeoOriginInternal::Param(name)
+                        }
+                    };
+                    ExpressionInternal::GeoDistance {
+                        origin,
+                        to: json_path_from_proto(&to)?,
+                    }
+                }
+                Variant::DatetimeDecay(decay) => {
+                    let grpc::DatetimeDecay {
+                        key,
+                        target,
+                        scale,
+                        midpoint,
+                    } = decay;
+                    let target = match target
+                        .ok_or_else(|| Status::invalid_argument("missing field: target"))?
+                    {
+                        grpc::datetime_decay::Target::Datetime(datetime) => {
+                            DatetimeTargetInternal::Datetime(datetime)
+                        }
+                        grpc::datetime_decay::Target::TargetParam(name) => {
+                            DatetimeTargetInternal::Param(name)
+                        }
+                    };
+                    ExpressionInternal::DatetimeDecay {
+                        key: json_path_from_proto(&key)?,
+                        target,
+                        scale,
+                        midpoint,
+                    }
+                }
+            };
+            Ok(expression)
+        }
+    }
+
+    /// Parsed formula is sent to remote shards without parameters, with defaults collected back
+    /// into the map
+    impl From<ParsedFormula> for grpc::Formula {
+        fn from(value: ParsedFormula) -> Self {
+            let mut defaults = serde_json::Map::new();
+            let expression = value.into_grpc(&mut defaults);
+            Self {
+                expression: Some(expression),
+                defaults: payload_to_proto(Payload(defaults)),
+                params: HashMap::new(),
+            }
+        }
+    }
+
+    impl ParsedFormula {
+        fn into_grpc(self, defaults: &mut serde_json::Map<String, Value>) -> grpc::Expression {
+            use grpc::expression::Variant;
+
+            let variant = match self {
+                ParsedFormula::Constant(value) => Variant::Constant(value),
+                ParsedFormula::Score { index, default } => {
+                    let name = format!("{SCORE_VARIABLE}[{index}]");
+                    defaults.insert(name.clone(), Value::from(default));
+                    Variant::Variable(name)
+                }
+                ParsedFormula::Payload { key, default } => {
+                    let name = key.to_string();
+                    if let Some(default) = default {
+                        defaults.insert(name.clone(), Value::from(default));
+                    }
+                    Variant::Variable(name)
+                }
+                ParsedFormula::Attribute { name, default } => {
+                    if let Some(default) = default {
+                        defaults.insert(name.clone(), Value::from(default));
+                    }
+                    Variant::Attribute(name)
+                }
+                ParsedFormula::Mult(expressions) => Variant::Mult(grpc::MultExpression {
+                    mult: expressions
+                        .into_iter()
+                        .map(|expression| expression.into_grpc(defaults))
+                        .collect(),
+                }),
+                ParsedFormula::Sum(expressions) => Variant::Sum(grpc::SumExpression {
+                    sum: expressions
+                        .into_iter()
+                        .map(|expression| expression.into_grpc(defaults))
+                        .collect(),
+                }),
+                ParsedFormula::Neg(expression) => {
+                    Variant::Neg(Box::new(expression.into_grpc(defaults)))
+                }
+                ParsedFormula::Div {
+                    left,
+                    right,
+                    by_zero_default,
+                } => Variant::Div(Box::new(grpc::DivExpression {
+                    left: Some(Box::new(left.into_grpc(defaults))),
+                    right: Some(Box::new(right.into_grpc(defaults))),
+                    by_zero_default: Some(by_zero_default),
+                })),
+                ParsedFormula::GeoDistance {
+                    origin,
+                    key,
+                    default,
+                } => {
+                    if let Some(default) = default {
+                        defaults.insert(
+                            key.to_string(),
+                            serde_json::to_value(default).unwrap_or_default(),
+                        );
+                    }
+                    Variant::GeoDistance(grpc::GeoDistance {
+                        origin: Some(grpc::geo_distance::Origin::Point(grpc::GeoPoint::from(
+                            origin,
+                        ))),
+                        to: key.to_string(),
+                    })
+                }
+                ParsedFormula::DatetimeDecay {
+                    key,
+                    target,
+                    scale,
+                    midpoint,
+                    default,
+                } => {
+                    if let Some(default) = default {
+                        defaults.insert(key.to_string(), Value::from(default.0.to_rfc3339()));
+                    }
+                    Variant::DatetimeDecay(grpc::DatetimeDecay {
+                        key: key.to_string(),
+                        target: Some(grpc::datetime_decay::Target::Datetime(
+                            target.0.to_rfc3339(),
+                        )),
+                        scale,
+                        midpoint: Some(midpoint),
+                    })
+                }
+            };
+            grpc::Expression {
+                variant: Some(variant),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn parse(query: Value) -> CollectionResult<ParsedFormula> {
+        let query: rest::FormulaQuery = serde_json::from_value(query).unwrap();
+        FormulaInternal::from(query).parse()
+    }
+
+    #[test]
+    fn test_formula_with_params() {
+        let formula = parse(json!({
+            "formula": {"sum": [
+                "$score",
+                {"mult": [{"param": "boost"}, "$score[1]"]},
+                {"neg": {"geo_distance": {"origin": {"param": "location"}, "to": "city.location"}}},
+                {"datetime_decay": {"key": "created_at", "target": {"param": "now"}, "scale": 3600}},
+            ]},
+            "params": {
+                "boost": 2.0,
+                "location": {"lat": 52.52, "lon": 13.405},
+                "now": "2024-05-01T12:00:00Z",
+            },
+            "defaults": {"$score[1]": 0.25},
+        }))
+        .unwrap();
+
+        assert_eq!(
+            formula.payload_keys(),
+            vec![
+                JsonPath::try_from("city.location").unwrap(),
+                JsonPath::try_from("created_at").unwrap(),
+            ],
+        );
+
+        let payload: Payload = serde_json::from_value(json!({
+            "city": {"location": {"lat": 52.52, "lon": 13.405}},
+            "created_at": "2024-05-01T11:00:00Z",
+        }))
+        .unwrap();
+        // Missing score of the second prefetch is taken from defaults, the point is at the origin
+        // and an hour old, so the decay is at the midpoint
+        let score = formula
+            .evaluate(&[Some(1.0), None], &payload, &HashMap::new())
+            .unwrap();
+        assert!((score - (1.0 + 2.0 * 0.25 + 0.5)).abs() < 1e-5);
+
+        // Same formula, as it is sent to remote shards
+        let remote = FormulaInternal::try_from(grpc::Formula::from(formula.clone()))
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(remote, formula);
+
+        // Payload variable without a value and a default
+        assert!(formula
+            .evaluate(&[Some(1.0)], &Payload::default(), &HashMap::new())
+            .is_err());
+    }
+
+    #[test]
+    fn test_formula_with_attributes() {
+        let formula = parse(json!({
+            "formula": {"mult": ["$score", {"attribute": "boost"}]},
+            "defaults": {"boost": 0.5},
+        }))
+        .unwrap();
+        assert!(formula.payload_keys().is_empty());
+        assert!(formula.has_attributes());
+
+        let attributes = HashMap::from([("boost".to_string(), AttributeValue::Integer(3))]);
+        let score = formula.evaluate(&[Some(2.0)], &Payload::default(), &attributes);
+        assert_eq!(score.unwrap(), 6.0);
+
+        // Points without the attribute get the default
+        let score = formula.evaluate(&[Some(2.0)], &Payload::default(), &HashMap::new());
+        assert_eq!(score.unwrap(), 1.0);
+
+        let remote = FormulaInternal::try_from(grpc::Formula::from(formula.clone()))
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(remote, formula);
+    }
+
+    #[test]
+    fn test_formula_errors() {
+        assert!(parse(json!({"formula": {"param": "missing"}})).is_err());
+        assert!(parse(json!({"formula": "$score[x]"})).is_err());
+
+        let formula: rest::FormulaQuery =
+            serde_json::from_value(json!({"formula": {"sum": ["$score", "$score[2]"]}})).unwrap();
+        let formula = FormulaInternal::from(formula);
+        assert!(formula.check_prefetches(3).is_ok());
+        assert!(formula.check_prefetches(2).is_err());
+    }
+}
@@ -2,7 +2,7 @@
 
 use api::rest::OrderByInterface;
 use common::types::ScoreType;
-use segment::types::{Filter, WithPayloadInterface, WithVector};
+use segment::types::{Filter, SearchParams, WithPayloadInterface, WithVector};
 
 use super::shard_query::{ScoringQuery, ShardPrefetch, ShardQueryRequest};
 use crate::operations::types::{
@@ -43,6 +43,9 @@ pub struct RescoreParams {
     /// Keep only points with better score than this threshold
     pub score_threshold: Option<ScoreType>,
 
+    /// Search params, e.g. quantization rescoring options, used when re-scoring with vectors
+    pub params: Option<SearchParams>,
+
     /// The vector(s) to return
     pub with_vector: WithVector,
 
@@ -140,6 +143,7 @@ impl PlannedQuery {
                         limit,
                         offset,
                         score_threshold,
+                        params,
                         with_vector,
                         with_payload,
                     }),
@@ -170,6 +174,11 @@ impl PlannedQuery {
                         "cannot apply Fusion without prefetches".to_string(),
                     ))
                 }
+                Some(ScoringQuery::Formula(_)) => {
+                    return Err(CollectionError::bad_request(
+                        "cannot apply Formula without prefetches".to_string(),
+                    ))
+                }
                 Some(ScoringQuery::OrderBy(order_by)) => {
                     // Everything should come from 1 scroll
                     let scroll = QueryScrollRequestInternal {
@@ -264,6 +273,7 @@ fn recurse_prefetches(
                     rescore,
                     limit,
                     score_threshold,
+                    params,
                     offset: 0, // Only apply offset at the root
                     with_vector: with_vector.clone(),
                     with_payload: with_payload.clone(),
@@ -296,6 +306,11 @@ fn recurse_prefetches(
                         "cannot apply Fusion without prefetches".to_string(),
                     ))
                 }
+                Some(ScoringQuery::Formula(_)) => {
+                    return Err(CollectionError::bad_request(
+                        "cannot apply Formula without prefetches".to_string(),
+                    ))
+                }
                 Some(ScoringQuery::OrderBy(order_by)) => {
                     let scroll = QueryScrollRequestInternal {
                         order_by: Some(OrderByInterface::Struct(order_by)),
@@ -354,7 +369,8 @@ mod tests {
     use segment::data_types::vectors::{MultiDenseVectorInternal, NamedVectorStruct, Vector};
     use segment::json_path::JsonPath;
     use segment::types::{
-        Condition, FieldCondition, Filter, Match, SearchParams, WithPayloadInterface, WithVector,
+        Condition, FieldCondition, Filter, Match, QuantizationSearchParams, SearchParams,
+        WithPayloadInterface, WithVector,
     };
     use sparse::common::sparse_vector::SparseVector;
 
@@ -453,6 +469,7 @@ mod tests {
                         limit: 100,
                         offset: 0,
                         score_threshold: None,
+                        params: None,
                         with_vector: WithVector::Bool(false),
                         with_payload: WithPayloadInterface::Bool(false),
                     })
@@ -469,6 +486,7 @@ mod tests {
                     limit: 10,
                     offset: 0,
                     score_threshold: None,
+                    params: None,
                     with_vector: WithVector::Bool(true),
                     with_payload: WithPayloadInterface::Bool(true),
                 })
@@ -711,6 +729,65 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_nested_prefetch_stage_params() {
+        let dummy_vector = vec![1.0, 2.0, 3.0];
+        let rescore_params = Some(SearchParams {
+            quantization: Some(QuantizationSearchParams {
+                rescore: Some(true),
+                oversampling: Some(2.0),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+
+        let request = ShardQueryRequest {
+            prefetches: vec![ShardPrefetch {
+                prefetches: vec![ShardPrefetch {
+                    prefetches: Vec::new(),
+                    query: Some(ScoringQuery::Vector(QueryEnum::Nearest(
+                        NamedVectorStruct::new_from_vector(
+                            Vector::Dense(dummy_vector.clone()),
+                            "byte",
+                        ),
+                    ))),
+                    limit: 1000,
+                    params: None,
+                    filter: None,
+                    score_threshold: Some(0.2),
+                }],
+                query: Some(ScoringQuery::Vector(QueryEnum::Nearest(
+                    NamedVectorStruct::new_from_vector(Vector::Dense(dummy_vector.clone()), "full"),
+                ))),
+                limit: 100,
+                params: rescore_params,
+                filter: None,
+                score_threshold: Some(0.7),
+            }],
+            query: Some(ScoringQuery::Fusion(Fusion::Rrf)),
+            filter: None,
+            score_threshold: None,
+            limit: 10,
+            offset: 0,
+            params: None,
+            with_payload: WithPayloadInterface::Bool(false),
+            with_vector: WithVector::Bool(false),
+        };
+
+        let planned_query = PlannedQuery::try_from(vec![request]).unwrap();
+
+        assert_eq!(planned_query.searches[0].score_threshold, Some(0.2));
+
+        // The intermediate stage is evaluated with its own limit, threshold and params
+        let Source::Prefetch(stage) = &planned_query.root_plans[0].sources[0] else {
+            panic!("expected a nested prefetch");
+        };
+        let stage_params = stage.rescore_params.as_ref().unwrap();
+        assert_eq!(stage_params.limit, 100);
+        assert_eq!(stage_params.score_threshold, Some(0.7));
+        assert_eq!(stage_params.params, rescore_params);
+    }
+
     pub fn make_prefetches_at_depth(depth: usize) -> ShardPrefetch {
         // recursive helper for accumulation
         pub fn make_prefetches_at_depth_acc(depth: usize, acc: ShardPrefetch) -> ShardPrefetch {
@@ -921,6 +998,7 @@ mod tests {
                                 limit: 10,
                                 offset: 0,
                                 score_threshold: None,
+                                params: None,
                                 with_vector: WithVector::Bool(true),
                                 with_payload: WithPayloadInterface::Bool(true),
                             }),
@@ -1,15 +1,17 @@
 use api::grpc::qdrant as grpc;
 use common::types::ScoreType;
 use itertools::Itertools;
+use segment::common::weighted_sum_fusion::ScoreNormalization;
 use segment::data_types::order_by::OrderBy;
 use segment::data_types::vectors::{NamedQuery, NamedVectorStruct, Vector, DEFAULT_VECTOR_NAME};
 use segment::types::{Filter, Order, ScoredPoint, SearchParams, WithPayloadInterface, WithVector};
 use segment::vector_storage::query::{ContextQuery, DiscoveryQuery, RecoQuery};
 use tonic::Status;
 
+use super::formula::{FormulaInternal, ParsedFormula};
 use crate::config::CollectionParams;
 use crate::operations::query_enum::QueryEnum;
-use crate::operations::types::CollectionResult;
+use crate::operations::types::{CollectionError, CollectionResult};
 
 /// Internal response type for a universal query request.
 ///
@@ -27,7 +29,7 @@ pub struct ShardQueryRequest {
     pub score_threshold: Option<ScoreType>,
     pub limit: usize,
     pub offset: usize,
-    /// Search params for when there is no prefetch
+    /// Search params. With prefetches, they are used to re-score the prefetched points by vector query
     pub params: Option<SearchParams>,
     pub with_vector: WithVector,
     pub with_payload: WithPayloadInterface,
@@ -46,6 +48,30 @@ impl ShardQueryRequest {
 #[derive(Debug, Clone, PartialEq)]
 pub enum Fusion {
     Rrf,
+    /// Weighted sum of the scores, one weight per prefetch
+    WeightedSum {
+        weights: Vec<ScoreType>,
+        /// Normalization of the scores of each prefetch, applied before the weights
+        normalization: Option<ScoreNormalization>,
+    },
+}
+
+impl Fusion {
+    /// Check, that the fusion can be applied to the given number of prefetches
+    pub fn check_prefetches(&self, num_prefetches: usize) -> CollectionResult<()> {
+        match self {
+            Fusion::Rrf => Ok(()),
+            Fusion::WeightedSum { weights, .. } => {
+                if weights.len() != num_prefetches {
+                    return Err(CollectionError::bad_request(format!(
+                        "Weighted fusion has {} weights, but there are {num_prefetches} prefetches. Provide one weight per prefetch.",
+                        weights.len(),
+                    )));
+                }
+                Ok(())
+            }
+        }
+    }
 }
 
 /// Same as `Query`, but with the resolved vector references.
@@ -54,20 +80,23 @@ pub enum ScoringQuery {
     /// Score points against some vector(s)
     Vector(QueryEnum),
 
-    /// Reciprocal rank fusion
+    /// Fuse the results of the prefetches
     Fusion(Fusion),
 
     /// Order by a payload field
     OrderBy(OrderBy),
+
+    /// Score the results of the prefetches by a formula
+    Formula(ParsedFormula),
 }
 
 impl ScoringQuery {
     pub fn needs_intermediate_results(&self) -> bool {
         match self {
             ScoringQuery::Fusion(fusion) => match fusion {
-                Fusion::Rrf => true,
+                Fusion::Rrf | Fusion::WeightedSum { .. } => true,
             },
-            ScoringQuery::Vector(_) | ScoringQuery::OrderBy(_) => false,
+            ScoringQuery::Vector(_) | ScoringQuery::OrderBy(_) | ScoringQuery::Formula(_) => false,
         }
     }
 
@@ -82,6 +111,7 @@ impl ScoringQuery {
     /// Returns the expected order of results, depending on the type of query
     pub fn order(
         opt_self: Option<&Self>,
+        params: Option<&SearchParams>,
         collection_params: &CollectionParams,
     ) -> CollectionResult<Order> {
         let order = match opt_self {
@@ -89,16 +119,17 @@ impl ScoringQuery {
                 ScoringQuery::Vector(query_enum) => {
                     if query_enum.is_distance_scored() {
                         collection_params
-                            .get_distance(query_enum.get_vector_name())?
+                            .get_search_distance(query_enum.get_vector_name(), params)?
                             .distance_order()
                     } else {
                         Order::LargeBetter
                     }
                 }
                 ScoringQuery::Fusion(fusion) => match fusion {
-                    Fusion::Rrf => Order::LargeBetter,
+                    Fusion::Rrf | Fusion::WeightedSum { .. } => Order::LargeBetter,
                 },
                 ScoringQuery::OrderBy(order_by) => Order::from(order_by.direction()),
+                ScoringQuery::Formula(_) => Order::LargeBetter,
             },
             None => {
                 // Order by ID
@@ -185,7 +216,7 @@ impl TryFrom<grpc::QueryShardPoints> for ShardQueryRequest {
             score_threshold,
             limit: limit as usize,
             offset: offset as usize,
-            params: params.map(SearchParams::from),
+            params: params.map(SearchParams::try_from).transpose()?,
             with_vector: with_vectors
                 .map(WithVector::from)
                 .unwrap_or(WithVector::Bool(false)),
@@ -222,7 +253,7 @@ impl TryFrom<grpc::query_shard_points::Prefetch> for ShardPrefetch {
                 .map(|query| ScoringQuery::try_from_grpc_query(query, using))
                 .transpose()?,
             limit: limit as usize,
-            params: params.map(SearchParams::from),
+            params: params.map(SearchParams::try_from).transpose()?,
             filter: filter.map(Filter::try_from).transpose()?,
             score_threshold,
         };
@@ -296,11 +327,29 @@ impl From<api::grpc::qdrant::Fusion> for Fusion {
     }
 }
 
-impl From<Fusion> for api::grpc::qdrant::Fusion {
-    fn from(fusion: Fusion) -> Self {
-        match fusion {
-            Fusion::Rrf => api::grpc::qdrant::Fusion::Rrf,
-        }
+impl TryFrom<api::grpc::qdrant::WeightedFusion> for Fusion {
+    type Error = tonic::Status;
+
+    fn try_from(fusion: api::grpc::qdrant::WeightedFusion) -> Result<Self, Self::Error> {
+        let api::grpc::qdrant::WeightedFusion {
+            weights,
+            normalization,
+        } = fusion;
+        let normalization = normalization
+            .map(|normalization| {
+                api::grpc::qdrant::ScoreNormalization::from_i32(normalization)
+                    .map(ScoreNormalization::from)
+                    .ok_or_else(|| {
+                        tonic::Status::invalid_argument(format!(
+                            "invalid score normalization value {normalization}",
+                        ))
+                    })
+            })
+            .transpose()?;
+        Ok(Fusion::WeightedSum {
+            weights,
+            normalization,
+        })
     }
 }
 
@@ -322,6 +371,14 @@ impl ScoringQuery {
             grpc::query_shard_points::query::Score::OrderBy(order_by) => {
                 ScoringQuery::OrderBy(OrderBy::try_from(order_by)?)
             }
+            grpc::query_shard_points::query::Score::WeightedFusion(fusion) => {
+                ScoringQuery::Fusion(Fusion::try_from(fusion)?)
+            }
+            grpc::query_shard_points::query::Score::Formula(formula) => ScoringQuery::Formula(
+                FormulaInternal::try_from(formula)?
+                    .parse()
+                    .map_err(|err| Status::invalid_argument(format!("Invalid formula: {err}")))?,
+            ),
         };
 
         Ok(scoring_query)
@@ -359,12 +416,25 @@ impl From<ScoringQuery> for grpc::query_shard_points::Query {
             ScoringQuery::Vector(query) => Self {
                 score: Some(Score::Vector(grpc::RawQuery::from(query))),
             },
-            ScoringQuery::Fusion(fusion) => Self {
-                score: Some(Score::Fusion(api::grpc::qdrant::Fusion::from(fusion) as i32)),
+            ScoringQuery::Fusion(Fusion::Rrf) => Self {
+                score: Some(Score::Fusion(api::grpc::qdrant::Fusion::Rrf as i32)),
+            },
+            ScoringQuery::Fusion(Fusion::WeightedSum {
+                weights,
+                normalization,
+            }) => Self {
+                score: Some(Score::WeightedFusion(grpc::WeightedFusion {
+                    weights,
+                    normalization: normalization
+                        .map(|normalization| grpc::ScoreNormalization::from(normalization) as i32),
+                })),
             },
             ScoringQuery::OrderBy(order_by) => Self {
                 score: Some(Score::OrderBy(grpc::OrderBy::from(order_by))),
             },
+            ScoringQuery::Formula(formula) => Self {
+                score: Some(Score::Formula(grpc::Formula::from(formula))),
+            },
         }
     }
 }
@@ -20,6 +20,7 @@ impl VectorParamsBuilder {
                 on_disk: None,
                 datatype: None,
                 multivec_config: None,
+                input_size: None,
             },
         }
     }
@@ -49,6 +50,11 @@ impl VectorParamsBuilder {
         self
     }
 
+    pub fn with_input_size(mut self, input_size: u64) -> Self {
+        self.vector_params.input_size = NonZeroU64::new(input_size);
+        self
+    }
+
     pub fn build(self) -> VectorParams {
         self.vector_params
     }
@@ -0,0 +1,335 @@
+use api::rest::{BatchVectorStruct, Vector, VectorStruct};
+use segment::common::operation_error::{OperationError, OperationResult};
+use segment::data_types::vectors::{
+    MultiDenseVectorInternal, Named, NamedQuery, NamedVectorStruct, Vector as QueryVector,
+    DEFAULT_VECTOR_NAME,
+};
+use segment::spaces::simple::cosine_preprocess;
+use segment::types::VectorElementType;
+use segment::vector_storage::query::TransformInto;
+
+use super::point_ops::{PointInsertOperationsInternal, PointOperations};
+use super::query_enum::QueryEnum;
+use super::types::{CollectionResult, VectorParams, VectorsConfig};
+use super::universal_query::shard_query::{ScoringQuery, ShardPrefetch, ShardQueryRequest};
+use super::vector_ops::VectorOperations;
+use super::CollectionUpdateOperations;
+
+/// Truncate a dense vector of the input size to the stored size, and normalize it to unit length.
+///
+/// Vectors, which already have the stored size, are kept as is.
+fn truncate_dense(
+    name: &str,
+    params: &VectorParams,
+    vector: &mut Vec<VectorElementType>,
+) -> OperationResult<()> {
+    let Some(input_size) = params.input_size else {
+        return Ok(());
+    };
+    let size = params.size.get() as usize;
+    let input_size = input_size.get() as usize;
+
+    if vector.len() == input_size {
+        vector.truncate(size);
+        *vector = cosine_preprocess(std::mem::take(vector));
+        Ok(())
+    } else if vector.len() == size {
+        Ok(())
+    } else {
+        Err(OperationError::ValidationError {
+            description: format!(
+                "Wrong input dimension of vector `{name}`: expected {input_size} or {size}, got {}",
+                vector.len(),
+            ),
+        })
+    }
+}
+
+fn truncate_multi_dense(
+    name: &str,
+    params: &VectorParams,
+    vectors: &mut [Vec<VectorElementType>],
+) -> OperationResult<()> {
+    vectors
+        .iter_mut()
+        .try_for_each(|vector| truncate_dense(name, params, vector))
+}
+
+fn truncate_vector(config: &VectorsConfig, name: &str, vector: &mut Vector) -> OperationResult<()> {
+    let Some(params) = config.get_params(name) else {
+        return Ok(());
+    };
+    match vector {
+        Vector::Dense(vector) => truncate_dense(name, params, vector),
+        Vector::MultiDense(vectors) => truncate_multi_dense(name, params, vectors),
+        Vector::Sparse(_) | Vector::Inference(_) => Ok(()),
+    }
+}
+
+fn truncate_struct(config: &VectorsConfig, vector: &mut VectorStruct) -> OperationResult<()> {
+    let default_params = || config.get_params(DEFAULT_VECTOR_NAME);
+    match vector {
+        VectorStruct::Single(vector) => match default_params() {
+            Some(params) => truncate_dense(DEFAULT_VECTOR_NAME, params, vector),
+            None => Ok(()),
+        },
+        VectorStruct::MultiDense(vectors) => match default_params() {
+            Some(params) => truncate_multi_dense(DEFAULT_VECTOR_NAME, params, vectors),
+            None => Ok(()),
+        },
+        VectorStruct::Named(vectors) => vectors
+            .iter_mut()
+            .try_for_each(|(name, vector)| truncate_vector(config, name, vector)),
+        VectorStruct::Inference(_) => Ok(()),
+    }
+}
+
+fn truncate_batch(config: &VectorsConfig, vectors: &mut BatchVectorStruct) -> OperationResult<()> {
+    let default_params = config.get_params(DEFAULT_VECTOR_NAME);
+    match vectors {
+        BatchVectorStruct::Single(vectors) => match default_params {
+            Some(params) => vectors
+                .iter_mut()
+                .try_for_each(|vector| truncate_dense(DEFAULT_VECTOR_NAME, params, vector)),
+            None => Ok(()),
+        },
+        BatchVectorStruct::MultiDense(vectors) => match default_params {
+            Some(params) => vectors
+                .iter_mut()
+                .try_for_each(|vectors| truncate_multi_dense(DEFAULT_VECTOR_NAME, params, vectors)),
+            None => Ok(()),
+        },
+        BatchVectorStruct::Named(vectors) => {
+            for (name, vectors) in vectors.iter_mut() {
+                for vector in vectors {
+                    truncate_vector(config, name, vector)?;
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Truncate the vectors, inserted or updated by the operation, to the stored size of vectors
+/// with a larger `input_size`.
+///
+/// Vectors are truncated before the operation is split by shards, so that the rest of the
+/// ingestion pipeline only sees vectors of the stored size.
+pub fn truncate_input_vectors(
+    config: &VectorsConfig,
+    operation: &mut CollectionUpdateOperations,
+) -> CollectionResult<()> {
+    if !config.has_input_size() {
+        return Ok(());
+    }
+    let result = match operation {
+        CollectionUpdateOperations::PointOperation(operation) => {
+            let insert = match operation {
+                PointOperations::UpsertPoints(insert) => insert,
+                PointOperations::UpsertPointsConditional(operation) => &mut operation.points_op,
+                _ => return Ok(()),
+            };
+            match insert {
+                PointInsertOperationsInternal::PointsList(points) => points
+                    .iter_mut()
+                    .try_for_each(|point| truncate_struct(config, &mut point.vector)),
+                PointInsertOperationsInternal::PointsBatch(batch) => {
+                    truncate_batch(config, &mut batch.vectors)
+                }
+            }
+        }
+        CollectionUpdateOperations::VectorOperation(VectorOperations::UpdateVectors(update)) => {
+            update
+                .points
+                .iter_mut()
+                .try_for_each(|point| truncate_struct(config, &mut point.vector))
+        }
+        _ => Ok(()),
+    };
+    Ok(result?)
+}
+
+fn truncate_query_vector(
+    name: &str,
+    params: &VectorParams,
+    vector: QueryVector,
+) -> OperationResult<QueryVector> {
+    match vector {
+        QueryVector::Dense(mut vector) => {
+            truncate_dense(name, params, &mut vector)?;
+            Ok(QueryVector::Dense(vector))
+        }
+        QueryVector::MultiDense(vector) => {
+            let mut vectors: Vec<_> = vector.multi_vectors().map(<[_]>::to_vec).collect();
+            truncate_multi_dense(name, params, &mut vectors)?;
+            Ok(QueryVector::MultiDense(
+                MultiDenseVectorInternal::new_unchecked(vectors),
+            ))
+        }
+        QueryVector::Sparse(_) => Ok(vector),
+    }
+}
+
+fn truncate_named_query<T>(
+    config: &VectorsConfig,
+    query: NamedQuery<T>,
+) -> CollectionResult<NamedQuery<T>>
+where
+    T: TransformInto<T, QueryVector, QueryVector>,
+{
+    let NamedQuery { query, using } = query;
+    let name = using.as_deref().unwrap_or(DEFAULT_VECTOR_NAME);
+    let Some(params) = config.get_params(name) else {
+        return Ok(NamedQuery { query, using });
+    };
+    let query = query.transform(|vector| truncate_query_vector(name, params, vector))?;
+    Ok(NamedQuery { query, using })
+}
+
+/// Truncate the query vectors to the stored size of vectors with a larger `input_size`, so that
+/// query vectors of the input size can be used.
+pub fn truncate_query_vectors(
+    config: &VectorsConfig,
+    query: QueryEnum,
+) -> CollectionResult<QueryEnum> {
+    if !config.has_input_size() {
+        return Ok(query);
+    }
+    match query {
+        QueryEnum::Nearest(vector) => {
+            let Some(params) = config.get_params(vector.get_name()) else {
+                return Ok(QueryEnum::Nearest(vector));
+            };
+            let vector = match vector {
+                NamedVectorStruct::Default(mut vector) => {
+                    truncate_dense(DEFAULT_VECTOR_NAME, params, &mut vector)?;
+                    NamedVectorStruct::Default(vector)
+                }
+                NamedVectorStruct::Dense(mut named) => {
+                    truncate_dense(&named.name, params, &mut named.vector)?;
+                    NamedVectorStruct::Dense(named)
+                }
+                NamedVectorStruct::MultiDense(mut named) => {
+                    let QueryVector::MultiDense(vector) = truncate_query_vector(
+                        &named.name,
+                        params,
+                        QueryVector::MultiDense(named.vector),
+                    )?
+                    else {
+                        unreachable!("multi-dense vector stays multi-dense");
+                    };
+                    named.vector = vector;
+                    NamedVectorStruct::MultiDense(named)
+                }
+                NamedVectorStruct::Sparse(named) => NamedVectorStruct::Sparse(named),
+            };
+            Ok(QueryEnum::Nearest(vector))
+        }
+        QueryEnum::RecommendBestScore(query) => Ok(QueryEnum::RecommendBestScore(
+            truncate_named_query(config, query)?,
+        )),
+        QueryEnum::Discover(query) => Ok(QueryEnum::Discover(truncate_named_query(config, query)?)),
+        QueryEnum::Context(query) => Ok(QueryEnum::Context(truncate_named_query(config, query)?)),
+    }
+}
+
+fn truncate_scoring_query(
+    config: &VectorsConfig,
+    query: &mut Option<ScoringQuery>,
+) -> CollectionResult<()> {
+    if let Some(ScoringQuery::Vector(vector_query)) = query {
+        *vector_query = truncate_query_vectors(config, vector_query.clone())?;
+    }
+    Ok(())
+}
+
+fn truncate_prefetch(config: &VectorsConfig, prefetch: &mut ShardPrefetch) -> CollectionResult<()> {
+    truncate_scoring_query(config, &mut prefetch.query)?;
+    prefetch
+        .prefetches
+        .iter_mut()
+        .try_for_each(|prefetch| truncate_prefetch(config, prefetch))
+}
+
+/// Truncate the query vectors of a universal query request and all of its prefetches
+pub fn truncate_shard_query_vectors(
+    config: &VectorsConfig,
+    request: &mut ShardQueryRequest,
+) -> CollectionResult<()> {
+    if !config.has_input_size() {
+        return Ok(());
+    }
+    truncate_scoring_query(config, &mut request.query)?;
+    request
+        .prefetches
+        .iter_mut()
+        .try_for_each(|prefetch| truncate_prefetch(config, prefetch))
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::types::Distance;
+
+    use super::*;
+    use crate::operations::point_ops::PointStruct;
+    use crate::operations::vector_params_builder::VectorParamsBuilder;
+
+    fn config() -> VectorsConfig {
+        VectorsConfig::Single(
+            VectorParamsBuilder::new(2, Distance::Dot)
+                .with_input_size(4)
+                .build(),
+        )
+    }
+
+    #[test]
+    fn test_truncate_input_vectors() {
+        let mut operation = CollectionUpdateOperations::PointOperation(
+            PointOperations::UpsertPoints(PointInsertOperationsInternal::PointsList(vec![
+                PointStruct {
+                    id: 1.into(),
+                    vector: VectorStruct::Single(vec![3.0, 4.0, 1.0, 1.0]),
+                    payload: None,
+                },
+                PointStruct {
+                    id: 2.into(),
+                    vector: VectorStruct::Single(vec![0.5, 0.5]),
+                    payload: None,
+                },
+            ])),
+        );
+        truncate_input_vectors(&config(), &mut operation).unwrap();
+
+        let CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+            PointInsertOperationsInternal::PointsList(points),
+        )) = operation
+        else {
+            panic!("operation is changed");
+        };
+        assert_eq!(points[0].vector, VectorStruct::Single(vec![0.6, 0.8]));
+        // Vectors of the stored size are kept as is
+        assert_eq!(points[1].vector, VectorStruct::Single(vec![0.5, 0.5]));
+    }
+
+    #[test]
+    fn test_truncate_wrong_dimension() {
+        let mut operation =
+            CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                PointInsertOperationsInternal::PointsList(vec![PointStruct {
+                    id: 1.into(),
+                    vector: VectorStruct::Single(vec![1.0, 2.0, 3.0]),
+                    payload: None,
+                }]),
+            ));
+        assert!(truncate_input_vectors(&config(), &mut operation).is_err());
+    }
+
+    #[test]
+    fn test_truncate_query_vectors() {
+        let query = truncate_query_vectors(&config(), QueryEnum::from(vec![0.0, 5.0, 1.0, 1.0]));
+        assert_eq!(
+            query.unwrap(),
+            QueryEnum::Nearest(NamedVectorStruct::Default(vec![0.0, 1.0])),
+        );
+    }
+}
@@ -0,0 +1,768 @@
+use segment::json_path::{JsonPath, JsonPathInterface as _};
+use segment::types::{
+    Condition, DateTimePayloadType, Filter, Payload, PayloadSchemaType, SearchParams,
+};
+use serde_json::Value;
+
+use crate::collection::payload_index_schema::PayloadIndexSchema;
+use crate::config::{PayloadFieldConstraints, StrictModeConfig};
+use crate::grouping::group_by::{GroupRequest, SourceRequest};
+use crate::operations::attribute_ops::AttributeOps;
+use crate::operations::payload_ops::{ConditionalSetPayloadOp, PayloadOps, SetPayloadOp};
+use crate::operations::point_ops::{
+    ConditionalDeleteOperationInternal, ConditionalInsertOperationInternal,
+    PointInsertOperationsInternal, PointOperations, WritePrecondition,
+};
+use crate::operations::types::{
+    CollectionError, CollectionResult, CoreSearchRequest, CountDistinctRequestInternal,
+    CountRequestInternal, DiscoverRequestInternal, FieldStatsRequestInternal, HistogramBuckets,
+    HistogramRequestInternal, RecommendRequestInternal, ScrollRequestInternal,
+};
+use crate::operations::universal_query::collection_query::{
+    CollectionPrefetch, CollectionQueryRequest,
+};
+use crate::operations::vector_ops::VectorOperations;
+use crate::operations::CollectionUpdateOperations;
+
+/// Checks requests against the enabled strict mode limits of a collection
+pub struct StrictModeChecker<'a> {
+    config: &'a StrictModeConfig,
+    payload_schema: &'a PayloadIndexSchema,
+}
+
+impl<'a> StrictModeChecker<'a> {
+    pub fn new(config: &'a StrictModeConfig, payload_schema: &'a PayloadIndexSchema) -> Self {
+        Self {
+            config,
+            payload_schema,
+        }
+    }
+
+    pub fn check_limit(&self, limit: usize) -> CollectionResult<()> {
+        match self.config.max_query_limit {
+            Some(max_limit) if limit > max_limit => Err(CollectionError::strict_mode(
+                "max_query_limit",
+                format!("limit {limit} is greater than the max of {max_limit}"),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn check_search_params(&self, params: Option<&SearchParams>) -> CollectionResult<()> {
+        let Some(params) = params else {
+            return Ok(());
+        };
+
+        if let (Some(hnsw_ef), Some(max_hnsw_ef)) = (params.hnsw_ef, self.config.search_max_hnsw_ef)
+        {
+            if hnsw_ef > max_hnsw_ef {
+                return Err(CollectionError::strict_mode(
+                    "search_max_hnsw_ef",
+                    format!("hnsw_ef {hnsw_ef} is greater than the max of {max_hnsw_ef}"),
+                ));
+            }
+        }
+
+        if params.exact && self.config.search_allow_exact == Some(false) {
+            return Err(CollectionError::strict_mode(
+                "search_allow_exact",
+                "exact search is not allowed",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Check filter of a read request
+    pub fn check_read_filter(&self, filter: Option<&Filter>) -> CollectionResult<()> {
+        if self.config.unindexed_filtering_retrieve == Some(false) {
+            self.check_filter_indexed(filter, "unindexed_filtering_retrieve")?;
+        }
+        Ok(())
+    }
+
+    /// Check filter of an update request
+    pub fn check_update_filter(&self, filter: Option<&Filter>) -> CollectionResult<()> {
+        if self.config.unindexed_filtering_update == Some(false) {
+            self.check_filter_indexed(filter, "unindexed_filtering_update")?;
+        }
+        Ok(())
+    }
+
+    pub fn check_payload(&self, payload: Option<&Payload>) -> CollectionResult<()> {
+        let (Some(payload), Some(max_size)) = (payload, self.config.max_payload_size_bytes) else {
+            return Ok(());
+        };
+
+        let size = serde_json::to_vec(payload)?.len();
+        if size > max_size {
+            return Err(CollectionError::strict_mode(
+                "max_payload_size_bytes",
+                format!("payload of {size} bytes is larger than the max of {max_size} bytes"),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Check payload against the payload schema of the collection
+    ///
+    /// `partial` payloads are merged into the existing ones, so they are not checked for the
+    /// presence of required keys.
+    pub fn check_payload_schema(
+        &self,
+        payload: Option<&Payload>,
+        partial: bool,
+    ) -> CollectionResult<()> {
+        let Some(schema) = &self.config.payload_schema else {
+            return Ok(());
+        };
+
+        let mut violations = Vec::new();
+        if !partial {
+            let missing = schema
+                .fields
+                .iter()
+                .filter(|(_, field)| field.required == Some(true))
+                .filter(|(key, _)| payload.map_or(true, |payload| !payload.0.contains_key(*key)));
+            for (key, _) in missing {
+                violations.push(format!("`{key}`: required key is missing"));
+            }
+        }
+        for (key, value) in payload.into_iter().flat_map(|payload| payload.0.iter()) {
+            match schema.fields.get(key) {
+                Some(field) => {
+                    if let Some(violation) = field_violation(field, value) {
+                        violations.push(format!("`{key}`: {violation}"));
+                    }
+                }
+                None if schema.allow_unknown_fields == Some(false) => {
+                    violations.push(format!("`{key}`: key is not in the schema"));
+                }
+                None => {}
+            }
+        }
+
+        Self::payload_schema_result(violations)
+    }
+
+    /// Check payload, which is set into a nested `key`, against the payload schema
+    ///
+    /// Nested keys can only be set in the values, which are objects.
+    pub fn check_nested_payload_schema(&self, key: &JsonPath) -> CollectionResult<()> {
+        let Some(schema) = &self.config.payload_schema else {
+            return Ok(());
+        };
+
+        let head = key.head();
+        let violation = match schema.fields.get(head) {
+            Some(PayloadFieldConstraints {
+                value_type: Some(value_type),
+                ..
+            }) if *value_type != PayloadSchemaType::Geo => Some(format!(
+                "nested key can't be set in a value of type {}",
+                value_type.name(),
+            )),
+            Some(_) => None,
+            None if schema.allow_unknown_fields == Some(false) => {
+                Some("key is not in the schema".to_string())
+            }
+            None => None,
+        };
+
+        Self::payload_schema_result(violation.map(|violation| format!("`{head}`: {violation}")))
+    }
+
+    /// Check that payload keys, which are deleted, are not required by the payload schema
+    ///
+    /// Nested keys can be deleted, the required key itself stays in the payload.
+    pub fn check_deleted_payload_keys(&self, keys: &[JsonPath]) -> CollectionResult<()> {
+        let Some(schema) = &self.config.payload_schema else {
+            return Ok(());
+        };
+
+        let violations = keys
+            .iter()
+            .filter(|key| key.rest.is_empty())
+            .filter(|key| {
+                schema
+                    .fields
+                    .get(key.head())
+                    .is_some_and(|field| field.required == Some(true))
+            })
+            .map(|key| format!("`{key}`: required key can't be deleted"));
+
+        Self::payload_schema_result(violations)
+    }
+
+    /// Check that payload can be cleared, i.e. the payload schema has no required keys
+    pub fn check_clear_payload(&self) -> CollectionResult<()> {
+        let Some(schema) = &self.config.payload_schema else {
+            return Ok(());
+        };
+
+        let violations = schema
+            .fields
+            .iter()
+            .filter(|(_, field)| field.required == Some(true))
+            .map(|(key, _)| format!("`{key}`: required key can't be cleared"));
+
+        Self::payload_schema_result(violations)
+    }
+
+    fn payload_schema_result(violations: impl IntoIterator<Item = String>) -> CollectionResult<()> {
+        let violations = violations.into_iter().collect::<Vec<_>>();
+        if violations.is_empty() {
+            return Ok(());
+        }
+        Err(CollectionError::strict_mode(
+            "payload_schema",
+            format!(
+                "payload doesn't match the schema: {}",
+                violations.join("; ")
+            ),
+        ))
+    }
+
+    fn check_filter_indexed(&self, filter: Option<&Filter>, setting: &str) -> CollectionResult<()> {
+        let mut unindexed = Vec::new();
+        if let Some(filter) = filter {
+            self.collect_unindexed(filter, None, &mut unindexed);
+        }
+
+        if unindexed.is_empty() {
+            return Ok(());
+        }
+
+        let keys = unindexed
+            .iter()
+            .map(|key| format!("`{key}`"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        Err(CollectionError::strict_mode(
+            setting,
+            format!("filtering by fields without payload index: {keys}"),
+        ))
+    }
+
+    fn collect_unindexed(
+        &self,
+        filter: &Filter,
+        nested_path: Option<&JsonPath>,
+        unindexed: &mut Vec<JsonPath>,
+    ) {
+        let conditions = filter
+            .should
+            .iter()
+            .chain(&filter.must)
+            .chain(&filter.must_not)
+            .flatten()
+            .chain(filter.min_should.iter().flat_map(|m| &m.conditions));
+
+        for condition in conditions {
+            let key = match condition {
+                Condition::Field(field) => &field.key,
+                Condition::IsEmpty(is_empty) => &is_empty.is_empty.key,
+                Condition::IsNull(is_null) => &is_null.is_null.key,
+                Condition::Nested(nested) => {
+                    let full_path = JsonPath::extend_or_new(nested_path, &nested.array_key());
+                    self.collect_unindexed(nested.filter(), Some(&full_path), unindexed);
+                    continue;
+                }
+                Condition::Filter(filter) => {
+                    self.collect_unindexed(filter, nested_path, unindexed);
+                    continue;
+                }
+                Condition::HasId(_)
+                | Condition::HasVector(_)
+                | Condition::Attribute(_)
+                | Condition::Resharding(_) => continue,
+            };
+
+            let full_path = JsonPath::extend_or_new(nested_path, key);
+            if !self.payload_schema.schema.contains_key(&full_path)
+                && !unindexed.contains(&full_path)
+            {
+                unindexed.push(full_path);
+            }
+        }
+    }
+}
+
+fn field_violation(field: &PayloadFieldConstraints, value: &Value) -> Option<String> {
+    match value {
+        Value::Null if field.required == Some(true) => Some("required value is null".to_string()),
+        Value::Null => None,
+        Value::Array(values) => {
+            if let Some(max_array_length) = field.max_array_length {
+                if values.len() > max_array_length {
+                    return Some(format!(
+                        "array of {} elements is longer than the max of {max_array_length}",
+                        values.len(),
+                    ));
+                }
+            }
+            values
+                .iter()
+                .find_map(|value| element_violation(field, value))
+        }
+        _ => element_violation(field, value),
+    }
+}
+
+fn element_violation(field: &PayloadFieldConstraints, value: &Value) -> Option<String> {
+    if let Some(value_type) = field.value_type {
+        if !matches_type(value, value_type) {
+            return Some(format!(
+                "expected {}, got {}",
+                value_type.name(),
+                json_type_name(value),
+            ));
+        }
+    }
+
+    if let (Some(max_length), Value::String(string)) = (field.max_length, value) {
+        let length = string.chars().count();
+        if length > max_length {
+            return Some(format!(
+                "string of {length} characters is longer than the max of {max_length}"
+            ));
+        }
+    }
+
+    None
+}
+
+fn matches_type(value: &Value, value_type: PayloadSchemaType) -> bool {
+    match value_type {
+        PayloadSchemaType::Keyword | PayloadSchemaType::Text | PayloadSchemaType::MinHash => {
+            value.is_string()
+        }
+        PayloadSchemaType::Integer => value.is_i64(),
+        PayloadSchemaType::BigInt => value.is_i64() || value.is_u64(),
+        PayloadSchemaType::Float => value.is_number(),
+        PayloadSchemaType::Bool => value.is_boolean(),
+        PayloadSchemaType::Geo => segment::types::value_type(value) == Some(PayloadSchemaType::Geo),
+        PayloadSchemaType::Datetime => value
+            .as_str()
+            .is_some_and(|value| value.parse::<DateTimePayloadType>().is_ok()),
+    }
+}
+
+fn json_type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "bool",
+        Value::Number(number) if number.is_f64() => "float",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Request, which can be checked against the strict mode limits of the collection
+pub trait StrictModeVerification {
+    fn check_strict_mode(&self, checker: &StrictModeChecker) -> CollectionResult<()>;
+}
+
+impl<T: StrictModeVerification> StrictModeVerification for [T] {
+    fn check_strict_mode(&self, checker: &StrictModeChecker) -> CollectionResult<()> {
+        self.iter()
+            .try_for_each(|request| request.check_strict_mode(checker))
+    }
+}
+
+impl<T: StrictModeVerification, S> StrictModeVerification for (T, S) {
+    fn check_strict_mode(&self, checker: &StrictModeChecker) -> CollectionResult<()> {
+        self.0.check_strict_mode(checker)
+    }
+}
+
+impl StrictModeVerification for CoreSearchRequest {
+    fn check_strict_mode(&self, checker: &StrictModeChecker) -> CollectionResult<()> {
+        checker.check_limit(self.limit)?;
+        checker.check_search_params(self.params.as_ref())?;
+        checker.check_read_filter(self.filter.as_ref())
+    }
+}
+
+impl StrictModeVerification for RecommendRequestInternal {
+    fn check_strict_mode(&self, checker: &StrictModeChecker) -> CollectionResult<()> {
+        checker.check_limit(self.limit)?;
+        checker.check_search_params(self.params.as_ref())?;
+        checker.check_read_filter(self.filter.as_ref())
+    }
+}
+
+impl StrictModeVerification for DiscoverRequestInternal {
+    fn check_strict_mode(&self, checker: &StrictModeChecker) -> CollectionResult<()> {
+        checker.check_limit(self.limit)?;
+        checker.check_search_params(self.params.as_ref())?;
+        checker.check_read_filter(self.filter.as_ref())
+    }
+}
+
+impl StrictModeVerification for GroupRequest {
+    fn check_strict_mode(&self, checker: &StrictModeChecker) -> CollectionResult<()> {
+        checker.check_limit(self.limit.saturating_mul(self.group_size))?;
+        match &self.source {
+            SourceRequest::Search(request) => {
+                checker.check_search_params(request.params.as_ref())?;
+                checker.check_read_filter(request.filter.as_ref())
+            }
+            SourceRequest::Recommend(request) => {
+                checker.check_search_params(request.params.as_ref())?;
+                checker.check_read_filter(request.filter.as_ref())
+            }
+        }
+    }
+}
+
+impl StrictModeVerification for CollectionPrefetch {
+    fn check_strict_mode(&self, checker: &StrictModeChecker) -> CollectionResult<()> {
+        checker.check_limit(self.limit)?;
+        checker.check_search_params(self.params.as_ref())?;
+        checker.check_read_filter(self.filter.as_ref())?;
+        self.prefetch.check_strict_mode(checker)
+    }
+}
+
+impl StrictModeVerification for CollectionQueryRequest {
+    fn check_strict_mode(&self, checker: &StrictModeChecker) -> CollectionResult<()> {
+        checker.check_limit(self.limit)?;
+        checker.check_search_params(self.params.as_ref())?;
+        checker.check_read_filter(self.filter.as_ref())?;
+        self.prefetch.check_strict_mode(checker)
+    }
+}
+
+impl StrictModeVerification for ScrollRequestInternal {
+    fn check_strict_mode(&self, checker: &StrictModeChecker) -> CollectionResult<()> {
+        let limit = self
+            .limit
+            .or(ScrollRequestInternal::default().limit)
+            .unwrap_or_default();
+        checker.check_limit(limit)?;
+        checker.check_read_filter(self.filter.as_ref())
+    }
+}
+
+impl StrictModeVerification for CountRequestInternal {
+    fn check_strict_mode(&self, checker: &StrictModeChecker) -> CollectionResult<()> {
+        checker.check_read_filter(self.filter.as_ref())
+    }
+}
+
+impl StrictModeVerification for CountDistinctRequestInternal {
+    fn check_strict_mode(&self, checker: &StrictModeChecker) -> CollectionResult<()> {
+        checker.check_read_filter(self.filter.as_ref())
+    }
+}
+
+impl StrictModeVerification for FieldStatsRequestInternal {
+    fn check_strict_mode(&self, checker: &StrictModeChecker) -> CollectionResult<()> {
+        checker.check_read_filter(self.filter.as_ref())
+    }
+}
+
+impl StrictModeVerification for HistogramRequestInternal {
+    fn check_strict_mode(&self, checker: &StrictModeChecker) -> CollectionResult<()> {
+        match self.buckets {
+            // Number of buckets is the number of values in the response
+            HistogramBuckets::Quantiles(quantiles) => checker.check_limit(quantiles),
+            HistogramBuckets::Interval(_) => Ok(()),
+        }
+    }
+}
+
+fn check_precondition(
+    precondition: &WritePrecondition,
+    checker: &StrictModeChecker,
+) -> CollectionResult<()> {
+    checker.check_update_filter(precondition.filter.as_ref())
+}
+
+fn check_insert_payloads(
+    points: &PointInsertOperationsInternal,
+    checker: &StrictModeChecker,
+) -> CollectionResult<()> {
+    match points {
+        PointInsertOperationsInternal::PointsBatch(batch) => batch
+            .payloads
+            .iter()
+            .flatten()
+            .try_for_each(|payload| check_point_payload(payload.as_ref(), checker)),
+        PointInsertOperationsInternal::PointsList(points) => points
+            .iter()
+            .try_for_each(|point| check_point_payload(point.payload.as_ref(), checker)),
+    }
+}
+
+fn check_point_payload(
+    payload: Option<&Payload>,
+    checker: &StrictModeChecker,
+) -> CollectionResult<()> {
+    checker.check_payload(payload)?;
+    checker.check_payload_schema(payload, false)
+}
+
+fn check_set_payload(
+    op: &SetPayloadOp,
+    partial: bool,
+    checker: &StrictModeChecker,
+) -> CollectionResult<()> {
+    checker.check_payload(Some(&op.payload))?;
+    match &op.key {
+        Some(key) => checker.check_nested_payload_schema(key)?,
+        None => checker.check_payload_schema(Some(&op.payload), partial)?,
+    }
+    checker.check_update_filter(op.filter.as_ref())
+}
+
+impl StrictModeVerification for CollectionUpdateOperations {
+    fn check_strict_mode(&self, checker: &StrictModeChecker) -> CollectionResult<()> {
+        match self {
+            CollectionUpdateOperations::PointOperation(op) => match op {
+                PointOperations::UpsertPoints(points) => check_insert_payloads(points, checker),
+                PointOperations::UpsertPointsConditional(ConditionalInsertOperationInternal {
+                    points_op,
+                    precondition,
+                }) => {
+                    check_insert_payloads(points_op, checker)?;
+                    check_precondition(precondition, checker)
+                }
+                PointOperations::DeletePoints { .. } => Ok(()),
+                PointOperations::DeletePointsConditional(ConditionalDeleteOperationInternal {
+                    ids: _,
+                    precondition,
+                }) => check_precondition(precondition, checker),
+                PointOperations::DeletePointsByFilter(filter) => {
+                    checker.check_update_filter(Some(filter))
+                }
+                PointOperations::SyncPoints(sync) => sync
+                    .points
+                    .iter()
+                    .try_for_each(|point| check_point_payload(point.payload.as_ref(), checker)),
+            },
+            CollectionUpdateOperations::VectorOperation(op) => match op {
+                VectorOperations::UpdateVectors(_) | VectorOperations::DeleteVectors(..) => Ok(()),
+                VectorOperations::DeleteVectorsByFilter(filter, _) => {
+                    checker.check_update_filter(Some(filter))
+                }
+            },
+            CollectionUpdateOperations::PayloadOperation(op) => match op {
+                PayloadOps::SetPayload(op) | PayloadOps::PatchPayload(op) => {
+                    check_set_payload(op, true, checker)
+                }
+                PayloadOps::OverwritePayload(op) => check_set_payload(op, false, checker),
+                PayloadOps::SetPayloadConditional(ConditionalSetPayloadOp {
+                    payload_op,
+                    precondition,
+                }) => {
+                    check_set_payload(payload_op, true, checker)?;
+                    check_precondition(precondition, checker)
+                }
+                PayloadOps::DeletePayload(op) => {
+                    checker.check_deleted_payload_keys(&op.keys)?;
+                    checker.check_update_filter(op.filter.as_ref())
+                }
+                PayloadOps::ClearPayload { .. } => checker.check_clear_payload(),
+                PayloadOps::ClearPayloadByFilter(filter) => {
+                    checker.check_clear_payload()?;
+                    checker.check_update_filter(Some(filter))
+                }
+            },
+            CollectionUpdateOperations::FieldIndexOperation(_) => Ok(()),
+            CollectionUpdateOperations::AttributeOperation(AttributeOps::SetAttributes(op)) => {
+                checker.check_update_filter(op.filter.as_ref())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::json_path::path;
+    use segment::types::{
+        FieldCondition, Match, Nested, NestedCondition, PayloadSchemaType, ValueVariants,
+    };
+
+    use super::*;
+    use crate::config::PayloadSchemaConfig;
+
+    fn field(key: &str) -> Condition {
+        Condition::Field(FieldCondition::new_match(
+            path(key),
+            Match::new_value(ValueVariants::Keyword("x".to_string())),
+        ))
+    }
+
+    #[test]
+    fn test_strict_mode_checks() {
+        let config = StrictModeConfig {
+            enabled: Some(true),
+            max_query_limit: Some(10),
+            search_max_hnsw_ef: Some(64),
+            search_allow_exact: Some(false),
+            unindexed_filtering_retrieve: Some(false),
+            unindexed_filtering_update: None,
+            max_payload_size_bytes: Some(16),
+            payload_schema: None,
+            max_points_count: None,
+            max_ram_usage_bytes: None,
+            max_disk_usage_bytes: None,
+        };
+        let payload_schema = PayloadIndexSchema {
+            schema: [
+                (path("city"), PayloadSchemaType::Keyword.into()),
+                (path("items[].name"), PayloadSchemaType::Keyword.into()),
+            ]
+            .into_iter()
+            .collect(),
+        };
+        let checker = StrictModeChecker::new(&config, &payload_schema);
+
+        assert!(checker.check_limit(10).is_ok());
+        let err = checker.check_limit(11).unwrap_err();
+        assert!(err.to_string().contains("`max_query_limit`"), "{err}");
+
+        let params = SearchParams {
+            hnsw_ef: Some(128),
+            ..Default::default()
+        };
+        assert!(checker.check_search_params(Some(&params)).is_err());
+        let params = SearchParams {
+            exact: true,
+            ..Default::default()
+        };
+        assert!(checker.check_search_params(Some(&params)).is_err());
+
+        let indexed = Filter::new_must(Condition::Nested(NestedCondition::new(Nested {
+            key: path("items"),
+            filter: Filter::new_must(field("name")),
+        })));
+        assert!(checker.check_read_filter(Some(&indexed)).is_ok());
+
+        let mut unindexed = Filter::new_must(field("city"));
+        unindexed.should = Some(vec![field("country")]);
+        let err = checker.check_read_filter(Some(&unindexed)).unwrap_err();
+        assert!(err.to_string().contains("`country`"), "{err}");
+        assert!(checker.check_update_filter(Some(&unindexed)).is_ok());
+
+        let payload: Payload = serde_json::from_str(r#"{"a": "b"}"#).unwrap();
+        assert!(checker.check_payload(Some(&payload)).is_ok());
+        let payload: Payload = serde_json::from_str(r#"{"a": "bbbbbbbbbbbbbbbb"}"#).unwrap();
+        assert!(checker.check_payload(Some(&payload)).is_err());
+    }
+
+    #[test]
+    fn test_payload_schema_checks() {
+        let config = StrictModeConfig {
+            enabled: Some(true),
+            payload_schema: Some(PayloadSchemaConfig {
+                fields: [
+                    (
+                        "title".to_string(),
+                        PayloadFieldConstraints {
+                            value_type: Some(PayloadSchemaType::Keyword),
+                            required: Some(true),
+                            max_length: Some(8),
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "price".to_string(),
+                        PayloadFieldConstraints {
+                            value_type: Some(PayloadSchemaType::Float),
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "tags".to_string(),
+                        PayloadFieldConstraints {
+                            value_type: Some(PayloadSchemaType::Keyword),
+                            max_array_length: Some(2),
+                            ..Default::default()
+                        },
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+                allow_unknown_fields: Some(false),
+            }),
+            ..Default::default()
+        };
+        let payload_schema = PayloadIndexSchema::default();
+        let checker = StrictModeChecker::new(&config, &payload_schema);
+
+        let check = |payload: &str, partial: bool| {
+            let payload: Payload = serde_json::from_str(payload).unwrap();
+            checker.check_payload_schema(Some(&payload), partial)
+        };
+
+        assert!(check(r#"{"title": "book", "price": 10, "tags": ["a"]}"#, false).is_ok());
+        assert!(check(r#"{"price": 10.5}"#, true).is_ok());
+        assert!(checker.check_payload_schema(None, true).is_ok());
+
+        let err = check(r#"{"price": 10}"#, false).unwrap_err();
+        assert!(
+            err.to_string().contains("`title`: required key is missing"),
+            "{err}"
+        );
+        let err = checker.check_payload_schema(None, false).unwrap_err();
+        assert!(err.to_string().contains("`title`"), "{err}");
+
+        let err = check(r#"{"title": null}"#, true).unwrap_err();
+        assert!(
+            err.to_string().contains("`title`: required value is null"),
+            "{err}"
+        );
+        let err = check(r#"{"title": "long title"}"#, true).unwrap_err();
+        assert!(
+            err.to_string().contains("longer than the max of 8"),
+            "{err}"
+        );
+
+        let err = check(r#"{"price": "10", "tags": ["a", 1], "extra": 1}"#, true).unwrap_err();
+        let err = err.to_string();
+        assert!(err.contains("`price`: expected float, got string"), "{err}");
+        assert!(
+            err.contains("`tags`: expected keyword, got integer"),
+            "{err}"
+        );
+        assert!(err.contains("`extra`: key is not in the schema"), "{err}");
+
+        let err = check(r#"{"tags": ["a", "b", "c"]}"#, true).unwrap_err();
+        assert!(err.to_string().contains("array of 3 elements"), "{err}");
+
+        assert!(checker
+            .check_nested_payload_schema(&path("title.a"))
+            .is_err());
+        assert!(checker
+            .check_nested_payload_schema(&path("other.a"))
+            .is_err());
+
+        assert!(checker
+            .check_deleted_payload_keys(&[path("price"), path("title.a")])
+            .is_ok());
+        let err = checker
+            .check_deleted_payload_keys(&[path("price"), path("title")])
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("`title`: required key can't be deleted"),
+            "{err}"
+        );
+
+        let err = checker.check_clear_payload().unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("`title`: required key can't be cleared"),
+            "{err}"
+        );
+        let config = StrictModeConfig::default();
+        let checker = StrictModeChecker::new(&config, &payload_schema);
+        assert!(checker.check_clear_payload().is_ok());
+    }
+}
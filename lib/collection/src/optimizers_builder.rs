@@ -12,7 +12,7 @@ use crate::collection_manager::optimizers::indexing_optimizer::IndexingOptimizer
 use crate::collection_manager::optimizers::merge_optimizer::MergeOptimizer;
 use crate::collection_manager::optimizers::segment_optimizer::OptimizerThresholds;
 use crate::collection_manager::optimizers::vacuum_optimizer::VacuumOptimizer;
-use crate::config::CollectionParams;
+use crate::config::{CollectionConfig, CollectionParams};
 use crate::update_handler::Optimizer;
 
 const DEFAULT_MAX_SEGMENT_PER_CPU_KB: usize = 200_000;
@@ -77,6 +77,12 @@ pub struct OptimizersConfig {
     /// If 0 - no optimization threads, optimizations will be disabled.
     #[serde(default)]
     pub max_optimization_threads: Option<usize>,
+    /// Defer building of vector indexes, for example, during a bulk upload.
+    /// While enabled, segments are not indexed regardless of `indexing_threshold`, and the collection
+    /// status is grey if some segments are waiting for indexing.
+    /// Once disabled, all deferred segments are indexed in the background.
+    #[serde(default)]
+    pub defer_indexing: bool,
 }
 
 impl OptimizersConfig {
@@ -91,6 +97,7 @@ impl OptimizersConfig {
             indexing_threshold: Some(100_000),
             flush_interval_sec: 60,
             max_optimization_threads: Some(0),
+            defer_indexing: false,
         }
     }
 
@@ -105,11 +112,20 @@ impl OptimizersConfig {
         }
     }
 
-    pub fn optimizer_thresholds(&self, num_indexing_threads: usize) -> OptimizerThresholds {
-        let indexing_threshold_kb = match self.indexing_threshold {
+    /// Indexing threshold, which applies once indexing is not deferred
+    pub fn indexing_threshold_kb(&self) -> usize {
+        match self.indexing_threshold {
             None => DEFAULT_INDEXING_THRESHOLD_KB, // default value
             Some(0) => usize::MAX,                 // disable vector index
             Some(custom) => custom,
+        }
+    }
+
+    pub fn optimizer_thresholds(&self, num_indexing_threads: usize) -> OptimizerThresholds {
+        let indexing_threshold_kb = if self.defer_indexing {
+            usize::MAX
+        } else {
+            self.indexing_threshold_kb()
         };
 
         let memmap_threshold_kb = match self.memmap_threshold {
@@ -198,3 +214,21 @@ pub fn build_optimizers(
         )),
     ])
 }
+
+/// Optimizer, which decides what segments of the shard have to be rebuilt for the given config
+pub fn build_config_mismatch_optimizer(
+    shard_path: &Path,
+    config: &CollectionConfig,
+) -> ConfigMismatchOptimizer {
+    let num_indexing_threads = num_rayon_threads(config.hnsw_config.max_indexing_threads);
+    ConfigMismatchOptimizer::new(
+        config
+            .optimizer_config
+            .optimizer_thresholds(num_indexing_threads),
+        shard_path.join(SEGMENTS_PATH),
+        shard_path.join(TEMP_SEGMENTS_PATH),
+        config.params.clone(),
+        config.hnsw_config.clone(),
+        config.quantization_config.clone(),
+    )
+}
@@ -3,6 +3,7 @@ use std::iter::Peekable;
 use std::time::Duration;
 
 use api::rest::RecommendStrategy;
+use common::hardware_counter::HardwareCounterCell;
 use itertools::Itertools;
 use segment::data_types::vectors::{
     DenseVector, NamedQuery, NamedVectorStruct, Vector, VectorElementType, VectorRef,
@@ -274,6 +275,7 @@ where
                 read_consistency,
                 shard_selector,
                 timeout,
+                &HardwareCounterCell::disabled(),
             ));
 
             Ok(())
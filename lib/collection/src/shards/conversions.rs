@@ -1,23 +1,26 @@
-use api::grpc::conversions::{convert_shard_key_from_grpc_opt, payload_to_proto};
+use api::grpc::conversions::{
+    attributes_to_proto, convert_shard_key_from_grpc_opt, payload_to_proto,
+};
 use api::grpc::qdrant::points_selector::PointsSelectorOneOf;
 use api::grpc::qdrant::{
     ClearPayloadPoints, ClearPayloadPointsInternal, CreateFieldIndexCollection,
     CreateFieldIndexCollectionInternal, DeleteFieldIndexCollection,
     DeleteFieldIndexCollectionInternal, DeletePayloadPoints, DeletePayloadPointsInternal,
     DeletePointVectors, DeletePoints, DeletePointsInternal, DeleteVectorsInternal, PointVectors,
-    PointsIdsList, PointsSelector, SetPayloadPoints, SetPayloadPointsInternal, SyncPoints,
-    SyncPointsInternal, UpdatePointVectors, UpdateVectorsInternal, UpsertPoints,
-    UpsertPointsInternal, VectorsSelector,
+    PointsIdsList, PointsSelector, SetAttributesPoints, SetAttributesPointsInternal,
+    SetPayloadPoints, SetPayloadPointsInternal, SyncPoints, SyncPointsInternal, UpdatePointVectors,
+    UpdateVectorsInternal, UpsertPoints, UpsertPointsInternal, VectorsSelector,
 };
 use segment::data_types::vectors::VectorStructInternal;
 use segment::json_path::JsonPath;
 use segment::types::{Filter, PayloadFieldSchema, PayloadSchemaParams, PointIdType, ScoredPoint};
 use tonic::Status;
 
+use crate::operations::attribute_ops::SetAttributesOp;
 use crate::operations::conversions::write_ordering_to_proto;
 use crate::operations::payload_ops::{DeletePayloadOp, SetPayloadOp};
 use crate::operations::point_ops::{
-    PointInsertOperationsInternal, PointSyncOperation, WriteOrdering,
+    PointInsertOperationsInternal, PointSyncOperation, WriteOrdering, WritePrecondition,
 };
 use crate::operations::types::CollectionResult;
 use crate::operations::vector_ops::UpdateVectorsOp;
@@ -55,6 +58,7 @@ pub fn internal_upsert_points(
     clock_tag: Option<ClockTag>,
     collection_name: String,
     point_insert_operations: PointInsertOperationsInternal,
+    precondition: Option<WritePrecondition>,
     wait: bool,
     ordering: Option<WriteOrdering>,
 ) -> CollectionResult<UpsertPointsInternal> {
@@ -73,6 +77,7 @@ pub fn internal_upsert_points(
             },
             ordering: ordering.map(write_ordering_to_proto),
             shard_key_selector: None,
+            precondition: precondition.map(Into::into),
         }),
     })
 }
@@ -82,6 +87,7 @@ pub fn internal_delete_points(
     clock_tag: Option<ClockTag>,
     collection_name: String,
     ids: Vec<PointIdType>,
+    precondition: Option<WritePrecondition>,
     wait: bool,
     ordering: Option<WriteOrdering>,
 ) -> DeletePointsInternal {
@@ -98,6 +104,7 @@ pub fn internal_delete_points(
             }),
             ordering: ordering.map(write_ordering_to_proto),
             shard_key_selector: None,
+            precondition: precondition.map(Into::into),
         }),
     }
 }
@@ -121,6 +128,7 @@ pub fn internal_delete_points_by_filter(
             }),
             ordering: ordering.map(write_ordering_to_proto),
             shard_key_selector: None,
+            precondition: None,
         }),
     }
 }
@@ -214,6 +222,7 @@ pub fn internal_set_payload(
     clock_tag: Option<ClockTag>,
     collection_name: String,
     set_payload: SetPayloadOp,
+    precondition: Option<WritePrecondition>,
     wait: bool,
     ordering: Option<WriteOrdering>,
 ) -> SetPayloadPointsInternal {
@@ -240,6 +249,7 @@ pub fn internal_set_payload(
             ordering: ordering.map(write_ordering_to_proto),
             shard_key_selector: None,
             key: set_payload.key.map(|key| key.to_string()),
+            precondition: precondition.map(Into::into),
         }),
     }
 }
@@ -330,6 +340,40 @@ pub fn internal_clear_payload_by_filter(
     }
 }
 
+pub fn internal_set_attributes(
+    shard_id: Option<ShardId>,
+    clock_tag: Option<ClockTag>,
+    collection_name: String,
+    set_attributes: SetAttributesOp,
+    wait: bool,
+    ordering: Option<WriteOrdering>,
+) -> SetAttributesPointsInternal {
+    let points_selector = if let Some(points) = set_attributes.points {
+        Some(PointsSelector {
+            points_selector_one_of: Some(PointsSelectorOneOf::Points(PointsIdsList {
+                ids: points.into_iter().map(|id| id.into()).collect(),
+            })),
+        })
+    } else {
+        set_attributes.filter.map(|filter| PointsSelector {
+            points_selector_one_of: Some(PointsSelectorOneOf::Filter(filter.into())),
+        })
+    };
+
+    SetAttributesPointsInternal {
+        shard_id,
+        clock_tag: clock_tag.map(Into::into),
+        set_attributes_points: Some(SetAttributesPoints {
+            collection_name,
+            wait: Some(wait),
+            attributes: attributes_to_proto(set_attributes.attributes),
+            points_selector,
+            ordering: ordering.map(write_ordering_to_proto),
+            shard_key_selector: None,
+        }),
+    }
+}
+
 pub fn internal_create_index(
     shard_id: Option<ShardId>,
     clock_tag: Option<ClockTag>,
@@ -364,6 +408,12 @@ pub fn internal_create_index(
                     segment::types::PayloadSchemaType::Datetime => {
                         api::grpc::qdrant::FieldType::Datetime as i32
                     }
+                    segment::types::PayloadSchemaType::BigInt => {
+                        api::grpc::qdrant::FieldType::BigInt as i32
+                    }
+                    segment::types::PayloadSchemaType::MinHash => {
+                        api::grpc::qdrant::FieldType::MinHash as i32
+                    }
                 },
                 None,
             ),
@@ -376,6 +426,10 @@ pub fn internal_create_index(
                     api::grpc::qdrant::FieldType::Integer as i32,
                     Some(integer_params.into()),
                 ),
+                PayloadSchemaParams::Geo(geo_params) => (
+                    api::grpc::qdrant::FieldType::Geo as i32,
+                    Some(geo_params.into()),
+                ),
             },
         })
         .map(|(field_type, field_params)| (Some(field_type), field_params))
@@ -3,6 +3,8 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
+use common::hardware_counter::HardwareCounterCell;
+use segment::data_types::aggregation::{DistinctValues, NumericStats, ValueDistribution};
 use segment::data_types::order_by::OrderBy;
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
@@ -11,10 +13,12 @@ use tokio::runtime::Handle;
 
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, PointRequestInternal, Record, UpdateResult,
+    CountDistinctRequestInternal, CountRequestInternal, CountResult, FieldStatsRequestInternal,
+    HistogramRequestInternal, PartialResponse, PointRequestInternal, Record, UpdateResult,
 };
 use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
 use crate::operations::OperationWithClockTag;
+use crate::shards::point_digests::{PointDigests, PointDigestsRequest};
 use crate::shards::shard_trait::ShardOperation;
 use crate::shards::telemetry::LocalShardTelemetry;
 
@@ -48,6 +52,7 @@ impl DummyShard {
             variant_name: Some("dummy shard".into()),
             segments: vec![],
             optimizations: Default::default(),
+            update_queue: None,
         }
     }
 
@@ -85,7 +90,8 @@ impl ShardOperation for DummyShard {
         _: Arc<CoreSearchRequestBatch>,
         _: &Handle,
         _: Option<Duration>,
-    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        _: &HardwareCounterCell,
+    ) -> CollectionResult<PartialResponse<Vec<Vec<ScoredPoint>>>> {
         self.dummy()
     }
 
@@ -93,6 +99,31 @@ impl ShardOperation for DummyShard {
         self.dummy()
     }
 
+    async fn count_distinct(
+        &self,
+        _: Arc<CountDistinctRequestInternal>,
+    ) -> CollectionResult<DistinctValues> {
+        self.dummy()
+    }
+
+    async fn histogram(
+        &self,
+        _: Arc<HistogramRequestInternal>,
+    ) -> CollectionResult<ValueDistribution> {
+        self.dummy()
+    }
+
+    async fn field_stats(
+        &self,
+        _: Arc<FieldStatsRequestInternal>,
+    ) -> CollectionResult<NumericStats> {
+        self.dummy()
+    }
+
+    async fn point_digests(&self, _: Arc<PointDigestsRequest>) -> CollectionResult<PointDigests> {
+        self.dummy()
+    }
+
     async fn retrieve(
         &self,
         _: Arc<PointRequestInternal>,
@@ -107,7 +138,8 @@ impl ShardOperation for DummyShard {
         _requests: Arc<Vec<ShardQueryRequest>>,
         _search_runtime_handle: &Handle,
         _timeout: Option<Duration>,
-    ) -> CollectionResult<Vec<ShardQueryResponse>> {
+        _hw_counter: &HardwareCounterCell,
+    ) -> CollectionResult<PartialResponse<Vec<ShardQueryResponse>>> {
         self.dummy()
     }
 }
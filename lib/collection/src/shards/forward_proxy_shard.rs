@@ -3,30 +3,39 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
+use common::hardware_counter::HardwareCounterCell;
+use common::io_throttling::BackgroundIo;
 use common::types::TelemetryDetail;
+use segment::data_types::aggregation::{DistinctValues, NumericStats, ValueDistribution};
 use segment::data_types::order_by::OrderBy;
+use segment::data_types::vectors::{Vector, VectorElementType, VectorStructInternal};
 use segment::types::{
-    ExtendedPointId, Filter, PointIdType, ScoredPoint, WithPayload, WithPayloadInterface,
-    WithVector,
+    AttributeValue, ExtendedPointId, Filter, PointIdType, ScoredPoint, WithPayload,
+    WithPayloadInterface, WithVector,
 };
 use tokio::runtime::Handle;
 use tokio::sync::Mutex;
 
 use super::shard::ShardId;
 use super::update_tracker::UpdateTracker;
+use crate::collection_manager::segments_searcher::SegmentsSearcher;
 use crate::hash_ring::HashRing;
+use crate::operations::attribute_ops::{AttributeOps, SetAttributesOp};
 use crate::operations::point_ops::{
     PointInsertOperationsInternal, PointOperations, PointStruct, PointSyncOperation,
 };
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, PointRequestInternal, Record, UpdateResult, UpdateStatus,
+    CountDistinctRequestInternal, CountRequestInternal, CountResult, FieldStatsRequestInternal,
+    HistogramRequestInternal, PartialResponse, PointRequestInternal, Record, UpdateResult,
+    UpdateStatus,
 };
 use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
 use crate::operations::{
     CollectionUpdateOperations, CreateIndex, FieldIndexOperations, OperationWithClockTag,
 };
 use crate::shards::local_shard::LocalShard;
+use crate::shards::point_digests::{PointDigests, PointDigestsRequest};
 use crate::shards::remote_shard::RemoteShard;
 use crate::shards::shard_trait::ShardOperation;
 use crate::shards::telemetry::LocalShardTelemetry;
@@ -97,7 +106,7 @@ impl ForwardProxyShard {
     ) -> CollectionResult<Option<PointIdType>> {
         debug_assert!(batch_size > 0);
         let limit = batch_size + 1;
-        let _update_lock = self.update_lock.lock().await;
+        let update_lock = self.update_lock.lock().await;
         let mut batch = self
             .wrapped_shard
             .scroll_by(
@@ -118,6 +127,8 @@ impl ForwardProxyShard {
             Some(batch.pop().unwrap().id)
         };
 
+        let transferred_bytes = batch.iter().map(estimate_record_size).sum();
+
         let points: Result<Vec<PointStruct>, String> = batch
             .into_iter()
             // If using a hashring filter, only transfer points that moved, otherwise transfer all
@@ -130,6 +141,7 @@ impl ForwardProxyShard {
             .collect();
 
         let points = points?;
+        let attribute_operations = self.transferred_attributes(&points).await?;
 
         // Use sync API to leverage potentially existing points
         // Normally use SyncPoints, to completely replace everything in the target shard
@@ -150,12 +162,88 @@ impl ForwardProxyShard {
 
         // TODO: Is cancelling `RemoteShard::update` safe for *receiver*?
         self.remote_shard
-            .update(OperationWithClockTag::from(insert_points_operation), wait) // TODO: Assign clock tag!? 🤔
+            .update(
+                OperationWithClockTag::from(insert_points_operation), // TODO: Assign clock tag!? 🤔
+                wait && attribute_operations.is_empty(),
+            )
             .await?;
 
+        let attribute_operations_count = attribute_operations.len();
+        for (index, attribute_operation) in attribute_operations.into_iter().enumerate() {
+            let attribute_operation = CollectionUpdateOperations::AttributeOperation(
+                AttributeOps::SetAttributes(attribute_operation),
+            );
+            self.remote_shard
+                .update(
+                    OperationWithClockTag::from(attribute_operation),
+                    wait && index + 1 == attribute_operations_count,
+                )
+                .await?;
+        }
+
+        // Wait for the IO budget without holding the lock, so updates are not blocked meanwhile
+        drop(update_lock);
+        BackgroundIo::ShardTransfer
+            .throttle_async(transferred_bytes)
+            .await;
+
         Ok(next_page_offset)
     }
 
+    /// Operations to set attributes of the transferred points on the remote shard
+    ///
+    /// Attributes are stored outside of the points, so transferred points don't carry them.
+    /// Points with equal attributes are grouped into a single operation. Attributes, which the
+    /// point doesn't have, are removed, so the remote shard doesn't keep stale values.
+    async fn transferred_attributes(
+        &self,
+        points: &[PointStruct],
+    ) -> CollectionResult<Vec<SetAttributesOp>> {
+        let columns: Vec<String> = self
+            .wrapped_shard
+            .collection_config
+            .read()
+            .await
+            .params
+            .attribute_columns
+            .keys()
+            .cloned()
+            .collect();
+        if columns.is_empty() || points.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let point_ids: Vec<_> = points.iter().map(|point| point.id).collect();
+        let mut point_attributes =
+            SegmentsSearcher::retrieve_attributes(self.wrapped_shard.segments(), &point_ids)?;
+
+        let mut groups: Vec<(Vec<Option<AttributeValue>>, Vec<PointIdType>)> = Vec::new();
+        for point_id in point_ids {
+            let mut attributes = point_attributes.remove(&point_id).unwrap_or_default();
+            let values: Vec<_> = columns
+                .iter()
+                .map(|column| attributes.remove(column))
+                .collect();
+            match groups
+                .iter_mut()
+                .find(|(group_values, _)| *group_values == values)
+            {
+                Some((_, group_points)) => group_points.push(point_id),
+                None => groups.push((values, vec![point_id])),
+            }
+        }
+
+        let operations = groups
+            .into_iter()
+            .map(|(values, points)| SetAttributesOp {
+                attributes: columns.iter().cloned().zip(values).collect(),
+                points: Some(points),
+                filter: None,
+            })
+            .collect();
+        Ok(operations)
+    }
+
     pub fn deconstruct(self) -> (LocalShard, RemoteShard) {
         (self.wrapped_shard, self.remote_shard)
     }
@@ -274,10 +362,11 @@ impl ShardOperation for ForwardProxyShard {
         request: Arc<CoreSearchRequestBatch>,
         search_runtime_handle: &Handle,
         timeout: Option<Duration>,
-    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        hw_counter: &HardwareCounterCell,
+    ) -> CollectionResult<PartialResponse<Vec<Vec<ScoredPoint>>>> {
         let local_shard = &self.wrapped_shard;
         local_shard
-            .core_search(request, search_runtime_handle, timeout)
+            .core_search(request, search_runtime_handle, timeout, hw_counter)
             .await
     }
 
@@ -286,6 +375,38 @@ impl ShardOperation for ForwardProxyShard {
         local_shard.count(request).await
     }
 
+    async fn count_distinct(
+        &self,
+        request: Arc<CountDistinctRequestInternal>,
+    ) -> CollectionResult<DistinctValues> {
+        let local_shard = &self.wrapped_shard;
+        local_shard.count_distinct(request).await
+    }
+
+    async fn histogram(
+        &self,
+        request: Arc<HistogramRequestInternal>,
+    ) -> CollectionResult<ValueDistribution> {
+        let local_shard = &self.wrapped_shard;
+        local_shard.histogram(request).await
+    }
+
+    async fn field_stats(
+        &self,
+        request: Arc<FieldStatsRequestInternal>,
+    ) -> CollectionResult<NumericStats> {
+        let local_shard = &self.wrapped_shard;
+        local_shard.field_stats(request).await
+    }
+
+    async fn point_digests(
+        &self,
+        request: Arc<PointDigestsRequest>,
+    ) -> CollectionResult<PointDigests> {
+        let local_shard = &self.wrapped_shard;
+        local_shard.point_digests(request).await
+    }
+
     async fn retrieve(
         &self,
         request: Arc<PointRequestInternal>,
@@ -303,10 +424,39 @@ impl ShardOperation for ForwardProxyShard {
         requests: Arc<Vec<ShardQueryRequest>>,
         search_runtime_handle: &Handle,
         timeout: Option<Duration>,
-    ) -> CollectionResult<Vec<ShardQueryResponse>> {
+        hw_counter: &HardwareCounterCell,
+    ) -> CollectionResult<PartialResponse<Vec<ShardQueryResponse>>> {
         let local_shard = &self.wrapped_shard;
         local_shard
-            .query_batch(requests, search_runtime_handle, timeout)
+            .query_batch(requests, search_runtime_handle, timeout, hw_counter)
             .await
     }
 }
+
+/// Approximate number of bytes, read from the shard for a transferred record
+fn estimate_record_size(record: &Record) -> usize {
+    const ELEMENT_SIZE: usize = size_of::<VectorElementType>();
+
+    let vector_size = |vector: &Vector| match vector {
+        Vector::Dense(vector) => vector.len() * ELEMENT_SIZE,
+        Vector::Sparse(vector) => vector.indices.len() * (size_of::<u32>() + ELEMENT_SIZE),
+        Vector::MultiDense(vector) => vector.flattened_vectors.len() * ELEMENT_SIZE,
+    };
+
+    let vectors_size = match &record.vector {
+        None => 0,
+        Some(VectorStructInternal::Single(vector)) => vector.len() * ELEMENT_SIZE,
+        Some(VectorStructInternal::MultiDense(vector)) => {
+            vector.flattened_vectors.len() * ELEMENT_SIZE
+        }
+        Some(VectorStructInternal::Named(vectors)) => vectors.values().map(vector_size).sum(),
+    };
+
+    let payload_size = record
+        .payload
+        .as_ref()
+        .and_then(|payload| serde_json::to_vec(payload).ok())
+        .map_or(0, |payload| payload.len());
+
+    vectors_size + payload_size
+}
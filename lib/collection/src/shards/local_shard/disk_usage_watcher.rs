@@ -1,9 +1,12 @@
+use std::collections::VecDeque;
 use std::path::PathBuf;
+use std::time::Duration;
 
 use tokio::sync::Mutex;
 use tokio::time::Instant;
 
-use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::types::{CollectionError, CollectionResult, ShardDiskUsage};
+use crate::shards::shard::ShardId;
 
 /// Defines how often the disk usage should be checked if the disk is far from being full
 const DEFAULT_FREQUENCY: usize = 128;
@@ -20,10 +23,67 @@ const FREE_SPACE_TO_CHECK_FREQUENCY_HEURISTIC_MB: &[(usize, usize); 5] =
 /// because some external process could have consumed the disk space
 const MIN_DISK_CHECK_INTERVAL_MILLIS: usize = 2000;
 
+/// Size of the shard is sampled at most this often, to project its growth
+const DISK_USAGE_SAMPLE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Growth is averaged over this many latest samples
+const MAX_DISK_USAGE_SAMPLES: usize = 60;
+
 #[derive(Default)]
 struct LastCheck {
     last_check_time: Option<Instant>,
     next_check_count: usize,
+    /// Result of the latest check, kept until the next one
+    is_full: Option<bool>,
+}
+
+/// Sizes of the shard directory over time
+#[derive(Default)]
+struct DiskUsageHistory {
+    samples: VecDeque<(Instant, u64)>,
+}
+
+impl DiskUsageHistory {
+    fn needs_sample(&self, now: Instant) -> bool {
+        match self.samples.back() {
+            Some((time, _)) => now - *time >= DISK_USAGE_SAMPLE_INTERVAL,
+            None => true,
+        }
+    }
+
+    fn record(&mut self, time: Instant, disk_usage_bytes: u64) {
+        if self.samples.len() == MAX_DISK_USAGE_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back((time, disk_usage_bytes));
+    }
+
+    fn latest(&self) -> Option<u64> {
+        self.samples.back().map(|(_, bytes)| *bytes)
+    }
+
+    /// Least squares slope of the samples, in bytes per second
+    fn growth_bytes_per_sec(&self) -> Option<f64> {
+        let (first_time, _) = *self.samples.front()?;
+        let points: Vec<(f64, f64)> = self
+            .samples
+            .iter()
+            .map(|(time, bytes)| ((*time - first_time).as_secs_f64(), *bytes as f64))
+            .collect();
+        if points.len() < 2 {
+            return None;
+        }
+
+        let n = points.len() as f64;
+        let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+        let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+        let covariance: f64 = points
+            .iter()
+            .map(|(x, y)| (x - mean_x) * (y - mean_y))
+            .sum();
+        let variance: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+        (variance > 0.0).then(|| covariance / variance)
+    }
 }
 
 pub struct DiskUsageWatcher {
@@ -31,6 +91,7 @@ pub struct DiskUsageWatcher {
     disabled: bool,
     min_free_disk_size_mb: usize,
     last_check: Mutex<LastCheck>,
+    history: Mutex<DiskUsageHistory>,
 }
 
 impl DiskUsageWatcher {
@@ -40,6 +101,7 @@ impl DiskUsageWatcher {
             disabled: false,
             min_free_disk_size_mb,
             last_check: Default::default(),
+            history: Default::default(),
         };
         match watcher.is_disk_full().await {
             Ok(Some(_)) => {} // do nothing
@@ -50,8 +112,9 @@ impl DiskUsageWatcher {
         };
         watcher
     }
-    /// Returns true if the disk free space is less than the `disk_buffer_threshold_mb`
-    /// As the side effect, it updates the disk usage every `update_count_threshold` calls
+    /// Returns true if the disk free space is less than the `min_free_disk_size_mb`
+    /// As the side effect, it updates the disk usage every `update_count_threshold` calls,
+    /// returning the result of the latest check in between
     pub async fn is_disk_full(&self) -> CollectionResult<Option<bool>> {
         if self.disabled {
             return Ok(None);
@@ -70,31 +133,94 @@ impl DiskUsageWatcher {
 
             last_check_guard.last_check_time = Some(Instant::now());
 
+            let min_free_space = self.min_free_disk_size_mb * 1024 * 1024;
             let is_full = match free_space {
                 Some(free_space) => {
                     let free_space = free_space as usize;
+                    // Check more often, when approaching the threshold
+                    let margin = free_space.saturating_sub(min_free_space);
                     let mut next_check = DEFAULT_FREQUENCY;
                     for (threshold_mb, interval) in FREE_SPACE_TO_CHECK_FREQUENCY_HEURISTIC_MB {
-                        if free_space < (*threshold_mb * 1024 * 1024) {
+                        if margin < (*threshold_mb * 1024 * 1024) {
                             next_check = *interval;
                             break;
                         }
                     }
                     last_check_guard.next_check_count = next_check;
 
-                    Some(free_space < self.min_free_disk_size_mb * 1024 * 1024)
+                    Some(free_space < min_free_space)
                 }
                 None => {
                     last_check_guard.next_check_count = 0;
                     None
                 }
             };
+            last_check_guard.is_full = is_full;
+            drop(last_check_guard);
+
+            self.sample_disk_usage(false).await?;
 
             Ok(is_full)
         } else {
             last_check_guard.next_check_count = last_check_guard.next_check_count.saturating_sub(1);
-            Ok(None)
+            Ok(last_check_guard.is_full)
+        }
+    }
+
+    /// Current size of the shard with its projected growth
+    pub async fn disk_usage(&self, shard_id: ShardId) -> CollectionResult<ShardDiskUsage> {
+        let disk_usage_bytes = self.sample_disk_usage(true).await?;
+        let free_space_bytes = self.get_free_space_bytes().await?;
+        let growth_bytes_per_sec = self.history.lock().await.growth_bytes_per_sec();
+
+        let min_free_space = (self.min_free_disk_size_mb * 1024 * 1024) as u64;
+        let writes_rejected = free_space_bytes.is_some_and(|free| free < min_free_space);
+        let hours_until_writes_rejected = match (free_space_bytes, growth_bytes_per_sec) {
+            (Some(free), Some(growth)) if growth > 0.0 && !writes_rejected => {
+                Some((free - min_free_space) as f64 / growth / 3600.0)
+            }
+            _ => None,
+        };
+
+        Ok(ShardDiskUsage {
+            shard_id,
+            disk_usage_bytes,
+            free_space_bytes,
+            growth_bytes_per_hour: growth_bytes_per_sec.map(|growth| growth * 3600.0),
+            hours_until_writes_rejected,
+            writes_rejected,
+        })
+    }
+
+    /// Record size of the shard directory, if it wasn't sampled recently or `force` is set
+    async fn sample_disk_usage(&self, force: bool) -> CollectionResult<u64> {
+        let mut history = self.history.lock().await;
+        let now = Instant::now();
+        if let Some(latest) = history
+            .latest()
+            .filter(|_| !force && !history.needs_sample(now))
+        {
+            return Ok(latest);
+        }
+
+        let path = self.disk_path.clone();
+        let disk_usage = tokio::task::spawn_blocking(move || fs_extra::dir::get_size(path))
+            .await
+            .map_err(|e| {
+                CollectionError::service_error(format!("Failed to join async task: {}", e))
+            })?
+            .map_err(|err| {
+                CollectionError::service_error(format!(
+                    "Failed to get disk usage of {}: {err}",
+                    self.disk_path.display(),
+                ))
+            })?;
+
+        // Forced samples, which are too close to the previous one, would skew the growth
+        if history.needs_sample(now) {
+            history.record(now, disk_usage);
         }
+        Ok(disk_usage)
     }
 
     /// Return current disk usage in bytes, if available
@@ -123,3 +249,25 @@ impl DiskUsageWatcher {
         Ok(result)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disk_usage_growth() {
+        let mut history = DiskUsageHistory::default();
+        let start = Instant::now();
+        assert_eq!(history.growth_bytes_per_sec(), None);
+
+        history.record(start, 1000);
+        assert_eq!(history.growth_bytes_per_sec(), None);
+
+        for i in 1..(MAX_DISK_USAGE_SAMPLES as u64 + 10) {
+            history.record(start + Duration::from_secs(i * 60), 1000 + i * 600);
+        }
+        assert_eq!(history.samples.len(), MAX_DISK_USAGE_SAMPLES);
+        let growth = history.growth_bytes_per_sec().unwrap();
+        assert!((growth - 10.0).abs() < 1e-6);
+    }
+}
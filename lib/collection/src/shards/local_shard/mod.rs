@@ -3,32 +3,37 @@ pub mod disk_usage_watcher;
 pub(super) mod query;
 pub(super) mod scroll;
 pub(super) mod search;
+pub(super) mod segment_snapshot;
 pub(super) mod shard_ops;
 
 use std::collections::{BTreeSet, HashMap};
 use std::mem::size_of;
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::thread;
 use std::time::{Duration, Instant};
 
 use arc_swap::ArcSwap;
+use chrono::{DateTime, Utc};
 use common::cpu::CpuBudget;
 use common::panic;
 use common::types::TelemetryDetail;
 use indicatif::{ProgressBar, ProgressStyle};
 use itertools::Itertools;
 use parking_lot::{Mutex as ParkingMutex, RwLock};
+use segment::data_types::aggregation::{DistinctValues, NumericStats, ValueDistribution};
 use segment::data_types::vectors::VectorElementType;
 use segment::entry::entry_point::SegmentEntry as _;
 use segment::index::field_index::CardinalityEstimation;
+use segment::json_path::JsonPath;
+use segment::problems::unindexed_field::unindexed_fields;
 use segment::segment::Segment;
 use segment::segment_constructor::{build_segment, load_segment};
 use segment::types::{
-    CompressionRatio, Filter, PayloadIndexInfo, PayloadKeyType, PayloadStorageType, PointIdType,
-    QuantizationConfig, SegmentConfig, SegmentType,
+    CompressionRatio, Filter, PayloadIndexInfo, PayloadKeyType, PointIdType, QuantizationConfig,
+    SegmentConfig, SegmentType,
 };
 use segment::utils::mem::Mem;
 use tokio::fs::{copy, create_dir_all, remove_dir_all, remove_file};
@@ -45,20 +50,30 @@ use crate::collection_manager::collection_updater::CollectionUpdater;
 use crate::collection_manager::holders::segment_holder::{
     LockedSegment, LockedSegmentHolder, SegmentHolder,
 };
-use crate::collection_manager::optimizers::TrackerLog;
+use crate::collection_manager::optimizers::{TrackerLog, TrackerTelemetry};
+use crate::collection_manager::point_history::{PointHistory, PointVersion, POINT_HISTORY_PATH};
+use crate::collection_manager::point_trash::{PointTrash, TrashedPoint, POINT_TRASH_PATH};
+use crate::common::collection_stats::{ShardStats, StatsCounters};
 use crate::common::file_utils::{move_dir, move_file};
+use crate::common::idempotency::{operation_hash, IdempotencyCache};
+use crate::common::sparse_vocabulary::SparseVocabularyStats;
 use crate::config::CollectionConfig;
+use crate::operations::config_impact::{ShardConfigChangeImpact, VectorFootprint};
+use crate::operations::segment_details::{SegmentDetails, ShardSegmentsInfo};
 use crate::operations::shared_storage_config::SharedStorageConfig;
 use crate::operations::types::{
     check_sparse_compatible_with_segment_config, CollectionError, CollectionInfoInternal,
-    CollectionResult, CollectionStatus, OptimizersStatus,
+    CollectionResult, CollectionStatus, OptimizersStatus, QuotaUsage, ShardDiskUsage, UpdateResult,
+    UpdateStatus,
 };
 use crate::operations::OperationWithClockTag;
-use crate::optimizers_builder::{build_optimizers, clear_temp_segments, OptimizersConfig};
+use crate::optimizers_builder::{
+    build_config_mismatch_optimizer, build_optimizers, clear_temp_segments, OptimizersConfig,
+};
 use crate::save_on_disk::SaveOnDisk;
 use crate::shards::shard::ShardId;
 use crate::shards::shard_config::{ShardConfig, SHARD_CONFIG_FILE};
-use crate::shards::telemetry::{LocalShardTelemetry, OptimizerTelemetry};
+use crate::shards::telemetry::{LocalShardTelemetry, OptimizerTelemetry, UpdateQueueTelemetry};
 use crate::shards::CollectionId;
 use crate::update_handler::{Optimizer, UpdateHandler, UpdateSignal};
 use crate::wal::SerdeWal;
@@ -86,6 +101,14 @@ pub struct LocalShard {
     pub(super) optimizers_log: Arc<ParkingMutex<TrackerLog>>,
     update_runtime: Handle,
     disk_usage_watcher: DiskUsageWatcher,
+    /// Number of updates, rejected by the update backpressure
+    update_queue_rejected: AtomicUsize,
+    /// Results of recent updates, which carried an idempotency key
+    idempotency_cache: Mutex<IdempotencyCache>,
+    /// Counters of recent reads and writes
+    pub(super) stats: ShardStats,
+    /// Names of the segments, which failed to load and were moved into the quarantine directory
+    quarantined_segments: Vec<String>,
 }
 
 /// Shard holds information about segments and WAL.
@@ -99,6 +122,13 @@ impl LocalShard {
         move_dir(wal_from, wal_to).await?;
         move_dir(segments_from, segments_to).await?;
 
+        for path in [POINT_HISTORY_PATH, POINT_TRASH_PATH] {
+            let path_from = from.join(path);
+            if path_from.exists() {
+                move_dir(path_from, to.join(path)).await?;
+            }
+        }
+
         LocalShardClocks::move_data(from, to).await?;
 
         Ok(())
@@ -127,6 +157,14 @@ impl LocalShard {
             remove_dir_all(segments_path).await?;
         }
 
+        // Delete point history and trash
+        for path in [POINT_HISTORY_PATH, POINT_TRASH_PATH] {
+            let path = shard_path.join(path);
+            if path.exists() {
+                remove_dir_all(path).await?;
+            }
+        }
+
         LocalShardClocks::delete_data(shard_path).await?;
 
         Ok(())
@@ -151,8 +189,10 @@ impl LocalShard {
         let optimizers_log = Arc::new(ParkingMutex::new(Default::default()));
 
         // default to 2x the WAL capacity
-        let disk_buffer_threshold_mb =
-            2 * (collection_config.read().await.wal_config.wal_capacity_mb);
+        let disk_buffer_threshold_mb = shared_storage_config
+            .min_free_disk_space_mb
+            .unwrap_or_default()
+            .max(2 * config.wal_config.wal_capacity_mb);
 
         let disk_usage_watcher = disk_usage_watcher::DiskUsageWatcher::new(
             shard_path.to_owned(),
@@ -197,6 +237,10 @@ impl LocalShard {
             optimizers,
             optimizers_log,
             disk_usage_watcher,
+            update_queue_rejected: AtomicUsize::new(0),
+            idempotency_cache: Mutex::new(IdempotencyCache::default()),
+            stats: ShardStats::default(),
+            quarantined_segments: Vec::new(),
         }
     }
 
@@ -246,7 +290,8 @@ impl LocalShard {
             let segments_path = entry.unwrap().path();
             let payload_index_schema = payload_index_schema.clone();
             // let semaphore_clone = semaphore.clone();
-            load_handlers.push(
+            load_handlers.push((
+                segments_path.clone(),
                 thread::Builder::new()
                     .name(format!("shard-load-{collection_id}-{id}"))
                     .spawn(move || {
@@ -267,18 +312,45 @@ impl LocalShard {
                         }
                         Ok::<_, CollectionError>(res)
                     })?,
-            );
+            ));
         }
 
         let mut segment_holder = SegmentHolder::default();
+        segment_holder.owner = Some((collection_id.clone(), id));
+        if let Some(versions) = collection_config_read.params.point_history_versions {
+            let point_history = PointHistory::open(shard_path, versions.get() as usize)?;
+            segment_holder.point_history = Some(Arc::new(point_history));
+        }
+        if let Some(retention_sec) = collection_config_read.params.soft_delete_retention_sec {
+            let point_trash = PointTrash::open(shard_path, retention_sec.get())?;
+            segment_holder.point_trash = Some(Arc::new(point_trash));
+        }
 
-        for handler in load_handlers {
-            let segment = handler.join().map_err(|err| {
-                CollectionError::service_error(format!(
-                    "Can't join segment load thread: {:?}",
-                    err.type_id()
-                ))
-            })??;
+        let mut quarantined_segments = Vec::new();
+
+        for (segment_path, handler) in load_handlers {
+            let segment = handler
+                .join()
+                .map_err(|err| {
+                    CollectionError::service_error(format!(
+                        "Can't join segment load thread: {:?}",
+                        err.type_id()
+                    ))
+                })
+                .and_then(|res| res);
+
+            let segment = match segment {
+                Ok(segment) => segment,
+                Err(err) if shared_storage_config.quarantine_corrupted_segments => {
+                    log::error!(
+                        "Failed to load segment {}, moving it to quarantine: {err}",
+                        segment_path.display(),
+                    );
+                    quarantined_segments.push(Self::quarantine_segment(shard_path, &segment_path)?);
+                    continue;
+                }
+                Err(err) => return Err(err),
+            };
 
             let Some(segment) = segment else {
                 continue;
@@ -339,7 +411,7 @@ impl LocalShard {
             )?;
         }
 
-        let local_shard = LocalShard::new(
+        let mut local_shard = LocalShard::new(
             segment_holder,
             collection_config,
             shared_storage_config,
@@ -352,6 +424,7 @@ impl LocalShard {
             update_runtime,
         )
         .await;
+        local_shard.quarantined_segments = quarantined_segments;
 
         // Apply outstanding operations from WAL
         local_shard.load_from_wal(collection_id).await?;
@@ -367,7 +440,10 @@ impl LocalShard {
         //
         // This heuristic is not perfect, but it exclude cases when we don't have enough RAM
         // even to store half of the vector data.
-        let do_mmap_prefault = available_memory_bytes * 2 > vectors_size_bytes;
+        //
+        // With warmup enabled, the collection reads memory mapped files itself.
+        let do_mmap_prefault = !local_shard.shared_storage_config.warmup.enabled
+            && available_memory_bytes * 2 > vectors_size_bytes;
 
         if do_mmap_prefault {
             for (_, segment) in local_shard.segments.read().iter() {
@@ -392,6 +468,38 @@ impl LocalShard {
         shard_path.join("segments")
     }
 
+    /// Directory, segments which failed to load are moved into
+    pub fn quarantine_path(shard_path: &Path) -> PathBuf {
+        shard_path.join("quarantine")
+    }
+
+    /// Names of the segments, which failed to load and were quarantined during the shard load
+    pub fn quarantined_segments(&self) -> &[String] {
+        &self.quarantined_segments
+    }
+
+    /// Move segment out of the segments directory, so that it is not loaded again, but its files
+    /// are kept for inspection. Returns the name of the segment.
+    fn quarantine_segment(shard_path: &Path, segment_path: &Path) -> CollectionResult<String> {
+        let name = segment_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let quarantine_path = Self::quarantine_path(shard_path);
+        std::fs::create_dir_all(&quarantine_path)?;
+        let target_path = quarantine_path.join(&name);
+        if target_path.exists() {
+            std::fs::remove_dir_all(&target_path)?;
+        }
+        std::fs::rename(segment_path, &target_path).map_err(|err| {
+            CollectionError::service_error(format!(
+                "Can't move segment {} to quarantine, due to {err}",
+                segment_path.display(),
+            ))
+        })?;
+        Ok(name)
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn build_local(
         id: ShardId,
@@ -454,6 +562,15 @@ impl LocalShard {
         })?;
 
         let mut segment_holder = SegmentHolder::default();
+        segment_holder.owner = Some((collection_id.clone(), id));
+        if let Some(versions) = config.params.point_history_versions {
+            let point_history = PointHistory::open(shard_path, versions.get() as usize)?;
+            segment_holder.point_history = Some(Arc::new(point_history));
+        }
+        if let Some(retention_sec) = config.params.soft_delete_retention_sec {
+            let point_trash = PointTrash::open(shard_path, retention_sec.get())?;
+            segment_holder.point_trash = Some(Arc::new(point_trash));
+        }
         let mut build_handlers = vec![];
 
         let vector_params = config.params.to_base_vector_data()?;
@@ -465,11 +582,9 @@ impl LocalShard {
             let segment_config = SegmentConfig {
                 vector_data: vector_params.clone(),
                 sparse_vector_data: sparse_vector_params.clone(),
-                payload_storage_type: if config.params.on_disk_payload {
-                    PayloadStorageType::OnDisk
-                } else {
-                    PayloadStorageType::InMemory
-                },
+                payload_storage_type: config.params.payload_storage_type(),
+                payload_columns: config.params.payload_columns.clone(),
+                payload_compression: config.params.payload_compression,
             };
             let segment = thread::Builder::new()
                 .name(format!("shard-build-{collection_id}-{id}"))
@@ -539,6 +654,8 @@ impl LocalShard {
     /// Loads latest collection operations from WAL
     pub async fn load_from_wal(&self, collection_id: CollectionId) -> CollectionResult<()> {
         let mut newest_clocks = self.wal.newest_clocks.lock().await;
+        let mut idempotency_cache = self.idempotency_cache.lock().await;
+        let idempotency_window = self.shared_storage_config.idempotency.window();
         let wal = self.wal.wal.lock();
         let bar = ProgressBar::new(wal.len(false));
 
@@ -578,6 +695,27 @@ impl LocalShard {
                 newest_clocks.advance_clock(clock_tag);
             }
 
+            // Retries of the operations, which are still in WAL, are not applied again after restart
+            if let (Some(key), Some(window)) = (update.idempotency_key, idempotency_window) {
+                let result = UpdateResult {
+                    operation_id: Some(op_num),
+                    status: UpdateStatus::Completed,
+                    clock_tag: None,
+                    conflicts: Vec::new(),
+                };
+                match operation_hash(&update.operation) {
+                    Ok(operation_hash) => idempotency_cache.insert_done(
+                        key,
+                        operation_hash,
+                        result,
+                        Instant::now() + window,
+                    ),
+                    Err(err) => {
+                        log::warn!("Can't restore idempotency key of operation {op_num}: {err}")
+                    }
+                }
+            }
+
             // Propagate `CollectionError::ServiceError`, but skip other error types.
             match &CollectionUpdater::update(segments, op_num, update.operation) {
                 Err(err @ CollectionError::ServiceError { error, backtrace }) => {
@@ -891,6 +1029,41 @@ impl LocalShard {
         Ok(all_points)
     }
 
+    pub fn distinct_values(
+        &self,
+        key: &JsonPath,
+        filter: Option<&Filter>,
+    ) -> CollectionResult<DistinctValues> {
+        let segments = self.segments().read();
+        let mut distinct = DistinctValues::default();
+        for (_id, segment) in segments.iter() {
+            distinct.merge(segment.get().read().distinct_values(key, filter)?);
+        }
+        Ok(distinct)
+    }
+
+    pub fn value_distribution(&self, key: &JsonPath) -> CollectionResult<ValueDistribution> {
+        let segments = self.segments().read();
+        let mut distribution = ValueDistribution::default();
+        for (_id, segment) in segments.iter() {
+            distribution.merge(segment.get().read().value_distribution(key)?);
+        }
+        Ok(distribution)
+    }
+
+    pub fn numeric_stats(
+        &self,
+        key: &JsonPath,
+        filter: Option<&Filter>,
+    ) -> CollectionResult<NumericStats> {
+        let segments = self.segments().read();
+        let mut stats = NumericStats::default();
+        for (_id, segment) in segments.iter() {
+            stats.merge(segment.get().read().numeric_stats(key, filter)?);
+        }
+        Ok(stats)
+    }
+
     pub fn get_telemetry_data(&self, detail: TelemetryDetail) -> LocalShardTelemetry {
         let segments_read_guard = self.segments.read();
         let segments: Vec<_> = segments_read_guard
@@ -922,9 +1095,259 @@ impl LocalShard {
                 optimizations,
                 log: self.optimizers_log.lock().to_telemetry(),
             },
+            update_queue: Some(self.update_queue_telemetry()),
+        }
+    }
+
+    /// Deleted points with the given ids, `None` if the collection doesn't keep deleted points
+    pub fn trashed_points(
+        &self,
+        point_ids: &[PointIdType],
+    ) -> CollectionResult<Option<Vec<TrashedPoint>>> {
+        let Some(point_trash) = self.segments.read().point_trash.clone() else {
+            return Ok(None);
+        };
+        let mut points = Vec::new();
+        for &point_id in point_ids {
+            points.extend(point_trash.get(point_id)?);
+        }
+        Ok(Some(points))
+    }
+
+    /// Deleted points with ids in `from..to`, which match the filter
+    pub fn scan_trash(
+        &self,
+        from: Option<PointIdType>,
+        to: Option<PointIdType>,
+        filter: Option<&Filter>,
+    ) -> CollectionResult<Vec<TrashedPoint>> {
+        match self.segments.read().point_trash.clone() {
+            Some(point_trash) => point_trash.scan(from, to, filter),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Retained versions of the point, `None` if the collection doesn't retain point history
+    pub fn point_history(
+        &self,
+        point_id: PointIdType,
+    ) -> CollectionResult<Option<Vec<PointVersion>>> {
+        let Some(point_history) = self.segments.read().point_history.clone() else {
+            return Ok(None);
+        };
+        point_history.get(point_id).map(Some)
+    }
+
+    /// Counters of the recent reads and writes, with the length of the window they cover,
+    /// and the log of the optimizations
+    pub fn stats(&self) -> (StatsCounters, Duration, Vec<TrackerTelemetry>) {
+        let (counters, window) = self.stats.window();
+        (counters, window, self.optimizers_log.lock().to_telemetry())
+    }
+
+    /// Whether all fields, used by the filter, are indexed
+    pub(super) fn is_filter_indexed(&self, filter: &Filter) -> bool {
+        unindexed_fields(filter, &self.payload_index_schema.read().schema).is_empty()
+    }
+
+    pub fn update_queue_telemetry(&self) -> UpdateQueueTelemetry {
+        let update_sender = self.update_sender.load();
+        UpdateQueueTelemetry {
+            length: update_sender.max_capacity() - update_sender.capacity(),
+            capacity: update_sender.max_capacity(),
+            rejected: self.update_queue_rejected.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Wait for a place in the update queue, if it is full, according to the update backpressure
+    /// config. Rejects the update with a rate limit error, if no place is freed in time.
+    pub async fn check_update_queue(&self) -> CollectionResult<()> {
+        let config = &self.shared_storage_config.update_backpressure;
+        if !config.enabled {
+            return Ok(());
+        }
+
+        let update_sender = self.update_sender.load_full();
+        if update_sender.capacity() > 0 {
+            return Ok(());
+        }
+
+        let max_wait = Duration::from_millis(config.max_wait_ms);
+        match tokio::time::timeout(max_wait, update_sender.reserve()).await {
+            // Place is released, so the update is queued in the usual way
+            Ok(permit) => {
+                drop(permit?);
+                Ok(())
+            }
+            Err(_elapsed) => {
+                self.update_queue_rejected.fetch_add(1, Ordering::Relaxed);
+                Err(CollectionError::rate_limit_exceeded(format!(
+                    "update queue of the shard is full ({} operations), retry later",
+                    update_sender.max_capacity(),
+                )))
+            }
         }
     }
 
+    /// Internals of each segment of the shard
+    pub fn segments_info(&self, shard_id: ShardId) -> ShardSegmentsInfo {
+        let segments = self
+            .segments
+            .read()
+            .iter()
+            .map(|(_id, segment)| {
+                let segment = segment.get();
+                let segment = segment.read();
+                let uuid = segment
+                    .data_path()
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                let telemetry = segment.get_telemetry_data(TelemetryDetail::default());
+                SegmentDetails::new(uuid, telemetry, segment.created_at())
+            })
+            .collect();
+
+        let last_optimization_at = self
+            .optimizers_log
+            .lock()
+            .to_telemetry()
+            .into_iter()
+            .filter_map(|tracker| tracker.end_at)
+            .max();
+
+        ShardSegmentsInfo {
+            shard_id,
+            last_optimization_at,
+            segments,
+            quarantined_segments: self.quarantined_segments.clone(),
+        }
+    }
+
+    /// Estimate the effect of changing the collection config to `new_config` on this shard
+    pub fn config_change_impact(
+        &self,
+        shard_id: ShardId,
+        new_config: &CollectionConfig,
+    ) -> ShardConfigChangeImpact {
+        let optimizer = build_config_mismatch_optimizer(&self.path, new_config);
+        let mut impact = ShardConfigChangeImpact {
+            shard_id,
+            ..Default::default()
+        };
+
+        for (_id, segment) in self.segments.read().iter() {
+            let segment = segment.get();
+            let segment = segment.read();
+            impact.total_segments += 1;
+            if !optimizer.requires_rebuild(&*segment) {
+                continue;
+            }
+            impact.segments_to_rebuild += 1;
+            impact.points_to_rebuild += segment.available_point_count();
+
+            let info = segment.info();
+            for (vector_name, vector_config) in &segment.config().vector_data {
+                let num_vectors = info
+                    .vector_data
+                    .get(vector_name)
+                    .map_or(0, |vector_info| vector_info.num_vectors);
+                let target_config = optimizer.target_vector_config(vector_name, vector_config);
+                let current = VectorFootprint::estimate(vector_config, num_vectors);
+                let target = VectorFootprint::estimate(&target_config, num_vectors);
+                let (disk_delta, ram_delta) = current.delta(&target);
+                impact.disk_delta_bytes += disk_delta;
+                impact.ram_delta_bytes += ram_delta;
+            }
+        }
+
+        impact.estimated_reindex_time_sec = self
+            .optimizers_log
+            .lock()
+            .points_per_second()
+            .map(|points_per_second| impact.points_to_rebuild as f64 / points_per_second);
+        impact
+    }
+
+    /// Memory mapped files of the segments, which are read during search
+    pub fn mmap_files(&self) -> Vec<PathBuf> {
+        self.segments
+            .read()
+            .iter()
+            .filter_map(|(_id, segment)| match segment {
+                LockedSegment::Original(segment) => Some(segment.read().mmap_files()),
+                LockedSegment::Proxy(_) => None,
+            })
+            .flatten()
+            .collect()
+    }
+
+    /// Size of the shard on disk, with its projected growth
+    pub async fn disk_usage(&self, shard_id: ShardId) -> CollectionResult<ShardDiskUsage> {
+        self.disk_usage_watcher.disk_usage(shard_id).await
+    }
+
+    /// Freeze updates of segments, flushes and optimizations of the shard, see
+    /// [`FreezeState`](crate::common::freeze::FreezeState). Ongoing optimizations are cancelled.
+    ///
+    /// Returns the time, at which the freeze is lifted automatically.
+    pub async fn freeze(&self, timeout: Duration) -> CollectionResult<DateTime<Utc>> {
+        let update_handler = self.update_handler.lock().await;
+        let frozen_until = update_handler.freeze.freeze(timeout).await;
+        update_handler.stop_optimizations().await?;
+        Ok(frozen_until)
+    }
+
+    pub async fn unfreeze(&self) -> CollectionResult<()> {
+        self.update_handler.lock().await.freeze.unfreeze().await;
+        // Restart optimizations, which were skipped or cancelled during the freeze
+        self.update_sender.load().send(UpdateSignal::Nop).await?;
+        Ok(())
+    }
+
+    /// Time, at which the freeze is lifted automatically, `None` if the shard is not frozen
+    pub async fn frozen_until(&self) -> Option<DateTime<Utc>> {
+        self.update_handler.lock().await.freeze.frozen_until().await
+    }
+
+    /// Number of points and sizes of the segments, limited by quotas of the collection
+    pub async fn quota_usage(&self) -> CollectionResult<QuotaUsage> {
+        let segments = self.segments.clone();
+        let usage = tokio::task::spawn_blocking(move || {
+            let mut usage = QuotaUsage::default();
+            for (_id, segment) in segments.read().iter() {
+                let info = segment.get().read().size_info();
+                usage.points_count += info.num_points;
+                usage.ram_usage_bytes += info.ram_usage_bytes;
+                usage.disk_usage_bytes += info.disk_usage_bytes;
+            }
+            usage
+        })
+        .await?;
+        Ok(usage)
+    }
+
+    /// Document frequencies of the sparse vector over all segments of the shard
+    pub async fn sparse_vocabulary_stats(
+        &self,
+        vector_name: &str,
+    ) -> CollectionResult<SparseVocabularyStats> {
+        let segments = self.segments.clone();
+        let vector_name = vector_name.to_string();
+        let stats = tokio::task::spawn_blocking(move || {
+            let mut stats = SparseVocabularyStats::default();
+            for (_id, segment) in segments.read().iter() {
+                stats.points_count += segment.get().read().fill_sparse_document_frequencies(
+                    &vector_name,
+                    &mut stats.document_frequencies,
+                );
+            }
+            stats
+        })
+        .await?;
+        Ok(stats)
+    }
+
     /// Returns estimated size of vector data in bytes
     async fn estimate_vector_data_size(&self) -> usize {
         let info = self.local_shard_info().await;
@@ -971,13 +1394,37 @@ impl LocalShard {
         let mut status = CollectionStatus::Green;
         let mut schema: HashMap<PayloadKeyType, PayloadIndexInfo> = Default::default();
         let mut optimizer_status = OptimizersStatus::Ok;
+        let mut deferred_segments_count = 0;
+        let optimizer_config = &collection_config.optimizer_config;
+        let indexing_threshold_bytes = optimizer_config
+            .indexing_threshold_kb()
+            .saturating_mul(1024);
 
         {
             let segments = self.segments().read();
             for (_idx, segment) in segments.iter() {
                 segments_count += 1;
 
-                let segment_info = segment.get().read().info();
+                let segment_entry = segment.get();
+                let read_segment = segment_entry.read();
+                let segment_info = read_segment.info();
+
+                if optimizer_config.defer_indexing
+                    && segment_info.segment_type != SegmentType::Special
+                {
+                    let segment_config = read_segment.config();
+                    let is_deferred = segment_config.vector_data.iter().any(|(name, data)| {
+                        !data.index.is_indexed()
+                            && read_segment
+                                .available_vectors_size_in_bytes(name)
+                                .unwrap_or_default()
+                                >= indexing_threshold_bytes
+                    });
+                    if is_deferred {
+                        deferred_segments_count += 1;
+                    }
+                }
+                drop(read_segment);
 
                 if segment_info.segment_type == SegmentType::Special {
                     status = CollectionStatus::Yellow;
@@ -1001,6 +1448,16 @@ impl LocalShard {
             }
         }
 
+        // Segments, which would be indexed if indexing was not deferred, are a pending optimization
+        if status == CollectionStatus::Green && deferred_segments_count > 0 {
+            status = CollectionStatus::Grey;
+            if optimizer_status == OptimizersStatus::Ok {
+                optimizer_status = OptimizersStatus::Error(format!(
+                    "indexing of {deferred_segments_count} segments is deferred",
+                ));
+            }
+        }
+
         // If still green while optimization conditions are triggered, mark as grey
         if status == CollectionStatus::Green
             && self.update_handler.lock().await.has_pending_optimizations()
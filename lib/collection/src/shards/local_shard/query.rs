@@ -4,18 +4,25 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use api::rest::OrderByInterface;
+use common::hardware_counter::HardwareCounterCell;
+use common::types::ScoreType;
 use futures::future::BoxFuture;
 use futures::FutureExt;
+use ordered_float::OrderedFloat;
 use segment::common::reciprocal_rank_fusion::rrf_scoring;
-use segment::types::{Filter, HasIdCondition, ScoredPoint, WithPayloadInterface, WithVector};
+use segment::common::weighted_sum_fusion::weighted_sum_scoring;
+use segment::types::{
+    Filter, HasIdCondition, Payload, PointIdType, ScoredPoint, WithPayloadInterface, WithVector,
+};
 use tokio::runtime::Handle;
 
 use super::LocalShard;
 use crate::collection_manager::segments_searcher::SegmentsSearcher;
 use crate::operations::types::{
-    CollectionError, CollectionResult, CoreSearchRequest, CoreSearchRequestBatch,
+    CollectionError, CollectionResult, CoreSearchRequest, CoreSearchRequestBatch, PartialResponse,
     QueryScrollRequestInternal,
 };
+use crate::operations::universal_query::formula::ParsedFormula;
 use crate::operations::universal_query::planned_query::{
     MergePlan, PlannedQuery, RescoreParams, Source,
 };
@@ -54,16 +61,21 @@ impl LocalShard {
         request: PlannedQuery,
         search_runtime_handle: &Handle,
         timeout: Option<Duration>,
-    ) -> CollectionResult<Vec<ShardQueryResponse>> {
+        hw_counter: &HardwareCounterCell,
+    ) -> CollectionResult<PartialResponse<Vec<ShardQueryResponse>>> {
         let start_time = std::time::Instant::now();
+        // Explicit timeout of a request allows to respond with partial results
+        let partial_on_timeout = timeout.is_some();
         let timeout = timeout.unwrap_or(self.shared_storage_config.search_timeout);
 
-        let searches_f = self.do_search(
+        let searches_f = self.query_search_batch(
             Arc::new(CoreSearchRequestBatch {
                 searches: request.searches,
             }),
             search_runtime_handle,
-            Some(timeout),
+            timeout,
+            partial_on_timeout,
+            hw_counter,
         );
 
         let scrolls_f =
@@ -71,7 +83,7 @@ impl LocalShard {
 
         // execute both searches and scrolls concurrently
         let (search_results, scroll_results) = tokio::try_join!(searches_f, scrolls_f)?;
-        let prefetch_holder = PrefetchResults::new(search_results, scroll_results);
+        let prefetch_holder = PrefetchResults::new(search_results.value, scroll_results);
 
         // decrease timeout by the time spent so far
         let timeout = timeout.saturating_sub(start_time.elapsed());
@@ -82,13 +94,51 @@ impl LocalShard {
                 &prefetch_holder,
                 search_runtime_handle,
                 timeout,
+                partial_on_timeout,
                 0,
+                hw_counter,
             )
         });
 
         let batched_scored_points = futures::future::try_join_all(merge_futures).await?;
 
-        Ok(batched_scored_points)
+        let timed_out = search_results.timed_out
+            || batched_scored_points
+                .iter()
+                .any(|scored_points| scored_points.timed_out);
+
+        Ok(PartialResponse {
+            value: batched_scored_points
+                .into_iter()
+                .map(|scored_points| scored_points.value)
+                .collect(),
+            timed_out,
+            shards: Vec::new(),
+        })
+    }
+
+    /// Search, which returns the points found so far at the `timeout`, if `partial_on_timeout`
+    async fn query_search_batch(
+        &self,
+        core_request: Arc<CoreSearchRequestBatch>,
+        search_runtime_handle: &Handle,
+        timeout: Duration,
+        partial_on_timeout: bool,
+        hw_counter: &HardwareCounterCell,
+    ) -> CollectionResult<PartialResponse<Vec<Vec<ScoredPoint>>>> {
+        if partial_on_timeout {
+            self.do_search_partial(core_request, search_runtime_handle, timeout, hw_counter)
+                .await
+        } else {
+            self.do_search(
+                core_request,
+                search_runtime_handle,
+                Some(timeout),
+                hw_counter,
+            )
+            .await
+            .map(PartialResponse::new)
+        }
     }
 
     /// Fetches the payload and/or vector if required. This will filter out points if they are deleted between search and retrieve.
@@ -135,20 +185,92 @@ impl LocalShard {
         Ok(query_response)
     }
 
+    /// Score the points of all sources by the formula, sorted by the score descending
+    fn score_with_formula<'a>(
+        &self,
+        sources: impl Iterator<Item = Cow<'a, Vec<ScoredPoint>>>,
+        formula: &ParsedFormula,
+    ) -> CollectionResult<Vec<ScoredPoint>> {
+        // Scores of each point in each source, in order of the sources
+        let mut points: HashMap<PointIdType, (ScoredPoint, Vec<Option<ScoreType>>)> =
+            HashMap::new();
+        let mut num_sources = 0;
+        for (index, source) in sources.enumerate() {
+            num_sources = index + 1;
+            for point in source.iter() {
+                let (_, scores) = points
+                    .entry(point.id)
+                    .or_insert_with(|| (point.clone(), Vec::new()));
+                scores.resize(index + 1, None);
+                scores[index] = Some(point.score);
+            }
+        }
+
+        let payload_keys = formula.payload_keys();
+        let payloads: HashMap<_, _> = if payload_keys.is_empty() {
+            HashMap::new()
+        } else {
+            let point_ids: Vec<_> = points.keys().copied().collect();
+            SegmentsSearcher::retrieve(
+                self.segments(),
+                &point_ids,
+                &(&WithPayloadInterface::Fields(payload_keys)).into(),
+                &WithVector::Bool(false),
+            )?
+            .into_iter()
+            .filter_map(|record| Some((record.id, record.payload?)))
+            .collect()
+        };
+
+        let attributes = if formula.has_attributes() {
+            let point_ids: Vec<_> = points.keys().copied().collect();
+            SegmentsSearcher::retrieve_attributes(self.segments(), &point_ids)?
+        } else {
+            HashMap::new()
+        };
+
+        let empty_payload = Payload::default();
+        let empty_attributes = HashMap::new();
+        let mut scored: Vec<_> = points
+            .into_values()
+            .map(|(mut point, mut scores)| {
+                scores.resize(num_sources, None);
+                let payload = payloads.get(&point.id).unwrap_or(&empty_payload);
+                let point_attributes = attributes.get(&point.id).unwrap_or(&empty_attributes);
+                let score = formula.evaluate(&scores, payload, point_attributes)?;
+                if !score.is_finite() {
+                    return Err(CollectionError::bad_request(format!(
+                        "Formula evaluated to {score} for point {}",
+                        point.id,
+                    )));
+                }
+                point.score = score;
+                Ok(point)
+            })
+            .collect::<CollectionResult<_>>()?;
+        scored.sort_unstable_by_key(|point| std::cmp::Reverse(OrderedFloat(point.score)));
+
+        Ok(scored)
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn recurse_prefetch<'shard, 'query>(
         &'shard self,
         merge_plan: MergePlan,
         prefetch_holder: &'query PrefetchResults,
         search_runtime_handle: &'shard Handle,
         timeout: Duration,
+        partial_on_timeout: bool,
         depth: usize,
-    ) -> BoxFuture<'query, CollectionResult<Vec<Vec<ScoredPoint>>>>
+        hw_counter: &'query HardwareCounterCell,
+    ) -> BoxFuture<'query, CollectionResult<PartialResponse<Vec<Vec<ScoredPoint>>>>>
     where
         'shard: 'query,
     {
         async move {
             let max_len = merge_plan.sources.len();
             let mut cow_sources = Vec::with_capacity(max_len);
+            let mut timed_out = false;
 
             // We need to preserve the order of the sources for some fusion strategies
             for source in merge_plan.sources.into_iter() {
@@ -166,12 +288,13 @@ impl LocalShard {
                                 prefetch_holder,
                                 search_runtime_handle,
                                 timeout,
+                                partial_on_timeout,
                                 depth + 1,
+                                hw_counter,
                             )
-                            .await?
-                            .into_iter()
-                            .map(Cow::Owned);
-                        cow_sources.extend(merged);
+                            .await?;
+                        timed_out |= merged.timed_out;
+                        cow_sources.extend(merged.value.into_iter().map(Cow::Owned));
                     }
                 }
             }
@@ -184,16 +307,26 @@ impl LocalShard {
                         rescore_params,
                         search_runtime_handle,
                         timeout,
+                        partial_on_timeout,
+                        hw_counter,
                     )
                     .await?;
 
-                Ok(vec![rescored])
+                Ok(PartialResponse {
+                    value: vec![rescored.value],
+                    timed_out: timed_out || rescored.timed_out,
+                    shards: Vec::new(),
+                })
             } else {
                 // The sources here are passed to the next layer without any extra processing.
                 // It is either a query without prefetches, or a fusion request and the intermediate results are passed to the next layer.
                 debug_assert_eq!(depth, 0);
                 // TODO(universal-query): maybe there's a way to pass ownership of the prefetch_holder to avoid cloning with Cow::into_owned here
-                Ok(cow_sources.into_iter().map(Cow::into_owned).collect())
+                Ok(PartialResponse {
+                    value: cow_sources.into_iter().map(Cow::into_owned).collect(),
+                    timed_out,
+                    shards: Vec::new(),
+                })
             }
         }
         .boxed()
@@ -206,38 +339,64 @@ impl LocalShard {
         rescore_params: RescoreParams,
         search_runtime_handle: &Handle,
         timeout: Duration,
-    ) -> CollectionResult<Vec<ScoredPoint>> {
+        partial_on_timeout: bool,
+        hw_counter: &HardwareCounterCell,
+    ) -> CollectionResult<PartialResponse<Vec<ScoredPoint>>> {
         let RescoreParams {
             rescore,
             offset,
             score_threshold,
+            params,
             limit,
             with_vector,
             with_payload,
         } = rescore_params;
 
         match rescore {
-            ScoringQuery::Fusion(Fusion::Rrf) => {
+            ScoringQuery::Fusion(fusion) => {
                 let sources: Vec<_> = sources.map(Cow::into_owned).collect();
 
-                let top_rrf = rrf_scoring(sources);
+                let top_fused = match fusion {
+                    Fusion::Rrf => rrf_scoring(sources),
+                    Fusion::WeightedSum {
+                        weights,
+                        normalization,
+                    } => weighted_sum_scoring(sources, &weights, normalization),
+                };
 
-                let top_rrf: Vec<_> = if let Some(score_threshold) = score_threshold {
-                    top_rrf
+                let top_fused: Vec<_> = if let Some(score_threshold) = score_threshold {
+                    top_fused
                         .into_iter()
                         .take_while(|point| point.score >= score_threshold)
                         .skip(offset)
                         .take(limit)
                         .collect()
                 } else {
-                    top_rrf.into_iter().skip(offset).take(limit).collect()
+                    top_fused.into_iter().skip(offset).take(limit).collect()
                 };
 
-                let filled_top_rrf = self
-                    .fill_with_payload_or_vectors(top_rrf, with_payload, with_vector)
+                let filled_top_fused = self
+                    .fill_with_payload_or_vectors(top_fused, with_payload, with_vector)
                     .await?;
 
-                Ok(filled_top_rrf)
+                Ok(PartialResponse::new(filled_top_fused))
+            }
+            ScoringQuery::Formula(formula) => {
+                let scored = self.score_with_formula(sources, &formula)?;
+
+                let top_scored: Vec<_> = scored
+                    .into_iter()
+                    .take_while(|point| match score_threshold {
+                        Some(score_threshold) => point.score >= score_threshold,
+                        None => true,
+                    })
+                    .skip(offset)
+                    .take(limit)
+                    .collect();
+
+                self.fill_with_payload_or_vectors(top_scored, with_payload, with_vector)
+                    .await
+                    .map(PartialResponse::new)
             }
             ScoringQuery::OrderBy(order_by) => {
                 // create single scroll request for rescoring query
@@ -261,6 +420,7 @@ impl LocalShard {
                 )
                 .await?
                 .pop()
+                .map(PartialResponse::new)
                 .ok_or_else(|| {
                     CollectionError::service_error(
                         "Rescoring with order-by query didn't return expected batch of results",
@@ -274,7 +434,7 @@ impl LocalShard {
                 let search_request = CoreSearchRequest {
                     query: query_enum,
                     filter: Some(filter),
-                    params: None,
+                    params,
                     limit,
                     offset,
                     with_payload: Some(with_payload),
@@ -286,18 +446,25 @@ impl LocalShard {
                     searches: vec![search_request],
                 };
 
-                self.do_search(
-                    Arc::new(rescoring_core_search_request),
-                    search_runtime_handle,
-                    Some(timeout),
-                )
-                .await?
+                let mut rescored = self
+                    .query_search_batch(
+                        Arc::new(rescoring_core_search_request),
+                        search_runtime_handle,
+                        timeout,
+                        partial_on_timeout,
+                        hw_counter,
+                    )
+                    .await?;
                 // One search request is sent. We expect only one result
-                .pop()
-                .ok_or_else(|| {
+                let points = rescored.value.pop().ok_or_else(|| {
                     CollectionError::service_error(
                         "Rescoring with vector(s) query didn't return expected batch of results",
                     )
+                })?;
+                Ok(PartialResponse {
+                    value: points,
+                    timed_out: rescored.timed_out,
+                    shards: Vec::new(),
                 })
             }
         }
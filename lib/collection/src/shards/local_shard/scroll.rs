@@ -3,7 +3,7 @@ use std::time::Duration;
 
 use futures::future::try_join_all;
 use itertools::Itertools as _;
-use segment::data_types::order_by::{Direction, OrderBy, OrderValue};
+use segment::data_types::order_by::{OrderBy, OrderedPoint};
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
 };
@@ -101,14 +101,14 @@ impl LocalShard {
                     .into_iter()
                     .zip(values)
                     .skip(*offset)
-                    .map(|(record, value)| ScoredPoint {
+                    .map(|(record, point)| ScoredPoint {
                         id: record.id,
                         version: 0,
                         score: 0.0,
                         payload: record.payload,
                         vector: record.vector,
                         shard_key: record.shard_key,
-                        order_value: Some(value),
+                        order_value: Some(point.value),
                     })
                     .collect()
             }
@@ -170,7 +170,7 @@ impl LocalShard {
         filter: Option<&Filter>,
         search_runtime_handle: &Handle,
         order_by: &OrderBy,
-    ) -> CollectionResult<(Vec<Record>, Vec<OrderValue>)> {
+    ) -> CollectionResult<(Vec<Record>, Vec<OrderedPoint>)> {
         let segments = self.segments();
 
         let (non_appendable, appendable) = segments.read().split_segments();
@@ -196,21 +196,20 @@ impl LocalShard {
             .chain(appendable)
             .collect::<Result<Vec<_>, _>>()?;
 
-        let (values, point_ids): (Vec<_>, Vec<_>) = all_reads
+        let ordered_points = all_reads
             .into_iter()
-            .kmerge_by(|a, b| match order_by.direction() {
-                Direction::Asc => a <= b,
-                Direction::Desc => a >= b,
-            })
+            .kmerge_by(|a, b| order_by.cmp_points(a, b).is_le())
             .dedup()
             .take(limit)
-            .unzip();
+            .collect_vec();
+
+        let point_ids = ordered_points.iter().map(|point| point.id).collect_vec();
 
         let with_payload = WithPayload::from(with_payload_interface);
 
         // Fetch with the requested vector and payload
         let records = SegmentsSearcher::retrieve(segments, &point_ids, &with_payload, with_vector)?;
 
-        Ok((records, values))
+        Ok((records, ordered_points))
     }
 }
@@ -1,6 +1,10 @@
+use std::pin::pin;
 use std::sync::Arc;
 use std::time::Duration;
 
+use common::hardware_counter::HardwareCounterCell;
+use parking_lot::Mutex;
+use segment::data_types::search_plan::SegmentSearchPlan;
 use segment::types::ScoredPoint;
 use tokio::runtime::Handle;
 
@@ -8,7 +12,12 @@ use super::LocalShard;
 use crate::collection_manager::segments_searcher::SegmentsSearcher;
 use crate::common::stopping_guard::StoppingGuard;
 use crate::operations::query_enum::QueryEnum;
-use crate::operations::types::{CollectionError, CollectionResult, CoreSearchRequestBatch};
+use crate::operations::types::{
+    CollectionError, CollectionResult, CoreSearchRequestBatch, PartialResponse,
+};
+
+/// Time given to stopped segment searches to return the points found so far
+pub(crate) const STOPPED_SEARCH_GRACE_PERIOD: Duration = Duration::from_secs(1);
 
 impl LocalShard {
     pub async fn do_search(
@@ -16,7 +25,72 @@ impl LocalShard {
         core_request: Arc<CoreSearchRequestBatch>,
         search_runtime_handle: &Handle,
         timeout: Option<Duration>,
+        hw_counter: &HardwareCounterCell,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        self.do_search_impl(
+            core_request,
+            search_runtime_handle,
+            timeout,
+            false,
+            None,
+            hw_counter,
+        )
+        .await
+        .map(|response| response.value)
+    }
+
+    /// Same as [`LocalShard::do_search`], but once `timeout` is reached, segment searches are
+    /// stopped and the points found so far are returned, instead of a timeout error
+    pub async fn do_search_partial(
+        &self,
+        core_request: Arc<CoreSearchRequestBatch>,
+        search_runtime_handle: &Handle,
+        timeout: Duration,
+        hw_counter: &HardwareCounterCell,
+    ) -> CollectionResult<PartialResponse<Vec<Vec<ScoredPoint>>>> {
+        self.do_search_impl(
+            core_request,
+            search_runtime_handle,
+            Some(timeout),
+            true,
+            None,
+            hw_counter,
+        )
+        .await
+    }
+
+    /// Same as [`LocalShard::do_search`], but also reports how each segment executed the search
+    pub async fn do_search_explain(
+        &self,
+        core_request: Arc<CoreSearchRequestBatch>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+        hw_counter: &HardwareCounterCell,
+    ) -> CollectionResult<(Vec<Vec<ScoredPoint>>, Vec<SegmentSearchPlan>)> {
+        let search_plans = Arc::new(Mutex::new(Vec::new()));
+        let res = self
+            .do_search_impl(
+                core_request,
+                search_runtime_handle,
+                timeout,
+                false,
+                Some(search_plans.clone()),
+                hw_counter,
+            )
+            .await?;
+        let search_plans = std::mem::take(&mut *search_plans.lock());
+        Ok((res.value, search_plans))
+    }
+
+    async fn do_search_impl(
+        &self,
+        core_request: Arc<CoreSearchRequestBatch>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+        partial_on_timeout: bool,
+        search_plans: Option<Arc<Mutex<Vec<SegmentSearchPlan>>>>,
+        hw_counter: &HardwareCounterCell,
+    ) -> CollectionResult<PartialResponse<Vec<Vec<ScoredPoint>>>> {
         let is_stopped_guard = StoppingGuard::new();
 
         let (query_context, collection_params) = {
@@ -30,38 +104,66 @@ impl LocalShard {
             )
             .await?;
 
-            let Some(query_context) = query_context_opt else {
+            let Some(mut query_context) = query_context_opt else {
                 // No segments to search
-                return Ok(vec![]);
+                return Ok(PartialResponse::new(vec![]));
             };
 
+            query_context = query_context.with_hardware_counter(hw_counter.clone());
+
+            if let Some(search_plans) = search_plans {
+                query_context = query_context.with_search_plans(search_plans);
+            }
+
             (query_context, collection_config.params.clone())
         };
 
-        let search_request = SegmentsSearcher::search(
+        let mut search_request = pin!(SegmentsSearcher::search(
             Arc::clone(&self.segments),
             Arc::clone(&core_request),
             search_runtime_handle,
             true,
             query_context,
-        );
+        ));
 
         let timeout = timeout.unwrap_or(self.shared_storage_config.search_timeout);
+        let timeout_error = || CollectionError::timeout(timeout.as_secs() as usize, "Search");
 
-        let res = tokio::time::timeout(timeout, search_request)
-            .await
-            .map_err(|_| {
+        let mut timed_out = false;
+        let res = match tokio::time::timeout(timeout, &mut search_request).await {
+            Ok(res) => res?,
+            Err(_) if partial_on_timeout => {
+                log::debug!(
+                    "Search timeout reached: {} seconds, returning partial results",
+                    timeout.as_secs(),
+                );
+                // Stopped segments return the best points they have found so far
+                is_stopped_guard.stop();
+                timed_out = true;
+                match tokio::time::timeout(STOPPED_SEARCH_GRACE_PERIOD, search_request).await {
+                    Ok(Ok(res)) => res,
+                    // Some search paths can't return partial results and are cancelled instead
+                    Ok(Err(CollectionError::Cancelled { .. })) | Err(_) => {
+                        return Err(timeout_error());
+                    }
+                    Ok(Err(err)) => return Err(err),
+                }
+            }
+            Err(_) => {
                 log::debug!("Search timeout reached: {} seconds", timeout.as_secs());
                 // StoppingGuard takes care of setting is_stopped to true
-                CollectionError::timeout(timeout.as_secs() as usize, "Search")
-            })??;
+                return Err(timeout_error());
+            }
+        };
 
         let top_results = res
             .into_iter()
             .zip(core_request.searches.iter())
             .map(|(vector_res, req)| {
                 let vector_name = req.query.get_vector_name();
-                let distance = collection_params.get_distance(vector_name).unwrap();
+                let distance = collection_params
+                    .get_search_distance(vector_name, req.params.as_ref())
+                    .unwrap();
                 let processed_res = vector_res.into_iter().map(|mut scored_point| {
                     match req.query {
                         QueryEnum::Nearest(_) => {
@@ -86,6 +188,10 @@ impl LocalShard {
                 }
             })
             .collect();
-        Ok(top_results)
+        Ok(PartialResponse {
+            value: top_results,
+            timed_out,
+            shards: Vec::new(),
+        })
     }
 }
@@ -0,0 +1,158 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+
+use segment::entry::entry_point::SegmentEntry as _;
+use segment::segment::Segment;
+use segment::segment_constructor::load_segment;
+use segment::types::SegmentConfig;
+use uuid::Uuid;
+
+use super::LocalShard;
+use crate::collection_manager::holders::segment_holder::{LockedSegment, SegmentId};
+use crate::operations::types::{CollectionError, CollectionResult};
+
+impl LocalShard {
+    /// Id of the segment in the segment holder, by the name of its directory
+    fn segment_id_by_uuid(
+        &self,
+        segment_uuid: &str,
+    ) -> CollectionResult<(SegmentId, LockedSegment)> {
+        self.segments
+            .read()
+            .iter()
+            .find(|(_id, segment)| {
+                segment
+                    .get()
+                    .read()
+                    .data_path()
+                    .file_name()
+                    .is_some_and(|name| name == segment_uuid)
+            })
+            .map(|(id, segment)| (*id, segment.clone()))
+            .ok_or_else(|| CollectionError::not_found(format!("Segment {segment_uuid}")))
+    }
+
+    /// Archive a single segment of the shard into `target_dir`.
+    ///
+    /// Returns the path of the archive.
+    pub async fn create_segment_snapshot(
+        &self,
+        segment_uuid: &str,
+        temp_path: &Path,
+        target_dir: &Path,
+    ) -> CollectionResult<PathBuf> {
+        let (_id, segment) = self.segment_id_by_uuid(segment_uuid)?;
+        let temp_path = temp_path.to_owned();
+        let target_dir = target_dir.to_owned();
+
+        let archive_path = tokio::task::spawn_blocking(move || {
+            segment.get().read().take_snapshot(&temp_path, &target_dir)
+        })
+        .await??;
+
+        Ok(archive_path)
+    }
+
+    /// Replace a segment of the shard with the segment from the snapshot archive.
+    ///
+    /// Updates of the replaced segment, which were applied after the snapshot was created, are
+    /// lost. Points from the snapshot, which have newer versions in other segments, are removed.
+    pub async fn replace_segment(
+        &self,
+        segment_uuid: &str,
+        snapshot_path: &Path,
+    ) -> CollectionResult<()> {
+        let (segment_id, old_segment) = self.segment_id_by_uuid(segment_uuid)?;
+        if matches!(old_segment, LockedSegment::Proxy(_)) {
+            return Err(CollectionError::bad_request(format!(
+                "Segment {segment_uuid} is being optimized, try again later",
+            )));
+        }
+        let old_config = old_segment.get().read().config().clone();
+
+        let segments_path = Self::segments_path(&self.path);
+        let snapshot_path = snapshot_path.to_owned();
+        let segments = self.segments.clone();
+
+        tokio::task::spawn_blocking(move || -> CollectionResult<()> {
+            let new_uuid = Uuid::new_v4().to_string();
+
+            // Unpack next to the segments, so that the segment can be moved in place without copying
+            let unpack_dir = tempfile::Builder::new()
+                .prefix(".segment-restore-")
+                .tempdir_in(&segments_path)?;
+            let archive_path = unpack_dir.path().join(format!("{new_uuid}.tar"));
+            std::fs::copy(&snapshot_path, &archive_path)?;
+            Segment::restore_snapshot(&archive_path, &new_uuid)?;
+
+            let new_segment_path = segments_path.join(&new_uuid);
+            std::fs::rename(unpack_dir.path().join(&new_uuid), &new_segment_path)?;
+
+            let new_segment = match load_segment(&new_segment_path, &AtomicBool::new(false)) {
+                Ok(Some(segment)) => segment,
+                Ok(None) => {
+                    std::fs::remove_dir_all(&new_segment_path)?;
+                    return Err(CollectionError::bad_input(
+                        "Segment snapshot doesn't contain a complete segment",
+                    ));
+                }
+                Err(err) => {
+                    std::fs::remove_dir_all(&new_segment_path)?;
+                    return Err(err.into());
+                }
+            };
+
+            if let Err(err) = check_compatible_config(&old_config, new_segment.config()) {
+                new_segment.drop_data()?;
+                return Err(err);
+            }
+
+            let (_new_id, replaced) = segments.write().swap_new(new_segment, &[segment_id]);
+            for segment in replaced {
+                segment.drop_data()?;
+            }
+            segments.read().deduplicate_points()?;
+
+            Ok(())
+        })
+        .await??;
+
+        Ok(())
+    }
+}
+
+/// Check that the segment from a snapshot has the same vectors as the segment it replaces
+fn check_compatible_config(old: &SegmentConfig, new: &SegmentConfig) -> CollectionResult<()> {
+    for (name, old_vector) in &old.vector_data {
+        let Some(new_vector) = new.vector_data.get(name) else {
+            return Err(CollectionError::bad_input(format!(
+                "Segment snapshot doesn't contain vector {name:?}",
+            )));
+        };
+        if new_vector.size != old_vector.size || new_vector.distance != old_vector.distance {
+            return Err(CollectionError::bad_input(format!(
+                "Vector {name:?} of the segment snapshot has size {} and distance {:?}, \
+                 expected size {} and distance {:?}",
+                new_vector.size, new_vector.distance, old_vector.size, old_vector.distance,
+            )));
+        }
+    }
+    if let Some(name) = new
+        .vector_data
+        .keys()
+        .find(|name| !old.vector_data.contains_key(*name))
+    {
+        return Err(CollectionError::bad_input(format!(
+            "Segment snapshot contains unknown vector {name:?}",
+        )));
+    }
+    let old_sparse: HashSet<_> = old.sparse_vector_data.keys().collect();
+    let new_sparse: HashSet<_> = new.sparse_vector_data.keys().collect();
+    if old_sparse != new_sparse {
+        return Err(CollectionError::bad_input(
+            "Sparse vectors of the segment snapshot don't match the replaced segment",
+        ));
+    }
+    Ok(())
+}
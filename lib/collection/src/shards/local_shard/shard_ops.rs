@@ -1,7 +1,10 @@
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
+use common::hardware_counter::HardwareCounterCell;
+use common::scheduling::Workload;
+use segment::data_types::aggregation::{DistinctValues, NumericStats, ValueDistribution};
 use segment::data_types::order_by::OrderBy;
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
@@ -10,14 +13,18 @@ use tokio::runtime::Handle;
 use tokio::sync::oneshot;
 
 use crate::collection_manager::segments_searcher::SegmentsSearcher;
+use crate::common::idempotency::{operation_hash, IdempotencyLookup};
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, PointRequestInternal, Record, UpdateResult, UpdateStatus,
+    CountDistinctRequestInternal, CountRequestInternal, CountResult, FieldStatsRequestInternal,
+    HistogramRequestInternal, PartialResponse, PointRequestInternal, Record, UpdateResult,
+    UpdateStatus,
 };
 use crate::operations::universal_query::planned_query::PlannedQuery;
 use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
 use crate::operations::OperationWithClockTag;
 use crate::shards::local_shard::LocalShard;
+use crate::shards::point_digests::{compute_point_digests, PointDigests, PointDigestsRequest};
 use crate::shards::shard_trait::ShardOperation;
 use crate::update_handler::{OperationData, UpdateSignal};
 
@@ -35,9 +42,12 @@ impl ShardOperation for LocalShard {
         mut operation: OperationWithClockTag,
         wait: bool,
     ) -> CollectionResult<UpdateResult> {
-        // `LocalShard::update` only has a single cancel safe `await`, WAL operations are blocking,
+        // `LocalShard::update` only has cancel safe `await`s, WAL operations are blocking,
         // and update is applied by a separate task, so, surprisingly, this method is cancel safe. :D
 
+        // Operation is queued while waiting for the update channel, and until it is applied
+        let queued = Workload::Ingestion.enter();
+
         let (callback_sender, callback_receiver) = if wait {
             let (tx, rx) = oneshot::channel();
             (Some(tx), Some(rx))
@@ -45,14 +55,53 @@ impl ShardOperation for LocalShard {
             (None, None)
         };
 
-        if self
-            .disk_usage_watcher
-            .is_disk_full()
-            .await?
-            .unwrap_or(false)
+        // Retried update returns the result of the first one, instead of being applied again.
+        // The key is reserved until the operation is written to WAL, so concurrent retries wait
+        // for the first one. Cache is only locked for the lookup.
+        let idempotency_window = self.shared_storage_config.idempotency.window();
+        let idempotency_sender = match (&operation.idempotency_key, idempotency_window) {
+            (Some(key), Some(window)) => {
+                let operation_hash = operation_hash(&operation.operation)?;
+                loop {
+                    let now = Instant::now();
+                    let lookup = self.idempotency_cache.lock().await.lookup(
+                        key,
+                        operation_hash,
+                        now + window,
+                        now,
+                    )?;
+                    let result = match lookup {
+                        IdempotencyLookup::Reserved(sender) => break Some(sender),
+                        IdempotencyLookup::Done(result) => Some(result),
+                        // Look up again, if the first update fails to be written
+                        IdempotencyLookup::InProgress(mut receiver) => receiver
+                            .wait_for(Option::is_some)
+                            .await
+                            .ok()
+                            .and_then(|result| result.clone()),
+                    };
+                    if let Some(result) = result {
+                        return Ok(UpdateResult {
+                            clock_tag: operation.clock_tag,
+                            ..result
+                        });
+                    }
+                }
+            }
+            _ => None,
+        };
+
+        // Deletes are still allowed, as they help to free up space
+        if operation.operation.is_write_operation()
+            && self
+                .disk_usage_watcher
+                .is_disk_full()
+                .await?
+                .unwrap_or(false)
         {
             return Err(CollectionError::service_error(
-                "No space left on device: WAL buffer size exceeds available disk space".to_string(),
+                "No space left on device: free disk space is below the threshold, only deletes are allowed"
+                    .to_string(),
             ));
         }
 
@@ -72,34 +121,56 @@ impl ShardOperation for LocalShard {
                         operation_id: None,
                         status: UpdateStatus::ClockRejected,
                         clock_tag: operation.clock_tag,
+                        conflicts: Vec::new(),
                     });
                 }
 
                 Err(err) => return Err(err.into()),
             };
 
+            if let Some(sender) = &idempotency_sender {
+                sender.send_replace(Some(UpdateResult {
+                    operation_id: Some(operation_id),
+                    status: UpdateStatus::Acknowledged,
+                    clock_tag: None,
+                    conflicts: Vec::new(),
+                }));
+            }
+
             channel_permit.send(UpdateSignal::Operation(OperationData {
                 op_num: operation_id,
                 operation: operation.operation,
                 sender: callback_sender,
                 wait,
+                queued,
             }));
 
             operation_id
         };
 
+        self.stats.record_write();
+
         if let Some(receiver) = callback_receiver {
-            let _res = receiver.await??;
-            Ok(UpdateResult {
+            let outcome = receiver.await??;
+            let result = UpdateResult {
                 operation_id: Some(operation_id),
                 status: UpdateStatus::Completed,
                 clock_tag: operation.clock_tag,
-            })
+                conflicts: outcome.conflicts,
+            };
+            if let Some(sender) = &idempotency_sender {
+                sender.send_replace(Some(UpdateResult {
+                    clock_tag: None,
+                    ..result.clone()
+                }));
+            }
+            Ok(result)
         } else {
             Ok(UpdateResult {
                 operation_id: Some(operation_id),
                 status: UpdateStatus::Acknowledged,
                 clock_tag: operation.clock_tag,
+                conflicts: Vec::new(),
             })
         }
     }
@@ -127,7 +198,7 @@ impl ShardOperation for LocalShard {
                 .await
             }
             Some(order_by) => {
-                let (mut records, values) = self
+                let (mut records, ordered_points) = self
                     .scroll_by_field(
                         limit,
                         with_payload_interface,
@@ -138,13 +209,20 @@ impl ShardOperation for LocalShard {
                     )
                     .await?;
 
-                records.iter_mut().zip(values).for_each(|(record, value)| {
-                    // Add order_by value to the payload. It will be removed in the next step, after crossing the shard boundary.
-                    let new_payload =
-                        OrderBy::insert_order_value_in_payload(record.payload.take(), value);
+                records
+                    .iter_mut()
+                    .zip(ordered_points)
+                    .for_each(|(record, point)| {
+                        // Add order_by values to the payload. They will be removed in the next step, after crossing the shard boundary.
+                        let new_payload = OrderBy::insert_order_value_in_payload(
+                            record.payload.take(),
+                            point.value,
+                        );
+                        let new_payload =
+                            OrderBy::insert_then_by_values_in_payload(new_payload, &point.then_by);
 
-                    record.payload = Some(new_payload);
-                });
+                        record.payload = Some(new_payload);
+                    });
 
                 Ok(records)
             }
@@ -161,9 +239,32 @@ impl ShardOperation for LocalShard {
         request: Arc<CoreSearchRequestBatch>,
         search_runtime_handle: &Handle,
         timeout: Option<Duration>,
-    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
-        self.do_search(request, search_runtime_handle, timeout)
-            .await
+        hw_counter: &HardwareCounterCell,
+    ) -> CollectionResult<PartialResponse<Vec<Vec<ScoredPoint>>>> {
+        let start = Instant::now();
+        // Explicit timeout of a request allows to respond with partial results
+        let result = match timeout {
+            Some(timeout) => {
+                self.do_search_partial(request.clone(), search_runtime_handle, timeout, hw_counter)
+                    .await
+            }
+            None => self
+                .do_search(request.clone(), search_runtime_handle, None, hw_counter)
+                .await
+                .map(PartialResponse::new),
+        };
+        if result.is_ok() {
+            self.stats.record_reads(
+                request.searches.len(),
+                start.elapsed(),
+                request
+                    .searches
+                    .iter()
+                    .filter_map(|search| search.filter.as_ref())
+                    .map(|filter| self.is_filter_indexed(filter)),
+            );
+        }
+        result
     }
 
     async fn count(&self, request: Arc<CountRequestInternal>) -> CollectionResult<CountResult> {
@@ -173,7 +274,41 @@ impl ShardOperation for LocalShard {
         } else {
             self.estimate_cardinality(request.filter.as_ref())?.exp
         };
-        Ok(CountResult { count: total_count })
+        Ok(CountResult {
+            count: total_count,
+            consistent: None,
+        })
+    }
+
+    async fn count_distinct(
+        &self,
+        request: Arc<CountDistinctRequestInternal>,
+    ) -> CollectionResult<DistinctValues> {
+        self.distinct_values(&request.key, request.filter.as_ref())
+    }
+
+    async fn histogram(
+        &self,
+        request: Arc<HistogramRequestInternal>,
+    ) -> CollectionResult<ValueDistribution> {
+        self.value_distribution(&request.key)
+    }
+
+    async fn field_stats(
+        &self,
+        request: Arc<FieldStatsRequestInternal>,
+    ) -> CollectionResult<NumericStats> {
+        self.numeric_stats(&request.key, request.filter.as_ref())
+    }
+
+    async fn point_digests(
+        &self,
+        request: Arc<PointDigestsRequest>,
+    ) -> CollectionResult<PointDigests> {
+        let segments = self.segments.clone();
+        let digests =
+            tokio::task::spawn_blocking(move || compute_point_digests(&segments, &request)).await?;
+        Ok(digests)
     }
 
     async fn retrieve(
@@ -190,10 +325,25 @@ impl ShardOperation for LocalShard {
         requests: Arc<Vec<ShardQueryRequest>>,
         search_runtime_handle: &Handle,
         timeout: Option<Duration>,
-    ) -> CollectionResult<Vec<ShardQueryResponse>> {
+        hw_counter: &HardwareCounterCell,
+    ) -> CollectionResult<PartialResponse<Vec<ShardQueryResponse>>> {
+        let start = Instant::now();
         let planned_query = PlannedQuery::try_from(requests.as_ref().to_owned())?;
 
-        self.do_planned_query(planned_query, search_runtime_handle, timeout)
-            .await
+        let result = self
+            .do_planned_query(planned_query, search_runtime_handle, timeout, hw_counter)
+            .await;
+        if result.is_ok() {
+            self.stats.record_reads(
+                requests.len(),
+                start.elapsed(),
+                requests.iter().filter_map(|request| {
+                    let filters: Vec<_> = request.filter_refs().into_iter().flatten().collect();
+                    (!filters.is_empty())
+                        .then(|| filters.iter().all(|filter| self.is_filter_indexed(filter)))
+                }),
+            );
+        }
+        result
     }
 }
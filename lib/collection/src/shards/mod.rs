@@ -4,6 +4,7 @@ mod conversions;
 pub mod dummy_shard;
 pub mod forward_proxy_shard;
 pub mod local_shard;
+pub mod point_digests;
 pub mod proxy_shard;
 pub mod queue_proxy_shard;
 pub mod remote_shard;
@@ -0,0 +1,121 @@
+use std::collections::{BTreeMap, HashSet};
+
+use segment::entry::entry_point::SegmentEntry as _;
+use segment::types::{ExtendedPointId, PointIdType, SeqNumberType};
+
+use crate::collection_manager::holders::segment_holder::LockedSegmentHolder;
+
+/// Request of the digests of point ranges of a shard, to compare its replicas
+///
+/// Points are hashed into `buckets` by their ids, so that the same points fall into the same
+/// buckets on all replicas.
+#[derive(Debug, Clone)]
+pub struct PointDigestsRequest {
+    pub buckets: usize,
+    /// Buckets, versions of whose points are also returned
+    pub list_buckets: Vec<usize>,
+}
+
+/// Order independent digest of the points in a bucket
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct BucketDigest {
+    pub count: usize,
+    /// XOR of the hashes of the ids and versions of the points
+    pub digest: u64,
+}
+
+impl BucketDigest {
+    fn add(&mut self, point_id: PointIdType, version: SeqNumberType) {
+        let mut bytes = point_id_bytes(point_id);
+        bytes.extend_from_slice(&version.to_le_bytes());
+        self.count += 1;
+        self.digest ^= seahash::hash(&bytes);
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PointDigests {
+    pub buckets: Vec<BucketDigest>,
+    /// Versions of the points in the listed buckets
+    pub points: BTreeMap<PointIdType, SeqNumberType>,
+}
+
+fn point_id_bytes(point_id: PointIdType) -> Vec<u8> {
+    match point_id {
+        ExtendedPointId::NumId(id) => [&[0], id.to_le_bytes().as_slice()].concat(),
+        ExtendedPointId::Uuid(uuid) => [&[1], uuid.as_bytes().as_slice()].concat(),
+    }
+}
+
+/// Bucket of the point, stable across peers and versions
+pub fn point_bucket(point_id: PointIdType, buckets: usize) -> usize {
+    (seahash::hash(&point_id_bytes(point_id)) % buckets as u64) as usize
+}
+
+/// Compute digests of the points in the segments.
+///
+/// A point, which is stored in several segments, is added to the bucket digest once per
+/// segment. This is rare and only leads to a false difference, which is resolved by comparing
+/// the listed versions.
+///
+/// This method performs blocking IO.
+pub fn compute_point_digests(
+    segments: &LockedSegmentHolder,
+    request: &PointDigestsRequest,
+) -> PointDigests {
+    let buckets_count = request.buckets.max(1);
+    let list_buckets: HashSet<_> = request.list_buckets.iter().copied().collect();
+    let mut digests = PointDigests {
+        buckets: vec![BucketDigest::default(); buckets_count],
+        points: BTreeMap::new(),
+    };
+
+    for (_id, segment) in segments.read().iter() {
+        let segment = segment.get();
+        let segment = segment.read();
+        for point_id in segment.iter_points() {
+            let Some(version) = segment.point_version(point_id) else {
+                continue;
+            };
+            let bucket = point_bucket(point_id, buckets_count);
+            digests.buckets[bucket].add(point_id, version);
+            if list_buckets.contains(&bucket) {
+                let listed = digests.points.entry(point_id).or_insert(version);
+                *listed = (*listed).max(version);
+            }
+        }
+    }
+
+    digests
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_digest_is_order_independent() {
+        let points = [(1, 5), (2, 7), (3, 1)];
+
+        let mut forward = BucketDigest::default();
+        for (id, version) in points {
+            forward.add(ExtendedPointId::NumId(id), version);
+        }
+        let mut backward = BucketDigest::default();
+        for (id, version) in points.into_iter().rev() {
+            backward.add(ExtendedPointId::NumId(id), version);
+        }
+        assert_eq!(forward, backward);
+        assert_eq!(forward.count, 3);
+
+        let mut other_version = BucketDigest::default();
+        for (id, version) in [(1, 5), (2, 8), (3, 1)] {
+            other_version.add(ExtendedPointId::NumId(id), version);
+        }
+        assert_ne!(forward.digest, other_version.digest);
+
+        let bucket = point_bucket(ExtendedPointId::NumId(42), 16);
+        assert!(bucket < 16);
+        assert_eq!(bucket, point_bucket(ExtendedPointId::NumId(42), 16));
+    }
+}
@@ -5,7 +5,9 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
+use common::hardware_counter::HardwareCounterCell;
 use common::types::TelemetryDetail;
+use segment::data_types::aggregation::{DistinctValues, NumericStats, ValueDistribution};
 use segment::data_types::order_by::OrderBy;
 use segment::types::{
     ExtendedPointId, Filter, PointIdType, ScoredPoint, WithPayload, WithPayloadInterface,
@@ -21,11 +23,13 @@ use crate::operations::operation_effect::{
 };
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, PointRequestInternal, Record, UpdateResult,
+    CountDistinctRequestInternal, CountRequestInternal, CountResult, FieldStatsRequestInternal,
+    HistogramRequestInternal, PartialResponse, PointRequestInternal, Record, UpdateResult,
 };
 use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
 use crate::operations::OperationWithClockTag;
 use crate::shards::local_shard::LocalShard;
+use crate::shards::point_digests::{PointDigests, PointDigestsRequest};
 use crate::shards::shard_trait::ShardOperation;
 use crate::shards::telemetry::LocalShardTelemetry;
 use crate::update_handler::UpdateSignal;
@@ -65,6 +69,10 @@ impl ProxyShard {
         res
     }
 
+    pub fn wrapped_shard(&self) -> &LocalShard {
+        &self.wrapped_shard
+    }
+
     /// Forward `create_snapshot` to `wrapped_shard`
     pub async fn create_snapshot(
         &self,
@@ -221,10 +229,11 @@ impl ShardOperation for ProxyShard {
         request: Arc<CoreSearchRequestBatch>,
         search_runtime_handle: &Handle,
         timeout: Option<Duration>,
-    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        hw_counter: &HardwareCounterCell,
+    ) -> CollectionResult<PartialResponse<Vec<Vec<ScoredPoint>>>> {
         let local_shard = &self.wrapped_shard;
         local_shard
-            .core_search(request, search_runtime_handle, timeout)
+            .core_search(request, search_runtime_handle, timeout, hw_counter)
             .await
     }
 
@@ -234,6 +243,41 @@ impl ShardOperation for ProxyShard {
         local_shard.count(request).await
     }
 
+    /// Forward read-only `count_distinct` to `wrapped_shard`
+    async fn count_distinct(
+        &self,
+        request: Arc<CountDistinctRequestInternal>,
+    ) -> CollectionResult<DistinctValues> {
+        let local_shard = &self.wrapped_shard;
+        local_shard.count_distinct(request).await
+    }
+
+    /// Forward read-only `histogram` to `wrapped_shard`
+    async fn histogram(
+        &self,
+        request: Arc<HistogramRequestInternal>,
+    ) -> CollectionResult<ValueDistribution> {
+        let local_shard = &self.wrapped_shard;
+        local_shard.histogram(request).await
+    }
+
+    /// Forward read-only `field_stats` to `wrapped_shard`
+    async fn field_stats(
+        &self,
+        request: Arc<FieldStatsRequestInternal>,
+    ) -> CollectionResult<NumericStats> {
+        let local_shard = &self.wrapped_shard;
+        local_shard.field_stats(request).await
+    }
+
+    async fn point_digests(
+        &self,
+        request: Arc<PointDigestsRequest>,
+    ) -> CollectionResult<PointDigests> {
+        let local_shard = &self.wrapped_shard;
+        local_shard.point_digests(request).await
+    }
+
     /// Forward read-only `retrieve` to `wrapped_shard`
     async fn retrieve(
         &self,
@@ -253,10 +297,11 @@ impl ShardOperation for ProxyShard {
         request: Arc<Vec<ShardQueryRequest>>,
         search_runtime_handle: &Handle,
         timeout: Option<Duration>,
-    ) -> CollectionResult<Vec<ShardQueryResponse>> {
+        hw_counter: &HardwareCounterCell,
+    ) -> CollectionResult<PartialResponse<Vec<ShardQueryResponse>>> {
         let local_shard = &self.wrapped_shard;
         local_shard
-            .query_batch(request, search_runtime_handle, timeout)
+            .query_batch(request, search_runtime_handle, timeout, hw_counter)
             .await
     }
 }
@@ -4,8 +4,10 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
+use common::hardware_counter::HardwareCounterCell;
 use common::types::TelemetryDetail;
 use parking_lot::Mutex as ParkingMutex;
+use segment::data_types::aggregation::{DistinctValues, NumericStats, ValueDistribution};
 use segment::data_types::order_by::OrderBy;
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
@@ -20,11 +22,13 @@ use super::update_tracker::UpdateTracker;
 use crate::operations::point_ops::WriteOrdering;
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, PointRequestInternal, Record, UpdateResult,
+    CountDistinctRequestInternal, CountRequestInternal, CountResult, FieldStatsRequestInternal,
+    HistogramRequestInternal, PartialResponse, PointRequestInternal, Record, UpdateResult,
 };
 use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
 use crate::operations::OperationWithClockTag;
 use crate::shards::local_shard::LocalShard;
+use crate::shards::point_digests::{PointDigests, PointDigestsRequest};
 use crate::shards::shard_trait::ShardOperation;
 use crate::shards::telemetry::LocalShardTelemetry;
 
@@ -75,6 +79,11 @@ impl QueueProxyShard {
         }
     }
 
+    /// Local shard, wrapped by this proxy. `None` if the proxy has been finalized.
+    pub fn wrapped_shard(&self) -> Option<&LocalShard> {
+        self.inner.as_ref().map(|inner| &inner.wrapped_shard)
+    }
+
     /// Queue proxy the given local shard and point to the remote shard, from a specific WAL version.
     ///
     /// This queues all (existing) updates from a specific WAL `version` and onwards. In other
@@ -261,11 +270,12 @@ impl ShardOperation for QueueProxyShard {
         request: Arc<CoreSearchRequestBatch>,
         search_runtime_handle: &Handle,
         timeout: Option<Duration>,
-    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        hw_counter: &HardwareCounterCell,
+    ) -> CollectionResult<PartialResponse<Vec<Vec<ScoredPoint>>>> {
         self.inner
             .as_ref()
             .expect("Queue proxy has been finalized")
-            .core_search(request, search_runtime_handle, timeout)
+            .core_search(request, search_runtime_handle, timeout, hw_counter)
             .await
     }
 
@@ -278,6 +288,53 @@ impl ShardOperation for QueueProxyShard {
             .await
     }
 
+    /// Forward read-only `count_distinct` to `wrapped_shard`
+    async fn count_distinct(
+        &self,
+        request: Arc<CountDistinctRequestInternal>,
+    ) -> CollectionResult<DistinctValues> {
+        self.inner
+            .as_ref()
+            .expect("Queue proxy has been finalized")
+            .count_distinct(request)
+            .await
+    }
+
+    /// Forward read-only `histogram` to `wrapped_shard`
+    async fn histogram(
+        &self,
+        request: Arc<HistogramRequestInternal>,
+    ) -> CollectionResult<ValueDistribution> {
+        self.inner
+            .as_ref()
+            .expect("Queue proxy has been finalized")
+            .histogram(request)
+            .await
+    }
+
+    /// Forward read-only `field_stats` to `wrapped_shard`
+    async fn field_stats(
+        &self,
+        request: Arc<FieldStatsRequestInternal>,
+    ) -> CollectionResult<NumericStats> {
+        self.inner
+            .as_ref()
+            .expect("Queue proxy has been finalized")
+            .field_stats(request)
+            .await
+    }
+
+    async fn point_digests(
+        &self,
+        request: Arc<PointDigestsRequest>,
+    ) -> CollectionResult<PointDigests> {
+        self.inner
+            .as_ref()
+            .expect("Queue proxy has been finalized")
+            .point_digests(request)
+            .await
+    }
+
     /// Forward read-only `retrieve` to `wrapped_shard`
     async fn retrieve(
         &self,
@@ -298,12 +355,13 @@ impl ShardOperation for QueueProxyShard {
         requests: Arc<Vec<ShardQueryRequest>>,
         search_runtime_handle: &Handle,
         timeout: Option<Duration>,
-    ) -> CollectionResult<Vec<ShardQueryResponse>> {
+        hw_counter: &HardwareCounterCell,
+    ) -> CollectionResult<PartialResponse<Vec<ShardQueryResponse>>> {
         self.inner
             .as_ref()
             .expect("Queue proxy has been finalized")
             .wrapped_shard
-            .query_batch(requests, search_runtime_handle, timeout)
+            .query_batch(requests, search_runtime_handle, timeout, hw_counter)
             .await
     }
 }
@@ -553,10 +611,11 @@ impl ShardOperation for Inner {
         request: Arc<CoreSearchRequestBatch>,
         search_runtime_handle: &Handle,
         timeout: Option<Duration>,
-    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        hw_counter: &HardwareCounterCell,
+    ) -> CollectionResult<PartialResponse<Vec<Vec<ScoredPoint>>>> {
         let local_shard = &self.wrapped_shard;
         local_shard
-            .core_search(request, search_runtime_handle, timeout)
+            .core_search(request, search_runtime_handle, timeout, hw_counter)
             .await
     }
 
@@ -566,6 +625,41 @@ impl ShardOperation for Inner {
         local_shard.count(request).await
     }
 
+    /// Forward read-only `count_distinct` to `wrapped_shard`
+    async fn count_distinct(
+        &self,
+        request: Arc<CountDistinctRequestInternal>,
+    ) -> CollectionResult<DistinctValues> {
+        let local_shard = &self.wrapped_shard;
+        local_shard.count_distinct(request).await
+    }
+
+    /// Forward read-only `histogram` to `wrapped_shard`
+    async fn histogram(
+        &self,
+        request: Arc<HistogramRequestInternal>,
+    ) -> CollectionResult<ValueDistribution> {
+        let local_shard = &self.wrapped_shard;
+        local_shard.histogram(request).await
+    }
+
+    /// Forward read-only `field_stats` to `wrapped_shard`
+    async fn field_stats(
+        &self,
+        request: Arc<FieldStatsRequestInternal>,
+    ) -> CollectionResult<NumericStats> {
+        let local_shard = &self.wrapped_shard;
+        local_shard.field_stats(request).await
+    }
+
+    async fn point_digests(
+        &self,
+        request: Arc<PointDigestsRequest>,
+    ) -> CollectionResult<PointDigests> {
+        let local_shard = &self.wrapped_shard;
+        local_shard.point_digests(request).await
+    }
+
     /// Forward read-only `retrieve` to `wrapped_shard`
     async fn retrieve(
         &self,
@@ -585,10 +679,11 @@ impl ShardOperation for Inner {
         request: Arc<Vec<ShardQueryRequest>>,
         search_runtime_handle: &Handle,
         timeout: Option<Duration>,
-    ) -> CollectionResult<Vec<ShardQueryResponse>> {
+        hw_counter: &HardwareCounterCell,
+    ) -> CollectionResult<PartialResponse<Vec<ShardQueryResponse>>> {
         let local_shard = &self.wrapped_shard;
         local_shard
-            .query_batch(request, search_runtime_handle, timeout)
+            .query_batch(request, search_runtime_handle, timeout, hw_counter)
             .await
     }
 }
@@ -9,28 +9,34 @@ use api::grpc::qdrant::qdrant_client::QdrantClient;
 use api::grpc::qdrant::shard_snapshot_location::Location;
 use api::grpc::qdrant::shard_snapshots_client::ShardSnapshotsClient;
 use api::grpc::qdrant::{
-    CollectionOperationResponse, CoreSearchBatchPointsInternal, CountPoints, CountPointsInternal,
-    GetCollectionInfoRequest, GetCollectionInfoRequestInternal, GetPoints, GetPointsInternal,
-    GetShardRecoveryPointRequest, HealthCheckRequest, InitiateShardTransferRequest,
-    QueryBatchPointsInternal, QueryShardPoints, RecoverShardSnapshotRequest,
-    RecoverSnapshotResponse, ScrollPoints, ScrollPointsInternal, ShardSnapshotLocation,
+    CollectionOperationResponse, CoreSearchBatchPointsInternal, CountDistinctPoints,
+    CountDistinctPointsInternal, CountPoints, CountPointsInternal, FieldStatsPoints,
+    FieldStatsPointsInternal, GetCollectionInfoRequest, GetCollectionInfoRequestInternal,
+    GetPoints, GetPointsInternal, GetShardRecoveryPointRequest, HealthCheckRequest,
+    HistogramPoints, HistogramPointsInternal, InitiateShardTransferRequest, PointDigestsInternal,
+    PointHistoryInternal, QueryBatchPointsInternal, QueryShardPoints, RecoverShardSnapshotRequest,
+    RecoverSnapshotResponse, ScanTrashInternal, ScrollPoints, ScrollPointsInternal,
+    ShardSnapshotLocation, TrashResponseInternal, TrashedPointsInternal,
     UpdateShardCutoffPointRequest, WaitForShardStateRequest,
 };
 use api::grpc::transport_channel_pool::{AddTimeout, MAX_GRPC_CHANNEL_TIMEOUT};
 use api::rest::SearchRequestInternal;
 use async_trait::async_trait;
+use common::hardware_counter::HardwareCounterCell;
 use common::types::TelemetryDetail;
 use itertools::Itertools;
 use parking_lot::Mutex;
 use segment::common::operation_time_statistics::{
     OperationDurationsAggregator, ScopeDurationMeasurer,
 };
-use segment::data_types::order_by::OrderBy;
+use segment::data_types::aggregation::{DistinctValues, NumericStats, ValueDistribution};
+use segment::data_types::order_by::{OrderBy, OrderByCursor};
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
 };
 use tokio::runtime::Handle;
 use tonic::codegen::InterceptedService;
+use tonic::metadata::MetadataValue;
 use tonic::transport::{Channel, Uri};
 use tonic::Status;
 use url::Url;
@@ -39,14 +45,20 @@ use super::conversions::{
     internal_delete_vectors, internal_delete_vectors_by_filter, internal_update_vectors,
 };
 use super::local_shard::clock_map::RecoveryPoint;
+use super::local_shard::search::STOPPED_SEARCH_GRACE_PERIOD;
 use super::replica_set::ReplicaState;
+use crate::collection_manager::point_history::PointVersion;
+use crate::collection_manager::point_trash::TrashedPoint;
+use crate::common::idempotency::IDEMPOTENCY_KEY_HEADER;
+use crate::operations::attribute_ops::AttributeOps;
 use crate::operations::conversions::try_record_from_grpc;
 use crate::operations::payload_ops::PayloadOps;
 use crate::operations::point_ops::{PointOperations, WriteOrdering};
 use crate::operations::snapshot_ops::SnapshotPriority;
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequest, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, PointRequestInternal, Record, UpdateResult,
+    CountDistinctRequestInternal, CountRequestInternal, CountResult, FieldStatsRequestInternal,
+    HistogramRequestInternal, PartialResponse, PointRequestInternal, Record, UpdateResult,
 };
 use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
 use crate::operations::vector_ops::VectorOperations;
@@ -55,9 +67,10 @@ use crate::shards::channel_service::ChannelService;
 use crate::shards::conversions::{
     internal_clear_payload, internal_clear_payload_by_filter, internal_create_index,
     internal_delete_index, internal_delete_payload, internal_delete_points,
-    internal_delete_points_by_filter, internal_set_payload, internal_sync_points,
-    internal_upsert_points, try_scored_point_from_grpc,
+    internal_delete_points_by_filter, internal_set_attributes, internal_set_payload,
+    internal_sync_points, internal_upsert_points, try_scored_point_from_grpc,
 };
+use crate::shards::point_digests::{PointDigests, PointDigestsRequest};
 use crate::shards::shard::{PeerId, ShardId};
 use crate::shards::shard_trait::ShardOperation;
 use crate::shards::telemetry::RemoteShardTelemetry;
@@ -251,6 +264,8 @@ impl RemoteShard {
         let mut timer = ScopeDurationMeasurer::new(&self.telemetry_update_durations);
         timer.set_success(false);
 
+        let idempotency_key = operation.idempotency_key.as_deref();
+
         let point_operation_response = match operation.operation {
             CollectionUpdateOperations::PointOperation(point_ops) => match point_ops {
                 PointOperations::UpsertPoints(point_insert_operations) => {
@@ -259,11 +274,32 @@ impl RemoteShard {
                         operation.clock_tag,
                         collection_name,
                         point_insert_operations,
+                        None,
                         wait,
                         ordering,
                     )?;
                     self.with_points_client(|mut client| async move {
-                        client.upsert(tonic::Request::new(request.clone())).await
+                        client
+                            .upsert(update_request(request.clone(), idempotency_key))
+                            .await
+                    })
+                    .await?
+                    .into_inner()
+                }
+                PointOperations::UpsertPointsConditional(conditional_upsert) => {
+                    let request = &internal_upsert_points(
+                        shard_id,
+                        operation.clock_tag,
+                        collection_name,
+                        conditional_upsert.points_op,
+                        Some(conditional_upsert.precondition),
+                        wait,
+                        ordering,
+                    )?;
+                    self.with_points_client(|mut client| async move {
+                        client
+                            .upsert(update_request(request.clone(), idempotency_key))
+                            .await
                     })
                     .await?
                     .into_inner()
@@ -274,11 +310,32 @@ impl RemoteShard {
                         operation.clock_tag,
                         collection_name,
                         ids,
+                        None,
                         wait,
                         ordering,
                     );
                     self.with_points_client(|mut client| async move {
-                        client.delete(tonic::Request::new(request.clone())).await
+                        client
+                            .delete(update_request(request.clone(), idempotency_key))
+                            .await
+                    })
+                    .await?
+                    .into_inner()
+                }
+                PointOperations::DeletePointsConditional(conditional_delete) => {
+                    let request = &internal_delete_points(
+                        shard_id,
+                        operation.clock_tag,
+                        collection_name,
+                        conditional_delete.ids,
+                        Some(conditional_delete.precondition),
+                        wait,
+                        ordering,
+                    );
+                    self.with_points_client(|mut client| async move {
+                        client
+                            .delete(update_request(request.clone(), idempotency_key))
+                            .await
                     })
                     .await?
                     .into_inner()
@@ -293,7 +350,9 @@ impl RemoteShard {
                         ordering,
                     );
                     self.with_points_client(|mut client| async move {
-                        client.delete(tonic::Request::new(request.clone())).await
+                        client
+                            .delete(update_request(request.clone(), idempotency_key))
+                            .await
                     })
                     .await?
                     .into_inner()
@@ -308,7 +367,9 @@ impl RemoteShard {
                         ordering,
                     )?;
                     self.with_points_client(|mut client| async move {
-                        client.sync(tonic::Request::new(request.clone())).await
+                        client
+                            .sync(update_request(request.clone(), idempotency_key))
+                            .await
                     })
                     .await?
                     .into_inner()
@@ -326,7 +387,7 @@ impl RemoteShard {
                     );
                     self.with_points_client(|mut client| async move {
                         client
-                            .update_vectors(tonic::Request::new(request.clone()))
+                            .update_vectors(update_request(request.clone(), idempotency_key))
                             .await
                     })
                     .await?
@@ -344,7 +405,7 @@ impl RemoteShard {
                     );
                     self.with_points_client(|mut client| async move {
                         client
-                            .delete_vectors(tonic::Request::new(request.clone()))
+                            .delete_vectors(update_request(request.clone(), idempotency_key))
                             .await
                     })
                     .await?
@@ -362,7 +423,7 @@ impl RemoteShard {
                     );
                     self.with_points_client(|mut client| async move {
                         client
-                            .delete_vectors(tonic::Request::new(request.clone()))
+                            .delete_vectors(update_request(request.clone(), idempotency_key))
                             .await
                     })
                     .await?
@@ -376,12 +437,31 @@ impl RemoteShard {
                         operation.clock_tag,
                         collection_name,
                         set_payload,
+                        None,
+                        wait,
+                        ordering,
+                    );
+                    self.with_points_client(|mut client| async move {
+                        client
+                            .set_payload(update_request(request.clone(), idempotency_key))
+                            .await
+                    })
+                    .await?
+                    .into_inner()
+                }
+                PayloadOps::SetPayloadConditional(conditional_set_payload) => {
+                    let request = &internal_set_payload(
+                        shard_id,
+                        operation.clock_tag,
+                        collection_name,
+                        conditional_set_payload.payload_op,
+                        Some(conditional_set_payload.precondition),
                         wait,
                         ordering,
                     );
                     self.with_points_client(|mut client| async move {
                         client
-                            .set_payload(tonic::Request::new(request.clone()))
+                            .set_payload(update_request(request.clone(), idempotency_key))
                             .await
                     })
                     .await?
@@ -398,7 +478,7 @@ impl RemoteShard {
                     );
                     self.with_points_client(|mut client| async move {
                         client
-                            .delete_payload(tonic::Request::new(request.clone()))
+                            .delete_payload(update_request(request.clone(), idempotency_key))
                             .await
                     })
                     .await?
@@ -415,7 +495,7 @@ impl RemoteShard {
                     );
                     self.with_points_client(|mut client| async move {
                         client
-                            .clear_payload(tonic::Request::new(request.clone()))
+                            .clear_payload(update_request(request.clone(), idempotency_key))
                             .await
                     })
                     .await?
@@ -432,7 +512,7 @@ impl RemoteShard {
                     );
                     self.with_points_client(|mut client| async move {
                         client
-                            .clear_payload(tonic::Request::new(request.clone()))
+                            .clear_payload(update_request(request.clone(), idempotency_key))
                             .await
                     })
                     .await?
@@ -444,12 +524,31 @@ impl RemoteShard {
                         operation.clock_tag,
                         collection_name,
                         set_payload,
+                        None,
+                        wait,
+                        ordering,
+                    );
+                    self.with_points_client(|mut client| async move {
+                        client
+                            .overwrite_payload(update_request(request.clone(), idempotency_key))
+                            .await
+                    })
+                    .await?
+                    .into_inner()
+                }
+                PayloadOps::PatchPayload(set_payload) => {
+                    let request = &internal_set_payload(
+                        shard_id,
+                        operation.clock_tag,
+                        collection_name,
+                        set_payload,
+                        None,
                         wait,
                         ordering,
                     );
                     self.with_points_client(|mut client| async move {
                         client
-                            .overwrite_payload(tonic::Request::new(request.clone()))
+                            .patch_payload(update_request(request.clone(), idempotency_key))
                             .await
                     })
                     .await?
@@ -469,7 +568,7 @@ impl RemoteShard {
                     );
                     self.with_points_client(|mut client| async move {
                         client
-                            .create_field_index(tonic::Request::new(request.clone()))
+                            .create_field_index(update_request(request.clone(), idempotency_key))
                             .await
                     })
                     .await?
@@ -486,7 +585,26 @@ impl RemoteShard {
                     );
                     self.with_points_client(|mut client| async move {
                         client
-                            .delete_field_index(tonic::Request::new(request.clone()))
+                            .delete_field_index(update_request(request.clone(), idempotency_key))
+                            .await
+                    })
+                    .await?
+                    .into_inner()
+                }
+            },
+            CollectionUpdateOperations::AttributeOperation(attribute_ops) => match attribute_ops {
+                AttributeOps::SetAttributes(set_attributes) => {
+                    let request = &internal_set_attributes(
+                        shard_id,
+                        operation.clock_tag,
+                        collection_name,
+                        set_attributes,
+                        wait,
+                        ordering,
+                    );
+                    self.with_points_client(|mut client| async move {
+                        client
+                            .set_attributes(update_request(request.clone(), idempotency_key))
                             .await
                     })
                     .await?
@@ -619,6 +737,103 @@ impl RemoteShard {
         Ok(())
     }
 
+    /// Retained versions of the point in the remote shard, `None` if the collection doesn't
+    /// retain point history
+    pub async fn point_history(
+        &self,
+        point_id: ExtendedPointId,
+    ) -> CollectionResult<Option<Vec<PointVersion>>> {
+        let request = &PointHistoryInternal {
+            collection_name: self.collection_id.clone(),
+            shard_id: Some(self.id),
+            id: Some(point_id.into()),
+        };
+        let response = self
+            .with_points_client(|mut client| async move {
+                client
+                    .point_history(tonic::Request::new(request.clone()))
+                    .await
+            })
+            .await?
+            .into_inner();
+
+        response
+            .versions
+            .map(|versions| {
+                serde_cbor::from_slice(&versions).map_err(|err| {
+                    CollectionError::service_error(format!(
+                        "Failed to read history of {point_id} from peer {}: {err}",
+                        self.peer_id,
+                    ))
+                })
+            })
+            .transpose()
+    }
+
+    /// Deleted points with the given ids in the remote shard, `None` if the collection doesn't
+    /// keep deleted points
+    pub async fn trashed_points(
+        &self,
+        point_ids: &[ExtendedPointId],
+    ) -> CollectionResult<Option<Vec<TrashedPoint>>> {
+        let request = &TrashedPointsInternal {
+            collection_name: self.collection_id.clone(),
+            shard_id: Some(self.id),
+            ids: point_ids.iter().map(|&point_id| point_id.into()).collect(),
+        };
+        let response = self
+            .with_points_client(|mut client| async move {
+                client
+                    .trashed_points(tonic::Request::new(request.clone()))
+                    .await
+            })
+            .await?
+            .into_inner();
+        self.decode_trash(response)
+    }
+
+    /// Deleted points with ids in `from..to`, which match the filter, in the remote shard
+    pub async fn scan_trash(
+        &self,
+        from: Option<ExtendedPointId>,
+        to: Option<ExtendedPointId>,
+        filter: Option<&Filter>,
+    ) -> CollectionResult<Vec<TrashedPoint>> {
+        let request = &ScanTrashInternal {
+            collection_name: self.collection_id.clone(),
+            shard_id: Some(self.id),
+            from: from.map(|point_id| point_id.into()),
+            to: to.map(|point_id| point_id.into()),
+            filter: filter.cloned().map(|filter| filter.into()),
+        };
+        let response = self
+            .with_points_client(|mut client| async move {
+                client
+                    .scan_trash(tonic::Request::new(request.clone()))
+                    .await
+            })
+            .await?
+            .into_inner();
+        Ok(self.decode_trash(response)?.unwrap_or_default())
+    }
+
+    fn decode_trash(
+        &self,
+        response: TrashResponseInternal,
+    ) -> CollectionResult<Option<Vec<TrashedPoint>>> {
+        response
+            .points
+            .map(|points| {
+                serde_cbor::from_slice(&points).map_err(|err| {
+                    CollectionError::service_error(format!(
+                        "Failed to read deleted points from peer {}: {err}",
+                        self.peer_id,
+                    ))
+                })
+            })
+            .transpose()
+    }
+
     pub async fn health_check(&self) -> CollectionResult<()> {
         let _ = self
             .with_qdrant_client(|mut client| async move {
@@ -632,6 +847,15 @@ impl RemoteShard {
 }
 
 // New-type to own the type in the crate for conversions via From
+/// Request of an update operation, with its idempotency key passed as metadata
+fn update_request<T>(request: T, idempotency_key: Option<&str>) -> tonic::Request<T> {
+    let mut request = tonic::Request::new(request);
+    if let Some(key) = idempotency_key.and_then(|key| MetadataValue::try_from(key).ok()) {
+        request.metadata_mut().insert(IDEMPOTENCY_KEY_HEADER, key);
+    }
+    request
+}
+
 pub struct CollectionSearchRequest<'a>(pub(crate) (CollectionId, &'a SearchRequestInternal));
 pub struct CollectionCoreSearchRequest<'a>(pub(crate) (CollectionId, &'a CoreSearchRequest));
 
@@ -663,6 +887,27 @@ impl ShardOperation for RemoteShard {
         _search_runtime_handle: &Handle,
         order_by: Option<&OrderBy>,
     ) -> CollectionResult<Vec<Record>> {
+        // Position after the previous page is forwarded as a cursor instead of `start_from`
+        let cursor = order_by.and_then(|order_by| {
+            order_by.start_after_id.map(|id| {
+                OrderByCursor {
+                    value: order_by.start_from(),
+                    then_by: order_by.start_after_then_by.clone(),
+                    id,
+                }
+                .encode()
+            })
+        });
+        let grpc_order_by = order_by.map(|order_by| {
+            let mut order_by = order_by.clone();
+            if cursor.is_some() {
+                order_by.start_from = None;
+                order_by.start_after_id = None;
+                order_by.start_after_then_by = Vec::new();
+            }
+            order_by.into()
+        });
+
         let scroll_points = ScrollPoints {
             collection_name: self.collection_id.clone(),
             filter: filter.map(|f| f.clone().into()),
@@ -672,7 +917,8 @@ impl ShardOperation for RemoteShard {
             with_vectors: Some(with_vector.clone().into()),
             read_consistency: None,
             shard_key_selector: None,
-            order_by: order_by.map(|o| o.clone().into()),
+            order_by: grpc_order_by,
+            cursor,
         };
         let request = &ScrollPointsInternal {
             scroll_points: Some(scroll_points),
@@ -721,7 +967,8 @@ impl ShardOperation for RemoteShard {
         batch_request: Arc<CoreSearchRequestBatch>,
         _search_runtime_handle: &Handle,
         timeout: Option<Duration>,
-    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        hw_counter: &HardwareCounterCell,
+    ) -> CollectionResult<PartialResponse<Vec<Vec<ScoredPoint>>>> {
         let mut timer = ScopeDurationMeasurer::new(&self.telemetry_search_durations);
         timer.set_success(false);
 
@@ -741,8 +988,9 @@ impl ShardOperation for RemoteShard {
             .with_points_client(|mut client| async move {
                 let mut request = tonic::Request::new(request.clone());
 
+                // Remote shard returns partial results at the timeout, wait for them
                 if let Some(timeout) = timeout {
-                    request.set_timeout(timeout);
+                    request.set_timeout(timeout + STOPPED_SEARCH_GRACE_PERIOD);
                 }
 
                 client.core_search_batch(request).await
@@ -750,6 +998,10 @@ impl ShardOperation for RemoteShard {
             .await?
             .into_inner();
 
+        if let Some(usage) = &search_batch_response.usage {
+            hw_counter.add_usage(&usage.into());
+        }
+
         let result: Result<Vec<Vec<ScoredPoint>>, Status> = search_batch_response
             .result
             .into_iter()
@@ -771,7 +1023,11 @@ impl ShardOperation for RemoteShard {
         if result.is_ok() {
             timer.set_success(true);
         }
-        result
+        result.map(|result| PartialResponse {
+            value: result,
+            timed_out: search_batch_response.timed_out.unwrap_or_default(),
+            shards: Vec::new(),
+        })
     }
 
     async fn count(&self, request: Arc<CountRequestInternal>) -> CollectionResult<CountResult> {
@@ -803,6 +1059,133 @@ impl ShardOperation for RemoteShard {
         )
     }
 
+    async fn count_distinct(
+        &self,
+        request: Arc<CountDistinctRequestInternal>,
+    ) -> CollectionResult<DistinctValues> {
+        let count_distinct_points = CountDistinctPoints {
+            collection_name: self.collection_id.clone(),
+            key: request.key.to_string(),
+            filter: request.filter.clone().map(|f| f.into()),
+            read_consistency: None,
+            shard_key_selector: None,
+        };
+
+        let request = &CountDistinctPointsInternal {
+            count_distinct_points: Some(count_distinct_points),
+            shard_id: Some(self.id),
+        };
+        let response = self
+            .with_points_client(|mut client| async move {
+                client
+                    .count_distinct(tonic::Request::new(request.clone()))
+                    .await
+            })
+            .await?
+            .into_inner();
+        response.result.map_or_else(
+            || {
+                Err(CollectionError::service_error(
+                    "Unexpected empty DistinctValues".to_string(),
+                ))
+            },
+            |distinct_values| Ok(distinct_values.try_into()?),
+        )
+    }
+
+    async fn histogram(
+        &self,
+        request: Arc<HistogramRequestInternal>,
+    ) -> CollectionResult<ValueDistribution> {
+        let histogram_points = HistogramPoints {
+            collection_name: self.collection_id.clone(),
+            key: request.key.to_string(),
+            buckets: Some(request.buckets.into()),
+            read_consistency: None,
+            shard_key_selector: None,
+        };
+
+        let request = &HistogramPointsInternal {
+            histogram_points: Some(histogram_points),
+            shard_id: Some(self.id),
+        };
+        let response = self
+            .with_points_client(|mut client| async move {
+                client.histogram(tonic::Request::new(request.clone())).await
+            })
+            .await?
+            .into_inner();
+        response.result.map_or_else(
+            || {
+                Err(CollectionError::service_error(
+                    "Unexpected empty ValueDistribution".to_string(),
+                ))
+            },
+            |distribution| Ok(distribution.into()),
+        )
+    }
+
+    async fn field_stats(
+        &self,
+        request: Arc<FieldStatsRequestInternal>,
+    ) -> CollectionResult<NumericStats> {
+        let field_stats_points = FieldStatsPoints {
+            collection_name: self.collection_id.clone(),
+            key: request.key.to_string(),
+            filter: request.filter.clone().map(|f| f.into()),
+            // Percentiles are computed by the caller
+            percentiles: Vec::new(),
+            read_consistency: None,
+            shard_key_selector: None,
+        };
+
+        let request = &FieldStatsPointsInternal {
+            field_stats_points: Some(field_stats_points),
+            shard_id: Some(self.id),
+        };
+        let response = self
+            .with_points_client(|mut client| async move {
+                client
+                    .field_stats(tonic::Request::new(request.clone()))
+                    .await
+            })
+            .await?
+            .into_inner();
+        response.result.map_or_else(
+            || {
+                Err(CollectionError::service_error(
+                    "Unexpected empty NumericStats".to_string(),
+                ))
+            },
+            |stats| Ok(stats.into()),
+        )
+    }
+
+    async fn point_digests(
+        &self,
+        request: Arc<PointDigestsRequest>,
+    ) -> CollectionResult<PointDigests> {
+        let request = &PointDigestsInternal {
+            collection_name: self.collection_id.clone(),
+            shard_id: Some(self.id),
+            buckets: request.buckets as u64,
+            list_buckets: request
+                .list_buckets
+                .iter()
+                .map(|&bucket| bucket as u64)
+                .collect(),
+        };
+        let response = self
+            .with_points_client(|mut client| async move {
+                client
+                    .point_digests(tonic::Request::new(request.clone()))
+                    .await
+            })
+            .await?
+            .into_inner();
+        Ok(response.try_into()?)
+    }
+
     async fn retrieve(
         &self,
         request: Arc<PointRequestInternal>,
@@ -843,7 +1226,8 @@ impl ShardOperation for RemoteShard {
         requests: Arc<Vec<ShardQueryRequest>>,
         _search_runtime_handle: &Handle,
         timeout: Option<Duration>,
-    ) -> CollectionResult<Vec<ShardQueryResponse>> {
+        hw_counter: &HardwareCounterCell,
+    ) -> CollectionResult<PartialResponse<Vec<ShardQueryResponse>>> {
         let mut timer = ScopeDurationMeasurer::new(&self.telemetry_search_durations);
         timer.set_success(false);
 
@@ -865,8 +1249,9 @@ impl ShardOperation for RemoteShard {
 
                 let mut request = tonic::Request::new(request.clone());
 
+                // Remote shard returns partial results at the timeout, wait for them
                 if let Some(timeout) = timeout {
-                    request.set_timeout(timeout);
+                    request.set_timeout(timeout + STOPPED_SEARCH_GRACE_PERIOD);
                 }
 
                 client.query_batch(request).await
@@ -874,6 +1259,10 @@ impl ShardOperation for RemoteShard {
             .await?
             .into_inner();
 
+        if let Some(usage) = &batch_response.usage {
+            hw_counter.add_usage(&usage.into());
+        }
+
         let result = batch_response
             .results
             .into_iter()
@@ -897,6 +1286,10 @@ impl ShardOperation for RemoteShard {
 
         timer.set_success(true);
 
-        Ok(result)
+        Ok(PartialResponse {
+            value: result,
+            timed_out: batch_response.timed_out.unwrap_or_default(),
+            shards: Vec::new(),
+        })
     }
 }
@@ -0,0 +1,309 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::future::try_join_all;
+use segment::types::{PointIdType, SeqNumberType, WithPayload, WithPayloadInterface, WithVector};
+
+use super::ShardReplicaSet;
+use crate::operations::point_ops::{
+    PointInsertOperationsInternal, PointOperations, PointStruct, WriteOrdering,
+};
+use crate::operations::types::{
+    CollectionError, CollectionResult, DivergentPoint, PointRequestInternal, Record,
+    ShardConsistency,
+};
+use crate::operations::CollectionUpdateOperations;
+use crate::shards::point_digests::{point_bucket, PointDigests, PointDigestsRequest};
+use crate::shards::remote_shard::RemoteShard;
+use crate::shards::shard::{PeerId, Shard};
+use crate::shards::shard_trait::ShardOperation;
+
+/// Maximum number of points, whose data is compared between the replicas in a single check
+const MAX_COMPARED_POINTS: usize = 10_000;
+
+/// Number of divergent points, which are listed in the report
+const DIVERGENT_POINTS_SAMPLE_SIZE: usize = 10;
+
+/// Delay between two listings of point versions before a repair.
+/// Points, which changed in between, are being updated and are not repaired.
+const REPAIR_STABILITY_DELAY: Duration = Duration::from_secs(1);
+
+/// Versions of the points on each of the replicas, `None` if the point is missing on the replica
+type ReplicaVersions = BTreeMap<PointIdType, Vec<Option<SeqNumberType>>>;
+
+impl ShardReplicaSet {
+    /// Digests of the points in the local replica
+    pub(crate) async fn local_point_digests(
+        &self,
+        request: PointDigestsRequest,
+    ) -> CollectionResult<PointDigests> {
+        let local_shard = self.local.read().await;
+        let Some(local_shard) = local_shard.as_ref() else {
+            return Err(CollectionError::NotFound {
+                what: "Peer does not have local shard".into(),
+            });
+        };
+
+        local_shard.get().point_digests(Arc::new(request)).await
+    }
+
+    /// Compare the active replicas of the shard, and optionally re-sync the divergent points.
+    ///
+    /// Replicas are compared by digests of point buckets first, then by versions of the points
+    /// in the divergent buckets, and at last by the data of the points with different versions.
+    ///
+    /// Divergent points are repaired from the replica with the most recent update in their
+    /// bucket: they are upserted with the data of this replica, or deleted if it doesn't have
+    /// them.
+    pub(crate) async fn check_consistency(
+        &self,
+        buckets: usize,
+        repair: bool,
+    ) -> CollectionResult<ShardConsistency> {
+        let buckets = buckets.max(1);
+        let mut report = ShardConsistency {
+            shard_id: self.shard_id,
+            peers: Vec::new(),
+            divergent_buckets: 0,
+            divergent_points: 0,
+            divergent_points_sample: Vec::new(),
+            repaired_points: 0,
+        };
+
+        let digests = self
+            .replica_point_digests(PointDigestsRequest {
+                buckets,
+                list_buckets: Vec::new(),
+            })
+            .await?;
+        report.peers = digests.iter().map(|(peer_id, _)| *peer_id).collect();
+        if digests.len() < 2 {
+            return Ok(report);
+        }
+
+        let divergent_buckets: Vec<_> = (0..buckets)
+            .filter(|&bucket| {
+                let first = digests[0].1.buckets.get(bucket);
+                digests
+                    .iter()
+                    .any(|(_, digests)| digests.buckets.get(bucket) != first)
+            })
+            .collect();
+        report.divergent_buckets = divergent_buckets.len();
+        if divergent_buckets.is_empty() {
+            return Ok(report);
+        }
+
+        let listing_request = PointDigestsRequest {
+            buckets,
+            list_buckets: divergent_buckets,
+        };
+        let listings = self.replica_point_digests(listing_request.clone()).await?;
+        ensure_same_peers(&report.peers, &listings)?;
+        let versions = replica_versions(&listings);
+
+        // Versions are assigned by each replica, so points with different versions might still
+        // have the same data
+        let candidates: Vec<_> = versions
+            .iter()
+            .filter(|(_, versions)| versions.iter().any(|version| *version != versions[0]))
+            .map(|(point_id, _)| *point_id)
+            .take(MAX_COMPARED_POINTS)
+            .collect();
+        if candidates.is_empty() {
+            return Ok(report);
+        }
+
+        let records = self.replica_records(&candidates).await?;
+        ensure_same_peers(&report.peers, &records)?;
+
+        let divergent_points: Vec<_> = candidates
+            .into_iter()
+            .filter(|point_id| {
+                let first = records[0].1.get(point_id);
+                records
+                    .iter()
+                    .any(|(_, records)| records.get(point_id) != first)
+            })
+            .collect();
+        report.divergent_points = divergent_points.len();
+        report.divergent_points_sample = divergent_points
+            .iter()
+            .take(DIVERGENT_POINTS_SAMPLE_SIZE)
+            .map(|point_id| DivergentPoint {
+                id: *point_id,
+                versions: versions[point_id].clone(),
+            })
+            .collect();
+
+        if !repair || divergent_points.is_empty() {
+            return Ok(report);
+        }
+
+        tokio::time::sleep(REPAIR_STABILITY_DELAY).await;
+        let relistings = self.replica_point_digests(listing_request).await?;
+        ensure_same_peers(&report.peers, &relistings)?;
+        let stable_versions = replica_versions(&relistings);
+
+        let sources = bucket_sources(&listings, buckets);
+        let mut upserts = Vec::new();
+        let mut deletes = Vec::new();
+        for point_id in divergent_points {
+            if stable_versions.get(&point_id) != versions.get(&point_id) {
+                continue;
+            }
+            let Some(&source) = sources.get(&point_bucket(point_id, buckets)) else {
+                continue;
+            };
+            match records[source].1.get(&point_id) {
+                Some(record) => upserts.push(
+                    PointStruct::try_from(record.clone())
+                        .map_err(CollectionError::service_error)?,
+                ),
+                None => deletes.push(point_id),
+            }
+        }
+
+        if !upserts.is_empty() {
+            let count = upserts.len();
+            let operation = CollectionUpdateOperations::PointOperation(
+                PointOperations::UpsertPoints(PointInsertOperationsInternal::PointsList(upserts)),
+            );
+            self.update_with_consistency(operation, None, true, WriteOrdering::Strong)
+                .await?;
+            report.repaired_points += count;
+        }
+        if !deletes.is_empty() {
+            let count = deletes.len();
+            let operation =
+                CollectionUpdateOperations::PointOperation(PointOperations::DeletePoints {
+                    ids: deletes,
+                });
+            self.update_with_consistency(operation, None, true, WriteOrdering::Strong)
+                .await?;
+            report.repaired_points += count;
+        }
+
+        Ok(report)
+    }
+
+    /// Active replicas of the shard, with their peers
+    fn active_replicas<'a>(
+        &self,
+        local: &'a Option<Shard>,
+        remotes: &'a [RemoteShard],
+    ) -> Vec<(PeerId, &'a (dyn ShardOperation + Send + Sync + 'a))> {
+        let this_peer_id = self.this_peer_id();
+        let local = local
+            .as_ref()
+            .filter(|_| self.peer_is_active(&this_peer_id))
+            .map(|local| (this_peer_id, local.get()));
+        let remotes = remotes
+            .iter()
+            .filter(|remote| self.peer_is_active(&remote.peer_id))
+            .map(|remote| {
+                (
+                    remote.peer_id,
+                    remote as &(dyn ShardOperation + Send + Sync),
+                )
+            });
+        local.into_iter().chain(remotes).collect()
+    }
+
+    /// Point digests of each of the active replicas, sorted by peer
+    async fn replica_point_digests(
+        &self,
+        request: PointDigestsRequest,
+    ) -> CollectionResult<Vec<(PeerId, PointDigests)>> {
+        let request = Arc::new(request);
+        let local = self.local.read().await;
+        let remotes = self.remotes.read().await;
+
+        let mut digests = try_join_all(self.active_replicas(&local, &remotes).into_iter().map(
+            |(peer_id, shard)| {
+                let request = request.clone();
+                async move { CollectionResult::Ok((peer_id, shard.point_digests(request).await?)) }
+            },
+        ))
+        .await?;
+        digests.sort_by_key(|(peer_id, _)| *peer_id);
+        Ok(digests)
+    }
+
+    /// Data of the points on each of the active replicas, sorted by peer
+    async fn replica_records(
+        &self,
+        point_ids: &[PointIdType],
+    ) -> CollectionResult<Vec<(PeerId, HashMap<PointIdType, Record>)>> {
+        let request = Arc::new(PointRequestInternal {
+            ids: point_ids.to_vec(),
+            with_payload: Some(WithPayloadInterface::Bool(true)),
+            with_vector: WithVector::Bool(true),
+        });
+        let with_payload = WithPayload::from(true);
+        let with_vector = WithVector::Bool(true);
+        let local = self.local.read().await;
+        let remotes = self.remotes.read().await;
+
+        let mut records = try_join_all(self.active_replicas(&local, &remotes).into_iter().map(
+            |(peer_id, shard)| {
+                let request = request.clone();
+                let with_payload = &with_payload;
+                let with_vector = &with_vector;
+                async move {
+                    let records = shard.retrieve(request, with_payload, with_vector).await?;
+                    let records = records
+                        .into_iter()
+                        .map(|record| (record.id, record))
+                        .collect();
+                    CollectionResult::Ok((peer_id, records))
+                }
+            },
+        ))
+        .await?;
+        records.sort_by_key(|(peer_id, _)| *peer_id);
+        Ok(records)
+    }
+}
+
+/// Replicas might change during the check, in which case the results can not be compared
+fn ensure_same_peers<T>(peers: &[PeerId], results: &[(PeerId, T)]) -> CollectionResult<()> {
+    if results.iter().map(|(peer_id, _)| peer_id).eq(peers) {
+        Ok(())
+    } else {
+        Err(CollectionError::service_error(
+            "Active replicas of the shard changed during the consistency check",
+        ))
+    }
+}
+
+fn replica_versions(listings: &[(PeerId, PointDigests)]) -> ReplicaVersions {
+    let mut versions = ReplicaVersions::new();
+    for (replica, (_, listing)) in listings.iter().enumerate() {
+        for (point_id, version) in &listing.points {
+            versions
+                .entry(*point_id)
+                .or_insert_with(|| vec![None; listings.len()])[replica] = Some(*version);
+        }
+    }
+    versions
+}
+
+/// Index of the replica with the most recent update of a listed point, per bucket
+fn bucket_sources(listings: &[(PeerId, PointDigests)], buckets: usize) -> HashMap<usize, usize> {
+    let mut latest: HashMap<usize, (SeqNumberType, usize)> = HashMap::new();
+    for (replica, (_, listing)) in listings.iter().enumerate() {
+        for (point_id, version) in &listing.points {
+            let bucket = point_bucket(*point_id, buckets);
+            let source = latest.entry(bucket).or_insert((*version, replica));
+            if *version > source.0 {
+                *source = (*version, replica);
+            }
+        }
+    }
+    latest
+        .into_iter()
+        .map(|(bucket, (_, replica))| (bucket, replica))
+        .collect()
+}
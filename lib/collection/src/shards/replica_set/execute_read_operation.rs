@@ -9,6 +9,7 @@ use rand::seq::SliceRandom as _;
 use super::ShardReplicaSet;
 use crate::operations::consistency_params::{ReadConsistency, ReadConsistencyType};
 use crate::operations::types::{CollectionError, CollectionResult};
+use crate::shards::local_shard::LocalShard;
 use crate::shards::remote_shard::RemoteShard;
 use crate::shards::resolve::{Resolve, ResolveCondition};
 use crate::shards::shard::Shard;
@@ -82,6 +83,9 @@ impl ShardReplicaSet {
                 (total_count / 2 + 1, ResolveCondition::All)
             }
 
+            // Availability of shards is handled by the collection, any replica is enough
+            ReadConsistency::Type(ReadConsistencyType::Partial) => (1, ResolveCondition::All),
+
             ReadConsistency::Factor(factor) => {
                 (factor.clamp(1, total_count), ResolveCondition::All)
             }
@@ -110,6 +114,46 @@ impl ShardReplicaSet {
         }
     }
 
+    /// Execute read op. of data, which is kept by local shards outside of the `ShardOperation`
+    /// interface, on an active replica:
+    /// 1 - Prefer local replica, if it is active
+    /// 2 - Otherwise try active remote replicas one by one, until one of them responds
+    /// 3 - Fallbacks to the local replica, if there are no active remote replicas
+    pub(crate) async fn execute_active_replica_read<Res, L, R>(
+        &self,
+        local_read: L,
+        remote_read: R,
+    ) -> CollectionResult<Res>
+    where
+        L: FnOnce(Option<&LocalShard>) -> CollectionResult<Res>,
+        R: Fn(&RemoteShard) -> BoxFuture<'_, CollectionResult<Res>>,
+    {
+        if !self.peer_is_active(&self.this_peer_id()) {
+            let remotes = self.remotes.read().await;
+
+            let mut last_error = None;
+            for remote in remotes
+                .iter()
+                .filter(|remote| self.peer_is_active(&remote.peer_id))
+            {
+                match remote_read(remote).await {
+                    Ok(result) => return Ok(result),
+                    Err(error) => {
+                        log::debug!("Read operation failed: {error}");
+                        last_error = Some(error);
+                    }
+                }
+            }
+
+            if let Some(error) = last_error {
+                return Err(error);
+            }
+        }
+
+        let local = self.local.read().await;
+        local_read(local.as_ref().and_then(Shard::local_shard))
+    }
+
     async fn execute_local_read_operation<Res, F>(&self, read_operation: F) -> CollectionResult<Res>
     where
         F: Fn(&(dyn ShardOperation + Send + Sync)) -> BoxFuture<'_, CollectionResult<Res>>,
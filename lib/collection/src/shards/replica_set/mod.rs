@@ -1,4 +1,5 @@
 pub mod clock_set;
+mod consistency;
 mod execute_read_operation;
 mod locally_disabled_peers;
 mod read_ops;
@@ -12,9 +13,12 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 
+use chrono::{DateTime, Utc};
 use common::cpu::CpuBudget;
 use common::types::TelemetryDetail;
+use futures::FutureExt as _;
 use schemars::JsonSchema;
+use segment::types::{Filter, PointIdType};
 use serde::{Deserialize, Serialize};
 use tokio::runtime::Handle;
 use tokio::sync::{Mutex, RwLock};
@@ -25,10 +29,17 @@ use super::remote_shard::RemoteShard;
 use super::transfer::ShardTransfer;
 use super::CollectionId;
 use crate::collection::payload_index_schema::PayloadIndexSchema;
+use crate::collection_manager::optimizers::TrackerTelemetry;
+use crate::collection_manager::point_history::PointVersion;
+use crate::collection_manager::point_trash::TrashedPoint;
+use crate::common::collection_stats::StatsCounters;
 use crate::common::snapshots_manager::SnapshotStorageManager;
+use crate::common::sparse_vocabulary::SparseVocabularyStats;
 use crate::config::CollectionConfig;
+use crate::operations::config_impact::ShardConfigChangeImpact;
+use crate::operations::segment_details::ShardSegmentsInfo;
 use crate::operations::shared_storage_config::SharedStorageConfig;
-use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::types::{CollectionError, CollectionResult, QuotaUsage, ShardDiskUsage};
 use crate::optimizers_builder::OptimizersConfig;
 use crate::save_on_disk::SaveOnDisk;
 use crate::shards::channel_service::ChannelService;
@@ -251,6 +262,7 @@ impl ShardReplicaSet {
         );
 
         let mut local_load_failure = false;
+        let mut local_quarantined = false;
         let local = if replica_state.read().is_local {
             let shard = if let Some(recovery_reason) = &shared_storage_config.recovery_mode {
                 Shard::Dummy(DummyShard::new(recovery_reason))
@@ -269,7 +281,10 @@ impl ShardReplicaSet {
                 .await;
 
                 match res {
-                    Ok(shard) => Shard::Local(shard),
+                    Ok(shard) => {
+                        local_quarantined = !shard.quarantined_segments().is_empty();
+                        Shard::Local(shard)
+                    }
                     Err(err) => {
                         if !shared_storage_config.handle_collection_load_errors {
                             panic!("Failed to load local shard {shard_path:?}: {err}")
@@ -325,6 +340,28 @@ impl ShardReplicaSet {
                 .disable_peer(this_peer_id);
         }
 
+        // Part of the local data is lost, report the replica as dead to recover it from a healthy
+        // replica. If there is none, keep serving the remaining data, quarantined segments are
+        // reported in the cluster info.
+        if local_quarantined {
+            if replica_set.active_remote_shards().await.is_empty() {
+                log::error!(
+                    "Segments of local shard {shard_path:?} are quarantined, \
+                     but there is no other active replica to recover them from, \
+                     serving the remaining points",
+                );
+            } else {
+                log::warn!(
+                    "Segments of local shard {shard_path:?} are quarantined, \
+                     recovering the replica from other replicas",
+                );
+                replica_set
+                    .locally_disabled_peers
+                    .write()
+                    .disable_peer(this_peer_id);
+            }
+        }
+
         replica_set
     }
 
@@ -759,6 +796,218 @@ impl ShardReplicaSet {
         }
     }
 
+    /// Internals of segments of the local replica, if there is one on this peer
+    pub(crate) async fn segments_info(&self) -> Option<ShardSegmentsInfo> {
+        let local_shard = self.local.read().await;
+        local_shard
+            .as_ref()
+            .and_then(Shard::local_shard)
+            .map(|local_shard| local_shard.segments_info(self.shard_id))
+    }
+
+    /// Effect of the config change on the local replica, if there is one on this peer
+    pub(crate) async fn config_change_impact(
+        &self,
+        new_config: &CollectionConfig,
+    ) -> Option<ShardConfigChangeImpact> {
+        let local_shard = self.local.read().await;
+        local_shard
+            .as_ref()
+            .and_then(Shard::local_shard)
+            .map(|local_shard| local_shard.config_change_impact(self.shard_id, new_config))
+    }
+
+    /// Memory mapped files of the local replica, if there is one on this peer
+    pub(crate) async fn mmap_files(&self) -> Vec<PathBuf> {
+        let local_shard = self.local.read().await;
+        local_shard
+            .as_ref()
+            .and_then(Shard::local_shard)
+            .map(LocalShard::mmap_files)
+            .unwrap_or_default()
+    }
+
+    /// Counters of the recent requests and the optimizations log of the local replica,
+    /// if there is one on this peer
+    pub(crate) async fn local_stats(
+        &self,
+    ) -> Option<(StatsCounters, Duration, Vec<TrackerTelemetry>)> {
+        let local_shard = self.local.read().await;
+        local_shard
+            .as_ref()
+            .and_then(Shard::local_shard)
+            .map(LocalShard::stats)
+    }
+
+    /// Disk usage of the local replica, if there is one on this peer
+    pub(crate) async fn disk_usage(&self) -> CollectionResult<Option<ShardDiskUsage>> {
+        let local_shard = self.local.read().await;
+        match local_shard.as_ref().and_then(Shard::local_shard) {
+            Some(local_shard) => Ok(Some(local_shard.disk_usage(self.shard_id).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Resource usage of the local replica, if there is one on this peer
+    pub(crate) async fn quota_usage(&self) -> CollectionResult<Option<QuotaUsage>> {
+        let local_shard = self.local.read().await;
+        match local_shard.as_ref().and_then(Shard::local_shard) {
+            Some(local_shard) => Ok(Some(local_shard.quota_usage().await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Sparse vocabulary statistics of the local replica, `None` if there is no local replica
+    pub(crate) async fn local_sparse_vocabulary_stats(
+        &self,
+        vector_name: &str,
+    ) -> CollectionResult<Option<SparseVocabularyStats>> {
+        let local_shard = self.local.read().await;
+        match local_shard.as_ref().and_then(Shard::local_shard) {
+            Some(local_shard) => Ok(Some(
+                local_shard.sparse_vocabulary_stats(vector_name).await?,
+            )),
+            None => Ok(None),
+        }
+    }
+
+    /// Freeze the local replica, returns the time at which the freeze is lifted, or `None` if
+    /// there is no local replica
+    pub(crate) async fn freeze_local(
+        &self,
+        timeout: Duration,
+    ) -> CollectionResult<Option<DateTime<Utc>>> {
+        let local_shard = self.local.read().await;
+        match local_shard.as_ref().and_then(Shard::local_shard) {
+            Some(local_shard) => Ok(Some(local_shard.freeze(timeout).await?)),
+            None => Ok(None),
+        }
+    }
+
+    pub(crate) async fn unfreeze_local(&self) -> CollectionResult<()> {
+        let local_shard = self.local.read().await;
+        match local_shard.as_ref().and_then(Shard::local_shard) {
+            Some(local_shard) => local_shard.unfreeze().await,
+            None => Ok(()),
+        }
+    }
+
+    /// Time, at which the freeze of the local replica is lifted, `None` if it is not frozen
+    pub(crate) async fn local_frozen_until(&self) -> Option<DateTime<Utc>> {
+        let local_shard = self.local.read().await;
+        match local_shard.as_ref().and_then(Shard::local_shard) {
+            Some(local_shard) => local_shard.frozen_until().await,
+            None => None,
+        }
+    }
+
+    /// Deleted points with the given ids in the local replica, if there is one on this peer
+    pub(crate) async fn local_trashed_points(
+        &self,
+        point_ids: &[PointIdType],
+    ) -> CollectionResult<Option<Vec<TrashedPoint>>> {
+        let local_shard = self.local.read().await;
+        match local_shard.as_ref().and_then(Shard::local_shard) {
+            Some(local_shard) => local_shard.trashed_points(point_ids),
+            None => Ok(None),
+        }
+    }
+
+    /// Deleted points with ids in `from..to`, which match the filter, in the local replica
+    pub(crate) async fn local_scan_trash(
+        &self,
+        from: Option<PointIdType>,
+        to: Option<PointIdType>,
+        filter: Option<&Filter>,
+    ) -> CollectionResult<Vec<TrashedPoint>> {
+        let local_shard = self.local.read().await;
+        match local_shard.as_ref().and_then(Shard::local_shard) {
+            Some(local_shard) => local_shard.scan_trash(from, to, filter),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Deleted points with the given ids in an active replica, remote if the local one isn't
+    /// active
+    pub(crate) async fn trashed_points(
+        &self,
+        point_ids: &[PointIdType],
+    ) -> CollectionResult<Option<Vec<TrashedPoint>>> {
+        let remote_point_ids = Arc::new(point_ids.to_vec());
+
+        self.execute_active_replica_read(
+            |local_shard| match local_shard {
+                Some(local_shard) => local_shard.trashed_points(point_ids),
+                None => Ok(None),
+            },
+            |remote| {
+                let point_ids = remote_point_ids.clone();
+                async move { remote.trashed_points(&point_ids).await }.boxed()
+            },
+        )
+        .await
+    }
+
+    /// Deleted points with ids in `from..to`, which match the filter, in an active replica,
+    /// remote if the local one isn't active
+    pub(crate) async fn scan_trash(
+        &self,
+        from: Option<PointIdType>,
+        to: Option<PointIdType>,
+        filter: Option<&Filter>,
+    ) -> CollectionResult<Vec<TrashedPoint>> {
+        let remote_filter = filter.map(|filter| Arc::new(filter.clone()));
+
+        self.execute_active_replica_read(
+            |local_shard| match local_shard {
+                Some(local_shard) => local_shard.scan_trash(from, to, filter),
+                None => Ok(Vec::new()),
+            },
+            |remote| {
+                let filter = remote_filter.clone();
+                async move { remote.scan_trash(from, to, filter.as_deref()).await }.boxed()
+            },
+        )
+        .await
+    }
+
+    /// Segments of the local replica, which failed to load and were quarantined
+    pub async fn quarantined_segments(&self) -> Vec<String> {
+        let local_shard = self.local.read().await;
+        local_shard
+            .as_ref()
+            .and_then(Shard::local_shard)
+            .map(|local_shard| local_shard.quarantined_segments().to_vec())
+            .unwrap_or_default()
+    }
+
+    /// Retained versions of the point in the local replica, if there is one on this peer
+    pub(crate) async fn local_point_history(
+        &self,
+        point_id: PointIdType,
+    ) -> CollectionResult<Option<Vec<PointVersion>>> {
+        let local_shard = self.local.read().await;
+        match local_shard.as_ref().and_then(Shard::local_shard) {
+            Some(local_shard) => local_shard.point_history(point_id),
+            None => Ok(None),
+        }
+    }
+
+    /// Retained versions of the point in an active replica, remote if the local one isn't active
+    pub(crate) async fn point_history(
+        &self,
+        point_id: PointIdType,
+    ) -> CollectionResult<Option<Vec<PointVersion>>> {
+        self.execute_active_replica_read(
+            |local_shard| match local_shard {
+                Some(local_shard) => local_shard.point_history(point_id),
+                None => Ok(None),
+            },
+            |remote| remote.point_history(point_id).boxed(),
+        )
+        .await
+    }
+
     pub(crate) async fn health_check(&self, peer_id: PeerId) -> CollectionResult<()> {
         let remotes = self.remotes.read().await;
 
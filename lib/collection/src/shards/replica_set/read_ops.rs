@@ -1,14 +1,18 @@
 use std::sync::Arc;
 use std::time::Duration;
 
+use common::hardware_counter::HardwareCounterCell;
 use futures::FutureExt as _;
+use segment::data_types::aggregation::{DistinctValues, NumericStats, ValueDistribution};
 use segment::data_types::order_by::OrderBy;
+use segment::data_types::search_plan::SegmentSearchPlan;
 use segment::types::*;
 
 use super::ShardReplicaSet;
 use crate::operations::consistency_params::ReadConsistency;
 use crate::operations::types::*;
 use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
+use crate::shards::resolve::Reconciled;
 
 impl ShardReplicaSet {
     #[allow(clippy::too_many_arguments)]
@@ -63,13 +67,20 @@ impl ShardReplicaSet {
         read_consistency: Option<ReadConsistency>,
         local_only: bool,
         timeout: Option<Duration>,
-    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        hw_counter: &HardwareCounterCell,
+    ) -> CollectionResult<PartialResponse<Vec<Vec<ScoredPoint>>>> {
         self.execute_and_resolve_read_operation(
             |shard| {
                 let request = Arc::clone(&request);
                 let search_runtime = self.search_runtime.clone();
+                let hw_counter = hw_counter.clone();
 
-                async move { shard.core_search(request, &search_runtime, timeout).await }.boxed()
+                async move {
+                    shard
+                        .core_search(request, &search_runtime, timeout, &hw_counter)
+                        .await
+                }
+                .boxed()
             },
             read_consistency,
             local_only,
@@ -77,16 +88,107 @@ impl ShardReplicaSet {
         .await
     }
 
+    /// Search on the local replica, reporting how each segment executed the search.
+    ///
+    /// Search plans are only available if this peer holds an active replica of the shard
+    /// and no specific read consistency is requested,
+    /// otherwise the search is executed as a regular one and no plans are returned.
+    pub async fn core_search_explain(
+        &self,
+        request: Arc<CoreSearchRequestBatch>,
+        read_consistency: Option<ReadConsistency>,
+        local_only: bool,
+        timeout: Option<Duration>,
+        hw_counter: &HardwareCounterCell,
+    ) -> CollectionResult<(
+        PartialResponse<Vec<Vec<ScoredPoint>>>,
+        Option<Vec<SegmentSearchPlan>>,
+    )> {
+        let use_local =
+            local_only || (read_consistency.is_none() && self.peer_is_active(&self.this_peer_id()));
+
+        {
+            let local = self.local.read().await;
+            let local_shard = local
+                .as_ref()
+                .filter(|_| use_local)
+                .and_then(|shard| shard.local_shard());
+
+            if let Some(local_shard) = local_shard {
+                let (res, search_plans) = local_shard
+                    .do_search_explain(request, &self.search_runtime, timeout, hw_counter)
+                    .await?;
+                return Ok((PartialResponse::new(res), Some(search_plans)));
+            }
+        }
+
+        let res = self
+            .core_search(request, read_consistency, local_only, timeout, hw_counter)
+            .await?;
+        Ok((res, None))
+    }
+
     pub async fn count(
         &self,
         request: Arc<CountRequestInternal>,
         read_consistency: Option<ReadConsistency>,
         local_only: bool,
-    ) -> CollectionResult<CountResult> {
+    ) -> CollectionResult<Reconciled<CountResult>> {
+        self.execute_and_resolve_read_operation(
+            |shard| {
+                let request = request.clone();
+                async move { shard.count(request).await.map(Reconciled::new) }.boxed()
+            },
+            read_consistency,
+            local_only,
+        )
+        .await
+    }
+
+    pub async fn count_distinct(
+        &self,
+        request: Arc<CountDistinctRequestInternal>,
+        read_consistency: Option<ReadConsistency>,
+        local_only: bool,
+    ) -> CollectionResult<Reconciled<DistinctValues>> {
+        self.execute_and_resolve_read_operation(
+            |shard| {
+                let request = request.clone();
+                async move { shard.count_distinct(request).await.map(Reconciled::new) }.boxed()
+            },
+            read_consistency,
+            local_only,
+        )
+        .await
+    }
+
+    pub async fn histogram(
+        &self,
+        request: Arc<HistogramRequestInternal>,
+        read_consistency: Option<ReadConsistency>,
+        local_only: bool,
+    ) -> CollectionResult<ValueDistribution> {
+        self.execute_and_resolve_read_operation(
+            |shard| {
+                let request = request.clone();
+                async move { shard.histogram(request).await }.boxed()
+            },
+            read_consistency,
+            local_only,
+        )
+        .await
+    }
+
+    pub async fn field_stats(
+        &self,
+        request: Arc<FieldStatsRequestInternal>,
+        read_consistency: Option<ReadConsistency>,
+        local_only: bool,
+    ) -> CollectionResult<NumericStats> {
         self.execute_and_resolve_read_operation(
             |shard| {
                 let request = request.clone();
-                async move { shard.count(request).await }.boxed()
+                async move { shard.field_stats(request).await }.boxed()
             },
             read_consistency,
             local_only,
@@ -144,13 +246,20 @@ impl ShardReplicaSet {
         read_consistency: Option<ReadConsistency>,
         local_only: bool,
         timeout: Option<Duration>,
-    ) -> CollectionResult<Vec<ShardQueryResponse>> {
+        hw_counter: &HardwareCounterCell,
+    ) -> CollectionResult<PartialResponse<Vec<ShardQueryResponse>>> {
         self.execute_and_resolve_read_operation(
             |shard| {
                 let requests = Arc::clone(&requests);
                 let search_runtime = self.search_runtime.clone();
+                let hw_counter = hw_counter.clone();
 
-                async move { shard.query_batch(requests, &search_runtime, timeout).await }.boxed()
+                async move {
+                    shard
+                        .query_batch(requests, &search_runtime, timeout, &hw_counter)
+                        .await
+                }
+                .boxed()
             },
             read_consistency,
             local_only,
@@ -1,5 +1,5 @@
 use std::ops::Deref as _;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use super::{ReplicaSetState, ReplicaState, ShardReplicaSet, REPLICA_STATE_FILE};
 use crate::operations::types::{CollectionError, CollectionResult};
@@ -168,4 +168,44 @@ impl ShardReplicaSet {
             }
         }
     }
+
+    fn local_segment_shard_error(&self) -> CollectionError {
+        CollectionError::bad_input(format!(
+            "Shard {} has no local replica on this peer",
+            self.shard_id,
+        ))
+    }
+
+    /// Archive a single segment of the local replica into `target_dir`
+    pub async fn create_segment_snapshot(
+        &self,
+        segment_uuid: &str,
+        temp_path: &Path,
+        target_dir: &Path,
+    ) -> CollectionResult<PathBuf> {
+        let local = self.local.read().await;
+        let local_shard = local
+            .as_ref()
+            .and_then(Shard::local_shard)
+            .ok_or_else(|| self.local_segment_shard_error())?;
+        local_shard
+            .create_segment_snapshot(segment_uuid, temp_path, target_dir)
+            .await
+    }
+
+    /// Replace a segment of the local replica with the segment from the snapshot archive
+    pub async fn restore_segment_snapshot(
+        &self,
+        segment_uuid: &str,
+        snapshot_path: &Path,
+    ) -> CollectionResult<()> {
+        let local = self.local.read().await;
+        let local_shard = local
+            .as_ref()
+            .and_then(Shard::local_shard)
+            .ok_or_else(|| self.local_segment_shard_error())?;
+        local_shard
+            .replace_segment(segment_uuid, snapshot_path)
+            .await
+    }
 }
@@ -9,7 +9,7 @@ use super::{clock_set, ReplicaSetState, ReplicaState, ShardReplicaSet};
 use crate::operations::point_ops::WriteOrdering;
 use crate::operations::types::{CollectionError, CollectionResult, UpdateResult, UpdateStatus};
 use crate::operations::{ClockTag, CollectionUpdateOperations, OperationWithClockTag};
-use crate::shards::shard::PeerId;
+use crate::shards::shard::{PeerId, Shard};
 use crate::shards::shard_trait::ShardOperation as _;
 
 /// Maximum number of attempts for applying an update with a new clock.
@@ -69,6 +69,7 @@ impl ShardReplicaSet {
     pub async fn update_with_consistency(
         &self,
         operation: CollectionUpdateOperations,
+        idempotency_key: Option<String>,
         wait: bool,
         ordering: WriteOrdering,
     ) -> CollectionResult<UpdateResult> {
@@ -91,10 +92,10 @@ impl ShardReplicaSet {
                 WriteOrdering::Weak => None,
             };
 
-            self.update(operation, wait).await
+            self.update(operation, idempotency_key, wait).await
         } else {
             // Forward the update to the designated leader
-            self.forward_update(leader_peer, operation, wait, ordering)
+            self.forward_update(leader_peer, operation, idempotency_key, wait, ordering)
                 .await
                 .map_err(|err| {
                     if err.is_transient() {
@@ -142,10 +143,22 @@ impl ShardReplicaSet {
     async fn update(
         &self,
         operation: CollectionUpdateOperations,
+        idempotency_key: Option<String>,
         wait: bool,
     ) -> CollectionResult<UpdateResult> {
         // `ShardRepilcaSet::update_impl` is not cancel safe, so this method is not cancel safe.
 
+        // Reject the update before it is applied to any replica, if the local queue is full
+        if let Some(local) = self
+            .local
+            .read()
+            .await
+            .as_ref()
+            .and_then(Shard::local_shard)
+        {
+            local.check_update_queue().await?;
+        }
+
         // TODO: Optimize `remotes`/`local`/`clock` locking for the "happy path"?
         //
         // E.g., refactor `update`/`update_impl`, so that it would be possible to:
@@ -158,7 +171,7 @@ impl ShardReplicaSet {
             let is_non_zero_tick = clock.current_tick().is_some();
 
             let res = self
-                .update_impl(operation.clone(), wait, &mut clock)
+                .update_impl(operation.clone(), idempotency_key.clone(), wait, &mut clock)
                 .await?;
 
             if let Some(res) = res {
@@ -188,6 +201,7 @@ impl ShardReplicaSet {
     async fn update_impl(
         &self,
         operation: CollectionUpdateOperations,
+        idempotency_key: Option<String>,
         wait: bool,
         clock: &mut clock_set::ClockGuard,
     ) -> CollectionResult<Option<UpdateResult>> {
@@ -218,7 +232,8 @@ impl ShardReplicaSet {
 
         let current_clock_tick = clock.tick_once();
         let clock_tag = ClockTag::new(this_peer_id, clock.id() as _, current_clock_tick);
-        let operation = OperationWithClockTag::new(operation, Some(clock_tag));
+        let operation = OperationWithClockTag::new(operation, Some(clock_tag))
+            .with_idempotency_key(idempotency_key);
 
         let mut update_futures = Vec::with_capacity(active_remote_shards.len() + 1);
 
@@ -398,10 +413,11 @@ impl ShardReplicaSet {
             return Ok(None);
         }
 
-        // there are enough successes, return the first one
+        // there are enough successes, return the first one, preferring completed ones,
+        // as only those report write conflicts
         let (_, res) = successes
             .into_iter()
-            .next()
+            .min_by_key(|(_, res)| !matches!(res.status, UpdateStatus::Completed))
             .expect("successes is not empty");
 
         Ok(Some(res))
@@ -487,6 +503,7 @@ impl ShardReplicaSet {
         &self,
         leader_peer: PeerId,
         operation: CollectionUpdateOperations,
+        idempotency_key: Option<String>,
         wait: bool,
         ordering: WriteOrdering,
     ) -> CollectionResult<UpdateResult> {
@@ -502,7 +519,12 @@ impl ShardReplicaSet {
         };
 
         remote_leader
-            .forward_update(OperationWithClockTag::from(operation), wait, ordering) // `clock_tag` *have to* be `None`!
+            .forward_update(
+                // `clock_tag` *have to* be `None`!
+                OperationWithClockTag::from(operation).with_idempotency_key(idempotency_key),
+                wait,
+                ordering,
+            )
             .await
     }
 }
@@ -554,6 +576,7 @@ mod tests {
         indexing_threshold: Some(50_000),
         flush_interval_sec: 30,
         max_optimization_threads: Some(2),
+        defer_indexing: false,
     };
 
     async fn new_shard_replica_set(collection_dir: &TempDir) -> ShardReplicaSet {
@@ -579,6 +602,7 @@ mod tests {
             wal_config,
             hnsw_config: Default::default(),
             quantization_config: None,
+            strict_mode_config: None,
         };
 
         let payload_index_schema_dir = Builder::new().prefix("qdrant-test").tempdir().unwrap();
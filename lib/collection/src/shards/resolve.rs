@@ -1,11 +1,12 @@
 use std::collections::{HashMap, HashSet};
 use std::hash;
 
+use segment::data_types::aggregation::{DistinctValues, NumericStats, ValueDistribution};
 use segment::types::{Payload, ScoredPoint};
 use tinyvec::TinyVec;
 
 use crate::common::transpose_iterator::transposed_iter;
-use crate::operations::types::{CountResult, Record};
+use crate::operations::types::{CountResult, PartialResponse, Record};
 use crate::operations::universal_query::shard_query::ShardQueryResponse;
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -18,6 +19,72 @@ pub trait Resolve: Sized {
     fn resolve(responses: Vec<Self>, condition: ResolveCondition) -> Self;
 }
 
+/// Response, resolved from the responses of the replicas, with whether they agreed on it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reconciled<T> {
+    pub value: T,
+    /// Whether all read replicas returned the same response. `None`, if only one replica was read.
+    pub consistent: Option<bool>,
+}
+
+impl<T> Reconciled<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            consistent: None,
+        }
+    }
+
+    /// Combine the value with the response of another shard
+    pub fn merge(&mut self, other: Reconciled<T>, merge: impl FnOnce(&mut T, T)) {
+        merge(&mut self.value, other.value);
+        self.consistent = match (self.consistent, other.consistent) {
+            (Some(consistent), Some(other)) => Some(consistent && other),
+            (consistent, None) | (None, consistent) => consistent,
+        };
+    }
+}
+
+/// Searches of all replicas are resolved, the response timed out if any of them did
+impl<T: Resolve> Resolve for PartialResponse<T> {
+    fn resolve(responses: Vec<Self>, condition: ResolveCondition) -> Self {
+        let timed_out = responses.iter().any(|response| response.timed_out);
+        let values = responses
+            .into_iter()
+            .map(|response| response.value)
+            .collect();
+        Self {
+            value: T::resolve(values, condition),
+            timed_out,
+            // Replicas of a single shard are read
+            shards: Vec::new(),
+        }
+    }
+}
+
+impl<T: Resolve + PartialEq> Resolve for Reconciled<T> {
+    fn resolve(responses: Vec<Self>, condition: ResolveCondition) -> Self {
+        let consistent = match responses.as_slice() {
+            [] => None,
+            [single] => single.consistent,
+            [first, rest @ ..] => Some(
+                rest.iter().all(|response| response.value == first.value)
+                    && responses
+                        .iter()
+                        .all(|response| response.consistent != Some(false)),
+            ),
+        };
+        let values = responses
+            .into_iter()
+            .map(|response| response.value)
+            .collect();
+        Self {
+            value: T::resolve(values, condition),
+            consistent,
+        }
+    }
+}
+
 impl Resolve for CountResult {
     fn resolve(records: Vec<Self>, condition: ResolveCondition) -> Self {
         match condition {
@@ -27,6 +94,7 @@ impl Resolve for CountResult {
                     .map(|result| result.count)
                     .min()
                     .unwrap_or_default(),
+                consistent: None,
             },
             ResolveCondition::Majority => {
                 let mut counts = records
@@ -37,12 +105,61 @@ impl Resolve for CountResult {
                 let middle = counts.len() / 2;
                 Self {
                     count: counts.get(middle).copied().unwrap_or_default(),
+                    consistent: None,
                 }
             }
         }
     }
 }
 
+/// Same as for [`CountResult`], values of the replica with the selected count are used
+impl Resolve for DistinctValues {
+    fn resolve(mut values: Vec<Self>, condition: ResolveCondition) -> Self {
+        values.sort_unstable_by_key(|values| values.count());
+        let selected = match condition {
+            ResolveCondition::All => 0,
+            ResolveCondition::Majority => values.len() / 2,
+        };
+        if selected < values.len() {
+            values.swap_remove(selected)
+        } else {
+            DistinctValues::default()
+        }
+    }
+}
+
+/// Values of the replica with the selected number of values are used
+impl Resolve for ValueDistribution {
+    fn resolve(mut values: Vec<Self>, condition: ResolveCondition) -> Self {
+        values.sort_unstable_by_key(|values| values.total_count());
+        let selected = match condition {
+            ResolveCondition::All => 0,
+            ResolveCondition::Majority => values.len() / 2,
+        };
+        if selected < values.len() {
+            values.swap_remove(selected)
+        } else {
+            ValueDistribution::default()
+        }
+    }
+}
+
+/// Statistics of the replica with the selected number of values are used
+impl Resolve for NumericStats {
+    fn resolve(mut values: Vec<Self>, condition: ResolveCondition) -> Self {
+        values.sort_unstable_by_key(|stats| stats.count());
+        let selected = match condition {
+            ResolveCondition::All => 0,
+            ResolveCondition::Majority => values.len() / 2,
+        };
+        if selected < values.len() {
+            values.swap_remove(selected)
+        } else {
+            NumericStats::default()
+        }
+    }
+}
+
 impl Resolve for Vec<Record> {
     fn resolve(records: Vec<Self>, condition: ResolveCondition) -> Self {
         let mut resolved = Resolver::resolve(records, |record| record.id, record_eq, condition);
@@ -525,6 +642,37 @@ mod test {
         test_resolve_simple(input_4(), expected_4_majority(), ResolveCondition::Majority);
     }
 
+    #[test]
+    fn resolve_reconciled() {
+        let input = simple_input([vec![1, 2], vec![1, 2], vec![1]]);
+        let reconciled: Vec<_> = input.into_iter().map(Reconciled::new).collect();
+
+        let resolved = Reconciled::resolve(reconciled.clone(), ResolveCondition::Majority);
+        assert_eq!(resolved.value, simple_expected([1, 2]));
+        assert_eq!(resolved.consistent, Some(false));
+
+        let resolved = Reconciled::resolve(reconciled[..2].to_vec(), ResolveCondition::All);
+        assert_eq!(resolved.consistent, Some(true));
+
+        let resolved = Reconciled::resolve(reconciled[..1].to_vec(), ResolveCondition::All);
+        assert_eq!(resolved.consistent, None);
+    }
+
+    #[test]
+    fn resolve_partial_response() {
+        let input = simple_input([vec![1, 2], vec![1, 2], vec![1]]);
+        let mut responses: Vec<_> = input.into_iter().map(PartialResponse::new).collect();
+
+        let resolved = PartialResponse::resolve(responses.clone(), ResolveCondition::Majority);
+        assert_eq!(resolved.value, simple_expected([1, 2]));
+        assert!(!resolved.timed_out);
+
+        responses[2].timed_out = true;
+        let resolved = PartialResponse::resolve(responses, ResolveCondition::Majority);
+        assert_eq!(resolved.value, simple_expected([1, 2]));
+        assert!(resolved.timed_out);
+    }
+
     fn test_resolve<T, E>(input: Vec<T>, expected: E, condition: ResolveCondition)
     where
         T: Resolve + Clone + PartialEq<E> + fmt::Debug,
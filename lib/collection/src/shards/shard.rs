@@ -63,6 +63,17 @@ impl Shard {
         }
     }
 
+    /// Local shard, which holds the data of this shard, if any
+    pub fn local_shard(&self) -> Option<&LocalShard> {
+        match self {
+            Shard::Local(local_shard) => Some(local_shard),
+            Shard::Proxy(proxy_shard) => Some(proxy_shard.wrapped_shard()),
+            Shard::ForwardProxy(proxy_shard) => Some(&proxy_shard.wrapped_shard),
+            Shard::QueueProxy(proxy_shard) => proxy_shard.wrapped_shard(),
+            Shard::Dummy(_) => None,
+        }
+    }
+
     pub fn get_telemetry_data(&self, detail: TelemetryDetail) -> LocalShardTelemetry {
         let mut telemetry = match self {
             Shard::Local(local_shard) => local_shard.get_telemetry_data(detail),
@@ -1174,6 +1174,79 @@ impl ShardHolder {
         snapshot_description
     }
 
+    /// Create a snapshot of a single segment of the local shard.
+    ///
+    /// The snapshot is stored along with the shard snapshots.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe.
+    pub async fn create_segment_snapshot(
+        &self,
+        snapshots_path: &Path,
+        collection_name: &str,
+        shard_id: ShardId,
+        segment_uuid: &str,
+        temp_dir: &Path,
+    ) -> CollectionResult<SnapshotDescription> {
+        self.assert_shard_is_local(shard_id).await?;
+
+        let shard = self
+            .get_shard(&shard_id)
+            .ok_or_else(|| shard_not_found_error(shard_id))?;
+
+        let snapshot_file_name = format!(
+            "{collection_name}-shard-{shard_id}-segment-{segment_uuid}-{}.snapshot",
+            chrono::Utc::now().format("%Y-%m-%d-%H-%M-%S"),
+        );
+        // Validate segment name before creating any files with it
+        let snapshot_path =
+            self.shard_snapshot_path_unchecked(snapshots_path, shard_id, &snapshot_file_name)?;
+
+        let snapshot_temp_dir = tempfile::Builder::new()
+            .prefix(&format!("{snapshot_file_name}-temp-"))
+            .tempdir_in(temp_dir)?;
+
+        let snapshot_target_dir = tempfile::Builder::new()
+            .prefix(&format!("{snapshot_file_name}-target-"))
+            .tempdir_in(temp_dir)?;
+
+        let archive_path = shard
+            .create_segment_snapshot(
+                segment_uuid,
+                snapshot_temp_dir.path(),
+                snapshot_target_dir.path(),
+            )
+            .await?;
+
+        let snapshot_manager = shard.get_snapshots_storage_manager()?;
+        snapshot_manager
+            .store_file(&archive_path, &snapshot_path)
+            .await
+    }
+
+    /// Replace a segment of the local shard with the segment from the snapshot archive.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is *not* cancel safe.
+    pub async fn restore_segment_snapshot(
+        &self,
+        snapshot_path: &Path,
+        shard_id: ShardId,
+        segment_uuid: &str,
+    ) -> CollectionResult<()> {
+        self.assert_shard_is_local(shard_id).await?;
+
+        let shard = self
+            .get_shard(&shard_id)
+            .ok_or_else(|| shard_not_found_error(shard_id))?;
+
+        shard
+            .restore_segment_snapshot(segment_uuid, snapshot_path)
+            .await
+    }
+
     /// # Cancel safety
     ///
     /// This method is *not* cancel safe.
@@ -32,6 +32,18 @@ pub struct LocalShardTelemetry {
     pub variant_name: Option<String>,
     pub segments: Vec<SegmentTelemetry>,
     pub optimizations: OptimizerTelemetry,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub update_queue: Option<UpdateQueueTelemetry>,
+}
+
+#[derive(Serialize, Clone, Debug, JsonSchema, Default)]
+pub struct UpdateQueueTelemetry {
+    /// Number of updates, waiting in the queue to be applied
+    pub length: usize,
+    /// Max number of updates in the queue
+    pub capacity: usize,
+    /// Number of updates, rejected because the queue was full
+    pub rejected: usize,
 }
 
 #[derive(Serialize, Clone, Debug, JsonSchema, Default)]
@@ -57,6 +69,7 @@ impl Anonymize for LocalShardTelemetry {
             variant_name: self.variant_name.clone(),
             segments: self.segments.anonymize(),
             optimizations: self.optimizations.anonymize(),
+            update_queue: self.update_queue.clone(),
         }
     }
 }
@@ -3,7 +3,7 @@ use segment::common::anonymize::Anonymize;
 use serde::Serialize;
 
 use crate::config::CollectionConfig;
-use crate::operations::types::ShardTransferInfo;
+use crate::operations::types::{QuotaUsage, ShardTransferInfo};
 use crate::shards::telemetry::ReplicaSetTelemetry;
 
 #[derive(Serialize, Clone, Debug, JsonSchema)]
@@ -13,6 +13,9 @@ pub struct CollectionTelemetry {
     pub config: CollectionConfig,
     pub shards: Vec<ReplicaSetTelemetry>,
     pub transfers: Vec<ShardTransferInfo>,
+    /// Estimated usage of the whole collection, if its strict mode sets any quotas
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quota_usage: Option<QuotaUsage>,
 }
 
 impl CollectionTelemetry {
@@ -34,6 +37,17 @@ impl Anonymize for CollectionTelemetry {
             init_time_ms: self.init_time_ms,
             shards: self.shards.anonymize(),
             transfers: vec![],
+            quota_usage: self.quota_usage.anonymize(),
+        }
+    }
+}
+
+impl Anonymize for QuotaUsage {
+    fn anonymize(&self) -> Self {
+        Self {
+            points_count: self.points_count.anonymize(),
+            ram_usage_bytes: self.ram_usage_bytes.anonymize(),
+            disk_usage_bytes: self.disk_usage_bytes.anonymize(),
         }
     }
 }
@@ -46,6 +60,7 @@ impl Anonymize for CollectionConfig {
             optimizer_config: self.optimizer_config.clone(),
             wal_config: self.wal_config.clone(),
             quantization_config: self.quantization_config.clone(),
+            strict_mode_config: self.strict_mode_config.clone(),
         }
     }
 }
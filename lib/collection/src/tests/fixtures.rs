@@ -21,6 +21,7 @@ pub const TEST_OPTIMIZERS_CONFIG: OptimizersConfig = OptimizersConfig {
     indexing_threshold: Some(50_000),
     flush_interval_sec: 30,
     max_optimization_threads: Some(2),
+    defer_indexing: false,
 };
 
 pub fn create_collection_config() -> CollectionConfig {
@@ -45,6 +46,7 @@ pub fn create_collection_config() -> CollectionConfig {
         wal_config,
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
+        strict_mode_config: None,
     }
 }
 
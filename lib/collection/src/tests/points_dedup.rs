@@ -4,6 +4,7 @@ use std::sync::Arc;
 
 use api::rest::{OrderByInterface, VectorStruct};
 use common::cpu::CpuBudget;
+use common::hardware_counter::HardwareCounterCell;
 use rand::{thread_rng, Rng};
 use segment::data_types::vectors::NamedVectorStruct;
 use segment::types::{
@@ -55,6 +56,7 @@ async fn fixture() -> Collection {
         wal_config,
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
+        strict_mode_config: None,
     };
 
     let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
@@ -157,6 +159,8 @@ async fn test_scroll_dedup() {
                 with_payload: Some(false.into()),
                 with_vector: false.into(),
                 order_by: None,
+                cursor: None,
+                include_deleted: None,
             },
             None,
             &ShardSelectorInternal::All,
@@ -183,6 +187,8 @@ async fn test_scroll_dedup() {
                 with_payload: Some(false.into()),
                 with_vector: false.into(),
                 order_by: Some(OrderByInterface::Key("num".parse().unwrap())),
+                cursor: None,
+                include_deleted: None,
             },
             None,
             &ShardSelectorInternal::All,
@@ -252,6 +258,7 @@ async fn test_search_dedup() {
             None,
             &ShardSelectorInternal::All,
             None,
+            &HardwareCounterCell::disabled(),
         )
         .await
         .expect("failed to search");
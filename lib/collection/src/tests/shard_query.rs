@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use common::cpu::CpuBudget;
+use common::hardware_counter::HardwareCounterCell;
 use segment::data_types::vectors::{NamedVectorStruct, Vector, DEFAULT_VECTOR_NAME};
 use segment::types::{PointIdType, WithPayloadInterface, WithVector};
 use tempfile::Builder;
@@ -64,7 +65,12 @@ async fn test_shard_query_rrf_rescoring() {
     };
 
     let sources_scores = shard
-        .query_batch(Arc::new(vec![query]), &current_runtime, None)
+        .query_batch(
+            Arc::new(vec![query]),
+            &current_runtime,
+            None,
+            &HardwareCounterCell::disabled(),
+        )
         .await;
     let expected_error =
         CollectionError::bad_request("cannot apply Fusion without prefetches".to_string());
@@ -98,9 +104,15 @@ async fn test_shard_query_rrf_rescoring() {
     };
 
     let sources_scores = shard
-        .query_batch(Arc::new(vec![query]), &current_runtime, None)
+        .query_batch(
+            Arc::new(vec![query]),
+            &current_runtime,
+            None,
+            &HardwareCounterCell::disabled(),
+        )
         .await
         .unwrap()
+        .value
         .pop()
         .unwrap();
 
@@ -144,9 +156,15 @@ async fn test_shard_query_rrf_rescoring() {
     };
 
     let sources_scores = shard
-        .query_batch(Arc::new(vec![query]), &current_runtime, None)
+        .query_batch(
+            Arc::new(vec![query]),
+            &current_runtime,
+            None,
+            &HardwareCounterCell::disabled(),
+        )
         .await
         .unwrap()
+        .value
         .pop()
         .unwrap();
 
@@ -187,9 +205,15 @@ async fn test_shard_query_rrf_rescoring() {
     };
 
     let sources_scores = shard
-        .query_batch(Arc::new(vec![query]), &current_runtime, None)
+        .query_batch(
+            Arc::new(vec![query]),
+            &current_runtime,
+            None,
+            &HardwareCounterCell::disabled(),
+        )
         .await
         .unwrap()
+        .value
         .pop()
         .unwrap();
 
@@ -264,9 +288,15 @@ async fn test_shard_query_vector_rescoring() {
     };
 
     let sources_scores = shard
-        .query_batch(Arc::new(vec![query]), &current_runtime, None)
+        .query_batch(
+            Arc::new(vec![query]),
+            &current_runtime,
+            None,
+            &HardwareCounterCell::disabled(),
+        )
         .await
         .unwrap()
+        .value
         .pop()
         .unwrap();
 
@@ -290,9 +320,15 @@ async fn test_shard_query_vector_rescoring() {
     };
 
     let sources_scores = shard
-        .query_batch(Arc::new(vec![query]), &current_runtime, None)
+        .query_batch(
+            Arc::new(vec![query]),
+            &current_runtime,
+            None,
+            &HardwareCounterCell::disabled(),
+        )
         .await
         .unwrap()
+        .value
         .pop()
         .unwrap();
 
@@ -319,9 +355,15 @@ async fn test_shard_query_vector_rescoring() {
     };
 
     let sources_scores = shard
-        .query_batch(Arc::new(vec![query]), &current_runtime, None)
+        .query_batch(
+            Arc::new(vec![query]),
+            &current_runtime,
+            None,
+            &HardwareCounterCell::disabled(),
+        )
         .await
         .unwrap()
+        .value
         .pop()
         .unwrap();
 
@@ -385,9 +427,15 @@ async fn test_shard_query_payload_vector() {
     };
 
     let sources_scores = shard
-        .query_batch(Arc::new(vec![query]), &current_runtime, None)
+        .query_batch(
+            Arc::new(vec![query]),
+            &current_runtime,
+            None,
+            &HardwareCounterCell::disabled(),
+        )
         .await
         .unwrap()
+        .value
         .pop()
         .unwrap();
 
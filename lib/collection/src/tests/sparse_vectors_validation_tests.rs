@@ -66,6 +66,7 @@ fn validate_error_sparse_vector_points_batch() {
             payloads: None,
         },
         shard_key: None,
+        precondition: None,
     });
 }
 
@@ -74,6 +75,7 @@ fn validate_error_sparse_vector_points_list() {
     check_validation_error(PointsList {
         points: vec![wrong_point_struct()],
         shard_key: None,
+        precondition: None,
     });
 }
 
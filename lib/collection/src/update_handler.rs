@@ -6,10 +6,12 @@ use std::sync::Arc;
 
 use common::cpu::CpuBudget;
 use common::panic;
+use common::scheduling::{Workload, WorkloadGuard};
 use itertools::Itertools;
 use log::{debug, error, info, trace, warn};
 use parking_lot::Mutex;
 use segment::common::operation_error::OperationResult;
+use segment::entry::entry_point::SegmentEntry as _;
 use segment::index::hnsw_index::num_rayon_threads;
 use segment::types::SeqNumberType;
 use tokio::runtime::Handle;
@@ -20,12 +22,13 @@ use tokio::time::error::Elapsed;
 use tokio::time::{timeout, Duration};
 
 use crate::collection::payload_index_schema::PayloadIndexSchema;
-use crate::collection_manager::collection_updater::CollectionUpdater;
+use crate::collection_manager::collection_updater::{CollectionUpdater, UpdateOutcome};
 use crate::collection_manager::holders::segment_holder::LockedSegmentHolder;
 use crate::collection_manager::optimizers::segment_optimizer::{
     OptimizerThresholds, SegmentOptimizer,
 };
 use crate::collection_manager::optimizers::{Tracker, TrackerLog, TrackerStatus};
+use crate::common::freeze::FreezeState;
 use crate::common::stoppable_task::{spawn_stoppable, StoppableTaskHandle};
 use crate::config::CollectionParams;
 use crate::operations::shared_storage_config::SharedStorageConfig;
@@ -53,7 +56,9 @@ pub struct OperationData {
     /// If operation was requested to wait for result
     pub wait: bool,
     /// Callback notification channel
-    pub sender: Option<oneshot::Sender<CollectionResult<usize>>>,
+    pub sender: Option<oneshot::Sender<CollectionResult<UpdateOutcome>>>,
+    /// Keeps operation counted in the ingestion queue depth, until it is applied
+    pub queued: WorkloadGuard,
 }
 
 /// Signal, used to inform Updater process
@@ -120,6 +125,8 @@ pub struct UpdateHandler {
     shard_path: PathBuf,
     /// Whether we have ever triggered optimizers since starting.
     has_triggered_optimizers: Arc<AtomicBool>,
+    /// Pauses updates of segments, flushes and optimizations for external backups
+    pub(crate) freeze: Arc<FreezeState>,
 }
 
 impl UpdateHandler {
@@ -158,6 +165,7 @@ impl UpdateHandler {
             clocks,
             shard_path,
             has_triggered_optimizers: Default::default(),
+            freeze: Default::default(),
         }
     }
 
@@ -175,12 +183,14 @@ impl UpdateHandler {
             self.max_optimization_threads,
             self.has_triggered_optimizers.clone(),
             self.payload_index_schema.clone(),
+            self.freeze.clone(),
         )));
         self.update_worker = Some(self.runtime_handle.spawn(Self::update_worker_fn(
             update_receiver,
             tx,
             self.wal.clone(),
             self.segments.clone(),
+            self.freeze.clone(),
         )));
         let (flush_tx, flush_rx) = oneshot::channel();
         self.flush_worker = Some(self.runtime_handle.spawn(Self::flush_worker(
@@ -191,6 +201,7 @@ impl UpdateHandler {
             flush_rx,
             self.clocks.clone(),
             self.shard_path.clone(),
+            self.freeze.clone(),
         )));
         self.flush_stop = Some(flush_tx);
     }
@@ -205,7 +216,13 @@ impl UpdateHandler {
 
     /// Gracefully wait before all optimizations stop
     /// If some optimization is in progress - it will be finished before shutdown.
+    ///
+    /// Lifts the freeze, as frozen workers can't apply the pending updates and stop.
     pub async fn wait_workers_stops(&mut self) -> CollectionResult<()> {
+        if self.freeze.unfreeze().await {
+            warn!("Lifting the freeze of the shard to stop update workers");
+        }
+
         let maybe_handle = self.update_worker.take();
         if let Some(handle) = maybe_handle {
             handle.await?;
@@ -233,6 +250,24 @@ impl UpdateHandler {
         Ok(())
     }
 
+    /// Cancel ongoing optimizations and wait until they are stopped.
+    /// Cancelled optimizations are started again by the optimizer worker.
+    pub(crate) async fn stop_optimizations(&self) -> CollectionResult<()> {
+        let stopping_handles = {
+            let mut opt_handles_guard = self.optimization_handles.lock().await;
+            std::mem::take(&mut *opt_handles_guard)
+                .into_iter()
+                .filter_map(|h| h.stop())
+                .collect_vec()
+        };
+
+        for res in stopping_handles {
+            res.await?;
+        }
+
+        Ok(())
+    }
+
     /// Checks if there are any failed operations.
     /// If so - attempts to re-apply all failed operations.
     async fn try_recover(segments: LockedSegmentHolder, wal: LockedWal) -> CollectionResult<usize> {
@@ -318,8 +353,25 @@ impl UpdateHandler {
                     {
                         let segments = segments.clone();
                         move |stopped| {
+                            let _running = Workload::Optimization.enter();
+
+                            // On Linux, use optimizer thread priority (low by default), so we
+                            // interfere less with serving traffic
+                            #[cfg(target_os = "linux")]
+                            if let Err(err) = Workload::Optimization.apply_thread_priority() {
+                                debug!("Failed to set thread priority for optimization, ignoring: {err}");
+                            }
+
                             // Track optimizer status
-                            let tracker = Tracker::start(optimizer.as_ref().name(), nsi.clone());
+                            let num_points = {
+                                let segments = segments.read();
+                                nsi.iter()
+                                    .filter_map(|id| segments.get(*id))
+                                    .map(|segment| segment.get().read().available_point_count())
+                                    .sum()
+                            };
+                            let tracker =
+                                Tracker::start(optimizer.as_ref().name(), nsi.clone(), num_points);
                             let tracker_handle = tracker.handle();
                             optimizers_log.lock().register(tracker);
 
@@ -515,6 +567,7 @@ impl UpdateHandler {
         max_handles: Option<usize>,
         has_triggered_optimizers: Arc<AtomicBool>,
         payload_index_schema: Arc<SaveOnDisk<PayloadIndexSchema>>,
+        freeze: Arc<FreezeState>,
     ) {
         let max_handles = max_handles.unwrap_or(usize::MAX);
         let max_indexing_threads = optimizers
@@ -525,6 +578,9 @@ impl UpdateHandler {
         // Asynchronous task to trigger optimizers once CPU budget is available again
         let mut cpu_available_trigger: Option<JoinHandle<()>> = None;
 
+        // Whether optimizations were skipped, because the shard was frozen
+        let mut skipped_while_frozen = false;
+
         loop {
             let receiver = timeout(OPTIMIZER_CLEANUP_INTERVAL, receiver.recv());
             let result = receiver.await;
@@ -536,9 +592,22 @@ impl UpdateHandler {
                 // Channel closed or stop signal
                 Ok(None | Some(OptimizerSignal::Stop)) => break,
                 // Clean up interval
-                Err(Elapsed { .. }) => continue,
+                Err(Elapsed { .. }) => {
+                    // Resume optimizations, which were skipped during a freeze
+                    if skipped_while_frozen && !freeze.is_frozen().await {
+                        skipped_while_frozen = false;
+                        let _ = sender.try_send(OptimizerSignal::Nop);
+                    }
+                    continue;
+                }
                 // Optimizer signal
                 Ok(Some(signal @ (OptimizerSignal::Nop | OptimizerSignal::Operation(_)))) => {
+                    // Segments must not change while the shard is frozen
+                    let Some(_unfrozen) = freeze.try_unfrozen().await else {
+                        skipped_while_frozen = true;
+                        continue;
+                    };
+
                     has_triggered_optimizers.store(true, Ordering::Relaxed);
 
                     // Ensure we have at least one appendable segment with enough capacity
@@ -622,6 +691,7 @@ impl UpdateHandler {
         optimize_sender: Sender<OptimizerSignal>,
         wal: LockedWal,
         segments: LockedSegmentHolder,
+        freeze: Arc<FreezeState>,
     ) {
         while let Some(signal) = receiver.recv().await {
             match signal {
@@ -630,7 +700,11 @@ impl UpdateHandler {
                     operation,
                     sender,
                     wait,
+                    queued: _queued,
                 }) => {
+                    // Updates stay in the WAL, until the freeze is lifted
+                    let unfrozen = freeze.unfrozen().await;
+
                     let flush_res = if wait {
                         wal.lock().flush().map_err(|err| {
                             CollectionError::service_error(format!(
@@ -644,6 +718,7 @@ impl UpdateHandler {
 
                     let operation_result = flush_res
                         .and_then(|_| CollectionUpdater::update(&segments, op_num, operation));
+                    drop(unfrozen);
 
                     let res = match operation_result {
                         Ok(update_res) => optimize_sender
@@ -700,6 +775,7 @@ impl UpdateHandler {
         mut stop_receiver: oneshot::Receiver<()>,
         clocks: LocalShardClocks,
         shard_path: PathBuf,
+        freeze: Arc<FreezeState>,
     ) {
         loop {
             // Stop flush worker on signal or if sender was dropped
@@ -712,6 +788,13 @@ impl UpdateHandler {
                 }
             }
 
+            // Segments and clock maps must not change, and the WAL must not be truncated, while
+            // the shard is frozen
+            let Some(_unfrozen) = freeze.try_unfrozen().await else {
+                trace!("Skipping flush of frozen shard");
+                continue;
+            };
+
             trace!("Attempting flushing");
             let wal_flash_job = wal.lock().flush_async();
 
@@ -725,6 +808,15 @@ impl UpdateHandler {
                 continue;
             }
 
+            let point_trash = segments.read().point_trash.clone();
+            if let Some(point_trash) = point_trash {
+                match point_trash.purge_expired() {
+                    Ok(0) => {}
+                    Ok(purged) => debug!("Purged {purged} expired points from the trash"),
+                    Err(err) => warn!("Failed to purge expired points from the trash: {err}"),
+                }
+            }
+
             let confirmed_version = Self::flush_segments(segments.clone());
             let confirmed_version = match confirmed_version {
                 Ok(version) => version,
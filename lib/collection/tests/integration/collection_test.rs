@@ -12,8 +12,10 @@ use collection::operations::types::{
 use collection::operations::CollectionUpdateOperations;
 use collection::recommendations::recommend_by;
 use collection::shards::replica_set::{ReplicaSetState, ReplicaState};
+use common::hardware_counter::HardwareCounterCell;
 use itertools::Itertools;
-use segment::data_types::order_by::{Direction, OrderBy};
+use segment::data_types::order_by::{Direction, OrderBy, ThenBy};
+use segment::data_types::search_plan::SearchStrategy;
 use segment::data_types::vectors::{BatchVectorStructInternal, VectorStructInternal};
 use segment::types::{
     Condition, ExtendedPointId, FieldCondition, Filter, HasIdCondition, Payload,
@@ -82,6 +84,7 @@ async fn test_collection_updater_with_shards(shard_number: u32) {
             None,
             &ShardSelectorInternal::All,
             None,
+            &HardwareCounterCell::disabled(),
         )
         .await;
 
@@ -95,6 +98,82 @@ async fn test_collection_updater_with_shards(shard_number: u32) {
     }
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_collection_explain_search() {
+    test_collection_explain_search_with_shards(1).await;
+    test_collection_explain_search_with_shards(N_SHARDS).await;
+}
+
+async fn test_collection_explain_search_with_shards(shard_number: u32) {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+
+    let collection = simple_collection_fixture(collection_dir.path(), shard_number).await;
+
+    let insert_points = CollectionUpdateOperations::PointOperation(
+        Batch {
+            ids: vec![0, 1, 2, 3, 4]
+                .into_iter()
+                .map(|x| x.into())
+                .collect_vec(),
+            vectors: BatchVectorStructInternal::from(vec![
+                vec![1.0, 0.0, 1.0, 1.0],
+                vec![1.0, 0.0, 1.0, 0.0],
+                vec![1.0, 1.0, 1.0, 1.0],
+                vec![1.0, 1.0, 0.0, 1.0],
+                vec![1.0, 0.0, 0.0, 0.0],
+            ])
+            .into(),
+            payloads: None,
+        }
+        .into(),
+    );
+
+    collection
+        .update_from_client_simple(insert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    let search_request = SearchRequestInternal {
+        vector: vec![1.0, 1.0, 1.0, 1.0].into(),
+        with_payload: None,
+        with_vector: None,
+        filter: Some(Filter::new_must(Condition::HasId(HasIdCondition {
+            has_id: HashSet::from([1.into(), 2.into(), 3.into()]),
+        }))),
+        params: None,
+        limit: 2,
+        offset: None,
+        score_threshold: None,
+    };
+
+    let (points, shards) = collection
+        .explain_search(search_request.into(), &ShardSelectorInternal::All, None)
+        .await
+        .unwrap();
+
+    assert_eq!(points.len(), 2);
+    assert_eq!(points[0].id, 2.into());
+
+    assert_eq!(shards.len(), shard_number as usize);
+
+    let vector_plans = shards
+        .iter()
+        .flat_map(|shard| shard.segments.as_ref().expect("shard is local"))
+        .flat_map(|segment| &segment.plans)
+        .collect_vec();
+
+    assert!(!vector_plans.is_empty());
+    for plan in &vector_plans {
+        assert_eq!(plan.strategy, SearchStrategy::FilteredScan);
+    }
+
+    let matched_points: usize = vector_plans
+        .iter()
+        .filter_map(|plan| plan.actual_cardinality)
+        .sum();
+    assert_eq!(matched_points, 3);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_collection_search_with_payload_and_vector() {
     test_collection_search_with_payload_and_vector_with_shards(1).await;
@@ -150,6 +229,7 @@ async fn test_collection_search_with_payload_and_vector_with_shards(shard_number
             None,
             &ShardSelectorInternal::All,
             None,
+            &HardwareCounterCell::disabled(),
         )
         .await;
 
@@ -421,6 +501,8 @@ async fn test_read_api_with_shards(shard_number: u32) {
                 with_payload: Some(WithPayloadInterface::Bool(true)),
                 with_vector: false.into(),
                 order_by: None,
+                cursor: None,
+                include_deleted: None,
             },
             None,
             &ShardSelectorInternal::All,
@@ -445,6 +527,7 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
     const PRICE_FLOAT_KEY: &str = "price_float";
     const PRICE_INT_KEY: &str = "price_int";
     const MULTI_VALUE_KEY: &str = "multi_value";
+    const RANK_KEY: &str = "rank";
 
     let get_payload = |value: f64| -> Option<Payload> {
         let mut payload_map = Map::new();
@@ -473,6 +556,15 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
         get_payload(2.0),
         get_payload(1.0),
     ];
+    let payloads = payloads
+        .into_iter()
+        .zip(0..)
+        .map(|(payload, id): (Option<Payload>, i64)| {
+            let mut payload = payload.unwrap();
+            payload.0.insert(RANK_KEY.to_string(), (id % 3).into());
+            Some(payload)
+        })
+        .collect_vec();
 
     let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
         Batch {
@@ -534,6 +626,15 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
         .await
         .unwrap();
 
+    collection
+        .create_payload_index_with_wait(
+            RANK_KEY.parse().unwrap(),
+            PayloadFieldSchema::FieldType(PayloadSchemaType::Integer),
+            true,
+        )
+        .await
+        .unwrap();
+
     ///////// Test single-valued fields ///////////
     for key in [PRICE_FLOAT_KEY, PRICE_INT_KEY].into_iter() {
         let result_asc = collection
@@ -548,7 +649,13 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
                         key: key.parse().unwrap(),
                         direction: Some(Direction::Asc),
                         start_from: None,
+                        geo: None,
+                        then_by: None,
+                        start_after_id: None,
+                        start_after_then_by: Vec::new(),
                     })),
+                    cursor: None,
+                    include_deleted: None,
                 },
                 None,
                 &ShardSelectorInternal::All,
@@ -578,7 +685,13 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
                         key: key.parse().unwrap(),
                         direction: Some(Direction::Desc),
                         start_from: None,
+                        geo: None,
+                        then_by: None,
+                        start_after_id: None,
+                        start_after_then_by: Vec::new(),
                     })),
+                    cursor: None,
+                    include_deleted: None,
                 },
                 None,
                 &ShardSelectorInternal::All,
@@ -617,7 +730,13 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
                         key: key.parse().unwrap(),
                         direction: Some(Direction::Asc),
                         start_from: None,
+                        geo: None,
+                        then_by: None,
+                        start_after_id: None,
+                        start_after_then_by: Vec::new(),
                     })),
+                    cursor: None,
+                    include_deleted: None,
                 },
                 None,
                 &ShardSelectorInternal::All,
@@ -655,7 +774,13 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
                         key: key.parse().unwrap(),
                         direction: Some(Direction::Desc),
                         start_from: None,
+                        geo: None,
+                        then_by: None,
+                        start_after_id: None,
+                        start_after_then_by: Vec::new(),
                     })),
+                    cursor: None,
+                    include_deleted: None,
                 },
                 None,
                 &ShardSelectorInternal::All,
@@ -681,6 +806,43 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
             valid_desc_second_page_points,
             desc_second_page_points
         );
+
+        // Paginate through all points with a cursor, points with equal values are ordered by id
+        let mut cursor = None;
+        let mut cursor_pages_points = Vec::new();
+        loop {
+            let page = collection
+                .scroll_by(
+                    ScrollRequestInternal {
+                        offset: None,
+                        limit: Some(3),
+                        filter: None,
+                        with_payload: Some(WithPayloadInterface::Bool(false)),
+                        with_vector: false.into(),
+                        order_by: Some(OrderByInterface::Key(key.parse().unwrap())),
+                        cursor: cursor.take(),
+                        include_deleted: None,
+                    },
+                    None,
+                    &ShardSelectorInternal::All,
+                )
+                .await
+                .unwrap();
+
+            cursor_pages_points.extend(page.points.iter().map(|point| point.id));
+
+            match page.next_page_cursor {
+                Some(next_page_cursor) => cursor = Some(next_page_cursor),
+                None => break,
+            }
+        }
+
+        let expected_points = [13, 12, 11, 10, 6, 7, 8, 9, 5, 4, 3, 2, 1, 0]
+            .into_iter()
+            .map(|x| x.into())
+            .collect::<Vec<ExtendedPointId>>();
+
+        assert_eq!(cursor_pages_points, expected_points);
     }
 
     ///////// Test multi-valued field ///////////
@@ -693,6 +855,8 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
                 with_payload: Some(WithPayloadInterface::Bool(true)),
                 with_vector: false.into(),
                 order_by: Some(OrderByInterface::Key(MULTI_VALUE_KEY.parse().unwrap())),
+                cursor: None,
+                include_deleted: None,
             },
             None,
             &ShardSelectorInternal::All,
@@ -713,6 +877,57 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
         })
         .values()
         .all(|&x| x == 2));
+
+    ///////// Test ordering by multiple keys ///////////
+    let order_by = OrderBy {
+        key: PRICE_INT_KEY.parse().unwrap(),
+        direction: Some(Direction::Asc),
+        start_from: None,
+        geo: None,
+        then_by: Some(vec![ThenBy {
+            key: RANK_KEY.parse().unwrap(),
+            direction: Some(Direction::Desc),
+        }]),
+        start_after_id: None,
+        start_after_then_by: Vec::new(),
+    };
+
+    let mut cursor = None;
+    let mut cursor_pages_points = Vec::new();
+    loop {
+        let page = collection
+            .scroll_by(
+                ScrollRequestInternal {
+                    offset: None,
+                    limit: Some(3),
+                    filter: None,
+                    with_payload: Some(WithPayloadInterface::Bool(false)),
+                    with_vector: false.into(),
+                    order_by: Some(OrderByInterface::Struct(order_by.clone())),
+                    cursor: cursor.take(),
+                    include_deleted: None,
+                },
+                None,
+                &ShardSelectorInternal::All,
+            )
+            .await
+            .unwrap();
+
+        cursor_pages_points.extend(page.points.iter().map(|point| point.id));
+
+        match page.next_page_cursor {
+            Some(next_page_cursor) => cursor = Some(next_page_cursor),
+            None => break,
+        }
+    }
+
+    // Points with price 5 are ordered by rank descending, then by id
+    let expected_points = [13, 12, 11, 10, 8, 7, 6, 9, 5, 4, 3, 2, 1, 0]
+        .into_iter()
+        .map(|x| x.into())
+        .collect::<Vec<ExtendedPointId>>();
+
+    assert_eq!(cursor_pages_points, expected_points);
 }
 
 #[tokio::test(flavor = "multi_thread")]
@@ -785,6 +1000,8 @@ async fn test_collection_delete_points_by_filter_with_shards(shard_number: u32)
                 with_payload: Some(WithPayloadInterface::Bool(false)),
                 with_vector: false.into(),
                 order_by: None,
+                cursor: None,
+                include_deleted: None,
             },
             None,
             &ShardSelectorInternal::All,
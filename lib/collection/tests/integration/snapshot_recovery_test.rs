@@ -15,6 +15,7 @@ use collection::shards::channel_service::ChannelService;
 use collection::shards::collection_shard_distribution::CollectionShardDistribution;
 use collection::shards::replica_set::ReplicaState;
 use common::cpu::CpuBudget;
+use common::hardware_counter::HardwareCounterCell;
 use segment::data_types::vectors::VectorStructInternal;
 use segment::types::{Distance, WithPayloadInterface, WithVector};
 use tempfile::Builder;
@@ -41,6 +42,7 @@ async fn _test_snapshot_and_recover_collection(node_type: NodeType) {
         wal_config,
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
+        strict_mode_config: None,
     };
 
     let snapshots_path = Builder::new().prefix("test_snapshots").tempdir().unwrap();
@@ -160,6 +162,7 @@ async fn _test_snapshot_and_recover_collection(node_type: NodeType) {
             None,
             &ShardSelectorInternal::All,
             None,
+            &HardwareCounterCell::disabled(),
         )
         .await
         .unwrap();
@@ -170,6 +173,7 @@ async fn _test_snapshot_and_recover_collection(node_type: NodeType) {
             None,
             &ShardSelectorInternal::All,
             None,
+            &HardwareCounterCell::disabled(),
         )
         .await
         .unwrap();
@@ -233,7 +233,7 @@ pub fn linux_high_thread_priority() -> Result<(), ThreadPriorityError> {
 /// - <https://linux.die.net/man/7/pthreads>
 /// - <https://linux.die.net/man/2/setpriority>
 #[cfg(target_os = "linux")]
-fn set_linux_thread_priority(priority: u8) -> Result<(), ThreadPriorityError> {
+pub(crate) fn set_linux_thread_priority(priority: u8) -> Result<(), ThreadPriorityError> {
     let new_priority = ThreadPriority::Crossplatform(
         ThreadPriorityValue::try_from(priority).map_err(ThreadPriorityError::ParseNice)?,
     );
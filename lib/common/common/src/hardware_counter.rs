@@ -0,0 +1,128 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+/// Hardware resources, spent on processing a single request
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HardwareUsage {
+    /// Number of vectors, which were scored against the query
+    pub cpu: usize,
+    /// Number of payload bytes, which were read
+    pub payload_io_read: usize,
+    /// Number of vector bytes, which were read
+    pub vector_io_read: usize,
+}
+
+impl HardwareUsage {
+    pub fn merge(&mut self, other: &HardwareUsage) {
+        self.cpu += other.cpu;
+        self.payload_io_read += other.payload_io_read;
+        self.vector_io_read += other.vector_io_read;
+    }
+}
+
+#[derive(Debug, Default)]
+struct HardwareCounters {
+    cpu: AtomicUsize,
+    payload_io_read: AtomicUsize,
+    vector_io_read: AtomicUsize,
+}
+
+/// Accumulates hardware usage of a request, while it is processed by multiple shards and segments.
+///
+/// Clones share the same counters. A disabled cell ignores all measurements,
+/// so it can be passed around unconditionally when usage reporting is not requested.
+#[derive(Debug, Default, Clone)]
+pub struct HardwareCounterCell {
+    counters: Option<Arc<HardwareCounters>>,
+}
+
+impl HardwareCounterCell {
+    pub fn new() -> Self {
+        Self {
+            counters: Some(Arc::default()),
+        }
+    }
+
+    pub const fn disabled() -> Self {
+        Self { counters: None }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.counters.is_some()
+    }
+
+    pub fn incr_cpu(&self, count: usize) {
+        if let Some(counters) = &self.counters {
+            counters.cpu.fetch_add(count, Ordering::Relaxed);
+        }
+    }
+
+    pub fn incr_payload_io_read(&self, bytes: usize) {
+        if let Some(counters) = &self.counters {
+            counters.payload_io_read.fetch_add(bytes, Ordering::Relaxed);
+        }
+    }
+
+    pub fn incr_vector_io_read(&self, bytes: usize) {
+        if let Some(counters) = &self.counters {
+            counters.vector_io_read.fetch_add(bytes, Ordering::Relaxed);
+        }
+    }
+
+    /// Account for `count` vectors of `vector_size` bytes each, scored against the query
+    pub fn incr_scored_vectors(&self, count: usize, vector_size: usize) {
+        self.incr_cpu(count);
+        self.incr_vector_io_read(count * vector_size);
+    }
+
+    /// Add usage, measured elsewhere. For example, on a remote peer.
+    pub fn add_usage(&self, usage: &HardwareUsage) {
+        self.incr_cpu(usage.cpu);
+        self.incr_payload_io_read(usage.payload_io_read);
+        self.incr_vector_io_read(usage.vector_io_read);
+    }
+
+    /// Measured usage, `None` if the cell is disabled
+    pub fn usage(&self) -> Option<HardwareUsage> {
+        self.counters.as_ref().map(|counters| HardwareUsage {
+            cpu: counters.cpu.load(Ordering::Relaxed),
+            payload_io_read: counters.payload_io_read.load(Ordering::Relaxed),
+            vector_io_read: counters.vector_io_read.load(Ordering::Relaxed),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hardware_counter_cell() {
+        let counter = HardwareCounterCell::new();
+        let clone = counter.clone();
+
+        counter.incr_scored_vectors(10, 16);
+        clone.incr_payload_io_read(100);
+        clone.add_usage(&HardwareUsage {
+            cpu: 1,
+            payload_io_read: 2,
+            vector_io_read: 3,
+        });
+
+        assert_eq!(
+            counter.usage(),
+            Some(HardwareUsage {
+                cpu: 11,
+                payload_io_read: 102,
+                vector_io_read: 163,
+            }),
+        );
+
+        let disabled = HardwareCounterCell::disabled();
+        disabled.incr_scored_vectors(10, 16);
+        assert!(!disabled.is_enabled());
+        assert_eq!(disabled.usage(), None);
+    }
+}
@@ -0,0 +1,176 @@
+//! Disk throughput budgets of background work.
+//!
+//! Segment rebuilds, snapshots and shard transfers read and write large amounts of data from the
+//! same disks, which serve searches. Each kind of background IO may get a limit of bytes per
+//! second, enforced by wrapping its readers and writers into [`ThrottledReader`] and
+//! [`ThrottledWriter`], or by calling [`BackgroundIo::throttle`] for IO done by other means.
+
+use std::io::{self, Read, Write};
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+
+use crate::rate_limiting::RateLimiter;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BackgroundIo {
+    /// Writes of segments, built by optimizers
+    Optimization,
+    /// Writes of segment and collection snapshot archives
+    Snapshot,
+    /// Reads of points, sent to other peers by shard transfers
+    ShardTransfer,
+}
+
+static LIMITERS: [Mutex<Option<RateLimiter>>; 3] =
+    [Mutex::new(None), Mutex::new(None), Mutex::new(None)];
+
+impl BackgroundIo {
+    fn index(self) -> usize {
+        match self {
+            BackgroundIo::Optimization => 0,
+            BackgroundIo::Snapshot => 1,
+            BackgroundIo::ShardTransfer => 2,
+        }
+    }
+
+    /// Limit throughput of this kind of IO, `None` removes the limit.
+    ///
+    /// Up to one second worth of IO can be done at once after a pause.
+    pub fn set_limit(self, bytes_per_sec: Option<u64>) {
+        let limiter = bytes_per_sec
+            .filter(|&rate| rate > 0)
+            .map(|rate| RateLimiter::new(rate as f64, rate as f64));
+        *LIMITERS[self.index()]
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = limiter;
+    }
+
+    pub fn is_limited(self) -> bool {
+        LIMITERS[self.index()]
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .is_some()
+    }
+
+    /// Take as many of `bytes` from the budget as currently available.
+    ///
+    /// Returns number of bytes left, and time to wait before taking them.
+    fn take(self, bytes: usize) -> Option<(usize, Duration)> {
+        let mut limiter = LIMITERS[self.index()]
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let limiter = limiter.as_mut()?;
+
+        let mut remaining = bytes as f64;
+        while remaining > 0.0 {
+            // Larger chunks would never fit into the bucket
+            let chunk = remaining.min(limiter.capacity());
+            match limiter.try_consume(chunk) {
+                Ok(()) => remaining -= chunk,
+                Err(wait) => return Some((remaining as usize, wait)),
+            }
+        }
+        None
+    }
+
+    /// Block current thread until IO of `bytes` fits into the budget.
+    pub fn throttle(self, bytes: usize) {
+        let mut remaining = bytes;
+        while let Some((left, wait)) = self.take(remaining) {
+            std::thread::sleep(wait);
+            remaining = left;
+        }
+    }
+
+    /// Wait until IO of `bytes` fits into the budget, without blocking the async runtime.
+    pub async fn throttle_async(self, bytes: usize) {
+        let mut remaining = bytes;
+        while let Some((left, wait)) = self.take(remaining) {
+            tokio::time::sleep(wait).await;
+            remaining = left;
+        }
+    }
+}
+
+/// Writer, which blocks when background IO exceeds its throughput budget
+pub struct ThrottledWriter<W> {
+    inner: W,
+    io: BackgroundIo,
+}
+
+impl<W: Write> ThrottledWriter<W> {
+    pub fn new(inner: W, io: BackgroundIo) -> Self {
+        Self { inner, io }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for ThrottledWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.io.throttle(written);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Reader, which blocks when background IO exceeds its throughput budget
+pub struct ThrottledReader<R> {
+    inner: R,
+    io: BackgroundIo,
+}
+
+impl<R: Read> ThrottledReader<R> {
+    pub fn new(inner: R, io: BackgroundIo) -> Self {
+        Self { inner, io }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for ThrottledReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.io.throttle(read);
+        Ok(read)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+
+    #[test]
+    fn test_throttled_writer() {
+        // Limits are global, so only one kind of IO is used in tests
+        let io = BackgroundIo::Snapshot;
+
+        let mut writer = ThrottledWriter::new(Vec::new(), io);
+        writer.write_all(&[0; 4096]).unwrap();
+        assert_eq!(writer.into_inner().len(), 4096);
+
+        io.set_limit(Some(1000));
+        assert!(io.is_limited());
+
+        // The first second worth of bytes passes at once, the rest takes 0.5 seconds
+        let start = Instant::now();
+        let mut reader = ThrottledReader::new(&[0u8; 1500][..], io);
+        let mut read = Vec::new();
+        reader.read_to_end(&mut read).unwrap();
+        assert_eq!(read.len(), 1500);
+        assert!(start.elapsed() >= Duration::from_millis(400));
+
+        io.set_limit(None);
+        assert!(!io.is_limited());
+    }
+}
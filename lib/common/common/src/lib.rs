@@ -1,8 +1,12 @@
 pub mod cpu;
 pub mod defaults;
 pub mod fixed_length_priority_queue;
+pub mod hardware_counter;
+pub mod io_throttling;
 pub mod math;
 pub mod panic;
+pub mod rate_limiting;
+pub mod scheduling;
 pub mod top_k;
 pub mod types;
 pub mod validation;
@@ -0,0 +1,84 @@
+use std::time::{Duration, Instant};
+
+/// Token bucket rate limiter.
+///
+/// The bucket holds up to `capacity` tokens and is refilled with `rate` tokens per second.
+/// Each operation takes some tokens, operations are rejected while the bucket is empty.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    capacity: f64,
+    rate: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    /// Create a full bucket, refilled with `rate` tokens per second
+    pub fn new(rate: f64, capacity: f64) -> Self {
+        Self {
+            capacity,
+            rate,
+            tokens: capacity,
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Max number of tokens, which can be taken at once
+    pub fn capacity(&self) -> f64 {
+        self.capacity
+    }
+
+    /// Try to take `tokens` from the bucket.
+    ///
+    /// If there are not enough tokens, nothing is taken and the time to wait
+    /// until the bucket is refilled enough is returned.
+    pub fn try_consume(&mut self, tokens: f64) -> Result<(), Duration> {
+        self.try_consume_at(tokens, Instant::now())
+    }
+
+    fn try_consume_at(&mut self, tokens: f64, now: Instant) -> Result<(), Duration> {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        self.tokens = (self.tokens + elapsed.as_secs_f64() * self.rate).min(self.capacity);
+        self.last_refill = now;
+
+        if self.tokens >= tokens {
+            self.tokens -= tokens;
+            return Ok(());
+        }
+
+        let missing = tokens - self.tokens;
+        Err(Duration::from_secs_f64(missing / self.rate))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limiter_refill() {
+        let mut limiter = RateLimiter::new(10.0, 2.0);
+        let start = limiter.last_refill;
+
+        assert!(limiter.try_consume_at(1.0, start).is_ok());
+        assert!(limiter.try_consume_at(1.0, start).is_ok());
+
+        let retry_after = limiter.try_consume_at(1.0, start).unwrap_err();
+        assert_eq!(retry_after, Duration::from_millis(100));
+
+        // Half of the required token is refilled
+        let retry_after = limiter
+            .try_consume_at(1.0, start + Duration::from_millis(50))
+            .unwrap_err();
+        assert_eq!(retry_after, Duration::from_millis(50));
+
+        assert!(limiter
+            .try_consume_at(1.0, start + Duration::from_millis(100))
+            .is_ok());
+
+        // Bucket never holds more than its capacity
+        let later = start + Duration::from_secs(60);
+        assert!(limiter.try_consume_at(2.0, later).is_ok());
+        assert!(limiter.try_consume_at(1.0, later).is_err());
+    }
+}
@@ -0,0 +1,154 @@
+//! Scheduling of CPU between search, optimization and ingestion workloads.
+//!
+//! Each workload runs on its own threads: search on the search runtime, ingestion on the update
+//! runtime and optimizations on dedicated blocking threads and index building pools. Threads of
+//! each workload get a configurable priority, so heavy optimizations can not starve searches.
+//! Number of tasks queued or running in each workload is tracked to be reported in telemetry.
+
+use std::sync::atomic::{AtomicU8, AtomicUsize, Ordering};
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(target_os = "linux")]
+use crate::cpu::{set_linux_thread_priority, ThreadPriorityError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Workload {
+    /// Searches in segments, including searches done by recommend, discover and query requests
+    Search,
+    /// Segment optimizations and index building
+    Optimization,
+    /// Updates of points and payloads
+    Ingestion,
+}
+
+/// Priority of threads, running a workload.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkloadPriority {
+    /// Nice value of 10
+    Low,
+    /// Nice value of 0
+    #[default]
+    Normal,
+    /// Nice value of -10, requires special privileges
+    High,
+}
+
+impl WorkloadPriority {
+    /// Cross-platform priority in percents, 50% of which correspond to the nice value of 0
+    fn percents(self) -> u8 {
+        match self {
+            WorkloadPriority::Low => 25,
+            WorkloadPriority::Normal => 50,
+            WorkloadPriority::High => 75,
+        }
+    }
+
+    fn from_percents(percents: u8) -> Self {
+        match percents {
+            25 => WorkloadPriority::Low,
+            75 => WorkloadPriority::High,
+            _ => WorkloadPriority::Normal,
+        }
+    }
+}
+
+static PRIORITIES: [AtomicU8; 3] = [
+    AtomicU8::new(50),
+    // Optimizations used to run with low priority before it became configurable
+    AtomicU8::new(25),
+    AtomicU8::new(50),
+];
+
+static QUEUE_DEPTHS: [AtomicUsize; 3] = [
+    AtomicUsize::new(0),
+    AtomicUsize::new(0),
+    AtomicUsize::new(0),
+];
+
+impl Workload {
+    fn index(self) -> usize {
+        match self {
+            Workload::Search => 0,
+            Workload::Optimization => 1,
+            Workload::Ingestion => 2,
+        }
+    }
+
+    /// Set priority for threads of this workload, started after this call.
+    pub fn set_priority(self, priority: WorkloadPriority) {
+        PRIORITIES[self.index()].store(priority.percents(), Ordering::Relaxed);
+    }
+
+    pub fn priority(self) -> WorkloadPriority {
+        WorkloadPriority::from_percents(PRIORITIES[self.index()].load(Ordering::Relaxed))
+    }
+
+    /// On Linux, apply configured priority of this workload to the current thread.
+    ///
+    /// Raising priority above normal is likely to fail without special privileges, so it is
+    /// recommended to soft-fail.
+    #[cfg(target_os = "linux")]
+    pub fn apply_thread_priority(self) -> Result<(), ThreadPriorityError> {
+        set_linux_thread_priority(self.priority().percents())
+    }
+
+    /// Register a task of this workload, which is queued or running until the guard is dropped.
+    pub fn enter(self) -> WorkloadGuard {
+        QUEUE_DEPTHS[self.index()].fetch_add(1, Ordering::Relaxed);
+        WorkloadGuard { workload: self }
+    }
+
+    /// Number of tasks of this workload, which are currently queued or running.
+    pub fn queue_depth(self) -> usize {
+        QUEUE_DEPTHS[self.index()].load(Ordering::Relaxed)
+    }
+}
+
+/// Keeps a task counted in the queue depth of its workload.
+#[derive(Debug)]
+pub struct WorkloadGuard {
+    workload: Workload,
+}
+
+impl Drop for WorkloadGuard {
+    fn drop(&mut self) {
+        QUEUE_DEPTHS[self.workload.index()].fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_queue_depth() {
+        // Counters are global, compare with the depth before the test
+        let before = Workload::Ingestion.queue_depth();
+
+        let first = Workload::Ingestion.enter();
+        let second = Workload::Ingestion.enter();
+        assert_eq!(Workload::Ingestion.queue_depth(), before + 2);
+
+        drop(first);
+        assert_eq!(Workload::Ingestion.queue_depth(), before + 1);
+        drop(second);
+        assert_eq!(Workload::Ingestion.queue_depth(), before);
+    }
+
+    #[test]
+    fn test_priority_roundtrip() {
+        for priority in [
+            WorkloadPriority::Low,
+            WorkloadPriority::Normal,
+            WorkloadPriority::High,
+        ] {
+            assert_eq!(
+                WorkloadPriority::from_percents(priority.percents()),
+                priority
+            );
+        }
+        assert_eq!(Workload::Optimization.priority(), WorkloadPriority::Low);
+    }
+}
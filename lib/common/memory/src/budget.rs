@@ -0,0 +1,212 @@
+//! Node-level accounting of memory, used by in-RAM data structures, against a global budget.
+//!
+//! Data structures, which may live either in RAM or on disk, reserve memory from the budget
+//! before being loaded into RAM. If the budget is exhausted, they are demoted to memory-mapped
+//! files instead, and optional work, like prefaulting of memmaps or caching, is skipped.
+//!
+//! See [`set_global_limit`] and [`global`].
+
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Global memory budget, unlimited by default
+static BUDGET: MemoryBudget = MemoryBudget::new();
+
+/// Set limit of the global memory budget in bytes. `None` means unlimited.
+///
+/// It is recommended to set the limit before loading any segment, reservations made before are
+/// kept regardless of the new limit.
+pub fn set_global_limit(limit_bytes: Option<usize>) {
+    BUDGET
+        .limit
+        .store(limit_bytes.unwrap_or(0), Ordering::Relaxed);
+}
+
+/// Get global memory budget.
+pub fn global() -> &'static MemoryBudget {
+    &BUDGET
+}
+
+/// What kind of data the memory is used for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MemoryCategory {
+    /// In-RAM vector indexes, like HNSW graph links
+    Index,
+    /// In-RAM quantized vectors
+    Quantization,
+    /// Optional caches, like filter results
+    Cache,
+    /// Temporary data of search requests
+    Scratch,
+}
+
+impl MemoryCategory {
+    pub const ALL: [MemoryCategory; 4] = [
+        MemoryCategory::Index,
+        MemoryCategory::Quantization,
+        MemoryCategory::Cache,
+        MemoryCategory::Scratch,
+    ];
+
+    fn index(self) -> usize {
+        match self {
+            MemoryCategory::Index => 0,
+            MemoryCategory::Quantization => 1,
+            MemoryCategory::Cache => 2,
+            MemoryCategory::Scratch => 3,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct MemoryBudget {
+    /// Limit in bytes, 0 means unlimited
+    limit: AtomicUsize,
+    used: [AtomicUsize; 4],
+}
+
+impl MemoryBudget {
+    const fn new() -> Self {
+        Self {
+            limit: AtomicUsize::new(0),
+            used: [
+                AtomicUsize::new(0),
+                AtomicUsize::new(0),
+                AtomicUsize::new(0),
+                AtomicUsize::new(0),
+            ],
+        }
+    }
+
+    /// Limit of the budget in bytes, `None` if unlimited
+    pub fn limit(&self) -> Option<usize> {
+        Some(self.limit.load(Ordering::Relaxed)).filter(|&limit| limit > 0)
+    }
+
+    /// Bytes, currently reserved for the given category
+    pub fn used(&self, category: MemoryCategory) -> usize {
+        self.used[category.index()].load(Ordering::Relaxed)
+    }
+
+    /// Bytes, currently reserved in all categories
+    pub fn total_used(&self) -> usize {
+        MemoryCategory::ALL
+            .iter()
+            .map(|&category| self.used(category))
+            .sum()
+    }
+
+    /// Whether reservations exceed the limit, so optional memory usage should be avoided
+    pub fn is_exhausted(&self) -> bool {
+        self.limit()
+            .map_or(false, |limit| self.total_used() >= limit)
+    }
+
+    /// Reserve memory, if it fits into the budget.
+    ///
+    /// Returns `None` if the budget is exhausted, then the caller is expected to demote its data
+    /// to disk, or to avoid using the memory at all.
+    pub fn try_reserve(
+        &'static self,
+        category: MemoryCategory,
+        bytes: usize,
+    ) -> Option<MemoryReservation> {
+        let used = &self.used[category.index()];
+        let previous = used.fetch_add(bytes, Ordering::Relaxed);
+
+        if let Some(limit) = self.limit() {
+            if bytes > 0 && self.total_used() > limit {
+                used.fetch_sub(bytes, Ordering::Relaxed);
+                log::debug!(
+                    "Memory budget of {limit} bytes is exhausted, can't reserve {bytes} bytes for {category:?} (used {previous} for {category:?})"
+                );
+                return None;
+            }
+        }
+
+        Some(MemoryReservation {
+            budget: self,
+            category,
+            bytes,
+        })
+    }
+
+    /// Reserve memory to load the file at `path` into RAM, see [`MemoryBudget::try_reserve`].
+    ///
+    /// Missing file doesn't take memory.
+    pub fn try_reserve_file(
+        &'static self,
+        category: MemoryCategory,
+        path: &Path,
+    ) -> Option<MemoryReservation> {
+        let bytes = fs::metadata(path).map_or(0, |metadata| metadata.len() as usize);
+        self.try_reserve(category, bytes)
+    }
+
+    /// Account memory, which is used regardless of the budget.
+    ///
+    /// The reservation still counts towards the limit, demoting other data.
+    pub fn reserve(&'static self, category: MemoryCategory, bytes: usize) -> MemoryReservation {
+        self.used[category.index()].fetch_add(bytes, Ordering::Relaxed);
+        MemoryReservation {
+            budget: self,
+            category,
+            bytes,
+        }
+    }
+}
+
+/// Memory, reserved from the [`MemoryBudget`], given back when dropped
+#[derive(Debug)]
+pub struct MemoryReservation {
+    budget: &'static MemoryBudget,
+    category: MemoryCategory,
+    bytes: usize,
+}
+
+impl MemoryReservation {
+    pub fn bytes(&self) -> usize {
+        self.bytes
+    }
+}
+
+impl Drop for MemoryReservation {
+    fn drop(&mut self) {
+        self.budget.used[self.category.index()].fetch_sub(self.bytes, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_budget() {
+        static TEST_BUDGET: MemoryBudget = MemoryBudget::new();
+        TEST_BUDGET.limit.store(100, Ordering::Relaxed);
+
+        let index = TEST_BUDGET.try_reserve(MemoryCategory::Index, 60).unwrap();
+        assert!(TEST_BUDGET
+            .try_reserve(MemoryCategory::Quantization, 50)
+            .is_none());
+        assert_eq!(TEST_BUDGET.used(MemoryCategory::Quantization), 0);
+
+        let scratch = TEST_BUDGET.reserve(MemoryCategory::Scratch, 50);
+        assert!(TEST_BUDGET.is_exhausted());
+        assert!(TEST_BUDGET.try_reserve(MemoryCategory::Cache, 1).is_none());
+
+        drop(scratch);
+        drop(index);
+        assert_eq!(TEST_BUDGET.total_used(), 0);
+
+        assert!(TEST_BUDGET
+            .try_reserve(MemoryCategory::Index, 200)
+            .is_none());
+        let index = TEST_BUDGET.try_reserve(MemoryCategory::Index, 100).unwrap();
+        assert_eq!(index.bytes(), 100);
+
+        // Empty reservations always succeed
+        assert!(TEST_BUDGET.try_reserve(MemoryCategory::Cache, 0).is_some());
+    }
+}
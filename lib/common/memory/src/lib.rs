@@ -1,2 +1,3 @@
+pub mod budget;
 pub mod madvise;
 pub mod mmap_ops;
@@ -68,6 +68,11 @@ impl PrefaultMmapPages {
     pub fn exec(&self) {
         prefault_mmap_pages(self.mmap.as_ref(), self.path.as_deref());
     }
+
+    /// File of the mmap, if known
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
 }
 
 fn prefault_mmap_pages<T>(mmap: &T, path: Option<&Path>)
@@ -52,6 +52,8 @@ fn multi_vector_search_benchmark(c: &mut Criterion) {
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_columns: Default::default(),
+        payload_compression: Default::default(),
     };
 
     let mut segment = build_segment(segment_dir.path(), &segment_config, true).unwrap();
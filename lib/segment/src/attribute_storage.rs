@@ -0,0 +1,329 @@
+//! Dense columns of scalar attributes of points.
+//!
+//! Attributes are typed numbers, which are stored outside of the payload, in one array per
+//! attribute indexed by the internal point id. Filtering and rescoring by an attribute reads a
+//! single array element, without reading or parsing the payload of the point.
+//! Columns are kept in RAM, and written into a single file of the segment on flush.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use bitvec::prelude::BitVec;
+use common::types::PointOffsetType;
+use io::file_operations::{atomic_save_bin, read_bin};
+use serde::{Deserialize, Serialize};
+
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::common::Flusher;
+use crate::types::{AttributeType, AttributeValue};
+
+const ATTRIBUTES_FILE: &str = "attributes.dat";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum ColumnValues {
+    Float(Vec<f32>),
+    Integer(Vec<i64>),
+}
+
+impl ColumnValues {
+    fn new(attribute_type: AttributeType) -> Self {
+        match attribute_type {
+            AttributeType::Float => ColumnValues::Float(Vec::new()),
+            AttributeType::Integer => ColumnValues::Integer(Vec::new()),
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            ColumnValues::Float(values) => values.len(),
+            ColumnValues::Integer(values) => values.len(),
+        }
+    }
+}
+
+/// Values of a single attribute of all points
+#[derive(Debug, Clone)]
+pub struct AttributeColumn {
+    values: ColumnValues,
+    /// Points, which have a value
+    present: BitVec,
+    count: usize,
+}
+
+impl AttributeColumn {
+    fn new(attribute_type: AttributeType) -> Self {
+        Self {
+            values: ColumnValues::new(attribute_type),
+            present: BitVec::new(),
+            count: 0,
+        }
+    }
+
+    pub fn attribute_type(&self) -> AttributeType {
+        match self.values {
+            ColumnValues::Float(_) => AttributeType::Float,
+            ColumnValues::Integer(_) => AttributeType::Integer,
+        }
+    }
+
+    /// Number of points, which have a value
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn get(&self, point_id: PointOffsetType) -> Option<AttributeValue> {
+        let idx = point_id as usize;
+        if !self.present.get(idx).is_some_and(|present| *present) {
+            return None;
+        }
+        match &self.values {
+            ColumnValues::Float(values) => Some(AttributeValue::Float(values[idx])),
+            ColumnValues::Integer(values) => Some(AttributeValue::Integer(values[idx])),
+        }
+    }
+
+    /// Iterate over points, having values, in order of their offsets
+    pub fn iter(&self) -> impl Iterator<Item = (PointOffsetType, AttributeValue)> + '_ {
+        self.present
+            .iter_ones()
+            .filter_map(|idx| Some((idx as PointOffsetType, self.get(idx as PointOffsetType)?)))
+    }
+
+    fn set(&mut self, point_id: PointOffsetType, value: AttributeValue) -> OperationResult<()> {
+        let idx = point_id as usize;
+        if self.values.len() <= idx {
+            match &mut self.values {
+                ColumnValues::Float(values) => values.resize(idx + 1, 0.0),
+                ColumnValues::Integer(values) => values.resize(idx + 1, 0),
+            }
+            self.present.resize(idx + 1, false);
+        }
+        match (&mut self.values, value) {
+            (ColumnValues::Float(values), AttributeValue::Float(value)) => values[idx] = value,
+            (ColumnValues::Integer(values), AttributeValue::Integer(value)) => values[idx] = value,
+            (ColumnValues::Float(values), AttributeValue::Integer(value)) => {
+                values[idx] = value as f32
+            }
+            (ColumnValues::Integer(_), AttributeValue::Float(_)) => {
+                return Err(OperationError::ValidationError {
+                    description: format!(
+                        "Wrong type of attribute value {}, expected integer",
+                        value.as_f64(),
+                    ),
+                })
+            }
+        }
+        if !self.present.replace(idx, true) {
+            self.count += 1;
+        }
+        Ok(())
+    }
+
+    fn remove(&mut self, point_id: PointOffsetType) {
+        let idx = point_id as usize;
+        if idx < self.present.len() && self.present.replace(idx, false) {
+            self.count -= 1;
+        }
+    }
+}
+
+/// Column, as it is persisted
+#[derive(Serialize, Deserialize)]
+struct StoredColumn {
+    values: ColumnValues,
+    /// Raw words of the bit vector of present values, of the length of `values`
+    present: Vec<usize>,
+}
+
+impl From<&AttributeColumn> for StoredColumn {
+    fn from(column: &AttributeColumn) -> Self {
+        Self {
+            values: column.values.clone(),
+            present: column.present.clone().into_vec(),
+        }
+    }
+}
+
+impl From<StoredColumn> for AttributeColumn {
+    fn from(stored: StoredColumn) -> Self {
+        let mut present = BitVec::from_vec(stored.present);
+        present.resize(stored.values.len(), false);
+        let count = present.count_ones();
+        Self {
+            values: stored.values,
+            present,
+            count,
+        }
+    }
+}
+
+/// Attribute columns of a segment, by attribute name
+#[derive(Debug, Default)]
+pub struct AttributeStorage {
+    path: PathBuf,
+    columns: HashMap<String, AttributeColumn>,
+    /// Whether columns were changed since the last flush
+    is_dirty: Arc<AtomicBool>,
+}
+
+impl AttributeStorage {
+    pub fn open(segment_path: &Path) -> OperationResult<Self> {
+        let path = segment_path.join(ATTRIBUTES_FILE);
+        let columns = if path.exists() {
+            let stored: HashMap<String, StoredColumn> = read_bin(&path)?;
+            stored
+                .into_iter()
+                .map(|(name, column)| (name, AttributeColumn::from(column)))
+                .collect()
+        } else {
+            HashMap::new()
+        };
+        Ok(Self {
+            path,
+            columns,
+            is_dirty: Default::default(),
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+
+    pub fn column(&self, name: &str) -> Option<&AttributeColumn> {
+        self.columns.get(name)
+    }
+
+    pub fn get(&self, name: &str, point_id: PointOffsetType) -> Option<AttributeValue> {
+        self.columns.get(name)?.get(point_id)
+    }
+
+    /// All attributes of the point
+    pub fn point_attributes(&self, point_id: PointOffsetType) -> HashMap<String, AttributeValue> {
+        self.columns
+            .iter()
+            .filter_map(|(name, column)| Some((name.clone(), column.get(point_id)?)))
+            .collect()
+    }
+
+    /// Set attributes of the point, `None` values remove the attribute.
+    ///
+    /// Columns are created on the first value, with the type of this value.
+    pub fn set(
+        &mut self,
+        point_id: PointOffsetType,
+        attributes: &HashMap<String, Option<AttributeValue>>,
+    ) -> OperationResult<()> {
+        self.is_dirty.store(true, Ordering::Relaxed);
+        for (name, value) in attributes {
+            match value {
+                Some(value) => self
+                    .columns
+                    .entry(name.clone())
+                    .or_insert_with(|| AttributeColumn::new(value.attribute_type()))
+                    .set(point_id, *value)
+                    .map_err(|err| match err {
+                        OperationError::ValidationError { description } => {
+                            OperationError::ValidationError {
+                                description: format!("{description} for attribute `{name}`"),
+                            }
+                        }
+                        err => err,
+                    })?,
+                None => {
+                    if let Some(column) = self.columns.get_mut(name) {
+                        column.remove(point_id);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove all attributes of the point
+    pub fn drop(&mut self, point_id: PointOffsetType) {
+        if self.columns.is_empty() {
+            return;
+        }
+        for column in self.columns.values_mut() {
+            column.remove(point_id);
+        }
+        self.is_dirty.store(true, Ordering::Relaxed);
+    }
+
+    pub fn files(&self) -> Vec<PathBuf> {
+        if !self.columns.is_empty() || self.path.exists() {
+            vec![self.path.clone()]
+        } else {
+            vec![]
+        }
+    }
+
+    pub fn flusher(&self) -> Flusher {
+        if !self.is_dirty.swap(false, Ordering::Relaxed) {
+            return Box::new(|| Ok(()));
+        }
+        let stored: HashMap<_, _> = self
+            .columns
+            .iter()
+            .map(|(name, column)| (name.clone(), StoredColumn::from(column)))
+            .collect();
+        let path = self.path.clone();
+        let is_dirty = self.is_dirty.clone();
+        Box::new(move || {
+            atomic_save_bin(&path, &stored).map_err(|err| {
+                // Write the columns again on the next flush
+                is_dirty.store(true, Ordering::Relaxed);
+                OperationError::from(err)
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+
+    #[test]
+    fn test_attribute_storage_persistence() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let mut storage = AttributeStorage::open(dir.path()).unwrap();
+
+        let attributes = |price: Option<AttributeValue>, stock: Option<AttributeValue>| {
+            HashMap::from([("price".to_string(), price), ("stock".to_string(), stock)])
+        };
+        storage
+            .set(
+                0,
+                &attributes(
+                    Some(AttributeValue::Float(9.5)),
+                    Some(AttributeValue::Integer(3)),
+                ),
+            )
+            .unwrap();
+        storage
+            .set(5, &attributes(Some(AttributeValue::Integer(2)), None))
+            .unwrap();
+        // Integer attributes don't accept floats
+        assert!(storage
+            .set(1, &attributes(None, Some(AttributeValue::Float(1.5))))
+            .is_err());
+        storage.set(0, &attributes(None, None)).unwrap();
+
+        storage.flusher()().unwrap();
+        let storage = AttributeStorage::open(dir.path()).unwrap();
+
+        assert_eq!(storage.get("price", 0), None);
+        assert_eq!(storage.get("price", 5), Some(AttributeValue::Float(2.0)));
+        assert_eq!(storage.get("price", 100), None);
+        assert_eq!(storage.column("price").unwrap().count(), 1);
+        assert!(storage.point_attributes(0).is_empty());
+        assert_eq!(
+            storage.point_attributes(5),
+            HashMap::from([("price".to_string(), AttributeValue::Float(2.0))]),
+        );
+    }
+}
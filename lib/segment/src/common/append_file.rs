@@ -0,0 +1,33 @@
+//! Helpers for files, which are only ever appended to by flushers.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+
+use crate::common::operation_error::OperationResult;
+
+/// Create the file if missing and cut off its incomplete tail, returns length of the file.
+///
+/// The last record may be partially written, if the process crashed during a flush.
+pub fn truncate_to_multiple(path: &Path, size: usize) -> OperationResult<usize> {
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(path)?;
+    let length = file.metadata()?.len() as usize;
+    let complete = length - length % size;
+    if complete != length {
+        log::warn!("Truncating incomplete tail of {}", path.display());
+        file.set_len(complete as u64)?;
+    }
+    Ok(complete)
+}
+
+/// Append bytes to the file and wait until they are persisted
+pub fn append_synced(path: &Path, bytes: &[u8]) -> OperationResult<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(bytes)?;
+    file.sync_all()?;
+    Ok(())
+}
@@ -0,0 +1,117 @@
+use serde::{Deserialize, Serialize};
+
+/// Number of the hash bits, which select a register
+const PRECISION: u32 = 14;
+
+const REGISTERS: usize = 1 << PRECISION;
+
+/// HyperLogLog sketch, estimating the number of distinct 64-bit hashes
+///
+/// Standard error of the estimation is `1.04 / sqrt(2^14)`, about 0.8%.
+/// Sketches of different sets are merged into the sketch of their union without loss of precision.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HyperLogLog {
+    registers: Vec<u8>,
+}
+
+impl Default for HyperLogLog {
+    fn default() -> Self {
+        Self {
+            registers: vec![0; REGISTERS],
+        }
+    }
+}
+
+impl HyperLogLog {
+    /// Restore the sketch from its registers, returns `None` if their number doesn't match
+    pub fn from_registers(registers: Vec<u8>) -> Option<Self> {
+        (registers.len() == REGISTERS).then_some(Self { registers })
+    }
+
+    pub fn registers(&self) -> &[u8] {
+        &self.registers
+    }
+
+    pub fn insert(&mut self, hash: u64) {
+        let index = (hash >> (64 - PRECISION)) as usize;
+        // Guard bit limits the rank by the number of the remaining hash bits
+        let remaining = (hash << PRECISION) | (1 << (PRECISION - 1));
+        let rank = remaining.leading_zeros() as u8 + 1;
+        let register = &mut self.registers[index];
+        *register = (*register).max(rank);
+    }
+
+    pub fn merge(&mut self, other: &Self) {
+        for (register, other) in self.registers.iter_mut().zip(&other.registers) {
+            *register = (*register).max(*other);
+        }
+    }
+
+    pub fn estimate(&self) -> usize {
+        let m = REGISTERS as f64;
+        let alpha = 0.7213 / (1.0 + 1.079 / m);
+        let sum: f64 = self
+            .registers
+            .iter()
+            .map(|&register| (-f64::from(register)).exp2())
+            .sum();
+        let estimate = alpha * m * m / sum;
+
+        let zeros = self
+            .registers
+            .iter()
+            .filter(|&&register| register == 0)
+            .count();
+        if estimate <= 2.5 * m && zeros > 0 {
+            // Linear counting is more precise for small cardinalities
+            (m * (m / zeros as f64).ln()).round() as usize
+        } else {
+            estimate.round() as usize
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::hash::{BuildHasher, BuildHasherDefault};
+
+    use seahash::SeaHasher;
+
+    use super::*;
+
+    fn hash(value: u64) -> u64 {
+        BuildHasherDefault::<SeaHasher>::default().hash_one(value)
+    }
+
+    #[test]
+    fn test_estimate() {
+        for count in [0, 10, 1_000, 100_000] {
+            let mut sketch = HyperLogLog::default();
+            for value in 0..count {
+                sketch.insert(hash(value));
+                // Duplicates don't change the estimation
+                sketch.insert(hash(value));
+            }
+            let error = sketch.estimate().abs_diff(count as usize) as f64;
+            assert!(error <= (count as f64 * 0.03).max(1.0), "{count}: {error}");
+        }
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut a = HyperLogLog::default();
+        let mut b = HyperLogLog::default();
+        let mut union = HyperLogLog::default();
+        for value in 0..20_000 {
+            if value < 15_000 {
+                a.insert(hash(value));
+            }
+            if value >= 5_000 {
+                b.insert(hash(value));
+            }
+            union.insert(hash(value));
+        }
+        a.merge(&b);
+        assert_eq!(a, union);
+    }
+}
@@ -1,5 +1,7 @@
 pub mod anonymize;
+pub mod append_file;
 pub mod error_logging;
+pub mod hyperloglog;
 pub mod macros;
 pub mod mmap_type;
 pub mod operation_error;
@@ -11,6 +13,7 @@ pub mod rocksdb_wrapper;
 pub mod utils;
 pub mod validate_snapshot_archive;
 pub mod vector_utils;
+pub mod weighted_sum_fusion;
 
 use std::sync::atomic::AtomicBool;
 
@@ -58,6 +58,18 @@ pub enum OperationError {
     WrongPayloadKey { description: String },
     #[error("No range index for `order_by` key: `{key}`. Please create one to use `order_by`. Check https://qdrant.tech/documentation/concepts/indexing/#payload-index to see which payload schemas support Range conditions")]
     MissingRangeIndexForOrderBy { key: String },
+    #[error(
+        "No geo index for `order_by` key: `{key}`. Please create one to order by geo distance"
+    )]
+    MissingGeoIndexForOrderBy { key: String },
+    #[error(
+        "No range index for histogram key: `{key}`. Please create an integer, float or datetime index to build a histogram"
+    )]
+    MissingRangeIndexForHistogram { key: String },
+    #[error(
+        "No range index for statistics key: `{key}`. Please create an integer, float or datetime index to compute statistics"
+    )]
+    MissingRangeIndexForStats { key: String },
 }
 
 impl OperationError {
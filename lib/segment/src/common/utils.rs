@@ -1,5 +1,8 @@
 use std::collections::HashMap;
 use std::ops::Bound;
+use std::sync::Arc;
+
+use atomic_refcell::AtomicRefCell;
 
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -11,9 +14,13 @@ use crate::data_types::vectors::Vector;
 use crate::index::field_index::FieldIndex;
 use crate::json_path::JsonPathString;
 use crate::types::PayloadKeyType;
+use crate::vector_storage::VectorStorageEnum;
 
 pub type IndexesMap = HashMap<PayloadKeyType, Vec<FieldIndex>>;
 
+/// Vector storages of a segment by vector name, used for `has_vector` condition
+pub type VectorStoragesMap = HashMap<String, Arc<AtomicRefCell<VectorStorageEnum>>>;
+
 /// A container for JSON values, optimized for the common case of a single value.
 pub type MultiValue<T> = SmallVec<[T; 1]>;
 
@@ -215,6 +222,36 @@ pub fn merge_map(
     }
 }
 
+/// Apply JSON Merge Patch (RFC 7396) to the destination map
+///
+/// Objects are merged recursively, `null` values remove the key, any other value replaces it.
+pub fn merge_patch_map(
+    dest: &mut serde_json::Map<String, Value>,
+    patch: &serde_json::Map<String, Value>,
+) {
+    for (key, value) in patch {
+        match value {
+            Value::Null => {
+                dest.remove(key);
+            }
+            Value::Object(patch_map) => {
+                let dest_value = dest
+                    .entry(key.to_owned())
+                    .or_insert_with(|| Value::Object(Default::default()));
+                if !dest_value.is_object() {
+                    *dest_value = Value::Object(Default::default());
+                }
+                if let Value::Object(dest_map) = dest_value {
+                    merge_patch_map(dest_map, patch_map);
+                }
+            }
+            _ => {
+                dest.insert(key.to_owned(), value.to_owned());
+            }
+        }
+    }
+}
+
 fn set_by_array_path<'a>(
     array_path: &str,
     array_index: Option<u32>,
@@ -1271,7 +1308,7 @@ mod tests {
     use schemars::{schema_for, JsonSchema};
     use serde::{Deserialize, Serialize};
 
-    use crate::common::utils::MaybeOneOrMany;
+    use crate::common::utils::{merge_patch_map, MaybeOneOrMany};
 
     #[test]
     fn test_deserialize_one_or_many() {
@@ -1363,4 +1400,58 @@ mod tests {
             schema_for!(()).schema.instance_type
         );
     }
+
+    #[test]
+    fn test_merge_patch_map() {
+        // Example from RFC 7396, Section 3
+        let mut dest = serde_json::from_str(
+            r#"
+            {
+                "title": "Goodbye!",
+                "author": { "givenName": "John", "familyName": "Doe" },
+                "tags": ["example", "sample"],
+                "content": "This will be unchanged"
+            }
+            "#,
+        )
+        .unwrap();
+
+        let patch = serde_json::from_str(
+            r#"
+            {
+                "title": "Hello!",
+                "phoneNumber": "+01-123-456-7890",
+                "author": { "familyName": null },
+                "tags": ["example"]
+            }
+            "#,
+        )
+        .unwrap();
+
+        merge_patch_map(&mut dest, &patch);
+
+        let expected: serde_json::Map<String, serde_json::Value> = serde_json::from_str(
+            r#"
+            {
+                "title": "Hello!",
+                "author": { "givenName": "John" },
+                "tags": ["example"],
+                "content": "This will be unchanged",
+                "phoneNumber": "+01-123-456-7890"
+            }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(dest, expected);
+
+        // Non-object values are replaced by patch objects, nulls in new objects are dropped
+        let mut dest = serde_json::from_str(r#"{ "a": "b" }"#).unwrap();
+        let patch = serde_json::from_str(r#"{ "a": { "b": "c", "d": null } }"#).unwrap();
+        merge_patch_map(&mut dest, &patch);
+        assert_eq!(
+            dest,
+            serde_json::from_str::<serde_json::Map<_, _>>(r#"{ "a": { "b": "c" } }"#).unwrap(),
+        );
+    }
 }
@@ -0,0 +1,206 @@
+//! Weighted score fusion combines results from multiple sources by summing their scores,
+//! multiplied by the weight of each source.
+//!
+//! Scores of each source can be normalized first, so that sources with different score ranges,
+//! like cosine similarity and sparse dot product, are comparable.
+
+use std::collections::hash_map::Entry;
+
+use ahash::{HashMap, HashMapExt};
+use ordered_float::OrderedFloat;
+
+use crate::types::{ExtendedPointId, ScoredPoint};
+
+/// Normalization of the scores of each source, applied before the weighted sum
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScoreNormalization {
+    /// Scale scores linearly, so that the best score of the source is `1` and the worst is `0`
+    MinMax,
+    /// Subtract the mean score of the source and divide by the standard deviation
+    ZScore,
+    /// Replace scores by the rank in the source: `1` for the first point, down to `1 / len`
+    Rank,
+}
+
+impl ScoreNormalization {
+    /// Normalize scores of a single source, which is sorted from the best to the worst point
+    pub fn normalize(&self, points: &mut [ScoredPoint]) {
+        if points.is_empty() {
+            return;
+        }
+        match self {
+            ScoreNormalization::MinMax => {
+                let (min, max) = points
+                    .iter()
+                    .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), point| {
+                        (min.min(point.score), max.max(point.score))
+                    });
+                let range = max - min;
+                for point in points {
+                    // All points are equally good
+                    point.score = if range > 0.0 {
+                        (point.score - min) / range
+                    } else {
+                        1.0
+                    };
+                }
+            }
+            ScoreNormalization::ZScore => {
+                let len = points.len() as f32;
+                let mean = points.iter().map(|point| point.score).sum::<f32>() / len;
+                let variance = points
+                    .iter()
+                    .map(|point| (point.score - mean).powi(2))
+                    .sum::<f32>()
+                    / len;
+                let std_dev = variance.sqrt();
+                for point in points {
+                    point.score = if std_dev > 0.0 {
+                        (point.score - mean) / std_dev
+                    } else {
+                        0.0
+                    };
+                }
+            }
+            ScoreNormalization::Rank => {
+                let len = points.len() as f32;
+                for (rank, point) in points.iter_mut().enumerate() {
+                    point.score = (len - rank as f32) / len;
+                }
+            }
+        }
+    }
+}
+
+/// Compute weighted sum of scores for multiple results from different sources.
+/// `weights` has one weight per response, missing weights are considered as `1.0`.
+/// A point, which is not present in a response, gets no score from it.
+///
+/// Without `normalization`, scores of the sources are summed as-is, so sources are expected to
+/// have comparable scores, where larger is better, e.g. cosine similarity of different named
+/// vectors.
+///
+/// The output is a single sorted list of ScoredPoint.
+/// Does not break ties.
+pub fn weighted_sum_scoring(
+    responses: impl IntoIterator<Item = Vec<ScoredPoint>>,
+    weights: &[f32],
+    normalization: Option<ScoreNormalization>,
+) -> Vec<ScoredPoint> {
+    // track scored points by id
+    let mut points_by_id: HashMap<ExtendedPointId, ScoredPoint> = HashMap::new();
+
+    for (index, mut response) in responses.into_iter().enumerate() {
+        if let Some(normalization) = normalization {
+            normalization.normalize(&mut response);
+        }
+        let weight = weights.get(index).copied().unwrap_or(1.0);
+        for mut point in response {
+            let weighted_score = point.score * weight;
+            match points_by_id.entry(point.id) {
+                Entry::Occupied(mut entry) => {
+                    entry.get_mut().score += weighted_score;
+                }
+                Entry::Vacant(entry) => {
+                    point.score = weighted_score;
+                    entry.insert(point);
+                }
+            }
+        }
+    }
+
+    let mut scores: Vec<_> = points_by_id.into_values().collect();
+    scores.sort_unstable_by(|a, b| {
+        // sort by score descending
+        OrderedFloat(b.score).cmp(&OrderedFloat(a.score))
+    });
+
+    scores
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_scored_point(id: u64, score: f32) -> ScoredPoint {
+        ScoredPoint {
+            id: id.into(),
+            version: 0,
+            score,
+            payload: None,
+            vector: None,
+            shard_key: None,
+            order_value: None,
+        }
+    }
+
+    #[test]
+    fn test_weighted_sum_scoring() {
+        let responses = vec![
+            vec![make_scored_point(1, 0.9), make_scored_point(2, 0.5)],
+            vec![make_scored_point(2, 0.8), make_scored_point(3, 0.6)],
+        ];
+
+        let scored_points = weighted_sum_scoring(responses, &[0.25, 1.0], None);
+        assert_eq!(scored_points.len(), 3);
+
+        assert_eq!(scored_points[0].id, 2.into());
+        assert_eq!(scored_points[0].score, 0.5 * 0.25 + 0.8);
+
+        assert_eq!(scored_points[1].id, 3.into());
+        assert_eq!(scored_points[1].score, 0.6);
+
+        assert_eq!(scored_points[2].id, 1.into());
+        assert_eq!(scored_points[2].score, 0.9 * 0.25);
+    }
+
+    #[test]
+    fn test_weighted_sum_scoring_normalized() {
+        // Dense similarities and sparse dot products with very different ranges
+        let responses = vec![
+            vec![
+                make_scored_point(1, 0.9),
+                make_scored_point(2, 0.8),
+                make_scored_point(3, 0.5),
+            ],
+            vec![
+                make_scored_point(2, 30.0),
+                make_scored_point(3, 25.0),
+                make_scored_point(4, 10.0),
+            ],
+        ];
+
+        let min_max = weighted_sum_scoring(
+            responses.clone(),
+            &[1.0, 1.0],
+            Some(ScoreNormalization::MinMax),
+        );
+        assert_eq!(min_max.len(), 4);
+        assert_eq!(min_max[0].id, 2.into());
+        assert!((min_max[0].score - (0.75 + 1.0)).abs() < 1e-5);
+        assert_eq!(min_max[3].id, 4.into());
+        assert_eq!(min_max[3].score, 0.0);
+
+        let rank = weighted_sum_scoring(
+            responses.clone(),
+            &[1.0, 1.0],
+            Some(ScoreNormalization::Rank),
+        );
+        assert_eq!(rank[0].id, 2.into());
+        assert!((rank[0].score - (2.0 / 3.0 + 1.0)).abs() < 1e-5);
+
+        // Without normalization, sparse scores would dominate even with a low weight
+        let z_score =
+            weighted_sum_scoring(responses, &[1.0, 0.1], Some(ScoreNormalization::ZScore));
+        assert_eq!(z_score[0].id, 1.into());
+    }
+
+    #[test]
+    fn test_normalize_equal_scores() {
+        let mut points = vec![make_scored_point(1, 0.5), make_scored_point(2, 0.5)];
+        ScoreNormalization::MinMax.normalize(&mut points);
+        assert!(points.iter().all(|point| point.score == 1.0));
+        ScoreNormalization::ZScore.normalize(&mut points);
+        assert!(points.iter().all(|point| point.score == 0.0));
+    }
+}
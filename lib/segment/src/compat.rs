@@ -64,6 +64,8 @@ impl From<SegmentConfigV5> for SegmentConfig {
             vector_data,
             sparse_vector_data: Default::default(),
             payload_storage_type: old_segment.payload_storage_type,
+            payload_columns: Default::default(),
+            payload_compression: Default::default(),
         }
     }
 }
@@ -127,6 +129,7 @@ impl From<SegmentStateV5> for SegmentState {
         Self {
             version: old.version,
             config: old.config.into(),
+            created_at: None,
         }
     }
 }
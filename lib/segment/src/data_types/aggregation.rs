@@ -0,0 +1,533 @@
+use std::collections::HashSet;
+use std::hash::Hasher;
+
+use schemars::JsonSchema;
+use seahash::SeaHasher;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::common::hyperloglog::HyperLogLog;
+
+/// Max number of distinct values, which are counted exactly.
+///
+/// Larger sets are converted into a sketch, which is cheap to transfer and merge.
+pub const MAX_EXACT_DISTINCT_VALUES: usize = 100_000;
+
+/// Distinct values of a payload field, mergeable across segments and shards
+///
+/// Values are represented by hashes, so the values from indexes and from payload are comparable.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DistinctValues {
+    /// Hashes of all distinct values
+    Exact(HashSet<u64>),
+    /// Sketch of distinct values, if they were read from payload or there are too many of them
+    Estimated(HyperLogLog),
+}
+
+impl Default for DistinctValues {
+    fn default() -> Self {
+        DistinctValues::Exact(HashSet::new())
+    }
+}
+
+impl DistinctValues {
+    pub fn estimated() -> Self {
+        DistinctValues::Estimated(HyperLogLog::default())
+    }
+
+    pub fn insert(&mut self, hash: u64) {
+        match self {
+            DistinctValues::Exact(hashes) => {
+                hashes.insert(hash);
+                if hashes.len() > MAX_EXACT_DISTINCT_VALUES {
+                    self.make_estimated();
+                }
+            }
+            DistinctValues::Estimated(sketch) => sketch.insert(hash),
+        }
+    }
+
+    fn make_estimated(&mut self) {
+        if let DistinctValues::Exact(hashes) = self {
+            let mut sketch = HyperLogLog::default();
+            hashes.iter().for_each(|hash| sketch.insert(*hash));
+            *self = DistinctValues::Estimated(sketch);
+        }
+    }
+
+    pub fn merge(&mut self, other: DistinctValues) {
+        match other {
+            DistinctValues::Exact(hashes) => hashes.into_iter().for_each(|hash| self.insert(hash)),
+            DistinctValues::Estimated(other_sketch) => {
+                self.make_estimated();
+                if let DistinctValues::Estimated(sketch) = self {
+                    sketch.merge(&other_sketch);
+                }
+            }
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        match self {
+            DistinctValues::Exact(hashes) => hashes.len(),
+            DistinctValues::Estimated(sketch) => sketch.estimate(),
+        }
+    }
+
+    pub fn is_exact(&self) -> bool {
+        matches!(self, DistinctValues::Exact(_))
+    }
+}
+
+/// Max number of buckets in a histogram
+pub const MAX_HISTOGRAM_BUCKETS: usize = 1000;
+
+/// Number of values in `[from; to)` range, distributed uniformly.
+///
+/// If `from == to`, the range contains exactly this value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ValueRange {
+    pub from: f64,
+    pub to: f64,
+    pub count: usize,
+}
+
+/// Approximate distribution of numeric values, sampled from histograms of numeric indexes
+///
+/// Distributions of different segments and shards are merged by joining their ranges,
+/// ranges of different distributions may overlap.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ValueDistribution {
+    ranges: Vec<ValueRange>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct HistogramBucket {
+    /// Lower bound of the bucket, inclusive
+    pub from: f64,
+    /// Upper bound of the bucket, exclusive. The last bucket includes its upper bound
+    pub to: f64,
+    /// Approximate number of values in the bucket
+    pub count: usize,
+}
+
+impl ValueDistribution {
+    pub fn new(ranges: Vec<ValueRange>) -> Self {
+        Self { ranges }
+    }
+
+    pub fn ranges(&self) -> &[ValueRange] {
+        &self.ranges
+    }
+
+    pub fn merge(&mut self, other: ValueDistribution) {
+        self.ranges.extend(other.ranges);
+    }
+
+    pub fn total_count(&self) -> usize {
+        self.ranges.iter().map(|range| range.count).sum()
+    }
+
+    fn min_max(&self) -> Option<(f64, f64)> {
+        let min = self
+            .ranges
+            .iter()
+            .map(|range| range.from)
+            .min_by(f64::total_cmp)?;
+        let max = self
+            .ranges
+            .iter()
+            .map(|range| range.to)
+            .max_by(f64::total_cmp)?;
+        Some((min, max))
+    }
+
+    /// Buckets of `interval` width, aligned to the multiples of `interval`.
+    ///
+    /// Returns `None` if more than [`MAX_HISTOGRAM_BUCKETS`] buckets are required.
+    pub fn fixed_width_buckets(&self, interval: f64) -> Option<Vec<HistogramBucket>> {
+        let Some((min, max)) = self.min_max() else {
+            return Some(Vec::new());
+        };
+        let first = (min / interval).floor();
+        let last = (max / interval).floor();
+        if last - first >= MAX_HISTOGRAM_BUCKETS as f64 {
+            return None;
+        }
+
+        let bucket = |value: f64| ((value / interval).floor() - first) as usize;
+        let mut counts = vec![0.0; (last - first) as usize + 1];
+        for range in &self.ranges {
+            if range.to <= range.from {
+                counts[bucket(range.from)] += range.count as f64;
+                continue;
+            }
+            let density = range.count as f64 / (range.to - range.from);
+            for (index, count) in counts
+                .iter_mut()
+                .enumerate()
+                .take(bucket(range.to) + 1)
+                .skip(bucket(range.from))
+            {
+                let bucket_from = (first + index as f64) * interval;
+                let covered = range.to.min(bucket_from + interval) - range.from.max(bucket_from);
+                *count += density * covered.max(0.0);
+            }
+        }
+
+        let buckets = counts
+            .into_iter()
+            .enumerate()
+            .map(|(index, count)| {
+                let from = (first + index as f64) * interval;
+                HistogramBucket {
+                    from,
+                    to: from + interval,
+                    count: count.round() as usize,
+                }
+            })
+            .collect();
+        Some(buckets)
+    }
+
+    /// `quantiles` buckets with about the same number of values in each
+    pub fn quantile_buckets(&self, quantiles: usize) -> Vec<HistogramBucket> {
+        let total = self.total_count();
+        let Some((min, max)) = self.min_max() else {
+            return Vec::new();
+        };
+        if total == 0 || quantiles == 0 {
+            return Vec::new();
+        }
+        let target = |k: usize| (k * total) as f64 / quantiles as f64;
+
+        let mut borders = vec![min];
+        borders.extend(self.inverse_cdf((1..quantiles).map(target)));
+        borders.push(max);
+
+        borders
+            .windows(2)
+            .enumerate()
+            .map(|(k, window)| HistogramBucket {
+                from: window[0],
+                to: window[1],
+                count: target(k + 1).round() as usize - target(k).round() as usize,
+            })
+            .collect()
+    }
+
+    /// Value, below which `percentile` percent of all values are
+    pub fn percentile(&self, percentile: f64) -> Option<f64> {
+        let total = self.total_count();
+        if total == 0 {
+            return None;
+        }
+        let target = total as f64 * percentile.clamp(0.0, 100.0) / 100.0;
+        self.inverse_cdf(std::iter::once(target)).pop()
+    }
+
+    /// Values, at which the number of smaller values reaches each of the ascending `targets`
+    fn inverse_cdf(&self, targets: impl ExactSizeIterator<Item = f64>) -> Vec<f64> {
+        let expected = targets.len();
+        let mut targets = targets.peekable();
+
+        // Cumulative distribution is piecewise linear: density changes on range borders,
+        // zero-length ranges add a step
+        let mut events = Vec::with_capacity(self.ranges.len() * 2);
+        for range in &self.ranges {
+            if range.to > range.from {
+                let density = range.count as f64 / (range.to - range.from);
+                events.push((range.from, density, 0.0));
+                events.push((range.to, -density, 0.0));
+            } else {
+                events.push((range.from, 0.0, range.count as f64));
+            }
+        }
+        events.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut values = Vec::with_capacity(expected);
+        let mut cumulative = 0.0;
+        let mut density = 0.0;
+        let mut position = events.first().map_or(0.0, |event| event.0);
+        for (value, density_change, step) in events {
+            let next = cumulative + density * (value - position);
+            while let Some(target) = targets.next_if(|&target| next > cumulative && target <= next)
+            {
+                values.push(position + (target - cumulative) / density);
+            }
+            cumulative = next + step;
+            position = value;
+            while targets.next_if(|&target| target <= cumulative).is_some() {
+                values.push(value);
+            }
+            density += density_change;
+        }
+        // Rounding errors might leave the last targets unreached
+        values.resize(expected, position);
+        values
+    }
+}
+
+/// Max number of ranges, which describe the distribution of values in [`NumericStats`]
+const MAX_STATS_RANGES: usize = 256;
+
+/// Statistics of numeric values, mergeable across segments and shards
+#[derive(Debug, Clone, PartialEq)]
+pub struct NumericStats {
+    count: usize,
+    sum: f64,
+    min: f64,
+    max: f64,
+    /// Compressed distribution of values, for percentiles
+    distribution: ValueDistribution,
+}
+
+impl Default for NumericStats {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            sum: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+            distribution: ValueDistribution::default(),
+        }
+    }
+}
+
+impl NumericStats {
+    pub fn from_values(mut values: Vec<f64>) -> Self {
+        values.retain(|value| !value.is_nan());
+        if values.is_empty() {
+            return Self::default();
+        }
+        values.sort_unstable_by(f64::total_cmp);
+
+        // Each range holds the same number of values, small sets are kept as is
+        let chunk_size = values.len().div_ceil(MAX_STATS_RANGES);
+        let ranges = values
+            .chunks(chunk_size)
+            .map(|chunk| ValueRange {
+                from: chunk[0],
+                to: chunk[chunk.len() - 1],
+                count: chunk.len(),
+            })
+            .collect();
+
+        Self {
+            count: values.len(),
+            sum: values.iter().sum(),
+            min: values[0],
+            max: values[values.len() - 1],
+            distribution: ValueDistribution::new(ranges),
+        }
+    }
+
+    /// Restore statistics from the parts, returned by [`NumericStats::into_parts`]
+    pub fn from_parts(
+        count: usize,
+        sum: f64,
+        min: f64,
+        max: f64,
+        distribution: ValueDistribution,
+    ) -> Self {
+        if count == 0 {
+            return Self::default();
+        }
+        Self {
+            count,
+            sum,
+            min,
+            max,
+            distribution,
+        }
+    }
+
+    /// Count, sum, min, max and distribution of values
+    pub fn into_parts(self) -> (usize, f64, f64, f64, ValueDistribution) {
+        (self.count, self.sum, self.min, self.max, self.distribution)
+    }
+
+    pub fn merge(&mut self, other: NumericStats) {
+        self.count += other.count;
+        self.sum += other.sum;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.distribution.merge(other.distribution);
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn min(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.min)
+    }
+
+    pub fn max(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.max)
+    }
+
+    pub fn avg(&self) -> Option<f64> {
+        (self.count > 0).then(|| self.sum / self.count as f64)
+    }
+
+    /// Approximate percentile, exact for small sets of values
+    pub fn percentile(&self, percentile: f64) -> Option<f64> {
+        self.distribution
+            .percentile(percentile)
+            .map(|value| value.clamp(self.min, self.max))
+    }
+}
+
+fn hash_tagged(tag: u8, bytes: &[u8]) -> u64 {
+    let mut hasher = SeaHasher::new();
+    hasher.write_u8(tag);
+    hasher.write(bytes);
+    hasher.finish()
+}
+
+pub fn hash_keyword(keyword: &str) -> u64 {
+    hash_tagged(b's', keyword.as_bytes())
+}
+
+pub fn hash_integer(value: i64) -> u64 {
+    hash_tagged(b'i', &value.to_le_bytes())
+}
+
+/// Hash of a single payload value, equal to the hash of the same value read from an index.
+///
+/// Returns `None` for `null`, which is not a value.
+pub fn hash_payload_value(value: &Value) -> Option<u64> {
+    let hash = match value {
+        Value::Null => return None,
+        Value::Bool(value) => hash_tagged(b'b', &[u8::from(*value)]),
+        Value::Number(number) => match number.as_i64() {
+            Some(integer) => hash_integer(integer),
+            None => {
+                let float = number.as_f64().unwrap_or_default();
+                // Integral floats are the same values as integers
+                if float.fract() == 0.0 && float.abs() < i64::MAX as f64 {
+                    hash_integer(float as i64)
+                } else {
+                    hash_tagged(b'f', &float.to_le_bytes())
+                }
+            }
+        },
+        Value::String(keyword) => hash_keyword(keyword),
+        Value::Array(_) | Value::Object(_) => hash_tagged(b'o', value.to_string().as_bytes()),
+    };
+    Some(hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_distinct_values() {
+        let mut a = DistinctValues::default();
+        let mut b = DistinctValues::default();
+        for keyword in ["a", "b", "c"] {
+            a.insert(hash_keyword(keyword));
+        }
+        for keyword in ["b", "c", "d"] {
+            b.insert(hash_keyword(keyword));
+        }
+        a.merge(b);
+        assert!(a.is_exact());
+        assert_eq!(a.count(), 4);
+
+        let mut estimated = DistinctValues::estimated();
+        estimated.insert(hash_keyword("e"));
+        a.merge(estimated);
+        assert!(!a.is_exact());
+        assert_eq!(a.count(), 5);
+
+        let mut large = DistinctValues::default();
+        (0..=MAX_EXACT_DISTINCT_VALUES as i64).for_each(|v| large.insert(hash_integer(v)));
+        assert!(!large.is_exact());
+    }
+
+    #[test]
+    fn test_histogram_buckets() {
+        // Values 0..100, one per integer, as ranges of 10 values
+        let ranges = (0..10)
+            .map(|i| ValueRange {
+                from: (i * 10) as f64,
+                to: (i * 10 + 10) as f64,
+                count: 10,
+            })
+            .collect();
+        let mut distribution = ValueDistribution::new(ranges);
+        assert_eq!(distribution.total_count(), 100);
+
+        let buckets = distribution.fixed_width_buckets(25.0).unwrap();
+        assert_eq!(buckets.len(), 5);
+        assert_eq!(buckets[0].from, 0.0);
+        assert_eq!(buckets[0].to, 25.0);
+        assert_eq!(
+            buckets
+                .iter()
+                .map(|bucket| bucket.count)
+                .collect::<Vec<_>>(),
+            vec![25, 25, 25, 25, 0],
+        );
+        assert!(distribution.fixed_width_buckets(0.01).is_none());
+
+        let quartiles = distribution.quantile_buckets(4);
+        assert_eq!(quartiles.len(), 4);
+        for (bucket, expected) in quartiles.iter().zip([0.0, 25.0, 50.0, 75.0]) {
+            assert!((bucket.from - expected).abs() < 1e-9);
+            assert_eq!(bucket.count, 25);
+        }
+        assert_eq!(quartiles[3].to, 100.0);
+
+        // Half of all values are the same
+        distribution.merge(ValueDistribution::new(vec![ValueRange {
+            from: 90.0,
+            to: 90.0,
+            count: 100,
+        }]));
+        let halves = distribution.quantile_buckets(2);
+        assert_eq!(halves[0].to, 90.0);
+        assert_eq!(halves[0].count, 100);
+        assert_eq!(halves[1].count, 100);
+    }
+
+    #[test]
+    fn test_numeric_stats() {
+        let mut stats = NumericStats::from_values(vec![3.0, 1.0, 2.0]);
+        stats.merge(NumericStats::from_values(vec![4.0, f64::NAN]));
+        stats.merge(NumericStats::default());
+
+        assert_eq!(stats.count(), 4);
+        assert_eq!(stats.min(), Some(1.0));
+        assert_eq!(stats.max(), Some(4.0));
+        assert_eq!(stats.avg(), Some(2.5));
+        assert_eq!(stats.percentile(0.0), Some(1.0));
+        assert_eq!(stats.percentile(50.0), Some(2.0));
+        assert_eq!(stats.percentile(100.0), Some(4.0));
+
+        // Large sets are compressed, percentiles are approximate
+        let large = NumericStats::from_values((0..10_000).map(f64::from).collect());
+        assert_eq!(large.count(), 10_000);
+        let median = large.percentile(50.0).unwrap();
+        assert!((median - 5_000.0).abs() < 50.0, "{median}");
+
+        let empty = NumericStats::default();
+        assert_eq!(empty.min(), None);
+        assert_eq!(empty.avg(), None);
+        assert_eq!(empty.percentile(50.0), None);
+    }
+
+    #[test]
+    fn test_payload_value_hash() {
+        let value = |json: &str| hash_payload_value(&serde_json::from_str(json).unwrap());
+        assert_eq!(value(r#""abc""#), Some(hash_keyword("abc")));
+        assert_eq!(value("42"), Some(hash_integer(42)));
+        assert_eq!(value("42.0"), Some(hash_integer(42)));
+        assert_ne!(value("42.5"), Some(hash_integer(42)));
+        assert_ne!(value(r#""42""#), value("42"));
+        assert_eq!(value("null"), None);
+    }
+}
@@ -1,9 +1,12 @@
+pub mod aggregation;
+pub mod geo_index;
 pub mod groups;
 pub mod integer_index;
 pub mod named_vectors;
 pub mod order_by;
 pub mod primitive;
 pub mod query_context;
+pub mod search_plan;
 pub mod text_index;
 pub mod tiny_map;
 pub mod vectors;
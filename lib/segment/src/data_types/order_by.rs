@@ -1,3 +1,5 @@
+use std::cmp::Ordering;
+
 use num_cmp::NumCmp;
 use ordered_float::OrderedFloat;
 use schemars::JsonSchema;
@@ -6,10 +8,12 @@ use validator::Validate;
 
 use crate::json_path::JsonPath;
 use crate::types::{
-    DateTimePayloadType, FloatPayloadType, IntPayloadType, Order, Payload, Range, RangeInterface,
+    BigIntPayloadType, DateTimePayloadType, FloatPayloadType, GeoPoint, IntPayloadType, Order,
+    Payload, PointIdType, Range, RangeInterface,
 };
 
 const INTERNAL_KEY_OF_ORDER_BY_VALUE: &str = "____ordered_with____";
+const INTERNAL_KEY_OF_THEN_BY_VALUES: &str = "____ordered_then_with____";
 
 #[derive(Deserialize, Serialize, JsonSchema, Copy, Clone, Debug, Default, PartialEq)]
 #[serde(rename_all = "snake_case")]
@@ -38,6 +42,16 @@ impl Direction {
     }
 }
 
+impl Direction {
+    /// Compare values in this direction
+    pub fn compare<T: Ord>(&self, a: &T, b: &T) -> Ordering {
+        match self {
+            Direction::Asc => a.cmp(b),
+            Direction::Desc => b.cmp(a),
+        }
+    }
+}
+
 impl From<Direction> for Order {
     fn from(direction: Direction) -> Self {
         match direction {
@@ -57,6 +71,40 @@ pub enum StartFrom {
     Datetime(DateTimePayloadType),
 }
 
+/// Additional key, which orders points with equal values of the previous keys
+#[derive(Deserialize, Serialize, JsonSchema, Validate, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct ThenBy {
+    /// Payload key to order by. Requires a range index.
+    pub key: JsonPath,
+
+    /// Direction of ordering: `asc` or `desc`. Default is ascending.
+    pub direction: Option<Direction>,
+}
+
+impl ThenBy {
+    pub fn direction(&self) -> Direction {
+        self.direction.unwrap_or_default()
+    }
+
+    /// Value of the points without a value for the key, so that they come last
+    pub fn missing_value(&self) -> OrderValue {
+        match self.direction() {
+            Direction::Asc => OrderValue::MAX,
+            Direction::Desc => OrderValue::MIN,
+        }
+    }
+
+    /// Value of a point with multiple values: the first one in the direction of ordering
+    pub fn select_value(&self, values: impl Iterator<Item = OrderValue>) -> OrderValue {
+        match self.direction() {
+            Direction::Asc => values.min(),
+            Direction::Desc => values.max(),
+        }
+        .unwrap_or_else(|| self.missing_value())
+    }
+}
+
 #[derive(Deserialize, Serialize, JsonSchema, Validate, Clone, Debug, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub struct OrderBy {
@@ -68,6 +116,23 @@ pub struct OrderBy {
 
     /// Which payload value to start scrolling from. Default is the lowest value for `asc` and the highest for `desc`
     pub start_from: Option<StartFrom>,
+
+    /// Order by distance from this point, in meters, instead of the payload value.
+    /// Requires a geo index on `key`. Ascending order returns the nearest points first.
+    pub geo: Option<GeoPoint>,
+
+    /// Order points with equal values of `key` by these keys, in the given order.
+    /// Each key has its own direction. Remaining ties are ordered by point id.
+    pub then_by: Option<Vec<ThenBy>>,
+
+    /// Internal: skip points up to and including this id among the ones with the `start_from` value.
+    /// Set from a scroll cursor, so that the next page starts right after the last returned point.
+    #[serde(skip)]
+    pub start_after_id: Option<PointIdType>,
+
+    /// Internal: values of the `then_by` keys of the point at `start_after_id`.
+    #[serde(skip)]
+    pub start_after_then_by: Vec<OrderValue>,
 }
 
 impl OrderBy {
@@ -76,11 +141,10 @@ impl OrderBy {
         self.start_from
             .as_ref()
             .map(|start_from| match start_from {
-                // TODO: When we introduce integer ranges, we'll stop doing lossy conversion to f64 here
-                // Accepting an integer as start_from simplifies the client generation.
-                StartFrom::Integer(i) => {
-                    RangeInterface::Float(self.direction().as_range_from(*i as f64))
-                }
+                // Integers, which are not representable by f64, must stay exact for the cursor
+                StartFrom::Integer(i) => RangeInterface::BigInt(
+                    self.direction().as_range_from(BigIntPayloadType::from(*i)),
+                ),
                 StartFrom::Float(f) => RangeInterface::Float(self.direction().as_range_from(*f)),
                 StartFrom::Datetime(dt) => {
                     RangeInterface::DateTime(self.direction().as_range_from(*dt))
@@ -93,6 +157,101 @@ impl OrderBy {
         self.direction.unwrap_or_default()
     }
 
+    /// Continue ordering right after the given cursor position
+    pub fn with_cursor(mut self, cursor: &OrderByCursor) -> Self {
+        self.start_from = Some(match cursor.value {
+            OrderValue::Int(i) => StartFrom::Integer(i),
+            OrderValue::Float(f) => StartFrom::Float(f),
+        });
+        self.start_after_id = Some(cursor.id);
+        self.start_after_then_by = cursor.then_by.clone();
+        self
+    }
+
+    pub fn then_by(&self) -> &[ThenBy] {
+        self.then_by.as_deref().unwrap_or_default()
+    }
+
+    /// Same ordering, which starts from `start_from` value including, regardless of the cursor position
+    pub fn without_cursor(&self) -> Self {
+        Self {
+            start_after_id: None,
+            start_after_then_by: Vec::new(),
+            ..self.clone()
+        }
+    }
+
+    /// Position of the point to continue ordering after, if set from a cursor
+    pub fn start_after(&self) -> Option<OrderedPoint> {
+        self.start_after_id.map(|id| OrderedPoint {
+            value: self.start_from(),
+            then_by: self.start_after_then_by.clone(),
+            id,
+        })
+    }
+
+    /// Compare positions of two points in this ordering.
+    ///
+    /// Points are compared by `key` value, then by the values of `then_by` keys, each in its
+    /// own direction. Remaining ties are resolved by point id, in the direction of `key`.
+    pub fn cmp_points(&self, a: &OrderedPoint, b: &OrderedPoint) -> Ordering {
+        let direction = self.direction();
+        direction
+            .compare(&a.value, &b.value)
+            .then_with(|| {
+                self.then_by()
+                    .iter()
+                    .zip(a.then_by.iter().zip(&b.then_by))
+                    .map(|(then_by, (a, b))| then_by.direction().compare(a, b))
+                    .find(|ordering| ordering.is_ne())
+                    .unwrap_or(Ordering::Equal)
+            })
+            .then_with(|| direction.compare(&a.id, &b.id))
+    }
+
+    /// Sort points in this ordering
+    pub fn sort_points(&self, points: &mut [OrderedPoint]) {
+        points.sort_unstable_by(|a, b| self.cmp_points(a, b));
+    }
+
+    /// Check if the `(value, id)` pair comes after the position of `start_from` and `start_after_id`.
+    ///
+    /// Points with the same value are ordered by their id, in the same direction as the values.
+    pub fn is_after_start(&self, value: OrderValue, id: PointIdType) -> bool {
+        let start_from = self.start_from();
+        match (self.direction(), self.start_after_id) {
+            (Direction::Asc, None) => value >= start_from,
+            (Direction::Desc, None) => value <= start_from,
+            (Direction::Asc, Some(after_id)) => (value, id) > (start_from, after_id),
+            (Direction::Desc, Some(after_id)) => (value, id) < (start_from, after_id),
+        }
+    }
+
+    /// Sort `(value, id)` pairs according to the direction, ties are resolved by point id.
+    pub fn sort_values_ids(&self, values_ids: &mut [(OrderValue, PointIdType)]) {
+        match self.direction() {
+            Direction::Asc => values_ids.sort_unstable(),
+            Direction::Desc => values_ids.sort_unstable_by(|a, b| b.cmp(a)),
+        }
+    }
+
+    /// Keep the first `limit` sorted pairs, and all the following pairs sharing the value of the last one.
+    pub fn truncate_with_ties(values_ids: &mut Vec<(OrderValue, PointIdType)>, limit: usize) {
+        if values_ids.len() <= limit {
+            return;
+        }
+        let Some(&(last_value, _)) = limit.checked_sub(1).map(|last| &values_ids[last]) else {
+            values_ids.clear();
+            return;
+        };
+        let len = limit
+            + values_ids[limit..]
+                .iter()
+                .take_while(|(value, _)| *value == last_value)
+                .count();
+        values_ids.truncate(len);
+    }
+
     pub fn start_from(&self) -> OrderValue {
         self.start_from
             .as_ref()
@@ -118,6 +277,56 @@ impl OrderBy {
         new_payload
     }
 
+    /// Add values of `then_by` keys to the payload, if there are any.
+    pub fn insert_then_by_values_in_payload(
+        mut payload: Payload,
+        values: &[OrderValue],
+    ) -> Payload {
+        if !values.is_empty() {
+            let values = values
+                .iter()
+                .copied()
+                .map(serde_json::Value::from)
+                .collect();
+            payload.0.insert(
+                INTERNAL_KEY_OF_THEN_BY_VALUES.to_string(),
+                serde_json::Value::Array(values),
+            );
+        }
+        payload
+    }
+
+    fn json_value_to_then_by_values(&self, value: Option<serde_json::Value>) -> Vec<OrderValue> {
+        let mut values = match value {
+            Some(serde_json::Value::Array(values)) => values.into_iter(),
+            _ => Vec::new().into_iter(),
+        };
+        self.then_by()
+            .iter()
+            .map(|then_by| {
+                values
+                    .next()
+                    .and_then(|v| OrderValue::try_from(v).ok())
+                    .unwrap_or_else(|| then_by.missing_value())
+            })
+            .collect()
+    }
+
+    pub fn get_then_by_values_from_payload(&self, payload: Option<&Payload>) -> Vec<OrderValue> {
+        self.json_value_to_then_by_values(
+            payload.and_then(|payload| payload.0.get(INTERNAL_KEY_OF_THEN_BY_VALUES).cloned()),
+        )
+    }
+
+    pub fn remove_then_by_values_from_payload(
+        &self,
+        payload: Option<&mut Payload>,
+    ) -> Vec<OrderValue> {
+        self.json_value_to_then_by_values(
+            payload.and_then(|payload| payload.0.remove(INTERNAL_KEY_OF_THEN_BY_VALUES)),
+        )
+    }
+
     fn json_value_to_ordering_value(&self, value: Option<serde_json::Value>) -> OrderValue {
         value
             .and_then(|v| OrderValue::try_from(v).ok())
@@ -140,6 +349,95 @@ impl OrderBy {
     }
 }
 
+/// Point with the values it is ordered by
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderedPoint {
+    /// Value of the `order_by.key`
+    pub value: OrderValue,
+    /// Values of the `order_by.then_by` keys
+    pub then_by: Vec<OrderValue>,
+    pub id: PointIdType,
+}
+
+impl From<(OrderValue, PointIdType)> for OrderedPoint {
+    fn from((value, id): (OrderValue, PointIdType)) -> Self {
+        Self {
+            value,
+            then_by: Vec::new(),
+            id,
+        }
+    }
+}
+
+/// Position of a point in an ordered scroll.
+///
+/// Encoded into an opaque string, which is returned to the user as a `next_page_cursor`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderByCursor {
+    pub value: OrderValue,
+    /// Values of the `then_by` keys, empty if there are none
+    pub then_by: Vec<OrderValue>,
+    pub id: PointIdType,
+}
+
+impl From<OrderedPoint> for OrderByCursor {
+    fn from(point: OrderedPoint) -> Self {
+        Self {
+            value: point.value,
+            then_by: point.then_by,
+            id: point.id,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct OrderByCursorRepr {
+    v: serde_json::Value,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    t: Vec<serde_json::Value>,
+    i: PointIdType,
+}
+
+impl OrderByCursor {
+    pub fn encode(&self) -> String {
+        let repr = OrderByCursorRepr {
+            v: self.value.into(),
+            t: self.then_by.iter().copied().map(Into::into).collect(),
+            i: self.id,
+        };
+        // Serialization of plain numbers and ids can't fail
+        let bytes = serde_json::to_vec(&repr).unwrap_or_default();
+        bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+
+    pub fn decode(cursor: &str) -> Result<Self, String> {
+        let invalid = || format!("Invalid scroll cursor: {cursor}");
+
+        if cursor.len() % 2 != 0 || !cursor.is_ascii() {
+            return Err(invalid());
+        }
+
+        let bytes = (0..cursor.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&cursor[i..i + 2], 16))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| invalid())?;
+
+        let repr: OrderByCursorRepr = serde_json::from_slice(&bytes).map_err(|_| invalid())?;
+
+        Ok(Self {
+            value: OrderValue::try_from(repr.v).map_err(|_| invalid())?,
+            then_by: repr
+                .t
+                .into_iter()
+                .map(OrderValue::try_from)
+                .collect::<Result<_, _>>()
+                .map_err(|_| invalid())?,
+            id: repr.i,
+        })
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, JsonSchema)]
 #[serde(untagged)]
 pub enum OrderValue {
@@ -228,7 +526,115 @@ impl Ord for OrderValue {
 mod tests {
     use proptest::proptest;
 
-    use crate::data_types::order_by::OrderValue;
+    use crate::data_types::order_by::{
+        Direction, OrderBy, OrderByCursor, OrderValue, OrderedPoint, StartFrom, ThenBy,
+    };
+    use crate::types::{ExtendedPointId, Range, RangeInterface};
+
+    #[test]
+    fn test_cursor_roundtrip() {
+        let cursors = [
+            OrderByCursor {
+                value: OrderValue::Int(-42),
+                then_by: vec![],
+                id: ExtendedPointId::NumId(7),
+            },
+            OrderByCursor {
+                value: OrderValue::Float(1.5),
+                then_by: vec![],
+                id: ExtendedPointId::Uuid(uuid::Uuid::nil()),
+            },
+            OrderByCursor {
+                value: OrderValue::Int(3),
+                then_by: vec![OrderValue::Float(0.5), OrderValue::Int(-1)],
+                id: ExtendedPointId::NumId(1),
+            },
+        ];
+
+        for cursor in cursors {
+            let encoded = cursor.encode();
+            assert_eq!(OrderByCursor::decode(&encoded).unwrap(), cursor);
+        }
+
+        assert!(OrderByCursor::decode("not a cursor").is_err());
+        assert!(OrderByCursor::decode("7b7d").is_err());
+    }
+
+    #[test]
+    fn test_then_by_ordering() {
+        let order_by = OrderBy {
+            key: "priority".parse().unwrap(),
+            direction: Some(Direction::Desc),
+            start_from: None,
+            geo: None,
+            then_by: Some(vec![ThenBy {
+                key: "created_at".parse().unwrap(),
+                direction: Some(Direction::Asc),
+            }]),
+            start_after_id: None,
+            start_after_then_by: vec![],
+        };
+
+        let point = |priority: i64, created_at: i64, id: u64| OrderedPoint {
+            value: OrderValue::Int(priority),
+            then_by: vec![OrderValue::Int(created_at)],
+            id: ExtendedPointId::NumId(id),
+        };
+
+        let mut points = vec![
+            point(1, 10, 1),
+            point(2, 20, 2),
+            point(2, 10, 3),
+            point(2, 10, 4),
+            point(1, 5, 5),
+        ];
+        order_by.sort_points(&mut points);
+
+        let ids: Vec<_> = points.iter().map(|point| point.id).collect();
+        let expected: Vec<_> = [4, 3, 2, 5, 1].map(ExtendedPointId::NumId).to_vec();
+        assert_eq!(ids, expected);
+    }
+
+    #[test]
+    fn test_integer_start_from_is_exact() {
+        let value = (1 << f64::MANTISSA_DIGITS) + 1;
+        let order_by = OrderBy {
+            key: "counter".parse().unwrap(),
+            direction: Some(Direction::Desc),
+            start_from: Some(StartFrom::Integer(value)),
+            geo: None,
+            then_by: None,
+            start_after_id: None,
+            start_after_then_by: vec![],
+        };
+        assert_eq!(
+            order_by.as_range(),
+            RangeInterface::BigInt(Range {
+                lte: Some(value.into()),
+                gt: None,
+                gte: None,
+                lt: None,
+            }),
+        );
+    }
+
+    #[test]
+    fn test_truncate_with_ties() {
+        let mut values_ids: Vec<_> = [1, 2, 2, 2, 3]
+            .into_iter()
+            .zip(1..)
+            .map(|(value, id)| (OrderValue::Int(value), ExtendedPointId::NumId(id)))
+            .collect();
+
+        OrderBy::truncate_with_ties(&mut values_ids, 6);
+        assert_eq!(values_ids.len(), 5);
+        OrderBy::truncate_with_ties(&mut values_ids, 2);
+        assert_eq!(values_ids.len(), 4);
+        OrderBy::truncate_with_ties(&mut values_ids, 1);
+        assert_eq!(values_ids.len(), 1);
+        OrderBy::truncate_with_ties(&mut values_ids, 0);
+        assert!(values_ids.is_empty());
+    }
 
     proptest! {
 
@@ -4,8 +4,11 @@ use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
 use bitvec::prelude::BitSlice;
+use common::hardware_counter::HardwareCounterCell;
+use parking_lot::Mutex;
 use sparse::common::types::{DimId, DimWeight};
 
+use crate::data_types::search_plan::{SearchPlanCollector, SegmentSearchPlan, VectorSearchPlan};
 use crate::data_types::tiny_map;
 
 #[derive(Debug)]
@@ -26,6 +29,18 @@ pub struct QueryContext {
     /// Required for processing sparse vector search with `idf-dot` similarity.
     #[allow(dead_code)]
     idf: tiny_map::TinyMap<String, HashMap<DimId, usize>>,
+
+    /// Number of points, which the `idf` statistics of a vector are collected over,
+    /// if they are not collected over the searched segments.
+    idf_point_counts: tiny_map::TinyMap<String, usize>,
+
+    /// Search plans of all searched segments.
+    /// Only present if the user asked to explain the search.
+    search_plans: Option<Arc<Mutex<Vec<SegmentSearchPlan>>>>,
+
+    /// Accumulates hardware usage of the search.
+    /// Disabled, unless the user asked to report the usage.
+    hardware_counter: HardwareCounterCell,
 }
 
 impl QueryContext {
@@ -35,6 +50,9 @@ impl QueryContext {
             search_optimized_threshold_kb,
             is_stopped: Arc::new(AtomicBool::new(false)),
             idf: tiny_map::TinyMap::new(),
+            idf_point_counts: tiny_map::TinyMap::new(),
+            search_plans: None,
+            hardware_counter: HardwareCounterCell::disabled(),
         }
     }
 
@@ -47,6 +65,24 @@ impl QueryContext {
         self
     }
 
+    pub fn with_search_plans(mut self, search_plans: Arc<Mutex<Vec<SegmentSearchPlan>>>) -> Self {
+        self.search_plans = Some(search_plans);
+        self
+    }
+
+    pub fn search_plans(&self) -> Option<&Mutex<Vec<SegmentSearchPlan>>> {
+        self.search_plans.as_deref()
+    }
+
+    pub fn with_hardware_counter(mut self, hardware_counter: HardwareCounterCell) -> Self {
+        self.hardware_counter = hardware_counter;
+        self
+    }
+
+    pub fn hardware_counter(&self) -> &HardwareCounterCell {
+        &self.hardware_counter
+    }
+
     pub fn available_point_count(&self) -> usize {
         self.available_point_count
     }
@@ -79,10 +115,29 @@ impl QueryContext {
         &mut self.idf
     }
 
+    /// Use externally collected statistics for `idf-dot` similarity of the vector,
+    /// instead of the statistics of the searched segments.
+    pub fn use_shared_idf(
+        &mut self,
+        vector_name: &str,
+        points_count: usize,
+        document_frequencies: &HashMap<DimId, usize>,
+    ) {
+        let Some(idf) = self.idf.get_mut(vector_name) else {
+            return;
+        };
+        for (index, count) in idf.iter_mut() {
+            *count = document_frequencies.get(index).copied().unwrap_or(0);
+        }
+        self.idf_point_counts
+            .insert(vector_name.to_string(), points_count);
+    }
+
     pub fn get_segment_query_context(&self) -> SegmentQueryContext {
         SegmentQueryContext {
             query_context: Some(self),
             deleted_points: None,
+            search_plans: None,
         }
     }
 }
@@ -98,6 +153,7 @@ impl Default for QueryContext {
 pub struct SegmentQueryContext<'a> {
     query_context: Option<&'a QueryContext>,
     deleted_points: Option<&'a BitSlice>,
+    search_plans: Option<&'a SearchPlanCollector>,
 }
 
 impl<'a> SegmentQueryContext<'a> {
@@ -105,14 +161,22 @@ impl<'a> SegmentQueryContext<'a> {
         if let Some(query_context) = self.query_context {
             VectorQueryContext {
                 available_point_count: query_context.available_point_count,
+                idf_point_count: query_context
+                    .idf_point_counts
+                    .get(vector_name)
+                    .copied()
+                    .unwrap_or(query_context.available_point_count),
                 search_optimized_threshold_kb: query_context.search_optimized_threshold_kb,
                 is_stopped: Some(&query_context.is_stopped),
                 idf: query_context.idf.get(vector_name),
                 deleted_points: self.deleted_points,
+                search_plans: self.search_plans,
+                hardware_counter: query_context.hardware_counter.clone(),
             }
         } else {
             VectorQueryContext {
                 deleted_points: self.deleted_points,
+                search_plans: self.search_plans,
                 ..Default::default()
             }
         }
@@ -122,6 +186,12 @@ impl<'a> SegmentQueryContext<'a> {
         self.deleted_points = Some(deleted_points);
         self
     }
+
+    /// Make vector indexes report the chosen search strategy into the collector
+    pub fn with_search_plans(mut self, search_plans: &'a SearchPlanCollector) -> Self {
+        self.search_plans = Some(search_plans);
+        self
+    }
 }
 
 /// Query context related to a specific vector
@@ -130,6 +200,9 @@ pub struct VectorQueryContext<'a> {
     /// Total amount of available points in the segment.
     available_point_count: usize,
 
+    /// Number of points, which the `idf` statistics are collected over.
+    idf_point_count: usize,
+
     /// Parameter, which defines how big a plain segment can be to be considered
     /// small enough to be searched with `indexed_only` option.
     search_optimized_threshold_kb: usize,
@@ -139,6 +212,10 @@ pub struct VectorQueryContext<'a> {
     idf: Option<&'a HashMap<DimId, usize>>,
 
     deleted_points: Option<&'a BitSlice>,
+
+    search_plans: Option<&'a SearchPlanCollector>,
+
+    hardware_counter: HardwareCounterCell,
 }
 
 pub enum SimpleCow<'a, T> {
@@ -185,7 +262,7 @@ impl VectorQueryContext<'_> {
 
     pub fn remap_idf_weights(&self, indices: &[DimId], weights: &mut [DimWeight]) {
         // Number of documents
-        let n = self.available_point_count as DimWeight;
+        let n = self.idf_point_count as DimWeight;
         for (weight, index) in weights.iter_mut().zip(indices) {
             // Document frequency
             let df = self
@@ -201,16 +278,34 @@ impl VectorQueryContext<'_> {
     pub fn is_require_idf(&self) -> bool {
         self.idf.is_some()
     }
+
+    /// Whether vector index should report its search plan
+    pub fn is_explain_enabled(&self) -> bool {
+        self.search_plans.is_some()
+    }
+
+    pub fn record_search_plan(&self, plan: VectorSearchPlan) {
+        if let Some(search_plans) = self.search_plans {
+            search_plans.record(plan);
+        }
+    }
+
+    pub fn hardware_counter(&self) -> &HardwareCounterCell {
+        &self.hardware_counter
+    }
 }
 
 impl Default for VectorQueryContext<'_> {
     fn default() -> Self {
         VectorQueryContext {
             available_point_count: 0,
+            idf_point_count: 0,
             search_optimized_threshold_kb: usize::MAX,
             is_stopped: None,
             idf: None,
             deleted_points: None,
+            search_plans: None,
+            hardware_counter: HardwareCounterCell::disabled(),
         }
     }
 }
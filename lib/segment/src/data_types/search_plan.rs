@@ -0,0 +1,129 @@
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::index::field_index::{CardinalityEstimation, PrimaryCondition};
+use crate::types::SegmentType;
+
+/// Strategy, chosen by a vector index to execute a search
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchStrategy {
+    /// Exact search over all vectors, requested explicitly
+    Exact,
+    /// Score every available point of the segment
+    FullScan,
+    /// Score only the points selected by the filter, using payload indexes where possible
+    FilteredScan,
+    /// Traverse the HNSW graph, filter is checked during traversal using payload block links
+    Hnsw,
+    /// Search the inverted index of sparse vectors
+    SparseIndex,
+}
+
+/// Number of points, expected to match the filter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct CardinalityBounds {
+    pub min: usize,
+    pub exp: usize,
+    pub max: usize,
+}
+
+/// Describes how a vector index executed a search for a batch of query vectors
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct VectorSearchPlan {
+    pub strategy: SearchStrategy,
+    /// Filter conditions, which were resolved through payload field indexes
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub indexed_conditions: Vec<String>,
+    /// Filter cardinality, estimated from payload indexes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_cardinality: Option<CardinalityBounds>,
+    /// If the estimation was not precise enough, and strategy was chosen by sampling points
+    pub sampled: bool,
+    /// Number of points, which actually matched the filter. Only known for scan strategies.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actual_cardinality: Option<usize>,
+    /// Time spent on estimating cardinality and choosing the strategy, in seconds
+    pub planning_time: f64,
+}
+
+impl VectorSearchPlan {
+    pub fn new(strategy: SearchStrategy) -> Self {
+        Self {
+            strategy,
+            indexed_conditions: vec![],
+            estimated_cardinality: None,
+            sampled: false,
+            actual_cardinality: None,
+            planning_time: 0.0,
+        }
+    }
+
+    pub fn with_estimation(mut self, estimation: &CardinalityEstimation) -> Self {
+        self.indexed_conditions = estimation
+            .primary_clauses
+            .iter()
+            .map(describe_primary_condition)
+            .collect();
+        self.estimated_cardinality = Some(CardinalityBounds {
+            min: estimation.min,
+            exp: estimation.exp,
+            max: estimation.max,
+        });
+        self
+    }
+}
+
+fn describe_primary_condition(condition: &PrimaryCondition) -> String {
+    match condition {
+        PrimaryCondition::Condition(field_condition) => field_condition.key.to_string(),
+        PrimaryCondition::IsEmpty(is_empty) => format!("is_empty({})", is_empty.is_empty.key),
+        PrimaryCondition::IsNull(is_null) => format!("is_null({})", is_null.is_null.key),
+        PrimaryCondition::Ids(_) => "has_id".to_string(),
+    }
+}
+
+/// Search plan of a batch of searches in a single segment
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct SegmentSearchPlan {
+    /// Name of the segment directory
+    pub segment: String,
+    pub segment_type: SegmentType,
+    pub available_points: usize,
+    pub vector_name: String,
+    /// Number of query vectors, searched together in this batch
+    pub batch_size: usize,
+    /// Number of points requested from the segment, might be lower than the limit due to sampling
+    pub top: usize,
+    pub plans: Vec<VectorSearchPlan>,
+    /// Total time spent on searching the segment, in seconds
+    pub search_time: f64,
+}
+
+/// Search plans of all segments of a single shard
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct ShardSearchPlan {
+    pub shard_id: u32,
+    /// Plans of each searched segment.
+    /// Not available if the shard was searched on a remote peer.
+    pub segments: Option<Vec<SegmentSearchPlan>>,
+}
+
+/// Collects search plans from vector indexes of a single segment.
+///
+/// Only attached to the query context if the user asked to explain the search.
+#[derive(Debug, Default)]
+pub struct SearchPlanCollector {
+    plans: Mutex<Vec<VectorSearchPlan>>,
+}
+
+impl SearchPlanCollector {
+    pub fn record(&self, plan: VectorSearchPlan) {
+        self.plans.lock().push(plan);
+    }
+
+    pub fn into_plans(self) -> Vec<VectorSearchPlan> {
+        self.plans.into_inner()
+    }
+}
@@ -1,20 +1,23 @@
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
+use chrono::{DateTime, Utc};
 use common::types::TelemetryDetail;
+use sparse::common::types::DimId;
 
 use crate::common::operation_error::{OperationResult, SegmentFailedState};
+use crate::data_types::aggregation::{DistinctValues, NumericStats, ValueDistribution};
 use crate::data_types::named_vectors::NamedVectors;
-use crate::data_types::order_by::{OrderBy, OrderValue};
+use crate::data_types::order_by::{OrderBy, OrderedPoint};
 use crate::data_types::query_context::{QueryContext, SegmentQueryContext};
 use crate::data_types::vectors::{QueryVector, Vector};
 use crate::index::field_index::CardinalityEstimation;
 use crate::json_path::JsonPath;
 use crate::telemetry::SegmentTelemetry;
 use crate::types::{
-    Filter, Payload, PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef, PointIdType,
-    ScoredPoint, SearchParams, SegmentConfig, SegmentInfo, SegmentType, SeqNumberType, WithPayload,
-    WithVector,
+    AttributeValue, Filter, Payload, PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef,
+    PayloadSelector, PointIdType, ScoredPoint, SearchParams, SegmentConfig, SegmentInfo,
+    SegmentType, SeqNumberType, WithPayload, WithVector,
 };
 
 /// Define all operations which can be performed with Segment or Segment-like entity.
@@ -83,6 +86,14 @@ pub trait SegmentEntry {
         full_payload: &Payload,
     ) -> OperationResult<bool>;
 
+    /// Apply JSON Merge Patch (RFC 7396) to the payload of the point
+    fn patch_payload(
+        &mut self,
+        op_num: SeqNumberType,
+        point_id: PointIdType,
+        patch: &Payload,
+    ) -> OperationResult<bool>;
+
     fn delete_payload(
         &mut self,
         op_num: SeqNumberType,
@@ -96,6 +107,18 @@ pub trait SegmentEntry {
         point_id: PointIdType,
     ) -> OperationResult<bool>;
 
+    /// Set scalar attributes of the point, `None` values remove the attribute
+    fn set_attributes(
+        &mut self,
+        op_num: SeqNumberType,
+        point_id: PointIdType,
+        attributes: &HashMap<String, Option<AttributeValue>>,
+    ) -> OperationResult<bool>;
+
+    /// Retrieve all scalar attributes of the point
+    fn attributes(&self, point_id: PointIdType)
+        -> OperationResult<HashMap<String, AttributeValue>>;
+
     fn vector(&self, vector_name: &str, point_id: PointIdType) -> OperationResult<Option<Vector>>;
 
     fn all_vectors(&self, point_id: PointIdType) -> OperationResult<NamedVectors>;
@@ -104,6 +127,14 @@ pub trait SegmentEntry {
     /// If not found, return empty payload
     fn payload(&self, point_id: PointIdType) -> OperationResult<Payload>;
 
+    /// Retrieve payload for the point, processed with the selector
+    /// Only the fields, required by the selector, are read from the storage
+    fn selected_payload(
+        &self,
+        point_id: PointIdType,
+        selector: &PayloadSelector,
+    ) -> OperationResult<Payload>;
+
     /// Iterator over all points in segment in ascending order.
     fn iter_points(&self) -> Box<dyn Iterator<Item = PointIdType> + '_>;
 
@@ -117,6 +148,12 @@ pub trait SegmentEntry {
 
     /// Return points which satisfies filtering condition ordered by the `order_by.key` field,
     /// starting with `order_by.start_from` value including.
+    /// If `order_by.start_after_id` is set, points with the `start_from` value are only returned
+    /// if their id comes after it.
+    ///
+    /// Points sharing the value of the last point are all included, so the result may exceed `limit`.
+    /// If `order_by.then_by` is set, ties are ordered by the `then_by` keys instead, and exactly
+    /// `limit` first points are returned.
     ///
     /// Will fail if there is no index for the order_by key.
     fn read_ordered_filtered<'a>(
@@ -124,7 +161,7 @@ pub trait SegmentEntry {
         limit: Option<usize>,
         filter: Option<&'a Filter>,
         order_by: &'a OrderBy,
-    ) -> OperationResult<Vec<(OrderValue, PointIdType)>>;
+    ) -> OperationResult<Vec<OrderedPoint>>;
 
     /// Read points in [from; to) range
     fn read_range(&self, from: Option<PointIdType>, to: Option<PointIdType>) -> Vec<PointIdType>;
@@ -135,6 +172,30 @@ pub trait SegmentEntry {
     /// Estimate available point count in this segment for given filter.
     fn estimate_point_count<'a>(&'a self, filter: Option<&'a Filter>) -> CardinalityEstimation;
 
+    /// Distinct values of the payload field among the points, matching the filter.
+    ///
+    /// Values are exact if the field has a keyword or integer map index,
+    /// otherwise they are read from payload into a sketch.
+    fn distinct_values(
+        &self,
+        key: &JsonPath,
+        filter: Option<&Filter>,
+    ) -> OperationResult<DistinctValues>;
+
+    /// Approximate distribution of the values of the numeric payload field.
+    ///
+    /// Read from the histogram of the range index, will fail if there is no such index.
+    fn value_distribution(&self, key: &JsonPath) -> OperationResult<ValueDistribution>;
+
+    /// Statistics of the values of the numeric payload field among the points, matching the filter.
+    ///
+    /// Values are read from the range index, will fail if there is no such index.
+    fn numeric_stats(
+        &self,
+        key: &JsonPath,
+        filter: Option<&Filter>,
+    ) -> OperationResult<NumericStats>;
+
     fn vector_names(&self) -> HashSet<String>;
 
     /// Number of available points
@@ -163,12 +224,23 @@ pub trait SegmentEntry {
     /// Get current stats of the segment
     fn info(&self) -> SegmentInfo;
 
+    /// Get current stats of the segment, with its RAM and disk usage
+    ///
+    /// This method performs blocking IO to measure the files of the segment.
+    fn size_info(&self) -> SegmentInfo;
+
     /// Get segment configuration
     fn config(&self) -> &SegmentConfig;
 
     /// Get current stats of the segment
     fn is_appendable(&self) -> bool;
 
+    /// Check if the named vector of points can be updated in place, without moving points out
+    ///
+    /// Besides [`SegmentConfig::is_vector_appendable`], the HNSW index may not accept more
+    /// stale points.
+    fn is_vector_appendable(&self, vector_name: &str) -> bool;
+
     /// Flushes current segment state into a persistent storage, if possible
     /// if sync == true, block current thread while flushing
     ///
@@ -181,6 +253,9 @@ pub trait SegmentEntry {
     /// Path to data, owned by segment
     fn data_path(&self) -> PathBuf;
 
+    /// When the segment was created, unknown for segments of older versions
+    fn created_at(&self) -> Option<DateTime<Utc>>;
+
     /// Delete field index, if exists
     fn delete_field_index(
         &mut self,
@@ -220,4 +295,12 @@ pub trait SegmentEntry {
     fn get_telemetry_data(&self, detail: TelemetryDetail) -> SegmentTelemetry;
 
     fn fill_query_context(&self, query_context: &mut QueryContext);
+
+    /// Add the number of vectors, which contain each of the dimensions of the sparse vector,
+    /// to `frequencies`. Returns the number of available points in the segment.
+    fn fill_sparse_document_frequencies(
+        &self,
+        vector_name: &str,
+        frequencies: &mut HashMap<DimId, usize>,
+    ) -> usize;
 }
@@ -142,12 +142,21 @@ where
     }
 
     pub fn get_raw_scorer(&self, query: DenseVector) -> OperationResult<Box<dyn RawScorer + '_>> {
+        self.get_stoppable_raw_scorer(query, &DEFAULT_STOPPED)
+    }
+
+    pub fn get_stoppable_raw_scorer<'a>(
+        &'a self,
+        query: DenseVector,
+        is_stopped: &'a AtomicBool,
+    ) -> OperationResult<Box<dyn RawScorer + 'a>> {
         let query = TMetric::preprocess(query).into();
         raw_scorer_impl(
             query,
             self,
+            self.distance(),
             self.deleted_vector_bitslice(),
-            &DEFAULT_STOPPED,
+            is_stopped,
         )
     }
 }
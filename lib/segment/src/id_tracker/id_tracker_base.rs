@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use bitvec::prelude::BitSlice;
 use common::types::PointOffsetType;
 use rand::rngs::StdRng;
@@ -5,6 +7,7 @@ use rand::{Rng, SeedableRng};
 
 use crate::common::operation_error::OperationResult;
 use crate::common::Flusher;
+use crate::id_tracker::mmap_id_tracker::MmapIdTracker;
 use crate::id_tracker::simple_id_tracker::SimpleIdTracker;
 use crate::types::{PointIdType, SeqNumberType};
 
@@ -142,6 +145,9 @@ pub trait IdTracker {
     /// It might happen that point doesn't have version due to un-flushed WAL.
     /// This method makes those points usable again.
     fn cleanup_versions(&mut self) -> OperationResult<()>;
+
+    /// Files, which persist the tracker, if it is not stored in RocksDB
+    fn files(&self) -> Vec<PathBuf>;
 }
 
 pub type IdTrackerSS = dyn IdTracker + Sync + Send;
@@ -149,6 +155,7 @@ pub type IdTrackerSS = dyn IdTracker + Sync + Send;
 pub enum IdTrackerEnum {
     MutableIdTracker(SimpleIdTracker),
     ImmutableIdTracker(SimpleIdTracker),
+    MmapIdTracker(MmapIdTracker),
 }
 
 impl IdTracker for IdTrackerEnum {
@@ -158,6 +165,7 @@ impl IdTracker for IdTrackerEnum {
             IdTrackerEnum::ImmutableIdTracker(id_tracker) => {
                 id_tracker.internal_version(internal_id)
             }
+            IdTrackerEnum::MmapIdTracker(id_tracker) => id_tracker.internal_version(internal_id),
         }
     }
 
@@ -173,6 +181,9 @@ impl IdTracker for IdTrackerEnum {
             IdTrackerEnum::ImmutableIdTracker(id_tracker) => {
                 id_tracker.set_internal_version(internal_id, version)
             }
+            IdTrackerEnum::MmapIdTracker(id_tracker) => {
+                id_tracker.set_internal_version(internal_id, version)
+            }
         }
     }
 
@@ -180,6 +191,7 @@ impl IdTracker for IdTrackerEnum {
         match self {
             IdTrackerEnum::MutableIdTracker(id_tracker) => id_tracker.internal_id(external_id),
             IdTrackerEnum::ImmutableIdTracker(id_tracker) => id_tracker.internal_id(external_id),
+            IdTrackerEnum::MmapIdTracker(id_tracker) => id_tracker.internal_id(external_id),
         }
     }
 
@@ -187,6 +199,7 @@ impl IdTracker for IdTrackerEnum {
         match self {
             IdTrackerEnum::MutableIdTracker(id_tracker) => id_tracker.external_id(internal_id),
             IdTrackerEnum::ImmutableIdTracker(id_tracker) => id_tracker.external_id(internal_id),
+            IdTrackerEnum::MmapIdTracker(id_tracker) => id_tracker.external_id(internal_id),
         }
     }
 
@@ -202,6 +215,9 @@ impl IdTracker for IdTrackerEnum {
             IdTrackerEnum::ImmutableIdTracker(id_tracker) => {
                 id_tracker.set_link(external_id, internal_id)
             }
+            IdTrackerEnum::MmapIdTracker(id_tracker) => {
+                id_tracker.set_link(external_id, internal_id)
+            }
         }
     }
 
@@ -209,6 +225,7 @@ impl IdTracker for IdTrackerEnum {
         match self {
             IdTrackerEnum::MutableIdTracker(id_tracker) => id_tracker.drop(external_id),
             IdTrackerEnum::ImmutableIdTracker(id_tracker) => id_tracker.drop(external_id),
+            IdTrackerEnum::MmapIdTracker(id_tracker) => id_tracker.drop(external_id),
         }
     }
 
@@ -216,6 +233,7 @@ impl IdTracker for IdTrackerEnum {
         match self {
             IdTrackerEnum::MutableIdTracker(id_tracker) => id_tracker.iter_external(),
             IdTrackerEnum::ImmutableIdTracker(id_tracker) => id_tracker.iter_external(),
+            IdTrackerEnum::MmapIdTracker(id_tracker) => id_tracker.iter_external(),
         }
     }
 
@@ -223,6 +241,7 @@ impl IdTracker for IdTrackerEnum {
         match self {
             IdTrackerEnum::MutableIdTracker(id_tracker) => id_tracker.iter_internal(),
             IdTrackerEnum::ImmutableIdTracker(id_tracker) => id_tracker.iter_internal(),
+            IdTrackerEnum::MmapIdTracker(id_tracker) => id_tracker.iter_internal(),
         }
     }
 
@@ -233,6 +252,7 @@ impl IdTracker for IdTrackerEnum {
         match self {
             IdTrackerEnum::MutableIdTracker(id_tracker) => id_tracker.iter_from(external_id),
             IdTrackerEnum::ImmutableIdTracker(id_tracker) => id_tracker.iter_from(external_id),
+            IdTrackerEnum::MmapIdTracker(id_tracker) => id_tracker.iter_from(external_id),
         }
     }
 
@@ -240,6 +260,7 @@ impl IdTracker for IdTrackerEnum {
         match self {
             IdTrackerEnum::MutableIdTracker(id_tracker) => id_tracker.iter_ids(),
             IdTrackerEnum::ImmutableIdTracker(id_tracker) => id_tracker.iter_ids(),
+            IdTrackerEnum::MmapIdTracker(id_tracker) => id_tracker.iter_ids(),
         }
     }
 
@@ -247,6 +268,7 @@ impl IdTracker for IdTrackerEnum {
         match self {
             IdTrackerEnum::MutableIdTracker(id_tracker) => id_tracker.mapping_flusher(),
             IdTrackerEnum::ImmutableIdTracker(id_tracker) => id_tracker.mapping_flusher(),
+            IdTrackerEnum::MmapIdTracker(id_tracker) => id_tracker.mapping_flusher(),
         }
     }
 
@@ -254,6 +276,7 @@ impl IdTracker for IdTrackerEnum {
         match self {
             IdTrackerEnum::MutableIdTracker(id_tracker) => id_tracker.versions_flusher(),
             IdTrackerEnum::ImmutableIdTracker(id_tracker) => id_tracker.versions_flusher(),
+            IdTrackerEnum::MmapIdTracker(id_tracker) => id_tracker.versions_flusher(),
         }
     }
 
@@ -261,6 +284,7 @@ impl IdTracker for IdTrackerEnum {
         match self {
             IdTrackerEnum::MutableIdTracker(id_tracker) => id_tracker.total_point_count(),
             IdTrackerEnum::ImmutableIdTracker(id_tracker) => id_tracker.total_point_count(),
+            IdTrackerEnum::MmapIdTracker(id_tracker) => id_tracker.total_point_count(),
         }
     }
 
@@ -268,6 +292,7 @@ impl IdTracker for IdTrackerEnum {
         match self {
             IdTrackerEnum::MutableIdTracker(id_tracker) => id_tracker.deleted_point_count(),
             IdTrackerEnum::ImmutableIdTracker(id_tracker) => id_tracker.deleted_point_count(),
+            IdTrackerEnum::MmapIdTracker(id_tracker) => id_tracker.deleted_point_count(),
         }
     }
 
@@ -275,6 +300,7 @@ impl IdTracker for IdTrackerEnum {
         match self {
             IdTrackerEnum::MutableIdTracker(id_tracker) => id_tracker.deleted_point_bitslice(),
             IdTrackerEnum::ImmutableIdTracker(id_tracker) => id_tracker.deleted_point_bitslice(),
+            IdTrackerEnum::MmapIdTracker(id_tracker) => id_tracker.deleted_point_bitslice(),
         }
     }
 
@@ -284,6 +310,7 @@ impl IdTracker for IdTrackerEnum {
             IdTrackerEnum::ImmutableIdTracker(id_tracker) => {
                 id_tracker.is_deleted_point(internal_id)
             }
+            IdTrackerEnum::MmapIdTracker(id_tracker) => id_tracker.is_deleted_point(internal_id),
         }
     }
 
@@ -291,6 +318,15 @@ impl IdTracker for IdTrackerEnum {
         match self {
             IdTrackerEnum::MutableIdTracker(id_tracker) => id_tracker.cleanup_versions(),
             IdTrackerEnum::ImmutableIdTracker(id_tracker) => id_tracker.cleanup_versions(),
+            IdTrackerEnum::MmapIdTracker(id_tracker) => id_tracker.cleanup_versions(),
+        }
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        match self {
+            IdTrackerEnum::MutableIdTracker(id_tracker) => id_tracker.files(),
+            IdTrackerEnum::ImmutableIdTracker(id_tracker) => id_tracker.files(),
+            IdTrackerEnum::MmapIdTracker(id_tracker) => id_tracker.files(),
         }
     }
 }
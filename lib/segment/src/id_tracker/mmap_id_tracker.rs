@@ -0,0 +1,547 @@
+//! Id tracker, persisted in plain files instead of RocksDB.
+//!
+//! Changes of the mapping between external and internal ids are appended to a log, which is read
+//! through mmap and replayed on load. Versions are stored as a dense array, indexed by internal
+//! id, and are overwritten in place. Files are only written by flushers, there are no background
+//! compactions, and loading is a single sequential read of each file.
+
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{Seek, SeekFrom, Write};
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use bitvec::prelude::{BitSlice, BitVec};
+use common::types::PointOffsetType;
+use memory::mmap_ops::open_read_mmap;
+use parking_lot::Mutex;
+use uuid::Uuid;
+
+use crate::common::append_file::{append_synced, truncate_to_multiple};
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::common::Flusher;
+use crate::id_tracker::simple_id_tracker::SimpleIdTracker;
+use crate::id_tracker::IdTracker;
+use crate::types::{PointIdType, SeqNumberType};
+
+const MAPPINGS_FILE: &str = "mappings.dat";
+const VERSIONS_FILE: &str = "versions.dat";
+
+/// Kind of change, internal id, and external id as 16 little endian bytes
+const MAPPING_ENTRY_SIZE: usize = 1 + 4 + 16;
+
+const VERSION_SIZE: usize = mem::size_of::<SeqNumberType>();
+
+/// The mapping log is rewritten on load, if it has this many entries per available point
+const COMPACTION_RATIO: usize = 2;
+
+/// External id of deleted internal ids
+const DELETED_EXTERNAL_ID: PointIdType = PointIdType::NumId(u64::MAX);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MappingChange {
+    Link(PointIdType, PointOffsetType),
+    Drop(PointIdType, PointOffsetType),
+}
+
+impl MappingChange {
+    fn encode(self, buffer: &mut Vec<u8>) {
+        let (is_drop, external_id, internal_id) = match self {
+            MappingChange::Link(external_id, internal_id) => (false, external_id, internal_id),
+            MappingChange::Drop(external_id, internal_id) => (true, external_id, internal_id),
+        };
+        let (is_uuid, id) = match external_id {
+            PointIdType::NumId(idx) => (false, u128::from(idx)),
+            PointIdType::Uuid(uuid) => (true, uuid.as_u128()),
+        };
+        buffer.push((u8::from(is_drop) << 1) | u8::from(is_uuid));
+        buffer.extend_from_slice(&internal_id.to_le_bytes());
+        buffer.extend_from_slice(&id.to_le_bytes());
+    }
+
+    fn decode(entry: &[u8]) -> OperationResult<Self> {
+        let corrupted = || OperationError::service_error("Corrupted id tracker mapping entry");
+
+        let kind = entry[0];
+        let internal_id = PointOffsetType::from_le_bytes(entry[1..5].try_into().unwrap());
+        let id = u128::from_le_bytes(entry[5..MAPPING_ENTRY_SIZE].try_into().unwrap());
+        let external_id = if kind & 1 == 0 {
+            PointIdType::NumId(u64::try_from(id).map_err(|_| corrupted())?)
+        } else {
+            PointIdType::Uuid(Uuid::from_u128(id))
+        };
+        match kind >> 1 {
+            0 => Ok(MappingChange::Link(external_id, internal_id)),
+            1 => Ok(MappingChange::Drop(external_id, internal_id)),
+            _ => Err(corrupted()),
+        }
+    }
+}
+
+pub struct MmapIdTracker {
+    dir: PathBuf,
+    deleted: BitVec,
+    internal_to_external: Vec<PointIdType>,
+    internal_to_version: Vec<SeqNumberType>,
+    external_to_internal_num: BTreeMap<u64, PointOffsetType>,
+    external_to_internal_uuid: BTreeMap<Uuid, PointOffsetType>,
+    /// Encoded mapping changes, not flushed yet
+    pending_mappings: Arc<Mutex<Vec<u8>>>,
+    /// Versions, not flushed yet
+    pending_versions: Arc<Mutex<BTreeMap<PointOffsetType, SeqNumberType>>>,
+    /// Prevents concurrent flushers from writing files at the same time
+    flush_lock: Arc<Mutex<()>>,
+}
+
+impl MmapIdTracker {
+    /// Check if the tracker was persisted in the directory
+    pub fn exists(dir: &Path) -> bool {
+        dir.join(MAPPINGS_FILE).exists()
+    }
+
+    pub fn open(dir: &Path) -> OperationResult<Self> {
+        fs::create_dir_all(dir)?;
+        let mut tracker = MmapIdTracker {
+            dir: dir.to_path_buf(),
+            deleted: BitVec::new(),
+            internal_to_external: Vec::new(),
+            internal_to_version: Vec::new(),
+            external_to_internal_num: BTreeMap::new(),
+            external_to_internal_uuid: BTreeMap::new(),
+            pending_mappings: Default::default(),
+            pending_versions: Default::default(),
+            flush_lock: Default::default(),
+        };
+
+        let mappings_path = dir.join(MAPPINGS_FILE);
+        let mut entries = 0;
+        if truncate_to_multiple(&mappings_path, MAPPING_ENTRY_SIZE)? > 0 {
+            let mmap = open_read_mmap(&mappings_path)?;
+            for entry in mmap.chunks_exact(MAPPING_ENTRY_SIZE) {
+                tracker.apply(MappingChange::decode(entry)?);
+                entries += 1;
+            }
+        }
+
+        let versions_path = dir.join(VERSIONS_FILE);
+        if truncate_to_multiple(&versions_path, VERSION_SIZE)? > 0 {
+            let mmap = open_read_mmap(&versions_path)?;
+            tracker.internal_to_version = mmap
+                .chunks_exact(VERSION_SIZE)
+                .map(|version| SeqNumberType::from_le_bytes(version.try_into().unwrap()))
+                .collect();
+        }
+
+        if entries > COMPACTION_RATIO * tracker.available_point_count().max(1) {
+            tracker.compact_mappings()?;
+        }
+
+        Ok(tracker)
+    }
+
+    /// Persist ids and versions of the RocksDB based tracker in the directory.
+    ///
+    /// Files are written into a temporary directory first, so the tracker is either migrated
+    /// completely, or not at all.
+    pub fn migrate_from(simple_id_tracker: &SimpleIdTracker, dir: &Path) -> OperationResult<()> {
+        let temp_dir = dir.with_extension("migrating");
+        if temp_dir.exists() {
+            fs::remove_dir_all(&temp_dir)?;
+        }
+
+        let mut tracker = Self::open(&temp_dir)?;
+        for (external_id, internal_id) in simple_id_tracker.iter_from(None) {
+            tracker.set_link(external_id, internal_id)?;
+            if let Some(version) = simple_id_tracker.internal_version(internal_id) {
+                tracker.set_internal_version(internal_id, version)?;
+            }
+        }
+        // Keep trailing deleted points, so the number of points matches vector storages
+        let total_point_count = simple_id_tracker.total_point_count();
+        if total_point_count > tracker.total_point_count() {
+            let last_internal_id = (total_point_count - 1) as PointOffsetType;
+            tracker.push_change(MappingChange::Drop(DELETED_EXTERNAL_ID, last_internal_id));
+        }
+        tracker.mapping_flusher()()?;
+        tracker.versions_flusher()()?;
+        drop(tracker);
+
+        fs::rename(&temp_dir, dir)?;
+        Ok(())
+    }
+
+    fn apply(&mut self, change: MappingChange) {
+        match change {
+            MappingChange::Link(external_id, internal_id) => self.link(external_id, internal_id),
+            MappingChange::Drop(external_id, internal_id) => {
+                if self.internal_id(external_id) == Some(internal_id) {
+                    self.unlink(external_id);
+                }
+                self.mark_deleted(internal_id);
+            }
+        }
+    }
+
+    fn link(&mut self, external_id: PointIdType, internal_id: PointOffsetType) {
+        match external_id {
+            PointIdType::NumId(idx) => {
+                self.external_to_internal_num.insert(idx, internal_id);
+            }
+            PointIdType::Uuid(uuid) => {
+                self.external_to_internal_uuid.insert(uuid, internal_id);
+            }
+        }
+
+        let internal_id = internal_id as usize;
+        if internal_id >= self.internal_to_external.len() {
+            self.internal_to_external
+                .resize(internal_id + 1, DELETED_EXTERNAL_ID);
+        }
+        if internal_id >= self.deleted.len() {
+            self.deleted.resize(internal_id + 1, true);
+        }
+        self.internal_to_external[internal_id] = external_id;
+        self.deleted.set(internal_id, false);
+    }
+
+    fn unlink(&mut self, external_id: PointIdType) -> Option<PointOffsetType> {
+        let internal_id = match external_id {
+            PointIdType::NumId(idx) => self.external_to_internal_num.remove(&idx),
+            PointIdType::Uuid(uuid) => self.external_to_internal_uuid.remove(&uuid),
+        }?;
+        self.mark_deleted(internal_id);
+        Some(internal_id)
+    }
+
+    fn mark_deleted(&mut self, internal_id: PointOffsetType) {
+        let internal_id = internal_id as usize;
+        if internal_id >= self.internal_to_external.len() {
+            self.internal_to_external
+                .resize(internal_id + 1, DELETED_EXTERNAL_ID);
+        }
+        if internal_id >= self.deleted.len() {
+            self.deleted.resize(internal_id + 1, true);
+        }
+        self.internal_to_external[internal_id] = DELETED_EXTERNAL_ID;
+        self.deleted.set(internal_id, true);
+    }
+
+    fn push_change(&self, change: MappingChange) {
+        change.encode(&mut self.pending_mappings.lock());
+    }
+
+    /// Replace the mapping log with links of available points only
+    fn compact_mappings(&self) -> OperationResult<()> {
+        let mut entries = Vec::with_capacity(self.available_point_count() * MAPPING_ENTRY_SIZE);
+        for (external_id, internal_id) in self.iter_from(None) {
+            MappingChange::Link(external_id, internal_id).encode(&mut entries);
+        }
+        if let Some(last_internal_id) = self.internal_to_external.len().checked_sub(1) {
+            if self.deleted[last_internal_id] {
+                MappingChange::Drop(DELETED_EXTERNAL_ID, last_internal_id as PointOffsetType)
+                    .encode(&mut entries);
+            }
+        }
+
+        let temp_path = self.dir.join(MAPPINGS_FILE).with_extension("tmp");
+        if temp_path.exists() {
+            fs::remove_file(&temp_path)?;
+        }
+        append_synced(&temp_path, &entries)?;
+        fs::rename(&temp_path, self.dir.join(MAPPINGS_FILE))?;
+        Ok(())
+    }
+}
+
+impl IdTracker for MmapIdTracker {
+    fn internal_version(&self, internal_id: PointOffsetType) -> Option<SeqNumberType> {
+        self.internal_to_version.get(internal_id as usize).copied()
+    }
+
+    fn set_internal_version(
+        &mut self,
+        internal_id: PointOffsetType,
+        version: SeqNumberType,
+    ) -> OperationResult<()> {
+        if self.external_id(internal_id).is_some() {
+            if internal_id as usize >= self.internal_to_version.len() {
+                self.internal_to_version.resize(internal_id as usize + 1, 0);
+            }
+            self.internal_to_version[internal_id as usize] = version;
+            self.pending_versions.lock().insert(internal_id, version);
+        }
+        Ok(())
+    }
+
+    fn internal_id(&self, external_id: PointIdType) -> Option<PointOffsetType> {
+        match external_id {
+            PointIdType::NumId(idx) => self.external_to_internal_num.get(&idx).copied(),
+            PointIdType::Uuid(uuid) => self.external_to_internal_uuid.get(&uuid).copied(),
+        }
+    }
+
+    fn external_id(&self, internal_id: PointOffsetType) -> Option<PointIdType> {
+        if self.is_deleted_point(internal_id) {
+            return None;
+        }
+        self.internal_to_external.get(internal_id as usize).copied()
+    }
+
+    fn set_link(
+        &mut self,
+        external_id: PointIdType,
+        internal_id: PointOffsetType,
+    ) -> OperationResult<()> {
+        self.link(external_id, internal_id);
+        self.push_change(MappingChange::Link(external_id, internal_id));
+        Ok(())
+    }
+
+    fn drop(&mut self, external_id: PointIdType) -> OperationResult<()> {
+        if let Some(internal_id) = self.unlink(external_id) {
+            self.push_change(MappingChange::Drop(external_id, internal_id));
+        }
+        Ok(())
+    }
+
+    fn iter_external(&self) -> Box<dyn Iterator<Item = PointIdType> + '_> {
+        let iter_num = self
+            .external_to_internal_num
+            .keys()
+            .copied()
+            .map(PointIdType::NumId);
+        let iter_uuid = self
+            .external_to_internal_uuid
+            .keys()
+            .copied()
+            .map(PointIdType::Uuid);
+        // order is important here, we want to iterate over the u64 ids first
+        Box::new(iter_num.chain(iter_uuid))
+    }
+
+    fn iter_internal(&self) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        Box::new(
+            (0..self.internal_to_external.len() as PointOffsetType)
+                .filter(move |i| !self.deleted[*i as usize]),
+        )
+    }
+
+    fn iter_from(
+        &self,
+        external_id: Option<PointIdType>,
+    ) -> Box<dyn Iterator<Item = (PointIdType, PointOffsetType)> + '_> {
+        let iter_num = |offset: u64| {
+            self.external_to_internal_num
+                .range(offset..)
+                .map(|(k, v)| (PointIdType::NumId(*k), *v))
+        };
+        let iter_uuid = |offset: Uuid| {
+            self.external_to_internal_uuid
+                .range(offset..)
+                .map(|(k, v)| (PointIdType::Uuid(*k), *v))
+        };
+
+        match external_id {
+            // order is important here, we want to iterate over the u64 ids first
+            None => Box::new(iter_num(0).chain(iter_uuid(Uuid::nil()))),
+            // u64 ids are less than uuids, so all uuids follow
+            Some(PointIdType::NumId(idx)) => Box::new(iter_num(idx).chain(iter_uuid(Uuid::nil()))),
+            Some(PointIdType::Uuid(uuid)) => Box::new(iter_uuid(uuid)),
+        }
+    }
+
+    fn iter_ids(&self) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        self.iter_internal()
+    }
+
+    /// Appends mapping changes to the log.
+    /// This function should be called _before_ flushing versions.
+    fn mapping_flusher(&self) -> Flusher {
+        let path = self.dir.join(MAPPINGS_FILE);
+        let pending_mappings = self.pending_mappings.clone();
+        let flush_lock = self.flush_lock.clone();
+
+        Box::new(move || {
+            let _flush_guard = flush_lock.lock();
+            let entries = mem::take(&mut *pending_mappings.lock());
+            if entries.is_empty() {
+                return Ok(());
+            }
+            if let Err(err) = append_synced(&path, &entries) {
+                // Keep changes for the next flush, in front of the ones made since
+                let mut pending = pending_mappings.lock();
+                let newer = mem::replace(&mut *pending, entries);
+                pending.extend(newer);
+                return Err(err);
+            }
+            Ok(())
+        })
+    }
+
+    /// Overwrites changed versions in place.
+    /// This function should be called _after_ flushing the mapping.
+    fn versions_flusher(&self) -> Flusher {
+        let path = self.dir.join(VERSIONS_FILE);
+        let pending_versions = self.pending_versions.clone();
+        let flush_lock = self.flush_lock.clone();
+
+        Box::new(move || {
+            let _flush_guard = flush_lock.lock();
+            let versions = mem::take(&mut *pending_versions.lock());
+            if versions.is_empty() {
+                return Ok(());
+            }
+
+            let write_versions = || -> OperationResult<()> {
+                let mut file = OpenOptions::new()
+                    .create(true)
+                    .truncate(false)
+                    .write(true)
+                    .open(&path)?;
+                for (&internal_id, version) in &versions {
+                    file.seek(SeekFrom::Start(
+                        (internal_id as usize * VERSION_SIZE) as u64,
+                    ))?;
+                    file.write_all(&version.to_le_bytes())?;
+                }
+                file.sync_all()?;
+                Ok(())
+            };
+
+            if let Err(err) = write_versions() {
+                // Keep versions for the next flush, unless they were changed since
+                let mut pending = pending_versions.lock();
+                for (internal_id, version) in versions {
+                    pending.entry(internal_id).or_insert(version);
+                }
+                return Err(err);
+            }
+            Ok(())
+        })
+    }
+
+    fn total_point_count(&self) -> usize {
+        self.internal_to_external.len()
+    }
+
+    fn available_point_count(&self) -> usize {
+        self.external_to_internal_num.len() + self.external_to_internal_uuid.len()
+    }
+
+    fn deleted_point_count(&self) -> usize {
+        self.total_point_count() - self.available_point_count()
+    }
+
+    fn deleted_point_bitslice(&self) -> &BitSlice {
+        &self.deleted
+    }
+
+    fn is_deleted_point(&self, internal_id: PointOffsetType) -> bool {
+        self.deleted
+            .get(internal_id as usize)
+            .as_deref()
+            .copied()
+            .unwrap_or(true)
+    }
+
+    fn cleanup_versions(&mut self) -> OperationResult<()> {
+        let to_remove: Vec<_> = self
+            .iter_internal()
+            .filter(|&internal_id| self.internal_version(internal_id).is_none())
+            .filter_map(|internal_id| self.external_id(internal_id))
+            .collect();
+        for external_id in to_remove {
+            self.drop(external_id)?;
+            log::debug!("dropped version for point {external_id} without version");
+        }
+        Ok(())
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        vec![self.dir.join(MAPPINGS_FILE), self.dir.join(VERSIONS_FILE)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::common::rocksdb_wrapper::{open_db, DB_VECTOR_CF};
+
+    #[test]
+    fn test_mmap_id_tracker_persistence() {
+        let dir = Builder::new().prefix("id_tracker").tempdir().unwrap();
+
+        let uuid = PointIdType::Uuid(Uuid::from_u128(42));
+        let mut id_tracker = MmapIdTracker::open(dir.path()).unwrap();
+        for (internal_id, external_id) in [100.into(), uuid, 50.into(), 70.into()]
+            .into_iter()
+            .enumerate()
+        {
+            let internal_id = internal_id as PointOffsetType;
+            id_tracker.set_link(external_id, internal_id).unwrap();
+            id_tracker.set_internal_version(internal_id, 10).unwrap();
+        }
+        id_tracker.drop(50.into()).unwrap();
+        id_tracker.set_internal_version(1, 11).unwrap();
+        id_tracker.mapping_flusher()().unwrap();
+        id_tracker.versions_flusher()().unwrap();
+
+        // Not flushed changes are lost
+        id_tracker.drop(100.into()).unwrap();
+        drop(id_tracker);
+
+        let id_tracker = MmapIdTracker::open(dir.path()).unwrap();
+        assert_eq!(id_tracker.total_point_count(), 4);
+        assert_eq!(id_tracker.available_point_count(), 3);
+        assert_eq!(id_tracker.internal_id(100.into()), Some(0));
+        assert_eq!(id_tracker.internal_id(50.into()), None);
+        assert!(id_tracker.is_deleted_point(2));
+        assert_eq!(id_tracker.external_id(1), Some(uuid));
+        assert_eq!(id_tracker.internal_version(1), Some(11));
+        assert_eq!(id_tracker.internal_version(3), Some(10));
+        assert_eq!(
+            id_tracker.iter_from(None).map(|(id, _)| id).collect_vec(),
+            vec![70.into(), 100.into(), uuid],
+        );
+    }
+
+    #[test]
+    fn test_migrate_from_rocksdb() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let db = open_db(dir.path(), &[DB_VECTOR_CF]).unwrap();
+
+        let mut simple_id_tracker = SimpleIdTracker::open(db).unwrap();
+        for internal_id in 0..10 {
+            let external_id = PointIdType::NumId(1000 + u64::from(internal_id));
+            simple_id_tracker
+                .set_link(external_id, internal_id)
+                .unwrap();
+            simple_id_tracker
+                .set_internal_version(internal_id, u64::from(internal_id))
+                .unwrap();
+        }
+        simple_id_tracker.drop(1009.into()).unwrap();
+        simple_id_tracker.drop(1003.into()).unwrap();
+
+        let tracker_dir = dir.path().join("id_tracker");
+        MmapIdTracker::migrate_from(&simple_id_tracker, &tracker_dir).unwrap();
+
+        let id_tracker = MmapIdTracker::open(&tracker_dir).unwrap();
+        assert_eq!(id_tracker.total_point_count(), 10);
+        assert_eq!(id_tracker.available_point_count(), 8);
+        assert_eq!(
+            id_tracker.iter_from(None).collect_vec(),
+            simple_id_tracker.iter_from(None).collect_vec(),
+        );
+        for internal_id in id_tracker.iter_ids() {
+            assert_eq!(
+                id_tracker.internal_version(internal_id),
+                simple_id_tracker.internal_version(internal_id),
+            );
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use std::collections::BTreeMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use bincode;
@@ -405,6 +406,10 @@ impl IdTracker for SimpleIdTracker {
         }
         Ok(())
     }
+
+    fn files(&self) -> Vec<PathBuf> {
+        vec![]
+    }
 }
 
 #[cfg(test)]
@@ -1,6 +1,7 @@
 use std::fmt::Formatter;
 
 use common::types::PointOffsetType;
+use memory::mmap_ops;
 use serde_json::Value;
 use smol_str::SmolStr;
 
@@ -8,16 +9,18 @@ use super::map_index::MapIndex;
 use super::numeric_index::StreamRange;
 use crate::common::operation_error::OperationResult;
 use crate::common::Flusher;
+use crate::data_types::aggregation::{hash_integer, hash_keyword, ValueDistribution};
 use crate::data_types::order_by::OrderValue;
 use crate::index::field_index::binary_index::BinaryIndex;
 use crate::index::field_index::full_text_index::text_index::FullTextIndex;
 use crate::index::field_index::geo_index::GeoMapIndex;
+use crate::index::field_index::min_hash_index::{MinHashIndex, SimilarQuery};
 use crate::index::field_index::numeric_index::NumericIndex;
 use crate::index::field_index::{CardinalityEstimation, PayloadBlockCondition};
 use crate::telemetry::PayloadIndexTelemetry;
 use crate::types::{
-    DateTimePayloadType, FieldCondition, FloatPayloadType, IntPayloadType, Match, MatchText,
-    PayloadKeyType, RangeInterface,
+    BigIntPayloadType, DateTimePayloadType, FieldCondition, FloatPayloadType, IntPayloadType,
+    Match, MatchText, PayloadKeyType, RangeInterface,
 };
 
 pub trait PayloadFieldIndex {
@@ -102,9 +105,11 @@ pub enum FieldIndex {
     IntMapIndex(MapIndex<IntPayloadType>),
     KeywordIndex(MapIndex<SmolStr>),
     FloatIndex(NumericIndex<FloatPayloadType>),
+    BigIntIndex(NumericIndex<BigIntPayloadType>),
     GeoIndex(GeoMapIndex),
     FullTextIndex(FullTextIndex),
     BinaryIndex(BinaryIndex),
+    MinHashIndex(MinHashIndex),
 }
 
 impl std::fmt::Debug for FieldIndex {
@@ -115,9 +120,11 @@ impl std::fmt::Debug for FieldIndex {
             FieldIndex::IntMapIndex(_index) => write!(f, "IntMapIndex"),
             FieldIndex::KeywordIndex(_index) => write!(f, "KeywordIndex"),
             FieldIndex::FloatIndex(_index) => write!(f, "FloatIndex"),
+            FieldIndex::BigIntIndex(_index) => write!(f, "BigIntIndex"),
             FieldIndex::GeoIndex(_index) => write!(f, "GeoIndex"),
             FieldIndex::BinaryIndex(_index) => write!(f, "BinaryIndex"),
             FieldIndex::FullTextIndex(_index) => write!(f, "FullTextIndex"),
+            FieldIndex::MinHashIndex(_index) => write!(f, "MinHashIndex"),
         }
     }
 }
@@ -141,6 +148,7 @@ impl FieldIndex {
             FieldIndex::IntMapIndex(_) => None,
             FieldIndex::KeywordIndex(_) => None,
             FieldIndex::FloatIndex(_) => None,
+            FieldIndex::BigIntIndex(_) => None,
             FieldIndex::GeoIndex(_) => None,
             FieldIndex::BinaryIndex(_) => None,
             FieldIndex::FullTextIndex(full_text_index) => match &condition.r#match {
@@ -156,6 +164,13 @@ impl FieldIndex {
                 }
                 _ => None,
             },
+            FieldIndex::MinHashIndex(min_hash_index) => match &condition.r#match {
+                Some(Match::Similar(similar)) => {
+                    let query = SimilarQuery::new(similar);
+                    Some(min_hash_index.check_payload(&query, payload_value))
+                }
+                _ => None,
+            },
         }
     }
 
@@ -166,9 +181,11 @@ impl FieldIndex {
             FieldIndex::IntMapIndex(payload_field_index) => payload_field_index,
             FieldIndex::KeywordIndex(payload_field_index) => payload_field_index,
             FieldIndex::FloatIndex(payload_field_index) => payload_field_index,
+            FieldIndex::BigIntIndex(payload_field_index) => payload_field_index,
             FieldIndex::GeoIndex(payload_field_index) => payload_field_index,
             FieldIndex::BinaryIndex(payload_field_index) => payload_field_index,
             FieldIndex::FullTextIndex(payload_field_index) => payload_field_index,
+            FieldIndex::MinHashIndex(payload_field_index) => payload_field_index,
         }
     }
 
@@ -180,9 +197,11 @@ impl FieldIndex {
             FieldIndex::IntMapIndex(ref mut payload_field_index) => payload_field_index,
             FieldIndex::KeywordIndex(ref mut payload_field_index) => payload_field_index,
             FieldIndex::FloatIndex(ref mut payload_field_index) => payload_field_index,
+            FieldIndex::BigIntIndex(ref mut payload_field_index) => payload_field_index,
             FieldIndex::GeoIndex(ref mut payload_field_index) => payload_field_index,
             FieldIndex::BinaryIndex(ref mut payload_field_index) => payload_field_index,
             FieldIndex::FullTextIndex(ref mut payload_field_index) => payload_field_index,
+            FieldIndex::MinHashIndex(ref mut payload_field_index) => payload_field_index,
         }
     }
 
@@ -193,9 +212,11 @@ impl FieldIndex {
             FieldIndex::IntMapIndex(ref mut payload_field_index) => payload_field_index.load(),
             FieldIndex::KeywordIndex(ref mut payload_field_index) => payload_field_index.load(),
             FieldIndex::FloatIndex(ref mut payload_field_index) => payload_field_index.load(),
+            FieldIndex::BigIntIndex(ref mut payload_field_index) => payload_field_index.load(),
             FieldIndex::GeoIndex(ref mut payload_field_index) => payload_field_index.load(),
             FieldIndex::BinaryIndex(ref mut payload_field_index) => payload_field_index.load(),
             FieldIndex::FullTextIndex(ref mut payload_field_index) => payload_field_index.load(),
+            FieldIndex::MinHashIndex(ref mut payload_field_index) => payload_field_index.load(),
         }
     }
 
@@ -206,9 +227,11 @@ impl FieldIndex {
             FieldIndex::IntMapIndex(index) => index.clear(),
             FieldIndex::KeywordIndex(index) => index.clear(),
             FieldIndex::FloatIndex(index) => index.clear(),
+            FieldIndex::BigIntIndex(index) => index.clear(),
             FieldIndex::GeoIndex(index) => index.clear(),
             FieldIndex::BinaryIndex(index) => index.clear(),
             FieldIndex::FullTextIndex(index) => index.clear(),
+            FieldIndex::MinHashIndex(index) => index.clear(),
         }
     }
 
@@ -219,9 +242,11 @@ impl FieldIndex {
             FieldIndex::IntMapIndex(index) => index.recreate(),
             FieldIndex::KeywordIndex(index) => index.recreate(),
             FieldIndex::FloatIndex(index) => index.recreate(),
+            FieldIndex::BigIntIndex(index) => index.recreate(),
             FieldIndex::GeoIndex(index) => index.recreate(),
             FieldIndex::BinaryIndex(index) => index.recreate(),
             FieldIndex::FullTextIndex(index) => index.recreate(),
+            FieldIndex::MinHashIndex(index) => index.recreate(),
         }
     }
 
@@ -229,6 +254,36 @@ impl FieldIndex {
         self.get_payload_field_index().count_indexed_points()
     }
 
+    pub fn prefault_mmap_pages(&self) -> Option<mmap_ops::PrefaultMmapPages> {
+        match self {
+            FieldIndex::GeoIndex(index) => index.prefault_mmap_pages(),
+            FieldIndex::IntIndex(_)
+            | FieldIndex::DatetimeIndex(_)
+            | FieldIndex::IntMapIndex(_)
+            | FieldIndex::KeywordIndex(_)
+            | FieldIndex::FloatIndex(_)
+            | FieldIndex::BigIntIndex(_)
+            | FieldIndex::BinaryIndex(_)
+            | FieldIndex::FullTextIndex(_)
+            | FieldIndex::MinHashIndex(_) => None,
+        }
+    }
+
+    pub fn clear_cache(&self) -> OperationResult<()> {
+        match self {
+            FieldIndex::GeoIndex(index) => index.clear_cache(),
+            FieldIndex::IntIndex(_)
+            | FieldIndex::DatetimeIndex(_)
+            | FieldIndex::IntMapIndex(_)
+            | FieldIndex::KeywordIndex(_)
+            | FieldIndex::FloatIndex(_)
+            | FieldIndex::BigIntIndex(_)
+            | FieldIndex::BinaryIndex(_)
+            | FieldIndex::FullTextIndex(_)
+            | FieldIndex::MinHashIndex(_) => Ok(()),
+        }
+    }
+
     pub fn flusher(&self) -> Flusher {
         self.get_payload_field_index().flusher()
     }
@@ -274,6 +329,9 @@ impl FieldIndex {
             FieldIndex::FloatIndex(ref mut payload_field_index) => {
                 payload_field_index.add_point(id, payload)
             }
+            FieldIndex::BigIntIndex(ref mut payload_field_index) => {
+                payload_field_index.add_point(id, payload)
+            }
             FieldIndex::GeoIndex(ref mut payload_field_index) => {
                 payload_field_index.add_point(id, payload)
             }
@@ -283,6 +341,9 @@ impl FieldIndex {
             FieldIndex::FullTextIndex(ref mut payload_field_index) => {
                 payload_field_index.add_point(id, payload)
             }
+            FieldIndex::MinHashIndex(ref mut payload_field_index) => {
+                payload_field_index.add_point(id, payload)
+            }
         }
     }
 
@@ -293,9 +354,11 @@ impl FieldIndex {
             FieldIndex::IntMapIndex(index) => index.remove_point(point_id),
             FieldIndex::KeywordIndex(index) => index.remove_point(point_id),
             FieldIndex::FloatIndex(index) => index.remove_point(point_id),
+            FieldIndex::BigIntIndex(index) => index.remove_point(point_id),
             FieldIndex::GeoIndex(index) => index.remove_point(point_id),
             FieldIndex::BinaryIndex(index) => index.remove_point(point_id),
             FieldIndex::FullTextIndex(index) => index.remove_point(point_id),
+            FieldIndex::MinHashIndex(index) => index.remove_point(point_id),
         }
     }
 
@@ -306,9 +369,11 @@ impl FieldIndex {
             FieldIndex::IntMapIndex(index) => index.get_telemetry_data(),
             FieldIndex::KeywordIndex(index) => index.get_telemetry_data(),
             FieldIndex::FloatIndex(index) => index.get_telemetry_data(),
+            FieldIndex::BigIntIndex(index) => index.get_telemetry_data(),
             FieldIndex::GeoIndex(index) => index.get_telemetry_data(),
             FieldIndex::BinaryIndex(index) => index.get_telemetry_data(),
             FieldIndex::FullTextIndex(index) => index.get_telemetry_data(),
+            FieldIndex::MinHashIndex(index) => index.get_telemetry_data(),
         }
     }
 
@@ -319,9 +384,11 @@ impl FieldIndex {
             FieldIndex::IntMapIndex(index) => index.values_count(point_id),
             FieldIndex::KeywordIndex(index) => index.values_count(point_id),
             FieldIndex::FloatIndex(index) => index.values_count(point_id),
+            FieldIndex::BigIntIndex(index) => index.values_count(point_id),
             FieldIndex::GeoIndex(index) => index.values_count(point_id),
             FieldIndex::BinaryIndex(index) => index.values_count(point_id),
             FieldIndex::FullTextIndex(index) => index.values_count(point_id),
+            FieldIndex::MinHashIndex(index) => index.values_count(point_id),
         }
     }
 
@@ -332,9 +399,11 @@ impl FieldIndex {
             FieldIndex::IntMapIndex(index) => index.values_is_empty(point_id),
             FieldIndex::KeywordIndex(index) => index.values_is_empty(point_id),
             FieldIndex::FloatIndex(index) => index.values_is_empty(point_id),
+            FieldIndex::BigIntIndex(index) => index.values_is_empty(point_id),
             FieldIndex::GeoIndex(index) => index.values_is_empty(point_id),
             FieldIndex::BinaryIndex(index) => index.values_is_empty(point_id),
             FieldIndex::FullTextIndex(index) => index.values_is_empty(point_id),
+            FieldIndex::MinHashIndex(index) => index.values_is_empty(point_id),
         }
     }
 
@@ -343,11 +412,78 @@ impl FieldIndex {
             FieldIndex::IntIndex(index) => Some(NumericFieldIndex::IntIndex(index)),
             FieldIndex::DatetimeIndex(index) => Some(NumericFieldIndex::IntIndex(index)),
             FieldIndex::FloatIndex(index) => Some(NumericFieldIndex::FloatIndex(index)),
-            FieldIndex::IntMapIndex(_)
+            // Values don't fit into order value
+            FieldIndex::BigIntIndex(_)
+            | FieldIndex::IntMapIndex(_)
+            | FieldIndex::KeywordIndex(_)
+            | FieldIndex::GeoIndex(_)
+            | FieldIndex::BinaryIndex(_)
+            | FieldIndex::FullTextIndex(_)
+            | FieldIndex::MinHashIndex(_) => None,
+        }
+    }
+
+    pub fn as_geo(&self) -> Option<&GeoMapIndex> {
+        match self {
+            FieldIndex::GeoIndex(index) => Some(index),
+            FieldIndex::IntIndex(_)
+            | FieldIndex::DatetimeIndex(_)
+            | FieldIndex::IntMapIndex(_)
             | FieldIndex::KeywordIndex(_)
+            | FieldIndex::FloatIndex(_)
+            | FieldIndex::BigIntIndex(_)
+            | FieldIndex::BinaryIndex(_)
+            | FieldIndex::FullTextIndex(_)
+            | FieldIndex::MinHashIndex(_) => None,
+        }
+    }
+
+    pub fn as_map(&self) -> Option<MapFieldIndex> {
+        match self {
+            FieldIndex::KeywordIndex(index) => Some(MapFieldIndex::KeywordIndex(index)),
+            FieldIndex::IntMapIndex(index) => Some(MapFieldIndex::IntMapIndex(index)),
+            FieldIndex::IntIndex(_)
+            | FieldIndex::DatetimeIndex(_)
+            | FieldIndex::FloatIndex(_)
+            | FieldIndex::BigIntIndex(_)
             | FieldIndex::GeoIndex(_)
             | FieldIndex::BinaryIndex(_)
-            | FieldIndex::FullTextIndex(_) => None,
+            | FieldIndex::FullTextIndex(_)
+            | FieldIndex::MinHashIndex(_) => None,
+        }
+    }
+}
+
+/// Index with a vocabulary of exact values
+pub enum MapFieldIndex<'a> {
+    KeywordIndex(&'a MapIndex<SmolStr>),
+    IntMapIndex(&'a MapIndex<IntPayloadType>),
+}
+
+impl<'a> MapFieldIndex<'a> {
+    /// Hashes of all values in the vocabulary
+    pub fn vocabulary_hashes(&self) -> Box<dyn Iterator<Item = u64> + 'a> {
+        match *self {
+            MapFieldIndex::KeywordIndex(index) => {
+                Box::new(index.iter_values().map(|value| hash_keyword(value)))
+            }
+            MapFieldIndex::IntMapIndex(index) => {
+                Box::new(index.iter_values().map(|value| hash_integer(*value)))
+            }
+        }
+    }
+
+    /// Hashes of the values of a single point
+    pub fn value_hashes(&self, idx: PointOffsetType) -> Vec<u64> {
+        match self {
+            MapFieldIndex::KeywordIndex(index) => index
+                .get_values(idx)
+                .map(|values| values.iter().map(|value| hash_keyword(value)).collect())
+                .unwrap_or_default(),
+            MapFieldIndex::IntMapIndex(index) => index
+                .get_values(idx)
+                .map(|values| values.iter().map(|value| hash_integer(*value)).collect())
+                .unwrap_or_default(),
         }
     }
 }
@@ -401,4 +537,27 @@ impl<'a> NumericFieldIndex<'a> {
             ),
         }
     }
+
+    /// Values of the point, integers are converted to floats
+    pub fn get_float_values(&self, idx: PointOffsetType) -> Box<dyn Iterator<Item = f64> + 'a> {
+        match self {
+            NumericFieldIndex::IntIndex(index) => Box::new(
+                index
+                    .get_values(idx)
+                    .into_iter()
+                    .flatten()
+                    .map(|value| *value as f64),
+            ),
+            NumericFieldIndex::FloatIndex(index) => {
+                Box::new(index.get_values(idx).into_iter().flatten().copied())
+            }
+        }
+    }
+
+    pub fn value_distribution(&self) -> ValueDistribution {
+        match self {
+            NumericFieldIndex::IntIndex(index) => index.value_distribution(),
+            NumericFieldIndex::FloatIndex(index) => index.value_distribution(),
+        }
+    }
 }
@@ -24,6 +24,7 @@ pub struct ImmutableGeoMapIndex {
     counts_per_hash: Vec<Counts>,
     points_map: Vec<(GeoHash, HashSet<PointOffsetType>)>,
     point_to_values: ImmutablePointToValues<GeoPoint>,
+    point_to_polygons: BTreeMap<PointOffsetType, Vec<Vec<GeoPoint>>>,
     pub points_count: usize,
     pub points_values_count: usize,
     pub max_values_per_point: usize,
@@ -37,6 +38,7 @@ impl ImmutableGeoMapIndex {
             counts_per_hash: Default::default(),
             points_map: Default::default(),
             point_to_values: Default::default(),
+            point_to_polygons: Default::default(),
             points_count: 0,
             points_values_count: 0,
             max_values_per_point: 0,
@@ -52,6 +54,12 @@ impl ImmutableGeoMapIndex {
         self.point_to_values.get_values(idx)
     }
 
+    pub fn get_polygons(&self, idx: PointOffsetType) -> &[Vec<GeoPoint>] {
+        self.point_to_polygons
+            .get(&idx)
+            .map_or(&[], |polygons| polygons.as_slice())
+    }
+
     pub fn get_points_per_hash(&self) -> impl Iterator<Item = (&GeoHash, usize)> {
         self.counts_per_hash
             .iter()
@@ -86,6 +94,7 @@ impl ImmutableGeoMapIndex {
             values_per_hash,
             points_map,
             point_to_values,
+            point_to_polygons,
             points_count,
             points_values_count,
             max_values_per_point,
@@ -124,6 +133,7 @@ impl ImmutableGeoMapIndex {
             .map(|(k, v)| (k.clone(), v.clone()))
             .collect();
         self.point_to_values = ImmutablePointToValues::new(point_to_values);
+        self.point_to_polygons = point_to_polygons;
         self.points_count = points_count;
         self.points_values_count = points_values_count;
         self.max_values_per_point = max_values_per_point;
@@ -133,20 +143,23 @@ impl ImmutableGeoMapIndex {
 
     pub fn remove_point(&mut self, idx: PointOffsetType) -> OperationResult<()> {
         let removed_geo_points = self.point_to_values.remove_point(idx);
-        if removed_geo_points.is_empty() {
+        let removed_polygons = self.point_to_polygons.remove(&idx).unwrap_or_default();
+        if removed_geo_points.is_empty() && removed_polygons.is_empty() {
             return Ok(());
         }
 
+        let removed_values = GeoMapIndex::db_values(removed_geo_points, removed_polygons);
+
         self.points_count -= 1;
-        self.points_values_count -= removed_geo_points.len();
-        let mut removed_geo_hashes = Vec::with_capacity(removed_geo_points.len());
+        self.points_values_count -= removed_values.len();
+        let mut removed_geo_hashes = Vec::with_capacity(removed_values.len());
 
-        for removed_geo_point in removed_geo_points {
+        for (removed_geo_point, polygon) in removed_values {
             let removed_geo_hash: GeoHash =
                 encode_max_precision(removed_geo_point.lon, removed_geo_point.lat).unwrap();
             removed_geo_hashes.push(removed_geo_hash.clone());
 
-            let key = GeoMapIndex::encode_db_key(&removed_geo_hash, idx);
+            let key = GeoMapIndex::encode_db_key(&removed_geo_hash, idx, polygon);
             self.db_wrapper.remove(key)?;
 
             if let Ok(index) = self
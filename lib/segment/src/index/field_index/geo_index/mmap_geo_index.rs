@@ -0,0 +1,565 @@
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::mem::{align_of, size_of};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use common::types::PointOffsetType;
+use memmap2::Mmap;
+use memory::mmap_ops::{self, PrefaultMmapPages};
+use parking_lot::RwLock;
+use rocksdb::DB;
+
+use super::mutable_geo_index::MutableGeoMapIndex;
+use super::GeoMapIndex;
+use crate::common::error_logging::LogError;
+use crate::common::mmap_type::MmapBitSlice;
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
+use crate::common::Flusher;
+use crate::index::field_index::geo_hash::{encode_max_precision, GeoHash, GEOHASH_MAX_LENGTH};
+use crate::types::GeoPoint;
+
+const DATA_FILE: &str = "geo_index.bin";
+const DELETED_FILE: &str = "geo_index_deleted.bin";
+const POLYGONS_FILE: &str = "geo_index_polygons.bin";
+
+/// Geo hash of fixed size, as it is stored in the file
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default)]
+struct StoredGeoHash {
+    bytes: [u8; GEOHASH_MAX_LENGTH],
+    len: u32,
+}
+
+impl StoredGeoHash {
+    fn new(hash: &str) -> OperationResult<Self> {
+        if hash.len() > GEOHASH_MAX_LENGTH {
+            return Err(OperationError::service_error(format!(
+                "Geo hash {hash} is longer than {GEOHASH_MAX_LENGTH}",
+            )));
+        }
+        let mut bytes = [0; GEOHASH_MAX_LENGTH];
+        bytes[..hash.len()].copy_from_slice(hash.as_bytes());
+        Ok(Self {
+            bytes,
+            len: hash.len() as u32,
+        })
+    }
+
+    /// Geo hashes are ASCII, so the order of bytes is the same as the order of strings
+    fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..(self.len as usize).min(GEOHASH_MAX_LENGTH)]
+    }
+
+    fn to_geo_hash(self) -> GeoHash {
+        GeoHash::new(std::str::from_utf8(self.as_bytes()).unwrap_or_default())
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default)]
+struct StoredCounts {
+    hash: StoredGeoHash,
+    points: u32,
+    values: u32,
+}
+
+/// Range of `posting_ids` with points of the hash
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default)]
+struct StoredPosting {
+    hash: StoredGeoHash,
+    start: u32,
+    end: u32,
+}
+
+/// Sizes of the sections of the data file
+///
+/// Sections follow the header in the order of the fields, each section is aligned by 8 bytes.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default)]
+struct Header {
+    /// Number of points with values, at the time the file was written
+    points_count: u64,
+    /// Number of values, at the time the file was written
+    points_values_count: u64,
+    max_values_per_point: u64,
+    /// `GeoPoint` values of all points
+    values: u64,
+    /// `u64` offsets of the values of each point, with one more offset for the end
+    point_offsets: u64,
+    /// `StoredCounts` of each hash, ordered by hash
+    counts: u64,
+    /// `StoredPosting` of each max precision hash, ordered by hash
+    postings: u64,
+    /// `u32` ids of points of all postings
+    posting_ids: u64,
+}
+
+fn section_size<T>(len: u64) -> usize {
+    (len as usize * size_of::<T>()).next_multiple_of(align_of::<u64>())
+}
+
+/// Byte ranges of the sections of the data file
+#[derive(Clone, Debug)]
+struct Sections {
+    values: Range<usize>,
+    point_offsets: Range<usize>,
+    counts: Range<usize>,
+    postings: Range<usize>,
+    posting_ids: Range<usize>,
+}
+
+impl Sections {
+    fn new(header: &Header) -> Self {
+        let mut offset = size_of::<Header>();
+        let mut next = |size: usize| {
+            let range = offset..offset + size;
+            offset += size;
+            range
+        };
+        Self {
+            values: next(section_size::<GeoPoint>(header.values)),
+            point_offsets: next(section_size::<u64>(header.point_offsets)),
+            counts: next(section_size::<StoredCounts>(header.counts)),
+            postings: next(section_size::<StoredPosting>(header.postings)),
+            posting_ids: next(section_size::<u32>(header.posting_ids)),
+        }
+    }
+
+    fn file_size(&self) -> usize {
+        self.posting_ids.end
+    }
+}
+
+/// Geo index, which keeps points and postings of hashes in memory mapped files
+///
+/// Used for non-appendable segments with `on_disk` geo index. Files are built from the RocksDB
+/// records of the index on first load, which are also kept in sync with deletions, so the files
+/// can be rebuilt at any time.
+///
+/// Number of points per hash is not updated on deletion, so cardinality estimations may be
+/// higher than the actual number of points until the segment is optimized.
+pub struct MmapGeoMapIndex {
+    path: PathBuf,
+    mmap: Option<Arc<Mmap>>,
+    header: Header,
+    sections: Sections,
+    /// Points, removed since the files were written
+    deleted: Option<MmapBitSlice>,
+    /// Exterior points of polygons. Polygons are rare, so they are kept in memory.
+    point_to_polygons: BTreeMap<PointOffsetType, Vec<Vec<GeoPoint>>>,
+    pub points_count: usize,
+    pub points_values_count: usize,
+    pub max_values_per_point: usize,
+    db_wrapper: DatabaseColumnWrapper,
+}
+
+impl MmapGeoMapIndex {
+    pub fn new(db: Arc<RwLock<DB>>, store_cf_name: &str, path: &Path) -> Self {
+        let db_wrapper = DatabaseColumnWrapper::new(db, store_cf_name);
+        let header = Header::default();
+        Self {
+            path: path.to_owned(),
+            mmap: None,
+            sections: Sections::new(&header),
+            header,
+            deleted: None,
+            point_to_polygons: Default::default(),
+            points_count: 0,
+            points_values_count: 0,
+            max_values_per_point: 0,
+            db_wrapper,
+        }
+    }
+
+    pub fn db_wrapper(&self) -> &DatabaseColumnWrapper {
+        &self.db_wrapper
+    }
+
+    fn section<T>(&self, range: &Range<usize>, len: u64) -> &[T] {
+        match &self.mmap {
+            Some(mmap) => {
+                let data = &mmap[range.start..range.start + len as usize * size_of::<T>()];
+                mmap_ops::transmute_from_u8_to_slice(data)
+            }
+            None => &[],
+        }
+    }
+
+    fn values(&self) -> &[GeoPoint] {
+        self.section(&self.sections.values, self.header.values)
+    }
+
+    fn point_offsets(&self) -> &[u64] {
+        self.section(&self.sections.point_offsets, self.header.point_offsets)
+    }
+
+    fn counts(&self) -> &[StoredCounts] {
+        self.section(&self.sections.counts, self.header.counts)
+    }
+
+    fn postings(&self) -> &[StoredPosting] {
+        self.section(&self.sections.postings, self.header.postings)
+    }
+
+    fn posting_ids(&self) -> &[u32] {
+        self.section(&self.sections.posting_ids, self.header.posting_ids)
+    }
+
+    fn is_deleted(&self, idx: PointOffsetType) -> bool {
+        self.deleted.as_ref().is_some_and(|deleted| {
+            deleted
+                .get(idx as usize)
+                .is_some_and(|is_deleted| *is_deleted)
+        })
+    }
+
+    /// Stored values of the point, including the ones of removed points
+    fn stored_values(&self, idx: PointOffsetType) -> &[GeoPoint] {
+        let offsets = self.point_offsets();
+        let idx = idx as usize;
+        if idx + 1 >= offsets.len() {
+            return &[];
+        }
+        &self.values()[offsets[idx] as usize..offsets[idx + 1] as usize]
+    }
+
+    pub fn get_values(&self, idx: PointOffsetType) -> Option<&[GeoPoint]> {
+        if idx as usize + 1 >= self.point_offsets().len() {
+            return None;
+        }
+        if self.is_deleted(idx) {
+            return Some(&[]);
+        }
+        Some(self.stored_values(idx))
+    }
+
+    pub fn get_polygons(&self, idx: PointOffsetType) -> &[Vec<GeoPoint>] {
+        self.point_to_polygons
+            .get(&idx)
+            .map_or(&[], |polygons| polygons.as_slice())
+    }
+
+    pub fn get_points_per_hash(&self) -> impl Iterator<Item = (GeoHash, usize)> + '_ {
+        self.counts()
+            .iter()
+            .map(|counts| (counts.hash.to_geo_hash(), counts.points as usize))
+    }
+
+    fn find_counts(&self, hash: &GeoHash) -> Option<&StoredCounts> {
+        let counts = self.counts();
+        counts
+            .binary_search_by(|counts| counts.hash.as_bytes().cmp(hash.as_bytes()))
+            .ok()
+            .map(|index| &counts[index])
+    }
+
+    pub fn get_points_of_hash(&self, hash: &GeoHash) -> usize {
+        self.find_counts(hash)
+            .map_or(0, |counts| counts.points as usize)
+    }
+
+    pub fn get_values_of_hash(&self, hash: &GeoHash) -> usize {
+        self.find_counts(hash)
+            .map_or(0, |counts| counts.values as usize)
+    }
+
+    /// Points of all stored hashes, which start with `geo`
+    pub fn get_stored_sub_regions(
+        &self,
+        geo: &GeoHash,
+    ) -> impl Iterator<Item = PointOffsetType> + '_ {
+        let prefix = geo.as_bytes().to_vec();
+        let postings = self.postings();
+        let start_index = postings.partition_point(|posting| posting.hash.as_bytes() < &prefix[..]);
+        let posting_ids = self.posting_ids();
+        postings[start_index..]
+            .iter()
+            .take_while(move |posting| posting.hash.as_bytes().starts_with(&prefix))
+            .flat_map(move |posting| &posting_ids[posting.start as usize..posting.end as usize])
+            .copied()
+            .filter(|idx| !self.is_deleted(*idx))
+    }
+
+    fn data_path(&self) -> PathBuf {
+        self.path.join(DATA_FILE)
+    }
+
+    fn deleted_path(&self) -> PathBuf {
+        self.path.join(DELETED_FILE)
+    }
+
+    fn polygons_path(&self) -> PathBuf {
+        self.path.join(POLYGONS_FILE)
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        vec![self.data_path(), self.deleted_path(), self.polygons_path()]
+    }
+
+    pub fn load(&mut self) -> OperationResult<bool> {
+        if !self.files().iter().all(|file| file.exists()) {
+            // Make sure no partially written files are left
+            self.remove_files()?;
+            let mut mutable = MutableGeoMapIndex::new(
+                self.db_wrapper.database.clone(),
+                &self.db_wrapper.column_name,
+            );
+            if !mutable.load()? {
+                return Ok(false);
+            }
+            self.write_files(mutable)?;
+        }
+        self.open_files()?;
+        Ok(true)
+    }
+
+    /// Write files of the index, the data file goes last, as it marks the files as complete
+    fn write_files(&self, mutable: MutableGeoMapIndex) -> OperationResult<()> {
+        let MutableGeoMapIndex {
+            points_per_hash,
+            values_per_hash,
+            points_map,
+            point_to_values,
+            point_to_polygons,
+            points_count,
+            points_values_count,
+            max_values_per_point,
+            ..
+        } = mutable;
+
+        let mut counts: BTreeMap<GeoHash, StoredCounts> = BTreeMap::new();
+        for (hash, points) in points_per_hash {
+            counts
+                .entry(hash.clone())
+                .or_insert(StoredCounts {
+                    hash: StoredGeoHash::new(&hash)?,
+                    ..Default::default()
+                })
+                .points = points as u32;
+        }
+        for (hash, values) in values_per_hash {
+            counts
+                .entry(hash.clone())
+                .or_insert(StoredCounts {
+                    hash: StoredGeoHash::new(&hash)?,
+                    ..Default::default()
+                })
+                .values = values as u32;
+        }
+        let counts: Vec<_> = counts.into_values().collect();
+
+        let mut values = Vec::with_capacity(points_values_count);
+        let mut point_offsets = Vec::with_capacity(point_to_values.len() + 1);
+        point_offsets.push(0u64);
+        for point_values in point_to_values {
+            values.extend(point_values);
+            point_offsets.push(values.len() as u64);
+        }
+
+        let mut postings = Vec::with_capacity(points_map.len());
+        let mut posting_ids: Vec<u32> = Vec::with_capacity(points_values_count);
+        for (hash, points) in points_map {
+            let start = posting_ids.len() as u32;
+            let mut points: Vec<_> = points.into_iter().collect();
+            points.sort_unstable();
+            posting_ids.extend(points);
+            postings.push(StoredPosting {
+                hash: StoredGeoHash::new(&hash)?,
+                start,
+                end: posting_ids.len() as u32,
+            });
+        }
+
+        let header = Header {
+            points_count: points_count as u64,
+            points_values_count: points_values_count as u64,
+            max_values_per_point: max_values_per_point as u64,
+            values: values.len() as u64,
+            point_offsets: point_offsets.len() as u64,
+            counts: counts.len() as u64,
+            postings: postings.len() as u64,
+            posting_ids: posting_ids.len() as u64,
+        };
+
+        fs::create_dir_all(&self.path)?;
+
+        mmap_ops::create_and_ensure_length(
+            &self.deleted_path(),
+            deleted_file_size(point_offsets.len()),
+        )?;
+
+        let polygons = bincode::serialize(&point_to_polygons).map_err(|err| {
+            OperationError::service_error(format!("Failed to serialize geo polygons: {err}"))
+        })?;
+        fs::write(self.polygons_path(), polygons)?;
+
+        let tmp_path = self.path.join(format!("{DATA_FILE}.tmp"));
+        let mut writer = BufWriter::new(File::create(&tmp_path)?);
+        writer.write_all(mmap_ops::transmute_to_u8(&header))?;
+        write_section(&mut writer, &values)?;
+        write_section(&mut writer, &point_offsets)?;
+        write_section(&mut writer, &counts)?;
+        write_section(&mut writer, &postings)?;
+        write_section(&mut writer, &posting_ids)?;
+        let file = writer.into_inner().map_err(|err| err.into_error())?;
+        file.sync_all()?;
+        fs::rename(tmp_path, self.data_path())?;
+
+        Ok(())
+    }
+
+    fn open_files(&mut self) -> OperationResult<()> {
+        let mmap = mmap_ops::open_read_mmap(&self.data_path()).describe("Open geo index mmap")?;
+        if mmap.len() < size_of::<Header>() {
+            return Err(OperationError::service_error(
+                "Geo index file is smaller than its header",
+            ));
+        }
+        let header = *mmap_ops::transmute_from_u8::<Header>(&mmap[..size_of::<Header>()]);
+        let sections = Sections::new(&header);
+        if mmap.len() < sections.file_size() {
+            return Err(OperationError::service_error(format!(
+                "Geo index file is truncated, expected {} bytes, got {}",
+                sections.file_size(),
+                mmap.len(),
+            )));
+        }
+
+        let deleted_mmap = mmap_ops::open_write_mmap(&self.deleted_path())
+            .describe("Open geo index deleted flags mmap")?;
+        let deleted = MmapBitSlice::try_from(deleted_mmap, 0)?;
+
+        let polygons = fs::read(self.polygons_path())?;
+        let point_to_polygons = bincode::deserialize(&polygons).map_err(|err| {
+            OperationError::service_error(format!("Failed to deserialize geo polygons: {err}"))
+        })?;
+
+        self.mmap = Some(Arc::new(mmap));
+        self.header = header;
+        self.sections = sections;
+        self.point_to_polygons = point_to_polygons;
+        self.points_count = header.points_count as usize;
+        self.points_values_count = header.points_values_count as usize;
+        self.max_values_per_point = header.max_values_per_point as usize;
+
+        // Exclude points, removed since the files were written
+        let deleted_points: Vec<_> = deleted.iter_ones().collect();
+        for idx in deleted_points {
+            let idx = idx as PointOffsetType;
+            let removed_polygons = self.point_to_polygons.remove(&idx).unwrap_or_default();
+            let removed_values = self.stored_values(idx).len()
+                + removed_polygons.iter().map(Vec::len).sum::<usize>();
+            if removed_values > 0 {
+                self.points_count = self.points_count.saturating_sub(1);
+                self.points_values_count = self.points_values_count.saturating_sub(removed_values);
+            }
+        }
+        self.deleted = Some(deleted);
+
+        Ok(())
+    }
+
+    fn remove_files(&self) -> OperationResult<()> {
+        for file in self.files() {
+            if file.exists() {
+                fs::remove_file(file)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn remove_point(&mut self, idx: PointOffsetType) -> OperationResult<()> {
+        if self.is_deleted(idx) {
+            return Ok(());
+        }
+        let removed_geo_points = self.stored_values(idx).to_vec();
+        let removed_polygons = self.point_to_polygons.remove(&idx).unwrap_or_default();
+        if removed_geo_points.is_empty() && removed_polygons.is_empty() {
+            return Ok(());
+        }
+
+        let removed_values = GeoMapIndex::db_values(removed_geo_points, removed_polygons);
+
+        self.points_count -= 1;
+        self.points_values_count -= removed_values.len();
+
+        for (removed_geo_point, polygon) in removed_values {
+            let removed_geo_hash: GeoHash =
+                encode_max_precision(removed_geo_point.lon, removed_geo_point.lat).unwrap();
+            let key = GeoMapIndex::encode_db_key(&removed_geo_hash, idx, polygon);
+            self.db_wrapper.remove(key)?;
+        }
+
+        if let Some(deleted) = &mut self.deleted {
+            if (idx as usize) < deleted.len() {
+                deleted.set(idx as usize, true);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn flusher(&self) -> Flusher {
+        let deleted_flusher = self.deleted.as_ref().map(MmapBitSlice::flusher);
+        let db_flusher = self.db_wrapper.flusher();
+        Box::new(move || {
+            if let Some(deleted_flusher) = deleted_flusher {
+                deleted_flusher()?;
+            }
+            db_flusher()
+        })
+    }
+
+    /// Task to populate the page cache with the data file
+    pub fn prefault_mmap_pages(&self) -> Option<PrefaultMmapPages> {
+        let mmap = self.mmap.clone()?;
+        Some(PrefaultMmapPages::new(mmap, Some(self.data_path())))
+    }
+
+    /// Drop the pages of the data file from the page cache, they are read from disk on next access
+    pub fn clear_cache(&self) -> OperationResult<()> {
+        #[cfg(unix)]
+        if let Some(mmap) = &self.mmap {
+            // The mapping is read-only, so the pages are just loaded from the file again
+            unsafe { mmap.unchecked_advise(memmap2::UncheckedAdvice::DontNeed)? };
+        }
+        Ok(())
+    }
+
+    pub fn clear(self) -> OperationResult<()> {
+        let Self {
+            path,
+            mmap,
+            deleted,
+            db_wrapper,
+            ..
+        } = self;
+        drop(mmap);
+        drop(deleted);
+        for file in [DATA_FILE, DELETED_FILE, POLYGONS_FILE] {
+            let file = path.join(file);
+            if file.exists() {
+                fs::remove_file(file)?;
+            }
+        }
+        db_wrapper.remove_column_family()
+    }
+}
+
+/// Size of the deleted flags file, aligned to the storage type of `BitSlice`
+fn deleted_file_size(num: usize) -> usize {
+    let unit_size = size_of::<usize>();
+    num.div_ceil(8).div_ceil(unit_size) * unit_size
+}
+
+fn write_section<T>(writer: &mut impl Write, section: &[T]) -> OperationResult<()> {
+    let data = mmap_ops::transmute_to_u8_slice(section);
+    writer.write_all(data)?;
+    let padding = section_size::<T>(section.len() as u64) - data.len();
+    writer.write_all(&[0; 8][..padding])?;
+    Ok(())
+}
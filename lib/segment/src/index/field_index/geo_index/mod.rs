@@ -1,17 +1,21 @@
 pub mod immutable_geo_index;
+pub mod mmap_geo_index;
 pub mod mutable_geo_index;
 
 use std::cmp::{max, min};
+use std::path::Path;
 use std::str::FromStr;
 use std::sync::Arc;
 
 use common::types::PointOffsetType;
 use itertools::Itertools;
+use memory::mmap_ops;
 use parking_lot::RwLock;
 use rocksdb::DB;
 use serde_json::Value;
 
 use self::immutable_geo_index::ImmutableGeoMapIndex;
+use self::mmap_geo_index::MmapGeoMapIndex;
 use self::mutable_geo_index::MutableGeoMapIndex;
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
@@ -26,7 +30,7 @@ use crate::index::field_index::{
 };
 use crate::telemetry::PayloadIndexTelemetry;
 use crate::types::{
-    FieldCondition, GeoBoundingBox, GeoPoint, GeoRadius, PayloadKeyType, PolygonWrapper,
+    FieldCondition, GeoBoundingBox, GeoPoint, GeoPolygon, GeoRadius, PayloadKeyType, PolygonWrapper,
 };
 
 /// Max number of sub-regions computed for an input geo query
@@ -36,6 +40,7 @@ const GEO_QUERY_MAX_REGION: usize = 12;
 pub enum GeoMapIndex {
     Mutable(MutableGeoMapIndex),
     Immutable(ImmutableGeoMapIndex),
+    Mmap(MmapGeoMapIndex),
 }
 
 impl GeoMapIndex {
@@ -48,10 +53,17 @@ impl GeoMapIndex {
         }
     }
 
+    /// Index of a non-appendable segment, which keeps its data in files under `path`
+    pub fn new_mmap(db: Arc<RwLock<DB>>, field: &str, path: &Path) -> Self {
+        let store_cf_name = GeoMapIndex::storage_cf_name(field);
+        GeoMapIndex::Mmap(MmapGeoMapIndex::new(db, &store_cf_name, path))
+    }
+
     fn db_wrapper(&self) -> &DatabaseColumnWrapper {
         match self {
             GeoMapIndex::Mutable(index) => index.db_wrapper(),
             GeoMapIndex::Immutable(index) => index.db_wrapper(),
+            GeoMapIndex::Mmap(index) => index.db_wrapper(),
         }
     }
 
@@ -59,6 +71,7 @@ impl GeoMapIndex {
         match self {
             GeoMapIndex::Mutable(index) => index.points_count,
             GeoMapIndex::Immutable(index) => index.points_count,
+            GeoMapIndex::Mmap(index) => index.points_count,
         }
     }
 
@@ -66,6 +79,7 @@ impl GeoMapIndex {
         match self {
             GeoMapIndex::Mutable(index) => index.points_values_count,
             GeoMapIndex::Immutable(index) => index.points_values_count,
+            GeoMapIndex::Mmap(index) => index.points_values_count,
         }
     }
 
@@ -78,6 +92,7 @@ impl GeoMapIndex {
         match self {
             GeoMapIndex::Mutable(index) => index.max_values_per_point,
             GeoMapIndex::Immutable(index) => index.max_values_per_point,
+            GeoMapIndex::Mmap(index) => index.max_values_per_point,
         }
     }
 
@@ -85,6 +100,7 @@ impl GeoMapIndex {
         match self {
             GeoMapIndex::Mutable(index) => index.get_points_of_hash(hash),
             GeoMapIndex::Immutable(index) => index.get_points_of_hash(hash),
+            GeoMapIndex::Mmap(index) => index.get_points_of_hash(hash),
         }
     }
 
@@ -92,6 +108,7 @@ impl GeoMapIndex {
         match self {
             GeoMapIndex::Mutable(index) => index.get_values_of_hash(hash),
             GeoMapIndex::Immutable(index) => index.get_values_of_hash(hash),
+            GeoMapIndex::Mmap(index) => index.get_values_of_hash(hash),
         }
     }
 
@@ -103,23 +120,53 @@ impl GeoMapIndex {
         self.db_wrapper().recreate_column_family()
     }
 
-    fn encode_db_key(value: &str, idx: PointOffsetType) -> String {
-        format!("{value}/{idx}")
+    /// Key of a point is `{geohash}/{idx}`, key of a point of polygon is `{geohash}/{idx}/{polygon}`
+    fn encode_db_key(value: &str, idx: PointOffsetType, polygon: Option<usize>) -> String {
+        match polygon {
+            None => format!("{value}/{idx}"),
+            Some(polygon) => format!("{value}/{idx}/{polygon}"),
+        }
     }
 
-    fn decode_db_key(s: &str) -> OperationResult<(GeoHash, PointOffsetType)> {
+    fn decode_db_key(s: &str) -> OperationResult<(GeoHash, PointOffsetType, Option<usize>)> {
         const DECODE_ERR: &str = "Index db parsing error: wrong data format";
-        let separator_pos = s
-            .rfind('/')
-            .ok_or_else(|| OperationError::service_error(DECODE_ERR))?;
-        if separator_pos == s.len() - 1 {
+        let mut parts = s.split('/');
+        let (Some(geohash), Some(idx_str)) = (parts.next(), parts.next()) else {
             return Err(OperationError::service_error(DECODE_ERR));
-        }
-        let geohash = s[..separator_pos].into();
-        let idx_str = &s[separator_pos + 1..];
+        };
         let idx = PointOffsetType::from_str(idx_str)
             .map_err(|_| OperationError::service_error(DECODE_ERR))?;
-        Ok((geohash, idx))
+        let polygon = parts
+            .next()
+            .map(|polygon| {
+                usize::from_str(polygon).map_err(|_| OperationError::service_error(DECODE_ERR))
+            })
+            .transpose()?;
+        if parts.next().is_some() {
+            return Err(OperationError::service_error(DECODE_ERR));
+        }
+        Ok((geohash.into(), idx, polygon))
+    }
+
+    /// Values of a point, as they are stored in DB: points, and points of polygons with the
+    /// number of the polygon
+    fn db_values(
+        points: Vec<GeoPoint>,
+        polygons: Vec<Vec<GeoPoint>>,
+    ) -> Vec<(GeoPoint, Option<usize>)> {
+        let polygon_points = polygons
+            .into_iter()
+            .enumerate()
+            .flat_map(|(polygon_idx, polygon)| {
+                polygon
+                    .into_iter()
+                    .map(move |point| (point, Some(polygon_idx)))
+            });
+        points
+            .into_iter()
+            .map(|point| (point, None))
+            .chain(polygon_points)
+            .collect()
     }
 
     fn decode_db_value<T: AsRef<[u8]>>(value: T) -> OperationResult<GeoPoint> {
@@ -145,32 +192,66 @@ impl GeoMapIndex {
     }
 
     pub fn flusher(&self) -> Flusher {
-        self.db_wrapper().flusher()
+        match self {
+            GeoMapIndex::Mutable(index) => index.db_wrapper().flusher(),
+            GeoMapIndex::Immutable(index) => index.db_wrapper().flusher(),
+            GeoMapIndex::Mmap(index) => index.flusher(),
+        }
+    }
+
+    pub fn prefault_mmap_pages(&self) -> Option<mmap_ops::PrefaultMmapPages> {
+        match self {
+            GeoMapIndex::Mutable(_) | GeoMapIndex::Immutable(_) => None,
+            GeoMapIndex::Mmap(index) => index.prefault_mmap_pages(),
+        }
+    }
+
+    pub fn clear_cache(&self) -> OperationResult<()> {
+        match self {
+            GeoMapIndex::Mutable(_) | GeoMapIndex::Immutable(_) => Ok(()),
+            GeoMapIndex::Mmap(index) => index.clear_cache(),
+        }
     }
 
     pub fn get_values(&self, idx: PointOffsetType) -> Option<&[GeoPoint]> {
         match self {
             GeoMapIndex::Mutable(index) => index.get_values(idx),
             GeoMapIndex::Immutable(index) => index.get_values(idx),
+            GeoMapIndex::Mmap(index) => index.get_values(idx),
         }
     }
 
+    /// Exterior points of polygons, stored in payload
+    pub fn get_polygons(&self, idx: PointOffsetType) -> &[Vec<GeoPoint>] {
+        match self {
+            GeoMapIndex::Mutable(index) => index.get_polygons(idx),
+            GeoMapIndex::Immutable(index) => index.get_polygons(idx),
+            GeoMapIndex::Mmap(index) => index.get_polygons(idx),
+        }
+    }
+
+    /// Check if any point of `idx` matches, or all points of any of its polygons
+    fn check_values(&self, idx: PointOffsetType, check_point: impl Fn(&GeoPoint) -> bool) -> bool {
+        let points_match = self
+            .get_values(idx)
+            .map_or(false, |values| values.iter().any(&check_point));
+        points_match
+            || self
+                .get_polygons(idx)
+                .iter()
+                .any(|polygon| polygon.iter().all(&check_point))
+    }
+
     pub fn check_radius(&self, idx: PointOffsetType, radius: &GeoRadius) -> bool {
-        self.get_values(idx)
-            .map(|values| values.iter().any(|x| radius.check_point(x)))
-            .unwrap_or(false)
+        self.check_values(idx, |x| radius.check_point(x))
     }
 
     pub fn check_box(&self, idx: PointOffsetType, bbox: &GeoBoundingBox) -> bool {
-        self.get_values(idx)
-            .map(|values| values.iter().any(|x| bbox.check_point(x)))
-            .unwrap_or(false)
+        self.check_values(idx, |x| bbox.check_point(x))
     }
 
     pub fn check_polygon(&self, idx: PointOffsetType, polygon: &PolygonWrapper) -> bool {
-        self.get_values(idx)
-            .map(|values| values.iter().any(|x| polygon.check_point(x)))
-            .unwrap_or(false)
+        self.check_values(idx, |x| polygon.check_point(x))
     }
 
     pub fn match_cardinality(&self, values: &[GeoHash]) -> CardinalityEstimation {
@@ -251,6 +332,12 @@ impl GeoMapIndex {
                     })
                     .unique(),
             ),
+            GeoMapIndex::Mmap(index) => Box::new(
+                values
+                    .into_iter()
+                    .flat_map(|top_geo_hash| index.get_stored_sub_regions(&top_geo_hash))
+                    .unique(),
+            ),
         }
     }
 
@@ -258,15 +345,21 @@ impl GeoMapIndex {
     fn get_large_hashes(
         &self,
         threshold: usize,
-    ) -> Box<dyn Iterator<Item = (&GeoHash, usize)> + '_> {
+    ) -> Box<dyn Iterator<Item = (GeoHash, usize)> + '_> {
         let filter_condition =
-            |(hash, size): &(&GeoHash, usize)| *size > threshold && !hash.is_empty();
+            |(hash, size): &(GeoHash, usize)| *size > threshold && !hash.is_empty();
         let mut large_regions = match self {
             GeoMapIndex::Mutable(index) => index
                 .get_points_per_hash()
+                .map(|(hash, size)| (hash.clone(), size))
                 .filter(filter_condition)
                 .collect_vec(),
             GeoMapIndex::Immutable(index) => index
+                .get_points_per_hash()
+                .map(|(hash, size)| (hash.clone(), size))
+                .filter(filter_condition)
+                .collect_vec(),
+            GeoMapIndex::Mmap(index) => index
                 .get_points_per_hash()
                 .filter(filter_condition)
                 .collect_vec(),
@@ -292,13 +385,12 @@ impl GeoMapIndex {
     }
 
     pub fn values_count(&self, point_id: PointOffsetType) -> usize {
-        self.get_values(point_id).map(|x| x.len()).unwrap_or(0)
+        let points_count = self.get_values(point_id).map(|x| x.len()).unwrap_or(0);
+        points_count + self.get_polygons(point_id).len()
     }
 
     pub fn values_is_empty(&self, point_id: PointOffsetType) -> bool {
-        self.get_values(point_id)
-            .map(|x| x.is_empty())
-            .unwrap_or(true)
+        self.values_count(point_id) == 0
     }
 }
 
@@ -306,7 +398,7 @@ impl ValueIndexer<GeoPoint> for GeoMapIndex {
     fn add_many(&mut self, id: PointOffsetType, values: Vec<GeoPoint>) -> OperationResult<()> {
         match self {
             GeoMapIndex::Mutable(index) => index.add_many_geo_points(id, &values),
-            GeoMapIndex::Immutable(_) => Err(OperationError::service_error(
+            GeoMapIndex::Immutable(_) | GeoMapIndex::Mmap(_) => Err(OperationError::service_error(
                 "Can't add values to immutable geo index",
             )),
         }
@@ -327,10 +419,37 @@ impl ValueIndexer<GeoPoint> for GeoMapIndex {
         }
     }
 
+    /// Points and polygons are both indexed, polygons by their exterior points
+    fn add_point(&mut self, id: PointOffsetType, payload: &[&Value]) -> OperationResult<()> {
+        self.remove_point(id)?;
+        let mut points = vec![];
+        let mut polygons = vec![];
+        for value in payload {
+            let values = match value {
+                Value::Array(values) => values.as_slice(),
+                _ => std::slice::from_ref(*value),
+            };
+            for value in values {
+                if let Some(point) = self.get_value(value) {
+                    points.push(point);
+                } else if let Some(polygon) = GeoPolygon::from_payload(value) {
+                    polygons.push(polygon.exterior.points);
+                }
+            }
+        }
+        match self {
+            GeoMapIndex::Mutable(index) => index.add_many_geo_shapes(id, &points, polygons),
+            GeoMapIndex::Immutable(_) | GeoMapIndex::Mmap(_) => Err(OperationError::service_error(
+                "Can't add values to immutable geo index",
+            )),
+        }
+    }
+
     fn remove_point(&mut self, id: PointOffsetType) -> OperationResult<()> {
         match self {
             GeoMapIndex::Mutable(index) => index.remove_point(id),
             GeoMapIndex::Immutable(index) => index.remove_point(id),
+            GeoMapIndex::Mmap(index) => index.remove_point(id),
         }
     }
 }
@@ -344,11 +463,16 @@ impl PayloadFieldIndex for GeoMapIndex {
         match self {
             GeoMapIndex::Mutable(index) => index.load(),
             GeoMapIndex::Immutable(index) => index.load(),
+            GeoMapIndex::Mmap(index) => index.load(),
         }
     }
 
     fn clear(self) -> OperationResult<()> {
-        self.db_wrapper().remove_column_family()
+        match self {
+            GeoMapIndex::Mutable(index) => index.db_wrapper().remove_column_family(),
+            GeoMapIndex::Immutable(index) => index.db_wrapper().remove_column_family(),
+            GeoMapIndex::Mmap(index) => index.clear(),
+        }
     }
 
     fn flusher(&self) -> Flusher {
@@ -364,10 +488,7 @@ impl PayloadFieldIndex for GeoMapIndex {
             let geo_condition_copy = geo_bounding_box.clone();
             return Ok(Box::new(self.get_iterator(geo_hashes).filter(
                 move |point| {
-                    self.get_values(*point)
-                        .unwrap()
-                        .iter()
-                        .any(|point| geo_condition_copy.check_point(point))
+                    self.check_values(*point, |point| geo_condition_copy.check_point(point))
                 },
             )));
         }
@@ -377,10 +498,7 @@ impl PayloadFieldIndex for GeoMapIndex {
             let geo_condition_copy = geo_radius.clone();
             return Ok(Box::new(self.get_iterator(geo_hashes).filter(
                 move |point| {
-                    self.get_values(*point)
-                        .unwrap()
-                        .iter()
-                        .any(|point| geo_condition_copy.check_point(point))
+                    self.check_values(*point, |point| geo_condition_copy.check_point(point))
                 },
             )));
         }
@@ -390,10 +508,7 @@ impl PayloadFieldIndex for GeoMapIndex {
             let geo_condition_copy = geo_polygon.convert();
             return Ok(Box::new(self.get_iterator(geo_hashes).filter(
                 move |point| {
-                    self.get_values(*point)
-                        .unwrap()
-                        .iter()
-                        .any(|point| geo_condition_copy.check_point(point))
+                    self.check_values(*point, |point| geo_condition_copy.check_point(point))
                 },
             )));
         }
@@ -464,7 +579,7 @@ impl PayloadFieldIndex for GeoMapIndex {
                 .map(move |(geo_hash, size)| PayloadBlockCondition {
                     condition: FieldCondition::new_geo_bounding_box(
                         key.clone(),
-                        geo_hash_to_box(geo_hash),
+                        geo_hash_to_box(&geo_hash),
                     ),
                     cardinality: size,
                 }),
@@ -1049,6 +1164,75 @@ mod tests {
         assert_eq!(point_offsets, vec![1]);
     }
 
+    #[rstest]
+    #[case(true)]
+    #[case(false)]
+    fn polygon_payload_filtering(#[case] is_appendable: bool) {
+        let temp_dir = Builder::new().prefix("test_dir").tempdir().unwrap();
+        let polygon_around = |center: &GeoPoint, size: f64| {
+            json!({
+                "exterior": {
+                    "points": [
+                        {"lon": center.lon - size, "lat": center.lat - size},
+                        {"lon": center.lon + size, "lat": center.lat - size},
+                        {"lon": center.lon + size, "lat": center.lat + size},
+                        {"lon": center.lon - size, "lat": center.lat + size},
+                        {"lon": center.lon - size, "lat": center.lat - size},
+                    ]
+                }
+            })
+        };
+        {
+            let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+            let mut index = GeoMapIndex::new(db, FIELD_NAME, true);
+            index.recreate().unwrap();
+
+            let small_berlin = polygon_around(&BERLIN, 0.01);
+            let large_berlin = polygon_around(&BERLIN, 2.0);
+            let potsdam = json!({"lon": POTSDAM.lon, "lat": POTSDAM.lat});
+            index.add_point(1, &[&small_berlin]).unwrap();
+            index.add_point(2, &[&large_berlin]).unwrap();
+            index.add_point(3, &[&potsdam]).unwrap();
+            index.add_point(4, &[&small_berlin]).unwrap();
+            index.remove_point(4).unwrap();
+            index.flusher()().unwrap();
+
+            assert_eq!(index.points_count(), 3);
+            assert_eq!(index.values_count(1), 1);
+            // Closing point of polygons is not duplicated
+            assert_eq!(index.points_values_count(), 4 + 4 + 1);
+        }
+
+        let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+        let mut new_index = GeoMapIndex::new(db, FIELD_NAME, is_appendable);
+        new_index.load().unwrap();
+        assert_eq!(new_index.points_count(), 3);
+        assert_eq!(new_index.points_values_count(), 4 + 4 + 1);
+
+        let berlin_geo_radius = GeoRadius {
+            center: BERLIN,
+            radius: 50_000.0,
+        };
+
+        // Only the small polygon is within the area, large one crosses its border
+        for field_condition in [
+            condition_for_geo_radius("test", berlin_geo_radius.clone()),
+            condition_for_geo_polygon("test", radius_to_polygon(&berlin_geo_radius)),
+        ] {
+            let point_offsets = new_index
+                .filter(&field_condition)
+                .unwrap()
+                .sorted()
+                .collect_vec();
+            assert_eq!(point_offsets, vec![1, 3]);
+
+            let cardinality = new_index.estimate_cardinality(&field_condition).unwrap();
+            assert!(cardinality.max >= 2);
+        }
+        assert!(new_index.check_radius(1, &berlin_geo_radius));
+        assert!(!new_index.check_radius(2, &berlin_geo_radius));
+    }
+
     #[rstest]
     #[case(true)]
     #[case(false)]
@@ -1233,4 +1417,83 @@ mod tests {
         // Only LOS_ANGELES is in the bounding box
         assert_eq!(point_offsets, vec![2]);
     }
+
+    #[test]
+    fn test_mmap_index() {
+        let temp_dir = Builder::new().prefix("test_dir").tempdir().unwrap();
+        let index_path = temp_dir.path().join("test_index");
+        let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+        let polygon = json!({
+            "exterior": {
+                "points": [
+                    {"lon": BERLIN.lon - 0.01, "lat": BERLIN.lat - 0.01},
+                    {"lon": BERLIN.lon + 0.01, "lat": BERLIN.lat - 0.01},
+                    {"lon": BERLIN.lon + 0.01, "lat": BERLIN.lat + 0.01},
+                    {"lon": BERLIN.lon - 0.01, "lat": BERLIN.lat - 0.01},
+                ]
+            }
+        });
+        {
+            let mut index = GeoMapIndex::new(db.clone(), FIELD_NAME, true);
+            index.recreate().unwrap();
+            let berlin = json!({"lon": BERLIN.lon, "lat": BERLIN.lat});
+            let potsdam = json!({"lon": POTSDAM.lon, "lat": POTSDAM.lat});
+            let tokyo = json!({"lon": TOKYO.lon, "lat": TOKYO.lat});
+            index.add_point(1, &[&berlin]).unwrap();
+            index.add_point(2, &[&potsdam]).unwrap();
+            index.add_point(3, &[&tokyo]).unwrap();
+            index.add_point(4, &[&polygon]).unwrap();
+            index.flusher()().unwrap();
+        }
+
+        let berlin_geo_radius = GeoRadius {
+            center: BERLIN,
+            radius: 50_000.0,
+        };
+        let condition = condition_for_geo_radius("test", berlin_geo_radius.clone());
+        let filter = |index: &GeoMapIndex| index.filter(&condition).unwrap().sorted().collect_vec();
+
+        // Files are built from db on first load
+        let mut index = GeoMapIndex::new_mmap(db.clone(), FIELD_NAME, &index_path);
+        assert!(index.load().unwrap());
+        assert!(matches!(index, GeoMapIndex::Mmap(_)));
+        assert!(index_path.join("geo_index.bin").exists());
+        assert_eq!(index.points_count(), 4);
+        assert_eq!(index.points_values_count(), 3 + 3);
+        assert_eq!(index.get_values(3), Some([TOKYO].as_slice()));
+        assert_eq!(index.get_polygons(4).len(), 1);
+        assert_eq!(filter(&index), vec![1, 2, 4]);
+        assert_eq!(index.get_points_of_hash(&Default::default()), 4);
+        assert!(index
+            .add_point(5, &[&json!({"lon": 0.0, "lat": 0.0})])
+            .is_err());
+
+        index.remove_point(2).unwrap();
+        index.remove_point(4).unwrap();
+        assert_eq!(index.points_count(), 2);
+        assert_eq!(filter(&index), vec![1]);
+        assert!(index.prefault_mmap_pages().is_some());
+        index.clear_cache().unwrap();
+        index.flusher()().unwrap();
+        drop(index);
+
+        // Deletions are persisted in the files and in db
+        let mut index = GeoMapIndex::new_mmap(db.clone(), FIELD_NAME, &index_path);
+        assert!(index.load().unwrap());
+        assert_eq!(index.points_count(), 2);
+        assert_eq!(index.points_values_count(), 2);
+        assert_eq!(index.get_values(2), Some([].as_slice()));
+        assert!(index.get_polygons(4).is_empty());
+        assert_eq!(filter(&index), vec![1]);
+        drop(index);
+
+        std::fs::remove_dir_all(&index_path).unwrap();
+        let mut index = GeoMapIndex::new_mmap(db, FIELD_NAME, &index_path);
+        assert!(index.load().unwrap());
+        assert_eq!(index.points_count(), 2);
+        assert_eq!(filter(&index), vec![1]);
+
+        index.clear().unwrap();
+        assert!(!index_path.join("geo_index.bin").exists());
+    }
 }
@@ -35,6 +35,10 @@ pub struct MutableGeoMapIndex {
      */
     pub points_map: BTreeMap<GeoHash, HashSet<PointOffsetType>>,
     pub point_to_values: Vec<Vec<GeoPoint>>,
+    /// Exterior points of polygons, stored in payload.
+    /// They are stored in the grid like any other points, but a polygon only matches
+    /// a condition if all of its points match.
+    pub point_to_polygons: BTreeMap<PointOffsetType, Vec<Vec<GeoPoint>>>,
     pub points_count: usize,
     pub points_values_count: usize,
     pub max_values_per_point: usize,
@@ -49,6 +53,7 @@ impl MutableGeoMapIndex {
             values_per_hash: Default::default(),
             points_map: Default::default(),
             point_to_values: vec![],
+            point_to_polygons: Default::default(),
             points_count: 0,
             points_values_count: 0,
             max_values_per_point: 0,
@@ -64,6 +69,12 @@ impl MutableGeoMapIndex {
         self.point_to_values.get(idx as usize).map(Vec::as_slice)
     }
 
+    pub fn get_polygons(&self, idx: PointOffsetType) -> &[Vec<GeoPoint>] {
+        self.point_to_polygons
+            .get(&idx)
+            .map_or(&[], |polygons| polygons.as_slice())
+    }
+
     pub fn get_points_per_hash(&self) -> impl Iterator<Item = (&GeoHash, usize)> {
         self.points_per_hash
             .iter()
@@ -90,23 +101,28 @@ impl MutableGeoMapIndex {
                 OperationError::service_error("Index load error: UTF8 error while DB parsing")
             })?;
 
-            let (geo_hash, idx) = GeoMapIndex::decode_db_key(key_str)?;
+            let (geo_hash, idx, polygon) = GeoMapIndex::decode_db_key(key_str)?;
             let geo_point = GeoMapIndex::decode_db_value(value)?;
 
             if self.point_to_values.len() <= idx as usize {
                 self.point_to_values.resize_with(idx as usize + 1, Vec::new);
             }
 
-            if self.point_to_values[idx as usize].is_empty() {
-                self.points_count += 1;
-            }
-
             points_to_hashes
                 .entry(idx)
                 .or_default()
                 .push(geo_hash.clone());
 
-            self.point_to_values[idx as usize].push(geo_point);
+            match polygon {
+                None => self.point_to_values[idx as usize].push(geo_point),
+                Some(polygon) => {
+                    let polygons = self.point_to_polygons.entry(idx).or_default();
+                    if polygons.len() <= polygon {
+                        polygons.resize_with(polygon + 1, Vec::new);
+                    }
+                    polygons[polygon].push(geo_point);
+                }
+            }
             self.points_map
                 .entry(geo_hash.clone())
                 .or_default()
@@ -115,6 +131,7 @@ impl MutableGeoMapIndex {
             self.points_values_count += 1;
         }
 
+        self.points_count = points_to_hashes.len();
         for (_idx, geo_hashes) in points_to_hashes.into_iter() {
             self.max_values_per_point = max(self.max_values_per_point, geo_hashes.len());
             self.increment_hash_point_counts(&geo_hashes);
@@ -126,26 +143,29 @@ impl MutableGeoMapIndex {
     }
 
     pub fn remove_point(&mut self, idx: PointOffsetType) -> OperationResult<()> {
-        if self.point_to_values.len() <= idx as usize {
+        let removed_geo_points = self
+            .point_to_values
+            .get_mut(idx as usize)
+            .map(std::mem::take)
+            .unwrap_or_default();
+        let removed_polygons = self.point_to_polygons.remove(&idx).unwrap_or_default();
+
+        if removed_geo_points.is_empty() && removed_polygons.is_empty() {
             return Ok(()); // Already removed or never actually existed
         }
 
-        let removed_geo_points = std::mem::take(&mut self.point_to_values[idx as usize]);
-
-        if removed_geo_points.is_empty() {
-            return Ok(());
-        }
+        let removed_values = GeoMapIndex::db_values(removed_geo_points, removed_polygons);
 
         self.points_count -= 1;
-        self.points_values_count -= removed_geo_points.len();
-        let mut removed_geo_hashes = Vec::with_capacity(removed_geo_points.len());
+        self.points_values_count -= removed_values.len();
+        let mut removed_geo_hashes = Vec::with_capacity(removed_values.len());
 
-        for removed_geo_point in removed_geo_points {
+        for (removed_geo_point, polygon) in removed_values {
             let removed_geo_hash: GeoHash =
                 encode_max_precision(removed_geo_point.lon, removed_geo_point.lat).unwrap();
             removed_geo_hashes.push(removed_geo_hash.clone());
 
-            let key = GeoMapIndex::encode_db_key(&removed_geo_hash, idx);
+            let key = GeoMapIndex::encode_db_key(&removed_geo_hash, idx, polygon);
             self.db_wrapper.remove(key)?;
 
             let is_last = if let Some(hash_ids) = self.points_map.get_mut(&removed_geo_hash) {
@@ -175,7 +195,36 @@ impl MutableGeoMapIndex {
         idx: PointOffsetType,
         values: &[GeoPoint],
     ) -> OperationResult<()> {
-        if values.is_empty() {
+        self.add_many_geo_shapes(idx, values, vec![])
+    }
+
+    /// Add points and polygons, given by their exterior points
+    pub fn add_many_geo_shapes(
+        &mut self,
+        idx: PointOffsetType,
+        values: &[GeoPoint],
+        polygons: Vec<Vec<GeoPoint>>,
+    ) -> OperationResult<()> {
+        // Points of polygons are unique within the polygon, as they are stored by geohash
+        let mut unique_polygons = Vec::with_capacity(polygons.len());
+        for polygon in polygons {
+            let mut seen_hashes = HashSet::new();
+            let mut unique_points = Vec::with_capacity(polygon.len());
+            for point in polygon {
+                let hash = encode_max_precision(point.lon, point.lat).map_err(|e| {
+                    OperationError::service_error(format!("Malformed geo points: {e}"))
+                })?;
+                if seen_hashes.insert(hash) {
+                    unique_points.push(point);
+                }
+            }
+            if !unique_points.is_empty() {
+                unique_polygons.push(unique_points);
+            }
+        }
+        let polygons = unique_polygons;
+
+        if values.is_empty() && polygons.is_empty() {
             return Ok(());
         }
 
@@ -185,14 +234,18 @@ impl MutableGeoMapIndex {
         }
 
         self.point_to_values[idx as usize] = values.to_vec();
+        if !polygons.is_empty() {
+            self.point_to_polygons.insert(idx, polygons.clone());
+        }
 
+        let added_values = GeoMapIndex::db_values(values.to_vec(), polygons);
         let mut geo_hashes = vec![];
 
-        for added_point in values {
+        for (added_point, polygon) in &added_values {
             let added_geo_hash: GeoHash = encode_max_precision(added_point.lon, added_point.lat)
                 .map_err(|e| OperationError::service_error(format!("Malformed geo points: {e}")))?;
 
-            let key = GeoMapIndex::encode_db_key(&added_geo_hash, idx);
+            let key = GeoMapIndex::encode_db_key(&added_geo_hash, idx, *polygon);
             let value = GeoMapIndex::encode_db_value(added_point);
 
             geo_hashes.push(added_geo_hash);
@@ -211,9 +264,9 @@ impl MutableGeoMapIndex {
 
         self.increment_hash_point_counts(&geo_hashes);
 
-        self.points_values_count += values.len();
+        self.points_values_count += added_values.len();
         self.points_count += 1;
-        self.max_values_per_point = self.max_values_per_point.max(values.len());
+        self.max_values_per_point = self.max_values_per_point.max(added_values.len());
         Ok(())
     }
 
@@ -42,6 +42,7 @@ pub trait Numericable: Num + Signed + PartialEq + PartialOrd + Copy {
     fn from_f64(x: f64) -> Self;
     fn to_i64(self) -> i64;
     fn from_i64(x: i64) -> Self;
+    fn from_i128(x: i128) -> Self;
     fn min(self, b: Self) -> Self {
         if self < b {
             self
@@ -84,11 +85,41 @@ impl Numericable for i64 {
     fn from_i64(x: i64) -> Self {
         x
     }
+    fn from_i128(x: i128) -> Self {
+        x.clamp(i64::MIN.into(), i64::MAX.into()) as i64
+    }
     fn abs_diff(self, b: Self) -> Self {
         i64::abs_diff(self, b) as i64
     }
 }
 
+impl Numericable for i128 {
+    fn min_value() -> Self {
+        i128::MIN
+    }
+    fn max_value() -> Self {
+        i128::MAX
+    }
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+    fn from_f64(x: f64) -> Self {
+        x as Self
+    }
+    fn to_i64(self) -> i64 {
+        i64::from_i128(self)
+    }
+    fn from_i64(x: i64) -> Self {
+        x.into()
+    }
+    fn from_i128(x: i128) -> Self {
+        x
+    }
+    fn abs_diff(self, b: Self) -> Self {
+        i128::abs_diff(self, b).min(i128::MAX as u128) as i128
+    }
+}
+
 impl Numericable for f64 {
     fn min_value() -> Self {
         f64::MIN
@@ -108,6 +139,9 @@ impl Numericable for f64 {
     fn from_i64(x: i64) -> Self {
         x as Self
     }
+    fn from_i128(x: i128) -> Self {
+        x as Self
+    }
 }
 
 #[derive(Debug)]
@@ -158,6 +192,23 @@ impl<T: Numericable> Histogram<T> {
         self.total_count
     }
 
+    /// Ranges between consecutive borders with the number of values in each.
+    ///
+    /// Left border of a range is included, right one is excluded, so counts of all ranges sum up
+    /// to the total count. The last range is zero-length and contains the most-right border only.
+    pub fn value_ranges(&self) -> Vec<(T, T, usize)> {
+        let mut ranges: Vec<_> = self
+            .borders
+            .iter()
+            .tuple_windows()
+            .map(|((a, a_count), (b, _b_count))| (a.val, b.val, a_count.right + 1))
+            .collect();
+        if let Some((last, _)) = self.borders.last_key_value() {
+            ranges.push((last.val, last.val, 1));
+        }
+        ranges
+    }
+
     /// Infers boundaries for bucket of given size and starting point.
     /// Returns `to` range of values starting provided `from`value which is expected to contain
     /// `range_size` values
@@ -33,6 +33,15 @@ impl<N: Default> ImmutablePointToValues<N> {
         }
     }
 
+    /// Number of points, including the ones without values
+    pub fn len(&self) -> usize {
+        self.point_to_values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.point_to_values.is_empty()
+    }
+
     pub fn get_values(&self, idx: PointOffsetType) -> Option<&[N]> {
         let range = self.point_to_values.get(idx as usize)?.clone();
         let range = range.start as usize..range.end as usize;
@@ -1,3 +1,4 @@
+use std::path::Path;
 use std::sync::Arc;
 
 use parking_lot::RwLock;
@@ -7,19 +8,24 @@ use super::binary_index::BinaryIndex;
 use crate::index::field_index::full_text_index::text_index::FullTextIndex;
 use crate::index::field_index::geo_index::GeoMapIndex;
 use crate::index::field_index::map_index::MapIndex;
+use crate::index::field_index::min_hash_index::MinHashIndex;
 use crate::index::field_index::numeric_index::NumericIndex;
 use crate::index::field_index::FieldIndex;
 use crate::json_path::JsonPath;
 use crate::types::{
-    FloatPayloadType, IntPayloadType, PayloadFieldSchema, PayloadSchemaParams, PayloadSchemaType,
+    BigIntPayloadType, FloatPayloadType, IntPayloadType, PayloadFieldSchema, PayloadSchemaParams,
+    PayloadSchemaType,
 };
 
 /// Selects index types based on field type
+///
+/// Indexes, which are stored in files rather than in `db`, keep them in `path`.
 pub fn index_selector(
     field: &JsonPath,
     payload_schema: &PayloadFieldSchema,
     db: Arc<RwLock<DB>>,
     is_appendable: bool,
+    path: &Path,
 ) -> Vec<FieldIndex> {
     let field: String = field.to_string();
     let field = field.as_str();
@@ -63,6 +69,14 @@ pub fn index_selector(
                     NumericIndex::<IntPayloadType>::new(db, field, is_appendable),
                 )]
             }
+            PayloadSchemaType::BigInt => {
+                vec![FieldIndex::BigIntIndex(
+                    NumericIndex::<BigIntPayloadType>::new(db, field, is_appendable),
+                )]
+            }
+            PayloadSchemaType::MinHash => {
+                vec![FieldIndex::MinHashIndex(MinHashIndex::new(db, field))]
+            }
         },
         PayloadFieldSchema::FieldParams(payload_params) => match payload_params {
             PayloadSchemaParams::Text(text_index_params) => vec![FieldIndex::FullTextIndex(
@@ -81,6 +95,14 @@ pub fn index_selector(
                 });
                 lookup.into_iter().chain(range).collect()
             }
+            PayloadSchemaParams::Geo(geo_params) => {
+                let index = if geo_params.on_disk.unwrap_or(false) && !is_appendable {
+                    GeoMapIndex::new_mmap(db, field, path)
+                } else {
+                    GeoMapIndex::new(db, field, is_appendable)
+                };
+                vec![FieldIndex::GeoIndex(index)]
+            }
         },
     }
 }
@@ -1,24 +1,35 @@
-use std::collections::HashMap;
+use std::borrow::Borrow;
 use std::fmt::Display;
 use std::hash::Hash;
 use std::iter;
-use std::ops::Range;
+use std::ops::Bound;
 use std::str::FromStr;
 use std::sync::Arc;
 
 use common::types::PointOffsetType;
 use parking_lot::RwLock;
+use roaring::RoaringBitmap;
 use rocksdb::DB;
 
 use super::mutable_map_index::MutableMapIndex;
+use super::perfect_hash::PerfectHash;
 use super::MapIndex;
 use crate::common::operation_error::OperationResult;
 use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
 use crate::index::field_index::immutable_point_to_values::ImmutablePointToValues;
 
-pub struct ImmutableMapIndex<N: Hash + Eq + Clone + Display + FromStr + Default> {
-    value_to_points: HashMap<N, Range<u32>>,
-    value_to_points_container: Vec<PointOffsetType>,
+pub struct ImmutableMapIndex<N: Hash + Eq + Ord + Clone + Display + FromStr + Default> {
+    /// Maps values to the slots of `slot_to_value`
+    value_hash: PerfectHash,
+    /// Position in `values` of the value of each slot
+    slot_to_value: Vec<u32>,
+    /// Distinct values in ascending order, for range queries.
+    /// Values of removed points stay here, with an empty range of points.
+    values: Vec<N>,
+    /// Points of each value in `values`
+    value_to_points: Vec<RoaringBitmap>,
+    /// Amount of values, which have at least one point
+    unique_values_count: usize,
     point_to_values: ImmutablePointToValues<N>,
     /// Amount of point which have at least one indexed payload value
     indexed_points: usize,
@@ -26,13 +37,16 @@ pub struct ImmutableMapIndex<N: Hash + Eq + Clone + Display + FromStr + Default>
     db_wrapper: DatabaseColumnWrapper,
 }
 
-impl<N: Hash + Eq + Clone + Display + FromStr + Default> ImmutableMapIndex<N> {
+impl<N: Hash + Eq + Ord + Clone + Display + FromStr + Default> ImmutableMapIndex<N> {
     pub fn new(db: Arc<RwLock<DB>>, field_name: &str) -> Self {
         let store_cf_name = MapIndex::<N>::storage_cf_name(field_name);
         let db_wrapper = DatabaseColumnWrapper::new(db, &store_cf_name);
         Self {
+            value_hash: Default::default(),
+            slot_to_value: Default::default(),
+            values: Default::default(),
             value_to_points: Default::default(),
-            value_to_points_container: Default::default(),
+            unique_values_count: 0,
             point_to_values: Default::default(),
             indexed_points: 0,
             values_count: 0,
@@ -40,84 +54,27 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> ImmutableMapIndex<N> {
         }
     }
 
-    /// Return mutable slice of a container which holds point_ids for given value.
-    fn get_mut_point_ids_slice<'a>(
-        value_to_points: &mut HashMap<N, Range<u32>>,
-        value_to_points_container: &'a mut [PointOffsetType],
-        value: &N,
-    ) -> Option<&'a mut [PointOffsetType]> {
-        match value_to_points.get(value) {
-            Some(vals_range) if vals_range.start < vals_range.end => {
-                let range = vals_range.start as usize..vals_range.end as usize;
-                let vals = &mut value_to_points_container[range];
-                Some(vals)
-            }
-            _ => None,
-        }
+    /// Position of the value in `values`
+    fn value_position<Q>(&self, value: &Q) -> Option<usize>
+    where
+        Q: ?Sized,
+        N: Borrow<Q>,
+        Q: Hash + Eq,
+    {
+        let slot = self.value_hash.slot(value)?;
+        let position = self.slot_to_value[slot] as usize;
+        // Values outside of the index are mapped to arbitrary slots
+        (self.values[position].borrow() == value).then_some(position)
     }
 
-    /// Shrinks the range of values-to-points by one.
-    /// Returns true if the last element was removed.
-    fn shrink_value_range(value_to_points: &mut HashMap<N, Range<u32>>, value: &N) -> bool {
-        if let Some(range) = value_to_points.get_mut(value) {
-            range.end -= 1;
-            return range.start == range.end; // true if the last element was removed
-        }
-        false
-    }
-
-    /// Removes `idx` from values-to-points-container.
-    /// It is implemented by shrinking the range of values-to-points by one and moving the removed element
-    /// out of the range.
-    /// Previously last element is swapped with the removed one and then the range is shrank by one.
-    ///
-    ///
-    /// Example:
-    ///     Before:
-    ///
-    /// value_to_points -> {
-    ///     "a": 0..5,
-    ///     "b": 5..10
-    /// }
-    /// value_to_points_container -> [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
-    ///
-    /// Args:
-    ///   value: "a"
-    ///   idx: 3
-    ///
-    /// After:
-    ///
-    /// value_to_points -> {
-    ///    "a": 0..4,
-    ///    "b": 5..10
-    /// }
-    ///
-    /// value_to_points_container -> [0, 1, 2, 4, (3), 5, 6, 7, 8, 9]
-    fn remove_idx_from_value_list(
-        value_to_points: &mut HashMap<N, Range<u32>>,
-        value_to_points_container: &mut [PointOffsetType],
-        value: &N,
-        idx: PointOffsetType,
-    ) {
-        let values = if let Some(values) =
-            Self::get_mut_point_ids_slice(value_to_points, value_to_points_container, value)
-        {
-            values
-        } else {
-            debug_assert!(false, "value {} not found in value_to_points", value);
-            return;
-        };
-
-        // Finds the index of `idx` in values-to-points map and swaps it with the last element.
-        // So that removed element is out of the shrank range.
-        if let Some(pos) = values.iter().position(|&x| x == idx) {
-            // remove `idx` from values-to-points map by swapping it with the last element
-            values.swap(pos, values.len() - 1);
-        }
-
-        if Self::shrink_value_range(value_to_points, value) {
-            value_to_points.remove(value);
-        }
+    pub fn get_points<Q>(&self, value: &Q) -> Option<&RoaringBitmap>
+    where
+        Q: ?Sized,
+        N: Borrow<Q>,
+        Q: Hash + Eq,
+    {
+        let points = &self.value_to_points[self.value_position(value)?];
+        (!points.is_empty()).then_some(points)
     }
 
     pub fn remove_point(&mut self, idx: PointOffsetType) -> OperationResult<()> {
@@ -128,12 +85,15 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> ImmutableMapIndex<N> {
             self.values_count -= removed_values.len();
 
             for value in removed_values {
-                Self::remove_idx_from_value_list(
-                    &mut self.value_to_points,
-                    &mut self.value_to_points_container,
-                    value,
-                    idx,
-                );
+                match self.value_position(value) {
+                    Some(position) => {
+                        let points = &mut self.value_to_points[position];
+                        if points.remove(idx) && points.is_empty() {
+                            self.unique_values_count -= 1;
+                        }
+                    }
+                    None => debug_assert!(false, "value {value} not found in value_to_points"),
+                }
                 // update db
                 let key = MapIndex::encode_db_record(value, idx);
                 self.db_wrapper.remove(key)?;
@@ -169,16 +129,20 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> ImmutableMapIndex<N> {
 
         self.indexed_points = indexed_points;
         self.values_count = values_count;
-        self.value_to_points.clear();
-        self.value_to_points_container.clear();
-
-        // flatten values-to-points map
-        for (value, points) in map {
-            let points = points.into_iter().collect::<Vec<_>>();
-            let container_len = self.value_to_points_container.len() as u32;
-            let range = container_len..container_len + points.len() as u32;
-            self.value_to_points.insert(value, range.clone());
-            self.value_to_points_container.extend(points);
+
+        let mut map: Vec<_> = map.into_iter().collect();
+        map.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+
+        (self.values, self.value_to_points) = map.into_iter().unzip();
+        self.unique_values_count = self.values.len();
+
+        self.value_hash = PerfectHash::build(&self.values)?;
+        self.slot_to_value = vec![0; self.values.len()];
+        for (position, value) in self.values.iter().enumerate() {
+            // Hash is built over these values, so each of them has a slot
+            if let Some(slot) = self.value_hash.slot(value) {
+                self.slot_to_value[slot] = position as u32;
+            }
         }
 
         self.point_to_values = ImmutablePointToValues::new(point_to_values);
@@ -190,6 +154,11 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> ImmutableMapIndex<N> {
         self.point_to_values.get_values(idx)
     }
 
+    /// Upper bound of offsets of points with values
+    pub fn get_point_ids_bound(&self) -> PointOffsetType {
+        self.point_to_values.len() as PointOffsetType
+    }
+
     pub fn get_indexed_points(&self) -> usize {
         self.indexed_points
     }
@@ -199,7 +168,7 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> ImmutableMapIndex<N> {
     }
 
     pub fn get_unique_values_count(&self) -> usize {
-        self.value_to_points.len()
+        self.unique_values_count
     }
 
     pub fn get_points_with_value_count<Q>(&self, value: &Q) -> Option<usize>
@@ -208,7 +177,7 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> ImmutableMapIndex<N> {
         N: std::borrow::Borrow<Q>,
         Q: Hash + Eq,
     {
-        self.value_to_points.get(value).map(|p| p.len())
+        self.get_points(value).map(|points| points.len() as usize)
     }
 
     pub fn get_iterator<Q>(&self, value: &Q) -> Box<dyn Iterator<Item = PointOffsetType> + '_>
@@ -217,15 +186,53 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> ImmutableMapIndex<N> {
         N: std::borrow::Borrow<Q>,
         Q: Hash + Eq,
     {
-        if let Some(range) = self.value_to_points.get(value) {
-            let range = range.start as usize..range.end as usize;
-            Box::new(self.value_to_points_container[range].iter().cloned())
+        if let Some(points) = self.get_points(value) {
+            Box::new(points.iter())
         } else {
             Box::new(iter::empty::<PointOffsetType>())
         }
     }
 
     pub fn get_values_iterator(&self) -> Box<dyn Iterator<Item = &N> + '_> {
-        Box::new(self.value_to_points.keys())
+        Box::new(Self::existing_values(&self.values, &self.value_to_points))
+    }
+
+    /// Values within the bounds, in ascending order
+    pub fn get_values_range<Q>(
+        &self,
+        start: Bound<&Q>,
+        end: Bound<&Q>,
+    ) -> Box<dyn Iterator<Item = &N> + '_>
+    where
+        Q: ?Sized + Ord,
+        N: Borrow<Q>,
+    {
+        let start_pos = match start {
+            Bound::Included(start) => self.values.partition_point(|v| v.borrow() < start),
+            Bound::Excluded(start) => self.values.partition_point(|v| v.borrow() <= start),
+            Bound::Unbounded => 0,
+        };
+        let end_pos = match end {
+            Bound::Included(end) => self.values.partition_point(|v| v.borrow() <= end),
+            Bound::Excluded(end) => self.values.partition_point(|v| v.borrow() < end),
+            Bound::Unbounded => self.values.len(),
+        };
+        let positions = start_pos..end_pos.max(start_pos);
+        Box::new(Self::existing_values(
+            &self.values[positions.clone()],
+            &self.value_to_points[positions],
+        ))
+    }
+
+    /// Values, which still have points
+    fn existing_values<'a>(
+        values: &'a [N],
+        value_to_points: &'a [RoaringBitmap],
+    ) -> impl Iterator<Item = &'a N> + 'a {
+        values
+            .iter()
+            .zip(value_to_points)
+            .filter(|(_, points)| !points.is_empty())
+            .map(|(value, _)| value)
     }
 }
@@ -1,17 +1,19 @@
 pub mod immutable_map_index;
 pub mod mutable_map_index;
+pub mod perfect_hash;
 
 use std::fmt::Display;
 use std::hash::{BuildHasher, Hash};
+use std::ops::Bound;
 use std::str::FromStr;
 use std::sync::Arc;
 
 use common::types::PointOffsetType;
 use immutable_map_index::ImmutableMapIndex;
 use indexmap::IndexSet;
-use itertools::Itertools;
 use mutable_map_index::MutableMapIndex;
 use parking_lot::RwLock;
+use roaring::RoaringBitmap;
 use rocksdb::DB;
 use serde_json::Value;
 use smol_str::SmolStr;
@@ -27,15 +29,18 @@ use crate::index::query_estimator::combine_should_estimations;
 use crate::telemetry::PayloadIndexTelemetry;
 use crate::types::{
     AnyVariants, FieldCondition, IntPayloadType, Match, MatchAny, MatchExcept, MatchValue,
-    PayloadKeyType, ValueVariants,
+    PayloadKeyType, Range, RangeInterface, ValueVariants,
 };
 
-pub enum MapIndex<N: Hash + Eq + Clone + Display + FromStr + Default> {
+/// Max number of other values, for which `except` is evaluated as a union of their points
+const EXCEPT_UNION_MAX_VALUES: usize = 64;
+
+pub enum MapIndex<N: Hash + Eq + Ord + Clone + Display + FromStr + Default> {
     Mutable(MutableMapIndex<N>),
     Immutable(ImmutableMapIndex<N>),
 }
 
-impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
+impl<N: Hash + Eq + Ord + Clone + Display + FromStr + Default> MapIndex<N> {
     pub fn new(db: Arc<RwLock<DB>>, field_name: &str, is_appendable: bool) -> Self {
         if is_appendable {
             MapIndex::Mutable(MutableMapIndex::new(db, field_name))
@@ -65,6 +70,13 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
         }
     }
 
+    fn get_point_ids_bound(&self) -> PointOffsetType {
+        match self {
+            MapIndex::Mutable(index) => index.get_point_ids_bound(),
+            MapIndex::Immutable(index) => index.get_point_ids_bound(),
+        }
+    }
+
     fn get_indexed_points(&self) -> usize {
         match self {
             MapIndex::Mutable(index) => index.get_indexed_points(),
@@ -110,6 +122,35 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
         }
     }
 
+    fn get_points<Q>(&self, value: &Q) -> Option<&RoaringBitmap>
+    where
+        Q: ?Sized,
+        N: std::borrow::Borrow<Q>,
+        Q: Hash + Eq,
+    {
+        match self {
+            MapIndex::Mutable(index) => index.get_points(value),
+            MapIndex::Immutable(index) => index.get_points(value),
+        }
+    }
+
+    /// Points, which have any of the `values`
+    fn union_points<'a, Q>(&'a self, values: impl IntoIterator<Item = &'a Q>) -> RoaringBitmap
+    where
+        Q: ?Sized + 'a,
+        N: std::borrow::Borrow<Q>,
+        Q: Hash + Eq,
+    {
+        let mut union = RoaringBitmap::new();
+        for points in values
+            .into_iter()
+            .filter_map(|value| self.get_points(value))
+        {
+            union |= points;
+        }
+        union
+    }
+
     fn get_values_iterator(&self) -> Box<dyn Iterator<Item = &N> + '_> {
         match self {
             MapIndex::Mutable(index) => index.get_values_iterator(),
@@ -117,6 +158,21 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
         }
     }
 
+    fn get_values_range<Q>(
+        &self,
+        start: Bound<&Q>,
+        end: Bound<&Q>,
+    ) -> Box<dyn Iterator<Item = &N> + '_>
+    where
+        Q: ?Sized + Ord,
+        N: std::borrow::Borrow<Q>,
+    {
+        match self {
+            MapIndex::Mutable(index) => index.get_values_range(start, end),
+            MapIndex::Immutable(index) => index.get_values_range(start, end),
+        }
+    }
+
     pub fn storage_cf_name(field: &str) -> String {
         format!("{field}_map")
     }
@@ -170,6 +226,14 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
         Ok((value, idx))
     }
 
+    /// Distinct values, which have at least one point
+    pub fn iter_values(&self) -> impl Iterator<Item = &N> + '_ {
+        self.get_values_iterator().filter(|value| {
+            self.get_points_with_value_count(*value)
+                .is_some_and(|count| count > 0)
+        })
+    }
+
     pub fn values_count(&self, point_id: PointOffsetType) -> usize {
         self.get_values(point_id).map(|x| x.len()).unwrap_or(0)
     }
@@ -292,6 +356,10 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
         }
     }
 
+    /// Points, which have any value except the `excluded` ones
+    ///
+    /// With few other values, it is a union of their points. Otherwise, the complement of points
+    /// with only excluded values is iterated, so the cost doesn't depend on the number of values.
     fn except_set<'a, A, K, S>(
         &'a self,
         excluded: &'a IndexSet<K, A>,
@@ -302,15 +370,73 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
         N: std::borrow::Borrow<S>,
         S: ?Sized + Hash + Eq,
     {
+        let other_values_count = self
+            .get_unique_values_count()
+            .saturating_sub(excluded.len());
+        if other_values_count <= EXCEPT_UNION_MAX_VALUES {
+            let points = self.union_points::<N>(
+                self.get_values_iterator()
+                    .filter(|key| !excluded.contains((*key).borrow())),
+            );
+            return Box::new(points.into_iter());
+        }
+
+        let excluded_points = self.union_points::<S>(excluded.iter().map(|key| key.borrow()));
+        Box::new((0..self.get_point_ids_bound()).filter(move |&point_id| {
+            if excluded_points.contains(point_id) {
+                self.get_values(point_id).is_some_and(|values| {
+                    values
+                        .iter()
+                        .any(|value| !excluded.contains(value.borrow()))
+                })
+            } else {
+                !self.values_is_empty(point_id)
+            }
+        }))
+    }
+
+    /// Points with any value, which match `except` of values of another type
+    fn points_with_values(&self) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
         Box::new(
-            self.get_values_iterator()
-                .filter(|key| !excluded.contains((*key).borrow()))
-                .flat_map(|key| self.get_iterator(key.borrow()))
-                .unique(),
+            (0..self.get_point_ids_bound()).filter(|&point_id| !self.values_is_empty(point_id)),
         )
     }
 }
 
+impl MapIndex<SmolStr> {
+    /// Keywords within the `range`, in lexicographic order
+    fn get_keywords_range<'a>(
+        &'a self,
+        range: &'a Range<String>,
+    ) -> Box<dyn Iterator<Item = &'a SmolStr> + 'a> {
+        let start = match (&range.gt, &range.gte) {
+            (Some(gt), Some(gte)) if gte > gt => Bound::Included(gte.as_str()),
+            (Some(gt), _) => Bound::Excluded(gt.as_str()),
+            (None, Some(gte)) => Bound::Included(gte.as_str()),
+            (None, None) => Bound::Unbounded,
+        };
+        let end = match (&range.lt, &range.lte) {
+            (Some(lt), Some(lte)) if lte < lt => Bound::Included(lte.as_str()),
+            (Some(lt), _) => Bound::Excluded(lt.as_str()),
+            (None, Some(lte)) => Bound::Included(lte.as_str()),
+            (None, None) => Bound::Unbounded,
+        };
+        self.get_values_range(start, end)
+    }
+
+    fn keyword_range_cardinality(&self, range: &Range<String>) -> CardinalityEstimation {
+        let estimations = self
+            .get_keywords_range(range)
+            .map(|keyword| self.match_cardinality(keyword.as_str()))
+            .collect::<Vec<_>>();
+        if estimations.is_empty() {
+            CardinalityEstimation::exact(0)
+        } else {
+            combine_should_estimations(&estimations, self.get_indexed_points())
+        }
+    }
+}
+
 impl PayloadFieldIndex for MapIndex<SmolStr> {
     fn count_indexed_points(&self) -> usize {
         self.get_indexed_points()
@@ -332,16 +458,19 @@ impl PayloadFieldIndex for MapIndex<SmolStr> {
         &'a self,
         condition: &'a FieldCondition,
     ) -> OperationResult<Box<dyn Iterator<Item = PointOffsetType> + 'a>> {
+        if let Some(RangeInterface::Keyword(range)) = &condition.range {
+            let points = self.union_points::<SmolStr>(self.get_keywords_range(range));
+            return Ok(Box::new(points.into_iter()));
+        }
+
         match &condition.r#match {
             Some(Match::Value(MatchValue {
                 value: ValueVariants::Keyword(keyword),
             })) => Ok(self.get_iterator(keyword.as_str())),
             Some(Match::Any(MatchAny { any: any_variant })) => match any_variant {
                 AnyVariants::Keywords(keywords) => Ok(Box::new(
-                    keywords
-                        .iter()
-                        .flat_map(|keyword| self.get_iterator(keyword.as_str()))
-                        .unique(),
+                    self.union_points::<str>(keywords.iter().map(|keyword| keyword.as_str()))
+                        .into_iter(),
                 )),
                 AnyVariants::Integers(integers) => {
                     if integers.is_empty() {
@@ -356,6 +485,9 @@ impl PayloadFieldIndex for MapIndex<SmolStr> {
             Some(Match::Except(MatchExcept {
                 except: AnyVariants::Keywords(keywords),
             })) => Ok(self.except_set::<_, _, str>(keywords)),
+            Some(Match::Except(MatchExcept {
+                except: AnyVariants::Integers(_),
+            })) => Ok(self.points_with_values()),
             _ => Err(OperationError::service_error("failed to filter")),
         }
     }
@@ -364,6 +496,12 @@ impl PayloadFieldIndex for MapIndex<SmolStr> {
         &self,
         condition: &FieldCondition,
     ) -> OperationResult<CardinalityEstimation> {
+        if let Some(RangeInterface::Keyword(range)) = &condition.range {
+            return Ok(self
+                .keyword_range_cardinality(range)
+                .with_primary_clause(PrimaryCondition::Condition(condition.clone())));
+        }
+
         match &condition.r#match {
             Some(Match::Value(MatchValue {
                 value: ValueVariants::Keyword(keyword),
@@ -401,7 +539,13 @@ impl PayloadFieldIndex for MapIndex<SmolStr> {
             },
             Some(Match::Except(MatchExcept {
                 except: AnyVariants::Keywords(keywords),
-            })) => Ok(self.except_cardinality::<str, &str>(keywords.iter().map(|k| k.as_str()))),
+            })) => Ok(self
+                .except_cardinality::<str, &str>(keywords.iter().map(|k| k.as_str()))
+                .with_primary_clause(PrimaryCondition::Condition(condition.clone()))),
+            Some(Match::Except(MatchExcept {
+                except: AnyVariants::Integers(_),
+            })) => Ok(CardinalityEstimation::exact(self.get_indexed_points())
+                .with_primary_clause(PrimaryCondition::Condition(condition.clone()))),
             _ => Err(OperationError::service_error(
                 "failed to estimate cardinality",
             )),
@@ -460,16 +604,16 @@ impl PayloadFieldIndex for MapIndex<IntPayloadType> {
                         ))
                     }
                 }
-                AnyVariants::Integers(integers) => Ok(Box::new(
-                    integers
-                        .iter()
-                        .flat_map(|integer| self.get_iterator(integer))
-                        .unique(),
-                )),
+                AnyVariants::Integers(integers) => {
+                    Ok(Box::new(self.union_points(integers.iter()).into_iter()))
+                }
             },
             Some(Match::Except(MatchExcept {
                 except: AnyVariants::Integers(integers),
             })) => Ok(self.except_set(integers)),
+            Some(Match::Except(MatchExcept {
+                except: AnyVariants::Keywords(_),
+            })) => Ok(self.points_with_values()),
             _ => Err(OperationError::service_error("failed to filter")),
         }
     }
@@ -515,10 +659,13 @@ impl PayloadFieldIndex for MapIndex<IntPayloadType> {
             },
             Some(Match::Except(MatchExcept {
                 except: AnyVariants::Integers(integers),
-            })) => {
-                Ok(self
-                    .except_cardinality::<IntPayloadType, IntPayloadType>(integers.iter().cloned()))
-            }
+            })) => Ok(self
+                .except_cardinality::<IntPayloadType, IntPayloadType>(integers.iter().cloned())
+                .with_primary_clause(PrimaryCondition::Condition(condition.clone()))),
+            Some(Match::Except(MatchExcept {
+                except: AnyVariants::Keywords(_),
+            })) => Ok(CardinalityEstimation::exact(self.get_indexed_points())
+                .with_primary_clause(PrimaryCondition::Condition(condition.clone()))),
             _ => Err(OperationError::service_error(
                 "failed to estimate cardinality",
             )),
@@ -610,7 +757,7 @@ mod tests {
 
     const FIELD_NAME: &str = "test";
 
-    fn save_map_index<N: Hash + Eq + Clone + Display + FromStr + Debug + Default>(
+    fn save_map_index<N: Hash + Eq + Ord + Clone + Display + FromStr + Debug + Default>(
         data: &[Vec<N>],
         path: &Path,
     ) {
@@ -628,7 +775,7 @@ mod tests {
         index.flusher()().unwrap();
     }
 
-    fn load_map_index<N: Hash + Eq + Clone + Display + FromStr + Debug + Default>(
+    fn load_map_index<N: Hash + Eq + Ord + Clone + Display + FromStr + Debug + Default>(
         data: &[Vec<N>],
         path: &Path,
     ) -> MapIndex<N> {
@@ -706,6 +853,209 @@ mod tests {
             .equals_min_exp_max(&CardinalityEstimation::exact(0)));
     }
 
+    #[test]
+    fn test_keyword_range() {
+        let data: Vec<Vec<SmolStr>> = vec![
+            vec!["cat.sub.001".into()],
+            vec!["cat.sub.002".into(), "dog.sub.001".into()],
+            vec!["cat.sub.010".into()],
+            vec!["cat.other.001".into()],
+            vec!["dog.sub.002".into()],
+            vec![],
+        ];
+
+        let temp_dir = Builder::new().prefix("store_dir").tempdir().unwrap();
+        save_map_index(&data, temp_dir.path());
+        let mutable_index = load_map_index(&data, temp_dir.path());
+        let mut immutable_index = MapIndex::<SmolStr>::new(
+            open_db_with_existing_cf(temp_dir.path()).unwrap(),
+            FIELD_NAME,
+            false,
+        );
+        immutable_index.load_from_db().unwrap();
+
+        let ranges = [
+            Range {
+                gte: Some("cat.sub.".to_string()),
+                lt: Some("cat.sub/".to_string()),
+                ..Default::default()
+            },
+            Range {
+                gt: Some("cat.sub.001".to_string()),
+                lte: Some("dog.sub.001".to_string()),
+                ..Default::default()
+            },
+            Range {
+                gte: Some("dog".to_string()),
+                ..Default::default()
+            },
+            Range {
+                gt: Some("z".to_string()),
+                lt: Some("a".to_string()),
+                ..Default::default()
+            },
+        ];
+
+        for range in ranges {
+            let condition =
+                FieldCondition::new_keyword_range(FIELD_NAME.parse().unwrap(), range.clone());
+            let expected: HashSet<PointOffsetType> = data
+                .iter()
+                .enumerate()
+                .filter(|(_, values)| values.iter().any(|v| range.check_keyword(v)))
+                .map(|(idx, _)| idx as PointOffsetType)
+                .collect();
+
+            for index in [&mutable_index, &immutable_index] {
+                let keywords = index.get_keywords_range(&range).collect::<Vec<_>>();
+                assert!(keywords.windows(2).all(|w| w[0] < w[1]));
+
+                let found: Vec<_> = index.filter(&condition).unwrap().collect();
+                assert_eq!(found.len(), expected.len());
+                assert_eq!(found.into_iter().collect::<HashSet<_>>(), expected);
+
+                let estimation = index.estimate_cardinality(&condition).unwrap();
+                assert!(estimation.min <= expected.len());
+                assert!(expected.len() <= estimation.max);
+            }
+        }
+    }
+
+    #[test]
+    fn test_match_any_and_except() {
+        let data: Vec<Vec<IntPayloadType>> = vec![vec![1, 2], vec![2], vec![3], vec![], vec![1, 4]];
+
+        let temp_dir = Builder::new().prefix("store_dir").tempdir().unwrap();
+        save_map_index(&data, temp_dir.path());
+        let mutable_index = load_map_index(&data, temp_dir.path());
+        let mut immutable_index = MapIndex::<IntPayloadType>::new(
+            open_db_with_existing_cf(temp_dir.path()).unwrap(),
+            FIELD_NAME,
+            false,
+        );
+        immutable_index.load_from_db().unwrap();
+
+        let any = FieldCondition::new_match(
+            FIELD_NAME.parse().unwrap(),
+            Match::Any(MatchAny {
+                any: AnyVariants::Integers([1, 2, 5].into_iter().collect()),
+            }),
+        );
+        let except = FieldCondition::new_match(
+            FIELD_NAME.parse().unwrap(),
+            Match::Except(MatchExcept {
+                except: AnyVariants::Integers([1, 2].into_iter().collect()),
+            }),
+        );
+
+        for index in [&mutable_index, &immutable_index] {
+            // Points are deduplicated and returned in ascending order
+            let found: Vec<_> = index.filter(&any).unwrap().collect();
+            assert_eq!(found, vec![0, 1, 4]);
+
+            let found: Vec<_> = index.filter(&except).unwrap().collect();
+            assert_eq!(found, vec![2, 4]);
+        }
+    }
+
+    #[test]
+    fn test_except_complement() {
+        // Enough distinct values to iterate the complement instead of the union
+        let data: Vec<Vec<IntPayloadType>> = (0..200)
+            .map(|i| match i % 4 {
+                0 => vec![],
+                1 => vec![1],
+                2 => vec![1, i],
+                _ => vec![i],
+            })
+            .collect();
+
+        let temp_dir = Builder::new().prefix("store_dir").tempdir().unwrap();
+        save_map_index(&data, temp_dir.path());
+        let mutable_index = load_map_index(&data, temp_dir.path());
+        let mut immutable_index = MapIndex::<IntPayloadType>::new(
+            open_db_with_existing_cf(temp_dir.path()).unwrap(),
+            FIELD_NAME,
+            false,
+        );
+        immutable_index.load_from_db().unwrap();
+
+        let excluded = [1, 3, 7];
+        let condition = FieldCondition::new_match(
+            FIELD_NAME.parse().unwrap(),
+            Match::Except(MatchExcept {
+                except: AnyVariants::Integers(excluded.into_iter().collect()),
+            }),
+        );
+        let expected: Vec<PointOffsetType> = data
+            .iter()
+            .enumerate()
+            .filter(|(_, values)| values.iter().any(|value| !excluded.contains(value)))
+            .map(|(idx, _)| idx as PointOffsetType)
+            .collect();
+
+        let other_type = FieldCondition::new_match(
+            FIELD_NAME.parse().unwrap(),
+            Match::Except(MatchExcept {
+                except: AnyVariants::Keywords(["a".to_string()].into_iter().collect()),
+            }),
+        );
+        let with_values: Vec<PointOffsetType> = data
+            .iter()
+            .enumerate()
+            .filter(|(_, values)| !values.is_empty())
+            .map(|(idx, _)| idx as PointOffsetType)
+            .collect();
+
+        for index in [&mutable_index, &immutable_index] {
+            let found: Vec<_> = index.filter(&condition).unwrap().collect();
+            assert_eq!(found, expected);
+
+            let estimation = index.estimate_cardinality(&condition).unwrap();
+            assert!(!estimation.primary_clauses.is_empty());
+            assert!(estimation.min <= expected.len());
+            assert!(expected.len() <= estimation.max);
+
+            let found: Vec<_> = index.filter(&other_type).unwrap().collect();
+            assert_eq!(found, with_values);
+        }
+    }
+
+    #[test]
+    fn test_immutable_index_remove_point() {
+        let data: Vec<Vec<SmolStr>> = vec![
+            vec!["a".into(), "b".into()],
+            vec!["b".into()],
+            vec!["c".into()],
+        ];
+
+        let temp_dir = Builder::new().prefix("store_dir").tempdir().unwrap();
+        save_map_index(&data, temp_dir.path());
+        let mut index = MapIndex::<SmolStr>::new(
+            open_db_with_existing_cf(temp_dir.path()).unwrap(),
+            FIELD_NAME,
+            false,
+        );
+        index.load_from_db().unwrap();
+        let MapIndex::Immutable(index) = &mut index else {
+            panic!("Wrong index type");
+        };
+
+        assert_eq!(index.get_unique_values_count(), 3);
+        assert_eq!(index.get_points_with_value_count("b"), Some(2));
+        assert_eq!(index.get_points_with_value_count("d"), None);
+
+        index.remove_point(0).unwrap();
+        index.remove_point(2).unwrap();
+        assert_eq!(index.get_unique_values_count(), 1);
+        assert_eq!(index.get_points_with_value_count("a"), None);
+        assert_eq!(index.get_iterator("b").collect::<Vec<_>>(), vec![1]);
+        assert_eq!(
+            index.get_values_iterator().collect::<Vec<_>>(),
+            vec![&SmolStr::new("b")],
+        );
+    }
+
     #[test]
     fn test_empty_index() {
         let data: Vec<Vec<String>> = vec![];
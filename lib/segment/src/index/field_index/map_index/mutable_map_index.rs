@@ -1,20 +1,23 @@
-use std::collections::{BTreeSet, HashMap};
+use std::borrow::Borrow;
+use std::collections::HashMap;
 use std::fmt::Display;
 use std::hash::Hash;
 use std::iter;
+use std::ops::{Bound, RangeBounds};
 use std::str::FromStr;
 use std::sync::Arc;
 
 use common::types::PointOffsetType;
 use parking_lot::RwLock;
+use roaring::RoaringBitmap;
 use rocksdb::DB;
 
 use super::MapIndex;
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
 
-pub struct MutableMapIndex<N: Hash + Eq + Clone + Display + FromStr> {
-    pub(super) map: HashMap<N, BTreeSet<PointOffsetType>>,
+pub struct MutableMapIndex<N: Hash + Eq + Ord + Clone + Display + FromStr> {
+    pub(super) map: HashMap<N, RoaringBitmap>,
     pub(super) point_to_values: Vec<Vec<N>>,
     /// Amount of point which have at least one indexed payload value
     pub(super) indexed_points: usize,
@@ -22,7 +25,7 @@ pub struct MutableMapIndex<N: Hash + Eq + Clone + Display + FromStr> {
     pub(super) db_wrapper: DatabaseColumnWrapper,
 }
 
-impl<N: Hash + Eq + Clone + Display + FromStr + Default> MutableMapIndex<N> {
+impl<N: Hash + Eq + Ord + Clone + Display + FromStr + Default> MutableMapIndex<N> {
     pub fn new(db: Arc<RwLock<DB>>, field_name: &str) -> Self {
         let store_cf_name = MapIndex::<N>::storage_cf_name(field_name);
         let db_wrapper = DatabaseColumnWrapper::new(db, &store_cf_name);
@@ -78,7 +81,7 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MutableMapIndex<N> {
 
         for value in &removed_values {
             if let Some(vals) = self.map.get_mut(value) {
-                vals.remove(&idx);
+                vals.remove(idx);
             }
             let key = MapIndex::encode_db_record(value, idx);
             self.db_wrapper.remove(key)?;
@@ -120,6 +123,11 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MutableMapIndex<N> {
         self.point_to_values.get(idx as usize).map(|v| v.as_slice())
     }
 
+    /// Upper bound of offsets of points with values
+    pub fn get_point_ids_bound(&self) -> PointOffsetType {
+        self.point_to_values.len() as PointOffsetType
+    }
+
     pub fn get_indexed_points(&self) -> usize {
         self.indexed_points
     }
@@ -138,7 +146,16 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MutableMapIndex<N> {
         N: std::borrow::Borrow<Q>,
         Q: Hash + Eq,
     {
-        self.map.get(value).map(|p| p.len())
+        self.map.get(value).map(|p| p.len() as usize)
+    }
+
+    pub fn get_points<Q>(&self, value: &Q) -> Option<&RoaringBitmap>
+    where
+        Q: ?Sized,
+        N: std::borrow::Borrow<Q>,
+        Q: Hash + Eq,
+    {
+        self.map.get(value)
     }
 
     pub fn get_iterator<Q>(&self, value: &Q) -> Box<dyn Iterator<Item = PointOffsetType> + '_>
@@ -149,11 +166,32 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MutableMapIndex<N> {
     {
         self.map
             .get(value)
-            .map(|ids| Box::new(ids.iter().copied()) as Box<dyn Iterator<Item = PointOffsetType>>)
+            .map(|ids| Box::new(ids.iter()) as Box<dyn Iterator<Item = PointOffsetType>>)
             .unwrap_or_else(|| Box::new(iter::empty::<PointOffsetType>()))
     }
 
     pub fn get_values_iterator(&self) -> Box<dyn Iterator<Item = &N> + '_> {
         Box::new(self.map.keys())
     }
+
+    /// Values within the bounds, in ascending order
+    ///
+    /// Values are not kept sorted on updates, so they are sorted on each call.
+    pub fn get_values_range<Q>(
+        &self,
+        start: Bound<&Q>,
+        end: Bound<&Q>,
+    ) -> Box<dyn Iterator<Item = &N> + '_>
+    where
+        Q: ?Sized + Ord,
+        N: Borrow<Q>,
+    {
+        let mut values: Vec<_> = self
+            .map
+            .keys()
+            .filter(|value| (start, end).contains(value.borrow()))
+            .collect();
+        values.sort_unstable();
+        Box::new(values.into_iter())
+    }
 }
@@ -0,0 +1,196 @@
+use std::hash::Hash;
+
+use ahash::RandomState;
+
+use crate::common::operation_error::{OperationError, OperationResult};
+
+/// Average number of keys in a bucket. Larger buckets take less memory, but longer to build.
+const AVG_BUCKET_SIZE: usize = 4;
+
+/// Number of seeds to try, before giving up on building the hash
+const MAX_SEEDS: u64 = 8;
+
+/// Minimal perfect hash function over a fixed set of keys
+///
+/// Maps each of `n` keys to a distinct slot in `0..n`, using about one `u32` per
+/// [`AVG_BUCKET_SIZE`] keys. Keys are not stored, so keys outside of the set are mapped to
+/// arbitrary slots and have to be checked by the caller.
+///
+/// Built with the hash-and-displace approach: keys are grouped into buckets, and each bucket gets
+/// a pilot value, which places all its keys into free slots.
+#[derive(Debug)]
+pub struct PerfectHash {
+    hasher: RandomState,
+    pilots: Vec<u32>,
+    slots: usize,
+}
+
+impl Default for PerfectHash {
+    fn default() -> Self {
+        Self {
+            hasher: hasher(0),
+            pilots: Vec::new(),
+            slots: 0,
+        }
+    }
+}
+
+impl PerfectHash {
+    /// Build the hash over distinct `keys`
+    pub fn build<'a, K>(keys: impl IntoIterator<Item = &'a K> + Clone) -> OperationResult<Self>
+    where
+        K: Hash + ?Sized + 'a,
+    {
+        for seed in 0..MAX_SEEDS {
+            let hasher = hasher(seed);
+            let hashes: Vec<u64> = keys
+                .clone()
+                .into_iter()
+                .map(|key| hasher.hash_one(key))
+                .collect();
+            if let Some(pilots) = find_pilots(&hashes) {
+                return Ok(Self {
+                    hasher,
+                    pilots,
+                    slots: hashes.len(),
+                });
+            }
+        }
+        Err(OperationError::service_error(
+            "Failed to build perfect hash, keys are not distinct",
+        ))
+    }
+
+    /// Number of slots, same as the number of keys
+    pub fn len(&self) -> usize {
+        self.slots
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slots == 0
+    }
+
+    /// Slot of the key. Returns `None` only if there are no slots.
+    pub fn slot<Q: Hash + ?Sized>(&self, key: &Q) -> Option<usize> {
+        if self.slots == 0 {
+            return None;
+        }
+        let hash = self.hasher.hash_one(key);
+        let pilot = self.pilots[bucket(hash, self.pilots.len())];
+        Some(position(hash, pilot, self.slots))
+    }
+}
+
+fn hasher(seed: u64) -> RandomState {
+    RandomState::with_seeds(
+        seed,
+        0x243f_6a88_85a3_08d3,
+        0x1319_8a2e_0370_7344,
+        0xa409_3822_299f_31d0,
+    )
+}
+
+/// Map `hash` uniformly into `0..n` without division
+fn fast_range(hash: u64, n: usize) -> usize {
+    ((u128::from(hash) * n as u128) >> 64) as usize
+}
+
+fn bucket(hash: u64, buckets: usize) -> usize {
+    fast_range(hash.rotate_left(32), buckets)
+}
+
+fn position(hash: u64, pilot: u32, slots: usize) -> usize {
+    // SplitMix64 finalizer, so that each pilot gives independent positions
+    let mut x = hash ^ u64::from(pilot).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^= x >> 31;
+    fast_range(x, slots)
+}
+
+/// Find a pilot for each bucket, so that all hashes get distinct positions.
+/// Returns `None` if some hashes are equal.
+fn find_pilots(hashes: &[u64]) -> Option<Vec<u32>> {
+    let slots = hashes.len();
+    if slots == 0 {
+        return Some(Vec::new());
+    }
+
+    let mut sorted_hashes = hashes.to_vec();
+    sorted_hashes.sort_unstable();
+    if sorted_hashes.windows(2).any(|pair| pair[0] == pair[1]) {
+        return None;
+    }
+
+    let buckets_count = slots.div_ceil(AVG_BUCKET_SIZE);
+    let mut buckets: Vec<Vec<u64>> = vec![Vec::new(); buckets_count];
+    for &hash in hashes {
+        buckets[bucket(hash, buckets_count)].push(hash);
+    }
+
+    // Largest buckets are the hardest to place, so they go first, while most slots are free
+    let mut order: Vec<usize> = (0..buckets_count).collect();
+    order.sort_unstable_by_key(|&bucket| std::cmp::Reverse(buckets[bucket].len()));
+
+    let mut taken = vec![false; slots];
+    let mut pilots = vec![0; buckets_count];
+    let mut positions = Vec::with_capacity(AVG_BUCKET_SIZE * 4);
+    for bucket in order {
+        let bucket_hashes = &buckets[bucket];
+        if bucket_hashes.is_empty() {
+            break;
+        }
+
+        let mut pilot = 0u32;
+        loop {
+            positions.clear();
+            let fits = bucket_hashes.iter().all(|&hash| {
+                let position = position(hash, pilot, slots);
+                let is_free = !taken[position] && !positions.contains(&position);
+                positions.push(position);
+                is_free
+            });
+            if fits {
+                break;
+            }
+            pilot = pilot.checked_add(1)?;
+        }
+
+        for &position in &positions {
+            taken[position] = true;
+        }
+        pilots[bucket] = pilot;
+    }
+
+    Some(pilots)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use smol_str::SmolStr;
+
+    use super::*;
+
+    #[test]
+    fn test_perfect_hash() {
+        for count in [0, 1, 2, 7, 1000, 10_000] {
+            let keys: Vec<SmolStr> = (0..count)
+                .map(|i| SmolStr::new(format!("key{i}")))
+                .collect();
+            let hash = PerfectHash::build(&keys).unwrap();
+            assert_eq!(hash.len(), count);
+
+            let slots: HashSet<usize> = keys
+                .iter()
+                .map(|key| hash.slot(key.as_str()).unwrap())
+                .collect();
+            assert_eq!(slots.len(), count);
+            assert!(slots.iter().all(|&slot| slot < count));
+        }
+
+        let keys = [1, 2, 2];
+        assert!(PerfectHash::build(&keys).is_err());
+    }
+}
@@ -0,0 +1,448 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use common::types::PointOffsetType;
+use parking_lot::RwLock;
+use rocksdb::DB;
+use serde_json::Value;
+
+use super::{CardinalityEstimation, PayloadFieldIndex, PrimaryCondition, ValueIndexer};
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
+use crate::common::Flusher;
+use crate::index::field_index::PayloadBlockCondition;
+use crate::telemetry::PayloadIndexTelemetry;
+use crate::types::{FieldCondition, Match, MatchSimilar, PayloadKeyType};
+
+/// Number of words in a shingle
+const SHINGLE_SIZE: usize = 3;
+
+/// Number of hash functions in a signature
+const SIGNATURE_SIZE: usize = 64;
+
+/// Signature is split into bands of rows. Values, which have all rows of any band equal, are
+/// compared with the query.
+const BAND_ROWS: usize = 4;
+const BANDS: usize = SIGNATURE_SIZE / BAND_ROWS;
+
+/// Below this similarity, similar values are likely to have no equal bands.
+/// Signatures of all values are compared instead.
+const MIN_BANDED_SIMILARITY: f32 = 0.5;
+
+/// MinHash signature of the set of shingles of a string
+pub type Signature = Vec<u32>;
+
+/// Hashes of the overlapping runs of `SHINGLE_SIZE` words of the text.
+/// Texts with fewer words are represented by a single shingle of all of them.
+pub fn text_shingles(text: &str) -> HashSet<u64> {
+    let words: Vec<_> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    words
+        .windows(SHINGLE_SIZE.min(words.len()).max(1))
+        .map(|shingle| seahash::hash(shingle.join(" ").as_bytes()))
+        .collect()
+}
+
+/// Jaccard similarity of two sets of shingles
+pub fn shingles_similarity(a: &HashSet<u64>, b: &HashSet<u64>) -> f32 {
+    let intersection = a.intersection(b).count();
+    let union = a.len() + b.len() - intersection;
+    if union == 0 {
+        return 0.0;
+    }
+    intersection as f32 / union as f32
+}
+
+/// Permute the hash of a shingle with the `seed`-th hash function
+#[inline]
+fn permute(shingle: u64, seed: u64) -> u32 {
+    // SplitMix64 finalizer
+    let mut z = shingle ^ seed.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    ((z ^ (z >> 31)) >> 32) as u32
+}
+
+/// MinHash signature of the text, `None` if the text has no words
+pub fn text_signature(text: &str) -> Option<Signature> {
+    let shingles = text_shingles(text);
+    if shingles.is_empty() {
+        return None;
+    }
+    let signature = (0..SIGNATURE_SIZE as u64)
+        .map(|seed| {
+            shingles
+                .iter()
+                .map(|shingle| permute(*shingle, seed))
+                .min()
+                .unwrap_or(u32::MAX)
+        })
+        .collect();
+    Some(signature)
+}
+
+/// Estimated Jaccard similarity of the shingles, which the signatures are built from
+pub fn signature_similarity(a: &[u32], b: &[u32]) -> f32 {
+    let equal = a.iter().zip(b).filter(|(a, b)| a == b).count();
+    equal as f32 / SIGNATURE_SIZE as f32
+}
+
+fn band_hashes(signature: &[u32]) -> impl Iterator<Item = u64> + '_ {
+    signature.chunks(BAND_ROWS).map(|band| {
+        let bytes: Vec<u8> = band.iter().flat_map(|row| row.to_le_bytes()).collect();
+        seahash::hash(&bytes)
+    })
+}
+
+/// Near-duplicate query, prepared for the index
+pub struct SimilarQuery {
+    signature: Option<Signature>,
+    min_similarity: f32,
+}
+
+impl SimilarQuery {
+    pub fn new(similar: &MatchSimilar) -> Self {
+        Self {
+            signature: text_signature(&similar.similar),
+            min_similarity: similar.min_similarity(),
+        }
+    }
+
+    fn check_signature(&self, signature: &[u32]) -> bool {
+        self.signature
+            .as_ref()
+            .is_some_and(|query| signature_similarity(query, signature) >= self.min_similarity)
+    }
+}
+
+/// Index of MinHash signatures of strings, for finding near-duplicate texts
+///
+/// Signatures are bucketed by locality-sensitive hashing of their bands, so that only values,
+/// which are likely to be similar to the query, are compared with it.
+pub struct MinHashIndex {
+    /// Signatures of the string values of each point
+    signatures: Vec<Vec<Signature>>,
+    /// Points, which have a value with the given hash of the band, per band
+    buckets: Vec<HashMap<u64, Vec<PointOffsetType>>>,
+    points_count: usize,
+    values_count: usize,
+    db_wrapper: DatabaseColumnWrapper,
+}
+
+impl MinHashIndex {
+    fn storage_cf_name(field: &str) -> String {
+        format!("{field}_min_hash")
+    }
+
+    pub fn new(db: Arc<RwLock<DB>>, field: &str) -> Self {
+        let store_cf_name = Self::storage_cf_name(field);
+        Self {
+            signatures: Vec::new(),
+            buckets: (0..BANDS).map(|_| HashMap::new()).collect(),
+            points_count: 0,
+            values_count: 0,
+            db_wrapper: DatabaseColumnWrapper::new(db, &store_cf_name),
+        }
+    }
+
+    pub fn recreate(&self) -> OperationResult<()> {
+        self.db_wrapper.recreate_column_family()
+    }
+
+    pub fn get_telemetry_data(&self) -> PayloadIndexTelemetry {
+        PayloadIndexTelemetry {
+            field_name: None,
+            points_values_count: self.values_count,
+            points_count: self.points_count,
+            histogram_bucket_size: None,
+        }
+    }
+
+    pub fn values_count(&self, point_id: PointOffsetType) -> usize {
+        self.signatures
+            .get(point_id as usize)
+            .map_or(0, |signatures| signatures.len())
+    }
+
+    pub fn values_is_empty(&self, point_id: PointOffsetType) -> bool {
+        self.values_count(point_id) == 0
+    }
+
+    /// Whether any of the values of the point is similar to the query
+    pub fn check_similar(&self, point_id: PointOffsetType, query: &SimilarQuery) -> bool {
+        self.signatures
+            .get(point_id as usize)
+            .is_some_and(|signatures| {
+                signatures
+                    .iter()
+                    .any(|signature| query.check_signature(signature))
+            })
+    }
+
+    /// Whether any of the string values of the payload is similar to the query
+    pub fn check_payload(&self, query: &SimilarQuery, payload_value: &Value) -> bool {
+        self.get_values(payload_value).iter().any(|value| {
+            text_signature(value).is_some_and(|signature| query.check_signature(&signature))
+        })
+    }
+
+    fn insert(&mut self, idx: PointOffsetType, signatures: Vec<Signature>) {
+        if signatures.is_empty() {
+            return;
+        }
+        for signature in &signatures {
+            for (band, hash) in band_hashes(signature).enumerate() {
+                let bucket = self.buckets[band].entry(hash).or_default();
+                // Values of a point are inserted one after another
+                if bucket.last() != Some(&idx) {
+                    bucket.push(idx);
+                }
+            }
+        }
+
+        if self.signatures.len() <= idx as usize {
+            self.signatures.resize_with(idx as usize + 1, Vec::new);
+        }
+        self.points_count += 1;
+        self.values_count += signatures.len();
+        self.signatures[idx as usize] = signatures;
+    }
+
+    /// Points, the values of which are similar to the query
+    fn matching_points(&self, query: &SimilarQuery) -> Vec<PointOffsetType> {
+        let Some(signature) = &query.signature else {
+            return Vec::new();
+        };
+
+        let candidates: Vec<PointOffsetType> = if query.min_similarity < MIN_BANDED_SIMILARITY {
+            (0..self.signatures.len() as PointOffsetType).collect()
+        } else {
+            let mut candidates: Vec<_> = band_hashes(signature)
+                .enumerate()
+                .filter_map(|(band, hash)| self.buckets[band].get(&hash))
+                .flatten()
+                .copied()
+                .collect();
+            candidates.sort_unstable();
+            candidates.dedup();
+            candidates
+        };
+
+        candidates
+            .into_iter()
+            .filter(|point_id| self.check_similar(*point_id, query))
+            .collect()
+    }
+
+    fn similar_condition(condition: &FieldCondition) -> Option<&MatchSimilar> {
+        match &condition.r#match {
+            Some(Match::Similar(similar)) => Some(similar),
+            _ => None,
+        }
+    }
+}
+
+impl ValueIndexer<String> for MinHashIndex {
+    fn add_many(&mut self, idx: PointOffsetType, values: Vec<String>) -> OperationResult<()> {
+        let signatures: Vec<_> = values
+            .iter()
+            .filter_map(|value| text_signature(value))
+            .collect();
+        if signatures.is_empty() {
+            return Ok(());
+        }
+
+        let db_document = bincode::serialize(&signatures).map_err(|e| {
+            OperationError::service_error(format!("Failed to serialize signatures: {e}"))
+        })?;
+        self.db_wrapper.put(idx.to_be_bytes(), db_document)?;
+        self.insert(idx, signatures);
+        Ok(())
+    }
+
+    fn get_value(&self, value: &Value) -> Option<String> {
+        if let Value::String(text) = value {
+            return Some(text.to_owned());
+        }
+        None
+    }
+
+    fn remove_point(&mut self, id: PointOffsetType) -> OperationResult<()> {
+        let Some(signatures) = self.signatures.get_mut(id as usize) else {
+            return Ok(());
+        };
+        if signatures.is_empty() {
+            return Ok(());
+        }
+        let signatures = std::mem::take(signatures);
+
+        for signature in &signatures {
+            for (band, hash) in band_hashes(signature).enumerate() {
+                if let Some(bucket) = self.buckets[band].get_mut(&hash) {
+                    bucket.retain(|point_id| *point_id != id);
+                    if bucket.is_empty() {
+                        self.buckets[band].remove(&hash);
+                    }
+                }
+            }
+        }
+        self.points_count -= 1;
+        self.values_count -= signatures.len();
+
+        self.db_wrapper.remove(id.to_be_bytes())?;
+        Ok(())
+    }
+}
+
+impl PayloadFieldIndex for MinHashIndex {
+    fn count_indexed_points(&self) -> usize {
+        self.points_count
+    }
+
+    fn load(&mut self) -> OperationResult<bool> {
+        if !self.db_wrapper.has_column_family()? {
+            return Ok(false);
+        }
+
+        let mut loaded = Vec::new();
+        {
+            let db = self.db_wrapper.lock_db();
+            for (key, value) in db.iter()? {
+                let idx =
+                    PointOffsetType::from_be_bytes(key.as_ref().try_into().map_err(|_| {
+                        OperationError::service_error("Malformed min hash index key")
+                    })?);
+                let signatures: Vec<Signature> = bincode::deserialize(&value).map_err(|e| {
+                    OperationError::service_error(format!("Failed to deserialize signatures: {e}"))
+                })?;
+                loaded.push((idx, signatures));
+            }
+        }
+        for (idx, signatures) in loaded {
+            self.insert(idx, signatures);
+        }
+        Ok(true)
+    }
+
+    fn clear(self) -> OperationResult<()> {
+        self.db_wrapper.remove_column_family()
+    }
+
+    fn flusher(&self) -> Flusher {
+        self.db_wrapper.flusher()
+    }
+
+    fn filter<'a>(
+        &'a self,
+        condition: &'a FieldCondition,
+    ) -> OperationResult<Box<dyn Iterator<Item = PointOffsetType> + 'a>> {
+        match Self::similar_condition(condition) {
+            Some(similar) => Ok(Box::new(
+                self.matching_points(&SimilarQuery::new(similar))
+                    .into_iter(),
+            )),
+            None => Err(OperationError::service_error("failed to filter")),
+        }
+    }
+
+    fn estimate_cardinality(
+        &self,
+        condition: &FieldCondition,
+    ) -> OperationResult<CardinalityEstimation> {
+        match Self::similar_condition(condition) {
+            Some(similar) => {
+                // Matching points are only a fraction of the compared ones, so they are counted
+                let count = self.matching_points(&SimilarQuery::new(similar)).len();
+                Ok(CardinalityEstimation::exact(count)
+                    .with_primary_clause(PrimaryCondition::Condition(condition.clone())))
+            }
+            None => Err(OperationError::service_error(
+                "failed to estimate cardinality",
+            )),
+        }
+    }
+
+    fn payload_blocks(
+        &self,
+        _threshold: usize,
+        _key: PayloadKeyType,
+    ) -> Box<dyn Iterator<Item = PayloadBlockCondition> + '_> {
+        // Near-duplicate texts don't form blocks of a known condition
+        Box::new(std::iter::empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::common::rocksdb_wrapper::open_db_with_existing_cf;
+    use crate::json_path::path;
+
+    fn similar_condition(text: &str, min_similarity: f32) -> FieldCondition {
+        FieldCondition::new_match(
+            path("body"),
+            Match::Similar(MatchSimilar {
+                similar: text.to_string(),
+                min_similarity: Some(min_similarity),
+            }),
+        )
+    }
+
+    #[test]
+    fn test_signature_similarity_estimates_jaccard() {
+        let a = "the quick brown fox jumps over the lazy dog near the river bank today";
+        let b = "the quick brown fox jumps over the lazy dog near the river bank tonight";
+        let exact = shingles_similarity(&text_shingles(a), &text_shingles(b));
+        let estimated =
+            signature_similarity(&text_signature(a).unwrap(), &text_signature(b).unwrap());
+        assert!((exact - estimated).abs() < 0.2, "{exact} vs {estimated}");
+        assert_eq!(text_signature(a), text_signature(&a.to_uppercase()));
+        assert!(text_signature(" ,. ").is_none());
+    }
+
+    #[test]
+    fn test_min_hash_index() {
+        let texts = [
+            "Cleanup jobs remove near duplicate documents before the vectors are indexed",
+            "cleanup jobs remove near-duplicate documents, before the vectors are indexed!",
+            "A completely different sentence about the weather in the mountains",
+        ];
+
+        let temp_dir = Builder::new().prefix("test_dir").tempdir().unwrap();
+        {
+            let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+            let mut index = MinHashIndex::new(db, "body");
+            index.recreate().unwrap();
+            for (idx, text) in texts.iter().enumerate() {
+                index
+                    .add_point(idx as PointOffsetType, &[&Value::from(*text)])
+                    .unwrap();
+            }
+            assert_eq!(index.count_indexed_points(), 3);
+
+            let condition = similar_condition(texts[0], 0.8);
+            let found: Vec<_> = index.filter(&condition).unwrap().collect();
+            assert_eq!(found, vec![0, 1]);
+
+            index.remove_point(1).unwrap();
+            let found: Vec<_> = index.filter(&condition).unwrap().collect();
+            assert_eq!(found, vec![0]);
+            index.flusher()().unwrap();
+        }
+
+        let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+        let mut index = MinHashIndex::new(db, "body");
+        assert!(index.load().unwrap());
+        assert_eq!(index.count_indexed_points(), 2);
+
+        // Low thresholds compare all signatures
+        let condition = similar_condition(texts[2], 0.1);
+        let found: Vec<_> = index.filter(&condition).unwrap().collect();
+        assert_eq!(found, vec![2]);
+    }
+}
@@ -12,6 +12,7 @@ mod histogram;
 mod immutable_point_to_values;
 pub mod index_selector;
 pub mod map_index;
+pub mod min_hash_index;
 pub mod numeric_index;
 mod stat_tools;
 
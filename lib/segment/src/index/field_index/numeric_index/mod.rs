@@ -23,19 +23,20 @@ use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
 use crate::common::utils::bound_map;
 use crate::common::Flusher;
+use crate::data_types::aggregation::{ValueDistribution, ValueRange};
 use crate::index::field_index::histogram::{Histogram, Numericable};
 use crate::index::field_index::stat_tools::estimate_multi_value_selection_cardinality;
 use crate::index::field_index::{
     CardinalityEstimation, PayloadBlockCondition, PayloadFieldIndex, PrimaryCondition, ValueIndexer,
 };
 use crate::index::key_encoding::{
-    decode_f64_key_ascending, decode_i64_key_ascending, encode_f64_key_ascending,
-    encode_i64_key_ascending,
+    decode_f64_key_ascending, decode_i128_key_ascending, decode_i64_key_ascending,
+    encode_f64_key_ascending, encode_i128_key_ascending, encode_i64_key_ascending,
 };
 use crate::telemetry::PayloadIndexTelemetry;
 use crate::types::{
-    DateTimePayloadType, FieldCondition, FloatPayloadType, IntPayloadType, PayloadKeyType, Range,
-    RangeInterface,
+    BigIntPayloadType, DateTimePayloadType, FieldCondition, FloatPayloadType, IntPayloadType,
+    PayloadKeyType, Range, RangeInterface,
 };
 
 const HISTOGRAM_MAX_BUCKET_SIZE: usize = 10_000;
@@ -70,6 +71,20 @@ impl Encodable for IntPayloadType {
     }
 }
 
+impl Encodable for BigIntPayloadType {
+    fn encode_key(&self, id: PointOffsetType) -> Vec<u8> {
+        encode_i128_key_ascending(*self, id)
+    }
+
+    fn decode_key(key: &[u8]) -> (PointOffsetType, Self) {
+        decode_i128_key_ascending(key)
+    }
+
+    fn cmp_encoded(&self, other: &Self) -> std::cmp::Ordering {
+        self.cmp(other)
+    }
+}
+
 impl Encodable for FloatPayloadType {
     fn encode_key(&self, id: PointOffsetType) -> Vec<u8> {
         encode_f64_key_ascending(*self, id)
@@ -214,6 +229,21 @@ impl<T: Encodable + Numericable + Default> NumericIndex<T> {
         }
     }
 
+    /// Approximate distribution of the indexed values, read from the histogram
+    pub fn value_distribution(&self) -> ValueDistribution {
+        let ranges = self
+            .get_histogram()
+            .value_ranges()
+            .into_iter()
+            .map(|(from, to, count)| ValueRange {
+                from: from.to_f64(),
+                to: to.to_f64(),
+                count,
+            })
+            .collect();
+        ValueDistribution::new(ranges)
+    }
+
     /// Maximum number of values per point
     ///
     /// # Warning
@@ -237,6 +267,9 @@ impl<T: Encodable + Numericable + Default> NumericIndex<T> {
             RangeInterface::DateTime(datetime_range) => {
                 datetime_range.map(|dt| T::from_i64(dt.timestamp()))
             }
+            RangeInterface::BigInt(big_int_range) => big_int_range.map(T::from_i128),
+            // Numbers never match keyword range
+            RangeInterface::Keyword(_) => return CardinalityEstimation::exact(0),
         };
 
         let lbound = if let Some(lte) = range.lte {
@@ -345,6 +378,10 @@ impl<T: Encodable + Numericable + Default> PayloadFieldIndex for NumericIndex<T>
             RangeInterface::DateTime(datetime_range) => {
                 datetime_range.map(|dt| T::from_i64(dt.timestamp()))
             }
+            RangeInterface::BigInt(big_int_range) => big_int_range.map(T::from_i128),
+            RangeInterface::Keyword(_) => {
+                return Err(OperationError::service_error("failed to filter"));
+            }
         }
         .as_index_key_bounds();
 
@@ -371,6 +408,8 @@ impl<T: Encodable + Numericable + Default> PayloadFieldIndex for NumericIndex<T>
         condition
             .range
             .as_ref()
+            // Keyword range is handled by keyword index
+            .filter(|range| !matches!(range, RangeInterface::Keyword(_)))
             .map(|range| {
                 let mut cardinality = self.range_cardinality(range);
                 cardinality
@@ -500,6 +539,32 @@ impl ValueIndexer<DateTimePayloadType> for NumericIndex<IntPayloadType> {
     }
 }
 
+impl ValueIndexer<BigIntPayloadType> for NumericIndex<BigIntPayloadType> {
+    fn add_many(
+        &mut self,
+        id: PointOffsetType,
+        values: Vec<BigIntPayloadType>,
+    ) -> OperationResult<()> {
+        match self {
+            NumericIndex::Mutable(index) => index.add_many_to_list(id, values),
+            NumericIndex::Immutable(_) => Err(OperationError::service_error(
+                "Can't add values to immutable numeric index",
+            )),
+        }
+    }
+
+    fn get_value(&self, value: &Value) -> Option<BigIntPayloadType> {
+        value
+            .as_i64()
+            .map(BigIntPayloadType::from)
+            .or_else(|| value.as_u64().map(BigIntPayloadType::from))
+    }
+
+    fn remove_point(&mut self, id: PointOffsetType) -> OperationResult<()> {
+        NumericIndex::remove_point(self, id)
+    }
+}
+
 impl ValueIndexer<FloatPayloadType> for NumericIndex<FloatPayloadType> {
     fn add_many(
         &mut self,
@@ -536,6 +601,8 @@ where
             RangeInterface::DateTime(datetime_range) => {
                 datetime_range.map(|dt| T::from_i64(dt.timestamp()))
             }
+            RangeInterface::BigInt(big_int_range) => big_int_range.map(T::from_i128),
+            RangeInterface::Keyword(_) => return Box::new(vec![].into_iter()),
         };
         let (start_bound, end_bound) = range.as_index_key_bounds();
 
@@ -7,6 +7,7 @@ use tempfile::{Builder, TempDir};
 use super::*;
 use crate::common::rocksdb_wrapper::open_db_with_existing_cf;
 use crate::json_path::path;
+use crate::payload_storage::condition_checker::ValueChecker;
 
 const COLUMN_NAME: &str = "test";
 
@@ -404,3 +405,63 @@ fn test_empty_cardinality(#[case] immutable: bool) {
         },
     );
 }
+
+#[rstest]
+#[case(true)]
+#[case(false)]
+fn test_big_int_range(#[case] immutable: bool) {
+    let temp_dir = Builder::new()
+        .prefix("test_numeric_index")
+        .tempdir()
+        .unwrap();
+    let db = open_db_with_existing_cf(temp_dir.path()).unwrap();
+    let mut index: NumericIndex<BigIntPayloadType> = NumericIndex::new(db, COLUMN_NAME, true);
+    index.recreate().unwrap();
+
+    // Snowflake-like ids, which differ only in the lowest bits
+    let base = u64::MAX - 1000;
+    let payloads = [
+        serde_json::json!(base),
+        serde_json::json!(base + 1),
+        serde_json::json!(base + 2),
+        serde_json::json!(i64::MAX),
+        serde_json::json!(-1),
+    ];
+    for (idx, payload) in payloads.iter().enumerate() {
+        ValueIndexer::<BigIntPayloadType>::add_point(
+            &mut index,
+            idx as PointOffsetType,
+            &[payload],
+        )
+        .unwrap();
+    }
+    index.flusher()().unwrap();
+
+    if immutable {
+        let db_ref = index.get_db_wrapper().database.clone();
+        index = NumericIndex::new(db_ref, COLUMN_NAME, false);
+        index.load().unwrap();
+    }
+
+    let range: RangeInterface =
+        serde_json::from_value(serde_json::json!({ "gt": base, "lte": base + 2 })).unwrap();
+    assert!(matches!(range, RangeInterface::BigInt(_)));
+
+    let condition = FieldCondition {
+        range: Some(range),
+        ..FieldCondition::new_range(path("unused"), Range::default())
+    };
+    let result = index.filter(&condition).unwrap().sorted().collect_vec();
+    assert_eq!(result, vec![1, 2]);
+
+    let estimation = index.estimate_cardinality(&condition).unwrap();
+    assert!(estimation.min <= result.len());
+    assert!(estimation.max >= result.len());
+
+    for (idx, payload) in payloads.iter().enumerate() {
+        assert_eq!(
+            condition.check(payload),
+            result.contains(&(idx as PointOffsetType)),
+        );
+    }
+}
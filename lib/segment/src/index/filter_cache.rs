@@ -0,0 +1,238 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use bitvec::vec::BitVec;
+use common::types::PointOffsetType;
+use memory::budget::MemoryReservation;
+use parking_lot::Mutex;
+use serde_json::Value;
+
+use crate::payload_storage::FilterContext;
+use crate::types::Filter;
+
+/// Max number of filters, cached in a single segment
+pub const FILTER_CACHE_CAPACITY: usize = 16;
+
+/// Filter in canonical form, which doesn't depend on the order of conditions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CanonicalFilter {
+    hash: u64,
+    repr: String,
+}
+
+impl CanonicalFilter {
+    pub fn new(filter: &Filter) -> Option<Self> {
+        let mut value = serde_json::to_value(filter).ok()?;
+        canonicalize(&mut value);
+        let repr = value.to_string();
+        Some(Self {
+            hash: seahash::hash(repr.as_bytes()),
+            repr,
+        })
+    }
+}
+
+/// Sort clauses of boolean conditions, so equivalent filters have the same representation
+fn canonicalize(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            for (key, value) in object.iter_mut() {
+                canonicalize(value);
+                if matches!(key.as_str(), "must" | "should" | "must_not" | "conditions") {
+                    if let Value::Array(clauses) = value {
+                        clauses.sort_by_cached_key(|clause| clause.to_string());
+                    }
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(canonicalize),
+        _ => {}
+    }
+}
+
+/// Result of a filter cache lookup
+pub enum CacheLookup {
+    /// Points, matching the filter
+    Hit(Arc<BitVec>),
+    /// Filter is requested repeatedly, matching points should be resolved and cached
+    Admit,
+    /// Filter is requested for the first time, not worth caching yet
+    Miss,
+}
+
+struct CacheEntry {
+    repr: String,
+    points: Arc<BitVec>,
+    last_used: u64,
+    _memory_reservation: MemoryReservation,
+}
+
+#[derive(Default)]
+struct FilterCacheInner {
+    tick: u64,
+    entries: HashMap<u64, CacheEntry>,
+    /// Filters, requested only once, with the time of the request
+    seen: HashMap<u64, u64>,
+}
+
+/// LRU cache of points, matching filters in an immutable segment.
+///
+/// Filters are only admitted to the cache on the second request, so one-off filters don't
+/// pay for resolving all matching points.
+pub struct FilterCache {
+    capacity: usize,
+    inner: Mutex<FilterCacheInner>,
+}
+
+impl FilterCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            inner: Default::default(),
+        }
+    }
+
+    pub fn lookup(&self, filter: &CanonicalFilter) -> CacheLookup {
+        let mut inner = self.inner.lock();
+        inner.tick += 1;
+        let tick = inner.tick;
+
+        if let Some(entry) = inner.entries.get_mut(&filter.hash) {
+            if entry.repr == filter.repr {
+                entry.last_used = tick;
+                return CacheLookup::Hit(entry.points.clone());
+            }
+            // Hash collision, never cache the second filter
+            return CacheLookup::Miss;
+        }
+
+        if inner.seen.remove(&filter.hash).is_some() {
+            return CacheLookup::Admit;
+        }
+
+        if inner.seen.len() >= self.capacity {
+            Self::evict_oldest(&mut inner.seen, |last_used| *last_used);
+        }
+        inner.seen.insert(filter.hash, tick);
+        CacheLookup::Miss
+    }
+
+    pub fn insert(
+        &self,
+        filter: CanonicalFilter,
+        points: Arc<BitVec>,
+        memory_reservation: MemoryReservation,
+    ) {
+        let mut inner = self.inner.lock();
+        if inner.entries.len() >= self.capacity && !inner.entries.contains_key(&filter.hash) {
+            Self::evict_oldest(&mut inner.entries, |entry| entry.last_used);
+        }
+        let last_used = inner.tick;
+        inner.entries.insert(
+            filter.hash,
+            CacheEntry {
+                repr: filter.repr,
+                points,
+                last_used,
+                _memory_reservation: memory_reservation,
+            },
+        );
+    }
+
+    /// Drop all cached filters, must be called on any change of payload
+    pub fn clear(&self) {
+        let mut inner = self.inner.lock();
+        inner.entries.clear();
+        inner.seen.clear();
+    }
+
+    fn evict_oldest<T>(map: &mut HashMap<u64, T>, last_used: impl Fn(&T) -> u64) {
+        let oldest = map
+            .iter()
+            .min_by_key(|(_, value)| last_used(value))
+            .map(|(key, _)| *key);
+        if let Some(oldest) = oldest {
+            map.remove(&oldest);
+        }
+    }
+}
+
+/// Filter context, checking points against a cached set of matching points
+pub struct CachedFilterContext {
+    points: Arc<BitVec>,
+}
+
+impl CachedFilterContext {
+    pub fn new(points: Arc<BitVec>) -> Self {
+        Self { points }
+    }
+}
+
+impl FilterContext for CachedFilterContext {
+    fn check(&self, point_id: PointOffsetType) -> bool {
+        self.points
+            .get(point_id as usize)
+            .map(|bit| *bit)
+            .unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use memory::budget::{self, MemoryCategory};
+
+    use super::*;
+    use crate::types::{Condition, FieldCondition, Match, ValueVariants};
+
+    fn condition(key: &str, value: &str) -> Condition {
+        Condition::Field(FieldCondition::new_match(
+            key.parse().unwrap(),
+            Match::new_value(ValueVariants::Keyword(value.to_string())),
+        ))
+    }
+
+    #[test]
+    fn test_canonical_filter_ignores_order() {
+        let a = Filter::new_must(condition("city", "Berlin"));
+        let mut b = a.clone();
+        b.must.as_mut().unwrap().push(condition("color", "red"));
+        let mut c = Filter::new_must(condition("color", "red"));
+        c.must.as_mut().unwrap().push(condition("city", "Berlin"));
+
+        let a = CanonicalFilter::new(&a).unwrap();
+        let b = CanonicalFilter::new(&b).unwrap();
+        let c = CanonicalFilter::new(&c).unwrap();
+        assert_ne!(a, b);
+        assert_eq!(b, c);
+    }
+
+    #[test]
+    fn test_admission_and_eviction() {
+        let cache = FilterCache::new(2);
+        let filters: Vec<_> = ["a", "b", "c"]
+            .into_iter()
+            .map(|value| CanonicalFilter::new(&Filter::new_must(condition("key", value))).unwrap())
+            .collect();
+        let points = Arc::new(BitVec::repeat(true, 4));
+        let reservation = || budget::global().reserve(MemoryCategory::Cache, 0);
+
+        // Filters are only cached when requested twice
+        assert!(matches!(cache.lookup(&filters[0]), CacheLookup::Miss));
+        assert!(matches!(cache.lookup(&filters[0]), CacheLookup::Admit));
+        cache.insert(filters[0].clone(), points.clone(), reservation());
+        assert!(matches!(cache.lookup(&filters[0]), CacheLookup::Hit(_)));
+
+        for filter in &filters[1..] {
+            assert!(matches!(cache.lookup(filter), CacheLookup::Miss));
+            assert!(matches!(cache.lookup(filter), CacheLookup::Admit));
+            cache.insert(filter.clone(), points.clone(), reservation());
+        }
+
+        // Least recently used filter is evicted
+        assert!(matches!(cache.lookup(&filters[0]), CacheLookup::Miss));
+        assert!(matches!(cache.lookup(&filters[2]), CacheLookup::Hit(_)));
+
+        cache.clear();
+        assert!(matches!(cache.lookup(&filters[2]), CacheLookup::Miss));
+    }
+}
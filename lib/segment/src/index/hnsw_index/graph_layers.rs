@@ -9,7 +9,7 @@ use memory::mmap_ops;
 use serde::{Deserialize, Serialize};
 
 use super::entry_points::EntryPoint;
-use super::graph_links::{GraphLinks, GraphLinksMmap};
+use super::graph_links::{GraphLinks, GraphLinksMmap, GraphLinksRam};
 use crate::common::operation_error::OperationResult;
 use crate::common::utils::rev_range;
 use crate::index::hnsw_index::entry_points::EntryPoints;
@@ -35,6 +35,27 @@ pub struct GraphLayersBackwardCompatibility {
     pub(super) entry_points: EntryPoints,
 }
 
+impl GraphLayersBackwardCompatibility {
+    /// Convert the graph from the legacy format, which kept links in the graph file, into a
+    /// separate links file
+    pub fn convert(graph_path: &Path, links_path: &Path) -> OperationResult<()> {
+        let legacy: Self = read_bin(graph_path)?;
+
+        let mut converter = GraphLinksConverter::new(legacy.links_layers);
+        converter.save_as(links_path)?;
+
+        let graph = GraphLayers {
+            m: legacy.m,
+            m0: legacy.m0,
+            ef_construct: legacy.ef_construct,
+            links: GraphLinksRam::from_converter(converter)?,
+            entry_points: legacy.entry_points,
+            visited_pool: VisitedPool::new(),
+        };
+        graph.save(graph_path)
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug)]
 pub struct GraphLayers<TGraphLinks: GraphLinks> {
     pub(super) m: usize,
@@ -60,6 +81,8 @@ pub trait GraphLayersBase {
     fn get_m(&self, level: usize) -> usize;
 
     /// Greedy search for closest points within a single graph layer
+    ///
+    /// If the search is stopped, returns early with the points found so far.
     fn _search_on_level(
         &self,
         searcher: &mut SearchContext,
@@ -71,7 +94,7 @@ pub trait GraphLayersBase {
         let mut points_ids: Vec<PointOffsetType> = Vec::with_capacity(2 * limit);
 
         while let Some(candidate) = searcher.candidates.pop() {
-            if candidate.score < searcher.lower_bound() {
+            if candidate.score < searcher.lower_bound() || points_scorer.is_stopped() {
                 break;
             }
 
@@ -124,7 +147,7 @@ pub trait GraphLayersBase {
             let limit = self.get_m(level);
 
             let mut changed = true;
-            while changed {
+            while changed && !points_scorer.is_stopped() {
                 changed = false;
 
                 links.clear();
@@ -239,45 +262,19 @@ impl<TGraphLinks> GraphLayers<TGraphLinks>
 where
     TGraphLinks: GraphLinks,
 {
+    /// Load the graph and its links.
+    ///
+    /// Graphs in the legacy format, without a links file, are converted by segment migrations.
     pub fn load(graph_path: &Path, links_path: &Path) -> OperationResult<Self> {
-        let try_self: Result<Self, FileStorageError> = if links_path.exists() {
-            read_bin(graph_path)
-        } else {
-            Err(FileStorageError::generic(format!(
+        if !links_path.exists() {
+            return Err(FileStorageError::generic(format!(
                 "Links file does not exists: {links_path:?}"
-            )))
-        };
-
-        match try_self {
-            Ok(mut slf) => {
-                let links = TGraphLinks::load_from_file(links_path)?;
-                slf.links = links;
-                Ok(slf)
-            }
-            Err(err) => {
-                let try_legacy: Result<GraphLayersBackwardCompatibility, _> = read_bin(graph_path);
-                if let Ok(legacy) = try_legacy {
-                    log::debug!("Converting legacy graph to new format");
-
-                    let mut converter = GraphLinksConverter::new(legacy.links_layers);
-                    converter.save_as(links_path)?;
-
-                    let links = TGraphLinks::from_converter(converter)?;
-                    let slf = Self {
-                        m: legacy.m,
-                        m0: legacy.m0,
-                        ef_construct: legacy.ef_construct,
-                        links,
-                        entry_points: legacy.entry_points,
-                        visited_pool: VisitedPool::new(),
-                    };
-                    slf.save(graph_path)?;
-                    Ok(slf)
-                } else {
-                    Err(err)?
-                }
-            }
+            ))
+            .into());
         }
+        let mut slf: Self = read_bin(graph_path)?;
+        slf.links = TGraphLinks::load_from_file(links_path)?;
+        Ok(slf)
     }
 
     pub fn save(&self, path: &Path) -> OperationResult<()> {
@@ -295,6 +292,7 @@ impl GraphLayers<GraphLinksMmap> {
 mod tests {
     use std::fs::File;
     use std::io::Write;
+    use std::sync::atomic::{AtomicBool, Ordering};
 
     use itertools::Itertools;
     use rand::rngs::StdRng;
@@ -306,7 +304,6 @@ mod tests {
     use crate::fixtures::index_fixtures::{
         random_vector, FakeFilterContext, TestRawScorerProducer,
     };
-    use crate::index::hnsw_index::graph_links::GraphLinksRam;
     use crate::index::hnsw_index::tests::create_graph_layer_fixture;
     use crate::spaces::metric::Metric;
     use crate::spaces::simple::{CosineMetric, DotProductMetric};
@@ -382,6 +379,41 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_search_stopped() {
+        let num_vectors = 100;
+        let dim = 8;
+        let top = 5;
+
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let (vector_holder, graph_layers) = create_graph_layer_fixture::<CosineMetric, _>(
+            num_vectors,
+            M,
+            dim,
+            false,
+            &mut rng,
+            None,
+        );
+
+        let query = random_vector(&mut rng, dim);
+        let is_stopped = AtomicBool::new(false);
+
+        let raw_scorer = vector_holder
+            .get_stoppable_raw_scorer(query.clone(), &is_stopped)
+            .unwrap();
+        let scorer = FilteredScorer::new(raw_scorer.as_ref(), None);
+        assert_eq!(graph_layers.search(top, 16, scorer, None).len(), top);
+
+        // Stopped search doesn't traverse the graph, only the entry point is returned
+        is_stopped.store(true, Ordering::Relaxed);
+        let raw_scorer = vector_holder
+            .get_stoppable_raw_scorer(query, &is_stopped)
+            .unwrap();
+        let scorer = FilteredScorer::new(raw_scorer.as_ref(), None);
+        assert_eq!(graph_layers.search(top, 16, scorer, None).len(), 1);
+    }
+
     #[test]
     fn test_save_and_load() {
         let num_vectors = 100;
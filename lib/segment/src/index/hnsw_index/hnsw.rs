@@ -1,16 +1,19 @@
 use std::fs::create_dir_all;
 use std::path::{Path, PathBuf};
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::thread;
+use std::time::Instant;
 
 use atomic_refcell::AtomicRefCell;
 use bitvec::prelude::BitSlice;
-#[cfg(target_os = "linux")]
-use common::cpu::linux_low_thread_priority;
 use common::cpu::CpuPermit;
+use common::fixed_length_priority_queue::FixedLengthPriorityQueue;
+#[cfg(target_os = "linux")]
+use common::scheduling::Workload;
 use common::types::{PointOffsetType, ScoredPointOffset, TelemetryDetail};
 use log::debug;
+use memory::budget::MemoryReservation;
 use memory::mmap_ops;
 use parking_lot::Mutex;
 use rand::thread_rng;
@@ -18,12 +21,14 @@ use rayon::prelude::*;
 use rayon::ThreadPool;
 
 use super::graph_links::{GraphLinks, GraphLinksMmap};
+use super::stale_points::StalePoints;
 use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
 use crate::common::operation_time_statistics::{
     OperationDurationsAggregator, ScopeDurationMeasurer,
 };
-use crate::common::BYTES_IN_KB;
+use crate::common::{Flusher, BYTES_IN_KB};
 use crate::data_types::query_context::VectorQueryContext;
+use crate::data_types::search_plan::{SearchStrategy, VectorSearchPlan};
 use crate::data_types::vectors::{QueryVector, Vector, VectorRef};
 use crate::id_tracker::IdTrackerSS;
 use crate::index::hnsw_index::build_condition_checker::BuildConditionChecker;
@@ -42,6 +47,7 @@ use crate::types::{
     default_quantization_ignore_value, default_quantization_oversampling_value, FieldCondition,
     Filter, HnswConfig, QuantizationSearchParams, SearchParams,
 };
+use crate::vector_storage::multi_dense::aggregated_vectors::AggregatedVectors;
 use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
 use crate::vector_storage::query::DiscoveryQuery;
 use crate::vector_storage::{
@@ -50,6 +56,13 @@ use crate::vector_storage::{
 
 const HNSW_USE_HEURISTIC: bool = true;
 
+/// Max number of stale points relative to the number of indexed points
+///
+/// Stale points are scored exhaustively on every graph search. Once there are too many of
+/// them, updated points are moved out of the segment instead, so deleted points are eventually
+/// cleaned up together with stale ones by the vacuum optimizer.
+const MAX_STALE_POINTS_RATIO: f64 = 0.05;
+
 /// Build first N points in HNSW graph using only a single thread, to avoid
 /// disconnected components in the graph.
 #[cfg(debug_assertions)]
@@ -65,7 +78,13 @@ pub struct HNSWIndex<TGraphLinks: GraphLinks> {
     config: HnswGraphConfig,
     path: PathBuf,
     graph: Option<GraphLayers<TGraphLinks>>,
+    /// Aggregated multivectors to build and traverse the graph with, if configured
+    aggregated_vectors: Option<AggregatedVectors>,
+    /// Points, which vectors were updated in place after the graph was built
+    stale_points: StalePoints,
     searches_telemetry: HNSWSearchesTelemetry,
+    /// Memory of the graph, loaded into RAM, reserved from the global memory budget
+    memory_reservation: Option<MemoryReservation>,
 }
 
 struct HNSWSearchesTelemetry {
@@ -122,6 +141,12 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         } else {
             None
         };
+        // Aggregates are computed again on build, don't compute them for an index to be built
+        let aggregated_vectors = match &graph {
+            Some(_) => AggregatedVectors::build(&vector_storage.borrow(), &AtomicBool::new(false))?,
+            None => None,
+        };
+        let stale_points = StalePoints::open(path)?;
         Ok(HNSWIndex {
             id_tracker,
             vector_storage,
@@ -130,6 +155,8 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
             config,
             path: path.to_owned(),
             graph,
+            aggregated_vectors,
+            stale_points,
             searches_telemetry: HNSWSearchesTelemetry {
                 unfiltered_hnsw: OperationDurationsAggregator::new(),
                 unfiltered_plain: OperationDurationsAggregator::new(),
@@ -138,9 +165,16 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
                 exact_filtered: OperationDurationsAggregator::new(),
                 exact_unfiltered: OperationDurationsAggregator::new(),
             },
+            memory_reservation: None,
         })
     }
 
+    /// Keep memory of the graph reserved, while the index is alive
+    pub fn with_memory_reservation(mut self, memory_reservation: MemoryReservation) -> Self {
+        self.memory_reservation = Some(memory_reservation);
+        self
+    }
+
     #[cfg(test)]
     pub(super) fn graph(&self) -> Option<&GraphLayers<TGraphLinks>> {
         self.graph.as_ref()
@@ -150,6 +184,17 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         self.quantized_vectors.clone()
     }
 
+    /// Whether one more vector can be updated in place, see [`MAX_STALE_POINTS_RATIO`]
+    pub fn can_update_in_place(&self) -> bool {
+        let max_stale_points = self.indexed_vector_count() as f64 * MAX_STALE_POINTS_RATIO;
+        (self.stale_points.len() as f64) < max_stale_points
+    }
+
+    /// Persist points, which vectors were updated in place, since the last flush
+    pub fn stale_points_flusher(&self) -> Flusher {
+        self.stale_points.flusher()
+    }
+
     fn save_config(&self) -> OperationResult<()> {
         let config_path = HnswGraphConfig::get_config_path(&self.path);
         self.config.save(&config_path)
@@ -217,14 +262,20 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
 
             let vector = vector_storage.get_vector(block_point_id);
             let vector = vector.as_vec_ref().into();
-            let raw_scorer = match quantized_vectors.as_ref() {
-                Some(quantized_storage) => quantized_storage.raw_scorer(
+            let raw_scorer = match (&self.aggregated_vectors, quantized_vectors.as_ref()) {
+                (Some(aggregated_vectors), _) => aggregated_vectors.raw_scorer(
+                    vector,
+                    id_tracker.deleted_point_bitslice(),
+                    deleted_bitslice,
+                    stopped,
+                ),
+                (None, Some(quantized_storage)) => quantized_storage.raw_scorer(
                     vector,
                     id_tracker.deleted_point_bitslice(),
                     deleted_bitslice,
                     stopped,
                 ),
-                None => {
+                (None, None) => {
                     new_raw_scorer(vector, &vector_storage, id_tracker.deleted_point_bitslice())
                 }
             }?;
@@ -286,29 +337,90 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
             .deleted_points()
             .unwrap_or(id_tracker.deleted_point_bitslice());
 
-        let raw_scorer = Self::construct_search_scorer(
-            vector,
-            &vector_storage,
-            quantized_vectors.as_ref(),
-            deleted_points,
-            params,
-            &is_stopped,
-        )?;
-        let oversampled_top = Self::get_oversampled_top(quantized_vectors.as_ref(), params, top);
+        // Graph of aggregated multivectors is traversed with aggregated vectors, and `ef`
+        // candidates are rescored with the full comparator
+        let graph_aggregated = self.aggregated_vectors.is_some();
+        let (raw_scorer, oversampled_top) =
+            if let Some(aggregated_vectors) = &self.aggregated_vectors {
+                let raw_scorer = aggregated_vectors.raw_scorer(
+                    vector.to_owned(),
+                    deleted_points,
+                    vector_storage.deleted_vector_bitslice(),
+                    &is_stopped,
+                )?;
+                (raw_scorer, top.max(ef))
+            } else {
+                let raw_scorer = Self::construct_search_scorer(
+                    vector,
+                    &vector_storage,
+                    quantized_vectors.as_ref(),
+                    deleted_points,
+                    params,
+                    &is_stopped,
+                )?;
+                let oversampled_top =
+                    Self::get_oversampled_top(quantized_vectors.as_ref(), params, top);
+                (raw_scorer, oversampled_top)
+            };
 
         let filter_context = filter.map(|f| payload_index.filter_context(f));
-        let points_scorer = FilteredScorer::new(raw_scorer.as_ref(), filter_context.as_deref());
+        let new_points_scorer = || {
+            FilteredScorer::new(raw_scorer.as_ref(), filter_context.as_deref())
+                .with_hardware_counter(
+                    vector_query_context.hardware_counter(),
+                    vector_storage.average_vector_size_in_bytes(),
+                )
+        };
 
         match &self.graph {
             Some(graph) => {
+                let search_result = graph.search(
+                    oversampled_top,
+                    ef,
+                    new_points_scorer(),
+                    custom_entry_points,
+                );
                 let search_result =
-                    graph.search(oversampled_top, ef, points_scorer, custom_entry_points);
-                self.postprocess_search_result(search_result, vector, params, top, &is_stopped)
+                    self.merge_stale_points(search_result, new_points_scorer(), oversampled_top);
+                self.postprocess_search_result(
+                    search_result,
+                    vector,
+                    params,
+                    top,
+                    graph_aggregated,
+                    &is_stopped,
+                )
             }
             None => Ok(Default::default()),
         }
     }
 
+    /// Add points, which vectors were updated after the graph was built, to the graph search result
+    ///
+    /// The graph may not lead to such points, so they are scored exhaustively.
+    fn merge_stale_points(
+        &self,
+        search_result: Vec<ScoredPointOffset>,
+        mut points_scorer: FilteredScorer,
+        top: usize,
+    ) -> Vec<ScoredPointOffset> {
+        if self.stale_points.is_empty() || top == 0 || points_scorer.is_stopped() {
+            return search_result;
+        }
+
+        let mut queue = FixedLengthPriorityQueue::new(top);
+        for scored_point in search_result {
+            if !self.stale_points.contains(scored_point.idx) {
+                queue.push(scored_point);
+            }
+        }
+        let mut stale_ids: Vec<_> = self.stale_points.iter().collect();
+        for scored_point in points_scorer.score_points(&mut stale_ids, 0) {
+            queue.push(*scored_point);
+        }
+        queue.into_vec()
+    }
+
     fn search_vectors_with_graph(
         &self,
         vectors: &[&QueryVector],
@@ -362,10 +474,15 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         )?;
         let oversampled_top = Self::get_oversampled_top(quantized_vectors.as_ref(), params, top);
 
+        vector_query_context.hardware_counter().incr_scored_vectors(
+            filtered_points.len(),
+            vector_storage.average_vector_size_in_bytes(),
+        );
+
         let search_result =
             raw_scorer.peek_top_iter(&mut filtered_points.iter().copied(), oversampled_top);
 
-        self.postprocess_search_result(search_result, vector, params, top, &is_stopped)
+        self.postprocess_search_result(search_result, vector, params, top, false, &is_stopped)
     }
 
     fn search_vectors_plain(
@@ -480,12 +597,15 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         }
     }
 
+    /// Rescore the results of an approximate search, if needed.
+    /// Results of a search by aggregated multivectors are always rescored.
     fn postprocess_search_result(
         &self,
         search_result: Vec<ScoredPointOffset>,
         vector: &QueryVector,
         params: Option<&SearchParams>,
         top: usize,
+        graph_aggregated: bool,
         is_stopped: &AtomicBool,
     ) -> OperationResult<Vec<ScoredPointOffset>> {
         let id_tracker = self.id_tracker.borrow();
@@ -498,11 +618,14 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
             .as_ref()
             .map(|q| q.default_rescoring())
             .unwrap_or(false);
-        let rescore = quantization_enabled
+        // Rescoring of a stopped search would return nothing, keep approximate scores instead
+        let quantization_rescore = quantization_enabled
             && params
                 .and_then(|p| p.quantization)
                 .and_then(|q| q.rescore)
                 .unwrap_or(default_rescoring);
+        let rescore =
+            (graph_aggregated || quantization_rescore) && !is_stopped.load(Ordering::Relaxed);
 
         let mut postprocess_result = if rescore {
             let raw_scorer = new_stoppable_raw_scorer(
@@ -554,6 +677,16 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                 let plain_search = exact
                     || vector_storage.available_vector_count() < self.config.full_scan_threshold;
 
+                if query_context.is_explain_enabled() {
+                    query_context.record_search_plan(VectorSearchPlan::new(if exact {
+                        SearchStrategy::Exact
+                    } else if plain_search {
+                        SearchStrategy::FullScan
+                    } else {
+                        SearchStrategy::Hnsw
+                    }));
+                }
+
                 // Do plain or graph search
                 if plain_search {
                     let _timer = ScopeDurationMeasurer::new(if exact {
@@ -567,6 +700,11 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
 
                     let is_stopped = query_context.is_stopped();
 
+                    query_context.hardware_counter().incr_scored_vectors(
+                        vectors.len() * vector_storage.available_vector_count(),
+                        vector_storage.average_vector_size_in_bytes(),
+                    );
+
                     vectors
                         .iter()
                         .map(|&vector| {
@@ -603,6 +741,7 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                     });
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.exact_filtered);
+                    query_context.record_search_plan(VectorSearchPlan::new(SearchStrategy::Exact));
                     return self.search_vectors_plain(
                         vectors,
                         query_filter,
@@ -612,19 +751,32 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                     );
                 }
 
+                let planning_start = Instant::now();
                 let payload_index = self.payload_index.borrow();
                 let vector_storage = self.vector_storage.borrow();
                 let id_tracker = self.id_tracker.borrow();
                 let available_vector_count = vector_storage.available_vector_count();
-                let query_point_cardinality = payload_index.estimate_cardinality(query_filter);
+                let query_point_cardinality =
+                    payload_index.estimate_cardinality_sampled(query_filter);
                 let query_cardinality = adjust_to_available_vectors(
                     query_point_cardinality,
                     available_vector_count,
                     id_tracker.available_point_count(),
                 );
 
+                let record_plan = |strategy: SearchStrategy, sampled: bool| {
+                    if query_context.is_explain_enabled() {
+                        let mut plan =
+                            VectorSearchPlan::new(strategy).with_estimation(&query_cardinality);
+                        plan.sampled = sampled;
+                        plan.planning_time = planning_start.elapsed().as_secs_f64();
+                        query_context.record_search_plan(plan);
+                    }
+                };
+
                 if query_cardinality.max < self.config.full_scan_threshold {
                     // if cardinality is small - use plain index
+                    record_plan(SearchStrategy::FilteredScan, false);
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.small_cardinality);
                     return self.search_vectors_plain(
@@ -638,6 +790,7 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
 
                 if query_cardinality.min > self.config.full_scan_threshold {
                     // if cardinality is high enough - use HNSW index
+                    record_plan(SearchStrategy::Hnsw, false);
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.large_cardinality);
                     return self.search_vectors_with_graph(
@@ -660,11 +813,13 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                     available_vector_count, // Check cardinality among available vectors
                 ) {
                     // if cardinality is high enough - use HNSW index
+                    record_plan(SearchStrategy::Hnsw, true);
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.large_cardinality);
                     self.search_vectors_with_graph(vectors, filter, top, params, query_context)
                 } else {
                     // if cardinality is small - use plain index
+                    record_plan(SearchStrategy::FilteredScan, true);
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.small_cardinality);
                     self.search_vectors_plain(vectors, query_filter, top, params, query_context)
@@ -679,6 +834,8 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
         stopped: &AtomicBool,
         _tick_progress: impl FnMut(),
     ) -> OperationResult<()> {
+        self.aggregated_vectors = AggregatedVectors::build(&self.vector_storage.borrow(), stopped)?;
+
         let vector_storage = self.vector_storage.borrow();
         let total_vector_count = vector_storage.total_vector_count();
         if total_vector_count == 0 {
@@ -691,6 +848,7 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
         let quantized_vectors = self.quantized_vectors.borrow();
         let mut rng = thread_rng();
         let deleted_bitslice = vector_storage.deleted_vector_bitslice();
+        let aggregated_vectors = self.aggregated_vectors.as_ref();
 
         debug!(
             "building HNSW for {total_vector_count} vectors with {} CPUs",
@@ -722,11 +880,12 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                     b = b.stack_size(stack_size);
                 }
                 b.spawn(|| {
-                    // On Linux, use lower thread priority so we interfere less with serving traffic
+                    // On Linux, use optimizer thread priority (low by default), so we interfere
+                    // less with serving traffic
                     #[cfg(target_os = "linux")]
-                    if let Err(err) = linux_low_thread_priority() {
+                    if let Err(err) = Workload::Optimization.apply_thread_priority() {
                         log::debug!(
-                            "Failed to set low thread priority for HNSW building, ignoring: {err}"
+                            "Failed to set thread priority for HNSW building, ignoring: {err}"
                         );
                     }
 
@@ -759,7 +918,14 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                 check_process_stopped(stopped)?;
                 let vector = vector_storage.get_vector(vector_id);
                 let vector = vector.as_vec_ref().into();
-                let raw_scorer = if let Some(quantized_storage) = quantized_vectors.as_ref() {
+                let raw_scorer = if let Some(aggregated_vectors) = aggregated_vectors {
+                    aggregated_vectors.raw_scorer(
+                        vector,
+                        id_tracker.deleted_point_bitslice(),
+                        vector_storage.deleted_vector_bitslice(),
+                        stopped,
+                    )
+                } else if let Some(quantized_storage) = quantized_vectors.as_ref() {
                     quantized_storage.raw_scorer(
                         vector,
                         id_tracker.deleted_point_bitslice(),
@@ -856,6 +1022,8 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
 
         let graph_links_path = GraphLayers::<TGraphLinks>::get_links_path(&self.path);
         self.graph = Some(graph_layers_builder.into_graph_layers(Some(&graph_links_path))?);
+        // The new graph is built for current vectors of all points
+        self.stale_points.clear()?;
 
         #[cfg(debug_assertions)]
         {
@@ -884,15 +1052,18 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
             filtered_sparse: Default::default(),
             unfiltered_exact: tm.exact_unfiltered.lock().get_statistics(detail),
             unfiltered_sparse: Default::default(),
+            stale_points: Some(self.stale_points.len()),
         }
     }
 
     fn files(&self) -> Vec<PathBuf> {
         if self.graph.is_some() {
-            vec![
+            let mut files = vec![
                 GraphLayers::<TGraphLinks>::get_path(&self.path),
                 GraphLayers::<TGraphLinks>::get_links_path(&self.path),
-            ]
+            ];
+            files.extend(self.stale_points.files());
+            files
         } else {
             vec![]
         }
@@ -906,11 +1077,35 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
             .unwrap_or(0)
     }
 
+    /// Update the vector in place, if the vector storage allows it
+    ///
+    /// Links of the point in the graph are kept as-is, the point is marked as stale instead and
+    /// scored exhaustively on search. Quantized vectors can't be updated, so they are rejected.
     fn update_vector(
         &mut self,
-        _id: PointOffsetType,
-        _vector: Option<VectorRef>,
+        id: PointOffsetType,
+        vector: Option<VectorRef>,
     ) -> OperationResult<()> {
-        Err(OperationError::service_error("Cannot update HNSW index"))
+        if self.quantized_vectors.borrow().is_some() {
+            return Err(OperationError::service_error(
+                "Cannot update quantized vectors of HNSW index",
+            ));
+        }
+        let mut vector_storage = self.vector_storage.borrow_mut();
+        match vector {
+            Some(vector) => {
+                // Mark the point first, so it is never missed by search if the storage is updated
+                self.stale_points.insert(id);
+                vector_storage.insert_vector(id, vector)?;
+                if let Some(aggregated_vectors) = &mut self.aggregated_vectors {
+                    aggregated_vectors.update(id, &vector_storage)?;
+                }
+            }
+            None => {
+                // Deleted vectors are excluded from search by the storage itself
+                vector_storage.delete_vector(id)?;
+            }
+        }
+        Ok(())
     }
 }
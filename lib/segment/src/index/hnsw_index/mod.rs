@@ -10,6 +10,7 @@ pub mod graph_links;
 pub mod hnsw;
 pub mod point_scorer;
 mod search_context;
+mod stale_points;
 
 #[cfg(test)]
 mod tests;
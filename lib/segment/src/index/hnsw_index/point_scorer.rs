@@ -1,3 +1,4 @@
+use common::hardware_counter::HardwareCounterCell;
 use common::types::{PointOffsetType, ScoreType, ScoredPointOffset};
 
 use crate::payload_storage::FilterContext;
@@ -7,6 +8,8 @@ pub struct FilteredScorer<'a> {
     pub raw_scorer: &'a dyn RawScorer,
     pub filter_context: Option<&'a dyn FilterContext>,
     points_buffer: Vec<ScoredPointOffset>,
+    hardware_counter: HardwareCounterCell,
+    vector_size: usize,
 }
 
 impl<'a> FilteredScorer<'a> {
@@ -18,9 +21,22 @@ impl<'a> FilteredScorer<'a> {
             raw_scorer,
             filter_context,
             points_buffer: Vec::new(),
+            hardware_counter: HardwareCounterCell::disabled(),
+            vector_size: 0,
         }
     }
 
+    /// Account scored vectors of `vector_size` bytes in the given counter
+    pub fn with_hardware_counter(
+        mut self,
+        hardware_counter: &HardwareCounterCell,
+        vector_size: usize,
+    ) -> Self {
+        self.hardware_counter = hardware_counter.clone();
+        self.vector_size = vector_size;
+        self
+    }
+
     pub fn check_vector(&self, point_id: PointOffsetType) -> bool {
         match self.filter_context {
             None => self.raw_scorer.check_vector(point_id),
@@ -66,6 +82,8 @@ impl<'a> FilteredScorer<'a> {
             self.points_buffer
                 .resize_with(limit, ScoredPointOffset::default);
         }
+        self.hardware_counter
+            .incr_scored_vectors(filtered_point_ids.len(), self.vector_size);
         let count = self
             .raw_scorer
             .score_points(filtered_point_ids, &mut self.points_buffer);
@@ -73,9 +91,16 @@ impl<'a> FilteredScorer<'a> {
     }
 
     pub fn score_point(&self, point_id: PointOffsetType) -> ScoreType {
+        self.hardware_counter
+            .incr_scored_vectors(1, self.vector_size);
         self.raw_scorer.score_point(point_id)
     }
 
+    /// Whether the search is stopped, and the best points found so far should be returned
+    pub fn is_stopped(&self) -> bool {
+        self.raw_scorer.is_stopped()
+    }
+
     pub fn score_internal(&self, point_a: PointOffsetType, point_b: PointOffsetType) -> ScoreType {
         self.raw_scorer.score_internal(point_a, point_b)
     }
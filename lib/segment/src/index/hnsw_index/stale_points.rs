@@ -0,0 +1,106 @@
+use std::collections::BTreeSet;
+use std::fs::remove_file;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use common::types::PointOffsetType;
+use io::file_operations::{atomic_save_json, read_json};
+
+use crate::common::operation_error::OperationResult;
+use crate::common::Flusher;
+
+const STALE_POINTS_FILE: &str = "stale_points.json";
+
+/// Points, which vectors were updated in place after the graph was built
+///
+/// Links of these points in the graph are built for their previous vectors, so the graph may
+/// not lead to them. They are scored exhaustively on top of every graph search, until the
+/// index is rebuilt.
+#[derive(Debug, Default)]
+pub struct StalePoints {
+    path: PathBuf,
+    points: BTreeSet<PointOffsetType>,
+    /// Whether points were changed since they were persisted last time
+    is_dirty: Arc<AtomicBool>,
+}
+
+impl StalePoints {
+    pub fn get_path(index_path: &Path) -> PathBuf {
+        index_path.join(STALE_POINTS_FILE)
+    }
+
+    pub fn open(index_path: &Path) -> OperationResult<Self> {
+        let path = Self::get_path(index_path);
+        let points = if path.exists() {
+            read_json(&path)?
+        } else {
+            BTreeSet::new()
+        };
+        Ok(Self {
+            path,
+            points,
+            is_dirty: Default::default(),
+        })
+    }
+
+    /// Mark the point as stale, changes are persisted on flush
+    pub fn insert(&mut self, point_id: PointOffsetType) {
+        if self.points.insert(point_id) {
+            self.is_dirty.store(true, Ordering::Release);
+        }
+    }
+
+    /// Forget all stale points, once the graph is rebuilt
+    pub fn clear(&mut self) -> OperationResult<()> {
+        self.points.clear();
+        self.is_dirty.store(false, Ordering::Release);
+        if self.path.exists() {
+            remove_file(&self.path)?;
+        }
+        Ok(())
+    }
+
+    pub fn contains(&self, point_id: PointOffsetType) -> bool {
+        self.points.contains(&point_id)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = PointOffsetType> + '_ {
+        self.points.iter().copied()
+    }
+
+    /// Persist current stale points, does nothing if they were not changed since the last flush
+    pub fn flusher(&self) -> Flusher {
+        if !self.is_dirty.swap(false, Ordering::AcqRel) {
+            return Box::new(|| Ok(()));
+        }
+        let path = self.path.clone();
+        let points = self.points.clone();
+        let is_dirty = self.is_dirty.clone();
+        Box::new(move || {
+            if let Err(err) = atomic_save_json(&path, &points) {
+                // Save again on the next flush
+                is_dirty.store(true, Ordering::Release);
+                return Err(err.into());
+            }
+            Ok(())
+        })
+    }
+
+    /// Persisted file, if there are any stale points
+    pub fn files(&self) -> Vec<PathBuf> {
+        if self.points.is_empty() {
+            vec![]
+        } else {
+            vec![self.path.clone()]
+        }
+    }
+}
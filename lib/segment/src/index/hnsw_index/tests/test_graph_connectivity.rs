@@ -50,6 +50,8 @@ fn test_graph_connectivity() {
             },
         )]),
         payload_storage_type: Default::default(),
+        payload_columns: Default::default(),
+        payload_compression: Default::default(),
         sparse_vector_data: Default::default(),
     };
 
@@ -5,6 +5,7 @@ const FLOAT_POS: u8 = 0x03;
 
 const F64_KEY_LEN: usize = 13;
 const I64_KEY_LEN: usize = 12;
+const I128_KEY_LEN: usize = 20;
 
 /// Encode a f64 into `buf`
 ///
@@ -87,6 +88,18 @@ pub fn decode_i64_ascending(buf: &[u8]) -> i64 {
     i ^ i64::MIN
 }
 
+/// Encode a i128 into `buf` so that is sorts ascending.
+pub fn encode_i128_ascending(val: i128, buf: &mut Vec<u8>) {
+    let i = val ^ i128::MIN;
+    buf.extend(i.to_be_bytes());
+}
+
+/// Decode a i128 from a slice
+pub fn decode_i128_ascending(buf: &[u8]) -> i128 {
+    let i = i128::from_be_bytes(buf[0..16].try_into().expect("cannot decode i128"));
+    i ^ i128::MIN
+}
+
 /// Encodes a f64 key so that it sort in ascending order.
 ///
 /// The key is compound by the numeric value of the key plus a u32 representing
@@ -155,12 +168,45 @@ pub fn decode_i64_key_ascending(buf: &[u8]) -> (u32, i64) {
     )
 }
 
+/// Encodes a i128 key so that it sort in ascending order.
+///
+/// Same as i64 key, but with 16 bytes of the numeric value.
+///
+/// # int key encoding format
+///
+///```text
+///
+/// 0                     16            20
+/// ┌─────────────────────┬──────────────┐
+/// │ key_val ^ i128::MIN │ point_offset │
+/// │    (big-endian)     │ (big-endian) │
+/// └─────────────────────┴──────────────┘
+///```
+pub fn encode_i128_key_ascending(key_val: i128, point_offset: u32) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(I128_KEY_LEN);
+    encode_i128_ascending(key_val, &mut buf);
+    buf.extend(point_offset.to_be_bytes());
+    buf
+}
+
+pub fn decode_i128_key_ascending(buf: &[u8]) -> (u32, i128) {
+    (
+        u32::from_be_bytes(
+            (&buf[I128_KEY_LEN - std::mem::size_of::<u32>()..])
+                .try_into()
+                .unwrap(),
+        ),
+        decode_i128_ascending(buf),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use std::cmp::Ordering;
 
     use crate::index::key_encoding::{
-        decode_f64_ascending, decode_i64_ascending, encode_f64_ascending, encode_i64_ascending,
+        decode_f64_ascending, decode_i128_ascending, decode_i64_ascending, encode_f64_ascending,
+        encode_i128_ascending, encode_i64_ascending,
     };
 
     #[test]
@@ -180,6 +226,15 @@ mod tests {
         test_i64_encoding_roundtrip(-98793);
     }
 
+    #[test]
+    fn test_encode_i128() {
+        test_i128_encoding_roundtrip(i128::MIN);
+        test_i128_encoding_roundtrip(i128::MAX);
+        test_i128_encoding_roundtrip(0);
+        test_i128_encoding_roundtrip(u64::MAX as i128);
+        test_i128_encoding_roundtrip(-98793);
+    }
+
     #[test]
     fn test_f64_lex_order() {
         let mut nan_buf = Vec::new();
@@ -211,6 +266,23 @@ mod tests {
         assert_eq!(zero_buf.cmp(&pos_buf), Ordering::Less);
     }
 
+    #[test]
+    fn test_i128_lex_order() {
+        let mut neg_buf = Vec::new();
+        let mut pos_buf = Vec::new();
+        let mut big_buf = Vec::new();
+        let mut bigger_buf = Vec::new();
+
+        encode_i128_ascending(-4324, &mut neg_buf);
+        encode_i128_ascending(123, &mut pos_buf);
+        encode_i128_ascending(i64::MAX as i128 + 1, &mut big_buf);
+        encode_i128_ascending(u64::MAX as i128, &mut bigger_buf);
+
+        assert_eq!(neg_buf.cmp(&pos_buf), Ordering::Less);
+        assert_eq!(pos_buf.cmp(&big_buf), Ordering::Less);
+        assert_eq!(big_buf.cmp(&bigger_buf), Ordering::Less);
+    }
+
     fn test_f64_encoding_roundtrip(val: f64) {
         let mut buf = Vec::new();
         encode_f64_ascending(val, &mut buf);
@@ -228,4 +300,11 @@ mod tests {
         let res = decode_i64_ascending(buf.as_slice());
         assert_eq!(val, res);
     }
+
+    fn test_i128_encoding_roundtrip(val: i128) {
+        let mut buf = Vec::new();
+        encode_i128_ascending(val, &mut buf);
+        let res = decode_i128_ascending(buf.as_slice());
+        assert_eq!(val, res);
+    }
 }
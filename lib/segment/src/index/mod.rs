@@ -1,6 +1,8 @@
 pub mod field_index;
+mod filter_cache;
 pub mod hnsw_index;
 mod key_encoding;
+pub mod payload_columns;
 mod payload_config;
 mod payload_index_base;
 pub mod plain_payload_index;
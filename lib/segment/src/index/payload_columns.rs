@@ -0,0 +1,233 @@
+//! Columnar layout of selected payload fields.
+//!
+//! Numeric and datetime values of each selected field are kept in a single contiguous array, so
+//! scans over the field, like ordering by it, don't need to read and parse the whole payload of
+//! every point.
+//! Columns are built from the payload storage on load, and are kept in sync with payload updates.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use common::types::PointOffsetType;
+use serde_json::Value;
+
+use crate::common::operation_error::OperationResult;
+use crate::data_types::order_by::OrderValue;
+use crate::payload_storage::payload_storage_enum::PayloadStorageEnum;
+use crate::types::{DateTimeWrapper, Payload, PayloadContainer, PayloadKeyType, PayloadKeyTypeRef};
+
+/// Values of a single field of all points
+#[derive(Debug, Default)]
+pub struct PayloadColumn {
+    /// Values of all points, values of a point are stored next to each other
+    values: Vec<OrderValue>,
+    /// Start and end of values of each point in `values`
+    ranges: Vec<(u32, u32)>,
+    /// Number of values, not referenced by any point anymore
+    garbage: usize,
+}
+
+impl PayloadColumn {
+    pub fn get(&self, point_id: PointOffsetType) -> &[OrderValue] {
+        match self.ranges.get(point_id as usize) {
+            Some(&(start, end)) => &self.values[start as usize..end as usize],
+            None => &[],
+        }
+    }
+
+    /// Iterate over points, having values, in order of their offsets
+    pub fn iter(&self) -> impl Iterator<Item = (PointOffsetType, &[OrderValue])> + '_ {
+        self.ranges
+            .iter()
+            .enumerate()
+            .filter(|(_, (start, end))| start < end)
+            .map(|(point_id, &(start, end))| {
+                (
+                    point_id as PointOffsetType,
+                    &self.values[start as usize..end as usize],
+                )
+            })
+    }
+
+    fn set(&mut self, point_id: PointOffsetType, values: impl IntoIterator<Item = OrderValue>) {
+        self.remove(point_id);
+        let start = self.values.len() as u32;
+        self.values.extend(values);
+        let end = self.values.len() as u32;
+
+        let point_id = point_id as usize;
+        if self.ranges.len() <= point_id {
+            self.ranges.resize(point_id + 1, (0, 0));
+        }
+        self.ranges[point_id] = (start, end);
+    }
+
+    fn remove(&mut self, point_id: PointOffsetType) {
+        let Some(range) = self.ranges.get_mut(point_id as usize) else {
+            return;
+        };
+        self.garbage += (range.1 - range.0) as usize;
+        *range = (0, 0);
+
+        // Values are only appended, so rewrite the column once most of it is garbage
+        if self.garbage > self.values.len() / 2 {
+            self.compact();
+        }
+    }
+
+    fn compact(&mut self) {
+        let mut values = Vec::with_capacity(self.values.len() - self.garbage);
+        for range in &mut self.ranges {
+            let start = values.len() as u32;
+            values.extend_from_slice(&self.values[range.0 as usize..range.1 as usize]);
+            *range = (start, values.len() as u32);
+        }
+        self.values = values;
+        self.garbage = 0;
+    }
+}
+
+/// Columns of the fields, selected in the segment config
+#[derive(Debug, Default)]
+pub struct PayloadColumns {
+    columns: HashMap<PayloadKeyType, PayloadColumn>,
+}
+
+impl PayloadColumns {
+    pub fn build(fields: &[PayloadKeyType], payload: &PayloadStorageEnum) -> OperationResult<Self> {
+        let mut columns = Self {
+            columns: fields
+                .iter()
+                .map(|field| (field.clone(), PayloadColumn::default()))
+                .collect(),
+        };
+        if !columns.is_empty() {
+            payload.iter(|point_id, point_payload| {
+                columns.update(point_id, point_payload);
+                Ok(true)
+            })?;
+        }
+        Ok(columns)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+
+    pub fn get(&self, field: PayloadKeyTypeRef) -> Option<&PayloadColumn> {
+        self.columns.get(field)
+    }
+
+    /// Replace values of the point with ones from its current payload
+    pub fn update(&mut self, point_id: PointOffsetType, payload: &Payload) {
+        for (field, column) in &mut self.columns {
+            let values = payload.get_value(field);
+            column.set(
+                point_id,
+                values.iter().flat_map(|value| column_values(value)),
+            );
+        }
+    }
+
+    pub fn remove(&mut self, point_id: PointOffsetType) {
+        for column in self.columns.values_mut() {
+            column.remove(point_id);
+        }
+    }
+}
+
+/// Numeric values of a payload value, or of its elements, if it is an array
+fn column_values(value: &Value) -> Vec<OrderValue> {
+    match value {
+        Value::Array(values) => values.iter().filter_map(column_value).collect(),
+        value => column_value(value).into_iter().collect(),
+    }
+}
+
+/// Number, or datetime string as microseconds timestamp, same as in the datetime index
+fn column_value(value: &Value) -> Option<OrderValue> {
+    match value {
+        Value::String(value) => DateTimeWrapper::from_str(value)
+            .ok()
+            .map(|datetime| OrderValue::Int(datetime.timestamp())),
+        value => OrderValue::try_from(value.clone()).ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use serde_json::json;
+
+    use super::*;
+    use crate::json_path::path;
+
+    #[test]
+    fn test_payload_column_updates() {
+        let mut columns = PayloadColumns {
+            columns: HashMap::from([(path("price"), PayloadColumn::default())]),
+        };
+
+        let payload = |value: Value| Payload::from(json!({ "price": value }));
+        columns.update(0, &payload(json!(10)));
+        columns.update(2, &payload(json!([1.5, "cheap", 3])));
+        columns.update(3, &payload(json!("free")));
+        columns.update(4, &payload(json!("2024-01-02T03:04:05Z")));
+
+        let column = columns.get(&path("price")).unwrap();
+        assert_eq!(column.get(0), &[OrderValue::Int(10)]);
+        assert!(column.get(1).is_empty());
+        assert_eq!(column.get(2), &[OrderValue::Float(1.5), OrderValue::Int(3)]);
+        assert!(column.get(3).is_empty());
+        assert_eq!(column.get(4), &[OrderValue::Int(1_704_164_645_000_000)]);
+
+        // Overwritten values are compacted, without changing values of other points
+        for i in 0..10 {
+            columns.update(0, &payload(json!(i)));
+        }
+        columns.remove(2);
+        columns.remove(4);
+
+        let column = columns.get(&path("price")).unwrap();
+        assert!(column.values.len() <= 2 * (column.values.len() - column.garbage));
+        let points: Vec<_> = column.iter().collect();
+        assert_eq!(points, vec![(0, &[OrderValue::Int(9)][..])]);
+    }
+
+    #[test]
+    fn test_payload_column_datetime_timezones() {
+        let mut columns = PayloadColumns {
+            columns: HashMap::from([(path("time"), PayloadColumn::default())]),
+        };
+
+        // In chronological order, but not in lexicographical one
+        let datetimes = [
+            "2000-06-08 00:18:53+0900",
+            "2000-06-07 07:25:34-1100",
+            "2000-07-10T00:18:53+0100",
+            "2000-07-11 00:25:34-01:00",
+            "2000-07-11 00:25:35-01",
+        ];
+        for (point_id, datetime) in datetimes.iter().rev().enumerate() {
+            let payload = Payload::from(json!({ "time": datetime }));
+            columns.update(point_id as PointOffsetType, &payload);
+        }
+
+        let column = columns.get(&path("time")).unwrap();
+        for (point_id, datetime) in datetimes.iter().rev().enumerate() {
+            // Same key as in the datetime index
+            let timestamp = DateTimeWrapper::from_str(datetime).unwrap().timestamp();
+            assert_eq!(
+                column.get(point_id as PointOffsetType),
+                &[OrderValue::Int(timestamp)],
+            );
+        }
+
+        let ordered_points: Vec<_> = column
+            .iter()
+            .sorted_by_key(|(_, values)| values[0])
+            .map(|(point_id, _)| point_id)
+            .collect();
+        assert_eq!(ordered_points, vec![4, 3, 2, 1, 0]);
+    }
+}
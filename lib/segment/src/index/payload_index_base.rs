@@ -32,6 +32,14 @@ pub trait PayloadIndex {
     /// A best estimation of the number of available points should be given.
     fn estimate_cardinality(&self, query: &Filter) -> CardinalityEstimation;
 
+    /// Estimate amount of points (min, max) which satisfies filtering condition, refining wide
+    /// bounds of the index-based estimation by checking a sample of points against the filter.
+    ///
+    /// More expensive than `estimate_cardinality`, meant for choosing a query plan.
+    fn estimate_cardinality_sampled(&self, query: &Filter) -> CardinalityEstimation {
+        self.estimate_cardinality(query)
+    }
+
     /// Estimate amount of points (min, max) which satisfies filtering of a nested condition.
     fn estimate_nested_cardinality(
         &self,
@@ -72,9 +80,15 @@ pub trait PayloadIndex {
         key: &Option<JsonPath>,
     ) -> OperationResult<()>;
 
+    /// Apply JSON Merge Patch (RFC 7396) to the payload of a concrete point
+    fn patch(&mut self, point_id: PointOffsetType, patch: &Payload) -> OperationResult<()>;
+
     /// Get payload for point
     fn payload(&self, point_id: PointOffsetType) -> OperationResult<Payload>;
 
+    /// Get only the given top level keys of the point payload
+    fn payload_fields(&self, point_id: PointOffsetType, keys: &[&str]) -> OperationResult<Payload>;
+
     /// Delete payload by key
     fn delete(
         &mut self,
@@ -16,6 +16,7 @@ use crate::common::operation_time_statistics::{
 };
 use crate::common::{Flusher, BYTES_IN_KB};
 use crate::data_types::query_context::VectorQueryContext;
+use crate::data_types::search_plan::{SearchStrategy, VectorSearchPlan};
 use crate::data_types::vectors::{QueryVector, VectorRef};
 use crate::id_tracker::IdTrackerSS;
 use crate::index::field_index::{CardinalityEstimation, PayloadBlockCondition};
@@ -167,10 +168,22 @@ impl PayloadIndex for PlainPayloadIndex {
         unreachable!()
     }
 
+    fn patch(&mut self, _point_id: PointOffsetType, _patch: &Payload) -> OperationResult<()> {
+        unreachable!()
+    }
+
     fn payload(&self, _point_id: PointOffsetType) -> OperationResult<Payload> {
         unreachable!()
     }
 
+    fn payload_fields(
+        &self,
+        _point_id: PointOffsetType,
+        _keys: &[&str],
+    ) -> OperationResult<Payload> {
+        unreachable!()
+    }
+
     fn delete(
         &mut self,
         _point_id: PointOffsetType,
@@ -281,9 +294,20 @@ impl VectorIndex for PlainIndex {
                 let payload_index = self.payload_index.borrow();
                 let vector_storage = self.vector_storage.borrow();
                 let filtered_ids_vec = payload_index.query_points(filter);
+                if query_context.is_explain_enabled() {
+                    let estimation = payload_index.estimate_cardinality(filter);
+                    let mut plan = VectorSearchPlan::new(SearchStrategy::FilteredScan)
+                        .with_estimation(&estimation);
+                    plan.actual_cardinality = Some(filtered_ids_vec.len());
+                    query_context.record_search_plan(plan);
+                }
                 let deleted_points = query_context
                     .deleted_points()
                     .unwrap_or(id_tracker.deleted_point_bitslice());
+                query_context.hardware_counter().incr_scored_vectors(
+                    vectors.len() * filtered_ids_vec.len(),
+                    vector_storage.average_vector_size_in_bytes(),
+                );
                 vectors
                     .iter()
                     .map(|&vector| {
@@ -301,11 +325,16 @@ impl VectorIndex for PlainIndex {
             }
             None => {
                 let _timer = ScopeDurationMeasurer::new(&self.unfiltered_searches_telemetry);
+                query_context.record_search_plan(VectorSearchPlan::new(SearchStrategy::FullScan));
                 let vector_storage = self.vector_storage.borrow();
                 let id_tracker = self.id_tracker.borrow();
                 let deleted_points = query_context
                     .deleted_points()
                     .unwrap_or(id_tracker.deleted_point_bitslice());
+                query_context.hardware_counter().incr_scored_vectors(
+                    vectors.len() * vector_storage.available_vector_count(),
+                    vector_storage.average_vector_size_in_bytes(),
+                );
                 vectors
                     .iter()
                     .map(|&vector| {
@@ -349,6 +378,7 @@ impl VectorIndex for PlainIndex {
             filtered_sparse: Default::default(),
             unfiltered_exact: OperationDurationStatistics::default(),
             unfiltered_sparse: OperationDurationStatistics::default(),
+            stale_points: None,
         }
     }
 
@@ -10,6 +10,10 @@ use itertools::Itertools;
 use crate::index::field_index::{CardinalityEstimation, PrimaryCondition};
 use crate::types::{Condition, Filter, MinShould};
 
+/// Max number of condition combinations, which `min_should` estimation combines one by one.
+/// Above it, the estimation assumes conditions are independent.
+const MAX_MIN_SHOULD_COMBINATIONS: usize = 64;
+
 /// Re-estimate cardinality based on number of available vectors
 /// Assuming that deleted vectors are not correlated with the filter
 ///
@@ -107,6 +111,10 @@ pub fn combine_min_should_estimations(
     min_count: usize,
     total: usize,
 ) -> CardinalityEstimation {
+    if count_combinations(estimations.len(), min_count) > MAX_MIN_SHOULD_COMBINATIONS {
+        return estimate_independent_min_should(estimations, min_count, total);
+    }
+
     /*
     | First estimate cardinality of intersections and then combine the estimations
     | ex) min_count : 2, # of estimations : 4
@@ -123,6 +131,81 @@ pub fn combine_min_should_estimations(
     combine_should_estimations(&intersection_estimations, total)
 }
 
+/// Number of `k`-combinations of `n` elements, saturating at `usize::MAX`
+fn count_combinations(n: usize, k: usize) -> usize {
+    if k > n {
+        return 0;
+    }
+    let k = k.min(n - k);
+    let mut count: usize = 1;
+    for i in 0..k {
+        // `count * (n - i)` is always divisible by `i + 1`
+        count = match count.checked_mul(n - i) {
+            Some(product) => product / (i + 1),
+            None => return usize::MAX,
+        };
+    }
+    count
+}
+
+/// Estimate `min_should` without enumerating combinations of conditions, for large clauses
+///
+/// `1 <= min_count <= estimations.len()`, smaller or larger counts have few combinations.
+fn estimate_independent_min_should(
+    estimations: &[CardinalityEstimation],
+    min_count: usize,
+    total: usize,
+) -> CardinalityEstimation {
+    let conditions_count = estimations.len();
+
+    // Point, matching `min_count` conditions, can't miss more than `conditions_count - min_count`
+    // of them. So it matches at least one of any `conditions_count - min_count + 1` conditions,
+    // and the most selective of them with indexes are enough to select candidates.
+    let required_clauses = conditions_count - min_count + 1;
+    let indexed: Vec<_> = estimations
+        .iter()
+        .filter(|estimation| !estimation.primary_clauses.is_empty())
+        .sorted_by_key(|estimation| estimation.exp)
+        .take(required_clauses)
+        .collect();
+    let primary_clauses = if indexed.len() == required_clauses {
+        indexed
+            .into_iter()
+            .flat_map(|estimation| estimation.primary_clauses.iter().cloned())
+            .collect()
+    } else {
+        vec![]
+    };
+
+    // Each point contributes at most `conditions_count` matches, and points with less than
+    // `min_count` matches contribute at most `min_count - 1` each
+    let min_matches: usize = estimations.iter().map(|x| x.min).sum();
+    let min = min_matches.saturating_sub((min_count - 1) * total) / required_clauses;
+    let max_matches: usize = estimations.iter().map(|x| x.max).sum();
+    let max = min(max_matches / min_count, total);
+
+    // Probability of at least `min_count` hits of independent conditions:
+    // `hits[j]` is the probability of exactly `j` hits, all `j >= min_count` are accumulated in the last
+    let mut hits = vec![0.0; min_count + 1];
+    hits[0] = 1.0;
+    for estimation in estimations {
+        let hit_prob = estimation.exp as f64 / total.max(1) as f64;
+        hits[min_count] += hits[min_count - 1] * hit_prob;
+        for j in (1..min_count).rev() {
+            hits[j] = hits[j] * (1.0 - hit_prob) + hits[j - 1] * hit_prob;
+        }
+        hits[0] *= 1.0 - hit_prob;
+    }
+    let exp = ((hits[min_count] * total as f64).round() as usize).clamp(min, max);
+
+    CardinalityEstimation {
+        primary_clauses,
+        min,
+        exp,
+        max,
+    }
+}
+
 pub fn combine_must_estimations(
     estimations: &[CardinalityEstimation],
     total: usize,
@@ -305,6 +388,8 @@ mod tests {
             Condition::Filter(_) => panic!("unexpected Filter"),
             Condition::Nested(_) => panic!("unexpected Nested"),
             Condition::Resharding(_) => panic!("unexpected Resharding"),
+            Condition::HasVector(_) => panic!("unexpected HasVector"),
+            Condition::Attribute(_) => panic!("unexpected Attribute"),
             Condition::Field(field) => match field.key.to_string().as_str() {
                 "color" => CardinalityEstimation {
                     primary_clauses: vec![PrimaryCondition::Condition(field.clone())],
@@ -453,6 +538,38 @@ mod tests {
         assert!(estimation.min <= estimation.exp);
     }
 
+    #[test]
+    fn large_min_should_estimation_query_test() {
+        // C(24, 12) combinations, not enumerated
+        let conditions = ["color", "size", "price"]
+            .into_iter()
+            .cycle()
+            .take(24)
+            .map(test_condition)
+            .collect_vec();
+        let query = Filter::new_min_should(MinShould {
+            conditions,
+            min_count: 12,
+        });
+
+        let estimation = estimate_filter(&test_estimator, &query, TOTAL);
+        assert_eq!(estimation.primary_clauses.len(), 13);
+        assert!(estimation.max <= TOTAL);
+        assert!(estimation.exp <= estimation.max);
+        assert!(estimation.min <= estimation.exp);
+
+        let mut conditions = vec![test_condition("unknown"); 12];
+        conditions.extend(vec![test_condition("price"); 12]);
+        let query = Filter::new_min_should(MinShould {
+            conditions,
+            min_count: 12,
+        });
+
+        // Not enough indexed conditions to select candidates
+        let estimation = estimate_filter(&test_estimator, &query, TOTAL);
+        assert!(estimation.primary_clauses.is_empty());
+    }
+
     #[test]
     fn min_should_with_min_count_same_as_condition_count_is_equivalent_to_must() {
         let conditions = vec![
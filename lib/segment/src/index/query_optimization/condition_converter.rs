@@ -1,29 +1,37 @@
 use std::collections::HashSet;
 
+use atomic_refcell::AtomicRefCell;
 use common::types::PointOffsetType;
 use serde_json::Value;
 
-use crate::common::utils::IndexesMap;
+use crate::attribute_storage::AttributeStorage;
+use crate::common::utils::{IndexesMap, VectorStoragesMap};
 use crate::id_tracker::IdTrackerSS;
+use crate::index::field_index::min_hash_index::SimilarQuery;
 use crate::index::field_index::FieldIndex;
 use crate::index::query_optimization::optimized_filter::ConditionCheckerFn;
 use crate::index::query_optimization::payload_provider::PayloadProvider;
+use crate::json_path::{JsonPath, JsonPathInterface};
 use crate::payload_storage::condition_checker::INDEXSET_ITER_THRESHOLD;
 use crate::payload_storage::query_checker::{
     check_field_condition, check_is_empty_condition, check_is_null_condition, check_payload,
     select_nested_indexes,
 };
 use crate::types::{
-    AnyVariants, Condition, DateTimePayloadType, FieldCondition, FloatPayloadType, GeoBoundingBox,
-    GeoPolygon, GeoRadius, IntPayloadType, Match, MatchAny, MatchExcept, MatchText, MatchValue,
-    OwnedPayloadRef, PayloadContainer, Range, RangeInterface, ValueVariants,
+    AnyVariants, BigIntPayloadType, Condition, DateTimePayloadType, FieldCondition, Filter,
+    FloatPayloadType, GeoBoundingBox, GeoPolygon, GeoRadius, IntPayloadType, Match, MatchAny,
+    MatchExcept, MatchText, MatchValue, OwnedPayloadRef, PayloadContainer, Range, RangeInterface,
+    ValueVariants,
 };
+use crate::vector_storage::VectorStorage;
 
 pub fn condition_converter<'a>(
     condition: &'a Condition,
     field_indexes: &'a IndexesMap,
     payload_provider: PayloadProvider,
     id_tracker: &IdTrackerSS,
+    vector_storages: &'a VectorStoragesMap,
+    attribute_storage: &'a AtomicRefCell<AttributeStorage>,
 ) -> ConditionCheckerFn<'a> {
     match condition {
         Condition::Field(field_condition) => field_indexes
@@ -73,6 +81,21 @@ pub fn condition_converter<'a>(
                 .collect();
             Box::new(move |point_id| segment_ids.contains(&point_id))
         }
+        Condition::HasVector(has_vector) => match vector_storages.get(&has_vector.has_vector) {
+            Some(vector_storage) => Box::new(move |point_id| {
+                let vector_storage = vector_storage.borrow();
+                (point_id as usize) < vector_storage.total_vector_count()
+                    && !vector_storage.is_deleted_vector(point_id)
+            }),
+            None => Box::new(|_| false),
+        },
+        Condition::Attribute(attribute) => Box::new(move |point_id| {
+            attribute.check(
+                attribute_storage
+                    .borrow()
+                    .get(&attribute.attribute, point_id),
+            )
+        }),
         Condition::Nested(nested) => {
             // Select indexes for nested fields. Trim nested part from key, so
             // that nested condition can address fields without nested part.
@@ -95,8 +118,10 @@ pub fn condition_converter<'a>(
             let nested_path = nested.array_key();
 
             let nested_indexes = select_nested_indexes(&nested_path, field_indexes);
+            let index_checkers =
+                NestedIndexCheckers::new(&nested_path, nested.filter(), field_indexes);
 
-            Box::new(move |point_id| {
+            let payload_checker = Box::new(move |point_id| {
                 payload_provider.with_payload(point_id, |payload| {
                     let field_values = payload.get_value(&nested_path);
 
@@ -119,7 +144,9 @@ pub fn condition_converter<'a>(
                     }
                     false
                 })
-            })
+            });
+
+            index_checkers.into_checker(payload_checker)
         }
         Condition::Resharding(cond) => {
             let segment_ids: HashSet<_> = id_tracker
@@ -134,6 +161,76 @@ pub fn condition_converter<'a>(
     }
 }
 
+/// Index checks of a nested filter, which respect that the whole filter must hold on the same
+/// array element
+///
+/// Indexes only know values of a point, not which element they came from. Some element matches
+/// a single field condition if and only if some value of the point does, so a filter of a single
+/// `must` condition, or of only `should` conditions, is resolved by indexes completely.
+/// For other filters, indexed `must` conditions can only rule out points, matching points are
+/// still checked element by element on the payload.
+enum NestedIndexCheckers<'a> {
+    /// Filter of a single `must` condition
+    Must(ConditionCheckerFn<'a>),
+    /// Filter of only `should` conditions
+    Should(Vec<ConditionCheckerFn<'a>>),
+    /// Checks of `must` conditions, which are required before the payload check
+    Necessary(Vec<ConditionCheckerFn<'a>>),
+}
+
+impl<'a> NestedIndexCheckers<'a> {
+    fn new(nested_path: &JsonPath, filter: &'a Filter, field_indexes: &'a IndexesMap) -> Self {
+        let index_checker = |condition: &'a Condition| -> Option<ConditionCheckerFn<'a>> {
+            let Condition::Field(field_condition) = condition else {
+                return None;
+            };
+            let full_path = nested_path.extend(&field_condition.key);
+            field_indexes
+                .get(&full_path)?
+                .iter()
+                .find_map(|index| field_condition_index(index, field_condition))
+        };
+
+        let must = filter.must.as_deref().unwrap_or_default();
+        let should = filter.should.as_deref().unwrap_or_default();
+        let has_must_not = filter
+            .must_not
+            .as_ref()
+            .is_some_and(|must_not| !must_not.is_empty());
+
+        if !has_must_not && filter.min_should.is_none() {
+            match (must, should) {
+                ([condition], []) => {
+                    if let Some(checker) = index_checker(condition) {
+                        return Self::Must(checker);
+                    }
+                }
+                ([], [_, ..]) => {
+                    if let Some(checkers) = should.iter().map(&index_checker).collect() {
+                        return Self::Should(checkers);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Self::Necessary(must.iter().filter_map(index_checker).collect())
+    }
+
+    fn into_checker(self, payload_checker: ConditionCheckerFn<'a>) -> ConditionCheckerFn<'a> {
+        match self {
+            Self::Must(checker) => checker,
+            Self::Should(checkers) => {
+                Box::new(move |point_id| checkers.iter().any(|checker| checker(point_id)))
+            }
+            Self::Necessary(checkers) if checkers.is_empty() => payload_checker,
+            Self::Necessary(checkers) => Box::new(move |point_id| {
+                checkers.iter().all(|checker| checker(point_id)) && payload_checker(point_id)
+            }),
+        }
+    }
+}
+
 pub fn field_condition_index<'a>(
     index: &'a FieldIndex,
     field_condition: &FieldCondition,
@@ -182,11 +279,7 @@ pub fn get_geo_polygon_checkers(
     let polygon_wrapper = geo_polygon.convert();
     match index {
         FieldIndex::GeoIndex(geo_index) => Some(Box::new(move |point_id: PointOffsetType| {
-            geo_index.get_values(point_id).map_or(false, |values| {
-                values
-                    .iter()
-                    .any(|geo_point| polygon_wrapper.check_point(geo_point))
-            })
+            geo_index.check_polygon(point_id, &polygon_wrapper)
         })),
         _ => None,
     }
@@ -198,11 +291,7 @@ pub fn get_geo_radius_checkers(
 ) -> Option<ConditionCheckerFn> {
     match index {
         FieldIndex::GeoIndex(geo_index) => Some(Box::new(move |point_id: PointOffsetType| {
-            geo_index.get_values(point_id).map_or(false, |values| {
-                values
-                    .iter()
-                    .any(|geo_point| geo_radius.check_point(geo_point))
-            })
+            geo_index.check_radius(point_id, &geo_radius)
         })),
         _ => None,
     }
@@ -214,12 +303,7 @@ pub fn get_geo_bounding_box_checkers(
 ) -> Option<ConditionCheckerFn> {
     match index {
         FieldIndex::GeoIndex(geo_index) => Some(Box::new(move |point_id: PointOffsetType| {
-            match geo_index.get_values(point_id) {
-                None => false,
-                Some(values) => values
-                    .iter()
-                    .any(|geo_point| geo_bounding_box.check_point(geo_point)),
-            }
+            geo_index.check_box(point_id, &geo_bounding_box)
         })),
         _ => None,
     }
@@ -229,6 +313,8 @@ pub fn get_range_checkers(index: &FieldIndex, range: RangeInterface) -> Option<C
     match range {
         RangeInterface::Float(range) => get_float_range_checkers(index, range),
         RangeInterface::DateTime(range) => get_datetime_range_checkers(index, range),
+        RangeInterface::Keyword(range) => get_keyword_range_checkers(index, range),
+        RangeInterface::BigInt(range) => get_big_int_range_checkers(index, range),
     }
 }
 
@@ -250,6 +336,43 @@ pub fn get_float_range_checkers(
                 .get_values(point_id)
                 .is_some_and(|values| values.iter().copied().any(|f| range.check_range(f)))
         })),
+        FieldIndex::BigIntIndex(num_index) => {
+            let range = range.map(|f| f as BigIntPayloadType);
+            Some(Box::new(move |point_id: PointOffsetType| {
+                num_index
+                    .get_values(point_id)
+                    .is_some_and(|values| values.iter().copied().any(|i| range.check_range(i)))
+            }))
+        }
+        _ => None,
+    }
+}
+
+pub fn get_big_int_range_checkers(
+    index: &FieldIndex,
+    range: Range<BigIntPayloadType>,
+) -> Option<ConditionCheckerFn> {
+    match index {
+        FieldIndex::IntIndex(num_index) => Some(Box::new(move |point_id: PointOffsetType| {
+            num_index.get_values(point_id).is_some_and(|values| {
+                values
+                    .iter()
+                    .any(|&i| range.check_range(BigIntPayloadType::from(i)))
+            })
+        })),
+        FieldIndex::FloatIndex(num_index) => {
+            let range = range.map(|i| i as FloatPayloadType);
+            Some(Box::new(move |point_id: PointOffsetType| {
+                num_index
+                    .get_values(point_id)
+                    .is_some_and(|values| values.iter().copied().any(|f| range.check_range(f)))
+            }))
+        }
+        FieldIndex::BigIntIndex(num_index) => Some(Box::new(move |point_id: PointOffsetType| {
+            num_index
+                .get_values(point_id)
+                .is_some_and(|values| values.iter().copied().any(|i| range.check_range(i)))
+        })),
         _ => None,
     }
 }
@@ -271,6 +394,22 @@ pub fn get_datetime_range_checkers(
     }
 }
 
+pub fn get_keyword_range_checkers(
+    index: &FieldIndex,
+    range: Range<String>,
+) -> Option<ConditionCheckerFn> {
+    match index {
+        FieldIndex::KeywordIndex(keyword_index) => {
+            Some(Box::new(move |point_id: PointOffsetType| {
+                keyword_index
+                    .get_values(point_id)
+                    .is_some_and(|values| values.iter().any(|k| range.check_keyword(k)))
+            }))
+        }
+        _ => None,
+    }
+}
+
 pub fn get_match_checkers(index: &FieldIndex, cond_match: Match) -> Option<ConditionCheckerFn> {
     match cond_match {
         Match::Value(MatchValue {
@@ -367,6 +506,15 @@ pub fn get_match_checkers(index: &FieldIndex, cond_match: Match) -> Option<Condi
                 index.values_count(point_id) > 0
             })),
         },
+        Match::Similar(similar) => match index {
+            FieldIndex::MinHashIndex(min_hash_index) => {
+                let query = SimilarQuery::new(&similar);
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    min_hash_index.check_similar(point_id, &query)
+                }))
+            }
+            _ => None,
+        },
     }
 }
 
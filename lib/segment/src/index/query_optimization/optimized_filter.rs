@@ -47,20 +47,14 @@ fn check_should(should: &Option<Vec<OptimizedCondition>>, point_id: PointOffsetT
 }
 
 fn check_min_should(min_should: &Option<OptimizedMinShould>, point_id: PointOffsetType) -> bool {
-    let check = |condition| check_condition(condition, point_id);
     match min_should {
         None => true,
         Some(OptimizedMinShould {
             conditions,
             min_count,
-        }) => {
-            conditions
-                .iter()
-                .filter(|cond| check(cond))
-                .take(*min_count)
-                .count()
-                == *min_count
-        }
+        }) => check_min_count(conditions, *min_count, |condition| {
+            check_condition(condition, point_id)
+        }),
     }
 }
 
@@ -79,3 +73,23 @@ fn check_must_not(must: &Option<Vec<OptimizedCondition>>, point_id: PointOffsetT
         Some(conditions) => conditions.iter().all(check),
     }
 }
+
+/// Check if at least `min_count` of `conditions` match.
+///
+/// Stops as soon as the result is known: either enough conditions matched, or there are too few
+/// conditions left to reach `min_count`.
+pub fn check_min_count<C>(conditions: &[C], min_count: usize, check: impl Fn(&C) -> bool) -> bool {
+    let mut matched = 0;
+    for (checked, condition) in conditions.iter().enumerate() {
+        if matched >= min_count {
+            return true;
+        }
+        if matched + (conditions.len() - checked) < min_count {
+            return false;
+        }
+        if check(condition) {
+            matched += 1;
+        }
+    }
+    matched >= min_count
+}
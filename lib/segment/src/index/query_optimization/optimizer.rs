@@ -1,8 +1,10 @@
 use std::cmp::Reverse;
 
+use atomic_refcell::AtomicRefCell;
 use itertools::Itertools;
 
-use crate::common::utils::IndexesMap;
+use crate::attribute_storage::AttributeStorage;
+use crate::common::utils::{IndexesMap, VectorStoragesMap};
 use crate::id_tracker::IdTrackerSS;
 use crate::index::field_index::CardinalityEstimation;
 use crate::index::query_estimator::{
@@ -30,6 +32,8 @@ use crate::types::{Condition, Filter, MinShould};
 ///
 /// * `filter` - original filter
 /// * `id_tracker` - used for converting collection-level ids into segment-level offsets of HasId condition
+/// * `vector_storages` - used for checking presence of vectors in HasVector condition
+/// * `attribute_storage` - used for checking values of attributes in Attribute condition
 /// * `estimator` - function to estimate cardinality of individual conditions
 /// * `total` - total number of points in segment (used for cardinality estimation)
 ///
@@ -39,6 +43,8 @@ use crate::types::{Condition, Filter, MinShould};
 pub fn optimize_filter<'a, F>(
     filter: &'a Filter,
     id_tracker: &IdTrackerSS,
+    vector_storages: &'a VectorStoragesMap,
+    attribute_storage: &'a AtomicRefCell<AttributeStorage>,
     field_indexes: &'a IndexesMap,
     payload_provider: PayloadProvider,
     estimator: &F,
@@ -55,6 +61,8 @@ where
                 let (optimized_conditions, estimation) = optimize_should(
                     conditions,
                     id_tracker,
+                    vector_storages,
+                    attribute_storage,
                     field_indexes,
                     payload_provider.clone(),
                     estimator,
@@ -76,6 +84,8 @@ where
                         conditions,
                         *min_count,
                         id_tracker,
+                        vector_storages,
+                        attribute_storage,
                         field_indexes,
                         payload_provider.clone(),
                         estimator,
@@ -96,6 +106,8 @@ where
                 let (optimized_conditions, estimation) = optimize_must(
                     conditions,
                     id_tracker,
+                    vector_storages,
+                    attribute_storage,
                     field_indexes,
                     payload_provider.clone(),
                     estimator,
@@ -112,6 +124,8 @@ where
                 let (optimized_conditions, estimation) = optimize_must_not(
                     conditions,
                     id_tracker,
+                    vector_storages,
+                    attribute_storage,
                     field_indexes,
                     payload_provider.clone(),
                     estimator,
@@ -134,6 +148,8 @@ where
 fn convert_conditions<'a, F>(
     conditions: &'a [Condition],
     id_tracker: &IdTrackerSS,
+    vector_storages: &'a VectorStoragesMap,
+    attribute_storage: &'a AtomicRefCell<AttributeStorage>,
     field_indexes: &'a IndexesMap,
     payload_provider: PayloadProvider,
     estimator: &F,
@@ -149,6 +165,8 @@ where
                 let (optimized_filter, estimation) = optimize_filter(
                     filter,
                     id_tracker,
+                    vector_storages,
+                    attribute_storage,
                     field_indexes,
                     payload_provider.clone(),
                     estimator,
@@ -163,6 +181,8 @@ where
                     field_indexes,
                     payload_provider.clone(),
                     id_tracker,
+                    vector_storages,
+                    attribute_storage,
                 );
                 (OptimizedCondition::Checker(condition_checker), estimation)
             }
@@ -173,6 +193,8 @@ where
 fn optimize_should<'a, F>(
     conditions: &'a [Condition],
     id_tracker: &IdTrackerSS,
+    vector_storages: &'a VectorStoragesMap,
+    attribute_storage: &'a AtomicRefCell<AttributeStorage>,
     field_indexes: &'a IndexesMap,
     payload_provider: PayloadProvider,
     estimator: &F,
@@ -184,6 +206,8 @@ where
     let mut converted = convert_conditions(
         conditions,
         id_tracker,
+        vector_storages,
+        attribute_storage,
         field_indexes,
         payload_provider,
         estimator,
@@ -200,6 +224,8 @@ fn optimize_min_should<'a, F>(
     conditions: &'a [Condition],
     min_count: usize,
     id_tracker: &IdTrackerSS,
+    vector_storages: &'a VectorStoragesMap,
+    attribute_storage: &'a AtomicRefCell<AttributeStorage>,
     field_indexes: &'a IndexesMap,
     payload_provider: PayloadProvider,
     estimator: &F,
@@ -211,6 +237,8 @@ where
     let mut converted = convert_conditions(
         conditions,
         id_tracker,
+        vector_storages,
+        attribute_storage,
         field_indexes,
         payload_provider,
         estimator,
@@ -234,6 +262,8 @@ where
 fn optimize_must<'a, F>(
     conditions: &'a [Condition],
     id_tracker: &IdTrackerSS,
+    vector_storages: &'a VectorStoragesMap,
+    attribute_storage: &'a AtomicRefCell<AttributeStorage>,
     field_indexes: &'a IndexesMap,
     payload_provider: PayloadProvider,
     estimator: &F,
@@ -245,6 +275,8 @@ where
     let mut converted = convert_conditions(
         conditions,
         id_tracker,
+        vector_storages,
+        attribute_storage,
         field_indexes,
         payload_provider,
         estimator,
@@ -260,6 +292,8 @@ where
 fn optimize_must_not<'a, F>(
     conditions: &'a [Condition],
     id_tracker: &IdTrackerSS,
+    vector_storages: &'a VectorStoragesMap,
+    attribute_storage: &'a AtomicRefCell<AttributeStorage>,
     field_indexes: &'a IndexesMap,
     payload_provider: PayloadProvider,
     estimator: &F,
@@ -271,6 +305,8 @@ where
     let mut converted = convert_conditions(
         conditions,
         id_tracker,
+        vector_storages,
+        attribute_storage,
         field_indexes,
         payload_provider,
         estimator,
@@ -2,8 +2,13 @@ use std::cmp::{max, min};
 
 use common::types::PointOffsetType;
 
+use crate::index::field_index::CardinalityEstimation;
+
 const MAX_ESTIMATED_POINTS: usize = 1000;
 
+/// Bounds of an estimation are wide, if they span more than this fraction of all points
+const WIDE_BOUNDS_FRACTION: f64 = 0.1;
+
 /// How many points do we need to check in order to estimate expected query cardinality.
 /// Based on <https://en.wikipedia.org/wiki/Binomial_proportion_confidence_interval>
 #[allow(dead_code)]
@@ -63,6 +68,60 @@ pub fn sample_check_cardinality(
     exp > threshold as i64
 }
 
+/// Whether bounds of the index-based estimation are too wide to choose a query plan with
+pub fn has_wide_bounds(estimation: &CardinalityEstimation, total_points: usize) -> bool {
+    let span = estimation.max.saturating_sub(estimation.min);
+    total_points > 0 && span as f64 > total_points as f64 * WIDE_BOUNDS_FRACTION
+}
+
+/// Narrow down wide bounds of the `estimation` by checking sampled points against the full filter.
+///
+/// Points are sampled until the confidence interval is 4 times narrower than the original
+/// bounds. Refined bounds never go beyond the original ones, and primary clauses are kept intact.
+pub fn sample_refine_cardinality(
+    estimation: CardinalityEstimation,
+    sample_points: impl Iterator<Item = PointOffsetType>,
+    checker: impl Fn(PointOffsetType) -> bool,
+    total_points: usize,
+) -> CardinalityEstimation {
+    if !has_wide_bounds(&estimation, total_points) {
+        return estimation;
+    }
+
+    let target_interval = (estimation.max - estimation.min) as i64 / 8;
+
+    let mut matched_points = 0;
+    let mut total_checked = 0;
+    let mut interval = None;
+    for idx in sample_points.take(MAX_ESTIMATED_POINTS) {
+        matched_points += checker(idx) as usize;
+        total_checked += 1;
+
+        let (exp, delta) =
+            confidence_agresti_coull_interval(total_checked, matched_points, total_points);
+        interval = Some((exp, delta));
+        if delta <= target_interval {
+            break;
+        }
+    }
+
+    let Some((exp, delta)) = interval else {
+        return estimation;
+    };
+
+    let min = (exp - delta).max(0) as usize;
+    let max = (exp + delta).max(0) as usize;
+
+    let min = min.clamp(estimation.min, estimation.max);
+    let max = max.clamp(min, estimation.max);
+    CardinalityEstimation {
+        primary_clauses: estimation.primary_clauses,
+        min,
+        exp: (exp.max(0) as usize).clamp(min, max),
+        max,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use rand::rngs::StdRng;
@@ -102,4 +161,33 @@ mod tests {
 
         assert!(res)
     }
+
+    #[test]
+    fn test_sample_refine_cardinality() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let total = 100_000;
+        let estimation = CardinalityEstimation {
+            primary_clauses: vec![],
+            min: 0,
+            exp: 50_000,
+            max: 80_000,
+        };
+
+        // Every 10th point matches
+        let sample = (0..).map(|_| rng.gen_range(0..total as PointOffsetType));
+        let refined = sample_refine_cardinality(estimation, sample, |idx| idx % 10 == 0, total);
+        assert!(refined.max - refined.min <= 20_000, "{refined:?}");
+        assert!(refined.max < 40_000, "{refined:?}");
+        assert!(refined.min <= refined.exp && refined.exp <= refined.max);
+
+        // Narrow bounds are not refined
+        let narrow = CardinalityEstimation {
+            primary_clauses: vec![],
+            min: 9_000,
+            exp: 9_500,
+            max: 10_000,
+        };
+        let refined = sample_refine_cardinality(narrow, std::iter::empty(), |_| true, total);
+        assert_eq!((refined.min, refined.max), (9_000, 10_000));
+    }
 }
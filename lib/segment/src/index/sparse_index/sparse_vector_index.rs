@@ -2,7 +2,7 @@ use std::borrow::Cow;
 use std::collections::HashMap;
 use std::fs::{create_dir_all, remove_dir_all, rename};
 use std::path::{Path, PathBuf};
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 use atomic_refcell::AtomicRefCell;
@@ -25,6 +25,7 @@ use crate::common::operation_error::{check_process_stopped, OperationError, Oper
 use crate::common::operation_time_statistics::ScopeDurationMeasurer;
 use crate::data_types::named_vectors::CowVector;
 use crate::data_types::query_context::VectorQueryContext;
+use crate::data_types::search_plan::{SearchStrategy, VectorSearchPlan};
 use crate::data_types::vectors::{QueryVector, Vector, VectorRef};
 use crate::id_tracker::IdTrackerSS;
 use crate::index::field_index::CardinalityEstimation;
@@ -280,7 +281,7 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
         let id_tracker = self.id_tracker.borrow();
         let payload_index = self.payload_index.borrow();
         let available_vector_count = vector_storage.available_vector_count();
-        let query_point_cardinality = payload_index.estimate_cardinality(filter);
+        let query_point_cardinality = payload_index.estimate_cardinality_sampled(filter);
         adjust_to_available_vectors(
             query_point_cardinality,
             available_vector_count,
@@ -311,6 +312,8 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
             deleted_point_bitslice,
             &is_stopped,
         )?;
+        let hardware_counter = vector_query_context.hardware_counter();
+        let vector_size = vector_storage.average_vector_size_in_bytes();
         match filter {
             Some(filter) => {
                 let payload_index = self.payload_index.borrow();
@@ -322,9 +325,14 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
                         prefiltered_points.as_ref().unwrap().iter().copied()
                     }
                 };
+                hardware_counter.incr_scored_vectors(filtered_points.len(), vector_size);
                 Ok(raw_scorer.peek_top_iter(&mut filtered_points, top))
             }
-            None => Ok(raw_scorer.peek_top_all(top)),
+            None => {
+                hardware_counter
+                    .incr_scored_vectors(vector_storage.available_vector_count(), vector_size);
+                Ok(raw_scorer.peek_top_all(top))
+            }
         }
     }
 
@@ -359,6 +367,10 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
         .filter(|&idx| check_deleted_condition(idx, deleted_vectors, deleted_point_bitslice))
         .collect_vec();
 
+        vector_query_context
+            .hardware_counter()
+            .incr_scored_vectors(ids.len(), vector_storage.average_vector_size_in_bytes());
+
         let sparse_vector = self.indices_tracker.remap_vector(sparse_vector.to_owned());
         let memory_handle = self.scores_memory_pool.get();
         let mut search_context = SearchContext::new(
@@ -437,7 +449,18 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
                     .config
                     .full_scan_threshold
                     .unwrap_or(DEFAULT_SPARSE_FULL_SCAN_THRESHOLD);
-                if query_cardinality.max < threshold {
+                let strategy = if query_cardinality.max < threshold {
+                    SearchStrategy::FilteredScan
+                } else {
+                    SearchStrategy::SparseIndex
+                };
+                if vector_query_context.is_explain_enabled() {
+                    vector_query_context.record_search_plan(
+                        VectorSearchPlan::new(strategy).with_estimation(&query_cardinality),
+                    );
+                }
+
+                if strategy == SearchStrategy::FilteredScan {
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.small_cardinality);
                     self.search_plain(
@@ -455,6 +478,8 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
             }
             None => {
                 let _timer = ScopeDurationMeasurer::new(&self.searches_telemetry.unfiltered_sparse);
+                vector_query_context
+                    .record_search_plan(VectorSearchPlan::new(SearchStrategy::SparseIndex));
                 self.search_sparse(&vector, filter, top, vector_query_context)
             }
         }
@@ -509,6 +534,18 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
             }
         }
     }
+
+    /// Add the number of vectors, which contain each of the dimensions of the index
+    pub fn fill_document_frequencies(&self, frequencies: &mut HashMap<DimId, usize>) {
+        for (dim_id, remapped_dim_id) in &self.indices_tracker.map {
+            match self.inverted_index.posting_list_len(remapped_dim_id) {
+                Some(0) | None => {}
+                Some(posting_list_len) => {
+                    *frequencies.entry(*dim_id).or_default() += posting_list_len
+                }
+            }
+        }
+    }
 }
 
 impl<TInvertedIndex: InvertedIndex> VectorIndex for SparseVectorIndex<TInvertedIndex> {
@@ -523,7 +560,11 @@ impl<TInvertedIndex: InvertedIndex> VectorIndex for SparseVectorIndex<TInvertedI
         let mut results = Vec::with_capacity(vectors.len());
         let mut prefiltered_points = None;
         for vector in vectors {
-            check_process_stopped(&query_context.is_stopped())?;
+            // Queries, which are not started before the search is stopped, get no results
+            if query_context.is_stopped().load(Ordering::Relaxed) {
+                results.push(Vec::new());
+                continue;
+            }
 
             let search_results = if query_context.is_require_idf() {
                 let vector = (*vector).clone().transform(|mut vector| {
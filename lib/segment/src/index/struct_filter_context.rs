@@ -1,6 +1,8 @@
+use atomic_refcell::AtomicRefCell;
 use common::types::PointOffsetType;
 
-use crate::common::utils::IndexesMap;
+use crate::attribute_storage::AttributeStorage;
+use crate::common::utils::{IndexesMap, VectorStoragesMap};
 use crate::id_tracker::IdTrackerSS;
 use crate::index::field_index::CardinalityEstimation;
 use crate::index::query_optimization::optimized_filter::{check_optimized_filter, OptimizedFilter};
@@ -17,6 +19,8 @@ impl<'a> StructFilterContext<'a> {
     pub fn new<F>(
         filter: &'a Filter,
         id_tracker: &IdTrackerSS,
+        vector_storages: &'a VectorStoragesMap,
+        attribute_storage: &'a AtomicRefCell<AttributeStorage>,
         payload_provider: PayloadProvider,
         field_indexes: &'a IndexesMap,
         estimator: &F,
@@ -28,6 +32,8 @@ impl<'a> StructFilterContext<'a> {
         let (optimized_filter, _) = optimize_filter(
             filter,
             id_tracker,
+            vector_storages,
+            attribute_storage,
             field_indexes,
             payload_provider,
             estimator,
@@ -1,28 +1,39 @@
 use std::collections::{HashMap, HashSet};
-use std::fs::create_dir_all;
+use std::fs::{create_dir_all, remove_dir_all};
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::{mpsc, Arc};
+use std::thread;
 
 use atomic_refcell::AtomicRefCell;
+use bitvec::vec::BitVec;
 use common::types::PointOffsetType;
 use log::debug;
+use memory::budget::{self, MemoryCategory};
+use memory::mmap_ops;
 use parking_lot::RwLock;
 use rocksdb::DB;
 use schemars::_serde_json::Value;
 
-use crate::common::operation_error::{OperationError, OperationResult};
+use crate::attribute_storage::AttributeStorage;
+use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
 use crate::common::rocksdb_wrapper::open_db_with_existing_cf;
-use crate::common::utils::IndexesMap;
+use crate::common::utils::{IndexesMap, VectorStoragesMap};
 use crate::common::Flusher;
 use crate::id_tracker::IdTrackerSS;
 use crate::index::field_index::index_selector::index_selector;
 use crate::index::field_index::{
     CardinalityEstimation, FieldIndex, PayloadBlockCondition, PrimaryCondition,
 };
+use crate::index::filter_cache::{
+    CacheLookup, CachedFilterContext, CanonicalFilter, FilterCache, FILTER_CACHE_CAPACITY,
+};
+use crate::index::payload_columns::{PayloadColumn, PayloadColumns};
 use crate::index::payload_config::PayloadConfig;
 use crate::index::query_estimator::estimate_filter;
 use crate::index::query_optimization::payload_provider::PayloadProvider;
+use crate::index::sample_estimation::{has_wide_bounds, sample_refine_cardinality};
 use crate::index::struct_filter_context::StructFilterContext;
 use crate::index::visited_pool::VisitedPool;
 use crate::index::PayloadIndex;
@@ -35,9 +46,13 @@ use crate::types::{
     IsEmptyCondition, IsNullCondition, Payload, PayloadContainer, PayloadField, PayloadFieldSchema,
     PayloadKeyType, PayloadKeyTypeRef, PayloadSchemaType,
 };
+use crate::vector_storage::VectorStorage;
 
 pub const PAYLOAD_FIELD_INDEX_PATH: &str = "fields";
 
+/// Max number of payloads, queued for each field index under construction
+const BUILD_QUEUE_SIZE: usize = 1024;
+
 /// `PayloadIndex` implementation, which actually uses index structures for providing faster search
 pub struct StructPayloadIndex {
     /// Payload storage
@@ -52,6 +67,14 @@ pub struct StructPayloadIndex {
     /// Used to select unique point ids
     visited_pool: VisitedPool,
     db: Arc<RwLock<DB>>,
+    /// Points, matching repeated filters. Only used in immutable segments
+    filter_cache: Option<FilterCache>,
+    /// Values of selected fields, stored per field for fast scans
+    payload_columns: PayloadColumns,
+    /// Used for `has_vector` condition
+    vector_storages: VectorStoragesMap,
+    /// Used for `attribute` condition
+    attribute_storage: Arc<AtomicRefCell<AttributeStorage>>,
 }
 
 impl StructPayloadIndex {
@@ -93,6 +116,54 @@ impl StructPayloadIndex {
         PayloadConfig::get_config_path(&self.path)
     }
 
+    /// Directory for the files of indexes of the field, which are not stored in the db
+    fn field_index_path(&self, field: PayloadKeyTypeRef) -> PathBuf {
+        let field = field.to_string();
+        let sanitized: String = field
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        // Hash keeps names of different fields distinct after sanitizing
+        let hash = seahash::hash(field.as_bytes());
+        self.path
+            .join(PAYLOAD_FIELD_INDEX_PATH)
+            .join(format!("{sanitized}-{hash:016x}"))
+    }
+
+    /// Tasks to populate the page cache with memory mapped field indexes
+    pub fn prefault_mmap_pages(&self) -> Vec<mmap_ops::PrefaultMmapPages> {
+        self.field_indexes
+            .values()
+            .flatten()
+            .filter_map(FieldIndex::prefault_mmap_pages)
+            .collect()
+    }
+
+    /// Drop memory mapped field indexes from the page cache
+    pub fn clear_cache(&self) -> OperationResult<()> {
+        for index in self.field_indexes.values().flatten() {
+            index.clear_cache()?;
+        }
+        Ok(())
+    }
+
+    /// Files of field indexes, which are not stored in the db
+    ///
+    /// These files are not included into snapshots, as they are rebuilt from the db records.
+    pub fn field_index_files(&self) -> OperationResult<Vec<PathBuf>> {
+        let mut files = Vec::new();
+        for field in self.config.indexed_fields.keys() {
+            let field_index_path = self.field_index_path(field);
+            if !field_index_path.is_dir() {
+                continue;
+            }
+            for entry in std::fs::read_dir(&field_index_path)? {
+                files.push(entry?.path());
+            }
+        }
+        Ok(files)
+    }
+
     fn save_config(&self) -> OperationResult<()> {
         let config_path = self.config_path();
         self.config.save(&config_path)
@@ -115,7 +186,13 @@ impl StructPayloadIndex {
         payload_schema: PayloadFieldSchema,
         is_appendable: bool,
     ) -> OperationResult<Vec<FieldIndex>> {
-        let mut indexes = index_selector(field, &payload_schema, self.db.clone(), is_appendable);
+        let mut indexes = index_selector(
+            field,
+            &payload_schema,
+            self.db.clone(),
+            is_appendable,
+            &self.field_index_path(field),
+        );
 
         let mut is_loaded = true;
         for ref mut index in indexes.iter_mut() {
@@ -158,6 +235,10 @@ impl StructPayloadIndex {
             path: path.to_owned(),
             visited_pool: Default::default(),
             db,
+            filter_cache: (!is_appendable).then(|| FilterCache::new(FILTER_CACHE_CAPACITY)),
+            payload_columns: Default::default(),
+            vector_storages: Default::default(),
+            attribute_storage: Default::default(),
         };
 
         if !index.config_path().exists() {
@@ -170,16 +251,37 @@ impl StructPayloadIndex {
         Ok(index)
     }
 
-    pub fn build_field_indexes(
+    /// Empty indexes of the field, ready to be filled with points
+    fn new_field_indexes(
         &self,
         field: PayloadKeyTypeRef,
-        payload_schema: PayloadFieldSchema,
+        payload_schema: &PayloadFieldSchema,
     ) -> OperationResult<Vec<FieldIndex>> {
-        let payload_storage = self.payload.borrow();
-        let mut field_indexes = index_selector(field, &payload_schema, self.db.clone(), true);
+        let field_index_path = self.field_index_path(field);
+        // Files of the previous index are built from its db records, which are recreated here
+        if field_index_path.exists() {
+            remove_dir_all(&field_index_path)?;
+        }
+        let field_indexes = index_selector(
+            field,
+            payload_schema,
+            self.db.clone(),
+            true,
+            &field_index_path,
+        );
         for index in &field_indexes {
             index.recreate()?;
         }
+        Ok(field_indexes)
+    }
+
+    pub fn build_field_indexes(
+        &self,
+        field: PayloadKeyTypeRef,
+        payload_schema: PayloadFieldSchema,
+    ) -> OperationResult<Vec<FieldIndex>> {
+        let payload_storage = self.payload.borrow();
+        let mut field_indexes = self.new_field_indexes(field, &payload_schema)?;
 
         payload_storage.iter(|point_id, point_payload| {
             let field_value = &point_payload.get_value(field);
@@ -191,6 +293,114 @@ impl StructPayloadIndex {
         Ok(field_indexes)
     }
 
+    /// Build indexes of `fields` in a single pass over payload storage
+    ///
+    /// Each payload is read once and handed to a worker per field, which fills the indexes of
+    /// its field. Built indexes of a non-appendable segment are persisted and loaded back in
+    /// their compact form, the same way as on segment load.
+    pub fn build_indexes(
+        &mut self,
+        fields: impl IntoIterator<Item = (PayloadKeyType, PayloadFieldSchema)>,
+        is_appendable: bool,
+        stopped: &AtomicBool,
+    ) -> OperationResult<()> {
+        check_process_stopped(stopped)?;
+        let fields: Vec<_> = fields.into_iter().collect();
+        let mut new_indexes = Vec::with_capacity(fields.len());
+        for (field, payload_schema) in &fields {
+            new_indexes.push(self.new_field_indexes(field, payload_schema)?);
+        }
+
+        let built_indexes = thread::scope(|scope| {
+            let mut workers = Vec::with_capacity(fields.len());
+            let mut senders = Vec::with_capacity(fields.len());
+            for ((field, _), mut field_indexes) in fields.iter().zip(new_indexes) {
+                let (sender, receiver) =
+                    mpsc::sync_channel::<(PointOffsetType, Arc<Payload>)>(BUILD_QUEUE_SIZE);
+                senders.push(sender);
+                workers.push(scope.spawn(move || -> OperationResult<Vec<FieldIndex>> {
+                    for (point_id, point_payload) in receiver {
+                        let field_value = &point_payload.get_value(field);
+                        for field_index in field_indexes.iter_mut() {
+                            field_index.add_point(point_id, field_value)?;
+                        }
+                    }
+                    Ok(field_indexes)
+                }));
+            }
+
+            let read_result = self.payload.borrow().iter(|point_id, point_payload| {
+                check_process_stopped(stopped)?;
+                let point_payload = Arc::new(point_payload.clone());
+                // Worker stops receiving only on error, which is returned once it is joined
+                senders.retain(|sender| sender.send((point_id, point_payload.clone())).is_ok());
+                Ok(!senders.is_empty())
+            });
+            drop(senders);
+
+            let built_indexes: Vec<_> = workers
+                .into_iter()
+                .map(|worker| {
+                    worker.join().unwrap_or_else(|_| {
+                        Err(OperationError::service_error(
+                            "Payload index building thread panicked",
+                        ))
+                    })
+                })
+                .collect();
+            read_result.map(|()| built_indexes)
+        })?;
+
+        for ((field, payload_schema), field_indexes) in fields.into_iter().zip(built_indexes) {
+            let mut field_indexes = field_indexes?;
+            if !is_appendable {
+                for index in &field_indexes {
+                    index.flusher()()?;
+                }
+                drop(field_indexes);
+                field_indexes = self.load_from_db(&field, payload_schema.clone(), is_appendable)?;
+            }
+            self.field_indexes.insert(field.clone(), field_indexes);
+            self.config.indexed_fields.insert(field, payload_schema);
+        }
+        self.save_config()?;
+        self.clear_filter_cache();
+        Ok(())
+    }
+
+    /// Keep values of `fields` in columns, replacing previously selected columns
+    pub fn set_payload_columns(&mut self, fields: &[PayloadKeyType]) -> OperationResult<()> {
+        self.payload_columns = PayloadColumns::build(fields, &self.payload.borrow())?;
+        Ok(())
+    }
+
+    pub fn set_vector_storages(&mut self, vector_storages: VectorStoragesMap) {
+        self.vector_storages = vector_storages;
+        self.clear_filter_cache();
+    }
+
+    pub fn set_attribute_storage(
+        &mut self,
+        attribute_storage: Arc<AtomicRefCell<AttributeStorage>>,
+    ) {
+        self.attribute_storage = attribute_storage;
+        self.clear_filter_cache();
+    }
+
+    pub fn payload_column(&self, field: PayloadKeyTypeRef) -> Option<&PayloadColumn> {
+        self.payload_columns.get(field)
+    }
+
+    /// Sync columns with the current payload of the point
+    fn update_payload_columns(&mut self, point_id: PointOffsetType) -> OperationResult<()> {
+        if self.payload_columns.is_empty() {
+            return Ok(());
+        }
+        let updated_payload = self.payload(point_id)?;
+        self.payload_columns.update(point_id, &updated_payload);
+        Ok(())
+    }
+
     fn build_and_save(
         &mut self,
         field: PayloadKeyTypeRef,
@@ -201,6 +411,85 @@ impl StructPayloadIndex {
         Ok(())
     }
 
+    /// Points, matching the filter, if it is cached or requested repeatedly
+    fn cached_filter_points(&self, filter: &Filter) -> Option<Arc<BitVec>> {
+        let filter_cache = self.filter_cache.as_ref()?;
+        let canonical_filter = CanonicalFilter::new(filter)?;
+        match filter_cache.lookup(&canonical_filter) {
+            CacheLookup::Hit(points) => Some(points),
+            CacheLookup::Admit => {
+                let total_points = self.id_tracker.borrow().total_point_count();
+                // Don't cache, if it doesn't fit into the memory budget
+                let memory_reservation = budget::global().try_reserve(
+                    MemoryCategory::Cache,
+                    total_points.div_ceil(u8::BITS as usize),
+                )?;
+                let mut points = BitVec::repeat(false, total_points);
+                for point_id in self.query_points_uncached(filter) {
+                    points.set(point_id as usize, true);
+                }
+                let points = Arc::new(points);
+                filter_cache.insert(canonical_filter, points.clone(), memory_reservation);
+                Some(points)
+            }
+            CacheLookup::Miss => None,
+        }
+    }
+
+    /// Drop cached filter results, e.g. after vectors of points were deleted
+    pub fn clear_filter_cache(&self) {
+        if let Some(filter_cache) = &self.filter_cache {
+            filter_cache.clear();
+        }
+    }
+
+    fn query_points_uncached(&self, query: &Filter) -> Vec<PointOffsetType> {
+        // Assume query is already estimated to be small enough so we can iterate over all matched ids
+
+        let query_cardinality = self.estimate_cardinality(query);
+
+        if query_cardinality.primary_clauses.is_empty() {
+            let id_tracker = self.id_tracker.borrow();
+            let full_scan_iterator = id_tracker.iter_ids();
+
+            let struct_filtered_context = self.struct_filtered_context(query);
+            // Worst case: query expected to return few matches, but index can't be used
+            let matched_points =
+                full_scan_iterator.filter(move |i| struct_filtered_context.check(*i));
+
+            matched_points.collect()
+        } else {
+            let points_iterator_ref = self.id_tracker.borrow();
+            let struct_filtered_context = self.struct_filtered_context(query);
+
+            // CPU-optimized strategy here: points are made unique before applying other filters.
+            // TODO: Implement iterator which holds the `visited_pool` and borrowed `vector_storage_ref` to prevent `preselected` array creation
+            let mut visited_list = self
+                .visited_pool
+                .get(points_iterator_ref.total_point_count());
+
+            let preselected: Vec<PointOffsetType> = query_cardinality
+                .primary_clauses
+                .iter()
+                .flat_map(|clause| {
+                    match clause {
+                        PrimaryCondition::Condition(field_condition) => {
+                            self.query_field(field_condition).unwrap_or_else(
+                                || points_iterator_ref.iter_ids(), /* index is not built */
+                            )
+                        }
+                        PrimaryCondition::Ids(ids) => Box::new(ids.iter().copied()),
+                        PrimaryCondition::IsEmpty(_) => points_iterator_ref.iter_ids(), /* there are no fast index for IsEmpty */
+                        PrimaryCondition::IsNull(_) => points_iterator_ref.iter_ids(),  /* no fast index for IsNull too */
+                    }
+                })
+                .filter(|&id| !visited_list.check_and_update_visited(id))
+                .filter(move |&i| struct_filtered_context.check(i))
+                .collect();
+            preselected
+        }
+    }
+
     /// Number of available points
     ///
     /// - excludes soft deleted points
@@ -215,6 +504,8 @@ impl StructPayloadIndex {
         StructFilterContext::new(
             filter,
             id_tracker.deref(),
+            &self.vector_storages,
+            &self.attribute_storage,
             payload_provider,
             &self.field_indexes,
             &estimator,
@@ -306,6 +597,41 @@ impl StructPayloadIndex {
                 }
             }
 
+            Condition::HasVector(has_vector) => {
+                let available_vectors = self
+                    .vector_storages
+                    .get(&has_vector.has_vector)
+                    .map_or(0, |vector_storage| {
+                        vector_storage.borrow().available_vector_count()
+                    });
+                CardinalityEstimation {
+                    primary_clauses: vec![],
+                    min: available_vectors,
+                    exp: available_vectors,
+                    max: available_vectors,
+                }
+            }
+
+            // Columns are dense, so matching values are counted exactly
+            Condition::Attribute(attribute) => {
+                let matching = self
+                    .attribute_storage
+                    .borrow()
+                    .column(&attribute.attribute)
+                    .map_or(0, |column| {
+                        column
+                            .iter()
+                            .filter(|(_, value)| attribute.check(Some(*value)))
+                            .count()
+                    });
+                CardinalityEstimation {
+                    primary_clauses: vec![],
+                    min: matching,
+                    exp: matching,
+                    max: matching,
+                }
+            }
+
             Condition::Field(field_condition) => self
                 .estimate_field_condition(field_condition, nested_path)
                 .unwrap_or_else(|| CardinalityEstimation::unknown(self.available_point_count())),
@@ -359,6 +685,7 @@ impl PayloadIndex for StructPayloadIndex {
         }
         self.build_and_save(field, payload_schema)?;
         self.save_config()?;
+        self.clear_filter_cache();
 
         Ok(())
     }
@@ -383,6 +710,24 @@ impl PayloadIndex for StructPayloadIndex {
         estimate_filter(&estimator, query, available_points)
     }
 
+    fn estimate_cardinality_sampled(&self, query: &Filter) -> CardinalityEstimation {
+        let estimation = self.estimate_cardinality(query);
+        let available_points = self.available_point_count();
+        if !has_wide_bounds(&estimation, available_points) {
+            return estimation;
+        }
+
+        // Bypass filter cache, sampling is not a repeated request of the filter
+        let filter_context = self.struct_filtered_context(query);
+        let id_tracker = self.id_tracker.borrow();
+        sample_refine_cardinality(
+            estimation,
+            id_tracker.sample_ids(None),
+            |idx| filter_context.check(idx),
+            available_points,
+        )
+    }
+
     fn estimate_nested_cardinality(
         &self,
         query: &Filter,
@@ -395,49 +740,16 @@ impl PayloadIndex for StructPayloadIndex {
     }
 
     fn query_points(&self, query: &Filter) -> Vec<PointOffsetType> {
-        // Assume query is already estimated to be small enough so we can iterate over all matched ids
-
-        let query_cardinality = self.estimate_cardinality(query);
-
-        if query_cardinality.primary_clauses.is_empty() {
-            let id_tracker = self.id_tracker.borrow();
-            let full_scan_iterator = id_tracker.iter_ids();
-
-            let struct_filtered_context = self.struct_filtered_context(query);
-            // Worst case: query expected to return few matches, but index can't be used
-            let matched_points =
-                full_scan_iterator.filter(move |i| struct_filtered_context.check(*i));
-
-            matched_points.collect()
-        } else {
-            let points_iterator_ref = self.id_tracker.borrow();
-            let struct_filtered_context = self.struct_filtered_context(query);
-
-            // CPU-optimized strategy here: points are made unique before applying other filters.
-            // TODO: Implement iterator which holds the `visited_pool` and borrowed `vector_storage_ref` to prevent `preselected` array creation
-            let mut visited_list = self
-                .visited_pool
-                .get(points_iterator_ref.total_point_count());
-
-            let preselected: Vec<PointOffsetType> = query_cardinality
-                .primary_clauses
-                .iter()
-                .flat_map(|clause| {
-                    match clause {
-                        PrimaryCondition::Condition(field_condition) => {
-                            self.query_field(field_condition).unwrap_or_else(
-                                || points_iterator_ref.iter_ids(), /* index is not built */
-                            )
-                        }
-                        PrimaryCondition::Ids(ids) => Box::new(ids.iter().copied()),
-                        PrimaryCondition::IsEmpty(_) => points_iterator_ref.iter_ids(), /* there are no fast index for IsEmpty */
-                        PrimaryCondition::IsNull(_) => points_iterator_ref.iter_ids(),  /* no fast index for IsNull too */
-                    }
-                })
-                .filter(|&id| !visited_list.check_and_update_visited(id))
-                .filter(move |&i| struct_filtered_context.check(i))
-                .collect();
-            preselected
+        match self.cached_filter_points(query) {
+            Some(points) => {
+                let id_tracker = self.id_tracker.borrow();
+                points
+                    .iter_ones()
+                    .map(|point_id| point_id as PointOffsetType)
+                    .filter(|&point_id| !id_tracker.is_deleted_point(point_id))
+                    .collect()
+            }
+            None => self.query_points_uncached(query),
         }
     }
 
@@ -455,7 +767,10 @@ impl PayloadIndex for StructPayloadIndex {
     }
 
     fn filter_context<'a>(&'a self, filter: &'a Filter) -> Box<dyn FilterContext + 'a> {
-        Box::new(self.struct_filtered_context(filter))
+        match self.cached_filter_points(filter) {
+            Some(points) => Box::new(CachedFilterContext::new(points)),
+            None => Box::new(self.struct_filtered_context(filter)),
+        }
     }
 
     fn payload_blocks(
@@ -480,6 +795,7 @@ impl PayloadIndex for StructPayloadIndex {
         payload: &Payload,
         key: &Option<JsonPath>,
     ) -> OperationResult<()> {
+        self.clear_filter_cache();
         if let Some(key) = key {
             self.payload
                 .borrow_mut()
@@ -487,6 +803,7 @@ impl PayloadIndex for StructPayloadIndex {
         } else {
             self.payload.borrow_mut().assign(point_id, payload)?;
         };
+        self.update_payload_columns(point_id)?;
 
         let updated_payload = self.payload(point_id)?;
         for (field, field_index) in &mut self.field_indexes {
@@ -507,29 +824,63 @@ impl PayloadIndex for StructPayloadIndex {
         Ok(())
     }
 
+    fn patch(&mut self, point_id: PointOffsetType, patch: &Payload) -> OperationResult<()> {
+        self.clear_filter_cache();
+        self.payload.borrow_mut().patch(point_id, patch)?;
+        self.update_payload_columns(point_id)?;
+
+        // Patch may only change the subtrees of its top-level keys
+        let updated_payload = self.payload(point_id)?;
+        for (field, field_index) in &mut self.field_indexes {
+            if !field.is_affected_by_value_set(&patch.0, None) {
+                continue;
+            }
+            let field_value = updated_payload.get_value(field);
+            if !field_value.is_empty() {
+                for index in field_index {
+                    index.add_point(point_id, &field_value)?;
+                }
+            } else {
+                for index in field_index {
+                    index.remove_point(point_id)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn payload(&self, point_id: PointOffsetType) -> OperationResult<Payload> {
         self.payload.borrow().payload(point_id)
     }
 
+    fn payload_fields(&self, point_id: PointOffsetType, keys: &[&str]) -> OperationResult<Payload> {
+        self.payload.borrow().payload_fields(point_id, keys)
+    }
+
     fn delete(
         &mut self,
         point_id: PointOffsetType,
         key: PayloadKeyTypeRef,
     ) -> OperationResult<Vec<Value>> {
+        self.clear_filter_cache();
         if let Some(indexes) = self.field_indexes.get_mut(key) {
             for index in indexes {
                 index.remove_point(point_id)?;
             }
         }
-        self.payload.borrow_mut().delete(point_id, key)
+        let deleted = self.payload.borrow_mut().delete(point_id, key)?;
+        self.update_payload_columns(point_id)?;
+        Ok(deleted)
     }
 
     fn drop(&mut self, point_id: PointOffsetType) -> OperationResult<Option<Payload>> {
+        self.clear_filter_cache();
         for (_, field_indexes) in self.field_indexes.iter_mut() {
             for index in field_indexes {
                 index.remove_point(point_id)?;
             }
         }
+        self.payload_columns.remove(point_id);
         self.payload.borrow_mut().drop(point_id)
     }
 
@@ -571,6 +922,8 @@ impl PayloadIndex for StructPayloadIndex {
     }
 
     fn files(&self) -> Vec<PathBuf> {
-        vec![self.config_path()]
+        let mut files = vec![self.config_path()];
+        files.extend(self.payload.borrow().files());
+        files
     }
 }
@@ -18,6 +18,7 @@ use super::hnsw_index::hnsw::HNSWIndex;
 use super::plain_payload_index::PlainIndex;
 use super::sparse_index::sparse_vector_index::SparseVectorIndex;
 use crate::common::operation_error::OperationResult;
+use crate::common::Flusher;
 use crate::data_types::query_context::VectorQueryContext;
 use crate::data_types::vectors::{QueryVector, VectorRef};
 use crate::telemetry::VectorIndexSearchesTelemetry;
@@ -112,6 +113,77 @@ impl VectorIndexEnum {
             Self::SparseCompressedMmapF16(index) => index.fill_idf_statistics(idf),
         }
     }
+
+    /// Document frequencies of all dimensions of a sparse index, does nothing for dense indexes
+    pub fn fill_document_frequencies(&self, frequencies: &mut HashMap<DimId, usize>) {
+        match self {
+            Self::Plain(_) | Self::HnswRam(_) | Self::HnswMmap(_) => (),
+            Self::SparseRam(index) => index.fill_document_frequencies(frequencies),
+            Self::SparseImmutableRam(index) => index.fill_document_frequencies(frequencies),
+            Self::SparseMmap(index) => index.fill_document_frequencies(frequencies),
+            Self::SparseCompressedImmutableRamF32(index) => {
+                index.fill_document_frequencies(frequencies)
+            }
+            Self::SparseCompressedImmutableRamF16(index) => {
+                index.fill_document_frequencies(frequencies)
+            }
+            Self::SparseCompressedMmapF32(index) => index.fill_document_frequencies(frequencies),
+            Self::SparseCompressedMmapF16(index) => index.fill_document_frequencies(frequencies),
+        }
+    }
+
+    /// Whether the index is read from memory mapped files, instead of being loaded into RAM
+    pub fn is_on_disk(&self) -> bool {
+        match self {
+            Self::Plain(_) => false,
+            Self::HnswRam(_) => false,
+            Self::HnswMmap(_) => true,
+            Self::SparseRam(_) => false,
+            Self::SparseImmutableRam(_) => false,
+            Self::SparseMmap(_) => true,
+            Self::SparseCompressedImmutableRamF32(_) => false,
+            Self::SparseCompressedImmutableRamF16(_) => false,
+            Self::SparseCompressedMmapF32(_) => true,
+            Self::SparseCompressedMmapF16(_) => true,
+        }
+    }
+
+    /// Whether one more vector can be updated in place, without rebuilding the index
+    ///
+    /// Only limits the number of stale points of HNSW indexes, the vector storage and the
+    /// segment config must allow updates as well.
+    pub fn can_update_in_place(&self) -> bool {
+        match self {
+            Self::HnswRam(index) => index.can_update_in_place(),
+            Self::HnswMmap(index) => index.can_update_in_place(),
+            Self::Plain(_)
+            | Self::SparseRam(_)
+            | Self::SparseImmutableRam(_)
+            | Self::SparseMmap(_)
+            | Self::SparseCompressedImmutableRamF32(_)
+            | Self::SparseCompressedImmutableRamF16(_)
+            | Self::SparseCompressedMmapF32(_)
+            | Self::SparseCompressedMmapF16(_) => true,
+        }
+    }
+
+    /// Persist changes of the index, made since the last flush
+    ///
+    /// Only HNSW indexes keep such changes, as other indexes are persisted by their storages.
+    pub fn flusher(&self) -> Flusher {
+        match self {
+            Self::HnswRam(index) => index.stale_points_flusher(),
+            Self::HnswMmap(index) => index.stale_points_flusher(),
+            Self::Plain(_)
+            | Self::SparseRam(_)
+            | Self::SparseImmutableRam(_)
+            | Self::SparseMmap(_)
+            | Self::SparseCompressedImmutableRamF32(_)
+            | Self::SparseCompressedImmutableRamF16(_)
+            | Self::SparseCompressedMmapF32(_)
+            | Self::SparseCompressedMmapF16(_) => Box::new(|| Ok(())),
+        }
+    }
 }
 
 impl VectorIndex for VectorIndexEnum {
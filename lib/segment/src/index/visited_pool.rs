@@ -2,6 +2,7 @@
 
 use common::defaults::POOL_KEEP_LIMIT;
 use common::types::PointOffsetType;
+use memory::budget::{self, MemoryCategory, MemoryReservation};
 use parking_lot::RwLock;
 
 /// Visited list handle is an owner of the `VisitedList`, which is returned by `VisitedPool` and returned back to it
@@ -19,6 +20,8 @@ pub struct VisitedListHandle<'a> {
 struct VisitedList {
     current_iter: usize,
     visit_counters: Vec<usize>,
+    /// Search scratch space, accounted in the global memory budget
+    memory_reservation: Option<MemoryReservation>,
 }
 
 impl Default for VisitedList {
@@ -26,17 +29,33 @@ impl Default for VisitedList {
         VisitedList {
             current_iter: 1,
             visit_counters: vec![],
+            memory_reservation: None,
         }
     }
 }
 
 impl VisitedList {
     fn new(num_points: usize) -> Self {
-        VisitedList {
+        let mut visited_list = VisitedList {
             current_iter: 1,
             visit_counters: vec![0; num_points],
+            memory_reservation: None,
+        };
+        visited_list.account_memory();
+        visited_list
+    }
+
+    fn resize(&mut self, num_points: usize) {
+        if self.visit_counters.len() != num_points {
+            self.visit_counters.resize(num_points, 0);
+            self.account_memory();
         }
     }
+
+    fn account_memory(&mut self) {
+        let bytes = self.visit_counters.len() * std::mem::size_of::<usize>();
+        self.memory_reservation = Some(budget::global().reserve(MemoryCategory::Scratch, bytes));
+    }
 }
 
 impl<'a> Drop for VisitedListHandle<'a> {
@@ -113,7 +132,7 @@ impl VisitedPool {
         match self.pool.write().pop() {
             None => VisitedListHandle::new(self, VisitedList::new(num_points)),
             Some(mut data) => {
-                data.visit_counters.resize(num_points, 0);
+                data.resize(num_points);
                 let mut visited_list = VisitedListHandle::new(self, data);
                 visited_list.next_iteration();
                 visited_list
@@ -1,3 +1,4 @@
+pub mod attribute_storage;
 pub mod common;
 pub mod entry;
 #[cfg(feature = "testing")]
@@ -9,6 +10,7 @@ pub mod problems;
 pub mod rocksdb_backup;
 pub mod segment;
 pub mod segment_constructor;
+pub mod segment_manifest;
 pub mod spaces;
 pub mod telemetry;
 
@@ -4,10 +4,11 @@ use std::str::FromStr;
 
 use serde_json::Value;
 
+use crate::index::field_index::min_hash_index::{shingles_similarity, text_shingles};
 use crate::types::{
-    AnyVariants, DateTimePayloadType, FieldCondition, FloatPayloadType, GeoBoundingBox, GeoPoint,
-    GeoPolygon, GeoRadius, Match, MatchAny, MatchExcept, MatchText, MatchValue, Range,
-    RangeInterface, ValueVariants, ValuesCount,
+    AnyVariants, BigIntPayloadType, DateTimePayloadType, FieldCondition, FloatPayloadType,
+    GeoBoundingBox, GeoPoint, GeoPolygon, GeoRadius, Match, MatchAny, MatchExcept, MatchText,
+    MatchValue, Range, RangeInterface, ValueVariants, ValuesCount,
 };
 
 /// Threshold representing the point to which iterating through an IndexSet is more efficient than using hashing.
@@ -52,6 +53,8 @@ impl ValueChecker for FieldCondition {
                 .is_some_and(|range_interface| match range_interface {
                     RangeInterface::Float(condition) => condition.check_match(payload),
                     RangeInterface::DateTime(condition) => condition.check_match(payload),
+                    RangeInterface::Keyword(condition) => condition.check_match(payload),
+                    RangeInterface::BigInt(condition) => condition.check_match(payload),
                 })
             || geo_radius
                 .as_ref()
@@ -136,6 +139,13 @@ impl ValueChecker for Match {
                 (Value::Number(_), _) => true,
                 (Value::String(_), _) => true,
             },
+            Match::Similar(similar) => match payload {
+                Value::String(stored) => {
+                    let query = text_shingles(&similar.similar);
+                    shingles_similarity(&query, &text_shingles(stored)) >= similar.min_similarity()
+                }
+                _ => false,
+            },
         }
     }
 }
@@ -161,54 +171,68 @@ impl ValueChecker for Range<DateTimePayloadType> {
     }
 }
 
-impl ValueChecker for GeoBoundingBox {
+impl ValueChecker for Range<BigIntPayloadType> {
     fn check_match(&self, payload: &Value) -> bool {
-        match payload {
-            Value::Object(obj) => {
-                let lon_op = obj.get("lon").and_then(|x| x.as_f64());
-                let lat_op = obj.get("lat").and_then(|x| x.as_f64());
-
-                if let (Some(lon), Some(lat)) = (lon_op, lat_op) {
-                    return self.check_point(&GeoPoint { lon, lat });
-                }
-                false
-            }
-            _ => false,
+        let Value::Number(num) = payload else {
+            return false;
+        };
+        match num
+            .as_i64()
+            .map(BigIntPayloadType::from)
+            .or_else(|| num.as_u64().map(BigIntPayloadType::from))
+        {
+            Some(integer) => self.check_range(integer),
+            // Fractional numbers can't be compared exactly
+            None => num
+                .as_f64()
+                .is_some_and(|number| self.map(|x| x as f64).check_range(number)),
         }
     }
 }
 
-impl ValueChecker for GeoRadius {
+impl ValueChecker for Range<String> {
     fn check_match(&self, payload: &Value) -> bool {
-        match payload {
-            Value::Object(obj) => {
-                let lon_op = obj.get("lon").and_then(|x| x.as_f64());
-                let lat_op = obj.get("lat").and_then(|x| x.as_f64());
+        payload
+            .as_str()
+            .is_some_and(|keyword| self.check_keyword(keyword))
+    }
+}
 
-                if let (Some(lon), Some(lat)) = (lon_op, lat_op) {
-                    return self.check_point(&GeoPoint { lon, lat });
-                }
-                false
+/// Check geo payload value: a point, or a polygon, which matches if its whole exterior matches
+fn check_geo_value(payload: &Value, check_point: impl Fn(&GeoPoint) -> bool) -> bool {
+    match payload {
+        Value::Object(obj) => {
+            let lon_op = obj.get("lon").and_then(|x| x.as_f64());
+            let lat_op = obj.get("lat").and_then(|x| x.as_f64());
+
+            if let (Some(lon), Some(lat)) = (lon_op, lat_op) {
+                return check_point(&GeoPoint { lon, lat });
             }
-            _ => false,
+
+            GeoPolygon::from_payload(payload).map_or(false, |polygon| {
+                polygon.exterior.points.iter().all(check_point)
+            })
         }
+        _ => false,
     }
 }
 
-impl ValueChecker for GeoPolygon {
+impl ValueChecker for GeoBoundingBox {
     fn check_match(&self, payload: &Value) -> bool {
-        match payload {
-            Value::Object(obj) => {
-                let lon_op = obj.get("lon").and_then(|x| x.as_f64());
-                let lat_op = obj.get("lat").and_then(|x| x.as_f64());
+        check_geo_value(payload, |point| self.check_point(point))
+    }
+}
 
-                if let (Some(lon), Some(lat)) = (lon_op, lat_op) {
-                    return self.convert().check_point(&GeoPoint { lon, lat });
-                }
-                false
-            }
-            _ => false,
-        }
+impl ValueChecker for GeoRadius {
+    fn check_match(&self, payload: &Value) -> bool {
+        check_geo_value(payload, |point| self.check_point(point))
+    }
+}
+
+impl ValueChecker for GeoPolygon {
+    fn check_match(&self, payload: &Value) -> bool {
+        let polygon = self.convert();
+        check_geo_value(payload, |point| polygon.check_point(point))
     }
 }
 
@@ -261,6 +285,47 @@ mod tests {
         assert!(!miss_geo_query.check(&berlin_and_moscow));
     }
 
+    #[test]
+    fn test_geo_polygon_payload_matching() {
+        let square = |lon: f64, lat: f64, size: f64| {
+            json!({
+                "exterior": {
+                    "points": [
+                        {"lon": lon, "lat": lat},
+                        {"lon": lon + size, "lat": lat},
+                        {"lon": lon + size, "lat": lat + size},
+                        {"lon": lon, "lat": lat + size},
+                        {"lon": lon, "lat": lat},
+                    ]
+                }
+            })
+        };
+
+        let area: GeoPolygon = serde_json::from_value(square(0.0, 0.0, 10.0)).unwrap();
+        let bbox = GeoBoundingBox {
+            top_left: GeoPoint {
+                lon: 0.0,
+                lat: 10.0,
+            },
+            bottom_right: GeoPoint {
+                lon: 10.0,
+                lat: 0.0,
+            },
+        };
+
+        // Polygon is matched only if it is inside the area
+        let inside = square(2.0, 2.0, 5.0);
+        let crossing = square(8.0, 8.0, 5.0);
+        assert!(area.check(&inside));
+        assert!(bbox.check(&inside));
+        assert!(!area.check(&crossing));
+        assert!(!bbox.check(&crossing));
+
+        // Invalid polygons are ignored
+        let not_closed = json!({"exterior": {"points": [{"lon": 1.0, "lat": 1.0}]}});
+        assert!(!area.check(&not_closed));
+    }
+
     #[test]
     fn test_value_count() {
         let countries = json!([
@@ -0,0 +1,324 @@
+//! Append-only storage of values, addressed by point offsets.
+//!
+//! Values are appended to a file of fixed size blocks, which is read through mmap. Location of
+//! the latest value of each point is kept in an in-memory page table, changes of which are
+//! appended to a separate log file and replayed on load. Nothing is ever rewritten in place, so
+//! writes never wait for a compaction. Space of overwritten values is reclaimed when the segment
+//! is rebuilt by optimizers.
+
+use std::fs;
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use common::types::PointOffsetType;
+use memmap2::Mmap;
+use memory::mmap_ops::open_read_mmap;
+use parking_lot::{Mutex, RwLock};
+
+use crate::common::append_file::{append_synced, truncate_to_multiple};
+use crate::common::operation_error::OperationResult;
+use crate::common::Flusher;
+
+const BLOCKS_FILE: &str = "blocks.dat";
+const PAGE_TABLE_FILE: &str = "page_table.dat";
+
+/// Values are aligned to blocks, so a single read of a small value touches a single page
+const BLOCK_SIZE: usize = 128;
+
+/// Point offset, first block and length, each as little endian `u32`
+const PAGE_TABLE_ENTRY_SIZE: usize = 12;
+
+/// Length of a page table entry, recording deletion of the value
+const DELETED: u32 = u32::MAX;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ValuePointer {
+    block: u32,
+    length: u32,
+}
+
+impl ValuePointer {
+    fn blocks(length: usize) -> usize {
+        length.div_ceil(BLOCK_SIZE).max(1)
+    }
+}
+
+/// Blocks and page table entries, shared with flushers
+struct Blocks {
+    /// Blocks, persisted in the file
+    mmap: Option<Mmap>,
+    mapped_blocks: u32,
+    /// Blocks, being written into the file by a flusher
+    flushing: Arc<Vec<u8>>,
+    /// Blocks, appended after the last flush
+    pending: Vec<u8>,
+    /// Page table entries, appended after the last flush
+    pending_entries: Vec<u8>,
+}
+
+impl Blocks {
+    fn total_blocks(&self) -> u32 {
+        self.mapped_blocks + ((self.flushing.len() + self.pending.len()) / BLOCK_SIZE) as u32
+    }
+
+    fn read(&self, pointer: ValuePointer) -> &[u8] {
+        let length = pointer.length as usize;
+        let mut offset = pointer.block as usize * BLOCK_SIZE;
+
+        let mapped_bytes = self.mapped_blocks as usize * BLOCK_SIZE;
+        if offset < mapped_bytes {
+            let mmap = self.mmap.as_ref().expect("mapped blocks require mmap");
+            return &mmap[offset..offset + length];
+        }
+        offset -= mapped_bytes;
+
+        if offset < self.flushing.len() {
+            return &self.flushing[offset..offset + length];
+        }
+        offset -= self.flushing.len();
+
+        &self.pending[offset..offset + length]
+    }
+}
+
+pub struct Gridstore {
+    dir: PathBuf,
+    /// Location of the latest value of each point
+    page_table: Vec<Option<ValuePointer>>,
+    blocks: Arc<RwLock<Blocks>>,
+    /// Prevents concurrent flushers from appending to files at the same time
+    flush_lock: Arc<Mutex<()>>,
+}
+
+impl Gridstore {
+    pub fn open(dir: &Path) -> OperationResult<Self> {
+        fs::create_dir_all(dir)?;
+
+        // Last block and entry may be partially written, if the process crashed during a flush
+        let blocks_path = dir.join(BLOCKS_FILE);
+        let mapped_blocks = (truncate_to_multiple(&blocks_path, BLOCK_SIZE)? / BLOCK_SIZE) as u32;
+        let mmap = if mapped_blocks > 0 {
+            Some(open_read_mmap(&blocks_path)?)
+        } else {
+            None
+        };
+
+        let page_table_path = dir.join(PAGE_TABLE_FILE);
+        truncate_to_multiple(&page_table_path, PAGE_TABLE_ENTRY_SIZE)?;
+        let entries = fs::read(&page_table_path)?;
+
+        let mut page_table: Vec<Option<ValuePointer>> = Vec::new();
+        for entry in entries.chunks_exact(PAGE_TABLE_ENTRY_SIZE) {
+            let field = |i: usize| u32::from_le_bytes(entry[i * 4..i * 4 + 4].try_into().unwrap());
+            let (point_offset, block, length) = (field(0) as usize, field(1), field(2));
+
+            let pointer = if length == DELETED {
+                None
+            } else {
+                let end_block = block as usize + ValuePointer::blocks(length as usize);
+                if end_block > mapped_blocks as usize {
+                    // Entries are persisted after blocks, so this is a corrupted entry
+                    log::warn!("Skipping gridstore entry of point {point_offset}, pointing beyond stored blocks");
+                    continue;
+                }
+                Some(ValuePointer { block, length })
+            };
+
+            if page_table.len() <= point_offset {
+                page_table.resize(point_offset + 1, None);
+            }
+            page_table[point_offset] = pointer;
+        }
+
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            page_table,
+            blocks: Arc::new(RwLock::new(Blocks {
+                mmap,
+                mapped_blocks,
+                flushing: Default::default(),
+                pending: Vec::new(),
+                pending_entries: Vec::new(),
+            })),
+            flush_lock: Default::default(),
+        })
+    }
+
+    /// Read value of the point with `f`, if any
+    pub fn read<T>(&self, point_offset: PointOffsetType, f: impl FnOnce(&[u8]) -> T) -> Option<T> {
+        let pointer = (*self.page_table.get(point_offset as usize)?)?;
+        Some(f(self.blocks.read().read(pointer)))
+    }
+
+    pub fn put(&mut self, point_offset: PointOffsetType, value: &[u8]) {
+        let length = value.len();
+        let mut blocks = self.blocks.write();
+
+        let pointer = ValuePointer {
+            block: blocks.total_blocks(),
+            length: length as u32,
+        };
+        blocks.pending.extend_from_slice(value);
+        let padding = ValuePointer::blocks(length) * BLOCK_SIZE - length;
+        blocks.pending.resize(blocks.pending.len() + padding, 0);
+        Self::append_entry(&mut blocks, point_offset, pointer.block, pointer.length);
+        drop(blocks);
+
+        self.set_pointer(point_offset, Some(pointer));
+    }
+
+    /// Delete value of the point, returns `false` if there was none
+    pub fn delete(&mut self, point_offset: PointOffsetType) -> bool {
+        let existed = self
+            .page_table
+            .get(point_offset as usize)
+            .is_some_and(Option::is_some);
+        if existed {
+            Self::append_entry(&mut self.blocks.write(), point_offset, 0, DELETED);
+            self.set_pointer(point_offset, None);
+        }
+        existed
+    }
+
+    /// Iterate over points, having a value, in order of their offsets
+    pub fn iter<E>(
+        &self,
+        mut f: impl FnMut(PointOffsetType, &[u8]) -> Result<bool, E>,
+    ) -> Result<(), E> {
+        let blocks = self.blocks.read();
+        for (point_offset, pointer) in self.page_table.iter().enumerate() {
+            let Some(pointer) = pointer else {
+                continue;
+            };
+            if !f(point_offset as PointOffsetType, blocks.read(*pointer))? {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove all values
+    pub fn wipe(&mut self) -> OperationResult<()> {
+        let _flush_guard = self.flush_lock.lock();
+        let mut blocks = self.blocks.write();
+
+        for file in self.files() {
+            if file.exists() {
+                fs::remove_file(file)?;
+            }
+        }
+
+        *blocks = Blocks {
+            mmap: None,
+            mapped_blocks: 0,
+            flushing: Default::default(),
+            pending: Vec::new(),
+            pending_entries: Vec::new(),
+        };
+        self.page_table.clear();
+        Ok(())
+    }
+
+    pub fn files(&self) -> Vec<PathBuf> {
+        vec![self.dir.join(BLOCKS_FILE), self.dir.join(PAGE_TABLE_FILE)]
+    }
+
+    /// Append pending blocks to the file first, and page table entries after them.
+    ///
+    /// If the process crashes in between, blocks without entries are never read,
+    /// so the storage is consistent at any moment.
+    pub fn flusher(&self) -> Flusher {
+        let dir = self.dir.clone();
+        let blocks = self.blocks.clone();
+        let flush_lock = self.flush_lock.clone();
+
+        Box::new(move || {
+            let _flush_guard = flush_lock.lock();
+
+            let (flushing, entries) = {
+                let mut blocks = blocks.write();
+                if blocks.pending_entries.is_empty() {
+                    return Ok(());
+                }
+                let pending = mem::take(&mut blocks.pending);
+                blocks.flushing = Arc::new(pending);
+                (
+                    blocks.flushing.clone(),
+                    mem::take(&mut blocks.pending_entries),
+                )
+            };
+
+            let blocks_path = dir.join(BLOCKS_FILE);
+            append_synced(&blocks_path, &flushing)?;
+            append_synced(&dir.join(PAGE_TABLE_FILE), &entries)?;
+
+            // Remap the file to read flushed blocks from it
+            let mmap = open_read_mmap(&blocks_path)?;
+            let mut blocks = blocks.write();
+            blocks.mapped_blocks += (flushing.len() / BLOCK_SIZE) as u32;
+            debug_assert!(blocks.mapped_blocks as usize * BLOCK_SIZE <= mmap.len());
+            blocks.mmap = Some(mmap);
+            blocks.flushing = Default::default();
+            Ok(())
+        })
+    }
+
+    fn append_entry(blocks: &mut Blocks, point_offset: PointOffsetType, block: u32, length: u32) {
+        for field in [point_offset, block, length] {
+            blocks
+                .pending_entries
+                .extend_from_slice(&field.to_le_bytes());
+        }
+    }
+
+    fn set_pointer(&mut self, point_offset: PointOffsetType, pointer: Option<ValuePointer>) {
+        let point_offset = point_offset as usize;
+        if self.page_table.len() <= point_offset {
+            self.page_table.resize(point_offset + 1, None);
+        }
+        self.page_table[point_offset] = pointer;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+
+    #[test]
+    fn test_gridstore_persistence() {
+        let dir = Builder::new().prefix("gridstore").tempdir().unwrap();
+
+        let mut store = Gridstore::open(dir.path()).unwrap();
+        store.put(0, b"first");
+        store.put(3, &[7; 300]);
+        store.flusher()().unwrap();
+
+        // Values are readable before and after flush
+        store.put(0, b"second");
+        store.put(1, b"");
+        assert!(store.delete(3));
+        assert!(!store.delete(2));
+        assert_eq!(store.read(0, <[u8]>::to_vec).unwrap(), b"second");
+        store.flusher()().unwrap();
+        assert_eq!(store.read(0, <[u8]>::to_vec).unwrap(), b"second");
+
+        // Unflushed values are lost on reopen
+        store.put(5, b"lost");
+        drop(store);
+
+        let store = Gridstore::open(dir.path()).unwrap();
+        let mut values = Vec::new();
+        store
+            .iter::<()>(|point_offset, value| {
+                values.push((point_offset, value.to_vec()));
+                Ok(true)
+            })
+            .unwrap();
+        assert_eq!(values, vec![(0, b"second".to_vec()), (1, Vec::new())],);
+        assert!(store.read(3, <[u8]>::to_vec).is_none());
+        assert!(store.read(5, <[u8]>::to_vec).is_none());
+    }
+}
@@ -0,0 +1,154 @@
+use std::path::{Path, PathBuf};
+
+use common::types::PointOffsetType;
+use serde_json::Value;
+
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::common::Flusher;
+use crate::json_path::JsonPath;
+use crate::payload_storage::gridstore::Gridstore;
+use crate::payload_storage::payload_fields::decode_payload_fields;
+use crate::payload_storage::PayloadStorage;
+use crate::types::Payload;
+
+/// Directory of the payload storage inside of the segment
+pub const GRIDSTORE_PAYLOAD_PATH: &str = "payload_storage";
+
+/// Gridstore implementation of `PayloadStorage`.
+/// Appends all changes to blocks on disk, and reads payload from them through mmap.
+/// Does not use RocksDB, so writes are never delayed by compactions.
+pub struct GridstorePayloadStorage {
+    storage: Gridstore,
+}
+
+impl GridstorePayloadStorage {
+    pub fn open(segment_path: &Path) -> OperationResult<Self> {
+        let storage = Gridstore::open(&segment_path.join(GRIDSTORE_PAYLOAD_PATH))?;
+        Ok(GridstorePayloadStorage { storage })
+    }
+
+    fn read_payload(&self, point_id: PointOffsetType) -> OperationResult<Option<Payload>> {
+        self.storage
+            .read(point_id, serde_cbor::from_slice::<Payload>)
+            .transpose()
+            .map_err(OperationError::from)
+    }
+
+    fn update_storage(&mut self, point_id: PointOffsetType, payload: &Payload) {
+        self.storage
+            .put(point_id, &serde_cbor::to_vec(payload).unwrap());
+    }
+
+    pub fn iter<F>(&self, mut callback: F) -> OperationResult<()>
+    where
+        F: FnMut(PointOffsetType, &Payload) -> OperationResult<bool>,
+    {
+        self.storage.iter(|point_id, raw| {
+            let payload: Payload = serde_cbor::from_slice(raw)?;
+            callback(point_id, &payload)
+        })
+    }
+}
+
+impl PayloadStorage for GridstorePayloadStorage {
+    fn assign_all(&mut self, point_id: PointOffsetType, payload: &Payload) -> OperationResult<()> {
+        self.update_storage(point_id, payload);
+        Ok(())
+    }
+
+    fn assign(&mut self, point_id: PointOffsetType, payload: &Payload) -> OperationResult<()> {
+        let mut point_payload = self.read_payload(point_id)?.unwrap_or_default();
+        point_payload.merge(payload);
+        self.update_storage(point_id, &point_payload);
+        Ok(())
+    }
+
+    fn assign_by_key(
+        &mut self,
+        point_id: PointOffsetType,
+        payload: &Payload,
+        key: &JsonPath,
+    ) -> OperationResult<()> {
+        let mut point_payload = self.read_payload(point_id)?.unwrap_or_default();
+        point_payload.merge_by_key(payload, key)?;
+        self.update_storage(point_id, &point_payload);
+        Ok(())
+    }
+
+    fn patch(&mut self, point_id: PointOffsetType, patch: &Payload) -> OperationResult<()> {
+        let mut point_payload = self.read_payload(point_id)?.unwrap_or_default();
+        point_payload.merge_patch(patch);
+        self.update_storage(point_id, &point_payload);
+        Ok(())
+    }
+
+    fn payload(&self, point_id: PointOffsetType) -> OperationResult<Payload> {
+        Ok(self.read_payload(point_id)?.unwrap_or_default())
+    }
+
+    fn payload_fields(&self, point_id: PointOffsetType, keys: &[&str]) -> OperationResult<Payload> {
+        let payload = self
+            .storage
+            .read(point_id, |raw| decode_payload_fields(raw, keys))
+            .transpose()?;
+        Ok(payload.unwrap_or_default())
+    }
+
+    fn delete(&mut self, point_id: PointOffsetType, key: &JsonPath) -> OperationResult<Vec<Value>> {
+        match self.read_payload(point_id)? {
+            Some(mut payload) => {
+                let res = payload.remove(key);
+                if !res.is_empty() {
+                    self.update_storage(point_id, &payload);
+                }
+                Ok(res)
+            }
+            None => Ok(vec![]),
+        }
+    }
+
+    fn drop(&mut self, point_id: PointOffsetType) -> OperationResult<Option<Payload>> {
+        let payload = self.read_payload(point_id)?;
+        self.storage.delete(point_id);
+        Ok(payload)
+    }
+
+    fn wipe(&mut self) -> OperationResult<()> {
+        self.storage.wipe()
+    }
+
+    fn flusher(&self) -> Flusher {
+        self.storage.flusher()
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        self.storage.files()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::json_path::path;
+
+    #[test]
+    fn test_gridstore_payload_storage() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+        let mut storage = GridstorePayloadStorage::open(dir.path()).unwrap();
+        let payload: Payload = serde_json::from_str(r#"{"name": "John Doe", "age": 42}"#).unwrap();
+        storage.assign(100, &payload).unwrap();
+        storage.delete(100, &path("age")).unwrap();
+        storage.flusher()().unwrap();
+        drop(storage);
+
+        let mut storage = GridstorePayloadStorage::open(dir.path()).unwrap();
+        let expected: Payload = serde_json::from_str(r#"{"name": "John Doe"}"#).unwrap();
+        assert_eq!(storage.payload(100).unwrap(), expected);
+
+        assert_eq!(storage.drop(100).unwrap(), Some(expected));
+        assert!(storage.payload(100).unwrap().is_empty());
+    }
+}
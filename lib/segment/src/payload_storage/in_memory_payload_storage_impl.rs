@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::path::PathBuf;
 
 use common::types::PointOffsetType;
 use serde_json::Value;
@@ -38,6 +39,11 @@ impl PayloadStorage for InMemoryPayloadStorage {
         }
     }
 
+    fn patch(&mut self, point_id: PointOffsetType, patch: &Payload) -> OperationResult<()> {
+        self.payload.entry(point_id).or_default().merge_patch(patch);
+        Ok(())
+    }
+
     fn payload(&self, point_id: PointOffsetType) -> OperationResult<Payload> {
         match self.payload.get(&point_id) {
             Some(payload) => Ok(payload.to_owned()),
@@ -68,6 +74,10 @@ impl PayloadStorage for InMemoryPayloadStorage {
     fn flusher(&self) -> Flusher {
         Box::new(|| Ok(()))
     }
+
+    fn files(&self) -> Vec<PathBuf> {
+        vec![]
+    }
 }
 
 #[cfg(test)]
@@ -1,9 +1,13 @@
 pub mod condition_checker;
+mod gridstore;
+pub mod gridstore_payload_storage;
 #[cfg(feature = "testing")]
 pub mod in_memory_payload_storage;
 #[cfg(feature = "testing")]
 pub mod in_memory_payload_storage_impl;
 pub mod on_disk_payload_storage;
+mod payload_compression;
+mod payload_fields;
 mod payload_storage_base;
 pub mod payload_storage_enum;
 pub mod query_checker;
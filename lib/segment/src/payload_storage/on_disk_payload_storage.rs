@@ -1,3 +1,4 @@
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use common::types::PointOffsetType;
@@ -5,23 +6,56 @@ use parking_lot::RwLock;
 use rocksdb::DB;
 use serde_json::Value;
 
-use crate::common::operation_error::{OperationError, OperationResult};
+use crate::common::operation_error::OperationResult;
 use crate::common::rocksdb_wrapper::{DatabaseColumnWrapper, DB_PAYLOAD_CF};
 use crate::common::Flusher;
 use crate::json_path::JsonPath;
+use crate::payload_storage::payload_compression::PayloadCompressor;
+use crate::payload_storage::payload_fields::decode_payload_fields;
 use crate::payload_storage::PayloadStorage;
-use crate::types::Payload;
+use crate::types::{Payload, PayloadCompression};
 
 /// On-disk implementation of `PayloadStorage`.
 /// Persists all changes to disk using `store`, does not keep payload in memory
 pub struct OnDiskPayloadStorage {
     db_wrapper: DatabaseColumnWrapper,
+    /// Compresses stored payloads, if enabled
+    compressor: Option<PayloadCompressor>,
 }
 
 impl OnDiskPayloadStorage {
     pub fn open(database: Arc<RwLock<DB>>) -> OperationResult<Self> {
         let db_wrapper = DatabaseColumnWrapper::new(database, DB_PAYLOAD_CF);
-        Ok(OnDiskPayloadStorage { db_wrapper })
+        Ok(OnDiskPayloadStorage {
+            db_wrapper,
+            compressor: None,
+        })
+    }
+
+    /// Compress payloads, written from now on, with a dictionary trained on this segment
+    pub fn with_compression(
+        mut self,
+        segment_path: &Path,
+        compression: PayloadCompression,
+    ) -> OperationResult<Self> {
+        self.compressor = Some(PayloadCompressor::open(segment_path, compression)?);
+        Ok(self)
+    }
+
+    fn decode(&self, raw: &[u8]) -> OperationResult<Payload> {
+        let payload = match &self.compressor {
+            Some(compressor) => serde_cbor::from_slice(&compressor.decompress(raw)?)?,
+            None => serde_cbor::from_slice(raw)?,
+        };
+        Ok(payload)
+    }
+
+    fn decode_fields(&self, raw: &[u8], keys: &[&str]) -> OperationResult<Payload> {
+        let payload = match &self.compressor {
+            Some(compressor) => decode_payload_fields(&compressor.decompress(raw)?, keys)?,
+            None => decode_payload_fields(raw, keys)?,
+        };
+        Ok(payload)
     }
 
     pub fn remove_from_storage(&self, point_id: PointOffsetType) -> OperationResult<()> {
@@ -34,18 +68,19 @@ impl OnDiskPayloadStorage {
         point_id: PointOffsetType,
         payload: &Payload,
     ) -> OperationResult<()> {
-        self.db_wrapper.put(
-            serde_cbor::to_vec(&point_id).unwrap(),
-            serde_cbor::to_vec(payload).unwrap(),
-        )
+        let mut value = serde_cbor::to_vec(payload).unwrap();
+        if let Some(compressor) = &self.compressor {
+            value = compressor.compress(value)?;
+        }
+        self.db_wrapper
+            .put(serde_cbor::to_vec(&point_id).unwrap(), value)
     }
 
     pub fn read_payload(&self, point_id: PointOffsetType) -> OperationResult<Option<Payload>> {
         let key = serde_cbor::to_vec(&point_id).unwrap();
         self.db_wrapper
-            .get_pinned(&key, |raw| serde_cbor::from_slice(raw))?
+            .get_pinned(&key, |raw| self.decode(raw))?
             .transpose()
-            .map_err(OperationError::from)
     }
 
     pub fn iter<F>(&self, mut callback: F) -> OperationResult<()>
@@ -53,10 +88,7 @@ impl OnDiskPayloadStorage {
         F: FnMut(PointOffsetType, &Payload) -> OperationResult<bool>,
     {
         for (key, val) in self.db_wrapper.lock_db().iter()? {
-            let do_continue = callback(
-                serde_cbor::from_slice(&key)?,
-                &serde_cbor::from_slice(&val)?,
-            )?;
+            let do_continue = callback(serde_cbor::from_slice(&key)?, &self.decode(&val)?)?;
             if !do_continue {
                 return Ok(());
             }
@@ -102,6 +134,12 @@ impl PayloadStorage for OnDiskPayloadStorage {
         }
     }
 
+    fn patch(&mut self, point_id: PointOffsetType, patch: &Payload) -> OperationResult<()> {
+        let mut point_payload = self.read_payload(point_id)?.unwrap_or_default();
+        point_payload.merge_patch(patch);
+        self.update_storage(point_id, &point_payload)
+    }
+
     fn payload(&self, point_id: PointOffsetType) -> OperationResult<Payload> {
         let payload = self.read_payload(point_id)?;
         match payload {
@@ -110,6 +148,15 @@ impl PayloadStorage for OnDiskPayloadStorage {
         }
     }
 
+    fn payload_fields(&self, point_id: PointOffsetType, keys: &[&str]) -> OperationResult<Payload> {
+        let key = serde_cbor::to_vec(&point_id).unwrap();
+        let payload = self
+            .db_wrapper
+            .get_pinned(&key, |raw| self.decode_fields(raw, keys))?
+            .transpose()?;
+        Ok(payload.unwrap_or_default())
+    }
+
     fn delete(&mut self, point_id: PointOffsetType, key: &JsonPath) -> OperationResult<Vec<Value>> {
         let stored_payload = self.read_payload(point_id)?;
 
@@ -138,4 +185,11 @@ impl PayloadStorage for OnDiskPayloadStorage {
     fn flusher(&self) -> Flusher {
         self.db_wrapper.flusher()
     }
+
+    fn files(&self) -> Vec<PathBuf> {
+        self.compressor
+            .as_ref()
+            .map(PayloadCompressor::files)
+            .unwrap_or_default()
+    }
 }
@@ -0,0 +1,169 @@
+//! Zstd compression of payload values, with a dictionary trained per segment.
+//!
+//! Payloads of a segment usually share most of their keys and many of their values, but a single
+//! payload is too small to compress well on its own. A dictionary, trained on the first payloads
+//! written into the segment, captures the common parts, while every value is still compressed
+//! separately and can be read without touching others. Values written before the dictionary is
+//! trained are kept as is, and are told apart from compressed ones by the zstd magic number.
+
+use std::borrow::Cow;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use io::file_operations::{atomic_save_bin, read_bin};
+use parking_lot::Mutex;
+use zstd::bulk::Compressor;
+use zstd::dict::DecoderDictionary;
+use zstd::stream::read::Decoder;
+
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::types::PayloadCompression;
+
+const PAYLOAD_DICTIONARY_FILE: &str = "payload_dictionary.dat";
+
+/// First bytes of every zstd frame. CBOR encoded payload is a map, so it never starts with them.
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+
+/// Number of payloads to train the dictionary on
+const TRAINING_SAMPLES: usize = 1024;
+
+const MAX_DICTIONARY_SIZE: usize = 64 * 1024;
+
+struct Dictionary {
+    compressor: Mutex<Compressor<'static>>,
+    decoder: DecoderDictionary<'static>,
+}
+
+impl Dictionary {
+    fn new(level: i32, dictionary: &[u8]) -> OperationResult<Self> {
+        Ok(Self {
+            compressor: Mutex::new(Compressor::with_dictionary(level, dictionary)?),
+            decoder: DecoderDictionary::copy(dictionary),
+        })
+    }
+}
+
+pub struct PayloadCompressor {
+    level: i32,
+    path: PathBuf,
+    dictionary: OnceLock<Dictionary>,
+    /// Payloads to train the dictionary on, `None` once training is done or has failed
+    samples: Mutex<Option<Vec<Vec<u8>>>>,
+}
+
+impl PayloadCompressor {
+    pub fn open(segment_path: &Path, compression: PayloadCompression) -> OperationResult<Self> {
+        let path = segment_path.join(PAYLOAD_DICTIONARY_FILE);
+        let dictionary = OnceLock::new();
+        if path.exists() {
+            let trained: Vec<u8> = read_bin(&path)?;
+            let _ = dictionary.set(Dictionary::new(compression.level, &trained)?);
+        }
+        let samples = dictionary.get().is_none().then(Vec::new);
+        Ok(Self {
+            level: compression.level,
+            path,
+            dictionary,
+            samples: Mutex::new(samples),
+        })
+    }
+
+    /// Compress CBOR encoded payload, if the dictionary is trained already
+    pub fn compress(&self, value: Vec<u8>) -> OperationResult<Vec<u8>> {
+        if self.dictionary.get().is_none() {
+            self.collect_sample(&value)?;
+        }
+        match self.dictionary.get() {
+            Some(dictionary) => Ok(dictionary.compressor.lock().compress(&value)?),
+            None => Ok(value),
+        }
+    }
+
+    /// Decompress the value, if it is compressed
+    pub fn decompress<'a>(&self, value: &'a [u8]) -> OperationResult<Cow<'a, [u8]>> {
+        if !value.starts_with(&ZSTD_MAGIC) {
+            return Ok(Cow::Borrowed(value));
+        }
+        let dictionary = self.dictionary.get().ok_or_else(|| {
+            OperationError::service_error("Payload is compressed, but its dictionary is missing")
+        })?;
+        let mut decompressed = Vec::new();
+        Decoder::with_prepared_dictionary(value, &dictionary.decoder)?
+            .read_to_end(&mut decompressed)?;
+        Ok(Cow::Owned(decompressed))
+    }
+
+    pub fn files(&self) -> Vec<PathBuf> {
+        if self.dictionary.get().is_some() {
+            vec![self.path.clone()]
+        } else {
+            vec![]
+        }
+    }
+
+    fn collect_sample(&self, value: &[u8]) -> OperationResult<()> {
+        let mut samples_guard = self.samples.lock();
+        let Some(samples) = samples_guard.as_mut() else {
+            return Ok(());
+        };
+        samples.push(value.to_vec());
+        if samples.len() < TRAINING_SAMPLES {
+            return Ok(());
+        }
+
+        let samples = samples_guard.take().unwrap_or_default();
+        match zstd::dict::from_samples(&samples, MAX_DICTIONARY_SIZE) {
+            Ok(trained) => {
+                // Persist the dictionary before any value is compressed with it
+                atomic_save_bin(&self.path, &trained)?;
+                let _ = self.dictionary.set(Dictionary::new(self.level, &trained)?);
+            }
+            Err(err) => {
+                log::warn!("Failed to train payload dictionary, payload stays uncompressed: {err}");
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::types::Payload;
+
+    #[test]
+    fn test_payload_compression() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let compression = PayloadCompression { level: 3 };
+
+        let encode = |i: usize| {
+            let payload = Payload::from(json!({
+                "description": "A verbose description, repeated in every payload",
+                "category": format!("category-{}", i % 10),
+                "price": i,
+            }));
+            serde_cbor::to_vec(&payload).unwrap()
+        };
+
+        let compressor = PayloadCompressor::open(dir.path(), compression).unwrap();
+        let stored: Vec<_> = (0..TRAINING_SAMPLES + 10)
+            .map(|i| compressor.compress(encode(i)).unwrap())
+            .collect();
+
+        // Samples are stored as is, values after training are compressed
+        assert_eq!(stored[0], encode(0));
+        let last = TRAINING_SAMPLES + 9;
+        assert!(stored[last].len() < encode(last).len());
+        assert_eq!(compressor.files().len(), 1);
+
+        // Both kinds of values are readable after reopening
+        let compressor = PayloadCompressor::open(dir.path(), compression).unwrap();
+        for i in [0, last] {
+            assert_eq!(compressor.decompress(&stored[i]).unwrap(), encode(i));
+        }
+    }
+}
@@ -0,0 +1,81 @@
+//! Decoding of selected top level fields of a serialized payload.
+//!
+//! Payload is stored as a CBOR map, where every value is length-prefixed or self-delimited. Values
+//! of fields, which are not requested, are skipped over without being parsed into JSON values, so
+//! reading a single field of a large payload doesn't allocate the rest of it.
+
+use std::fmt;
+
+use serde::de::{DeserializeSeed, IgnoredAny, MapAccess, Visitor};
+use serde::Deserializer;
+use serde_json::{Map, Value};
+
+use crate::types::Payload;
+
+/// Decode only the given top level keys of a CBOR encoded payload
+pub fn decode_payload_fields(raw: &[u8], keys: &[&str]) -> serde_cbor::Result<Payload> {
+    let mut deserializer = serde_cbor::Deserializer::from_slice(raw);
+    let payload = PayloadFields { keys }.deserialize(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(payload)
+}
+
+struct PayloadFields<'k> {
+    keys: &'k [&'k str],
+}
+
+impl<'de> DeserializeSeed<'de> for PayloadFields<'_> {
+    type Value = Payload;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Payload, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for PayloadFields<'_> {
+    type Value = Payload;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a payload map")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Payload, A::Error> {
+        let mut payload = Map::new();
+        while let Some(key) = map.next_key::<String>()? {
+            if self.keys.contains(&key.as_str()) {
+                payload.insert(key, map.next_value::<Value>()?);
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+        Ok(Payload(payload))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_decode_payload_fields() {
+        let payload = Payload::from(json!({
+            "title": "Title",
+            "body": { "text": "Long text", "tags": ["a", "b"] },
+            "rating": 4.5,
+        }));
+        let raw = serde_cbor::to_vec(&payload).unwrap();
+
+        let fields = decode_payload_fields(&raw, &["title", "rating", "missing"]).unwrap();
+        assert_eq!(
+            fields,
+            Payload::from(json!({ "title": "Title", "rating": 4.5 }))
+        );
+
+        let fields = decode_payload_fields(&raw, &["body"]).unwrap();
+        assert_eq!(fields.0.get("body"), payload.0.get("body"));
+
+        assert!(decode_payload_fields(&raw, &[]).unwrap().is_empty());
+    }
+}
@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use common::types::PointOffsetType;
 use serde_json::Value;
 
@@ -26,10 +28,21 @@ pub trait PayloadStorage {
         key: &JsonPath,
     ) -> OperationResult<()>;
 
+    /// Apply JSON Merge Patch (RFC 7396) to the payload of a concrete point
+    fn patch(&mut self, point_id: PointOffsetType, patch: &Payload) -> OperationResult<()>;
+
     /// Get payload for point
     /// If no payload found, return empty payload
     fn payload(&self, point_id: PointOffsetType) -> OperationResult<Payload>;
 
+    /// Get only the given top level keys of the point payload
+    /// Storages, keeping payload serialized, skip other fields without parsing them
+    fn payload_fields(&self, point_id: PointOffsetType, keys: &[&str]) -> OperationResult<Payload> {
+        let mut payload = self.payload(point_id)?;
+        payload.0.retain(|key, _| keys.contains(&key.as_str()));
+        Ok(payload)
+    }
+
     /// Delete payload by key
     fn delete(&mut self, point_id: PointOffsetType, key: &JsonPath) -> OperationResult<Vec<Value>>;
 
@@ -41,6 +54,9 @@ pub trait PayloadStorage {
 
     /// Return function that forces persistence of current storage state.
     fn flusher(&self) -> Flusher;
+
+    /// Files of the storage, which are not in RocksDB
+    fn files(&self) -> Vec<PathBuf>;
 }
 
 pub trait ConditionChecker {
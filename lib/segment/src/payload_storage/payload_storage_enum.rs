@@ -1,9 +1,12 @@
+use std::path::PathBuf;
+
 use common::types::PointOffsetType;
 use serde_json::Value;
 
 use crate::common::operation_error::OperationResult;
 use crate::common::Flusher;
 use crate::json_path::JsonPath;
+use crate::payload_storage::gridstore_payload_storage::GridstorePayloadStorage;
 #[cfg(feature = "testing")]
 use crate::payload_storage::in_memory_payload_storage::InMemoryPayloadStorage;
 use crate::payload_storage::on_disk_payload_storage::OnDiskPayloadStorage;
@@ -16,6 +19,7 @@ pub enum PayloadStorageEnum {
     InMemoryPayloadStorage(InMemoryPayloadStorage),
     SimplePayloadStorage(SimplePayloadStorage),
     OnDiskPayloadStorage(OnDiskPayloadStorage),
+    GridstorePayloadStorage(GridstorePayloadStorage),
 }
 
 #[cfg(feature = "testing")]
@@ -37,6 +41,12 @@ impl From<OnDiskPayloadStorage> for PayloadStorageEnum {
     }
 }
 
+impl From<GridstorePayloadStorage> for PayloadStorageEnum {
+    fn from(a: GridstorePayloadStorage) -> Self {
+        PayloadStorageEnum::GridstorePayloadStorage(a)
+    }
+}
+
 impl PayloadStorageEnum {
     pub fn iter<F>(&self, callback: F) -> OperationResult<()>
     where
@@ -47,6 +57,7 @@ impl PayloadStorageEnum {
             PayloadStorageEnum::InMemoryPayloadStorage(s) => s.iter(callback),
             PayloadStorageEnum::SimplePayloadStorage(s) => s.iter(callback),
             PayloadStorageEnum::OnDiskPayloadStorage(s) => s.iter(callback),
+            PayloadStorageEnum::GridstorePayloadStorage(s) => s.iter(callback),
         }
     }
 }
@@ -58,6 +69,7 @@ impl PayloadStorage for PayloadStorageEnum {
             PayloadStorageEnum::InMemoryPayloadStorage(s) => s.assign(point_id, payload),
             PayloadStorageEnum::SimplePayloadStorage(s) => s.assign(point_id, payload),
             PayloadStorageEnum::OnDiskPayloadStorage(s) => s.assign(point_id, payload),
+            PayloadStorageEnum::GridstorePayloadStorage(s) => s.assign(point_id, payload),
         }
     }
 
@@ -74,6 +86,19 @@ impl PayloadStorage for PayloadStorageEnum {
             }
             PayloadStorageEnum::SimplePayloadStorage(s) => s.assign_by_key(point_id, payload, key),
             PayloadStorageEnum::OnDiskPayloadStorage(s) => s.assign_by_key(point_id, payload, key),
+            PayloadStorageEnum::GridstorePayloadStorage(s) => {
+                s.assign_by_key(point_id, payload, key)
+            }
+        }
+    }
+
+    fn patch(&mut self, point_id: PointOffsetType, patch: &Payload) -> OperationResult<()> {
+        match self {
+            #[cfg(feature = "testing")]
+            PayloadStorageEnum::InMemoryPayloadStorage(s) => s.patch(point_id, patch),
+            PayloadStorageEnum::SimplePayloadStorage(s) => s.patch(point_id, patch),
+            PayloadStorageEnum::OnDiskPayloadStorage(s) => s.patch(point_id, patch),
+            PayloadStorageEnum::GridstorePayloadStorage(s) => s.patch(point_id, patch),
         }
     }
 
@@ -83,6 +108,17 @@ impl PayloadStorage for PayloadStorageEnum {
             PayloadStorageEnum::InMemoryPayloadStorage(s) => s.payload(point_id),
             PayloadStorageEnum::SimplePayloadStorage(s) => s.payload(point_id),
             PayloadStorageEnum::OnDiskPayloadStorage(s) => s.payload(point_id),
+            PayloadStorageEnum::GridstorePayloadStorage(s) => s.payload(point_id),
+        }
+    }
+
+    fn payload_fields(&self, point_id: PointOffsetType, keys: &[&str]) -> OperationResult<Payload> {
+        match self {
+            #[cfg(feature = "testing")]
+            PayloadStorageEnum::InMemoryPayloadStorage(s) => s.payload_fields(point_id, keys),
+            PayloadStorageEnum::SimplePayloadStorage(s) => s.payload_fields(point_id, keys),
+            PayloadStorageEnum::OnDiskPayloadStorage(s) => s.payload_fields(point_id, keys),
+            PayloadStorageEnum::GridstorePayloadStorage(s) => s.payload_fields(point_id, keys),
         }
     }
 
@@ -92,6 +128,7 @@ impl PayloadStorage for PayloadStorageEnum {
             PayloadStorageEnum::InMemoryPayloadStorage(s) => s.delete(point_id, key),
             PayloadStorageEnum::SimplePayloadStorage(s) => s.delete(point_id, key),
             PayloadStorageEnum::OnDiskPayloadStorage(s) => s.delete(point_id, key),
+            PayloadStorageEnum::GridstorePayloadStorage(s) => s.delete(point_id, key),
         }
     }
 
@@ -101,6 +138,7 @@ impl PayloadStorage for PayloadStorageEnum {
             PayloadStorageEnum::InMemoryPayloadStorage(s) => s.drop(point_id),
             PayloadStorageEnum::SimplePayloadStorage(s) => s.drop(point_id),
             PayloadStorageEnum::OnDiskPayloadStorage(s) => s.drop(point_id),
+            PayloadStorageEnum::GridstorePayloadStorage(s) => s.drop(point_id),
         }
     }
 
@@ -110,6 +148,7 @@ impl PayloadStorage for PayloadStorageEnum {
             PayloadStorageEnum::InMemoryPayloadStorage(s) => s.wipe(),
             PayloadStorageEnum::SimplePayloadStorage(s) => s.wipe(),
             PayloadStorageEnum::OnDiskPayloadStorage(s) => s.wipe(),
+            PayloadStorageEnum::GridstorePayloadStorage(s) => s.wipe(),
         }
     }
 
@@ -119,6 +158,17 @@ impl PayloadStorage for PayloadStorageEnum {
             PayloadStorageEnum::InMemoryPayloadStorage(s) => s.flusher(),
             PayloadStorageEnum::SimplePayloadStorage(s) => s.flusher(),
             PayloadStorageEnum::OnDiskPayloadStorage(s) => s.flusher(),
+            PayloadStorageEnum::GridstorePayloadStorage(s) => s.flusher(),
+        }
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        match self {
+            #[cfg(feature = "testing")]
+            PayloadStorageEnum::InMemoryPayloadStorage(s) => s.files(),
+            PayloadStorageEnum::SimplePayloadStorage(s) => s.files(),
+            PayloadStorageEnum::OnDiskPayloadStorage(s) => s.files(),
+            PayloadStorageEnum::GridstorePayloadStorage(s) => s.files(),
         }
     }
 }
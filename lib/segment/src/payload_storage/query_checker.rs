@@ -11,6 +11,7 @@ use common::types::PointOffsetType;
 use crate::common::utils::{check_is_empty, check_is_null, IndexesMap};
 use crate::id_tracker::IdTrackerSS;
 use crate::index::field_index::FieldIndex;
+use crate::index::query_optimization::optimized_filter::check_min_count;
 use crate::json_path::JsonPathInterface as _;
 use crate::payload_storage::condition_checker::ValueChecker;
 use crate::payload_storage::payload_storage_enum::PayloadStorageEnum;
@@ -55,20 +56,14 @@ fn check_min_should<F>(checker: &F, min_should: &Option<MinShould>) -> bool
 where
     F: Fn(&Condition) -> bool,
 {
-    let check = |x| check_condition(checker, x);
     match min_should {
         None => true,
         Some(MinShould {
             conditions,
             min_count,
-        }) => {
-            conditions
-                .iter()
-                .filter(|cond| check(cond))
-                .take(*min_count)
-                .count()
-                == *min_count
-        }
+        }) => check_min_count(conditions, *min_count, |condition| {
+            check_condition(checker, condition)
+        }),
     }
 }
 
@@ -130,6 +125,8 @@ where
         Condition::HasId(has_id) => id_tracker
             .and_then(|id_tracker| id_tracker.external_id(point_id))
             .map_or(false, |id| has_id.has_id.contains(&id)),
+        // Vectors and attributes are not known to payload checks, and nested objects don't have them
+        Condition::HasVector(_) | Condition::Attribute(_) => false,
         Condition::Nested(nested) => {
             let nested_path = nested.array_key();
             let nested_indexes = select_nested_indexes(&nested_path, field_indexes);
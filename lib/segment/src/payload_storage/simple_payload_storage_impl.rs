@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::path::PathBuf;
 
 use common::types::PointOffsetType;
 use serde_json::Value;
@@ -41,6 +42,14 @@ impl PayloadStorage for SimplePayloadStorage {
         }
     }
 
+    fn patch(&mut self, point_id: PointOffsetType, patch: &Payload) -> OperationResult<()> {
+        self.payload.entry(point_id).or_default().merge_patch(patch);
+
+        self.update_storage(&point_id)?;
+
+        Ok(())
+    }
+
     fn payload(&self, point_id: PointOffsetType) -> OperationResult<Payload> {
         match self.payload.get(&point_id) {
             Some(payload) => Ok(payload.to_owned()),
@@ -48,6 +57,17 @@ impl PayloadStorage for SimplePayloadStorage {
         }
     }
 
+    fn payload_fields(&self, point_id: PointOffsetType, keys: &[&str]) -> OperationResult<Payload> {
+        let Some(payload) = self.payload.get(&point_id) else {
+            return Ok(Default::default());
+        };
+        let fields = keys
+            .iter()
+            .filter_map(|&key| Some((key.to_string(), payload.0.get(key)?.clone())))
+            .collect();
+        Ok(Payload(fields))
+    }
+
     fn delete(
         &mut self,
         point_id: PointOffsetType,
@@ -79,6 +99,10 @@ impl PayloadStorage for SimplePayloadStorage {
     fn flusher(&self) -> Flusher {
         self.db_wrapper.flusher()
     }
+
+    fn files(&self) -> Vec<PathBuf> {
+        vec![]
+    }
 }
 
 #[cfg(test)]
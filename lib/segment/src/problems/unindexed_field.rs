@@ -1,6 +1,6 @@
 use std::any::TypeId;
 use std::collections::{HashMap, HashSet};
-use std::sync::OnceLock;
+use std::sync::{PoisonError, RwLock};
 use std::time::Duration;
 
 use http::header::CONTENT_TYPE;
@@ -26,13 +26,23 @@ pub struct UnindexedField {
 }
 
 /// Don't use this directly, use `UnindexedField::slow_query_threshold()` instead
-pub static SLOW_QUERY_THRESHOLD: OnceLock<Duration> = OnceLock::new();
+static SLOW_QUERY_THRESHOLD: RwLock<Option<Duration>> = RwLock::new(None);
 
 impl UnindexedField {
     const DEFAULT_SLOW_QUERY_SECS: f32 = 1.2;
 
     pub fn slow_query_threshold() -> Duration {
-        *SLOW_QUERY_THRESHOLD.get_or_init(|| Duration::from_secs_f32(Self::DEFAULT_SLOW_QUERY_SECS))
+        SLOW_QUERY_THRESHOLD
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .unwrap_or_else(|| Duration::from_secs_f32(Self::DEFAULT_SLOW_QUERY_SECS))
+    }
+
+    /// Change the threshold at runtime, `None` restores the default
+    pub fn set_slow_query_threshold(threshold: Option<Duration>) {
+        *SLOW_QUERY_THRESHOLD
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = threshold;
     }
 
     pub fn get_instance_id(collection_name: &str, field_name: &JsonPathV2) -> String {
@@ -90,8 +100,7 @@ impl UnindexedField {
         payload_schema: &HashMap<PayloadKeyType, PayloadFieldSchema>,
         collection_name: String,
     ) {
-        let unindexed_issues =
-            Extractor::new(filter, payload_schema, collection_name).into_issues();
+        let unindexed_issues = Extractor::new(filter, payload_schema).into_issues(collection_name);
 
         log::trace!("Found unindexed issues: {unindexed_issues:#?}");
 
@@ -212,6 +221,9 @@ fn infer_schema_from_field_condition(field_condition: &FieldCondition) -> Vec<Pa
             }
             Match::Any(match_any) => infer_schema_from_any_variants(&match_any.any),
             Match::Except(match_except) => infer_schema_from_any_variants(&match_except.except),
+            Match::Similar(_match_similar) => {
+                PayloadFieldSchema::FieldType(PayloadSchemaType::MinHash)
+            }
         })
     }
     if let Some(range_interface) = range {
@@ -222,6 +234,13 @@ fn infer_schema_from_field_condition(field_condition: &FieldCondition) -> Vec<Pa
             RangeInterface::Float(_) => {
                 inferred.push(PayloadFieldSchema::FieldType(PayloadSchemaType::Float));
                 inferred.push(PayloadFieldSchema::FieldType(PayloadSchemaType::Integer));
+                inferred.push(PayloadFieldSchema::FieldType(PayloadSchemaType::BigInt));
+            }
+            RangeInterface::Keyword(_) => {
+                inferred.push(PayloadFieldSchema::FieldType(PayloadSchemaType::Keyword));
+            }
+            RangeInterface::BigInt(_) => {
+                inferred.push(PayloadFieldSchema::FieldType(PayloadSchemaType::BigInt));
             }
         }
     }
@@ -236,22 +255,42 @@ fn infer_schema_from_field_condition(field_condition: &FieldCondition) -> Vec<Pa
     inferred
 }
 
+/// Whether existing `index` serves the conditions, which need the `inferred` index
+fn index_suits(inferred: &PayloadFieldSchema, index: &PayloadFieldSchema) -> bool {
+    match (inferred, index) {
+        // Params of geo index only select the storage
+        (
+            PayloadFieldSchema::FieldType(PayloadSchemaType::Geo),
+            PayloadFieldSchema::FieldParams(PayloadSchemaParams::Geo(_)),
+        ) => true,
+        _ => inferred == index,
+    }
+}
+
+/// Fields of the filter, which are not indexed by a suitable index, with the indexes which would
+/// speed up their conditions
+///
+/// Candidate indexes of each field are listed in the order of their inference, the most specific first.
+pub fn unindexed_fields(
+    filter: &Filter,
+    payload_schema: &HashMap<PayloadKeyType, PayloadFieldSchema>,
+) -> HashMap<PayloadKeyType, Vec<PayloadFieldSchema>> {
+    Extractor::new(filter, payload_schema).unindexed_schema
+}
+
 struct Extractor<'a> {
     payload_schema: &'a HashMap<PayloadKeyType, PayloadFieldSchema>,
     unindexed_schema: HashMap<PayloadKeyType, Vec<PayloadFieldSchema>>,
-    collection_name: String,
 }
 
 impl<'a> Extractor<'a> {
     fn new(
         filter: &Filter,
         payload_schema: &'a HashMap<PayloadKeyType, PayloadFieldSchema>,
-        collection_name: String,
     ) -> Self {
         let mut extractor = Self {
             payload_schema,
             unindexed_schema: HashMap::new(),
-            collection_name,
         };
 
         extractor.update_from_filter(None, filter);
@@ -259,13 +298,13 @@ impl<'a> Extractor<'a> {
         extractor
     }
 
-    fn into_issues(self) -> Vec<UnindexedField> {
+    fn into_issues(self, collection_name: String) -> Vec<UnindexedField> {
         self.unindexed_schema
             .into_iter()
             .filter_map(|(key, field_schemas)| {
                 let field_schemas = HashSet::from_iter(field_schemas);
 
-                UnindexedField::try_new(key, field_schemas, self.collection_name.clone()).ok()
+                UnindexedField::try_new(key, field_schemas, collection_name.clone()).ok()
             })
             .collect()
     }
@@ -323,6 +362,8 @@ impl<'a> Extractor<'a> {
             }
             // No index needed
             Condition::HasId(_) => return,
+            Condition::HasVector(_) => return,
+            Condition::Attribute(_) => return,
             Condition::Resharding(_) => return,
         };
 
@@ -330,7 +371,9 @@ impl<'a> Extractor<'a> {
 
         let needs_index = match self.payload_schema.get(&full_key) {
             Some(index_info) => {
-                let already_indexed = inferred.iter().any(|inferred| inferred == index_info);
+                let already_indexed = inferred
+                    .iter()
+                    .any(|inferred| index_suits(inferred, index_info));
 
                 !already_indexed
             }
@@ -1,5 +1,6 @@
 use std::cmp::max;
 use std::collections::{HashMap, HashSet};
+use std::ffi::OsStr;
 use std::fs::{self, File};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -7,6 +8,9 @@ use std::thread::{self, JoinHandle};
 
 use atomic_refcell::AtomicRefCell;
 use bitvec::prelude::BitVec;
+use chrono::{DateTime, Utc};
+use common::hardware_counter::HardwareCounterCell;
+use common::io_throttling::{BackgroundIo, ThrottledWriter};
 use common::types::{PointOffsetType, ScoredPointOffset, TelemetryDetail};
 use io::file_operations::{atomic_save_json, read_json};
 use io::storage_version::{StorageVersion, VERSION_FILE};
@@ -14,18 +18,25 @@ use itertools::Either;
 use memory::mmap_ops;
 use parking_lot::{Mutex, RwLock};
 use rocksdb::DB;
+use serde_json::Value;
+use sparse::common::types::DimId;
+use sparse::index::inverted_index::INDEX_FILE_NAME;
 use tar::Builder;
 use uuid::Uuid;
 
+use crate::attribute_storage::AttributeStorage;
 use crate::common::operation_error::OperationError::TypeInferenceError;
 use crate::common::operation_error::{
     get_service_error, OperationError, OperationResult, SegmentFailedState,
 };
 use crate::common::validate_snapshot_archive::open_snapshot_archive_with_validation;
-use crate::common::{check_named_vectors, check_query_vectors, check_stopped, check_vector_name};
+use crate::common::{check_named_vectors, check_query_vectors, check_vector_name};
+use crate::data_types::aggregation::{
+    hash_payload_value, DistinctValues, NumericStats, ValueDistribution,
+};
 use crate::data_types::named_vectors::NamedVectors;
-use crate::data_types::order_by::{Direction, OrderBy, OrderValue};
-use crate::data_types::query_context::{QueryContext, SegmentQueryContext};
+use crate::data_types::order_by::{Direction, OrderBy, OrderValue, OrderedPoint};
+use crate::data_types::query_context::{QueryContext, SegmentQueryContext, VectorQueryContext};
 use crate::data_types::vectors::{QueryVector, Vector};
 use crate::entry::entry_point::SegmentEntry;
 use crate::id_tracker::IdTrackerSS;
@@ -34,16 +45,20 @@ use crate::index::field_index::CardinalityEstimation;
 use crate::index::struct_payload_index::StructPayloadIndex;
 use crate::index::{PayloadIndex, VectorIndex, VectorIndexEnum};
 use crate::json_path::JsonPath;
+use crate::segment_constructor::migrations::STORAGE_FORMAT_FILE;
+use crate::segment_manifest::SegmentManifest;
 use crate::spaces::tools::{peek_top_largest_iterable, peek_top_smallest_iterable};
 use crate::telemetry::SegmentTelemetry;
 use crate::types::{
-    Filter, Payload, PayloadFieldSchema, PayloadIndexInfo, PayloadKeyType, PayloadKeyTypeRef,
-    PayloadSchemaType, PointIdType, ScoredPoint, SearchParams, SegmentConfig, SegmentInfo,
-    SegmentState, SegmentType, SeqNumberType, VectorDataInfo, WithPayload, WithVector,
+    AttributeValue, Distance, Filter, GeoPoint, Payload, PayloadContainer, PayloadFieldSchema,
+    PayloadIndexInfo, PayloadKeyType, PayloadKeyTypeRef, PayloadSchemaType, PayloadSelector,
+    PointIdType, ScoredPoint, SearchParams, SegmentConfig, SegmentInfo, SegmentState, SegmentType,
+    SeqNumberType, VectorDataInfo, WithPayload, WithVector,
 };
 use crate::utils;
 use crate::utils::fs::find_symlink;
 use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
+use crate::vector_storage::raw_scorer::new_stoppable_raw_scorer_with_distance;
 use crate::vector_storage::{VectorStorage, VectorStorageEnum};
 
 pub const SEGMENT_STATE_FILE: &str = "segment.json";
@@ -81,6 +96,8 @@ pub struct Segment {
     pub id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
     pub vector_data: HashMap<String, VectorData>,
     pub payload_index: Arc<AtomicRefCell<StructPayloadIndex>>,
+    /// Scalar attributes of points, stored outside of the payload
+    pub attribute_storage: Arc<AtomicRefCell<AttributeStorage>>,
     /// Shows if it is possible to insert more points into this segment
     pub appendable_flag: bool,
     /// Shows what kind of indexes and storages are used in this segment
@@ -91,6 +108,8 @@ pub struct Segment {
     pub error_status: Option<SegmentFailedState>,
     pub database: Arc<RwLock<DB>>,
     pub flush_thread: Mutex<Option<JoinHandle<OperationResult<SeqNumberType>>>>,
+    /// When the segment was created, if known
+    pub created_at: Option<DateTime<Utc>>,
 }
 
 pub struct VectorData {
@@ -113,6 +132,34 @@ impl VectorData {
 
         index_task.into_iter().chain(storage_task)
     }
+
+    /// Memory mapped files of vector storage, index and quantized data
+    pub fn mmap_files(&self) -> Vec<PathBuf> {
+        let mut files: Vec<_> = self
+            .prefault_mmap_pages()
+            .filter_map(|task| task.path().map(Path::to_path_buf))
+            .collect();
+
+        match &*self.vector_index.borrow() {
+            index @ (VectorIndexEnum::SparseMmap(_)
+            | VectorIndexEnum::SparseCompressedMmapF32(_)
+            | VectorIndexEnum::SparseCompressedMmapF16(_)) => {
+                // Posting lists, without the small config and tracker files
+                files.extend(
+                    index
+                        .files()
+                        .into_iter()
+                        .filter(|file| file.file_name() == Some(OsStr::new(INDEX_FILE_NAME))),
+                );
+            }
+            _ => {}
+        }
+
+        if let Some(quantized_vectors) = self.quantized_vectors.borrow().as_ref() {
+            files.extend(quantized_vectors.mmap_files());
+        }
+        files
+    }
 }
 
 impl Segment {
@@ -156,19 +203,28 @@ impl Segment {
     ///
     /// # Warning
     ///
-    /// Available for appendable segments only.
+    /// Available for appendable segments, or for vectors which can be updated in place.
+    /// See [`SegmentConfig::is_vector_appendable`].
     fn update_vectors(
         &mut self,
         internal_id: PointOffsetType,
         vectors: NamedVectors,
     ) -> OperationResult<()> {
-        debug_assert!(self.is_appendable());
+        debug_assert!(
+            self.is_appendable()
+                || vectors
+                    .keys()
+                    .all(|vector_name| self.segment_config.is_vector_appendable(vector_name))
+        );
         check_named_vectors(&vectors, &self.segment_config)?;
         for (vector_name, new_vector) in vectors {
             let vector_data = &self.vector_data[vector_name.as_ref()];
             let mut vector_index = vector_data.vector_index.borrow_mut();
             vector_index.update_vector(internal_id, Some(new_vector.as_vec_ref()))?;
         }
+        // Cached results of `has_vector` conditions are not valid anymore, a deleted vector may
+        // be stored again in place
+        self.payload_index.borrow().clear_filter_cache();
         Ok(())
     }
 
@@ -406,6 +462,7 @@ impl Segment {
         SegmentState {
             version: self.version,
             config: self.segment_config.clone(),
+            created_at: self.created_at,
         }
     }
 
@@ -414,6 +471,57 @@ impl Segment {
         Ok(atomic_save_json(&state_path, state)?)
     }
 
+    /// Files of vector storages, vector indexes, quantized vectors and id tracker
+    ///
+    /// In non-appendable segments, these files keep their size, as points are only updated in
+    /// place. Unlike payloads, which can be changed in any segment.
+    fn fixed_component_files(&self) -> Vec<PathBuf> {
+        let mut files = Vec::new();
+        for vector_data in self.vector_data.values() {
+            files.extend(vector_data.vector_index.borrow().files());
+            files.extend(vector_data.vector_storage.borrow().files());
+            if let Some(quantized_vectors) = vector_data.quantized_vectors.borrow().as_ref() {
+                files.extend(quantized_vectors.files());
+            }
+        }
+        files.extend(self.attribute_storage.borrow().files());
+        files.extend(self.id_tracker.borrow().files());
+        files
+    }
+
+    /// Files of vector storages, vector indexes and quantized vectors, which are loaded into RAM
+    fn ram_resident_files(&self) -> Vec<PathBuf> {
+        let mut files = Vec::new();
+        for vector_data in self.vector_data.values() {
+            let vector_index = vector_data.vector_index.borrow();
+            if !vector_index.is_on_disk() {
+                files.extend(vector_index.files());
+            }
+            let vector_storage = vector_data.vector_storage.borrow();
+            if !vector_storage.is_on_disk() {
+                files.extend(vector_storage.files());
+            }
+            if let Some(quantized_vectors) = vector_data.quantized_vectors.borrow().as_ref() {
+                if !quantized_vectors.is_on_disk() {
+                    files.extend(quantized_vectors.files());
+                }
+            }
+        }
+        files
+    }
+
+    /// Record the current set of segment files in the manifest
+    pub fn save_manifest(&self) -> OperationResult<()> {
+        let derived_files = self.payload_index.borrow().field_index_files()?;
+        SegmentManifest::collect(
+            &self.current_path,
+            self.version,
+            self.fixed_component_files(),
+            derived_files,
+        )?
+        .save(&self.current_path)
+    }
+
     pub fn load_state(current_path: &Path) -> OperationResult<SegmentState> {
         let state_path = current_path.join(SEGMENT_STATE_FILE);
         read_json(&state_path).map_err(|err| {
@@ -493,6 +601,21 @@ impl Segment {
         self.payload_index.borrow().payload(point_offset)
     }
 
+    /// Retrieve only the part of the payload, required by the selector, by internal ID
+    fn payload_fields_by_offset(
+        &self,
+        point_offset: PointOffsetType,
+        selector: Option<&PayloadSelector>,
+    ) -> OperationResult<Payload> {
+        match selector.and_then(PayloadSelector::required_keys) {
+            Some(keys) => self
+                .payload_index
+                .borrow()
+                .payload_fields(point_offset, &keys),
+            None => self.payload_by_offset(point_offset),
+        }
+    }
+
     pub fn save_current_state(&self) -> OperationResult<()> {
         Self::save_state(&self.get_state(), &self.current_path)
     }
@@ -582,12 +705,74 @@ impl Segment {
         }
     }
 
+    /// Exact search, which scores vectors with the given distance instead of the configured one.
+    ///
+    /// Neither the vector index nor quantized vectors can be used, as they are built for the
+    /// configured distance.
+    fn search_with_distance(
+        &self,
+        vector_name: &str,
+        query_vectors: &[&QueryVector],
+        distance: Distance,
+        filter: Option<&Filter>,
+        top: usize,
+        query_context: &VectorQueryContext,
+    ) -> OperationResult<Vec<Vec<ScoredPointOffset>>> {
+        let vector_config = self
+            .segment_config
+            .vector_data
+            .get(vector_name)
+            .ok_or(OperationError::WrongSparse)?;
+        if !vector_config.distance.can_score_with(distance) {
+            return Err(OperationError::ValidationError {
+                description: format!(
+                    "Vector {vector_name} is stored for {:?} distance and can't be scored with {distance:?} distance",
+                    vector_config.distance,
+                ),
+            });
+        }
+
+        let is_stopped = query_context.is_stopped();
+        let vector_storage = self.vector_data[vector_name].vector_storage.borrow();
+        let id_tracker = self.id_tracker.borrow();
+        let deleted_points = query_context
+            .deleted_points()
+            .unwrap_or(id_tracker.deleted_point_bitslice());
+
+        let filtered_ids = filter.map(|filter| self.payload_index.borrow().query_points(filter));
+        let scored_count = filtered_ids
+            .as_ref()
+            .map_or(vector_storage.available_vector_count(), Vec::len);
+        query_context.hardware_counter().incr_scored_vectors(
+            query_vectors.len() * scored_count,
+            vector_storage.average_vector_size_in_bytes(),
+        );
+
+        query_vectors
+            .iter()
+            .map(|&vector| {
+                let scorer = new_stoppable_raw_scorer_with_distance(
+                    vector.to_owned(),
+                    &vector_storage,
+                    distance,
+                    deleted_points,
+                    &is_stopped,
+                )?;
+                Ok(match &filtered_ids {
+                    Some(ids) => scorer.peek_top_iter(&mut ids.iter().copied(), top),
+                    None => scorer.peek_top_all(top),
+                })
+            })
+            .collect()
+    }
+
     /// Converts raw ScoredPointOffset search result into ScoredPoint result
     fn process_search_result(
         &self,
         internal_result: &[ScoredPointOffset],
         with_payload: &WithPayload,
         with_vector: &WithVector,
+        hardware_counter: &HardwareCounterCell,
     ) -> OperationResult<Vec<ScoredPoint>> {
         let id_tracker = self.id_tracker.borrow();
         internal_result
@@ -614,8 +799,15 @@ impl Segment {
                     ))
                 })?;
                 let payload = if with_payload.enable {
-                    let initial_payload = self.payload_by_offset(point_offset)?;
-                    let processed_payload = if let Some(i) = &with_payload.payload_selector {
+                    let selector = with_payload.payload_selector.as_ref();
+                    let initial_payload = self.payload_fields_by_offset(point_offset, selector)?;
+                    if hardware_counter.is_enabled() {
+                        let payload_size = serde_json::to_vec(&initial_payload)
+                            .map(|bytes| bytes.len())
+                            .unwrap_or_default();
+                        hardware_counter.incr_payload_io_read(payload_size);
+                    }
+                    let processed_payload = if let Some(i) = selector {
                         i.process(initial_payload)
                     } else {
                         initial_payload
@@ -663,7 +855,7 @@ impl Segment {
     fn should_pre_filter(&self, filter: &Filter, limit: Option<usize>) -> bool {
         let query_cardinality = {
             let payload_index = self.payload_index.borrow();
-            payload_index.estimate_cardinality(filter)
+            payload_index.estimate_cardinality_sampled(filter)
         };
 
         // ToDo: Add telemetry for this heuristics
@@ -761,8 +953,6 @@ impl Segment {
                 key: order_by.key.to_string(),
             })?;
 
-        let start_from = order_by.start_from();
-
         let values_ids_iterator = payload_index
             .query_points(condition)
             .into_iter()
@@ -770,39 +960,17 @@ impl Segment {
                 // Repeat a point for as many values as it has
                 numeric_index
                     .get_ordering_values(internal_id)
-                    // But only those which start from `start_from` 😛
-                    .filter(|value| match order_by.direction() {
-                        Direction::Asc => value >= &start_from,
-                        Direction::Desc => value <= &start_from,
-                    })
                     .map(move |ordering_value| (ordering_value, internal_id))
             })
             .filter_map(|(value, internal_id)| {
                 id_tracker
                     .external_id(internal_id)
                     .map(|external_id| (value, external_id))
-            });
-
-        let page = match order_by.direction() {
-            Direction::Asc => {
-                let mut page = match limit {
-                    Some(limit) => peek_top_smallest_iterable(values_ids_iterator, limit),
-                    None => values_ids_iterator.collect(),
-                };
-                page.sort_unstable_by(|(value_a, _), (value_b, _)| value_a.cmp(value_b));
-                page
-            }
-            Direction::Desc => {
-                let mut page = match limit {
-                    Some(limit) => peek_top_largest_iterable(values_ids_iterator, limit),
-                    None => values_ids_iterator.collect(),
-                };
-                page.sort_unstable_by(|(value_a, _), (value_b, _)| value_b.cmp(value_a));
-                page
-            }
-        };
+            })
+            // But only those which start from `start_from` 😛
+            .filter(|(value, external_id)| order_by.is_after_start(*value, *external_id));
 
-        Ok(page)
+        Ok(select_ordered_page(order_by, values_ids_iterator, limit))
     }
 
     pub fn filtered_read_by_id_stream(
@@ -859,15 +1027,227 @@ impl Segment {
             }
         };
 
-        let reads = filtered_iter
+        let values_ids_iterator = filtered_iter
             .filter_map(|(value, internal_id)| {
                 id_tracker
                     .external_id(internal_id)
                     .map(|external_id| (value, external_id))
             })
-            .take(limit.unwrap_or(usize::MAX))
+            .filter(|(value, external_id)| order_by.is_after_start(*value, *external_id));
+
+        // The index yields points with equal values in internal id order, but pages are ordered by
+        // external id within equal values. Points sharing the value of the `limit`-th point are
+        // all considered, and only the first `limit` pairs in the `(value, id)` order are kept.
+        let mut read_count = 0;
+        let mut last_value = None;
+        let values_ids_iterator = values_ids_iterator.take_while(|(value, _)| {
+            if limit.is_some_and(|limit| read_count >= limit) && last_value != Some(*value) {
+                return false;
+            }
+            read_count += 1;
+            last_value = Some(*value);
+            true
+        });
+
+        Ok(select_ordered_page(order_by, values_ids_iterator, limit))
+    }
+
+    /// Order points by the distance from `order_by.geo` to the nearest of their geo values.
+    ///
+    /// Distances are computed from the points stored in the geo index, payload is not read.
+    pub fn filtered_read_by_geo_distance(
+        &self,
+        order_by: &OrderBy,
+        origin: &GeoPoint,
+        limit: Option<usize>,
+        filter: Option<&Filter>,
+    ) -> OperationResult<Vec<(OrderValue, PointIdType)>> {
+        let payload_index = self.payload_index.borrow();
+        let id_tracker = self.id_tracker.borrow();
+
+        let geo_index = payload_index
+            .field_indexes
+            .get(&order_by.key)
+            .and_then(|indexes| indexes.iter().find_map(|index| index.as_geo()))
+            .ok_or_else(|| OperationError::MissingGeoIndexForOrderBy {
+                key: order_by.key.to_string(),
+            })?;
+
+        let filter_context = filter.map(|filter| payload_index.filter_context(filter));
+
+        let values_ids_iterator = id_tracker
+            .iter_ids()
+            .filter(|internal_id| match &filter_context {
+                Some(filter_context) => filter_context.check(*internal_id),
+                None => true,
+            })
+            .filter_map(|internal_id| {
+                let points = geo_index.get_values(internal_id).unwrap_or_default();
+                let polygons = geo_index.get_polygons(internal_id);
+                let distance = points
+                    .iter()
+                    .chain(polygons.iter().flatten())
+                    .map(|point| origin.distance(point))
+                    .min_by(|a, b| a.total_cmp(b))?;
+                let external_id = id_tracker.external_id(internal_id)?;
+                Some((OrderValue::Float(distance), external_id))
+            })
+            .filter(|(value, external_id)| order_by.is_after_start(*value, *external_id));
+
+        Ok(select_ordered_page(order_by, values_ids_iterator, limit))
+    }
+
+    /// Order points by scanning the payload column of the key.
+    ///
+    /// Only used if the key has no range index, returns `None` if there is no column either.
+    pub fn filtered_read_by_column(
+        &self,
+        order_by: &OrderBy,
+        limit: Option<usize>,
+        filter: Option<&Filter>,
+    ) -> Option<Vec<(OrderValue, PointIdType)>> {
+        let payload_index = self.payload_index.borrow();
+        let has_range_index = payload_index
+            .field_indexes
+            .get(&order_by.key)
+            .is_some_and(|indexes| indexes.iter().any(|index| index.as_numeric().is_some()));
+        if has_range_index {
+            return None;
+        }
+        let column = payload_index.payload_column(&order_by.key)?;
+
+        let id_tracker = self.id_tracker.borrow();
+        let filter_context = filter.map(|filter| payload_index.filter_context(filter));
+
+        let values_ids_iterator = column
+            .iter()
+            .filter(|(internal_id, _)| match &filter_context {
+                Some(filter_context) => filter_context.check(*internal_id),
+                None => true,
+            })
+            .filter_map(|(internal_id, values)| {
+                let external_id = id_tracker.external_id(internal_id)?;
+                // Repeat a point for as many values as it has
+                Some(values.iter().map(move |value| (*value, external_id)))
+            })
+            .flatten()
+            .filter(|(value, external_id)| order_by.is_after_start(*value, *external_id));
+
+        Some(select_ordered_page(order_by, values_ids_iterator, limit))
+    }
+
+    /// Order points by `order_by.key` only, ties are ordered by point id
+    fn read_ordered_by_key(
+        &self,
+        limit: Option<usize>,
+        filter: Option<&Filter>,
+        order_by: &OrderBy,
+    ) -> OperationResult<Vec<(OrderValue, PointIdType)>> {
+        if let Some(origin) = &order_by.geo {
+            return self.filtered_read_by_geo_distance(order_by, origin, limit, filter);
+        }
+        if let Some(page) = self.filtered_read_by_column(order_by, limit, filter) {
+            return Ok(page);
+        }
+        match filter {
+            None => self.filtered_read_by_value_stream(order_by, limit, None),
+            Some(filter) => {
+                if self.should_pre_filter(filter, limit) {
+                    self.filtered_read_by_index_ordered(order_by, limit, filter)
+                } else {
+                    self.filtered_read_by_value_stream(order_by, limit, Some(filter))
+                }
+            }
+        }
+    }
+
+    /// Order points by `order_by.key`, and points with equal values by the `then_by` keys.
+    ///
+    /// Reads by the key include all points sharing the value of the last one, so the `then_by`
+    /// values are only read for the points of the page.
+    fn read_ordered_then_by(
+        &self,
+        limit: Option<usize>,
+        filter: Option<&Filter>,
+        order_by: &OrderBy,
+    ) -> OperationResult<Vec<OrderedPoint>> {
+        let start_after = order_by.start_after();
+        // Position of the points with the `start_from` value relative to the cursor depends on
+        // the `then_by` keys, so all of them are read
+        let by_key = order_by.without_cursor();
+
+        let mut read_limit = limit;
+        loop {
+            let page = self.read_ordered_by_key(read_limit, filter, &by_key)?;
+            let read_count = page.len();
+
+            let mut points = self.read_then_by_values(order_by, page)?;
+            if let Some(start_after) = &start_after {
+                points.retain(|point| order_by.cmp_points(point, start_after).is_gt());
+            }
+            order_by.sort_points(&mut points);
+
+            let skipped = read_count - points.len();
+            let exhausted = read_limit.map_or(true, |read_limit| read_count < read_limit);
+            match limit {
+                // Points before the cursor took the place of the following ones, read them too.
+                // All skipped points share the `start_from` value, so a single retry is enough.
+                Some(limit) if points.len() < limit && !exhausted && read_limit == Some(limit) => {
+                    read_limit = Some(limit + skipped);
+                }
+                _ => {
+                    if let Some(limit) = limit {
+                        points.truncate(limit);
+                    }
+                    return Ok(points);
+                }
+            }
+        }
+    }
+
+    /// Read values of `order_by.then_by` keys from their range indexes
+    fn read_then_by_values(
+        &self,
+        order_by: &OrderBy,
+        values_ids: Vec<(OrderValue, PointIdType)>,
+    ) -> OperationResult<Vec<OrderedPoint>> {
+        let payload_index = self.payload_index.borrow();
+        let id_tracker = self.id_tracker.borrow();
+
+        let numeric_indexes = order_by
+            .then_by()
+            .iter()
+            .map(|then_by| {
+                payload_index
+                    .field_indexes
+                    .get(&then_by.key)
+                    .and_then(|indexes| indexes.iter().find_map(|index| index.as_numeric()))
+                    .ok_or_else(|| OperationError::MissingRangeIndexForOrderBy {
+                        key: then_by.key.to_string(),
+                    })
+            })
+            .collect::<OperationResult<Vec<_>>>()?;
+
+        let points = values_ids
+            .into_iter()
+            .map(|(value, id)| {
+                let internal_id = id_tracker.internal_id(id);
+                let then_by =
+                    order_by
+                        .then_by()
+                        .iter()
+                        .zip(&numeric_indexes)
+                        .map(|(then_by, numeric_index)| {
+                            then_by.select_value(internal_id.into_iter().flat_map(|internal_id| {
+                                numeric_index.get_ordering_values(internal_id)
+                            }))
+                        })
+                        .collect();
+                OrderedPoint { value, then_by, id }
+            })
             .collect();
-        Ok(reads)
+
+        Ok(points)
     }
 
     /// Check consistency of the segment's data and repair it if possible.
@@ -1039,11 +1419,41 @@ impl Segment {
         self.id_tracker.borrow().total_point_count()
     }
 
+    /// Memory mapped files of the segment, which are read during search
+    ///
+    /// Reading these files in advance populates the page cache, so first searches don't wait
+    /// for the disk.
+    pub fn mmap_files(&self) -> Vec<PathBuf> {
+        let mut files: Vec<_> = self
+            .vector_data
+            .values()
+            .flat_map(VectorData::mmap_files)
+            .collect();
+        files.extend(
+            self.payload_index
+                .borrow()
+                .prefault_mmap_pages()
+                .iter()
+                .filter_map(|task| task.path().map(Path::to_path_buf)),
+        );
+        files
+    }
+
     pub fn prefault_mmap_pages(&self) {
+        // Prefaulting is optional, don't compete with in-RAM data for memory
+        if memory::budget::global().is_exhausted() {
+            log::debug!(
+                "Memory budget is exhausted, skip prefaulting mmap pages of segment {:?}",
+                self.current_path,
+            );
+            return;
+        }
+
         let tasks: Vec<_> = self
             .vector_data
             .values()
             .flat_map(|data| data.prefault_mmap_pages())
+            .chain(self.payload_index.borrow().prefault_mmap_pages())
             .collect();
 
         let _ = thread::Builder::new()
@@ -1114,20 +1524,41 @@ impl SegmentEntry for Segment {
         check_query_vectors(vector_name, query_vectors, &self.segment_config)?;
         let vector_data = &self.vector_data[vector_name];
         let vector_query_context = query_context.get_vector_context(vector_name);
-        let internal_results = vector_data.vector_index.borrow().search(
-            query_vectors,
-            filter,
-            top,
-            params,
-            &vector_query_context,
-        )?;
+        let distance_override = self
+            .segment_config
+            .vector_data
+            .get(vector_name)
+            .and_then(|config| SearchParams::distance_override(params, config.distance));
+        let internal_results = match distance_override {
+            Some(distance) => self.search_with_distance(
+                vector_name,
+                query_vectors,
+                distance,
+                filter,
+                top,
+                &vector_query_context,
+            )?,
+            None => vector_data.vector_index.borrow().search(
+                query_vectors,
+                filter,
+                top,
+                params,
+                &vector_query_context,
+            )?,
+        };
 
-        check_stopped(&vector_query_context.is_stopped())?;
+        // A stopped search returns the points found so far, they are still processed as usual,
+        // so that a timed out request can respond with partial results
 
         let res = internal_results
             .iter()
             .map(|internal_result| {
-                self.process_search_result(internal_result, with_payload, with_vector)
+                self.process_search_result(
+                    internal_result,
+                    with_payload,
+                    with_vector,
+                    vector_query_context.hardware_counter(),
+                )
             })
             .collect();
 
@@ -1168,6 +1599,7 @@ impl SegmentEntry for Segment {
                 self.handle_point_version_and_failure(op_num, Some(internal_id), |segment| {
                     // Mark point as deleted, drop mapping
                     segment.payload_index.borrow_mut().drop(internal_id)?;
+                    segment.attribute_storage.borrow_mut().drop(internal_id);
                     segment.id_tracker.borrow_mut().drop(point_id)?;
 
                     // Before, we propagated point deletions to also delete its vectors. This turns
@@ -1231,6 +1663,10 @@ impl SegmentEntry for Segment {
                     )?;
                     let mut vector_storage = vector_data.vector_storage.borrow_mut();
                     let is_deleted = vector_storage.delete_vector(internal_id)?;
+                    if is_deleted {
+                        // Cached results of `has_vector` conditions are not valid anymore
+                        segment.payload_index.borrow().clear_filter_cache();
+                    }
                     Ok((is_deleted, Some(internal_id)))
                 })
             }
@@ -1280,6 +1716,27 @@ impl SegmentEntry for Segment {
         })
     }
 
+    fn patch_payload(
+        &mut self,
+        op_num: SeqNumberType,
+        point_id: PointIdType,
+        patch: &Payload,
+    ) -> OperationResult<bool> {
+        let internal_id = self.id_tracker.borrow().internal_id(point_id);
+        self.handle_point_version_and_failure(op_num, internal_id, |segment| match internal_id {
+            Some(internal_id) => {
+                segment
+                    .payload_index
+                    .borrow_mut()
+                    .patch(internal_id, patch)?;
+                Ok((true, Some(internal_id)))
+            }
+            None => Err(OperationError::PointIdError {
+                missed_point_id: point_id,
+            }),
+        })
+    }
+
     fn delete_payload(
         &mut self,
         op_num: SeqNumberType,
@@ -1318,6 +1775,40 @@ impl SegmentEntry for Segment {
         })
     }
 
+    fn set_attributes(
+        &mut self,
+        op_num: SeqNumberType,
+        point_id: PointIdType,
+        attributes: &HashMap<String, Option<AttributeValue>>,
+    ) -> OperationResult<bool> {
+        let internal_id = self.id_tracker.borrow().internal_id(point_id);
+        self.handle_point_version_and_failure(op_num, internal_id, |segment| match internal_id {
+            Some(internal_id) => {
+                segment
+                    .attribute_storage
+                    .borrow_mut()
+                    .set(internal_id, attributes)?;
+                // Cached results of `attribute` conditions are not valid anymore
+                segment.payload_index.borrow().clear_filter_cache();
+                Ok((true, Some(internal_id)))
+            }
+            None => Err(OperationError::PointIdError {
+                missed_point_id: point_id,
+            }),
+        })
+    }
+
+    fn attributes(
+        &self,
+        point_id: PointIdType,
+    ) -> OperationResult<HashMap<String, AttributeValue>> {
+        let internal_id = self.lookup_internal_id(point_id)?;
+        Ok(self
+            .attribute_storage
+            .borrow()
+            .point_attributes(internal_id))
+    }
+
     fn vector(&self, vector_name: &str, point_id: PointIdType) -> OperationResult<Option<Vector>> {
         check_vector_name(vector_name, &self.segment_config)?;
         let internal_id = self.lookup_internal_id(point_id)?;
@@ -1340,6 +1831,16 @@ impl SegmentEntry for Segment {
         self.payload_by_offset(internal_id)
     }
 
+    fn selected_payload(
+        &self,
+        point_id: PointIdType,
+        selector: &PayloadSelector,
+    ) -> OperationResult<Payload> {
+        let internal_id = self.lookup_internal_id(point_id)?;
+        let payload = self.payload_fields_by_offset(internal_id, Some(selector))?;
+        Ok(selector.process(payload))
+    }
+
     fn iter_points(&self) -> Box<dyn Iterator<Item = PointIdType> + '_> {
         // Sorry for that, but I didn't find any way easier.
         // If you try simply return iterator - it won't work because AtomicRef should exist
@@ -1371,17 +1872,12 @@ impl SegmentEntry for Segment {
         limit: Option<usize>,
         filter: Option<&'a Filter>,
         order_by: &'a OrderBy,
-    ) -> OperationResult<Vec<(OrderValue, PointIdType)>> {
-        match filter {
-            None => self.filtered_read_by_value_stream(order_by, limit, None),
-            Some(filter) => {
-                if self.should_pre_filter(filter, limit) {
-                    self.filtered_read_by_index_ordered(order_by, limit, filter)
-                } else {
-                    self.filtered_read_by_value_stream(order_by, limit, Some(filter))
-                }
-            }
+    ) -> OperationResult<Vec<OrderedPoint>> {
+        if !order_by.then_by().is_empty() {
+            return self.read_ordered_then_by(limit, filter, order_by);
         }
+        let page = self.read_ordered_by_key(limit, filter, order_by)?;
+        Ok(page.into_iter().map(OrderedPoint::from).collect())
     }
 
     fn read_range(&self, from: Option<PointIdType>, to: Option<PointIdType>) -> Vec<PointIdType> {
@@ -1431,6 +1927,97 @@ impl SegmentEntry for Segment {
         }
     }
 
+    fn distinct_values(
+        &self,
+        key: &JsonPath,
+        filter: Option<&Filter>,
+    ) -> OperationResult<DistinctValues> {
+        let payload_index = self.payload_index.borrow();
+        let id_tracker = self.id_tracker.borrow();
+
+        let points: Box<dyn Iterator<Item = PointOffsetType> + '_> = match filter {
+            Some(filter) => Box::new(payload_index.query_points(filter).into_iter()),
+            None => id_tracker.iter_ids(),
+        };
+
+        let map_index = payload_index
+            .field_indexes
+            .get(key)
+            .and_then(|indexes| indexes.iter().find_map(|index| index.as_map()));
+
+        if let Some(map_index) = map_index {
+            let mut distinct = DistinctValues::default();
+            match filter {
+                None => map_index
+                    .vocabulary_hashes()
+                    .for_each(|hash| distinct.insert(hash)),
+                Some(_) => points
+                    .flat_map(|idx| map_index.value_hashes(idx))
+                    .for_each(|hash| distinct.insert(hash)),
+            }
+            return Ok(distinct);
+        }
+
+        // No vocabulary for this field, read values from payload
+        let mut distinct = DistinctValues::estimated();
+        for idx in points {
+            let payload = payload_index.payload_fields(idx, &[key.first_key.as_str()])?;
+            for value in payload.get_value(key) {
+                match value {
+                    Value::Array(values) => values
+                        .iter()
+                        .filter_map(hash_payload_value)
+                        .for_each(|hash| distinct.insert(hash)),
+                    value => {
+                        if let Some(hash) = hash_payload_value(value) {
+                            distinct.insert(hash);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(distinct)
+    }
+
+    fn value_distribution(&self, key: &JsonPath) -> OperationResult<ValueDistribution> {
+        let payload_index = self.payload_index.borrow();
+        payload_index
+            .field_indexes
+            .get(key)
+            .and_then(|indexes| indexes.iter().find_map(|index| index.as_numeric()))
+            .map(|numeric_index| numeric_index.value_distribution())
+            .ok_or_else(|| OperationError::MissingRangeIndexForHistogram {
+                key: key.to_string(),
+            })
+    }
+
+    fn numeric_stats(
+        &self,
+        key: &JsonPath,
+        filter: Option<&Filter>,
+    ) -> OperationResult<NumericStats> {
+        let payload_index = self.payload_index.borrow();
+        let id_tracker = self.id_tracker.borrow();
+
+        let numeric_index = payload_index
+            .field_indexes
+            .get(key)
+            .and_then(|indexes| indexes.iter().find_map(|index| index.as_numeric()))
+            .ok_or_else(|| OperationError::MissingRangeIndexForStats {
+                key: key.to_string(),
+            })?;
+
+        let points: Box<dyn Iterator<Item = PointOffsetType> + '_> = match filter {
+            Some(filter) => Box::new(payload_index.query_points(filter).into_iter()),
+            None => id_tracker.iter_ids(),
+        };
+
+        let values = points
+            .flat_map(|idx| numeric_index.get_float_values(idx))
+            .collect();
+        Ok(NumericStats::from_values(values))
+    }
+
     fn segment_type(&self) -> SegmentType {
         self.segment_type
     }
@@ -1488,14 +2075,32 @@ impl SegmentEntry for Segment {
             num_indexed_vectors,
             num_points: self.available_point_count(),
             num_deleted_vectors: self.deleted_point_count(),
-            ram_usage_bytes: 0,  // ToDo: Implement
-            disk_usage_bytes: 0, // ToDo: Implement
+            ram_usage_bytes: 0,  // Only measured by `size_info`
+            disk_usage_bytes: 0, // Only measured by `size_info`
             is_appendable: self.appendable_flag,
             index_schema: schema,
             vector_data: vector_data_info,
         }
     }
 
+    fn size_info(&self) -> SegmentInfo {
+        let file_size = |path: &PathBuf| fs::metadata(path).map_or(0, |meta| meta.len() as usize);
+        let ram_usage_bytes = self.ram_resident_files().iter().map(file_size).sum();
+        let disk_usage_bytes = fs_extra::dir::get_size(&self.current_path).unwrap_or_else(|err| {
+            log::warn!(
+                "Failed to get disk usage of segment {}: {err}",
+                self.current_path.display(),
+            );
+            0
+        }) as usize;
+
+        SegmentInfo {
+            ram_usage_bytes,
+            disk_usage_bytes,
+            ..self.info()
+        }
+    }
+
     fn config(&self) -> &SegmentConfig {
         &self.segment_config
     }
@@ -1504,6 +2109,14 @@ impl SegmentEntry for Segment {
         self.appendable_flag
     }
 
+    fn is_vector_appendable(&self, vector_name: &str) -> bool {
+        self.segment_config.is_vector_appendable(vector_name)
+            && self
+                .vector_data
+                .get(vector_name)
+                .is_some_and(|vector_data| vector_data.vector_index.borrow().can_update_in_place())
+    }
+
     fn flush(&self, sync: bool) -> OperationResult<SeqNumberType> {
         let current_persisted_version: Option<SeqNumberType> = *self.persisted_version.lock();
         if !sync && self.is_background_flushing() {
@@ -1530,12 +2143,20 @@ impl SegmentEntry for Segment {
             .values()
             .map(|v| v.vector_storage.borrow().flusher())
             .collect();
+        let vector_index_flushers: Vec<_> = self
+            .vector_data
+            .values()
+            .map(|v| v.vector_index.borrow().flusher())
+            .collect();
         let state = self.get_state();
         let current_path = self.current_path.clone();
         let id_tracker_mapping_flusher = self.id_tracker.borrow().mapping_flusher();
         let payload_index_flusher = self.payload_index.borrow().flusher();
+        let attribute_storage_flusher = self.attribute_storage.borrow().flusher();
         let id_tracker_versions_flusher = self.id_tracker.borrow().versions_flusher();
         let persisted_version = self.persisted_version.clone();
+        let component_files = self.fixed_component_files();
+        let field_index_files = self.payload_index.borrow().field_index_files()?;
 
         // Flush order is important:
         //
@@ -1594,9 +2215,17 @@ impl SegmentEntry for Segment {
                     OperationError::service_error(format!("Failed to flush vector_storage: {err}"))
                 })?;
             }
+            for vector_index_flusher in vector_index_flushers {
+                vector_index_flusher().map_err(|err| {
+                    OperationError::service_error(format!("Failed to flush vector_index: {err}"))
+                })?;
+            }
             payload_index_flusher().map_err(|err| {
                 OperationError::service_error(format!("Failed to flush payload_index: {err}"))
             })?;
+            attribute_storage_flusher().map_err(|err| {
+                OperationError::service_error(format!("Failed to flush attribute_storage: {err}"))
+            })?;
             // Id Tracker contains versions of points. We need to flush it after vector_storage and payload_index flush.
             // This is because vector_storage and payload_index flush are not atomic.
             // If payload or vector flush fails, we will be able to recover data from WAL.
@@ -1609,6 +2238,17 @@ impl SegmentEntry for Segment {
             Self::save_state(&state, &current_path).map_err(|err| {
                 OperationError::service_error(format!("Failed to flush segment state: {err}"))
             })?;
+            // Manifest is saved once all files are flushed, so it lists a complete set of files
+            SegmentManifest::collect(
+                &current_path,
+                state.version,
+                component_files,
+                field_index_files,
+            )
+            .and_then(|manifest| manifest.save(&current_path))
+            .map_err(|err| {
+                OperationError::service_error(format!("Failed to flush segment manifest: {err}"))
+            })?;
             *persisted_version.lock() = state.version;
 
             debug_assert!(state.version.is_some());
@@ -1647,6 +2287,10 @@ impl SegmentEntry for Segment {
         self.current_path.clone()
     }
 
+    fn created_at(&self) -> Option<DateTime<Utc>> {
+        self.created_at
+    }
+
     fn delete_field_index(&mut self, op_num: u64, key: PayloadKeyTypeRef) -> OperationResult<bool> {
         self.handle_segment_version_and_failure(op_num, |segment| {
             segment.payload_index.borrow_mut().drop_index(key)?;
@@ -1762,7 +2406,7 @@ impl SegmentEntry for Segment {
             ))
         })?;
 
-        let mut builder = Builder::new(file);
+        let mut builder = Builder::new(ThrottledWriter::new(file, BackgroundIo::Snapshot));
 
         builder
             .append_dir_all(SNAPSHOT_PATH, &temp_path)
@@ -1770,38 +2414,9 @@ impl SegmentEntry for Segment {
 
         let files = Path::new(SNAPSHOT_PATH).join(SNAPSHOT_FILES_PATH);
 
-        for vector_data in self.vector_data.values() {
-            for file in vector_data.vector_index.borrow().files() {
-                utils::tar::append_file_relative_to_base(
-                    &mut builder,
-                    &self.current_path,
-                    &file,
-                    &files,
-                )?;
-            }
-
-            for file in vector_data.vector_storage.borrow().files() {
-                utils::tar::append_file_relative_to_base(
-                    &mut builder,
-                    &self.current_path,
-                    &file,
-                    &files,
-                )?;
-            }
-
-            if let Some(quantized_vectors) = vector_data.quantized_vectors.borrow().as_ref() {
-                for file in quantized_vectors.files() {
-                    utils::tar::append_file_relative_to_base(
-                        &mut builder,
-                        &self.current_path,
-                        &file,
-                        &files,
-                    )?;
-                }
-            }
-        }
-
-        for file in self.payload_index.borrow().files() {
+        let component_files = self.fixed_component_files();
+        let payload_index_files = self.payload_index.borrow().files();
+        for file in component_files.into_iter().chain(payload_index_files) {
             utils::tar::append_file_relative_to_base(
                 &mut builder,
                 &self.current_path,
@@ -1816,6 +2431,15 @@ impl SegmentEntry for Segment {
             &files.join(SEGMENT_STATE_FILE),
         )?;
 
+        let storage_format_path = self.current_path.join(STORAGE_FORMAT_FILE);
+        if storage_format_path.exists() {
+            utils::tar::append_file(
+                &mut builder,
+                &storage_format_path,
+                &files.join(STORAGE_FORMAT_FILE),
+            )?;
+        }
+
         utils::tar::append_file(
             &mut builder,
             &self.current_path.join(VERSION_FILE),
@@ -1866,6 +2490,46 @@ impl SegmentEntry for Segment {
             }
         }
     }
+
+    fn fill_sparse_document_frequencies(
+        &self,
+        vector_name: &str,
+        frequencies: &mut HashMap<DimId, usize>,
+    ) -> usize {
+        if let Some(vector_data) = self.vector_data.get(vector_name) {
+            vector_data
+                .vector_index
+                .borrow()
+                .fill_document_frequencies(frequencies);
+        }
+        self.available_point_count()
+    }
+}
+
+/// Select and sort the first `limit` pairs of the ordering.
+///
+/// If the ordering has `then_by` keys, pairs sharing the value of the last one are included too,
+/// as their order is only known after reading the other keys.
+fn select_ordered_page(
+    order_by: &OrderBy,
+    values_ids: impl Iterator<Item = (OrderValue, PointIdType)>,
+    limit: Option<usize>,
+) -> Vec<(OrderValue, PointIdType)> {
+    let with_ties = !order_by.then_by().is_empty();
+    let mut page = match (limit, order_by.direction()) {
+        (Some(limit), Direction::Asc) if !with_ties => {
+            peek_top_smallest_iterable(values_ids, limit)
+        }
+        (Some(limit), Direction::Desc) if !with_ties => {
+            peek_top_largest_iterable(values_ids, limit)
+        }
+        _ => values_ids.collect(),
+    };
+    order_by.sort_values_ids(&mut page);
+    if let Some(limit) = limit {
+        OrderBy::truncate_with_ties(&mut page, limit);
+    }
+    page
 }
 
 impl Drop for Segment {
@@ -1885,6 +2549,7 @@ mod tests {
     use super::*;
     use crate::common::check_vector;
     use crate::common::operation_error::OperationError::PointIdError;
+    use crate::data_types::order_by::OrderByCursor;
     use crate::data_types::vectors::{only_default_vector, DEFAULT_VECTOR_NAME};
     use crate::segment_constructor::{build_segment, load_segment};
     use crate::types::{Distance, Indexes, SegmentConfig, VectorDataConfig, VectorStorageType};
@@ -1942,6 +2607,8 @@ mod tests {
             )]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_columns: Default::default(),
+            payload_compression: Default::default(),
         };
         let mut segment = build_segment(dir.path(), &config, true).unwrap();
 
@@ -2016,6 +2683,8 @@ mod tests {
             )]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_columns: Default::default(),
+            payload_compression: Default::default(),
         };
 
         let mut segment = build_segment(dir.path(), &config, true).unwrap();
@@ -2080,6 +2749,142 @@ mod tests {
         assert!(results_with_invalid_filter.is_empty());
     }
 
+    #[test]
+    fn test_patch_payload() {
+        let dir = Builder::new().prefix("payload_dir").tempdir().unwrap();
+        let config = SegmentConfig {
+            vector_data: HashMap::from([(
+                DEFAULT_VECTOR_NAME.to_owned(),
+                VectorDataConfig {
+                    size: 2,
+                    distance: Distance::Dot,
+                    storage_type: VectorStorageType::Memory,
+                    index: Indexes::Plain {},
+                    quantization_config: None,
+                    multivec_config: None,
+                    datatype: None,
+                },
+            )]),
+            sparse_vector_data: Default::default(),
+            payload_storage_type: Default::default(),
+            payload_columns: Default::default(),
+            payload_compression: Default::default(),
+        };
+
+        let mut segment = build_segment(dir.path(), &config, true).unwrap();
+        segment
+            .upsert_point(0, 0.into(), only_default_vector(&[1.0, 1.0]))
+            .unwrap();
+
+        let height_key: JsonPath = "metadata.height".parse().unwrap();
+        segment
+            .create_field_index(1, &height_key, Some(&PayloadSchemaType::Integer.into()))
+            .unwrap();
+
+        let payload: Payload = serde_json::from_str(
+            r#"{ "name": "John Doe", "metadata": { "height": 50, "width": 60 } }"#,
+        )
+        .unwrap();
+        segment.set_full_payload(2, 0.into(), &payload).unwrap();
+
+        let patch: Payload = serde_json::from_str(
+            r#"{ "name": null, "metadata": { "height": 55, "width": null } }"#,
+        )
+        .unwrap();
+        segment.patch_payload(3, 0.into(), &patch).unwrap();
+
+        let expected: Payload =
+            serde_json::from_str(r#"{ "metadata": { "height": 55 } }"#).unwrap();
+        assert_eq!(segment.payload(0.into()).unwrap(), expected);
+
+        // Field index is updated with the patched value
+        let filter = |height: i64| {
+            Filter::new_must(crate::types::Condition::Field(
+                crate::types::FieldCondition::new_match(height_key.clone(), height.into()),
+            ))
+        };
+        assert!(segment
+            .read_filtered(None, None, Some(&filter(50)))
+            .is_empty());
+        assert_eq!(
+            segment.read_filtered(None, None, Some(&filter(55))),
+            vec![0.into()],
+        );
+    }
+
+    #[test]
+    fn test_read_ordered_with_common_value() {
+        let dir = Builder::new().prefix("payload_dir").tempdir().unwrap();
+        let config = SegmentConfig {
+            vector_data: HashMap::from([(
+                DEFAULT_VECTOR_NAME.to_owned(),
+                VectorDataConfig {
+                    size: 2,
+                    distance: Distance::Dot,
+                    storage_type: VectorStorageType::Memory,
+                    index: Indexes::Plain {},
+                    quantization_config: None,
+                    multivec_config: None,
+                    datatype: None,
+                },
+            )]),
+            sparse_vector_data: Default::default(),
+            payload_storage_type: Default::default(),
+            payload_columns: Default::default(),
+            payload_compression: Default::default(),
+        };
+
+        let mut segment = build_segment(dir.path(), &config, true).unwrap();
+        let key: JsonPath = "rank".parse().unwrap();
+        segment
+            .create_field_index(0, &key, Some(&PayloadSchemaType::Integer.into()))
+            .unwrap();
+
+        // Internal ids are in the reverse order of external ids
+        let mut op_num = 1;
+        for (id, rank) in (1..=10u64).rev().map(|id| (id, 7)).chain([(20, 1)]) {
+            segment
+                .upsert_point(op_num, id.into(), only_default_vector(&[1.0, 1.0]))
+                .unwrap();
+            let payload: Payload =
+                serde_json::from_value(serde_json::json!({ "rank": rank })).unwrap();
+            segment
+                .set_full_payload(op_num + 1, id.into(), &payload)
+                .unwrap();
+            op_num += 2;
+        }
+
+        let order_by = OrderBy {
+            key,
+            direction: None,
+            start_from: None,
+            geo: None,
+            then_by: None,
+            start_after_id: None,
+            start_after_then_by: vec![],
+        };
+        let read_ids = |order_by: &OrderBy| {
+            segment
+                .read_ordered_filtered(Some(3), None, order_by)
+                .unwrap()
+                .into_iter()
+                .map(|point| point.id)
+                .collect::<Vec<_>>()
+        };
+
+        // Page is limited, even if the value of the last point is shared by more points
+        let ids = [20u64, 1, 2].map(PointIdType::from).to_vec();
+        assert_eq!(read_ids(&order_by), ids);
+
+        let cursor = OrderByCursor {
+            value: OrderValue::Int(7),
+            then_by: vec![],
+            id: 2.into(),
+        };
+        let ids = [3u64, 4, 5].map(PointIdType::from).to_vec();
+        assert_eq!(read_ids(&order_by.with_cursor(&cursor)), ids);
+    }
+
     #[test]
     fn test_snapshot() {
         let data = r#"
@@ -2108,6 +2913,8 @@ mod tests {
             )]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_columns: Default::default(),
+            payload_compression: Default::default(),
         };
 
         let mut segment = build_segment(segment_base_dir.path(), &config, true).unwrap();
@@ -2204,6 +3011,8 @@ mod tests {
             )]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_columns: Default::default(),
+            payload_compression: Default::default(),
         };
 
         let mut segment = build_segment(segment_base_dir.path(), &config, true).unwrap();
@@ -2238,6 +3047,8 @@ mod tests {
             )]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_columns: Default::default(),
+            payload_compression: Default::default(),
         };
         let mut segment = build_segment(dir.path(), &config, true).unwrap();
 
@@ -2333,6 +3144,8 @@ mod tests {
             )]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_columns: Default::default(),
+            payload_compression: Default::default(),
         };
         let mut segment = build_segment(dir.path(), &config, true).unwrap();
 
@@ -2401,6 +3214,8 @@ mod tests {
             ]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_columns: Default::default(),
+            payload_compression: Default::default(),
         };
         let mut segment = build_segment(dir.path(), &config, true).unwrap();
 
@@ -2518,6 +3333,8 @@ mod tests {
             ]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_columns: Default::default(),
+            payload_compression: Default::default(),
         };
         let mut segment = build_segment(dir.path(), &config, true).unwrap();
 
@@ -2679,6 +3496,8 @@ mod tests {
             )]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_columns: Default::default(),
+            payload_compression: Default::default(),
         };
         let mut segment = build_segment(dir.path(), &config, true).unwrap();
         segment
@@ -0,0 +1,419 @@
+//! Versioned migrations of the storage format of segment components
+//!
+//! Each component of a segment has its own format version, stored in [`STORAGE_FORMAT_FILE`].
+//! A migration converts one component to the next format version. Migrations are applied in
+//! order on load, so that components are only read in their current format.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use io::file_operations::{atomic_save_json, read_json};
+use io::storage_version::StorageVersion as _;
+use semver::Version;
+use serde::{Deserialize, Serialize};
+
+use super::{get_vector_index_path, open_segment_db, ID_TRACKER_PATH};
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::common::rocksdb_wrapper::{DatabaseColumnWrapper, DB_MAPPING_CF, DB_VERSIONS_CF};
+use crate::data_types::vectors::DEFAULT_VECTOR_NAME;
+use crate::id_tracker::mmap_id_tracker::MmapIdTracker;
+use crate::id_tracker::simple_id_tracker::SimpleIdTracker;
+use crate::id_tracker::IdTracker as _;
+use crate::index::hnsw_index::graph_layers::{GraphLayers, GraphLayersBackwardCompatibility};
+use crate::index::hnsw_index::graph_links::GraphLinksRam;
+use crate::segment::{Segment, SegmentVersion, SEGMENT_STATE_FILE};
+use crate::types::{Distance, Indexes, PayloadStorageType, SegmentState, SeqNumberType};
+
+pub const STORAGE_FORMAT_FILE: &str = "storage_format.json";
+
+/// Number of segments, migrated by this process, for progress reporting
+static MIGRATED_SEGMENTS: AtomicUsize = AtomicUsize::new(0);
+
+/// Component of a segment, which has its own storage format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SegmentComponent {
+    State,
+    IdTracker,
+    VectorIndex,
+}
+
+impl fmt::Display for SegmentComponent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            SegmentComponent::State => "segment state",
+            SegmentComponent::IdTracker => "id tracker",
+            SegmentComponent::VectorIndex => "vector index",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Conversion of a segment component to the next version of its storage format
+pub struct SegmentMigration {
+    pub component: SegmentComponent,
+    /// Format version of the component after the migration
+    pub version: u32,
+    pub description: &'static str,
+    /// Whether the segment, stored by the given application version, needs the migration
+    is_required: fn(&Path, &Version) -> OperationResult<bool>,
+    /// Convert the component. Must be safe to repeat, if the process stops midway.
+    apply: fn(&Path, &Version) -> OperationResult<()>,
+}
+
+/// All migrations, in the order they are applied
+pub static SEGMENT_MIGRATIONS: &[SegmentMigration] = &[
+    SegmentMigration {
+        component: SegmentComponent::State,
+        version: 1,
+        description: "convert segment state of v0.3 - v0.5 to the current format",
+        is_required: |path, stored_version| {
+            Ok(stored_version.major == 0
+                && stored_version.minor <= 5
+                && Segment::load_state(path).is_err())
+        },
+        apply: |path, stored_version| {
+            let segment_state = load_segment_state(path, stored_version)?;
+            Segment::save_state(&segment_state, path)
+        },
+    },
+    SegmentMigration {
+        component: SegmentComponent::IdTracker,
+        version: 1,
+        description: "move point ids and versions out of RocksDB into the mmap id tracker",
+        is_required: |path, _| Ok(!MmapIdTracker::exists(&path.join(ID_TRACKER_PATH))),
+        apply: |path, _| {
+            let config = Segment::load_state(path)?.config;
+            let database = open_segment_db(path, &config)?;
+            let simple_id_tracker = SimpleIdTracker::open(database.clone())?;
+            log::debug!(
+                "Moving {} points of segment {} out of RocksDB",
+                simple_id_tracker.total_point_count(),
+                path.display(),
+            );
+            MmapIdTracker::migrate_from(&simple_id_tracker, &path.join(ID_TRACKER_PATH))?;
+            for column_family in [DB_MAPPING_CF, DB_VERSIONS_CF] {
+                DatabaseColumnWrapper::new(database.clone(), column_family)
+                    .recreate_column_family()?;
+            }
+            Ok(())
+        },
+    },
+    SegmentMigration {
+        component: SegmentComponent::VectorIndex,
+        version: 1,
+        description: "move links of legacy HNSW graphs into a separate file",
+        is_required: |path, stored_version| {
+            Ok(!legacy_hnsw_graphs(path, stored_version)?.is_empty())
+        },
+        apply: |path, stored_version| {
+            for vector_index_path in legacy_hnsw_graphs(path, stored_version)? {
+                GraphLayersBackwardCompatibility::convert(
+                    &GraphLayers::<GraphLinksRam>::get_path(&vector_index_path),
+                    &GraphLayers::<GraphLinksRam>::get_links_path(&vector_index_path),
+                )?;
+            }
+            Ok(())
+        },
+    },
+];
+
+/// Format versions of the components of a segment
+///
+/// Components, which are missing in the file, have version 0. Segments, created before the
+/// versions were stored, are checked for each migration.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageFormat(BTreeMap<SegmentComponent, u32>);
+
+impl StorageFormat {
+    /// Formats, segments are created with by this version of the application
+    pub fn current() -> Self {
+        let mut format = Self::default();
+        for migration in SEGMENT_MIGRATIONS {
+            format.set(migration.component, migration.version);
+        }
+        format
+    }
+
+    pub fn load(segment_path: &Path) -> OperationResult<Self> {
+        let path = segment_path.join(STORAGE_FORMAT_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(read_json(&path)?)
+    }
+
+    pub fn save(&self, segment_path: &Path) -> OperationResult<()> {
+        Ok(atomic_save_json(
+            &segment_path.join(STORAGE_FORMAT_FILE),
+            self,
+        )?)
+    }
+
+    pub fn version(&self, component: SegmentComponent) -> u32 {
+        self.0.get(&component).copied().unwrap_or(0)
+    }
+
+    fn set(&mut self, component: SegmentComponent, version: u32) {
+        let stored = self.0.entry(component).or_default();
+        *stored = (*stored).max(version);
+    }
+}
+
+/// Check, that the segment, stored by the given application version, can be loaded
+pub fn check_segment_version(stored_version: &Version) -> OperationResult<()> {
+    let app_version = SegmentVersion::current();
+
+    if *stored_version > app_version {
+        return Err(OperationError::service_error(format!(
+            "Data version {stored_version} is newer than application version {app_version}. \
+            Please upgrade the application. Compatibility is not guaranteed."
+        )));
+    }
+
+    if stored_version.major == 0 && stored_version.minor < 3 {
+        return Err(OperationError::service_error(format!(
+            "Segment version({stored_version}) is not compatible with current version({app_version})"
+        )));
+    }
+
+    Ok(())
+}
+
+/// Migrations, the segment needs before it can be loaded, in the order they are applied
+///
+/// Only reads the segment, so it can be used to check the storage before an upgrade.
+pub fn pending_migrations(
+    segment_path: &Path,
+    stored_version: &Version,
+) -> OperationResult<Vec<&'static SegmentMigration>> {
+    let stored_format = StorageFormat::load(segment_path)?;
+    let mut pending = Vec::new();
+    for migration in SEGMENT_MIGRATIONS {
+        if migration.version > stored_format.version(migration.component)
+            && (migration.is_required)(segment_path, stored_version)?
+        {
+            pending.push(migration);
+        }
+    }
+    Ok(pending)
+}
+
+/// Apply pending migrations to the segment, and store the current format versions
+///
+/// Format version of a component is stored after each migration, so an interrupted migration
+/// continues with the next one.
+pub fn migrate_segment(segment_path: &Path, stored_version: &Version) -> OperationResult<()> {
+    let current_format = StorageFormat::current();
+    let mut stored_format = StorageFormat::load(segment_path)?;
+    if stored_format == current_format {
+        return Ok(());
+    }
+
+    let pending = pending_migrations(segment_path, stored_version)?;
+    for (i, migration) in pending.iter().enumerate() {
+        log::info!(
+            "Migrating {} of segment {} ({}/{}): {}",
+            migration.component,
+            segment_path.display(),
+            i + 1,
+            pending.len(),
+            migration.description,
+        );
+        (migration.apply)(segment_path, stored_version)?;
+        stored_format.set(migration.component, migration.version);
+        stored_format.save(segment_path)?;
+    }
+
+    if !pending.is_empty() {
+        let migrated = MIGRATED_SEGMENTS.fetch_add(1, Ordering::Relaxed) + 1;
+        log::info!("Migrated {migrated} segments so far");
+    }
+
+    current_format.save(segment_path)
+}
+
+/// Vector index directories of the segment with HNSW graphs in the legacy format
+fn legacy_hnsw_graphs(
+    segment_path: &Path,
+    stored_version: &Version,
+) -> OperationResult<Vec<PathBuf>> {
+    let config = load_segment_state(segment_path, stored_version)?.config;
+    let paths = config
+        .vector_data
+        .iter()
+        .filter(|(_, vector_config)| matches!(vector_config.index, Indexes::Hnsw(_)))
+        .map(|(vector_name, _)| get_vector_index_path(segment_path, vector_name))
+        .filter(|vector_index_path| {
+            GraphLayers::<GraphLinksRam>::get_path(vector_index_path).exists()
+                && !GraphLayers::<GraphLinksRam>::get_links_path(vector_index_path).exists()
+        })
+        .collect();
+    Ok(paths)
+}
+
+/// Load segment state, stored in any supported format
+fn load_segment_state(
+    segment_path: &Path,
+    stored_version: &Version,
+) -> OperationResult<SegmentState> {
+    let is_legacy = stored_version.major == 0 && stored_version.minor <= 5;
+    match Segment::load_state(segment_path) {
+        Ok(state) => Ok(state),
+        Err(_) if is_legacy && stored_version.minor == 3 => load_segment_state_v3(segment_path),
+        Err(_) if is_legacy => load_segment_state_v5(segment_path),
+        Err(err) => Err(err),
+    }
+}
+
+/// Load v0.3.* segment data and migrate to current version
+#[allow(deprecated)]
+fn load_segment_state_v3(segment_path: &Path) -> OperationResult<SegmentState> {
+    use crate::compat::{SegmentConfigV5, StorageTypeV5, VectorDataConfigV5};
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    #[deprecated]
+    pub struct SegmentStateV3 {
+        pub version: SeqNumberType,
+        pub config: SegmentConfigV3,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    #[deprecated]
+    pub struct SegmentConfigV3 {
+        /// Size of a vectors used
+        pub vector_size: usize,
+        /// Type of distance function used for measuring distance between vectors
+        pub distance: Distance,
+        /// Type of index used for search
+        pub index: Indexes,
+        /// Type of vector storage
+        pub storage_type: StorageTypeV5,
+        /// Defines payload storage type
+        #[serde(default)]
+        pub payload_storage_type: PayloadStorageType,
+    }
+
+    let path = segment_path.join(SEGMENT_STATE_FILE);
+
+    let mut contents = String::new();
+
+    let mut file = File::open(&path)?;
+    file.read_to_string(&mut contents)?;
+
+    serde_json::from_str::<SegmentStateV3>(&contents)
+        .map(|state| {
+            // Construct V5 version, then convert into current
+            let vector_data = VectorDataConfigV5 {
+                size: state.config.vector_size,
+                distance: state.config.distance,
+                hnsw_config: None,
+                quantization_config: None,
+                on_disk: None,
+            };
+            let segment_config = SegmentConfigV5 {
+                vector_data: HashMap::from([(DEFAULT_VECTOR_NAME.to_owned(), vector_data)]),
+                index: state.config.index,
+                storage_type: state.config.storage_type,
+                payload_storage_type: state.config.payload_storage_type,
+                quantization_config: None,
+            };
+
+            SegmentState {
+                version: Some(state.version),
+                config: segment_config.into(),
+                created_at: None,
+            }
+        })
+        .map_err(|err| {
+            OperationError::service_error(format!(
+                "Failed to read segment {}. Error: {}",
+                path.to_str().unwrap(),
+                err
+            ))
+        })
+}
+
+/// Load v0.5.0 segment data and migrate to current version
+#[allow(deprecated)]
+fn load_segment_state_v5(segment_path: &Path) -> OperationResult<SegmentState> {
+    use crate::compat::SegmentStateV5;
+
+    let path = segment_path.join(SEGMENT_STATE_FILE);
+
+    let mut contents = String::new();
+
+    let mut file = File::open(&path)?;
+    file.read_to_string(&mut contents)?;
+
+    serde_json::from_str::<SegmentStateV5>(&contents)
+        .map(Into::into)
+        .map_err(|err| {
+            OperationError::service_error(format!(
+                "Failed to read segment {}. Error: {}",
+                path.to_str().unwrap(),
+                err
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::id_tracker::IdTracker;
+    use crate::segment_constructor::simple_segment_constructor::build_simple_segment;
+
+    #[test]
+    fn test_migrate_id_tracker() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let segment = build_simple_segment(dir.path(), 4, Distance::Dot).unwrap();
+        let segment_path = segment.current_path.clone();
+        drop(segment);
+
+        let version = SegmentVersion::current();
+        assert_eq!(
+            StorageFormat::load(&segment_path).unwrap(),
+            StorageFormat::current(),
+        );
+        assert!(pending_migrations(&segment_path, &version)
+            .unwrap()
+            .is_empty());
+
+        // Segment, created before format versions were stored, with ids in RocksDB
+        std::fs::remove_file(segment_path.join(STORAGE_FORMAT_FILE)).unwrap();
+        std::fs::remove_dir_all(segment_path.join(ID_TRACKER_PATH)).unwrap();
+        {
+            let config = Segment::load_state(&segment_path).unwrap().config;
+            let database = open_segment_db(&segment_path, &config).unwrap();
+            let mut simple_id_tracker = SimpleIdTracker::open(database).unwrap();
+            simple_id_tracker.set_link(100.into(), 0).unwrap();
+            simple_id_tracker.set_internal_version(0, 5).unwrap();
+        }
+
+        let pending = pending_migrations(&segment_path, &version).unwrap();
+        assert_eq!(
+            pending
+                .iter()
+                .map(|migration| migration.component)
+                .collect::<Vec<_>>(),
+            vec![SegmentComponent::IdTracker],
+        );
+
+        migrate_segment(&segment_path, &version).unwrap();
+        assert_eq!(
+            StorageFormat::load(&segment_path).unwrap(),
+            StorageFormat::current(),
+        );
+        let id_tracker = MmapIdTracker::open(&segment_path.join(ID_TRACKER_PATH)).unwrap();
+        assert_eq!(id_tracker.internal_id(100.into()), Some(0));
+        assert_eq!(id_tracker.internal_version(0), Some(5));
+    }
+}
@@ -1,3 +1,4 @@
+pub mod migrations;
 pub mod segment_builder;
 mod segment_constructor_base;
 pub mod simple_segment_constructor;
@@ -5,15 +5,21 @@ use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
 use atomic_refcell::AtomicRefCell;
+use chrono::Utc;
 use common::cpu::CpuPermit;
+use common::io_throttling::BackgroundIo;
+use common::types::PointOffsetType;
 use io::storage_version::StorageVersion;
+use parking_lot::RwLock;
+use rocksdb::DB;
 
 use super::{
     create_id_tracker, create_payload_storage, create_sparse_vector_index,
-    create_sparse_vector_storage, create_vector_index, get_payload_index_path,
-    get_vector_index_path, get_vector_storage_path, new_segment_path, open_segment_db,
-    open_vector_storage,
+    create_sparse_vector_storage, create_vector_index, get_builder_vector_storage_path,
+    get_payload_index_path, get_vector_index_path, get_vector_storage_path, new_segment_path,
+    open_segment_db, open_vector_storage, write_in_memory_vector_storage,
 };
+use crate::attribute_storage::AttributeStorage;
 use crate::common::error_logging::LogError;
 use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
 use crate::entry::entry_point::SegmentEntry;
@@ -21,12 +27,15 @@ use crate::id_tracker::{IdTracker, IdTrackerEnum};
 use crate::index::sparse_index::sparse_vector_index::SparseVectorIndexOpenArgs;
 use crate::index::struct_payload_index::StructPayloadIndex;
 use crate::index::{PayloadIndex, VectorIndex};
+use crate::payload_storage::on_disk_payload_storage::OnDiskPayloadStorage;
 use crate::payload_storage::payload_storage_enum::PayloadStorageEnum;
 use crate::payload_storage::PayloadStorage;
 use crate::segment::{Segment, SegmentVersion};
 use crate::segment_constructor::load_segment;
+use crate::segment_constructor::migrations::StorageFormat;
 use crate::types::{
-    PayloadFieldSchema, PayloadKeyType, SegmentConfig, SegmentState, SeqNumberType,
+    PayloadFieldSchema, PayloadKeyType, PayloadStorageType, SegmentConfig, SegmentState,
+    SeqNumberType, VectorDataConfig, VectorStorageType,
 };
 use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
 use crate::vector_storage::{VectorStorage, VectorStorageEnum};
@@ -36,8 +45,10 @@ pub struct SegmentBuilder {
     version: SeqNumberType,
     id_tracker: IdTrackerEnum,
     payload_storage: PayloadStorageEnum,
+    attribute_storage: AttributeStorage,
     vector_storages: HashMap<String, VectorStorageEnum>,
     segment_config: SegmentConfig,
+    database: Arc<RwLock<DB>>,
 
     // The path, where fully created segment will be moved
     destination_path: PathBuf,
@@ -60,20 +71,22 @@ impl SegmentBuilder {
 
         let database = open_segment_db(&temp_path, segment_config)?;
 
-        let id_tracker = create_id_tracker(database.clone())?;
+        let id_tracker = create_id_tracker(&temp_path)?;
 
-        let payload_storage = create_payload_storage(database.clone(), segment_config)?;
+        let payload_storage =
+            Self::create_builder_payload_storage(database.clone(), segment_config, &temp_path)?;
+
+        let attribute_storage = AttributeStorage::open(&temp_path)?;
 
         let mut vector_storages = HashMap::new();
 
         for (vector_name, vector_config) in &segment_config.vector_data {
-            let vector_storage_path = get_vector_storage_path(&temp_path, vector_name);
-            let vector_storage = open_vector_storage(
+            let vector_storage = Self::open_builder_vector_storage(
                 &database,
                 vector_config,
-                &stopped,
-                &vector_storage_path,
+                &temp_path,
                 vector_name,
+                &stopped,
             )?;
 
             vector_storages.insert(vector_name.to_owned(), vector_storage);
@@ -95,8 +108,10 @@ impl SegmentBuilder {
             version: Default::default(), // default version is 0
             id_tracker,
             payload_storage,
+            attribute_storage,
             vector_storages,
             segment_config: segment_config.clone(),
+            database,
 
             destination_path,
             temp_path,
@@ -135,6 +150,7 @@ impl SegmentBuilder {
             })
             .collect();
         let other_payload_index = other.payload_index.borrow();
+        let other_attribute_storage = other.attribute_storage.borrow();
 
         let id_tracker = &mut self.id_tracker;
 
@@ -152,9 +168,13 @@ impl SegmentBuilder {
                     "Cannot update from other segment because if missing vector name {vector_name}"
                 ))
             })?;
+            // Copying of vectors makes most of the IO of a segment rebuild
+            let vector_size = other_vector_storage.average_vector_size_in_bytes();
             let internal_range = vector_storage.update_from(
                 other_vector_storage,
-                &mut other_id_tracker.iter_ids(),
+                &mut other_id_tracker
+                    .iter_ids()
+                    .inspect(|_| BackgroundIo::Optimization.throttle(vector_size)),
                 stopped,
             )?;
             match new_internal_range.clone() {
@@ -205,6 +225,12 @@ impl SegmentBuilder {
                             self.payload_storage
                                 .assign(new_internal_id, &other_payload)?;
                         }
+                        Self::copy_attributes(
+                            &mut self.attribute_storage,
+                            &other_attribute_storage,
+                            old_internal_id,
+                            new_internal_id,
+                        )?;
                     }
                     Some(existing_internal_id) => {
                         // Point exists in both: newly constructed and old segments, so we need to merge them
@@ -217,12 +243,19 @@ impl SegmentBuilder {
                             id_tracker.set_link(external_id, new_internal_id)?;
                             id_tracker.set_internal_version(new_internal_id, other_version)?;
                             self.payload_storage.drop(existing_internal_id)?;
+                            self.attribute_storage.drop(existing_internal_id);
                             let other_payload = other_payload_index.payload(old_internal_id)?;
                             // Propagate payload to new segment
                             if !other_payload.is_empty() {
                                 self.payload_storage
                                     .assign(new_internal_id, &other_payload)?;
                             }
+                            Self::copy_attributes(
+                                &mut self.attribute_storage,
+                                &other_attribute_storage,
+                                old_internal_id,
+                                new_internal_id,
+                            )?;
                             existing_internal_id
                         } else {
                             // Old version is still good, do not move anything else
@@ -247,14 +280,33 @@ impl SegmentBuilder {
         Ok(true)
     }
 
+    fn copy_attributes(
+        attribute_storage: &mut AttributeStorage,
+        other: &AttributeStorage,
+        old_internal_id: PointOffsetType,
+        new_internal_id: PointOffsetType,
+    ) -> OperationResult<()> {
+        let attributes: HashMap<_, _> = other
+            .point_attributes(old_internal_id)
+            .into_iter()
+            .map(|(name, value)| (name, Some(value)))
+            .collect();
+        if attributes.is_empty() {
+            return Ok(());
+        }
+        attribute_storage.set(new_internal_id, &attributes)
+    }
+
     pub fn build(self, permit: CpuPermit, stopped: &AtomicBool) -> Result<Segment, OperationError> {
         let (temp_path, destination_path) = {
             let SegmentBuilder {
                 version,
                 id_tracker,
                 payload_storage,
+                attribute_storage,
                 mut vector_storages,
                 segment_config,
+                database,
                 destination_path,
                 temp_path,
                 indexed_fields,
@@ -263,6 +315,7 @@ impl SegmentBuilder {
             let appendable_flag = segment_config.is_appendable();
 
             payload_storage.flusher()()?;
+            attribute_storage.flusher()()?;
             let payload_storage_arc = Arc::new(AtomicRefCell::new(payload_storage));
 
             id_tracker.mapping_flusher()()?;
@@ -278,10 +331,7 @@ impl SegmentBuilder {
                 appendable_flag,
             )?;
 
-            for (field, payload_schema) in indexed_fields {
-                payload_index.set_indexed(&field, payload_schema)?;
-                check_process_stopped(stopped)?;
-            }
+            payload_index.build_indexes(indexed_fields, appendable_flag, stopped)?;
 
             payload_index.flusher()()?;
             let payload_index_arc = Arc::new(AtomicRefCell::new(payload_index));
@@ -317,12 +367,29 @@ impl SegmentBuilder {
                     vector_config,
                     &vector_index_path,
                     id_tracker_arc.clone(),
-                    vector_storage_arc,
+                    vector_storage_arc.clone(),
                     payload_index_arc.clone(),
                     quantized_vectors_arc,
                 )?;
 
                 vector_index.build_index(permit.clone(), stopped)?;
+                drop(vector_index);
+
+                if vector_config.storage_type == VectorStorageType::Memory {
+                    write_in_memory_vector_storage(
+                        &database,
+                        vector_config,
+                        vector_name,
+                        &vector_storage_arc.borrow(),
+                        stopped,
+                    )?;
+                    drop(vector_storage_arc);
+                    std::fs::remove_dir_all(get_builder_vector_storage_path(
+                        &temp_path,
+                        vector_name,
+                    ))
+                    .describe("Removing vectors of segment builder")?;
+                }
             }
 
             for (vector_name, sparse_vector_config) in &segment_config.sparse_vector_data {
@@ -363,10 +430,13 @@ impl SegmentBuilder {
                 &SegmentState {
                     version: Some(version),
                     config: segment_config,
+                    created_at: Some(Utc::now()),
                 },
                 &temp_path,
             )?;
 
+            StorageFormat::current().save(&temp_path)?;
+
             // After version is saved, segment can be loaded on restart
             SegmentVersion::save(&temp_path)?;
             // All temp data is evicted from RAM
@@ -386,6 +456,62 @@ impl SegmentBuilder {
         Ok(loaded_segment)
     }
 
+    /// Storage for vectors of the segment under construction
+    ///
+    /// Vectors of in-memory storage are kept in appendable mmap files while the segment is built,
+    /// and written in the format of in-memory storage once its indexes are built. So vectors are
+    /// not held in memory until the built segment is loaded.
+    fn open_builder_vector_storage(
+        database: &Arc<RwLock<DB>>,
+        vector_config: &VectorDataConfig,
+        temp_path: &Path,
+        vector_name: &str,
+        stopped: &AtomicBool,
+    ) -> OperationResult<VectorStorageEnum> {
+        match vector_config.storage_type {
+            VectorStorageType::Memory => {
+                let builder_config = VectorDataConfig {
+                    storage_type: VectorStorageType::ChunkedMmap,
+                    ..vector_config.clone()
+                };
+                open_vector_storage(
+                    database,
+                    &builder_config,
+                    stopped,
+                    &get_builder_vector_storage_path(temp_path, vector_name),
+                    vector_name,
+                )
+            }
+            VectorStorageType::Mmap | VectorStorageType::ChunkedMmap => open_vector_storage(
+                database,
+                vector_config,
+                stopped,
+                &get_vector_storage_path(temp_path, vector_name),
+                vector_name,
+            ),
+        }
+    }
+
+    /// Storage for payloads of the segment under construction
+    ///
+    /// Payloads are written once and read back once, to build payload indexes, so payloads
+    /// of in-memory storage are kept on disk until the built segment is loaded. Both storages
+    /// use the same format.
+    fn create_builder_payload_storage(
+        database: Arc<RwLock<DB>>,
+        segment_config: &SegmentConfig,
+        temp_path: &Path,
+    ) -> OperationResult<PayloadStorageEnum> {
+        match segment_config.payload_storage_type {
+            PayloadStorageType::InMemory => Ok(PayloadStorageEnum::from(
+                OnDiskPayloadStorage::open(database)?,
+            )),
+            PayloadStorageType::OnDisk | PayloadStorageType::Gridstore => {
+                create_payload_storage(database, segment_config, temp_path)
+            }
+        }
+    }
+
     fn update_quantization(
         segment_config: &SegmentConfig,
         vector_storages: &HashMap<String, VectorStorageEnum>,
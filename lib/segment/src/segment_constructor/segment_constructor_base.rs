@@ -1,23 +1,25 @@
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::Read;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
 use atomic_refcell::AtomicRefCell;
+use chrono::Utc;
 use io::storage_version::StorageVersion;
 use log::info;
+use memory::budget::{self, MemoryCategory};
 use parking_lot::{Mutex, RwLock};
 use rocksdb::DB;
-use serde::Deserialize;
 use uuid::Uuid;
 
+use super::migrations::{check_segment_version, migrate_segment, StorageFormat};
+use crate::attribute_storage::AttributeStorage;
 use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
 use crate::common::rocksdb_wrapper::{open_db, DB_VECTOR_CF};
-use crate::data_types::vectors::DEFAULT_VECTOR_NAME;
-use crate::id_tracker::simple_id_tracker::SimpleIdTracker;
+use crate::data_types::vectors::{VectorElementType, VectorElementTypeByte, VectorElementTypeHalf};
+use crate::id_tracker::mmap_id_tracker::MmapIdTracker;
 use crate::id_tracker::{IdTracker, IdTrackerEnum, IdTrackerSS};
+use crate::index::hnsw_index::graph_layers::GraphLayers;
 use crate::index::hnsw_index::graph_links::{GraphLinksMmap, GraphLinksRam};
 use crate::index::hnsw_index::hnsw::HNSWIndex;
 use crate::index::plain_payload_index::PlainIndex;
@@ -25,15 +27,17 @@ use crate::index::sparse_index::sparse_index_config::{SparseIndexType, SparseVec
 use crate::index::sparse_index::sparse_vector_index::{
     self, SparseVectorIndex, SparseVectorIndexOpenArgs,
 };
-use crate::index::struct_payload_index::StructPayloadIndex;
+use crate::index::struct_payload_index::{StructPayloadIndex, PAYLOAD_FIELD_INDEX_PATH};
 use crate::index::VectorIndexEnum;
+use crate::payload_storage::gridstore_payload_storage::GridstorePayloadStorage;
 use crate::payload_storage::on_disk_payload_storage::OnDiskPayloadStorage;
 use crate::payload_storage::payload_storage_enum::PayloadStorageEnum;
 use crate::payload_storage::simple_payload_storage::SimplePayloadStorage;
-use crate::segment::{Segment, SegmentVersion, VectorData, SEGMENT_STATE_FILE};
+use crate::segment::{Segment, SegmentVersion, VectorData};
+use crate::segment_manifest::SegmentManifest;
 use crate::types::{
-    Distance, Indexes, PayloadStorageType, SegmentConfig, SegmentState, SegmentType, SeqNumberType,
-    VectorDataConfig, VectorStorageDatatype, VectorStorageType,
+    Indexes, PayloadStorageType, SegmentConfig, SegmentType, SeqNumberType, VectorDataConfig,
+    VectorStorageDatatype, VectorStorageType,
 };
 use crate::vector_storage::dense::appendable_mmap_dense_vector_storage::{
     open_appendable_memmap_vector_storage, open_appendable_memmap_vector_storage_byte,
@@ -44,7 +48,7 @@ use crate::vector_storage::dense::memmap_dense_vector_storage::{
 };
 use crate::vector_storage::dense::simple_dense_vector_storage::{
     open_simple_dense_byte_vector_storage, open_simple_dense_half_vector_storage,
-    open_simple_dense_vector_storage,
+    open_simple_dense_vector_storage, write_simple_dense_vectors,
 };
 use crate::vector_storage::multi_dense::appendable_mmap_multi_dense_vector_storage::{
     open_appendable_memmap_multi_vector_storage, open_appendable_memmap_multi_vector_storage_byte,
@@ -52,7 +56,7 @@ use crate::vector_storage::multi_dense::appendable_mmap_multi_dense_vector_stora
 };
 use crate::vector_storage::multi_dense::simple_multi_dense_vector_storage::{
     open_simple_multi_dense_vector_storage, open_simple_multi_dense_vector_storage_byte,
-    open_simple_multi_dense_vector_storage_half,
+    open_simple_multi_dense_vector_storage_half, write_simple_multi_dense_vectors,
 };
 use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
 use crate::vector_storage::simple_sparse_vector_storage::open_simple_sparse_vector_storage;
@@ -60,7 +64,9 @@ use crate::vector_storage::{VectorStorage, VectorStorageEnum};
 
 pub const PAYLOAD_INDEX_PATH: &str = "payload_index";
 pub const VECTOR_STORAGE_PATH: &str = "vector_storage";
+pub const BUILDER_VECTOR_STORAGE_PATH: &str = "builder_vector_storage";
 pub const VECTOR_INDEX_PATH: &str = "vector_index";
+pub const ID_TRACKER_PATH: &str = "id_tracker";
 
 fn sp<T>(t: T) -> Arc<AtomicRefCell<T>> {
     Arc::new(AtomicRefCell::new(t))
@@ -81,6 +87,14 @@ pub fn get_vector_storage_path(segment_path: &Path, vector_name: &str) -> PathBu
     ))
 }
 
+/// Path of vectors, which are kept on disk only while the segment is built
+pub fn get_builder_vector_storage_path(segment_path: &Path, vector_name: &str) -> PathBuf {
+    segment_path.join(get_vector_name_with_prefix(
+        BUILDER_VECTOR_STORAGE_PATH,
+        vector_name,
+    ))
+}
+
 pub fn get_vector_index_path(segment_path: &Path, vector_name: &str) -> PathBuf {
     segment_path.join(get_vector_name_with_prefix(VECTOR_INDEX_PATH, vector_name))
 }
@@ -250,6 +264,69 @@ pub(crate) fn open_vector_storage(
     }
 }
 
+/// Write vectors of `other` storage in the format of the in-memory storage of `vector_config`
+///
+/// Vectors are written into the database one by one, without loading them into memory.
+pub(crate) fn write_in_memory_vector_storage(
+    database: &Arc<RwLock<DB>>,
+    vector_config: &VectorDataConfig,
+    vector_name: &str,
+    other: &VectorStorageEnum,
+    stopped: &AtomicBool,
+) -> OperationResult<()> {
+    debug_assert_eq!(vector_config.storage_type, VectorStorageType::Memory);
+    let db_column_name = get_vector_name_with_prefix(DB_VECTOR_CF, vector_name);
+    let database = database.clone();
+    let storage_element_type = vector_config.datatype.unwrap_or_default();
+
+    if vector_config.multivec_config.is_some() {
+        match storage_element_type {
+            VectorStorageDatatype::Float32 => {
+                write_simple_multi_dense_vectors::<VectorElementType>(
+                    database,
+                    &db_column_name,
+                    other,
+                    stopped,
+                )
+            }
+            VectorStorageDatatype::Uint8 => {
+                write_simple_multi_dense_vectors::<VectorElementTypeByte>(
+                    database,
+                    &db_column_name,
+                    other,
+                    stopped,
+                )
+            }
+            VectorStorageDatatype::Float16 => write_simple_multi_dense_vectors::<
+                VectorElementTypeHalf,
+            >(
+                database, &db_column_name, other, stopped
+            ),
+        }
+    } else {
+        match storage_element_type {
+            VectorStorageDatatype::Float32 => write_simple_dense_vectors::<VectorElementType>(
+                database,
+                &db_column_name,
+                other,
+                stopped,
+            ),
+            VectorStorageDatatype::Uint8 => write_simple_dense_vectors::<VectorElementTypeByte>(
+                database,
+                &db_column_name,
+                other,
+                stopped,
+            ),
+            VectorStorageDatatype::Float16 => write_simple_dense_vectors::<VectorElementTypeHalf>(
+                database,
+                &db_column_name,
+                other,
+                stopped,
+            ),
+        }
+    }
+}
+
 pub(crate) fn open_segment_db(
     segment_path: &Path,
     config: &SegmentConfig,
@@ -272,21 +349,31 @@ pub(crate) fn open_segment_db(
 pub(crate) fn create_payload_storage(
     database: Arc<RwLock<DB>>,
     config: &SegmentConfig,
+    segment_path: &Path,
 ) -> OperationResult<PayloadStorageEnum> {
     let payload_storage = match config.payload_storage_type {
         PayloadStorageType::InMemory => {
             PayloadStorageEnum::from(SimplePayloadStorage::open(database)?)
         }
         PayloadStorageType::OnDisk => {
-            PayloadStorageEnum::from(OnDiskPayloadStorage::open(database)?)
+            let mut storage = OnDiskPayloadStorage::open(database)?;
+            if let Some(compression) = config.payload_compression {
+                storage = storage.with_compression(segment_path, compression)?;
+            }
+            PayloadStorageEnum::from(storage)
+        }
+        PayloadStorageType::Gridstore => {
+            PayloadStorageEnum::from(GridstorePayloadStorage::open(segment_path)?)
         }
     };
     Ok(payload_storage)
 }
 
-pub(crate) fn create_id_tracker(database: Arc<RwLock<DB>>) -> OperationResult<IdTrackerEnum> {
-    Ok(IdTrackerEnum::MutableIdTracker(SimpleIdTracker::open(
-        database,
+pub(crate) fn create_id_tracker(segment_path: &Path) -> OperationResult<IdTrackerEnum> {
+    let id_tracker_path = segment_path.join(ID_TRACKER_PATH);
+    // Segments, created before the mmap id tracker, are migrated by `migrate_segment`
+    Ok(IdTrackerEnum::MmapIdTracker(MmapIdTracker::open(
+        &id_tracker_path,
     )?))
 }
 
@@ -309,17 +396,33 @@ pub(crate) fn create_vector_index(
             payload_index.clone(),
         )),
         Indexes::Hnsw(vector_hnsw_config) => {
-            if vector_hnsw_config.on_disk == Some(true) {
-                VectorIndexEnum::HnswMmap(HNSWIndex::<GraphLinksMmap>::open(
-                    vector_index_path,
-                    id_tracker.clone(),
-                    vector_storage.clone(),
-                    quantized_vectors.clone(),
-                    payload_index.clone(),
-                    vector_hnsw_config.clone(),
-                )?)
+            // Graph links are loaded into RAM only if they fit into the memory budget
+            let memory_reservation = if vector_hnsw_config.on_disk == Some(true) {
+                None
+            } else {
+                let links_path = GraphLayers::<GraphLinksRam>::get_links_path(vector_index_path);
+                let reservation =
+                    budget::global().try_reserve_file(MemoryCategory::Index, &links_path);
+                if reservation.is_none() {
+                    info!("Memory budget is exhausted, using memmap for HNSW index at {vector_index_path:?}");
+                }
+                reservation
+            };
+
+            if let Some(memory_reservation) = memory_reservation {
+                VectorIndexEnum::HnswRam(
+                    HNSWIndex::<GraphLinksRam>::open(
+                        vector_index_path,
+                        id_tracker.clone(),
+                        vector_storage.clone(),
+                        quantized_vectors.clone(),
+                        payload_index.clone(),
+                        vector_hnsw_config.clone(),
+                    )?
+                    .with_memory_reservation(memory_reservation),
+                )
             } else {
-                VectorIndexEnum::HnswRam(HNSWIndex::<GraphLinksRam>::open(
+                VectorIndexEnum::HnswMmap(HNSWIndex::<GraphLinksMmap>::open(
                     vector_index_path,
                     id_tracker.clone(),
                     vector_storage.clone(),
@@ -399,19 +502,27 @@ fn create_segment(
     stopped: &AtomicBool,
 ) -> OperationResult<Segment> {
     let database = open_segment_db(segment_path, config)?;
-    let payload_storage = sp(create_payload_storage(database.clone(), config)?);
+    let payload_storage = sp(create_payload_storage(
+        database.clone(),
+        config,
+        segment_path,
+    )?);
 
     let appendable_flag = config.is_appendable();
 
-    let id_tracker = sp(create_id_tracker(database.clone())?);
+    let id_tracker = sp(create_id_tracker(segment_path)?);
 
     let payload_index_path = get_payload_index_path(segment_path);
-    let payload_index: Arc<AtomicRefCell<StructPayloadIndex>> = sp(StructPayloadIndex::open(
+    let mut payload_index = StructPayloadIndex::open(
         payload_storage,
         id_tracker.clone(),
         &payload_index_path,
         appendable_flag,
-    )?);
+    )?;
+    payload_index.set_payload_columns(&config.payload_columns)?;
+    let attribute_storage = sp(AttributeStorage::open(segment_path)?);
+    payload_index.set_attribute_storage(attribute_storage.clone());
+    let payload_index: Arc<AtomicRefCell<StructPayloadIndex>> = sp(payload_index);
 
     let mut vector_data = HashMap::new();
     for (vector_name, vector_config) in &config.vector_data {
@@ -512,6 +623,15 @@ fn create_segment(
         );
     }
 
+    payload_index.borrow_mut().set_vector_storages(
+        vector_data
+            .iter()
+            .map(|(vector_name, vector_data)| {
+                (vector_name.clone(), vector_data.vector_storage.clone())
+            })
+            .collect(),
+    );
+
     let segment_type = if config.is_any_vector_indexed() {
         SegmentType::Indexed
     } else {
@@ -527,10 +647,12 @@ fn create_segment(
         segment_type,
         appendable_flag,
         payload_index,
+        attribute_storage,
         segment_config: config.clone(),
         error_status: None,
         database,
         flush_thread: Mutex::new(None),
+        created_at: None,
     })
 }
 
@@ -556,38 +678,33 @@ pub fn load_segment(path: &Path, stopped: &AtomicBool) -> OperationResult<Option
         return Ok(None);
     };
 
-    let app_version = SegmentVersion::current();
+    check_segment_version(&stored_version)?;
+    migrate_segment(path, &stored_version)?;
 
+    let app_version = SegmentVersion::current();
     if stored_version != app_version {
-        info!("Migrating segment {} -> {}", stored_version, app_version,);
-
-        if stored_version > app_version {
-            return Err(OperationError::service_error(format!(
-                "Data version {stored_version} is newer than application version {app_version}. \
-                Please upgrade the application. Compatibility is not guaranteed."
-            )));
-        }
-
-        if stored_version.major == 0 && stored_version.minor < 3 {
-            return Err(OperationError::service_error(format!(
-                "Segment version({stored_version}) is not compatible with current version({app_version})"
-            )));
-        }
-
-        if stored_version.major == 0 && stored_version.minor == 3 {
-            let segment_state = load_segment_state_v3(path)?;
-            Segment::save_state(&segment_state, path)?;
-        } else if stored_version.major == 0 && stored_version.minor <= 5 {
-            let segment_state = load_segment_state_v5(path)?;
-            Segment::save_state(&segment_state, path)?;
-        }
-
+        info!("Migrated segment {stored_version} -> {app_version}");
         SegmentVersion::save(path)?
     }
 
     let segment_state = Segment::load_state(path)?;
 
-    let segment = create_segment(segment_state.version, path, &segment_state.config, stopped)?;
+    let manifest = SegmentManifest::load(path)?;
+    if let Some(manifest) = &manifest {
+        manifest.recover(
+            path,
+            &get_payload_index_path(path).join(PAYLOAD_FIELD_INDEX_PATH),
+            segment_state.config.is_appendable(),
+        )?;
+    }
+
+    let mut segment = create_segment(segment_state.version, path, &segment_state.config, stopped)?;
+    segment.created_at = segment_state.created_at;
+
+    // Segments, created before manifests were introduced or restored from snapshots
+    if manifest.is_none() {
+        segment.save_manifest()?;
+    }
 
     Ok(Some(segment))
 }
@@ -617,8 +734,10 @@ pub fn build_segment(
 
     std::fs::create_dir_all(&segment_path)?;
 
-    let segment = create_segment(None, &segment_path, config, &AtomicBool::new(false))?;
+    let mut segment = create_segment(None, &segment_path, config, &AtomicBool::new(false))?;
+    segment.created_at = Some(Utc::now());
     segment.save_current_state()?;
+    StorageFormat::current().save(&segment_path)?;
 
     // Version is the last file to save, as it will be used to check if segment was built correctly.
     // If it is not saved, segment will be skipped.
@@ -628,95 +747,3 @@ pub fn build_segment(
 
     Ok(segment)
 }
-
-/// Load v0.3.* segment data and migrate to current version
-#[allow(deprecated)]
-fn load_segment_state_v3(segment_path: &Path) -> OperationResult<SegmentState> {
-    use crate::compat::{SegmentConfigV5, StorageTypeV5, VectorDataConfigV5};
-
-    #[derive(Deserialize)]
-    #[serde(rename_all = "snake_case")]
-    #[deprecated]
-    pub struct SegmentStateV3 {
-        pub version: SeqNumberType,
-        pub config: SegmentConfigV3,
-    }
-
-    #[derive(Deserialize)]
-    #[serde(rename_all = "snake_case")]
-    #[deprecated]
-    pub struct SegmentConfigV3 {
-        /// Size of a vectors used
-        pub vector_size: usize,
-        /// Type of distance function used for measuring distance between vectors
-        pub distance: Distance,
-        /// Type of index used for search
-        pub index: Indexes,
-        /// Type of vector storage
-        pub storage_type: StorageTypeV5,
-        /// Defines payload storage type
-        #[serde(default)]
-        pub payload_storage_type: PayloadStorageType,
-    }
-
-    let path = segment_path.join(SEGMENT_STATE_FILE);
-
-    let mut contents = String::new();
-
-    let mut file = File::open(&path)?;
-    file.read_to_string(&mut contents)?;
-
-    serde_json::from_str::<SegmentStateV3>(&contents)
-        .map(|state| {
-            // Construct V5 version, then convert into current
-            let vector_data = VectorDataConfigV5 {
-                size: state.config.vector_size,
-                distance: state.config.distance,
-                hnsw_config: None,
-                quantization_config: None,
-                on_disk: None,
-            };
-            let segment_config = SegmentConfigV5 {
-                vector_data: HashMap::from([(DEFAULT_VECTOR_NAME.to_owned(), vector_data)]),
-                index: state.config.index,
-                storage_type: state.config.storage_type,
-                payload_storage_type: state.config.payload_storage_type,
-                quantization_config: None,
-            };
-
-            SegmentState {
-                version: Some(state.version),
-                config: segment_config.into(),
-            }
-        })
-        .map_err(|err| {
-            OperationError::service_error(format!(
-                "Failed to read segment {}. Error: {}",
-                path.to_str().unwrap(),
-                err
-            ))
-        })
-}
-
-/// Load v0.5.0 segment data and migrate to current version
-#[allow(deprecated)]
-fn load_segment_state_v5(segment_path: &Path) -> OperationResult<SegmentState> {
-    use crate::compat::SegmentStateV5;
-
-    let path = segment_path.join(SEGMENT_STATE_FILE);
-
-    let mut contents = String::new();
-
-    let mut file = File::open(&path)?;
-    file.read_to_string(&mut contents)?;
-
-    serde_json::from_str::<SegmentStateV5>(&contents)
-        .map(Into::into)
-        .map_err(|err| {
-            OperationError::service_error(format!(
-                "Failed to read segment {}. Error: {}",
-                path.to_str().unwrap(),
-                err
-            ))
-        })
-}
@@ -35,6 +35,8 @@ pub fn build_simple_segment(
             )]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_columns: Default::default(),
+            payload_compression: Default::default(),
         },
         true,
     )
@@ -78,6 +80,8 @@ pub fn build_multivec_segment(
             vector_data: vectors_config,
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_columns: Default::default(),
+            payload_compression: Default::default(),
         },
         true,
     )
@@ -0,0 +1,235 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use io::file_operations::{atomic_save_json, read_json};
+use serde::{Deserialize, Serialize};
+
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::types::SeqNumberType;
+
+pub const SEGMENT_MANIFEST_FILE: &str = "manifest.json";
+
+/// File of a segment component, as it was when the manifest was saved
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestFile {
+    /// Path, relative to the segment directory
+    pub path: PathBuf,
+    pub size: u64,
+}
+
+/// Set of component files of a segment
+///
+/// Manifest is saved atomically, once all listed files are written and flushed. Components,
+/// which replace their files, write the new files first, so the manifest on disk always
+/// describes a complete set of files.
+///
+/// Files are split in two groups:
+/// - `files` of vector storages, quantized data, vector indexes, attribute columns and id tracker,
+///   which can't be restored from other data;
+/// - `derived_files` of payload field indexes, which are built from the index records in the db.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SegmentManifest {
+    /// Version of the segment at the time the manifest was saved
+    pub version: Option<SeqNumberType>,
+    pub files: Vec<ManifestFile>,
+    #[serde(default)]
+    pub derived_files: Vec<ManifestFile>,
+}
+
+impl SegmentManifest {
+    /// Record current sizes of the files. Paths are expected to be within `segment_path`.
+    ///
+    /// Files, which are not created yet, are not recorded.
+    pub fn collect(
+        segment_path: &Path,
+        version: Option<SeqNumberType>,
+        files: impl IntoIterator<Item = PathBuf>,
+        derived_files: impl IntoIterator<Item = PathBuf>,
+    ) -> OperationResult<Self> {
+        let record = |files: &mut dyn Iterator<Item = PathBuf>| {
+            let mut records = Vec::new();
+            for file in files {
+                let size = match fs::metadata(&file) {
+                    Ok(metadata) => metadata.len(),
+                    Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+                    Err(err) => return Err(err.into()),
+                };
+                let path = file
+                    .strip_prefix(segment_path)
+                    .map_err(|_| {
+                        OperationError::service_error(format!(
+                            "File {} is outside of segment directory {}",
+                            file.display(),
+                            segment_path.display(),
+                        ))
+                    })?
+                    .to_path_buf();
+                records.push(ManifestFile { path, size });
+            }
+            records.sort_unstable_by(|a, b| a.path.cmp(&b.path));
+            records.dedup();
+            OperationResult::Ok(records)
+        };
+        Ok(Self {
+            version,
+            files: record(&mut files.into_iter())?,
+            derived_files: record(&mut derived_files.into_iter())?,
+        })
+    }
+
+    pub fn save(&self, segment_path: &Path) -> OperationResult<()> {
+        Ok(atomic_save_json(
+            &segment_path.join(SEGMENT_MANIFEST_FILE),
+            self,
+        )?)
+    }
+
+    /// Load the manifest of the segment, if it was saved
+    pub fn load(segment_path: &Path) -> OperationResult<Option<Self>> {
+        let manifest_path = segment_path.join(SEGMENT_MANIFEST_FILE);
+        if !manifest_path.exists() {
+            return Ok(None);
+        }
+        read_json(&manifest_path).map(Some).map_err(|err| {
+            OperationError::service_error(format!(
+                "Failed to read segment manifest {}: {err}",
+                manifest_path.display(),
+            ))
+        })
+    }
+
+    /// Check the files of the segment against the manifest, and roll back changes of derived
+    /// files, which were interrupted before the manifest was saved
+    ///
+    /// - Directories in `derived_path`, which content differs from the manifest, are removed, so
+    ///   that their indexes are rebuilt from the db on load.
+    /// - Missing or resized files of a non-appendable segment are reported as an error, as they
+    ///   can't be restored. Files of appendable segments change between flushes, the changes
+    ///   made after the last flush are recovered from WAL instead.
+    pub fn recover(
+        &self,
+        segment_path: &Path,
+        derived_path: &Path,
+        is_appendable: bool,
+    ) -> OperationResult<()> {
+        self.rollback_derived_files(segment_path, derived_path)?;
+
+        if is_appendable {
+            return Ok(());
+        }
+
+        let damaged: Vec<_> = self
+            .files
+            .iter()
+            .filter_map(|file| match fs::metadata(segment_path.join(&file.path)) {
+                Ok(metadata) if metadata.len() == file.size => None,
+                Ok(metadata) => Some(format!(
+                    "{} ({} bytes, expected {})",
+                    file.path.display(),
+                    metadata.len(),
+                    file.size,
+                )),
+                Err(_) => Some(format!("{} (missing)", file.path.display())),
+            })
+            .collect();
+        if damaged.is_empty() {
+            return Ok(());
+        }
+        Err(OperationError::service_error(format!(
+            "Segment {} doesn't match its manifest, damaged files: {}",
+            segment_path.display(),
+            damaged.join(", "),
+        )))
+    }
+
+    fn rollback_derived_files(
+        &self,
+        segment_path: &Path,
+        derived_path: &Path,
+    ) -> OperationResult<()> {
+        if !derived_path.is_dir() {
+            return Ok(());
+        }
+
+        let mut expected: HashMap<PathBuf, HashSet<(PathBuf, u64)>> = HashMap::new();
+        for file in &self.derived_files {
+            let path = segment_path.join(&file.path);
+            if let Some(dir) = path.parent() {
+                expected
+                    .entry(dir.to_path_buf())
+                    .or_default()
+                    .insert((path.clone(), file.size));
+            }
+        }
+
+        for entry in fs::read_dir(derived_path)? {
+            let dir = entry?.path();
+            let mut actual = HashSet::new();
+            if dir.is_dir() {
+                for file in fs::read_dir(&dir)? {
+                    let file = file?;
+                    actual.insert((file.path(), file.metadata()?.len()));
+                }
+            }
+            if expected
+                .get(&dir)
+                .map_or(false, |expected| *expected == actual)
+            {
+                continue;
+            }
+            log::warn!(
+                "Removing index files {}, which don't match the segment manifest",
+                dir.display(),
+            );
+            if dir.is_dir() {
+                fs::remove_dir_all(&dir)?;
+            } else {
+                fs::remove_file(&dir)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+
+    #[test]
+    fn test_segment_manifest_recover() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let derived_path = dir.path().join("fields");
+        let vectors = dir.path().join("vectors.bin");
+        fs::write(&vectors, [0u8; 16]).unwrap();
+        let complete = derived_path.join("complete");
+        let interrupted = derived_path.join("interrupted");
+        fs::create_dir_all(&complete).unwrap();
+        fs::write(complete.join("index.bin"), [0u8; 8]).unwrap();
+
+        let manifest = SegmentManifest::collect(
+            dir.path(),
+            Some(10),
+            [vectors.clone()],
+            [complete.join("index.bin")],
+        )
+        .unwrap();
+        manifest.save(dir.path()).unwrap();
+        let manifest = SegmentManifest::load(dir.path()).unwrap().unwrap();
+        assert_eq!(manifest.files[0].path, PathBuf::from("vectors.bin"));
+
+        // Index files, written after the manifest was saved, are removed
+        fs::create_dir_all(&interrupted).unwrap();
+        fs::write(interrupted.join("index.bin"), [0u8; 4]).unwrap();
+        manifest.recover(dir.path(), &derived_path, false).unwrap();
+        assert!(complete.join("index.bin").exists());
+        assert!(!interrupted.exists());
+
+        // Truncated files of non-appendable segments can't be recovered
+        fs::write(&vectors, [0u8; 8]).unwrap();
+        assert!(manifest.recover(dir.path(), &derived_path, false).is_err());
+        manifest.recover(dir.path(), &derived_path, true).unwrap();
+    }
+}
@@ -69,6 +69,10 @@ pub struct VectorIndexSearchesTelemetry {
 
     #[serde(skip_serializing_if = "OperationDurationStatistics::is_empty")]
     pub unfiltered_exact: OperationDurationStatistics,
+
+    /// Number of points, which vectors were updated after the HNSW graph was built
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stale_points: Option<usize>,
 }
 
 impl Anonymize for SegmentTelemetry {
@@ -125,6 +129,8 @@ impl Anonymize for SegmentConfig {
             vector_data: self.vector_data.anonymize(),
             sparse_vector_data: self.sparse_vector_data.anonymize(),
             payload_storage_type: self.payload_storage_type,
+            payload_columns: self.payload_columns.anonymize(),
+            payload_compression: self.payload_compression,
         }
     }
 }
@@ -164,6 +170,7 @@ impl Anonymize for VectorIndexSearchesTelemetry {
             filtered_exact: self.filtered_exact.anonymize(),
             filtered_sparse: self.filtered_sparse.anonymize(),
             unfiltered_exact: self.filtered_exact.anonymize(),
+            stale_points: self.stale_points.anonymize(),
         }
     }
 }
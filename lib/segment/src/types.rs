@@ -25,6 +25,7 @@ use validator::{Validate, ValidationError, ValidationErrors};
 
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::utils::{self, MaybeOneOrMany, MultiValue};
+use crate::data_types::geo_index::GeoIndexParams;
 use crate::data_types::integer_index::IntegerIndexParams;
 use crate::data_types::order_by::OrderValue;
 use crate::data_types::text_index::TextIndexParams;
@@ -45,6 +46,8 @@ pub type TagType = u64;
 pub type FloatPayloadType = f64;
 /// Type of integer point payload
 pub type IntPayloadType = i64;
+/// Type of integer point payload, which doesn't fit into `IntPayloadType`, like u64 identifiers
+pub type BigIntPayloadType = i128;
 /// Type of datetime point payload
 pub type DateTimePayloadType = DateTimeWrapper;
 
@@ -183,6 +186,13 @@ impl Distance {
         }
     }
 
+    /// Whether vectors, stored for this distance, can be scored with the `other` distance.
+    /// Only vectors of `Cosine` distance are normalized, other distances can be computed on any
+    /// vectors.
+    pub fn can_score_with(&self, other: Distance) -> bool {
+        *self == Distance::Cosine || other != Distance::Cosine
+    }
+
     /// Checks if score satisfies threshold condition
     pub fn check_threshold(&self, score: ScoreType, threshold: ScoreType) -> bool {
         match self.distance_order() {
@@ -285,12 +295,17 @@ impl PayloadIndexInfo {
                     params: Some(schema_params),
                     points: points_count,
                 },
+                PayloadSchemaParams::Geo(_) => PayloadIndexInfo {
+                    data_type: PayloadSchemaType::Geo,
+                    params: Some(schema_params),
+                    points: points_count,
+                },
             },
         }
     }
 }
 
-#[derive(Debug, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+#[derive(Debug, Serialize, JsonSchema, Clone, PartialEq, Eq, Default)]
 #[serde(rename_all = "snake_case")]
 pub struct VectorDataInfo {
     pub num_vectors: usize,
@@ -370,6 +385,23 @@ pub struct SearchParams {
     /// guarantee that all uploaded vectors will be included in search results
     #[serde(default)]
     pub indexed_only: bool,
+
+    /// Score vectors with this distance instead of the configured one, e.g. `Dot` for vectors,
+    /// stored with `Cosine` distance, to skip normalization of the query.
+    /// `Cosine` can only be used for vectors, stored with `Cosine` distance, as only those are normalized.
+    /// Search with a different distance is always exact, because the index and quantization are
+    /// built for the configured distance.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub distance: Option<Distance>,
+}
+
+impl SearchParams {
+    /// Distance to score vectors with, if it differs from the configured `distance`
+    pub fn distance_override(params: Option<&Self>, distance: Distance) -> Option<Distance> {
+        params
+            .and_then(|params| params.distance)
+            .filter(|&search_distance| search_distance != distance)
+    }
 }
 
 /// Collection default values
@@ -688,14 +720,30 @@ pub enum PayloadStorageType {
     InMemory,
     // Store payload on disk only, read each time it is requested
     OnDisk,
+    // Store payload in append-only blocks on disk, read through mmap
+    Gridstore,
 }
 
 impl PayloadStorageType {
     pub fn is_on_disk(&self) -> bool {
-        matches!(self, PayloadStorageType::OnDisk)
+        matches!(
+            self,
+            PayloadStorageType::OnDisk | PayloadStorageType::Gridstore
+        )
     }
 }
 
+/// Zstd compression of payload values, with a dictionary trained on payloads of each segment.
+/// Each value is compressed separately, so single points are still read without decompressing
+/// others. Only applies to the on-disk payload storage.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct PayloadCompression {
+    /// Compression level, from 1 to 22. Higher levels compress better, but slower.
+    #[validate(range(min = 1, max = 22))]
+    pub level: i32,
+}
+
 #[derive(Default, Debug, Deserialize, Serialize, JsonSchema, Clone)]
 #[serde(rename_all = "snake_case")]
 pub struct SegmentConfig {
@@ -706,6 +754,12 @@ pub struct SegmentConfig {
     pub sparse_vector_data: HashMap<String, SparseVectorDataConfig>,
     /// Defines payload storage type
     pub payload_storage_type: PayloadStorageType,
+    /// Payload fields, whose values are also stored in columns for fast scans
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub payload_columns: Vec<PayloadKeyType>,
+    /// Compression of payload in the on-disk payload storage
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload_compression: Option<PayloadCompression>,
 }
 
 impl SegmentConfig {
@@ -780,6 +834,24 @@ impl SegmentConfig {
             )
             .all(|v| v)
     }
+
+    /// Check if the given named vector can be updated in place
+    ///
+    /// This may be true for a single vector even if the segment itself is not appendable.
+    /// Dense vectors only require a mutable storage, an HNSW index keeps updated points as stale
+    /// and scores them exhaustively. Quantized vectors are not updated in place, as quantized
+    /// data would be left stale. Sparse vectors require a mutable index, because immutable
+    /// inverted indexes hold the vector values themselves.
+    pub fn is_vector_appendable(&self, vector_name: &str) -> bool {
+        if let Some(vector_config) = self.vector_data.get(vector_name) {
+            vector_config.storage_type.is_appendable()
+                && vector_config.quantization_config.is_none()
+        } else if let Some(sparse_vector_config) = self.sparse_vector_data.get(vector_name) {
+            sparse_vector_config.index.index_type.is_appendable()
+        } else {
+            false
+        }
+    }
 }
 
 /// Storage types for vectors
@@ -818,6 +890,12 @@ pub enum VectorStorageDatatype {
 pub struct MultiVectorConfig {
     /// How to compare multivector points
     pub comparator: MultiVectorComparator,
+    /// Aggregate the vectors of each point into a single vector to build and traverse the HNSW
+    /// graph with. Candidates, found in the graph, are rescored with the `comparator`.
+    /// Aggregated vectors are kept in memory, while the HNSW index is loaded.
+    /// If not set, the graph is built with the `comparator`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hnsw_aggregation: Option<MultiVectorAggregation>,
 }
 
 #[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Eq, PartialEq, Copy, Clone, Hash)]
@@ -827,6 +905,16 @@ pub enum MultiVectorComparator {
     MaxSim,
 }
 
+/// How to aggregate the vectors of a multivector point into a single vector
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Eq, PartialEq, Copy, Clone, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum MultiVectorAggregation {
+    /// Mean of the vectors
+    Mean,
+    /// First vector, e.g. the vector of the `[CLS]` token
+    First,
+}
+
 impl VectorStorageType {
     /// Whether this storage type is a mmap on disk
     pub fn is_on_disk(&self) -> bool {
@@ -835,6 +923,14 @@ impl VectorStorageType {
             Self::Mmap | Self::ChunkedMmap => true,
         }
     }
+
+    /// Whether vectors can be appended to or updated in this storage type
+    pub fn is_appendable(&self) -> bool {
+        match self {
+            Self::Memory | Self::ChunkedMmap => true,
+            Self::Mmap => false,
+        }
+    }
 }
 
 /// Config of single vector data storage
@@ -868,12 +964,7 @@ impl VectorDataConfig {
             Indexes::Plain {} => true,
             Indexes::Hnsw(_) => false,
         };
-        let is_storage_appendable = match self.storage_type {
-            VectorStorageType::Memory => true,
-            VectorStorageType::Mmap => false,
-            VectorStorageType::ChunkedMmap => true,
-        };
-        is_index_appendable && is_storage_appendable
+        is_index_appendable && self.storage_type.is_appendable()
     }
 }
 
@@ -902,11 +993,15 @@ pub const DEFAULT_SPARSE_FULL_SCAN_THRESHOLD: usize = 5_000;
 pub struct SegmentState {
     pub version: Option<SeqNumberType>,
     pub config: SegmentConfig,
+    /// When the segment was created, either empty or built by an optimizer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 /// Geo point payload schema
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Default)]
 #[serde(try_from = "GeoPointShadow")]
+#[repr(C)]
 pub struct GeoPoint {
     pub lon: f64,
     pub lat: f64,
@@ -953,6 +1048,11 @@ impl GeoPoint {
         Self::validate(lon, lat)?;
         Ok(GeoPoint { lon, lat })
     }
+
+    /// Haversine distance to `other`, in meters
+    pub fn distance(&self, other: &GeoPoint) -> f64 {
+        Point::new(self.lon, self.lat).haversine_distance(&Point::new(other.lon, other.lat))
+    }
 }
 
 impl TryFrom<GeoPointShadow> for GeoPoint {
@@ -982,6 +1082,11 @@ impl Payload {
         utils::merge_map(&mut self.0, &value.0)
     }
 
+    /// Apply JSON Merge Patch (RFC 7396)
+    pub fn merge_patch(&mut self, patch: &Payload) {
+        utils::merge_patch_map(&mut self.0, &patch.0)
+    }
+
     pub fn merge_by_key(&mut self, value: &Payload, key: &JsonPath) -> OperationResult<()> {
         JsonPathInterface::value_set(Some(key), &mut self.0, &value.0);
         Ok(())
@@ -1142,6 +1247,47 @@ pub enum JsonPayload {
     Geo(PayloadVariant<GeoPoint>),
 }
 
+/// Type of the values of an attribute column
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AttributeType {
+    Float,
+    Integer,
+}
+
+impl Display for AttributeType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            AttributeType::Float => write!(f, "float"),
+            AttributeType::Integer => write!(f, "integer"),
+        }
+    }
+}
+
+/// Value of a scalar attribute of a point, stored in a dense column outside of the payload
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq)]
+#[serde(untagged)]
+pub enum AttributeValue {
+    Integer(i64),
+    Float(f32),
+}
+
+impl AttributeValue {
+    pub fn attribute_type(&self) -> AttributeType {
+        match self {
+            AttributeValue::Integer(_) => AttributeType::Integer,
+            AttributeValue::Float(_) => AttributeType::Float,
+        }
+    }
+
+    pub fn as_f64(&self) -> f64 {
+        match self {
+            AttributeValue::Integer(value) => *value as f64,
+            AttributeValue::Float(value) => f64::from(*value),
+        }
+    }
+}
+
 /// All possible names of payload types
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Hash, Eq, EnumIter)]
 #[serde(rename_all = "snake_case")]
@@ -1153,6 +1299,8 @@ pub enum PayloadSchemaType {
     Text,
     Bool,
     Datetime,
+    BigInt,
+    MinHash,
 }
 
 impl PayloadSchemaType {
@@ -1168,6 +1316,7 @@ impl PayloadSchemaType {
 pub enum PayloadSchemaParams {
     Text(TextIndexParams),
     Integer(IntegerIndexParams),
+    Geo(GeoIndexParams),
 }
 
 impl PayloadSchemaParams {
@@ -1189,13 +1338,16 @@ impl PayloadFieldSchema {
         match self {
             PayloadFieldSchema::FieldType(PayloadSchemaType::Integer)
             | PayloadFieldSchema::FieldType(PayloadSchemaType::Datetime)
-            | PayloadFieldSchema::FieldType(PayloadSchemaType::Float) => true,
+            | PayloadFieldSchema::FieldType(PayloadSchemaType::Float)
+            | PayloadFieldSchema::FieldType(PayloadSchemaType::BigInt) => true,
 
             PayloadFieldSchema::FieldType(PayloadSchemaType::Bool)
             | PayloadFieldSchema::FieldType(PayloadSchemaType::Keyword)
             | PayloadFieldSchema::FieldType(PayloadSchemaType::Text)
             | PayloadFieldSchema::FieldType(PayloadSchemaType::Geo)
-            | PayloadFieldSchema::FieldParams(PayloadSchemaParams::Text(_)) => false,
+            | PayloadFieldSchema::FieldType(PayloadSchemaType::MinHash)
+            | PayloadFieldSchema::FieldParams(PayloadSchemaParams::Text(_))
+            | PayloadFieldSchema::FieldParams(PayloadSchemaParams::Geo(_)) => false,
 
             PayloadFieldSchema::FieldParams(PayloadSchemaParams::Integer(IntegerIndexParams {
                 range,
@@ -1234,9 +1386,17 @@ impl TryFrom<PayloadIndexInfo> for PayloadFieldSchema {
             (PayloadSchemaType::Integer, PayloadSchemaParams::Integer(params)) => Ok(
                 PayloadFieldSchema::FieldParams(PayloadSchemaParams::Integer(params)),
             ),
-            (data_type, PayloadSchemaParams::Integer(_) | PayloadSchemaParams::Text(_)) => Err(
-                format!("Payload field with type {data_type:?} has unexpected params"),
+            (PayloadSchemaType::Geo, PayloadSchemaParams::Geo(params)) => Ok(
+                PayloadFieldSchema::FieldParams(PayloadSchemaParams::Geo(params)),
             ),
+            (
+                data_type,
+                PayloadSchemaParams::Integer(_)
+                | PayloadSchemaParams::Text(_)
+                | PayloadSchemaParams::Geo(_),
+            ) => Err(format!(
+                "Payload field with type {data_type:?} has unexpected params"
+            )),
         }
     }
 }
@@ -1248,6 +1408,8 @@ pub fn value_type(value: &Value) -> Option<PayloadSchemaType> {
         Value::Number(num) => {
             if num.is_i64() {
                 Some(PayloadSchemaType::Integer)
+            } else if num.is_u64() {
+                Some(PayloadSchemaType::BigInt)
             } else if num.is_f64() {
                 Some(PayloadSchemaType::Float)
             } else {
@@ -1332,6 +1494,32 @@ impl From<String> for MatchText {
     }
 }
 
+/// Default minimal similarity of near-duplicate texts
+pub const DEFAULT_MIN_SIMILARITY: f32 = 0.8;
+
+/// Near-duplicate match of the strings.
+///
+/// Strings are compared by the Jaccard similarity of their sets of word shingles.
+/// If the field has a `min_hash` index, the similarity is estimated by the index.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct MatchSimilar {
+    /// Text to find near-duplicates of
+    pub similar: String,
+    /// Minimal similarity of the strings, from 0 to 1.
+    /// Default: 0.8
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_similarity: Option<f32>,
+}
+
+impl Eq for MatchSimilar {}
+
+impl MatchSimilar {
+    pub fn min_similarity(&self) -> f32 {
+        self.min_similarity.unwrap_or(DEFAULT_MIN_SIMILARITY)
+    }
+}
+
 /// Exact match on any of the given values
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -1354,6 +1542,7 @@ pub enum MatchInterface {
     Text(MatchText),
     Any(MatchAny),
     Except(MatchExcept),
+    Similar(MatchSimilar),
 }
 
 /// Match filter request
@@ -1364,6 +1553,7 @@ pub enum Match {
     Text(MatchText),
     Any(MatchAny),
     Except(MatchExcept),
+    Similar(MatchSimilar),
 }
 
 impl Match {
@@ -1399,6 +1589,7 @@ impl From<MatchInterface> for Match {
             MatchInterface::Except(except) => Self::Except(MatchExcept {
                 except: except.except,
             }),
+            MatchInterface::Similar(similar) => Self::Similar(similar),
         }
     }
 }
@@ -1471,16 +1662,79 @@ impl From<Vec<IntPayloadType>> for MatchExcept {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[derive(Debug, Serialize, JsonSchema, Clone, PartialEq)]
 #[serde(untagged)]
 pub enum RangeInterface {
     Float(Range<FloatPayloadType>),
     DateTime(Range<DateTimePayloadType>),
+    // Lexicographic range of keywords, only used if bounds can't be parsed as datetime
+    Keyword(Range<String>),
+    // Only used if some of integer bounds can't be represented as float without precision loss
+    BigInt(Range<BigIntPayloadType>),
+}
+
+/// Max integer, which can be represented by `f64` without precision loss
+const MAX_EXACT_FLOAT_INTEGER: u128 = 1 << f64::MANTISSA_DIGITS;
+
+impl<'de> Deserialize<'de> for RangeInterface {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum RangeInterface {
+            Float(Range<FloatPayloadType>),
+            DateTime(Range<DateTimePayloadType>),
+            Keyword(Range<String>),
+        }
+
+        // Bounds are parsed from `Value`, so large integers are not converted to float yet
+        let value = Value::deserialize(deserializer)?;
+        if let Some(range) = parse_big_int_range(&value) {
+            return Ok(Self::BigInt(range));
+        }
+
+        let range = RangeInterface::deserialize(value).map_err(serde::de::Error::custom)?;
+        Ok(match range {
+            RangeInterface::Float(range) => Self::Float(range),
+            RangeInterface::DateTime(range) => Self::DateTime(range),
+            RangeInterface::Keyword(range) => Self::Keyword(range),
+        })
+    }
+}
+
+/// Parse range with integer bounds, if some of them can't be represented as float exactly
+fn parse_big_int_range(value: &Value) -> Option<Range<BigIntPayloadType>> {
+    let object = value.as_object()?;
+    let mut is_exact_float = true;
+    let mut parse_bound = |name: &str| match object.get(name) {
+        None | Some(Value::Null) => Some(None),
+        Some(Value::Number(number)) => {
+            let bound = number
+                .as_i64()
+                .map(BigIntPayloadType::from)
+                .or_else(|| number.as_u64().map(BigIntPayloadType::from))?;
+            is_exact_float &= bound.unsigned_abs() <= MAX_EXACT_FLOAT_INTEGER;
+            Some(Some(bound))
+        }
+        Some(_) => None,
+    };
+
+    let range = Range {
+        lt: parse_bound("lt")?,
+        gt: parse_bound("gt")?,
+        gte: parse_bound("gte")?,
+        lte: parse_bound("lte")?,
+    };
+    (!is_exact_float).then_some(range)
 }
 
 /// Range filter request
 #[macro_rules_attribute::macro_rules_derive(crate::common::macros::schemars_rename_generics)]
-#[derive_args(< FloatPayloadType > => "Range", < DateTimePayloadType > => "DatetimeRange")]
+#[derive_args(
+    < FloatPayloadType > => "Range",
+    < DateTimePayloadType > => "DatetimeRange",
+    < String > => "KeywordRange",
+    < BigIntPayloadType > => "BigIntRange"
+)]
 #[derive(Debug, Deserialize, Serialize, Default, Clone, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub struct Range<T> {
@@ -1547,6 +1801,16 @@ impl<T: Copy + PartialOrd> Range<T> {
     }
 }
 
+impl Range<String> {
+    /// Check if keyword lies in the range, in lexicographic order
+    pub fn check_keyword(&self, keyword: &str) -> bool {
+        self.lt.as_ref().map_or(true, |x| keyword < x.as_str())
+            && self.gt.as_ref().map_or(true, |x| keyword > x.as_str())
+            && self.lte.as_ref().map_or(true, |x| keyword <= x.as_str())
+            && self.gte.as_ref().map_or(true, |x| keyword >= x.as_str())
+    }
+}
+
 /// Values count filter request
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Copy, Clone, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -1579,6 +1843,7 @@ impl ValuesCount {
 /// Geo filter request
 ///
 /// Matches coordinates inside the rectangle, described by coordinates of lop-left and bottom-right edges
+/// Polygons, stored in payload, match if their exterior is inside the rectangle.
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub struct GeoBoundingBox {
@@ -1606,6 +1871,7 @@ impl GeoBoundingBox {
 /// Geo filter request
 ///
 /// Matches coordinates inside the circle of `radius` and center with coordinates `center`
+/// Polygons, stored in payload, match if their exterior is inside the circle.
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub struct GeoRadius {
@@ -1617,8 +1883,7 @@ pub struct GeoRadius {
 
 impl GeoRadius {
     pub fn check_point(&self, point: &GeoPoint) -> bool {
-        let query_center = Point::new(self.center.lon, self.center.lat);
-        query_center.haversine_distance(&Point::new(point.lon, point.lat)) < self.radius
+        self.center.distance(point) < self.radius
     }
 }
 
@@ -1642,6 +1907,7 @@ impl PolygonWrapper {
 /// Geo filter request
 ///
 /// Matches coordinates inside the polygon, defined by `exterior` and `interiors`
+/// Polygons, stored in payload, match if all points of their exterior are inside the polygon.
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
 #[serde(try_from = "GeoPolygonShadow", rename_all = "snake_case")]
 pub struct GeoPolygon {
@@ -1709,6 +1975,18 @@ impl GeoPolygon {
         }
     }
 
+    /// Parse a polygon, stored in payload in the same format as in filters.
+    ///
+    /// Returns `None` if the value is not a valid polygon.
+    pub fn from_payload(value: &Value) -> Option<Self> {
+        match value {
+            Value::Object(obj) if obj.contains_key("exterior") => {
+                serde_json::from_value(value.clone()).ok()
+            }
+            _ => None,
+        }
+    }
+
     pub fn new(exterior: &GeoLineString, interiors: &Vec<GeoLineString>) -> OperationResult<Self> {
         Self::validate_line_string(exterior)?;
 
@@ -1800,6 +2078,18 @@ impl FieldCondition {
         }
     }
 
+    pub fn new_keyword_range(key: JsonPath, keyword_range: Range<String>) -> Self {
+        Self {
+            key,
+            r#match: None,
+            range: Some(RangeInterface::Keyword(keyword_range)),
+            geo_bounding_box: None,
+            geo_radius: None,
+            geo_polygon: None,
+            values_count: None,
+        }
+    }
+
     pub fn new_geo_bounding_box(key: JsonPath, geo_bounding_box: GeoBoundingBox) -> Self {
         Self {
             key,
@@ -1866,12 +2156,18 @@ impl FieldCondition {
 
 pub fn validate_field_condition(field_condition: &FieldCondition) -> Result<(), ValidationError> {
     if field_condition.all_fields_none() {
-        Err(ValidationError::new(
+        return Err(ValidationError::new(
             "At least one field condition must be specified",
-        ))
-    } else {
-        Ok(())
+        ));
     }
+    if let Some(Match::Similar(similar)) = &field_condition.r#match {
+        if !(0.0..=1.0).contains(&similar.min_similarity()) {
+            return Err(ValidationError::new(
+                "min_similarity must be between 0 and 1",
+            ));
+        }
+    }
+    Ok(())
 }
 
 /// Payload field
@@ -1921,6 +2217,33 @@ impl From<HashSet<PointIdType>> for HasIdCondition {
     }
 }
 
+/// Filter points which have specific vector assigned
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+pub struct HasVectorCondition {
+    pub has_vector: String,
+}
+
+impl From<String> for HasVectorCondition {
+    fn from(vector: String) -> Self {
+        HasVectorCondition { has_vector: vector }
+    }
+}
+
+/// Filter points by the value of an attribute column
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+pub struct AttributeCondition {
+    /// Name of the attribute
+    pub attribute: String,
+    /// Range of the attribute value. Points without the attribute don't match
+    pub range: Range<FloatPayloadType>,
+}
+
+impl AttributeCondition {
+    pub fn check(&self, value: Option<AttributeValue>) -> bool {
+        value.is_some_and(|value| self.range.check_range(value.as_f64()))
+    }
+}
+
 /// Select points with payload for a specified nested field
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Validate)]
 pub struct Nested {
@@ -1968,6 +2291,10 @@ pub enum Condition {
     IsNull(IsNullCondition),
     /// Check if points id is in a given set
     HasId(HasIdCondition),
+    /// Check if point has vector assigned
+    HasVector(HasVectorCondition),
+    /// Check if attribute of the point is in range
+    Attribute(AttributeCondition),
     /// Nested filters
     Nested(NestedCondition),
     /// Nested filter
@@ -1984,6 +2311,8 @@ impl PartialEq for Condition {
             (Self::IsEmpty(this), Self::IsEmpty(other)) => this == other,
             (Self::IsNull(this), Self::IsNull(other)) => this == other,
             (Self::HasId(this), Self::HasId(other)) => this == other,
+            (Self::HasVector(this), Self::HasVector(other)) => this == other,
+            (Self::Attribute(this), Self::Attribute(other)) => this == other,
             (Self::Nested(this), Self::Nested(other)) => this == other,
             (Self::Filter(this), Self::Filter(other)) => this == other,
             (Self::Resharding(this), Self::Resharding(other)) => this.eq(other.deref()),
@@ -2008,7 +2337,11 @@ impl Condition {
 impl Validate for Condition {
     fn validate(&self) -> Result<(), ValidationErrors> {
         match self {
-            Condition::HasId(_) | Condition::IsEmpty(_) | Condition::IsNull(_) => Ok(()),
+            Condition::HasId(_)
+            | Condition::HasVector(_)
+            | Condition::Attribute(_)
+            | Condition::IsEmpty(_)
+            | Condition::IsNull(_) => Ok(()),
             Condition::Field(field_condition) => field_condition.validate(),
             Condition::Nested(nested_condition) => nested_condition.validate(),
             Condition::Filter(filter) => filter.validate(),
@@ -2139,6 +2472,20 @@ impl PayloadSelectorExclude {
     }
 }
 
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct PayloadSelectorProject {
+    /// Keys of the result payload, mapped to the paths of their values in the original payload.
+    /// Paths, which match multiple values, produce an array. Missing values are skipped.
+    pub project: BTreeMap<String, PayloadKeyType>,
+}
+
+impl PayloadSelectorProject {
+    pub fn new(project: BTreeMap<String, PayloadKeyType>) -> Self {
+        Self { project }
+    }
+}
+
 /// Specifies how to treat payload selector
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
 #[serde(untagged, rename_all = "snake_case")]
@@ -2147,6 +2494,8 @@ pub enum PayloadSelector {
     Include(PayloadSelectorInclude),
     /// Exclude this fields from result payload. Keep all other fields.
     Exclude(PayloadSelectorExclude),
+    /// Build result payload from the selected values, allows to rename and flatten fields
+    Project(PayloadSelectorProject),
 }
 
 impl From<PayloadSelectorExclude> for WithPayloadInterface {
@@ -2161,6 +2510,12 @@ impl From<PayloadSelectorInclude> for WithPayloadInterface {
     }
 }
 
+impl From<PayloadSelectorProject> for WithPayloadInterface {
+    fn from(selector: PayloadSelectorProject) -> Self {
+        WithPayloadInterface::Selector(PayloadSelector::Project(selector))
+    }
+}
+
 impl PayloadSelector {
     pub fn new_include(vecs_payload_key_type: Vec<PayloadKeyType>) -> Self {
         PayloadSelector::Include(PayloadSelectorInclude {
@@ -2174,6 +2529,28 @@ impl PayloadSelector {
         })
     }
 
+    /// Top level keys of the payload, required to process the selector.
+    /// `None` if the whole payload is required.
+    pub fn required_keys(&self) -> Option<Vec<&str>> {
+        match self {
+            PayloadSelector::Include(selector) => Some(
+                selector
+                    .include
+                    .iter()
+                    .map(|path| path.first_key.as_str())
+                    .collect(),
+            ),
+            PayloadSelector::Exclude(_) => None,
+            PayloadSelector::Project(selector) => Some(
+                selector
+                    .project
+                    .values()
+                    .map(|path| path.first_key.as_str())
+                    .collect(),
+            ),
+        }
+    }
+
     /// Process payload selector
     pub fn process(&self, x: Payload) -> Payload {
         match self {
@@ -2191,6 +2568,18 @@ impl PayloadSelector {
                     .all(|pattern| !pattern.check_exclude_pattern(key))
             })
             .into(),
+            PayloadSelector::Project(selector) => {
+                let mut projected = Map::new();
+                for (key, path) in &selector.project {
+                    let value = match x.get_value(path).as_slice() {
+                        [] => continue,
+                        [value] => (*value).clone(),
+                        values => Value::Array(values.iter().map(|&value| value.clone()).collect()),
+                    };
+                    projected.insert(key.clone(), value);
+                }
+                projected.into()
+            }
         }
     }
 }
@@ -2470,6 +2859,31 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_search_distance_override() {
+        let params = SearchParams {
+            distance: Some(Distance::Dot),
+            ..Default::default()
+        };
+        assert_eq!(
+            SearchParams::distance_override(Some(&params), Distance::Cosine),
+            Some(Distance::Dot),
+        );
+        assert_eq!(
+            SearchParams::distance_override(Some(&params), Distance::Dot),
+            None,
+        );
+        assert_eq!(
+            SearchParams::distance_override(None, Distance::Cosine),
+            None
+        );
+
+        assert!(Distance::Cosine.can_score_with(Distance::Dot));
+        assert!(Distance::Dot.can_score_with(Distance::Euclid));
+        // Only vectors of Cosine distance are normalized
+        assert!(!Distance::Dot.can_score_with(Distance::Cosine));
+    }
+
     #[test]
     fn test_geo_radius_check_point() {
         let radius = GeoRadius {
@@ -3622,6 +4036,48 @@ mod tests {
         });
         assert_eq!(payload, expected.into());
     }
+
+    #[test]
+    fn test_payload_selector_project() {
+        let payload = json!({
+            "a": 1,
+            "b": {
+                "c": 123,
+                "i": [
+                    {"j": 1, "k": 2},
+                    {"j": 3, "k": 4}
+                ]
+            }
+        });
+
+        let selector = PayloadSelector::Project(PayloadSelectorProject::new(BTreeMap::from([
+            ("renamed".to_string(), path("a")),
+            ("c".to_string(), path("b.c")),
+            ("js".to_string(), path("b.i[].j")),
+            ("missing".to_string(), path("b.x")),
+        ])));
+        let payload = selector.process(payload.into());
+
+        // renamed & flattened fields, multiple values are collected into an array
+        let expected = json!({
+            "renamed": 1,
+            "c": 123,
+            "js": [1, 3],
+        });
+        assert_eq!(payload, expected.into());
+
+        let selector: PayloadSelector = serde_json::from_value(json!({
+            "project": {"city": "address.city"}
+        }))
+        .unwrap();
+        assert_eq!(
+            selector,
+            PayloadSelector::Project(PayloadSelectorProject::new(BTreeMap::from([(
+                "city".to_string(),
+                path("address.city"),
+            )]))),
+        );
+    }
 }
 
 pub type TheMap<K, V> = BTreeMap<K, V>;
@@ -2,5 +2,6 @@ pub mod fmt;
 pub mod fs;
 pub mod mem;
 pub mod path;
+pub mod payload_size_limit;
 pub mod scored_point_ties;
 pub mod tar;
@@ -0,0 +1,87 @@
+use serde_json::Value;
+
+use crate::types::{Payload, ScoredPoint};
+
+/// Approximate size of a payload field, serialized as a member of a JSON object
+fn field_size(key: &str, value: &Value) -> usize {
+    // Quotes around the key, colon and separating comma
+    let value_size = serde_json::to_vec(value).map_or(0, |bytes| bytes.len());
+    key.len() + 4 + value_size
+}
+
+/// Keep top-level fields of the payload in order, while their total size fits into `max_bytes`.
+///
+/// Returns the size of the kept fields and whether any field was removed.
+pub fn truncate_payload(payload: &mut Payload, max_bytes: usize) -> (usize, bool) {
+    let mut size = 0;
+    let mut truncated = false;
+    payload.0.retain(|key, value| {
+        if truncated {
+            return false;
+        }
+        let field_size = field_size(key, value);
+        if size + field_size > max_bytes {
+            truncated = true;
+            return false;
+        }
+        size += field_size;
+        true
+    });
+    (size, truncated)
+}
+
+/// Truncate payloads of the points, so that their total size doesn't exceed `limit` bytes.
+///
+/// Points are processed in order, so that better points keep more of their payload.
+/// Returns, for each point, whether its payload is truncated.
+pub fn truncate_payloads(points: &mut [ScoredPoint], limit: usize) -> Vec<bool> {
+    let mut remaining = limit;
+    points
+        .iter_mut()
+        .map(|point| {
+            let Some(payload) = &mut point.payload else {
+                return false;
+            };
+            let (size, truncated) = truncate_payload(payload, remaining);
+            remaining -= size;
+            truncated
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn point(id: u64, payload: Value) -> ScoredPoint {
+        ScoredPoint {
+            id: id.into(),
+            version: 0,
+            score: 0.0,
+            payload: Some(serde_json::from_value(payload).unwrap()),
+            vector: None,
+            shard_key: None,
+            order_value: None,
+        }
+    }
+
+    #[test]
+    fn test_truncate_payloads() {
+        let mut points = vec![
+            point(1, json!({"a": "x".repeat(10), "b": "y".repeat(10)})),
+            point(2, json!({"a": "x".repeat(10), "b": "y".repeat(10)})),
+            point(3, json!({"a": 1})),
+        ];
+        // Field "a" takes 1 + 4 + 12 = 17 bytes, field "b" the same
+        let truncated = truncate_payloads(&mut points, 17 * 3);
+        assert_eq!(truncated, vec![false, true, true]);
+
+        let payload = |index: usize| points[index].payload.as_ref().unwrap();
+        assert_eq!(payload(0).0.len(), 2);
+        assert_eq!(payload(1).0.len(), 1);
+        assert!(payload(1).0.contains_key("a"));
+        assert!(payload(2).0.is_empty());
+    }
+}
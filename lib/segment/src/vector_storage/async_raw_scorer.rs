@@ -201,6 +201,10 @@ where
 
         pq.into_vec()
     }
+
+    fn is_stopped(&self) -> bool {
+        self.is_stopped.load(Ordering::Relaxed)
+    }
 }
 
 struct AsyncRawScorerBuilder<'a> {
@@ -139,6 +139,32 @@ pub fn open_simple_dense_half_vector_storage(
     Ok(VectorStorageEnum::DenseSimpleHalf(storage))
 }
 
+/// Write all vectors of `other` storage into the database column, in the format of this storage
+///
+/// Vectors are written one by one, so they are not held in memory. The storage can be opened
+/// from the column afterwards.
+pub fn write_simple_dense_vectors<T: PrimitiveVectorElement>(
+    database: Arc<RwLock<DB>>,
+    database_column_name: &str,
+    other: &VectorStorageEnum,
+    stopped: &AtomicBool,
+) -> OperationResult<()> {
+    let db_wrapper = DatabaseColumnWrapper::new(database, database_column_name);
+    for point_id in 0..other.total_vector_count() as PointOffsetType {
+        check_process_stopped(stopped)?;
+        let vector = T::slice_from_float_cow(Cow::try_from(other.get_vector(point_id))?);
+        let record = StoredDenseVector {
+            deleted: other.is_deleted_vector(point_id),
+            vector: vector.into_owned(),
+        };
+        db_wrapper.put(
+            bincode::serialize(&point_id).unwrap(),
+            bincode::serialize(&record).unwrap(),
+        )?;
+    }
+    db_wrapper.flusher()()
+}
+
 impl<T: PrimitiveVectorElement> SimpleDenseVectorStorage<T> {
     /// Set deleted flag for given key. Returns previous deleted state.
     #[inline]
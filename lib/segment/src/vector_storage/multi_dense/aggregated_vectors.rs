@@ -0,0 +1,221 @@
+use std::sync::atomic::AtomicBool;
+
+use bitvec::prelude::BitSlice;
+use common::types::PointOffsetType;
+
+use crate::common::operation_error::{check_process_stopped, OperationResult};
+use crate::data_types::vectors::{
+    DenseVector, MultiDenseVectorInternal, QueryVector, TypedMultiDenseVectorRef, Vector,
+    VectorElementType,
+};
+use crate::spaces::metric::Metric;
+use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
+use crate::types::{Distance, MultiVectorAggregation};
+use crate::vector_storage::chunked_vectors::ChunkedVectors;
+use crate::vector_storage::query::TransformInto;
+use crate::vector_storage::raw_scorer::{raw_aggregated_scorer_impl, RawScorer};
+use crate::vector_storage::{VectorStorage, VectorStorageEnum};
+
+/// Single vector per point, aggregated from its multi-dense vector, to build and traverse the
+/// HNSW graph with.
+///
+/// Aggregates are computed once and kept in memory, so the graph compares plain dense vectors
+/// instead of aggregating both multi-dense vectors on every comparison. Aggregates are kept as
+/// floats for any storage datatype, preprocessed for the distance.
+pub struct AggregatedVectors {
+    aggregation: MultiVectorAggregation,
+    distance: Distance,
+    vectors: ChunkedVectors<VectorElementType>,
+}
+
+impl AggregatedVectors {
+    /// Aggregate all vectors of the storage, if it is configured with an HNSW aggregation
+    pub fn build(
+        vector_storage: &VectorStorageEnum,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Option<Self>> {
+        let Some(aggregation) = vector_storage
+            .try_multi_vector_config()
+            .and_then(|config| config.hnsw_aggregation)
+        else {
+            return Ok(None);
+        };
+        let Vector::MultiDense(placeholder) = vector_storage.default_vector() else {
+            return Ok(None);
+        };
+
+        let mut aggregated_vectors = Self {
+            aggregation,
+            distance: vector_storage.distance(),
+            vectors: ChunkedVectors::new(placeholder.dim),
+        };
+        for key in 0..vector_storage.total_vector_count() as PointOffsetType {
+            check_process_stopped(stopped)?;
+            aggregated_vectors.update(key, vector_storage)?;
+        }
+        Ok(Some(aggregated_vectors))
+    }
+
+    /// Aggregate the vector again, after it was updated in the storage
+    pub fn update(
+        &mut self,
+        key: PointOffsetType,
+        vector_storage: &VectorStorageEnum,
+    ) -> OperationResult<()> {
+        let vector = vector_storage.get_vector(key);
+        let multi_vector: TypedMultiDenseVectorRef<VectorElementType> =
+            vector.as_vec_ref().try_into()?;
+        let aggregated = self.aggregate(multi_vector);
+        self.vectors.insert(key, &aggregated)?;
+        Ok(())
+    }
+
+    pub fn get(&self, key: PointOffsetType) -> &[VectorElementType] {
+        self.vectors.get(key)
+    }
+
+    /// Scorer of the aggregated vectors, the query is aggregated once
+    pub fn raw_scorer<'a>(
+        &'a self,
+        query: QueryVector,
+        point_deleted: &'a BitSlice,
+        vec_deleted: &'a BitSlice,
+        is_stopped: &'a AtomicBool,
+    ) -> OperationResult<Box<dyn RawScorer + 'a>> {
+        let query = query.transform(|vector| {
+            let multi_vector = MultiDenseVectorInternal::try_from(vector)?;
+            Ok(Vector::Dense(
+                self.aggregate(TypedMultiDenseVectorRef::from(&multi_vector)),
+            ))
+        })?;
+        raw_aggregated_scorer_impl(
+            query,
+            self,
+            self.distance,
+            point_deleted,
+            vec_deleted,
+            is_stopped,
+        )
+    }
+
+    fn aggregate(&self, multi_vector: TypedMultiDenseVectorRef<VectorElementType>) -> DenseVector {
+        let aggregated = match self.aggregation {
+            MultiVectorAggregation::First => multi_vector
+                .multi_vectors()
+                .next()
+                .map(<[VectorElementType]>::to_vec)
+                .unwrap_or_else(|| vec![0.0; multi_vector.dim]),
+            MultiVectorAggregation::Mean => {
+                let mut mean = vec![0.0; multi_vector.dim];
+                for vector in multi_vector.multi_vectors() {
+                    let vector = self.preprocess(vector.to_vec());
+                    for (sum, value) in mean.iter_mut().zip(vector) {
+                        *sum += value;
+                    }
+                }
+                let count = multi_vector.vectors_count().max(1) as f32;
+                mean.iter_mut().for_each(|sum| *sum /= count);
+                mean
+            }
+        };
+        self.preprocess(aggregated)
+    }
+
+    fn preprocess(&self, vector: DenseVector) -> DenseVector {
+        match self.distance {
+            Distance::Cosine => <CosineMetric as Metric<VectorElementType>>::preprocess(vector),
+            Distance::Euclid => <EuclidMetric as Metric<VectorElementType>>::preprocess(vector),
+            Distance::Dot => <DotProductMetric as Metric<VectorElementType>>::preprocess(vector),
+            Distance::Manhattan => {
+                <ManhattanMetric as Metric<VectorElementType>>::preprocess(vector)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bitvec::prelude::BitVec;
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::common::rocksdb_wrapper::{open_db, DB_VECTOR_CF};
+    use crate::data_types::vectors::VectorRef;
+    use crate::types::MultiVectorConfig;
+    use crate::vector_storage::multi_dense::simple_multi_dense_vector_storage::open_simple_multi_dense_vector_storage;
+
+    #[test]
+    fn test_aggregated_vectors_follow_storage() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let db = open_db(dir.path(), &[DB_VECTOR_CF]).unwrap();
+        let stopped = AtomicBool::new(false);
+        let mut storage = open_simple_multi_dense_vector_storage(
+            db,
+            DB_VECTOR_CF,
+            2,
+            Distance::Dot,
+            MultiVectorConfig {
+                hnsw_aggregation: Some(MultiVectorAggregation::Mean),
+                ..Default::default()
+            },
+            &stopped,
+        )
+        .unwrap();
+
+        let insert = |storage: &mut VectorStorageEnum, key, vectors: Vec<f32>| {
+            let multi_vector = MultiDenseVectorInternal::new(vectors, 2);
+            storage
+                .insert_vector(
+                    key,
+                    VectorRef::from(TypedMultiDenseVectorRef::from(&multi_vector)),
+                )
+                .unwrap();
+        };
+        insert(&mut storage, 0, vec![1.0, 0.0, 3.0, 2.0]);
+        insert(&mut storage, 1, vec![0.0, 1.0]);
+
+        let mut aggregated_vectors = AggregatedVectors::build(&storage, &stopped)
+            .unwrap()
+            .unwrap();
+        assert_eq!(aggregated_vectors.get(0), &[2.0, 1.0]);
+        assert_eq!(aggregated_vectors.get(1), &[0.0, 1.0]);
+
+        insert(&mut storage, 1, vec![4.0, 0.0, 0.0, 4.0]);
+        aggregated_vectors.update(1, &storage).unwrap();
+        assert_eq!(aggregated_vectors.get(1), &[2.0, 2.0]);
+
+        let query = MultiDenseVectorInternal::new(vec![1.0, 1.0, 1.0, 3.0], 2);
+        let point_deleted = BitVec::repeat(false, 2);
+        let raw_scorer = aggregated_vectors
+            .raw_scorer(
+                QueryVector::Nearest(Vector::MultiDense(query)),
+                &point_deleted,
+                storage.deleted_vector_bitslice(),
+                &stopped,
+            )
+            .unwrap();
+        // Query is aggregated into [1.0, 2.0]
+        assert_eq!(raw_scorer.score_point(0), 4.0);
+        assert_eq!(raw_scorer.score_point(1), 6.0);
+        assert_eq!(raw_scorer.score_internal(0, 1), 6.0);
+    }
+
+    #[test]
+    fn test_no_aggregated_vectors_without_aggregation() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let db = open_db(dir.path(), &[DB_VECTOR_CF]).unwrap();
+        let stopped = AtomicBool::new(false);
+        let storage = open_simple_multi_dense_vector_storage(
+            db,
+            DB_VECTOR_CF,
+            2,
+            Distance::Dot,
+            MultiVectorConfig::default(),
+            &stopped,
+        )
+        .unwrap();
+        assert!(AggregatedVectors::build(&storage, &stopped)
+            .unwrap()
+            .is_none());
+    }
+}
@@ -1,2 +1,3 @@
+pub mod aggregated_vectors;
 pub mod appendable_mmap_multi_dense_vector_storage;
 pub mod simple_multi_dense_vector_storage;
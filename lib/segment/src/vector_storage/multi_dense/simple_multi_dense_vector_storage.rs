@@ -104,6 +104,35 @@ pub fn open_simple_multi_dense_vector_storage_half(
     Ok(VectorStorageEnum::MultiDenseSimpleHalf(storage))
 }
 
+/// Write all vectors of `other` storage into the database column, in the format of this storage
+///
+/// Vectors are written one by one, so they are not held in memory. The storage can be opened
+/// from the column afterwards.
+pub fn write_simple_multi_dense_vectors<T: PrimitiveVectorElement>(
+    database: Arc<RwLock<DB>>,
+    database_column_name: &str,
+    other: &VectorStorageEnum,
+    stopped: &AtomicBool,
+) -> OperationResult<()> {
+    let db_wrapper = DatabaseColumnWrapper::new(database, database_column_name);
+    for point_id in 0..other.total_vector_count() as PointOffsetType {
+        check_process_stopped(stopped)?;
+        let other_vector = other.get_vector(point_id);
+        let multi_vector: TypedMultiDenseVectorRef<VectorElementType> =
+            other_vector.as_vec_ref().try_into()?;
+        let multi_vector = T::from_float_multivector(CowMultiVector::Borrowed(multi_vector));
+        let record = StoredMultiDenseVector {
+            deleted: other.is_deleted_vector(point_id),
+            vector: multi_vector.as_vec_ref().to_owned(),
+        };
+        db_wrapper.put(
+            bincode::serialize(&point_id).unwrap(),
+            bincode::serialize(&record).unwrap(),
+        )?;
+    }
+    db_wrapper.flusher()()
+}
+
 fn open_simple_multi_dense_vector_storage_impl<T: PrimitiveVectorElement>(
     database: Arc<RwLock<DB>>,
     database_column_name: &str,
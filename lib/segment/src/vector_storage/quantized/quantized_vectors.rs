@@ -4,6 +4,7 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use bitvec::slice::BitSlice;
 use common::types::PointOffsetType;
 use io::file_operations::{atomic_save_json, read_json};
+use memory::budget::{self, MemoryCategory, MemoryReservation};
 use quantization::encoded_vectors_binary::{EncodedBinVector, EncodedVectorsBin};
 use quantization::{
     EncodedQueryPQ, EncodedQueryU8, EncodedVectors, EncodedVectorsPQ, EncodedVectorsU8,
@@ -100,6 +101,8 @@ pub struct QuantizedVectors {
     path: PathBuf,
     distance: Distance,
     datatype: VectorStorageDatatype,
+    /// Memory of quantized data, loaded into RAM, reserved from the global memory budget
+    memory_reservation: Option<MemoryReservation>,
 }
 
 impl QuantizedVectors {
@@ -110,6 +113,36 @@ impl QuantizedVectors {
         )
     }
 
+    /// Whether quantized data is memory mapped, instead of loaded into RAM
+    pub fn is_on_disk(&self) -> bool {
+        match self.storage_impl {
+            QuantizedVectorStorage::ScalarRam(_) => false,
+            QuantizedVectorStorage::ScalarMmap(_) => true,
+            QuantizedVectorStorage::PQRam(_) => false,
+            QuantizedVectorStorage::PQMmap(_) => true,
+            QuantizedVectorStorage::BinaryRam(_) => false,
+            QuantizedVectorStorage::BinaryMmap(_) => true,
+            QuantizedVectorStorage::ScalarRamMulti(_) => false,
+            QuantizedVectorStorage::ScalarMmapMulti(_) => true,
+            QuantizedVectorStorage::PQRamMulti(_) => false,
+            QuantizedVectorStorage::PQMmapMulti(_) => true,
+            QuantizedVectorStorage::BinaryRamMulti(_) => false,
+            QuantizedVectorStorage::BinaryMmapMulti(_) => true,
+        }
+    }
+
+    /// Memory mapped data files, empty if quantized data is loaded into RAM
+    pub fn mmap_files(&self) -> Vec<PathBuf> {
+        if !self.is_on_disk() {
+            return Vec::new();
+        }
+        let mut files = vec![self.path.join(QUANTIZED_DATA_PATH)];
+        if self.is_multivector() {
+            files.push(self.path.join(QUANTIZED_OFFSETS_PATH));
+        }
+        files
+    }
+
     pub fn is_multivector(&self) -> bool {
         match self.storage_impl {
             QuantizedVectorStorage::ScalarRam(_) => false,
@@ -322,6 +355,8 @@ impl QuantizedVectors {
             path: path.to_path_buf(),
             distance,
             datatype,
+            // Segment builder only keeps created vectors until the segment is loaded
+            memory_reservation: None,
         };
 
         quantized_vectors.save_to(path)?;
@@ -414,6 +449,8 @@ impl QuantizedVectors {
             path: path.to_path_buf(),
             distance,
             datatype,
+            // Segment builder only keeps created vectors until the segment is loaded
+            memory_reservation: None,
         };
 
         quantized_vectors.save_to(path)?;
@@ -434,13 +471,32 @@ impl QuantizedVectors {
         let meta_path = path.join(QUANTIZED_META_PATH);
         let config_path = path.join(QUANTIZED_CONFIG_PATH);
         let config: QuantizedVectorsConfig = read_json(&config_path)?;
+
+        // Quantized data is loaded into RAM only if it fits into the memory budget
+        let always_ram = match &config.quantization_config {
+            QuantizationConfig::Scalar(ScalarQuantization { scalar }) => scalar.always_ram,
+            QuantizationConfig::Product(ProductQuantization { product }) => product.always_ram,
+            QuantizationConfig::Binary(BinaryQuantization { binary }) => binary.always_ram,
+        };
+        let memory_reservation = if Self::is_ram(always_ram, on_disk_vector_storage) {
+            let reservation =
+                budget::global().try_reserve_file(MemoryCategory::Quantization, &data_path);
+            if reservation.is_none() {
+                log::info!(
+                    "Memory budget is exhausted, using memmap for quantized vectors at {path:?}"
+                );
+            }
+            reservation
+        } else {
+            None
+        };
         let quantized_store = if let Some(multivector_config) =
             vector_storage.try_multi_vector_config()
         {
             let offsets_path = path.join(QUANTIZED_OFFSETS_PATH);
             match &config.quantization_config {
                 QuantizationConfig::Scalar(ScalarQuantization { scalar }) => {
-                    if Self::is_ram(scalar.always_ram, on_disk_vector_storage) {
+                    if memory_reservation.is_some() {
                         QuantizedVectorStorage::ScalarRamMulti(
                             QuantizedMultivectorStorage::load_multi(
                                 &data_path,
@@ -463,7 +519,7 @@ impl QuantizedVectors {
                     }
                 }
                 QuantizationConfig::Product(ProductQuantization { product: pq }) => {
-                    if Self::is_ram(pq.always_ram, on_disk_vector_storage) {
+                    if memory_reservation.is_some() {
                         QuantizedVectorStorage::PQRamMulti(QuantizedMultivectorStorage::load_multi(
                             &data_path,
                             &meta_path,
@@ -484,7 +540,7 @@ impl QuantizedVectors {
                     }
                 }
                 QuantizationConfig::Binary(BinaryQuantization { binary }) => {
-                    if Self::is_ram(binary.always_ram, on_disk_vector_storage) {
+                    if memory_reservation.is_some() {
                         QuantizedVectorStorage::BinaryRamMulti(
                             QuantizedMultivectorStorage::load_multi(
                                 &data_path,
@@ -510,7 +566,7 @@ impl QuantizedVectors {
         } else {
             match &config.quantization_config {
                 QuantizationConfig::Scalar(ScalarQuantization { scalar }) => {
-                    if Self::is_ram(scalar.always_ram, on_disk_vector_storage) {
+                    if memory_reservation.is_some() {
                         QuantizedVectorStorage::ScalarRam(EncodedVectorsU8::load(
                             &data_path,
                             &meta_path,
@@ -525,7 +581,7 @@ impl QuantizedVectors {
                     }
                 }
                 QuantizationConfig::Product(ProductQuantization { product: pq }) => {
-                    if Self::is_ram(pq.always_ram, on_disk_vector_storage) {
+                    if memory_reservation.is_some() {
                         QuantizedVectorStorage::PQRam(EncodedVectorsPQ::load(
                             &data_path,
                             &meta_path,
@@ -540,7 +596,7 @@ impl QuantizedVectors {
                     }
                 }
                 QuantizationConfig::Binary(BinaryQuantization { binary }) => {
-                    if Self::is_ram(binary.always_ram, on_disk_vector_storage) {
+                    if memory_reservation.is_some() {
                         QuantizedVectorStorage::BinaryRam(EncodedVectorsBin::load(
                             &data_path,
                             &meta_path,
@@ -563,6 +619,7 @@ impl QuantizedVectors {
             path: path.to_path_buf(),
             distance,
             datatype,
+            memory_reservation,
         })
     }
 
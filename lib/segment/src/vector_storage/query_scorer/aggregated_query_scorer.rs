@@ -0,0 +1,91 @@
+use std::marker::PhantomData;
+
+use common::types::{PointOffsetType, ScoreType};
+
+use crate::data_types::vectors::{DenseVector, VectorElementType};
+use crate::spaces::metric::Metric;
+use crate::vector_storage::multi_dense::aggregated_vectors::AggregatedVectors;
+use crate::vector_storage::query::Query;
+use crate::vector_storage::query_scorer::QueryScorer;
+
+/// Scorer of aggregated multi-dense vectors against a single aggregated query
+pub struct AggregatedMetricQueryScorer<'a, TMetric: Metric<VectorElementType>> {
+    aggregated_vectors: &'a AggregatedVectors,
+    query: DenseVector,
+    metric: PhantomData<TMetric>,
+}
+
+impl<'a, TMetric: Metric<VectorElementType>> AggregatedMetricQueryScorer<'a, TMetric> {
+    /// The query must be aggregated by the same `aggregated_vectors`
+    pub fn new(query: DenseVector, aggregated_vectors: &'a AggregatedVectors) -> Self {
+        Self {
+            aggregated_vectors,
+            query,
+            metric: PhantomData,
+        }
+    }
+}
+
+impl<'a, TMetric: Metric<VectorElementType>> QueryScorer<[VectorElementType]>
+    for AggregatedMetricQueryScorer<'a, TMetric>
+{
+    #[inline]
+    fn score_stored(&self, idx: PointOffsetType) -> ScoreType {
+        TMetric::similarity(&self.query, self.aggregated_vectors.get(idx))
+    }
+
+    #[inline]
+    fn score(&self, v2: &[VectorElementType]) -> ScoreType {
+        TMetric::similarity(&self.query, v2)
+    }
+
+    fn score_internal(&self, point_a: PointOffsetType, point_b: PointOffsetType) -> ScoreType {
+        TMetric::similarity(
+            self.aggregated_vectors.get(point_a),
+            self.aggregated_vectors.get(point_b),
+        )
+    }
+}
+
+/// Scorer of aggregated multi-dense vectors against a query of multiple aggregated examples
+pub struct AggregatedCustomQueryScorer<
+    'a,
+    TMetric: Metric<VectorElementType>,
+    TQuery: Query<DenseVector>,
+> {
+    aggregated_vectors: &'a AggregatedVectors,
+    query: TQuery,
+    metric: PhantomData<TMetric>,
+}
+
+impl<'a, TMetric: Metric<VectorElementType>, TQuery: Query<DenseVector>>
+    AggregatedCustomQueryScorer<'a, TMetric, TQuery>
+{
+    /// Examples of the query must be aggregated by the same `aggregated_vectors`
+    pub fn new(query: TQuery, aggregated_vectors: &'a AggregatedVectors) -> Self {
+        Self {
+            aggregated_vectors,
+            query,
+            metric: PhantomData,
+        }
+    }
+}
+
+impl<'a, TMetric: Metric<VectorElementType>, TQuery: Query<DenseVector>>
+    QueryScorer<[VectorElementType]> for AggregatedCustomQueryScorer<'a, TMetric, TQuery>
+{
+    #[inline]
+    fn score_stored(&self, idx: PointOffsetType) -> ScoreType {
+        self.score(self.aggregated_vectors.get(idx))
+    }
+
+    #[inline]
+    fn score(&self, against: &[VectorElementType]) -> ScoreType {
+        self.query
+            .score_by(|example| TMetric::similarity(example, against))
+    }
+
+    fn score_internal(&self, _point_a: PointOffsetType, _point_b: PointOffsetType) -> ScoreType {
+        unimplemented!("Custom scorer can compare against multiple vectors, not just one")
+    }
+}
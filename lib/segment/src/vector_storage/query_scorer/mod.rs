@@ -5,6 +5,7 @@ use crate::data_types::vectors::TypedMultiDenseVectorRef;
 use crate::spaces::metric::Metric;
 use crate::types::{MultiVectorComparator, MultiVectorConfig};
 
+pub mod aggregated_query_scorer;
 pub mod custom_query_scorer;
 pub mod metric_query_scorer;
 pub mod multi_custom_query_scorer;
@@ -4,7 +4,11 @@ use bitvec::prelude::BitSlice;
 use common::types::{PointOffsetType, ScoreType, ScoredPointOffset};
 use sparse::common::sparse_vector::SparseVector;
 
+use super::multi_dense::aggregated_vectors::AggregatedVectors;
 use super::query::{ContextQuery, DiscoveryQuery, RecoQuery, TransformInto};
+use super::query_scorer::aggregated_query_scorer::{
+    AggregatedCustomQueryScorer, AggregatedMetricQueryScorer,
+};
 use super::query_scorer::custom_query_scorer::CustomQueryScorer;
 use super::query_scorer::multi_custom_query_scorer::MultiCustomQueryScorer;
 use super::query_scorer::sparse_custom_query_scorer::SparseCustomQueryScorer;
@@ -82,6 +86,9 @@ pub trait RawScorer {
     ) -> Vec<ScoredPointOffset>;
 
     fn peek_top_all(&self, top: usize) -> Vec<ScoredPointOffset>;
+
+    /// Return true if the search is stopped externally, so that callers can stop iterating early
+    fn is_stopped(&self) -> bool;
 }
 
 pub struct RawScorerImpl<'a, TVector: ?Sized, TQueryScorer>
@@ -108,18 +115,38 @@ pub fn new_stoppable_raw_scorer<'a>(
     vector_storage: &'a VectorStorageEnum,
     point_deleted: &'a BitSlice,
     is_stopped: &'a AtomicBool,
+) -> OperationResult<Box<dyn RawScorer + 'a>> {
+    new_stoppable_raw_scorer_with_distance(
+        query,
+        vector_storage,
+        vector_storage.distance(),
+        point_deleted,
+        is_stopped,
+    )
+}
+
+/// Same as [`new_stoppable_raw_scorer`], but scores vectors with the given `distance` instead of
+/// the distance of the storage. Ignored for sparse vectors, which are always scored with dot product.
+pub fn new_stoppable_raw_scorer_with_distance<'a>(
+    query: QueryVector,
+    vector_storage: &'a VectorStorageEnum,
+    distance: Distance,
+    point_deleted: &'a BitSlice,
+    is_stopped: &'a AtomicBool,
 ) -> OperationResult<Box<dyn RawScorer + 'a>> {
     match vector_storage {
-        VectorStorageEnum::DenseSimple(vs) => raw_scorer_impl(query, vs, point_deleted, is_stopped),
+        VectorStorageEnum::DenseSimple(vs) => {
+            raw_scorer_impl(query, vs, distance, point_deleted, is_stopped)
+        }
         VectorStorageEnum::DenseSimpleByte(vs) => {
-            raw_scorer_byte_impl(query, vs, point_deleted, is_stopped)
+            raw_scorer_byte_impl(query, vs, distance, point_deleted, is_stopped)
         }
         VectorStorageEnum::DenseSimpleHalf(vs) => {
-            raw_scorer_half_impl(query, vs, point_deleted, is_stopped)
+            raw_scorer_half_impl(query, vs, distance, point_deleted, is_stopped)
         }
 
         VectorStorageEnum::DenseMemmap(vs) => {
-            if vs.has_async_reader() {
+            if vs.has_async_reader() && distance == vs.distance() {
                 #[cfg(target_os = "linux")]
                 {
                     let scorer_result =
@@ -134,46 +161,46 @@ pub fn new_stoppable_raw_scorer<'a>(
                 log::warn!("async raw scorer is only supported on Linux");
             }
 
-            raw_scorer_impl(query, vs.as_ref(), point_deleted, is_stopped)
+            raw_scorer_impl(query, vs.as_ref(), distance, point_deleted, is_stopped)
         }
 
         // TODO(byte_storage): Implement async raw scorer for DenseMemmapByte and DenseMemmapHalf
         VectorStorageEnum::DenseMemmapByte(vs) => {
-            raw_scorer_byte_impl(query, vs.as_ref(), point_deleted, is_stopped)
+            raw_scorer_byte_impl(query, vs.as_ref(), distance, point_deleted, is_stopped)
         }
         VectorStorageEnum::DenseMemmapHalf(vs) => {
-            raw_scorer_half_impl(query, vs.as_ref(), point_deleted, is_stopped)
+            raw_scorer_half_impl(query, vs.as_ref(), distance, point_deleted, is_stopped)
         }
 
         VectorStorageEnum::DenseAppendableMemmap(vs) => {
-            raw_scorer_impl(query, vs.as_ref(), point_deleted, is_stopped)
+            raw_scorer_impl(query, vs.as_ref(), distance, point_deleted, is_stopped)
         }
         VectorStorageEnum::DenseAppendableMemmapByte(vs) => {
-            raw_scorer_byte_impl(query, vs.as_ref(), point_deleted, is_stopped)
+            raw_scorer_byte_impl(query, vs.as_ref(), distance, point_deleted, is_stopped)
         }
         VectorStorageEnum::DenseAppendableMemmapHalf(vs) => {
-            raw_scorer_half_impl(query, vs.as_ref(), point_deleted, is_stopped)
+            raw_scorer_half_impl(query, vs.as_ref(), distance, point_deleted, is_stopped)
         }
         VectorStorageEnum::SparseSimple(vs) => {
             raw_sparse_scorer_impl(query, vs, point_deleted, is_stopped)
         }
         VectorStorageEnum::MultiDenseSimple(vs) => {
-            raw_multi_scorer_impl(query, vs, point_deleted, is_stopped)
+            raw_multi_scorer_impl(query, vs, distance, point_deleted, is_stopped)
         }
         VectorStorageEnum::MultiDenseSimpleByte(vs) => {
-            raw_multi_scorer_byte_impl(query, vs, point_deleted, is_stopped)
+            raw_multi_scorer_byte_impl(query, vs, distance, point_deleted, is_stopped)
         }
         VectorStorageEnum::MultiDenseSimpleHalf(vs) => {
-            raw_multi_scorer_half_impl(query, vs, point_deleted, is_stopped)
+            raw_multi_scorer_half_impl(query, vs, distance, point_deleted, is_stopped)
         }
         VectorStorageEnum::MultiDenseAppendableMemmap(vs) => {
-            raw_multi_scorer_impl(query, vs.as_ref(), point_deleted, is_stopped)
+            raw_multi_scorer_impl(query, vs.as_ref(), distance, point_deleted, is_stopped)
         }
         VectorStorageEnum::MultiDenseAppendableMemmapByte(vs) => {
-            raw_multi_scorer_byte_impl(query, vs.as_ref(), point_deleted, is_stopped)
+            raw_multi_scorer_byte_impl(query, vs.as_ref(), distance, point_deleted, is_stopped)
         }
         VectorStorageEnum::MultiDenseAppendableMemmapHalf(vs) => {
-            raw_multi_scorer_half_impl(query, vs.as_ref(), point_deleted, is_stopped)
+            raw_multi_scorer_half_impl(query, vs.as_ref(), distance, point_deleted, is_stopped)
         }
     }
 }
@@ -232,10 +259,11 @@ pub fn new_raw_scorer<'a>(
 pub fn raw_scorer_impl<'a, TVectorStorage: DenseVectorStorage<VectorElementType>>(
     query: QueryVector,
     vector_storage: &'a TVectorStorage,
+    distance: Distance,
     point_deleted: &'a BitSlice,
     is_stopped: &'a AtomicBool,
 ) -> OperationResult<Box<dyn RawScorer + 'a>> {
-    match vector_storage.distance() {
+    match distance {
         Distance::Cosine => new_scorer_with_metric::<CosineMetric, _>(
             query,
             vector_storage,
@@ -326,10 +354,11 @@ fn new_scorer_with_metric<
 pub fn raw_scorer_byte_impl<'a, TVectorStorage: DenseVectorStorage<VectorElementTypeByte>>(
     query: QueryVector,
     vector_storage: &'a TVectorStorage,
+    distance: Distance,
     point_deleted: &'a BitSlice,
     is_stopped: &'a AtomicBool,
 ) -> OperationResult<Box<dyn RawScorer + 'a>> {
-    match vector_storage.distance() {
+    match distance {
         Distance::Cosine => new_scorer_byte_with_metric::<CosineMetric, _>(
             query,
             vector_storage,
@@ -420,10 +449,11 @@ fn new_scorer_byte_with_metric<
 pub fn raw_scorer_half_impl<'a, TVectorStorage: DenseVectorStorage<VectorElementTypeHalf>>(
     query: QueryVector,
     vector_storage: &'a TVectorStorage,
+    distance: Distance,
     point_deleted: &'a BitSlice,
     is_stopped: &'a AtomicBool,
 ) -> OperationResult<Box<dyn RawScorer + 'a>> {
-    match vector_storage.distance() {
+    match distance {
         Distance::Cosine => new_scorer_half_with_metric::<CosineMetric, _>(
             query,
             vector_storage,
@@ -511,6 +541,91 @@ fn new_scorer_half_with_metric<
     }
 }
 
+/// Scorer of aggregated multi-dense vectors, the query vectors must be aggregated already
+pub fn raw_aggregated_scorer_impl<'a>(
+    query: QueryVector,
+    aggregated_vectors: &'a AggregatedVectors,
+    distance: Distance,
+    point_deleted: &'a BitSlice,
+    vec_deleted: &'a BitSlice,
+    is_stopped: &'a AtomicBool,
+) -> OperationResult<Box<dyn RawScorer + 'a>> {
+    match distance {
+        Distance::Cosine => new_aggregated_scorer_with_metric::<CosineMetric>(
+            query,
+            aggregated_vectors,
+            point_deleted,
+            vec_deleted,
+            is_stopped,
+        ),
+        Distance::Euclid => new_aggregated_scorer_with_metric::<EuclidMetric>(
+            query,
+            aggregated_vectors,
+            point_deleted,
+            vec_deleted,
+            is_stopped,
+        ),
+        Distance::Dot => new_aggregated_scorer_with_metric::<DotProductMetric>(
+            query,
+            aggregated_vectors,
+            point_deleted,
+            vec_deleted,
+            is_stopped,
+        ),
+        Distance::Manhattan => new_aggregated_scorer_with_metric::<ManhattanMetric>(
+            query,
+            aggregated_vectors,
+            point_deleted,
+            vec_deleted,
+            is_stopped,
+        ),
+    }
+}
+
+fn new_aggregated_scorer_with_metric<'a, TMetric: Metric<VectorElementType> + 'a>(
+    query: QueryVector,
+    aggregated_vectors: &'a AggregatedVectors,
+    point_deleted: &'a BitSlice,
+    vec_deleted: &'a BitSlice,
+    is_stopped: &'a AtomicBool,
+) -> OperationResult<Box<dyn RawScorer + 'a>> {
+    match query {
+        QueryVector::Nearest(vector) => raw_scorer_from_query_scorer(
+            AggregatedMetricQueryScorer::<TMetric>::new(vector.try_into()?, aggregated_vectors),
+            point_deleted,
+            vec_deleted,
+            is_stopped,
+        ),
+        QueryVector::Recommend(reco_query) => {
+            let reco_query: RecoQuery<DenseVector> = reco_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                AggregatedCustomQueryScorer::<TMetric, _>::new(reco_query, aggregated_vectors),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+        QueryVector::Discovery(discovery_query) => {
+            let discovery_query: DiscoveryQuery<DenseVector> = discovery_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                AggregatedCustomQueryScorer::<TMetric, _>::new(discovery_query, aggregated_vectors),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+        QueryVector::Context(context_query) => {
+            let context_query: ContextQuery<DenseVector> = context_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                AggregatedCustomQueryScorer::<TMetric, _>::new(context_query, aggregated_vectors),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+    }
+}
+
 pub fn raw_scorer_from_query_scorer<'a, TVector, TQueryScorer>(
     query_scorer: TQueryScorer,
     point_deleted: &'a BitSlice,
@@ -533,10 +648,11 @@ where
 pub fn raw_multi_scorer_impl<'a, TVectorStorage: MultiVectorStorage<VectorElementType>>(
     query: QueryVector,
     vector_storage: &'a TVectorStorage,
+    distance: Distance,
     point_deleted: &'a BitSlice,
     is_stopped: &'a AtomicBool,
 ) -> OperationResult<Box<dyn RawScorer + 'a>> {
-    match vector_storage.distance() {
+    match distance {
         Distance::Cosine => new_multi_scorer_with_metric::<CosineMetric, _>(
             query,
             vector_storage,
@@ -629,10 +745,11 @@ fn new_multi_scorer_with_metric<
 pub fn raw_multi_scorer_byte_impl<'a, TVectorStorage: MultiVectorStorage<VectorElementTypeByte>>(
     query: QueryVector,
     vector_storage: &'a TVectorStorage,
+    distance: Distance,
     point_deleted: &'a BitSlice,
     is_stopped: &'a AtomicBool,
 ) -> OperationResult<Box<dyn RawScorer + 'a>> {
-    match vector_storage.distance() {
+    match distance {
         Distance::Cosine => new_multi_scorer_byte_with_metric::<CosineMetric, _>(
             query,
             vector_storage,
@@ -725,10 +842,11 @@ fn new_multi_scorer_byte_with_metric<
 pub fn raw_multi_scorer_half_impl<'a, TVectorStorage: MultiVectorStorage<VectorElementTypeHalf>>(
     query: QueryVector,
     vector_storage: &'a TVectorStorage,
+    distance: Distance,
     point_deleted: &'a BitSlice,
     is_stopped: &'a AtomicBool,
 ) -> OperationResult<Box<dyn RawScorer + 'a>> {
-    match vector_storage.distance() {
+    match distance {
         Distance::Cosine => new_multi_scorer_half_with_metric::<CosineMetric, _>(
             query,
             vector_storage,
@@ -902,6 +1020,10 @@ where
             });
         peek_top_largest_iterable(scores, top)
     }
+
+    fn is_stopped(&self) -> bool {
+        self.is_stopped.load(Ordering::Relaxed)
+    }
 }
 
 #[inline]
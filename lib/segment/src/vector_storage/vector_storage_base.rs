@@ -51,6 +51,11 @@ pub trait VectorStorage {
 
     fn available_size_in_bytes(&self) -> usize;
 
+    /// Average size of a single available vector, used to estimate the amount of data read by a search
+    fn average_vector_size_in_bytes(&self) -> usize {
+        self.available_size_in_bytes() / self.available_vector_count().max(1)
+    }
+
     /// Get the vector by the given key
     fn get_vector(&self, key: PointOffsetType) -> CowVector;
 
@@ -48,6 +48,8 @@ fn test_batch_and_single_request_equivalency() {
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_columns: Default::default(),
+        payload_compression: Default::default(),
     };
 
     let int_key = "int";
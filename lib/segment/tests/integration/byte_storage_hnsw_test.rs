@@ -134,6 +134,8 @@ fn test_byte_storage_hnsw(
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_columns: Default::default(),
+        payload_compression: Default::default(),
     };
     let config_byte = SegmentConfig {
         vector_data: HashMap::from([(
@@ -150,6 +152,8 @@ fn test_byte_storage_hnsw(
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_columns: Default::default(),
+        payload_compression: Default::default(),
     };
 
     let int_key = "int";
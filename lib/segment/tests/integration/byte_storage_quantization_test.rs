@@ -257,6 +257,8 @@ fn test_byte_storage_binary_quantization_hnsw(
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_columns: Default::default(),
+        payload_compression: Default::default(),
     };
 
     let int_key = "int";
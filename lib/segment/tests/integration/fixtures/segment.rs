@@ -165,6 +165,8 @@ pub fn build_segment_3(path: &Path) -> Segment {
             ]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_columns: Default::default(),
+            payload_compression: Default::default(),
         },
         true,
     )
@@ -258,6 +260,8 @@ pub fn build_segment_sparse_1(path: &Path) -> Segment {
                 },
             )]),
             payload_storage_type: Default::default(),
+            payload_columns: Default::default(),
+            payload_compression: Default::default(),
         },
         true,
     )
@@ -343,6 +347,8 @@ pub fn build_segment_sparse_2(path: &Path) -> Segment {
                 },
             )]),
             payload_storage_type: Default::default(),
+            payload_columns: Default::default(),
+            payload_compression: Default::default(),
         },
         true,
     )
@@ -80,6 +80,8 @@ fn hnsw_discover_precision() {
             },
         )]),
         payload_storage_type: Default::default(),
+        payload_columns: Default::default(),
+        payload_compression: Default::default(),
         sparse_vector_data: Default::default(),
     };
 
@@ -192,6 +194,8 @@ fn filtered_hnsw_discover_precision() {
             },
         )]),
         payload_storage_type: Default::default(),
+        payload_columns: Default::default(),
+        payload_compression: Default::default(),
         sparse_vector_data: Default::default(),
     };
 
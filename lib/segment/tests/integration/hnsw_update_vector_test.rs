@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+use common::cpu::CpuPermit;
+use common::types::TelemetryDetail;
+use rand::thread_rng;
+use segment::data_types::vectors::{
+    only_default_vector, QueryVector, VectorRef, DEFAULT_VECTOR_NAME,
+};
+use segment::entry::entry_point::SegmentEntry;
+use segment::fixtures::payload_fixtures::random_vector;
+use segment::index::hnsw_index::graph_links::GraphLinksRam;
+use segment::index::hnsw_index::hnsw::HNSWIndex;
+use segment::index::hnsw_index::num_rayon_threads;
+use segment::index::VectorIndex;
+use segment::segment_constructor::build_segment;
+use segment::types::{
+    Distance, HnswConfig, Indexes, SegmentConfig, SeqNumberType, VectorDataConfig,
+    VectorStorageType,
+};
+use tempfile::Builder;
+
+#[test]
+fn test_hnsw_update_vector_in_place() {
+    let stopped = AtomicBool::new(false);
+
+    let dim = 8;
+    let num_vectors: u64 = 1_000;
+    let top = 3;
+
+    let mut rnd = thread_rng();
+
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let hnsw_dir = Builder::new().prefix("hnsw_dir").tempdir().unwrap();
+
+    let config = SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: dim,
+                distance: Distance::Euclid,
+                storage_type: VectorStorageType::Memory,
+                index: Indexes::Plain {},
+                quantization_config: None,
+                multivec_config: None,
+                datatype: None,
+            },
+        )]),
+        sparse_vector_data: Default::default(),
+        payload_storage_type: Default::default(),
+        payload_columns: Default::default(),
+        payload_compression: Default::default(),
+    };
+
+    let mut segment = build_segment(dir.path(), &config, true).unwrap();
+    for n in 0..num_vectors {
+        let vector = random_vector(&mut rnd, dim);
+        segment
+            .upsert_point(n as SeqNumberType, n.into(), only_default_vector(&vector))
+            .unwrap();
+    }
+
+    let hnsw_config = HnswConfig {
+        m: 8,
+        ef_construct: 16,
+        full_scan_threshold: 1, // KB
+        max_indexing_threads: 2,
+        on_disk: Some(false),
+        payload_m: None,
+    };
+
+    let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
+    let permit = Arc::new(CpuPermit::dummy(permit_cpu_count as u32));
+
+    let open_index = || {
+        HNSWIndex::<GraphLinksRam>::open(
+            hnsw_dir.path(),
+            segment.id_tracker.clone(),
+            segment.vector_data[DEFAULT_VECTOR_NAME]
+                .vector_storage
+                .clone(),
+            segment.vector_data[DEFAULT_VECTOR_NAME]
+                .quantized_vectors
+                .clone(),
+            segment.payload_index.clone(),
+            hnsw_config.clone(),
+        )
+        .unwrap()
+    };
+
+    let mut hnsw_index = open_index();
+    hnsw_index.build_index(permit, &stopped).unwrap();
+
+    // Move the point far away from all other points, the graph doesn't know its new position
+    let point_id = 42;
+    let new_vector = vec![100.0; dim];
+    hnsw_index
+        .update_vector(point_id, Some(VectorRef::from(&new_vector)))
+        .unwrap();
+
+    let query = QueryVector::from(vec![100.0; dim]);
+    let search = |hnsw_index: &HNSWIndex<GraphLinksRam>| {
+        hnsw_index
+            .search(&[&query], None, top, None, &Default::default())
+            .unwrap()
+            .pop()
+            .unwrap()
+    };
+
+    let result = search(&hnsw_index);
+    assert_eq!(result.len(), top);
+    assert_eq!(result[0].idx, point_id);
+    assert_eq!(result[0].score, 0.0);
+    assert!(hnsw_index
+        .files()
+        .iter()
+        .any(|file| file.ends_with("stale_points.json")));
+
+    assert_eq!(
+        hnsw_index
+            .get_telemetry_data(TelemetryDetail::default())
+            .stale_points,
+        Some(1),
+    );
+
+    // Stale points are persisted on flush
+    hnsw_index.stale_points_flusher()().unwrap();
+    drop(hnsw_index);
+    let mut hnsw_index = open_index();
+    let result = search(&hnsw_index);
+    assert_eq!(result[0].idx, point_id);
+
+    // Number of stale points is limited to a fraction of indexed points
+    let mut next_point_id = 100;
+    while hnsw_index.can_update_in_place() {
+        hnsw_index
+            .update_vector(next_point_id, Some(VectorRef::from(&new_vector)))
+            .unwrap();
+        next_point_id += 1;
+    }
+    assert_eq!(
+        hnsw_index
+            .get_telemetry_data(TelemetryDetail::default())
+            .stale_points,
+        Some(50),
+    );
+}
@@ -6,6 +6,7 @@ use atomic_refcell::AtomicRefCell;
 use common::cpu::CpuPermit;
 use rand::prelude::StdRng;
 use rand::SeedableRng;
+use rstest::rstest;
 use segment::common::rocksdb_wrapper::{open_db, DB_VECTOR_CF};
 use segment::data_types::vectors::{
     only_default_vector, MultiDenseVectorInternal, QueryVector, TypedMultiDenseVectorRef,
@@ -22,16 +23,22 @@ use segment::segment_constructor::build_segment;
 use segment::spaces::metric::Metric;
 use segment::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
 use segment::types::{
-    Condition, Distance, FieldCondition, Filter, HnswConfig, Indexes, MultiVectorConfig, Payload,
-    PayloadSchemaType, SegmentConfig, SeqNumberType, VectorDataConfig, VectorStorageType,
+    Condition, Distance, FieldCondition, Filter, HnswConfig, Indexes, MultiVectorAggregation,
+    MultiVectorConfig, Payload, PayloadSchemaType, SegmentConfig, SeqNumberType, VectorDataConfig,
+    VectorStorageType,
 };
 use segment::vector_storage::multi_dense::simple_multi_dense_vector_storage::open_simple_multi_dense_vector_storage;
 use segment::vector_storage::VectorStorage;
 use serde_json::json;
 use tempfile::Builder;
 
-#[test]
-fn test_single_multi_and_dense_hnsw_equivalency() {
+#[rstest]
+#[case::max_sim(None)]
+#[case::mean(Some(MultiVectorAggregation::Mean))]
+#[case::first(Some(MultiVectorAggregation::First))]
+fn test_single_multi_and_dense_hnsw_equivalency(
+    #[case] hnsw_aggregation: Option<MultiVectorAggregation>,
+) {
     let num_vectors: u64 = 1_000;
     let distance = Distance::Cosine;
     let num_payload_values = 2;
@@ -56,6 +63,8 @@ fn test_single_multi_and_dense_hnsw_equivalency() {
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_columns: Default::default(),
+        payload_compression: Default::default(),
     };
 
     let int_key = "int";
@@ -73,7 +82,10 @@ fn test_single_multi_and_dense_hnsw_equivalency() {
         DB_VECTOR_CF,
         dim,
         distance,
-        MultiVectorConfig::default(),
+        MultiVectorConfig {
+            hnsw_aggregation,
+            ..Default::default()
+        },
         &AtomicBool::new(false),
     )
     .unwrap();
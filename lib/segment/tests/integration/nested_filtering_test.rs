@@ -245,3 +245,88 @@ fn test_filtering_context_consistency() {
         assert!(!res3.is_empty());
     }
 }
+
+#[test]
+fn test_nested_index_per_element_semantics() {
+    let indexed_dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+    let plain_dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+
+    let open_index = |dir: &std::path::Path| {
+        let mut payload_storage = InMemoryPayloadStorage::default();
+        for (idx, payload) in nested_payloads().into_iter().enumerate() {
+            payload_storage
+                .assign(idx as PointOffsetType, &payload)
+                .unwrap();
+        }
+        let wrapped_payload_storage = Arc::new(AtomicRefCell::new(payload_storage.into()));
+        let id_tracker = Arc::new(AtomicRefCell::new(FixtureIdTracker::new(NUM_POINTS)));
+        StructPayloadIndex::open(wrapped_payload_storage, id_tracker, dir, true).unwrap()
+    };
+
+    let mut indexed = open_index(indexed_dir.path());
+    for key in ["arr1[].a", "arr1[].b", "arr1[].c"] {
+        indexed
+            .set_indexed(&path(key), PayloadSchemaType::Integer.into())
+            .unwrap();
+    }
+    let plain = open_index(plain_dir.path());
+
+    let nested_filters = [
+        // Single condition, resolved by index
+        Filter::new_must(Condition::Field(FieldCondition::new_match(
+            path("b"),
+            3.into(),
+        ))),
+        // Only `should` conditions, resolved by index
+        Filter {
+            should: Some(vec![
+                Condition::Field(FieldCondition::new_match(path("a"), 6.into())),
+                Condition::Field(FieldCondition::new_match(path("b"), 14.into())),
+            ]),
+            min_should: None,
+            must: None,
+            must_not: None,
+        },
+        // Both values exist in odd points, but never in the same element
+        Filter {
+            must: Some(vec![
+                Condition::Field(FieldCondition::new_match(path("a"), 1.into())),
+                Condition::Field(FieldCondition::new_match(path("c"), 3.into())),
+            ]),
+            should: None,
+            min_should: None,
+            must_not: None,
+        },
+        // Same element
+        Filter {
+            must: Some(vec![
+                Condition::Field(FieldCondition::new_match(path("a"), 3.into())),
+                Condition::Field(FieldCondition::new_match(path("b"), 5.into())),
+            ]),
+            should: None,
+            min_should: None,
+            must_not: None,
+        },
+    ];
+
+    for nested_filter in nested_filters {
+        let filter = Filter::new_must(Condition::new_nested(path("arr1"), nested_filter));
+        let indexed_res = indexed.query_points(&filter);
+        let plain_res = plain.query_points(&filter);
+        assert_eq!(indexed_res, plain_res, "{filter:?}");
+    }
+
+    let never_same_element = Filter::new_must(Condition::new_nested(
+        path("arr1"),
+        Filter {
+            must: Some(vec![
+                Condition::Field(FieldCondition::new_match(path("a"), 1.into())),
+                Condition::Field(FieldCondition::new_match(path("c"), 3.into())),
+            ]),
+            should: None,
+            min_should: None,
+            must_not: None,
+        },
+    ));
+    assert!(indexed.query_points(&never_same_element).is_empty());
+}
@@ -10,9 +10,14 @@ use segment::data_types::named_vectors::NamedVectors;
 use segment::data_types::vectors::{only_default_vector, VectorRef, DEFAULT_VECTOR_NAME};
 use segment::entry::entry_point::SegmentEntry;
 use segment::index::hnsw_index::num_rayon_threads;
+use segment::json_path::path;
 use segment::segment::Segment;
 use segment::segment_constructor::segment_builder::SegmentBuilder;
-use segment::types::{Indexes, SegmentConfig, VectorDataConfig, VectorStorageType};
+use segment::types::{
+    Condition, FieldCondition, Filter, Indexes, PayloadSchemaType, Range, SegmentConfig,
+    VectorDataConfig, VectorStorageType,
+};
+use serde_json::json;
 use sparse::common::sparse_vector::SparseVector;
 use tempfile::Builder;
 
@@ -164,6 +169,8 @@ fn estimate_build_time(segment: &Segment, stop_delay_millis: Option<u64>) -> (u6
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_columns: Default::default(),
+        payload_compression: Default::default(),
     };
 
     let mut builder = SegmentBuilder::new(dir.path(), temp_dir.path(), &segment_config).unwrap();
@@ -266,3 +273,141 @@ fn test_building_cancellation() {
         was_cancelled_later,
     );
 }
+
+#[test]
+fn test_building_segment_with_payload_index() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let temp_dir = Builder::new().prefix("segment_temp_dir").tempdir().unwrap();
+
+    let stopped = AtomicBool::new(false);
+
+    let mut segment1 = build_segment_1(dir.path());
+    for point_id in 1..=5 {
+        segment1
+            .set_payload(
+                7,
+                point_id.into(),
+                &json!({ "count": point_id }).into(),
+                &None,
+            )
+            .unwrap();
+    }
+    segment1
+        .create_field_index(8, &path("color"), Some(&PayloadSchemaType::Keyword.into()))
+        .unwrap();
+    segment1
+        .create_field_index(9, &path("count"), Some(&PayloadSchemaType::Integer.into()))
+        .unwrap();
+
+    // Non-appendable segment, so that indexes are reloaded in their immutable form
+    let mut config = segment1.segment_config.clone();
+    config
+        .vector_data
+        .get_mut(DEFAULT_VECTOR_NAME)
+        .unwrap()
+        .storage_type = VectorStorageType::Mmap;
+    assert!(!config.is_appendable());
+
+    let mut builder = SegmentBuilder::new(dir.path(), temp_dir.path(), &config).unwrap();
+    builder.update_from(&segment1, &stopped).unwrap();
+
+    let permit_cpu_count = num_rayon_threads(0);
+    let permit = CpuPermit::dummy(permit_cpu_count as u32);
+    let merged_segment: Segment = builder.build(permit, &stopped).unwrap();
+
+    assert_eq!(merged_segment.available_point_count(), 5);
+    for point_id in segment1.iter_points() {
+        assert_eq!(
+            merged_segment.payload(point_id).unwrap(),
+            segment1.payload(point_id).unwrap(),
+        );
+    }
+
+    let indexed_fields = merged_segment.get_indexed_fields();
+    assert!(indexed_fields.contains_key(&path("color")));
+    assert!(indexed_fields.contains_key(&path("count")));
+
+    let filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
+        path("color"),
+        "red".to_owned().into(),
+    )));
+    assert_eq!(
+        merged_segment.read_filtered(None, None, Some(&filter)),
+        segment1.read_filtered(None, None, Some(&filter)),
+    );
+    assert_eq!(
+        merged_segment
+            .read_filtered(None, None, Some(&filter))
+            .len(),
+        4
+    );
+
+    let filter = Filter::new_must(Condition::Field(FieldCondition::new_range(
+        path("count"),
+        Range {
+            gte: Some(2.0),
+            ..Default::default()
+        },
+    )));
+    assert_eq!(
+        merged_segment
+            .read_filtered(None, None, Some(&filter))
+            .len(),
+        4
+    );
+}
+
+#[test]
+fn test_building_segment_with_in_memory_vectors() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let temp_dir = Builder::new().prefix("segment_temp_dir").tempdir().unwrap();
+
+    let stopped = AtomicBool::new(false);
+
+    let mut segment1 = build_segment_1(dir.path());
+    segment1
+        .delete_vector(10, 2.into(), DEFAULT_VECTOR_NAME)
+        .unwrap();
+
+    let config = segment1.segment_config.clone();
+    assert_eq!(
+        config.vector_data[DEFAULT_VECTOR_NAME].storage_type,
+        VectorStorageType::Memory,
+    );
+
+    let mut builder = SegmentBuilder::new(dir.path(), temp_dir.path(), &config).unwrap();
+    builder.update_from(&segment1, &stopped).unwrap();
+
+    let permit_cpu_count = num_rayon_threads(0);
+    let permit = CpuPermit::dummy(permit_cpu_count as u32);
+    let merged_segment: Segment = builder.build(permit, &stopped).unwrap();
+
+    // Vectors are kept on disk while building, and loaded from the in-memory storage format
+    for point_id in segment1.iter_points() {
+        assert_eq!(
+            merged_segment
+                .vector(DEFAULT_VECTOR_NAME, point_id)
+                .unwrap(),
+            segment1.vector(DEFAULT_VECTOR_NAME, point_id).unwrap(),
+        );
+    }
+    assert!(merged_segment
+        .vector(DEFAULT_VECTOR_NAME, 2.into())
+        .unwrap()
+        .is_none());
+
+    let builder_files = merged_segment
+        .current_path
+        .read_dir()
+        .unwrap()
+        .filter(|entry| {
+            entry
+                .as_ref()
+                .unwrap()
+                .file_name()
+                .to_string_lossy()
+                .starts_with("builder_vector_storage")
+        })
+        .count();
+    assert_eq!(builder_files, 0);
+}
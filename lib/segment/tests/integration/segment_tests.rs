@@ -10,9 +10,12 @@ use segment::data_types::vectors::{
 };
 use segment::entry::entry_point::SegmentEntry;
 use segment::fixtures::index_fixtures::random_vector;
+use segment::json_path::path;
 use segment::segment_constructor::load_segment;
 use segment::segment_constructor::simple_segment_constructor::build_simple_segment;
-use segment::types::{Condition, Distance, Filter, SearchParams, WithPayload};
+use segment::types::{
+    Condition, Distance, Filter, PayloadSchemaType, PointIdType, SearchParams, WithPayload,
+};
 use tempfile::Builder;
 
 use crate::fixtures::segment::{build_segment_1, build_segment_3};
@@ -261,6 +264,7 @@ fn test_update_named_vector() {
         exact: true,
         quantization: None,
         indexed_only: false,
+        distance: None,
     };
     let nearest_upsert = segment
         .search(
@@ -328,3 +332,138 @@ fn test_update_named_vector() {
     // check that nearests are the same
     assert_eq!(nearest_upsert.id, nearest_update.id);
 }
+
+#[test]
+fn test_has_vector_condition() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let mut segment = build_segment_3(dir.path());
+
+    segment
+        .upsert_point(
+            10,
+            6.into(),
+            NamedVectors::from_pairs([
+                ("vector2".to_owned(), vec![10.]),
+                ("vector3".to_owned(), vec![5., 6., 7., 8.]),
+            ]),
+        )
+        .unwrap();
+    segment.delete_vector(11, 2.into(), "vector1").unwrap();
+
+    let has_vector1 = Filter::new_must(Condition::HasVector("vector1".to_owned().into()));
+    let points = segment.read_filtered(None, None, Some(&has_vector1));
+    assert_eq!(points, vec![1.into(), 3.into(), 4.into(), 5.into()]);
+
+    let missing_vector1 = Filter::new_must_not(Condition::HasVector("vector1".to_owned().into()));
+    let points = segment.read_filtered(None, None, Some(&missing_vector1));
+    assert_eq!(points, vec![2.into(), 6.into()]);
+
+    let unknown_vector = Filter::new_must(Condition::HasVector("unknown".to_owned().into()));
+    assert!(segment
+        .read_filtered(None, None, Some(&unknown_vector))
+        .is_empty());
+}
+
+#[test]
+fn test_distinct_values() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let mut segment = build_segment_1(dir.path());
+    let key = path("color");
+
+    // Without index, values are read from payload
+    let distinct = segment.distinct_values(&key, None).unwrap();
+    assert!(!distinct.is_exact());
+    assert_eq!(distinct.count(), 2);
+
+    segment
+        .create_field_index(7, &key, Some(&PayloadSchemaType::Keyword.into()))
+        .unwrap();
+
+    let distinct = segment.distinct_values(&key, None).unwrap();
+    assert!(distinct.is_exact());
+    assert_eq!(distinct.count(), 2);
+
+    let only_red = Filter::new_must(Condition::HasId(
+        HashSet::<PointIdType>::from([1.into(), 2.into()]).into(),
+    ));
+    let distinct = segment.distinct_values(&key, Some(&only_red)).unwrap();
+    assert!(distinct.is_exact());
+    assert_eq!(distinct.count(), 1);
+
+    // Values of deleted points are not counted
+    segment.delete_point(8, 3.into()).unwrap();
+    segment.delete_point(9, 4.into()).unwrap();
+    segment.delete_point(10, 5.into()).unwrap();
+    let distinct = segment.distinct_values(&key, None).unwrap();
+    assert_eq!(distinct.count(), 1);
+}
+
+#[test]
+fn test_value_distribution() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let mut segment = build_segment_1(dir.path());
+    let key = path("price");
+
+    for id in 1..=5u64 {
+        let payload = serde_json::json!({ "price": id * 10 }).into();
+        segment
+            .set_payload(10 + id, id.into(), &payload, &None)
+            .unwrap();
+    }
+
+    // Histogram requires a range index
+    let result = segment.value_distribution(&key);
+    assert!(matches!(
+        result,
+        Err(OperationError::MissingRangeIndexForHistogram { .. })
+    ));
+
+    segment
+        .create_field_index(20, &key, Some(&PayloadSchemaType::Integer.into()))
+        .unwrap();
+
+    let distribution = segment.value_distribution(&key).unwrap();
+    assert_eq!(distribution.total_count(), 5);
+
+    let buckets = distribution.fixed_width_buckets(100.0).unwrap();
+    assert_eq!(buckets.len(), 1);
+    assert_eq!(buckets[0].count, 5);
+}
+
+#[test]
+fn test_numeric_stats() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let mut segment = build_segment_1(dir.path());
+    let key = path("price");
+
+    for id in 1..=5u64 {
+        let payload = serde_json::json!({ "price": id as f64 * 1.5 }).into();
+        segment
+            .set_payload(10 + id, id.into(), &payload, &None)
+            .unwrap();
+    }
+
+    let result = segment.numeric_stats(&key, None);
+    assert!(matches!(
+        result,
+        Err(OperationError::MissingRangeIndexForStats { .. })
+    ));
+
+    segment
+        .create_field_index(20, &key, Some(&PayloadSchemaType::Float.into()))
+        .unwrap();
+
+    let stats = segment.numeric_stats(&key, None).unwrap();
+    assert_eq!(stats.count(), 5);
+    assert_eq!(stats.min(), Some(1.5));
+    assert_eq!(stats.max(), Some(7.5));
+    assert_eq!(stats.avg(), Some(4.5));
+    assert_eq!(stats.percentile(50.0), Some(4.5));
+
+    let filter = Filter::new_must(Condition::HasId(
+        HashSet::<PointIdType>::from([1.into(), 2.into()]).into(),
+    ));
+    let stats = segment.numeric_stats(&key, Some(&filter)).unwrap();
+    assert_eq!(stats.count(), 2);
+    assert_eq!(stats.max(), Some(3.0));
+}
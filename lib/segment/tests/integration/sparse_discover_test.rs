@@ -129,6 +129,8 @@ fn sparse_index_discover_test() {
             },
         )]),
         payload_storage_type: Default::default(),
+        payload_columns: Default::default(),
+        payload_compression: Default::default(),
     };
     let dense_config = SegmentConfig {
         vector_data: HashMap::from([(
@@ -144,6 +146,8 @@ fn sparse_index_discover_test() {
             },
         )]),
         payload_storage_type: Default::default(),
+        payload_columns: Default::default(),
+        payload_compression: Default::default(),
         sparse_vector_data: Default::default(),
     };
 
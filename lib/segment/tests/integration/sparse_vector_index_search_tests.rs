@@ -631,6 +631,8 @@ fn sparse_vector_index_persistence_test() {
             },
         )]),
         payload_storage_type: Default::default(),
+        payload_columns: Default::default(),
+        payload_compression: Default::default(),
     };
     let mut segment = build_segment(dir.path(), &config, true).unwrap();
 
@@ -835,6 +837,8 @@ fn sparse_vector_test_large_index() {
             },
         )]),
         payload_storage_type: Default::default(),
+        payload_columns: Default::default(),
+        payload_compression: Default::default(),
     };
     let mut segment = build_segment(dir.path(), &config, true).unwrap();
 
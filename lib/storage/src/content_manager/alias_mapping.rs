@@ -5,11 +5,13 @@ use std::path::{Path, PathBuf};
 
 use collection::shards::CollectionId;
 use io::file_operations::{atomic_save_json, read_json};
+use segment::types::Filter;
 use serde::{Deserialize, Serialize};
 
 use crate::content_manager::errors::StorageError;
 
 pub const ALIAS_MAPPING_CONFIG_FILE: &str = "data.json";
+pub const ALIAS_FILTERS_CONFIG_FILE: &str = "filters.json";
 
 type Alias = String;
 
@@ -26,6 +28,20 @@ impl AliasMapping {
     }
 }
 
+/// Filters of the aliases, which are merged into every request made through the alias
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct AliasFilters(HashMap<Alias, Filter>);
+
+impl AliasFilters {
+    pub fn load(path: &Path) -> Result<Self, StorageError> {
+        Ok(read_json(path)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), StorageError> {
+        Ok(atomic_save_json(path, self)?)
+    }
+}
+
 /// Persists mapping between alias and collection name. The data is assumed to be relatively small.
 /// - Reads are served from memory.
 /// - Writes are durably saved.
@@ -33,6 +49,8 @@ impl AliasMapping {
 pub struct AliasPersistence {
     data_path: PathBuf,
     alias_mapping: AliasMapping,
+    filters_path: PathBuf,
+    alias_filters: AliasFilters,
 }
 
 impl AliasPersistence {
@@ -56,9 +74,17 @@ impl AliasPersistence {
         }
         let data_path = Self::init_file(&dir_path)?;
         let alias_mapping = AliasMapping::load(&data_path)?;
+        let filters_path = dir_path.join(ALIAS_FILTERS_CONFIG_FILE);
+        let alias_filters = if filters_path.exists() {
+            AliasFilters::load(&filters_path)?
+        } else {
+            AliasFilters::default()
+        };
         Ok(AliasPersistence {
             data_path,
             alias_mapping,
+            filters_path,
+            alias_filters,
         })
     }
 
@@ -66,9 +92,29 @@ impl AliasPersistence {
         self.alias_mapping.0.get(alias).cloned()
     }
 
-    pub fn insert(&mut self, alias: String, collection_name: String) -> Result<(), StorageError> {
+    /// Filter of the alias, if the alias is a filtered view of its collection
+    pub fn get_filter(&self, alias: &str) -> Option<&Filter> {
+        self.alias_filters.0.get(alias)
+    }
+
+    pub fn insert(
+        &mut self,
+        alias: String,
+        collection_name: String,
+        filter: Option<Filter>,
+    ) -> Result<(), StorageError> {
+        let filters_changed = match filter {
+            Some(filter) => {
+                self.alias_filters.0.insert(alias.clone(), filter);
+                true
+            }
+            None => self.alias_filters.0.remove(&alias).is_some(),
+        };
         self.alias_mapping.0.insert(alias, collection_name);
         self.alias_mapping.save(&self.data_path)?;
+        if filters_changed {
+            self.alias_filters.save(&self.filters_path)?;
+        }
         Ok(())
     }
 
@@ -78,6 +124,9 @@ impl AliasPersistence {
         if output.is_some() {
             self.alias_mapping.save(&self.data_path)?;
         }
+        if self.alias_filters.0.remove(alias).is_some() {
+            self.alias_filters.save(&self.filters_path)?;
+        }
 
         Ok(output)
     }
@@ -92,6 +141,15 @@ impl AliasPersistence {
             self.alias_mapping.save(&self.data_path)?;
         }
 
+        let prev_filters_len = self.alias_filters.0.len();
+        let alias_mapping = &self.alias_mapping;
+        self.alias_filters
+            .0
+            .retain(|alias, _| alias_mapping.0.contains_key(alias));
+        if prev_filters_len != self.alias_filters.0.len() {
+            self.alias_filters.save(&self.filters_path)?;
+        }
+
         Ok(())
     }
 
@@ -106,9 +164,15 @@ impl AliasPersistence {
             }),
             Some(collection_name) => {
                 self.alias_mapping.0.remove(old_alias_name);
-                self.alias_mapping.0.insert(new_alias_name, collection_name);
+                self.alias_mapping
+                    .0
+                    .insert(new_alias_name.clone(), collection_name);
                 // 'remove' & 'insert' saved atomically
                 self.alias_mapping.save(&self.data_path)?;
+                if let Some(filter) = self.alias_filters.0.remove(old_alias_name) {
+                    self.alias_filters.0.insert(new_alias_name, filter);
+                    self.alias_filters.save(&self.filters_path)?;
+                }
                 Ok(())
             }
         }
@@ -128,9 +192,19 @@ impl AliasPersistence {
         &self.alias_mapping
     }
 
-    pub fn apply_state(&mut self, alias_mapping: AliasMapping) -> Result<(), StorageError> {
+    pub fn filters_state(&self) -> &AliasFilters {
+        &self.alias_filters
+    }
+
+    pub fn apply_state(
+        &mut self,
+        alias_mapping: AliasMapping,
+        alias_filters: AliasFilters,
+    ) -> Result<(), StorageError> {
         self.alias_mapping = alias_mapping;
         self.alias_mapping.save(&self.data_path)?;
+        self.alias_filters = alias_filters;
+        self.alias_filters.save(&self.filters_path)?;
         Ok(())
     }
 
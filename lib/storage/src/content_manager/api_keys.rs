@@ -0,0 +1,295 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use io::file_operations::{atomic_save_json, read_json};
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use validator::Validate;
+
+use crate::content_manager::errors::StorageError;
+use crate::rbac::{Access, CollectionAccess, CollectionAccessList, CollectionAccessMode};
+
+pub const API_KEYS_CONFIG_FILE: &str = "data.json";
+
+/// Length of generated API keys
+const API_KEY_LENGTH: usize = 40;
+
+/// What requests a managed API key is allowed to make
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Eq, Hash, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiKeyRole {
+    /// Read points and collection info
+    ReadOnly,
+    /// Read and write points, and manage payload indexes
+    ReadWrite,
+    /// Full access, including collection and cluster management. Can't be scoped to collections.
+    Admin,
+}
+
+/// Managed API key, as it is stored and listed. The key itself is only returned on creation.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, PartialEq, Eq, Hash, Clone)]
+pub struct ApiKeyDescription {
+    /// Unique name of the key
+    #[validate(length(min = 1, max = 255))]
+    pub name: String,
+    pub role: ApiKeyRole,
+    /// Collections, the key is scoped to. If not set - all collections.
+    /// Read-write keys must be scoped to collections.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(length(min = 1))]
+    pub collections: Option<Vec<String>>,
+}
+
+/// Change of role or collections of an existing API key, the key itself stays the same
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+pub struct UpdateApiKey {
+    pub role: ApiKeyRole,
+    #[serde(default)]
+    #[validate(length(min = 1))]
+    pub collections: Option<Vec<String>>,
+}
+
+/// Newly created API key
+#[derive(Debug, Serialize, JsonSchema, Clone)]
+pub struct CreatedApiKey {
+    pub name: String,
+    /// Secret to pass in the `api-key` header. It is not stored, so it can't be retrieved later.
+    pub key: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Hash, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiKeyOperation {
+    /// Only the hash of the key is passed through consensus
+    Create {
+        description: ApiKeyDescription,
+        key_hash: String,
+    },
+    Update(ApiKeyDescription),
+    Delete {
+        name: String,
+    },
+}
+
+impl ApiKeyDescription {
+    pub fn validate_scope(&self) -> Result<(), StorageError> {
+        match (self.role, &self.collections) {
+            (ApiKeyRole::Admin, Some(_)) => Err(StorageError::bad_input(
+                "Admin API keys can't be scoped to collections",
+            )),
+            (ApiKeyRole::ReadWrite, None) => Err(StorageError::bad_input(
+                "Read-write API keys must be scoped to collections, use admin role for global write access",
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Access, granted to requests with this key
+    pub fn access(&self) -> Access {
+        let mode = match (self.role, &self.collections) {
+            (ApiKeyRole::Admin, _) => return Access::full("Admin API key"),
+            (ApiKeyRole::ReadOnly, None) => return Access::full_ro("Read-only API key"),
+            (ApiKeyRole::ReadOnly, Some(_)) => CollectionAccessMode::Read,
+            (ApiKeyRole::ReadWrite, _) => CollectionAccessMode::ReadWrite,
+        };
+        let collections = self.collections.iter().flatten();
+        Access::Collection(CollectionAccessList(
+            collections
+                .map(|collection| CollectionAccess {
+                    collection: collection.clone(),
+                    access: mode,
+                    payload: None,
+                    filter: None,
+                })
+                .collect(),
+        ))
+    }
+}
+
+/// Generate a new random API key
+pub fn generate_api_key() -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(API_KEY_LENGTH)
+        .map(char::from)
+        .collect()
+}
+
+/// Hex encoded SHA-256 hash of the key, under which it is stored
+pub fn hash_api_key(key: &str) -> String {
+    format!("{:x}", Sha256::digest(key.as_bytes()))
+}
+
+/// Managed API keys by hash of the key
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, Default)]
+pub struct ApiKeyMapping(HashMap<String, ApiKeyDescription>);
+
+impl ApiKeyMapping {
+    pub fn load(path: &Path) -> Result<Self, StorageError> {
+        Ok(read_json(path)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), StorageError> {
+        Ok(atomic_save_json(path, self)?)
+    }
+}
+
+/// Persists managed API keys, which are changed through consensus.
+/// - Reads are served from memory.
+/// - Writes are durably saved.
+#[derive(Debug)]
+pub struct ApiKeyPersistence {
+    data_path: PathBuf,
+    api_keys: ApiKeyMapping,
+}
+
+impl ApiKeyPersistence {
+    pub fn open(dir_path: PathBuf) -> Result<Self, StorageError> {
+        if !dir_path.exists() {
+            fs::create_dir_all(&dir_path)?;
+        }
+        let data_path = dir_path.join(API_KEYS_CONFIG_FILE);
+        let api_keys = if data_path.exists() {
+            ApiKeyMapping::load(&data_path)?
+        } else {
+            ApiKeyMapping::default()
+        };
+        Ok(Self {
+            data_path,
+            api_keys,
+        })
+    }
+
+    /// Find the key by its value, as passed in a request
+    pub fn find(&self, key: &str) -> Option<&ApiKeyDescription> {
+        self.api_keys.0.get(&hash_api_key(key))
+    }
+
+    /// All keys, sorted by name
+    pub fn list(&self) -> Vec<ApiKeyDescription> {
+        let mut api_keys: Vec<_> = self.api_keys.0.values().cloned().collect();
+        api_keys.sort_by(|a, b| a.name.cmp(&b.name));
+        api_keys
+    }
+
+    fn hash_of(&self, name: &str) -> Option<String> {
+        self.api_keys
+            .0
+            .iter()
+            .find(|(_, description)| description.name == name)
+            .map(|(hash, _)| hash.clone())
+    }
+
+    pub fn apply(&mut self, operation: ApiKeyOperation) -> Result<(), StorageError> {
+        match operation {
+            ApiKeyOperation::Create {
+                description,
+                key_hash,
+            } => {
+                description.validate_scope()?;
+                if self.hash_of(&description.name).is_some() {
+                    return Err(StorageError::already_exists(format!(
+                        "API key {} already exists",
+                        description.name,
+                    )));
+                }
+                if self.api_keys.0.contains_key(&key_hash) {
+                    return Err(StorageError::bad_input("API key is already in use"));
+                }
+                self.api_keys.0.insert(key_hash, description);
+            }
+            ApiKeyOperation::Update(description) => {
+                description.validate_scope()?;
+                let hash = self.hash_of(&description.name).ok_or_else(|| {
+                    StorageError::not_found(format!("API key {} not found", description.name))
+                })?;
+                self.api_keys.0.insert(hash, description);
+            }
+            ApiKeyOperation::Delete { name } => {
+                let hash = self
+                    .hash_of(&name)
+                    .ok_or_else(|| StorageError::not_found(format!("API key {name} not found")))?;
+                self.api_keys.0.remove(&hash);
+            }
+        }
+        self.api_keys.save(&self.data_path)
+    }
+
+    pub fn state(&self) -> &ApiKeyMapping {
+        &self.api_keys
+    }
+
+    pub fn apply_state(&mut self, api_keys: ApiKeyMapping) -> Result<(), StorageError> {
+        self.api_keys = api_keys;
+        self.api_keys.save(&self.data_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::rbac::AccessRequirements;
+
+    fn description(
+        name: &str,
+        role: ApiKeyRole,
+        collections: Option<&[&str]>,
+    ) -> ApiKeyDescription {
+        ApiKeyDescription {
+            name: name.to_string(),
+            role,
+            collections: collections.map(|c| c.iter().map(|c| c.to_string()).collect()),
+        }
+    }
+
+    #[test]
+    fn test_api_key_persistence() {
+        let dir = Builder::new().prefix("api_keys").tempdir().unwrap();
+        let mut persistence = ApiKeyPersistence::open(dir.path().to_path_buf()).unwrap();
+
+        let key = generate_api_key();
+        let reader = description("reader", ApiKeyRole::ReadOnly, Some(&["a"]));
+        persistence
+            .apply(ApiKeyOperation::Create {
+                description: reader.clone(),
+                key_hash: hash_api_key(&key),
+            })
+            .unwrap();
+
+        // Names are unique
+        let duplicate = ApiKeyOperation::Create {
+            description: reader.clone(),
+            key_hash: hash_api_key(&generate_api_key()),
+        };
+        assert!(persistence.apply(duplicate).is_err());
+
+        // Admin keys are global
+        let admin = description("admin", ApiKeyRole::Admin, Some(&["a"]));
+        assert!(persistence.apply(ApiKeyOperation::Update(admin)).is_err());
+
+        let writer = description("reader", ApiKeyRole::ReadWrite, Some(&["a", "b"]));
+        persistence
+            .apply(ApiKeyOperation::Update(writer.clone()))
+            .unwrap();
+
+        // Changes are persisted
+        let persistence = ApiKeyPersistence::open(dir.path().to_path_buf()).unwrap();
+        let found = persistence.find(&key).unwrap();
+        assert_eq!(found, &writer);
+        assert!(persistence.find("wrong").is_none());
+
+        let access = found.access();
+        let write = AccessRequirements::new().write();
+        assert!(access.check_collection_access("b", write).is_ok());
+        assert!(access.check_collection_access("c", write).is_err());
+        assert!(access
+            .check_global_access(AccessRequirements::new())
+            .is_err());
+    }
+}
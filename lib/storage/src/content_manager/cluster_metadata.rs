@@ -0,0 +1,291 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use io::file_operations::{atomic_save_json, read_json};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::content_manager::errors::StorageError;
+
+pub const CLUSTER_METADATA_CONFIG_FILE: &str = "data.json";
+
+/// Max size of a single value in bytes
+pub const MAX_METADATA_VALUE_SIZE: usize = 64 * 1024;
+
+/// Max total size of keys and values of a namespace in bytes
+pub const MAX_METADATA_NAMESPACE_SIZE: usize = 1024 * 1024;
+
+/// Max number of namespaces
+pub const MAX_METADATA_NAMESPACES: usize = 1024;
+
+/// Value, stored under a key of the cluster metadata
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, PartialEq, Eq, Hash, Clone)]
+pub struct MetadataValue {
+    /// Arbitrary string, e.g. a model version or a serialized JSON document
+    pub value: String,
+}
+
+/// Key with its value, as it is listed
+#[derive(Debug, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+pub struct MetadataEntry {
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Hash, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum MetadataOperation {
+    /// Set the value of the key, replacing the previous one
+    Put {
+        namespace: String,
+        key: String,
+        value: String,
+    },
+    Delete {
+        namespace: String,
+        key: String,
+    },
+}
+
+/// Values of the cluster metadata by namespace and key
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, Default)]
+pub struct MetadataMapping(HashMap<String, BTreeMap<String, String>>);
+
+impl MetadataMapping {
+    pub fn load(path: &Path) -> Result<Self, StorageError> {
+        Ok(read_json(path)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), StorageError> {
+        Ok(atomic_save_json(path, self)?)
+    }
+
+    fn namespace_size(&self, namespace: &str) -> usize {
+        self.0.get(namespace).map_or(0, |entries| {
+            entries
+                .iter()
+                .map(|(key, value)| key.len() + value.len())
+                .sum()
+        })
+    }
+
+    /// Check that the operation keeps the store within its limits.
+    ///
+    /// Limits are checked on the replicated state, so all peers accept or reject the operation.
+    fn check_limits(&self, operation: &MetadataOperation) -> Result<(), StorageError> {
+        let MetadataOperation::Put {
+            namespace,
+            key,
+            value,
+        } = operation
+        else {
+            return Ok(());
+        };
+        if value.len() > MAX_METADATA_VALUE_SIZE {
+            return Err(StorageError::bad_input(format!(
+                "Metadata value must not be larger than {MAX_METADATA_VALUE_SIZE} bytes",
+            )));
+        }
+        if !self.0.contains_key(namespace) && self.0.len() >= MAX_METADATA_NAMESPACES {
+            return Err(StorageError::bad_input(format!(
+                "Cluster metadata can't have more than {MAX_METADATA_NAMESPACES} namespaces",
+            )));
+        }
+        let replaced = self
+            .0
+            .get(namespace)
+            .and_then(|entries| entries.get(key))
+            .map_or(0, |old_value| key.len() + old_value.len());
+        let size = self.namespace_size(namespace) - replaced + key.len() + value.len();
+        if size > MAX_METADATA_NAMESPACE_SIZE {
+            return Err(StorageError::bad_input(format!(
+                "Metadata namespace {namespace} must not be larger than \
+                 {MAX_METADATA_NAMESPACE_SIZE} bytes",
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Persists cluster metadata, which is changed through consensus.
+/// - Reads are served from memory.
+/// - Writes are durably saved.
+#[derive(Debug)]
+pub struct MetadataPersistence {
+    data_path: PathBuf,
+    metadata: MetadataMapping,
+}
+
+impl MetadataPersistence {
+    pub fn open(dir_path: PathBuf) -> Result<Self, StorageError> {
+        if !dir_path.exists() {
+            fs::create_dir_all(&dir_path)?;
+        }
+        let data_path = dir_path.join(CLUSTER_METADATA_CONFIG_FILE);
+        let metadata = if data_path.exists() {
+            MetadataMapping::load(&data_path)?
+        } else {
+            MetadataMapping::default()
+        };
+        Ok(Self {
+            data_path,
+            metadata,
+        })
+    }
+
+    pub fn get(&self, namespace: &str, key: &str) -> Result<&str, StorageError> {
+        self.metadata
+            .0
+            .get(namespace)
+            .and_then(|entries| entries.get(key))
+            .map(String::as_str)
+            .ok_or_else(|| {
+                StorageError::not_found(format!("Metadata key {key} in {namespace} not found"))
+            })
+    }
+
+    /// All keys of the namespace with their values, sorted by key
+    pub fn list(&self, namespace: &str) -> Vec<MetadataEntry> {
+        self.metadata
+            .0
+            .get(namespace)
+            .into_iter()
+            .flatten()
+            .map(|(key, value)| MetadataEntry {
+                key: key.clone(),
+                value: value.clone(),
+            })
+            .collect()
+    }
+
+    pub fn apply(&mut self, operation: MetadataOperation) -> Result<(), StorageError> {
+        self.metadata.check_limits(&operation)?;
+        match operation {
+            MetadataOperation::Put {
+                namespace,
+                key,
+                value,
+            } => {
+                self.metadata
+                    .0
+                    .entry(namespace)
+                    .or_default()
+                    .insert(key, value);
+            }
+            MetadataOperation::Delete { namespace, key } => {
+                let entries = self.metadata.0.get_mut(&namespace);
+                if entries.and_then(|entries| entries.remove(&key)).is_none() {
+                    return Err(StorageError::not_found(format!(
+                        "Metadata key {key} in {namespace} not found"
+                    )));
+                }
+                if self
+                    .metadata
+                    .0
+                    .get(&namespace)
+                    .is_some_and(BTreeMap::is_empty)
+                {
+                    self.metadata.0.remove(&namespace);
+                }
+            }
+        }
+        self.metadata.save(&self.data_path)
+    }
+
+    pub fn state(&self) -> &MetadataMapping {
+        &self.metadata
+    }
+
+    pub fn apply_state(&mut self, metadata: MetadataMapping) -> Result<(), StorageError> {
+        self.metadata = metadata;
+        self.metadata.save(&self.data_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+
+    fn put(namespace: &str, key: &str, value: String) -> MetadataOperation {
+        MetadataOperation::Put {
+            namespace: namespace.to_string(),
+            key: key.to_string(),
+            value,
+        }
+    }
+
+    #[test]
+    fn test_cluster_metadata() {
+        let dir = Builder::new().prefix("cluster_metadata").tempdir().unwrap();
+        let mut persistence = MetadataPersistence::open(dir.path().to_path_buf()).unwrap();
+
+        persistence
+            .apply(put("models", "embedder", "v2".to_string()))
+            .unwrap();
+        persistence
+            .apply(put("models", "reranker", "v1".to_string()))
+            .unwrap();
+
+        // Changes are persisted
+        let mut persistence = MetadataPersistence::open(dir.path().to_path_buf()).unwrap();
+        assert_eq!(persistence.get("models", "embedder").unwrap(), "v2");
+        assert!(persistence.get("other", "embedder").is_err());
+        assert_eq!(
+            persistence.list("models"),
+            vec![
+                MetadataEntry {
+                    key: "embedder".to_string(),
+                    value: "v2".to_string(),
+                },
+                MetadataEntry {
+                    key: "reranker".to_string(),
+                    value: "v1".to_string(),
+                },
+            ],
+        );
+
+        persistence
+            .apply(MetadataOperation::Delete {
+                namespace: "models".to_string(),
+                key: "embedder".to_string(),
+            })
+            .unwrap();
+        assert!(persistence.get("models", "embedder").is_err());
+        assert!(persistence
+            .apply(MetadataOperation::Delete {
+                namespace: "models".to_string(),
+                key: "embedder".to_string(),
+            })
+            .is_err());
+    }
+
+    #[test]
+    fn test_cluster_metadata_limits() {
+        let dir = Builder::new().prefix("cluster_metadata").tempdir().unwrap();
+        let mut persistence = MetadataPersistence::open(dir.path().to_path_buf()).unwrap();
+
+        let value = "x".repeat(MAX_METADATA_VALUE_SIZE);
+        assert!(persistence
+            .apply(put("big", "key", format!("{value}x")))
+            .is_err());
+
+        let keys = MAX_METADATA_NAMESPACE_SIZE / MAX_METADATA_VALUE_SIZE;
+        for key in 0..keys - 1 {
+            persistence
+                .apply(put("big", &key.to_string(), value.clone()))
+                .unwrap();
+        }
+        assert!(persistence
+            .apply(put("big", "last", value.clone()))
+            .is_err());
+
+        // Replacing a value doesn't count the old one
+        persistence.apply(put("big", "0", value.clone())).unwrap();
+        // Other namespaces have their own limit
+        persistence.apply(put("small", "last", value)).unwrap();
+    }
+}
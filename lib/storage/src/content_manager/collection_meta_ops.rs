@@ -1,6 +1,8 @@
 use std::collections::BTreeMap;
+use std::num::{NonZeroU32, NonZeroU64};
 
-use collection::config::{CollectionConfig, ShardingMethod};
+use collection::config::{CollectionConfig, ShardingMethod, StrictModeConfig};
+use collection::operations::computed_fields::ComputedField;
 use collection::operations::config_diff::{
     CollectionParamsDiff, HnswConfigDiff, OptimizersConfigDiff, QuantizationConfigDiff,
     WalConfigDiff,
@@ -14,21 +16,43 @@ use collection::shards::shard::{PeerId, ShardId, ShardsPlacement};
 use collection::shards::transfer::{ShardTransfer, ShardTransferKey, ShardTransferRestart};
 use collection::shards::{replica_set, CollectionId};
 use schemars::JsonSchema;
-use segment::types::{PayloadFieldSchema, PayloadKeyType, QuantizationConfig, ShardKey};
+use segment::types::{
+    AttributeType, Filter, PayloadCompression, PayloadFieldSchema, PayloadKeyType,
+    QuantizationConfig, ShardKey,
+};
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
+use crate::content_manager::api_keys::ApiKeyOperation;
+use crate::content_manager::cluster_metadata::MetadataOperation;
+use crate::content_manager::collection_templates::TemplateOperation;
 use crate::content_manager::shard_distribution::ShardDistributionProposal;
+use crate::content_manager::snapshot_schedules::SnapshotScheduleOperation;
 
 // *Operation wrapper structure is only required for better OpenAPI generation
 
 /// Create alternative name for a collection.
 /// Collection will be available under both names for search, retrieve,
-#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Eq, Hash, Clone)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Clone)]
 #[serde(rename_all = "snake_case")]
 pub struct CreateAlias {
     pub collection_name: String,
     pub alias_name: String,
+    /// If set, the alias is a filtered view of the collection: the filter is merged into every
+    /// request made through the alias. Points, not matching the filter, can't be read, and
+    /// can't be written except by deletes, through the alias.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<Filter>,
+}
+
+impl Eq for CreateAlias {}
+
+impl std::hash::Hash for CreateAlias {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        // Filter is not hashable, so aliases, which only differ by filter, have the same hash
+        self.collection_name.hash(state);
+        self.alias_name.hash(state);
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Eq, Hash, Clone)]
@@ -145,6 +169,43 @@ pub struct CreateCollection {
     /// Note: those payload values that are involved in filtering and are indexed - remain in RAM.
     #[serde(default)]
     pub on_disk_payload: Option<bool>,
+    /// If true - point's payload is stored in gridstore: append-only blocks on disk, read through
+    /// mmap, instead of RocksDB. Writes of payload are never stalled by compactions.
+    /// When enabled, `on_disk_payload` has no effect. Default is false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gridstore_payload: Option<bool>,
+    /// Numeric payload fields, whose values are also stored per field contiguously.
+    /// Ordering by such a field scans its column instead of reading full payloads,
+    /// even if the field has no range index. Can only be set on collection creation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload_columns: Option<Vec<PayloadKeyType>>,
+    /// If set - payload in the on-disk payload storage is compressed with zstd,
+    /// using a dictionary trained on payloads of each segment. Can only be set on collection creation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub payload_compression: Option<PayloadCompression>,
+    /// If set - this number of last versions of each point (vectors and payload) is retained,
+    /// and can be retrieved by version or by time. Can only be set on collection creation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub point_history_versions: Option<NonZeroU32>,
+    /// If set - deleted points are kept in the trash of the shard for this number of seconds,
+    /// and can be restored by id. Can only be set on collection creation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub soft_delete_retention_sec: Option<NonZeroU64>,
+    /// Payload fields, computed from other payload fields or vectors on every upsert, and indexed.
+    /// Can only be set on collection creation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub computed_fields: Option<Vec<ComputedField>>,
+    /// Typed scalar attributes of points, stored in dense columns outside of the payload.
+    /// Filters and formula queries read attributes without reading the payload.
+    /// Can only be set on collection creation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attribute_columns: Option<BTreeMap<String, AttributeType>>,
+    /// Name of the collection template to take parameters from, which are not set in this request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(length(min = 1))]
+    pub template: Option<String>,
     /// Custom params for HNSW index. If none - values from service configuration file are used.
     #[validate]
     pub hnsw_config: Option<HnswConfigDiff>,
@@ -165,6 +226,10 @@ pub struct CreateCollection {
     /// Sparse vector data config.
     #[validate]
     pub sparse_vectors: Option<BTreeMap<String, SparseVectorParams>>,
+    /// Strict mode limits of the collection. If none - strict mode is disabled.
+    #[serde(default)]
+    #[validate]
+    pub strict_mode_config: Option<StrictModeConfig>,
 }
 
 /// Operation for creating new collection and (optionally) specify index params
@@ -222,6 +287,10 @@ pub struct UpdateCollection {
     /// Map of sparse vector data parameters to update for each sparse vector.
     #[validate]
     pub sparse_vectors: Option<SparseVectorsConfig>,
+    /// Strict mode limits to update. If none - it is left unchanged.
+    #[serde(default)]
+    #[validate]
+    pub strict_mode_config: Option<StrictModeConfig>,
 }
 
 /// Operation for updating parameters of the existing collection
@@ -244,6 +313,7 @@ impl UpdateCollectionOperation {
                 optimizers_config: None,
                 quantization_config: None,
                 sparse_vectors: None,
+                strict_mode_config: None,
             },
             shard_replica_changes: None,
         }
@@ -385,6 +455,10 @@ pub enum CollectionMetaOperations {
     DropShardKey(DropShardKey),
     CreatePayloadIndex(CreatePayloadIndex),
     DropPayloadIndex(DropPayloadIndex),
+    ApiKeys(ApiKeyOperation),
+    Templates(TemplateOperation),
+    ClusterMetadata(MetadataOperation),
+    SnapshotSchedules(SnapshotScheduleOperation),
     Nop { token: usize }, // Empty operation
 }
 
@@ -399,12 +473,21 @@ impl From<CollectionConfig> for CreateCollection {
             replication_factor: Some(value.params.replication_factor.get()),
             write_consistency_factor: Some(value.params.write_consistency_factor.get()),
             on_disk_payload: Some(value.params.on_disk_payload),
+            gridstore_payload: Some(value.params.gridstore_payload),
+            payload_columns: Some(value.params.payload_columns),
+            payload_compression: value.params.payload_compression,
+            point_history_versions: value.params.point_history_versions,
+            soft_delete_retention_sec: value.params.soft_delete_retention_sec,
+            computed_fields: Some(value.params.computed_fields),
+            attribute_columns: Some(value.params.attribute_columns),
+            template: None,
             hnsw_config: Some(value.hnsw_config.into()),
             wal_config: Some(value.wal_config.into()),
             optimizers_config: Some(value.optimizer_config.into()),
             init_from: None,
             quantization_config: value.quantization_config,
             sparse_vectors: value.params.sparse_vectors,
+            strict_mode_config: value.strict_mode_config,
         }
     }
 }
@@ -0,0 +1,245 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use collection::operations::config_diff::{HnswConfigDiff, OptimizersConfigDiff, WalConfigDiff};
+use collection::operations::types::{SparseVectorParams, VectorsConfig};
+use io::file_operations::{atomic_save_json, read_json};
+use merge::Merge;
+use schemars::JsonSchema;
+use segment::types::QuantizationConfig;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::content_manager::collection_meta_ops::CreateCollection;
+use crate::content_manager::errors::StorageError;
+
+pub const TEMPLATES_CONFIG_FILE: &str = "data.json";
+
+/// Named set of collection parameters, which new collections can be created from
+///
+/// Parameters, set in the create collection request, take precedence over the template.
+/// Nested configs, like `hnsw_config`, are merged field by field.
+#[derive(
+    Debug, Deserialize, Serialize, JsonSchema, Validate, PartialEq, Eq, Hash, Clone, Default,
+)]
+#[serde(rename_all = "snake_case")]
+pub struct CollectionTemplate {
+    /// Vector data config. Used if the request doesn't configure any vectors.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub vectors: Option<VectorsConfig>,
+    /// Sparse vector data config. Used if the request doesn't configure any sparse vectors.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub sparse_vectors: Option<BTreeMap<String, SparseVectorParams>>,
+    /// If true - point's payload will not be stored in memory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_disk_payload: Option<bool>,
+    /// Custom params for HNSW index
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub hnsw_config: Option<HnswConfigDiff>,
+    /// Custom params for WAL
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub wal_config: Option<WalConfigDiff>,
+    /// Custom params for optimizers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub optimizers_config: Option<OptimizersConfigDiff>,
+    /// Quantization parameters. Used if the request doesn't configure quantization.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub quantization_config: Option<QuantizationConfig>,
+}
+
+/// Template with its name, as it is listed
+#[derive(Debug, Serialize, JsonSchema, Clone)]
+pub struct CollectionTemplateDescription {
+    pub name: String,
+    pub template: CollectionTemplate,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Hash, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum TemplateOperation {
+    /// Create the template or replace an existing one with the same name
+    Put {
+        name: String,
+        template: CollectionTemplate,
+    },
+    Delete {
+        name: String,
+    },
+}
+
+impl CollectionTemplate {
+    /// Fill parameters, which are not set in the create collection request, from the template
+    pub fn apply(&self, mut request: CreateCollection) -> CreateCollection {
+        if request.vectors.vectors_num() == 0 {
+            if let Some(vectors) = &self.vectors {
+                request.vectors = vectors.clone();
+            }
+        }
+        if request.sparse_vectors.is_none() {
+            request.sparse_vectors.clone_from(&self.sparse_vectors);
+        }
+        if request.on_disk_payload.is_none() {
+            request.on_disk_payload = self.on_disk_payload;
+        }
+        merge_diff(&mut request.hnsw_config, &self.hnsw_config);
+        merge_diff(&mut request.wal_config, &self.wal_config);
+        merge_diff(&mut request.optimizers_config, &self.optimizers_config);
+        if request.quantization_config.is_none() {
+            request
+                .quantization_config
+                .clone_from(&self.quantization_config);
+        }
+        request
+    }
+}
+
+/// Fill fields of the request diff, which are not set, from the template diff
+fn merge_diff<T: Merge + Clone>(request: &mut Option<T>, template: &Option<T>) {
+    let Some(template) = template else {
+        return;
+    };
+    match request {
+        Some(request) => request.merge(template.clone()),
+        None => *request = Some(template.clone()),
+    }
+}
+
+/// Collection templates by name
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, Default)]
+pub struct TemplateMapping(HashMap<String, CollectionTemplate>);
+
+impl TemplateMapping {
+    pub fn load(path: &Path) -> Result<Self, StorageError> {
+        Ok(read_json(path)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), StorageError> {
+        Ok(atomic_save_json(path, self)?)
+    }
+}
+
+/// Persists collection templates, which are changed through consensus.
+/// - Reads are served from memory.
+/// - Writes are durably saved.
+#[derive(Debug)]
+pub struct TemplatePersistence {
+    data_path: PathBuf,
+    templates: TemplateMapping,
+}
+
+impl TemplatePersistence {
+    pub fn open(dir_path: PathBuf) -> Result<Self, StorageError> {
+        if !dir_path.exists() {
+            fs::create_dir_all(&dir_path)?;
+        }
+        let data_path = dir_path.join(TEMPLATES_CONFIG_FILE);
+        let templates = if data_path.exists() {
+            TemplateMapping::load(&data_path)?
+        } else {
+            TemplateMapping::default()
+        };
+        Ok(Self {
+            data_path,
+            templates,
+        })
+    }
+
+    pub fn get(&self, name: &str) -> Result<&CollectionTemplate, StorageError> {
+        self.templates
+            .0
+            .get(name)
+            .ok_or_else(|| StorageError::not_found(format!("Template {name} not found")))
+    }
+
+    /// All templates, sorted by name
+    pub fn list(&self) -> Vec<CollectionTemplateDescription> {
+        let mut templates: Vec<_> = self
+            .templates
+            .0
+            .iter()
+            .map(|(name, template)| CollectionTemplateDescription {
+                name: name.clone(),
+                template: template.clone(),
+            })
+            .collect();
+        templates.sort_by(|a, b| a.name.cmp(&b.name));
+        templates
+    }
+
+    pub fn apply(&mut self, operation: TemplateOperation) -> Result<(), StorageError> {
+        match operation {
+            TemplateOperation::Put { name, template } => {
+                self.templates.0.insert(name, template);
+            }
+            TemplateOperation::Delete { name } => {
+                if self.templates.0.remove(&name).is_none() {
+                    return Err(StorageError::not_found(format!(
+                        "Template {name} not found"
+                    )));
+                }
+            }
+        }
+        self.templates.save(&self.data_path)
+    }
+
+    pub fn state(&self) -> &TemplateMapping {
+        &self.templates
+    }
+
+    pub fn apply_state(&mut self, templates: TemplateMapping) -> Result<(), StorageError> {
+        self.templates = templates;
+        self.templates.save(&self.data_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use tempfile::Builder;
+
+    use super::*;
+
+    #[test]
+    fn test_collection_template() {
+        let dir = Builder::new().prefix("templates").tempdir().unwrap();
+        let mut persistence = TemplatePersistence::open(dir.path().to_path_buf()).unwrap();
+
+        let template: CollectionTemplate = serde_json::from_value(json!({
+            "vectors": {"size": 4, "distance": "Cosine"},
+            "on_disk_payload": true,
+            "hnsw_config": {"m": 32, "ef_construct": 200},
+        }))
+        .unwrap();
+        persistence
+            .apply(TemplateOperation::Put {
+                name: "small".to_string(),
+                template: template.clone(),
+            })
+            .unwrap();
+
+        // Changes are persisted
+        let persistence = TemplatePersistence::open(dir.path().to_path_buf()).unwrap();
+        assert_eq!(persistence.get("small").unwrap(), &template);
+        assert!(persistence.get("large").is_err());
+
+        let request: CreateCollection = serde_json::from_value(json!({
+            "on_disk_payload": false,
+            "hnsw_config": {"m": 16},
+        }))
+        .unwrap();
+        let request = persistence.get("small").unwrap().apply(request);
+        assert_eq!(request.vectors.vectors_num(), 1);
+        // Request parameters take precedence
+        assert_eq!(request.on_disk_payload, Some(false));
+        let hnsw_config = request.hnsw_config.unwrap();
+        assert_eq!(hnsw_config.m, Some(16));
+        assert_eq!(hnsw_config.ef_construct, Some(200));
+    }
+}
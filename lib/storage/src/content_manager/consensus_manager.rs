@@ -24,9 +24,13 @@ use tokio::sync::broadcast::Receiver;
 use tokio::time::error::Elapsed;
 use tonic::transport::Uri;
 
-use super::alias_mapping::AliasMapping;
+use super::alias_mapping::{AliasFilters, AliasMapping};
+use super::api_keys::ApiKeyMapping;
+use super::cluster_metadata::MetadataMapping;
+use super::collection_templates::TemplateMapping;
 use super::consensus_ops::{ConsensusOperations, SnapshotStatus};
 use super::errors::StorageError;
+use super::snapshot_schedules::SnapshotScheduleMapping;
 use super::CollectionContainer;
 use crate::content_manager::consensus::consensus_wal::ConsensusOpWal;
 use crate::content_manager::consensus::entry_queue::EntryId;
@@ -59,6 +63,16 @@ pub struct SnapshotData {
 pub struct CollectionsSnapshot {
     pub collections: HashMap<CollectionId, collection_state::State>,
     pub aliases: AliasMapping,
+    #[serde(default)]
+    pub alias_filters: AliasFilters,
+    #[serde(default)]
+    pub api_keys: ApiKeyMapping,
+    #[serde(default)]
+    pub templates: TemplateMapping,
+    #[serde(default)]
+    pub cluster_metadata: MetadataMapping,
+    #[serde(default)]
+    pub snapshot_schedules: SnapshotScheduleMapping,
 }
 
 impl TryFrom<&[u8]> for SnapshotData {
@@ -1,5 +1,10 @@
+use std::num::{NonZeroU32, NonZeroU64};
+
+use api::grpc::conversions::{attribute_columns_from_proto, json_path_from_proto};
+use collection::operations::computed_fields::ComputedField;
 use collection::operations::conversions::sharding_method_from_proto;
 use collection::operations::types::SparseVectorsConfig;
+use segment::types::PayloadCompression;
 use tonic::Status;
 
 use crate::content_manager::collection_meta_ops::{
@@ -22,6 +27,7 @@ pub fn error_to_status(error: StorageError) -> tonic::Status {
         StorageError::ChecksumMismatch { .. } => tonic::Code::DataLoss,
         StorageError::Forbidden { .. } => tonic::Code::PermissionDenied,
         StorageError::PreconditionFailed { .. } => tonic::Code::FailedPrecondition,
+        StorageError::RateLimitExceeded { .. } => tonic::Code::ResourceExhausted,
     };
     tonic::Status::new(error_code, format!("{error}"))
 }
@@ -47,6 +53,30 @@ impl TryFrom<api::grpc::qdrant::CreateCollection> for CollectionMetaOperations {
                 optimizers_config: value.optimizers_config.map(|v| v.into()),
                 shard_number: value.shard_number,
                 on_disk_payload: value.on_disk_payload,
+                gridstore_payload: value.gridstore_payload,
+                payload_columns: Some(
+                    value
+                        .payload_columns
+                        .iter()
+                        .map(|key| json_path_from_proto(key))
+                        .collect::<Result<_, _>>()?,
+                ),
+                payload_compression: value
+                    .payload_compression_level
+                    .map(|level| PayloadCompression { level }),
+                point_history_versions: value.point_history_versions.and_then(NonZeroU32::new),
+                soft_delete_retention_sec: value
+                    .soft_delete_retention_sec
+                    .and_then(NonZeroU64::new),
+                computed_fields: Some(
+                    value
+                        .computed_fields
+                        .into_iter()
+                        .map(ComputedField::try_from)
+                        .collect::<Result<_, _>>()?,
+                ),
+                attribute_columns: Some(attribute_columns_from_proto(value.attribute_columns)?),
+                template: value.template,
                 replication_factor: value.replication_factor,
                 write_consistency_factor: value.write_consistency_factor,
                 init_from: value
@@ -60,6 +90,10 @@ impl TryFrom<api::grpc::qdrant::CreateCollection> for CollectionMetaOperations {
                     .sharding_method
                     .map(sharding_method_from_proto)
                     .transpose()?,
+                strict_mode_config: value
+                    .strict_mode_config
+                    .map(TryInto::try_into)
+                    .transpose()?,
             },
         )))
     }
@@ -88,6 +122,10 @@ impl TryFrom<api::grpc::qdrant::UpdateCollection> for CollectionMetaOperations {
                     .sparse_vectors_config
                     .map(TryInto::try_into)
                     .transpose()?,
+                strict_mode_config: value
+                    .strict_mode_config
+                    .map(TryInto::try_into)
+                    .transpose()?,
             },
         )))
     }
@@ -109,6 +147,7 @@ impl From<api::grpc::qdrant::CreateAlias> for AliasOperations {
             create_alias: CreateAlias {
                 collection_name: value.collection_name,
                 alias_name: value.alias_name,
+                filter: None,
             },
         })
     }
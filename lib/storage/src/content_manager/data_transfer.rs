@@ -85,6 +85,8 @@ async fn replicate_shard_data(
             with_payload: Some(WithPayloadInterface::Bool(true)),
             with_vector: WithVector::Bool(true),
             order_by: None,
+            cursor: None,
+            include_deleted: None,
         };
 
         let collections_read = collections.read().await;
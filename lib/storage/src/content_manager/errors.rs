@@ -32,6 +32,8 @@ pub enum StorageError {
     Forbidden { description: String },
     #[error("Pre-condition failure: {description}")]
     PreconditionFailed { description: String }, // system is not in the state to perform the operation
+    #[error("Rate limit exceeded: {description}")]
+    RateLimitExceeded { description: String },
 }
 
 impl StorageError {
@@ -131,6 +133,12 @@ impl StorageError {
                 description: overriding_description,
                 backtrace: None,
             },
+            CollectionError::StrictMode { .. } => StorageError::Forbidden {
+                description: overriding_description,
+            },
+            CollectionError::RateLimitExceeded { .. } => StorageError::RateLimitExceeded {
+                description: overriding_description,
+            },
         }
     }
 }
@@ -179,6 +187,12 @@ impl From<CollectionError> for StorageError {
                 description: format!("{err}"),
                 backtrace: None,
             },
+            CollectionError::StrictMode { .. } => StorageError::Forbidden {
+                description: format!("{err}"),
+            },
+            CollectionError::RateLimitExceeded { description } => {
+                StorageError::RateLimitExceeded { description }
+            }
         }
     }
 }
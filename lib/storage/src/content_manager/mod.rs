@@ -5,7 +5,10 @@ use self::consensus_manager::CollectionsSnapshot;
 use self::errors::StorageError;
 
 pub mod alias_mapping;
+pub mod api_keys;
+pub mod cluster_metadata;
 pub mod collection_meta_ops;
+pub mod collection_templates;
 mod collections_ops;
 pub mod consensus;
 pub mod consensus_manager;
@@ -13,6 +16,7 @@ pub mod conversions;
 mod data_transfer;
 pub mod errors;
 pub mod shard_distribution;
+pub mod snapshot_schedules;
 pub mod snapshots;
 pub mod toc;
 
@@ -130,6 +134,7 @@ pub mod consensus_ops {
                     hnsw_config: None,
                     quantization_config: None,
                     sparse_vectors: None,
+                    strict_mode_config: None,
                 },
             );
             operation
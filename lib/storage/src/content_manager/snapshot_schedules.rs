@@ -0,0 +1,616 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Datelike, DurationRound, NaiveDate, TimeDelta, Timelike, Utc};
+use collection::common::snapshots_manager::SnapshotsStorageConfig;
+use io::file_operations::{atomic_save_json, read_json};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationError};
+
+use crate::content_manager::errors::StorageError;
+
+pub const SNAPSHOT_SCHEDULES_CONFIG_FILE: &str = "data.json";
+
+/// Max number of years, the next run of a cron expression is looked for
+const MAX_CRON_LOOKAHEAD_YEARS: i64 = 5;
+
+/// Storage, scheduled snapshots are saved to
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Eq, Hash, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ScheduledSnapshotStorage {
+    /// Snapshots directory of the peer
+    #[default]
+    Local,
+    /// S3 bucket from the snapshots config of the peer
+    S3,
+}
+
+impl From<ScheduledSnapshotStorage> for SnapshotsStorageConfig {
+    fn from(storage: ScheduledSnapshotStorage) -> Self {
+        match storage {
+            ScheduledSnapshotStorage::Local => SnapshotsStorageConfig::Local,
+            ScheduledSnapshotStorage::S3 => SnapshotsStorageConfig::S3,
+        }
+    }
+}
+
+/// Policy of creating collection snapshots periodically
+///
+/// Each peer creates snapshots of its local shards, the same way as with the snapshot API.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, PartialEq, Eq, Hash, Clone)]
+pub struct SnapshotSchedule {
+    /// Cron expression in UTC: `minute hour day-of-month month day-of-week`.
+    /// Fields accept `*`, numbers, ranges (`1-5`), lists (`1,15`) and steps (`*/15`).
+    /// Shortcuts `@hourly`, `@daily`, `@weekly` and `@monthly` are also accepted.
+    #[validate(custom = "validate_cron")]
+    pub cron: String,
+    /// Number of the latest snapshots of the schedule to keep, older ones are deleted
+    #[validate(range(min = 1))]
+    pub retention: usize,
+    /// Where to save the snapshots. Default: `local`
+    #[serde(default)]
+    pub storage: ScheduledSnapshotStorage,
+}
+
+/// Result of the runs of a schedule on this peer
+#[derive(Debug, Serialize, JsonSchema, Clone, Default, PartialEq)]
+pub struct SnapshotScheduleStatus {
+    /// Time the next snapshot will be created at
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_run_at: Option<DateTime<Utc>>,
+    /// Time the last snapshot was started at
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_run_at: Option<DateTime<Utc>>,
+    /// Name of the last snapshot, created successfully
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_snapshot: Option<String>,
+    /// Error of the last run, if it failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
+/// Schedule with its name and status, as it is listed
+#[derive(Debug, Serialize, JsonSchema, Clone)]
+pub struct SnapshotScheduleInfo {
+    pub name: String,
+    #[serde(flatten)]
+    pub schedule: SnapshotSchedule,
+    pub status: SnapshotScheduleStatus,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Hash, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum SnapshotScheduleOperation {
+    /// Create the schedule or replace the existing one with the same name
+    Set {
+        collection_name: String,
+        name: String,
+        schedule: SnapshotSchedule,
+    },
+    Delete {
+        collection_name: String,
+        name: String,
+    },
+}
+
+impl SnapshotScheduleOperation {
+    pub fn collection_name(&self) -> &str {
+        match self {
+            SnapshotScheduleOperation::Set {
+                collection_name, ..
+            }
+            | SnapshotScheduleOperation::Delete {
+                collection_name, ..
+            } => collection_name,
+        }
+    }
+}
+
+/// Snapshot schedules by collection and schedule name
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, Default)]
+pub struct SnapshotScheduleMapping(HashMap<String, BTreeMap<String, SnapshotSchedule>>);
+
+impl SnapshotScheduleMapping {
+    pub fn load(path: &Path) -> Result<Self, StorageError> {
+        Ok(read_json(path)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), StorageError> {
+        Ok(atomic_save_json(path, self)?)
+    }
+}
+
+/// Persists snapshot schedules, which are changed through consensus.
+/// - Reads are served from memory.
+/// - Writes are durably saved.
+#[derive(Debug)]
+pub struct SnapshotSchedulePersistence {
+    data_path: PathBuf,
+    schedules: SnapshotScheduleMapping,
+}
+
+impl SnapshotSchedulePersistence {
+    pub fn open(dir_path: PathBuf) -> Result<Self, StorageError> {
+        if !dir_path.exists() {
+            fs::create_dir_all(&dir_path)?;
+        }
+        let data_path = dir_path.join(SNAPSHOT_SCHEDULES_CONFIG_FILE);
+        let schedules = if data_path.exists() {
+            SnapshotScheduleMapping::load(&data_path)?
+        } else {
+            SnapshotScheduleMapping::default()
+        };
+        Ok(Self {
+            data_path,
+            schedules,
+        })
+    }
+
+    /// Schedules of the collection, sorted by name
+    pub fn list(&self, collection_name: &str) -> Vec<(String, SnapshotSchedule)> {
+        self.schedules
+            .0
+            .get(collection_name)
+            .into_iter()
+            .flatten()
+            .map(|(name, schedule)| (name.clone(), schedule.clone()))
+            .collect()
+    }
+
+    /// Schedules of all collections, with the names of their collections
+    pub fn all(&self) -> Vec<(String, String, SnapshotSchedule)> {
+        self.schedules
+            .0
+            .iter()
+            .flat_map(|(collection_name, schedules)| {
+                schedules.iter().map(|(name, schedule)| {
+                    (collection_name.clone(), name.clone(), schedule.clone())
+                })
+            })
+            .collect()
+    }
+
+    pub fn apply(&mut self, operation: SnapshotScheduleOperation) -> Result<(), StorageError> {
+        match operation {
+            SnapshotScheduleOperation::Set {
+                collection_name,
+                name,
+                schedule,
+            } => {
+                self.schedules
+                    .0
+                    .entry(collection_name)
+                    .or_default()
+                    .insert(name, schedule);
+            }
+            SnapshotScheduleOperation::Delete {
+                collection_name,
+                name,
+            } => {
+                let schedules = self.schedules.0.get_mut(&collection_name);
+                if schedules
+                    .and_then(|schedules| schedules.remove(&name))
+                    .is_none()
+                {
+                    return Err(StorageError::not_found(format!(
+                        "Snapshot schedule {name} of collection {collection_name} not found"
+                    )));
+                }
+                if self
+                    .schedules
+                    .0
+                    .get(&collection_name)
+                    .is_some_and(BTreeMap::is_empty)
+                {
+                    self.schedules.0.remove(&collection_name);
+                }
+            }
+        }
+        self.schedules.save(&self.data_path)
+    }
+
+    /// Remove all schedules of the deleted collection
+    pub fn remove_collection(&mut self, collection_name: &str) -> Result<(), StorageError> {
+        if self.schedules.0.remove(collection_name).is_some() {
+            self.schedules.save(&self.data_path)?;
+        }
+        Ok(())
+    }
+
+    pub fn state(&self) -> &SnapshotScheduleMapping {
+        &self.schedules
+    }
+
+    pub fn apply_state(&mut self, schedules: SnapshotScheduleMapping) -> Result<(), StorageError> {
+        self.schedules = schedules;
+        self.schedules.save(&self.data_path)
+    }
+}
+
+/// Statuses of the schedules on this peer, not persisted.
+///
+/// Runs, which were missed while the peer was down, are not caught up.
+#[derive(Debug, Default)]
+pub struct SnapshotScheduleStatuses {
+    /// Status with the cron expression, the next run was computed from
+    statuses: HashMap<(String, String), (String, SnapshotScheduleStatus)>,
+}
+
+impl SnapshotScheduleStatuses {
+    pub fn get(&self, collection_name: &str, name: &str) -> SnapshotScheduleStatus {
+        self.statuses
+            .get(&(collection_name.to_string(), name.to_string()))
+            .map(|(_, status)| status.clone())
+            .unwrap_or_default()
+    }
+
+    /// Schedules, which are due at `now`.
+    ///
+    /// Statuses of removed schedules are dropped, next runs of new and changed ones are planned.
+    pub fn due(
+        &mut self,
+        schedules: Vec<(String, String, SnapshotSchedule)>,
+        now: DateTime<Utc>,
+    ) -> Vec<(String, String, SnapshotSchedule)> {
+        let mut due = Vec::new();
+        let mut statuses = HashMap::with_capacity(schedules.len());
+        for (collection_name, name, schedule) in schedules {
+            let key = (collection_name, name);
+            let (cron, mut status) = self.statuses.remove(&key).unwrap_or_default();
+            if cron != schedule.cron {
+                match CronExpression::parse(&schedule.cron) {
+                    Ok(expression) => status.next_run_at = expression.next_after(now),
+                    Err(err) => {
+                        status.next_run_at = None;
+                        status.last_error = Some(format!("Invalid cron expression: {err}"));
+                    }
+                }
+            }
+            if status
+                .next_run_at
+                .is_some_and(|next_run_at| next_run_at <= now)
+            {
+                due.push((key.0.clone(), key.1.clone(), schedule.clone()));
+            }
+            statuses.insert(key, (schedule.cron, status));
+        }
+        self.statuses = statuses;
+        due
+    }
+
+    /// Record the result of a run, which started at `started_at`, and plan the next one
+    pub fn finish(
+        &mut self,
+        collection_name: &str,
+        name: &str,
+        started_at: DateTime<Utc>,
+        result: Result<String, StorageError>,
+    ) {
+        let Some((cron, status)) = self
+            .statuses
+            .get_mut(&(collection_name.to_string(), name.to_string()))
+        else {
+            // Schedule was removed during the run
+            return;
+        };
+        status.last_run_at = Some(started_at);
+        match result {
+            Ok(snapshot_name) => {
+                status.last_snapshot = Some(snapshot_name);
+                status.last_error = None;
+            }
+            Err(err) => status.last_error = Some(err.to_string()),
+        }
+        status.next_run_at = CronExpression::parse(cron)
+            .ok()
+            .and_then(|expression| expression.next_after(Utc::now().max(started_at)));
+    }
+}
+
+/// Parsed cron expression. Each field is a bit set of the matching values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronExpression {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    /// Sunday is 0
+    days_of_week: u64,
+    /// If both days of month and days of week are restricted, a day matching either is matched
+    any_day_of_month: bool,
+    any_day_of_week: bool,
+}
+
+impl CronExpression {
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        let expression = match expression.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            expression => expression,
+        };
+        let fields: Vec<_> = expression.split_whitespace().collect();
+        let [minutes, hours, days_of_month, months, days_of_week] = fields[..] else {
+            return Err(format!(
+                "expected 5 fields: minute hour day-of-month month day-of-week, got {}",
+                fields.len(),
+            ));
+        };
+        // Both 0 and 7 are Sunday
+        let weekdays = parse_cron_field(days_of_week, 0, 7)?;
+        Ok(Self {
+            minutes: parse_cron_field(minutes, 0, 59)?,
+            hours: parse_cron_field(hours, 0, 23)?,
+            days_of_month: parse_cron_field(days_of_month, 1, 31)?,
+            months: parse_cron_field(months, 1, 12)?,
+            days_of_week: (weekdays | (weekdays >> 7)) & 0x7f,
+            any_day_of_month: days_of_month == "*",
+            any_day_of_week: days_of_week == "*",
+        })
+    }
+
+    fn matches_day(&self, day_of_month: u32, day_of_week: u32) -> bool {
+        let day_of_month = self.days_of_month & (1 << day_of_month) != 0;
+        let day_of_week = self.days_of_week & (1 << day_of_week) != 0;
+        match (self.any_day_of_month, self.any_day_of_week) {
+            (true, true) => true,
+            (true, false) => day_of_week,
+            (false, true) => day_of_month,
+            (false, false) => day_of_month || day_of_week,
+        }
+    }
+
+    /// First matching minute strictly after `time`
+    pub fn next_after(&self, time: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let mut next = time.duration_trunc(TimeDelta::minutes(1)).ok()? + TimeDelta::minutes(1);
+        let limit = time + TimeDelta::days(366 * MAX_CRON_LOOKAHEAD_YEARS);
+        while next <= limit {
+            if self.months & (1 << next.month()) == 0 {
+                let (year, month) = if next.month() == 12 {
+                    (next.year() + 1, 1)
+                } else {
+                    (next.year(), next.month() + 1)
+                };
+                next = NaiveDate::from_ymd_opt(year, month, 1)?
+                    .and_hms_opt(0, 0, 0)?
+                    .and_utc();
+                continue;
+            }
+            if !self.matches_day(next.day(), next.weekday().num_days_from_sunday()) {
+                next = (next.date_naive() + TimeDelta::days(1))
+                    .and_hms_opt(0, 0, 0)?
+                    .and_utc();
+                continue;
+            }
+            if self.hours & (1 << next.hour()) == 0 {
+                next = next.duration_trunc(TimeDelta::hours(1)).ok()? + TimeDelta::hours(1);
+                continue;
+            }
+            if self.minutes & (1 << next.minute()) == 0 {
+                next += TimeDelta::minutes(1);
+                continue;
+            }
+            return Some(next);
+        }
+        None
+    }
+}
+
+/// Parse a field of a cron expression into a bit set of the values within `min..=max`
+fn parse_cron_field(field: &str, min: u32, max: u32) -> Result<u64, String> {
+    let parse_value = |value: &str| -> Result<u32, String> {
+        let value = value
+            .parse()
+            .map_err(|_| format!("invalid value {value:?} in field {field:?}"))?;
+        if !(min..=max).contains(&value) {
+            return Err(format!(
+                "value {value} in field {field:?} is out of range {min}-{max}",
+            ));
+        }
+        Ok(value)
+    };
+
+    let mut bits = 0;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .map_err(|_| format!("invalid step {step:?} in field {field:?}"))?;
+                if step == 0 {
+                    return Err(format!("step in field {field:?} must be positive"));
+                }
+                (range, step)
+            }
+            None => (part, 1),
+        };
+        let (start, end) = match range.split_once('-') {
+            _ if range == "*" => (min, max),
+            Some((start, end)) => (parse_value(start)?, parse_value(end)?),
+            // `5/10` is from 5 to the end of the range
+            None if step > 1 => (parse_value(range)?, max),
+            None => {
+                let value = parse_value(range)?;
+                (value, value)
+            }
+        };
+        if start > end {
+            return Err(format!("range {range:?} in field {field:?} is empty"));
+        }
+        for value in (start..=end).step_by(step as usize) {
+            bits |= 1 << value;
+        }
+    }
+    Ok(bits)
+}
+
+/// Schedule names are a part of snapshot file names, so only a safe set of characters is allowed
+pub fn validate_snapshot_schedule_name(name: &str) -> Result<(), ValidationError> {
+    if name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Ok(());
+    }
+    let mut error = ValidationError::new("snapshot_schedule_name");
+    error.message =
+        Some("schedule name may only contain latin letters, digits, '-' and '_'".into());
+    Err(error)
+}
+
+fn validate_cron(cron: &str) -> Result<(), ValidationError> {
+    CronExpression::parse(cron).map(|_| ()).map_err(|err| {
+        let mut error = ValidationError::new("cron");
+        error.message = Some(format!("invalid cron expression: {err}").into());
+        error
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use tempfile::Builder;
+
+    use super::*;
+
+    fn time(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, minute, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_cron_next_after() {
+        let every_15 = CronExpression::parse("*/15 * * * *").unwrap();
+        assert_eq!(
+            every_15.next_after(time(2024, 5, 10, 12, 7)),
+            Some(time(2024, 5, 10, 12, 15)),
+        );
+        // Strictly after
+        assert_eq!(
+            every_15.next_after(time(2024, 5, 10, 12, 45)),
+            Some(time(2024, 5, 10, 13, 0)),
+        );
+
+        let daily = CronExpression::parse("@daily").unwrap();
+        assert_eq!(
+            daily.next_after(time(2024, 12, 31, 23, 59)),
+            Some(time(2025, 1, 1, 0, 0)),
+        );
+
+        // Weekdays at 3:30
+        let weekdays = CronExpression::parse("30 3 * * 1-5").unwrap();
+        // 2024-05-11 is Saturday
+        assert_eq!(
+            weekdays.next_after(time(2024, 5, 11, 0, 0)),
+            Some(time(2024, 5, 13, 3, 30)),
+        );
+
+        // 29th of February only
+        let leap = CronExpression::parse("0 0 29 2 *").unwrap();
+        assert_eq!(
+            leap.next_after(time(2024, 3, 1, 0, 0)),
+            Some(time(2028, 2, 29, 0, 0)),
+        );
+        let never = CronExpression::parse("0 0 31 2 *").unwrap();
+        assert_eq!(never.next_after(time(2024, 3, 1, 0, 0)), None);
+
+        // Either day of month or Sunday (7)
+        let either = CronExpression::parse("0 12 1 * 7").unwrap();
+        // 2024-05-12 is Sunday
+        assert_eq!(
+            either.next_after(time(2024, 5, 6, 0, 0)),
+            Some(time(2024, 5, 12, 12, 0)),
+        );
+    }
+
+    #[test]
+    fn test_cron_parse_errors() {
+        assert!(CronExpression::parse("* * * *").is_err());
+        assert!(CronExpression::parse("60 * * * *").is_err());
+        assert!(CronExpression::parse("*/0 * * * *").is_err());
+        assert!(CronExpression::parse("5-1 * * * *").is_err());
+        assert!(CronExpression::parse("0 0 0 * *").is_err());
+        assert!(CronExpression::parse("0,30 8-18/2 * 1,7 *").is_ok());
+    }
+
+    #[test]
+    fn test_snapshot_schedule_statuses() {
+        let schedule = SnapshotSchedule {
+            cron: "0 * * * *".to_string(),
+            retention: 3,
+            storage: ScheduledSnapshotStorage::Local,
+        };
+        let schedules = vec![("coll".to_string(), "hourly".to_string(), schedule.clone())];
+        let mut statuses = SnapshotScheduleStatuses::default();
+
+        // First run is planned, not started
+        assert!(statuses
+            .due(schedules.clone(), time(2024, 5, 10, 12, 30))
+            .is_empty());
+        assert_eq!(
+            statuses.get("coll", "hourly").next_run_at,
+            Some(time(2024, 5, 10, 13, 0)),
+        );
+        assert_eq!(
+            statuses
+                .due(schedules.clone(), time(2024, 5, 10, 13, 0))
+                .len(),
+            1,
+        );
+
+        statuses.finish(
+            "coll",
+            "hourly",
+            time(2024, 5, 10, 13, 0),
+            Err(StorageError::service_error("disk is full")),
+        );
+        let status = statuses.get("coll", "hourly");
+        assert_eq!(status.last_run_at, Some(time(2024, 5, 10, 13, 0)));
+        assert!(status.last_error.is_some());
+
+        // Removed schedules are forgotten
+        assert!(statuses
+            .due(Vec::new(), time(2024, 5, 10, 14, 0))
+            .is_empty());
+        assert_eq!(statuses.get("coll", "hourly"), Default::default());
+    }
+
+    #[test]
+    fn test_snapshot_schedule_persistence() {
+        let dir = Builder::new()
+            .prefix("snapshot_schedules")
+            .tempdir()
+            .unwrap();
+        let mut persistence = SnapshotSchedulePersistence::open(dir.path().to_path_buf()).unwrap();
+        let schedule = SnapshotSchedule {
+            cron: "@daily".to_string(),
+            retention: 7,
+            storage: ScheduledSnapshotStorage::S3,
+        };
+        persistence
+            .apply(SnapshotScheduleOperation::Set {
+                collection_name: "coll".to_string(),
+                name: "daily".to_string(),
+                schedule: schedule.clone(),
+            })
+            .unwrap();
+
+        let mut persistence = SnapshotSchedulePersistence::open(dir.path().to_path_buf()).unwrap();
+        assert_eq!(
+            persistence.list("coll"),
+            vec![("daily".to_string(), schedule)],
+        );
+        assert!(persistence
+            .apply(SnapshotScheduleOperation::Delete {
+                collection_name: "coll".to_string(),
+                name: "hourly".to_string(),
+            })
+            .is_err());
+
+        persistence.remove_collection("coll").unwrap();
+        assert!(persistence.all().is_empty());
+    }
+}
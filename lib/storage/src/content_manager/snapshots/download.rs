@@ -1,5 +1,6 @@
 use std::path::{Path, PathBuf};
 
+use collection::common::snapshots_manager::SnapshotStorageManager;
 use futures::StreamExt;
 use reqwest;
 use tempfile::TempPath;
@@ -60,11 +61,15 @@ async fn download_file(
 
 /// Download a snapshot from the given URI.
 ///
+/// `s3://bucket/key` URIs are downloaded from the snapshots object storage, configured for the
+/// cluster.
+///
 /// May returen a `TempPath` if a file was downloaded from a remote source. If it is dropped the
 /// downloaded file is deleted automatically. To keep the file `keep()` may be used.
 #[must_use = "may return a TempPath, if dropped the downloaded file is deleted"]
 pub async fn download_snapshot(
     client: &reqwest::Client,
+    snapshots_storage: &SnapshotStorageManager,
     url: Url,
     snapshots_dir: &Path,
 ) -> Result<(PathBuf, Option<TempPath>), StorageError> {
@@ -88,6 +93,12 @@ pub async fn download_snapshot(
             let temp_path = download_file(client, &url, &download_to).await?;
             Ok((download_to, Some(temp_path)))
         }
+        "s3" => {
+            let key = object_storage_key(snapshots_storage, &url)?;
+            Ok(snapshots_storage
+                .get_local_snapshot(Path::new(&key), snapshots_dir)
+                .await?)
+        }
         _ => Err(StorageError::bad_request(format!(
             "URL {} with schema {} is not supported",
             url,
@@ -95,3 +106,68 @@ pub async fn download_snapshot(
         ))),
     }
 }
+
+/// Key of the snapshot in the snapshots object storage, referenced by `s3://bucket/key` URL
+fn object_storage_key(
+    snapshots_storage: &SnapshotStorageManager,
+    url: &Url,
+) -> Result<String, StorageError> {
+    if !matches!(snapshots_storage, SnapshotStorageManager::S3(_)) {
+        return Err(StorageError::bad_request(format!(
+            "Can't recover from {url}, snapshots are not stored in S3",
+        )));
+    }
+
+    if let (Some(bucket), Some(url_bucket)) = (snapshots_storage.s3_bucket(), url.host_str()) {
+        if bucket != url_bucket {
+            return Err(StorageError::bad_request(format!(
+                "Can't recover from {url}, snapshots are stored in bucket {bucket}",
+            )));
+        }
+    }
+
+    let key = url.path().trim_start_matches('/');
+    if key.is_empty() {
+        return Err(StorageError::bad_request(format!(
+            "Invalid snapshot URI {url}, object key is missing",
+        )));
+    }
+    Ok(key.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use collection::common::snapshots_manager::{
+        S3Config, SnapShotsConfig, SnapshotsStorageConfig,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_object_storage_key() {
+        let s3 = SnapshotStorageManager::new(SnapShotsConfig {
+            snapshots_storage: SnapshotsStorageConfig::S3,
+            s3_config: Some(S3Config {
+                bucket: "snapshots".to_string(),
+                region: Some("us-east-1".to_string()),
+                ..Default::default()
+            }),
+        })
+        .unwrap();
+
+        let url = Url::parse("s3://snapshots/collection/test.snapshot").unwrap();
+        assert_eq!(
+            object_storage_key(&s3, &url).unwrap(),
+            "collection/test.snapshot",
+        );
+
+        let other_bucket = Url::parse("s3://other/collection/test.snapshot").unwrap();
+        assert!(object_storage_key(&s3, &other_bucket).is_err());
+
+        let no_key = Url::parse("s3://snapshots/").unwrap();
+        assert!(object_storage_key(&s3, &no_key).is_err());
+
+        let local = SnapshotStorageManager::new(SnapShotsConfig::default()).unwrap();
+        assert!(object_storage_key(&local, &url).is_err());
+    }
+}
@@ -5,6 +5,7 @@ use std::collections::HashMap;
 use std::path::Path;
 
 use collection::operations::snapshot_ops::SnapshotDescription;
+use segment::types::Filter;
 use serde::{Deserialize, Serialize};
 use tar::Builder as TarBuilder;
 use tempfile::TempPath;
@@ -23,6 +24,9 @@ pub struct SnapshotConfig {
     /// Aliases for collections `<alias>:<collection_name>`
     #[serde(default)]
     pub collections_aliases: HashMap<String, String>,
+    /// Filters of the filtered aliases
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub aliases_filters: HashMap<String, Filter>,
 }
 
 pub async fn do_delete_full_snapshot(
@@ -109,8 +113,12 @@ async fn _do_create_full_snapshot(
         .collect();
 
     let mut alias_mapping: HashMap<String, String> = Default::default();
+    let mut aliases_filters: HashMap<String, Filter> = Default::default();
     for collection_pass in &all_collections {
         for alias in toc.collection_aliases(collection_pass, &access).await? {
+            if let Some(filter) = toc.alias_filter(&alias).await {
+                aliases_filters.insert(alias.clone(), filter);
+            }
             alias_mapping.insert(alias.to_string(), collection_pass.name().to_string());
         }
     }
@@ -121,6 +129,7 @@ async fn _do_create_full_snapshot(
         let snapshot_config = SnapshotConfig {
             collections_mapping: collection_name_to_snapshot_path,
             collections_aliases: alias_mapping,
+            aliases_filters,
         };
         let mut config_file = tokio::fs::File::create(&config_path).await?;
         config_file
@@ -1,12 +1,16 @@
 use collection::collection::Collection;
 use collection::common::sha_256::{hash_file, hashes_equal};
 use collection::config::CollectionConfig;
-use collection::operations::snapshot_ops::{SnapshotPriority, SnapshotRecover};
+use collection::operations::snapshot_ops::{
+    RemoteSnapshotRecover, SnapshotDescription, SnapshotPriority, SnapshotRecover,
+};
 use collection::shards::replica_set::ReplicaState;
 use collection::shards::shard::{PeerId, ShardId};
 use collection::shards::shard_config::ShardType;
 use collection::shards::shard_versioning::latest_shard_paths;
+use serde::Deserialize;
 use tokio::task::JoinHandle;
+use url::Url;
 
 use crate::content_manager::collection_meta_ops::{
     CollectionMetaOperations, CreateCollectionOperation,
@@ -64,6 +68,136 @@ pub fn do_recover_from_snapshot(
     }))
 }
 
+/// Recover collection from a snapshot of a collection on another Qdrant instance.
+///
+/// If no snapshot name is given, a fresh snapshot is created on the source instance first.
+/// The checksum reported by the source is used to verify the downloaded snapshot.
+pub fn do_recover_from_remote_snapshot(
+    dispatcher: &Dispatcher,
+    collection_name: &str,
+    source: RemoteSnapshotRecover,
+    access: Access,
+    client: reqwest::Client,
+) -> Result<JoinHandle<Result<bool, StorageError>>, StorageError> {
+    // Check access before sending any requests to the source instance
+    access.check_global_access(AccessRequirements::new().manage())?;
+
+    let dispatcher = dispatcher.clone();
+    let collection_name = collection_name.to_string();
+    Ok(tokio::spawn(async move {
+        let RemoteSnapshotRecover {
+            url,
+            collection_name: source_collection_name,
+            snapshot_name,
+            priority,
+            api_key,
+        } = source;
+
+        let source_collection_name =
+            source_collection_name.unwrap_or_else(|| collection_name.clone());
+
+        let snapshot =
+            resolve_remote_snapshot(&client, &url, &source_collection_name, snapshot_name).await?;
+
+        let location = remote_snapshot_url(&url, &source_collection_name, &snapshot.name)?;
+
+        log::debug!("Recovering collection {collection_name} from remote snapshot {location}");
+
+        let snapshot_recover = SnapshotRecover {
+            location,
+            priority,
+            checksum: snapshot.checksum,
+            api_key,
+        };
+
+        do_recover_from_snapshot(
+            &dispatcher,
+            &collection_name,
+            snapshot_recover,
+            access,
+            client,
+        )?
+        .await?
+    }))
+}
+
+/// Response envelope of the Qdrant REST API
+#[derive(Deserialize)]
+struct RemoteResponse<T> {
+    result: Option<T>,
+}
+
+/// Find the snapshot on the source instance, or create a new one
+async fn resolve_remote_snapshot(
+    client: &reqwest::Client,
+    url: &Url,
+    collection_name: &str,
+    snapshot_name: Option<String>,
+) -> Result<SnapshotDescription, StorageError> {
+    let snapshots_url = remote_snapshots_url(url, collection_name)?;
+
+    let response = match &snapshot_name {
+        None => {
+            log::debug!("Creating snapshot on remote instance {snapshots_url}");
+            client
+                .post(snapshots_url.clone())
+                .query(&[("wait", "true")])
+                .send()
+                .await?
+        }
+        Some(_) => client.get(snapshots_url.clone()).send().await?,
+    };
+
+    if !response.status().is_success() {
+        return Err(StorageError::bad_input(format!(
+            "Failed to get snapshot from {snapshots_url}: status - {}",
+            response.status()
+        )));
+    }
+
+    let body = response.bytes().await?;
+
+    let snapshot = match snapshot_name {
+        None => serde_json::from_slice::<RemoteResponse<SnapshotDescription>>(&body)?.result,
+        Some(snapshot_name) => {
+            serde_json::from_slice::<RemoteResponse<Vec<SnapshotDescription>>>(&body)?
+                .result
+                .unwrap_or_default()
+                .into_iter()
+                .find(|snapshot| snapshot.name == snapshot_name)
+        }
+    };
+
+    snapshot.ok_or_else(|| {
+        StorageError::not_found(format!(
+            "Snapshot of collection {collection_name} not found on {url}"
+        ))
+    })
+}
+
+fn remote_snapshots_url(url: &Url, collection_name: &str) -> Result<Url, StorageError> {
+    let mut snapshots_url = url.clone();
+    snapshots_url
+        .path_segments_mut()
+        .map_err(|_| StorageError::bad_input(format!("Invalid source URL {url}")))?
+        .pop_if_empty()
+        .extend(["collections", collection_name, "snapshots"]);
+    Ok(snapshots_url)
+}
+
+fn remote_snapshot_url(
+    url: &Url,
+    collection_name: &str,
+    snapshot_name: &str,
+) -> Result<Url, StorageError> {
+    let mut snapshot_url = remote_snapshots_url(url, collection_name)?;
+    snapshot_url
+        .path_segments_mut()
+        .map_err(|_| StorageError::bad_input(format!("Invalid source URL {url}")))?
+        .push(snapshot_name);
+    Ok(snapshot_url)
+}
+
 async fn _do_recover_from_snapshot(
     dispatcher: Dispatcher,
     access: Access,
@@ -90,8 +224,9 @@ async fn _do_recover_from_snapshot(
         download_dir.path().display(),
     );
 
+    let snapshots_storage = toc.get_snapshots_storage_manager()?;
     let (snapshot_path, snapshot_temp_path) =
-        download_snapshot(client, location, download_dir.path()).await?;
+        download_snapshot(client, &snapshots_storage, location, download_dir.path()).await?;
 
     if let Some(checksum) = checksum {
         let snapshot_checksum = hash_file(&snapshot_path).await?;
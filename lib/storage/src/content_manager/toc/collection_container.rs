@@ -122,6 +122,16 @@ impl TableOfContent {
         consensus_manager::CollectionsSnapshot {
             collections,
             aliases: self.alias_persistence.read().await.state().clone(),
+            alias_filters: self.alias_persistence.read().await.filters_state().clone(),
+            api_keys: self.api_key_persistence.read().await.state().clone(),
+            templates: self.template_persistence.read().await.state().clone(),
+            cluster_metadata: self.metadata_persistence.read().await.state().clone(),
+            snapshot_schedules: self
+                .snapshot_schedule_persistence
+                .read()
+                .await
+                .state()
+                .clone(),
         }
     }
 
@@ -239,7 +249,31 @@ impl TableOfContent {
             self.alias_persistence
                 .write()
                 .await
-                .apply_state(data.aliases)?;
+                .apply_state(data.aliases, data.alias_filters)?;
+
+            // Apply managed API keys
+            self.api_key_persistence
+                .write()
+                .await
+                .apply_state(data.api_keys)?;
+
+            // Apply collection templates
+            self.template_persistence
+                .write()
+                .await
+                .apply_state(data.templates)?;
+
+            // Apply cluster metadata
+            self.metadata_persistence
+                .write()
+                .await
+                .apply_state(data.cluster_metadata)?;
+
+            // Apply snapshot schedules
+            self.snapshot_schedule_persistence
+                .write()
+                .await
+                .apply_state(data.snapshot_schedules)?;
 
             Ok(())
         })
@@ -4,6 +4,8 @@ use std::path::Path;
 use collection::collection_state;
 use collection::config::ShardingMethod;
 use collection::events::{CollectionDeletedEvent, IndexCreatedEvent};
+use collection::operations::config_diff::DiffConfig as _;
+use collection::operations::config_impact::ConfigChangeImpact;
 use collection::shards::collection_shard_distribution::CollectionShardDistribution;
 use collection::shards::replica_set::ReplicaState;
 use collection::shards::transfer::ShardTransfer;
@@ -16,6 +18,7 @@ use crate::content_manager::collections_ops::Checker as _;
 use crate::content_manager::consensus_ops::ConsensusOperations;
 use crate::content_manager::errors::StorageError;
 use crate::content_manager::shard_distribution::ShardDistributionProposal;
+use crate::rbac::CollectionPass;
 
 impl TableOfContent {
     pub(super) fn perform_collection_meta_op_sync(
@@ -91,6 +94,29 @@ impl TableOfContent {
                 self.set_shard_replica_state(operation).await.map(|()| true)
             }
             CollectionMetaOperations::Nop { .. } => Ok(true),
+            CollectionMetaOperations::ApiKeys(operation) => {
+                log::debug!("Changing API keys");
+                self.api_key_persistence.write().await.apply(operation)?;
+                Ok(true)
+            }
+            CollectionMetaOperations::Templates(operation) => {
+                log::debug!("Changing collection templates");
+                self.template_persistence.write().await.apply(operation)?;
+                Ok(true)
+            }
+            CollectionMetaOperations::ClusterMetadata(operation) => {
+                log::debug!("Changing cluster metadata");
+                self.metadata_persistence.write().await.apply(operation)?;
+                Ok(true)
+            }
+            CollectionMetaOperations::SnapshotSchedules(operation) => {
+                log::debug!(
+                    "Changing snapshot schedules of collection {}",
+                    operation.collection_name(),
+                );
+                self.apply_snapshot_schedule_operation(operation).await?;
+                Ok(true)
+            }
             CollectionMetaOperations::CreateShardKey(create_shard_key) => {
                 log::debug!("Create shard key {:?}", create_shard_key);
                 self.create_shard_key(create_shard_key).await.map(|()| true)
@@ -126,6 +152,7 @@ impl TableOfContent {
             optimizers_config,
             quantization_config,
             sparse_vectors,
+            strict_mode_config,
         } = operation.update_collection;
         let collection = self
             .get_collection_unchecked(&operation.collection_name)
@@ -158,6 +185,9 @@ impl TableOfContent {
             collection.update_sparse_vectors_from_other(&diff).await?;
             recreate_optimizers = true;
         }
+        if let Some(diff) = strict_mode_config {
+            collection.update_strict_mode_config(diff).await?;
+        }
         if let Some(changes) = replica_changes {
             collection.handle_replica_changes(changes).await?;
         }
@@ -169,6 +199,56 @@ impl TableOfContent {
         Ok(true)
     }
 
+    /// Collection config, as it would be after the update, with the estimated effect of the change
+    ///
+    /// The update is not applied. Only shards, located on this peer, are estimated.
+    pub async fn estimate_collection_update(
+        &self,
+        collection_pass: &CollectionPass<'_>,
+        update: UpdateCollection,
+    ) -> Result<ConfigChangeImpact, StorageError> {
+        let UpdateCollection {
+            vectors,
+            hnsw_config,
+            params,
+            optimizers_config,
+            quantization_config,
+            sparse_vectors,
+            strict_mode_config,
+        } = update;
+        let collection = self.get_collection(collection_pass).await?;
+        let mut config = collection.state().await.config;
+
+        if let Some(diff) = optimizers_config {
+            config.optimizer_config = diff.update(&config.optimizer_config)?;
+        }
+        if let Some(diff) = params {
+            config.params = diff.update(&config.params)?;
+        }
+        if let Some(diff) = hnsw_config {
+            config.hnsw_config = diff.update(&config.hnsw_config)?;
+        }
+        if let Some(diff) = vectors {
+            diff.check_vector_names(&config.params)?;
+            config.params.update_vectors_from_diff(&diff)?;
+        }
+        if let Some(diff) = quantization_config {
+            config.quantization_config = diff.into_config();
+        }
+        if let Some(diff) = sparse_vectors {
+            diff.check_vector_names(&config.params)?;
+            config.params.update_sparse_vectors_from_other(&diff)?;
+        }
+        if let Some(diff) = strict_mode_config {
+            config.strict_mode_config = Some(match &config.strict_mode_config {
+                Some(strict_mode_config) => diff.update(strict_mode_config)?,
+                None => diff,
+            });
+        }
+
+        Ok(collection.estimate_config_change(config).await)
+    }
+
     pub(super) async fn delete_collection(
         &self,
         collection_name: &str,
@@ -179,6 +259,10 @@ impl TableOfContent {
                 .write()
                 .await
                 .remove_collection(collection_name)?;
+            self.snapshot_schedule_persistence
+                .write()
+                .await
+                .remove_collection(collection_name)?;
 
             let path = self.get_collection_path(collection_name);
             drop(removed);
@@ -242,6 +326,7 @@ impl TableOfContent {
                         CreateAlias {
                             collection_name,
                             alias_name,
+                            filter,
                         },
                 }) => {
                     collection_lock
@@ -251,7 +336,7 @@ impl TableOfContent {
                         .validate_collection_not_exists(&alias_name)
                         .await?;
 
-                    alias_lock.insert(alias_name, collection_name)?;
+                    alias_lock.insert(alias_name, collection_name, filter)?;
                 }
                 AliasOperations::DeleteAlias(DeleteAliasOperation {
                     delete_alias: DeleteAlias { alias_name },
@@ -3,6 +3,8 @@ use std::num::NonZeroU32;
 
 use collection::collection::Collection;
 use collection::config::{self, CollectionConfig, CollectionParams, ShardingMethod};
+use collection::events::CollectionCreatedEvent;
+use collection::operations::computed_fields::check_computed_fields;
 use collection::operations::config_diff::DiffConfig as _;
 use collection::operations::types::{
     check_sparse_compatible, CollectionResult, SparseVectorParams, VectorsConfig,
@@ -31,11 +33,30 @@ impl TableOfContent {
         // To prevent parallel writing of the files, we use this lock.
         let collection_create_guard = self.collection_create_lock.lock().await;
 
+        // Templates are changed through consensus, so all peers see the same template here
+        let operation = match &operation.template {
+            Some(template) => self
+                .template_persistence
+                .read()
+                .await
+                .get(template)?
+                .apply(operation),
+            None => operation,
+        };
+
         let CreateCollection {
             mut vectors,
             shard_number,
             sharding_method,
             on_disk_payload,
+            gridstore_payload,
+            payload_columns,
+            payload_compression,
+            point_history_versions,
+            soft_delete_retention_sec,
+            computed_fields,
+            attribute_columns,
+            template: _,
             hnsw_config: hnsw_config_diff,
             wal_config: wal_config_diff,
             optimizers_config: optimizers_config_diff,
@@ -44,6 +65,7 @@ impl TableOfContent {
             init_from,
             quantization_config,
             sparse_vectors,
+            strict_mode_config,
         } = operation;
 
         self.collections
@@ -139,6 +161,13 @@ impl TableOfContent {
             })?,
             sharding_method,
             on_disk_payload: on_disk_payload.unwrap_or(self.storage_config.on_disk_payload),
+            gridstore_payload: gridstore_payload.unwrap_or_default(),
+            payload_columns: payload_columns.unwrap_or_default(),
+            payload_compression,
+            point_history_versions,
+            soft_delete_retention_sec,
+            computed_fields: computed_fields.unwrap_or_default(),
+            attribute_columns: attribute_columns.unwrap_or_default(),
             replication_factor: NonZeroU32::new(replication_factor).ok_or(
                 StorageError::BadInput {
                     description: "`replication_factor` cannot be 0".to_string(),
@@ -151,6 +180,8 @@ impl TableOfContent {
             )?,
             read_fan_out_factor: None,
         };
+        check_computed_fields(&collection_params.computed_fields, &collection_params)?;
+
         let wal_config = match wal_config_diff {
             None => self.storage_config.wal.clone(),
             Some(diff) => diff.update(&self.storage_config.wal)?,
@@ -186,6 +217,7 @@ impl TableOfContent {
             optimizer_config: optimizers_config,
             hnsw_config,
             quantization_config,
+            strict_mode_config,
         };
         let collection = Collection::new(
             collection_name.to_string(),
@@ -217,6 +249,12 @@ impl TableOfContent {
         )
         .await?;
 
+        for field in &collection_config.params.computed_fields {
+            collection
+                .create_payload_index(field.key_path(), field.function.index_schema())
+                .await?;
+        }
+
         let local_shards = collection.get_local_shards().await;
 
         {
@@ -229,6 +267,10 @@ impl TableOfContent {
 
         drop(collection_create_guard);
 
+        issues::publish(CollectionCreatedEvent {
+            collection_id: collection_name.to_string(),
+        });
+
         // Notify the collection is created and ready to use
         for shard_id in local_shards {
             self.on_peer_created(collection_name.to_string(), self.this_peer_id, shard_id)
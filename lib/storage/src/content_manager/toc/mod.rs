@@ -5,7 +5,9 @@ mod create_collection;
 mod locks;
 mod point_ops;
 mod point_ops_internal;
+mod snapshot_schedules;
 mod snapshots;
+mod sparse_vocabularies;
 mod temp_directories;
 pub mod transfer;
 
@@ -32,6 +34,7 @@ use collection::telemetry::CollectionTelemetry;
 use common::cpu::{get_num_cpus, CpuBudget};
 use futures::future::try_join_all;
 use futures::Future;
+use segment::types::Filter;
 use tokio::runtime::Runtime;
 use tokio::sync::{Mutex, RwLock, RwLockReadGuard, Semaphore};
 use tonic::codegen::InterceptedService;
@@ -40,16 +43,28 @@ use tonic::Status;
 
 use self::transfer::ShardTransferDispatcher;
 use crate::content_manager::alias_mapping::AliasPersistence;
+use crate::content_manager::api_keys::{ApiKeyDescription, ApiKeyPersistence};
+use crate::content_manager::cluster_metadata::{MetadataEntry, MetadataPersistence};
 use crate::content_manager::collection_meta_ops::CreateCollectionOperation;
+use crate::content_manager::collection_templates::{
+    CollectionTemplate, CollectionTemplateDescription, TemplatePersistence,
+};
 use crate::content_manager::collections_ops::{Checker, Collections};
 use crate::content_manager::consensus::operation_sender::OperationSender;
 use crate::content_manager::errors::StorageError;
 use crate::content_manager::shard_distribution::ShardDistributionProposal;
+use crate::content_manager::snapshot_schedules::{
+    SnapshotSchedulePersistence, SnapshotScheduleStatuses,
+};
 use crate::rbac::{Access, AccessRequirements, CollectionPass};
 use crate::types::{PeerAddressById, StorageConfig};
 use crate::ConsensusOperations;
 
 pub const ALIASES_PATH: &str = "aliases";
+pub const API_KEYS_PATH: &str = "api_keys";
+pub const TEMPLATES_PATH: &str = "templates";
+pub const CLUSTER_METADATA_PATH: &str = "cluster_metadata";
+pub const SNAPSHOT_SCHEDULES_PATH: &str = "snapshot_schedules";
 pub const COLLECTIONS_DIR: &str = "collections";
 pub const FULL_SNAPSHOT_FILE_NAME: &str = "full-snapshot";
 
@@ -66,6 +81,12 @@ pub struct TableOfContent {
     /// Assigns CPU permits to tasks to limit overall resource utilization.
     optimizer_cpu_budget: CpuBudget,
     alias_persistence: RwLock<AliasPersistence>,
+    api_key_persistence: RwLock<ApiKeyPersistence>,
+    template_persistence: RwLock<TemplatePersistence>,
+    metadata_persistence: RwLock<MetadataPersistence>,
+    snapshot_schedule_persistence: RwLock<SnapshotSchedulePersistence>,
+    /// Runs of the snapshot schedules on this peer
+    snapshot_schedule_statuses: parking_lot::Mutex<SnapshotScheduleStatuses>,
     pub this_peer_id: PeerId,
     channel_service: ChannelService,
     /// Backlink to the consensus, if none - single node mode
@@ -169,6 +190,20 @@ impl TableOfContent {
         let alias_path = Path::new(&storage_config.storage_path).join(ALIASES_PATH);
         let alias_persistence =
             AliasPersistence::open(alias_path).expect("Can't open database by the provided config");
+        let api_keys_path = Path::new(&storage_config.storage_path).join(API_KEYS_PATH);
+        let api_key_persistence =
+            ApiKeyPersistence::open(api_keys_path).expect("Can't open API keys storage");
+        let templates_path = Path::new(&storage_config.storage_path).join(TEMPLATES_PATH);
+        let template_persistence = TemplatePersistence::open(templates_path)
+            .expect("Can't open collection templates storage");
+        let metadata_path = Path::new(&storage_config.storage_path).join(CLUSTER_METADATA_PATH);
+        let metadata_persistence =
+            MetadataPersistence::open(metadata_path).expect("Can't open cluster metadata storage");
+        let snapshot_schedules_path =
+            Path::new(&storage_config.storage_path).join(SNAPSHOT_SCHEDULES_PATH);
+        let snapshot_schedule_persistence =
+            SnapshotSchedulePersistence::open(snapshot_schedules_path)
+                .expect("Can't open snapshot schedules storage");
 
         let rate_limiter = match storage_config.performance.update_rate_limit {
             Some(limit) => Some(Semaphore::new(limit)),
@@ -195,6 +230,11 @@ impl TableOfContent {
             general_runtime,
             optimizer_cpu_budget,
             alias_persistence: RwLock::new(alias_persistence),
+            api_key_persistence: RwLock::new(api_key_persistence),
+            template_persistence: RwLock::new(template_persistence),
+            metadata_persistence: RwLock::new(metadata_persistence),
+            snapshot_schedule_persistence: RwLock::new(snapshot_schedule_persistence),
+            snapshot_schedule_statuses: Default::default(),
             this_peer_id,
             channel_service,
             consensus_proposal_sender,
@@ -303,6 +343,24 @@ impl TableOfContent {
         Ok(resolved_name)
     }
 
+    /// Filter of the alias, if it is a filtered view of its collection
+    pub async fn alias_filter(&self, alias: &str) -> Option<Filter> {
+        self.alias_persistence
+            .read()
+            .await
+            .get_filter(alias)
+            .cloned()
+    }
+
+    /// Restrict the access by the filter of the alias, if the request is made through a filtered
+    /// alias, so the filter is merged into the request like a filter constraint of the access
+    async fn alias_restricted_access(&self, collection_name: &str, access: Access) -> Access {
+        match self.alias_filter(collection_name).await {
+            Some(filter) => access.restrict_by_filter(collection_name, filter),
+            None => access,
+        }
+    }
+
     /// List of all aliases for a given collection
     pub async fn collection_aliases(
         &self,
@@ -331,9 +389,11 @@ impl TableOfContent {
         let mut aliases: Vec<AliasDescription> = Default::default();
         for collection_pass in &all_collections {
             for alias in self.collection_aliases(collection_pass, access).await? {
+                let filter = self.alias_filter(&alias).await;
                 aliases.push(AliasDescription {
                     alias_name: alias.to_string(),
                     collection_name: collection_pass.to_string(),
+                    filter,
                 });
             }
         }
@@ -341,6 +401,62 @@ impl TableOfContent {
         Ok(aliases)
     }
 
+    /// List of all managed API keys, without the keys themselves
+    pub async fn list_api_keys(
+        &self,
+        access: &Access,
+    ) -> Result<Vec<ApiKeyDescription>, StorageError> {
+        access.check_global_access(AccessRequirements::new().manage())?;
+        Ok(self.api_key_persistence.read().await.list())
+    }
+
+    /// Managed API key, matching the key passed in a request
+    pub async fn find_api_key(&self, key: &str) -> Option<ApiKeyDescription> {
+        self.api_key_persistence.read().await.find(key).cloned()
+    }
+
+    /// List of all collection templates
+    pub async fn list_templates(
+        &self,
+        access: &Access,
+    ) -> Result<Vec<CollectionTemplateDescription>, StorageError> {
+        access.check_global_access(AccessRequirements::new())?;
+        Ok(self.template_persistence.read().await.list())
+    }
+
+    pub async fn get_template(
+        &self,
+        name: &str,
+        access: &Access,
+    ) -> Result<CollectionTemplate, StorageError> {
+        access.check_global_access(AccessRequirements::new())?;
+        self.template_persistence.read().await.get(name).cloned()
+    }
+
+    /// All keys of the cluster metadata namespace with their values
+    pub async fn list_cluster_metadata(
+        &self,
+        namespace: &str,
+        access: &Access,
+    ) -> Result<Vec<MetadataEntry>, StorageError> {
+        access.check_global_access(AccessRequirements::new())?;
+        Ok(self.metadata_persistence.read().await.list(namespace))
+    }
+
+    pub async fn get_cluster_metadata(
+        &self,
+        namespace: &str,
+        key: &str,
+        access: &Access,
+    ) -> Result<String, StorageError> {
+        access.check_global_access(AccessRequirements::new())?;
+        self.metadata_persistence
+            .read()
+            .await
+            .get(namespace, key)
+            .map(str::to_string)
+    }
+
     pub async fn suggest_shard_distribution(
         &self,
         op: &CreateCollectionOperation,
@@ -9,9 +9,13 @@ use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::*;
 use collection::operations::universal_query::collection_query::CollectionQueryRequest;
 use collection::operations::{CollectionUpdateOperations, OperationWithClockTag};
+use collection::shards::resolve::Reconciled;
 use collection::{discovery, recommendations};
+use common::hardware_counter::HardwareCounterCell;
 use futures::stream::FuturesUnordered;
 use futures::TryStreamExt as _;
+use segment::data_types::aggregation::{DistinctValues, NumericStats, ValueDistribution};
+use segment::data_types::search_plan::ShardSearchPlan;
 use segment::types::{ScoredPoint, ShardKey};
 
 use super::TableOfContent;
@@ -38,9 +42,13 @@ impl TableOfContent {
         access: Access,
         timeout: Option<Duration>,
     ) -> Result<Vec<ScoredPoint>, StorageError> {
+        ReadConsistency::check_not_partial(read_consistency, "recommend")?;
+
+        let access = self.alias_restricted_access(collection_name, access).await;
         let collection_pass = access.check_point_op(collection_name, &mut request)?;
 
         let collection = self.get_collection(&collection_pass).await?;
+        collection.check_strict_mode(&request).await?;
         recommendations::recommend_by(
             request,
             &collection,
@@ -71,6 +79,9 @@ impl TableOfContent {
         access: Access,
         timeout: Option<Duration>,
     ) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
+        ReadConsistency::check_not_partial(read_consistency, "recommend")?;
+
+        let access = self.alias_restricted_access(collection_name, access).await;
         let mut collection_pass = None;
         for (request, _shard_selector) in &mut requests {
             collection_pass = Some(access.check_point_op(collection_name, request)?);
@@ -80,6 +91,7 @@ impl TableOfContent {
         };
 
         let collection = self.get_collection(&collection_pass).await?;
+        collection.check_strict_mode(requests.as_slice()).await?;
         recommendations::recommend_batch_by(
             requests,
             &collection,
@@ -101,6 +113,7 @@ impl TableOfContent {
     /// * `shard_selection` - which local shard to use
     /// * `timeout` - how long to wait for the response
     /// * `read_consistency` - consistency level
+    /// * `hw_counter` - accumulates hardware resources spent on the search
     ///
     /// # Result
     ///
@@ -108,23 +121,91 @@ impl TableOfContent {
     pub async fn core_search_batch(
         &self,
         collection_name: &str,
-        mut request: CoreSearchRequestBatch,
+        request: CoreSearchRequestBatch,
         read_consistency: Option<ReadConsistency>,
         shard_selection: ShardSelectorInternal,
         access: Access,
         timeout: Option<Duration>,
+        hw_counter: &HardwareCounterCell,
     ) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
+        let results = self
+            .core_search_batch_with_shards(
+                collection_name,
+                request,
+                read_consistency,
+                shard_selection,
+                access,
+                timeout,
+                hw_counter,
+            )
+            .await?;
+        Ok(results.value)
+    }
+
+    /// Same as [`TableOfContent::core_search_batch`], but also reports whether any search was
+    /// stopped at the timeout, and the status of each shard, if the read consistency is `partial`
+    pub async fn core_search_batch_with_shards(
+        &self,
+        collection_name: &str,
+        mut request: CoreSearchRequestBatch,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: ShardSelectorInternal,
+        access: Access,
+        timeout: Option<Duration>,
+        hw_counter: &HardwareCounterCell,
+    ) -> Result<PartialResponse<Vec<Vec<ScoredPoint>>>, StorageError> {
+        let access = self.alias_restricted_access(collection_name, access).await;
         let mut collection_pass = None;
         for request in &mut request.searches {
             collection_pass = Some(access.check_point_op(collection_name, request)?);
         }
         let Some(collection_pass) = collection_pass else {
-            return Ok(vec![]);
+            return Ok(PartialResponse::new(vec![]));
         };
 
         let collection = self.get_collection(&collection_pass).await?;
         collection
-            .core_search_batch(request, read_consistency, shard_selection, timeout)
+            .check_strict_mode(request.searches.as_slice())
+            .await?;
+        collection
+            .core_search_batch_with_shards(
+                request,
+                read_consistency,
+                shard_selection,
+                timeout,
+                hw_counter,
+            )
+            .await
+            .map_err(|err| err.into())
+    }
+
+    /// Search for the closest points and report the search plan of each shard and segment
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - in what collection do we search
+    /// * `request` - [`CoreSearchRequest`]
+    /// * `shard_selection` - which local shard to use
+    /// * `timeout` - how long to wait for the response
+    ///
+    /// # Result
+    ///
+    /// Points with search score and the search plans
+    pub async fn explain_search(
+        &self,
+        collection_name: &str,
+        mut request: CoreSearchRequest,
+        shard_selection: ShardSelectorInternal,
+        access: Access,
+        timeout: Option<Duration>,
+    ) -> Result<(Vec<ScoredPoint>, Vec<ShardSearchPlan>), StorageError> {
+        let access = self.alias_restricted_access(collection_name, access).await;
+        let collection_pass = access.check_point_op(collection_name, &mut request)?;
+
+        let collection = self.get_collection(&collection_pass).await?;
+        collection.check_strict_mode(&request).await?;
+        collection
+            .explain_search(request, &shard_selection, timeout)
             .await
             .map_err(|err| err.into())
     }
@@ -149,15 +230,118 @@ impl TableOfContent {
         shard_selection: ShardSelectorInternal,
         access: Access,
     ) -> Result<CountResult, StorageError> {
+        ReadConsistency::check_not_partial(read_consistency, "count")?;
+
+        let access = self.alias_restricted_access(collection_name, access).await;
         let collection_pass = access.check_point_op(collection_name, &mut request)?;
 
         let collection = self.get_collection(&collection_pass).await?;
+        collection.check_strict_mode(&request).await?;
         collection
             .count(request, read_consistency, &shard_selection)
             .await
             .map_err(|err| err.into())
     }
 
+    /// Count distinct values of the payload field among the points, matching the filter
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - in what collection do we count
+    /// * `request` - [`CountDistinctRequestInternal`]
+    /// * `shard_selection` - which local shard to use
+    ///
+    /// # Result
+    ///
+    /// Distinct values of the field, merged across the selected shards.
+    ///
+    pub async fn count_distinct(
+        &self,
+        collection_name: &str,
+        mut request: CountDistinctRequestInternal,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: ShardSelectorInternal,
+        access: Access,
+    ) -> Result<Reconciled<DistinctValues>, StorageError> {
+        ReadConsistency::check_not_partial(read_consistency, "count distinct")?;
+
+        let access = self.alias_restricted_access(collection_name, access).await;
+        let collection_pass = access.check_point_op(collection_name, &mut request)?;
+
+        let collection = self.get_collection(&collection_pass).await?;
+        collection.check_strict_mode(&request).await?;
+        collection
+            .count_distinct(request, read_consistency, &shard_selection)
+            .await
+            .map_err(|err| err.into())
+    }
+
+    /// Statistics of the values of the numeric payload field among the points, matching the filter
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - in what collection do we look for the values
+    /// * `request` - [`FieldStatsRequestInternal`]
+    /// * `shard_selection` - which local shard to use
+    ///
+    /// # Result
+    ///
+    /// Statistics of the field, merged across the selected shards.
+    ///
+    pub async fn field_stats(
+        &self,
+        collection_name: &str,
+        mut request: FieldStatsRequestInternal,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: ShardSelectorInternal,
+        access: Access,
+    ) -> Result<NumericStats, StorageError> {
+        ReadConsistency::check_not_partial(read_consistency, "field stats")?;
+
+        let access = self.alias_restricted_access(collection_name, access).await;
+        let collection_pass = access.check_point_op(collection_name, &mut request)?;
+
+        let collection = self.get_collection(&collection_pass).await?;
+        collection.check_strict_mode(&request).await?;
+        collection
+            .field_stats(request, read_consistency, &shard_selection)
+            .await
+            .map_err(|err| err.into())
+    }
+
+    /// Distribution of the values of the numeric payload field, read from its range index
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - in what collection do we look for the values
+    /// * `request` - [`HistogramRequestInternal`]
+    /// * `shard_selection` - which local shard to use
+    ///
+    /// # Result
+    ///
+    /// Value distribution of the field, merged across the selected shards.
+    ///
+    pub async fn histogram(
+        &self,
+        collection_name: &str,
+        mut request: HistogramRequestInternal,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: ShardSelectorInternal,
+        access: Access,
+    ) -> Result<ValueDistribution, StorageError> {
+        ReadConsistency::check_not_partial(read_consistency, "histogram")?;
+
+        let access = self.alias_restricted_access(collection_name, access).await;
+        let collection_pass = access.check_point_op(collection_name, &mut request)?;
+
+        let collection = self.get_collection(&collection_pass).await?;
+        collection.check_strict_mode(&request).await?;
+        collection
+            .histogram(request, read_consistency, &shard_selection)
+            .await
+            .map_err(|err| err.into())
+    }
+
     /// Return specific points by IDs
     ///
     /// # Arguments
@@ -177,6 +361,9 @@ impl TableOfContent {
         shard_selection: ShardSelectorInternal,
         access: Access,
     ) -> Result<Vec<Record>, StorageError> {
+        ReadConsistency::check_not_partial(read_consistency, "retrieve")?;
+
+        let access = self.alias_restricted_access(collection_name, access).await;
         let collection_pass = access.check_point_op(collection_name, &mut request)?;
 
         let collection = self.get_collection(&collection_pass).await?;
@@ -195,9 +382,13 @@ impl TableOfContent {
         access: Access,
         timeout: Option<Duration>,
     ) -> Result<GroupsResult, StorageError> {
+        ReadConsistency::check_not_partial(read_consistency, "groups")?;
+
+        let access = self.alias_restricted_access(collection_name, access).await;
         let collection_pass = access.check_point_op(collection_name, &mut request)?;
 
         let collection = self.get_collection(&collection_pass).await?;
+        collection.check_strict_mode(&request).await?;
 
         let collection_by_name = |name| self.get_collection_opt(name);
 
@@ -222,9 +413,13 @@ impl TableOfContent {
         access: Access,
         timeout: Option<Duration>,
     ) -> Result<Vec<ScoredPoint>, StorageError> {
+        ReadConsistency::check_not_partial(read_consistency, "discover")?;
+
+        let access = self.alias_restricted_access(collection_name, access).await;
         let collection_pass = access.check_point_op(collection_name, &mut request)?;
 
         let collection = self.get_collection(&collection_pass).await?;
+        collection.check_strict_mode(&request).await?;
         discovery::discover(
             request,
             &collection,
@@ -245,6 +440,9 @@ impl TableOfContent {
         access: Access,
         timeout: Option<Duration>,
     ) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
+        ReadConsistency::check_not_partial(read_consistency, "discover")?;
+
+        let access = self.alias_restricted_access(collection_name, access).await;
         let mut collection_pass = None;
         for (request, _shard_selector) in &mut requests {
             collection_pass = Some(access.check_point_op(collection_name, request)?);
@@ -254,6 +452,7 @@ impl TableOfContent {
         };
 
         let collection = self.get_collection(&collection_pass).await?;
+        collection.check_strict_mode(requests.as_slice()).await?;
 
         discovery::discover_batch(
             requests,
@@ -285,9 +484,13 @@ impl TableOfContent {
         shard_selection: ShardSelectorInternal,
         access: Access,
     ) -> Result<ScrollResult, StorageError> {
+        ReadConsistency::check_not_partial(read_consistency, "scroll")?;
+
+        let access = self.alias_restricted_access(collection_name, access).await;
         let collection_pass = access.check_point_op(collection_name, &mut request)?;
 
         let collection = self.get_collection(&collection_pass).await?;
+        collection.check_strict_mode(&request).await?;
         collection
             .scroll_by(request, read_consistency, &shard_selection)
             .await
@@ -301,19 +504,24 @@ impl TableOfContent {
         read_consistency: Option<ReadConsistency>,
         access: Access,
         timeout: Option<Duration>,
-    ) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
+        hw_counter: &HardwareCounterCell,
+    ) -> Result<PartialResponse<Vec<Vec<ScoredPoint>>>, StorageError> {
+        ReadConsistency::check_not_partial(read_consistency, "query")?;
+
+        let access = self.alias_restricted_access(collection_name, access).await;
         let mut collection_pass = None;
         for (request, _shard_selector) in &mut requests {
             collection_pass = Some(access.check_point_op(collection_name, request)?);
         }
         let Some(collection_pass) = collection_pass else {
-            return Ok(vec![]);
+            return Ok(PartialResponse::new(vec![]));
         };
 
         let collection = self.get_collection(&collection_pass).await?;
+        collection.check_strict_mode(requests.as_slice()).await?;
 
         collection
-            .query_batch(requests, read_consistency, timeout)
+            .query_batch(requests, read_consistency, timeout, hw_counter)
             .await
             .map_err(|err| err.into())
     }
@@ -328,6 +536,7 @@ impl TableOfContent {
         collection: &Collection,
         shard_keys: Vec<ShardKey>,
         operation: CollectionUpdateOperations,
+        idempotency_key: Option<String>,
         wait: bool,
         ordering: WriteOrdering,
     ) -> Result<UpdateResult, StorageError> {
@@ -336,7 +545,13 @@ impl TableOfContent {
         let updates: FuturesUnordered<_> = shard_keys
             .into_iter()
             .map(|shard_key| {
-                collection.update_from_client(operation.clone(), wait, ordering, Some(shard_key))
+                collection.update_from_client(
+                    operation.clone(),
+                    idempotency_key.clone(),
+                    wait,
+                    ordering,
+                    Some(shard_key),
+                )
             })
             .collect();
 
@@ -361,6 +576,7 @@ impl TableOfContent {
         shard_selector: ShardSelectorInternal,
         access: Access,
     ) -> Result<UpdateResult, StorageError> {
+        let access = self.alias_restricted_access(collection_name, access).await;
         let collection_pass = access.check_point_op(collection_name, &mut operation.operation)?;
 
         // `TableOfContent::_update_shard_keys` and `Collection::update_from_*` are cancel safe,
@@ -412,12 +628,24 @@ impl TableOfContent {
             self.check_write_lock()?;
         }
 
+        // Strict mode is only checked on the first node in the chain
+        if !shard_selector.is_shard_id() {
+            collection.check_strict_mode(&operation.operation).await?;
+            collection.check_quotas(&operation.operation).await?;
+        }
+
         // TODO: `debug_assert(operation.clock_tag.is_none())` for `_update_shard_keys`/`update_from_client`!?
 
         let res = match shard_selector {
             ShardSelectorInternal::Empty => {
                 collection
-                    .update_from_client(operation.operation, wait, ordering, None)
+                    .update_from_client(
+                        operation.operation,
+                        operation.idempotency_key,
+                        wait,
+                        ordering,
+                        None,
+                    )
                     .await?
             }
 
@@ -425,13 +653,20 @@ impl TableOfContent {
                 let shard_keys = collection.get_shard_keys().await;
                 if shard_keys.is_empty() {
                     collection
-                        .update_from_client(operation.operation, wait, ordering, None)
+                        .update_from_client(
+                            operation.operation,
+                            operation.idempotency_key,
+                            wait,
+                            ordering,
+                            None,
+                        )
                         .await?
                 } else {
                     Self::_update_shard_keys(
                         &collection,
                         shard_keys,
                         operation.operation,
+                        operation.idempotency_key,
                         wait,
                         ordering,
                     )
@@ -441,7 +676,13 @@ impl TableOfContent {
 
             ShardSelectorInternal::ShardKey(shard_key) => {
                 collection
-                    .update_from_client(operation.operation, wait, ordering, Some(shard_key))
+                    .update_from_client(
+                        operation.operation,
+                        operation.idempotency_key,
+                        wait,
+                        ordering,
+                        Some(shard_key),
+                    )
                     .await?
             }
 
@@ -450,6 +691,7 @@ impl TableOfContent {
                     &collection,
                     shard_keys,
                     operation.operation,
+                    operation.idempotency_key,
                     wait,
                     ordering,
                 )
@@ -3,7 +3,9 @@
 use std::time::Duration;
 
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::PartialResponse;
 use collection::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
+use common::hardware_counter::HardwareCounterCell;
 
 use super::TableOfContent;
 use crate::content_manager::errors::StorageError;
@@ -15,11 +17,12 @@ impl TableOfContent {
         requests: Vec<ShardQueryRequest>,
         shard_selection: ShardSelectorInternal,
         timeout: Option<Duration>,
-    ) -> Result<Vec<ShardQueryResponse>, StorageError> {
+        hw_counter: &HardwareCounterCell,
+    ) -> Result<PartialResponse<Vec<ShardQueryResponse>>, StorageError> {
         let collection = self.get_collection_unchecked(collection_name).await?;
 
         let res = collection
-            .query_batch_internal(requests, &shard_selection, timeout)
+            .query_batch_internal(requests, &shard_selection, timeout, hw_counter)
             .await?;
 
         Ok(res)
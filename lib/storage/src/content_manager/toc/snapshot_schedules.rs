@@ -0,0 +1,106 @@
+use chrono::{DateTime, Utc};
+
+use super::TableOfContent;
+use crate::content_manager::errors::StorageError;
+use crate::content_manager::snapshot_schedules::{
+    SnapshotSchedule, SnapshotScheduleInfo, SnapshotScheduleOperation,
+};
+use crate::rbac::{Access, AccessRequirements};
+
+impl TableOfContent {
+    /// Schedules of the collection, with the status of their runs on this peer
+    pub async fn list_snapshot_schedules(
+        &self,
+        collection_name: &str,
+        access: &Access,
+    ) -> Result<Vec<SnapshotScheduleInfo>, StorageError> {
+        let collection_pass =
+            access.check_collection_access(collection_name, AccessRequirements::new().whole())?;
+        let collection = self.get_collection(&collection_pass).await?;
+        let collection_name = collection.name();
+
+        let schedules = self
+            .snapshot_schedule_persistence
+            .read()
+            .await
+            .list(&collection_name);
+        let statuses = self.snapshot_schedule_statuses.lock();
+        Ok(schedules
+            .into_iter()
+            .map(|(name, schedule)| SnapshotScheduleInfo {
+                status: statuses.get(&collection_name, &name),
+                name,
+                schedule,
+            })
+            .collect())
+    }
+
+    pub(super) async fn apply_snapshot_schedule_operation(
+        &self,
+        mut operation: SnapshotScheduleOperation,
+    ) -> Result<(), StorageError> {
+        // Schedules are stored by the name of the collection, not of its alias
+        let resolved_name = {
+            let collections = self.collections.read().await;
+            let aliases = self.alias_persistence.read().await;
+            Self::resolve_name(operation.collection_name(), &collections, &aliases).await?
+        };
+        match &mut operation {
+            SnapshotScheduleOperation::Set {
+                collection_name, ..
+            }
+            | SnapshotScheduleOperation::Delete {
+                collection_name, ..
+            } => *collection_name = resolved_name,
+        }
+        self.snapshot_schedule_persistence
+            .write()
+            .await
+            .apply(operation)
+    }
+
+    /// Create snapshots of all schedules, which are due at `now`, one after another
+    pub async fn run_due_snapshot_schedules(&self, now: DateTime<Utc>) {
+        let schedules = self.snapshot_schedule_persistence.read().await.all();
+        let due = self.snapshot_schedule_statuses.lock().due(schedules, now);
+
+        for (collection_name, name, schedule) in due {
+            log::info!("Creating scheduled snapshot {name} of collection {collection_name}");
+            let started_at = Utc::now();
+            let result = self
+                .create_scheduled_snapshot(&collection_name, &name, &schedule)
+                .await;
+            if let Err(err) = &result {
+                log::error!(
+                    "Failed to create scheduled snapshot {name} of collection {collection_name}: {err}",
+                );
+            }
+            self.snapshot_schedule_statuses.lock().finish(
+                &collection_name,
+                &name,
+                started_at,
+                result,
+            );
+        }
+    }
+
+    async fn create_scheduled_snapshot(
+        &self,
+        collection_name: &str,
+        name: &str,
+        schedule: &SnapshotSchedule,
+    ) -> Result<String, StorageError> {
+        let collection = self.get_collection_unchecked(collection_name).await?;
+        let temp_dir = self.optional_temp_or_storage_temp_path()?;
+        let snapshot = collection
+            .create_scheduled_snapshot(
+                &temp_dir,
+                self.this_peer_id,
+                name,
+                schedule.storage.into(),
+                schedule.retention,
+            )
+            .await?;
+        Ok(snapshot.name)
+    }
+}
@@ -0,0 +1,31 @@
+use std::collections::HashMap;
+
+use collection::common::sparse_vocabulary::{set_shared_vocabularies, SparseVocabularyStats};
+
+use super::TableOfContent;
+
+impl TableOfContent {
+    /// Collect statistics of the shared sparse vocabularies over all collections of this peer,
+    /// and make them available to the searches of the collections.
+    pub async fn refresh_sparse_vocabularies(&self) {
+        let mut vocabularies: HashMap<String, SparseVocabularyStats> = HashMap::new();
+        let collection_names: Vec<_> = self.collections.read().await.keys().cloned().collect();
+        for collection_name in collection_names {
+            // Collection might be deleted in the meantime
+            let Some(collection) = self.get_collection_opt(collection_name.clone()).await else {
+                continue;
+            };
+            match collection.sparse_vocabulary_stats().await {
+                Ok(stats) => {
+                    for (vocabulary, stats) in stats {
+                        vocabularies.entry(vocabulary).or_default().merge(&stats);
+                    }
+                }
+                Err(err) => log::warn!(
+                    "Failed to collect sparse vocabulary statistics of collection {collection_name}: {err}",
+                ),
+            }
+        }
+        set_shared_vocabularies(vocabularies);
+    }
+}
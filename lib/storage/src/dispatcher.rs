@@ -146,6 +146,18 @@ impl Dispatcher {
                     })
                 }
 
+                // Sync nodes, so API key changes take effect on all of them
+                CollectionMetaOperations::ApiKeys(_) => true,
+
+                // Sync nodes, so changed templates can be read from all of them
+                CollectionMetaOperations::Templates(_) => true,
+
+                // Sync nodes, so changed metadata can be read from all of them
+                CollectionMetaOperations::ClusterMetadata(_) => true,
+
+                // Sync nodes, so all of them run the changed schedules
+                CollectionMetaOperations::SnapshotSchedules(_) => true,
+
                 // TODO(resharding): Do we need/want to synchronize `Resharding` operations?
                 CollectionMetaOperations::Resharding(_, _) => false,
 
@@ -0,0 +1,215 @@
+//! Embedded mode: run collections in-process, without REST and gRPC servers
+//!
+//! [`EmbeddedQdrant`] opens a storage directory the same way the server does, so the storage
+//! created by an application can later be served by a regular Qdrant instance, and vice versa.
+//!
+//! ```ignore
+//! let qdrant = EmbeddedQdrant::open(&storage_config)?;
+//! let runtime = qdrant.runtime();
+//! runtime.block_on(qdrant.create_collection("books", create_collection))?;
+//! runtime.block_on(qdrant.upsert("books", points, true))?;
+//! let hits = runtime.block_on(qdrant.query("books", query))?;
+//! ```
+
+use std::sync::Arc;
+
+use api::rest::schema::QueryRequestInternal;
+use collection::operations::point_ops::{
+    ConditionalInsertOperationInternal, PointInsertOperations, PointOperations, WriteOrdering,
+};
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::snapshot_ops::SnapshotDescription;
+use collection::operations::types::UpdateResult;
+use collection::operations::{CollectionUpdateOperations, OperationWithClockTag};
+use collection::shards::channel_service::ChannelService;
+use common::cpu::{get_cpu_budget, CpuBudget};
+use common::hardware_counter::HardwareCounterCell;
+use segment::types::ScoredPoint;
+use tokio::runtime::{Handle, Runtime};
+
+use crate::content_manager::collection_meta_ops::{
+    CollectionMetaOperations, CreateCollection, CreateCollectionOperation,
+    DeleteCollectionOperation,
+};
+use crate::content_manager::consensus::persistent::Persistent;
+use crate::content_manager::errors::StorageError;
+use crate::content_manager::toc::TableOfContent;
+use crate::dispatcher::Dispatcher;
+use crate::rbac::{Access, AccessRequirements};
+use crate::types::StorageConfig;
+
+const EMBEDDED_ACCESS: Access = Access::full("Embedded mode");
+
+/// Qdrant storage, running in the current process
+///
+/// All operations are executed locally: there is no consensus and no other peers, so the
+/// storage is the same as of a single node server.
+pub struct EmbeddedQdrant {
+    /// Runtime for the calls of the application, storage uses own runtimes internally
+    runtime: Runtime,
+    dispatcher: Dispatcher,
+}
+
+impl EmbeddedQdrant {
+    /// Open the storage at `config.storage_path`, creating it if it doesn't exist.
+    ///
+    /// Runtimes are created and dropped by this struct, so it must be opened and dropped
+    /// outside of async context.
+    pub fn open(config: &StorageConfig) -> Result<Self, StorageError> {
+        let this_peer_id = Persistent::load_or_init(&config.storage_path, true)?.this_peer_id();
+
+        let search_threads = config.performance.max_search_threads;
+        let search_runtime = new_runtime("search", search_threads)?;
+        let update_runtime = new_runtime("update", 0)?;
+        let general_runtime = new_runtime("general", 0)?;
+        let runtime = new_runtime("embedded", 0)?;
+
+        let cpu_budget = CpuBudget::new(get_cpu_budget(config.performance.optimizer_cpu_budget));
+
+        let toc = TableOfContent::new(
+            config,
+            search_runtime,
+            update_runtime,
+            general_runtime,
+            cpu_budget,
+            ChannelService::new(0, None),
+            this_peer_id,
+            None,
+        );
+        toc.clear_all_tmp_directories()?;
+
+        // Load all stored collections
+        runtime.block_on(async {
+            for collection in toc.all_collections(&EMBEDDED_ACCESS).await {
+                log::debug!("Loaded collection: {collection}");
+            }
+        });
+
+        Ok(Self {
+            runtime,
+            dispatcher: Dispatcher::new(Arc::new(toc)),
+        })
+    }
+
+    /// Runtime, to drive the operations of this storage from sync code
+    pub fn runtime(&self) -> &Handle {
+        self.runtime.handle()
+    }
+
+    /// Underlying storage, for the operations not covered by this API
+    pub fn toc(&self) -> &Arc<TableOfContent> {
+        self.dispatcher.toc(&EMBEDDED_ACCESS)
+    }
+
+    pub async fn create_collection(
+        &self,
+        collection_name: &str,
+        create_collection: CreateCollection,
+    ) -> Result<bool, StorageError> {
+        let operation =
+            CreateCollectionOperation::new(collection_name.to_string(), create_collection);
+        self.dispatcher
+            .submit_collection_meta_op(
+                CollectionMetaOperations::CreateCollection(operation),
+                EMBEDDED_ACCESS.clone(),
+                None,
+            )
+            .await
+    }
+
+    pub async fn delete_collection(&self, collection_name: &str) -> Result<bool, StorageError> {
+        self.dispatcher
+            .submit_collection_meta_op(
+                CollectionMetaOperations::DeleteCollection(DeleteCollectionOperation(
+                    collection_name.to_string(),
+                )),
+                EMBEDDED_ACCESS.clone(),
+                None,
+            )
+            .await
+    }
+
+    /// Names of all collections
+    pub async fn list_collections(&self) -> Vec<String> {
+        self.toc()
+            .all_collections(&EMBEDDED_ACCESS)
+            .await
+            .into_iter()
+            .map(|pass| pass.name().to_string())
+            .collect()
+    }
+
+    /// Insert or update points.
+    ///
+    /// If `wait` is false, returns as soon as the operation is written to WAL.
+    pub async fn upsert(
+        &self,
+        collection_name: &str,
+        points: PointInsertOperations,
+        wait: bool,
+    ) -> Result<UpdateResult, StorageError> {
+        let (shard_key, operation, precondition) = points.decompose();
+        let point_operation = match precondition.filter(|precondition| !precondition.is_empty()) {
+            Some(precondition) => {
+                PointOperations::UpsertPointsConditional(ConditionalInsertOperationInternal {
+                    points_op: operation,
+                    precondition,
+                })
+            }
+            None => PointOperations::UpsertPoints(operation),
+        };
+        let operation = CollectionUpdateOperations::PointOperation(point_operation);
+
+        self.toc()
+            .update(
+                collection_name,
+                OperationWithClockTag::new(operation, None),
+                wait,
+                WriteOrdering::default(),
+                ShardSelectorInternal::from(shard_key),
+                EMBEDDED_ACCESS.clone(),
+            )
+            .await
+    }
+
+    /// Universal query, same as `POST /collections/{collection_name}/points/query`
+    pub async fn query(
+        &self,
+        collection_name: &str,
+        request: QueryRequestInternal,
+    ) -> Result<Vec<ScoredPoint>, StorageError> {
+        let mut results = self
+            .toc()
+            .query_batch(
+                collection_name,
+                vec![(request.into(), ShardSelectorInternal::All)],
+                None,
+                EMBEDDED_ACCESS.clone(),
+                None,
+                &HardwareCounterCell::new(),
+            )
+            .await?;
+        Ok(results.value.pop().unwrap_or_default())
+    }
+
+    /// Create a snapshot of the collection in `config.snapshots_path`.
+    ///
+    /// Snapshots are compatible with the server and can be recovered by it.
+    pub async fn create_snapshot(
+        &self,
+        collection_name: &str,
+    ) -> Result<SnapshotDescription, StorageError> {
+        let pass = EMBEDDED_ACCESS
+            .check_collection_access(collection_name, AccessRequirements::new().write().whole())?;
+        self.toc().create_snapshot(&pass).await
+    }
+}
+
+fn new_runtime(name: &'static str, threads: usize) -> Result<Runtime, StorageError> {
+    let mut builder = tokio::runtime::Builder::new_multi_thread();
+    builder.enable_all().thread_name(format!("embedded-{name}"));
+    if threads > 0 {
+        builder.worker_threads(threads);
+    }
+    Ok(builder.build()?)
+}
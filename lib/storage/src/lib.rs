@@ -12,6 +12,8 @@ use types::ClusterStatus;
 
 pub mod content_manager;
 pub mod dispatcher;
+#[cfg(feature = "embedded")]
+pub mod embedded;
 pub mod issues_subscribers;
 pub mod rbac;
 pub mod types;
@@ -2,7 +2,7 @@ use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 
 use segment::json_path::JsonPath;
-use segment::types::ValueVariants;
+use segment::types::{Condition, Filter, ValueVariants};
 use serde::{Deserialize, Serialize};
 use validator::{Validate, ValidateArgs, ValidationError, ValidationErrors};
 
@@ -38,6 +38,12 @@ pub struct CollectionAccess {
     /// An object where each key is a JSON path, and each value is JSON value.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub payload: Option<PayloadConstraint>,
+
+    /// Filter constraint.
+    /// Merged into every read request of the collection, so points not matching it are never
+    /// visible. Like `payload`, restricts writes to deletes of matching points.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<Filter>,
 }
 
 #[derive(Serialize, Deserialize, Eq, PartialEq, Copy, Clone, Debug)]
@@ -106,6 +112,43 @@ impl Access {
     }
 }
 
+impl Access {
+    /// Restrict the access to `collection_name` by the filter, like a filter constraint does.
+    /// Access to other collections is not changed, except for global access, which becomes
+    /// limited to `collection_name`.
+    pub fn restrict_by_filter(self, collection_name: &str, filter: Filter) -> Self {
+        // Nest the filter, so its `should` conditions are not merged with ones of the request
+        let filter = Filter::new_must(Condition::Filter(filter));
+        match self {
+            Access::Global(mode) => {
+                let access = match mode {
+                    GlobalAccessMode::Read => CollectionAccessMode::Read,
+                    GlobalAccessMode::Manage => CollectionAccessMode::ReadWrite,
+                };
+                Access::Collection(CollectionAccessList(vec![CollectionAccess {
+                    collection: collection_name.to_string(),
+                    access,
+                    payload: None,
+                    filter: Some(filter),
+                }]))
+            }
+            Access::Collection(mut list) => {
+                for access in list
+                    .0
+                    .iter_mut()
+                    .filter(|access| access.collection == collection_name)
+                {
+                    access.filter = Some(match access.filter.take() {
+                        Some(existing) => existing.merge_owned(filter.clone()),
+                        None => filter.clone(),
+                    });
+                }
+                Access::Collection(list)
+            }
+        }
+    }
+}
+
 impl CollectionAccessList {
     pub(self) fn find_view<'a>(
         &'a self,
@@ -124,6 +167,7 @@ impl CollectionAccessList {
             collection: collection_name,
             access: access.access,
             payload: &access.payload,
+            filter: &access.filter,
         })
     }
 }
@@ -133,6 +177,7 @@ struct CollectionAccessView<'a> {
     pub collection: &'a str,
     pub access: CollectionAccessMode,
     pub payload: &'a Option<PayloadConstraint>,
+    pub filter: &'a Option<Filter>,
 }
 
 impl<'a> CollectionAccessView<'a> {
@@ -140,6 +185,9 @@ impl<'a> CollectionAccessView<'a> {
         if self.payload.is_some() {
             return incompatible_with_payload_constraint(self.collection);
         }
+        if self.filter.is_some() {
+            return incompatible_with_filter_constraint(self.collection);
+        }
         Ok(())
     }
 
@@ -167,8 +215,8 @@ impl<'a> CollectionAccessView<'a> {
                 "Manage access for this operation is required",
             ));
         }
-        if whole && self.payload.is_some() {
-            return incompatible_with_payload_constraint(self.collection);
+        if whole {
+            self.check_whole_access()?;
         }
         Ok(())
     }
@@ -268,6 +316,15 @@ fn incompatible_with_payload_constraint<T>(collection_name: &str) -> Result<T, S
     )))
 }
 
+/// Helper function to indicate that the operation is not allowed when `filter` constraint is
+/// present.
+fn incompatible_with_filter_constraint<T>(collection_name: &str) -> Result<T, StorageError> {
+    Err(StorageError::forbidden(format!(
+        "This operation is not allowed when \"filter\" restriction is present for collection \
+         {collection_name}"
+    )))
+}
+
 impl Access {
     /// Return a list of validation errors in a format suitable for [ValidationErrors::merge_all].
     pub fn validate(&self) -> Vec<Result<(), ValidationErrors>> {
@@ -324,6 +381,7 @@ impl AccessCollectionBuilder {
                 CollectionAccessMode::Read
             },
             payload: (!whole).then(|| PayloadConstraint::new_test(name)),
+            filter: None,
         });
         self
     }
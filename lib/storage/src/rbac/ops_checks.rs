@@ -5,10 +5,14 @@ use std::mem::take;
 use api::rest::LookupLocation;
 use collection::grouping::group_by::{GroupRequest, SourceRequest};
 use collection::lookup::WithLookup;
-use collection::operations::payload_ops::{DeletePayloadOp, PayloadOps, SetPayloadOp};
+use collection::operations::attribute_ops::AttributeOps;
+use collection::operations::payload_ops::{
+    ConditionalSetPayloadOp, DeletePayloadOp, PayloadOps, SetPayloadOp,
+};
 use collection::operations::point_ops::{PointIdsList, PointOperations};
 use collection::operations::types::{
-    ContextExamplePair, CoreSearchRequest, CountRequestInternal, DiscoverRequestInternal,
+    ContextExamplePair, CoreSearchRequest, CountDistinctRequestInternal, CountRequestInternal,
+    DiscoverRequestInternal, FieldStatsRequestInternal, HistogramRequestInternal,
     PointRequestInternal, RecommendExample, RecommendRequestInternal, ScrollRequestInternal,
 };
 use collection::operations::universal_query::collection_query::{
@@ -19,8 +23,9 @@ use collection::operations::CollectionUpdateOperations;
 use segment::types::{Condition, ExtendedPointId, FieldCondition, Filter, Match, Payload};
 
 use super::{
-    incompatible_with_payload_constraint, Access, AccessRequirements, CollectionAccessList,
-    CollectionAccessView, CollectionPass, PayloadConstraint,
+    incompatible_with_filter_constraint, incompatible_with_payload_constraint, Access,
+    AccessRequirements, CollectionAccessList, CollectionAccessView, CollectionPass,
+    PayloadConstraint,
 };
 use crate::content_manager::collection_meta_ops::CollectionMetaOperations;
 use crate::content_manager::errors::StorageError;
@@ -57,7 +62,10 @@ impl Access {
             | CollectionMetaOperations::TransferShard(_, _)
             | CollectionMetaOperations::SetShardReplicaState(_)
             | CollectionMetaOperations::CreateShardKey(_)
-            | CollectionMetaOperations::DropShardKey(_) => {
+            | CollectionMetaOperations::DropShardKey(_)
+            | CollectionMetaOperations::ApiKeys(_)
+            | CollectionMetaOperations::Templates(_)
+            | CollectionMetaOperations::ClusterMetadata(_) => {
                 self.check_global_access(AccessRequirements::new().manage())?;
             }
             CollectionMetaOperations::CreatePayloadIndex(op) => {
@@ -72,6 +80,12 @@ impl Access {
                     AccessRequirements::new().write().whole(),
                 )?;
             }
+            CollectionMetaOperations::SnapshotSchedules(op) => {
+                self.check_collection_access(
+                    op.collection_name(),
+                    AccessRequirements::new().write().whole(),
+                )?;
+            }
             CollectionMetaOperations::Nop { token: _ } => (),
         }
         Ok(())
@@ -111,10 +125,19 @@ impl CollectionAccessList {
 }
 
 impl<'a> CollectionAccessView<'a> {
+    /// Filter, matching only points accessible by `payload` and `filter` constraints
+    fn restriction_filter(&self) -> Option<Filter> {
+        let payload_filter = self.payload.as_ref().map(PayloadConstraint::to_filter);
+        match (payload_filter, self.filter.clone()) {
+            (Some(payload_filter), Some(filter)) => Some(payload_filter.merge_owned(filter)),
+            (payload_filter, filter) => payload_filter.or(filter),
+        }
+    }
+
     fn apply_filter(&self, filter: &mut Option<Filter>) {
-        if let Some(payload) = &self.payload {
+        if let Some(restriction) = self.restriction_filter() {
             let f = filter.get_or_insert_with(Default::default);
-            *f = take(f).merge_owned(payload.to_filter());
+            *f = take(f).merge_owned(restriction);
         }
     }
 
@@ -241,6 +264,63 @@ impl CheckableCollectionOperation for CountRequestInternal {
     }
 }
 
+impl CheckableCollectionOperation for CountDistinctRequestInternal {
+    fn access_requirements(&self) -> AccessRequirements {
+        AccessRequirements {
+            write: false,
+            manage: false,
+            whole: false,
+        }
+    }
+
+    fn check_access(
+        &mut self,
+        view: CollectionAccessView<'_>,
+        _access: &CollectionAccessList,
+    ) -> Result<(), StorageError> {
+        view.apply_filter(&mut self.filter);
+        Ok(())
+    }
+}
+
+impl CheckableCollectionOperation for FieldStatsRequestInternal {
+    fn access_requirements(&self) -> AccessRequirements {
+        AccessRequirements {
+            write: false,
+            manage: false,
+            whole: false,
+        }
+    }
+
+    fn check_access(
+        &mut self,
+        view: CollectionAccessView<'_>,
+        _access: &CollectionAccessList,
+    ) -> Result<(), StorageError> {
+        view.apply_filter(&mut self.filter);
+        Ok(())
+    }
+}
+
+impl CheckableCollectionOperation for HistogramRequestInternal {
+    fn access_requirements(&self) -> AccessRequirements {
+        AccessRequirements {
+            write: false,
+            manage: false,
+            // Histogram covers all points, it can't be restricted by a filter
+            whole: true,
+        }
+    }
+
+    fn check_access(
+        &mut self,
+        _view: CollectionAccessView<'_>,
+        _access: &CollectionAccessList,
+    ) -> Result<(), StorageError> {
+        Ok(())
+    }
+}
+
 impl CheckableCollectionOperation for GroupRequest {
     fn access_requirements(&self) -> AccessRequirements {
         AccessRequirements {
@@ -369,7 +449,8 @@ impl CheckableCollectionOperation for CollectionUpdateOperations {
         match self {
             CollectionUpdateOperations::PointOperation(_)
             | CollectionUpdateOperations::VectorOperation(_)
-            | CollectionUpdateOperations::PayloadOperation(_) => AccessRequirements {
+            | CollectionUpdateOperations::PayloadOperation(_)
+            | CollectionUpdateOperations::AttributeOperation(_) => AccessRequirements {
                 write: true,
                 manage: false,
                 whole: false, // Checked in `check_access()`
@@ -389,19 +470,25 @@ impl CheckableCollectionOperation for CollectionUpdateOperations {
     ) -> Result<(), StorageError> {
         match self {
             CollectionUpdateOperations::PointOperation(op) => match op {
-                PointOperations::UpsertPoints(_) => {
+                PointOperations::UpsertPoints(_) | PointOperations::UpsertPointsConditional(_) => {
                     view.check_whole_access()?;
                 }
                 PointOperations::DeletePoints { ids } => {
-                    if let Some(payload) = &view.payload {
+                    if let Some(restriction) = view.restriction_filter() {
                         *op = PointOperations::DeletePointsByFilter(
-                            make_filter_from_ids(take(ids)).merge_owned(payload.to_filter()),
+                            make_filter_from_ids(take(ids)).merge_owned(restriction),
                         );
                     }
                 }
                 PointOperations::DeletePointsByFilter(filter) => {
-                    if let Some(payload) = &view.payload {
-                        *filter = take(filter).merge_owned(payload.to_filter());
+                    if let Some(restriction) = view.restriction_filter() {
+                        *filter = take(filter).merge_owned(restriction);
+                    }
+                }
+                PointOperations::DeletePointsConditional(conditional_delete) => {
+                    if let Some(restriction) = view.restriction_filter() {
+                        let filter = &mut conditional_delete.precondition.filter;
+                        *filter = Some(filter.take().unwrap_or_default().merge_owned(restriction));
                     }
                 }
                 PointOperations::SyncPoints(_) => {
@@ -413,26 +500,35 @@ impl CheckableCollectionOperation for CollectionUpdateOperations {
                 VectorOperations::UpdateVectors(_) => {
                     view.check_whole_access()?;
                 }
-                VectorOperations::DeleteVectors(PointIdsList { points, shard_key }, vectors) => {
-                    if let Some(payload) = &view.payload {
+                VectorOperations::DeleteVectors(
+                    PointIdsList {
+                        points, shard_key, ..
+                    },
+                    vectors,
+                ) => {
+                    if let Some(restriction) = view.restriction_filter() {
                         if shard_key.is_some() {
                             // It is unclear where to put the shard_key
-                            return incompatible_with_payload_constraint(view.collection);
+                            return view.check_whole_access();
                         }
                         *op = VectorOperations::DeleteVectorsByFilter(
-                            make_filter_from_ids(take(points)).merge_owned(payload.to_filter()),
+                            make_filter_from_ids(take(points)).merge_owned(restriction),
                             take(vectors),
                         );
                     }
                 }
                 VectorOperations::DeleteVectorsByFilter(filter, _) => {
-                    if let Some(payload) = &view.payload {
-                        *filter = take(filter).merge_owned(payload.to_filter());
+                    if let Some(restriction) = view.restriction_filter() {
+                        *filter = take(filter).merge_owned(restriction);
                     }
                 }
             },
 
             CollectionUpdateOperations::PayloadOperation(op) => 'a: {
+                if view.filter.is_some() {
+                    // Payload changes could move points out of the filter
+                    return incompatible_with_filter_constraint(view.collection);
+                }
                 let Some(payload) = &view.payload else {
                     // Allow all operations when there is no payload constraint
                     break 'a;
@@ -444,6 +540,16 @@ impl CheckableCollectionOperation for CollectionUpdateOperations {
                         points,
                         filter,
                         key: _, // TODO: validate
+                    })
+                    | PayloadOps::SetPayloadConditional(ConditionalSetPayloadOp {
+                        payload_op:
+                            SetPayloadOp {
+                                payload: _, // TODO: validate
+                                points,
+                                filter,
+                                key: _, // TODO: validate
+                            },
+                        precondition: _,
                     }) => {
                         let filter = filter.get_or_insert_with(Default::default);
                         if let Some(points) = take(points) {
@@ -489,6 +595,12 @@ impl CheckableCollectionOperation for CollectionUpdateOperations {
                         points,
                         filter,
                         key: _, // TODO: validate
+                    })
+                    | PayloadOps::PatchPayload(SetPayloadOp {
+                        payload: _, // TODO: validate
+                        points,
+                        filter,
+                        key: _, // TODO: validate
                     }) => {
                         let filter = filter.get_or_insert_with(Default::default);
                         if let Some(points) = take(points) {
@@ -502,6 +614,13 @@ impl CheckableCollectionOperation for CollectionUpdateOperations {
             }
 
             CollectionUpdateOperations::FieldIndexOperation(_) => (),
+
+            CollectionUpdateOperations::AttributeOperation(op) => match op {
+                AttributeOps::SetAttributes(_) => {
+                    // Attribute changes could move points out of the filter
+                    view.check_whole_access()?;
+                }
+            },
         }
         Ok(())
     }
@@ -560,6 +679,7 @@ mod tests {
                 "field".parse().unwrap(),
                 ValueVariants::Integer(42),
             )]))),
+            filter: None,
         }]);
 
         let mut filter = None;
@@ -606,23 +726,98 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn test_apply_filter_constraint() {
+        let tenant = |value: &str| {
+            Condition::Field(FieldCondition::new_match(
+                "tenant".parse().unwrap(),
+                Match::new_value(ValueVariants::Keyword(value.to_string())),
+            ))
+        };
+        let list = CollectionAccessList(vec![CollectionAccess {
+            collection: "col".to_string(),
+            access: CollectionAccessMode::ReadWrite,
+            payload: None,
+            filter: Some(Filter::new_must(tenant("a"))),
+        }]);
+        let view = list.find_view("col").unwrap();
+
+        // Requested filter can't widen the constraint
+        let mut filter = Some(Filter {
+            should: Some(vec![tenant("b")]),
+            ..Default::default()
+        });
+        view.apply_filter(&mut filter);
+        assert_eq!(
+            filter,
+            Some(Filter {
+                should: Some(vec![tenant("b")]),
+                must: Some(vec![tenant("a")]),
+                ..Default::default()
+            })
+        );
+
+        // Operations on explicit points are not allowed
+        assert!(view
+            .meets_requirements(AccessRequirements::new().whole())
+            .is_err());
+    }
+
+    #[test]
+    fn test_restrict_by_filter() {
+        let tenant = |value: &str| {
+            Condition::Field(FieldCondition::new_match(
+                "tenant".parse().unwrap(),
+                Match::new_value(ValueVariants::Keyword(value.to_string())),
+            ))
+        };
+
+        let access = Access::full("Test").restrict_by_filter("col", Filter::new_must(tenant("a")));
+        let Access::Collection(list) = &access else {
+            panic!("global access is not restricted");
+        };
+        let view = list.find_view("col").unwrap();
+        assert_eq!(view.access, CollectionAccessMode::ReadWrite);
+        let nested = |value: &str| Condition::Filter(Filter::new_must(tenant(value)));
+        assert_eq!(
+            view.restriction_filter(),
+            Some(Filter::new_must(nested("a")))
+        );
+
+        // Filter of the alias is merged with the filter constraint of the access
+        let access = access.restrict_by_filter("col", Filter::new_must(tenant("b")));
+        let Access::Collection(list) = &access else {
+            panic!("access is not restricted");
+        };
+        assert_eq!(
+            list.find_view("col").unwrap().restriction_filter(),
+            Some(Filter {
+                must: Some(vec![nested("a"), nested("b")]),
+                ..Default::default()
+            })
+        );
+    }
 }
 
 #[cfg(test)]
 mod tests_ops {
+    use std::collections::HashMap;
     use std::fmt::Debug;
 
     use api::rest::{
         BatchVectorStruct, LookupLocation, OrderByInterface, RecommendStrategy,
         SearchRequestInternal, VectorStruct,
     };
+    use collection::operations::attribute_ops::{AttributeOpsDiscriminants, SetAttributesOp};
     use collection::operations::payload_ops::PayloadOpsDiscriminants;
     use collection::operations::point_ops::{
-        Batch, PointInsertOperationsInternal, PointInsertOperationsInternalDiscriminants,
-        PointOperationsDiscriminants, PointStruct, PointSyncOperation,
+        Batch, ConditionalDeleteOperationInternal, ConditionalInsertOperationInternal,
+        PointInsertOperationsInternal, PointInsertOperationsInternalDiscriminants,
+        PointOperationsDiscriminants, PointStruct, PointSyncOperation, WritePrecondition,
     };
     use collection::operations::query_enum::QueryEnum;
-    use collection::operations::types::UsingVector;
+    use collection::operations::types::{HistogramBuckets, UsingVector};
     use collection::operations::vector_ops::{
         PointVectors, UpdateVectorsOp, VectorOperationsDiscriminants,
     };
@@ -863,6 +1058,85 @@ mod tests_ops {
         );
     }
 
+    #[test]
+    fn test_count_distinct_request_internal() {
+        let op = CountDistinctRequestInternal {
+            key: "tag".parse().unwrap(),
+            filter: None,
+        };
+
+        assert_allowed(&op, &Access::Global(GlobalAccessMode::Read));
+
+        assert_allowed(
+            &op,
+            &AccessCollectionBuilder::new()
+                .add("col", false, true)
+                .into(),
+        );
+
+        assert_allowed_rewrite(
+            &op,
+            &AccessCollectionBuilder::new()
+                .add("col", false, false)
+                .into(),
+            |op| {
+                op.filter = Some(PayloadConstraint::new_test("col").to_filter());
+            },
+        );
+    }
+
+    #[test]
+    fn test_field_stats_request_internal() {
+        let op = FieldStatsRequestInternal {
+            key: "price".parse().unwrap(),
+            filter: None,
+            percentiles: vec![50.0],
+        };
+
+        assert_allowed(&op, &Access::Global(GlobalAccessMode::Read));
+
+        assert_allowed(
+            &op,
+            &AccessCollectionBuilder::new()
+                .add("col", false, true)
+                .into(),
+        );
+
+        assert_allowed_rewrite(
+            &op,
+            &AccessCollectionBuilder::new()
+                .add("col", false, false)
+                .into(),
+            |op| {
+                op.filter = Some(PayloadConstraint::new_test("col").to_filter());
+            },
+        );
+    }
+
+    #[test]
+    fn test_histogram_request_internal() {
+        let op = HistogramRequestInternal {
+            key: "price".parse().unwrap(),
+            buckets: HistogramBuckets::Quantiles(10),
+        };
+
+        assert_allowed(&op, &Access::Global(GlobalAccessMode::Read));
+
+        assert_allowed(
+            &op,
+            &AccessCollectionBuilder::new()
+                .add("col", false, true)
+                .into(),
+        );
+
+        assert_forbidden(
+            &op,
+            &AccessCollectionBuilder::new()
+                .add("col", false, false)
+                .into(),
+        );
+    }
+
     #[test]
     fn test_group_request_source() {
         let op = GroupRequest {
@@ -1047,6 +1321,8 @@ mod tests_ops {
             with_payload: Some(WithPayloadInterface::Bool(true)),
             with_vector: WithVector::Bool(true),
             order_by: Some(OrderByInterface::Key("path".parse().unwrap())),
+            cursor: None,
+            include_deleted: None,
         };
 
         assert_allowed(&op, &Access::Global(GlobalAccessMode::Manage));
@@ -1085,6 +1361,9 @@ mod tests_ops {
             CollectionUpdateOperationsDiscriminants::FieldIndexOperation => {
                 check_collection_update_operations_field_index()
             }
+            CollectionUpdateOperationsDiscriminants::AttributeOperation => {
+                check_collection_update_operations_attributes()
+            }
         });
     }
 
@@ -1117,6 +1396,23 @@ mod tests_ops {
                 }
             }
 
+            PointOperationsDiscriminants::UpsertPointsConditional => {
+                let op = CollectionUpdateOperations::PointOperation(
+                    PointOperations::UpsertPointsConditional(ConditionalInsertOperationInternal {
+                        points_op: PointInsertOperationsInternal::PointsList(vec![PointStruct {
+                            id: ExtendedPointId::NumId(12345),
+                            vector: VectorStruct::Single(vec![0.0, 1.0, 2.0]),
+                            payload: None,
+                        }]),
+                        precondition: WritePrecondition {
+                            version: Some(1),
+                            filter: None,
+                        },
+                    }),
+                );
+                assert_requires_whole_write_access(&op);
+            }
+
             PointOperationsDiscriminants::DeletePoints => {
                 let op =
                     CollectionUpdateOperations::PointOperation(PointOperations::DeletePoints {
@@ -1125,6 +1421,42 @@ mod tests_ops {
                 check_collection_update_operations_delete_points(&op);
             }
 
+            PointOperationsDiscriminants::DeletePointsConditional => {
+                let make_op = |filter| {
+                    CollectionUpdateOperations::PointOperation(
+                        PointOperations::DeletePointsConditional(
+                            ConditionalDeleteOperationInternal {
+                                ids: vec![ExtendedPointId::NumId(12345)],
+                                precondition: WritePrecondition {
+                                    version: Some(1),
+                                    filter,
+                                },
+                            },
+                        ),
+                    )
+                };
+                let op = make_op(None);
+
+                assert_allowed(&op, &Access::Global(GlobalAccessMode::Manage));
+                assert_forbidden(&op, &Access::Global(GlobalAccessMode::Read));
+                assert_allowed(
+                    &op,
+                    &AccessCollectionBuilder::new().add("col", true, true).into(),
+                );
+                assert_allowed_rewrite(
+                    &op,
+                    &AccessCollectionBuilder::new()
+                        .add("col", true, false)
+                        .into(),
+                    |op| {
+                        *op = make_op(Some(
+                            Filter::default()
+                                .merge_owned(PayloadConstraint::new_test("col").to_filter()),
+                        ));
+                    },
+                );
+            }
+
             PointOperationsDiscriminants::DeletePointsByFilter => {
                 let op = CollectionUpdateOperations::PointOperation(
                     PointOperations::DeletePointsByFilter(make_filter_from_ids(vec![
@@ -1200,6 +1532,7 @@ mod tests_ops {
                         PointIdsList {
                             points: vec![ExtendedPointId::NumId(12345)],
                             shard_key: None,
+                            precondition: None,
                         },
                         vec!["vector".to_string()],
                     ));
@@ -1261,6 +1594,20 @@ mod tests_ops {
                     filter: None,
                     key: None,
                 }),
+                PayloadOpsDiscriminants::SetPayloadConditional => {
+                    PayloadOps::SetPayloadConditional(ConditionalSetPayloadOp {
+                        payload_op: SetPayloadOp {
+                            payload: Payload::default(),
+                            points: Some(vec![ExtendedPointId::NumId(12345)]),
+                            filter: None,
+                            key: None,
+                        },
+                        precondition: WritePrecondition {
+                            version: Some(1),
+                            filter: None,
+                        },
+                    })
+                }
                 PayloadOpsDiscriminants::DeletePayload => {
                     PayloadOps::DeletePayload(DeletePayloadOp {
                         keys: vec!["path".parse().unwrap()],
@@ -1284,6 +1631,12 @@ mod tests_ops {
                         key: None,
                     })
                 }
+                PayloadOpsDiscriminants::PatchPayload => PayloadOps::PatchPayload(SetPayloadOp {
+                    payload: Payload::default(),
+                    points: Some(vec![ExtendedPointId::NumId(12345)]),
+                    filter: None,
+                    key: None,
+                }),
             };
 
             let op = CollectionUpdateOperations::PayloadOperation(inner);
@@ -1291,6 +1644,24 @@ mod tests_ops {
         }
     }
 
+    /// Tests for [`CollectionUpdateOperations::AttributeOperation`].
+    fn check_collection_update_operations_attributes() {
+        for discr in AttributeOpsDiscriminants::iter() {
+            let inner = match discr {
+                AttributeOpsDiscriminants::SetAttributes => {
+                    AttributeOps::SetAttributes(SetAttributesOp {
+                        attributes: HashMap::new(),
+                        points: Some(vec![ExtendedPointId::NumId(12345)]),
+                        filter: None,
+                    })
+                }
+            };
+
+            let op = CollectionUpdateOperations::AttributeOperation(inner);
+            assert_requires_whole_write_access(&op);
+        }
+    }
+
     /// Tests for [`CollectionUpdateOperations::FieldIndexOperation`].
     fn check_collection_update_operations_field_index() {
         for discr in FieldIndexOperationsDiscriminants::iter() {
@@ -3,7 +3,14 @@ use std::num::NonZeroUsize;
 use std::time::Duration;
 
 use chrono::{DateTime, Utc};
+use collection::common::consistency_check::ConsistencyCheckConfig;
+use collection::common::idempotency::IdempotencyConfig;
+use collection::common::index_advisor::IndexAdvisorConfig;
+use collection::common::recall_evaluation::RecallEvaluationConfig;
+use collection::common::slow_query_log::SlowQueryLogConfig;
 use collection::common::snapshots_manager::SnapShotsConfig;
+use collection::common::update_backpressure::UpdateBackpressureConfig;
+use collection::common::warmup::WarmupConfig;
 use collection::config::WalConfig;
 use collection::operations::config_diff::OptimizersConfigDiff;
 use collection::operations::shared_storage_config::{
@@ -13,6 +20,7 @@ use collection::operations::types::{NodeType, PeerMetadata};
 use collection::optimizers_builder::OptimizersConfig;
 use collection::shards::shard::PeerId;
 use collection::shards::transfer::ShardTransferMethod;
+use common::scheduling::WorkloadPriority;
 use memory::madvise;
 use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
@@ -39,6 +47,27 @@ pub struct PerformanceConfig {
     /// If positive - use this absolute number of CPUs.
     #[serde(default)]
     pub optimizer_cpu_budget: isize,
+    /// Priority of threads, serving search requests.
+    #[serde(default)]
+    pub search_thread_priority: WorkloadPriority,
+    /// Priority of threads, running optimizations and building indices.
+    #[serde(default = "default_optimizer_thread_priority")]
+    pub optimizer_thread_priority: WorkloadPriority,
+    /// Priority of threads, applying updates.
+    #[serde(default)]
+    pub update_thread_priority: WorkloadPriority,
+    /// Max disk throughput of segment rebuilds by optimizers, in megabytes per second.
+    /// Unlimited if not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub optimizer_io_limit_mb_per_sec: Option<usize>,
+    /// Max disk throughput of writing snapshot archives, in megabytes per second.
+    /// Unlimited if not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snapshot_io_limit_mb_per_sec: Option<usize>,
+    /// Max throughput of reading points for outgoing shard transfers, in megabytes per second.
+    /// Unlimited if not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_transfer_io_limit_mb_per_sec: Option<usize>,
     #[serde(default = "default_io_shard_transfers_limit")]
     pub incoming_shard_transfers_limit: Option<usize>,
     #[serde(default = "default_io_shard_transfers_limit")]
@@ -49,6 +78,10 @@ const fn default_io_shard_transfers_limit() -> Option<usize> {
     DEFAULT_IO_SHARD_TRANSFER_LIMIT
 }
 
+const fn default_optimizer_thread_priority() -> WorkloadPriority {
+    WorkloadPriority::Low
+}
+
 /// Global configuration of the storage, loaded on the service launch, default stored in ./config
 #[derive(Clone, Debug, Deserialize, Validate)]
 pub struct StorageConfig {
@@ -76,12 +109,22 @@ pub struct StorageConfig {
     pub hnsw_index: HnswConfig,
     #[serde(default = "default_mmap_advice")]
     pub mmap_advice: madvise::Advice,
+    /// Budget of RAM in megabytes for in-RAM indexes, quantized vectors, caches and search
+    /// scratch space. Data, not fitting into the budget, is demoted to memmap. Unlimited if not set.
+    #[serde(default)]
+    pub memory_budget_mb: Option<usize>,
     #[serde(default)]
     pub node_type: NodeType,
     #[serde(default)]
     pub update_queue_size: Option<usize>,
     #[serde(default)]
     pub handle_collection_load_errors: bool,
+    /// Move segments, which fail to load, out of the shard instead of failing the shard load.
+    /// The replica is then marked as dead, so that it is recovered from healthy replicas.
+    /// Without a healthy replica, it serves the remaining points, and reports the quarantined
+    /// segments in the cluster info.
+    #[serde(default)]
+    pub quarantine_corrupted_segments: bool,
     #[serde(default)]
     pub async_scorer: bool,
     /// If provided - qdrant will start in recovery mode, which means that it will not accept any new data.
@@ -97,6 +140,31 @@ pub struct StorageConfig {
     /// Default values for collections.
     #[serde(default)]
     pub collection: Option<CollectionConfigDefaults>,
+    /// Log requests, which are slower than the configured threshold.
+    #[serde(default)]
+    pub slow_query_log: SlowQueryLogConfig,
+    /// Track unindexed fields used in filters, recommend and optionally create indexes for them.
+    #[serde(default)]
+    pub index_advisor: IndexAdvisorConfig,
+    /// Read memory mapped files of collections in the background after load.
+    #[serde(default)]
+    pub warmup: WarmupConfig,
+    /// Reject writes to a shard, when free space of its disk falls below this number of megabytes.
+    /// Deletes and snapshots are still allowed. At least twice the WAL capacity is always kept.
+    #[serde(default)]
+    pub min_free_disk_space_mb: Option<usize>,
+    /// Reject updates, which can't be queued in time, when the update queue of a shard is full.
+    #[serde(default)]
+    pub update_backpressure: UpdateBackpressureConfig,
+    /// Deduplicate retried updates, which carry the same idempotency key.
+    #[serde(default)]
+    pub idempotency: IdempotencyConfig,
+    /// Compare replicas of shards in the background, and optionally re-sync divergent points.
+    #[serde(default)]
+    pub consistency_check: ConsistencyCheckConfig,
+    /// Compare the approximate search with exact search in the background.
+    #[serde(default)]
+    pub recall_evaluation: RecallEvaluationConfig,
 }
 
 impl StorageConfig {
@@ -105,6 +173,7 @@ impl StorageConfig {
             self.update_queue_size,
             self.node_type,
             self.handle_collection_load_errors,
+            self.quarantine_corrupted_segments,
             self.recovery_mode.clone(),
             self.performance
                 .search_timeout_sec
@@ -116,6 +185,12 @@ impl StorageConfig {
             self.performance.outgoing_shard_transfers_limit,
             self.snapshots_path.clone(),
             self.snapshots_config.clone(),
+            self.slow_query_log.clone(),
+            self.index_advisor.clone(),
+            self.warmup.clone(),
+            self.min_free_disk_space_mb,
+            self.update_backpressure.clone(),
+            self.idempotency.clone(),
         )
     }
 }
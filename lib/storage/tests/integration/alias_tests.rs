@@ -45,6 +45,7 @@ fn test_alias_operation() {
             indexing_threshold: Some(100),
             flush_interval_sec: 2,
             max_optimization_threads: Some(2),
+            defer_indexing: false,
         },
         optimizers_overwrite: None,
         wal: Default::default(),
@@ -52,6 +53,12 @@ fn test_alias_operation() {
             max_search_threads: 1,
             max_optimization_threads: 1,
             optimizer_cpu_budget: 0,
+            search_thread_priority: Default::default(),
+            optimizer_thread_priority: Default::default(),
+            update_thread_priority: Default::default(),
+            optimizer_io_limit_mb_per_sec: None,
+            snapshot_io_limit_mb_per_sec: None,
+            shard_transfer_io_limit_mb_per_sec: None,
             update_rate_limit: None,
             search_timeout_sec: None,
             incoming_shard_transfers_limit: Some(1),
@@ -59,15 +66,23 @@ fn test_alias_operation() {
         },
         hnsw_index: Default::default(),
         mmap_advice: madvise::Advice::Random,
+        memory_budget_mb: None,
         node_type: Default::default(),
         update_queue_size: Default::default(),
         handle_collection_load_errors: false,
+        quarantine_corrupted_segments: false,
         recovery_mode: None,
         async_scorer: false,
         update_concurrency: Some(NonZeroUsize::new(2).unwrap()),
         // update_concurrency: None,
         shard_transfer_method: None,
         collection: None,
+        slow_query_log: Default::default(),
+        index_advisor: Default::default(),
+        warmup: Default::default(),
+        min_free_disk_space_mb: None,
+        update_backpressure: Default::default(),
+        idempotency: Default::default(),
     };
 
     let search_runtime = Runtime::new().unwrap();
@@ -107,11 +122,20 @@ fn test_alias_operation() {
                         optimizers_config: None,
                         shard_number: Some(1),
                         on_disk_payload: None,
+                        gridstore_payload: None,
+                        payload_columns: None,
+                        payload_compression: None,
+                        point_history_versions: None,
+                        soft_delete_retention_sec: None,
+                        computed_fields: None,
+                        attribute_columns: None,
+                        template: None,
                         replication_factor: None,
                         write_consistency_factor: None,
                         init_from: None,
                         quantization_config: None,
                         sharding_method: None,
+                        strict_mode_config: None,
                     },
                 )),
                 FULL_ACCESS.clone(),
@@ -126,6 +150,7 @@ fn test_alias_operation() {
                 actions: vec![CreateAlias {
                         collection_name: "test".to_string(),
                         alias_name: "test_alias".to_string(),
+                        filter: None,
                     }
                     .into()],
             }),
@@ -141,6 +166,7 @@ fn test_alias_operation() {
                         CreateAlias {
                             collection_name: "test".to_string(),
                             alias_name: "test_alias2".to_string(),
+                            filter: None,
                         }
                         .into(),
                         DeleteAlias {
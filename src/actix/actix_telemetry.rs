@@ -6,6 +6,9 @@ use actix_web::Error;
 use futures_util::future::LocalBoxFuture;
 use parking_lot::Mutex;
 
+use crate::common::telemetry_ops::latency_telemetry::{
+    trace_id_from_traceparent, LatencyOperation, LatencyTelemetryCollector, TRACEPARENT_HEADER,
+};
 use crate::common::telemetry_ops::requests_telemetry::{
     ActixTelemetryCollector, ActixWorkerTelemetryCollector,
 };
@@ -13,10 +16,12 @@ use crate::common::telemetry_ops::requests_telemetry::{
 pub struct ActixTelemetryService<S> {
     service: S,
     telemetry_data: Arc<Mutex<ActixWorkerTelemetryCollector>>,
+    latency_collector: Arc<LatencyTelemetryCollector>,
 }
 
 pub struct ActixTelemetryTransform {
     telemetry_collector: Arc<Mutex<ActixTelemetryCollector>>,
+    latency_collector: Arc<LatencyTelemetryCollector>,
 }
 
 /// Actix telemetry service. It hooks every request and looks into response status code.
@@ -40,8 +45,22 @@ where
             .match_pattern()
             .unwrap_or_else(|| "unknown".to_owned());
         let request_key = format!("{} {}", request.method(), match_pattern);
+        // Latency of operations is also tracked per collection, linked to the trace of the request
+        let latency_key = LatencyOperation::from_rest_endpoint(&request_key).zip(
+            request
+                .match_info()
+                .get("name")
+                .map(|collection| collection.to_string()),
+        );
+        let trace_id = request
+            .headers()
+            .get(TRACEPARENT_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(trace_id_from_traceparent)
+            .map(|trace_id| trace_id.to_string());
         let future = self.service.call(request);
         let telemetry_data = self.telemetry_data.clone();
+        let latency_collector = self.latency_collector.clone();
         Box::pin(async move {
             let instant = std::time::Instant::now();
             let response = future.await?;
@@ -49,15 +68,27 @@ where
             telemetry_data
                 .lock()
                 .add_response(request_key, status, instant);
+            if let Some((operation, collection)) = latency_key {
+                latency_collector.observe(
+                    &collection,
+                    operation,
+                    instant.elapsed(),
+                    trace_id.as_deref(),
+                );
+            }
             Ok(response)
         })
     }
 }
 
 impl ActixTelemetryTransform {
-    pub fn new(telemetry_collector: Arc<Mutex<ActixTelemetryCollector>>) -> Self {
+    pub fn new(
+        telemetry_collector: Arc<Mutex<ActixTelemetryCollector>>,
+        latency_collector: Arc<LatencyTelemetryCollector>,
+    ) -> Self {
         Self {
             telemetry_collector,
+            latency_collector,
         }
     }
 }
@@ -85,6 +116,7 @@ where
                 .telemetry_collector
                 .lock()
                 .create_web_worker_telemetry(),
+            latency_collector: self.latency_collector.clone(),
         }))
     }
 }
@@ -0,0 +1,109 @@
+use actix_web::rt::time::Instant;
+use actix_web::{delete, get, post, put, web, Responder};
+use actix_web_validator::{Json, Path, Query};
+use serde::Deserialize;
+use storage::content_manager::api_keys::{
+    generate_api_key, hash_api_key, ApiKeyDescription, ApiKeyOperation, CreatedApiKey, UpdateApiKey,
+};
+use storage::content_manager::collection_meta_ops::CollectionMetaOperations;
+use storage::dispatcher::Dispatcher;
+use validator::Validate;
+
+use crate::actix::api::collections_api::WaitTimeout;
+use crate::actix::auth::ActixAccess;
+use crate::actix::helpers::process_response;
+
+#[derive(Deserialize, Validate)]
+struct ApiKeyPath {
+    #[validate(length(min = 1, max = 255))]
+    key_name: String,
+}
+
+#[get("/api_keys")]
+async fn list_api_keys(
+    dispatcher: web::Data<Dispatcher>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = dispatcher.toc(&access).list_api_keys(&access).await;
+    process_response(response, timing)
+}
+
+#[post("/api_keys")]
+async fn create_api_key(
+    dispatcher: web::Data<Dispatcher>,
+    description: Json<ApiKeyDescription>,
+    Query(query): Query<WaitTimeout>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let description = description.into_inner();
+    let name = description.name.clone();
+    let key = generate_api_key();
+    let operation = ApiKeyOperation::Create {
+        description,
+        key_hash: hash_api_key(&key),
+    };
+    let response = dispatcher
+        .submit_collection_meta_op(
+            CollectionMetaOperations::ApiKeys(operation),
+            access,
+            query.timeout(),
+        )
+        .await
+        .map(|_| CreatedApiKey { name, key });
+    process_response(response, timing)
+}
+
+#[put("/api_keys/{key_name}")]
+async fn update_api_key(
+    dispatcher: web::Data<Dispatcher>,
+    path: Path<ApiKeyPath>,
+    update: Json<UpdateApiKey>,
+    Query(query): Query<WaitTimeout>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let UpdateApiKey { role, collections } = update.into_inner();
+    let description = ApiKeyDescription {
+        name: path.into_inner().key_name,
+        role,
+        collections,
+    };
+    let response = dispatcher
+        .submit_collection_meta_op(
+            CollectionMetaOperations::ApiKeys(ApiKeyOperation::Update(description)),
+            access,
+            query.timeout(),
+        )
+        .await;
+    process_response(response, timing)
+}
+
+#[delete("/api_keys/{key_name}")]
+async fn delete_api_key(
+    dispatcher: web::Data<Dispatcher>,
+    path: Path<ApiKeyPath>,
+    Query(query): Query<WaitTimeout>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let operation = ApiKeyOperation::Delete {
+        name: path.into_inner().key_name,
+    };
+    let response = dispatcher
+        .submit_collection_meta_op(
+            CollectionMetaOperations::ApiKeys(operation),
+            access,
+            query.timeout(),
+        )
+        .await;
+    process_response(response, timing)
+}
+
+pub fn config_api_keys_api(cfg: &mut web::ServiceConfig) {
+    cfg.service(list_api_keys)
+        .service(create_api_key)
+        .service(update_api_key)
+        .service(delete_api_key);
+}
@@ -0,0 +1,107 @@
+use actix_web::rt::time::Instant;
+use actix_web::{delete, get, put, web, Responder};
+use actix_web_validator::{Json, Path, Query};
+use serde::Deserialize;
+use storage::content_manager::cluster_metadata::{MetadataOperation, MetadataValue};
+use storage::content_manager::collection_meta_ops::CollectionMetaOperations;
+use storage::dispatcher::Dispatcher;
+use validator::Validate;
+
+use crate::actix::api::collections_api::WaitTimeout;
+use crate::actix::auth::ActixAccess;
+use crate::actix::helpers::process_response;
+
+#[derive(Deserialize, Validate)]
+struct NamespacePath {
+    #[validate(length(min = 1, max = 255))]
+    namespace: String,
+}
+
+#[derive(Deserialize, Validate)]
+struct MetadataKeyPath {
+    #[validate(length(min = 1, max = 255))]
+    namespace: String,
+    #[validate(length(min = 1, max = 255))]
+    key: String,
+}
+
+#[get("/cluster/metadata/{namespace}")]
+async fn list_cluster_metadata(
+    dispatcher: web::Data<Dispatcher>,
+    path: Path<NamespacePath>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = dispatcher
+        .toc(&access)
+        .list_cluster_metadata(&path.namespace, &access)
+        .await;
+    process_response(response, timing)
+}
+
+#[get("/cluster/metadata/{namespace}/{key}")]
+async fn get_cluster_metadata_key(
+    dispatcher: web::Data<Dispatcher>,
+    path: Path<MetadataKeyPath>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = dispatcher
+        .toc(&access)
+        .get_cluster_metadata(&path.namespace, &path.key, &access)
+        .await
+        .map(|value| MetadataValue { value });
+    process_response(response, timing)
+}
+
+#[put("/cluster/metadata/{namespace}/{key}")]
+async fn put_cluster_metadata_key(
+    dispatcher: web::Data<Dispatcher>,
+    path: Path<MetadataKeyPath>,
+    value: Json<MetadataValue>,
+    Query(query): Query<WaitTimeout>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let MetadataKeyPath { namespace, key } = path.into_inner();
+    let operation = MetadataOperation::Put {
+        namespace,
+        key,
+        value: value.into_inner().value,
+    };
+    let response = dispatcher
+        .submit_collection_meta_op(
+            CollectionMetaOperations::ClusterMetadata(operation),
+            access,
+            query.timeout(),
+        )
+        .await;
+    process_response(response, timing)
+}
+
+#[delete("/cluster/metadata/{namespace}/{key}")]
+async fn delete_cluster_metadata_key(
+    dispatcher: web::Data<Dispatcher>,
+    path: Path<MetadataKeyPath>,
+    Query(query): Query<WaitTimeout>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let MetadataKeyPath { namespace, key } = path.into_inner();
+    let operation = MetadataOperation::Delete { namespace, key };
+    let response = dispatcher
+        .submit_collection_meta_op(
+            CollectionMetaOperations::ClusterMetadata(operation),
+            access,
+            query.timeout(),
+        )
+        .await;
+    process_response(response, timing)
+}
+
+pub fn config_cluster_metadata_api(cfg: &mut web::ServiceConfig) {
+    cfg.service(list_cluster_metadata)
+        .service(get_cluster_metadata_key)
+        .service(put_cluster_metadata_key)
+        .service(delete_cluster_metadata_key);
+}
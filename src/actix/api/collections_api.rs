@@ -4,7 +4,12 @@ use std::time::Duration;
 use actix_web::rt::time::Instant;
 use actix_web::{delete, get, patch, post, put, web, HttpResponse, Responder};
 use actix_web_validator::{Json, Path, Query};
+use collection::collection::payload_schema_inference::DEFAULT_PAYLOAD_SCHEMA_SAMPLE;
+use collection::collection::vector_diagnostics::DEFAULT_VECTOR_DIAGNOSTICS_SAMPLE;
+use collection::common::consistency_check::DEFAULT_CONSISTENCY_CHECK_BUCKETS;
+use collection::common::freeze::MAX_FREEZE_TIMEOUT;
 use collection::operations::cluster_ops::ClusterOperations;
+use collection::operations::types::{RecallEvaluationRequest, SearchTuningRequest};
 use serde::Deserialize;
 use storage::content_manager::collection_meta_ops::{
     ChangeAliasesOperation, CollectionMetaOperations, CreateCollection, CreateCollectionOperation,
@@ -31,6 +36,45 @@ impl WaitTimeout {
     }
 }
 
+#[derive(Debug, Deserialize, Validate)]
+struct UpdateCollectionParams {
+    #[validate(range(min = 1))]
+    timeout: Option<u64>,
+    /// Only estimate the effect of the update, without applying it
+    #[serde(default)]
+    dry_run: bool,
+}
+
+const DEFAULT_FREEZE_TIMEOUT: Duration = Duration::from_secs(600);
+
+#[derive(Debug, Deserialize, Validate)]
+struct FreezeParams {
+    /// Time in seconds, after which the freeze is lifted automatically
+    #[validate(range(min = 1, max = 86400))]
+    timeout: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+struct ConsistencyCheckParams {
+    /// Re-sync divergent points from the most recently updated replica
+    #[serde(default)]
+    repair: bool,
+    #[validate(range(min = 1, max = 65536))]
+    buckets: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+struct PayloadSchemaParams {
+    #[validate(range(min = 1, max = 100_000))]
+    sample: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+struct VectorDiagnosticsParams {
+    #[validate(range(min = 1, max = 10_000))]
+    sample: Option<usize>,
+}
+
 #[get("/collections")]
 fn get_collections(
     dispatcher: web::Data<Dispatcher>,
@@ -110,11 +154,21 @@ async fn update_collection(
     dispatcher: web::Data<Dispatcher>,
     collection: Path<CollectionPath>,
     operation: Json<UpdateCollection>,
-    Query(query): Query<WaitTimeout>,
+    Query(query): Query<UpdateCollectionParams>,
     ActixAccess(access): ActixAccess,
-) -> impl Responder {
+) -> HttpResponse {
     let timing = Instant::now();
     let name = collection.name.clone();
+    if query.dry_run {
+        let response = do_estimate_collection_update(
+            dispatcher.toc(&access),
+            access,
+            &name,
+            operation.into_inner(),
+        )
+        .await;
+        return process_response(response, timing);
+    }
     let response = dispatcher
         .submit_collection_meta_op(
             CollectionMetaOperations::UpdateCollection(UpdateCollectionOperation::new(
@@ -122,7 +176,7 @@ async fn update_collection(
                 operation.into_inner(),
             )),
             access,
-            query.timeout(),
+            query.timeout.map(Duration::from_secs),
         )
         .await;
     process_response(response, timing)
@@ -199,6 +253,199 @@ async fn update_collection_cluster(
     process_response(response, timing)
 }
 
+#[get("/collections/{name}/slow_queries")]
+async fn get_slow_queries(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = do_get_slow_queries(dispatcher.toc(&access), access, &collection.name).await;
+    process_response(response, timing)
+}
+
+#[get("/collections/{name}/index_recommendations")]
+async fn get_index_recommendations(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response =
+        do_get_index_recommendations(dispatcher.toc(&access), access, &collection.name).await;
+    process_response(response, timing)
+}
+
+#[get("/collections/{name}/warmup")]
+async fn get_warmup_progress(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = do_get_warmup_progress(dispatcher.toc(&access), access, &collection.name).await;
+    process_response(response, timing)
+}
+
+#[get("/collections/{name}/segments")]
+async fn get_collection_segments(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response =
+        do_get_collection_segments(dispatcher.toc(&access), access, &collection.name).await;
+    process_response(response, timing)
+}
+
+#[get("/collections/{name}/stats")]
+async fn get_collection_stats(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = do_get_collection_stats(dispatcher.toc(&access), access, &collection.name).await;
+    process_response(response, timing)
+}
+
+#[get("/collections/{name}/disk_usage")]
+async fn get_collection_disk_usage(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response =
+        do_get_collection_disk_usage(dispatcher.toc(&access), access, &collection.name).await;
+    process_response(response, timing)
+}
+
+#[get("/collections/{name}/freeze")]
+async fn get_collection_freeze(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response =
+        do_get_collection_freeze(dispatcher.toc(&access), access, &collection.name).await;
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/freeze")]
+async fn freeze_collection(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    params: Query<FreezeParams>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let timeout = params
+        .timeout
+        .map_or(DEFAULT_FREEZE_TIMEOUT, Duration::from_secs)
+        .min(MAX_FREEZE_TIMEOUT);
+    let response =
+        do_freeze_collection(dispatcher.toc(&access), access, &collection.name, timeout).await;
+    process_response(response, timing)
+}
+
+#[delete("/collections/{name}/freeze")]
+async fn unfreeze_collection(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = do_unfreeze_collection(dispatcher.toc(&access), access, &collection.name).await;
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/consistency")]
+async fn check_collection_consistency(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    params: Query<ConsistencyCheckParams>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let buckets = params.buckets.unwrap_or(DEFAULT_CONSISTENCY_CHECK_BUCKETS);
+    let response = do_check_collection_consistency(
+        dispatcher.toc(&access),
+        access,
+        &collection.name,
+        buckets,
+        params.repair,
+    )
+    .await;
+    process_response(response, timing)
+}
+
+#[get("/collections/{name}/payload_schema")]
+async fn get_payload_schema(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    params: Query<PayloadSchemaParams>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let sample = params.sample.unwrap_or(DEFAULT_PAYLOAD_SCHEMA_SAMPLE);
+    let response =
+        do_infer_payload_schema(dispatcher.toc(&access), access, &collection.name, sample).await;
+    process_response(response, timing)
+}
+
+#[get("/collections/{name}/vector_diagnostics")]
+async fn get_vector_diagnostics(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    params: Query<VectorDiagnosticsParams>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let sample = params.sample.unwrap_or(DEFAULT_VECTOR_DIAGNOSTICS_SAMPLE);
+    let response =
+        do_get_vector_diagnostics(dispatcher.toc(&access), access, &collection.name, sample).await;
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/recall")]
+async fn evaluate_recall(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<RecallEvaluationRequest>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = do_evaluate_recall(
+        dispatcher.toc(&access),
+        access,
+        &collection.name,
+        request.into_inner(),
+    )
+    .await;
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/tune_search")]
+async fn tune_search(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<SearchTuningRequest>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = do_tune_search(
+        &dispatcher.into_inner(),
+        access,
+        &collection.name,
+        request.into_inner(),
+    )
+    .await;
+    process_response(response, timing)
+}
+
 // Configure services
 pub fn config_collections_api(cfg: &mut web::ServiceConfig) {
     // Ordering of services is important for correct path pattern matching
@@ -213,7 +460,21 @@ pub fn config_collections_api(cfg: &mut web::ServiceConfig) {
         .service(get_aliases)
         .service(get_collection_aliases)
         .service(get_cluster_info)
-        .service(update_collection_cluster);
+        .service(update_collection_cluster)
+        .service(get_slow_queries)
+        .service(get_index_recommendations)
+        .service(get_warmup_progress)
+        .service(get_collection_segments)
+        .service(get_collection_stats)
+        .service(get_collection_disk_usage)
+        .service(check_collection_consistency)
+        .service(get_collection_freeze)
+        .service(freeze_collection)
+        .service(unfreeze_collection)
+        .service(get_payload_schema)
+        .service(get_vector_diagnostics)
+        .service(evaluate_recall)
+        .service(tune_search);
 }
 
 #[cfg(test)]
@@ -2,14 +2,18 @@ use actix_web::rt::time::Instant;
 use actix_web::{post, web, Responder};
 use actix_web_validator::{Json, Path, Query};
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
-use collection::operations::types::CountRequest;
+use collection::operations::types::{
+    CountDistinctRequest, CountDistinctResult, CountRequest, FieldStatsRequest, FieldStatsResult,
+    HistogramRequest, HistogramResult,
+};
+use storage::content_manager::errors::StorageError;
 use storage::dispatcher::Dispatcher;
 
 use super::CollectionPath;
 use crate::actix::api::read_params::ReadParams;
 use crate::actix::auth::ActixAccess;
 use crate::actix::helpers::process_response;
-use crate::common::points::do_count_points;
+use crate::common::points::{do_count_distinct, do_count_points, do_field_stats, do_histogram};
 
 #[post("/collections/{name}/points/count")]
 async fn count_points(
@@ -44,3 +48,109 @@ async fn count_points(
 
     process_response(response, timing)
 }
+
+#[post("/collections/{name}/points/count_distinct")]
+async fn count_distinct(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<CountDistinctRequest>,
+    params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let CountDistinctRequest {
+        count_distinct_request,
+        shard_key,
+    } = request.into_inner();
+
+    let shard_selector = match shard_key {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => ShardSelectorInternal::from(shard_keys),
+    };
+
+    let response = do_count_distinct(
+        dispatcher.toc(&access),
+        &collection.name,
+        count_distinct_request,
+        params.consistency,
+        shard_selector,
+        access,
+    )
+    .await
+    .map(CountDistinctResult::from);
+
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/points/histogram")]
+async fn histogram(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<HistogramRequest>,
+    params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let HistogramRequest {
+        histogram_request,
+        shard_key,
+    } = request.into_inner();
+
+    let shard_selector = match shard_key {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => ShardSelectorInternal::from(shard_keys),
+    };
+
+    let buckets = histogram_request.buckets;
+    let response = do_histogram(
+        dispatcher.toc(&access),
+        &collection.name,
+        histogram_request,
+        params.consistency,
+        shard_selector,
+        access,
+    )
+    .await
+    .and_then(|distribution| {
+        HistogramResult::new(&distribution, buckets).map_err(StorageError::from)
+    });
+
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/points/field_stats")]
+async fn field_stats(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<FieldStatsRequest>,
+    params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let FieldStatsRequest {
+        field_stats_request,
+        shard_key,
+    } = request.into_inner();
+
+    let shard_selector = match shard_key {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => ShardSelectorInternal::from(shard_keys),
+    };
+
+    let percentiles = field_stats_request.percentiles.clone();
+    let response = do_field_stats(
+        dispatcher.toc(&access),
+        &collection.name,
+        field_stats_request,
+        params.consistency,
+        shard_selector,
+        access,
+    )
+    .await
+    .map(|stats| FieldStatsResult::new(&stats, &percentiles));
+
+    process_response(response, timing)
+}
@@ -1,4 +1,6 @@
+pub mod api_keys_api;
 pub mod cluster_api;
+pub mod cluster_metadata_api;
 pub mod collections_api;
 pub mod count_api;
 pub mod debug_api;
@@ -12,6 +14,7 @@ pub mod search_api;
 pub mod service_api;
 pub mod shards_api;
 pub mod snapshot_api;
+pub mod templates_api;
 pub mod update_api;
 
 use common::validation::validate_collection_name;
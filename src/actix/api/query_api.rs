@@ -1,5 +1,7 @@
+use actix_web::rt::time::Instant;
 use actix_web::{post, web, Responder};
 use actix_web_validator::{Json, Path, Query};
+use api::rest::conversions::scored_points_with_payload_limit;
 use api::rest::{QueryRequest, QueryRequestBatch, QueryResponse};
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::universal_query::collection_query::CollectionQueryRequest;
@@ -11,27 +13,38 @@ use super::read_params::ReadParams;
 use super::CollectionPath;
 use crate::actix::auth::ActixAccess;
 use crate::actix::helpers;
+use crate::common::inference::InferenceService;
+use crate::settings::ServiceConfig;
 
 #[post("/collections/{name}/points/query")]
 async fn query_points(
     dispatcher: web::Data<Dispatcher>,
+    inference: web::Data<InferenceService>,
+    service_config: web::Data<ServiceConfig>,
     collection: Path<CollectionPath>,
     request: Json<QueryRequest>,
     params: Query<ReadParams>,
     ActixAccess(access): ActixAccess,
 ) -> impl Responder {
-    helpers::time(async move {
+    let timing = Instant::now();
+    let hw_counter = service_config.hardware_counter();
+
+    let response = async {
+        let mut request = request.into_inner();
+        inference.infer(&mut request).await?;
+
         let QueryRequest {
             internal: query_request,
             shard_key,
-        } = request.into_inner();
+        } = request;
+        let payload_size_limit = query_request.payload_size_limit;
 
         let shard_selection = match shard_key {
             None => ShardSelectorInternal::All,
             Some(shard_keys) => shard_keys.into(),
         };
 
-        let points = dispatcher
+        let mut response = dispatcher
             .toc(&access)
             .query_batch(
                 &collection.name,
@@ -39,32 +52,44 @@ async fn query_points(
                 params.consistency,
                 access,
                 params.timeout(),
+                &hw_counter,
             )
-            .await?
-            .pop()
-            .ok_or_else(|| {
-                StorageError::service_error("Expected at least one response for one query")
-            })?
-            .into_iter()
-            .map(api::rest::ScoredPoint::from)
-            .collect_vec();
+            .await?;
+        let points = response.value.pop().ok_or_else(|| {
+            StorageError::service_error("Expected at least one response for one query")
+        })?;
+        let points = scored_points_with_payload_limit(points, payload_size_limit);
+
+        Ok::<_, StorageError>(response.map(|_| QueryResponse { points }))
+    }
+    .await;
 
-        Ok(QueryResponse { points })
-    })
-    .await
+    helpers::process_search_response(response, timing, params.timeout(), &hw_counter)
 }
 
 #[post("/collections/{name}/points/query/batch")]
 async fn query_points_batch(
     dispatcher: web::Data<Dispatcher>,
+    inference: web::Data<InferenceService>,
+    service_config: web::Data<ServiceConfig>,
     collection: Path<CollectionPath>,
     request: Json<QueryRequestBatch>,
     params: Query<ReadParams>,
     ActixAccess(access): ActixAccess,
 ) -> impl Responder {
-    helpers::time(async move {
-        let QueryRequestBatch { searches } = request.into_inner();
+    let timing = Instant::now();
+    let hw_counter = service_config.hardware_counter();
+
+    let response = async {
+        let mut request = request.into_inner();
+        inference.infer(&mut request).await?;
 
+        let QueryRequestBatch { searches } = request;
+
+        let payload_size_limits = searches
+            .iter()
+            .map(|request| request.internal.payload_size_limit)
+            .collect_vec();
         let batch = searches
             .into_iter()
             .map(|request| {
@@ -91,20 +116,24 @@ async fn query_points_batch(
                 params.consistency,
                 access,
                 params.timeout(),
+                &hw_counter,
             )
             .await?
-            .into_iter()
-            .map(|response| QueryResponse {
-                points: response
+            .map(|batch| {
+                batch
                     .into_iter()
-                    .map(api::rest::ScoredPoint::from)
-                    .collect_vec(),
-            })
-            .collect_vec();
+                    .zip(payload_size_limits)
+                    .map(|(response, payload_size_limit)| QueryResponse {
+                        points: scored_points_with_payload_limit(response, payload_size_limit),
+                    })
+                    .collect_vec()
+            });
+
+        Ok::<_, StorageError>(res)
+    }
+    .await;
 
-        Ok(res)
-    })
-    .await
+    helpers::process_search_response(response, timing, params.timeout(), &hw_counter)
 }
 
 pub fn config_query_api(cfg: &mut web::ServiceConfig) {
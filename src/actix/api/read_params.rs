@@ -64,6 +64,7 @@ mod test {
         test("all", from_type(ReadConsistencyType::All));
         test("majority", from_type(ReadConsistencyType::Majority));
         test("quorum", from_type(ReadConsistencyType::Quorum));
+        test("partial", from_type(ReadConsistencyType::Partial));
     }
 
     #[test]
@@ -6,7 +6,7 @@ use actix_web_validator::{Json, Path, Query};
 use collection::operations::consistency_params::ReadConsistency;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
-    RecommendGroupsRequest, RecommendRequest, RecommendRequestBatch,
+    RecommendGroupsRequest, RecommendRequest, RecommendRequestBatch, RecommendSharedRequestBatch,
 };
 use itertools::Itertools;
 use segment::types::ScoredPoint;
@@ -120,6 +120,40 @@ async fn recommend_batch_points(
     process_response(response, timing)
 }
 
+#[post("/collections/{name}/points/recommend/shared")]
+async fn recommend_shared_batch_points(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<RecommendSharedRequestBatch>,
+    params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let response = do_recommend_batch_points(
+        dispatcher.toc(&access),
+        &collection.name,
+        RecommendRequestBatch::from(request.into_inner()),
+        params.consistency,
+        access,
+        params.timeout(),
+    )
+    .await
+    .map(|batch_scored_points| {
+        batch_scored_points
+            .into_iter()
+            .map(|scored_points| {
+                scored_points
+                    .into_iter()
+                    .map(api::rest::ScoredPoint::from)
+                    .collect_vec()
+            })
+            .collect_vec()
+    });
+
+    process_response(response, timing)
+}
+
 #[post("/collections/{name}/points/recommend/groups")]
 async fn recommend_point_groups(
     dispatcher: web::Data<Dispatcher>,
@@ -157,5 +191,6 @@ async fn recommend_point_groups(
 pub fn config_recommend_api(cfg: &mut web::ServiceConfig) {
     cfg.service(recommend_points)
         .service(recommend_batch_points)
+        .service(recommend_shared_batch_points)
         .service(recommend_point_groups);
 }
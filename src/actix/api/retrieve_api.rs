@@ -1,9 +1,11 @@
 use actix_web::rt::time::Instant;
-use actix_web::{get, post, web, Responder};
+use actix_web::{get, post, web, HttpResponse, Responder};
 use actix_web_validator::{Json, Path, Query};
+use chrono::{DateTime, Utc};
 use collection::operations::consistency_params::ReadConsistency;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{PointRequest, PointRequestInternal, Record, ScrollRequest};
+use futures::TryStreamExt;
 use itertools::Itertools;
 use segment::types::{PointIdType, WithPayloadInterface};
 use serde::Deserialize;
@@ -17,7 +19,9 @@ use super::read_params::ReadParams;
 use super::CollectionPath;
 use crate::actix::auth::ActixAccess;
 use crate::actix::helpers::{self, process_response};
-use crate::common::points::do_get_points;
+use crate::common::collections::do_get_point_history;
+use crate::common::export::{do_export_points, ExportRequest, ARROW_STREAM_CONTENT_TYPE};
+use crate::common::points::{do_get_points, do_scroll_points_stream};
 
 #[derive(Deserialize, Validate)]
 struct PointPath {
@@ -84,6 +88,40 @@ async fn get_point(
     .await
 }
 
+#[derive(Deserialize, Validate)]
+struct PointHistoryParams {
+    /// Return the version, which was current after the operation with this number
+    version: Option<u64>,
+    /// Return the version, which was current at this time
+    timestamp: Option<DateTime<Utc>>,
+}
+
+#[get("/collections/{name}/points/{id}/history")]
+async fn get_point_history(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    point: Path<PointPath>,
+    params: Query<PointHistoryParams>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    helpers::time(async move {
+        let point_id: PointIdType = point.id.parse().map_err(|_| StorageError::BadInput {
+            description: format!("Can not recognize \"{}\" as point id", point.id),
+        })?;
+
+        do_get_point_history(
+            dispatcher.toc(&access),
+            access,
+            &collection.name,
+            point_id,
+            params.version,
+            params.timestamp,
+        )
+        .await
+    })
+    .await
+}
+
 #[post("/collections/{name}/points")]
 async fn get_points(
     dispatcher: web::Data<Dispatcher>,
@@ -151,3 +189,86 @@ async fn scroll_points(
 
     process_response(response, timing)
 }
+
+/// Content type of newline-delimited JSON
+const NDJSON_CONTENT_TYPE: &str = "application/x-ndjson";
+
+/// Scroll all points, matching the request, writing one JSON record per line as the points are
+/// read. `limit` is the size of pages, which points are read in.
+#[post("/collections/{name}/points/scroll/stream")]
+async fn scroll_points_stream(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<ScrollRequest>,
+    params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
+) -> HttpResponse {
+    let timing = Instant::now();
+
+    let ScrollRequest {
+        scroll_request,
+        shard_key,
+    } = request.into_inner();
+
+    let shard_selection = match shard_key {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => ShardSelectorInternal::from(shard_keys),
+    };
+
+    let stream = do_scroll_points_stream(
+        dispatcher.toc(&access).clone(),
+        collection.into_inner().name,
+        scroll_request,
+        params.consistency,
+        shard_selection,
+        access,
+    )
+    .await;
+
+    let stream = match stream {
+        Ok(stream) => stream,
+        Err(err) => return helpers::process_response_error(err, timing),
+    };
+
+    let stream = stream.and_then(|points| async move {
+        let mut chunk = Vec::new();
+        for point in points {
+            serde_json::to_writer(&mut chunk, &point).map_err(|err| {
+                StorageError::service_error(format!("Failed to serialize point: {err}"))
+            })?;
+            chunk.push(b'\n');
+        }
+        Ok(web::Bytes::from(chunk))
+    });
+
+    HttpResponse::Ok()
+        .content_type(NDJSON_CONTENT_TYPE)
+        .streaming(stream)
+}
+
+#[post("/collections/{name}/points/export")]
+async fn export_points(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<ExportRequest>,
+    params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
+) -> HttpResponse {
+    let timing = Instant::now();
+
+    let stream = do_export_points(
+        dispatcher.toc(&access).clone(),
+        collection.into_inner().name,
+        request.into_inner(),
+        params.consistency,
+        access,
+    )
+    .await;
+
+    match stream {
+        Ok(stream) => HttpResponse::Ok()
+            .content_type(ARROW_STREAM_CONTENT_TYPE)
+            .streaming(stream.map_ok(web::Bytes::from)),
+        Err(err) => helpers::process_response_error(err, timing),
+    }
+}
@@ -11,20 +11,23 @@ use storage::dispatcher::Dispatcher;
 use super::read_params::ReadParams;
 use super::CollectionPath;
 use crate::actix::auth::ActixAccess;
-use crate::actix::helpers::process_response;
+use crate::actix::helpers::{process_response, process_search_response};
 use crate::common::points::{
-    do_core_search_points, do_search_batch_points, do_search_point_groups,
+    do_core_search_points, do_explain_search_points, do_search_batch_points, do_search_point_groups,
 };
+use crate::settings::ServiceConfig;
 
 #[post("/collections/{name}/points/search")]
 async fn search_points(
     dispatcher: web::Data<Dispatcher>,
+    service_config: web::Data<ServiceConfig>,
     collection: Path<CollectionPath>,
     request: Json<SearchRequest>,
     params: Query<ReadParams>,
     ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
+    let hw_counter = service_config.hardware_counter();
 
     let SearchRequest {
         search_request,
@@ -44,13 +47,56 @@ async fn search_points(
         shard_selection,
         access,
         params.timeout(),
+        &hw_counter,
     )
     .await
-    .map(|scored_points| {
-        scored_points
+    .map(|response| {
+        response.map(|scored_points| {
+            scored_points
+                .into_iter()
+                .map(api::rest::ScoredPoint::from)
+                .collect_vec()
+        })
+    });
+
+    process_search_response(response, timing, params.timeout(), &hw_counter)
+}
+
+#[post("/collections/{name}/points/search/explain")]
+async fn explain_search_points(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<SearchRequest>,
+    params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let SearchRequest {
+        search_request,
+        shard_key,
+    } = request.into_inner();
+
+    let shard_selection = match shard_key {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => shard_keys.into(),
+    };
+
+    let response = do_explain_search_points(
+        dispatcher.toc(&access),
+        &collection.name,
+        search_request.into(),
+        shard_selection,
+        access,
+        params.timeout(),
+    )
+    .await
+    .map(|(scored_points, shards)| api::rest::SearchExplainResponse {
+        points: scored_points
             .into_iter()
             .map(api::rest::ScoredPoint::from)
-            .collect_vec()
+            .collect_vec(),
+        shards,
     });
 
     process_response(response, timing)
@@ -59,12 +105,14 @@ async fn search_points(
 #[post("/collections/{name}/points/search/batch")]
 async fn batch_search_points(
     dispatcher: web::Data<Dispatcher>,
+    service_config: web::Data<ServiceConfig>,
     collection: Path<CollectionPath>,
     request: Json<SearchRequestBatch>,
     params: Query<ReadParams>,
     ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
+    let hw_counter = service_config.hardware_counter();
 
     let request = request.into_inner();
     let requests = request
@@ -92,21 +140,24 @@ async fn batch_search_points(
         params.consistency,
         access,
         params.timeout(),
+        &hw_counter,
     )
     .await
-    .map(|batch_scored_points| {
-        batch_scored_points
-            .into_iter()
-            .map(|scored_points| {
-                scored_points
-                    .into_iter()
-                    .map(api::rest::ScoredPoint::from)
-                    .collect_vec()
-            })
-            .collect_vec()
+    .map(|response| {
+        response.map(|batch_scored_points| {
+            batch_scored_points
+                .into_iter()
+                .map(|scored_points| {
+                    scored_points
+                        .into_iter()
+                        .map(api::rest::ScoredPoint::from)
+                        .collect_vec()
+                })
+                .collect_vec()
+        })
     });
 
-    process_response(response, timing)
+    process_search_response(response, timing, params.timeout(), &hw_counter)
 }
 
 #[post("/collections/{name}/points/search/groups")]
@@ -146,6 +197,7 @@ async fn search_point_groups(
 // Configure services
 pub fn config_search_api(cfg: &mut web::ServiceConfig) {
     cfg.service(search_points)
+        .service(explain_search_points)
         .service(batch_search_points)
         .service(search_point_groups);
 }
@@ -1,11 +1,11 @@
 use std::future::Future;
 use std::sync::Arc;
 
-use actix_web::http::header::ContentType;
+use actix_web::http::header::{self, ContentType};
 use actix_web::http::StatusCode;
 use actix_web::rt::time::Instant;
 use actix_web::web::Query;
-use actix_web::{get, post, web, HttpResponse, Responder};
+use actix_web::{get, post, web, HttpRequest, HttpResponse, Responder};
 use actix_web_validator::Json;
 use common::types::{DetailsLevel, TelemetryDetail};
 use schemars::JsonSchema;
@@ -18,9 +18,11 @@ use tokio::sync::Mutex;
 
 use crate::actix::auth::ActixAccess;
 use crate::actix::helpers::{self, process_response_error};
+use crate::common::audit::{AuditLog, AuditQuery};
+use crate::common::config_reload::ConfigReloader;
 use crate::common::health;
 use crate::common::helpers::LocksOption;
-use crate::common::metrics::MetricsData;
+use crate::common::metrics::{MetricsData, OPEN_METRICS_CONTENT_TYPE};
 use crate::common::stacktrace::get_stack_trace;
 use crate::common::telemetry::TelemetryCollector;
 use crate::tracing;
@@ -67,6 +69,7 @@ pub struct MetricsParam {
 async fn metrics(
     telemetry_collector: web::Data<Mutex<TelemetryCollector>>,
     params: Query<MetricsParam>,
+    request: HttpRequest,
     ActixAccess(access): ActixAccess,
 ) -> HttpResponse {
     if let Err(err) = access.check_global_access(AccessRequirements::new()) {
@@ -84,15 +87,32 @@ async fn metrics(
             },
         )
         .await;
-    let telemetry_data = if anonymize {
-        telemetry_data.anonymize()
+    let latencies = telemetry_collector
+        .latency_telemetry_collector
+        .get_histograms();
+    let (telemetry_data, latencies) = if anonymize {
+        (telemetry_data.anonymize(), latencies.anonymize())
     } else {
-        telemetry_data
+        (telemetry_data, latencies)
     };
+    let metrics_data = MetricsData::new(telemetry_data, latencies);
 
-    HttpResponse::Ok()
-        .content_type(ContentType::plaintext())
-        .body(MetricsData::from(telemetry_data).format_metrics())
+    // Exemplars are only supported by OpenMetrics format, so it is sent if the scraper accepts it
+    let accepts_open_metrics = request
+        .headers()
+        .get(header::ACCEPT)
+        .and_then(|accept| accept.to_str().ok())
+        .is_some_and(|accept| accept.contains("application/openmetrics-text"));
+
+    if accepts_open_metrics {
+        HttpResponse::Ok()
+            .content_type(OPEN_METRICS_CONTENT_TYPE)
+            .body(metrics_data.format_open_metrics())
+    } else {
+        HttpResponse::Ok()
+            .content_type(ContentType::plaintext())
+            .body(metrics_data.format_metrics())
+    }
 }
 
 #[post("/locks")]
@@ -137,6 +157,22 @@ fn get_stacktrace(ActixAccess(access): ActixAccess) -> impl Future<Output = Http
     })
 }
 
+#[get("/audit")]
+fn get_audit_log(
+    audit_log: web::Data<Option<Arc<AuditLog>>>,
+    query: Query<AuditQuery>,
+    ActixAccess(access): ActixAccess,
+) -> impl Future<Output = HttpResponse> {
+    helpers::time(async move {
+        access.check_global_access(AccessRequirements::new().manage())?;
+        let audit_log = audit_log
+            .as_ref()
+            .as_ref()
+            .ok_or_else(|| StorageError::bad_request("Audit log is disabled"))?;
+        Ok(audit_log.query(&query))
+    })
+}
+
 #[get("/healthz")]
 async fn healthz() -> impl Responder {
     kubernetes_healthz().await
@@ -195,6 +231,28 @@ async fn update_logger_config(
     helpers::process_response(result, timing)
 }
 
+#[get("/config")]
+fn get_runtime_config(
+    reloader: web::Data<ConfigReloader>,
+    ActixAccess(access): ActixAccess,
+) -> impl Future<Output = HttpResponse> {
+    helpers::time(async move {
+        access.check_global_access(AccessRequirements::new().manage())?;
+        Ok(reloader.config().await)
+    })
+}
+
+#[post("/config/reload")]
+fn reload_config(
+    reloader: web::Data<ConfigReloader>,
+    ActixAccess(access): ActixAccess,
+) -> impl Future<Output = HttpResponse> {
+    helpers::time(async move {
+        access.check_global_access(AccessRequirements::new().manage())?;
+        reloader.reload().await
+    })
+}
+
 // Configure services
 pub fn config_service_api(cfg: &mut web::ServiceConfig) {
     cfg.service(telemetry)
@@ -202,9 +260,12 @@ pub fn config_service_api(cfg: &mut web::ServiceConfig) {
         .service(put_locks)
         .service(get_locks)
         .service(get_stacktrace)
+        .service(get_audit_log)
         .service(healthz)
         .service(livez)
         .service(readyz)
         .service(get_logger_config)
-        .service(update_logger_config);
+        .service(update_logger_config)
+        .service(get_runtime_config)
+        .service(reload_config);
 }
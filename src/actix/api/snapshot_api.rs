@@ -9,15 +9,21 @@ use collection::common::file_utils::move_file;
 use collection::common::sha_256::{hash_file, hashes_equal};
 use collection::common::snapshot_stream::SnapshotStream;
 use collection::operations::snapshot_ops::{
-    ShardSnapshotRecover, SnapshotPriority, SnapshotRecover,
+    RemoteSnapshotRecover, ShardSnapshotRecover, SnapshotPriority, SnapshotRecover,
 };
 use collection::shards::shard::ShardId;
 use futures::{FutureExt as _, TryFutureExt as _};
 use reqwest::Url;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use storage::content_manager::collection_meta_ops::CollectionMetaOperations;
 use storage::content_manager::errors::StorageError;
-use storage::content_manager::snapshots::recover::do_recover_from_snapshot;
+use storage::content_manager::snapshot_schedules::{
+    validate_snapshot_schedule_name, SnapshotSchedule, SnapshotScheduleOperation,
+};
+use storage::content_manager::snapshots::recover::{
+    do_recover_from_remote_snapshot, do_recover_from_snapshot,
+};
 use storage::content_manager::snapshots::{
     do_create_full_snapshot, do_delete_collection_snapshot, do_delete_full_snapshot,
     do_list_full_snapshots,
@@ -29,6 +35,7 @@ use uuid::Uuid;
 use validator::Validate;
 
 use super::{CollectionPath, StrictCollectionPath};
+use crate::actix::api::collections_api::WaitTimeout;
 use crate::actix::auth::ActixAccess;
 use crate::actix::helpers::{self, process_response, HttpError};
 use crate::common;
@@ -42,6 +49,14 @@ struct SnapshotPath {
     name: String,
 }
 
+#[derive(Deserialize, Validate)]
+struct SnapshotSchedulePath {
+    #[validate(length(min = 1, max = 255))]
+    collection_name: String,
+    #[validate(length(min = 1, max = 64), custom = "validate_snapshot_schedule_name")]
+    schedule_name: String,
+}
+
 #[derive(Deserialize, Serialize, JsonSchema, Validate)]
 pub struct SnapshotUploadingParam {
     pub wait: Option<bool>,
@@ -53,6 +68,16 @@ pub struct SnapshotUploadingParam {
     pub checksum: Option<String>,
 }
 
+#[derive(Deserialize, Serialize, JsonSchema, Validate)]
+pub struct SegmentSnapshotUploadingParam {
+    pub wait: Option<bool>,
+
+    /// Optional SHA256 checksum to verify snapshot integrity before the segment is replaced.
+    #[serde(default)]
+    #[validate(custom = "::common::validation::validate_sha256_hash")]
+    pub checksum: Option<String>,
+}
+
 #[derive(Deserialize, Serialize, JsonSchema, Validate)]
 pub struct SnapshottingParam {
     pub wait: Option<bool>,
@@ -240,6 +265,29 @@ async fn recover_from_snapshot(
     .await
 }
 
+#[put("/collections/{name}/snapshots/recover/remote")]
+async fn recover_from_remote_snapshot(
+    dispatcher: web::Data<Dispatcher>,
+    http_client: web::Data<HttpClient>,
+    collection: valid::Path<CollectionPath>,
+    request: valid::Json<RemoteSnapshotRecover>,
+    params: valid::Query<SnapshottingParam>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    helpers::time_or_accept_with_handle(params.wait.unwrap_or(true), async move {
+        let remote_snapshot_recover = request.into_inner();
+        let http_client = http_client.client(remote_snapshot_recover.api_key.as_deref())?;
+        do_recover_from_remote_snapshot(
+            dispatcher.get_ref(),
+            &collection.name,
+            remote_snapshot_recover,
+            access,
+            http_client,
+        )
+    })
+    .await
+}
+
 #[get("/collections/{name}/snapshots/{snapshot_name}")]
 async fn get_snapshot(
     req: HttpRequest,
@@ -258,6 +306,24 @@ async fn get_snapshot(
     .await
 }
 
+#[post("/collections/{name}/snapshots/{snapshot_name}/verify")]
+async fn verify_snapshot(
+    dispatcher: web::Data<Dispatcher>,
+    path: web::Path<(String, String)>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let (collection_name, snapshot_name) = path.into_inner();
+    let timing = Instant::now();
+    let response = common::snapshots::verify_collection_snapshot(
+        dispatcher.toc(&access),
+        access,
+        &collection_name,
+        &snapshot_name,
+    )
+    .await;
+    process_response(response, timing)
+}
+
 #[get("/snapshots")]
 async fn list_full_snapshots(
     dispatcher: web::Data<Dispatcher>,
@@ -455,6 +521,67 @@ async fn upload_shard_snapshot(
     helpers::time_or_accept(future, wait.unwrap_or(true)).await
 }
 
+#[post("/collections/{collection}/shards/{shard}/segments/{segment}/snapshots")]
+async fn create_segment_snapshot(
+    dispatcher: web::Data<Dispatcher>,
+    path: web::Path<(String, ShardId, String)>,
+    query: web::Query<SnapshottingParam>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let (collection, shard, segment) = path.into_inner();
+    let future = common::snapshots::create_segment_snapshot(
+        dispatcher.toc(&access).clone(),
+        access,
+        collection,
+        shard,
+        segment,
+    );
+
+    helpers::time_or_accept(future, query.wait.unwrap_or(true)).await
+}
+
+#[post("/collections/{collection}/shards/{shard}/segments/{segment}/snapshots/upload")]
+async fn upload_segment_snapshot(
+    dispatcher: web::Data<Dispatcher>,
+    path: web::Path<(String, ShardId, String)>,
+    query: valid::Query<SegmentSnapshotUploadingParam>,
+    MultipartForm(form): MultipartForm<SnapshottingForm>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let (collection, shard, segment) = path.into_inner();
+    let SegmentSnapshotUploadingParam { wait, checksum } = query.into_inner();
+
+    // - `restore_segment_snapshot` is *not* cancel safe
+    //   - but the task is *spawned* on the runtime and won't be cancelled, if request is cancelled
+
+    let future = cancel::future::spawn_cancel_on_drop(move |cancel| async move {
+        // TODO: Run this check before the multipart blob is uploaded
+        let collection_pass = access
+            .check_global_access(AccessRequirements::new().manage())?
+            .issue_pass(&collection);
+
+        if let Some(checksum) = checksum {
+            let snapshot_checksum = hash_file(form.snapshot.file.path()).await?;
+            if !hashes_equal(snapshot_checksum.as_str(), checksum.as_str()) {
+                return Err(StorageError::checksum_mismatch(snapshot_checksum, checksum));
+            }
+        }
+
+        let future = dispatcher.toc(&access).get_collection(&collection_pass);
+        let collection = cancel::future::cancel_on_token(cancel, future).await??;
+
+        // `restore_segment_snapshot` is *not* cancel safe
+        collection
+            .restore_segment_snapshot(shard, &segment, form.snapshot.file.path())
+            .await?;
+
+        Ok(())
+    })
+    .map(|x| x.map_err(Into::into).and_then(|x| x));
+
+    helpers::time_or_accept(future, wait.unwrap_or(true)).await
+}
+
 #[get("/collections/{collection}/shards/{shard}/snapshots/{snapshot}")]
 async fn download_shard_snapshot(
     req: HttpRequest,
@@ -505,13 +632,83 @@ async fn delete_shard_snapshot(
     helpers::time_or_accept(future, query.wait.unwrap_or(true)).await
 }
 
+#[get("/collections/{name}/snapshot_schedules")]
+async fn list_snapshot_schedules(
+    dispatcher: web::Data<Dispatcher>,
+    collection: valid::Path<CollectionPath>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = dispatcher
+        .toc(&access)
+        .list_snapshot_schedules(&collection.name, &access)
+        .await;
+    process_response(response, timing)
+}
+
+#[put("/collections/{collection_name}/snapshot_schedules/{schedule_name}")]
+async fn set_snapshot_schedule(
+    dispatcher: web::Data<Dispatcher>,
+    path: valid::Path<SnapshotSchedulePath>,
+    schedule: valid::Json<SnapshotSchedule>,
+    valid::Query(query): valid::Query<WaitTimeout>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let SnapshotSchedulePath {
+        collection_name,
+        schedule_name,
+    } = path.into_inner();
+    let operation = SnapshotScheduleOperation::Set {
+        collection_name,
+        name: schedule_name,
+        schedule: schedule.into_inner(),
+    };
+    let response = dispatcher
+        .submit_collection_meta_op(
+            CollectionMetaOperations::SnapshotSchedules(operation),
+            access,
+            query.timeout(),
+        )
+        .await;
+    process_response(response, timing)
+}
+
+#[delete("/collections/{collection_name}/snapshot_schedules/{schedule_name}")]
+async fn delete_snapshot_schedule(
+    dispatcher: web::Data<Dispatcher>,
+    path: valid::Path<SnapshotSchedulePath>,
+    valid::Query(query): valid::Query<WaitTimeout>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let SnapshotSchedulePath {
+        collection_name,
+        schedule_name,
+    } = path.into_inner();
+    let operation = SnapshotScheduleOperation::Delete {
+        collection_name,
+        name: schedule_name,
+    };
+    let response = dispatcher
+        .submit_collection_meta_op(
+            CollectionMetaOperations::SnapshotSchedules(operation),
+            access,
+            query.timeout(),
+        )
+        .await;
+    process_response(response, timing)
+}
+
 // Configure services
 pub fn config_snapshots_api(cfg: &mut web::ServiceConfig) {
     cfg.service(list_snapshots)
         .service(create_snapshot)
         .service(upload_snapshot)
         .service(recover_from_snapshot)
+        .service(recover_from_remote_snapshot)
         .service(get_snapshot)
+        .service(verify_snapshot)
         .service(list_full_snapshots)
         .service(create_full_snapshot)
         .service(get_full_snapshot)
@@ -521,6 +718,11 @@ pub fn config_snapshots_api(cfg: &mut web::ServiceConfig) {
         .service(create_shard_snapshot)
         .service(recover_shard_snapshot)
         .service(upload_shard_snapshot)
+        .service(create_segment_snapshot)
+        .service(upload_segment_snapshot)
         .service(download_shard_snapshot)
-        .service(delete_shard_snapshot);
+        .service(delete_shard_snapshot)
+        .service(list_snapshot_schedules)
+        .service(set_snapshot_schedule)
+        .service(delete_snapshot_schedule);
 }
@@ -0,0 +1,93 @@
+use actix_web::rt::time::Instant;
+use actix_web::{delete, get, put, web, Responder};
+use actix_web_validator::{Json, Path, Query};
+use serde::Deserialize;
+use storage::content_manager::collection_meta_ops::CollectionMetaOperations;
+use storage::content_manager::collection_templates::{CollectionTemplate, TemplateOperation};
+use storage::dispatcher::Dispatcher;
+use validator::Validate;
+
+use crate::actix::api::collections_api::WaitTimeout;
+use crate::actix::auth::ActixAccess;
+use crate::actix::helpers::process_response;
+
+#[derive(Deserialize, Validate)]
+struct TemplatePath {
+    #[validate(length(min = 1, max = 255))]
+    template_name: String,
+}
+
+#[get("/templates")]
+async fn list_templates(
+    dispatcher: web::Data<Dispatcher>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = dispatcher.toc(&access).list_templates(&access).await;
+    process_response(response, timing)
+}
+
+#[get("/templates/{template_name}")]
+async fn get_template(
+    dispatcher: web::Data<Dispatcher>,
+    path: Path<TemplatePath>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = dispatcher
+        .toc(&access)
+        .get_template(&path.template_name, &access)
+        .await;
+    process_response(response, timing)
+}
+
+#[put("/templates/{template_name}")]
+async fn put_template(
+    dispatcher: web::Data<Dispatcher>,
+    path: Path<TemplatePath>,
+    template: Json<CollectionTemplate>,
+    Query(query): Query<WaitTimeout>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let operation = TemplateOperation::Put {
+        name: path.into_inner().template_name,
+        template: template.into_inner(),
+    };
+    let response = dispatcher
+        .submit_collection_meta_op(
+            CollectionMetaOperations::Templates(operation),
+            access,
+            query.timeout(),
+        )
+        .await;
+    process_response(response, timing)
+}
+
+#[delete("/templates/{template_name}")]
+async fn delete_template(
+    dispatcher: web::Data<Dispatcher>,
+    path: Path<TemplatePath>,
+    Query(query): Query<WaitTimeout>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let operation = TemplateOperation::Delete {
+        name: path.into_inner().template_name,
+    };
+    let response = dispatcher
+        .submit_collection_meta_op(
+            CollectionMetaOperations::Templates(operation),
+            access,
+            query.timeout(),
+        )
+        .await;
+    process_response(response, timing)
+}
+
+pub fn config_templates_api(cfg: &mut web::ServiceConfig) {
+    cfg.service(list_templates)
+        .service(get_template)
+        .service(put_template)
+        .service(delete_template);
+}
@@ -1,8 +1,16 @@
+use std::future::{ready, Ready};
+
+use actix_web::dev::Payload;
 use actix_web::rt::time::Instant;
-use actix_web::{delete, post, put, web, Responder};
+use actix_web::{delete, patch, post, put, web, FromRequest, HttpRequest, Responder};
 use actix_web_validator::{Json, Path, Query};
+use collection::common::idempotency::{parse_idempotency_key, IDEMPOTENCY_KEY_HEADER};
+use collection::operations::attribute_ops::SetAttributes;
 use collection::operations::payload_ops::{DeletePayload, SetPayload};
-use collection::operations::point_ops::{PointInsertOperations, PointsSelector, WriteOrdering};
+use collection::operations::point_ops::{
+    PointInsertOperations, PointsSelector, RestorePoints, WriteOrdering,
+};
+use collection::operations::types::CollectionError;
 use collection::operations::vector_ops::{DeleteVectors, UpdateVectors};
 use schemars::JsonSchema;
 use segment::json_path::{JsonPath, JsonPathInterface};
@@ -12,10 +20,13 @@ use validator::Validate;
 
 use super::CollectionPath;
 use crate::actix::auth::ActixAccess;
-use crate::actix::helpers::process_response;
+use crate::actix::helpers::{process_response, process_response_error, HttpError};
+use crate::actix::streaming_json::StreamingJson;
+use crate::common::inference::InferenceService;
 use crate::common::points::{
-    do_batch_update_points, do_clear_payload, do_create_index, do_delete_index, do_delete_payload,
-    do_delete_points, do_delete_vectors, do_overwrite_payload, do_set_payload, do_update_vectors,
+    do_batch_update_points, do_bulk_update_points, do_clear_payload, do_create_index,
+    do_delete_index, do_delete_payload, do_delete_points, do_delete_vectors, do_overwrite_payload,
+    do_patch_payload, do_restore_points, do_set_attributes, do_set_payload, do_update_vectors,
     do_upsert_points, CreateFieldIndex, UpdateOperations,
 };
 
@@ -32,16 +43,43 @@ pub struct UpdateParam {
     pub ordering: Option<WriteOrdering>,
 }
 
+/// Idempotency key of an update, sent in the `idempotency-key` header
+struct IdempotencyKey(Option<String>);
+
+impl FromRequest for IdempotencyKey {
+    type Error = HttpError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        let key = req
+            .headers()
+            .get(IDEMPOTENCY_KEY_HEADER)
+            .map(|value| {
+                let value = value.to_str().map_err(|_| {
+                    CollectionError::bad_input("Idempotency key must be a visible ASCII string")
+                })?;
+                parse_idempotency_key(value)
+            })
+            .transpose();
+        ready(key.map(IdempotencyKey).map_err(HttpError::from))
+    }
+}
+
 #[put("/collections/{name}/points")]
 async fn upsert_points(
     dispatcher: web::Data<Dispatcher>,
+    inference: web::Data<InferenceService>,
     collection: Path<CollectionPath>,
-    operation: Json<PointInsertOperations>,
+    operation: StreamingJson<PointInsertOperations>,
     params: Query<UpdateParam>,
+    IdempotencyKey(idempotency_key): IdempotencyKey,
     ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
-    let operation = operation.into_inner();
+    let mut operation = operation.into_inner();
+    if let Err(err) = inference.infer(&mut operation).await {
+        return process_response_error(err, timing);
+    }
     let wait = params.wait.unwrap_or(false);
     let ordering = params.ordering.unwrap_or_default();
 
@@ -50,6 +88,7 @@ async fn upsert_points(
         collection.into_inner().name,
         operation,
         None,
+        idempotency_key,
         None,
         wait,
         ordering,
@@ -65,6 +104,7 @@ async fn delete_points(
     collection: Path<CollectionPath>,
     operation: Json<PointsSelector>,
     params: Query<UpdateParam>,
+    IdempotencyKey(idempotency_key): IdempotencyKey,
     ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
@@ -77,6 +117,7 @@ async fn delete_points(
         collection.into_inner().name,
         operation,
         None,
+        idempotency_key,
         None,
         wait,
         ordering,
@@ -86,16 +127,45 @@ async fn delete_points(
     process_response(response, timing)
 }
 
+#[post("/collections/{name}/points/restore")]
+async fn restore_points(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<RestorePoints>,
+    params: Query<UpdateParam>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let wait = params.wait.unwrap_or(false);
+    let ordering = params.ordering.unwrap_or_default();
+
+    let response = do_restore_points(
+        dispatcher.toc(&access).clone(),
+        collection.into_inner().name,
+        request.into_inner(),
+        wait,
+        ordering,
+        access,
+    )
+    .await;
+    process_response(response, timing)
+}
+
 #[put("/collections/{name}/points/vectors")]
 async fn update_vectors(
     dispatcher: web::Data<Dispatcher>,
+    inference: web::Data<InferenceService>,
     collection: Path<CollectionPath>,
     operation: Json<UpdateVectors>,
     params: Query<UpdateParam>,
+    IdempotencyKey(idempotency_key): IdempotencyKey,
     ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
-    let operation = operation.into_inner();
+    let mut operation = operation.into_inner();
+    if let Err(err) = inference.infer(&mut operation).await {
+        return process_response_error(err, timing);
+    }
     let wait = params.wait.unwrap_or(false);
     let ordering = params.ordering.unwrap_or_default();
 
@@ -104,6 +174,7 @@ async fn update_vectors(
         collection.into_inner().name,
         operation,
         None,
+        idempotency_key,
         None,
         wait,
         ordering,
@@ -119,6 +190,7 @@ async fn delete_vectors(
     collection: Path<CollectionPath>,
     operation: Json<DeleteVectors>,
     params: Query<UpdateParam>,
+    IdempotencyKey(idempotency_key): IdempotencyKey,
     ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
@@ -131,6 +203,7 @@ async fn delete_vectors(
         collection.into_inner().name,
         operation,
         None,
+        idempotency_key,
         None,
         wait,
         ordering,
@@ -146,6 +219,7 @@ async fn set_payload(
     collection: Path<CollectionPath>,
     operation: Json<SetPayload>,
     params: Query<UpdateParam>,
+    IdempotencyKey(idempotency_key): IdempotencyKey,
     ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
@@ -158,6 +232,7 @@ async fn set_payload(
         collection.into_inner().name,
         operation,
         None,
+        idempotency_key,
         None,
         wait,
         ordering,
@@ -173,6 +248,7 @@ async fn overwrite_payload(
     collection: Path<CollectionPath>,
     operation: Json<SetPayload>,
     params: Query<UpdateParam>,
+    IdempotencyKey(idempotency_key): IdempotencyKey,
     ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
@@ -185,6 +261,36 @@ async fn overwrite_payload(
         collection.into_inner().name,
         operation,
         None,
+        idempotency_key,
+        None,
+        wait,
+        ordering,
+        access,
+    )
+    .await;
+    process_response(response, timing)
+}
+
+#[patch("/collections/{name}/points/payload")]
+async fn patch_payload(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    operation: Json<SetPayload>,
+    params: Query<UpdateParam>,
+    IdempotencyKey(idempotency_key): IdempotencyKey,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let operation = operation.into_inner();
+    let wait = params.wait.unwrap_or(false);
+    let ordering = params.ordering.unwrap_or_default();
+
+    let response = do_patch_payload(
+        dispatcher.toc(&access).clone(),
+        collection.into_inner().name,
+        operation,
+        None,
+        idempotency_key,
         None,
         wait,
         ordering,
@@ -200,6 +306,7 @@ async fn delete_payload(
     collection: Path<CollectionPath>,
     operation: Json<DeletePayload>,
     params: Query<UpdateParam>,
+    IdempotencyKey(idempotency_key): IdempotencyKey,
     ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
@@ -212,6 +319,7 @@ async fn delete_payload(
         collection.into_inner().name,
         operation,
         None,
+        idempotency_key,
         None,
         wait,
         ordering,
@@ -227,6 +335,7 @@ async fn clear_payload(
     collection: Path<CollectionPath>,
     operation: Json<PointsSelector>,
     params: Query<UpdateParam>,
+    IdempotencyKey(idempotency_key): IdempotencyKey,
     ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
@@ -239,6 +348,36 @@ async fn clear_payload(
         collection.into_inner().name,
         operation,
         None,
+        idempotency_key,
+        None,
+        wait,
+        ordering,
+        access,
+    )
+    .await;
+    process_response(response, timing)
+}
+
+#[put("/collections/{name}/points/attributes")]
+async fn set_attributes(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    operation: Json<SetAttributes>,
+    params: Query<UpdateParam>,
+    IdempotencyKey(idempotency_key): IdempotencyKey,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let operation = operation.into_inner();
+    let wait = params.wait.unwrap_or(false);
+    let ordering = params.ordering.unwrap_or_default();
+
+    let response = do_set_attributes(
+        dispatcher.toc(&access).clone(),
+        collection.into_inner().name,
+        operation,
+        None,
+        idempotency_key,
         None,
         wait,
         ordering,
@@ -251,13 +390,18 @@ async fn clear_payload(
 #[post("/collections/{name}/points/batch")]
 async fn update_batch(
     dispatcher: web::Data<Dispatcher>,
+    inference: web::Data<InferenceService>,
     collection: Path<CollectionPath>,
     operations: Json<UpdateOperations>,
     params: Query<UpdateParam>,
+    IdempotencyKey(idempotency_key): IdempotencyKey,
     ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
-    let operations = operations.into_inner();
+    let mut operations = operations.into_inner();
+    if let Err(err) = inference.infer(&mut operations).await {
+        return process_response_error(err, timing);
+    }
     let wait = params.wait.unwrap_or(false);
     let ordering = params.ordering.unwrap_or_default();
 
@@ -266,6 +410,7 @@ async fn update_batch(
         collection.into_inner().name,
         operations.operations,
         None,
+        idempotency_key,
         None,
         wait,
         ordering,
@@ -274,6 +419,40 @@ async fn update_batch(
     .await;
     process_response(response, timing)
 }
+
+#[post("/collections/{name}/points/bulk")]
+async fn update_bulk(
+    dispatcher: web::Data<Dispatcher>,
+    inference: web::Data<InferenceService>,
+    collection: Path<CollectionPath>,
+    operations: Json<UpdateOperations>,
+    params: Query<UpdateParam>,
+    IdempotencyKey(idempotency_key): IdempotencyKey,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let mut operations = operations.into_inner();
+    if let Err(err) = inference.infer(&mut operations).await {
+        return process_response_error(err, timing);
+    }
+    let wait = params.wait.unwrap_or(false);
+    let ordering = params.ordering.unwrap_or_default();
+
+    let response = do_bulk_update_points(
+        dispatcher.toc(&access).clone(),
+        collection.into_inner().name,
+        operations.operations,
+        None,
+        idempotency_key,
+        None,
+        wait,
+        ordering,
+        access,
+    )
+    .await;
+    process_response(response, timing)
+}
+
 #[put("/collections/{name}/index")]
 async fn create_field_index(
     dispatcher: web::Data<Dispatcher>,
@@ -331,13 +510,17 @@ async fn delete_field_index(
 pub fn config_update_api(cfg: &mut web::ServiceConfig) {
     cfg.service(upsert_points)
         .service(delete_points)
+        .service(restore_points)
         .service(update_vectors)
         .service(delete_vectors)
         .service(set_payload)
         .service(overwrite_payload)
+        .service(patch_payload)
         .service(delete_payload)
         .service(clear_payload)
+        .service(set_attributes)
         .service(create_field_index)
         .service(delete_field_index)
-        .service(update_batch);
+        .service(update_batch)
+        .service(update_bulk);
 }
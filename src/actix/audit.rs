@@ -0,0 +1,155 @@
+use std::future::{ready, Ready};
+use std::rc::Rc;
+use std::sync::Arc;
+
+use actix_web::dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::Method;
+use actix_web::{Error, HttpMessage};
+use chrono::Utc;
+use futures_util::future::LocalBoxFuture;
+
+use crate::common::audit::{AuditEntry, AuditLog, AuditTransport};
+use crate::common::auth::AuthSubject;
+
+/// Records write requests into the audit log, once they are handled
+pub struct Audit {
+    audit_log: Arc<AuditLog>,
+}
+
+impl Audit {
+    pub fn new(audit_log: Arc<AuditLog>) -> Self {
+        Self { audit_log }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for Audit
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = AuditMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(AuditMiddleware {
+            audit_log: self.audit_log.clone(),
+            service: Rc::new(service),
+        }))
+    }
+}
+
+pub struct AuditMiddleware<S> {
+    audit_log: Arc<AuditLog>,
+    service: Rc<S>,
+}
+
+/// Requests, which don't modify anything. Search-like requests use `POST` to pass their body.
+fn is_read_request(method: &Method, path: &str) -> bool {
+    if matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS) {
+        return true;
+    }
+    if method != Method::POST {
+        return false;
+    }
+
+    let Some((_collection_name, operation)) = path
+        .strip_prefix("/collections/")
+        .and_then(|path| path.split_once('/'))
+    else {
+        return false;
+    };
+
+    let mut parts = operation.split('/');
+    match (parts.next(), parts.next()) {
+        // Retrieve points by ids
+        (Some("points"), None) => true,
+        (Some("points"), Some(points_operation)) => matches!(
+            points_operation,
+            "count" | "discover" | "export" | "query" | "recommend" | "scroll" | "search"
+        ),
+        (Some("facet"), _) => true,
+        _ => false,
+    }
+}
+
+impl<S, B> Service<ServiceRequest> for AuditMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        if is_read_request(req.method(), req.path()) {
+            return Box::pin(self.service.call(req));
+        }
+
+        let operation = format!(
+            "{} {}",
+            req.method(),
+            req.match_pattern()
+                .unwrap_or_else(|| req.path().to_string()),
+        );
+        let subject = req
+            .extensions()
+            .get::<AuthSubject>()
+            .map(|subject| subject.0.clone());
+
+        let audit_log = self.audit_log.clone();
+        let service = self.service.clone();
+        Box::pin(async move {
+            let response = service.call(req).await?;
+
+            // Path parameters are only known after routing
+            let collection = response
+                .request()
+                .match_info()
+                .get("name")
+                .map(str::to_string);
+            let status = response.status();
+            audit_log.record(AuditEntry {
+                timestamp: Utc::now(),
+                subject,
+                transport: AuditTransport::Rest,
+                operation,
+                collection,
+                success: status.is_success(),
+                status: status.as_u16().to_string(),
+            });
+
+            Ok(response)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_read_request() {
+        let cases = [
+            (Method::GET, "/collections/c", true),
+            (Method::POST, "/collections/c/points", true),
+            (Method::POST, "/collections/c/points/search/batch", true),
+            (Method::POST, "/collections/c/facet", true),
+            (Method::PUT, "/collections/c/points", false),
+            (Method::POST, "/collections/c/points/delete", false),
+            (Method::POST, "/collections/c/points/payload", false),
+            (Method::POST, "/collections/aliases", false),
+            (Method::DELETE, "/collections/c", false),
+        ];
+        for (method, path, expected) in cases {
+            assert_eq!(is_read_request(&method, path), expected, "{method} {path}");
+        }
+    }
+}
@@ -121,12 +121,13 @@ where
                 .validate_request(|key| req.headers().get(key).and_then(|val| val.to_str().ok()))
                 .await
             {
-                Ok(access) => {
+                Ok((access, subject)) => {
                     let _previous = req.extensions_mut().insert::<Access>(access);
                     debug_assert!(
                         _previous.is_none(),
                         "Previous access object should not exist in the request"
                     );
+                    req.extensions_mut().insert(subject);
                     service.call(req).await
                 }
                 Err(e) => {
@@ -1,10 +1,12 @@
 use std::fmt::Debug;
 use std::future::Future;
+use std::time::Duration;
 
 use actix_web::rt::time::Instant;
 use actix_web::{http, HttpResponse, ResponseError};
 use api::grpc::models::{ApiResponse, ApiStatus};
-use collection::operations::types::CollectionError;
+use collection::operations::types::{CollectionError, PartialResponse};
+use common::hardware_counter::HardwareCounterCell;
 use serde::Serialize;
 use storage::content_manager::errors::StorageError;
 use tokio::task::JoinHandle;
@@ -14,10 +16,25 @@ pub fn accepted_response(timing: Instant) -> HttpResponse {
         result: None,
         status: ApiStatus::Accepted,
         time: timing.elapsed().as_secs_f64(),
+        usage: None,
+        timed_out: None,
+        shards: None,
     })
 }
 
 pub fn process_response<D>(response: Result<D, StorageError>, timing: Instant) -> HttpResponse
+where
+    D: Serialize,
+{
+    process_response_with_usage(response, timing, &HardwareCounterCell::disabled())
+}
+
+/// Same as [`process_response`], but also reports hardware usage of the request, if measured
+pub fn process_response_with_usage<D>(
+    response: Result<D, StorageError>,
+    timing: Instant,
+    hw_counter: &HardwareCounterCell,
+) -> HttpResponse
 where
     D: Serialize,
 {
@@ -26,6 +43,39 @@ where
             result: Some(res),
             status: ApiStatus::Ok,
             time: timing.elapsed().as_secs_f64(),
+            usage: hw_counter.usage().map(From::from),
+            timed_out: None,
+            shards: None,
+        }),
+        Err(err) => process_response_error(err, timing),
+    }
+}
+
+/// Same as [`process_response_with_usage`], but also reports whether any search was stopped at
+/// the explicit `timeout` of the request. Stopped searches respond with the points found so far.
+///
+/// Statuses of the shards are reported, if there are any, i.e. if read consistency is `partial`.
+pub fn process_search_response<D>(
+    response: Result<PartialResponse<D>, StorageError>,
+    timing: Instant,
+    timeout: Option<Duration>,
+    hw_counter: &HardwareCounterCell,
+) -> HttpResponse
+where
+    D: Serialize,
+{
+    match response {
+        Ok(PartialResponse {
+            value,
+            timed_out,
+            shards,
+        }) => HttpResponse::Ok().json(ApiResponse {
+            result: Some(value),
+            status: ApiStatus::Ok,
+            time: timing.elapsed().as_secs_f64(),
+            usage: hw_counter.usage().map(From::from),
+            timed_out: timeout.map(|_| timed_out),
+            shards: (!shards.is_empty()).then_some(shards),
         }),
         Err(err) => process_response_error(err, timing),
     }
@@ -49,6 +99,9 @@ pub fn process_response_error(err: StorageError, timing: Instant) -> HttpRespons
         result: None,
         status: ApiStatus::Error(error.to_string()),
         time: timing.elapsed().as_secs_f64(),
+        usage: None,
+        timed_out: None,
+        shards: None,
     })
 }
 
@@ -158,6 +211,7 @@ impl ResponseError for HttpError {
             StorageError::ChecksumMismatch { .. } => http::StatusCode::BAD_REQUEST,
             StorageError::Forbidden { .. } => http::StatusCode::FORBIDDEN,
             StorageError::PreconditionFailed { .. } => http::StatusCode::INTERNAL_SERVER_ERROR,
+            StorageError::RateLimitExceeded { .. } => http::StatusCode::TOO_MANY_REQUESTS,
         }
     }
 }
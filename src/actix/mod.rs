@@ -1,10 +1,12 @@
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
 pub mod actix_telemetry;
 pub mod api;
+mod audit;
 mod auth;
-mod certificate_helpers;
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
 pub mod helpers;
+mod rate_limiting;
+mod streaming_json;
 
 use std::io;
 use std::path::Path;
@@ -21,25 +23,38 @@ use collection::operations::validation;
 use storage::dispatcher::Dispatcher;
 use storage::rbac::Access;
 
+use crate::actix::api::api_keys_api::config_api_keys_api;
 use crate::actix::api::cluster_api::config_cluster_api;
+use crate::actix::api::cluster_metadata_api::config_cluster_metadata_api;
 use crate::actix::api::collections_api::config_collections_api;
-use crate::actix::api::count_api::count_points;
+use crate::actix::api::count_api::{count_distinct, count_points, field_stats, histogram};
 use crate::actix::api::debug_api::config_debugger_api;
 use crate::actix::api::discovery_api::config_discovery_api;
 use crate::actix::api::issues_api::config_issues_api;
 use crate::actix::api::query_api::config_query_api;
 use crate::actix::api::recommend_api::config_recommend_api;
-use crate::actix::api::retrieve_api::{get_point, get_points, scroll_points};
+use crate::actix::api::retrieve_api::{
+    export_points, get_point, get_point_history, get_points, scroll_points, scroll_points_stream,
+};
 use crate::actix::api::search_api::config_search_api;
 use crate::actix::api::service_api::config_service_api;
 use crate::actix::api::shards_api::config_shards_api;
 use crate::actix::api::snapshot_api::config_snapshots_api;
+use crate::actix::api::templates_api::config_templates_api;
 use crate::actix::api::update_api::config_update_api;
+use crate::actix::audit::Audit;
 use crate::actix::auth::{Auth, WhitelistItem};
+use crate::actix::rate_limiting::RateLimit;
+use crate::actix::streaming_json::StreamingJsonConfig;
+use crate::common::audit::AuditLog;
 use crate::common::auth::AuthKeys;
+use crate::common::certificate_helpers;
+use crate::common::config_reload::ConfigReloader;
 use crate::common::debugger::DebuggerState;
 use crate::common::health;
 use crate::common::http_client::HttpClient;
+use crate::common::inference::InferenceService;
+use crate::common::rate_limiting::RateLimiters;
 use crate::common::telemetry::TelemetryCollector;
 use crate::settings::{max_web_workers, Settings};
 use crate::tracing::LoggerHandle;
@@ -59,6 +74,9 @@ pub fn init(
     health_checker: Option<Arc<health::HealthChecker>>,
     settings: Settings,
     logger_handle: LoggerHandle,
+    config_reloader: Arc<ConfigReloader>,
+    rate_limiters: Option<Arc<RateLimiters>>,
+    audit_log: Option<Arc<AuditLog>>,
 ) -> io::Result<()> {
     actix_web::rt::System::new().block_on(async {
         let auth_keys = AuthKeys::try_create(
@@ -70,16 +88,25 @@ pub fn init(
             .upload_dir()
             .unwrap();
         let dispatcher_data = web::Data::from(dispatcher);
-        let actix_telemetry_collector = telemetry_collector
-            .lock()
-            .await
-            .actix_telemetry_collector
-            .clone();
+        let (actix_telemetry_collector, latency_telemetry_collector) = {
+            let telemetry_collector = telemetry_collector.lock().await;
+            (
+                telemetry_collector.actix_telemetry_collector.clone(),
+                telemetry_collector.latency_telemetry_collector.clone(),
+            )
+        };
         let debugger_state = web::Data::new(DebuggerState::from_settings(&settings));
         let telemetry_collector_data = web::Data::from(telemetry_collector);
         let logger_handle_data = web::Data::new(logger_handle);
+        let config_reloader_data = web::Data::from(config_reloader);
         let http_client = web::Data::new(HttpClient::from_settings(&settings)?);
+        let inference_service = web::Data::new(
+            InferenceService::from_settings(&settings)
+                .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?,
+        );
         let health_checker = web::Data::new(health_checker);
+        let audit_log_data = web::Data::new(audit_log.clone());
+        let service_config = web::Data::new(settings.service.clone());
         let static_folder = settings
             .service
             .static_content_dir
@@ -128,9 +155,24 @@ pub fn init(
             let validate_json_config = actix_web_validator::JsonConfig::default()
                 .limit(settings.service.max_request_size_mb * 1024 * 1024)
                 .error_handler(|err, rec| validation_error_handler("JSON body", err, rec));
+            let streaming_json_config = StreamingJsonConfig {
+                limit: settings
+                    .service
+                    .max_upsert_request_size_mb
+                    .unwrap_or(settings.service.max_request_size_mb)
+                    * 1024
+                    * 1024,
+            };
 
             let mut app = App::new()
+                // Audit is executed last, so it sees the authenticated subject and routed path
+                .wrap(ConditionEx::from_option(audit_log.clone().map(Audit::new)))
                 .wrap(Compress::default()) // Reads the `Accept-Encoding` header to negotiate which compression codec to use.
+                // Rate limiting is checked after authentication,
+                // so unauthenticated requests can't exhaust limits of a collection
+                .wrap(ConditionEx::from_option(
+                    rate_limiters.clone().map(RateLimit::new),
+                ))
                 // api_key middleware
                 // note: the last call to `wrap()` or `wrap_fn()` is executed first
                 .wrap(ConditionEx::from_option(auth_keys.as_ref().map(
@@ -149,16 +191,22 @@ pub fn init(
                 )
                 .wrap(actix_telemetry::ActixTelemetryTransform::new(
                     actix_telemetry_collector.clone(),
+                    latency_telemetry_collector.clone(),
                 ))
                 .app_data(dispatcher_data.clone())
                 .app_data(telemetry_collector_data.clone())
                 .app_data(logger_handle_data.clone())
+                .app_data(config_reloader_data.clone())
                 .app_data(http_client.clone())
+                .app_data(inference_service.clone())
                 .app_data(debugger_state.clone())
                 .app_data(health_checker.clone())
+                .app_data(audit_log_data.clone())
+                .app_data(service_config.clone())
                 .app_data(validate_path_config)
                 .app_data(validate_query_config)
                 .app_data(validate_json_config)
+                .app_data(streaming_json_config)
                 .app_data(TempFileConfig::default().directory(&upload_dir))
                 .app_data(MultipartFormConfig::default().total_limit(usize::MAX))
                 .service(index)
@@ -166,7 +214,10 @@ pub fn init(
                 .configure(config_snapshots_api)
                 .configure(config_update_api)
                 .configure(config_cluster_api)
+                .configure(config_cluster_metadata_api)
                 .configure(config_service_api)
+                .configure(config_api_keys_api)
+                .configure(config_templates_api)
                 .configure(config_search_api)
                 .configure(config_recommend_api)
                 .configure(config_discovery_api)
@@ -177,7 +228,13 @@ pub fn init(
                 // Ordering of services is important for correct path pattern matching
                 // See: <https://github.com/qdrant/qdrant/issues/3543>
                 .service(scroll_points)
+                .service(scroll_points_stream)
+                .service(export_points)
                 .service(count_points)
+                .service(count_distinct)
+                .service(histogram)
+                .service(field_stats)
+                .service(get_point_history)
                 .service(get_point)
                 .service(get_points);
 
@@ -258,6 +315,9 @@ fn validation_error_handler(
         result: None,
         status: ApiStatus::Error(msg),
         time: 0.0,
+        usage: None,
+        timed_out: None,
+        shards: None,
     });
     error::InternalError::from_response(err, response).into()
 }
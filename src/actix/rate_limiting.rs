@@ -0,0 +1,184 @@
+use std::future::{ready, Ready};
+use std::sync::Arc;
+
+use actix_web::body::{BoxBody, EitherBody};
+use actix_web::dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::{header, Method};
+use actix_web::{Error, HttpResponse};
+use api::grpc::models::{ApiResponse, ApiStatus};
+use futures_util::future::LocalBoxFuture;
+
+use crate::common::auth::extract_api_key;
+use crate::common::rate_limiting::{RateLimiters, RequestKind};
+
+/// Rejects point requests, exceeding rate limits of their collection or API key
+pub struct RateLimit {
+    rate_limiters: Arc<RateLimiters>,
+}
+
+impl RateLimit {
+    pub fn new(rate_limiters: Arc<RateLimiters>) -> Self {
+        Self { rate_limiters }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RateLimit
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<EitherBody<B, BoxBody>>, Error = Error>
+        + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B, BoxBody>>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = RateLimitMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RateLimitMiddleware {
+            rate_limiters: self.rate_limiters.clone(),
+            service: Arc::new(service),
+        }))
+    }
+}
+
+pub struct RateLimitMiddleware<S> {
+    rate_limiters: Arc<RateLimiters>,
+    service: Arc<S>,
+}
+
+/// Collection name and kind of point requests, other requests are not rate limited
+fn classify_request(method: &Method, path: &str) -> Option<(&str, RequestKind)> {
+    let (collection_name, operation) = path.strip_prefix("/collections/")?.split_once('/')?;
+
+    let kind = if let Some(points_operation) = operation.strip_prefix("points") {
+        let is_read = method == Method::GET
+            || (method == Method::POST
+                && matches!(
+                    points_operation.split('/').nth(1).unwrap_or_default(),
+                    "" | "count"
+                        | "discover"
+                        | "export"
+                        | "query"
+                        | "recommend"
+                        | "scroll"
+                        | "search"
+                ));
+        if is_read {
+            RequestKind::Read
+        } else {
+            RequestKind::Write
+        }
+    } else if operation == "index" || operation.starts_with("index/") {
+        RequestKind::Write
+    } else {
+        return None;
+    };
+
+    Some((collection_name, kind))
+}
+
+impl<S, B> Service<ServiceRequest> for RateLimitMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<EitherBody<B, BoxBody>>, Error = Error>
+        + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B, BoxBody>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let Some((collection_name, kind)) = classify_request(req.method(), req.path()) else {
+            return Box::pin(self.service.call(req));
+        };
+
+        let api_key = extract_api_key(|key| req.headers().get(key).and_then(|v| v.to_str().ok()));
+
+        match self
+            .rate_limiters
+            .check(Some(collection_name), api_key, kind)
+        {
+            Ok(()) => Box::pin(self.service.call(req)),
+            Err(rate_limited) => {
+                let resp = HttpResponse::TooManyRequests()
+                    .insert_header((header::RETRY_AFTER, rate_limited.retry_after_secs()))
+                    .json(ApiResponse::<()> {
+                        result: None,
+                        status: ApiStatus::Error(rate_limited.description),
+                        time: 0.0,
+                        usage: None,
+                        timed_out: None,
+                        shards: None,
+                    });
+                Box::pin(ready(Ok(req.into_response(resp).map_into_right_body())))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_request() {
+        let cases = [
+            (
+                Method::POST,
+                "/collections/c/points/search",
+                Some(RequestKind::Read),
+            ),
+            (
+                Method::POST,
+                "/collections/c/points/query/batch",
+                Some(RequestKind::Read),
+            ),
+            (
+                Method::POST,
+                "/collections/c/points",
+                Some(RequestKind::Read),
+            ),
+            (
+                Method::GET,
+                "/collections/c/points/1",
+                Some(RequestKind::Read),
+            ),
+            (
+                Method::PUT,
+                "/collections/c/points",
+                Some(RequestKind::Write),
+            ),
+            (
+                Method::POST,
+                "/collections/c/points/delete",
+                Some(RequestKind::Write),
+            ),
+            (
+                Method::PUT,
+                "/collections/c/points/payload",
+                Some(RequestKind::Write),
+            ),
+            (
+                Method::PUT,
+                "/collections/c/index",
+                Some(RequestKind::Write),
+            ),
+            (Method::GET, "/collections/c", None),
+            (Method::GET, "/collections/c/cluster", None),
+            (Method::GET, "/telemetry", None),
+        ];
+
+        for (method, path, expected) in cases {
+            let kind = classify_request(&method, path).map(|(collection, kind)| {
+                assert_eq!(collection, "c");
+                kind
+            });
+            assert_eq!(kind, expected, "{method} {path}");
+        }
+    }
+}
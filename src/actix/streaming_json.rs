@@ -0,0 +1,174 @@
+use std::io::{self, BufReader, Read};
+use std::ops::Deref;
+
+use actix_web::dev::{Decompress, Payload};
+use actix_web::error::JsonPayloadError;
+use actix_web::web::Bytes;
+use actix_web::{FromRequest, HttpMessage, HttpRequest};
+use futures::future::LocalBoxFuture;
+use futures::StreamExt;
+use serde::de::DeserializeOwned;
+use tokio::sync::mpsc;
+use validator::Validate;
+
+/// Number of body chunks, buffered between the network and the parser
+const CHUNKS_BUFFER: usize = 16;
+
+/// Limits of [`StreamingJson`] bodies
+#[derive(Clone, Copy, Debug)]
+pub struct StreamingJsonConfig {
+    /// Max size of the body, in bytes, after decompression
+    pub limit: usize,
+}
+
+/// JSON body extractor, which parses the body while it is being received
+///
+/// Unlike [`actix_web_validator::Json`], the raw body is never buffered as a whole, so peak
+/// memory of a large request is close to the size of the parsed value.
+/// The limit applies to the decompressed body, so a small compressed request can't expand
+/// into an arbitrary amount of data.
+pub struct StreamingJson<T>(pub T);
+
+impl<T> StreamingJson<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for StreamingJson<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> FromRequest for StreamingJson<T>
+where
+    T: DeserializeOwned + Validate + Send + 'static,
+{
+    type Error = actix_web::Error;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let req = req.clone();
+        let payload = Decompress::from_headers(payload.take(), req.headers());
+
+        Box::pin(async move {
+            let limit = req
+                .app_data::<StreamingJsonConfig>()
+                .map_or(usize::MAX, |config| config.limit);
+
+            let result = parse(&req, payload, limit).await.and_then(|value: T| {
+                value.validate()?;
+                Ok(value)
+            });
+
+            result
+                .map(StreamingJson)
+                .map_err(|err| super::validation_error_handler("JSON body", err, &req))
+        })
+    }
+}
+
+async fn parse<T>(
+    req: &HttpRequest,
+    mut payload: Decompress<Payload>,
+    limit: usize,
+) -> Result<T, actix_web_validator::Error>
+where
+    T: DeserializeOwned + Send + 'static,
+{
+    let is_json = req.mime_type().ok().flatten().map_or(false, |mime| {
+        mime.subtype().as_str() == "json" || mime.suffix().map(|s| s.as_str()) == Some("json")
+    });
+    if !is_json {
+        return Err(JsonPayloadError::ContentType.into());
+    }
+
+    if let Some(length) = content_length(req) {
+        if length > limit {
+            return Err(JsonPayloadError::OverflowKnownLength { length, limit }.into());
+        }
+    }
+
+    // Parsing is blocking, so it runs on a separate thread, which reads chunks on demand
+    let (sender, receiver) = mpsc::channel(CHUNKS_BUFFER);
+    let parser = actix_web::rt::task::spawn_blocking(move || {
+        serde_json::from_reader::<_, T>(BufReader::new(ChunksReader {
+            receiver,
+            chunk: Bytes::new(),
+        }))
+    });
+
+    let mut received = 0;
+    while let Some(chunk) = payload.next().await {
+        let chunk = chunk.map_err(JsonPayloadError::Payload)?;
+        received += chunk.len();
+        if received > limit {
+            return Err(JsonPayloadError::Overflow { limit }.into());
+        }
+        if sender.send(chunk).await.is_err() {
+            // Parser stopped early, it has an error for us
+            break;
+        }
+    }
+    drop(sender);
+
+    parser
+        .await
+        .map_err(|err| JsonPayloadError::Payload(io::Error::new(io::ErrorKind::Other, err).into()))?
+        .map_err(|err| JsonPayloadError::Deserialize(err).into())
+}
+
+fn content_length(req: &HttpRequest) -> Option<usize> {
+    req.headers()
+        .get(actix_web::http::header::CONTENT_LENGTH)?
+        .to_str()
+        .ok()?
+        .parse()
+        .ok()
+}
+
+/// Blocking reader of body chunks, received from the network
+struct ChunksReader {
+    receiver: mpsc::Receiver<Bytes>,
+    chunk: Bytes,
+}
+
+impl Read for ChunksReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.chunk.is_empty() {
+            match self.receiver.blocking_recv() {
+                Some(chunk) => self.chunk = chunk,
+                // End of body
+                None => return Ok(0),
+            }
+        }
+
+        let len = buf.len().min(self.chunk.len());
+        buf[..len].copy_from_slice(&self.chunk.split_to(len));
+        Ok(len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunks_reader() {
+        let (sender, receiver) = mpsc::channel(CHUNKS_BUFFER);
+        for chunk in [r#"{"a": [1, "#, "", r#"2, 3], "b""#, r#": "c"}"#] {
+            sender.try_send(Bytes::from(chunk)).unwrap();
+        }
+        drop(sender);
+
+        let reader = ChunksReader {
+            receiver,
+            chunk: Bytes::new(),
+        };
+        let value: serde_json::Value = serde_json::from_reader(reader).unwrap();
+        assert_eq!(value, serde_json::json!({"a": [1, 2, 3], "b": "c"}));
+    }
+}
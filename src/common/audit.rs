@@ -0,0 +1,300 @@
+use std::collections::VecDeque;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write as _};
+use std::net::UdpSocket;
+#[cfg(unix)]
+use std::os::unix::net::UnixDatagram;
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::thread;
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+const DEFAULT_MAX_MEMORY_ENTRIES: usize = 1000;
+
+/// Local syslog socket, used if no address is configured
+const SYSLOG_SOCKET: &str = "/dev/log";
+
+/// Facility `security/authorization` (10) and severity `informational` (6)
+const SYSLOG_PRIORITY: u8 = 10 * 8 + 6;
+
+/// Configuration of the audit log of write operations
+#[derive(Debug, Deserialize, Clone)]
+pub struct AuditConfig {
+    /// Number of latest entries, kept in memory and available at `/audit`
+    #[serde(default = "default_max_memory_entries")]
+    pub max_memory_entries: usize,
+    /// Where entries are written to, in addition to memory
+    #[serde(default)]
+    pub sinks: Vec<AuditSinkConfig>,
+}
+
+const fn default_max_memory_entries() -> usize {
+    DEFAULT_MAX_MEMORY_ENTRIES
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AuditSinkConfig {
+    /// Append entries to a file, one JSON object per line
+    File { path: PathBuf },
+    /// Send entries to syslog, either over UDP to `host:port` or to the local socket
+    Syslog {
+        #[serde(default)]
+        address: Option<String>,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditTransport {
+    Rest,
+    Grpc,
+}
+
+/// Write operation, performed through the API
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq)]
+pub struct AuditEntry {
+    pub timestamp: DateTime<Utc>,
+    /// Who performed the operation: `api_key`, `read_only_api_key` or `jwt:{subject}`.
+    /// Absent if authentication is disabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    pub transport: AuditTransport,
+    /// REST endpoint, like `PUT /collections/{name}/points`, or gRPC method, like `qdrant.Points/Upsert`
+    pub operation: String,
+    /// Collection, the operation was performed on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collection: Option<String>,
+    /// If the operation succeeded
+    pub success: bool,
+    /// HTTP status code or gRPC status code name
+    pub status: String,
+}
+
+/// Filter of entries, returned by `/audit`
+#[derive(Debug, Deserialize, JsonSchema, Default)]
+pub struct AuditQuery {
+    /// Max number of entries to return, latest first
+    pub limit: Option<usize>,
+    pub collection: Option<String>,
+    pub subject: Option<String>,
+}
+
+/// Destination of audit entries
+pub trait AuditSink: Send {
+    fn write(&mut self, entry: &AuditEntry) -> io::Result<()>;
+}
+
+struct FileSink {
+    file: File,
+}
+
+impl AuditSink for FileSink {
+    fn write(&mut self, entry: &AuditEntry) -> io::Result<()> {
+        let mut line = serde_json::to_vec(entry)?;
+        line.push(b'\n');
+        self.file.write_all(&line)
+    }
+}
+
+enum SyslogSink {
+    Udp(UdpSocket),
+    #[cfg(unix)]
+    Unix(UnixDatagram),
+}
+
+impl SyslogSink {
+    fn connect(address: Option<&str>) -> io::Result<Self> {
+        match address {
+            Some(address) => {
+                let socket = UdpSocket::bind("0.0.0.0:0")?;
+                socket.connect(address)?;
+                Ok(Self::Udp(socket))
+            }
+            #[cfg(unix)]
+            None => {
+                let socket = UnixDatagram::unbound()?;
+                socket.connect(SYSLOG_SOCKET)?;
+                Ok(Self::Unix(socket))
+            }
+            #[cfg(not(unix))]
+            None => Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "syslog address is required on this platform",
+            )),
+        }
+    }
+}
+
+impl AuditSink for SyslogSink {
+    fn write(&mut self, entry: &AuditEntry) -> io::Result<()> {
+        let message = format!(
+            "<{SYSLOG_PRIORITY}>qdrant: {}",
+            serde_json::to_string(entry)?
+        );
+        match self {
+            SyslogSink::Udp(socket) => socket.send(message.as_bytes())?,
+            #[cfg(unix)]
+            SyslogSink::Unix(socket) => socket.send(message.as_bytes())?,
+        };
+        Ok(())
+    }
+}
+
+fn open_sink(config: &AuditSinkConfig) -> io::Result<Box<dyn AuditSink>> {
+    match config {
+        AuditSinkConfig::File { path } => {
+            let file = OpenOptions::new().create(true).append(true).open(path)?;
+            Ok(Box::new(FileSink { file }))
+        }
+        AuditSinkConfig::Syslog { address } => {
+            Ok(Box::new(SyslogSink::connect(address.as_deref())?))
+        }
+    }
+}
+
+/// Append-only log of write operations, shared by REST and gRPC servers.
+///
+/// Latest entries are kept in memory, and all of them are written to the configured sinks by
+/// a background thread, so requests never wait for a slow sink.
+pub struct AuditLog {
+    max_memory_entries: usize,
+    entries: Mutex<VecDeque<AuditEntry>>,
+    sender: Option<mpsc::Sender<AuditEntry>>,
+}
+
+impl AuditLog {
+    pub fn new(config: &AuditConfig) -> io::Result<Self> {
+        let sinks = config
+            .sinks
+            .iter()
+            .map(open_sink)
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let sender = if sinks.is_empty() {
+            None
+        } else {
+            let (sender, receiver) = mpsc::channel();
+            thread::Builder::new()
+                .name("audit-log".to_string())
+                .spawn(move || write_to_sinks(receiver, sinks))?;
+            Some(sender)
+        };
+
+        Ok(Self {
+            max_memory_entries: config.max_memory_entries,
+            entries: Mutex::new(VecDeque::with_capacity(config.max_memory_entries)),
+            sender,
+        })
+    }
+
+    pub fn record(&self, entry: AuditEntry) {
+        if let Some(sender) = &self.sender {
+            if sender.send(entry.clone()).is_err() {
+                log::error!("Audit log writer is stopped, entry is only kept in memory");
+            }
+        }
+
+        if self.max_memory_entries == 0 {
+            return;
+        }
+        let mut entries = self.entries.lock();
+        if entries.len() == self.max_memory_entries {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+
+    /// Recent entries, matching the query, latest first
+    pub fn query(&self, query: &AuditQuery) -> Vec<AuditEntry> {
+        let entries = self.entries.lock();
+        entries
+            .iter()
+            .rev()
+            .filter(|entry| {
+                query.collection.as_ref().map_or(true, |collection| {
+                    entry.collection.as_ref() == Some(collection)
+                })
+            })
+            .filter(|entry| {
+                query
+                    .subject
+                    .as_ref()
+                    .map_or(true, |subject| entry.subject.as_ref() == Some(subject))
+            })
+            .take(query.limit.unwrap_or(usize::MAX))
+            .cloned()
+            .collect()
+    }
+}
+
+fn write_to_sinks(receiver: mpsc::Receiver<AuditEntry>, mut sinks: Vec<Box<dyn AuditSink>>) {
+    for entry in receiver {
+        for sink in &mut sinks {
+            if let Err(err) = sink.write(&entry) {
+                log::error!("Failed to write audit log entry: {err}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(collection: &str, subject: Option<&str>) -> AuditEntry {
+        AuditEntry {
+            timestamp: Utc::now(),
+            subject: subject.map(str::to_string),
+            transport: AuditTransport::Rest,
+            operation: "PUT /collections/{name}/points".to_string(),
+            collection: Some(collection.to_string()),
+            success: true,
+            status: "200".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_audit_log() {
+        let dir = tempfile::Builder::new().prefix("audit").tempdir().unwrap();
+        let path = dir.path().join("audit.log");
+        let config = AuditConfig {
+            max_memory_entries: 2,
+            sinks: vec![AuditSinkConfig::File { path: path.clone() }],
+        };
+        let audit_log = AuditLog::new(&config).unwrap();
+
+        audit_log.record(entry("a", Some("api_key")));
+        audit_log.record(entry("b", Some("jwt:user")));
+        audit_log.record(entry("a", None));
+
+        // Only the latest entries are kept in memory
+        let entries = audit_log.query(&AuditQuery::default());
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].subject, None);
+
+        let query = AuditQuery {
+            subject: Some("jwt:user".to_string()),
+            ..Default::default()
+        };
+        let entries = audit_log.query(&query);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].collection.as_deref(), Some("b"));
+
+        // All entries are written to the file, once the writer is stopped
+        drop(audit_log);
+        let mut lines = 0;
+        for _ in 0..100 {
+            lines = std::fs::read_to_string(&path).unwrap().lines().count();
+            if lines == 3 {
+                break;
+            }
+            thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert_eq!(lines, 3);
+    }
+}
@@ -9,6 +9,10 @@ pub struct Claims {
     /// Expiration time (seconds since UNIX epoch)
     pub exp: Option<u64>,
 
+    /// Subject of the token, recorded in the audit log
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sub: Option<String>,
+
     #[serde(default = "default_access")]
     pub access: Access,
 
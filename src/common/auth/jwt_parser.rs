@@ -53,7 +53,7 @@ impl JwtParser {
 
 #[cfg(test)]
 mod tests {
-    use segment::types::ValueVariants;
+    use segment::types::{Condition, FieldCondition, Filter, Match, ValueVariants};
     use storage::rbac::{
         Access, CollectionAccess, CollectionAccessList, CollectionAccessMode, GlobalAccessMode,
         PayloadConstraint,
@@ -77,6 +77,7 @@ mod tests {
             .as_secs();
         let claims = Claims {
             exp: Some(exp),
+            sub: Some("user".to_string()),
             access: Access::Collection(CollectionAccessList(vec![CollectionAccess {
                 collection: "collection".to_string(),
                 access: CollectionAccessMode::ReadWrite,
@@ -92,6 +93,12 @@ mod tests {
                     .into_iter()
                     .collect(),
                 )),
+                filter: Some(Filter::new_must(Condition::Field(
+                    FieldCondition::new_match(
+                        "tenant".parse().unwrap(),
+                        Match::new_value(ValueVariants::Keyword("tenant_1".to_string())),
+                    ),
+                ))),
             }])),
             value_exists: None,
         };
@@ -114,6 +121,7 @@ mod tests {
 
         let mut claims = Claims {
             exp: Some(exp),
+            sub: None,
             access: Access::Global(GlobalAccessMode::Read),
             value_exists: None,
         };
@@ -140,6 +148,7 @@ mod tests {
     fn test_invalid_token() {
         let claims = Claims {
             exp: None,
+            sub: None,
             access: Access::Global(GlobalAccessMode::Read),
             value_exists: None,
         };
@@ -17,6 +17,27 @@ pub mod jwt_parser;
 
 pub const HTTP_HEADER_API_KEY: &str = "api-key";
 
+/// API key or JWT, provided with the request either in `api-key` header or as a bearer token
+pub fn extract_api_key<'a>(get_header: impl Fn(&'a str) -> Option<&'a str>) -> Option<&'a str> {
+    get_header(HTTP_HEADER_API_KEY)
+        .or_else(|| get_header("authorization").and_then(|v| v.strip_prefix("Bearer ")))
+}
+
+/// Who made the request, as recorded in the audit log:
+/// `api_key`, `read_only_api_key`, a managed key, like `api_key:name`,
+/// or `jwt` with the subject of the token, like `jwt:user`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AuthSubject(pub String);
+
+impl AuthSubject {
+    fn jwt(sub: Option<String>) -> Self {
+        match sub {
+            Some(sub) => Self(format!("jwt:{sub}")),
+            None => Self("jwt".to_string()),
+        }
+    }
+}
+
 /// The API keys used for auth
 #[derive(Clone)]
 pub struct AuthKeys {
@@ -29,7 +50,7 @@ pub struct AuthKeys {
     /// A JWT parser, based on the read_write key
     jwt_parser: Option<JwtParser>,
 
-    /// Table of content, needed to do stateful validation of JWT
+    /// Table of content, needed to do stateful validation of JWT and to find managed API keys
     toc: Arc<TableOfContent>,
 }
 
@@ -74,26 +95,38 @@ impl AuthKeys {
     pub async fn validate_request<'a>(
         &self,
         get_header: impl Fn(&'a str) -> Option<&'a str>,
-    ) -> Result<Access, AuthError> {
-        let Some(key) = get_header(HTTP_HEADER_API_KEY)
-            .or_else(|| get_header("authorization").and_then(|v| v.strip_prefix("Bearer ")))
-        else {
+    ) -> Result<(Access, AuthSubject), AuthError> {
+        let Some(key) = extract_api_key(get_header) else {
             return Err(AuthError::Unauthorized(
                 "Must provide an API key or an Authorization bearer token".to_string(),
             ));
         };
 
         if self.can_write(key) {
-            return Ok(Access::full("Read-write access by key"));
+            return Ok((
+                Access::full("Read-write access by key"),
+                AuthSubject("api_key".to_string()),
+            ));
         }
 
         if self.can_read(key) {
-            return Ok(Access::full_ro("Read-only access by key"));
+            return Ok((
+                Access::full_ro("Read-only access by key"),
+                AuthSubject("read_only_api_key".to_string()),
+            ));
+        }
+
+        if let Some(api_key) = self.toc.find_api_key(key).await {
+            return Ok((
+                api_key.access(),
+                AuthSubject(format!("api_key:{}", api_key.name)),
+            ));
         }
 
         if let Some(claims) = self.jwt_parser.as_ref().and_then(|p| p.decode(key)) {
             let Claims {
                 exp: _, // already validated on decoding
+                sub,
                 access,
                 value_exists,
             } = claims?;
@@ -102,7 +135,7 @@ impl AuthKeys {
                 self.validate_value_exists(&value_exists).await?;
             }
 
-            return Ok(access);
+            return Ok((access, AuthSubject::jwt(sub)));
         }
 
         Err(AuthError::Unauthorized(
@@ -118,6 +151,8 @@ impl AuthKeys {
             with_payload: Some(WithPayloadInterface::Bool(false)),
             with_vector: WithVector::Bool(false),
             order_by: None,
+            cursor: None,
+            include_deleted: None,
         };
 
         let res = self
@@ -0,0 +1,398 @@
+use std::fmt::{self, Debug};
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+
+use parking_lot::RwLock;
+use rustls::client::danger::HandshakeSignatureValid;
+use rustls::client::VerifierBuilderError;
+use rustls::pki_types::{CertificateDer, UnixTime};
+use rustls::server::danger::{ClientCertVerified, ClientCertVerifier};
+use rustls::server::{ClientHello, ResolvesServerCert, WebPkiClientVerifier};
+use rustls::sign::CertifiedKey;
+use rustls::{
+    crypto, DigitallySignedStruct, DistinguishedName, RootCertStore, ServerConfig, SignatureScheme,
+};
+use rustls_pemfile::Item;
+
+use crate::settings::{Settings, TlsConfig};
+
+type Result<T> = std::result::Result<T, Error>;
+
+/// How often TLS files are checked for changes
+const FILES_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Modification time and size of each watched file, changes when any of the files is replaced
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FilesVersion(Vec<Option<(SystemTime, u64)>>);
+
+impl FilesVersion {
+    fn read(paths: &[String]) -> Self {
+        Self(
+            paths
+                .iter()
+                .map(|path| {
+                    let metadata = fs::metadata(path).ok()?;
+                    Some((metadata.modified().ok()?, metadata.len()))
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Value, loaded from TLS files on disk
+struct Loaded<T> {
+    value: T,
+    /// Last time the value was loaded
+    loaded_at: Instant,
+    /// Last time the files were checked for changes
+    checked_at: Instant,
+    /// Version of the files, the value was loaded from
+    version: FilesVersion,
+}
+
+/// Value, which is reloaded when its files change on disk, or when the TTL is reached
+///
+/// If reloading fails, an error is logged and the old value is kept, so a partially written or
+/// invalid file never breaks new connections.
+struct ReloadingFiles<T> {
+    /// Watched files
+    paths: Vec<String>,
+
+    /// Reload unconditionally after this time
+    ttl: Option<Duration>,
+
+    check_interval: Duration,
+
+    load: Box<dyn Fn() -> Result<T> + Send + Sync>,
+
+    current: RwLock<Loaded<T>>,
+}
+
+impl<T: Clone> ReloadingFiles<T> {
+    fn new(
+        paths: Vec<String>,
+        ttl: Option<Duration>,
+        load: impl Fn() -> Result<T> + Send + Sync + 'static,
+    ) -> Result<Self> {
+        let version = FilesVersion::read(&paths);
+        let value = load()?;
+        let now = Instant::now();
+
+        Ok(Self {
+            paths,
+            ttl,
+            check_interval: FILES_CHECK_INTERVAL,
+            load: Box::new(load),
+            current: RwLock::new(Loaded {
+                value,
+                loaded_at: now,
+                checked_at: now,
+                version,
+            }),
+        })
+    }
+
+    fn is_expired(&self, current: &Loaded<T>) -> bool {
+        self.ttl
+            .map_or(false, |ttl| current.loaded_at.elapsed() >= ttl)
+    }
+
+    fn is_check_due(&self, current: &Loaded<T>) -> bool {
+        current.checked_at.elapsed() >= self.check_interval || self.is_expired(current)
+    }
+
+    /// Get current value, reloading it first if needed
+    fn get(&self, name: &str) -> T {
+        // Get read-only lock to the value. If nothing is due, return the value.
+        let current = self.current.read();
+        if !self.is_check_due(&current) {
+            return current.value.clone();
+        }
+        drop(current);
+
+        // If a check is due:
+        // - get read-write lock to the value
+        // - *re-check that it is due* (to avoid reloading multiple times from concurrent threads)
+        // - reload if files changed or TTL is expired
+        let mut current = self.current.write();
+        if self.is_check_due(&current) {
+            current.checked_at = Instant::now();
+
+            let version = FilesVersion::read(&self.paths);
+            if version != current.version || self.is_expired(&current) {
+                match (self.load)() {
+                    Ok(value) => {
+                        log::info!("Reloaded TLS {name}");
+                        *current = Loaded {
+                            value,
+                            loaded_at: Instant::now(),
+                            checked_at: Instant::now(),
+                            version,
+                        };
+                    }
+                    Err(err) => {
+                        log::error!("Failed to reload TLS {name}, keeping current: {err}");
+                    }
+                }
+            }
+        }
+
+        current.value.clone()
+    }
+}
+
+impl<T> Debug for ReloadingFiles<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReloadingFiles")
+            .field("paths", &self.paths)
+            .field("ttl", &self.ttl)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A server certificate resolver, which rotates the certificate when the certificate or key
+/// files change, or when the TTL is reached
+#[derive(Debug)]
+struct RotatingCertificateResolver {
+    key: ReloadingFiles<Arc<CertifiedKey>>,
+}
+
+impl RotatingCertificateResolver {
+    pub fn new(tls_config: TlsConfig, ttl: Option<Duration>) -> Result<Self> {
+        let paths = vec![tls_config.cert.clone(), tls_config.key.clone()];
+        let key = ReloadingFiles::new(paths, ttl, move || load_certified_key(&tls_config))?;
+        Ok(Self { key })
+    }
+}
+
+impl ResolvesServerCert for RotatingCertificateResolver {
+    fn resolve(&self, _client_hello: ClientHello<'_>) -> Option<Arc<CertifiedKey>> {
+        Some(self.key.get("server certificate"))
+    }
+}
+
+/// A client certificate verifier, which reloads the CA bundle when its file changes
+///
+/// A new bundle is only used if it is valid, otherwise clients keep being verified against the
+/// previous one.
+#[derive(Debug)]
+struct RotatingClientCertVerifier {
+    verifier: ReloadingFiles<Arc<dyn ClientCertVerifier>>,
+}
+
+impl RotatingClientCertVerifier {
+    pub fn new(tls_config: &TlsConfig) -> Result<Self> {
+        let ca_cert = tls_config.ca_cert.clone();
+        let verifier = ReloadingFiles::new(vec![ca_cert.clone()], None, move || {
+            load_client_cert_verifier(&ca_cert)
+        })?;
+        Ok(Self { verifier })
+    }
+
+    fn current(&self) -> Arc<dyn ClientCertVerifier> {
+        self.verifier.get("client CA certificate")
+    }
+}
+
+impl ClientCertVerifier for RotatingClientCertVerifier {
+    fn root_hint_subjects(&self) -> &[DistinguishedName] {
+        // Hints can't be borrowed from a verifier which may be replaced at any time.
+        // Without hints, clients choose the certificate to send themselves.
+        &[]
+    }
+
+    fn verify_client_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        now: UnixTime,
+    ) -> std::result::Result<ClientCertVerified, rustls::Error> {
+        self.current()
+            .verify_client_cert(end_entity, intermediates, now)
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        self.current().verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        self.current().verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.current().supported_verify_schemes()
+    }
+}
+
+/// Load TLS configuration and construct certified key.
+fn load_certified_key(tls_config: &TlsConfig) -> Result<Arc<CertifiedKey>> {
+    // Load certificates
+    let certs: Vec<CertificateDer> = with_buf_read(&tls_config.cert, |rd| {
+        rustls_pemfile::read_all(rd).collect::<io::Result<Vec<_>>>()
+    })?
+    .into_iter()
+    .filter_map(|item| match item {
+        Item::X509Certificate(data) => Some(data),
+        _ => None,
+    })
+    .collect();
+    if certs.is_empty() {
+        return Err(Error::NoServerCert);
+    }
+
+    // Load private key
+    let private_key_item =
+        with_buf_read(&tls_config.key, rustls_pemfile::read_one)?.ok_or(Error::NoPrivateKey)?;
+    let private_key = match private_key_item {
+        Item::Pkcs1Key(pkey) => rustls_pki_types::PrivateKeyDer::from(pkey),
+        Item::Pkcs8Key(pkey) => rustls_pki_types::PrivateKeyDer::from(pkey),
+        Item::Sec1Key(pkey) => rustls_pki_types::PrivateKeyDer::from(pkey),
+        _ => return Err(Error::InvalidPrivateKey),
+    };
+    let signing_key = crypto::ring::sign::any_supported_type(&private_key).map_err(Error::Sign)?;
+
+    // Construct certified key
+    let certified_key = CertifiedKey::new(certs, signing_key);
+    Ok(Arc::new(certified_key))
+}
+
+/// Load CA bundle and construct client certificate verifier.
+fn load_client_cert_verifier(ca_cert: &str) -> Result<Arc<dyn ClientCertVerifier>> {
+    let mut root_cert_store = RootCertStore::empty();
+    let ca_certs: Vec<CertificateDer> =
+        with_buf_read(ca_cert, |rd| rustls_pemfile::certs(rd).collect())?;
+    root_cert_store.add_parsable_certificates(ca_certs);
+    WebPkiClientVerifier::builder(root_cert_store.into())
+        .build()
+        .map_err(Error::ClientCertVerifier)
+}
+
+/// Generate a server configuration with TLS, which picks up changed TLS files without restart
+///
+/// If `verify_client` is set, client certificates are verified against the CA file.
+fn tls_server_config(tls_config: &TlsConfig, verify_client: bool) -> Result<ServerConfig> {
+    let config = ServerConfig::builder();
+
+    // Verify client CA or not
+    let config = if verify_client {
+        let client_cert_verifier = RotatingClientCertVerifier::new(tls_config)?;
+        config.with_client_cert_verifier(Arc::new(client_cert_verifier))
+    } else {
+        config.with_no_client_auth()
+    };
+
+    // Configure rotating certificate resolver
+    let ttl = match tls_config.cert_ttl {
+        None | Some(0) => None,
+        Some(seconds) => Some(Duration::from_secs(seconds)),
+    };
+    let cert_resolver = RotatingCertificateResolver::new(tls_config.clone(), ttl)?;
+    let config = config.with_cert_resolver(Arc::new(cert_resolver));
+
+    Ok(config)
+}
+
+/// Generate an actix server configuration with TLS
+///
+/// Uses TLS settings as configured in configuration by user.
+pub fn actix_tls_server_config(settings: &Settings) -> Result<ServerConfig> {
+    let tls_config = settings
+        .tls
+        .as_ref()
+        .ok_or_else(Settings::tls_config_is_undefined_error)
+        .map_err(Error::Io)?;
+
+    tls_server_config(tls_config, settings.service.verify_https_client_certificate)
+}
+
+/// Generate a tonic server configuration with TLS
+///
+/// Client certificates are verified for intra-cluster communication only.
+pub fn tonic_tls_server_config(
+    tls_config: &TlsConfig,
+    verify_client: bool,
+) -> Result<ServerConfig> {
+    let mut config = tls_server_config(tls_config, verify_client)?;
+    // gRPC requires HTTP/2
+    config.alpn_protocols = vec![b"h2".to_vec()];
+    Ok(config)
+}
+
+fn with_buf_read<T>(path: &str, f: impl FnOnce(&mut dyn BufRead) -> io::Result<T>) -> Result<T> {
+    let file = File::open(path).map_err(|err| Error::OpenFile(err, path.into()))?;
+    let mut reader = BufReader::new(file);
+    let dyn_reader: &mut dyn BufRead = &mut reader;
+    f(dyn_reader).map_err(|err| Error::ReadFile(err, path.into()))
+}
+
+/// TLS errors.
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("TLS file could not be opened: {1}")]
+    OpenFile(#[source] io::Error, String),
+    #[error("TLS file could not be read: {1}")]
+    ReadFile(#[source] io::Error, String),
+    #[error("general TLS IO error")]
+    Io(#[source] io::Error),
+    #[error("no server certificate found")]
+    NoServerCert,
+    #[error("no private key found")]
+    NoPrivateKey,
+    #[error("invalid private key")]
+    InvalidPrivateKey,
+    #[error("TLS signing error")]
+    Sign(#[source] rustls::Error),
+    #[error("client certificate verification")]
+    ClientCertVerifier(#[source] VerifierBuilderError),
+}
+
+impl From<Error> for io::Error {
+    fn from(err: Error) -> Self {
+        io::Error::new(io::ErrorKind::Other, err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reload_on_file_change() {
+        let dir = tempfile::Builder::new().prefix("tls").tempdir().unwrap();
+        let path = dir.path().join("cert.pem");
+        fs::write(&path, "first").unwrap();
+
+        let path_str = path.to_str().unwrap().to_string();
+        let load_path = path.clone();
+        let mut reloading = ReloadingFiles::new(vec![path_str], None, move || {
+            let content = fs::read_to_string(&load_path).map_err(Error::Io)?;
+            if content.is_empty() {
+                return Err(Error::NoServerCert);
+            }
+            Ok(content)
+        })
+        .unwrap();
+        reloading.check_interval = Duration::ZERO;
+
+        assert_eq!(reloading.get("test"), "first");
+
+        fs::write(&path, "second version").unwrap();
+        assert_eq!(reloading.get("test"), "second version");
+
+        // Invalid file keeps the previous value
+        fs::write(&path, "").unwrap();
+        assert_eq!(reloading.get("test"), "second version");
+    }
+}
@@ -1,17 +1,32 @@
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use std::time::Duration;
 
 use api::grpc::models::{CollectionDescription, CollectionsResponse};
 use api::grpc::qdrant::CollectionExists;
+use chrono::{DateTime, Utc};
+use collection::collection::freeze::CollectionFreezeStatus;
+use collection::collection::health::CollectionHealth;
+use collection::collection_manager::point_history::PointVersion;
+use collection::common::collection_stats::CollectionStats;
+use collection::common::index_advisor::IndexRecommendation;
+use collection::common::slow_query_log::SlowQueryEntry;
+use collection::common::warmup::WarmupProgress;
 use collection::config::ShardingMethod;
 use collection::operations::cluster_ops::{
     AbortTransferOperation, ClusterOperations, DropReplicaOperation, MoveShardOperation,
     ReplicateShardOperation, RestartTransfer, RestartTransferOperation, StartResharding,
 };
+use collection::operations::config_diff::{HnswConfigDiff, QuantizationConfigDiff};
+use collection::operations::config_impact::ConfigChangeImpact;
+use collection::operations::segment_details::ShardSegmentsInfo;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::snapshot_ops::SnapshotDescription;
 use collection::operations::types::{
-    AliasDescription, CollectionClusterInfo, CollectionInfo, CollectionsAliasesResponse,
+    AliasDescription, CollectionClusterInfo, CollectionConsistency, CollectionDiskUsage,
+    CollectionInfo, CollectionsAliasesResponse, PayloadSchemaReport, RecallEvaluation,
+    RecallEvaluationRequest, SearchTuningReport, SearchTuningRequest, VectorDiagnosticsReport,
+    VectorParamsDiff, VectorsConfigDiff,
 };
 use collection::shards::replica_set;
 use collection::shards::resharding::ReshardKey;
@@ -19,10 +34,11 @@ use collection::shards::shard::{PeerId, ShardId, ShardsPlacement};
 use collection::shards::transfer::{ShardTransfer, ShardTransferKey, ShardTransferRestart};
 use itertools::Itertools;
 use rand::prelude::SliceRandom;
+use segment::types::{PointIdType, SeqNumberType};
 use storage::content_manager::collection_meta_ops::ShardTransferOperations::{Abort, Start};
 use storage::content_manager::collection_meta_ops::{
     CollectionMetaOperations, CreateShardKey, DropShardKey, ReshardingOperation,
-    ShardTransferOperations, UpdateCollectionOperation,
+    ShardTransferOperations, UpdateCollection, UpdateCollectionOperation,
 };
 use storage::content_manager::errors::StorageError;
 use storage::content_manager::toc::TableOfContent;
@@ -129,15 +145,15 @@ pub async fn do_list_collection_aliases(
 ) -> Result<CollectionsAliasesResponse, StorageError> {
     let collection_pass =
         access.check_collection_access(collection_name, AccessRequirements::new())?;
-    let aliases: Vec<AliasDescription> = toc
-        .collection_aliases(&collection_pass, &access)
-        .await?
-        .into_iter()
-        .map(|alias| AliasDescription {
+    let mut aliases = Vec::new();
+    for alias in toc.collection_aliases(&collection_pass, &access).await? {
+        let filter = toc.alias_filter(&alias).await;
+        aliases.push(AliasDescription {
             alias_name: alias,
             collection_name: collection_name.to_string(),
-        })
-        .collect();
+            filter,
+        });
+    }
     Ok(CollectionsAliasesResponse { aliases })
 }
 
@@ -187,6 +203,255 @@ pub async fn do_get_collection_cluster(
     Ok(collection.cluster_info(toc.this_peer_id).await?)
 }
 
+pub async fn do_get_slow_queries(
+    toc: &TableOfContent,
+    access: Access,
+    name: &str,
+) -> Result<Vec<SlowQueryEntry>, StorageError> {
+    let collection_pass =
+        access.check_collection_access(name, AccessRequirements::new().whole())?;
+    let collection = toc.get_collection(&collection_pass).await?;
+    Ok(collection.slow_queries())
+}
+
+pub async fn do_get_index_recommendations(
+    toc: &TableOfContent,
+    access: Access,
+    name: &str,
+) -> Result<Vec<IndexRecommendation>, StorageError> {
+    let collection_pass =
+        access.check_collection_access(name, AccessRequirements::new().whole())?;
+    let collection = toc.get_collection(&collection_pass).await?;
+    Ok(collection.index_recommendations())
+}
+
+pub async fn do_get_warmup_progress(
+    toc: &TableOfContent,
+    access: Access,
+    name: &str,
+) -> Result<WarmupProgress, StorageError> {
+    let collection_pass =
+        access.check_collection_access(name, AccessRequirements::new().whole())?;
+    let collection = toc.get_collection(&collection_pass).await?;
+    Ok(collection.warmup_progress())
+}
+
+pub async fn do_get_collection_health(
+    toc: &TableOfContent,
+    access: Access,
+    name: &str,
+) -> Result<CollectionHealth, StorageError> {
+    let collection_pass = access.check_collection_access(name, AccessRequirements::new())?;
+    let collection = toc.get_collection(&collection_pass).await?;
+    Ok(collection.health().await)
+}
+
+pub async fn do_get_collection_stats(
+    toc: &TableOfContent,
+    access: Access,
+    name: &str,
+) -> Result<CollectionStats, StorageError> {
+    let collection_pass =
+        access.check_collection_access(name, AccessRequirements::new().whole())?;
+    let collection = toc.get_collection(&collection_pass).await?;
+    Ok(collection.stats().await)
+}
+
+pub async fn do_get_collection_disk_usage(
+    toc: &TableOfContent,
+    access: Access,
+    name: &str,
+) -> Result<CollectionDiskUsage, StorageError> {
+    let collection_pass =
+        access.check_collection_access(name, AccessRequirements::new().whole())?;
+    let collection = toc.get_collection(&collection_pass).await?;
+    Ok(collection.disk_usage().await?)
+}
+
+pub async fn do_get_collection_freeze(
+    toc: &TableOfContent,
+    access: Access,
+    name: &str,
+) -> Result<CollectionFreezeStatus, StorageError> {
+    let collection_pass =
+        access.check_collection_access(name, AccessRequirements::new().whole())?;
+    let collection = toc.get_collection(&collection_pass).await?;
+    Ok(collection.freeze_status().await)
+}
+
+pub async fn do_freeze_collection(
+    toc: &TableOfContent,
+    access: Access,
+    name: &str,
+    timeout: Duration,
+) -> Result<CollectionFreezeStatus, StorageError> {
+    let collection_pass =
+        access.check_collection_access(name, AccessRequirements::new().manage().whole())?;
+    let collection = toc.get_collection(&collection_pass).await?;
+    Ok(collection.freeze(timeout).await?)
+}
+
+pub async fn do_unfreeze_collection(
+    toc: &TableOfContent,
+    access: Access,
+    name: &str,
+) -> Result<CollectionFreezeStatus, StorageError> {
+    let collection_pass =
+        access.check_collection_access(name, AccessRequirements::new().manage().whole())?;
+    let collection = toc.get_collection(&collection_pass).await?;
+    Ok(collection.unfreeze().await?)
+}
+
+pub async fn do_check_collection_consistency(
+    toc: &TableOfContent,
+    access: Access,
+    name: &str,
+    buckets: usize,
+    repair: bool,
+) -> Result<CollectionConsistency, StorageError> {
+    let collection_pass =
+        access.check_collection_access(name, AccessRequirements::new().write().manage().whole())?;
+    let collection = toc.get_collection(&collection_pass).await?;
+    Ok(collection.check_consistency(buckets, repair, false).await?)
+}
+
+pub async fn do_estimate_collection_update(
+    toc: &TableOfContent,
+    access: Access,
+    name: &str,
+    update: UpdateCollection,
+) -> Result<ConfigChangeImpact, StorageError> {
+    let collection_pass =
+        access.check_collection_access(name, AccessRequirements::new().write().manage().whole())?;
+    toc.estimate_collection_update(&collection_pass, update)
+        .await
+}
+
+pub async fn do_get_point_history(
+    toc: &TableOfContent,
+    access: Access,
+    name: &str,
+    point_id: PointIdType,
+    version: Option<SeqNumberType>,
+    timestamp: Option<DateTime<Utc>>,
+) -> Result<Vec<PointVersion>, StorageError> {
+    // Versions are not checked against payload constraints of the access
+    let collection_pass =
+        access.check_collection_access(name, AccessRequirements::new().whole())?;
+    let collection = toc.get_collection(&collection_pass).await?;
+    Ok(collection
+        .point_history(point_id, version, timestamp)
+        .await?)
+}
+
+/// Fields of payloads, found in a random sample of points, with suggested indexes
+pub async fn do_infer_payload_schema(
+    toc: &TableOfContent,
+    access: Access,
+    name: &str,
+    sample: usize,
+) -> Result<PayloadSchemaReport, StorageError> {
+    let collection_pass =
+        access.check_collection_access(name, AccessRequirements::new().whole())?;
+    let collection = toc.get_collection(&collection_pass).await?;
+    Ok(collection
+        .infer_payload_schema(sample, None, &ShardSelectorInternal::All)
+        .await?)
+}
+
+/// Norms, duplicates and intrinsic dimensionality of vectors, found in a sample of points
+pub async fn do_get_vector_diagnostics(
+    toc: &TableOfContent,
+    access: Access,
+    name: &str,
+    sample: usize,
+) -> Result<VectorDiagnosticsReport, StorageError> {
+    let collection_pass =
+        access.check_collection_access(name, AccessRequirements::new().whole())?;
+    let collection = toc.get_collection(&collection_pass).await?;
+    Ok(collection
+        .vector_diagnostics(sample, None, &ShardSelectorInternal::All)
+        .await?)
+}
+
+pub async fn do_evaluate_recall(
+    toc: &TableOfContent,
+    access: Access,
+    name: &str,
+    request: RecallEvaluationRequest,
+) -> Result<RecallEvaluation, StorageError> {
+    let collection_pass =
+        access.check_collection_access(name, AccessRequirements::new().whole())?;
+    let collection = toc.get_collection(&collection_pass).await?;
+    Ok(collection.evaluate_recall(request).await?)
+}
+
+/// Sweep index and search params on a sample of points, and optionally apply `m` and
+/// quantization of the chosen settings to the vector config through consensus
+pub async fn do_tune_search(
+    dispatcher: &Dispatcher,
+    access: Access,
+    name: &str,
+    request: SearchTuningRequest,
+) -> Result<SearchTuningReport, StorageError> {
+    let apply = request.apply;
+    let requirements = if apply {
+        AccessRequirements::new().write().manage().whole()
+    } else {
+        AccessRequirements::new().whole()
+    };
+    let collection_pass = access.check_collection_access(name, requirements)?;
+    let toc = dispatcher.toc(&access);
+    let collection = toc.get_collection(&collection_pass).await?;
+    let temp_dir = toc.optional_temp_or_storage_temp_path()?;
+    let mut report = collection.tune_search(request, &temp_dir).await?;
+
+    let Some(chosen) = &report.chosen else {
+        return Ok(report);
+    };
+    if !apply {
+        return Ok(report);
+    }
+    let vector_diff = VectorParamsDiff {
+        hnsw_config: Some(HnswConfigDiff {
+            m: Some(chosen.m),
+            ..Default::default()
+        }),
+        quantization_config: Some(
+            chosen
+                .quantization
+                .clone()
+                .map_or_else(QuantizationConfigDiff::new_disabled, Into::into),
+        ),
+        on_disk: None,
+    };
+    let mut operation = UpdateCollectionOperation::new_empty(name.to_string());
+    operation.update_collection.vectors = Some(VectorsConfigDiff(BTreeMap::from([(
+        report.vector.clone(),
+        vector_diff,
+    )])));
+    dispatcher
+        .submit_collection_meta_op(
+            CollectionMetaOperations::UpdateCollection(operation),
+            access,
+            None,
+        )
+        .await?;
+    report.applied = true;
+    Ok(report)
+}
+
+pub async fn do_get_collection_segments(
+    toc: &TableOfContent,
+    access: Access,
+    name: &str,
+) -> Result<Vec<ShardSegmentsInfo>, StorageError> {
+    let collection_pass =
+        access.check_collection_access(name, AccessRequirements::new().whole())?;
+    let collection = toc.get_collection(&collection_pass).await?;
+    Ok(collection.segments_info().await)
+}
+
 pub async fn do_update_collection_cluster(
     dispatcher: &Dispatcher,
     collection_name: String,
@@ -0,0 +1,162 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use ::common::io_throttling::BackgroundIo;
+use schemars::JsonSchema;
+use segment::problems::UnindexedField;
+use serde::Serialize;
+use storage::content_manager::errors::StorageError;
+use tokio::sync::Mutex;
+use validator::Validate as _;
+
+use crate::common::rate_limiting::{RateLimiters, RateLimitsConfig};
+use crate::settings::Settings;
+use crate::tracing::LoggerHandle;
+
+/// Settings, which are applied without restarting the node
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct RuntimeConfig {
+    /// Log level of the default logger
+    pub log_level: Option<String>,
+    /// Disk write limit of segment optimizations in megabytes per second
+    pub optimizer_io_limit_mb_per_sec: Option<usize>,
+    /// Disk write limit of snapshots in megabytes per second
+    pub snapshot_io_limit_mb_per_sec: Option<usize>,
+    /// Disk read limit of outgoing shard transfers in megabytes per second
+    pub shard_transfer_io_limit_mb_per_sec: Option<usize>,
+    /// Duration of a query in seconds, after which unindexed fields are reported as issues
+    pub slow_query_secs: Option<f32>,
+    /// Limits of request rates per collection
+    pub rate_limits: RateLimitsConfig,
+}
+
+impl RuntimeConfig {
+    fn from_settings(settings: &Settings) -> Self {
+        let performance = &settings.storage.performance;
+        Self {
+            log_level: settings
+                .logger
+                .with_top_level_directive(settings.log_level.clone())
+                .default
+                .log_level,
+            optimizer_io_limit_mb_per_sec: performance.optimizer_io_limit_mb_per_sec,
+            snapshot_io_limit_mb_per_sec: performance.snapshot_io_limit_mb_per_sec,
+            shard_transfer_io_limit_mb_per_sec: performance.shard_transfer_io_limit_mb_per_sec,
+            slow_query_secs: settings.service.slow_query_secs,
+            rate_limits: settings.service.rate_limits.clone().unwrap_or_default(),
+        }
+    }
+
+    fn validate(&self) -> Result<(), StorageError> {
+        if let Some(log_level) = &self.log_level {
+            tracing_subscriber::EnvFilter::builder()
+                .with_regex(false)
+                .parse(log_level)
+                .map_err(|err| {
+                    StorageError::bad_input(format!("Invalid log level {log_level}: {err}"))
+                })?;
+        }
+        if self
+            .slow_query_secs
+            .is_some_and(|secs| !secs.is_finite() || secs < 0.0)
+        {
+            return Err(StorageError::bad_input(
+                "slow_query_secs must be a non-negative number",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Applies changes of runtime-tunable settings from the config files, on `SIGHUP` or through
+/// the API. Changes of other settings require a restart and are ignored.
+pub struct ConfigReloader {
+    config_path: Option<String>,
+    logger_handle: LoggerHandle,
+    rate_limiters: Arc<RateLimiters>,
+    /// Currently applied config, also serializes reloads
+    current: Mutex<RuntimeConfig>,
+}
+
+impl ConfigReloader {
+    pub fn new(
+        config_path: Option<String>,
+        settings: &Settings,
+        logger_handle: LoggerHandle,
+        rate_limiters: Arc<RateLimiters>,
+    ) -> Self {
+        Self {
+            config_path,
+            logger_handle,
+            rate_limiters,
+            current: Mutex::new(RuntimeConfig::from_settings(settings)),
+        }
+    }
+
+    pub async fn config(&self) -> RuntimeConfig {
+        self.current.lock().await.clone()
+    }
+
+    /// Read the config files again and apply the changed runtime-tunable settings.
+    ///
+    /// Nothing is applied if the new config is invalid.
+    pub async fn reload(&self) -> Result<RuntimeConfig, StorageError> {
+        let settings = Settings::new(self.config_path.clone())
+            .map_err(|err| StorageError::bad_input(format!("Can't load config: {err}")))?;
+        settings
+            .validate()
+            .map_err(|err| StorageError::bad_input(format!("Invalid config: {err}")))?;
+        let new = RuntimeConfig::from_settings(&settings);
+        new.validate()?;
+
+        let mut current = self.current.lock().await;
+
+        if new.log_level != current.log_level {
+            let mut logger_config = self.logger_handle.get_config().await;
+            logger_config.default.log_level = new.log_level.clone();
+            self.logger_handle
+                .update_config(logger_config)
+                .await
+                .map_err(|err| StorageError::service_error(err.to_string()))?;
+        }
+
+        let mb_per_sec = |limit: Option<usize>| limit.map(|mb| mb as u64 * 1024 * 1024);
+        BackgroundIo::Optimization.set_limit(mb_per_sec(new.optimizer_io_limit_mb_per_sec));
+        BackgroundIo::Snapshot.set_limit(mb_per_sec(new.snapshot_io_limit_mb_per_sec));
+        BackgroundIo::ShardTransfer.set_limit(mb_per_sec(new.shard_transfer_io_limit_mb_per_sec));
+
+        UnindexedField::set_slow_query_threshold(new.slow_query_secs.map(Duration::from_secs_f32));
+
+        if new.rate_limits != current.rate_limits {
+            self.rate_limiters.set_config(new.rate_limits.clone());
+        }
+
+        if new != *current {
+            log::info!("Applied changes of reloaded config");
+        }
+        *current = new.clone();
+        Ok(new)
+    }
+
+    /// Reload the config on each `SIGHUP`
+    #[cfg(unix)]
+    pub async fn reload_on_sighup(self: Arc<Self>) {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let mut hangups = match signal(SignalKind::hangup()) {
+            Ok(hangups) => hangups,
+            Err(err) => {
+                log::error!(
+                    "Can't listen for SIGHUP, config reload is only available via API: {err}"
+                );
+                return;
+            }
+        };
+        while hangups.recv().await.is_some() {
+            log::info!("Received SIGHUP, reloading config");
+            if let Err(err) = self.reload().await {
+                log::error!("Failed to reload config: {err}");
+            }
+        }
+    }
+}
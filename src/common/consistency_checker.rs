@@ -0,0 +1,64 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use collection::common::consistency_check::ConsistencyCheckConfig;
+use storage::dispatcher::Dispatcher;
+use storage::rbac::Access;
+
+const FULL_ACCESS: Access = Access::full("Consistency checker");
+
+/// Periodically compare the replicas of the shards, which have an active replica on this peer
+///
+/// Each shard is checked by its active peer with the lowest id. Divergent points are reported in
+/// the log and, if enabled, re-synced.
+pub async fn run_consistency_checks(dispatcher: Arc<Dispatcher>, config: ConsistencyCheckConfig) {
+    let Some(interval_sec) = config.interval_sec else {
+        return;
+    };
+    let interval = Duration::from_secs(interval_sec.max(1));
+    loop {
+        tokio::time::sleep(interval).await;
+        check_collections(&dispatcher, &config).await;
+    }
+}
+
+async fn check_collections(dispatcher: &Dispatcher, config: &ConsistencyCheckConfig) {
+    let toc = dispatcher.toc(&FULL_ACCESS);
+    for collection_pass in toc.all_collections(&FULL_ACCESS).await {
+        let Ok(collection) = toc.get_collection(&collection_pass).await else {
+            // Collection was deleted in the meantime
+            continue;
+        };
+        let collection_name = collection_pass.name();
+
+        let report = match collection
+            .check_consistency(config.buckets, config.repair, true)
+            .await
+        {
+            Ok(report) => report,
+            Err(err) => {
+                log::warn!("Failed to check consistency of collection {collection_name}: {err}");
+                continue;
+            }
+        };
+
+        for shard in report.shards {
+            if shard.divergent_points == 0 {
+                continue;
+            }
+            let sample = shard
+                .divergent_points_sample
+                .iter()
+                .map(|point| point.id.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            log::warn!(
+                "Replicas of shard {} of collection {collection_name} on peers {:?} have {} divergent points, repaired {}, e.g. {sample}",
+                shard.shard_id,
+                shard.peers,
+                shard.divergent_points,
+                shard.repaired_points,
+            );
+        }
+    }
+}
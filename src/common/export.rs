@@ -0,0 +1,501 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use api::rest::{DenseVector, MultiDenseVector, Record, ShardKeySelector, Vector, VectorStruct};
+use arrow_array::builder::{Float32Builder, ListBuilder, StringBuilder, UInt32Builder};
+use arrow_array::{ArrayRef, RecordBatch};
+use arrow_ipc::writer::StreamWriter;
+use arrow_schema::{ArrowError, DataType, Field, Schema, SchemaRef};
+use collection::config::CollectionParams;
+use collection::operations::consistency_params::ReadConsistency;
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::{ScrollRequestInternal, VectorsConfig};
+use futures::Stream;
+use schemars::JsonSchema;
+use segment::json_path::JsonPath;
+use segment::types::{
+    ExtendedPointId, Filter, PayloadContainer, PayloadSelector, PayloadSelectorProject,
+    WithPayloadInterface, WithVector,
+};
+use serde::{Deserialize, Serialize};
+use sparse::common::sparse_vector::SparseVector;
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::TableOfContent;
+use storage::rbac::Access;
+use validator::{Validate, ValidationError};
+
+use crate::common::collections::do_get_collection;
+
+/// Content type of the Arrow IPC streaming format
+pub const ARROW_STREAM_CONTENT_TYPE: &str = "application/vnd.apache.arrow.stream";
+
+const DEFAULT_EXPORT_BATCH_SIZE: usize = 1000;
+
+/// Export points of the collection as a stream of Arrow record batches
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(rename_all = "snake_case")]
+#[validate(schema(function = "validate_export_payload"))]
+pub struct ExportRequest {
+    /// Export only points which satisfy this conditions. If not provided - all points.
+    #[validate]
+    pub filter: Option<Filter>,
+
+    /// Which vectors to export. Default is true.
+    #[serde(alias = "with_vectors")]
+    pub with_vector: Option<WithVector>,
+
+    /// Payload fields to export, each in a separate `payload.<field>` column.
+    /// If not provided - whole payload is exported in a single `payload` column.
+    /// Values are encoded as JSON strings.
+    pub payload_fields: Option<Vec<JsonPath>>,
+
+    /// Payload values to export, each in a separate `payload.<key>` column.
+    /// Keys are mapped to the paths of the values in the original payload,
+    /// so nested values can be renamed and flattened before being sent.
+    /// Can't be used together with `payload_fields`.
+    pub payload_projection: Option<BTreeMap<String, JsonPath>>,
+
+    /// Number of points in a single record batch. Default: 1000
+    #[validate(range(min = 1))]
+    pub batch_size: Option<usize>,
+
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    pub shard_key: Option<ShardKeySelector>,
+}
+
+fn validate_export_payload(request: &ExportRequest) -> Result<(), ValidationError> {
+    if request.payload_fields.is_some() && request.payload_projection.is_some() {
+        let mut error = ValidationError::new("payload_fields");
+        error.message = Some("payload_fields and payload_projection are mutually exclusive".into());
+        return Err(error);
+    }
+    Ok(())
+}
+
+/// Stream points of the collection in the Arrow IPC streaming format.
+///
+/// The first chunk contains the schema, each following chunk contains a single record batch.
+/// Points are read page by page, so the whole collection is never loaded into memory.
+pub async fn do_export_points(
+    toc: Arc<TableOfContent>,
+    collection_name: String,
+    request: ExportRequest,
+    read_consistency: Option<ReadConsistency>,
+    access: Access,
+) -> Result<impl Stream<Item = Result<Vec<u8>, StorageError>>, StorageError> {
+    let ExportRequest {
+        filter,
+        with_vector,
+        payload_fields,
+        payload_projection,
+        batch_size,
+        shard_key,
+    } = request;
+
+    let with_vector = with_vector.unwrap_or(WithVector::Bool(true));
+    let batch_size = batch_size.unwrap_or(DEFAULT_EXPORT_BATCH_SIZE);
+    let shard_selection = match shard_key {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => ShardSelectorInternal::from(shard_keys),
+    };
+
+    let collection_info = do_get_collection(&toc, access.clone(), &collection_name, None).await?;
+
+    // Projection is applied by the shards, so only the selected values are transferred
+    let with_payload = match (payload_fields, payload_projection) {
+        (_, Some(projection)) => PayloadSelectorProject::new(projection).into(),
+        (Some(fields), None) => WithPayloadInterface::Fields(fields),
+        (None, None) => WithPayloadInterface::Bool(true),
+    };
+
+    let builder =
+        RecordBatchBuilder::new(&collection_info.config.params, &with_vector, &with_payload);
+    let writer =
+        StreamWriter::try_new(Vec::new(), &builder.schema).map_err(arrow_to_storage_error)?;
+
+    let state = ExportState {
+        builder,
+        writer,
+        offset: None,
+        is_finished: false,
+    };
+
+    let stream = futures::stream::try_unfold(state, move |mut state| {
+        let toc = toc.clone();
+        let collection_name = collection_name.clone();
+        let scroll_request = ScrollRequestInternal {
+            offset: state.offset,
+            limit: Some(batch_size),
+            filter: filter.clone(),
+            with_payload: Some(with_payload.clone()),
+            with_vector: with_vector.clone(),
+            order_by: None,
+            cursor: None,
+            include_deleted: None,
+        };
+        let shard_selection = shard_selection.clone();
+        let access = access.clone();
+
+        async move {
+            if state.is_finished {
+                return Ok(None);
+            }
+
+            let scroll_result = toc
+                .scroll(
+                    &collection_name,
+                    scroll_request,
+                    read_consistency,
+                    shard_selection,
+                    access,
+                )
+                .await?;
+
+            if !scroll_result.points.is_empty() {
+                let batch = state
+                    .builder
+                    .build(&scroll_result.points)
+                    .map_err(arrow_to_storage_error)?;
+                state.writer.write(&batch).map_err(arrow_to_storage_error)?;
+            }
+
+            state.offset = scroll_result.next_page_offset;
+            if state.offset.is_none() {
+                state.writer.finish().map_err(arrow_to_storage_error)?;
+                state.is_finished = true;
+            }
+
+            let chunk = std::mem::take(state.writer.get_mut());
+            Ok(Some((chunk, state)))
+        }
+    });
+
+    Ok(stream)
+}
+
+struct ExportState {
+    builder: RecordBatchBuilder,
+    writer: StreamWriter<Vec<u8>>,
+    offset: Option<ExtendedPointId>,
+    is_finished: bool,
+}
+
+fn arrow_to_storage_error(err: ArrowError) -> StorageError {
+    StorageError::service_error(format!("Failed to encode Arrow record batch: {err}"))
+}
+
+/// Converts pages of points into Arrow record batches with a fixed schema.
+///
+/// Schema is derived from the collection config, so all batches of the export are compatible
+/// even if some points have no value for a vector or payload field.
+struct RecordBatchBuilder {
+    schema: SchemaRef,
+    ids: StringBuilder,
+    vectors: Vec<VectorColumn>,
+    payload: PayloadColumns,
+}
+
+enum VectorColumn {
+    Dense {
+        name: String,
+        builder: ListBuilder<Float32Builder>,
+    },
+    MultiDense {
+        name: String,
+        builder: ListBuilder<ListBuilder<Float32Builder>>,
+    },
+    Sparse {
+        name: String,
+        indices: ListBuilder<UInt32Builder>,
+        values: ListBuilder<Float32Builder>,
+    },
+}
+
+enum PayloadColumns {
+    Whole(StringBuilder),
+    Fields(Vec<(JsonPath, StringBuilder)>),
+    Projected(Vec<(String, StringBuilder)>),
+}
+
+impl RecordBatchBuilder {
+    fn new(
+        params: &CollectionParams,
+        with_vector: &WithVector,
+        with_payload: &WithPayloadInterface,
+    ) -> Self {
+        let mut fields = vec![Field::new("id", DataType::Utf8, false)];
+        let mut vectors = Vec::new();
+
+        let dense_vectors = match &params.vectors {
+            VectorsConfig::Single(params) => vec![(String::new(), params)],
+            VectorsConfig::Multi(params) => params
+                .iter()
+                .map(|(name, params)| (name.clone(), params))
+                .collect(),
+        };
+
+        for (name, vector_params) in dense_vectors {
+            if !is_vector_exported(with_vector, &name) {
+                continue;
+            }
+
+            let column_name = vector_column_name(&name);
+            if vector_params.multivec_config.is_some() {
+                fields.push(Field::new(
+                    column_name,
+                    list_of(list_of(DataType::Float32)),
+                    true,
+                ));
+                vectors.push(VectorColumn::MultiDense {
+                    name,
+                    builder: ListBuilder::new(ListBuilder::new(Float32Builder::new())),
+                });
+            } else {
+                fields.push(Field::new(column_name, list_of(DataType::Float32), true));
+                vectors.push(VectorColumn::Dense {
+                    name,
+                    builder: ListBuilder::new(Float32Builder::new()),
+                });
+            }
+        }
+
+        for name in params
+            .sparse_vectors
+            .iter()
+            .flat_map(|sparse| sparse.keys())
+        {
+            if !is_vector_exported(with_vector, name) {
+                continue;
+            }
+
+            let column_name = vector_column_name(name);
+            fields.push(Field::new(
+                format!("{column_name}.indices"),
+                list_of(DataType::UInt32),
+                true,
+            ));
+            fields.push(Field::new(
+                format!("{column_name}.values"),
+                list_of(DataType::Float32),
+                true,
+            ));
+            vectors.push(VectorColumn::Sparse {
+                name: name.clone(),
+                indices: ListBuilder::new(UInt32Builder::new()),
+                values: ListBuilder::new(Float32Builder::new()),
+            });
+        }
+
+        let payload = match with_payload {
+            WithPayloadInterface::Fields(payload_fields) => PayloadColumns::Fields(
+                payload_fields
+                    .iter()
+                    .map(|path| {
+                        fields.push(Field::new(format!("payload.{path}"), DataType::Utf8, true));
+                        (path.clone(), StringBuilder::new())
+                    })
+                    .collect(),
+            ),
+            WithPayloadInterface::Selector(PayloadSelector::Project(selector)) => {
+                PayloadColumns::Projected(
+                    selector
+                        .project
+                        .keys()
+                        .map(|key| {
+                            fields.push(Field::new(format!("payload.{key}"), DataType::Utf8, true));
+                            (key.clone(), StringBuilder::new())
+                        })
+                        .collect(),
+                )
+            }
+            WithPayloadInterface::Bool(_) | WithPayloadInterface::Selector(_) => {
+                fields.push(Field::new("payload", DataType::Utf8, true));
+                PayloadColumns::Whole(StringBuilder::new())
+            }
+        };
+
+        Self {
+            schema: Arc::new(Schema::new(fields)),
+            ids: StringBuilder::new(),
+            vectors,
+            payload,
+        }
+    }
+
+    /// Build a record batch from the given points. Builders are reset and can be reused.
+    fn build(&mut self, points: &[Record]) -> Result<RecordBatch, ArrowError> {
+        for point in points {
+            self.ids.append_value(point.id.to_string());
+
+            for column in &mut self.vectors {
+                column.append(point.vector.as_ref());
+            }
+
+            match &mut self.payload {
+                PayloadColumns::Whole(builder) => match &point.payload {
+                    Some(payload) => builder.append_value(to_json(payload)?),
+                    None => builder.append_null(),
+                },
+                PayloadColumns::Fields(fields) => {
+                    for (path, builder) in fields {
+                        let values = point
+                            .payload
+                            .as_ref()
+                            .map(|payload| payload.get_value(path))
+                            .unwrap_or_default();
+                        match values.as_slice() {
+                            [] => builder.append_null(),
+                            [value] => builder.append_value(to_json(value)?),
+                            values => builder.append_value(to_json(&values)?),
+                        }
+                    }
+                }
+                PayloadColumns::Projected(fields) => {
+                    for (key, builder) in fields {
+                        match point
+                            .payload
+                            .as_ref()
+                            .and_then(|payload| payload.0.get(key))
+                        {
+                            Some(value) => builder.append_value(to_json(value)?),
+                            None => builder.append_null(),
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut columns: Vec<ArrayRef> = vec![Arc::new(self.ids.finish())];
+        for column in &mut self.vectors {
+            column.finish_into(&mut columns);
+        }
+        match &mut self.payload {
+            PayloadColumns::Whole(builder) => columns.push(Arc::new(builder.finish())),
+            PayloadColumns::Fields(fields) => {
+                for (_, builder) in fields {
+                    columns.push(Arc::new(builder.finish()));
+                }
+            }
+            PayloadColumns::Projected(fields) => {
+                for (_, builder) in fields {
+                    columns.push(Arc::new(builder.finish()));
+                }
+            }
+        }
+
+        RecordBatch::try_new(self.schema.clone(), columns)
+    }
+}
+
+impl VectorColumn {
+    fn append(&mut self, vectors: Option<&VectorStruct>) {
+        match self {
+            VectorColumn::Dense { name, builder } => match get_dense(vectors, name) {
+                Some(vector) => {
+                    builder.values().append_slice(vector);
+                    builder.append(true);
+                }
+                None => builder.append_null(),
+            },
+            VectorColumn::MultiDense { name, builder } => match get_multi_dense(vectors, name) {
+                Some(vectors) => {
+                    for vector in vectors {
+                        builder.values().values().append_slice(vector);
+                        builder.values().append(true);
+                    }
+                    builder.append(true);
+                }
+                None => builder.append_null(),
+            },
+            VectorColumn::Sparse {
+                name,
+                indices,
+                values,
+            } => match get_sparse(vectors, name) {
+                Some(vector) => {
+                    indices.values().append_slice(&vector.indices);
+                    indices.append(true);
+                    values.values().append_slice(&vector.values);
+                    values.append(true);
+                }
+                None => {
+                    indices.append_null();
+                    values.append_null();
+                }
+            },
+        }
+    }
+
+    fn finish_into(&mut self, columns: &mut Vec<ArrayRef>) {
+        match self {
+            VectorColumn::Dense { builder, .. } => columns.push(Arc::new(builder.finish())),
+            VectorColumn::MultiDense { builder, .. } => columns.push(Arc::new(builder.finish())),
+            VectorColumn::Sparse {
+                indices, values, ..
+            } => {
+                columns.push(Arc::new(indices.finish()));
+                columns.push(Arc::new(values.finish()));
+            }
+        }
+    }
+}
+
+fn is_vector_exported(with_vector: &WithVector, name: &str) -> bool {
+    match with_vector {
+        WithVector::Bool(enabled) => *enabled,
+        WithVector::Selector(names) => names.iter().any(|selected| selected == name),
+    }
+}
+
+fn to_json(value: &impl Serialize) -> Result<String, ArrowError> {
+    serde_json::to_string(value).map_err(|err| ArrowError::JsonError(err.to_string()))
+}
+
+fn list_of(data_type: DataType) -> DataType {
+    DataType::List(Arc::new(Field::new("item", data_type, true)))
+}
+
+fn vector_column_name(name: &str) -> String {
+    if name.is_empty() {
+        "vector".to_string()
+    } else {
+        format!("vector.{name}")
+    }
+}
+
+fn get_named<'a>(vectors: Option<&'a VectorStruct>, name: &str) -> Option<&'a Vector> {
+    match vectors? {
+        VectorStruct::Named(vectors) => vectors.get(name),
+        VectorStruct::Single(_) | VectorStruct::MultiDense(_) | VectorStruct::Inference(_) => None,
+    }
+}
+
+fn get_dense<'a>(vectors: Option<&'a VectorStruct>, name: &str) -> Option<&'a DenseVector> {
+    match vectors? {
+        VectorStruct::Single(vector) if name.is_empty() => Some(vector),
+        _ => match get_named(vectors, name)? {
+            Vector::Dense(vector) => Some(vector),
+            Vector::Sparse(_) | Vector::MultiDense(_) | Vector::Inference(_) => None,
+        },
+    }
+}
+
+fn get_multi_dense<'a>(
+    vectors: Option<&'a VectorStruct>,
+    name: &str,
+) -> Option<&'a MultiDenseVector> {
+    match vectors? {
+        VectorStruct::MultiDense(vector) if name.is_empty() => Some(vector),
+        _ => match get_named(vectors, name)? {
+            Vector::MultiDense(vector) => Some(vector),
+            Vector::Dense(_) | Vector::Sparse(_) | Vector::Inference(_) => None,
+        },
+    }
+}
+
+fn get_sparse<'a>(vectors: Option<&'a VectorStruct>, name: &str) -> Option<&'a SparseVector> {
+    match get_named(vectors, name)? {
+        Vector::Sparse(vector) => Some(vector),
+        Vector::Dense(_) | Vector::MultiDense(_) | Vector::Inference(_) => None,
+    }
+}
@@ -2,11 +2,12 @@ use std::cmp::max;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::{fs, io};
 
+use common::scheduling::Workload;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use tokio::runtime;
 use tokio::runtime::Runtime;
-use tonic::transport::{Certificate, ClientTlsConfig, Identity, ServerTlsConfig};
+use tonic::transport::{Certificate, ClientTlsConfig, Identity};
 use validator::Validate;
 
 use crate::settings::{Settings, TlsConfig};
@@ -46,6 +47,7 @@ pub fn create_search_runtime(max_search_threads: usize) -> io::Result<Runtime> {
             let id = ATOMIC_ID.fetch_add(1, Ordering::SeqCst);
             format!("search-{id}")
         })
+        .on_thread_start(|| apply_thread_priority(Workload::Search))
         .build()
 }
 
@@ -58,7 +60,9 @@ pub fn create_update_runtime(max_optimization_threads: usize) -> io::Result<Runt
             static ATOMIC_ID: AtomicUsize = AtomicUsize::new(0);
             let update_id = ATOMIC_ID.fetch_add(1, Ordering::SeqCst);
             format!("update-{update_id}")
-        });
+        })
+        // Blocking threads, running optimizations, switch to optimizer priority on their own
+        .on_thread_start(|| apply_thread_priority(Workload::Ingestion));
 
     if max_optimization_threads > 0 {
         // panics if val is not larger than 0.
@@ -67,6 +71,16 @@ pub fn create_update_runtime(max_optimization_threads: usize) -> io::Result<Runt
     update_runtime_builder.build()
 }
 
+/// On Linux, apply configured priority of the workload to a thread of its runtime.
+fn apply_thread_priority(workload: Workload) {
+    #[cfg(target_os = "linux")]
+    if let Err(err) = workload.apply_thread_priority() {
+        log::debug!("Failed to set thread priority for {workload:?} workload, ignoring: {err}");
+    }
+    #[cfg(not(target_os = "linux"))]
+    let _ = workload;
+}
+
 pub fn create_general_purpose_runtime() -> io::Result<Runtime> {
     runtime::Builder::new_multi_thread()
         .enable_time()
@@ -94,18 +108,6 @@ pub fn load_tls_client_config(settings: &Settings) -> io::Result<Option<ClientTl
     }
 }
 
-/// Load server TLS configuration for external gRPC
-pub fn load_tls_external_server_config(tls_config: &TlsConfig) -> io::Result<ServerTlsConfig> {
-    Ok(ServerTlsConfig::new().identity(load_identity(tls_config)?))
-}
-
-/// Load server TLS configuration for internal gRPC, check client certificate against CA
-pub fn load_tls_internal_server_config(tls_config: &TlsConfig) -> io::Result<ServerTlsConfig> {
-    Ok(ServerTlsConfig::new()
-        .identity(load_identity(tls_config)?)
-        .client_ca_root(load_ca_certificate(tls_config)?))
-}
-
 fn load_identity(tls_config: &TlsConfig) -> io::Result<Identity> {
     let cert = fs::read_to_string(&tls_config.cert)?;
     let key = fs::read_to_string(&tls_config.key)?;
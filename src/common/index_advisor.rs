@@ -0,0 +1,61 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use collection::operations::point_ops::WriteOrdering;
+use storage::dispatcher::Dispatcher;
+use storage::rbac::Access;
+
+use crate::common::points::{do_create_index, CreateFieldIndex};
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+const FULL_ACCESS: Access = Access::full("Index advisor");
+
+/// Periodically create payload indexes, recommended by the index advisors of collections
+///
+/// Indexes are created through consensus, the same way as with the API. In a cluster, each peer
+/// creates indexes based on the requests it handled.
+pub async fn run_auto_indexing(dispatcher: Arc<Dispatcher>) {
+    loop {
+        tokio::time::sleep(CHECK_INTERVAL).await;
+        create_recommended_indexes(&dispatcher).await;
+    }
+}
+
+async fn create_recommended_indexes(dispatcher: &Arc<Dispatcher>) {
+    let toc = dispatcher.toc(&FULL_ACCESS);
+    for collection_pass in toc.all_collections(&FULL_ACCESS).await {
+        let indexes = match toc.get_collection(&collection_pass).await {
+            Ok(collection) => collection.take_indexes_to_auto_create(),
+            // Collection was deleted in the meantime
+            Err(_) => continue,
+        };
+
+        for (field_name, field_schema) in indexes {
+            let collection_name = collection_pass.name().to_string();
+            log::info!(
+                "Creating recommended index on field {field_name} of collection {collection_name}",
+            );
+            let operation = CreateFieldIndex {
+                field_name: field_name.clone(),
+                field_schema: Some(field_schema),
+            };
+            let result = do_create_index(
+                dispatcher.clone(),
+                collection_name.clone(),
+                operation,
+                None,
+                None,
+                false,
+                WriteOrdering::default(),
+                FULL_ACCESS.clone(),
+            )
+            .await;
+            if let Err(err) = result {
+                log::warn!(
+                    "Failed to create recommended index on field {field_name} of collection {collection_name}: {err}",
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,392 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use api::rest::{
+    BatchVectorStruct, ContextPair, InferenceObject, Prefetch, Query, QueryInterface, QueryRequest,
+    QueryRequestBatch, Vector, VectorInput, VectorStruct,
+};
+use collection::operations::point_ops::PointInsertOperations;
+use collection::operations::vector_ops::UpdateVectors;
+use serde::{Deserialize, Serialize};
+use storage::content_manager::errors::StorageError;
+use validator::Validate;
+
+use crate::common::points::{UpdateOperation, UpdateOperations};
+use crate::settings::Settings;
+
+const DEFAULT_INFERENCE_TIMEOUT_SEC: u64 = 30;
+
+#[derive(Debug, Deserialize, Clone, Validate)]
+pub struct InferenceConfig {
+    /// URL of the inference endpoint, which converts documents and images into vectors
+    pub address: String,
+    /// Maximum time to wait for the inference endpoint to respond
+    #[serde(default = "default_inference_timeout_sec")]
+    #[validate(range(min = 1))]
+    pub timeout_sec: u64,
+    /// Bearer token, sent to the inference endpoint with every request
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+const fn default_inference_timeout_sec() -> u64 {
+    DEFAULT_INFERENCE_TIMEOUT_SEC
+}
+
+/// Converts inference objects of REST requests into vectors, before the requests are executed.
+///
+/// All objects of a single request are sent to the inference endpoint in one batch.
+pub struct InferenceService {
+    config: Option<InferenceConfig>,
+    client: reqwest::Client,
+}
+
+impl InferenceService {
+    pub fn from_settings(settings: &Settings) -> reqwest::Result<Self> {
+        let config = settings.inference.clone();
+        let timeout = config
+            .as_ref()
+            .map_or(DEFAULT_INFERENCE_TIMEOUT_SEC, |config| config.timeout_sec);
+
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(timeout))
+            .build()?;
+
+        Ok(Self { config, client })
+    }
+
+    /// Replace all inference objects of the request with vectors, produced by the inference endpoint
+    pub async fn infer(
+        &self,
+        request: &mut impl InferenceRequestVisitor,
+    ) -> Result<(), StorageError> {
+        let mut objects = Vec::new();
+        let mut positions = HashMap::new();
+
+        request.visit(&mut |input| {
+            if let Some(object) = input.inference_object() {
+                if !positions.contains_key(object) {
+                    positions.insert(object.clone(), objects.len());
+                    objects.push(object.clone());
+                }
+            }
+            Ok(())
+        })?;
+
+        if objects.is_empty() {
+            return Ok(());
+        }
+
+        let vectors = self.embed(&objects).await?;
+
+        request.visit(&mut |input| {
+            let Some(position) = input.inference_object().map(|object| positions[object]) else {
+                return Ok(());
+            };
+            input.set_vector(vectors[position].clone())
+        })
+    }
+
+    async fn embed(&self, objects: &[InferenceObject]) -> Result<Vec<Vector>, StorageError> {
+        let Some(config) = &self.config else {
+            return Err(StorageError::bad_request(
+                "Inference is not configured, vectors must be provided explicitly",
+            ));
+        };
+
+        let request = InferenceRequest {
+            inputs: objects.iter().map(InferenceInput::from).collect(),
+        };
+
+        let mut builder = self.client.post(&config.address).json(&request);
+        if let Some(token) = &config.token {
+            builder = builder.bearer_auth(token);
+        }
+
+        let response = builder.send().await.map_err(|err| {
+            StorageError::service_error(format!("Failed to reach inference endpoint: {err}"))
+        })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(StorageError::service_error(format!(
+                "Inference endpoint responded with {status}: {body}",
+            )));
+        }
+
+        let InferenceResponse { embeddings } = response.json().await.map_err(|err| {
+            StorageError::service_error(format!("Invalid response of inference endpoint: {err}"))
+        })?;
+
+        if embeddings.len() != objects.len() {
+            return Err(StorageError::service_error(format!(
+                "Inference endpoint returned {} embeddings for {} inputs",
+                embeddings.len(),
+                objects.len(),
+            )));
+        }
+
+        if embeddings
+            .iter()
+            .any(|vector| matches!(vector, Vector::Inference(_)))
+        {
+            return Err(StorageError::service_error(
+                "Inference endpoint returned an object instead of a vector",
+            ));
+        }
+
+        Ok(embeddings)
+    }
+}
+
+#[derive(Serialize)]
+struct InferenceRequest<'a> {
+    inputs: Vec<InferenceInput<'a>>,
+}
+
+#[derive(Serialize)]
+struct InferenceInput<'a> {
+    data: &'a str,
+    data_type: InferenceDataType,
+    model: &'a str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum InferenceDataType {
+    Text,
+    Image,
+}
+
+impl<'a> From<&'a InferenceObject> for InferenceInput<'a> {
+    fn from(object: &'a InferenceObject) -> Self {
+        match object {
+            InferenceObject::Document(document) => Self {
+                data: &document.text,
+                data_type: InferenceDataType::Text,
+                model: &document.model,
+            },
+            InferenceObject::Image(image) => Self {
+                data: &image.image,
+                data_type: InferenceDataType::Image,
+                model: &image.model,
+            },
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct InferenceResponse {
+    embeddings: Vec<Vector>,
+}
+
+/// Part of a request, which may be given as an inference object instead of a vector
+pub trait InferenceSlot {
+    fn inference_object(&self) -> Option<&InferenceObject>;
+
+    fn set_vector(&mut self, vector: Vector) -> Result<(), StorageError>;
+}
+
+impl InferenceSlot for Vector {
+    fn inference_object(&self) -> Option<&InferenceObject> {
+        match self {
+            Vector::Inference(object) => Some(object),
+            Vector::Dense(_) | Vector::Sparse(_) | Vector::MultiDense(_) => None,
+        }
+    }
+
+    fn set_vector(&mut self, vector: Vector) -> Result<(), StorageError> {
+        *self = vector;
+        Ok(())
+    }
+}
+
+impl InferenceSlot for VectorStruct {
+    fn inference_object(&self) -> Option<&InferenceObject> {
+        match self {
+            VectorStruct::Inference(object) => Some(object),
+            VectorStruct::Single(_) | VectorStruct::MultiDense(_) | VectorStruct::Named(_) => None,
+        }
+    }
+
+    fn set_vector(&mut self, vector: Vector) -> Result<(), StorageError> {
+        *self = match vector {
+            Vector::Dense(vector) => VectorStruct::Single(vector),
+            Vector::MultiDense(vector) => VectorStruct::MultiDense(vector),
+            Vector::Sparse(_) | Vector::Inference(_) => {
+                return Err(StorageError::bad_input(
+                    "Model produced a sparse vector, which requires a vector name",
+                ));
+            }
+        };
+        Ok(())
+    }
+}
+
+impl InferenceSlot for VectorInput {
+    fn inference_object(&self) -> Option<&InferenceObject> {
+        match self {
+            VectorInput::Inference(object) => Some(object),
+            VectorInput::DenseVector(_)
+            | VectorInput::SparseVector(_)
+            | VectorInput::MultiDenseVector(_)
+            | VectorInput::Id(_) => None,
+        }
+    }
+
+    fn set_vector(&mut self, vector: Vector) -> Result<(), StorageError> {
+        *self = match vector {
+            Vector::Dense(vector) => VectorInput::DenseVector(vector),
+            Vector::Sparse(vector) => VectorInput::SparseVector(vector),
+            Vector::MultiDense(vector) => VectorInput::MultiDenseVector(vector),
+            Vector::Inference(_) => {
+                return Err(StorageError::service_error(
+                    "Inference object can't be used as a vector",
+                ));
+            }
+        };
+        Ok(())
+    }
+}
+
+type Visitor<'a> = dyn FnMut(&mut dyn InferenceSlot) -> Result<(), StorageError> + 'a;
+
+/// Request, which may contain inference objects
+pub trait InferenceRequestVisitor {
+    fn visit(&mut self, visitor: &mut Visitor) -> Result<(), StorageError>;
+}
+
+impl InferenceRequestVisitor for PointInsertOperations {
+    fn visit(&mut self, visitor: &mut Visitor) -> Result<(), StorageError> {
+        match self {
+            PointInsertOperations::PointsBatch(batch) => match &mut batch.batch.vectors {
+                BatchVectorStruct::Named(vectors) => {
+                    for vector in vectors.values_mut().flatten() {
+                        visitor(vector)?;
+                    }
+                }
+                BatchVectorStruct::Single(_) | BatchVectorStruct::MultiDense(_) => {}
+            },
+            PointInsertOperations::PointsList(list) => {
+                for point in &mut list.points {
+                    visit_vector_struct(&mut point.vector, visitor)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl InferenceRequestVisitor for UpdateVectors {
+    fn visit(&mut self, visitor: &mut Visitor) -> Result<(), StorageError> {
+        for point in &mut self.points {
+            visit_vector_struct(&mut point.vector, visitor)?;
+        }
+        Ok(())
+    }
+}
+
+impl InferenceRequestVisitor for UpdateOperations {
+    fn visit(&mut self, visitor: &mut Visitor) -> Result<(), StorageError> {
+        for operation in &mut self.operations {
+            match operation {
+                UpdateOperation::Upsert(upsert) => upsert.upsert.visit(visitor)?,
+                UpdateOperation::UpdateVectors(update) => update.update_vectors.visit(visitor)?,
+                UpdateOperation::Delete(_)
+                | UpdateOperation::SetPayload(_)
+                | UpdateOperation::OverwritePayload(_)
+                | UpdateOperation::DeletePayload(_)
+                | UpdateOperation::ClearPayload(_)
+                | UpdateOperation::DeleteVectors(_) => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+impl InferenceRequestVisitor for QueryRequest {
+    fn visit(&mut self, visitor: &mut Visitor) -> Result<(), StorageError> {
+        let request = &mut self.internal;
+        for prefetch in request.prefetch.iter_mut().flatten() {
+            visit_prefetch(prefetch, visitor)?;
+        }
+        if let Some(query) = &mut request.query {
+            visit_query(query, visitor)?;
+        }
+        Ok(())
+    }
+}
+
+impl InferenceRequestVisitor for QueryRequestBatch {
+    fn visit(&mut self, visitor: &mut Visitor) -> Result<(), StorageError> {
+        for request in &mut self.searches {
+            request.visit(visitor)?;
+        }
+        Ok(())
+    }
+}
+
+fn visit_vector_struct(
+    vector: &mut VectorStruct,
+    visitor: &mut Visitor,
+) -> Result<(), StorageError> {
+    match vector {
+        VectorStruct::Named(vectors) => {
+            for vector in vectors.values_mut() {
+                visitor(vector)?;
+            }
+            Ok(())
+        }
+        VectorStruct::Inference(_) => visitor(vector),
+        VectorStruct::Single(_) | VectorStruct::MultiDense(_) => Ok(()),
+    }
+}
+
+fn visit_prefetch(prefetch: &mut Prefetch, visitor: &mut Visitor) -> Result<(), StorageError> {
+    for prefetch in prefetch.prefetch.iter_mut().flatten() {
+        visit_prefetch(prefetch, visitor)?;
+    }
+    if let Some(query) = &mut prefetch.query {
+        visit_query(query, visitor)?;
+    }
+    Ok(())
+}
+
+fn visit_query(query: &mut QueryInterface, visitor: &mut Visitor) -> Result<(), StorageError> {
+    match query {
+        QueryInterface::Nearest(vector) => visitor(vector),
+        QueryInterface::Query(Query::Nearest(nearest)) => visitor(&mut nearest.nearest),
+        QueryInterface::Query(Query::Recommend(recommend)) => {
+            let recommend = &mut recommend.recommend;
+            let positive = recommend.positive.iter_mut().flatten();
+            let negative = recommend.negative.iter_mut().flatten();
+            for vector in positive.chain(negative) {
+                visitor(vector)?;
+            }
+            Ok(())
+        }
+        QueryInterface::Query(Query::Discover(discover)) => {
+            let discover = &mut discover.discover;
+            visitor(&mut discover.target)?;
+            visit_context(discover.context.iter_mut().flatten(), visitor)
+        }
+        QueryInterface::Query(Query::Context(context)) => {
+            visit_context(context.context.0.iter_mut().flatten(), visitor)
+        }
+        QueryInterface::Query(
+            Query::OrderBy(_) | Query::Fusion(_) | Query::WeightedFusion(_) | Query::Formula(_),
+        ) => Ok(()),
+    }
+}
+
+fn visit_context<'a>(
+    pairs: impl Iterator<Item = &'a mut ContextPair>,
+    visitor: &mut Visitor,
+) -> Result<(), StorageError> {
+    for pair in pairs {
+        visitor(&mut pair.positive)?;
+        visitor(&mut pair.negative)?;
+    }
+    Ok(())
+}
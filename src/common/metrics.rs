@@ -1,3 +1,5 @@
+use std::fmt::Write as _;
+
 use prometheus::proto::{Counter, Gauge, LabelPair, Metric, MetricFamily, MetricType};
 use prometheus::TextEncoder;
 use segment::common::operation_time_statistics::OperationDurationStatistics;
@@ -8,9 +10,12 @@ use crate::common::telemetry_ops::cluster_telemetry::{ClusterStatusTelemetry, Cl
 use crate::common::telemetry_ops::collections_telemetry::{
     CollectionTelemetryEnum, CollectionsTelemetry,
 };
+use crate::common::telemetry_ops::latency_telemetry::{Exemplar, LatencyHistogram};
+use crate::common::telemetry_ops::memory_telemetry::MemoryTelemetry;
 use crate::common::telemetry_ops::requests_telemetry::{
     GrpcTelemetry, RequestsTelemetry, WebApiTelemetry,
 };
+use crate::common::telemetry_ops::workloads_telemetry::WorkloadsTelemetry;
 
 /// Whitelist for REST endpoints in metrics output.
 ///
@@ -27,6 +32,7 @@ const REST_ENDPOINT_WHITELIST: &[&str] = &[
     "/collections/{name}/points/query/batch",
     "/collections/{name}/points/recommend",
     "/collections/{name}/points/recommend/batch",
+    "/collections/{name}/points/recommend/shared",
     "/collections/{name}/points/search",
     "/collections/{name}/points/search/batch",
 ];
@@ -40,35 +46,68 @@ const GRPC_ENDPOINT_WHITELIST: &[&str] = &[
     "/qdrant.Points/Discover",
     "/qdrant.Points/DiscoverBatch",
     "/qdrant.Points/OverwritePayload",
+    "/qdrant.Points/PatchPayload",
     "/qdrant.Points/Query",
     "/qdrant.Points/QueryBatch",
     "/qdrant.Points/Recommend",
     "/qdrant.Points/RecommendBatch",
     "/qdrant.Points/Search",
     "/qdrant.Points/SearchBatch",
+    "/qdrant.Points/SetAttributes",
     "/qdrant.Points/SetPayload",
     "/qdrant.Points/Upsert",
+    "/qdrant.Points/UpsertStream",
 ];
 
 /// For REST requests, only report timings when having this HTTP response status.
 const REST_TIMINGS_FOR_STATUS: u16 = 200;
 
+/// Name of the metric family of per collection latency histograms.
+const LATENCY_METRIC_FAMILY: &str = "collection_request_duration_seconds";
+
+/// Content type of metrics, formatted with [`MetricsData::format_open_metrics`].
+pub const OPEN_METRICS_CONTENT_TYPE: &str =
+    "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
 /// Encapsulates metrics data in Prometheus format.
 pub struct MetricsData {
     metrics: Vec<MetricFamily>,
+    /// Exemplars of buckets of each metric in the latency histograms family
+    latency_exemplars: Vec<Vec<Option<Exemplar>>>,
 }
 
 impl MetricsData {
+    pub fn new(telemetry_data: TelemetryData, latencies: Vec<LatencyHistogram>) -> Self {
+        let mut metrics = vec![];
+        telemetry_data.add_metrics(&mut metrics);
+        latencies.add_metrics(&mut metrics);
+        let latency_exemplars = latencies
+            .into_iter()
+            .map(|histogram| histogram.exemplars)
+            .collect();
+        Self {
+            metrics,
+            latency_exemplars,
+        }
+    }
+
     pub fn format_metrics(&self) -> String {
         TextEncoder::new().encode_to_string(&self.metrics).unwrap()
     }
-}
 
-impl From<TelemetryData> for MetricsData {
-    fn from(telemetry_data: TelemetryData) -> Self {
-        let mut metrics = vec![];
-        telemetry_data.add_metrics(&mut metrics);
-        Self { metrics }
+    /// Format metrics in OpenMetrics text format.
+    ///
+    /// Unlike the Prometheus text format, it carries exemplars of latency histogram buckets,
+    /// linking them to traces of requests.
+    pub fn format_open_metrics(&self) -> String {
+        let mut output = String::new();
+        for family in &self.metrics {
+            let exemplars = (family.get_name() == LATENCY_METRIC_FAMILY)
+                .then_some(self.latency_exemplars.as_slice());
+            encode_open_metrics_family(&mut output, family, exemplars);
+        }
+        output.push_str("# EOF\n");
+        output
     }
 }
 
@@ -83,6 +122,8 @@ impl MetricsProvider for TelemetryData {
         self.collections.add_metrics(metrics);
         self.cluster.add_metrics(metrics);
         self.requests.add_metrics(metrics);
+        self.workloads.add_metrics(metrics);
+        self.memory.add_metrics(metrics);
     }
 }
 
@@ -354,6 +395,72 @@ impl OperationDurationMetricsBuilder {
     }
 }
 
+impl MetricsProvider for Vec<LatencyHistogram> {
+    fn add_metrics(&self, metrics: &mut Vec<MetricFamily>) {
+        if self.is_empty() {
+            return;
+        }
+        metrics.push(metric_family(
+            LATENCY_METRIC_FAMILY,
+            "request duration histogram of each collection and operation",
+            MetricType::HISTOGRAM,
+            self.iter()
+                .map(|latency| {
+                    histogram(
+                        latency.count,
+                        latency.sum_secs,
+                        &latency.buckets,
+                        &[
+                            ("collection", latency.collection.as_str()),
+                            ("operation", latency.operation.as_str()),
+                        ],
+                    )
+                })
+                .collect(),
+        ));
+    }
+}
+
+impl MetricsProvider for WorkloadsTelemetry {
+    fn add_metrics(&self, metrics: &mut Vec<MetricFamily>) {
+        metrics.push(metric_family(
+            "workload_queue_depth",
+            "number of queued or running tasks of the workload",
+            MetricType::GAUGE,
+            vec![
+                gauge(self.search_queue_depth as f64, &[("workload", "search")]),
+                gauge(
+                    self.optimization_queue_depth as f64,
+                    &[("workload", "optimization")],
+                ),
+                gauge(
+                    self.ingestion_queue_depth as f64,
+                    &[("workload", "ingestion")],
+                ),
+            ],
+        ));
+    }
+}
+
+impl MetricsProvider for MemoryTelemetry {
+    fn add_metrics(&self, metrics: &mut Vec<MetricFamily>) {
+        metrics.push(metric_family(
+            "memory_budget_used_bytes",
+            "memory reserved from the memory budget by category",
+            MetricType::GAUGE,
+            vec![
+                gauge(self.index_bytes as f64, &[("category", "index")]),
+                gauge(
+                    self.quantization_bytes as f64,
+                    &[("category", "quantization")],
+                ),
+                gauge(self.cache_bytes as f64, &[("category", "cache")]),
+                gauge(self.scratch_bytes as f64, &[("category", "scratch")]),
+            ],
+        ));
+    }
+}
+
 fn metric_family(name: &str, help: &str, r#type: MetricType, metrics: Vec<Metric>) -> MetricFamily {
     let mut metric_family = MetricFamily::default();
     metric_family.set_name(name.into());
@@ -413,6 +520,124 @@ fn histogram(
     metric
 }
 
+/// Encode a metric family in OpenMetrics text format, with optional exemplars of each metric
+fn encode_open_metrics_family(
+    output: &mut String,
+    family: &MetricFamily,
+    exemplars: Option<&[Vec<Option<Exemplar>>]>,
+) {
+    let field_type = family.get_field_type();
+    // Names of counter families don't include the suffix of their samples
+    let name = match field_type {
+        MetricType::COUNTER => family.get_name().trim_end_matches("_total"),
+        _ => family.get_name(),
+    };
+    let type_name = match field_type {
+        MetricType::COUNTER => "counter",
+        MetricType::GAUGE => "gauge",
+        MetricType::HISTOGRAM => "histogram",
+        MetricType::SUMMARY => "summary",
+        MetricType::UNTYPED => "unknown",
+    };
+    let _ = writeln!(output, "# TYPE {name} {type_name}");
+    let _ = writeln!(
+        output,
+        "# HELP {name} {}",
+        escape_label_value(family.get_help())
+    );
+
+    for (i, metric) in family.get_metric().iter().enumerate() {
+        let labels: Vec<_> = metric
+            .get_label()
+            .iter()
+            .map(|label| (label.get_name(), escape_label_value(label.get_value())))
+            .collect();
+        let format_labels = |extra: Option<(&str, String)>| {
+            let pairs: Vec<_> = labels
+                .iter()
+                .map(|(name, value)| format!("{name}=\"{value}\""))
+                .chain(extra.map(|(name, value)| format!("{name}=\"{value}\"")))
+                .collect();
+            if pairs.is_empty() {
+                String::new()
+            } else {
+                format!("{{{}}}", pairs.join(","))
+            }
+        };
+
+        match field_type {
+            MetricType::COUNTER => {
+                let value = format_float(metric.get_counter().get_value());
+                let _ = writeln!(output, "{name}_total{} {value}", format_labels(None));
+            }
+            MetricType::GAUGE => {
+                let value = format_float(metric.get_gauge().get_value());
+                let _ = writeln!(output, "{name}{} {value}", format_labels(None));
+            }
+            MetricType::HISTOGRAM => {
+                let histogram = metric.get_histogram();
+                let metric_exemplars = exemplars.and_then(|exemplars| exemplars.get(i));
+                let bounds = histogram
+                    .get_bucket()
+                    .iter()
+                    .map(|bucket| {
+                        (
+                            format_float(bucket.get_upper_bound()),
+                            bucket.get_cumulative_count(),
+                        )
+                    })
+                    .chain([("+Inf".to_string(), histogram.get_sample_count())]);
+                for (j, (upper_bound, count)) in bounds.enumerate() {
+                    let _ = write!(
+                        output,
+                        "{name}_bucket{} {count}",
+                        format_labels(Some(("le", upper_bound))),
+                    );
+                    if let Some(Some(exemplar)) = metric_exemplars.and_then(|e| e.get(j)) {
+                        let _ = write!(
+                            output,
+                            " # {{trace_id=\"{}\"}} {} {}",
+                            escape_label_value(&exemplar.trace_id),
+                            format_float(exemplar.value_secs),
+                            exemplar.timestamp,
+                        );
+                    }
+                    output.push('\n');
+                }
+                let _ = writeln!(
+                    output,
+                    "{name}_sum{} {}",
+                    format_labels(None),
+                    format_float(histogram.get_sample_sum()),
+                );
+                let _ = writeln!(
+                    output,
+                    "{name}_count{} {}",
+                    format_labels(None),
+                    histogram.get_sample_count(),
+                );
+            }
+            MetricType::SUMMARY | MetricType::UNTYPED => {}
+        }
+    }
+}
+
+/// Format a float in the canonical OpenMetrics form, integral values keep their fraction
+fn format_float(value: f64) -> String {
+    if value.is_finite() && value.fract() == 0.0 {
+        format!("{value:.1}")
+    } else {
+        value.to_string()
+    }
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
 fn label_pair(name: &str, value: &str) -> LabelPair {
     let mut label = LabelPair::default();
     label.set_name(name.into());
@@ -435,4 +660,47 @@ mod tests {
             "GRPC_ENDPOINT_WHITELIST must be sorted in code to allow binary search"
         );
     }
+
+    #[test]
+    fn test_open_metrics_exemplars() {
+        use std::time::Duration;
+
+        use super::*;
+        use crate::common::telemetry_ops::latency_telemetry::{
+            LatencyOperation, LatencyTelemetryCollector,
+        };
+
+        let collector = LatencyTelemetryCollector::new(Some(&[0.1, 1.0]));
+        let trace_id = "4bf92f3577b34da6a3ce929d0e0e4736";
+        collector.observe(
+            "books",
+            LatencyOperation::Search,
+            Duration::from_millis(500),
+            Some(trace_id),
+        );
+        let latencies = collector.get_histograms();
+
+        let mut metrics = vec![];
+        latencies.add_metrics(&mut metrics);
+        let metrics_data = MetricsData {
+            metrics,
+            latency_exemplars: latencies.into_iter().map(|h| h.exemplars).collect(),
+        };
+
+        let output = metrics_data.format_open_metrics();
+        let labels = r#"collection="books",operation="search""#;
+        assert!(output.contains(&format!(
+            "collection_request_duration_seconds_bucket{{{labels},le=\"0.1\"}} 0\n"
+        )));
+        assert!(output.contains(&format!(
+            "collection_request_duration_seconds_bucket{{{labels},le=\"1.0\"}} 1 # {{trace_id=\"{trace_id}\"}} 0.5 "
+        )));
+        assert!(output.contains(&format!(
+            "collection_request_duration_seconds_count{{{labels}}} 1\n"
+        )));
+        assert!(output.ends_with("# EOF\n"));
+
+        // Prometheus text format has no exemplars
+        assert!(!metrics_data.format_metrics().contains(trace_id));
+    }
 }
@@ -1,16 +1,24 @@
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
 pub mod collections;
+pub mod consistency_checker;
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
 pub mod error_reporting;
+pub mod export;
 #[allow(dead_code)]
 pub mod health;
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
 pub mod helpers;
 pub mod http_client;
+pub mod index_advisor;
+#[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
+pub mod inference;
 pub mod metrics;
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
 pub mod points;
+pub mod recall_evaluator;
+pub mod snapshot_scheduler;
 pub mod snapshots;
+pub mod sparse_vocabularies;
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
 pub mod stacktrace;
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
@@ -19,10 +27,21 @@ pub mod telemetry_ops;
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
 pub mod telemetry_reporting;
 
+pub mod webhooks;
+
+pub mod audit;
+
 pub mod auth;
 
+pub mod certificate_helpers;
+
 pub mod strings;
 
 pub mod debugger;
 
 pub mod pyroscope_state;
+
+pub mod rate_limiting;
+
+#[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
+pub mod config_reload;
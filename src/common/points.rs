@@ -1,20 +1,24 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 
-use api::rest::{SearchGroupsRequestInternal, ShardKeySelector};
+use api::rest::{SearchGroupsRequestInternal, ShardKeySelector, ShardReadStatus};
 use collection::common::batching::batch_requests;
+use collection::operations::attribute_ops::{AttributeOps, SetAttributes, SetAttributesOp};
 use collection::operations::consistency_params::ReadConsistency;
 use collection::operations::payload_ops::{
-    DeletePayload, DeletePayloadOp, PayloadOps, SetPayload, SetPayloadOp,
+    ConditionalSetPayloadOp, DeletePayload, DeletePayloadOp, PayloadOps, SetPayload, SetPayloadOp,
 };
 use collection::operations::point_ops::{
-    FilterSelector, PointIdsList, PointInsertOperations, PointOperations, PointsSelector,
-    WriteOrdering,
+    ConditionalDeleteOperationInternal, ConditionalInsertOperationInternal, FilterSelector,
+    PointIdsList, PointInsertOperations, PointInsertOperationsInternal, PointOperations,
+    PointStruct, PointsSelector, RestorePoints, WriteOrdering,
 };
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
-    CoreSearchRequest, CoreSearchRequestBatch, CountRequestInternal, CountResult,
-    DiscoverRequestBatch, DiscoverRequestInternal, GroupsResult, PointRequestInternal,
+    CoreSearchRequest, CoreSearchRequestBatch, CountDistinctRequestInternal, CountRequestInternal,
+    CountResult, DiscoverRequestBatch, DiscoverRequestInternal, FieldStatsRequestInternal,
+    GroupsResult, HistogramRequestInternal, PartialResponse, PointRequestInternal,
     RecommendGroupsRequestInternal, Record, ScrollRequestInternal, ScrollResult, UpdateResult,
 };
 use collection::operations::universal_query::collection_query::CollectionQueryRequest;
@@ -24,10 +28,15 @@ use collection::operations::vector_ops::{
 use collection::operations::{
     ClockTag, CollectionUpdateOperations, CreateIndex, FieldIndexOperations, OperationWithClockTag,
 };
+use collection::shards::resolve::Reconciled;
 use collection::shards::shard::ShardId;
+use common::hardware_counter::HardwareCounterCell;
+use futures::{Stream, StreamExt};
 use schemars::JsonSchema;
+use segment::data_types::aggregation::{DistinctValues, NumericStats, ValueDistribution};
+use segment::data_types::search_plan::ShardSearchPlan;
 use segment::json_path::JsonPath;
-use segment::types::{PayloadFieldSchema, PayloadKeyType, ScoredPoint};
+use segment::types::{PayloadFieldSchema, PayloadKeyType, ScoredPoint, ShardKey};
 use serde::{Deserialize, Serialize};
 use storage::content_manager::collection_meta_ops::{
     CollectionMetaOperations, CreatePayloadIndex, DropPayloadIndex,
@@ -35,7 +44,7 @@ use storage::content_manager::collection_meta_ops::{
 use storage::content_manager::errors::StorageError;
 use storage::content_manager::toc::TableOfContent;
 use storage::dispatcher::Dispatcher;
-use storage::rbac::Access;
+use storage::rbac::{Access, AccessRequirements};
 use validator::Validate;
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate)]
@@ -48,7 +57,7 @@ pub struct CreateFieldIndex {
 #[derive(Deserialize, Serialize, JsonSchema, Validate)]
 pub struct UpsertOperation {
     #[validate]
-    upsert: PointInsertOperations,
+    pub upsert: PointInsertOperations,
 }
 
 #[derive(Deserialize, Serialize, JsonSchema, Validate)]
@@ -84,7 +93,7 @@ pub struct ClearPayloadOperation {
 #[derive(Deserialize, Serialize, JsonSchema, Validate)]
 pub struct UpdateVectorsOperation {
     #[validate]
-    update_vectors: UpdateVectors,
+    pub update_vectors: UpdateVectors,
 }
 
 #[derive(Deserialize, Serialize, JsonSchema, Validate)]
@@ -112,6 +121,32 @@ pub struct UpdateOperations {
     pub operations: Vec<UpdateOperation>,
 }
 
+/// Status of a single operation of the bulk update
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct BulkUpdateItemResult {
+    /// Result of the operation, if it was applied successfully
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<UpdateResult>,
+    /// Reason of the operation failure
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl From<Result<UpdateResult, StorageError>> for BulkUpdateItemResult {
+    fn from(result: Result<UpdateResult, StorageError>) -> Self {
+        match result {
+            Ok(result) => Self {
+                result: Some(result),
+                error: None,
+            },
+            Err(err) => Self {
+                result: None,
+                error: Some(err.to_string()),
+            },
+        }
+    }
+}
+
 impl Validate for UpdateOperation {
     fn validate(&self) -> Result<(), validator::ValidationErrors> {
         match self {
@@ -158,26 +193,43 @@ fn get_shard_selector_for_update(
     }
 }
 
+/// Idempotency key of the `index`-th operation, when a request is applied as multiple operations
+pub fn sub_operation_key(idempotency_key: &Option<String>, index: usize) -> Option<String> {
+    idempotency_key
+        .as_ref()
+        .map(|idempotency_key| format!("{idempotency_key}/{index}"))
+}
+
 #[allow(clippy::too_many_arguments)]
 pub async fn do_upsert_points(
     toc: Arc<TableOfContent>,
     collection_name: String,
     operation: PointInsertOperations,
     clock_tag: Option<ClockTag>,
+    idempotency_key: Option<String>,
     shard_selection: Option<ShardId>,
     wait: bool,
     ordering: WriteOrdering,
     access: Access,
 ) -> Result<UpdateResult, StorageError> {
-    let (shard_key, operation) = operation.decompose();
-    let collection_operation =
-        CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(operation));
+    let (shard_key, operation, precondition) = operation.decompose();
+    let point_operation = match precondition.filter(|precondition| !precondition.is_empty()) {
+        Some(precondition) => {
+            PointOperations::UpsertPointsConditional(ConditionalInsertOperationInternal {
+                points_op: operation,
+                precondition,
+            })
+        }
+        None => PointOperations::UpsertPoints(operation),
+    };
+    let collection_operation = CollectionUpdateOperations::PointOperation(point_operation);
 
     let shard_selector = get_shard_selector_for_update(shard_selection, shard_key);
 
     toc.update(
         &collection_name,
-        OperationWithClockTag::new(collection_operation, clock_tag),
+        OperationWithClockTag::new(collection_operation, clock_tag)
+            .with_idempotency_key(idempotency_key),
         wait,
         ordering,
         shard_selector,
@@ -192,15 +244,27 @@ pub async fn do_delete_points(
     collection_name: String,
     points: PointsSelector,
     clock_tag: Option<ClockTag>,
+    idempotency_key: Option<String>,
     shard_selection: Option<ShardId>,
     wait: bool,
     ordering: WriteOrdering,
     access: Access,
 ) -> Result<UpdateResult, StorageError> {
     let (point_operation, shard_key) = match points {
-        PointsSelector::PointIdsSelector(PointIdsList { points, shard_key }) => {
-            (PointOperations::DeletePoints { ids: points }, shard_key)
-        }
+        PointsSelector::PointIdsSelector(PointIdsList {
+            points,
+            shard_key,
+            precondition,
+        }) => match precondition.filter(|precondition| !precondition.is_empty()) {
+            Some(precondition) => (
+                PointOperations::DeletePointsConditional(ConditionalDeleteOperationInternal {
+                    ids: points,
+                    precondition,
+                }),
+                shard_key,
+            ),
+            None => (PointOperations::DeletePoints { ids: points }, shard_key),
+        },
         PointsSelector::FilterSelector(FilterSelector { filter, shard_key }) => {
             (PointOperations::DeletePointsByFilter(filter), shard_key)
         }
@@ -210,7 +274,8 @@ pub async fn do_delete_points(
 
     toc.update(
         &collection_name,
-        OperationWithClockTag::new(collection_operation, clock_tag),
+        OperationWithClockTag::new(collection_operation, clock_tag)
+            .with_idempotency_key(idempotency_key),
         wait,
         ordering,
         shard_selector,
@@ -219,12 +284,61 @@ pub async fn do_delete_points(
     .await
 }
 
+/// Insert deleted points from the trash back into their shards
+pub async fn do_restore_points(
+    toc: Arc<TableOfContent>,
+    collection_name: String,
+    request: RestorePoints,
+    wait: bool,
+    ordering: WriteOrdering,
+    access: Access,
+) -> Result<UpdateResult, StorageError> {
+    // Trashed points are not checked against payload constraints of the access
+    let collection_pass = access
+        .check_collection_access(&collection_name, AccessRequirements::new().write().whole())?;
+    let trashed = toc
+        .get_collection(&collection_pass)
+        .await?
+        .trashed_points(&request.points)
+        .await?;
+
+    let mut points_by_shard_key: HashMap<Option<ShardKey>, Vec<PointStruct>> = HashMap::new();
+    for (point, shard_key) in trashed {
+        points_by_shard_key
+            .entry(shard_key)
+            .or_default()
+            .push(point);
+    }
+
+    let mut result = None;
+    for (shard_key, points) in points_by_shard_key {
+        let collection_operation = CollectionUpdateOperations::PointOperation(
+            PointOperations::UpsertPoints(PointInsertOperationsInternal::PointsList(points)),
+        );
+        let shard_selector =
+            get_shard_selector_for_update(None, shard_key.map(ShardKeySelector::ShardKey));
+        result = Some(
+            toc.update(
+                &collection_name,
+                OperationWithClockTag::from(collection_operation),
+                wait,
+                ordering,
+                shard_selector,
+                access.clone(),
+            )
+            .await?,
+        );
+    }
+    result.ok_or_else(|| StorageError::bad_input("No points to restore"))
+}
+
 #[allow(clippy::too_many_arguments)]
 pub async fn do_update_vectors(
     toc: Arc<TableOfContent>,
     collection_name: String,
     operation: UpdateVectors,
     clock_tag: Option<ClockTag>,
+    idempotency_key: Option<String>,
     shard_selection: Option<ShardId>,
     wait: bool,
     ordering: WriteOrdering,
@@ -240,7 +354,8 @@ pub async fn do_update_vectors(
 
     toc.update(
         &collection_name,
-        OperationWithClockTag::new(collection_operation, clock_tag),
+        OperationWithClockTag::new(collection_operation, clock_tag)
+            .with_idempotency_key(idempotency_key),
         wait,
         ordering,
         shard_selector,
@@ -255,6 +370,7 @@ pub async fn do_delete_vectors(
     collection_name: String,
     operation: DeleteVectors,
     clock_tag: Option<ClockTag>,
+    idempotency_key: Option<String>,
     shard_selection: Option<ShardId>,
     wait: bool,
     ordering: WriteOrdering,
@@ -272,6 +388,16 @@ pub async fn do_delete_vectors(
     let vector_names: Vec<_> = vector.into_iter().collect();
     let shard_selector = get_shard_selector_for_update(shard_selection, shard_key);
 
+    // Deletion by filter and by ids are separate operations, each needs a key of its own
+    let (filter_key, points_key) = if filter.is_some() && points.is_some() {
+        (
+            sub_operation_key(&idempotency_key, 0),
+            sub_operation_key(&idempotency_key, 1),
+        )
+    } else {
+        (idempotency_key.clone(), idempotency_key)
+    };
+
     let mut result = None;
 
     if let Some(filter) = filter {
@@ -283,7 +409,8 @@ pub async fn do_delete_vectors(
         result = Some(
             toc.update(
                 &collection_name,
-                OperationWithClockTag::new(collection_operation, clock_tag),
+                OperationWithClockTag::new(collection_operation, clock_tag)
+                    .with_idempotency_key(filter_key),
                 wait,
                 ordering,
                 shard_selector.clone(),
@@ -299,7 +426,8 @@ pub async fn do_delete_vectors(
         result = Some(
             toc.update(
                 &collection_name,
-                OperationWithClockTag::new(collection_operation, clock_tag),
+                OperationWithClockTag::new(collection_operation, clock_tag)
+                    .with_idempotency_key(points_key),
                 wait,
                 ordering,
                 shard_selector,
@@ -318,6 +446,7 @@ pub async fn do_set_payload(
     collection_name: String,
     operation: SetPayload,
     clock_tag: Option<ClockTag>,
+    idempotency_key: Option<String>,
     shard_selection: Option<ShardId>,
     wait: bool,
     ordering: WriteOrdering,
@@ -329,21 +458,80 @@ pub async fn do_set_payload(
         filter,
         shard_key,
         key,
+        precondition,
+    } = operation;
+
+    let payload_op = SetPayloadOp {
+        payload,
+        points,
+        filter,
+        key,
+    };
+    let payload_operation = match precondition.filter(|precondition| !precondition.is_empty()) {
+        Some(precondition) => PayloadOps::SetPayloadConditional(ConditionalSetPayloadOp {
+            payload_op,
+            precondition,
+        }),
+        None => PayloadOps::SetPayload(payload_op),
+    };
+    let collection_operation = CollectionUpdateOperations::PayloadOperation(payload_operation);
+
+    let shard_selector = get_shard_selector_for_update(shard_selection, shard_key);
+
+    toc.update(
+        &collection_name,
+        OperationWithClockTag::new(collection_operation, clock_tag)
+            .with_idempotency_key(idempotency_key),
+        wait,
+        ordering,
+        shard_selector,
+        access,
+    )
+    .await
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn do_overwrite_payload(
+    toc: Arc<TableOfContent>,
+    collection_name: String,
+    operation: SetPayload,
+    clock_tag: Option<ClockTag>,
+    idempotency_key: Option<String>,
+    shard_selection: Option<ShardId>,
+    wait: bool,
+    ordering: WriteOrdering,
+    access: Access,
+) -> Result<UpdateResult, StorageError> {
+    let SetPayload {
+        points,
+        payload,
+        filter,
+        shard_key,
+        precondition,
+        ..
     } = operation;
 
+    if precondition.is_some() {
+        return Err(StorageError::bad_request(
+            "Precondition is not supported for overwrite payload operation",
+        ));
+    }
+
     let collection_operation =
-        CollectionUpdateOperations::PayloadOperation(PayloadOps::SetPayload(SetPayloadOp {
+        CollectionUpdateOperations::PayloadOperation(PayloadOps::OverwritePayload(SetPayloadOp {
             payload,
             points,
             filter,
-            key,
+            // overwrite operation doesn't support payload selector
+            key: None,
         }));
 
     let shard_selector = get_shard_selector_for_update(shard_selection, shard_key);
 
     toc.update(
         &collection_name,
-        OperationWithClockTag::new(collection_operation, clock_tag),
+        OperationWithClockTag::new(collection_operation, clock_tag)
+            .with_idempotency_key(idempotency_key),
         wait,
         ordering,
         shard_selector,
@@ -353,11 +541,12 @@ pub async fn do_set_payload(
 }
 
 #[allow(clippy::too_many_arguments)]
-pub async fn do_overwrite_payload(
+pub async fn do_patch_payload(
     toc: Arc<TableOfContent>,
     collection_name: String,
     operation: SetPayload,
     clock_tag: Option<ClockTag>,
+    idempotency_key: Option<String>,
     shard_selection: Option<ShardId>,
     wait: bool,
     ordering: WriteOrdering,
@@ -368,15 +557,27 @@ pub async fn do_overwrite_payload(
         payload,
         filter,
         shard_key,
-        ..
+        key,
+        precondition,
     } = operation;
 
+    if key.is_some() {
+        return Err(StorageError::bad_request(
+            "Key is not supported for patch payload operation",
+        ));
+    }
+
+    if precondition.is_some() {
+        return Err(StorageError::bad_request(
+            "Precondition is not supported for patch payload operation",
+        ));
+    }
+
     let collection_operation =
-        CollectionUpdateOperations::PayloadOperation(PayloadOps::OverwritePayload(SetPayloadOp {
+        CollectionUpdateOperations::PayloadOperation(PayloadOps::PatchPayload(SetPayloadOp {
             payload,
             points,
             filter,
-            // overwrite operation doesn't support payload selector
             key: None,
         }));
 
@@ -384,7 +585,8 @@ pub async fn do_overwrite_payload(
 
     toc.update(
         &collection_name,
-        OperationWithClockTag::new(collection_operation, clock_tag),
+        OperationWithClockTag::new(collection_operation, clock_tag)
+            .with_idempotency_key(idempotency_key),
         wait,
         ordering,
         shard_selector,
@@ -399,6 +601,7 @@ pub async fn do_delete_payload(
     collection_name: String,
     operation: DeletePayload,
     clock_tag: Option<ClockTag>,
+    idempotency_key: Option<String>,
     shard_selection: Option<ShardId>,
     wait: bool,
     ordering: WriteOrdering,
@@ -422,7 +625,8 @@ pub async fn do_delete_payload(
 
     toc.update(
         &collection_name,
-        OperationWithClockTag::new(collection_operation, clock_tag),
+        OperationWithClockTag::new(collection_operation, clock_tag)
+            .with_idempotency_key(idempotency_key),
         wait,
         ordering,
         shard_selector,
@@ -437,13 +641,23 @@ pub async fn do_clear_payload(
     collection_name: String,
     points: PointsSelector,
     clock_tag: Option<ClockTag>,
+    idempotency_key: Option<String>,
     shard_selection: Option<ShardId>,
     wait: bool,
     ordering: WriteOrdering,
     access: Access,
 ) -> Result<UpdateResult, StorageError> {
     let (point_operation, shard_key) = match points {
-        PointsSelector::PointIdsSelector(PointIdsList { points, shard_key }) => {
+        PointsSelector::PointIdsSelector(PointIdsList {
+            points,
+            shard_key,
+            precondition,
+        }) => {
+            if precondition.is_some() {
+                return Err(StorageError::bad_request(
+                    "Precondition is not supported for clear payload operation",
+                ));
+            }
             (PayloadOps::ClearPayload { points }, shard_key)
         }
         PointsSelector::FilterSelector(FilterSelector { filter, shard_key }) => {
@@ -457,7 +671,49 @@ pub async fn do_clear_payload(
 
     toc.update(
         &collection_name,
-        OperationWithClockTag::new(collection_operation, clock_tag),
+        OperationWithClockTag::new(collection_operation, clock_tag)
+            .with_idempotency_key(idempotency_key),
+        wait,
+        ordering,
+        shard_selector,
+        access,
+    )
+    .await
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn do_set_attributes(
+    toc: Arc<TableOfContent>,
+    collection_name: String,
+    operation: SetAttributes,
+    clock_tag: Option<ClockTag>,
+    idempotency_key: Option<String>,
+    shard_selection: Option<ShardId>,
+    wait: bool,
+    ordering: WriteOrdering,
+    access: Access,
+) -> Result<UpdateResult, StorageError> {
+    let SetAttributes {
+        attributes,
+        points,
+        filter,
+        shard_key,
+    } = operation;
+
+    let collection_operation = CollectionUpdateOperations::AttributeOperation(
+        AttributeOps::SetAttributes(SetAttributesOp {
+            attributes,
+            points,
+            filter,
+        }),
+    );
+
+    let shard_selector = get_shard_selector_for_update(shard_selection, shard_key);
+
+    toc.update(
+        &collection_name,
+        OperationWithClockTag::new(collection_operation, clock_tag)
+            .with_idempotency_key(idempotency_key),
         wait,
         ordering,
         shard_selector,
@@ -472,124 +728,200 @@ pub async fn do_batch_update_points(
     collection_name: String,
     operations: Vec<UpdateOperation>,
     clock_tag: Option<ClockTag>,
+    idempotency_key: Option<String>,
     shard_selection: Option<ShardId>,
     wait: bool,
     ordering: WriteOrdering,
     access: Access,
 ) -> Result<Vec<UpdateResult>, StorageError> {
     let mut results = Vec::with_capacity(operations.len());
-    for operation in operations {
-        let result = match operation {
-            UpdateOperation::Upsert(operation) => {
-                do_upsert_points(
-                    toc.clone(),
-                    collection_name.clone(),
-                    operation.upsert,
-                    clock_tag,
-                    shard_selection,
-                    wait,
-                    ordering,
-                    access.clone(),
-                )
-                .await
-            }
-            UpdateOperation::Delete(operation) => {
-                do_delete_points(
-                    toc.clone(),
-                    collection_name.clone(),
-                    operation.delete,
-                    clock_tag,
-                    shard_selection,
-                    wait,
-                    ordering,
-                    access.clone(),
-                )
-                .await
-            }
-            UpdateOperation::SetPayload(operation) => {
-                do_set_payload(
-                    toc.clone(),
-                    collection_name.clone(),
-                    operation.set_payload,
-                    clock_tag,
-                    shard_selection,
-                    wait,
-                    ordering,
-                    access.clone(),
-                )
-                .await
-            }
-            UpdateOperation::OverwritePayload(operation) => {
-                do_overwrite_payload(
-                    toc.clone(),
-                    collection_name.clone(),
-                    operation.overwrite_payload,
-                    clock_tag,
-                    shard_selection,
-                    wait,
-                    ordering,
-                    access.clone(),
-                )
-                .await
-            }
-            UpdateOperation::DeletePayload(operation) => {
-                do_delete_payload(
-                    toc.clone(),
-                    collection_name.clone(),
-                    operation.delete_payload,
-                    clock_tag,
-                    shard_selection,
-                    wait,
-                    ordering,
-                    access.clone(),
-                )
-                .await
-            }
-            UpdateOperation::ClearPayload(operation) => {
-                do_clear_payload(
-                    toc.clone(),
-                    collection_name.clone(),
-                    operation.clear_payload,
-                    clock_tag,
-                    shard_selection,
-                    wait,
-                    ordering,
-                    access.clone(),
-                )
-                .await
-            }
-            UpdateOperation::UpdateVectors(operation) => {
-                do_update_vectors(
-                    toc.clone(),
-                    collection_name.clone(),
-                    operation.update_vectors,
-                    clock_tag,
-                    shard_selection,
-                    wait,
-                    ordering,
-                    access.clone(),
-                )
-                .await
-            }
-            UpdateOperation::DeleteVectors(operation) => {
-                do_delete_vectors(
-                    toc.clone(),
-                    collection_name.clone(),
-                    operation.delete_vectors,
-                    clock_tag,
-                    shard_selection,
-                    wait,
-                    ordering,
-                    access.clone(),
-                )
-                .await
-            }
-        }?;
+    for (index, operation) in operations.into_iter().enumerate() {
+        let result = do_update_operation(
+            toc.clone(),
+            collection_name.clone(),
+            operation,
+            clock_tag,
+            sub_operation_key(&idempotency_key, index),
+            shard_selection,
+            wait,
+            ordering,
+            access.clone(),
+        )
+        .await?;
         results.push(result);
     }
     Ok(results)
 }
 
+/// Apply a series of update operations, reporting the status of each operation individually
+///
+/// Unlike [`do_batch_update_points`], a failed operation does not stop the processing
+/// of the following ones. Each operation is written into the WAL of every affected shard
+/// as a single entry, so it is either applied to the shard as a whole or not at all.
+///
+/// Access violations are not reported per operation, but fail the whole request.
+#[allow(clippy::too_many_arguments)]
+pub async fn do_bulk_update_points(
+    toc: Arc<TableOfContent>,
+    collection_name: String,
+    operations: Vec<UpdateOperation>,
+    clock_tag: Option<ClockTag>,
+    idempotency_key: Option<String>,
+    shard_selection: Option<ShardId>,
+    wait: bool,
+    ordering: WriteOrdering,
+    access: Access,
+) -> Result<Vec<BulkUpdateItemResult>, StorageError> {
+    let mut results = Vec::with_capacity(operations.len());
+    for (index, operation) in operations.into_iter().enumerate() {
+        let result = do_update_operation(
+            toc.clone(),
+            collection_name.clone(),
+            operation,
+            clock_tag,
+            sub_operation_key(&idempotency_key, index),
+            shard_selection,
+            wait,
+            ordering,
+            access.clone(),
+        )
+        .await;
+        if let Err(err @ StorageError::Forbidden { .. }) = result {
+            return Err(err);
+        }
+        results.push(BulkUpdateItemResult::from(result));
+    }
+    Ok(results)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn do_update_operation(
+    toc: Arc<TableOfContent>,
+    collection_name: String,
+    operation: UpdateOperation,
+    clock_tag: Option<ClockTag>,
+    idempotency_key: Option<String>,
+    shard_selection: Option<ShardId>,
+    wait: bool,
+    ordering: WriteOrdering,
+    access: Access,
+) -> Result<UpdateResult, StorageError> {
+    match operation {
+        UpdateOperation::Upsert(operation) => {
+            do_upsert_points(
+                toc,
+                collection_name,
+                operation.upsert,
+                clock_tag,
+                idempotency_key,
+                shard_selection,
+                wait,
+                ordering,
+                access,
+            )
+            .await
+        }
+        UpdateOperation::Delete(operation) => {
+            do_delete_points(
+                toc,
+                collection_name,
+                operation.delete,
+                clock_tag,
+                idempotency_key,
+                shard_selection,
+                wait,
+                ordering,
+                access,
+            )
+            .await
+        }
+        UpdateOperation::SetPayload(operation) => {
+            do_set_payload(
+                toc,
+                collection_name,
+                operation.set_payload,
+                clock_tag,
+                idempotency_key,
+                shard_selection,
+                wait,
+                ordering,
+                access,
+            )
+            .await
+        }
+        UpdateOperation::OverwritePayload(operation) => {
+            do_overwrite_payload(
+                toc,
+                collection_name,
+                operation.overwrite_payload,
+                clock_tag,
+                idempotency_key,
+                shard_selection,
+                wait,
+                ordering,
+                access,
+            )
+            .await
+        }
+        UpdateOperation::DeletePayload(operation) => {
+            do_delete_payload(
+                toc,
+                collection_name,
+                operation.delete_payload,
+                clock_tag,
+                idempotency_key,
+                shard_selection,
+                wait,
+                ordering,
+                access,
+            )
+            .await
+        }
+        UpdateOperation::ClearPayload(operation) => {
+            do_clear_payload(
+                toc,
+                collection_name,
+                operation.clear_payload,
+                clock_tag,
+                idempotency_key,
+                shard_selection,
+                wait,
+                ordering,
+                access,
+            )
+            .await
+        }
+        UpdateOperation::UpdateVectors(operation) => {
+            do_update_vectors(
+                toc,
+                collection_name,
+                operation.update_vectors,
+                clock_tag,
+                idempotency_key,
+                shard_selection,
+                wait,
+                ordering,
+                access,
+            )
+            .await
+        }
+        UpdateOperation::DeleteVectors(operation) => {
+            do_delete_vectors(
+                toc,
+                collection_name,
+                operation.delete_vectors,
+                clock_tag,
+                idempotency_key,
+                shard_selection,
+                wait,
+                ordering,
+                access,
+            )
+            .await
+        }
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 pub async fn do_create_index_internal(
     toc: Arc<TableOfContent>,
@@ -756,7 +1088,8 @@ pub async fn do_core_search_points(
     shard_selection: ShardSelectorInternal,
     access: Access,
     timeout: Option<Duration>,
-) -> Result<Vec<ScoredPoint>, StorageError> {
+    hw_counter: &HardwareCounterCell,
+) -> Result<PartialResponse<Vec<ScoredPoint>>, StorageError> {
     let batch_res = do_core_search_batch_points(
         toc,
         collection_name,
@@ -767,12 +1100,31 @@ pub async fn do_core_search_points(
         shard_selection,
         access,
         timeout,
+        hw_counter,
     )
     .await?;
-    batch_res
+    let res = batch_res
+        .value
         .into_iter()
         .next()
-        .ok_or_else(|| StorageError::service_error("Empty search result"))
+        .ok_or_else(|| StorageError::service_error("Empty search result"))?;
+    Ok(PartialResponse {
+        value: res,
+        timed_out: batch_res.timed_out,
+        shards: batch_res.shards,
+    })
+}
+
+pub async fn do_explain_search_points(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: CoreSearchRequest,
+    shard_selection: ShardSelectorInternal,
+    access: Access,
+    timeout: Option<Duration>,
+) -> Result<(Vec<ScoredPoint>, Vec<ShardSearchPlan>), StorageError> {
+    toc.explain_search(collection_name, request, shard_selection, access, timeout)
+        .await
 }
 
 pub async fn do_search_batch_points(
@@ -782,7 +1134,8 @@ pub async fn do_search_batch_points(
     read_consistency: Option<ReadConsistency>,
     access: Access,
     timeout: Option<Duration>,
-) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
+    hw_counter: &HardwareCounterCell,
+) -> Result<PartialResponse<Vec<Vec<ScoredPoint>>>, StorageError> {
     let requests = batch_requests::<
         (CoreSearchRequest, ShardSelectorInternal),
         ShardSelectorInternal,
@@ -804,13 +1157,14 @@ pub async fn do_search_batch_points(
                 searches: core_requests,
             };
 
-            let req = toc.core_search_batch(
+            let req = toc.core_search_batch_with_shards(
                 collection_name,
                 core_batch,
                 read_consistency,
                 shard_selector,
                 access.clone(),
                 timeout,
+                hw_counter,
             );
             res.push(req);
             Ok(())
@@ -818,8 +1172,36 @@ pub async fn do_search_batch_points(
     )?;
 
     let results = futures::future::try_join_all(requests).await?;
+    let timed_out = results.iter().any(|results| results.timed_out);
+    let (results, shard_statuses): (Vec<_>, Vec<_>) = results
+        .into_iter()
+        .map(|results| (results.value, results.shards))
+        .unzip();
     let flatten_results: Vec<Vec<_>> = results.into_iter().flatten().collect();
-    Ok(flatten_results)
+    Ok(PartialResponse {
+        value: flatten_results,
+        timed_out,
+        shards: merge_shard_statuses(shard_statuses.into_iter().flatten()),
+    })
+}
+
+/// Statuses of the shards, read by multiple requests of a batch.
+/// A shard is reported as failed, if it failed for any of the requests.
+fn merge_shard_statuses(
+    statuses: impl IntoIterator<Item = ShardReadStatus>,
+) -> Vec<ShardReadStatus> {
+    let mut merged: Vec<ShardReadStatus> = Vec::new();
+    for status in statuses {
+        match merged
+            .iter_mut()
+            .find(|merged| merged.shard_id == status.shard_id)
+        {
+            Some(merged) if merged.ok => *merged = status,
+            Some(_) => {}
+            None => merged.push(status),
+        }
+    }
+    merged
 }
 
 pub async fn do_core_search_batch_points(
@@ -830,14 +1212,16 @@ pub async fn do_core_search_batch_points(
     shard_selection: ShardSelectorInternal,
     access: Access,
     timeout: Option<Duration>,
-) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
-    toc.core_search_batch(
+    hw_counter: &HardwareCounterCell,
+) -> Result<PartialResponse<Vec<Vec<ScoredPoint>>>, StorageError> {
+    toc.core_search_batch_with_shards(
         collection_name,
         request,
         read_consistency,
         shard_selection,
         access,
         timeout,
+        hw_counter,
     )
     .await
 }
@@ -945,6 +1329,60 @@ pub async fn do_count_points(
     .await
 }
 
+pub async fn do_count_distinct(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: CountDistinctRequestInternal,
+    read_consistency: Option<ReadConsistency>,
+    shard_selection: ShardSelectorInternal,
+    access: Access,
+) -> Result<Reconciled<DistinctValues>, StorageError> {
+    toc.count_distinct(
+        collection_name,
+        request,
+        read_consistency,
+        shard_selection,
+        access,
+    )
+    .await
+}
+
+pub async fn do_histogram(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: HistogramRequestInternal,
+    read_consistency: Option<ReadConsistency>,
+    shard_selection: ShardSelectorInternal,
+    access: Access,
+) -> Result<ValueDistribution, StorageError> {
+    toc.histogram(
+        collection_name,
+        request,
+        read_consistency,
+        shard_selection,
+        access,
+    )
+    .await
+}
+
+pub async fn do_field_stats(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: FieldStatsRequestInternal,
+    read_consistency: Option<ReadConsistency>,
+    shard_selection: ShardSelectorInternal,
+    access: Access,
+) -> Result<NumericStats, StorageError> {
+    toc.field_stats(
+        collection_name,
+        request,
+        read_consistency,
+        shard_selection,
+        access,
+    )
+    .await
+}
+
 pub async fn do_get_points(
     toc: &TableOfContent,
     collection_name: &str,
@@ -981,6 +1419,74 @@ pub async fn do_scroll_points(
     .await
 }
 
+/// Scroll all points, matching the request, as a stream of pages.
+///
+/// Pages of `limit` points are read on demand, so only a single page is kept in memory, however
+/// large the collection is. Ordered scroll continues from the cursor of the previous page.
+///
+/// The first page is read before returning, so that invalid requests fail with an error, rather
+/// than with a broken stream.
+pub async fn do_scroll_points_stream(
+    toc: Arc<TableOfContent>,
+    collection_name: String,
+    request: ScrollRequestInternal,
+    read_consistency: Option<ReadConsistency>,
+    shard_selection: ShardSelectorInternal,
+    access: Access,
+) -> Result<impl Stream<Item = Result<Vec<api::rest::Record>, StorageError>>, StorageError> {
+    // Deleted points are only reported per page and can't be streamed
+    let request = ScrollRequestInternal {
+        include_deleted: None,
+        ..request
+    };
+
+    let scroll_page = move |request: ScrollRequestInternal| {
+        let toc = toc.clone();
+        let collection_name = collection_name.clone();
+        let shard_selection = shard_selection.clone();
+        let access = access.clone();
+
+        async move {
+            let page = toc
+                .scroll(
+                    &collection_name,
+                    request.clone(),
+                    read_consistency,
+                    shard_selection,
+                    access,
+                )
+                .await?;
+
+            let next_request = if page.next_page_offset.is_some() || page.next_page_cursor.is_some()
+            {
+                Some(ScrollRequestInternal {
+                    offset: page.next_page_offset,
+                    cursor: page.next_page_cursor,
+                    ..request
+                })
+            } else {
+                None
+            };
+
+            Ok::<_, StorageError>((page.points, next_request))
+        }
+    };
+
+    let (first_page, next_request) = scroll_page(request).await?;
+
+    let next_pages = futures::stream::try_unfold(next_request, move |request| {
+        let scroll_page = scroll_page.clone();
+        async move {
+            match request {
+                Some(request) => scroll_page(request).await.map(Some),
+                None => Ok(None),
+            }
+        }
+    });
+
+    Ok(futures::stream::once(async { Ok(first_page) }).chain(next_pages))
+}
+
 pub async fn do_query_points(
     toc: &TableOfContent,
     collection_name: &str,
@@ -989,15 +1495,29 @@ pub async fn do_query_points(
     shard_selection: ShardSelectorInternal,
     access: Access,
     timeout: Option<Duration>,
-) -> Result<Vec<ScoredPoint>, StorageError> {
+    hw_counter: &HardwareCounterCell,
+) -> Result<PartialResponse<Vec<ScoredPoint>>, StorageError> {
     let requests = vec![(request, shard_selection)];
     let batch_res = toc
-        .query_batch(collection_name, requests, read_consistency, access, timeout)
+        .query_batch(
+            collection_name,
+            requests,
+            read_consistency,
+            access,
+            timeout,
+            hw_counter,
+        )
         .await?;
-    batch_res
+    let res = batch_res
+        .value
         .into_iter()
         .next()
-        .ok_or_else(|| StorageError::service_error("Empty query result"))
+        .ok_or_else(|| StorageError::service_error("Empty query result"))?;
+    Ok(PartialResponse {
+        value: res,
+        timed_out: batch_res.timed_out,
+        shards: batch_res.shards,
+    })
 }
 
 pub async fn do_query_batch_points(
@@ -1007,7 +1527,15 @@ pub async fn do_query_batch_points(
     read_consistency: Option<ReadConsistency>,
     access: Access,
     timeout: Option<Duration>,
-) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
-    toc.query_batch(collection_name, requests, read_consistency, access, timeout)
-        .await
+    hw_counter: &HardwareCounterCell,
+) -> Result<PartialResponse<Vec<Vec<ScoredPoint>>>, StorageError> {
+    toc.query_batch(
+        collection_name,
+        requests,
+        read_consistency,
+        access,
+        timeout,
+        hw_counter,
+    )
+    .await
 }
@@ -0,0 +1,235 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use common::rate_limiting::RateLimiter;
+use parking_lot::{Mutex, RwLock};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Configuration of request rate limits
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Default, PartialEq)]
+pub struct RateLimitsConfig {
+    /// Limits of each collection, not listed in `collections`
+    #[serde(default)]
+    pub collection_default: Option<RateLimit>,
+    /// Limits of specific collections
+    #[serde(default)]
+    pub collections: HashMap<String, RateLimit>,
+    /// Limits of specific API keys or JWT tokens, applied in addition to collection limits.
+    /// Not shown, as the keys are secrets.
+    #[serde(default, skip_serializing)]
+    #[schemars(skip)]
+    pub api_keys: HashMap<String, RateLimit>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, Default, PartialEq)]
+pub struct RateLimit {
+    /// Max number of read requests per second. Unlimited if not set.
+    #[serde(default)]
+    pub read_rps: Option<f64>,
+    /// Max number of write requests per second. Unlimited if not set.
+    #[serde(default)]
+    pub write_rps: Option<f64>,
+    /// For how many seconds requests can be accumulated and spent at once, after a pause.
+    /// Default is 1 second.
+    #[serde(default)]
+    pub burst_sec: Option<f64>,
+}
+
+impl RateLimit {
+    const DEFAULT_BURST_SEC: f64 = 1.0;
+
+    fn limiter(&self, kind: RequestKind) -> Option<RateLimiter> {
+        let rps = match kind {
+            RequestKind::Read => self.read_rps,
+            RequestKind::Write => self.write_rps,
+        }?;
+        let burst_sec = self.burst_sec.unwrap_or(Self::DEFAULT_BURST_SEC);
+        // Bucket must fit at least one request, otherwise no request would ever pass
+        let capacity = (rps * burst_sec).max(1.0);
+        Some(RateLimiter::new(rps, capacity))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RequestKind {
+    Read,
+    Write,
+}
+
+/// Request was rejected because of a rate limit
+#[derive(Debug, Clone)]
+pub struct RateLimited {
+    pub description: String,
+    /// How long to wait before retrying the request
+    pub retry_after: Duration,
+}
+
+impl RateLimited {
+    /// Retry delay in whole seconds, as used by `Retry-After` headers
+    pub fn retry_after_secs(&self) -> u64 {
+        self.retry_after.as_secs_f64().ceil().max(1.0) as u64
+    }
+}
+
+type Buckets = Mutex<HashMap<(String, RequestKind), Option<RateLimiter>>>;
+
+/// Token buckets of all rate limited collections and API keys, shared by REST and gRPC servers
+pub struct RateLimiters {
+    config: RwLock<RateLimitsConfig>,
+    collections: Buckets,
+    api_keys: Buckets,
+}
+
+impl RateLimiters {
+    pub fn new(config: RateLimitsConfig) -> Self {
+        Self {
+            config: RwLock::new(config),
+            collections: Default::default(),
+            api_keys: Default::default(),
+        }
+    }
+
+    /// Replace the limits. Buckets are created anew, so accumulated bursts are reset.
+    pub fn set_config(&self, config: RateLimitsConfig) {
+        let mut current = self.config.write();
+        *current = config;
+        self.collections.lock().clear();
+        self.api_keys.lock().clear();
+    }
+
+    /// Take a token for the request from the buckets of the collection and API key.
+    ///
+    /// API key is checked first, so a request rejected by the collection limit still
+    /// counts towards the limit of the key.
+    pub fn check(
+        &self,
+        collection_name: Option<&str>,
+        api_key: Option<&str>,
+        kind: RequestKind,
+    ) -> Result<(), RateLimited> {
+        let config = self.config.read();
+
+        if let Some(api_key) = api_key {
+            if let Some(limit) = config.api_keys.get(api_key) {
+                Self::consume(&self.api_keys, api_key, limit, kind).map_err(|retry_after| {
+                    RateLimited {
+                        description: format!(
+                            "Rate limit of the API key for {kind:?} requests exceeded"
+                        ),
+                        retry_after,
+                    }
+                })?;
+            }
+        }
+
+        if let Some(collection_name) = collection_name {
+            let limit = config
+                .collections
+                .get(collection_name)
+                .or(config.collection_default.as_ref());
+            if let Some(limit) = limit {
+                Self::consume(&self.collections, collection_name, limit, kind).map_err(
+                    |retry_after| RateLimited {
+                        description: format!(
+                            "Rate limit of collection {collection_name} for {kind:?} requests exceeded"
+                        ),
+                        retry_after,
+                    },
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn consume(
+        buckets: &Buckets,
+        name: &str,
+        limit: &RateLimit,
+        kind: RequestKind,
+    ) -> Result<(), Duration> {
+        let mut buckets = buckets.lock();
+        let bucket = buckets
+            .entry((name.to_string(), kind))
+            .or_insert_with(|| limit.limiter(kind));
+        match bucket {
+            Some(bucket) => bucket.try_consume(1.0),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collection_and_key_limits() {
+        let limits = RateLimiters::new(RateLimitsConfig {
+            collection_default: Some(RateLimit {
+                read_rps: Some(1.0),
+                write_rps: None,
+                burst_sec: Some(2.0),
+            }),
+            collections: HashMap::from([("unlimited".to_string(), RateLimit::default())]),
+            api_keys: HashMap::from([(
+                "key".to_string(),
+                RateLimit {
+                    read_rps: None,
+                    write_rps: Some(0.1),
+                    burst_sec: None,
+                },
+            )]),
+        });
+
+        // Burst of 2 reads for collections with the default limit
+        assert!(limits.check(Some("a"), None, RequestKind::Read).is_ok());
+        assert!(limits.check(Some("a"), None, RequestKind::Read).is_ok());
+        let rejected = limits
+            .check(Some("a"), None, RequestKind::Read)
+            .unwrap_err();
+        assert_eq!(rejected.retry_after_secs(), 1);
+
+        // Buckets are separate for each collection and request kind
+        assert!(limits.check(Some("b"), None, RequestKind::Read).is_ok());
+        for _ in 0..10 {
+            assert!(limits.check(Some("a"), None, RequestKind::Write).is_ok());
+            assert!(limits
+                .check(Some("unlimited"), None, RequestKind::Read)
+                .is_ok());
+        }
+
+        // Capacity is never below a single request
+        assert!(limits.check(None, Some("key"), RequestKind::Write).is_ok());
+        let rejected = limits
+            .check(None, Some("key"), RequestKind::Write)
+            .unwrap_err();
+        assert_eq!(rejected.retry_after_secs(), 10);
+        assert!(limits
+            .check(None, Some("other"), RequestKind::Write)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_reload_limits() {
+        let limits = RateLimiters::new(RateLimitsConfig::default());
+        for _ in 0..10 {
+            assert!(limits.check(Some("a"), None, RequestKind::Write).is_ok());
+        }
+
+        limits.set_config(RateLimitsConfig {
+            collection_default: Some(RateLimit {
+                read_rps: None,
+                write_rps: Some(1.0),
+                burst_sec: None,
+            }),
+            ..Default::default()
+        });
+        assert!(limits.check(Some("a"), None, RequestKind::Write).is_ok());
+        assert!(limits.check(Some("a"), None, RequestKind::Write).is_err());
+
+        limits.set_config(RateLimitsConfig::default());
+        assert!(limits.check(Some("a"), None, RequestKind::Write).is_ok());
+    }
+}
@@ -0,0 +1,66 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use collection::common::recall_evaluation::RecallEvaluationConfig;
+use collection::operations::types::RecallEvaluationRequest;
+use storage::dispatcher::Dispatcher;
+use storage::rbac::Access;
+
+const FULL_ACCESS: Access = Access::full("Recall evaluator");
+
+/// Periodically compare the approximate search with exact search, and log recall of each vector
+///
+/// Each collection is evaluated by its peer with the lowest id, as the evaluation searches all
+/// shards of the collection.
+pub async fn run_recall_evaluations(dispatcher: Arc<Dispatcher>, config: RecallEvaluationConfig) {
+    let Some(interval_sec) = config.interval_sec else {
+        return;
+    };
+    let interval = Duration::from_secs(interval_sec.max(1));
+    loop {
+        tokio::time::sleep(interval).await;
+        evaluate_collections(&dispatcher, &config).await;
+    }
+}
+
+async fn evaluate_collections(dispatcher: &Dispatcher, config: &RecallEvaluationConfig) {
+    let toc = dispatcher.toc(&FULL_ACCESS);
+    for collection_pass in toc.all_collections(&FULL_ACCESS).await {
+        let Ok(collection) = toc.get_collection(&collection_pass).await else {
+            // Collection was deleted in the meantime
+            continue;
+        };
+        if !collection.is_recall_evaluation_coordinator().await {
+            continue;
+        }
+        let collection_name = collection_pass.name();
+
+        let request = RecallEvaluationRequest {
+            sample: Some(config.sample),
+            limit: Some(config.limit),
+            ..Default::default()
+        };
+        let report = match collection.evaluate_recall(request).await {
+            Ok(report) => report,
+            Err(err) => {
+                log::warn!("Failed to evaluate recall of collection {collection_name}: {err}");
+                continue;
+            }
+        };
+
+        for (name, vector) in report.vectors {
+            if vector.queries == 0 {
+                continue;
+            }
+            log::info!(
+                "Recall@{} of vector `{name}` of collection {collection_name} over {} queries: mean {:.4}, min {:.4}, p99 latency {:.6}s, exact p99 latency {:.6}s",
+                vector.limit,
+                vector.queries,
+                vector.recall,
+                vector.min_recall,
+                vector.latency.p99,
+                vector.exact_latency.p99,
+            );
+        }
+    }
+}
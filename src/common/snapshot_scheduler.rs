@@ -0,0 +1,22 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use storage::dispatcher::Dispatcher;
+use storage::rbac::Access;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+const FULL_ACCESS: Access = Access::full("Snapshot scheduler");
+
+/// Periodically create snapshots of the collections, which have snapshot schedules
+///
+/// Schedules are replicated through consensus, and each peer snapshots its local shards.
+pub async fn run_snapshot_schedules(dispatcher: Arc<Dispatcher>) {
+    loop {
+        tokio::time::sleep(CHECK_INTERVAL).await;
+        dispatcher
+            .toc(&FULL_ACCESS)
+            .run_due_snapshot_schedules(chrono::Utc::now())
+            .await;
+    }
+}
@@ -2,6 +2,7 @@ use std::fmt;
 use std::path::Path;
 use std::sync::Arc;
 
+use collection::collection::snapshot_verification::SnapshotVerification;
 use collection::collection::Collection;
 use collection::common::sha_256::hash_file;
 use collection::operations::snapshot_ops::{
@@ -36,6 +37,51 @@ pub async fn create_shard_snapshot(
     Ok(snapshot)
 }
 
+/// # Cancel safety
+///
+/// This function is cancel safe.
+pub async fn create_segment_snapshot(
+    toc: Arc<TableOfContent>,
+    access: Access,
+    collection_name: String,
+    shard_id: ShardId,
+    segment_uuid: String,
+) -> Result<SnapshotDescription, StorageError> {
+    let collection_pass = access
+        .check_collection_access(&collection_name, AccessRequirements::new().write().whole())?;
+    let collection = toc.get_collection(&collection_pass).await?;
+
+    let snapshot = collection
+        .create_segment_snapshot(
+            shard_id,
+            &segment_uuid,
+            &toc.optional_temp_or_snapshot_temp_path()?,
+        )
+        .await?;
+
+    Ok(snapshot)
+}
+
+/// # Cancel safety
+///
+/// This function is cancel safe.
+pub async fn verify_collection_snapshot(
+    toc: &TableOfContent,
+    access: Access,
+    collection_name: &str,
+    snapshot_name: &str,
+) -> Result<SnapshotVerification, StorageError> {
+    let collection_pass =
+        access.check_collection_access(collection_name, AccessRequirements::new().whole())?;
+    let collection = toc.get_collection(&collection_pass).await?;
+
+    let report = collection
+        .verify_stored_snapshot(snapshot_name, &toc.optional_temp_or_snapshot_temp_path()?)
+        .await?;
+
+    Ok(report)
+}
+
 /// # Cancel safety
 ///
 /// This function is cancel safe.
@@ -116,7 +162,7 @@ pub async fn recover_shard_snapshot(
 
             let (snapshot_path, snapshot_temp_path) = match snapshot_location {
                 ShardSnapshotLocation::Url(url) => {
-                    if !matches!(url.scheme(), "http" | "https") {
+                    if !matches!(url.scheme(), "http" | "https" | "s3") {
                         let description = format!(
                             "Invalid snapshot URL {url}: URLs with {} scheme are not supported",
                             url.scheme(),
@@ -127,9 +173,15 @@ pub async fn recover_shard_snapshot(
 
                     let client = client.client(api_key.as_deref())?;
 
+                    let snapshots_storage = toc.get_snapshots_storage_manager()?;
                     let (snapshot_path, snapshot_temp_path) =
-                        snapshots::download::download_snapshot(&client, url, download_dir.path())
-                            .await?;
+                        snapshots::download::download_snapshot(
+                            &client,
+                            &snapshots_storage,
+                            url,
+                            download_dir.path(),
+                        )
+                        .await?;
 
                     (snapshot_path, snapshot_temp_path)
                 }
@@ -144,8 +196,11 @@ pub async fn recover_shard_snapshot(
                             snapshot_file_name,
                         )
                         .await?;
+                    let (snapshot_path, snapshot_temp_path) = snapshot_storage_manager
+                        .get_local_snapshot(&snapshot_path, download_dir.path())
+                        .await?;
                     check_shard_snapshot_file_exists(&snapshot_path)?;
-                    (snapshot_path, None)
+                    (snapshot_path, snapshot_temp_path)
                 }
             };
 
@@ -0,0 +1,22 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use storage::dispatcher::Dispatcher;
+use storage::rbac::Access;
+
+const FULL_ACCESS: Access = Access::full("Sparse vocabularies");
+
+/// Interval between refreshes of the shared sparse vocabulary statistics
+const REFRESH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Periodically refresh the statistics of the shared sparse vocabularies, so that collections
+/// with the same vocabulary use the same IDF weights.
+pub async fn run_sparse_vocabulary_refresh(dispatcher: Arc<Dispatcher>) {
+    loop {
+        dispatcher
+            .toc(&FULL_ACCESS)
+            .refresh_sparse_vocabularies()
+            .await;
+        tokio::time::sleep(REFRESH_INTERVAL).await;
+    }
+}
@@ -12,9 +12,12 @@ use uuid::Uuid;
 use crate::common::telemetry_ops::app_telemetry::{AppBuildTelemetry, AppBuildTelemetryCollector};
 use crate::common::telemetry_ops::cluster_telemetry::ClusterTelemetry;
 use crate::common::telemetry_ops::collections_telemetry::CollectionsTelemetry;
+use crate::common::telemetry_ops::latency_telemetry::LatencyTelemetryCollector;
+use crate::common::telemetry_ops::memory_telemetry::MemoryTelemetry;
 use crate::common::telemetry_ops::requests_telemetry::{
     ActixTelemetryCollector, RequestsTelemetry, TonicTelemetryCollector,
 };
+use crate::common::telemetry_ops::workloads_telemetry::WorkloadsTelemetry;
 use crate::settings::Settings;
 
 pub struct TelemetryCollector {
@@ -24,6 +27,7 @@ pub struct TelemetryCollector {
     pub app_telemetry_collector: AppBuildTelemetryCollector,
     pub actix_telemetry_collector: Arc<Mutex<ActixTelemetryCollector>>,
     pub tonic_telemetry_collector: Arc<Mutex<TonicTelemetryCollector>>,
+    pub latency_telemetry_collector: Arc<LatencyTelemetryCollector>,
 }
 
 // Whole telemetry data
@@ -34,6 +38,8 @@ pub struct TelemetryData {
     pub(crate) collections: CollectionsTelemetry,
     pub(crate) cluster: ClusterTelemetry,
     pub(crate) requests: RequestsTelemetry,
+    pub(crate) workloads: WorkloadsTelemetry,
+    pub(crate) memory: MemoryTelemetry,
 }
 
 impl Anonymize for TelemetryData {
@@ -44,6 +50,8 @@ impl Anonymize for TelemetryData {
             collections: self.collections.anonymize(),
             cluster: self.cluster.anonymize(),
             requests: self.requests.anonymize(),
+            workloads: self.workloads.anonymize(),
+            memory: self.memory.anonymize(),
         }
     }
 }
@@ -60,7 +68,6 @@ impl TelemetryCollector {
     pub fn new(settings: Settings, dispatcher: Arc<Dispatcher>, id: Uuid) -> Self {
         Self {
             process_id: id,
-            settings,
             dispatcher,
             app_telemetry_collector: AppBuildTelemetryCollector::new(),
             actix_telemetry_collector: Arc::new(Mutex::new(ActixTelemetryCollector {
@@ -69,6 +76,10 @@ impl TelemetryCollector {
             tonic_telemetry_collector: Arc::new(Mutex::new(TonicTelemetryCollector {
                 workers: Vec::new(),
             })),
+            latency_telemetry_collector: Arc::new(LatencyTelemetryCollector::new(
+                settings.service.latency_histogram_buckets.as_deref(),
+            )),
+            settings,
         }
     }
 
@@ -84,6 +95,8 @@ impl TelemetryCollector {
                 &self.tonic_telemetry_collector.lock(),
                 detail,
             ),
+            workloads: WorkloadsTelemetry::collect(),
+            memory: MemoryTelemetry::collect(),
         }
     }
 }
@@ -0,0 +1,260 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use parking_lot::Mutex;
+use segment::common::anonymize::Anonymize;
+
+/// Default upper bounds of latency histogram buckets, in seconds
+pub const DEFAULT_LATENCY_BUCKETS_SECS: &[f64] = &[
+    0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Header with W3C trace context of a request
+pub const TRACEPARENT_HEADER: &str = "traceparent";
+
+/// API operation, which latency is tracked per collection
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LatencyOperation {
+    Search,
+    Upsert,
+    Scroll,
+}
+
+impl LatencyOperation {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LatencyOperation::Search => "search",
+            LatencyOperation::Upsert => "upsert",
+            LatencyOperation::Scroll => "scroll",
+        }
+    }
+
+    /// Operation of a REST endpoint, given as `{method} {pattern}`
+    pub fn from_rest_endpoint(endpoint: &str) -> Option<Self> {
+        match endpoint {
+            "POST /collections/{name}/points/search"
+            | "POST /collections/{name}/points/search/batch"
+            | "POST /collections/{name}/points/query"
+            | "POST /collections/{name}/points/query/batch" => Some(LatencyOperation::Search),
+            "PUT /collections/{name}/points" => Some(LatencyOperation::Upsert),
+            "POST /collections/{name}/points/scroll" => Some(LatencyOperation::Scroll),
+            _ => None,
+        }
+    }
+}
+
+/// Trace id of a W3C `traceparent` header value, like `00-{trace-id}-{parent-id}-{flags}`
+pub fn trace_id_from_traceparent(traceparent: &str) -> Option<&str> {
+    let mut parts = traceparent.trim().split('-');
+    let (_version, trace_id) = (parts.next()?, parts.next()?);
+    let is_valid = trace_id.len() == 32
+        && trace_id.bytes().all(|b| b.is_ascii_hexdigit())
+        && trace_id.bytes().any(|b| b != b'0');
+    is_valid.then_some(trace_id)
+}
+
+/// Observation, linking a histogram bucket to the trace of a request
+#[derive(Clone, Debug, PartialEq)]
+pub struct Exemplar {
+    pub trace_id: String,
+    pub value_secs: f64,
+    /// Seconds since the Unix epoch
+    pub timestamp: f64,
+}
+
+#[derive(Clone, Debug)]
+pub struct LatencyHistogram {
+    pub collection: String,
+    pub operation: LatencyOperation,
+    pub count: u64,
+    pub sum_secs: f64,
+    /// Pairs of upper bound and cumulative count, excluding the `+Inf` bucket
+    pub buckets: Vec<(f64, u64)>,
+    /// Latest traced observation of each bucket, the last one is of the `+Inf` bucket
+    pub exemplars: Vec<Option<Exemplar>>,
+}
+
+impl Anonymize for LatencyHistogram {
+    fn anonymize(&self) -> Self {
+        Self {
+            collection: self.collection.anonymize(),
+            exemplars: vec![None; self.exemplars.len()],
+            ..self.clone()
+        }
+    }
+}
+
+struct HistogramData {
+    /// Non-cumulative counts of each bucket, the last one is of the `+Inf` bucket
+    counts: Vec<u64>,
+    sum_secs: f64,
+    exemplars: Vec<Option<Exemplar>>,
+}
+
+/// Latency histograms of operations of each collection, shared by REST and gRPC servers
+pub struct LatencyTelemetryCollector {
+    buckets_secs: Vec<f64>,
+    histograms: Mutex<HashMap<(String, LatencyOperation), HistogramData>>,
+}
+
+impl LatencyTelemetryCollector {
+    pub fn new(buckets_secs: Option<&[f64]>) -> Self {
+        let mut buckets_secs: Vec<f64> = buckets_secs
+            .unwrap_or(DEFAULT_LATENCY_BUCKETS_SECS)
+            .iter()
+            .copied()
+            .filter(|bound| bound.is_finite() && *bound > 0.0)
+            .collect();
+        buckets_secs.sort_by(f64::total_cmp);
+        buckets_secs.dedup();
+        Self {
+            buckets_secs,
+            histograms: Default::default(),
+        }
+    }
+
+    pub fn observe(
+        &self,
+        collection: &str,
+        operation: LatencyOperation,
+        duration: Duration,
+        trace_id: Option<&str>,
+    ) {
+        let value_secs = duration.as_secs_f64();
+        let bucket = self
+            .buckets_secs
+            .iter()
+            .position(|&bound| value_secs <= bound)
+            .unwrap_or(self.buckets_secs.len());
+
+        let mut histograms = self.histograms.lock();
+        let histogram = histograms
+            .entry((collection.to_string(), operation))
+            .or_insert_with(|| HistogramData {
+                counts: vec![0; self.buckets_secs.len() + 1],
+                sum_secs: 0.0,
+                exemplars: vec![None; self.buckets_secs.len() + 1],
+            });
+        histogram.counts[bucket] += 1;
+        histogram.sum_secs += value_secs;
+        if let Some(trace_id) = trace_id {
+            histogram.exemplars[bucket] = Some(Exemplar {
+                trace_id: trace_id.to_string(),
+                value_secs,
+                timestamp: SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs_f64(),
+            });
+        }
+    }
+
+    /// Measure latency of an operation until the returned guard is dropped
+    pub fn measure(
+        &self,
+        collection: &str,
+        operation: LatencyOperation,
+        trace_id: Option<&str>,
+    ) -> LatencyMeasurer<'_> {
+        LatencyMeasurer {
+            collector: self,
+            collection: collection.to_string(),
+            operation,
+            trace_id: trace_id.map(str::to_string),
+            instant: Instant::now(),
+        }
+    }
+
+    /// Histograms of all collections, sorted by collection and operation
+    pub fn get_histograms(&self) -> Vec<LatencyHistogram> {
+        let histograms = self.histograms.lock();
+        let mut result: Vec<_> = histograms
+            .iter()
+            .map(|((collection, operation), data)| {
+                let mut cumulative_count = 0;
+                let buckets = self
+                    .buckets_secs
+                    .iter()
+                    .zip(&data.counts)
+                    .map(|(&bound, &count)| {
+                        cumulative_count += count;
+                        (bound, cumulative_count)
+                    })
+                    .collect();
+                LatencyHistogram {
+                    collection: collection.clone(),
+                    operation: *operation,
+                    count: data.counts.iter().sum(),
+                    sum_secs: data.sum_secs,
+                    buckets,
+                    exemplars: data.exemplars.clone(),
+                }
+            })
+            .collect();
+        result.sort_by(|a, b| (&a.collection, a.operation).cmp(&(&b.collection, b.operation)));
+        result
+    }
+}
+
+/// Records latency of an operation into [`LatencyTelemetryCollector`] on drop
+pub struct LatencyMeasurer<'a> {
+    collector: &'a LatencyTelemetryCollector,
+    collection: String,
+    operation: LatencyOperation,
+    trace_id: Option<String>,
+    instant: Instant,
+}
+
+impl Drop for LatencyMeasurer<'_> {
+    fn drop(&mut self) {
+        self.collector.observe(
+            &self.collection,
+            self.operation,
+            self.instant.elapsed(),
+            self.trace_id.as_deref(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trace_id_from_traceparent() {
+        assert_eq!(
+            trace_id_from_traceparent("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"),
+            Some("4bf92f3577b34da6a3ce929d0e0e4736"),
+        );
+        assert_eq!(
+            trace_id_from_traceparent("00-00000000000000000000000000000000-00f067aa0ba902b7-01"),
+            None,
+        );
+        assert_eq!(trace_id_from_traceparent("garbage"), None);
+    }
+
+    #[test]
+    fn test_latency_histograms() {
+        let collector = LatencyTelemetryCollector::new(Some(&[0.1, 0.01, 1.0, 0.1]));
+        let search = LatencyOperation::Search;
+        collector.observe("a", search, Duration::from_millis(5), None);
+        collector.observe("a", search, Duration::from_millis(50), Some("trace"));
+        collector.observe("a", search, Duration::from_secs(5), None);
+        collector.observe(
+            "b",
+            LatencyOperation::Upsert,
+            Duration::from_millis(5),
+            None,
+        );
+
+        let histograms = collector.get_histograms();
+        assert_eq!(histograms.len(), 2);
+
+        let histogram = &histograms[0];
+        assert_eq!(histogram.collection, "a");
+        assert_eq!(histogram.count, 3);
+        assert_eq!(histogram.buckets, vec![(0.01, 1), (0.1, 2), (1.0, 2)]);
+        let exemplars: Vec<_> = histogram.exemplars.iter().map(Option::is_some).collect();
+        assert_eq!(exemplars, vec![false, true, false, false]);
+    }
+}
@@ -0,0 +1,39 @@
+use memory::budget::{self, MemoryCategory};
+use schemars::JsonSchema;
+use segment::common::anonymize::Anonymize;
+use serde::Serialize;
+
+/// Memory, reserved from the node memory budget, in bytes
+#[derive(Serialize, Clone, Debug, JsonSchema)]
+pub struct MemoryTelemetry {
+    /// Configured budget, unlimited if not set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub budget_bytes: Option<usize>,
+    /// In-RAM vector indexes
+    pub index_bytes: usize,
+    /// In-RAM quantized vectors
+    pub quantization_bytes: usize,
+    /// Caches of filter results
+    pub cache_bytes: usize,
+    /// Temporary data of search requests
+    pub scratch_bytes: usize,
+}
+
+impl MemoryTelemetry {
+    pub fn collect() -> Self {
+        let budget = budget::global();
+        Self {
+            budget_bytes: budget.limit(),
+            index_bytes: budget.used(MemoryCategory::Index),
+            quantization_bytes: budget.used(MemoryCategory::Quantization),
+            cache_bytes: budget.used(MemoryCategory::Cache),
+            scratch_bytes: budget.used(MemoryCategory::Scratch),
+        }
+    }
+}
+
+impl Anonymize for MemoryTelemetry {
+    fn anonymize(&self) -> Self {
+        self.clone()
+    }
+}
@@ -1,4 +1,7 @@
 pub mod app_telemetry;
 pub mod cluster_telemetry;
 pub mod collections_telemetry;
+pub mod latency_telemetry;
+pub mod memory_telemetry;
 pub mod requests_telemetry;
+pub mod workloads_telemetry;
@@ -0,0 +1,31 @@
+use common::scheduling::Workload;
+use schemars::JsonSchema;
+use segment::common::anonymize::Anonymize;
+use serde::Serialize;
+
+/// Number of tasks, currently queued or running in each workload of this node
+#[derive(Serialize, Clone, Debug, JsonSchema)]
+pub struct WorkloadsTelemetry {
+    /// Search batches, waiting for or running on search threads
+    pub search_queue_depth: usize,
+    /// Running optimizations
+    pub optimization_queue_depth: usize,
+    /// Update operations, waiting in update queues of shards or being applied
+    pub ingestion_queue_depth: usize,
+}
+
+impl WorkloadsTelemetry {
+    pub fn collect() -> Self {
+        Self {
+            search_queue_depth: Workload::Search.queue_depth(),
+            optimization_queue_depth: Workload::Optimization.queue_depth(),
+            ingestion_queue_depth: Workload::Ingestion.queue_depth(),
+        }
+    }
+}
+
+impl Anonymize for WorkloadsTelemetry {
+    fn anonymize(&self) -> Self {
+        self.clone()
+    }
+}
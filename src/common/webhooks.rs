@@ -0,0 +1,282 @@
+use std::collections::HashMap;
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use collection::events::{
+    CollectionCreatedEvent, CollectionDeletedEvent, OptimizerErrorEvent, ReplicaDeadEvent,
+    ShardTransferFinishedEvent,
+};
+use collection::shards::shard::{PeerId, ShardId};
+use collection::shards::transfer::ShardTransferMethod;
+use issues::broker::Subscriber;
+use serde::{Deserialize, Serialize};
+
+/// Timeout of a single webhook request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Delays before retries of a failed webhook request
+const RETRY_DELAYS: [Duration; 3] = [
+    Duration::from_secs(1),
+    Duration::from_secs(5),
+    Duration::from_secs(30),
+];
+
+/// Endpoint, notified about collection lifecycle events with `POST` requests
+#[derive(Debug, Deserialize, Clone)]
+pub struct WebhookConfig {
+    pub url: String,
+    /// Types of events to send. If not set - all events.
+    #[serde(default)]
+    pub events: Option<Vec<EventType>>,
+    /// Additional request headers, e.g. for authorization
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+}
+
+impl WebhookConfig {
+    fn accepts(&self, event_type: EventType) -> bool {
+        self.events
+            .as_ref()
+            .map_or(true, |events| events.contains(&event_type))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EventType {
+    CollectionCreated,
+    CollectionDeleted,
+    OptimizerError,
+    ShardTransferFinished,
+    ReplicaDead,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum EventDetails {
+    CollectionCreated,
+    CollectionDeleted,
+    OptimizerError {
+        shard_id: ShardId,
+        error: String,
+    },
+    ShardTransferFinished {
+        shard_id: ShardId,
+        from: PeerId,
+        to: PeerId,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        method: Option<ShardTransferMethod>,
+    },
+    ReplicaDead {
+        shard_id: ShardId,
+        peer_id: PeerId,
+    },
+}
+
+impl EventDetails {
+    fn event_type(&self) -> EventType {
+        match self {
+            EventDetails::CollectionCreated => EventType::CollectionCreated,
+            EventDetails::CollectionDeleted => EventType::CollectionDeleted,
+            EventDetails::OptimizerError { .. } => EventType::OptimizerError,
+            EventDetails::ShardTransferFinished { .. } => EventType::ShardTransferFinished,
+            EventDetails::ReplicaDead { .. } => EventType::ReplicaDead,
+        }
+    }
+}
+
+/// Body of webhook requests
+#[derive(Debug, Serialize, Clone, PartialEq)]
+struct Event {
+    timestamp: DateTime<Utc>,
+    /// Peer, which observed the event. In a cluster, each peer sends its own notification
+    /// about collection-wide events.
+    reported_by: PeerId,
+    collection: String,
+    #[serde(flatten)]
+    details: EventDetails,
+}
+
+/// Sends collection lifecycle events to the configured webhooks
+///
+/// Events are published by the [`issues`] broker and sent by a background thread, so the
+/// publishing operation never waits for a slow webhook.
+#[derive(Clone)]
+pub struct WebhookNotifier {
+    this_peer_id: PeerId,
+    sender: mpsc::Sender<Event>,
+}
+
+impl WebhookNotifier {
+    pub fn new(this_peer_id: PeerId, webhooks: Vec<WebhookConfig>) -> std::io::Result<Self> {
+        let (sender, receiver) = mpsc::channel();
+        thread::Builder::new()
+            .name("webhooks".to_string())
+            .spawn(move || send_events(receiver, webhooks))?;
+        Ok(Self {
+            this_peer_id,
+            sender,
+        })
+    }
+
+    /// Subscribe to all events, sent to webhooks
+    pub fn subscribe(self) {
+        issues::broker::add_subscriber::<CollectionCreatedEvent>(Box::new(self.clone()));
+        issues::broker::add_subscriber::<CollectionDeletedEvent>(Box::new(self.clone()));
+        issues::broker::add_subscriber::<OptimizerErrorEvent>(Box::new(self.clone()));
+        issues::broker::add_subscriber::<ShardTransferFinishedEvent>(Box::new(self.clone()));
+        issues::broker::add_subscriber::<ReplicaDeadEvent>(Box::new(self));
+    }
+
+    fn send(&self, collection: &str, details: EventDetails) {
+        let event = Event {
+            timestamp: Utc::now(),
+            reported_by: self.this_peer_id,
+            collection: collection.to_string(),
+            details,
+        };
+        if self.sender.send(event).is_err() {
+            log::error!("Webhook sender is stopped, event is dropped");
+        }
+    }
+}
+
+impl Subscriber<CollectionCreatedEvent> for WebhookNotifier {
+    fn notify(&self, event: Arc<CollectionCreatedEvent>) {
+        self.send(&event.collection_id, EventDetails::CollectionCreated);
+    }
+}
+
+impl Subscriber<CollectionDeletedEvent> for WebhookNotifier {
+    fn notify(&self, event: Arc<CollectionDeletedEvent>) {
+        self.send(&event.collection_id, EventDetails::CollectionDeleted);
+    }
+}
+
+impl Subscriber<OptimizerErrorEvent> for WebhookNotifier {
+    fn notify(&self, event: Arc<OptimizerErrorEvent>) {
+        let details = EventDetails::OptimizerError {
+            shard_id: event.shard_id,
+            error: event.error.clone(),
+        };
+        self.send(&event.collection_id, details);
+    }
+}
+
+impl Subscriber<ShardTransferFinishedEvent> for WebhookNotifier {
+    fn notify(&self, event: Arc<ShardTransferFinishedEvent>) {
+        let details = EventDetails::ShardTransferFinished {
+            shard_id: event.shard_id,
+            from: event.from,
+            to: event.to,
+            method: event.method,
+        };
+        self.send(&event.collection_id, details);
+    }
+}
+
+impl Subscriber<ReplicaDeadEvent> for WebhookNotifier {
+    fn notify(&self, event: Arc<ReplicaDeadEvent>) {
+        let details = EventDetails::ReplicaDead {
+            shard_id: event.shard_id,
+            peer_id: event.peer_id,
+        };
+        self.send(&event.collection_id, details);
+    }
+}
+
+fn send_events(receiver: mpsc::Receiver<Event>, webhooks: Vec<WebhookConfig>) {
+    let client = match reqwest::blocking::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+    {
+        Ok(client) => client,
+        Err(err) => {
+            log::error!("Failed to create webhook client, events won't be sent: {err}");
+            return;
+        }
+    };
+
+    for event in receiver {
+        let event_type = event.details.event_type();
+        for webhook in webhooks
+            .iter()
+            .filter(|webhook| webhook.accepts(event_type))
+        {
+            send_event(&client, webhook, &event);
+        }
+    }
+}
+
+fn send_event(client: &reqwest::blocking::Client, webhook: &WebhookConfig, event: &Event) {
+    let body = match serde_json::to_vec(event) {
+        Ok(body) => body,
+        Err(err) => {
+            log::error!("Failed to serialize webhook event: {err}");
+            return;
+        }
+    };
+
+    let mut delays = RETRY_DELAYS.iter();
+    loop {
+        let mut request = client
+            .post(&webhook.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body.clone());
+        for (name, value) in &webhook.headers {
+            request = request.header(name, value);
+        }
+
+        let error = match request
+            .send()
+            .and_then(|response| response.error_for_status())
+        {
+            Ok(_) => return,
+            Err(err) => err,
+        };
+        match delays.next() {
+            Some(delay) => {
+                log::debug!(
+                    "Failed to send event to webhook {}, retrying: {error}",
+                    webhook.url
+                );
+                thread::sleep(*delay);
+            }
+            None => {
+                log::warn!("Failed to send event to webhook {}: {error}", webhook.url);
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_format() {
+        let event = Event {
+            timestamp: Utc::now(),
+            reported_by: 1,
+            collection: "books".to_string(),
+            details: EventDetails::ReplicaDead {
+                shard_id: 2,
+                peer_id: 3,
+            },
+        };
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            serde_json::json!({
+                "timestamp": event.timestamp,
+                "reported_by": 1,
+                "collection": "books",
+                "type": "replica_dead",
+                "shard_id": 2,
+                "peer_id": 3,
+            }),
+        );
+    }
+}
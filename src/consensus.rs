@@ -28,6 +28,7 @@ use tokio::sync::watch;
 use tokio::time::sleep;
 use tonic::transport::{ClientTlsConfig, Uri};
 
+use crate::common::certificate_helpers;
 use crate::common::helpers;
 use crate::common::telemetry_ops::requests_telemetry::TonicTelemetryCollector;
 use crate::settings::{ConsensusConfig, Settings};
@@ -143,7 +144,8 @@ impl Consensus {
                 .clone()
                 .ok_or_else(Settings::tls_config_is_undefined_error)?;
 
-            Some(helpers::load_tls_internal_server_config(&tls_config)?)
+            let config = certificate_helpers::tonic_tls_server_config(&tls_config, true)?;
+            Some(Arc::new(config))
         } else {
             None
         };
@@ -1340,11 +1342,20 @@ mod tests {
                             optimizers_config: None,
                             shard_number: Some(2),
                             on_disk_payload: None,
+                            gridstore_payload: None,
+                            payload_columns: None,
+                            payload_compression: None,
+                            point_history_versions: None,
+                            soft_delete_retention_sec: None,
+                            computed_fields: None,
+                            attribute_columns: None,
+                            template: None,
                             replication_factor: None,
                             write_consistency_factor: None,
                             init_from: None,
                             quantization_config: None,
                             sharding_method: None,
+                            strict_mode_config: None,
                         },
                     )),
                     Access::full("For test"),
@@ -1,16 +1,18 @@
 use std::time::Duration;
 
 use collection::events::{CollectionDeletedEvent, IndexCreatedEvent, SlowQueryEvent};
-use segment::problems::unindexed_field;
+use segment::problems::UnindexedField;
 use storage::issues_subscribers::UnindexedFieldSubscriber;
 
 use crate::settings::Settings;
 
 pub fn setup_subscribers(settings: &Settings) {
-    settings
-        .service
-        .slow_query_secs
-        .map(|secs| unindexed_field::SLOW_QUERY_THRESHOLD.set(Duration::from_secs_f32(secs)));
+    UnindexedField::set_slow_query_threshold(
+        settings
+            .service
+            .slow_query_secs
+            .map(Duration::from_secs_f32),
+    );
 
     let unindexed_subscriber = UnindexedFieldSubscriber;
 
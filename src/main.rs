@@ -18,6 +18,8 @@ use std::thread::JoinHandle;
 use std::time::Duration;
 
 use ::common::cpu::{get_cpu_budget, CpuBudget};
+use ::common::io_throttling::BackgroundIo;
+use ::common::scheduling::Workload;
 use ::tonic::transport::Uri;
 use api::grpc::transport_channel_pool::TransportChannelPool;
 use clap::Parser;
@@ -38,14 +40,24 @@ use storage::rbac::Access;
 ))]
 use tikv_jemallocator::Jemalloc;
 
+use crate::common::audit::AuditLog;
+use crate::common::config_reload::ConfigReloader;
+use crate::common::consistency_checker;
 use crate::common::helpers::{
     create_general_purpose_runtime, create_search_runtime, create_update_runtime,
     load_tls_client_config,
 };
+use crate::common::index_advisor;
+use crate::common::rate_limiting::RateLimiters;
+use crate::common::recall_evaluator;
+use crate::common::snapshot_scheduler;
+use crate::common::sparse_vocabularies;
 use crate::common::telemetry::TelemetryCollector;
 use crate::common::telemetry_reporting::TelemetryReporter;
+use crate::common::webhooks::WebhookNotifier;
 use crate::greeting::welcome;
 use crate::migrations::single_to_cluster::handle_existing_collections;
+use crate::migrations::storage_compatibility::check_storage_compatibility;
 use crate::settings::Settings;
 use crate::snapshots::{recover_full_snapshot, recover_snapshots};
 use crate::startup::{remove_started_file_indicator, touch_started_file_indicator};
@@ -118,6 +130,12 @@ struct Args {
     /// Run stacktrace collector. Used for debugging.
     #[arg(long, action, default_value_t = false)]
     stacktrace: bool,
+
+    /// Check whether the storage can be loaded by this version of Qdrant and list the
+    /// migrations it needs, then exit.
+    /// The storage is not changed.
+    #[arg(long, action, default_value_t = false)]
+    check_compatibility: bool,
 }
 
 fn main() -> anyhow::Result<()> {
@@ -134,7 +152,7 @@ fn main() -> anyhow::Result<()> {
 
     remove_started_file_indicator();
 
-    let settings = Settings::new(args.config_path)?;
+    let settings = Settings::new(args.config_path.clone())?;
 
     let reporting_enabled = !settings.telemetry_disabled && !args.disable_telemetry;
 
@@ -149,6 +167,12 @@ fn main() -> anyhow::Result<()> {
     setup_panic_hook(reporting_enabled, reporting_id.to_string());
 
     memory::madvise::set_global(settings.storage.mmap_advice);
+    memory::budget::set_global_limit(
+        settings
+            .storage
+            .memory_budget_mb
+            .map(|budget_mb| budget_mb * 1024 * 1024),
+    );
     segment::vector_storage::common::set_async_scorer(settings.storage.async_scorer);
 
     welcome(&settings);
@@ -163,9 +187,18 @@ fn main() -> anyhow::Result<()> {
     // Validate as soon as possible, but we must initialize logging first
     settings.validate_and_warn();
 
+    if args.check_compatibility {
+        if !check_storage_compatibility(&settings.storage.storage_path)? {
+            anyhow::bail!("Storage is not compatible with this version of Qdrant");
+        }
+        log::info!("Storage is compatible with this version of Qdrant");
+        return Ok(());
+    }
+
     // Saved state of the consensus.
     let persistent_consensus_state =
         Persistent::load_or_init(&settings.storage.storage_path, args.bootstrap.is_none())?;
+    let this_peer_id = persistent_consensus_state.this_peer_id();
 
     let is_distributed_deployment = settings.cluster.enabled;
 
@@ -194,6 +227,20 @@ fn main() -> anyhow::Result<()> {
         vec![]
     };
 
+    // Priorities must be set before runtime threads are started
+    let performance_config = &settings.storage.performance;
+    Workload::Search.set_priority(performance_config.search_thread_priority);
+    Workload::Optimization.set_priority(performance_config.optimizer_thread_priority);
+    Workload::Ingestion.set_priority(performance_config.update_thread_priority);
+
+    let mb_per_sec = |limit: Option<usize>| limit.map(|mb| mb as u64 * 1024 * 1024);
+    BackgroundIo::Optimization
+        .set_limit(mb_per_sec(performance_config.optimizer_io_limit_mb_per_sec));
+    BackgroundIo::Snapshot.set_limit(mb_per_sec(performance_config.snapshot_io_limit_mb_per_sec));
+    BackgroundIo::ShardTransfer.set_limit(mb_per_sec(
+        performance_config.shard_transfer_io_limit_mb_per_sec,
+    ));
+
     // Create and own search runtime out of the scope of async context to ensure correct
     // destruction of it
     let search_runtime = create_search_runtime(settings.storage.performance.max_search_threads)
@@ -381,6 +428,7 @@ fn main() -> anyhow::Result<()> {
     };
 
     let tonic_telemetry_collector = telemetry_collector.tonic_telemetry_collector.clone();
+    let latency_telemetry_collector = telemetry_collector.latency_telemetry_collector.clone();
 
     //
     // Telemetry reporting
@@ -400,6 +448,37 @@ fn main() -> anyhow::Result<()> {
     // Setup subscribers to listen for issue-able events
     issues_setup::setup_subscribers(&settings);
 
+    if !settings.service.webhooks.is_empty() {
+        WebhookNotifier::new(this_peer_id, settings.service.webhooks.clone())?.subscribe();
+    }
+
+    let index_advisor_config = &settings.storage.index_advisor;
+    if index_advisor_config.enabled && index_advisor_config.auto_create_threshold.is_some() {
+        runtime_handle.spawn(index_advisor::run_auto_indexing(dispatcher_arc.clone()));
+    }
+
+    runtime_handle.spawn(snapshot_scheduler::run_snapshot_schedules(
+        dispatcher_arc.clone(),
+    ));
+
+    runtime_handle.spawn(sparse_vocabularies::run_sparse_vocabulary_refresh(
+        dispatcher_arc.clone(),
+    ));
+
+    if settings.storage.consistency_check.interval_sec.is_some() {
+        runtime_handle.spawn(consistency_checker::run_consistency_checks(
+            dispatcher_arc.clone(),
+            settings.storage.consistency_check.clone(),
+        ));
+    }
+
+    if settings.storage.recall_evaluation.interval_sec.is_some() {
+        runtime_handle.spawn(recall_evaluator::run_recall_evaluations(
+            dispatcher_arc.clone(),
+            settings.storage.recall_evaluation.clone(),
+        ));
+    }
+
     // Helper to better log start errors
     let log_err_if_any = |server_name, result| match result {
         Err(err) => {
@@ -409,6 +488,29 @@ fn main() -> anyhow::Result<()> {
         ok => ok,
     };
 
+    // Rate limits are shared by REST and gRPC servers.
+    // Limiters are created even without limits, so that limits can be set by config reload.
+    let rate_limiters = Arc::new(RateLimiters::new(
+        settings.service.rate_limits.clone().unwrap_or_default(),
+    ));
+
+    let config_reloader = Arc::new(ConfigReloader::new(
+        args.config_path,
+        &settings,
+        logger_handle.clone(),
+        rate_limiters.clone(),
+    ));
+    #[cfg(unix)]
+    runtime_handle.spawn(config_reloader.clone().reload_on_sighup());
+
+    // Audit log is shared by REST and gRPC servers
+    let audit_log = settings
+        .service
+        .audit
+        .as_ref()
+        .map(|config| AuditLog::new(config).map(Arc::new))
+        .transpose()?;
+
     //
     // REST API server
     //
@@ -417,6 +519,8 @@ fn main() -> anyhow::Result<()> {
     {
         let dispatcher_arc = dispatcher_arc.clone();
         let settings = settings.clone();
+        let rate_limiters = rate_limiters.clone();
+        let audit_log = audit_log.clone();
         let handle = thread::Builder::new()
             .name("web".to_string())
             .spawn(move || {
@@ -428,6 +532,9 @@ fn main() -> anyhow::Result<()> {
                         health_checker,
                         settings,
                         logger_handle,
+                        config_reloader,
+                        Some(rate_limiters),
+                        audit_log,
                     ),
                 )
             })
@@ -452,6 +559,9 @@ fn main() -> anyhow::Result<()> {
                         settings,
                         grpc_port,
                         runtime_handle,
+                        Some(rate_limiters),
+                        latency_telemetry_collector,
+                        audit_log,
                     ),
                 )
             })
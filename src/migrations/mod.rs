@@ -1 +1,2 @@
 pub mod single_to_cluster;
+pub mod storage_compatibility;
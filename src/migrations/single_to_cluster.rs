@@ -58,11 +58,20 @@ pub async fn handle_existing_collections(
                         .get(),
                 ),
                 on_disk_payload: Some(collection_state.config.params.on_disk_payload),
+                gridstore_payload: Some(collection_state.config.params.gridstore_payload),
+                payload_columns: Some(collection_state.config.params.payload_columns),
+                payload_compression: collection_state.config.params.payload_compression,
+                point_history_versions: collection_state.config.params.point_history_versions,
+                soft_delete_retention_sec: collection_state.config.params.soft_delete_retention_sec,
+                computed_fields: Some(collection_state.config.params.computed_fields),
+                attribute_columns: Some(collection_state.config.params.attribute_columns),
+                template: None,
                 hnsw_config: Some(collection_state.config.hnsw_config.into()),
                 wal_config: Some(collection_state.config.wal_config.into()),
                 optimizers_config: Some(collection_state.config.optimizer_config.into()),
                 init_from: None,
                 quantization_config: collection_state.config.quantization_config,
+                strict_mode_config: collection_state.config.strict_mode_config,
             },
         );
 
@@ -0,0 +1,56 @@
+use std::path::Path;
+
+use collection::collection::Collection;
+use storage::content_manager::toc::COLLECTIONS_DIR;
+
+/// Check, whether all collections in the storage can be loaded by this version, and report
+/// the migrations they need. The storage is not changed.
+///
+/// Returns `false` if any collection can't be loaded.
+pub fn check_storage_compatibility(storage_path: &str) -> anyhow::Result<bool> {
+    let collections_path = Path::new(storage_path).join(COLLECTIONS_DIR);
+    if !collections_path.exists() {
+        log::info!("No collections found in {storage_path}");
+        return Ok(true);
+    }
+
+    let mut collection_paths = std::fs::read_dir(&collections_path)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    collection_paths.retain(|path| path.is_dir());
+    collection_paths.sort();
+
+    let mut compatible = true;
+    for collection_path in collection_paths {
+        let collection_name = collection_path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy();
+        let report = Collection::check_compatibility(&collection_path);
+
+        for (segment_path, migration) in &report.pending_migrations {
+            log::info!(
+                "Collection {collection_name}: segment {} needs migration of {}: {}",
+                segment_path.display(),
+                migration.component,
+                migration.description,
+            );
+        }
+        for error in &report.errors {
+            log::error!("Collection {collection_name}: {error}");
+        }
+        log::info!(
+            "Collection {collection_name}: {}, {} segments checked, {} migrations pending",
+            if report.is_compatible() {
+                "compatible"
+            } else {
+                "not compatible"
+            },
+            report.segments,
+            report.pending_migrations.len(),
+        );
+
+        compatible &= report.is_compatible();
+    }
+    Ok(compatible)
+}
@@ -1,30 +1,57 @@
 use api::grpc::models::{CollectionsResponse, VersionInfo};
-use api::rest::{QueryRequest, QueryRequestBatch, QueryResponse, Record, ScoredPoint};
+use api::rest::{
+    HardwareUsage, QueryRequest, QueryRequestBatch, QueryResponse, Record, ScoredPoint,
+    SearchExplainResponse, ShardReadStatus,
+};
+use collection::collection::freeze::CollectionFreezeStatus;
+use collection::collection::snapshot_verification::SnapshotVerification;
+use collection::collection_manager::point_history::PointVersion;
+use collection::common::collection_stats::CollectionStats;
+use collection::common::index_advisor::IndexRecommendation;
+use collection::common::slow_query_log::SlowQueryEntry;
+use collection::common::warmup::WarmupProgress;
+use collection::operations::attribute_ops::SetAttributes;
 use collection::operations::cluster_ops::ClusterOperations;
+use collection::operations::config_impact::ConfigChangeImpact;
 use collection::operations::consistency_params::ReadConsistency;
 use collection::operations::payload_ops::{DeletePayload, SetPayload};
-use collection::operations::point_ops::{PointInsertOperations, PointsSelector, WriteOrdering};
+use collection::operations::point_ops::{
+    PointInsertOperations, PointsSelector, RestorePoints, WriteOrdering,
+};
+use collection::operations::segment_details::ShardSegmentsInfo;
 use collection::operations::snapshot_ops::{
-    ShardSnapshotRecover, SnapshotDescription, SnapshotRecover,
+    RemoteSnapshotRecover, ShardSnapshotRecover, SnapshotDescription, SnapshotRecover,
 };
 use collection::operations::types::{
-    AliasDescription, CollectionClusterInfo, CollectionExistence, CollectionInfo,
-    CollectionsAliasesResponse, CountRequest, CountResult, DiscoverRequest, DiscoverRequestBatch,
-    GroupsResult, PointGroup, PointRequest, RecommendGroupsRequest, RecommendRequest,
-    RecommendRequestBatch, ScrollRequest, ScrollResult, SearchGroupsRequest, SearchRequest,
-    SearchRequestBatch, UpdateResult,
+    AliasDescription, CollectionClusterInfo, CollectionConsistency, CollectionDiskUsage,
+    CollectionExistence, CollectionInfo, CollectionsAliasesResponse, CountDistinctRequest,
+    CountDistinctResult, CountRequest, CountResult, DiscoverRequest, DiscoverRequestBatch,
+    FieldStatsRequest, FieldStatsResult, GroupsResult, HistogramRequest, HistogramResult,
+    PayloadSchemaReport, PointGroup, PointRequest, RecallEvaluation, RecallEvaluationRequest,
+    RecommendGroupsRequest, RecommendRequest, RecommendRequestBatch, RecommendSharedRequestBatch,
+    ScrollRequest, ScrollResult, SearchGroupsRequest, SearchRequest, SearchRequestBatch,
+    SearchTuningReport, SearchTuningRequest, UpdateResult, VectorDiagnosticsReport,
 };
 use collection::operations::vector_ops::{DeleteVectors, UpdateVectors};
 use schemars::gen::SchemaSettings;
 use schemars::JsonSchema;
 use serde::Serialize;
+use storage::content_manager::api_keys::{ApiKeyDescription, CreatedApiKey, UpdateApiKey};
+use storage::content_manager::cluster_metadata::{MetadataEntry, MetadataValue};
 use storage::content_manager::collection_meta_ops::{
     ChangeAliasesOperation, CreateCollection, UpdateCollection,
 };
+use storage::content_manager::collection_templates::{
+    CollectionTemplate, CollectionTemplateDescription,
+};
+use storage::content_manager::snapshot_schedules::{SnapshotSchedule, SnapshotScheduleInfo};
 use storage::types::ClusterStatus;
 
+use crate::common::audit::AuditEntry;
+use crate::common::config_reload::RuntimeConfig;
+use crate::common::export::ExportRequest;
 use crate::common::helpers::LocksOption;
-use crate::common::points::{CreateFieldIndex, UpdateOperations};
+use crate::common::points::{BulkUpdateItemResult, CreateFieldIndex, UpdateOperations};
 use crate::common::telemetry::TelemetryData;
 
 mod actix;
@@ -84,6 +111,49 @@ struct AllDefinitions {
     be: QueryRequest,
     bf: QueryRequestBatch,
     bg: QueryResponse,
+    bh: SearchExplainResponse,
+    bi: BulkUpdateItemResult,
+    bj: ExportRequest,
+    bk: RemoteSnapshotRecover,
+    bl: HardwareUsage,
+    bm: SlowQueryEntry,
+    bn: ShardSegmentsInfo,
+    bo: CollectionDiskUsage,
+    bp: AuditEntry,
+    bq: ApiKeyDescription,
+    br: UpdateApiKey,
+    bs: CreatedApiKey,
+    bt: CountDistinctRequest,
+    bu: CountDistinctResult,
+    bv: HistogramRequest,
+    bw: HistogramResult,
+    bx: FieldStatsRequest,
+    by: FieldStatsResult,
+    bz: PayloadSchemaReport,
+    ca: IndexRecommendation,
+    cb: WarmupProgress,
+    cc: PointVersion,
+    cd: RestorePoints,
+    ce: CollectionTemplate,
+    cf: Vec<CollectionTemplateDescription>,
+    cg: ConfigChangeImpact,
+    ch: RecommendSharedRequestBatch,
+    ci: ShardReadStatus,
+    cj: CollectionStats,
+    ck: MetadataValue,
+    cl: Vec<MetadataEntry>,
+    cm: SnapshotSchedule,
+    cn: Vec<SnapshotScheduleInfo>,
+    co: SnapshotVerification,
+    cp: RuntimeConfig,
+    cq: CollectionConsistency,
+    cr: CollectionFreezeStatus,
+    cs: SetAttributes,
+    ct: VectorDiagnosticsReport,
+    cu: RecallEvaluationRequest,
+    cv: RecallEvaluation,
+    cw: SearchTuningRequest,
+    cx: SearchTuningReport,
 }
 
 fn save_schema<T: JsonSchema>() {
@@ -4,12 +4,17 @@ use api::grpc::transport_channel_pool::{
     DEFAULT_CONNECT_TIMEOUT, DEFAULT_GRPC_TIMEOUT, DEFAULT_POOL_SIZE,
 };
 use collection::operations::validation;
+use common::hardware_counter::HardwareCounterCell;
 use config::{Config, ConfigError, Environment, File, FileFormat, Source};
 use serde::Deserialize;
 use storage::types::StorageConfig;
 use validator::Validate;
 
+use crate::common::audit::AuditConfig;
 use crate::common::debugger::DebuggerConfig;
+use crate::common::inference::InferenceConfig;
+use crate::common::rate_limiting::RateLimitsConfig;
+use crate::common::webhooks::WebhookConfig;
 use crate::tracing;
 
 const DEFAULT_CONFIG: &str = include_str!("../config/config.yaml");
@@ -22,6 +27,10 @@ pub struct ServiceConfig {
     pub http_port: u16,
     pub grpc_port: Option<u16>, // None means that gRPC is disabled
     pub max_request_size_mb: usize,
+    /// Max size of upsert requests, which are parsed while being received.
+    /// If not set - same as `max_request_size_mb`.
+    #[serde(default)]
+    pub max_upsert_request_size_mb: Option<usize>,
     pub max_workers: Option<usize>,
     #[serde(default = "default_cors")]
     pub enable_cors: bool,
@@ -46,6 +55,37 @@ pub struct ServiceConfig {
 
     /// How much time is considered too long for a query to execute.
     pub slow_query_secs: Option<f32>,
+
+    /// If hardware usage of search and query requests is reported in responses.
+    #[serde(default)]
+    pub hardware_reporting: bool,
+
+    /// Limits of request rates per collection and API key. Not limited if not set.
+    #[serde(default)]
+    pub rate_limits: Option<RateLimitsConfig>,
+
+    /// Upper bounds of buckets of per collection latency histograms in `/metrics`, in seconds.
+    #[serde(default)]
+    pub latency_histogram_buckets: Option<Vec<f64>>,
+
+    /// Audit log of write operations. Disabled if not set.
+    #[serde(default)]
+    pub audit: Option<AuditConfig>,
+
+    /// Endpoints, notified about collection lifecycle events
+    #[serde(default)]
+    pub webhooks: Vec<WebhookConfig>,
+}
+
+impl ServiceConfig {
+    /// Hardware counter for a new request, enabled only if usage reporting is configured
+    pub fn hardware_counter(&self) -> HardwareCounterCell {
+        if self.hardware_reporting {
+            HardwareCounterCell::new()
+        } else {
+            HardwareCounterCell::disabled()
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Clone, Default, Validate)]
@@ -143,6 +183,8 @@ pub struct Settings {
     pub tls: Option<TlsConfig>,
     #[serde(default)]
     pub debugger: DebuggerConfig,
+    #[validate]
+    pub inference: Option<InferenceConfig>,
     /// A list of messages for errors that happened during loading the configuration. We collect
     /// them and store them here while loading because then our logger is not configured yet.
     /// We therefore need to log these messages later, after the logger is ready.
@@ -20,17 +20,32 @@ use storage::dispatcher::Dispatcher;
 use tonic::{Request, Response, Status};
 
 use super::validate;
+use crate::common::audit::AuditLog;
 use crate::common::collections::*;
 use crate::tonic::api::collections_common::get;
+use crate::tonic::audit::PendingAudit;
 use crate::tonic::auth::extract_access;
 
 pub struct CollectionsService {
     dispatcher: Arc<Dispatcher>,
+    audit_log: Option<Arc<AuditLog>>,
 }
 
 impl CollectionsService {
-    pub fn new(dispatcher: Arc<Dispatcher>) -> Self {
-        Self { dispatcher }
+    pub fn new(dispatcher: Arc<Dispatcher>, audit_log: Option<Arc<AuditLog>>) -> Self {
+        Self {
+            dispatcher,
+            audit_log,
+        }
+    }
+
+    fn start_audit<R>(
+        &self,
+        request: &Request<R>,
+        operation: &'static str,
+        collection: Option<&str>,
+    ) -> PendingAudit {
+        PendingAudit::new(self.audit_log.as_ref(), request, operation, collection)
     }
 
     async fn perform_operation<O>(
@@ -96,7 +111,13 @@ impl Collections for CollectionsService {
         request: Request<CreateCollection>,
     ) -> Result<Response<CollectionOperationResponse>, Status> {
         validate(request.get_ref())?;
-        self.perform_operation(request).await
+        let collection_name = request.get_ref().collection_name.clone();
+        let audit = self.start_audit(
+            &request,
+            "qdrant.Collections/Create",
+            Some(&collection_name),
+        );
+        audit.record(self.perform_operation(request).await)
     }
 
     async fn update(
@@ -104,7 +125,13 @@ impl Collections for CollectionsService {
         request: Request<UpdateCollection>,
     ) -> Result<Response<CollectionOperationResponse>, Status> {
         validate(request.get_ref())?;
-        self.perform_operation(request).await
+        let collection_name = request.get_ref().collection_name.clone();
+        let audit = self.start_audit(
+            &request,
+            "qdrant.Collections/Update",
+            Some(&collection_name),
+        );
+        audit.record(self.perform_operation(request).await)
     }
 
     async fn delete(
@@ -112,7 +139,13 @@ impl Collections for CollectionsService {
         request: Request<DeleteCollection>,
     ) -> Result<Response<CollectionOperationResponse>, Status> {
         validate(request.get_ref())?;
-        self.perform_operation(request).await
+        let collection_name = request.get_ref().collection_name.clone();
+        let audit = self.start_audit(
+            &request,
+            "qdrant.Collections/Delete",
+            Some(&collection_name),
+        );
+        audit.record(self.perform_operation(request).await)
     }
 
     async fn update_aliases(
@@ -120,7 +153,8 @@ impl Collections for CollectionsService {
         request: Request<ChangeAliases>,
     ) -> Result<Response<CollectionOperationResponse>, Status> {
         validate(request.get_ref())?;
-        self.perform_operation(request).await
+        let audit = self.start_audit(&request, "qdrant.Collections/UpdateAliases", None);
+        audit.record(self.perform_operation(request).await)
     }
 
     async fn list_collection_aliases(
@@ -202,97 +236,128 @@ impl Collections for CollectionsService {
         mut request: Request<UpdateCollectionClusterSetupRequest>,
     ) -> Result<Response<UpdateCollectionClusterSetupResponse>, Status> {
         validate(request.get_ref())?;
+        let collection_name = request.get_ref().collection_name.clone();
+        let audit = self.start_audit(
+            &request,
+            "qdrant.Collections/UpdateCollectionClusterSetup",
+            Some(&collection_name),
+        );
         let access = extract_access(&mut request);
-        let UpdateCollectionClusterSetupRequest {
-            collection_name,
-            operation,
-            timeout,
-            ..
-        } = request.into_inner();
-        let result = do_update_collection_cluster(
-            self.dispatcher.as_ref(),
-            collection_name,
-            operation
-                .ok_or(Status::new(tonic::Code::InvalidArgument, "empty operation"))?
-                .try_into()?,
-            access,
-            timeout.map(std::time::Duration::from_secs),
+        audit.record(
+            async {
+                let UpdateCollectionClusterSetupRequest {
+                    collection_name,
+                    operation,
+                    timeout,
+                    ..
+                } = request.into_inner();
+                let result = do_update_collection_cluster(
+                    self.dispatcher.as_ref(),
+                    collection_name,
+                    operation
+                        .ok_or(Status::new(tonic::Code::InvalidArgument, "empty operation"))?
+                        .try_into()?,
+                    access,
+                    timeout.map(std::time::Duration::from_secs),
+                )
+                .await
+                .map_err(error_to_status)?;
+                Ok(Response::new(UpdateCollectionClusterSetupResponse {
+                    result,
+                }))
+            }
+            .await,
         )
-        .await
-        .map_err(error_to_status)?;
-        Ok(Response::new(UpdateCollectionClusterSetupResponse {
-            result,
-        }))
     }
 
     async fn create_shard_key(
         &self,
         mut request: Request<CreateShardKeyRequest>,
     ) -> Result<Response<CreateShardKeyResponse>, Status> {
+        let collection_name = request.get_ref().collection_name.clone();
+        let audit = self.start_audit(
+            &request,
+            "qdrant.Collections/CreateShardKey",
+            Some(&collection_name),
+        );
         let access = extract_access(&mut request);
+        audit.record(
+            async {
+                let CreateShardKeyRequest {
+                    collection_name,
+                    request,
+                    timeout,
+                } = request.into_inner();
+
+                let Some(request) = request else {
+                    return Err(Status::new(tonic::Code::InvalidArgument, "empty request"));
+                };
+
+                let timeout = timeout.map(std::time::Duration::from_secs);
+
+                let operation = ClusterOperations::CreateShardingKey(CreateShardingKeyOperation {
+                    create_sharding_key: request.try_into()?,
+                });
+
+                let result = do_update_collection_cluster(
+                    self.dispatcher.as_ref(),
+                    collection_name,
+                    operation,
+                    access,
+                    timeout,
+                )
+                .await
+                .map_err(error_to_status)?;
 
-        let CreateShardKeyRequest {
-            collection_name,
-            request,
-            timeout,
-        } = request.into_inner();
-
-        let Some(request) = request else {
-            return Err(Status::new(tonic::Code::InvalidArgument, "empty request"));
-        };
-
-        let timeout = timeout.map(std::time::Duration::from_secs);
-
-        let operation = ClusterOperations::CreateShardingKey(CreateShardingKeyOperation {
-            create_sharding_key: request.try_into()?,
-        });
-
-        let result = do_update_collection_cluster(
-            self.dispatcher.as_ref(),
-            collection_name,
-            operation,
-            access,
-            timeout,
+                Ok(Response::new(CreateShardKeyResponse { result }))
+            }
+            .await,
         )
-        .await
-        .map_err(error_to_status)?;
-
-        Ok(Response::new(CreateShardKeyResponse { result }))
     }
 
     async fn delete_shard_key(
         &self,
         mut request: Request<DeleteShardKeyRequest>,
     ) -> Result<Response<DeleteShardKeyResponse>, Status> {
+        let collection_name = request.get_ref().collection_name.clone();
+        let audit = self.start_audit(
+            &request,
+            "qdrant.Collections/DeleteShardKey",
+            Some(&collection_name),
+        );
         let access = extract_access(&mut request);
+        audit.record(
+            async {
+                let DeleteShardKeyRequest {
+                    collection_name,
+                    request,
+                    timeout,
+                } = request.into_inner();
+
+                let Some(request) = request else {
+                    return Err(Status::new(tonic::Code::InvalidArgument, "empty request"));
+                };
+
+                let timeout = timeout.map(std::time::Duration::from_secs);
+
+                let operation = ClusterOperations::DropShardingKey(DropShardingKeyOperation {
+                    drop_sharding_key: request.try_into()?,
+                });
+
+                let result = do_update_collection_cluster(
+                    self.dispatcher.as_ref(),
+                    collection_name,
+                    operation,
+                    access,
+                    timeout,
+                )
+                .await
+                .map_err(error_to_status)?;
 
-        let DeleteShardKeyRequest {
-            collection_name,
-            request,
-            timeout,
-        } = request.into_inner();
-
-        let Some(request) = request else {
-            return Err(Status::new(tonic::Code::InvalidArgument, "empty request"));
-        };
-
-        let timeout = timeout.map(std::time::Duration::from_secs);
-
-        let operation = ClusterOperations::DropShardingKey(DropShardingKeyOperation {
-            drop_sharding_key: request.try_into()?,
-        });
-
-        let result = do_update_collection_cluster(
-            self.dispatcher.as_ref(),
-            collection_name,
-            operation,
-            access,
-            timeout,
+                Ok(Response::new(DeleteShardKeyResponse { result }))
+            }
+            .await,
         )
-        .await
-        .map_err(error_to_status)?;
-
-        Ok(Response::new(DeleteShardKeyResponse { result }))
     }
 }
 
@@ -3,39 +3,111 @@ use std::time::Duration;
 
 use api::grpc::qdrant::points_server::Points;
 use api::grpc::qdrant::{
-    ClearPayloadPoints, CountPoints, CountResponse, CreateFieldIndexCollection,
-    DeleteFieldIndexCollection, DeletePayloadPoints, DeletePointVectors, DeletePoints,
-    DiscoverBatchPoints, DiscoverBatchResponse, DiscoverPoints, DiscoverResponse, GetPoints,
-    GetResponse, PointsOperationResponse, QueryBatchPoints, QueryBatchResponse, QueryPoints,
-    QueryResponse, RecommendBatchPoints, RecommendBatchResponse, RecommendGroupsResponse,
-    RecommendPointGroups, RecommendPoints, RecommendResponse, ScrollPoints, ScrollResponse,
-    SearchBatchPoints, SearchBatchResponse, SearchGroupsResponse, SearchPointGroups, SearchPoints,
-    SearchResponse, SetPayloadPoints, UpdateBatchPoints, UpdateBatchResponse, UpdatePointVectors,
-    UpsertPoints,
+    BulkUpdateResponse, ClearPayloadPoints, CountDistinctPoints, CountDistinctResponse,
+    CountPoints, CountResponse, CreateFieldIndexCollection, DeleteFieldIndexCollection,
+    DeletePayloadPoints, DeletePointVectors, DeletePoints, DiscoverBatchPoints,
+    DiscoverBatchResponse, DiscoverPoints, DiscoverResponse, FieldStatsPoints, FieldStatsResponse,
+    GetPoints, GetResponse, HistogramPoints, HistogramResponse, PointsOperationResponse,
+    QueryBatchPoints, QueryBatchResponse, QueryPoints, QueryResponse, RecommendBatchPoints,
+    RecommendBatchResponse, RecommendGroupsResponse, RecommendPointGroups, RecommendPoints,
+    RecommendResponse, ScrollPoints, ScrollResponse, SearchBatchPoints, SearchBatchResponse,
+    SearchGroupsResponse, SearchPointGroups, SearchPoints, SearchResponse, SetAttributesPoints,
+    SetPayloadPoints, UpdateBatchPoints, UpdateBatchResponse, UpdatePointVectors, UpsertPoints,
+    UpsertPointsChunk,
 };
 use collection::operations::types::CoreSearchRequest;
 use storage::dispatcher::Dispatcher;
-use tonic::{Request, Response, Status};
+use tonic::{Request, Response, Status, Streaming};
 
 use super::points_common::{
-    delete_vectors, discover, discover_batch, query, query_batch, recommend_groups, search_groups,
-    update_batch, update_vectors,
+    bulk_update, delete_vectors, discover, discover_batch, extract_idempotency_key, query,
+    query_batch, recommend_groups, search_groups, update_batch, update_vectors,
 };
 use super::validate;
+use crate::common::audit::AuditLog;
+use crate::common::rate_limiting::{RateLimiters, RequestKind};
+use crate::common::telemetry_ops::latency_telemetry::{
+    trace_id_from_traceparent, LatencyMeasurer, LatencyOperation, LatencyTelemetryCollector,
+    TRACEPARENT_HEADER,
+};
+use crate::settings::ServiceConfig;
 use crate::tonic::api::points_common::{
-    clear_payload, convert_shard_selector_for_read, core_search_batch, count, create_field_index,
-    delete, delete_field_index, delete_payload, get, overwrite_payload, recommend, recommend_batch,
-    scroll, search, set_payload, upsert,
+    clear_payload, convert_shard_selector_for_read, core_search_batch, count, count_distinct,
+    create_field_index, delete, delete_field_index, delete_payload, field_stats, get, histogram,
+    overwrite_payload, patch_payload, recommend, recommend_batch, scroll, scroll_stream, search,
+    set_attributes, set_payload, upsert, upsert_stream, ScrollStreamResponseStream,
+    UpsertStreamResponseStream,
 };
+use crate::tonic::audit::PendingAudit;
 use crate::tonic::auth::extract_access;
+use crate::tonic::rate_limiting::{check_rate_limit, CollectionRequest};
 
 pub struct PointsService {
     dispatcher: Arc<Dispatcher>,
+    service_config: ServiceConfig,
+    rate_limiters: Option<Arc<RateLimiters>>,
+    latency_collector: Arc<LatencyTelemetryCollector>,
+    audit_log: Option<Arc<AuditLog>>,
 }
 
 impl PointsService {
-    pub fn new(dispatcher: Arc<Dispatcher>) -> Self {
-        Self { dispatcher }
+    pub fn new(
+        dispatcher: Arc<Dispatcher>,
+        service_config: ServiceConfig,
+        rate_limiters: Option<Arc<RateLimiters>>,
+        latency_collector: Arc<LatencyTelemetryCollector>,
+        audit_log: Option<Arc<AuditLog>>,
+    ) -> Self {
+        Self {
+            dispatcher,
+            service_config,
+            rate_limiters,
+            latency_collector,
+            audit_log,
+        }
+    }
+
+    /// Measure latency of the request in its collection, until the returned guard is dropped
+    fn measure_latency<R: CollectionRequest>(
+        &self,
+        request: &Request<R>,
+        operation: LatencyOperation,
+    ) -> LatencyMeasurer<'_> {
+        let trace_id = request
+            .metadata()
+            .get(TRACEPARENT_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(trace_id_from_traceparent);
+        self.latency_collector
+            .measure(request.get_ref().collection_name(), operation, trace_id)
+    }
+
+    /// Audit the write request in its collection
+    fn start_audit<R: CollectionRequest>(
+        &self,
+        request: &Request<R>,
+        operation: &'static str,
+    ) -> PendingAudit {
+        let collection = request.get_ref().collection_name();
+        PendingAudit::new(
+            self.audit_log.as_ref(),
+            request,
+            operation,
+            Some(collection),
+        )
+    }
+
+    fn check_rate_limit<R: CollectionRequest>(
+        &self,
+        request: &Request<R>,
+        kind: RequestKind,
+    ) -> Result<(), Status> {
+        check_rate_limit(
+            self.rate_limiters.as_deref(),
+            request,
+            Some(request.get_ref().collection_name()),
+            kind,
+        )
     }
 }
 
@@ -46,18 +118,50 @@ impl Points for PointsService {
         mut request: Request<UpsertPoints>,
     ) -> Result<Response<PointsOperationResponse>, Status> {
         validate(request.get_ref())?;
+        self.check_rate_limit(&request, RequestKind::Write)?;
+        let _latency = self.measure_latency(&request, LatencyOperation::Upsert);
 
+        let audit = self.start_audit(&request, "qdrant.Points/Upsert");
+        let idempotency_key = extract_idempotency_key(&request)?;
         let access = extract_access(&mut request);
 
-        upsert(
-            self.dispatcher.toc(&access).clone(),
-            request.into_inner(),
+        audit.record(
+            upsert(
+                self.dispatcher.toc(&access).clone(),
+                request.into_inner(),
+                None,
+                idempotency_key,
+                None,
+                access,
+            )
+            .await
+            .map(|resp| resp.map(Into::into)),
+        )
+    }
+
+    type UpsertStreamStream = UpsertStreamResponseStream;
+
+    async fn upsert_stream(
+        &self,
+        mut request: Request<Streaming<UpsertPointsChunk>>,
+    ) -> Result<Response<Self::UpsertStreamStream>, Status> {
+        // Collection is only known from the chunks, so only the limit of the API key applies
+        check_rate_limit(
+            self.rate_limiters.as_deref(),
+            &request,
             None,
+            RequestKind::Write,
+        )?;
+
+        let audit = PendingAudit::new(
+            self.audit_log.as_ref(),
+            &request,
+            "qdrant.Points/UpsertStream",
             None,
-            access,
-        )
-        .await
-        .map(|resp| resp.map(Into::into))
+        );
+        let access = extract_access(&mut request);
+
+        audit.record(upsert_stream(self.dispatcher.clone(), request.into_inner(), access).await)
     }
 
     async fn delete(
@@ -65,22 +169,29 @@ impl Points for PointsService {
         mut request: Request<DeletePoints>,
     ) -> Result<Response<PointsOperationResponse>, Status> {
         validate(request.get_ref())?;
+        self.check_rate_limit(&request, RequestKind::Write)?;
 
+        let audit = self.start_audit(&request, "qdrant.Points/Delete");
+        let idempotency_key = extract_idempotency_key(&request)?;
         let access = extract_access(&mut request);
 
-        delete(
-            self.dispatcher.toc(&access).clone(),
-            request.into_inner(),
-            None,
-            None,
-            access,
+        audit.record(
+            delete(
+                self.dispatcher.toc(&access).clone(),
+                request.into_inner(),
+                None,
+                idempotency_key,
+                None,
+                access,
+            )
+            .await
+            .map(|resp| resp.map(Into::into)),
         )
-        .await
-        .map(|resp| resp.map(Into::into))
     }
 
     async fn get(&self, mut request: Request<GetPoints>) -> Result<Response<GetResponse>, Status> {
         validate(request.get_ref())?;
+        self.check_rate_limit(&request, RequestKind::Read)?;
 
         let access = extract_access(&mut request);
 
@@ -98,18 +209,24 @@ impl Points for PointsService {
         mut request: Request<UpdatePointVectors>,
     ) -> Result<Response<PointsOperationResponse>, Status> {
         validate(request.get_ref())?;
+        self.check_rate_limit(&request, RequestKind::Write)?;
 
+        let audit = self.start_audit(&request, "qdrant.Points/UpdateVectors");
+        let idempotency_key = extract_idempotency_key(&request)?;
         let access = extract_access(&mut request);
 
-        update_vectors(
-            self.dispatcher.toc(&access).clone(),
-            request.into_inner(),
-            None,
-            None,
-            access,
+        audit.record(
+            update_vectors(
+                self.dispatcher.toc(&access).clone(),
+                request.into_inner(),
+                None,
+                idempotency_key,
+                None,
+                access,
+            )
+            .await
+            .map(|resp| resp.map(Into::into)),
         )
-        .await
-        .map(|resp| resp.map(Into::into))
     }
 
     async fn delete_vectors(
@@ -117,18 +234,24 @@ impl Points for PointsService {
         mut request: Request<DeletePointVectors>,
     ) -> Result<Response<PointsOperationResponse>, Status> {
         validate(request.get_ref())?;
+        self.check_rate_limit(&request, RequestKind::Write)?;
 
+        let audit = self.start_audit(&request, "qdrant.Points/DeleteVectors");
+        let idempotency_key = extract_idempotency_key(&request)?;
         let access = extract_access(&mut request);
 
-        delete_vectors(
-            self.dispatcher.toc(&access).clone(),
-            request.into_inner(),
-            None,
-            None,
-            access,
+        audit.record(
+            delete_vectors(
+                self.dispatcher.toc(&access).clone(),
+                request.into_inner(),
+                None,
+                idempotency_key,
+                None,
+                access,
+            )
+            .await
+            .map(|resp| resp.map(Into::into)),
         )
-        .await
-        .map(|resp| resp.map(Into::into))
     }
 
     async fn set_payload(
@@ -136,18 +259,24 @@ impl Points for PointsService {
         mut request: Request<SetPayloadPoints>,
     ) -> Result<Response<PointsOperationResponse>, Status> {
         validate(request.get_ref())?;
+        self.check_rate_limit(&request, RequestKind::Write)?;
 
+        let audit = self.start_audit(&request, "qdrant.Points/SetPayload");
+        let idempotency_key = extract_idempotency_key(&request)?;
         let access = extract_access(&mut request);
 
-        set_payload(
-            self.dispatcher.toc(&access).clone(),
-            request.into_inner(),
-            None,
-            None,
-            access,
+        audit.record(
+            set_payload(
+                self.dispatcher.toc(&access).clone(),
+                request.into_inner(),
+                None,
+                idempotency_key,
+                None,
+                access,
+            )
+            .await
+            .map(|resp| resp.map(Into::into)),
         )
-        .await
-        .map(|resp| resp.map(Into::into))
     }
 
     async fn overwrite_payload(
@@ -155,18 +284,49 @@ impl Points for PointsService {
         mut request: Request<SetPayloadPoints>,
     ) -> Result<Response<PointsOperationResponse>, Status> {
         validate(request.get_ref())?;
+        self.check_rate_limit(&request, RequestKind::Write)?;
 
+        let audit = self.start_audit(&request, "qdrant.Points/OverwritePayload");
+        let idempotency_key = extract_idempotency_key(&request)?;
         let access = extract_access(&mut request);
 
-        overwrite_payload(
-            self.dispatcher.toc(&access).clone(),
-            request.into_inner(),
-            None,
-            None,
-            access,
+        audit.record(
+            overwrite_payload(
+                self.dispatcher.toc(&access).clone(),
+                request.into_inner(),
+                None,
+                idempotency_key,
+                None,
+                access,
+            )
+            .await
+            .map(|resp| resp.map(Into::into)),
+        )
+    }
+
+    async fn patch_payload(
+        &self,
+        mut request: Request<SetPayloadPoints>,
+    ) -> Result<Response<PointsOperationResponse>, Status> {
+        validate(request.get_ref())?;
+        self.check_rate_limit(&request, RequestKind::Write)?;
+
+        let audit = self.start_audit(&request, "qdrant.Points/PatchPayload");
+        let idempotency_key = extract_idempotency_key(&request)?;
+        let access = extract_access(&mut request);
+
+        audit.record(
+            patch_payload(
+                self.dispatcher.toc(&access).clone(),
+                request.into_inner(),
+                None,
+                idempotency_key,
+                None,
+                access,
+            )
+            .await
+            .map(|resp| resp.map(Into::into)),
         )
-        .await
-        .map(|resp| resp.map(Into::into))
     }
 
     async fn delete_payload(
@@ -174,18 +334,24 @@ impl Points for PointsService {
         mut request: Request<DeletePayloadPoints>,
     ) -> Result<Response<PointsOperationResponse>, Status> {
         validate(request.get_ref())?;
+        self.check_rate_limit(&request, RequestKind::Write)?;
 
+        let audit = self.start_audit(&request, "qdrant.Points/DeletePayload");
+        let idempotency_key = extract_idempotency_key(&request)?;
         let access = extract_access(&mut request);
 
-        delete_payload(
-            self.dispatcher.toc(&access).clone(),
-            request.into_inner(),
-            None,
-            None,
-            access,
+        audit.record(
+            delete_payload(
+                self.dispatcher.toc(&access).clone(),
+                request.into_inner(),
+                None,
+                idempotency_key,
+                None,
+                access,
+            )
+            .await
+            .map(|resp| resp.map(Into::into)),
         )
-        .await
-        .map(|resp| resp.map(Into::into))
     }
 
     async fn clear_payload(
@@ -193,18 +359,49 @@ impl Points for PointsService {
         mut request: Request<ClearPayloadPoints>,
     ) -> Result<Response<PointsOperationResponse>, Status> {
         validate(request.get_ref())?;
+        self.check_rate_limit(&request, RequestKind::Write)?;
 
+        let audit = self.start_audit(&request, "qdrant.Points/ClearPayload");
+        let idempotency_key = extract_idempotency_key(&request)?;
         let access = extract_access(&mut request);
 
-        clear_payload(
-            self.dispatcher.toc(&access).clone(),
-            request.into_inner(),
-            None,
-            None,
-            access,
+        audit.record(
+            clear_payload(
+                self.dispatcher.toc(&access).clone(),
+                request.into_inner(),
+                None,
+                idempotency_key,
+                None,
+                access,
+            )
+            .await
+            .map(|resp| resp.map(Into::into)),
+        )
+    }
+
+    async fn set_attributes(
+        &self,
+        mut request: Request<SetAttributesPoints>,
+    ) -> Result<Response<PointsOperationResponse>, Status> {
+        validate(request.get_ref())?;
+        self.check_rate_limit(&request, RequestKind::Write)?;
+
+        let audit = self.start_audit(&request, "qdrant.Points/SetAttributes");
+        let idempotency_key = extract_idempotency_key(&request)?;
+        let access = extract_access(&mut request);
+
+        audit.record(
+            set_attributes(
+                self.dispatcher.toc(&access).clone(),
+                request.into_inner(),
+                None,
+                idempotency_key,
+                None,
+                access,
+            )
+            .await
+            .map(|resp| resp.map(Into::into)),
         )
-        .await
-        .map(|resp| resp.map(Into::into))
     }
 
     async fn update_batch(
@@ -212,17 +409,47 @@ impl Points for PointsService {
         mut request: Request<UpdateBatchPoints>,
     ) -> Result<Response<UpdateBatchResponse>, Status> {
         validate(request.get_ref())?;
+        self.check_rate_limit(&request, RequestKind::Write)?;
 
+        let audit = self.start_audit(&request, "qdrant.Points/UpdateBatch");
+        let idempotency_key = extract_idempotency_key(&request)?;
         let access = extract_access(&mut request);
 
-        update_batch(
-            self.dispatcher.toc(&access).clone(),
-            request.into_inner(),
-            None,
-            None,
-            access,
+        audit.record(
+            update_batch(
+                self.dispatcher.toc(&access).clone(),
+                request.into_inner(),
+                None,
+                idempotency_key,
+                None,
+                access,
+            )
+            .await,
+        )
+    }
+
+    async fn bulk_update(
+        &self,
+        mut request: Request<UpdateBatchPoints>,
+    ) -> Result<Response<BulkUpdateResponse>, Status> {
+        validate(request.get_ref())?;
+        self.check_rate_limit(&request, RequestKind::Write)?;
+
+        let audit = self.start_audit(&request, "qdrant.Points/BulkUpdate");
+        let idempotency_key = extract_idempotency_key(&request)?;
+        let access = extract_access(&mut request);
+
+        audit.record(
+            bulk_update(
+                self.dispatcher.toc(&access).clone(),
+                request.into_inner(),
+                None,
+                idempotency_key,
+                None,
+                access,
+            )
+            .await,
         )
-        .await
     }
 
     async fn create_field_index(
@@ -230,18 +457,22 @@ impl Points for PointsService {
         mut request: Request<CreateFieldIndexCollection>,
     ) -> Result<Response<PointsOperationResponse>, Status> {
         validate(request.get_ref())?;
+        self.check_rate_limit(&request, RequestKind::Write)?;
 
+        let audit = self.start_audit(&request, "qdrant.Points/CreateFieldIndex");
         let access = extract_access(&mut request);
 
-        create_field_index(
-            self.dispatcher.clone(),
-            request.into_inner(),
-            None,
-            None,
-            access,
+        audit.record(
+            create_field_index(
+                self.dispatcher.clone(),
+                request.into_inner(),
+                None,
+                None,
+                access,
+            )
+            .await
+            .map(|resp| resp.map(Into::into)),
         )
-        .await
-        .map(|resp| resp.map(Into::into))
     }
 
     async fn delete_field_index(
@@ -249,18 +480,22 @@ impl Points for PointsService {
         mut request: Request<DeleteFieldIndexCollection>,
     ) -> Result<Response<PointsOperationResponse>, Status> {
         validate(request.get_ref())?;
+        self.check_rate_limit(&request, RequestKind::Write)?;
 
+        let audit = self.start_audit(&request, "qdrant.Points/DeleteFieldIndex");
         let access = extract_access(&mut request);
 
-        delete_field_index(
-            self.dispatcher.clone(),
-            request.into_inner(),
-            None,
-            None,
-            access,
+        audit.record(
+            delete_field_index(
+                self.dispatcher.clone(),
+                request.into_inner(),
+                None,
+                None,
+                access,
+            )
+            .await
+            .map(|resp| resp.map(Into::into)),
         )
-        .await
-        .map(|resp| resp.map(Into::into))
     }
 
     async fn search(
@@ -268,12 +503,15 @@ impl Points for PointsService {
         mut request: Request<SearchPoints>,
     ) -> Result<Response<SearchResponse>, Status> {
         validate(request.get_ref())?;
+        self.check_rate_limit(&request, RequestKind::Read)?;
+        let _latency = self.measure_latency(&request, LatencyOperation::Search);
         let access = extract_access(&mut request);
         search(
             self.dispatcher.toc(&access),
             request.into_inner(),
             None,
             access,
+            &self.service_config.hardware_counter(),
         )
         .await
     }
@@ -283,6 +521,8 @@ impl Points for PointsService {
         mut request: Request<SearchBatchPoints>,
     ) -> Result<Response<SearchBatchResponse>, Status> {
         validate(request.get_ref())?;
+        self.check_rate_limit(&request, RequestKind::Read)?;
+        let _latency = self.measure_latency(&request, LatencyOperation::Search);
 
         let access = extract_access(&mut request);
 
@@ -313,6 +553,7 @@ impl Points for PointsService {
             read_consistency,
             access,
             timeout,
+            &self.service_config.hardware_counter(),
         )
         .await
     }
@@ -322,6 +563,7 @@ impl Points for PointsService {
         mut request: Request<SearchPointGroups>,
     ) -> Result<Response<SearchGroupsResponse>, Status> {
         validate(request.get_ref())?;
+        self.check_rate_limit(&request, RequestKind::Read)?;
         let access = extract_access(&mut request);
         search_groups(
             self.dispatcher.toc(&access),
@@ -337,6 +579,8 @@ impl Points for PointsService {
         mut request: Request<ScrollPoints>,
     ) -> Result<Response<ScrollResponse>, Status> {
         validate(request.get_ref())?;
+        self.check_rate_limit(&request, RequestKind::Read)?;
+        let _latency = self.measure_latency(&request, LatencyOperation::Scroll);
 
         let access = extract_access(&mut request);
 
@@ -349,11 +593,31 @@ impl Points for PointsService {
         .await
     }
 
+    type ScrollStreamStream = ScrollStreamResponseStream;
+
+    async fn scroll_stream(
+        &self,
+        mut request: Request<ScrollPoints>,
+    ) -> Result<Response<Self::ScrollStreamStream>, Status> {
+        validate(request.get_ref())?;
+        self.check_rate_limit(&request, RequestKind::Read)?;
+
+        let access = extract_access(&mut request);
+
+        scroll_stream(
+            self.dispatcher.toc(&access).clone(),
+            request.into_inner(),
+            access,
+        )
+        .await
+    }
+
     async fn recommend(
         &self,
         mut request: Request<RecommendPoints>,
     ) -> Result<Response<RecommendResponse>, Status> {
         validate(request.get_ref())?;
+        self.check_rate_limit(&request, RequestKind::Read)?;
         let access = extract_access(&mut request);
         recommend(self.dispatcher.toc(&access), request.into_inner(), access).await
     }
@@ -363,6 +627,7 @@ impl Points for PointsService {
         mut request: Request<RecommendBatchPoints>,
     ) -> Result<Response<RecommendBatchResponse>, Status> {
         validate(request.get_ref())?;
+        self.check_rate_limit(&request, RequestKind::Read)?;
         let access = extract_access(&mut request);
         let RecommendBatchPoints {
             collection_name,
@@ -386,6 +651,7 @@ impl Points for PointsService {
         mut request: Request<RecommendPointGroups>,
     ) -> Result<Response<RecommendGroupsResponse>, Status> {
         validate(request.get_ref())?;
+        self.check_rate_limit(&request, RequestKind::Read)?;
 
         let access = extract_access(&mut request);
 
@@ -397,6 +663,7 @@ impl Points for PointsService {
         mut request: Request<DiscoverPoints>,
     ) -> Result<Response<DiscoverResponse>, Status> {
         validate(request.get_ref())?;
+        self.check_rate_limit(&request, RequestKind::Read)?;
 
         let access = extract_access(&mut request);
 
@@ -408,6 +675,7 @@ impl Points for PointsService {
         mut request: Request<DiscoverBatchPoints>,
     ) -> Result<Response<DiscoverBatchResponse>, Status> {
         validate(request.get_ref())?;
+        self.check_rate_limit(&request, RequestKind::Read)?;
 
         let access = extract_access(&mut request);
 
@@ -434,6 +702,7 @@ impl Points for PointsService {
         mut request: Request<CountPoints>,
     ) -> Result<Response<CountResponse>, Status> {
         validate(request.get_ref())?;
+        self.check_rate_limit(&request, RequestKind::Read)?;
 
         let access = extract_access(&mut request);
 
@@ -446,17 +715,74 @@ impl Points for PointsService {
         .await
     }
 
+    async fn count_distinct(
+        &self,
+        mut request: Request<CountDistinctPoints>,
+    ) -> Result<Response<CountDistinctResponse>, Status> {
+        validate(request.get_ref())?;
+        self.check_rate_limit(&request, RequestKind::Read)?;
+
+        let access = extract_access(&mut request);
+
+        count_distinct(
+            self.dispatcher.toc(&access),
+            request.into_inner(),
+            None,
+            access,
+        )
+        .await
+    }
+
+    async fn histogram(
+        &self,
+        mut request: Request<HistogramPoints>,
+    ) -> Result<Response<HistogramResponse>, Status> {
+        validate(request.get_ref())?;
+        self.check_rate_limit(&request, RequestKind::Read)?;
+
+        let access = extract_access(&mut request);
+
+        histogram(
+            self.dispatcher.toc(&access),
+            request.into_inner(),
+            None,
+            access,
+        )
+        .await
+    }
+
+    async fn field_stats(
+        &self,
+        mut request: Request<FieldStatsPoints>,
+    ) -> Result<Response<FieldStatsResponse>, Status> {
+        validate(request.get_ref())?;
+        self.check_rate_limit(&request, RequestKind::Read)?;
+
+        let access = extract_access(&mut request);
+
+        field_stats(
+            self.dispatcher.toc(&access),
+            request.into_inner(),
+            None,
+            access,
+        )
+        .await
+    }
+
     async fn query(
         &self,
         mut request: Request<QueryPoints>,
     ) -> Result<Response<QueryResponse>, Status> {
         validate(request.get_ref())?;
+        self.check_rate_limit(&request, RequestKind::Read)?;
+        let _latency = self.measure_latency(&request, LatencyOperation::Search);
         let access = extract_access(&mut request);
         query(
             self.dispatcher.toc(&access),
             request.into_inner(),
             None,
             access,
+            &self.service_config.hardware_counter(),
         )
         .await
     }
@@ -466,6 +792,8 @@ impl Points for PointsService {
         mut request: Request<QueryBatchPoints>,
     ) -> Result<Response<QueryBatchResponse>, Status> {
         validate(request.get_ref())?;
+        self.check_rate_limit(&request, RequestKind::Read)?;
+        let _latency = self.measure_latency(&request, LatencyOperation::Search);
         let access = extract_access(&mut request);
         let request = request.into_inner();
         let QueryBatchPoints {
@@ -482,6 +810,7 @@ impl Points for PointsService {
             read_consistency,
             access,
             timeout,
+            &self.service_config.hardware_counter(),
         )
         .await
     }
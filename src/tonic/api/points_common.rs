@@ -1,22 +1,32 @@
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-use api::grpc::conversions::{json_path_from_proto, proto_to_payloads};
+use api::grpc::conversions::{
+    attributes_from_proto, json_path_from_proto, proto_to_payloads,
+    scored_points_with_payload_limit,
+};
 use api::grpc::qdrant::payload_index_params::IndexParams;
 use api::grpc::qdrant::points_update_operation::{ClearPayload, Operation, PointStructList};
 use api::grpc::qdrant::{
-    points_update_operation, BatchResult, ClearPayloadPoints, CoreSearchPoints, CountPoints,
+    points_update_operation, BatchResult, BulkUpdateItemResult, BulkUpdateResponse,
+    ClearPayloadPoints, CoreSearchPoints, CountDistinctPoints, CountDistinctResponse, CountPoints,
     CountResponse, CreateFieldIndexCollection, DeleteFieldIndexCollection, DeletePayloadPoints,
     DeletePointVectors, DeletePoints, DiscoverBatchResponse, DiscoverPoints, DiscoverResponse,
-    FieldType, GetPoints, GetResponse, PayloadIndexParams, PointsOperationResponseInternal,
-    PointsSelector, QueryBatchResponse, QueryPoints, QueryResponse,
+    DistinctValuesResponseInternal, FieldStatsPoints, FieldStatsResponse, FieldType, GetPoints,
+    GetResponse, HistogramPoints, HistogramResponse, NumericStatsResponseInternal,
+    PayloadIndexParams, PointsOperationResponse, PointsOperationResponseInternal, PointsSelector,
+    PointsUpdateOperation, QueryBatchResponse, QueryPoints, QueryResponse,
     ReadConsistency as ReadConsistencyGrpc, RecommendBatchResponse, RecommendGroupsResponse,
-    RecommendPointGroups, RecommendPoints, RecommendResponse, ScrollPoints, ScrollResponse,
-    SearchBatchResponse, SearchGroupsResponse, SearchPointGroups, SearchPoints, SearchResponse,
-    SetPayloadPoints, SyncPoints, UpdateBatchPoints, UpdateBatchResponse, UpdatePointVectors,
-    UpsertPoints,
+    RecommendPointGroups, RecommendPoints, RecommendResponse, RetrievedPoint, ScrollPoints,
+    ScrollResponse, SearchBatchResponse, SearchGroupsResponse, SearchPointGroups, SearchPoints,
+    SearchResponse, SetAttributesPoints, SetPayloadPoints, SyncPoints, UpdateBatchPoints,
+    UpdateBatchResponse, UpdatePointVectors, UpsertPoints, UpsertPointsChunk, UpsertStreamResponse,
+    ValueDistributionResponseInternal, WriteOrdering,
 };
 use api::rest::{OrderByInterface, ShardKeySelector};
+use collection::common::idempotency::{parse_idempotency_key, IDEMPOTENCY_KEY_HEADER};
+use collection::operations::attribute_ops::SetAttributes;
+use collection::operations::config_diff::OptimizersConfigDiff;
 use collection::operations::consistency_params::ReadConsistency;
 use collection::operations::conversions::{
     try_discover_request_from_grpc, try_points_selector_from_grpc, write_ordering_from_proto,
@@ -28,33 +38,62 @@ use collection::operations::point_ops::{
 use collection::operations::query_enum::QueryEnum;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
-    default_exact_count, CoreSearchRequest, CoreSearchRequestBatch, PointRequestInternal,
+    default_exact_count, default_percentiles, CoreSearchRequest, CoreSearchRequestBatch,
+    CountDistinctRequestInternal, CountDistinctResult, FieldStatsRequestInternal, FieldStatsResult,
+    HistogramBuckets, HistogramRequestInternal, HistogramResult, PointRequestInternal,
     RecommendExample, Record, ScrollRequestInternal,
 };
 use collection::operations::universal_query::collection_query::CollectionQueryRequest;
 use collection::operations::vector_ops::{DeleteVectors, PointVectors, UpdateVectors};
 use collection::operations::{ClockTag, CollectionUpdateOperations, OperationWithClockTag};
+use collection::optimizers_builder::DEFAULT_INDEXING_THRESHOLD_KB;
+use collection::shards::resolve::Reconciled;
 use collection::shards::shard::ShardId;
+use common::hardware_counter::HardwareCounterCell;
+use futures::stream::BoxStream;
+use futures::{StreamExt, TryStreamExt};
 use itertools::Itertools;
+use segment::data_types::aggregation::{DistinctValues, NumericStats, ValueDistribution};
 use segment::data_types::order_by::OrderBy;
 use segment::data_types::vectors::VectorStructInternal;
 use segment::types::{
     ExtendedPointId, Filter, PayloadFieldSchema, PayloadSchemaParams, PayloadSchemaType,
 };
+use storage::content_manager::collection_meta_ops::{
+    CollectionMetaOperations, UpdateCollectionOperation,
+};
 use storage::content_manager::conversions::error_to_status;
 use storage::content_manager::toc::TableOfContent;
 use storage::dispatcher::Dispatcher;
 use storage::rbac::Access;
-use tonic::{Response, Status};
+use tokio::sync::mpsc;
+use tonic::{Request, Response, Status, Streaming};
 
+use super::validate;
+use crate::common::collections::do_get_collection;
 use crate::common::points::{
-    do_clear_payload, do_core_search_points, do_count_points, do_create_index,
+    do_clear_payload, do_core_search_points, do_count_distinct, do_count_points, do_create_index,
     do_create_index_internal, do_delete_index, do_delete_index_internal, do_delete_payload,
-    do_delete_points, do_delete_vectors, do_get_points, do_overwrite_payload,
-    do_query_batch_points, do_query_points, do_scroll_points, do_search_batch_points,
-    do_set_payload, do_update_vectors, do_upsert_points, CreateFieldIndex,
+    do_delete_points, do_delete_vectors, do_field_stats, do_get_points, do_histogram,
+    do_overwrite_payload, do_patch_payload, do_query_batch_points, do_query_points,
+    do_scroll_points, do_scroll_points_stream, do_search_batch_points, do_set_attributes,
+    do_set_payload, do_update_vectors, do_upsert_points, sub_operation_key, CreateFieldIndex,
 };
 
+/// Idempotency key of an update, sent in the `idempotency-key` metadata
+pub fn extract_idempotency_key<T>(request: &Request<T>) -> Result<Option<String>, Status> {
+    request
+        .metadata()
+        .get(IDEMPOTENCY_KEY_HEADER)
+        .map(|value| {
+            let value = value.to_str().map_err(|_| {
+                Status::invalid_argument("Idempotency key must be a visible ASCII string")
+            })?;
+            parse_idempotency_key(value).map_err(|err| Status::invalid_argument(err.to_string()))
+        })
+        .transpose()
+}
+
 fn extract_points_selector(
     points_selector: Option<PointsSelector>,
 ) -> Result<(Option<Vec<ExtendedPointId>>, Option<Filter>), Status> {
@@ -102,6 +141,7 @@ pub async fn upsert(
     toc: Arc<TableOfContent>,
     upsert_points: UpsertPoints,
     clock_tag: Option<ClockTag>,
+    idempotency_key: Option<String>,
     shard_selection: Option<ShardId>,
     access: Access,
 ) -> Result<Response<PointsOperationResponseInternal>, Status> {
@@ -111,6 +151,7 @@ pub async fn upsert(
         points,
         ordering,
         shard_key_selector,
+        precondition,
     } = upsert_points;
     let points = points
         .into_iter()
@@ -119,6 +160,7 @@ pub async fn upsert(
     let operation = PointInsertOperations::PointsList(PointsList {
         points,
         shard_key: shard_key_selector.map(ShardKeySelector::from),
+        precondition: precondition.map(TryInto::try_into).transpose()?,
     });
     let timing = Instant::now();
     let result = do_upsert_points(
@@ -126,6 +168,7 @@ pub async fn upsert(
         collection_name,
         operation,
         clock_tag,
+        idempotency_key,
         shard_selection,
         wait.unwrap_or(false),
         write_ordering_from_proto(ordering)?,
@@ -138,6 +181,174 @@ pub async fn upsert(
     Ok(Response::new(response))
 }
 
+pub type UpsertStreamResponseStream = BoxStream<'static, Result<UpsertStreamResponse, Status>>;
+
+/// Upsert points from a stream of chunks, acknowledging each chunk once it is applied.
+///
+/// Chunks are processed one by one: the next chunk is not read from the stream
+/// until the previous one is acknowledged, so the client is slowed down by HTTP/2 flow control.
+pub async fn upsert_stream(
+    dispatcher: Arc<Dispatcher>,
+    mut chunks: Streaming<UpsertPointsChunk>,
+    access: Access,
+) -> Result<Response<UpsertStreamResponseStream>, Status> {
+    let (sender, receiver) = mpsc::channel(1);
+
+    tokio::spawn(async move {
+        let mut deferred_indexing = None;
+
+        let result = process_upsert_stream(
+            &dispatcher,
+            &mut chunks,
+            &access,
+            &mut deferred_indexing,
+            &sender,
+        )
+        .await;
+
+        if let Err(err) = result {
+            let _ = sender.send(Err(err)).await;
+        }
+
+        // Restore indexing, even if the stream was interrupted
+        if let Some((collection_name, indexing_threshold)) = deferred_indexing {
+            if let Err(err) =
+                set_indexing_threshold(&dispatcher, collection_name, indexing_threshold, access)
+                    .await
+            {
+                log::error!("Can't restore indexing threshold after upsert stream: {err}");
+            }
+        }
+    });
+
+    let stream = futures::stream::unfold(receiver, |mut receiver| async move {
+        receiver.recv().await.map(|item| (item, receiver))
+    });
+
+    Ok(Response::new(stream.boxed()))
+}
+
+async fn process_upsert_stream(
+    dispatcher: &Dispatcher,
+    chunks: &mut Streaming<UpsertPointsChunk>,
+    access: &Access,
+    deferred_indexing: &mut Option<(String, usize)>,
+    sender: &mpsc::Sender<Result<UpsertStreamResponse, Status>>,
+) -> Result<(), Status> {
+    let mut stream_collection_name: Option<String> = None;
+    let mut offset = 0;
+
+    while let Some(chunk) = chunks.message().await? {
+        validate(&chunk)?;
+
+        let UpsertPointsChunk {
+            collection_name,
+            wait,
+            points,
+            ordering,
+            shard_key_selector,
+            defer_indexing,
+        } = chunk;
+
+        match &stream_collection_name {
+            Some(stream_collection_name) if stream_collection_name != &collection_name => {
+                return Err(Status::invalid_argument(format!(
+                    "All chunks of the stream must target the same collection, expected {stream_collection_name}, got {collection_name}",
+                )));
+            }
+            Some(_) => {}
+            None => {
+                if defer_indexing.unwrap_or(false) {
+                    let collection_info = do_get_collection(
+                        dispatcher.toc(access),
+                        access.clone(),
+                        &collection_name,
+                        None,
+                    )
+                    .await
+                    .map_err(error_to_status)?;
+                    let indexing_threshold = collection_info
+                        .config
+                        .optimizer_config
+                        .indexing_threshold
+                        .unwrap_or(DEFAULT_INDEXING_THRESHOLD_KB);
+
+                    set_indexing_threshold(dispatcher, collection_name.clone(), 0, access.clone())
+                        .await?;
+                    *deferred_indexing = Some((collection_name.clone(), indexing_threshold));
+                }
+                stream_collection_name = Some(collection_name.clone());
+            }
+        }
+
+        let points_count = points.len() as u64;
+
+        let response = upsert(
+            dispatcher.toc(access).clone(),
+            UpsertPoints {
+                collection_name,
+                wait,
+                points,
+                ordering,
+                shard_key_selector,
+                precondition: None,
+            },
+            None,
+            None,
+            None,
+            access.clone(),
+        )
+        .await?;
+
+        offset += points_count;
+
+        let PointsOperationResponse { result, time } = response.into_inner().into();
+        let ack = UpsertStreamResponse {
+            offset,
+            result,
+            time,
+        };
+
+        if sender.send(Ok(ack)).await.is_err() {
+            // Client has disconnected, no need to process the rest of the stream
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+async fn set_indexing_threshold(
+    dispatcher: &Dispatcher,
+    collection_name: String,
+    indexing_threshold: usize,
+    access: Access,
+) -> Result<(), Status> {
+    let mut operation = UpdateCollectionOperation::new_empty(collection_name);
+    operation.update_collection.optimizers_config = Some(OptimizersConfigDiff {
+        deleted_threshold: None,
+        vacuum_min_vector_number: None,
+        default_segment_number: None,
+        max_segment_size: None,
+        memmap_threshold: None,
+        indexing_threshold: Some(indexing_threshold),
+        flush_interval_sec: None,
+        max_optimization_threads: None,
+        defer_indexing: None,
+    });
+
+    dispatcher
+        .submit_collection_meta_op(
+            CollectionMetaOperations::UpdateCollection(operation),
+            access,
+            None,
+        )
+        .await
+        .map_err(error_to_status)?;
+
+    Ok(())
+}
+
 pub async fn sync(
     toc: Arc<TableOfContent>,
     sync_points: SyncPoints,
@@ -196,6 +407,7 @@ pub async fn delete(
     toc: Arc<TableOfContent>,
     delete_points: DeletePoints,
     clock_tag: Option<ClockTag>,
+    idempotency_key: Option<String>,
     shard_selection: Option<ShardId>,
     access: Access,
 ) -> Result<Response<PointsOperationResponseInternal>, Status> {
@@ -205,19 +417,34 @@ pub async fn delete(
         points,
         ordering,
         shard_key_selector,
+        precondition,
     } = delete_points;
 
-    let points_selector = match points {
+    let mut points_selector = match points {
         None => return Err(Status::invalid_argument("PointSelector is missing")),
         Some(p) => try_points_selector_from_grpc(p, shard_key_selector)?,
     };
 
+    if let Some(precondition) = precondition {
+        match &mut points_selector {
+            point_ops::PointsSelector::PointIdsSelector(points_list) => {
+                points_list.precondition = Some(precondition.try_into()?);
+            }
+            point_ops::PointsSelector::FilterSelector(_) => {
+                return Err(Status::invalid_argument(
+                    "Precondition is only supported for deleting points by ids",
+                ));
+            }
+        }
+    }
+
     let timing = Instant::now();
     let result = do_delete_points(
         toc,
         collection_name,
         points_selector,
         clock_tag,
+        idempotency_key,
         shard_selection,
         wait.unwrap_or(false),
         write_ordering_from_proto(ordering)?,
@@ -234,6 +461,7 @@ pub async fn update_vectors(
     toc: Arc<TableOfContent>,
     update_point_vectors: UpdatePointVectors,
     clock_tag: Option<ClockTag>,
+    idempotency_key: Option<String>,
     shard_selection: Option<ShardId>,
     access: Access,
 ) -> Result<Response<PointsOperationResponseInternal>, Status> {
@@ -273,6 +501,7 @@ pub async fn update_vectors(
         collection_name,
         operation,
         clock_tag,
+        idempotency_key,
         shard_selection,
         wait.unwrap_or(false),
         write_ordering_from_proto(ordering)?,
@@ -289,6 +518,7 @@ pub async fn delete_vectors(
     toc: Arc<TableOfContent>,
     delete_point_vectors: DeletePointVectors,
     clock_tag: Option<ClockTag>,
+    idempotency_key: Option<String>,
     shard_selection: Option<ShardId>,
     access: Access,
 ) -> Result<Response<PointsOperationResponseInternal>, Status> {
@@ -320,6 +550,7 @@ pub async fn delete_vectors(
         collection_name,
         operation,
         clock_tag,
+        idempotency_key,
         shard_selection,
         wait.unwrap_or(false),
         write_ordering_from_proto(ordering)?,
@@ -336,6 +567,7 @@ pub async fn set_payload(
     toc: Arc<TableOfContent>,
     set_payload_points: SetPayloadPoints,
     clock_tag: Option<ClockTag>,
+    idempotency_key: Option<String>,
     shard_selection: Option<ShardId>,
     access: Access,
 ) -> Result<Response<PointsOperationResponseInternal>, Status> {
@@ -347,6 +579,7 @@ pub async fn set_payload(
         ordering,
         shard_key_selector,
         key,
+        precondition,
     } = set_payload_points;
     let key = key.map(|k| json_path_from_proto(&k)).transpose()?;
 
@@ -357,6 +590,7 @@ pub async fn set_payload(
         filter,
         shard_key: shard_key_selector.map(ShardKeySelector::from),
         key,
+        precondition: precondition.map(TryInto::try_into).transpose()?,
     };
 
     let timing = Instant::now();
@@ -365,6 +599,7 @@ pub async fn set_payload(
         collection_name,
         operation,
         clock_tag,
+        idempotency_key,
         shard_selection,
         wait.unwrap_or(false),
         write_ordering_from_proto(ordering)?,
@@ -381,6 +616,7 @@ pub async fn overwrite_payload(
     toc: Arc<TableOfContent>,
     set_payload_points: SetPayloadPoints,
     clock_tag: Option<ClockTag>,
+    idempotency_key: Option<String>,
     shard_selection: Option<ShardId>,
     access: Access,
 ) -> Result<Response<PointsOperationResponseInternal>, Status> {
@@ -402,6 +638,7 @@ pub async fn overwrite_payload(
         shard_key: shard_key_selector.map(ShardKeySelector::from),
         // overwrite operation don't support indicate path of property
         key: None,
+        precondition: None,
     };
 
     let timing = Instant::now();
@@ -410,6 +647,55 @@ pub async fn overwrite_payload(
         collection_name,
         operation,
         clock_tag,
+        idempotency_key,
+        shard_selection,
+        wait.unwrap_or(false),
+        write_ordering_from_proto(ordering)?,
+        access,
+    )
+    .await
+    .map_err(error_to_status)?;
+
+    let response = points_operation_response_internal(timing, result);
+    Ok(Response::new(response))
+}
+
+pub async fn patch_payload(
+    toc: Arc<TableOfContent>,
+    set_payload_points: SetPayloadPoints,
+    clock_tag: Option<ClockTag>,
+    idempotency_key: Option<String>,
+    shard_selection: Option<ShardId>,
+    access: Access,
+) -> Result<Response<PointsOperationResponseInternal>, Status> {
+    let SetPayloadPoints {
+        collection_name,
+        wait,
+        payload,
+        points_selector,
+        ordering,
+        shard_key_selector,
+        ..
+    } = set_payload_points;
+
+    let (points, filter) = extract_points_selector(points_selector)?;
+    let operation = collection::operations::payload_ops::SetPayload {
+        payload: proto_to_payloads(payload)?,
+        points,
+        filter,
+        shard_key: shard_key_selector.map(ShardKeySelector::from),
+        // patch operation don't support indicate path of property
+        key: None,
+        precondition: None,
+    };
+
+    let timing = Instant::now();
+    let result = do_patch_payload(
+        toc,
+        collection_name,
+        operation,
+        clock_tag,
+        idempotency_key,
         shard_selection,
         wait.unwrap_or(false),
         write_ordering_from_proto(ordering)?,
@@ -426,6 +712,7 @@ pub async fn delete_payload(
     toc: Arc<TableOfContent>,
     delete_payload_points: DeletePayloadPoints,
     clock_tag: Option<ClockTag>,
+    idempotency_key: Option<String>,
     shard_selection: Option<ShardId>,
     access: Access,
 ) -> Result<Response<PointsOperationResponseInternal>, Status> {
@@ -453,6 +740,7 @@ pub async fn delete_payload(
         collection_name,
         operation,
         clock_tag,
+        idempotency_key,
         shard_selection,
         wait.unwrap_or(false),
         write_ordering_from_proto(ordering)?,
@@ -469,6 +757,7 @@ pub async fn clear_payload(
     toc: Arc<TableOfContent>,
     clear_payload_points: ClearPayloadPoints,
     clock_tag: Option<ClockTag>,
+    idempotency_key: Option<String>,
     shard_selection: Option<ShardId>,
     access: Access,
 ) -> Result<Response<PointsOperationResponseInternal>, Status> {
@@ -491,6 +780,51 @@ pub async fn clear_payload(
         collection_name,
         points_selector,
         clock_tag,
+        idempotency_key,
+        shard_selection,
+        wait.unwrap_or(false),
+        write_ordering_from_proto(ordering)?,
+        access,
+    )
+    .await
+    .map_err(error_to_status)?;
+
+    let response = points_operation_response_internal(timing, result);
+    Ok(Response::new(response))
+}
+
+pub async fn set_attributes(
+    toc: Arc<TableOfContent>,
+    set_attributes_points: SetAttributesPoints,
+    clock_tag: Option<ClockTag>,
+    idempotency_key: Option<String>,
+    shard_selection: Option<ShardId>,
+    access: Access,
+) -> Result<Response<PointsOperationResponseInternal>, Status> {
+    let SetAttributesPoints {
+        collection_name,
+        wait,
+        attributes,
+        points_selector,
+        ordering,
+        shard_key_selector,
+    } = set_attributes_points;
+
+    let (points, filter) = extract_points_selector(points_selector)?;
+    let operation = SetAttributes {
+        attributes: attributes_from_proto(attributes),
+        points,
+        filter,
+        shard_key: shard_key_selector.map(ShardKeySelector::from),
+    };
+
+    let timing = Instant::now();
+    let result = do_set_attributes(
+        toc,
+        collection_name,
+        operation,
+        clock_tag,
+        idempotency_key,
         shard_selection,
         wait.unwrap_or(false),
         write_ordering_from_proto(ordering)?,
@@ -507,6 +841,7 @@ pub async fn update_batch(
     toc: Arc<TableOfContent>,
     update_batch_points: UpdateBatchPoints,
     clock_tag: Option<ClockTag>,
+    idempotency_key: Option<String>,
     shard_selection: Option<ShardId>,
     access: Access,
 ) -> Result<Response<UpdateBatchResponse>, Status> {
@@ -519,230 +854,320 @@ pub async fn update_batch(
 
     let timing = Instant::now();
     let mut results = Vec::with_capacity(operations.len());
-    for op in operations {
-        let operation = op
-            .operation
-            .ok_or(Status::invalid_argument("Operation is missing"))?;
-        let collection_name = collection_name.clone();
-        let ordering = ordering.clone();
-        let result = match operation {
-            points_update_operation::Operation::Upsert(PointStructList {
-                points,
-                shard_key_selector,
-            }) => {
-                upsert(
-                    toc.clone(),
-                    UpsertPoints {
-                        collection_name,
-                        points,
-                        wait,
-                        ordering,
-                        shard_key_selector,
-                    },
-                    clock_tag,
-                    shard_selection,
-                    access.clone(),
-                )
-                .await
-            }
-            points_update_operation::Operation::DeleteDeprecated(points) => {
-                delete(
-                    toc.clone(),
-                    DeletePoints {
-                        collection_name,
-                        wait,
-                        points: Some(points),
-                        ordering,
-                        shard_key_selector: None,
-                    },
-                    clock_tag,
-                    shard_selection,
-                    access.clone(),
-                )
-                .await
-            }
-            points_update_operation::Operation::SetPayload(
-                points_update_operation::SetPayload {
+    for (index, op) in operations.into_iter().enumerate() {
+        let result = update_batch_operation(
+            toc.clone(),
+            collection_name.clone(),
+            wait,
+            ordering.clone(),
+            op,
+            clock_tag,
+            sub_operation_key(&idempotency_key, index),
+            shard_selection,
+            access.clone(),
+        )
+        .await?;
+        results.push(result);
+    }
+    Ok(Response::new(UpdateBatchResponse {
+        result: results
+            .into_iter()
+            .map(|response| response.into_inner().result.unwrap().into())
+            .collect(),
+        time: timing.elapsed().as_secs_f64(),
+    }))
+}
+
+/// Perform multiple update operations, reporting status of each operation individually
+///
+/// A failed operation does not stop the processing of the following ones.
+/// Access violations fail the whole request.
+pub async fn bulk_update(
+    toc: Arc<TableOfContent>,
+    update_batch_points: UpdateBatchPoints,
+    clock_tag: Option<ClockTag>,
+    idempotency_key: Option<String>,
+    shard_selection: Option<ShardId>,
+    access: Access,
+) -> Result<Response<BulkUpdateResponse>, Status> {
+    let UpdateBatchPoints {
+        collection_name,
+        wait,
+        operations,
+        ordering,
+    } = update_batch_points;
+
+    let timing = Instant::now();
+    let mut results = Vec::with_capacity(operations.len());
+    for (index, op) in operations.into_iter().enumerate() {
+        let result = update_batch_operation(
+            toc.clone(),
+            collection_name.clone(),
+            wait,
+            ordering.clone(),
+            op,
+            clock_tag,
+            sub_operation_key(&idempotency_key, index),
+            shard_selection,
+            access.clone(),
+        )
+        .await;
+        let item = match result {
+            Ok(response) => BulkUpdateItemResult {
+                result: response.into_inner().result.map(Into::into),
+                error: None,
+            },
+            Err(status) if status.code() == tonic::Code::PermissionDenied => return Err(status),
+            Err(status) => BulkUpdateItemResult {
+                result: None,
+                error: Some(status.message().to_string()),
+            },
+        };
+        results.push(item);
+    }
+    Ok(Response::new(BulkUpdateResponse {
+        result: results,
+        time: timing.elapsed().as_secs_f64(),
+    }))
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn update_batch_operation(
+    toc: Arc<TableOfContent>,
+    collection_name: String,
+    wait: Option<bool>,
+    ordering: Option<WriteOrdering>,
+    op: PointsUpdateOperation,
+    clock_tag: Option<ClockTag>,
+    idempotency_key: Option<String>,
+    shard_selection: Option<ShardId>,
+    access: Access,
+) -> Result<Response<PointsOperationResponseInternal>, Status> {
+    let operation = op
+        .operation
+        .ok_or(Status::invalid_argument("Operation is missing"))?;
+    match operation {
+        points_update_operation::Operation::Upsert(PointStructList {
+            points,
+            shard_key_selector,
+        }) => {
+            upsert(
+                toc,
+                UpsertPoints {
+                    collection_name,
+                    points,
+                    wait,
+                    ordering,
+                    shard_key_selector,
+                    precondition: None,
+                },
+                clock_tag,
+                idempotency_key,
+                shard_selection,
+                access,
+            )
+            .await
+        }
+        points_update_operation::Operation::DeleteDeprecated(points) => {
+            delete(
+                toc,
+                DeletePoints {
+                    collection_name,
+                    wait,
+                    points: Some(points),
+                    ordering,
+                    shard_key_selector: None,
+                    precondition: None,
+                },
+                clock_tag,
+                idempotency_key,
+                shard_selection,
+                access,
+            )
+            .await
+        }
+        points_update_operation::Operation::SetPayload(points_update_operation::SetPayload {
+            payload,
+            points_selector,
+            shard_key_selector,
+            key,
+        }) => {
+            set_payload(
+                toc,
+                SetPayloadPoints {
+                    collection_name,
+                    wait,
                     payload,
                     points_selector,
+                    ordering,
                     shard_key_selector,
                     key,
+                    precondition: None,
                 },
-            ) => {
-                set_payload(
-                    toc.clone(),
-                    SetPayloadPoints {
-                        collection_name,
-                        wait,
-                        payload,
-                        points_selector,
-                        ordering,
-                        shard_key_selector,
-                        key,
-                    },
-                    clock_tag,
-                    shard_selection,
-                    access.clone(),
-                )
-                .await
-            }
-            points_update_operation::Operation::OverwritePayload(
-                points_update_operation::OverwritePayload {
+                clock_tag,
+                idempotency_key,
+                shard_selection,
+                access,
+            )
+            .await
+        }
+        points_update_operation::Operation::OverwritePayload(
+            points_update_operation::OverwritePayload {
+                payload,
+                points_selector,
+                shard_key_selector,
+                ..
+            },
+        ) => {
+            overwrite_payload(
+                toc,
+                SetPayloadPoints {
+                    collection_name,
+                    wait,
                     payload,
                     points_selector,
+                    ordering,
                     shard_key_selector,
-                    ..
+                    // overwrite operation don't support it
+                    key: None,
+                    precondition: None,
                 },
-            ) => {
-                overwrite_payload(
-                    toc.clone(),
-                    SetPayloadPoints {
-                        collection_name,
-                        wait,
-                        payload,
-                        points_selector,
-                        ordering,
-                        shard_key_selector,
-                        // overwrite operation don't support it
-                        key: None,
-                    },
-                    clock_tag,
-                    shard_selection,
-                    access.clone(),
-                )
-                .await
-            }
-            points_update_operation::Operation::DeletePayload(
-                points_update_operation::DeletePayload {
+                clock_tag,
+                idempotency_key,
+                shard_selection,
+                access,
+            )
+            .await
+        }
+        points_update_operation::Operation::DeletePayload(
+            points_update_operation::DeletePayload {
+                keys,
+                points_selector,
+                shard_key_selector,
+            },
+        ) => {
+            delete_payload(
+                toc,
+                DeletePayloadPoints {
+                    collection_name,
+                    wait,
                     keys,
                     points_selector,
+                    ordering,
                     shard_key_selector,
                 },
-            ) => {
-                delete_payload(
-                    toc.clone(),
-                    DeletePayloadPoints {
-                        collection_name,
-                        wait,
-                        keys,
-                        points_selector,
-                        ordering,
-                        shard_key_selector,
-                    },
-                    clock_tag,
-                    shard_selection,
-                    access.clone(),
-                )
-                .await
-            }
-            points_update_operation::Operation::ClearPayload(ClearPayload {
+                clock_tag,
+                idempotency_key,
+                shard_selection,
+                access,
+            )
+            .await
+        }
+        points_update_operation::Operation::ClearPayload(ClearPayload {
+            points,
+            shard_key_selector,
+        }) => {
+            clear_payload(
+                toc,
+                ClearPayloadPoints {
+                    collection_name,
+                    wait,
+                    points,
+                    ordering,
+                    shard_key_selector,
+                },
+                clock_tag,
+                idempotency_key,
+                shard_selection,
+                access,
+            )
+            .await
+        }
+        points_update_operation::Operation::UpdateVectors(
+            points_update_operation::UpdateVectors {
                 points,
                 shard_key_selector,
-            }) => {
-                clear_payload(
-                    toc.clone(),
-                    ClearPayloadPoints {
-                        collection_name,
-                        wait,
-                        points,
-                        ordering,
-                        shard_key_selector,
-                    },
-                    clock_tag,
-                    shard_selection,
-                    access.clone(),
-                )
-                .await
-            }
-            points_update_operation::Operation::UpdateVectors(
-                points_update_operation::UpdateVectors {
+            },
+        ) => {
+            update_vectors(
+                toc,
+                UpdatePointVectors {
+                    collection_name,
+                    wait,
                     points,
+                    ordering,
                     shard_key_selector,
                 },
-            ) => {
-                update_vectors(
-                    toc.clone(),
-                    UpdatePointVectors {
-                        collection_name,
-                        wait,
-                        points,
-                        ordering,
-                        shard_key_selector,
-                    },
-                    clock_tag,
-                    shard_selection,
-                    access.clone(),
-                )
-                .await
-            }
-            points_update_operation::Operation::DeleteVectors(
-                points_update_operation::DeleteVectors {
+                clock_tag,
+                idempotency_key,
+                shard_selection,
+                access,
+            )
+            .await
+        }
+        points_update_operation::Operation::DeleteVectors(
+            points_update_operation::DeleteVectors {
+                points_selector,
+                vectors,
+                shard_key_selector,
+            },
+        ) => {
+            delete_vectors(
+                toc,
+                DeletePointVectors {
+                    collection_name,
+                    wait,
                     points_selector,
                     vectors,
+                    ordering,
                     shard_key_selector,
                 },
-            ) => {
-                delete_vectors(
-                    toc.clone(),
-                    DeletePointVectors {
-                        collection_name,
-                        wait,
-                        points_selector,
-                        vectors,
-                        ordering,
-                        shard_key_selector,
-                    },
-                    clock_tag,
-                    shard_selection,
-                    access.clone(),
-                )
-                .await
-            }
-            Operation::ClearPayloadDeprecated(selector) => {
-                clear_payload(
-                    toc.clone(),
-                    ClearPayloadPoints {
-                        collection_name,
-                        wait,
-                        points: Some(selector),
-                        ordering,
-                        shard_key_selector: None,
-                    },
-                    clock_tag,
-                    shard_selection,
-                    access.clone(),
-                )
-                .await
-            }
-            Operation::DeletePoints(points_update_operation::DeletePoints {
-                points,
-                shard_key_selector,
-            }) => {
-                delete(
-                    toc.clone(),
-                    DeletePoints {
-                        collection_name,
-                        wait,
-                        points,
-                        ordering,
-                        shard_key_selector,
-                    },
-                    clock_tag,
-                    shard_selection,
-                    access.clone(),
-                )
-                .await
-            }
-        }?;
-        results.push(result);
+                clock_tag,
+                idempotency_key,
+                shard_selection,
+                access,
+            )
+            .await
+        }
+        Operation::ClearPayloadDeprecated(selector) => {
+            clear_payload(
+                toc,
+                ClearPayloadPoints {
+                    collection_name,
+                    wait,
+                    points: Some(selector),
+                    ordering,
+                    shard_key_selector: None,
+                },
+                clock_tag,
+                idempotency_key,
+                shard_selection,
+                access,
+            )
+            .await
+        }
+        Operation::DeletePoints(points_update_operation::DeletePoints {
+            points,
+            shard_key_selector,
+        }) => {
+            delete(
+                toc,
+                DeletePoints {
+                    collection_name,
+                    wait,
+                    points,
+                    ordering,
+                    shard_key_selector,
+                    precondition: None,
+                },
+                clock_tag,
+                idempotency_key,
+                shard_selection,
+                access,
+            )
+            .await
+        }
     }
-    Ok(Response::new(UpdateBatchResponse {
-        result: results
-            .into_iter()
-            .map(|response| response.into_inner().result.unwrap().into())
-            .collect(),
-        time: timing.elapsed().as_secs_f64(),
-    }))
 }
 
 fn convert_field_type(
@@ -772,6 +1197,15 @@ fn convert_field_type(
         ) => Some(PayloadFieldSchema::FieldParams(
             PayloadSchemaParams::Integer(integer_params.try_into()?),
         )),
+        // Parameterized geo type
+        (
+            Some(FieldType::Geo),
+            Some(PayloadIndexParams {
+                index_params: Some(IndexParams::GeoIndexParams(geo_params)),
+            }),
+        ) => Some(PayloadFieldSchema::FieldParams(PayloadSchemaParams::Geo(
+            geo_params.try_into()?,
+        ))),
         // Regular field types
         (Some(v), None | Some(PayloadIndexParams { index_params: None })) => match v {
             FieldType::Keyword => Some(PayloadSchemaType::Keyword.into()),
@@ -781,6 +1215,8 @@ fn convert_field_type(
             FieldType::Text => Some(PayloadSchemaType::Text.into()),
             FieldType::Bool => Some(PayloadSchemaType::Bool.into()),
             FieldType::Datetime => Some(PayloadSchemaType::Datetime.into()),
+            FieldType::BigInt => Some(PayloadSchemaType::BigInt.into()),
+            FieldType::MinHash => Some(PayloadSchemaType::MinHash.into()),
         },
         // Parameterized index with mismatching types
         (
@@ -949,6 +1385,7 @@ pub async fn search(
     search_points: SearchPoints,
     shard_selection: Option<ShardId>,
     access: Access,
+    hw_counter: &HardwareCounterCell,
 ) -> Result<Response<SearchResponse>, Status> {
     let SearchPoints {
         collection_name,
@@ -975,7 +1412,7 @@ pub async fn search(
     let search_request = CoreSearchRequest {
         query: QueryEnum::Nearest(vector_struct),
         filter: filter.map(|f| f.try_into()).transpose()?,
-        params: params.map(|p| p.into()),
+        params: params.map(|p| p.try_into()).transpose()?,
         limit: limit as usize,
         offset: offset.unwrap_or_default() as usize,
         with_payload: with_payload.map(|wp| wp.try_into()).transpose()?,
@@ -998,16 +1435,21 @@ pub async fn search(
         shard_selector,
         access,
         timeout.map(Duration::from_secs),
+        hw_counter,
     )
     .await
     .map_err(error_to_status)?;
 
     let response = SearchResponse {
         result: scored_points
+            .value
             .into_iter()
             .map(|point| point.into())
             .collect(),
         time: timing.elapsed().as_secs_f64(),
+        usage: hw_counter.usage().map(From::from),
+        timed_out: timeout.map(|_| scored_points.timed_out),
+        shards: scored_points.shards.into_iter().map(From::from).collect(),
     };
 
     Ok(Response::new(response))
@@ -1020,6 +1462,7 @@ pub async fn core_search_batch(
     read_consistency: Option<ReadConsistencyGrpc>,
     access: Access,
     timeout: Option<Duration>,
+    hw_counter: &HardwareCounterCell,
 ) -> Result<Response<SearchBatchResponse>, Status> {
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
 
@@ -1032,18 +1475,23 @@ pub async fn core_search_batch(
         read_consistency,
         access,
         timeout,
+        hw_counter,
     )
     .await
     .map_err(error_to_status)?;
 
     let response = SearchBatchResponse {
         result: scored_points
+            .value
             .into_iter()
             .map(|points| BatchResult {
                 result: points.into_iter().map(|p| p.into()).collect(),
             })
             .collect(),
         time: timing.elapsed().as_secs_f64(),
+        usage: hw_counter.usage().map(From::from),
+        timed_out: timeout.map(|_| scored_points.timed_out),
+        shards: scored_points.shards.into_iter().map(From::from).collect(),
     };
 
     Ok(Response::new(response))
@@ -1057,6 +1505,7 @@ pub async fn core_search_list(
     shard_selection: Option<ShardId>,
     access: Access,
     timeout: Option<Duration>,
+    hw_counter: &HardwareCounterCell,
 ) -> Result<Response<SearchBatchResponse>, Status> {
     let searches: Result<Vec<_>, Status> =
         search_points.into_iter().map(TryInto::try_into).collect();
@@ -1080,25 +1529,30 @@ pub async fn core_search_list(
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
 
     let scored_points = toc
-        .core_search_batch(
+        .core_search_batch_with_shards(
             &collection_name,
             request,
             read_consistency,
             shard_selection,
             access,
             timeout,
+            hw_counter,
         )
         .await
         .map_err(error_to_status)?;
 
     let response = SearchBatchResponse {
         result: scored_points
+            .value
             .into_iter()
             .map(|points| BatchResult {
                 result: points.into_iter().map(|p| p.into()).collect(),
             })
             .collect(),
         time: timing.elapsed().as_secs_f64(),
+        usage: hw_counter.usage().map(From::from),
+        timed_out: timeout.map(|_| scored_points.timed_out),
+        shards: scored_points.shards.into_iter().map(From::from).collect(),
     };
 
     Ok(Response::new(response))
@@ -1199,7 +1653,7 @@ pub async fn recommend(
         negative,
         strategy: strategy.map(|s| s.try_into()).transpose()?,
         filter: filter.map(|f| f.try_into()).transpose()?,
-        params: params.map(|p| p.into()),
+        params: params.map(|p| p.try_into()).transpose()?,
         limit: limit as usize,
         offset: offset.map(|x| x as usize),
         with_payload: with_payload.map(|wp| wp.try_into()).transpose()?,
@@ -1404,12 +1858,19 @@ pub async fn discover_batch(
 
     Ok(Response::new(response))
 }
-pub async fn scroll(
-    toc: &TableOfContent,
+/// Collection name, request, read consistency and shard selector of the scroll
+fn convert_scroll_points(
     scroll_points: ScrollPoints,
     shard_selection: Option<ShardId>,
-    access: Access,
-) -> Result<Response<ScrollResponse>, Status> {
+) -> Result<
+    (
+        String,
+        ScrollRequestInternal,
+        Option<ReadConsistency>,
+        ShardSelectorInternal,
+    ),
+    Status,
+> {
     let ScrollPoints {
         collection_name,
         filter,
@@ -1420,6 +1881,7 @@ pub async fn scroll(
         read_consistency,
         shard_key_selector,
         order_by,
+        cursor,
     } = scroll_points;
 
     let scroll_request = ScrollRequestInternal {
@@ -1434,12 +1896,31 @@ pub async fn scroll(
             .map(OrderBy::try_from)
             .transpose()?
             .map(OrderByInterface::Struct),
+        cursor,
+        include_deleted: None,
     };
 
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
 
     let shard_selector = convert_shard_selector_for_read(shard_selection, shard_key_selector);
 
+    Ok((
+        collection_name,
+        scroll_request,
+        read_consistency,
+        shard_selector,
+    ))
+}
+
+pub async fn scroll(
+    toc: &TableOfContent,
+    scroll_points: ScrollPoints,
+    shard_selection: Option<ShardId>,
+    access: Access,
+) -> Result<Response<ScrollResponse>, Status> {
+    let (collection_name, scroll_request, read_consistency, shard_selector) =
+        convert_scroll_points(scroll_points, shard_selection)?;
+
     let timing = Instant::now();
     let scrolled_points = do_scroll_points(
         toc,
@@ -1454,6 +1935,8 @@ pub async fn scroll(
 
     let response = ScrollResponse {
         next_page_offset: scrolled_points.next_page_offset.map(|n| n.into()),
+        next_page_cursor: scrolled_points.next_page_cursor,
+
         result: scrolled_points
             .points
             .into_iter()
@@ -1466,6 +1949,40 @@ pub async fn scroll(
     Ok(Response::new(response))
 }
 
+pub type ScrollStreamResponseStream = BoxStream<'static, Result<RetrievedPoint, Status>>;
+
+/// Scroll all points, matching the request, streaming them page by page as they are read
+pub async fn scroll_stream(
+    toc: Arc<TableOfContent>,
+    scroll_points: ScrollPoints,
+    access: Access,
+) -> Result<Response<ScrollStreamResponseStream>, Status> {
+    let (collection_name, scroll_request, read_consistency, shard_selector) =
+        convert_scroll_points(scroll_points, None)?;
+
+    let stream = do_scroll_points_stream(
+        toc,
+        collection_name,
+        scroll_request,
+        read_consistency,
+        shard_selector,
+        access,
+    )
+    .await
+    .map_err(error_to_status)?
+    .map_ok(|points| {
+        futures::stream::iter(
+            points
+                .into_iter()
+                .map(|point| Ok(RetrievedPoint::from(Record::from(point)))),
+        )
+    })
+    .map_err(error_to_status)
+    .try_flatten();
+
+    Ok(Response::new(stream.boxed()))
+}
+
 pub async fn count(
     toc: &TableOfContent,
     count_points: CountPoints,
@@ -1509,6 +2026,243 @@ pub async fn count(
     Ok(Response::new(response))
 }
 
+async fn count_distinct_values(
+    toc: &TableOfContent,
+    count_distinct_points: CountDistinctPoints,
+    shard_selection: Option<ShardId>,
+    access: Access,
+) -> Result<Reconciled<DistinctValues>, Status> {
+    let CountDistinctPoints {
+        collection_name,
+        key,
+        filter,
+        read_consistency,
+        shard_key_selector,
+    } = count_distinct_points;
+
+    let count_distinct_request = CountDistinctRequestInternal {
+        key: json_path_from_proto(&key)?,
+        filter: filter.map(|f| f.try_into()).transpose()?,
+    };
+
+    let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
+
+    let shard_selector = convert_shard_selector_for_read(shard_selection, shard_key_selector);
+
+    do_count_distinct(
+        toc,
+        &collection_name,
+        count_distinct_request,
+        read_consistency,
+        shard_selector,
+        access,
+    )
+    .await
+    .map_err(error_to_status)
+}
+
+pub async fn count_distinct(
+    toc: &TableOfContent,
+    count_distinct_points: CountDistinctPoints,
+    shard_selection: Option<ShardId>,
+    access: Access,
+) -> Result<Response<CountDistinctResponse>, Status> {
+    let timing = Instant::now();
+    let distinct_values =
+        count_distinct_values(toc, count_distinct_points, shard_selection, access).await?;
+
+    let response = CountDistinctResponse {
+        result: Some(CountDistinctResult::from(distinct_values).into()),
+        time: timing.elapsed().as_secs_f64(),
+    };
+
+    Ok(Response::new(response))
+}
+
+/// Same as [`count_distinct`], but returns the values themselves, so they can be merged with
+/// the values of other shards
+pub async fn count_distinct_internal(
+    toc: &TableOfContent,
+    count_distinct_points: CountDistinctPoints,
+    shard_selection: Option<ShardId>,
+    access: Access,
+) -> Result<Response<DistinctValuesResponseInternal>, Status> {
+    let timing = Instant::now();
+    let distinct_values =
+        count_distinct_values(toc, count_distinct_points, shard_selection, access).await?;
+
+    let response = DistinctValuesResponseInternal {
+        result: Some(distinct_values.value.into()),
+        time: timing.elapsed().as_secs_f64(),
+    };
+
+    Ok(Response::new(response))
+}
+
+async fn numeric_stats(
+    toc: &TableOfContent,
+    field_stats_points: FieldStatsPoints,
+    shard_selection: Option<ShardId>,
+    access: Access,
+) -> Result<(NumericStats, Vec<f64>), Status> {
+    let FieldStatsPoints {
+        collection_name,
+        key,
+        filter,
+        percentiles,
+        read_consistency,
+        shard_key_selector,
+    } = field_stats_points;
+
+    let field_stats_request = FieldStatsRequestInternal {
+        key: json_path_from_proto(&key)?,
+        filter: filter.map(|f| f.try_into()).transpose()?,
+        percentiles: if percentiles.is_empty() {
+            default_percentiles()
+        } else {
+            percentiles
+        },
+    };
+    validate(&field_stats_request)?;
+
+    let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
+
+    let shard_selector = convert_shard_selector_for_read(shard_selection, shard_key_selector);
+
+    let percentiles = field_stats_request.percentiles.clone();
+    let stats = do_field_stats(
+        toc,
+        &collection_name,
+        field_stats_request,
+        read_consistency,
+        shard_selector,
+        access,
+    )
+    .await
+    .map_err(error_to_status)?;
+
+    Ok((stats, percentiles))
+}
+
+pub async fn field_stats(
+    toc: &TableOfContent,
+    field_stats_points: FieldStatsPoints,
+    shard_selection: Option<ShardId>,
+    access: Access,
+) -> Result<Response<FieldStatsResponse>, Status> {
+    let timing = Instant::now();
+    let (stats, percentiles) =
+        numeric_stats(toc, field_stats_points, shard_selection, access).await?;
+
+    let response = FieldStatsResponse {
+        result: Some(FieldStatsResult::new(&stats, &percentiles).into()),
+        time: timing.elapsed().as_secs_f64(),
+    };
+
+    Ok(Response::new(response))
+}
+
+/// Same as [`field_stats`], but returns the statistics with the distribution of values, so they
+/// can be merged with the statistics of other shards
+pub async fn field_stats_internal(
+    toc: &TableOfContent,
+    field_stats_points: FieldStatsPoints,
+    shard_selection: Option<ShardId>,
+    access: Access,
+) -> Result<Response<NumericStatsResponseInternal>, Status> {
+    let timing = Instant::now();
+    let (stats, _) = numeric_stats(toc, field_stats_points, shard_selection, access).await?;
+
+    let response = NumericStatsResponseInternal {
+        result: Some(stats.into()),
+        time: timing.elapsed().as_secs_f64(),
+    };
+
+    Ok(Response::new(response))
+}
+
+async fn value_distribution(
+    toc: &TableOfContent,
+    histogram_points: HistogramPoints,
+    shard_selection: Option<ShardId>,
+    access: Access,
+) -> Result<(ValueDistribution, HistogramBuckets), Status> {
+    let HistogramPoints {
+        collection_name,
+        key,
+        buckets,
+        read_consistency,
+        shard_key_selector,
+    } = histogram_points;
+
+    let buckets = buckets
+        .map(HistogramBuckets::from)
+        .ok_or_else(|| Status::invalid_argument("Histogram buckets are not specified"))?;
+    let histogram_request = HistogramRequestInternal {
+        key: json_path_from_proto(&key)?,
+        buckets,
+    };
+    validate(&histogram_request)?;
+
+    let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
+
+    let shard_selector = convert_shard_selector_for_read(shard_selection, shard_key_selector);
+
+    let distribution = do_histogram(
+        toc,
+        &collection_name,
+        histogram_request,
+        read_consistency,
+        shard_selector,
+        access,
+    )
+    .await
+    .map_err(error_to_status)?;
+
+    Ok((distribution, buckets))
+}
+
+pub async fn histogram(
+    toc: &TableOfContent,
+    histogram_points: HistogramPoints,
+    shard_selection: Option<ShardId>,
+    access: Access,
+) -> Result<Response<HistogramResponse>, Status> {
+    let timing = Instant::now();
+    let (distribution, buckets) =
+        value_distribution(toc, histogram_points, shard_selection, access).await?;
+
+    let histogram =
+        HistogramResult::new(&distribution, buckets).map_err(|err| error_to_status(err.into()))?;
+
+    let response = HistogramResponse {
+        result: histogram.buckets.into_iter().map(Into::into).collect(),
+        time: timing.elapsed().as_secs_f64(),
+    };
+
+    Ok(Response::new(response))
+}
+
+/// Same as [`histogram`], but returns the distribution of values, so it can be merged with
+/// the distributions of other shards
+pub async fn histogram_internal(
+    toc: &TableOfContent,
+    histogram_points: HistogramPoints,
+    shard_selection: Option<ShardId>,
+    access: Access,
+) -> Result<Response<ValueDistributionResponseInternal>, Status> {
+    let timing = Instant::now();
+    let (distribution, _) =
+        value_distribution(toc, histogram_points, shard_selection, access).await?;
+
+    let response = ValueDistributionResponseInternal {
+        result: Some(distribution.into()),
+        time: timing.elapsed().as_secs_f64(),
+    };
+
+    Ok(Response::new(response))
+}
+
 pub async fn get(
     toc: &TableOfContent,
     get_points: GetPoints,
@@ -1565,6 +2319,7 @@ pub async fn query(
     query_points: QueryPoints,
     shard_selection: Option<ShardId>,
     access: Access,
+    hw_counter: &HardwareCounterCell,
 ) -> Result<Response<QueryResponse>, Status> {
     let shard_key_selector = query_points.shard_key_selector.clone();
     let shard_selector = convert_shard_selector_for_read(shard_selection, shard_key_selector);
@@ -1575,6 +2330,7 @@ pub async fn query(
         .transpose()?;
     let timeout = query_points.timeout.map(Duration::from_secs);
     let collection_name = query_points.collection_name.clone();
+    let payload_size_limit = query_points.payload_size_limit;
     let request = CollectionQueryRequest::try_from(query_points)?;
     let timing = Instant::now();
     let scored_points = do_query_points(
@@ -1585,16 +2341,16 @@ pub async fn query(
         shard_selector,
         access,
         timeout,
+        hw_counter,
     )
     .await
     .map_err(error_to_status)?;
 
     let response = QueryResponse {
-        result: scored_points
-            .into_iter()
-            .map(|point| point.into())
-            .collect(),
+        result: scored_points_with_payload_limit(scored_points.value, payload_size_limit),
         time: timing.elapsed().as_secs_f64(),
+        usage: hw_counter.usage().map(From::from),
+        timed_out: timeout.map(|_| scored_points.timed_out),
     };
 
     Ok(Response::new(response))
@@ -1607,10 +2363,13 @@ pub async fn query_batch(
     read_consistency: Option<ReadConsistencyGrpc>,
     access: Access,
     timeout: Option<Duration>,
+    hw_counter: &HardwareCounterCell,
 ) -> Result<Response<QueryBatchResponse>, Status> {
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
     let mut requests = Vec::with_capacity(points.len());
+    let mut payload_size_limits = Vec::with_capacity(points.len());
     for query_points in points {
+        payload_size_limits.push(query_points.payload_size_limit);
         let shard_key_selector = query_points.shard_key_selector.clone();
         let shard_selector = convert_shard_selector_for_read(None, shard_key_selector);
         let request = CollectionQueryRequest::try_from(query_points)?;
@@ -1624,18 +2383,23 @@ pub async fn query_batch(
         read_consistency,
         access,
         timeout,
+        hw_counter,
     )
     .await
     .map_err(error_to_status)?;
 
     let response = QueryBatchResponse {
         result: scored_points
+            .value
             .into_iter()
-            .map(|points| BatchResult {
-                result: points.into_iter().map(|p| p.into()).collect(),
+            .zip(payload_size_limits)
+            .map(|(points, payload_size_limit)| BatchResult {
+                result: scored_points_with_payload_limit(points, payload_size_limit),
             })
             .collect(),
         time: timing.elapsed().as_secs_f64(),
+        usage: hw_counter.usage().map(From::from),
+        timed_out: timeout.map(|_| scored_points.timed_out),
     };
 
     Ok(Response::new(response))
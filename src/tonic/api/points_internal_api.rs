@@ -3,29 +3,39 @@ use std::time::{Duration, Instant};
 
 use api::grpc::qdrant::points_internal_server::PointsInternal;
 use api::grpc::qdrant::{
-    ClearPayloadPointsInternal, CoreSearchBatchPointsInternal, CountPointsInternal, CountResponse,
-    CreateFieldIndexCollectionInternal, DeleteFieldIndexCollectionInternal,
-    DeletePayloadPointsInternal, DeletePointsInternal, DeleteVectorsInternal, GetPointsInternal,
-    GetResponse, IntermediateResult, PointsOperationResponseInternal, QueryBatchPointsInternal,
-    QueryBatchResponseInternal, QueryResultInternal, QueryShardPoints, RecommendPointsInternal,
-    RecommendResponse, ScrollPointsInternal, ScrollResponse, SearchBatchResponse,
-    SetPayloadPointsInternal, SyncPointsInternal, UpdateVectorsInternal, UpsertPointsInternal,
+    ClearPayloadPointsInternal, CoreSearchBatchPointsInternal, CountDistinctPointsInternal,
+    CountPointsInternal, CountResponse, CreateFieldIndexCollectionInternal,
+    DeleteFieldIndexCollectionInternal, DeletePayloadPointsInternal, DeletePointsInternal,
+    DeleteVectorsInternal, DistinctValuesResponseInternal, FieldStatsPointsInternal,
+    GetPointsInternal, GetResponse, HistogramPointsInternal, IntermediateResult,
+    NumericStatsResponseInternal, PointDigestsInternal, PointDigestsResponseInternal,
+    PointHistoryInternal, PointHistoryResponseInternal, PointsOperationResponseInternal,
+    QueryBatchPointsInternal, QueryBatchResponseInternal, QueryResultInternal, QueryShardPoints,
+    RecommendPointsInternal, RecommendResponse, ScanTrashInternal, ScrollPointsInternal,
+    ScrollResponse, SearchBatchResponse, SetAttributesPointsInternal, SetPayloadPointsInternal,
+    SyncPointsInternal, TrashResponseInternal, TrashedPointsInternal, UpdateVectorsInternal,
+    UpsertPointsInternal, ValueDistributionResponseInternal,
 };
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::universal_query::shard_query::ShardQueryRequest;
+use collection::shards::point_digests::PointDigestsRequest;
 use collection::shards::shard::ShardId;
+use common::hardware_counter::HardwareCounterCell;
 use itertools::Itertools;
+use segment::types::{Filter, PointIdType};
 use storage::content_manager::conversions::error_to_status;
 use storage::content_manager::toc::TableOfContent;
-use storage::rbac::Access;
+use storage::rbac::{Access, AccessRequirements};
 use tonic::{Request, Response, Status};
 
 use super::points_common::core_search_list;
 use super::validate_and_log;
+use crate::settings::ServiceConfig;
 use crate::tonic::api::points_common::{
-    clear_payload, count, create_field_index_internal, delete, delete_field_index_internal,
-    delete_payload, delete_vectors, get, overwrite_payload, recommend, scroll, set_payload, sync,
-    update_vectors, upsert,
+    clear_payload, count, count_distinct_internal, create_field_index_internal, delete,
+    delete_field_index_internal, delete_payload, delete_vectors, extract_idempotency_key,
+    field_stats_internal, get, histogram_internal, overwrite_payload, patch_payload, recommend,
+    scroll, set_attributes, set_payload, sync, update_vectors, upsert,
 };
 
 const FULL_ACCESS: Access = Access::full("Internal API");
@@ -33,11 +43,15 @@ const FULL_ACCESS: Access = Access::full("Internal API");
 /// This API is intended for P2P communication within a distributed deployment.
 pub struct PointsInternalService {
     toc: Arc<TableOfContent>,
+    service_config: ServiceConfig,
 }
 
 impl PointsInternalService {
-    pub fn new(toc: Arc<TableOfContent>) -> Self {
-        Self { toc }
+    pub fn new(toc: Arc<TableOfContent>, service_config: ServiceConfig) -> Self {
+        Self {
+            toc,
+            service_config,
+        }
     }
 }
 
@@ -47,6 +61,7 @@ pub async fn query_batch_internal(
     query_points: Vec<QueryShardPoints>,
     shard_selection: Option<ShardId>,
     timeout: Option<Duration>,
+    hw_counter: &HardwareCounterCell,
 ) -> Result<Response<QueryBatchResponseInternal>, Status> {
     let batch_requests: Vec<_> = query_points
         .into_iter()
@@ -66,12 +81,19 @@ pub async fn query_batch_internal(
     };
 
     let batch_response = toc
-        .query_batch_internal(&collection_name, batch_requests, shard_selection, timeout)
+        .query_batch_internal(
+            &collection_name,
+            batch_requests,
+            shard_selection,
+            timeout,
+            hw_counter,
+        )
         .await
         .map_err(error_to_status)?;
 
     let response = QueryBatchResponseInternal {
         results: batch_response
+            .value
             .into_iter()
             .map(|response| QueryResultInternal {
                 intermediate_results: response
@@ -83,6 +105,8 @@ pub async fn query_batch_internal(
             })
             .collect(),
         time: timing.elapsed().as_secs_f64(),
+        usage: hw_counter.usage().map(From::from),
+        timed_out: timeout.map(|_| batch_response.timed_out),
     };
 
     Ok(Response::new(response))
@@ -95,6 +119,7 @@ impl PointsInternal for PointsInternalService {
         request: Request<UpsertPointsInternal>,
     ) -> Result<Response<PointsOperationResponseInternal>, Status> {
         validate_and_log(request.get_ref());
+        let idempotency_key = extract_idempotency_key(&request)?;
 
         let UpsertPointsInternal {
             upsert_points,
@@ -109,6 +134,7 @@ impl PointsInternal for PointsInternalService {
             self.toc.clone(),
             upsert_points,
             clock_tag.map(Into::into),
+            idempotency_key,
             shard_id,
             FULL_ACCESS.clone(),
         )
@@ -120,6 +146,7 @@ impl PointsInternal for PointsInternalService {
         request: Request<DeletePointsInternal>,
     ) -> Result<Response<PointsOperationResponseInternal>, Status> {
         validate_and_log(request.get_ref());
+        let idempotency_key = extract_idempotency_key(&request)?;
 
         let DeletePointsInternal {
             delete_points,
@@ -134,6 +161,7 @@ impl PointsInternal for PointsInternalService {
             self.toc.clone(),
             delete_points,
             clock_tag.map(Into::into),
+            idempotency_key,
             shard_id,
             FULL_ACCESS.clone(),
         )
@@ -145,6 +173,7 @@ impl PointsInternal for PointsInternalService {
         request: Request<UpdateVectorsInternal>,
     ) -> Result<Response<PointsOperationResponseInternal>, Status> {
         validate_and_log(request.get_ref());
+        let idempotency_key = extract_idempotency_key(&request)?;
 
         let request = request.into_inner();
 
@@ -159,6 +188,7 @@ impl PointsInternal for PointsInternalService {
             self.toc.clone(),
             update_point_vectors,
             clock_tag.map(Into::into),
+            idempotency_key,
             shard_id,
             FULL_ACCESS.clone(),
         )
@@ -170,6 +200,7 @@ impl PointsInternal for PointsInternalService {
         request: Request<DeleteVectorsInternal>,
     ) -> Result<Response<PointsOperationResponseInternal>, Status> {
         validate_and_log(request.get_ref());
+        let idempotency_key = extract_idempotency_key(&request)?;
 
         let request = request.into_inner();
 
@@ -184,6 +215,7 @@ impl PointsInternal for PointsInternalService {
             self.toc.clone(),
             delete_point_vectors,
             clock_tag.map(Into::into),
+            idempotency_key,
             shard_id,
             FULL_ACCESS.clone(),
         )
@@ -195,6 +227,7 @@ impl PointsInternal for PointsInternalService {
         request: Request<SetPayloadPointsInternal>,
     ) -> Result<Response<PointsOperationResponseInternal>, Status> {
         validate_and_log(request.get_ref());
+        let idempotency_key = extract_idempotency_key(&request)?;
 
         let SetPayloadPointsInternal {
             set_payload_points,
@@ -209,6 +242,7 @@ impl PointsInternal for PointsInternalService {
             self.toc.clone(),
             set_payload_points,
             clock_tag.map(Into::into),
+            idempotency_key,
             shard_id,
             FULL_ACCESS.clone(),
         )
@@ -220,6 +254,7 @@ impl PointsInternal for PointsInternalService {
         request: Request<SetPayloadPointsInternal>,
     ) -> Result<Response<PointsOperationResponseInternal>, Status> {
         validate_and_log(request.get_ref());
+        let idempotency_key = extract_idempotency_key(&request)?;
 
         let SetPayloadPointsInternal {
             set_payload_points,
@@ -234,6 +269,34 @@ impl PointsInternal for PointsInternalService {
             self.toc.clone(),
             set_payload_points,
             clock_tag.map(Into::into),
+            idempotency_key,
+            shard_id,
+            FULL_ACCESS.clone(),
+        )
+        .await
+    }
+
+    async fn patch_payload(
+        &self,
+        request: Request<SetPayloadPointsInternal>,
+    ) -> Result<Response<PointsOperationResponseInternal>, Status> {
+        validate_and_log(request.get_ref());
+        let idempotency_key = extract_idempotency_key(&request)?;
+
+        let SetPayloadPointsInternal {
+            set_payload_points,
+            shard_id,
+            clock_tag,
+        } = request.into_inner();
+
+        let set_payload_points = set_payload_points
+            .ok_or_else(|| Status::invalid_argument("SetPayloadPoints is missing"))?;
+
+        patch_payload(
+            self.toc.clone(),
+            set_payload_points,
+            clock_tag.map(Into::into),
+            idempotency_key,
             shard_id,
             FULL_ACCESS.clone(),
         )
@@ -245,6 +308,7 @@ impl PointsInternal for PointsInternalService {
         request: Request<DeletePayloadPointsInternal>,
     ) -> Result<Response<PointsOperationResponseInternal>, Status> {
         validate_and_log(request.get_ref());
+        let idempotency_key = extract_idempotency_key(&request)?;
 
         let DeletePayloadPointsInternal {
             delete_payload_points,
@@ -259,6 +323,7 @@ impl PointsInternal for PointsInternalService {
             self.toc.clone(),
             delete_payload_points,
             clock_tag.map(Into::into),
+            idempotency_key,
             shard_id,
             FULL_ACCESS.clone(),
         )
@@ -270,6 +335,7 @@ impl PointsInternal for PointsInternalService {
         request: Request<ClearPayloadPointsInternal>,
     ) -> Result<Response<PointsOperationResponseInternal>, Status> {
         validate_and_log(request.get_ref());
+        let idempotency_key = extract_idempotency_key(&request)?;
 
         let ClearPayloadPointsInternal {
             clear_payload_points,
@@ -284,6 +350,34 @@ impl PointsInternal for PointsInternalService {
             self.toc.clone(),
             clear_payload_points,
             clock_tag.map(Into::into),
+            idempotency_key,
+            shard_id,
+            FULL_ACCESS.clone(),
+        )
+        .await
+    }
+
+    async fn set_attributes(
+        &self,
+        request: Request<SetAttributesPointsInternal>,
+    ) -> Result<Response<PointsOperationResponseInternal>, Status> {
+        validate_and_log(request.get_ref());
+        let idempotency_key = extract_idempotency_key(&request)?;
+
+        let SetAttributesPointsInternal {
+            set_attributes_points,
+            shard_id,
+            clock_tag,
+        } = request.into_inner();
+
+        let set_attributes_points = set_attributes_points
+            .ok_or_else(|| Status::invalid_argument("SetAttributesPoints is missing"))?;
+
+        set_attributes(
+            self.toc.clone(),
+            set_attributes_points,
+            clock_tag.map(Into::into),
+            idempotency_key,
             shard_id,
             FULL_ACCESS.clone(),
         )
@@ -367,6 +461,7 @@ impl PointsInternal for PointsInternalService {
             shard_id,
             FULL_ACCESS.clone(),
             timeout,
+            &self.service_config.hardware_counter(),
         )
         .await
     }
@@ -457,6 +552,235 @@ impl PointsInternal for PointsInternalService {
         .await
     }
 
+    async fn count_distinct(
+        &self,
+        request: Request<CountDistinctPointsInternal>,
+    ) -> Result<Response<DistinctValuesResponseInternal>, Status> {
+        validate_and_log(request.get_ref());
+
+        let CountDistinctPointsInternal {
+            count_distinct_points,
+            shard_id,
+        } = request.into_inner();
+
+        let count_distinct_points = count_distinct_points
+            .ok_or_else(|| Status::invalid_argument("CountDistinctPoints is missing"))?;
+        count_distinct_internal(
+            self.toc.as_ref(),
+            count_distinct_points,
+            shard_id,
+            FULL_ACCESS.clone(),
+        )
+        .await
+    }
+
+    async fn histogram(
+        &self,
+        request: Request<HistogramPointsInternal>,
+    ) -> Result<Response<ValueDistributionResponseInternal>, Status> {
+        validate_and_log(request.get_ref());
+
+        let HistogramPointsInternal {
+            histogram_points,
+            shard_id,
+        } = request.into_inner();
+
+        let histogram_points = histogram_points
+            .ok_or_else(|| Status::invalid_argument("HistogramPoints is missing"))?;
+        histogram_internal(
+            self.toc.as_ref(),
+            histogram_points,
+            shard_id,
+            FULL_ACCESS.clone(),
+        )
+        .await
+    }
+
+    async fn field_stats(
+        &self,
+        request: Request<FieldStatsPointsInternal>,
+    ) -> Result<Response<NumericStatsResponseInternal>, Status> {
+        validate_and_log(request.get_ref());
+
+        let FieldStatsPointsInternal {
+            field_stats_points,
+            shard_id,
+        } = request.into_inner();
+
+        let field_stats_points = field_stats_points
+            .ok_or_else(|| Status::invalid_argument("FieldStatsPoints is missing"))?;
+        field_stats_internal(
+            self.toc.as_ref(),
+            field_stats_points,
+            shard_id,
+            FULL_ACCESS.clone(),
+        )
+        .await
+    }
+
+    async fn point_digests(
+        &self,
+        request: Request<PointDigestsInternal>,
+    ) -> Result<Response<PointDigestsResponseInternal>, Status> {
+        validate_and_log(request.get_ref());
+
+        let timing = Instant::now();
+        let PointDigestsInternal {
+            collection_name,
+            shard_id,
+            buckets,
+            list_buckets,
+        } = request.into_inner();
+
+        let shard_id = shard_id.ok_or_else(|| Status::invalid_argument("Shard id is missing"))?;
+        let collection_pass = FULL_ACCESS
+            .check_collection_access(&collection_name, AccessRequirements::new())
+            .map_err(error_to_status)?;
+        let collection = self
+            .toc
+            .get_collection(&collection_pass)
+            .await
+            .map_err(error_to_status)?;
+
+        let request = PointDigestsRequest {
+            buckets: buckets as usize,
+            list_buckets: list_buckets
+                .into_iter()
+                .map(|bucket| bucket as usize)
+                .collect(),
+        };
+        let digests = collection
+            .local_point_digests(shard_id, request)
+            .await
+            .map_err(|err| error_to_status(err.into()))?;
+
+        let mut response = PointDigestsResponseInternal::from(digests);
+        response.time = timing.elapsed().as_secs_f64();
+        Ok(Response::new(response))
+    }
+
+    async fn point_history(
+        &self,
+        request: Request<PointHistoryInternal>,
+    ) -> Result<Response<PointHistoryResponseInternal>, Status> {
+        validate_and_log(request.get_ref());
+
+        let timing = Instant::now();
+        let PointHistoryInternal {
+            collection_name,
+            shard_id,
+            id,
+        } = request.into_inner();
+
+        let shard_id = shard_id.ok_or_else(|| Status::invalid_argument("Shard id is missing"))?;
+        let point_id = PointIdType::try_from(
+            id.ok_or_else(|| Status::invalid_argument("Point id is missing"))?,
+        )?;
+        let collection_pass = FULL_ACCESS
+            .check_collection_access(&collection_name, AccessRequirements::new())
+            .map_err(error_to_status)?;
+        let collection = self
+            .toc
+            .get_collection(&collection_pass)
+            .await
+            .map_err(error_to_status)?;
+
+        let versions = collection
+            .local_point_history(shard_id, point_id)
+            .await
+            .map_err(|err| error_to_status(err.into()))?
+            .map(|versions| serde_cbor::to_vec(&versions))
+            .transpose()
+            .map_err(|err| Status::internal(format!("Failed to encode point history: {err}")))?;
+
+        Ok(Response::new(PointHistoryResponseInternal {
+            versions,
+            time: timing.elapsed().as_secs_f64(),
+        }))
+    }
+
+    async fn trashed_points(
+        &self,
+        request: Request<TrashedPointsInternal>,
+    ) -> Result<Response<TrashResponseInternal>, Status> {
+        validate_and_log(request.get_ref());
+
+        let timing = Instant::now();
+        let TrashedPointsInternal {
+            collection_name,
+            shard_id,
+            ids,
+        } = request.into_inner();
+
+        let shard_id = shard_id.ok_or_else(|| Status::invalid_argument("Shard id is missing"))?;
+        let point_ids: Vec<PointIdType> = ids
+            .into_iter()
+            .map(PointIdType::try_from)
+            .collect::<Result<_, _>>()?;
+        let collection_pass = FULL_ACCESS
+            .check_collection_access(&collection_name, AccessRequirements::new())
+            .map_err(error_to_status)?;
+        let collection = self
+            .toc
+            .get_collection(&collection_pass)
+            .await
+            .map_err(error_to_status)?;
+
+        let points = collection
+            .local_trashed_points(shard_id, &point_ids)
+            .await
+            .map_err(|err| error_to_status(err.into()))?
+            .map(|points| serde_cbor::to_vec(&points))
+            .transpose()
+            .map_err(|err| Status::internal(format!("Failed to encode deleted points: {err}")))?;
+
+        Ok(Response::new(TrashResponseInternal {
+            points,
+            time: timing.elapsed().as_secs_f64(),
+        }))
+    }
+
+    async fn scan_trash(
+        &self,
+        request: Request<ScanTrashInternal>,
+    ) -> Result<Response<TrashResponseInternal>, Status> {
+        validate_and_log(request.get_ref());
+
+        let timing = Instant::now();
+        let ScanTrashInternal {
+            collection_name,
+            shard_id,
+            from,
+            to,
+            filter,
+        } = request.into_inner();
+
+        let shard_id = shard_id.ok_or_else(|| Status::invalid_argument("Shard id is missing"))?;
+        let from = from.map(PointIdType::try_from).transpose()?;
+        let to = to.map(PointIdType::try_from).transpose()?;
+        let filter = filter.map(Filter::try_from).transpose()?;
+        let collection_pass = FULL_ACCESS
+            .check_collection_access(&collection_name, AccessRequirements::new())
+            .map_err(error_to_status)?;
+        let collection = self
+            .toc
+            .get_collection(&collection_pass)
+            .await
+            .map_err(error_to_status)?;
+
+        let points = collection
+            .local_scan_trash(shard_id, from, to, filter.as_ref())
+            .await
+            .map_err(|err| error_to_status(err.into()))?;
+        let points = serde_cbor::to_vec(&points)
+            .map_err(|err| Status::internal(format!("Failed to encode deleted points: {err}")))?;
+
+        Ok(Response::new(TrashResponseInternal {
+            points: Some(points),
+            time: timing.elapsed().as_secs_f64(),
+        }))
+    }
+
     async fn sync(
         &self,
         request: Request<SyncPointsInternal>,
@@ -502,6 +826,7 @@ impl PointsInternal for PointsInternalService {
             query_points,
             shard_id,
             timeout,
+            &self.service_config.hardware_counter(),
         )
         .await
     }
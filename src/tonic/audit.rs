@@ -0,0 +1,62 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use tonic::{Code, Request, Status};
+
+use crate::common::audit::{AuditEntry, AuditLog, AuditTransport};
+use crate::common::auth::AuthSubject;
+
+/// Write operation, which is recorded into the audit log once its result is known
+pub struct PendingAudit {
+    audit_log: Option<Arc<AuditLog>>,
+    subject: Option<String>,
+    operation: &'static str,
+    collection: Option<String>,
+}
+
+impl PendingAudit {
+    /// Start auditing `operation`, given as a gRPC method name, like `qdrant.Points/Upsert`.
+    /// Does nothing if the audit log is disabled.
+    pub fn new<R>(
+        audit_log: Option<&Arc<AuditLog>>,
+        request: &Request<R>,
+        operation: &'static str,
+        collection: Option<&str>,
+    ) -> Self {
+        let subject = audit_log.and_then(|_| {
+            request
+                .extensions()
+                .get::<AuthSubject>()
+                .map(|subject| subject.0.clone())
+        });
+        Self {
+            audit_log: audit_log.cloned(),
+            subject,
+            operation,
+            collection: collection.map(str::to_string),
+        }
+    }
+
+    /// Record the operation with its result, and pass the result through
+    pub fn record<T>(self, result: Result<T, Status>) -> Result<T, Status> {
+        let Some(audit_log) = self.audit_log else {
+            return result;
+        };
+
+        let code = match &result {
+            Ok(_) => Code::Ok,
+            Err(status) => status.code(),
+        };
+        audit_log.record(AuditEntry {
+            timestamp: Utc::now(),
+            subject: self.subject,
+            transport: AuditTransport::Grpc,
+            operation: self.operation.to_string(),
+            collection: self.collection,
+            success: code == Code::Ok,
+            status: format!("{code:?}"),
+        });
+
+        result
+    }
+}
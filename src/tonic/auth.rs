@@ -20,7 +20,7 @@ pub struct AuthMiddleware<S> {
 }
 
 async fn check(auth_keys: Arc<AuthKeys>, mut req: Request) -> Result<Request, Status> {
-    let access = auth_keys
+    let (access, subject) = auth_keys
         .validate_request(|key| req.headers().get(key).and_then(|val| val.to_str().ok()))
         .await
         .map_err(|e| match e {
@@ -34,6 +34,7 @@ async fn check(auth_keys: Arc<AuthKeys>, mut req: Request) -> Result<Request, St
         _previous.is_none(),
         "Previous access object should not exist in the request"
     );
+    req.extensions_mut().insert(subject);
 
     Ok(req)
 }
@@ -1,6 +1,9 @@
 mod api;
+mod audit;
 mod auth;
 mod logging;
+mod rate_limiting;
+mod tls;
 mod tonic_telemetry;
 
 use std::io;
@@ -28,19 +31,26 @@ use ::api::grpc::qdrant::{
     WaitOnConsensusCommitRequest, WaitOnConsensusCommitResponse,
 };
 use ::api::grpc::QDRANT_DESCRIPTOR_SET;
+use collection::collection::health::CollectionHealth;
 use storage::content_manager::consensus_manager::ConsensusStateRef;
+use storage::content_manager::conversions::error_to_status;
 use storage::content_manager::toc::TableOfContent;
 use storage::dispatcher::Dispatcher;
 use storage::rbac::Access;
 use tokio::runtime::Handle;
 use tokio::signal;
 use tonic::codec::CompressionEncoding;
-use tonic::transport::{Server, ServerTlsConfig};
+use tonic::transport::Server;
 use tonic::{Request, Response, Status};
 
+use crate::common::audit::AuditLog;
 use crate::common::auth::AuthKeys;
+use crate::common::certificate_helpers;
+use crate::common::collections::do_get_collection_health;
 use crate::common::helpers;
 use crate::common::http_client::HttpClient;
+use crate::common::rate_limiting::RateLimiters;
+use crate::common::telemetry_ops::latency_telemetry::LatencyTelemetryCollector;
 use crate::common::telemetry_ops::requests_telemetry::TonicTelemetryCollector;
 use crate::settings::Settings;
 use crate::tonic::api::collections_api::CollectionsService;
@@ -62,18 +72,53 @@ impl Qdrant for QdrantService {
     }
 }
 
+/// Names of the services, which are always serving, used in health checks
+const PUBLIC_SERVICES: &[&str] = &[
+    "qdrant.Collections",
+    "qdrant.Points",
+    "qdrant.Snapshots",
+    "qdrant.Qdrant",
+    "grpc.health.v1.Health",
+];
+
 // Additional health check service that follows gRPC health check protocol as described in #2614
-#[derive(Default)]
-pub struct HealthService {}
+//
+// Besides the services, the name of a collection can be checked. The collection is serving once
+// its local replicas are loaded and active, so load balancers can route around peers, which are
+// still warming up.
+pub struct HealthService {
+    dispatcher: Arc<Dispatcher>,
+}
+
+impl HealthService {
+    pub fn new(dispatcher: Arc<Dispatcher>) -> Self {
+        Self { dispatcher }
+    }
+}
 
 #[tonic::async_trait]
 impl Health for HealthService {
     async fn check(
         &self,
-        _request: Request<ProtocolHealthCheckRequest>,
+        mut request: Request<ProtocolHealthCheckRequest>,
     ) -> Result<Response<ProtocolHealthCheckResponse>, Status> {
+        let access = auth::extract_access(&mut request);
+        let service = request.into_inner().service;
+
+        let status = if service.is_empty() || PUBLIC_SERVICES.contains(&service.as_str()) {
+            ServingStatus::Serving
+        } else {
+            let health = do_get_collection_health(self.dispatcher.toc(&access), access, &service)
+                .await
+                .map_err(error_to_status)?;
+            match health {
+                CollectionHealth::Ready | CollectionHealth::Degraded => ServingStatus::Serving,
+                CollectionHealth::Loading => ServingStatus::NotServing,
+            }
+        };
+
         let response = ProtocolHealthCheckResponse {
-            status: ServingStatus::Serving as i32,
+            status: status as i32,
         };
 
         Ok(Response::new(response))
@@ -149,15 +194,24 @@ pub fn init(
     settings: Settings,
     grpc_port: u16,
     runtime: Handle,
+    rate_limiters: Option<Arc<RateLimiters>>,
+    latency_collector: Arc<LatencyTelemetryCollector>,
+    audit_log: Option<Arc<AuditLog>>,
 ) -> io::Result<()> {
     runtime.block_on(async {
         let socket =
             SocketAddr::from((settings.service.host.parse::<IpAddr>().unwrap(), grpc_port));
 
         let qdrant_service = QdrantService::default();
-        let health_service = HealthService::default();
-        let collections_service = CollectionsService::new(dispatcher.clone());
-        let points_service = PointsService::new(dispatcher.clone());
+        let health_service = HealthService::new(dispatcher.clone());
+        let collections_service = CollectionsService::new(dispatcher.clone(), audit_log.clone());
+        let points_service = PointsService::new(
+            dispatcher.clone(),
+            settings.service.clone(),
+            rate_limiters,
+            latency_collector,
+            audit_log,
+        );
         let snapshot_service = SnapshotsService::new(dispatcher.clone());
 
         // Only advertise the public services. By default, all services in QDRANT_DESCRIPTOR_SET
@@ -174,19 +228,17 @@ pub fn init(
 
         log::info!("Qdrant gRPC listening on {}", grpc_port);
 
-        let mut server = Server::builder();
-
-        if settings.service.enable_tls {
-            log::info!("TLS enabled for gRPC API (TTL not supported)");
+        let server = Server::builder();
 
-            let tls_server_config = helpers::load_tls_external_server_config(settings.tls()?)?;
+        let tls_server_config = if settings.service.enable_tls {
+            log::info!("TLS enabled for gRPC API");
 
-            server = server
-                .tls_config(tls_server_config)
-                .map_err(helpers::tonic_error_to_io_error)?;
+            let config = certificate_helpers::tonic_tls_server_config(settings.tls()?, false)?;
+            Some(Arc::new(config))
         } else {
             log::info!("TLS disabled for gRPC API");
-        }
+            None
+        };
 
         // The stack of middleware that our service will be wrapped in
         let middleware_layer = tower::ServiceBuilder::new()
@@ -205,7 +257,7 @@ pub fn init(
             })
             .into_inner();
 
-        server
+        let router = server
             .layer(middleware_layer)
             .add_service(reflection_service)
             .add_service(
@@ -237,12 +289,21 @@ pub fn init(
                     .send_compressed(CompressionEncoding::Gzip)
                     .accept_compressed(CompressionEncoding::Gzip)
                     .max_decoding_message_size(usize::MAX),
-            )
-            .serve_with_shutdown(socket, async {
-                wait_stop_signal("gRPC service").await;
-            })
-            .await
-            .map_err(helpers::tonic_error_to_io_error)
+            );
+
+        let shutdown = async {
+            wait_stop_signal("gRPC service").await;
+        };
+        let result = match tls_server_config {
+            Some(config) => {
+                let listener = tokio::net::TcpListener::bind(socket).await?;
+                router
+                    .serve_with_incoming_shutdown(tls::incoming(listener, config), shutdown)
+                    .await
+            }
+            None => router.serve_with_shutdown(socket, shutdown).await,
+        };
+        result.map_err(helpers::tonic_error_to_io_error)
     })?;
 
     Ok(())
@@ -256,7 +317,7 @@ pub fn init_internal(
     settings: Settings,
     host: String,
     internal_grpc_port: u16,
-    tls_config: Option<ServerTlsConfig>,
+    tls_config: Option<Arc<rustls::ServerConfig>>,
     to_consensus: tokio::sync::mpsc::Sender<crate::consensus::Message>,
     runtime: Handle,
 ) -> std::io::Result<()> {
@@ -271,16 +332,17 @@ pub fn init_internal(
             let socket = SocketAddr::from((host.parse::<IpAddr>().unwrap(), internal_grpc_port));
 
             let qdrant_service = QdrantService::default();
+            let points_internal_service =
+                PointsInternalService::new(toc.clone(), settings.service.clone());
             let qdrant_internal_service =
                 QdrantInternalService::new(settings, consensus_state.clone());
             let collections_internal_service = CollectionsInternalService::new(toc.clone());
-            let points_internal_service = PointsInternalService::new(toc.clone());
             let shard_snapshots_service = ShardSnapshotsService::new(toc.clone(), http_client);
             let raft_service = RaftService::new(to_consensus, consensus_state);
 
             log::debug!("Qdrant internal gRPC listening on {}", internal_grpc_port);
 
-            let mut server = Server::builder()
+            let server = Server::builder()
                 // Internally use a high limit for pending accept streams.
                 // We can have a huge number of reset/dropped HTTP2 streams in our internal
                 // communication when there are a lot of clients dropping connections. This
@@ -290,10 +352,8 @@ pub fn init_internal(
                 // More info: <https://github.com/qdrant/qdrant/issues/1907>
                 .http2_max_pending_accept_reset_streams(Some(1024));
 
-            if let Some(config) = tls_config {
-                log::info!("TLS enabled for internal gRPC API (TTL not supported)");
-
-                server = server.tls_config(config)?;
+            if tls_config.is_some() {
+                log::info!("TLS enabled for internal gRPC API");
             } else {
                 log::info!("TLS disabled for internal gRPC API");
             };
@@ -306,7 +366,7 @@ pub fn init_internal(
                 ))
                 .into_inner();
 
-            server
+            let router = server
                 .layer(middleware_layer)
                 .add_service(
                     QdrantServer::new(qdrant_service)
@@ -343,11 +403,21 @@ pub fn init_internal(
                         .send_compressed(CompressionEncoding::Gzip)
                         .accept_compressed(CompressionEncoding::Gzip)
                         .max_decoding_message_size(usize::MAX),
-                )
-                .serve_with_shutdown(socket, async {
-                    wait_stop_signal("internal gRPC").await;
-                })
-                .await
+                );
+
+            let shutdown = async {
+                wait_stop_signal("internal gRPC").await;
+            };
+            let result = match tls_config {
+                Some(config) => {
+                    let listener = tokio::net::TcpListener::bind(socket).await?;
+                    router
+                        .serve_with_incoming_shutdown(tls::incoming(listener, config), shutdown)
+                        .await
+                }
+                None => router.serve_with_shutdown(socket, shutdown).await,
+            };
+            result.map_err(helpers::tonic_error_to_io_error)
         })
         .unwrap();
     Ok(())
@@ -0,0 +1,86 @@
+use api::grpc::qdrant::{
+    ClearPayloadPoints, CountDistinctPoints, CountPoints, CreateFieldIndexCollection,
+    DeleteFieldIndexCollection, DeletePayloadPoints, DeletePointVectors, DeletePoints,
+    DiscoverBatchPoints, DiscoverPoints, FieldStatsPoints, GetPoints, HistogramPoints,
+    QueryBatchPoints, QueryPoints, RecommendBatchPoints, RecommendPointGroups, RecommendPoints,
+    ScrollPoints, SearchBatchPoints, SearchPointGroups, SearchPoints, SetPayloadPoints,
+    UpdateBatchPoints, UpdatePointVectors, UpsertPoints,
+};
+use tonic::{Request, Status};
+
+use crate::common::auth::extract_api_key;
+use crate::common::rate_limiting::{RateLimiters, RequestKind};
+
+/// Check rate limits of the collection and API key of a gRPC request.
+///
+/// Rejected requests get `RESOURCE_EXHAUSTED` status with a `retry-after` metadata entry,
+/// holding the number of seconds to wait before retrying.
+pub fn check_rate_limit<R>(
+    rate_limiters: Option<&RateLimiters>,
+    request: &Request<R>,
+    collection_name: Option<&str>,
+    kind: RequestKind,
+) -> Result<(), Status> {
+    let Some(rate_limiters) = rate_limiters else {
+        return Ok(());
+    };
+
+    let metadata = request.metadata();
+    let api_key = extract_api_key(|key| metadata.get(key).and_then(|v| v.to_str().ok()));
+
+    rate_limiters
+        .check(collection_name, api_key, kind)
+        .map_err(|rate_limited| {
+            let mut status = Status::resource_exhausted(rate_limited.description);
+            if let Ok(retry_after) = rate_limited.retry_after_secs().to_string().parse() {
+                status.metadata_mut().insert("retry-after", retry_after);
+            }
+            status
+        })
+}
+
+/// gRPC request, addressed to a single collection
+pub trait CollectionRequest {
+    fn collection_name(&self) -> &str;
+}
+
+macro_rules! impl_collection_request {
+    ($($request:ty),* $(,)?) => {
+        $(
+            impl CollectionRequest for $request {
+                fn collection_name(&self) -> &str {
+                    &self.collection_name
+                }
+            }
+        )*
+    };
+}
+
+impl_collection_request!(
+    ClearPayloadPoints,
+    CountDistinctPoints,
+    CountPoints,
+    CreateFieldIndexCollection,
+    DeleteFieldIndexCollection,
+    DeletePayloadPoints,
+    DeletePointVectors,
+    DeletePoints,
+    DiscoverBatchPoints,
+    DiscoverPoints,
+    FieldStatsPoints,
+    GetPoints,
+    HistogramPoints,
+    QueryBatchPoints,
+    QueryPoints,
+    RecommendBatchPoints,
+    RecommendPointGroups,
+    RecommendPoints,
+    ScrollPoints,
+    SearchBatchPoints,
+    SearchPointGroups,
+    SearchPoints,
+    SetPayloadPoints,
+    UpdateBatchPoints,
+    UpdatePointVectors,
+    UpsertPoints,
+);
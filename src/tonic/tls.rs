@@ -0,0 +1,118 @@
+use std::io;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures::Stream;
+use rustls::ServerConfig;
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio_rustls::server::TlsStream;
+use tokio_rustls::TlsAcceptor;
+use tonic::transport::server::{Connected, TcpConnectInfo};
+
+/// Max number of established TLS connections, waiting to be picked up by the server
+const ACCEPTED_CONNECTIONS_BUFFER: usize = 128;
+
+/// TLS connection, accepted with our own rustls configuration
+///
+/// Tonic only supports TLS configuration it loads once on start, so to rotate certificates
+/// connections are accepted by us and passed to tonic as plain IO.
+pub struct TlsConnection(TlsStream<TcpStream>);
+
+impl Connected for TlsConnection {
+    type ConnectInfo = TcpConnectInfo;
+
+    fn connect_info(&self) -> Self::ConnectInfo {
+        self.0.get_ref().0.connect_info()
+    }
+}
+
+impl AsyncRead for TlsConnection {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for TlsConnection {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.0).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_shutdown(cx)
+    }
+
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.0).poll_write_vectored(cx, bufs)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.0.is_write_vectored()
+    }
+}
+
+/// Accept TLS connections on `listener`.
+///
+/// Handshakes are performed in separate tasks, so a slow client doesn't block others.
+/// Failed handshakes are logged and dropped, they don't stop the server.
+pub fn incoming(
+    listener: TcpListener,
+    config: Arc<ServerConfig>,
+) -> impl Stream<Item = io::Result<TlsConnection>> {
+    let acceptor = TlsAcceptor::from(config);
+    let (sender, receiver) = mpsc::channel(ACCEPTED_CONNECTIONS_BUFFER);
+
+    tokio::spawn(async move {
+        loop {
+            let (stream, remote_addr) = match listener.accept().await {
+                Ok(accepted) => accepted,
+                Err(err) => {
+                    log::warn!("Failed to accept gRPC connection: {err}");
+                    continue;
+                }
+            };
+            if let Err(err) = stream.set_nodelay(true) {
+                log::debug!("Failed to set TCP_NODELAY for {remote_addr}: {err}");
+            }
+
+            let acceptor = acceptor.clone();
+            let sender = sender.clone();
+            tokio::spawn(async move {
+                match acceptor.accept(stream).await {
+                    Ok(stream) => {
+                        // Receiver is gone if the server is stopped
+                        let _ = sender.send(TlsConnection(stream)).await;
+                    }
+                    Err(err) => log::debug!("TLS handshake with {remote_addr} failed: {err}"),
+                }
+            });
+
+            if sender.is_closed() {
+                break;
+            }
+        }
+    });
+
+    futures::stream::unfold(receiver, |mut receiver| async move {
+        let connection = receiver.recv().await?;
+        Some((Ok(connection), receiver))
+    })
+}
@@ -129,6 +129,92 @@ ACTION_ACCESS = {
         "qdrant.Collections/CollectionClusterInfo",
         coll_rw_payload=False,
     ),
+    "get_slow_queries": EndpointAccess(
+        True,
+        True,
+        True,
+        "GET /collections/{collection_name}/slow_queries",
+        coll_rw_payload=False,
+    ),
+    "get_collection_segments": EndpointAccess(
+        True,
+        True,
+        True,
+        "GET /collections/{collection_name}/segments",
+        coll_rw_payload=False,
+    ),
+    "get_collection_disk_usage": EndpointAccess(
+        True,
+        True,
+        True,
+        "GET /collections/{collection_name}/disk_usage",
+        coll_rw_payload=False,
+    ),
+    "get_collection_stats": EndpointAccess(
+        True,
+        True,
+        True,
+        "GET /collections/{collection_name}/stats",
+        coll_rw_payload=False,
+    ),
+    "get_index_recommendations": EndpointAccess(
+        True,
+        True,
+        True,
+        "GET /collections/{collection_name}/index_recommendations",
+        coll_rw_payload=False,
+    ),
+    "get_warmup_progress": EndpointAccess(
+        True,
+        True,
+        True,
+        "GET /collections/{collection_name}/warmup",
+        coll_rw_payload=False,
+    ),
+    "get_payload_schema": EndpointAccess(
+        True,
+        True,
+        True,
+        "GET /collections/{collection_name}/payload_schema",
+        coll_rw_payload=False,
+    ),
+    "get_vector_diagnostics": EndpointAccess(
+        True,
+        True,
+        True,
+        "GET /collections/{collection_name}/vector_diagnostics",
+        coll_rw_payload=False,
+    ),
+    "evaluate_recall": EndpointAccess(
+        True,
+        True,
+        True,
+        "POST /collections/{collection_name}/recall",
+        coll_rw_payload=False,
+    ),
+    "tune_search": EndpointAccess(
+        True,
+        True,
+        True,
+        "POST /collections/{collection_name}/tune_search",
+        coll_rw_payload=False,
+    ),
+    "get_collection_freeze": EndpointAccess(
+        True,
+        True,
+        True,
+        "GET /collections/{collection_name}/freeze",
+        coll_rw_payload=False,
+    ),
+    "freeze_collection": EndpointAccess(
+        False, False, True, "POST /collections/{collection_name}/freeze"
+    ),
+    "unfreeze_collection": EndpointAccess(
+        False, False, True, "DELETE /collections/{collection_name}/freeze"
+    ),
+    "check_collection_consistency": EndpointAccess(
+        False, False, True, "POST /collections/{collection_name}/consistency"
+    ),
     "collection_exists": EndpointAccess(
         True,
         True,
@@ -295,6 +381,13 @@ ACTION_ACCESS = {
         "GET /collections/{collection_name}/snapshots/{snapshot_name}",
         coll_rw_payload=False,
     ),
+    "verify_collection_snapshot": EndpointAccess(
+        True,
+        True,
+        True,
+        "POST /collections/{collection_name}/snapshots/{snapshot_name}/verify",
+        coll_rw_payload=False,
+    ),
     "upload_collection_snapshot": EndpointAccess(
         False, False, True, "POST /collections/{collection_name}/snapshots/upload"
     ),
@@ -304,6 +397,12 @@ ACTION_ACCESS = {
         True,
         "PUT /collections/{collection_name}/snapshots/recover",
     ),
+    "recover_collection_remote_snapshot": EndpointAccess(
+        False,
+        False,
+        True,
+        "PUT /collections/{collection_name}/snapshots/recover/remote",
+    ),
     ### Shard Snapshots ###
     "upload_shard_snapshot": EndpointAccess(
         False,
@@ -345,6 +444,41 @@ ACTION_ACCESS = {
         "GET /collections/{collection_name}/shards/{shard_id}/snapshots/{snapshot_name}",
         coll_rw_payload=False,
     ),
+    "create_segment_snapshot": EndpointAccess(
+        False,
+        True,
+        True,
+        "POST /collections/{collection_name}/shards/{shard_id}/segments/{segment_id}/snapshots",
+        coll_rw_payload=False,
+    ),
+    "upload_segment_snapshot": EndpointAccess(
+        False,
+        False,
+        True,
+        "POST /collections/{collection_name}/shards/{shard_id}/segments/{segment_id}/snapshots/upload",
+    ),
+    ### Snapshot schedules ###
+    "list_snapshot_schedules": EndpointAccess(
+        True,
+        True,
+        True,
+        "GET /collections/{collection_name}/snapshot_schedules",
+        coll_rw_payload=False,
+    ),
+    "set_snapshot_schedule": EndpointAccess(
+        False,
+        True,
+        True,
+        "PUT /collections/{collection_name}/snapshot_schedules/{schedule_name}",
+        coll_rw_payload=False,
+    ),
+    "delete_snapshot_schedule": EndpointAccess(
+        False,
+        True,
+        True,
+        "DELETE /collections/{collection_name}/snapshot_schedules/{schedule_name}",
+        coll_rw_payload=False,
+    ),
     ### Full Snapshots ###
     "list_full_snapshots": EndpointAccess(
         True,
@@ -367,6 +501,18 @@ ACTION_ACCESS = {
     "get_cluster": EndpointAccess(True, False, True, "GET /cluster", coll_r=False),
     "recover_raft_state": EndpointAccess(False, False, True, "POST /cluster/recover"),
     "delete_peer": EndpointAccess(False, False, True, "DELETE /cluster/peer/{peer_id}"),
+    "list_cluster_metadata": EndpointAccess(
+        True, False, True, "GET /cluster/metadata/{namespace}", coll_r=False
+    ),
+    "get_cluster_metadata_key": EndpointAccess(
+        True, False, True, "GET /cluster/metadata/{namespace}/{key}", coll_r=False
+    ),
+    "put_cluster_metadata_key": EndpointAccess(
+        False, False, True, "PUT /cluster/metadata/{namespace}/{key}"
+    ),
+    "delete_cluster_metadata_key": EndpointAccess(
+        False, False, True, "DELETE /cluster/metadata/{namespace}/{key}"
+    ),
     ### Points ###
     "get_point": EndpointAccess(
         True,
@@ -383,6 +529,13 @@ ACTION_ACCESS = {
         "qdrant.Points/Get",
         coll_rw_payload=False,
     ),
+    "get_point_history": EndpointAccess(
+        True,
+        True,
+        True,
+        "GET /collections/{collection_name}/points/{id}/history",
+        coll_rw_payload=False,
+    ),
     "upsert_points": EndpointAccess(
         False,
         True,
@@ -391,6 +544,14 @@ ACTION_ACCESS = {
         "qdrant.Points/Upsert",
         coll_rw_payload=False,
     ),
+    "upsert_points_stream": EndpointAccess(
+        False,
+        True,
+        True,
+        "PUT /collections/{collection_name}/points",
+        "qdrant.Points/UpsertStream",
+        coll_rw_payload=False,
+    ),
     "update_points_batch": EndpointAccess(
         False,
         True,
@@ -399,6 +560,14 @@ ACTION_ACCESS = {
         "qdrant.Points/UpdateBatch",
         coll_rw_payload=False,
     ),
+    "update_points_bulk": EndpointAccess(
+        False,
+        True,
+        True,
+        "POST /collections/{collection_name}/points/bulk",
+        "qdrant.Points/BulkUpdate",
+        coll_rw_payload=False,
+    ),
     "delete_points": EndpointAccess(
         False,
         True,
@@ -406,6 +575,13 @@ ACTION_ACCESS = {
         "POST /collections/{collection_name}/points/delete",
         "qdrant.Points/Delete",
     ),
+    "restore_points": EndpointAccess(
+        False,
+        True,
+        True,
+        "POST /collections/{collection_name}/points/restore",
+        coll_rw_payload=False,
+    ),
     "update_vectors": EndpointAccess(
         False,
         True,
@@ -437,6 +613,14 @@ ACTION_ACCESS = {
         "qdrant.Points/OverwritePayload",
         coll_rw_payload=False,
     ),
+    "patch_payload": EndpointAccess(
+        False,
+        True,
+        True,
+        "PATCH /collections/{collection_name}/points/payload",
+        "qdrant.Points/PatchPayload",
+        coll_rw_payload=False,
+    ),
     "delete_payload": EndpointAccess(
         False,
         True,
@@ -453,6 +637,14 @@ ACTION_ACCESS = {
         "qdrant.Points/ClearPayload",
         coll_rw_payload=False,
     ),
+    "set_attributes": EndpointAccess(
+        False,
+        True,
+        True,
+        "PUT /collections/{collection_name}/points/attributes",
+        "qdrant.Points/SetAttributes",
+        coll_rw_payload=False,
+    ),
     "scroll_points": EndpointAccess(
         True,
         True,
@@ -460,6 +652,20 @@ ACTION_ACCESS = {
         "POST /collections/{collection_name}/points/scroll",
         "qdrant.Points/Scroll",
     ),
+    "scroll_points_stream": EndpointAccess(
+        True,
+        True,
+        True,
+        "POST /collections/{collection_name}/points/scroll/stream",
+        "qdrant.Points/ScrollStream",
+    ),
+    "export_points": EndpointAccess(
+        True,
+        True,
+        True,
+        "POST /collections/{collection_name}/points/export",
+        coll_rw_payload=False,
+    ),
     "search_points": EndpointAccess(
         True,
         True,
@@ -474,6 +680,12 @@ ACTION_ACCESS = {
         "POST /collections/{collection_name}/points/search/batch",
         "qdrant.Points/SearchBatch",
     ),
+    "explain_search_points": EndpointAccess(
+        True,
+        True,
+        True,
+        "POST /collections/{collection_name}/points/search/explain",
+    ),
     "search_point_groups": EndpointAccess(
         True,
         True,
@@ -497,6 +709,13 @@ ACTION_ACCESS = {
         "qdrant.Points/RecommendBatch",
         coll_rw_payload=False,
     ),
+    "recommend_shared_batch_points": EndpointAccess(
+        True,
+        True,
+        True,
+        "POST /collections/{collection_name}/points/recommend/shared",
+        coll_rw_payload=False,
+    ),
     "recommend_point_groups": EndpointAccess(
         True,
         True,
@@ -524,6 +743,28 @@ ACTION_ACCESS = {
     "count_points": EndpointAccess(
         True, True, True, "POST /collections/{collection_name}/points/count", "qdrant.Points/Count"
     ),
+    "count_distinct": EndpointAccess(
+        True,
+        True,
+        True,
+        "POST /collections/{collection_name}/points/count_distinct",
+        "qdrant.Points/CountDistinct",
+    ),
+    "field_stats": EndpointAccess(
+        True,
+        True,
+        True,
+        "POST /collections/{collection_name}/points/field_stats",
+        "qdrant.Points/FieldStats",
+    ),
+    "histogram": EndpointAccess(
+        True,
+        True,
+        True,
+        "POST /collections/{collection_name}/points/histogram",
+        "qdrant.Points/Histogram",
+        coll_rw_payload=False,
+    ),
     "query_points": EndpointAccess(
         True, True, True, "POST /collections/{collection_name}/points/query", "qdrant.Points/Query"
     ),
@@ -539,8 +780,22 @@ ACTION_ACCESS = {
     "metrics": EndpointAccess(True, False, True, "GET /metrics", coll_r=False),
     "post_locks": EndpointAccess(False, False, True, "POST /locks"),
     "get_locks": EndpointAccess(True, False, True, "GET /locks", coll_r=False),
+    "get_audit_log": EndpointAccess(False, False, True, "GET /audit"),
+    "get_runtime_config": EndpointAccess(False, False, True, "GET /config"),
+    "reload_config": EndpointAccess(False, False, True, "POST /config/reload"),
+    "list_api_keys": EndpointAccess(False, False, True, "GET /api_keys"),
+    "create_api_key": EndpointAccess(False, False, True, "POST /api_keys"),
+    "update_api_key": EndpointAccess(False, False, True, "PUT /api_keys/{key_name}"),
+    "delete_api_key": EndpointAccess(False, False, True, "DELETE /api_keys/{key_name}"),
     "get_issues": EndpointAccess(False, False, True, "GET /issues"),
     "clear_issues": EndpointAccess(False, False, True, "DELETE /issues"),
+    ### Templates ###
+    "list_templates": EndpointAccess(True, False, True, "GET /templates", coll_r=False),
+    "get_template": EndpointAccess(
+        True, False, True, "GET /templates/{template_name}", coll_r=False
+    ),
+    "put_template": EndpointAccess(False, False, True, "PUT /templates/{template_name}"),
+    "delete_template": EndpointAccess(False, False, True, "DELETE /templates/{template_name}"),
 }
 
 
@@ -659,7 +914,10 @@ def check_grpc_access(
         request = request()
 
     try:
-        _res = client.request(service=service, method=method, request=request)
+        res = client.request(service=service, method=method, request=request)
+        # Streaming responses are lazy, errors are only raised while consuming them
+        if not isinstance(res, dict):
+            list(res)
     except grpc.RpcError as e:
         if should_succeed:
             if e.code() not in [grpc.StatusCode.INVALID_ARGUMENT, grpc.StatusCode.NOT_FOUND]:
@@ -937,6 +1195,108 @@ def test_get_collection_cluster_info():
     )
 
 
+def test_get_slow_queries():
+    check_access(
+        "get_slow_queries",
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
+def test_get_collection_segments():
+    check_access(
+        "get_collection_segments",
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
+def test_get_collection_disk_usage():
+    check_access(
+        "get_collection_disk_usage",
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
+def test_get_collection_stats():
+    check_access(
+        "get_collection_stats",
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
+def test_get_index_recommendations():
+    check_access(
+        "get_index_recommendations",
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
+def test_get_warmup_progress():
+    check_access(
+        "get_warmup_progress",
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
+def test_get_payload_schema():
+    check_access(
+        "get_payload_schema",
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
+def test_get_vector_diagnostics():
+    check_access(
+        "get_vector_diagnostics",
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
+def test_evaluate_recall():
+    check_access(
+        "evaluate_recall",
+        rest_request={"sample": 1, "limit": 1},
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
+def test_tune_search():
+    check_access(
+        "tune_search",
+        rest_request={"sample": 10, "queries": 1, "m": [16], "ef": [16]},
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
+def test_get_collection_freeze():
+    check_access(
+        "get_collection_freeze",
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
+def test_freeze_collection():
+    # Lift the freeze quickly, not to delay updates of the following tests
+    check_access(
+        "freeze_collection",
+        rest_req_kwargs={"params": {"timeout": 1}},
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
+def test_unfreeze_collection():
+    check_access(
+        "unfreeze_collection",
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
+def test_check_collection_consistency():
+    check_access(
+        "check_collection_consistency",
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
 def test_collection_exists():
     check_access(
         "collection_exists",
@@ -1201,6 +1561,25 @@ def test_create_collection_snapshot():
     )
 
 
+def test_list_snapshot_schedules():
+    check_access("list_snapshot_schedules", path_params={"collection_name": COLL_NAME})
+
+
+def test_set_snapshot_schedule():
+    check_access(
+        "set_snapshot_schedule",
+        rest_request={"cron": "0 0 1 1 *", "retention": 1},
+        path_params={"collection_name": COLL_NAME, "schedule_name": "yearly"},
+    )
+
+
+def test_delete_snapshot_schedule():
+    check_access(
+        "delete_snapshot_schedule",
+        path_params={"collection_name": COLL_NAME, "schedule_name": random_str()},
+    )
+
+
 def test_delete_collection_snapshot():
     fake_name = random_str()
     check_access(
@@ -1227,6 +1606,20 @@ def test_download_collection_snapshot():
     )
 
 
+def test_verify_collection_snapshot():
+    res = requests.post(
+        f"{REST_URI}/collections/{COLL_NAME}/snapshots?wait=true",
+        headers=API_KEY_HEADERS,
+    )
+    res.raise_for_status()
+    filename = res.json()["result"]["name"]
+
+    check_access(
+        "verify_collection_snapshot",
+        path_params={"collection_name": COLL_NAME, "snapshot_name": filename},
+    )
+
+
 @pytest.fixture(scope="module")
 def collection_snapshot():
     res = requests.post(
@@ -1267,6 +1660,14 @@ def test_recover_collection_snapshot(collection_snapshot: bytes):
     )
 
 
+def test_recover_collection_remote_snapshot():
+    check_access(
+        "recover_collection_remote_snapshot",
+        rest_request={"url": REST_URI, "api_key": SECRET},
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
 @pytest.fixture(scope="module")
 def shard_snapshot_name():
     res = requests.post(
@@ -1344,6 +1745,29 @@ def test_download_shard_snapshot(shard_snapshot_name: str):
     )
 
 
+def test_create_segment_snapshot():
+    check_access(
+        "create_segment_snapshot",
+        path_params={
+            "collection_name": COLL_NAME,
+            "shard_id": SHARD_ID,
+            "segment_id": random_str(),
+        },
+    )
+
+
+def test_upload_segment_snapshot(shard_snapshot: bytes):
+    check_access(
+        "upload_segment_snapshot",
+        rest_req_kwargs={"files": {"snapshot": shard_snapshot}, "timeout": 1},
+        path_params={
+            "collection_name": COLL_NAME,
+            "shard_id": SHARD_ID,
+            "segment_id": random_str(),
+        },
+    )
+
+
 def test_list_full_snapshots():
     check_access("list_full_snapshots")
 
@@ -1375,6 +1799,29 @@ def test_recover_raft_state():
     check_access("recover_raft_state")
 
 
+def test_list_cluster_metadata():
+    check_access("list_cluster_metadata", path_params={"namespace": "models"})
+
+
+def test_get_cluster_metadata_key():
+    check_access("get_cluster_metadata_key", path_params={"namespace": "models", "key": "embedder"})
+
+
+def test_put_cluster_metadata_key():
+    check_access(
+        "put_cluster_metadata_key",
+        rest_request={"value": "v1"},
+        path_params={"namespace": "models", "key": "embedder"},
+    )
+
+
+def test_delete_cluster_metadata_key():
+    check_access(
+        "delete_cluster_metadata_key",
+        path_params={"namespace": "models", "key": random_str},
+    )
+
+
 def test_delete_peer():
     check_access("delete_peer", path_params={"peer_id": "2000"})
 
@@ -1395,6 +1842,13 @@ def test_get_points():
     )
 
 
+def test_get_point_history():
+    check_access(
+        "get_point_history",
+        path_params={"collection_name": COLL_NAME, "id": 1},
+    )
+
+
 def test_upsert_points():
     check_access(
         "upsert_points",
@@ -1408,7 +1862,27 @@ def test_upsert_points():
     )
 
 
-def test_update_points_batch():
+def test_upsert_points_stream():
+    check_access(
+        "upsert_points_stream",
+        rest_request={"points": [{"id": 1, "vector": [1, 2, 3, 4]}], "shard_key": SHARD_KEY},
+        path_params={"collection_name": COLL_NAME},
+        grpc_request=[
+            {
+                "collection_name": COLL_NAME,
+                "points": [{"id": {"num": 1}, "vectors": {"vector": {"data": [1, 2, 3, 4]}}}],
+                "shard_key_selector": {"shard_keys": [{"keyword": SHARD_KEY}]},
+            },
+            {
+                "collection_name": COLL_NAME,
+                "points": [{"id": {"num": 2}, "vectors": {"vector": {"data": [1, 2, 3, 4]}}}],
+                "shard_key_selector": {"shard_keys": [{"keyword": SHARD_KEY}]},
+            },
+        ],
+    )
+
+
+def update_operations():
     rest_operations = [
         {"upsert": {"shard_key": SHARD_KEY, "points": [{"id": 1, "vector": [1, 2, 3, 4]}]}},
         {"delete": {"shard_key": SHARD_KEY, "points": [3]}},
@@ -1483,6 +1957,12 @@ def test_update_points_batch():
         {"clear_payload": {**SHARD_KEY_SELECTOR, "points": {"points": {"ids": [{"num": 11}]}}}},
     ]
 
+    return rest_operations, grpc_operations
+
+
+def test_update_points_batch():
+    rest_operations, grpc_operations = update_operations()
+
     check_access(
         "update_points_batch",
         rest_request={"operations": rest_operations},
@@ -1494,6 +1974,20 @@ def test_update_points_batch():
     )
 
 
+def test_update_points_bulk():
+    rest_operations, grpc_operations = update_operations()
+
+    check_access(
+        "update_points_bulk",
+        rest_request={"operations": rest_operations},
+        path_params={"collection_name": COLL_NAME},
+        grpc_request={
+            "collection_name": COLL_NAME,
+            "operations": grpc_operations,
+        },
+    )
+
+
 def test_delete_points():
     check_access(
         "delete_points",
@@ -1507,6 +2001,14 @@ def test_delete_points():
     )
 
 
+def test_restore_points():
+    check_access(
+        "restore_points",
+        rest_request={"points": [1]},
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
 def test_update_vectors():
     check_access(
         "update_vectors",
@@ -1562,6 +2064,20 @@ def test_overwrite_payload():
     )
 
 
+def test_patch_payload():
+    check_access(
+        "patch_payload",
+        rest_request={"points": [1], "payload": {"my_key": "value"}, "shard_key": SHARD_KEY},
+        path_params={"collection_name": COLL_NAME},
+        grpc_request={
+            "collection_name": COLL_NAME,
+            "points_selector": {"points": {"ids": [{"num": 1}]}},
+            "payload": {"my_key": {"string_value": "value"}},
+            **SHARD_KEY_SELECTOR,
+        },
+    )
+
+
 def test_delete_payload():
     check_access(
         "delete_payload",
@@ -1589,6 +2105,20 @@ def test_clear_payload():
     )
 
 
+def test_set_attributes():
+    check_access(
+        "set_attributes",
+        rest_request={"attributes": {}, "points": [1], "shard_key": SHARD_KEY},
+        path_params={"collection_name": COLL_NAME},
+        grpc_request={
+            "collection_name": COLL_NAME,
+            "attributes": {},
+            "points_selector": {"points": {"ids": [{"num": 1}]}},
+            **SHARD_KEY_SELECTOR,
+        },
+    )
+
+
 def test_scroll_points():
     check_access(
         "scroll_points",
@@ -1598,6 +2128,23 @@ def test_scroll_points():
     )
 
 
+def test_scroll_points_stream():
+    check_access(
+        "scroll_points_stream",
+        rest_request={"limit": 10},
+        path_params={"collection_name": COLL_NAME},
+        grpc_request={"collection_name": COLL_NAME, "limit": 10},
+    )
+
+
+def test_export_points():
+    check_access(
+        "export_points",
+        rest_request={"batch_size": 10},
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
 def test_search_points():
     check_access(
         "search_points",
@@ -1620,6 +2167,14 @@ def test_search_points_batch():
     )
 
 
+def test_explain_search_points():
+    check_access(
+        "explain_search_points",
+        rest_request={"vector": [1, 2, 3, 4], "limit": 10},
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
 def test_search_point_groups():
     query = {"vector": [1, 2, 3, 4], "limit": 10, "group_by": FIELD_NAME, "group_size": 3}
     check_access(
@@ -1657,6 +2212,14 @@ def test_recommend_points_batch():
     )
 
 
+def test_recommend_shared_batch_points():
+    check_access(
+        "recommend_shared_batch_points",
+        rest_request={"positive": [1], "searches": [{"limit": 10}]},
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
 def test_recommend_point_groups():
     check_access(
         "recommend_point_groups",
@@ -1712,6 +2275,33 @@ def test_count_points():
     )
 
 
+def test_count_distinct():
+    check_access(
+        "count_distinct",
+        rest_request={"key": "user_id"},
+        path_params={"collection_name": COLL_NAME},
+        grpc_request={"collection_name": COLL_NAME, "key": "user_id"},
+    )
+
+
+def test_field_stats():
+    check_access(
+        "field_stats",
+        rest_request={"key": "user_id", "percentiles": [50]},
+        path_params={"collection_name": COLL_NAME},
+        grpc_request={"collection_name": COLL_NAME, "key": "user_id", "percentiles": [50]},
+    )
+
+
+def test_histogram():
+    check_access(
+        "histogram",
+        rest_request={"key": "user_id", "buckets": {"quantiles": 4}},
+        path_params={"collection_name": COLL_NAME},
+        grpc_request={"collection_name": COLL_NAME, "key": "user_id", "quantiles": 4},
+    )
+
+
 def test_query_points():
     check_access(
         "query_points",
@@ -1784,9 +2374,64 @@ def test_get_locks():
     check_access("get_locks")
 
 
+def test_get_audit_log():
+    check_access("get_audit_log")
+
+
+def test_get_runtime_config():
+    check_access("get_runtime_config")
+
+
+def test_reload_config():
+    check_access("reload_config")
+
+
+def test_list_api_keys():
+    check_access("list_api_keys")
+
+
+def test_create_api_key():
+    check_access(
+        "create_api_key",
+        rest_request=lambda: {"name": random_str(), "role": "read_only"},
+    )
+
+
+def test_update_api_key():
+    check_access(
+        "update_api_key",
+        rest_request={"role": "read_write", "collections": [COLL_NAME]},
+        path_params={"key_name": random_str},
+    )
+
+
+def test_delete_api_key():
+    check_access("delete_api_key", path_params={"key_name": random_str})
+
+
 def test_get_issues():
     check_access("get_issues")
 
 
 def test_clear_issues():
     check_access("clear_issues")
+
+
+def test_list_templates():
+    check_access("list_templates")
+
+
+def test_get_template():
+    check_access("get_template", path_params={"template_name": "jwt_test_template"})
+
+
+def test_put_template():
+    check_access(
+        "put_template",
+        rest_request={"on_disk_payload": True},
+        path_params={"template_name": "jwt_test_template"},
+    )
+
+
+def test_delete_template():
+    check_access("delete_template", path_params={"template_name": random_str()})
@@ -19,6 +19,22 @@ def berlin_token():
     )
 
 
+@pytest.fixture(scope="module")
+def berlin_filter_token():
+    return encode_jwt(
+        {
+            "access": [
+                {
+                    "collection": COLL_NAME,
+                    "access": "rw",
+                    "filter": {"must": [{"key": "city", "match": {"value": "Berlin"}}]},
+                }
+            ]
+        },
+        SECRET,
+    )
+
+
 @pytest.fixture(scope="module", autouse=True)
 def setup(jwt_cluster):
     peer_api_uris, peer_dirs, bootstrap_uri = jwt_cluster
@@ -288,3 +304,50 @@ def test_payload_claim_fails_with_ids_in_query(berlin_token, query, in_prefetch)
         res.json()["status"]["error"]
         == 'Forbidden: This operation is not allowed when "payload" restriction is present for collection jwt_test_collection'
     )
+
+
+def test_filter_claim(berlin_filter_token):
+    headers = {"Authorization": f"Bearer {berlin_filter_token}"}
+
+    # A filter in the request can't widen the filter of the token
+    res = requests.post(
+        f"{REST_URI}/collections/{COLL_NAME}/points/scroll",
+        json={
+            "limit": 100,
+            "with_payload": True,
+            "filter": {"should": [{"key": "city", "match": {"value": "London"}}]},
+        },
+        headers=headers,
+    )
+    res.raise_for_status()
+    assert all(
+        point["payload"].get("city") == "Berlin" for point in res.json()["result"]["points"]
+    )
+
+    res = requests.post(
+        f"{REST_URI}/collections/{COLL_NAME}/points/count",
+        json={"exact": True},
+        headers=headers,
+    )
+    res.raise_for_status()
+    count_with_token = res.json()["result"]["count"]
+
+    res = requests.post(
+        f"{REST_URI}/collections/{COLL_NAME}/points/count",
+        json={"exact": True, "filter": {"must": [{"key": "city", "match": {"value": "Berlin"}}]}},
+        headers=API_KEY_HEADERS,
+    )
+    res.raise_for_status()
+    assert count_with_token == res.json()["result"]["count"]
+
+    # Points are not retrieved by ids, as they could be outside of the filter
+    res = requests.post(
+        f"{REST_URI}/collections/{COLL_NAME}/points",
+        json={"ids": [1, 2, 3]},
+        headers=headers,
+    )
+    assert res.status_code == 403, res.json()
+    assert (
+        res.json()["status"]["error"]
+        == 'Forbidden: This operation is not allowed when "filter" restriction is present for collection jwt_test_collection'
+    )
@@ -0,0 +1,73 @@
+import pathlib
+import random
+
+from .fixtures import DENSE_VECTOR_SIZE, create_collection, upsert_random_points
+from .utils import *
+from .assertions import assert_http_ok
+
+N_PEERS = 3
+N_SHARDS = 3
+COLLECTION_NAME = "test_collection"
+
+
+def search_points(peer_url, consistency, **params):
+    return requests.post(
+        f"{peer_url}/collections/{COLLECTION_NAME}/points/search?consistency={consistency}",
+        json={
+            "vector": [random.random() for _ in range(DENSE_VECTOR_SIZE)],
+            "limit": 100,
+            **params,
+        },
+        timeout=10,
+    )
+
+
+def test_partial_read(tmp_path: pathlib.Path):
+    assert_project_root()
+
+    peer_urls, peer_dirs, bootstrap_url = start_cluster(tmp_path, N_PEERS)
+
+    create_collection(peer_urls[0], shard_number=N_SHARDS, replication_factor=1)
+    wait_collection_exists_and_active_on_all_peers(collection_name=COLLECTION_NAME, peer_api_uris=peer_urls)
+    upsert_random_points(peer_urls[0], 100)
+
+    # All shards are available, so every shard is reported as ok
+    res = search_points(peer_urls[0], "partial")
+    assert_http_ok(res)
+    shards = res.json()["shards"]
+    assert len(shards) == N_SHARDS
+    assert all(shard["ok"] for shard in shards)
+    assert len(res.json()["result"]) == 100
+
+    # Other reads can't report the status of each shard, so they reject partial consistency
+    res = requests.post(
+        f"{peer_urls[0]}/collections/{COLLECTION_NAME}/points/scroll?consistency=partial",
+        json={"limit": 10},
+    )
+    assert res.status_code == 400
+    res = requests.post(
+        f"{peer_urls[0]}/collections/{COLLECTION_NAME}/points/query?consistency=partial",
+        json={"limit": 10},
+    )
+    assert res.status_code == 400
+
+    # Kill last peer, the only replica of some shard is lost
+    processes.pop().kill()
+
+    res = search_points(peer_urls[0], "all")
+    assert not res.ok
+
+    res = search_points(peer_urls[0], "partial")
+    assert_http_ok(res)
+    shards = res.json()["shards"]
+    failed = [shard for shard in shards if not shard["ok"]]
+    assert len(failed) > 0
+    assert all("error" in shard for shard in failed)
+    assert 0 < len(res.json()["result"]) < 100
+
+    # Large offset makes search fetch payload separately, only from the shards, which responded
+    res = search_points(peer_urls[0], "partial", limit=5, offset=20, with_payload=True)
+    assert_http_ok(res)
+    points = res.json()["result"]
+    assert len(points) > 0
+    assert all("payload" in point for point in points)
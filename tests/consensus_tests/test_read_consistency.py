@@ -74,3 +74,13 @@ def test_shard_consistency(tmp_path: pathlib.Path):
     for res in results:
         for idx, row in enumerate(res['points']):
             assert row == results[0]['points'][idx]
+
+    # Replicas have the same data, so they agree on the count
+    for url in peer_api_uris:
+        res = requests.post(
+            f"{url}/collections/{COLLECTION_NAME}/points/count?consistency=all",
+            json={"exact": True},
+            timeout=10
+        )
+        assert_http_ok(res)
+        assert res.json()["result"]["consistent"] is True
@@ -0,0 +1,85 @@
+import pathlib
+
+from .fixtures import create_collection, upsert_random_points
+from .utils import *
+
+N_PEERS = 2
+COLLECTION_NAME = "test_collection"
+QUARANTINE_ENV = {"QDRANT__STORAGE__QUARANTINE_CORRUPTED_SEGMENTS": "true"}
+
+
+def test_corrupted_segment_is_recovered_from_replica(tmp_path: pathlib.Path):
+    assert_project_root()
+
+    peer_urls, peer_dirs, bootstrap_url = start_cluster(tmp_path, N_PEERS)
+
+    create_collection(peer_urls[0], shard_number=1, replication_factor=N_PEERS)
+    wait_collection_exists_and_active_on_all_peers(
+        collection_name=COLLECTION_NAME,
+        peer_api_uris=peer_urls,
+    )
+
+    upsert_random_points(peer_urls[0], 100)
+
+    # Kill the last peer
+    processes.pop().kill()
+
+    collection_path = Path(peer_dirs[-1]) / "storage" / "collections" / COLLECTION_NAME
+    shard_path = next(filter(
+        lambda shard: (shard / "segments").exists(),
+        collection_path.iterdir(),
+    ))
+    segment_path = next(filter(lambda path: path.is_dir(), (shard_path / "segments").iterdir()))
+
+    # Corrupt `segment.json` file inside a segment (to fail the segment load)
+    with open(segment_path / "segment.json", "a") as segment_json_file:
+        segment_json_file.write("borked")
+
+    # Peer starts, instead of failing to load the shard
+    peer_url = start_peer(peer_dirs[-1], "peer_0_restarted.log", bootstrap_url, extra_env=QUARANTINE_ENV)
+    wait_for_peer_online(peer_url)
+
+    # Segment is kept for inspection, but not loaded
+    assert (shard_path / "quarantine" / segment_path.name).exists()
+    assert not segment_path.exists()
+
+    # Replica is recovered from the healthy one
+    wait_for_all_replicas_active(peer_urls[0], COLLECTION_NAME)
+    wait_collection_points_count(peer_url, COLLECTION_NAME, 100)
+
+
+def test_corrupted_segment_without_replica_is_reported(tmp_path: pathlib.Path):
+    assert_project_root()
+
+    peer_urls, peer_dirs, bootstrap_url = start_cluster(tmp_path, N_PEERS)
+
+    # Each peer has the only replica of its shard
+    create_collection(peer_urls[0], shard_number=N_PEERS, replication_factor=1)
+    wait_collection_exists_and_active_on_all_peers(
+        collection_name=COLLECTION_NAME,
+        peer_api_uris=peer_urls,
+    )
+
+    upsert_random_points(peer_urls[0], 100)
+
+    # Kill the last peer
+    processes.pop().kill()
+
+    collection_path = Path(peer_dirs[-1]) / "storage" / "collections" / COLLECTION_NAME
+    shard_path = next(filter(
+        lambda shard: (shard / "segments").exists(),
+        collection_path.iterdir(),
+    ))
+    segment_path = next(filter(lambda path: path.is_dir(), (shard_path / "segments").iterdir()))
+
+    # Corrupt `segment.json` file inside a segment (to fail the segment load)
+    with open(segment_path / "segment.json", "a") as segment_json_file:
+        segment_json_file.write("borked")
+
+    peer_url = start_peer(peer_dirs[-1], "peer_0_restarted.log", bootstrap_url, extra_env=QUARANTINE_ENV)
+    wait_for_peer_online(peer_url)
+
+    # There is no replica to recover from, the replica reports its missing segments
+    cluster_info = get_collection_cluster_info(peer_url, COLLECTION_NAME)
+    assert len(cluster_info["local_shards"]) == 1
+    assert cluster_info["local_shards"][0]["quarantined_segments"] == [segment_path.name]
@@ -279,3 +279,85 @@ def test_shard_stream_transfer_fast_burst(tmp_path: pathlib.Path):
 
 
 
+
+
+# Move a shard of a collection with attribute columns from one node to another
+#
+# Attributes are stored outside of the points, test that transferred points keep
+# their attributes on the receiving side
+def test_shard_stream_transfer_attributes(tmp_path: pathlib.Path):
+    assert_project_root()
+
+    peer_api_uris, peer_dirs, bootstrap_uri = start_cluster(tmp_path, N_PEERS, 20000)
+
+    r = requests.put(
+        f"{peer_api_uris[0]}/collections/{COLLECTION_NAME}?timeout=10", json={
+            "vectors": {"size": 4, "distance": "Dot"},
+            "shard_number": N_SHARDS,
+            "replication_factor": N_REPLICA,
+            "attribute_columns": {"price": "integer"},
+        })
+    assert_http_ok(r)
+    wait_collection_exists_and_active_on_all_peers(
+        collection_name=COLLECTION_NAME,
+        peer_api_uris=peer_api_uris
+    )
+
+    upsert_random_points(peer_api_uris[0], 100, with_sparse_vector=False)
+
+    # Every fifth point is left without the attribute
+    for price in range(1, 5):
+        r = requests.put(
+            f"{peer_api_uris[0]}/collections/{COLLECTION_NAME}/points/attributes?wait=true", json={
+                "attributes": {"price": price},
+                "points": [point_id for point_id in range(100) if point_id % 5 == price],
+            })
+        assert_http_ok(r)
+
+    transfer_collection_cluster_info = get_collection_cluster_info(peer_api_uris[0], COLLECTION_NAME)
+    receiver_collection_cluster_info = get_collection_cluster_info(peer_api_uris[2], COLLECTION_NAME)
+
+    from_peer_id = transfer_collection_cluster_info['peer_id']
+    to_peer_id = receiver_collection_cluster_info['peer_id']
+
+    shard_id = transfer_collection_cluster_info['local_shards'][0]['shard_id']
+
+    r = requests.post(
+        f"{peer_api_uris[0]}/collections/{COLLECTION_NAME}/cluster", json={
+            "move_shard": {
+                "shard_id": shard_id,
+                "from_peer_id": from_peer_id,
+                "to_peer_id": to_peer_id,
+                "method": "stream_records",
+            }
+        })
+    assert_http_ok(r)
+
+    wait_for_collection_shard_transfers_count(peer_api_uris[0], COLLECTION_NAME, 0)
+
+    receiver_collection_cluster_info = get_collection_cluster_info(peer_api_uris[2], COLLECTION_NAME)
+    assert len(receiver_collection_cluster_info['local_shards']) == 2
+
+    # The moved shard is only on the receiver now, so counts include its attributes
+    for uri in peer_api_uris:
+        r = requests.post(
+            f"{uri}/collections/{COLLECTION_NAME}/points/count", json={
+                "exact": True,
+                "filter": {
+                    "must": [{"attribute": "price", "range": {"gte": 2}}]
+                },
+            }
+        )
+        assert_http_ok(r)
+        assert r.json()["result"]['count'] == 60
+
+        r = requests.post(
+            f"{uri}/collections/{COLLECTION_NAME}/points/count", json={
+                "exact": True,
+                "filter": {
+                    "must": [{"attribute": "price", "range": {"gte": 0}}]
+                },
+            }
+        )
+        assert_http_ok(r)
+        assert r.json()["result"]['count'] == 80
@@ -361,3 +361,44 @@ def test_batch_update_payload():
             },
         ]
     )
+
+
+def test_bulk_update():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/bulk',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "operations": [
+                {
+                    "upsert": {
+                        "points": [{"id": 100, "vector": [0.1, 0.2, 0.3, 0.4], "payload": {}}]
+                    }
+                },
+                {
+                    "update_vectors": {
+                        "points": [{"id": 101, "vector": [0.1, 0.2, 0.3, 0.4]}]
+                    }
+                },
+                {
+                    "set_payload": {
+                        "points": [100],
+                        "payload": {"key": "value"},
+                    }
+                },
+            ]
+        }
+    )
+    assert response.ok
+
+    result = response.json()['result']
+    assert len(result) == 3
+    assert result[0]['result']['status'] == 'completed'
+    assert 'error' not in result[0]
+    # Failed operation does not prevent the following ones
+    assert 'result' not in result[1]
+    assert '101' in result[1]['error']
+    assert result[2]['result']['status'] == 'completed'
+
+    assert_points([{'id': 100, 'payload': {"key": "value"}}], nonexisting_ids=[101])
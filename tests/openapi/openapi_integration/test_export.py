@@ -0,0 +1,74 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_export'
+
+# Arrow IPC streams are framed by a continuation marker,
+# and terminated by the marker followed by a zero length
+ARROW_CONTINUATION = b'\xff\xff\xff\xff'
+ARROW_END_OF_STREAM = ARROW_CONTINUATION + b'\x00\x00\x00\x00'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def test_export_points():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/export',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "batch_size": 3,
+            "payload_fields": ["city"],
+        }
+    )
+    assert response.ok
+    assert response.headers['content-type'] == 'application/vnd.apache.arrow.stream'
+
+    content = response.content
+    assert content.startswith(ARROW_CONTINUATION)
+    assert content.endswith(ARROW_END_OF_STREAM)
+    assert b'payload.city' in content
+
+
+def test_export_missing_collection():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/export',
+        method="POST",
+        path_params={'collection_name': 'missing_collection'},
+        body={}
+    )
+    assert response.status_code == 404
+
+
+def test_export_payload_projection():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/export',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "payload_projection": {"town": "city"},
+        }
+    )
+    assert response.ok
+    assert b'payload.town' in response.content
+    assert b'payload.city' not in response.content
+
+
+def test_export_payload_fields_and_projection():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/export',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "payload_fields": ["city"],
+            "payload_projection": {"town": "city"},
+        }
+    )
+    assert response.status_code == 422
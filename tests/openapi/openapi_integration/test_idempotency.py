@@ -0,0 +1,77 @@
+import pytest
+import requests
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+from .helpers.settings import QDRANT_HOST
+
+collection_name = 'test_collection_idempotency'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def delete_point(point_id, idempotency_key):
+    return requests.post(
+        f"{QDRANT_HOST}/collections/{collection_name}/points/delete",
+        params={"wait": "true"},
+        json={"points": [point_id]},
+        headers={"idempotency-key": idempotency_key},
+    )
+
+
+def point_exists(point_id):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/{id}',
+        method="GET",
+        path_params={'collection_name': collection_name, 'id': point_id},
+    )
+    return response.ok
+
+
+def test_retried_delete_is_not_applied_again():
+    response = delete_point(1, "delete-1")
+    assert response.ok
+    operation_id = response.json()['result']['operation_id']
+    assert not point_exists(1)
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={"points": [{"id": 1, "vector": [0.05, 0.61, 0.76, 0.74]}]}
+    )
+    assert response.ok
+    assert point_exists(1)
+
+    # Retry returns the result of the first request, and doesn't delete the new point
+    response = delete_point(1, "delete-1")
+    assert response.ok
+    assert response.json()['result']['operation_id'] == operation_id
+    assert point_exists(1)
+
+    # Different key is a different request
+    response = delete_point(1, "delete-2")
+    assert response.ok
+    assert not point_exists(1)
+
+
+def test_empty_idempotency_key():
+    response = delete_point(1, "")
+    assert response.status_code == 400
+
+
+def test_idempotency_key_of_different_update():
+    response = delete_point(1, "delete-1")
+    assert response.ok
+    assert not point_exists(1)
+
+    # Key can't be reused for a different update within the window
+    response = delete_point(2, "delete-1")
+    assert response.status_code == 400
+    assert point_exists(2)
@@ -0,0 +1,47 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_inference'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def test_upsert_document_without_inference():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {
+                    "id": 100,
+                    "vector": {"text": "Berlin is the capital of Germany", "model": "test-model"},
+                }
+            ]
+        }
+    )
+    assert response.status_code == 400
+    assert "Inference is not configured" in response.json()["status"]["error"]
+
+
+def test_query_image_without_inference():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/query',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "query": {"image": "https://example.com/image.png", "model": "test-model"},
+            "limit": 3,
+        }
+    )
+    assert response.status_code == 400
+    assert "Inference is not configured" in response.json()["status"]["error"]
+
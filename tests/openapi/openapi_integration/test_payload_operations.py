@@ -589,3 +589,49 @@ def test_payload_index_overwrite():
     )
     assert response.ok
     assert len(response.json()["result"]["points"]) == 1
+
+
+def test_patch_payload():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/payload',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "payload": {
+                "title": "Goodbye!",
+                "author": {"givenName": "John", "familyName": "Doe"},
+                "tags": ["example", "sample"],
+            },
+            "points": [6]
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/payload',
+        method="PATCH",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "payload": {
+                "title": "Hello!",
+                "author": {"familyName": None},
+                "tags": ["example"],
+            },
+            "points": [6]
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/{id}',
+        method="GET",
+        path_params={'collection_name': collection_name, 'id': 6},
+    )
+    assert response.ok
+    assert response.json()['result']['payload'] == {
+        "title": "Hello!",
+        "author": {"givenName": "John"},
+        "tags": ["example"],
+    }
@@ -315,3 +315,38 @@ def test_payload_selectors():
             ],
         }
     }
+
+
+def test_payload_selector_project():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/scroll',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "filter": {
+                "must": [
+                    {
+                        "key": "country.name",
+                        "match": {
+                            "value": "Germany",
+                        }
+                    }
+                ]
+            },
+            "limit": 3,
+            "with_payload": {
+                "project": {
+                    "country": "country.name",
+                    "capital": "country.capital",
+                    "cities": "country.cities[].name",
+                    "missing": "country.language",
+                },
+            },
+        }
+    )
+    assert response.ok
+    assert response.json()['result']['points'][0]['payload'] == {
+        "country": "Germany",
+        "capital": "Berlin",
+        "cities": ["Berlin", "Munich", "Hamburg"],
+    }
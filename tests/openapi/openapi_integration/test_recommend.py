@@ -113,6 +113,36 @@ def test_single_vs_batch():
         assert single_response.json()["result"] == batch_response.json()["result"][i]
 
 
+def test_shared_examples_batch():
+    searches = [
+        {"positive": [1], "limit": 3},
+        {"negative": [5], "strategy": "best_score", "limit": 3},
+    ]
+
+    shared_response = request_with_validation(
+        api="/collections/{collection_name}/points/recommend/shared",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={"positive": [2], "negative": [4], "searches": searches},
+    )
+    assert shared_response.ok
+
+    batch_response = request_with_validation(
+        api="/collections/{collection_name}/points/recommend/batch",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "searches": [
+                {"positive": [1, 2], "negative": [4], "limit": 3},
+                {"positive": [2], "negative": [5, 4], "strategy": "best_score", "limit": 3},
+            ]
+        },
+    )
+    assert batch_response.ok
+
+    assert shared_response.json()["result"] == batch_response.json()["result"]
+
+
 def test_without_positives():
     def req_with_positives(positive, strategy=None):
         if strategy is None:
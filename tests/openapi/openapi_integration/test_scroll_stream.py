@@ -0,0 +1,66 @@
+import json
+
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_scroll_stream'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def test_scroll_stream():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/scroll',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"limit": 100, "with_payload": True}
+    )
+    assert response.ok
+    expected = response.json()['result']['points']
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/scroll/stream',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"limit": 3, "with_payload": True}
+    )
+    assert response.ok
+    assert response.headers['content-type'] == 'application/x-ndjson'
+
+    points = [json.loads(line) for line in response.text.splitlines()]
+    assert points == expected
+
+
+def test_scroll_stream_filter():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/scroll/stream',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "limit": 1,
+            "filter": {"must": [{"key": "city", "match": {"value": "London"}}]},
+            "with_payload": ["city"],
+        }
+    )
+    assert response.ok
+
+    points = [json.loads(line) for line in response.text.splitlines()]
+    assert len(points) > 0
+    assert all("London" in point['payload']['city'] for point in points)
+
+
+def test_scroll_stream_missing_collection():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/scroll/stream',
+        method="POST",
+        path_params={'collection_name': 'missing_collection'},
+        body={}
+    )
+    assert response.status_code == 404
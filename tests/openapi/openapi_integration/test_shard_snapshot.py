@@ -241,3 +241,98 @@ def test_shard_snapshot_security():
     )
     assert not response.ok
     assert response.status_code == 404
+
+
+def test_segment_snapshot_operations():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/count',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"exact": True},
+    )
+    assert response.ok
+    points_count = response.json()['result']['count']
+
+    response = requests.get(f"http://{QDRANT_HOST}/collections/{collection_name}/segments")
+    assert response.ok
+    segment = next(
+        segment
+        for segment in response.json()['result'][0]['segments']
+        if segment['num_points'] > 0
+    )
+    segment_id = segment['uuid']
+
+    # create snapshot of the segment
+    response = request_with_validation(
+        api='/collections/{collection_name}/shards/{shard_id}/segments/{segment_id}/snapshots',
+        method="POST",
+        path_params={'shard_id': 0, 'collection_name': collection_name, 'segment_id': segment_id},
+        query_params={'wait': 'true'},
+    )
+    assert response.ok
+    snapshot_name = response.json()['result']['name']
+    snapshot_checksum = response.json()['result']['checksum']
+    assert segment_id in snapshot_name
+
+    # it is downloaded as a shard snapshot
+    response = request_with_validation(
+        api='/collections/{collection_name}/shards/{shard_id}/snapshots/{snapshot_name}',
+        method="GET",
+        path_params={'shard_id': 0, 'collection_name': collection_name,
+                     'snapshot_name': snapshot_name},
+    )
+    assert response.ok
+    snapshot = response.content
+    assert snapshot_checksum == hashlib.sha256(snapshot).hexdigest()
+
+    upload_url = (
+        f"http://{QDRANT_HOST}/collections/{collection_name}/shards/0"
+        f"/segments/{segment_id}/snapshots/upload"
+    )
+
+    # wrong checksum is rejected
+    response = requests.post(
+        upload_url,
+        params={'checksum': "3" * len(snapshot_checksum)},
+        files={'snapshot': snapshot},
+    )
+    assert response.status_code == 400
+
+    # unknown segment is not found
+    response = requests.post(
+        f"http://{QDRANT_HOST}/collections/{collection_name}/shards/0"
+        f"/segments/unknown/snapshots/upload",
+        files={'snapshot': snapshot},
+    )
+    assert response.status_code == 404
+
+    response = requests.post(
+        upload_url,
+        params={'checksum': snapshot_checksum},
+        files={'snapshot': snapshot},
+    )
+    assert response.ok
+
+    # replaced segment has a new id, but the same points
+    response = requests.get(f"http://{QDRANT_HOST}/collections/{collection_name}/segments")
+    assert response.ok
+    segment_ids = [segment['uuid'] for segment in response.json()['result'][0]['segments']]
+    assert segment_id not in segment_ids
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/count',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"exact": True},
+    )
+    assert response.ok
+    assert response.json()['result']['count'] == points_count
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/shards/{shard_id}/snapshots/{snapshot_name}',
+        method="DELETE",
+        path_params={'shard_id': 0, 'collection_name': collection_name,
+                     'snapshot_name': snapshot_name},
+        query_params={'wait': 'true'},
+    )
+    assert response.ok
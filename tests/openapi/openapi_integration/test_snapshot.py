@@ -424,3 +424,90 @@ def test_collection_snapshot_security():
     )
     assert not response.ok
     assert response.status_code == 404
+
+
+def test_snapshot_schedules():
+    response = request_with_validation(
+        api='/collections/{collection_name}/snapshot_schedules/{schedule_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name, 'schedule_name': 'nightly'},
+        body={"cron": "30 2 * * *", "retention": 3},
+    )
+    assert response.ok
+
+    # Invalid cron expression is rejected
+    response = request_with_validation(
+        api='/collections/{collection_name}/snapshot_schedules/{schedule_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name, 'schedule_name': 'broken'},
+        body={"cron": "61 * * * *", "retention": 3},
+    )
+    assert response.status_code == 422
+
+    # Schedules can't be set for missing collections
+    response = request_with_validation(
+        api='/collections/{collection_name}/snapshot_schedules/{schedule_name}',
+        method="PUT",
+        path_params={'collection_name': 'missing_collection', 'schedule_name': 'nightly'},
+        body={"cron": "@daily", "retention": 1},
+    )
+    assert response.status_code == 404
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/snapshot_schedules',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    schedules = response.json()['result']
+    assert len(schedules) == 1
+    assert schedules[0]['name'] == 'nightly'
+    assert schedules[0]['cron'] == '30 2 * * *'
+    assert schedules[0]['storage'] == 'local'
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/snapshot_schedules/{schedule_name}',
+        method="DELETE",
+        path_params={'collection_name': collection_name, 'schedule_name': 'nightly'},
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/snapshot_schedules',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    assert response.json()['result'] == []
+
+
+def test_verify_snapshot():
+    response = request_with_validation(
+        api='/collections/{collection_name}/snapshots',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+    )
+    assert response.ok
+    snapshot_name = response.json()['result']['name']
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/snapshots/{snapshot_name}/verify',
+        method="POST",
+        path_params={'collection_name': collection_name, 'snapshot_name': snapshot_name},
+    )
+    assert response.ok
+    result = response.json()['result']
+    assert result['valid'], result['errors']
+    assert result['checksum_matches']
+    assert result['has_manifest']
+    assert result['verified_files'] > 0
+    assert len(result['shards']) == 1
+    assert result['shards'][0]['has_local_data']
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/snapshots/{snapshot_name}/verify',
+        method="POST",
+        path_params={'collection_name': collection_name, 'snapshot_name': 'missing.snapshot'},
+    )
+    assert response.status_code == 404
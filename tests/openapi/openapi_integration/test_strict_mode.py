@@ -0,0 +1,170 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_strict_mode'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def set_strict_mode(strict_mode_config):
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PATCH",
+        path_params={'collection_name': collection_name},
+        body={
+            "strict_mode_config": strict_mode_config,
+        }
+    )
+    assert response.ok
+
+
+def search(body):
+    return request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "vector": [0.2, 0.1, 0.9, 0.7],
+            **body,
+        }
+    )
+
+
+def test_strict_mode_limits():
+    set_strict_mode({
+        "enabled": True,
+        "max_query_limit": 5,
+        "search_allow_exact": False,
+    })
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    assert response.json()['result']['config']['strict_mode_config']['max_query_limit'] == 5
+
+    assert search({"limit": 5}).ok
+
+    response = search({"limit": 6})
+    assert response.status_code == 403
+    assert "`max_query_limit`" in response.json()['status']['error']
+
+    response = search({"limit": 3, "params": {"exact": True}})
+    assert response.status_code == 403
+    assert "`search_allow_exact`" in response.json()['status']['error']
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/scroll',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"limit": 100}
+    )
+    assert response.status_code == 403
+
+    # Fields, not listed in the update, are left unchanged
+    set_strict_mode({"enabled": False})
+    assert search({"limit": 6}).ok
+    set_strict_mode({"enabled": True})
+    assert search({"limit": 6}).status_code == 403
+
+
+def test_strict_mode_unindexed_filter():
+    set_strict_mode({
+        "enabled": True,
+        "unindexed_filtering_retrieve": False,
+    })
+
+    city_filter = {
+        "must": [
+            {"key": "city", "match": {"value": "London"}}
+        ]
+    }
+
+    response = search({"limit": 3, "filter": city_filter})
+    assert response.status_code == 403
+    assert "`city`" in response.json()['status']['error']
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/index',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "field_name": "city",
+            "field_schema": "keyword"
+        }
+    )
+    assert response.ok
+
+    assert search({"limit": 3, "filter": city_filter}).ok
+
+
+def test_strict_mode_payload_size():
+    set_strict_mode({
+        "enabled": True,
+        "max_payload_size_bytes": 32,
+    })
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/payload',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "payload": {"city": "Berlin"},
+            "points": [1],
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/payload',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "payload": {"description": "x" * 100},
+            "points": [1],
+        }
+    )
+    assert response.status_code == 403
+    assert "`max_payload_size_bytes`" in response.json()['status']['error']
+
+
+def test_strict_mode_points_quota():
+    # Collection is set up with 10 points
+    set_strict_mode({
+        "enabled": True,
+        "max_points_count": 10,
+    })
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [{"id": 100, "vector": [0.1, 0.2, 0.3, 0.4]}],
+        }
+    )
+    assert response.status_code == 403
+    assert "`max_points_count`" in response.json()['status']['error']
+
+    # Deletes are still allowed
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/delete',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={"points": [1]}
+    )
+    assert response.ok